@@ -0,0 +1,50 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use imgal::filter::{self, ConvolveMethod};
+use imgal::kernel::parametric;
+use imgal::simulation::pattern;
+
+// the (row, col) sizes of the images
+const SIZES: [usize; 2] = [256, 1024];
+
+fn bench_fft_convolve(c: &mut Criterion) {
+    // a disk kernel is not separable, so it is convolved as a whole
+    let kernel = parametric::disk(7.0, 2, Some(true)).unwrap();
+    let mut group = c.benchmark_group("filter/convolve_fft");
+    for size in SIZES {
+        let data = pattern::blobs_2d((size, size), 0.01, 3.0, 42)
+            .unwrap()
+            .into_dyn();
+        group.throughput(Throughput::Elements(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("disk_15x15", size), &data, |b, data| {
+            b.iter(|| {
+                filter::convolve(
+                    black_box(data.view()),
+                    kernel.view(),
+                    None,
+                    Some(ConvolveMethod::Fft),
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_fft_convolve_1d(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filter/fft_convolve_1d");
+    for len in [4096, 65536] {
+        let signal: Vec<f64> = (0..len).map(|i| (i as f64 * 0.01).sin()).collect();
+        let response: Vec<f64> = (0..len / 4).map(|i| (-(i as f64) / 64.0).exp()).collect();
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &signal, |b, signal| {
+            b.iter(|| filter::fft_convolve_1d(black_box(signal), &response))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_fft_convolve, bench_fft_convolve_1d);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use ndarray::Array3;
+
+use imgal::simulation::noise;
+
+// the (row, col) sizes of the images, 256 time bins per pixel
+const SIZES: [usize; 2] = [64, 256];
+
+fn bench_poisson_3d(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simulation/poisson_3d");
+    for size in SIZES {
+        let data = Array3::<f64>::from_elem((size, size, 256), 20.0);
+        group.throughput(Throughput::Elements(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("f64", size), &data, |b, data| {
+            b.iter(|| noise::poisson_3d(black_box(data.view()), 1.0, 42, None).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_poisson_3d_mut(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simulation/poisson_3d_mut");
+    for size in SIZES {
+        let data = Array3::<f32>::from_elem((size, size, 256), 20.0);
+        group.throughput(Throughput::Elements(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("f32", size), &data, |b, data| {
+            b.iter_batched_ref(
+                || data.clone(),
+                |d| noise::poisson_3d_mut(d.view_mut(), 1.0, 42, None).unwrap(),
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_poisson_3d, bench_poisson_3d_mut);
+criterion_main!(benches);
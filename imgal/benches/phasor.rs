@@ -0,0 +1,65 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use imgal::phasor::{calibration, time_domain};
+use imgal::simulation::decay;
+
+// simulated biexponential decay parameters
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+
+// the (row, col) sizes of the decay images, 256 time bins per pixel
+const SIZES: [usize; 2] = [64, 256];
+
+fn bench_time_domain_image(c: &mut Criterion) {
+    let mut group = c.benchmark_group("phasor/time_domain_image");
+    for size in SIZES {
+        // photon counts are stored as u16 by most acquisition software
+        let data = decay::ideal_exponential_3d(
+            SAMPLES,
+            PERIOD,
+            &TAUS,
+            &FRACTIONS,
+            TOTAL_COUNTS,
+            (size, size),
+        )
+        .unwrap()
+        .mapv(|v| v.round() as u16);
+        group.throughput(Throughput::Elements(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("u16", size), &data, |b, data| {
+            b.iter(|| time_domain::image(black_box(data.view()), PERIOD, None, None, None).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_calibration_image(c: &mut Criterion) {
+    let mut group = c.benchmark_group("phasor/calibration_image");
+    for size in [256, 1024] {
+        let data = time_domain::image(
+            decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, (1, 1))
+                .unwrap()
+                .view(),
+            PERIOD,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+        .broadcast((size, size, 2))
+        .unwrap()
+        .to_owned();
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_with_input(BenchmarkId::new("f64", size), &data, |b, data| {
+            b.iter(|| calibration::image(black_box(data.view()), 0.95, 0.1, None, None).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_time_domain_image, bench_calibration_image);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use imgal::simulation::pattern;
+use imgal::threshold;
+
+// the (row, col) sizes of the images
+const SIZES: [usize; 2] = [512, 2048];
+
+fn bench_otsu_mask(c: &mut Criterion) {
+    let mut group = c.benchmark_group("threshold/otsu_mask");
+    for size in SIZES {
+        let data = pattern::blobs_2d((size, size), 0.01, 3.0, 42)
+            .unwrap()
+            .into_dyn();
+        group.throughput(Throughput::Elements(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("f64", size), &data, |b, data| {
+            b.iter(|| threshold::otsu_mask(black_box(data.view()), None, None).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_manual_mask(c: &mut Criterion) {
+    let mut group = c.benchmark_group("threshold/manual_mask");
+    for size in SIZES {
+        let data = pattern::blobs_2d((size, size), 0.01, 3.0, 42)
+            .unwrap()
+            .into_dyn();
+        group.throughput(Throughput::Elements(data.len() as u64));
+        group.bench_with_input(BenchmarkId::new("f64", size), &data, |b, data| {
+            b.iter(|| threshold::manual_mask(black_box(data.view()), 0.5))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_otsu_mask, bench_manual_mask);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+//! Fluorescence anisotropy functions.
+pub mod steady_state;
+pub use steady_state::{g_factor, steady_state};
+pub mod time_resolved;
+pub use time_resolved::{decay, decay_image, tail_g_factor};
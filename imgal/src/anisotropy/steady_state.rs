@@ -0,0 +1,134 @@
+use ndarray::{ArrayD, ArrayViewD, Zip};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::traits::numeric::ToFloat64;
+
+/// Estimate the instrument G-factor from a reference with a known anisotropy.
+///
+/// # Description
+///
+/// The G-factor is the ratio of the detection sensitivities of the
+/// perpendicular and parallel polarization channels. Measuring a reference
+/// with a known anisotropy (_e.g._ a free dye in a low viscosity solvent with
+/// r ≈ 0.0) gives the G-factor from the total channel intensities:
+///
+/// ```text
+/// G = (ΣI∥ / ΣI⊥) × (1 - r) / (1 + 2r)
+/// ```
+///
+/// # Arguments
+///
+/// * `parallel`: I∥, the parallel polarization channel of the reference.
+/// * `perpendicular`: I⊥, the perpendicular polarization channel of the
+///   reference. Must match the shape of `parallel`.
+/// * `reference`: The known anisotropy of the reference, default = 0.0. Must be
+///   within [-0.2, 0.4].
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The G-factor.
+/// * `Err(ImgalError)`: If the channel shapes do not match. If reference is
+///   outside of [-0.2, 0.4]. If either channel does not have a total intensity
+///   > 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1007/978-0-387-46312-4_10>
+pub fn g_factor<T>(
+    parallel: ArrayViewD<T>,
+    perpendicular: ArrayViewD<T>,
+    reference: Option<f64>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let r = reference.unwrap_or(0.0);
+
+    // check if the channel and reference parameters are valid
+    validate::same_shape(parallel.shape(), perpendicular.shape())?;
+    validate::in_range("reference", r, -0.2, 0.4)?;
+    let par: f64 = parallel.iter().map(|v| v.to_f64()).sum();
+    let perp: f64 = perpendicular.iter().map(|v| v.to_f64()).sum();
+    validate::positive("parallel", par)?;
+    validate::positive("perpendicular", perp)?;
+
+    Ok(par / perp * (1.0 - r) / (1.0 + 2.0 * r))
+}
+
+/// Compute the steady-state anisotropy image of two polarization channels.
+///
+/// # Description
+///
+/// This function computes the anisotropy of each pixel from its parallel (I∥)
+/// and perpendicular (I⊥) polarization intensities:
+///
+/// ```text
+/// r = (I∥ - G × I⊥) / (I∥ + 2G × I⊥)
+/// ```
+///
+/// where G is the instrument G-factor (see `g_factor`). The denominator is the
+/// total intensity, pixels without intensity are NaN.
+///
+/// # Arguments
+///
+/// * `parallel`: I∥, the n-dimensional parallel polarization channel.
+/// * `perpendicular`: I⊥, the n-dimensional perpendicular polarization
+///   channel. Must match the shape of `parallel`.
+/// * `g_factor`: The instrument G-factor, default = 1.0. Must be > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The anisotropy image with the same shape as the
+///   channels.
+/// * `Err(ImgalError)`: If the channel shapes do not match. If g_factor is
+///   <= 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1007/978-0-387-46312-4_10>
+pub fn steady_state<T>(
+    parallel: ArrayViewD<T>,
+    perpendicular: ArrayViewD<T>,
+    g_factor: Option<f64>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let g = g_factor.unwrap_or(1.0);
+
+    // check if the channel and g_factor parameters are valid
+    validate::same_shape(parallel.shape(), perpendicular.shape())?;
+    validate::positive("g_factor", g)?;
+
+    // compute the anisotropy per pixel
+    let mut r_arr = ArrayD::<f64>::zeros(parallel.raw_dim());
+    Zip::from(&mut r_arr)
+        .and(&parallel)
+        .and(&perpendicular)
+        .par_for_each(|r, pa, pe| {
+            *r = anisotropy(pa.to_f64(), pe.to_f64(), g);
+        });
+
+    // report pixels with no intensity, their anisotropy is NaN
+    let nan_count = r_arr.iter().filter(|v| !v.is_finite()).count();
+    if nan_count > 0 {
+        warning::warn(Warning::NonFiniteOutput { count: nan_count });
+    }
+
+    Ok(r_arr)
+}
+
+/// Compute the anisotropy of a parallel and perpendicular intensity pair, NaN
+/// if the total intensity is 0.0.
+pub(crate) fn anisotropy(parallel: f64, perpendicular: f64, g: f64) -> f64 {
+    let total = parallel + 2.0 * g * perpendicular;
+    if total == 0.0 {
+        return f64::NAN;
+    }
+
+    (parallel - g * perpendicular) / total
+}
@@ -0,0 +1,202 @@
+use ndarray::{Array3, ArrayView2, ArrayView3, Axis, Zip};
+
+use crate::anisotropy::steady_state::anisotropy;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the 1-dimensional anisotropy decay curve of two polarization decay
+/// curves.
+///
+/// # Description
+///
+/// This function computes the time-resolved anisotropy r(t) from the parallel
+/// I∥(t) and perpendicular I⊥(t) polarization decay curves:
+///
+/// ```text
+/// r(t) = (I∥(t) - G × I⊥(t)) / (I∥(t) + 2G × I⊥(t))
+/// ```
+///
+/// where G is the instrument G-factor (see `tail_g_factor`). Time bins without
+/// intensity are NaN.
+///
+/// # Arguments
+///
+/// * `parallel`: I∥(t), the parallel polarization decay curve.
+/// * `perpendicular`: I⊥(t), the perpendicular polarization decay curve. Must
+///   be the same length as `parallel`.
+/// * `g_factor`: The instrument G-factor, default = 1.0. Must be > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The anisotropy decay curve, r(t).
+/// * `Err(ImgalError)`: If the decay curve lengths do not match. If g_factor is
+///   <= 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1007/978-0-387-46312-4_11>
+pub fn decay<T>(
+    parallel: &[T],
+    perpendicular: &[T],
+    g_factor: Option<f64>,
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let g = g_factor.unwrap_or(1.0);
+
+    // check if the decay curve and g_factor parameters are valid
+    validate::same_length(parallel.len(), perpendicular.len())?;
+    validate::positive("g_factor", g)?;
+
+    Ok(parallel
+        .iter()
+        .zip(perpendicular.iter())
+        .map(|(pa, pe)| anisotropy(pa.to_f64(), pe.to_f64(), g))
+        .collect())
+}
+
+/// Compute the anisotropy decay curve of each pixel of two 3-dimensional
+/// polarization decay images.
+///
+/// # Description
+///
+/// This function computes the time-resolved anisotropy r(t) of each pixel of
+/// dual-channel decay stacks, see `decay`. Time bins without intensity are
+/// NaN.
+///
+/// # Arguments
+///
+/// * `parallel`: I∥(t), the parallel polarization decay image.
+/// * `perpendicular`: I⊥(t), the perpendicular polarization decay image. Must
+///   match the shape of `parallel`.
+/// * `g_factor`: The instrument G-factor, default = 1.0. Must be > 0.0.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of the images without the
+///   decay axis.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The anisotropy decay image with the same shape as the
+///   input images.
+/// * `Err(ImgalError)`: If axis is >= 3. If the image shapes do not match. If
+///   g_factor is <= 0.0. If the mask shape does not match the image shape.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1007/978-0-387-46312-4_11>
+pub fn decay_image<T>(
+    parallel: ArrayView3<T>,
+    perpendicular: ArrayView3<T>,
+    g_factor: Option<f64>,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let g = g_factor.unwrap_or(1.0);
+    let a = axis.unwrap_or(2);
+
+    // check if the image, g_factor, axis and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate::same_shape(parallel.shape(), perpendicular.shape())?;
+    validate::positive("g_factor", g)?;
+    if let Some(msk) = mask {
+        let mut shape = parallel.shape().to_vec();
+        shape.remove(a);
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // compute the anisotropy decay per lane, optionally only in mask area
+    let mut r_arr = Array3::<f64>::zeros(parallel.raw_dim());
+    let lanes = Zip::from(r_arr.lanes_mut(Axis(a)))
+        .and(parallel.lanes(Axis(a)))
+        .and(perpendicular.lanes(Axis(a)));
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        lanes.and(msk).par_for_each(|mut r_ln, pa_ln, pe_ln, m| {
+            if *m {
+                Zip::from(&mut r_ln)
+                    .and(&pa_ln)
+                    .and(&pe_ln)
+                    .for_each(|r, pa, pe| *r = anisotropy(pa.to_f64(), pe.to_f64(), g));
+            }
+        });
+    } else {
+        lanes.par_for_each(|mut r_ln, pa_ln, pe_ln| {
+            Zip::from(&mut r_ln)
+                .and(&pa_ln)
+                .and(&pe_ln)
+                .for_each(|r, pa, pe| *r = anisotropy(pa.to_f64(), pe.to_f64(), g));
+        });
+    }
+
+    Ok(r_arr)
+}
+
+/// Estimate the instrument G-factor by tail matching a reference decay.
+///
+/// # Description
+///
+/// The anisotropy of a reference with a rotational correlation time much
+/// shorter than its lifetime (_e.g._ a free dye) decays to 0.0, so its
+/// polarization decay curves only differ by the G-factor at late times. This
+/// function estimates the G-factor from the decay tails starting at time bin
+/// `start`:
+///
+/// ```text
+/// G = Σₜ₌ₛₜₐᵣₜ I∥(t) / Σₜ₌ₛₜₐᵣₜ I⊥(t)
+/// ```
+///
+/// # Arguments
+///
+/// * `parallel`: I∥(t), the parallel polarization decay curve of the
+///   reference.
+/// * `perpendicular`: I⊥(t), the perpendicular polarization decay curve of the
+///   reference. Must be the same length as `parallel`.
+/// * `start`: The first time bin of the tail, must be less than the decay
+///   curve length.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The G-factor.
+/// * `Err(ImgalError)`: If the decay curve lengths do not match. If start is
+///   not less than the decay curve length. If either tail does not have a total
+///   intensity > 0.0.
+pub fn tail_g_factor<T>(
+    parallel: &[T],
+    perpendicular: &[T],
+    start: usize,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the decay curve and start parameters are valid
+    validate::same_length(parallel.len(), perpendicular.len())?;
+    if start >= parallel.len() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "start",
+            reason: format!(
+                "must be less than the decay curve length {} but got {}",
+                parallel.len(),
+                start
+            ),
+        });
+    }
+
+    // sum the decay tails
+    let par: f64 = parallel[start..].iter().map(|v| v.to_f64()).sum();
+    let perp: f64 = perpendicular[start..].iter().map(|v| v.to_f64()).sum();
+    validate::positive("parallel", par)?;
+    validate::positive("perpendicular", perp)?;
+
+    Ok(par / perp)
+}
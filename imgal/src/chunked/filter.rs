@@ -0,0 +1,243 @@
+use std::collections::VecDeque;
+
+use ndarray::{ArrayD, ArrayViewD, Axis, Slice, concatenate};
+
+use crate::error::{ImgalError, validate};
+use crate::filter::spatial::{self, BorderMode, GaussianMethod, gaussian_radius};
+use crate::traits::numeric::ToFloat64;
+
+/// An iterator that applies a function to consecutive chunks of an array with
+/// a halo of their neighboring data, see `halo_map`.
+pub struct HaloMap<I, T, F> {
+    chunks: I,
+    axis: usize,
+    halo: usize,
+    f: F,
+    shape: Option<Vec<usize>>,
+    leading: Option<ArrayD<T>>,
+    pending: VecDeque<ArrayD<T>>,
+    done: bool,
+}
+
+/// Apply a function to consecutive chunks of an n-dimensional array with a halo
+/// of their neighboring data.
+///
+/// # Description
+///
+/// This function processes an array that is split into consecutive chunks
+/// along `axis` (_e.g._ the planes or slabs of an out-of-core volume) one chunk
+/// at a time. Each chunk is extended with up to `halo` elements of the
+/// preceding and following chunks before `f` is applied, and the output of `f`
+/// is cropped back to the chunk. Neighborhood operations with a radius of at
+/// most `halo` along `axis` then give the same result as on the full array,
+/// the border mode of `f` only applies at the first and last chunk.
+///
+/// The returned iterator is lazy, it reads chunks as far ahead as the halo
+/// needs and keeps at most the current chunk, the halos and the chunks they
+/// span in memory. Each output chunk can be written before the next one is
+/// computed.
+///
+/// # Arguments
+///
+/// * `chunks`: The consecutive chunks of the array along `axis`. Chunks can have
+///   different lengths along `axis` but must match in all other axes.
+/// * `axis`: The axis the array is split along.
+/// * `halo`: The number of neighboring elements along `axis` to extend each
+///   chunk with.
+/// * `f`: The function to apply to each extended chunk. Must return an array of
+///   the same shape as its input.
+///
+/// # Returns
+///
+/// * `HaloMap`: An iterator over the `Result` of each output chunk. An item is
+///   `Err(ImgalError)` if axis is out of bounds of a chunk, if the chunk shapes
+///   do not match, if `f` fails or if the output of `f` does not match the
+///   shape of its input. The iterator ends after the first error.
+pub fn halo_map<I, T, U, F>(chunks: I, axis: usize, halo: usize, f: F) -> HaloMap<I::IntoIter, T, F>
+where
+    I: IntoIterator<Item = ArrayD<T>>,
+    T: Clone,
+    U: Clone,
+    F: FnMut(ArrayViewD<T>) -> Result<ArrayD<U>, ImgalError>,
+{
+    HaloMap {
+        chunks: chunks.into_iter(),
+        axis,
+        halo,
+        f,
+        shape: None,
+        leading: None,
+        pending: VecDeque::new(),
+        done: false,
+    }
+}
+
+impl<I, T, U, F> Iterator for HaloMap<I, T, F>
+where
+    I: Iterator<Item = ArrayD<T>>,
+    T: Clone,
+    U: Clone,
+    F: FnMut(ArrayViewD<T>) -> Result<ArrayD<U>, ImgalError>,
+{
+    type Item = Result<ArrayD<U>, ImgalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // read chunks until the current chunk has its trailing halo
+        loop {
+            let trailing: usize = self
+                .pending
+                .iter()
+                .skip(1)
+                .map(|c| c.len_of(Axis(self.axis)))
+                .sum();
+            if self.done || (!self.pending.is_empty() && trailing >= self.halo) {
+                break;
+            }
+            match self.chunks.next() {
+                Some(chunk) => {
+                    if let Err(e) = self.validate_chunk(&chunk) {
+                        return self.fail(e);
+                    }
+                    self.pending.push_back(chunk);
+                }
+                None => self.done = true,
+            }
+        }
+        let current = self.pending.pop_front()?;
+
+        // extend the current chunk with the leading and trailing halos
+        let ax = Axis(self.axis);
+        let leading = self.leading.take();
+        let mut views: Vec<ArrayViewD<T>> = leading.iter().map(|l| l.view()).collect();
+        views.push(current.view());
+        let mut need = self.halo;
+        for c in self.pending.iter() {
+            if need == 0 {
+                break;
+            }
+            let n = need.min(c.len_of(ax));
+            views.push(c.slice_axis(ax, Slice::from(..n)));
+            need -= n;
+        }
+        let extended = match concatenate(ax, &views) {
+            Ok(arr) => arr,
+            Err(_) => {
+                return self.fail(ImgalError::InvalidArrayGeneric {
+                    msg: "Chunks can not be concatenated along the chunk axis.",
+                });
+            }
+        };
+
+        // apply the function and crop the output to the current chunk
+        let start = leading.map_or(0, |l| l.len_of(ax));
+        let end = start + current.len_of(ax);
+        let output = match (self.f)(extended.view()) {
+            Ok(arr) => arr,
+            Err(e) => return self.fail(e),
+        };
+        if let Err(e) = validate::same_shape(output.shape(), extended.shape()) {
+            return self.fail(e);
+        }
+        self.leading = Some(
+            extended
+                .slice_axis(ax, Slice::from(end.saturating_sub(self.halo)..end))
+                .to_owned(),
+        );
+
+        Some(Ok(output
+            .slice_axis(ax, Slice::from(start..end))
+            .to_owned()))
+    }
+}
+
+impl<I, T, F> HaloMap<I, T, F> {
+    /// Check that a chunk has the axis and matches the shape of the first
+    /// chunk without the axis.
+    fn validate_chunk(&mut self, chunk: &ArrayD<T>) -> Result<(), ImgalError> {
+        validate::axis(self.axis, chunk.ndim())?;
+        let mut shape = chunk.shape().to_vec();
+        shape.remove(self.axis);
+        match self.shape.as_ref() {
+            Some(s) => validate::same_shape(&shape, s),
+            None => {
+                self.shape = Some(shape);
+                Ok(())
+            }
+        }
+    }
+
+    /// Stop the iteration after an error.
+    fn fail<U>(&mut self, e: ImgalError) -> Option<Result<ArrayD<U>, ImgalError>> {
+        self.done = true;
+        self.pending.clear();
+        self.leading = None;
+
+        Some(Err(e))
+    }
+}
+
+/// Apply a Gaussian filter to an n-dimensional image split into chunks.
+///
+/// # Description
+///
+/// This function smooths an image that is split into consecutive chunks along
+/// `axis` with a Gaussian kernel, see `filter::gaussian`. Each chunk is
+/// filtered with a halo of the neighboring chunks as wide as the 1-dimensional
+/// pass along `axis` (see `halo_map`), so with the direct method the filtered
+/// chunks are equal to the chunks of the filtered full image.
+///
+/// # Arguments
+///
+/// * `chunks`: The consecutive chunks of the image along `axis`. Chunks can
+///   have different lengths along `axis` but must match in all other axes.
+/// * `sigma`: The standard deviation of the Gaussian kernel along each axis,
+///   in pixels. Must have one value per axis. A sigma of 0.0 does not filter
+///   that axis.
+/// * `border`: The border mode, default = `BorderMode::Reflect`. The
+///   `BorderMode::Wrap` mode is not supported along `axis`.
+/// * `method`: The 1-dimensional pass method, default = `GaussianMethod::Direct`.
+/// * `axis`: The axis the image is split along.
+///
+/// # Returns
+///
+/// * `Ok(impl Iterator<Item = Result<ArrayD<f64>, ImgalError>>)`: An iterator
+///   over the filtered chunks, see `halo_map`.
+/// * `Err(ImgalError)`: If axis is out of bounds of sigma. If a sigma is < 0.0
+///   or not finite. If border is `BorderMode::Wrap` and sigma along axis is
+///   > 0.0.
+pub fn gaussian<I, T>(
+    chunks: I,
+    sigma: &[f64],
+    border: Option<BorderMode>,
+    method: Option<GaussianMethod>,
+    axis: usize,
+) -> Result<impl Iterator<Item = Result<ArrayD<f64>, ImgalError>>, ImgalError>
+where
+    I: IntoIterator<Item = ArrayD<T>>,
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let border = border.unwrap_or(BorderMode::Reflect);
+    let method = method.unwrap_or(GaussianMethod::Direct);
+
+    // check if the sigma, axis and border parameters are valid
+    spatial::validate_sigma("sigma", sigma, sigma.len())?;
+    validate::axis(axis, sigma.len())?;
+    let halo = if sigma[axis] > 0.0 {
+        gaussian_radius(sigma[axis], method)
+    } else {
+        0
+    };
+    if halo > 0 && border == BorderMode::Wrap {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "border",
+            reason: "must not wrap along the chunk axis".to_string(),
+        });
+    }
+
+    // filter each chunk with its halo
+    let sigma = sigma.to_vec();
+    Ok(halo_map(chunks, axis, halo, move |block| {
+        spatial::gaussian(block, &sigma, Some(border), Some(method))
+    }))
+}
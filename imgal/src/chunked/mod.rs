@@ -0,0 +1,9 @@
+//! Chunked (out-of-core) processing functions.
+pub mod filter;
+pub use filter::{HaloMap, gaussian, halo_map};
+pub mod operator;
+pub use operator::{lane_op, pixel_op};
+pub mod phasor;
+pub use phasor::time_domain_image;
+pub mod threshold;
+pub use threshold::{manual_mask, otsu};
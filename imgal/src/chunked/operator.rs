@@ -0,0 +1,77 @@
+use ndarray::ArrayD;
+
+use crate::error::ImgalError;
+use crate::operator::{self, LaneOp, PixelOp};
+use crate::traits::numeric::ToFloat64;
+
+/// Apply a pixel operator to an n-dimensional image split into chunks.
+///
+/// # Description
+///
+/// This function applies the pixel operator to each chunk of an image, see
+/// `operator::pixel`. The output of a pixel only depends on its own value, so
+/// chunks need no halo and the output chunks are equal to the chunks of the
+/// full output image.
+///
+/// The returned iterator is lazy, each chunk is read and computed when the
+/// iterator is advanced and can be written before the next one is read.
+///
+/// # Arguments
+///
+/// * `chunks`: The chunks of the image.
+/// * `op`: The pixel operator.
+///
+/// # Returns
+///
+/// * `impl Iterator<Item = ArrayD<f64>>`: An iterator over the output of each
+///   chunk.
+pub fn pixel_op<I, T, O>(chunks: I, op: &O) -> impl Iterator<Item = ArrayD<f64>>
+where
+    I: IntoIterator<Item = ArrayD<T>>,
+    T: ToFloat64,
+    O: PixelOp + ?Sized,
+{
+    chunks.into_iter().map(move |chunk| {
+        operator::pixel(chunk.view(), op, None)
+            .expect("A pixel operator without a mask can not fail.")
+    })
+}
+
+/// Apply a lane operator to an n-dimensional image split into chunks.
+///
+/// # Description
+///
+/// This function applies the lane operator to each chunk of an image that is
+/// split along an axis other than the lane axis (_e.g._ blocks of rows of an
+/// out-of-core acquisition), see `operator::lane`. The output of a lane only
+/// depends on its own values, so chunks need no halo and the output chunks are
+/// equal to the chunks of the full output image.
+///
+/// The returned iterator is lazy, each chunk is read and computed when the
+/// iterator is advanced and can be written before the next one is read.
+///
+/// # Arguments
+///
+/// * `chunks`: The chunks of the image, each with the full lane axis.
+/// * `op`: The lane operator.
+/// * `axis`: The lane axis, default = the last axis.
+///
+/// # Returns
+///
+/// * `impl Iterator<Item = Result<ArrayD<f64>, ImgalError>>`: An iterator over
+///   the output of each chunk. An item is `Err(ImgalError)` if `axis` is >= the
+///   number of dimensions of the chunk.
+pub fn lane_op<I, T, O>(
+    chunks: I,
+    op: &O,
+    axis: Option<usize>,
+) -> impl Iterator<Item = Result<ArrayD<f64>, ImgalError>>
+where
+    I: IntoIterator<Item = ArrayD<T>>,
+    T: ToFloat64,
+    O: LaneOp + ?Sized,
+{
+    chunks
+        .into_iter()
+        .map(move |chunk| operator::lane(chunk.view(), op, None, axis))
+}
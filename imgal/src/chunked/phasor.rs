@@ -0,0 +1,47 @@
+use ndarray::Array3;
+
+use crate::error::ImgalError;
+use crate::phasor::time_domain;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image split into chunks.
+///
+/// # Description
+///
+/// This function computes the phasor coordinates of each chunk of a decay image
+/// that is split along a spatial axis (_e.g._ blocks of rows of an out-of-core
+/// acquisition), see `phasor::time_domain::image`. The coordinates of a pixel
+/// only depend on its own decay curve, so chunks need no halo and the output
+/// chunks are equal to the chunks of the full phasor image.
+///
+/// The returned iterator is lazy, each chunk is read and computed when the
+/// iterator is advanced and can be written before the next one is read.
+///
+/// # Arguments
+///
+/// * `chunks`: The chunks of the decay image, each with the full decay axis.
+/// * `period`: The period (_i.e._ time interval). Must be greater than 0.0.
+/// * `harmonic`: The harmonic value, default = 1.0. Must be >= 1.0.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `impl Iterator<Item = Result<Array3<f64>, ImgalError>>`: An iterator over
+///   the phasor coordinates of each chunk, with G and S indexed at 0 and 1 on
+///   the decay axis. An item is `Err(ImgalError)` if axis is >= 3, if period is
+///   <= 0.0 or if harmonic is < 1.0.
+pub fn time_domain_image<I, T>(
+    chunks: I,
+    period: f64,
+    harmonic: Option<f64>,
+    axis: Option<usize>,
+) -> impl Iterator<Item = Result<Array3<f64>, ImgalError>>
+where
+    I: IntoIterator<Item = Array3<T>>,
+    T: ToFloat64,
+{
+    chunks
+        .into_iter()
+        .map(move |chunk| time_domain::image(chunk.view(), period, None, harmonic, axis))
+}
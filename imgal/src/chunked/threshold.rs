@@ -0,0 +1,104 @@
+use ndarray::{ArrayD, Zip};
+
+use crate::error::ImgalError;
+use crate::threshold::otsu::{Histogram, histogram_otsu_threshold, validate_bins};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the Otsu threshold value of an n-dimensional image split into
+/// chunks.
+///
+/// # Description
+///
+/// This function computes the Otsu threshold value of an image that is split
+/// into chunks of any shape, see `threshold::otsu`. The chunks are read twice,
+/// once for the value range of the histogram and once to count the values, so
+/// only one chunk is in memory at a time. The threshold value is equal to the
+/// threshold value of the full image.
+///
+/// # Arguments
+///
+/// * `chunks`: A function that returns a new iterator over the chunks of the
+///   image, _e.g._ by opening the image reader. Both iterators must yield the
+///   same values.
+/// * `bins`: The number of histogram bins, default = 256. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The Otsu threshold value. If all pixels have the same value,
+///   that value is returned.
+/// * `Err(ImgalError)`: If bins is 0. If the chunks are empty.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TSMC.1979.4310076>
+pub fn otsu<F, I, T>(chunks: F, bins: Option<usize>) -> Result<f64, ImgalError>
+where
+    F: Fn() -> I,
+    I: IntoIterator<Item = ArrayD<T>>,
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(256);
+
+    // check if the bins parameter is valid
+    validate_bins(bins)?;
+
+    // find the value range of all chunks
+    let (min, max) = chunks()
+        .into_iter()
+        .flat_map(|chunk| chunk.into_iter())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            let v = v.to_f64();
+            (lo.min(v), hi.max(v))
+        });
+    if min > max {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "chunks",
+            reason: "must contain at least one pixel".to_string(),
+        });
+    }
+
+    // count the values of all chunks
+    let mut hist = Histogram::with_range(min, max, bins);
+    chunks()
+        .into_iter()
+        .for_each(|chunk| chunk.iter().for_each(|v| hist.add(v.to_f64())));
+
+    Ok(histogram_otsu_threshold(&hist))
+}
+
+/// Create boolean masks of an n-dimensional image split into chunks from a
+/// threshold value.
+///
+/// # Description
+///
+/// This function creates the threshold mask (as a boolean array) of each chunk
+/// of an image, see `threshold::manual_mask`. The threshold value of the full
+/// image can be computed with `otsu`.
+///
+/// The returned iterator is lazy, each chunk is read and masked when the
+/// iterator is advanced and can be written before the next one is read.
+///
+/// # Arguments
+///
+/// * `chunks`: The chunks of the image.
+/// * `threshold`: The image pixel threshold value.
+///
+/// # Returns
+///
+/// * `impl Iterator<Item = ArrayD<bool>>`: An iterator over the mask of each
+///   chunk, with pixels that are greater than the threshold value set as `true`
+///   and pixels that are below the threshold value set as `false`.
+pub fn manual_mask<I, T>(chunks: I, threshold: f64) -> impl Iterator<Item = ArrayD<bool>>
+where
+    I: IntoIterator<Item = ArrayD<T>>,
+    T: ToFloat64,
+{
+    chunks.into_iter().map(move |chunk| {
+        let mut mask = ArrayD::<bool>::default(chunk.dim());
+        Zip::from(&chunk).and(&mut mask).par_for_each(|v, m| {
+            *m = v.to_f64() > threshold;
+        });
+        mask
+    })
+}
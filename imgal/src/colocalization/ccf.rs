@@ -0,0 +1,105 @@
+use ndarray::{ArrayViewD, Axis, Slice};
+use rayon::prelude::*;
+
+use crate::colocalization::pearson::correlation;
+use crate::error::{ImgalError, validate};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the Van Steensel cross-correlation function (CCF) of two
+/// n-dimensional images.
+///
+/// # Description
+///
+/// This function computes the Pearson correlation coefficient of image `A` and
+/// image `B` shifted by `dx` pixels along `axis`, for every shift `dx` in
+/// [-max_shift, max_shift]:
+///
+/// ```text
+/// CCF(dx) = r(a(x), b(x + dx))
+/// ```
+///
+/// Only the overlapping pixels of the shifted images are used, pixels shifted
+/// out of the image are not wrapped around. Colocalized signals give a CCF
+/// that peaks at a shift of 0, a peak at a non-zero shift indicates an offset
+/// between the channels (_e.g._ chromatic aberration) and a dip at a shift of
+/// 0 indicates exclusion. The shifts are computed in parallel.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`, that is shifted. Image `B`
+///   must have the same shape as image `A`.
+/// * `max_shift`: The maximum shift, in pixels. Must be < the length of `axis`.
+/// * `axis`: The shift axis, default = the last axis.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, isize))`: The CCF curve of length 2 × max_shift + 1, where
+///   index `i` holds the shift `i - max_shift`, and the shift of the CCF peak.
+///   Shifts where either image is constant over the overlap are NaN.
+/// * `Err(ImgalError)`: If the shapes of image `A` and `B` do not match. If axis
+///   is >= the number of dimensions. If max_shift is >= the length of `axis`.
+///   If either image is constant at every shift.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1242/jcs.109.4.787>
+pub fn ccf<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    max_shift: usize,
+    axis: Option<usize>,
+) -> Result<(Vec<f64>, isize), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(data_a.ndim().saturating_sub(1));
+
+    // check if the image shapes, axis and max_shift parameters are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    validate::axis(a, data_a.ndim())?;
+    let len = data_a.len_of(Axis(a));
+    if max_shift >= len {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "max_shift",
+            reason: format!("must be < the axis length {} but got {}", len, max_shift),
+        });
+    }
+
+    // compute the correlation of the overlapping pixels at each shift
+    let curve: Vec<f64> = (0..2 * max_shift + 1)
+        .into_par_iter()
+        .map(|i| {
+            let dx = i as isize - max_shift as isize;
+            let overlap = len - dx.unsigned_abs();
+            let start_a = (-dx).max(0);
+            let start_b = dx.max(0);
+            let view_a =
+                data_a.slice_axis(Axis(a), Slice::from(start_a..start_a + overlap as isize));
+            let view_b =
+                data_b.slice_axis(Axis(a), Slice::from(start_b..start_b + overlap as isize));
+            let values_a: Vec<f64> = view_a.iter().map(|v| v.to_f64()).collect();
+            let values_b: Vec<f64> = view_b.iter().map(|v| v.to_f64()).collect();
+            correlation(&values_a, &values_b)
+        })
+        .collect();
+
+    // find the CCF peak
+    let peak = curve.iter().enumerate().filter(|(_, r)| !r.is_nan()).fold(
+        None,
+        |best: Option<(usize, f64)>, (i, &r)| match best {
+            Some((_, b)) if b >= r => best,
+            _ => Some((i, r)),
+        },
+    );
+    let Some((peak, _)) = peak else {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must have non-constant intensities in both images".to_string(),
+        });
+    };
+
+    Ok((curve, peak as isize - max_shift as isize))
+}
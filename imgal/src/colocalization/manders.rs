@@ -0,0 +1,240 @@
+use std::cmp::Ordering;
+
+use ndarray::{ArrayViewD, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the Manders split colocalization coefficients of two n-dimensional
+/// images.
+///
+/// # Description
+///
+/// This function computes the Manders split coefficients M1 and M2, the
+/// fraction of the intensity of image `A` that colocalizes with signal in
+/// image `B` and vice versa:
+///
+/// ```text
+/// M1 = Σ aᵢ,coloc / Σ aᵢ, aᵢ,coloc = aᵢ if bᵢ > threshold_b
+/// M2 = Σ bᵢ,coloc / Σ bᵢ, bᵢ,coloc = bᵢ if aᵢ > threshold_a
+/// ```
+///
+/// where the sums of `A` (`B`) only include the pixels with aᵢ > threshold_a
+/// (bᵢ > threshold_b). Unbiased threshold values can be computed with
+/// `costes_threshold`.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded. Must
+///   match the shape of the images.
+/// * `threshold_a`: The pixel intensity threshold value of image `A`,
+///   default = 0.
+/// * `threshold_b`: The pixel intensity threshold value of image `B`,
+///   default = 0.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The M1 and M2 coefficients in [0, 1].
+/// * `Err(ImgalError)`: If the shapes of image `A`, `B` and the mask do not
+///   match. If no used pixel of either image is above its threshold value (or
+///   the intensity sum above it is 0.0).
+///
+/// # Reference
+///
+/// <https://doi.org/10.1111/j.1365-2818.1993.tb03313.x>
+pub fn manders<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+    threshold_a: Option<T>,
+    threshold_b: Option<T>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let t_a = threshold_a.unwrap_or_default();
+    let t_b = threshold_b.unwrap_or_default();
+
+    // check if the image and mask shapes are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    if let Some(msk) = mask.as_ref() {
+        validate::same_shape(msk.shape(), data_a.shape())?;
+    }
+
+    // accumulate the total and colocalized intensity sums of both images
+    let mut sums = [0.0; 4];
+    let mut accumulate = |a: T, b: T| {
+        let (above_a, above_b) = (a > t_a, b > t_b);
+        if above_a {
+            sums[0] += a.to_f64();
+            if above_b {
+                sums[1] += a.to_f64();
+            }
+        }
+        if above_b {
+            sums[2] += b.to_f64();
+            if above_a {
+                sums[3] += b.to_f64();
+            }
+        }
+    };
+    match mask {
+        Some(msk) => Zip::from(&data_a)
+            .and(&data_b)
+            .and(&msk)
+            .for_each(|&a, &b, &m| {
+                if m {
+                    accumulate(a, b);
+                }
+            }),
+        None => Zip::from(&data_a)
+            .and(&data_b)
+            .for_each(|&a, &b| accumulate(a, b)),
+    }
+    if sums[0] == 0.0 || sums[2] == 0.0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must have a non-zero intensity sum above the threshold in both images"
+                .to_string(),
+        });
+    }
+
+    Ok((sums[1] / sums[0], sums[3] / sums[2]))
+}
+
+/// Compute the Costes automatic threshold values of two n-dimensional images.
+///
+/// # Description
+///
+/// This function fits the intensities of image `B` against image `A` with an
+/// orthogonal (total least squares) regression line, `b = slope × a + intercept`,
+/// and lowers the threshold of image `A` (with the threshold of image `B` on
+/// the regression line) until the Pearson correlation coefficient of the
+/// pixels below either threshold value is <= 0.0. The pixels below the
+/// returned thresholds therefore carry no positive correlation (_i.e._ they
+/// are background), which gives unbiased thresholds for `manders`.
+///
+/// Every threshold at which a pixel leaves the set of pixels below the
+/// thresholds is tested, in descending order. If the correlation never drops
+/// to <= 0.0, the lowest tested thresholds are returned.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded. Must
+///   match the shape of the images.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The threshold values of image `A` and `B`.
+/// * `Err(ImgalError)`: If the shapes of image `A`, `B` and the mask do not
+///   match. If the intensities of the images are not positively correlated
+///   (_i.e._ the regression slope is not positive).
+///
+/// # Reference
+///
+/// <https://doi.org/10.1529/biophysj.103.038422>
+pub fn costes_threshold<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the image and mask shapes are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    if let Some(msk) = mask.as_ref() {
+        validate::same_shape(msk.shape(), data_a.shape())?;
+    }
+
+    // collect the intensities of the used pixels
+    let mut pixels: Vec<(f64, f64)> = match mask {
+        Some(msk) => {
+            Zip::from(&data_a)
+                .and(&data_b)
+                .and(&msk)
+                .fold(Vec::new(), |mut acc, &a, &b, &m| {
+                    if m {
+                        acc.push((a.to_f64(), b.to_f64()));
+                    }
+                    acc
+                })
+        }
+        None => data_a
+            .iter()
+            .zip(data_b.iter())
+            .map(|(a, b)| (a.to_f64(), b.to_f64()))
+            .collect(),
+    };
+
+    // fit the orthogonal regression line
+    let n = pixels.len() as f64;
+    let mean_a = pixels.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_b = pixels.iter().map(|p| p.1).sum::<f64>() / n;
+    let (cov, var_a, var_b) = pixels.iter().fold((0.0, 0.0, 0.0), |acc, &(a, b)| {
+        let da = a - mean_a;
+        let db = b - mean_b;
+        (acc.0 + da * db, acc.1 + da * da, acc.2 + db * db)
+    });
+    if cov.is_nan() || cov <= 0.0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must have positively correlated intensities in both images".to_string(),
+        });
+    }
+    let slope = (var_b - var_a + ((var_b - var_a).powi(2) + 4.0 * cov * cov).sqrt()) / (2.0 * cov);
+    let intercept = mean_b - slope * mean_a;
+
+    // a pixel leaves the set below the thresholds once the threshold of image
+    // A is lower than its leave value, sweep the pixels by descending leave
+    // value with running (mean centered) sums
+    let leave = |&(a, b): &(f64, f64)| a.min((b - intercept) / slope);
+    pixels.sort_unstable_by(|p, q| leave(q).partial_cmp(&leave(p)).unwrap_or(Ordering::Equal));
+    let mut sums = pixels.iter().fold([0.0; 5], |mut acc, &(a, b)| {
+        let da = a - mean_a;
+        let db = b - mean_b;
+        acc[0] += da;
+        acc[1] += db;
+        acc[2] += da * da;
+        acc[3] += db * db;
+        acc[4] += da * db;
+        acc
+    });
+    let mut t_a = leave(&pixels[0]);
+    let mut i = 0;
+    while i < pixels.len() {
+        // test the thresholds at the current leave value
+        t_a = leave(&pixels[i]);
+        let count = (pixels.len() - i) as f64;
+        let cov = sums[4] - sums[0] * sums[1] / count;
+        let var_a = sums[2] - sums[0] * sums[0] / count;
+        let var_b = sums[3] - sums[1] * sums[1] / count;
+        let denom = (var_a * var_b).sqrt();
+        if denom > 0.0 && cov / denom <= 0.0 {
+            break;
+        }
+
+        // remove the pixels leaving below the current threshold
+        while i < pixels.len() && leave(&pixels[i]) >= t_a {
+            let da = pixels[i].0 - mean_a;
+            let db = pixels[i].1 - mean_b;
+            sums[0] -= da;
+            sums[1] -= db;
+            sums[2] -= da * da;
+            sums[3] -= db * db;
+            sums[4] -= da * db;
+            i += 1;
+        }
+    }
+
+    Ok((t_a, slope * t_a + intercept))
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use ndarray::{ArrayD, ArrayViewD, Axis, IxDyn, Slice, Zip};
+use rayon::prelude::*;
+
+use crate::colocalization::pearson::correlation;
+use crate::error::{ImgalError, validate};
+use crate::traits::numeric::ToFloat64;
+
+/// Colocalization metrics of the pixel intensities of two images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// The Pearson correlation coefficient, see `colocalization::pearson`.
+    Pearson,
+    /// The Manders M1 coefficient with thresholds of 0, see
+    /// `colocalization::manders`.
+    MandersM1,
+    /// The Manders M2 coefficient with thresholds of 0, see
+    /// `colocalization::manders`.
+    MandersM2,
+}
+
+impl Metric {
+    /// Compute the metric of the paired pixel intensities of image `A` and
+    /// `B`, NaN if the metric is undefined (_e.g._ for constant intensities).
+    pub fn compute(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            Metric::Pearson => correlation(a, b),
+            Metric::MandersM1 => manders_split(a, b),
+            Metric::MandersM2 => manders_split(b, a),
+        }
+    }
+}
+
+/// Compute a colocalization metric per tile of two n-dimensional images.
+///
+/// # Description
+///
+/// This function splits the images into tiles and computes the colocalization
+/// metric of the pixel intensities of each tile, in parallel. The result is a
+/// low resolution map of the metric with one pixel per tile, which shows where
+/// in the image the channels colocalize. Tiles at the end of an axis that is
+/// not a multiple of the tile size are smaller.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `tile`: The tile size per axis. Each tile size must be >= 1.
+/// * `metric`: The metric function, takes the paired pixel intensities of image
+///   `A` and `B` in a tile and returns the metric value, _e.g._
+///   `|a, b| Metric::Pearson.compute(a, b)`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The metric map, with the shape of the images divided by
+///   the tile size (rounded up).
+/// * `Err(ImgalError)`: If the shapes of image `A` and `B` do not match. If the
+///   tile does not have one size per axis or a tile size is 0.
+pub fn tile_map<T, F>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    tile: &[usize],
+    metric: F,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+    F: Fn(&[f64], &[f64]) -> f64 + Sync,
+{
+    // check if the image shapes and tile parameter are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    validate::same_length(tile.len(), data_a.ndim())?;
+    if tile.contains(&0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "tile",
+            reason: format!("must have sizes >= 1 but got {:?}", tile),
+        });
+    }
+
+    // compute the metric of each tile in parallel
+    let map_shape: Vec<usize> = data_a
+        .shape()
+        .iter()
+        .zip(tile)
+        .map(|(&n, &t)| n.div_ceil(t))
+        .collect();
+    let tiles: Vec<IxDyn> = ndarray::indices(IxDyn(&map_shape)).into_iter().collect();
+    let values: Vec<f64> = tiles
+        .par_iter()
+        .map(|idx| {
+            let bounds = |ax: usize| {
+                let start = idx[ax] * tile[ax];
+                Slice::from(start..(start + tile[ax]).min(data_a.len_of(Axis(ax))))
+            };
+            let view_a = data_a.slice_each_axis(|ad| bounds(ad.axis.index()));
+            let view_b = data_b.slice_each_axis(|ad| bounds(ad.axis.index()));
+            let a: Vec<f64> = view_a.iter().map(|v| v.to_f64()).collect();
+            let b: Vec<f64> = view_b.iter().map(|v| v.to_f64()).collect();
+            metric(&a, &b)
+        })
+        .collect();
+
+    Ok(ArrayD::from_shape_vec(IxDyn(&map_shape), values)
+        .expect("The metric map shape matches the number of tiles."))
+}
+
+/// Compute a colocalization metric per labeled region of two n-dimensional
+/// images.
+///
+/// # Description
+///
+/// This function computes the colocalization metric of the pixel intensities
+/// of each labeled region (_e.g._ each segmented cell), in parallel, and maps
+/// the metric value back onto the pixels of the region. Pixels with the label
+/// 0 (_i.e._ the default value of the label type) are unlabeled and set to
+/// NaN.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `labels`: The label image, pixels with the same label belong to the same
+///   region. Must match the shape of the images.
+/// * `metric`: The metric function, takes the paired pixel intensities of image
+///   `A` and `B` in a region and returns the metric value, _e.g._
+///   `|a, b| Metric::Pearson.compute(a, b)`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The metric map with the same shape as the images.
+/// * `Err(ImgalError)`: If the shapes of image `A`, `B` and the label image do
+///   not match.
+pub fn label_map<T, L, F>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    labels: ArrayViewD<L>,
+    metric: F,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+    L: Copy + Eq + Hash + Default + Send + Sync,
+    F: Fn(&[f64], &[f64]) -> f64 + Sync,
+{
+    // check if the image and label shapes are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    validate::same_shape(labels.shape(), data_a.shape())?;
+
+    // group the pixel intensities by region
+    let background = L::default();
+    let mut regions: HashMap<L, (Vec<f64>, Vec<f64>)> = HashMap::new();
+    Zip::from(&data_a)
+        .and(&data_b)
+        .and(&labels)
+        .for_each(|a, b, &l| {
+            if l != background {
+                let region = regions.entry(l).or_default();
+                region.0.push(a.to_f64());
+                region.1.push(b.to_f64());
+            }
+        });
+
+    // compute the metric of each region in parallel and map it back
+    let values: HashMap<L, f64> = regions
+        .into_par_iter()
+        .map(|(l, (a, b))| (l, metric(&a, &b)))
+        .collect();
+    let mut map = ArrayD::<f64>::from_elem(data_a.raw_dim(), f64::NAN);
+    Zip::from(&mut map).and(&labels).par_for_each(|mp, l| {
+        if let Some(&v) = values.get(l) {
+            *mp = v;
+        }
+    });
+
+    Ok(map)
+}
+
+/// Compute the Manders split coefficient of the first channel with thresholds
+/// of 0, NaN if the first channel has no intensity above 0.
+fn manders_split(a: &[f64], b: &[f64]) -> f64 {
+    let (coloc, total) = a
+        .iter()
+        .zip(b)
+        .filter(|&(&x, _)| x > 0.0)
+        .fold((0.0, 0.0), |(c, t), (&x, &y)| {
+            (if y > 0.0 { c + x } else { c }, t + x)
+        });
+    if total > 0.0 { coloc / total } else { f64::NAN }
+}
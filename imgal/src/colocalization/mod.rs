@@ -1,4 +1,15 @@
 //! Colocalization analysis functions (2D and 3D).
+pub mod ccf;
+pub use ccf::ccf;
+pub mod manders;
+pub use manders::{costes_threshold, manders};
+pub mod map;
+pub use map::{Metric, label_map, tile_map};
+pub mod object;
+pub use object::object;
+pub mod pearson;
+pub use pearson::{costes_test, pearson};
 pub mod saca;
 pub use saca::saca_2d;
 pub use saca::saca_3d;
+pub use saca::saca_significance_mask;
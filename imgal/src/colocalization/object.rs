@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use ndarray::{ArrayViewD, Dimension};
+
+use crate::error::{ImgalError, validate};
+use crate::roi::{ResultsRow, ResultsTable};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute object-based colocalization measurements of two label images.
+///
+/// # Description
+///
+/// This function measures each object (_i.e._ labeled region) of label image
+/// `A` against the objects of label image `B` and vice versa. Pixels with the
+/// label 0 (_i.e._ the default value of the label type) are background, binary
+/// masks can be passed as label images where all foreground pixels form one
+/// object. The following measurements are computed per object:
+///
+/// * `area`: The number of pixels of the object.
+/// * `centroid_<axis>`: The centroid of the object along each axis, in pixels.
+/// * `overlap`: The fraction of the object pixels that overlap with any object
+///   of the other image.
+/// * `nearest_distance`: The distance between the object centroid and the
+///   nearest object centroid of the other image, scaled by the pixel
+///   `spacing`. NaN if the other image has no objects.
+/// * `nearest_label`: The label of the nearest object of the other image. NaN
+///   if the other image has no objects.
+///
+/// The measurements are returned as a tidy results table, where `roi` is the
+/// object label, `channel` is 0 for the objects of image `A` and 1 for the
+/// objects of image `B`, and `slice` is 0. The rows are ordered by channel,
+/// then by ascending label, then by measurement.
+///
+/// # Arguments
+///
+/// * `labels_a`: The n-dimensional label image, `A`. Image `A` must have the
+///   same shape as image `B`.
+/// * `labels_b`: The n-dimensional label image, `B`. Image `B` must have the
+///   same shape as image `A`.
+/// * `spacing`: The pixel spacing per axis used for the centroid distances,
+///   default = 1.0 for every axis. Each spacing must be > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(ResultsTable)`: The tidy table of per-object measurements.
+/// * `Err(ImgalError)`: If the shapes of image `A` and `B` do not match. If the
+///   spacing does not have one value per axis or a spacing is <= 0.0.
+pub fn object<L>(
+    labels_a: ArrayViewD<L>,
+    labels_b: ArrayViewD<L>,
+    spacing: Option<&[f64]>,
+) -> Result<ResultsTable, ImgalError>
+where
+    L: ToFloat64 + Eq + Hash,
+{
+    // set optional parameters if needed
+    let ndim = labels_a.ndim();
+    let spacing = spacing.map_or_else(|| vec![1.0; ndim], |s| s.to_vec());
+
+    // check if the label image shapes and the spacing parameter are valid
+    validate::same_shape(labels_a.shape(), labels_b.shape())?;
+    validate::same_length(spacing.len(), ndim)?;
+    spacing
+        .iter()
+        .try_for_each(|&s| validate::positive("spacing", s))?;
+
+    // accumulate the area, coordinate sums and overlap of each object
+    let background = L::default();
+    let mut objects_a: HashMap<L, ObjectStats> = HashMap::new();
+    let mut objects_b: HashMap<L, ObjectStats> = HashMap::new();
+    labels_a
+        .indexed_iter()
+        .zip(labels_b.iter())
+        .for_each(|((idx, &la), &lb)| {
+            let overlap = la != background && lb != background;
+            if la != background {
+                objects_a
+                    .entry(la)
+                    .or_insert_with(|| ObjectStats::new(ndim))
+                    .add(idx.slice(), overlap);
+            }
+            if lb != background {
+                objects_b
+                    .entry(lb)
+                    .or_insert_with(|| ObjectStats::new(ndim))
+                    .add(idx.slice(), overlap);
+            }
+        });
+
+    // sort the objects by label and measure them against the other image
+    let sorted = |objects: HashMap<L, ObjectStats>| {
+        let mut objects: Vec<(L, ObjectStats)> = objects.into_iter().collect();
+        objects.sort_by(|a, b| a.0.to_f64().total_cmp(&b.0.to_f64()));
+        objects
+    };
+    let objects_a = sorted(objects_a);
+    let objects_b = sorted(objects_b);
+    let mut table = ResultsTable::new();
+    measure_objects(&mut table, &objects_a, &objects_b, &spacing, 0);
+    measure_objects(&mut table, &objects_b, &objects_a, &spacing, 1);
+
+    Ok(table)
+}
+
+/// The running statistics of a labeled object.
+struct ObjectStats {
+    area: usize,
+    coord_sums: Vec<f64>,
+    overlap: usize,
+}
+
+impl ObjectStats {
+    /// Create empty object statistics.
+    fn new(ndim: usize) -> Self {
+        ObjectStats {
+            area: 0,
+            coord_sums: vec![0.0; ndim],
+            overlap: 0,
+        }
+    }
+
+    /// Add a pixel to the object.
+    fn add(&mut self, idx: &[usize], overlap: bool) {
+        self.area += 1;
+        self.coord_sums
+            .iter_mut()
+            .zip(idx)
+            .for_each(|(s, &i)| *s += i as f64);
+        if overlap {
+            self.overlap += 1;
+        }
+    }
+
+    /// The centroid of the object, in pixels.
+    fn centroid(&self) -> Vec<f64> {
+        self.coord_sums
+            .iter()
+            .map(|s| s / self.area as f64)
+            .collect()
+    }
+}
+
+/// Measure the objects of one image against the objects of the other image
+/// and append the rows to the results table.
+fn measure_objects<L>(
+    table: &mut ResultsTable,
+    objects: &[(L, ObjectStats)],
+    others: &[(L, ObjectStats)],
+    spacing: &[f64],
+    channel: usize,
+) where
+    L: ToFloat64,
+{
+    let other_centroids: Vec<(f64, Vec<f64>)> = others
+        .iter()
+        .map(|(l, o)| (l.to_f64(), o.centroid()))
+        .collect();
+    objects.iter().for_each(|(label, stats)| {
+        let centroid = stats.centroid();
+
+        // find the nearest object centroid of the other image
+        let (nearest_label, nearest_distance) =
+            other_centroids
+                .iter()
+                .fold((f64::NAN, f64::NAN), |(best_l, best_d), (l, c)| {
+                    let d = centroid
+                        .iter()
+                        .zip(c)
+                        .zip(spacing)
+                        .map(|((a, b), s)| ((a - b) * s).powi(2))
+                        .sum::<f64>()
+                        .sqrt();
+                    if best_d.is_nan() || d < best_d {
+                        (*l, d)
+                    } else {
+                        (best_l, best_d)
+                    }
+                });
+
+        // collect the object measurements
+        let mut measurements = vec![("area".to_string(), stats.area as f64)];
+        centroid
+            .iter()
+            .enumerate()
+            .for_each(|(ax, &c)| measurements.push((format!("centroid_{}", ax), c)));
+        measurements.push((
+            "overlap".to_string(),
+            stats.overlap as f64 / stats.area as f64,
+        ));
+        measurements.push(("nearest_distance".to_string(), nearest_distance));
+        measurements.push(("nearest_label".to_string(), nearest_label));
+        measurements.into_iter().for_each(|(measurement, value)| {
+            table.push(ResultsRow {
+                roi: label.to_f64().to_string(),
+                groups: Vec::new(),
+                slice: 0,
+                channel,
+                measurement,
+                value,
+            });
+        });
+    });
+}
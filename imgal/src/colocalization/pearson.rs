@@ -0,0 +1,254 @@
+use ndarray::{ArrayViewD, IxDyn, Zip};
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::simulation::rng::Seed;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the Pearson correlation coefficient of two n-dimensional images.
+///
+/// # Description
+///
+/// This function computes the Pearson correlation coefficient (PCC) of the
+/// pixel intensities of image `A` and `B`:
+///
+/// ```text
+/// r = Σ(aᵢ - ā)(bᵢ - b̄) / √(Σ(aᵢ - ā)² × Σ(bᵢ - b̄)²)
+/// ```
+///
+/// Only the pixels inside the optional mask and above the optional threshold
+/// values of both images are used.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded. Must
+///   match the shape of the images.
+/// * `threshold_a`: An optional pixel intensity threshold value for image `A`,
+///   pixels with a value <= `threshold_a` are excluded.
+/// * `threshold_b`: An optional pixel intensity threshold value for image `B`,
+///   pixels with a value <= `threshold_b` are excluded.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The Pearson correlation coefficient in [-1, 1].
+/// * `Err(ImgalError)`: If the shapes of image `A`, `B` and the mask do not
+///   match. If the intensities of either image are constant over the used
+///   pixels (_e.g._ fewer than 2 pixels are used).
+pub fn pearson<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+    threshold_a: Option<T>,
+    threshold_b: Option<T>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the image and mask shapes are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    if let Some(msk) = mask.as_ref() {
+        validate::same_shape(msk.shape(), data_a.shape())?;
+    }
+
+    // collect the intensities of the used pixels
+    let used = |a: T, b: T, m: bool| {
+        m && threshold_a.is_none_or(|t| a > t) && threshold_b.is_none_or(|t| b > t)
+    };
+    let mut values_a = Vec::new();
+    let mut values_b = Vec::new();
+    match mask {
+        Some(msk) => Zip::from(&data_a)
+            .and(&data_b)
+            .and(&msk)
+            .for_each(|&a, &b, &m| {
+                if used(a, b, m) {
+                    values_a.push(a.to_f64());
+                    values_b.push(b.to_f64());
+                }
+            }),
+        None => Zip::from(&data_a).and(&data_b).for_each(|&a, &b| {
+            if used(a, b, true) {
+                values_a.push(a.to_f64());
+                values_b.push(b.to_f64());
+            }
+        }),
+    }
+
+    let r = correlation(&values_a, &values_b);
+    if !r.is_finite() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!(
+                "must have non-constant intensities in both images but got {} usable pixel(s)",
+                values_a.len()
+            ),
+        });
+    }
+
+    Ok(r)
+}
+
+/// Test the significance of the Pearson correlation coefficient of two
+/// n-dimensional images with Costes block scrambling.
+///
+/// # Description
+///
+/// This function randomizes image `A` by shuffling blocks of pixels (_e.g._
+/// blocks the size of the point spread function, which keeps the spatial
+/// autocorrelation of the image within each block) and computes the Pearson
+/// correlation coefficient of each scrambled image `A` with image `B`. The
+/// p-value is the fraction of scrambled coefficients that are >= the observed
+/// coefficient, with the observed image counted as one of the permutations:
+///
+/// ```text
+/// p = (1 + #{r_scrambled >= r}) / (1 + iterations)
+/// ```
+///
+/// Only the pixels covered by whole blocks are used, trailing pixels along each
+/// axis that do not fill a block are excluded from the observed and the
+/// scrambled coefficients. The iterations are computed in parallel.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`, that is scrambled. Image `A`
+///   must have the same shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `block`: The block size per axis. Each block size must be >= 1 and <= the
+///   matching image axis length.
+/// * `iterations`: The number of scrambled images, default = 200. Must be >= 1.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded. The
+///   mask is not scrambled. Must match the shape of the images.
+/// * `seed`: Pseudorandom number generator seed, the iteration `i` is seeded
+///   with `seed + i`. If `None`, a thread local generator is used. Pass
+///   `&mut rng` to draw the next stream of an `Rng` context, each iteration is
+///   then scrambled with its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok((f64, Vec<f64>))`: The p-value and the Pearson correlation coefficient
+///   of each scrambled image.
+/// * `Err(ImgalError)`: If the shapes of image `A`, `B` and the mask do not
+///   match. If the block does not have one size per axis or a block size is
+///   outside of [1, axis length]. If iterations is 0. If the intensities of
+///   either image are constant over the used pixels.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1529/biophysj.103.038422>
+pub fn costes_test<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    block: &[usize],
+    iterations: Option<usize>,
+    mask: Option<ArrayViewD<bool>>,
+    seed: impl Into<Seed>,
+) -> Result<(f64, Vec<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let iterations = iterations.unwrap_or(200);
+
+    // check if the image, mask, block and iterations parameters are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    if let Some(msk) = mask.as_ref() {
+        validate::same_shape(msk.shape(), data_a.shape())?;
+    }
+    validate::same_length(block.len(), data_a.ndim())?;
+    block
+        .iter()
+        .zip(data_a.shape())
+        .try_for_each(|(&b, &n)| validate::in_range("block", b as f64, 1.0, n as f64))?;
+    if iterations == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "iterations",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+
+    // split the pixels covered by whole blocks into blocks of image A, and
+    // record the block and offset of each used pixel
+    let grid: Vec<usize> = block
+        .iter()
+        .zip(data_a.shape())
+        .map(|(&b, &n)| n / b)
+        .collect();
+    let n_blocks: usize = grid.iter().product();
+    let block_len: usize = block.iter().product();
+    let mut blocks = vec![0.0; n_blocks * block_len];
+    let mut pixels: Vec<(usize, usize)> = Vec::new();
+    let mut values_b = Vec::new();
+    let crop: Vec<usize> = grid.iter().zip(block).map(|(g, b)| g * b).collect();
+    ndarray::indices(IxDyn(&crop)).into_iter().for_each(|idx| {
+        let (mut b_id, mut offset) = (0, 0);
+        (0..crop.len()).for_each(|ax| {
+            b_id = b_id * grid[ax] + idx[ax] / block[ax];
+            offset = offset * block[ax] + idx[ax] % block[ax];
+        });
+        blocks[b_id * block_len + offset] = data_a[&idx].to_f64();
+        if mask.as_ref().is_none_or(|m| m[&idx]) {
+            pixels.push((b_id, offset));
+            values_b.push(data_b[&idx].to_f64());
+        }
+    });
+
+    // compute the observed coefficient
+    let values_a: Vec<f64> = pixels
+        .iter()
+        .map(|&(b_id, offset)| blocks[b_id * block_len + offset])
+        .collect();
+    let observed = correlation(&values_a, &values_b);
+    if !observed.is_finite() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!(
+                "must have non-constant intensities in both images but got {} usable pixel(s)",
+                values_a.len()
+            ),
+        });
+    }
+
+    // compute the coefficient of each scrambled image in parallel
+    let seed = seed.into();
+    let coefficients: Vec<f64> = (0..iterations)
+        .into_par_iter()
+        .map(|i| {
+            let mut rng = seed.lane_offset(i);
+            let mut order: Vec<usize> = (0..n_blocks).collect();
+            order.shuffle(&mut rng);
+            let scrambled: Vec<f64> = pixels
+                .iter()
+                .map(|&(b_id, offset)| blocks[order[b_id] * block_len + offset])
+                .collect();
+            correlation(&scrambled, &values_b)
+        })
+        .collect();
+    let above = coefficients.iter().filter(|&&r| r >= observed).count();
+
+    Ok(((1 + above) as f64 / (1 + iterations) as f64, coefficients))
+}
+
+/// Compute the Pearson correlation coefficient of two equal length slices, NaN
+/// if either slice is constant.
+pub(crate) fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / n;
+    let mean_b = b.iter().sum::<f64>() / n;
+    let (cov, var_a, var_b) = a.iter().zip(b).fold((0.0, 0.0, 0.0), |acc, (&x, &y)| {
+        let dx = x - mean_a;
+        let dy = y - mean_b;
+        (acc.0 + dx * dy, acc.1 + dx * dx, acc.2 + dy * dy)
+    });
+    let denom = (var_a * var_b).sqrt();
+    if denom > 0.0 {
+        (cov / denom).clamp(-1.0, 1.0)
+    } else {
+        f64::NAN
+    }
+}
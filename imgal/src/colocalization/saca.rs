@@ -1,12 +1,15 @@
 use std::mem;
 
 use ndarray::{
-    Array2, Array3, Array4, ArrayView2, ArrayView3, ArrayViewMut2, ArrayViewMut3, ArrayViewMut4,
-    Axis, Zip,
+    Array2, Array3, Array4, ArrayD, ArrayView2, ArrayView3, ArrayViewD, ArrayViewMut2,
+    ArrayViewMut3, ArrayViewMut4, Axis, Zip,
 };
 use rayon::prelude::*;
 
-use crate::error::ArrayError;
+use crate::distribution::inverse_normal_cdf;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
 use crate::kernel::neighborhood::{weighted_circle, weighted_sphere};
 use crate::statistics::{effective_sample_size, weighted_kendall_tau_b};
 use crate::traits::numeric::ToFloat64;
@@ -44,7 +47,7 @@ use crate::traits::numeric::ToFloat64;
 /// * `OK(Array2<f64>)`: The pixel-wise _z-score_ indicating colocalization or
 ///    anti-colocalization by its sign and the degree or strength of the
 ///    relationship through its absolute values.
-/// * `Err(ArrayError)`: If the dimensions of image `A` and `B` do not match.
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
 ///
 /// # Reference
 ///
@@ -54,20 +57,14 @@ pub fn saca_2d<T>(
     data_b: ArrayView2<T>,
     threshold_a: T,
     threshold_b: T,
-) -> Result<Array2<f64>, ArrayError>
+) -> Result<Array2<f64>, ImgalError>
 where
     T: ToFloat64,
 {
     // TODO make 2D output for now, final output should be 3D (heatmap + p-values)
     // ensure input images have the same shape
+    validate::same_shape(data_a.shape(), data_b.shape())?;
     let dims_a = data_a.dim();
-    let dims_b = data_b.dim();
-    if dims_a != dims_b {
-        return Err(ArrayError::MismatchedArrayShapes {
-            shape_a: vec![dims_a.0, dims_a.1],
-            shape_b: vec![dims_b.0, dims_b.1],
-        });
-    }
 
     // create image buffers
     let mut result = Array2::<f64>::zeros(dims_a);
@@ -159,7 +156,7 @@ where
 /// * `OK(Array3<f64>)`: The pixel-wise _z-score_ indicating colocalization or
 ///    anti-colocalization by its sign and the degree or strength of the
 ///    relationship through its absolute values.
-/// * `Err(ArrayError)`: If the dimensions of image `A` and `B` do not match.
+/// * `Err(ImgalError)`: If the dimensions of image `A` and `B` do not match.
 ///
 /// # Reference
 ///
@@ -169,19 +166,13 @@ pub fn saca_3d<T>(
     data_b: ArrayView3<T>,
     threshold_a: T,
     threshold_b: T,
-) -> Result<Array3<f64>, ArrayError>
+) -> Result<Array3<f64>, ImgalError>
 where
     T: ToFloat64,
 {
     // TODO: consider returning z-score with p-value
+    validate::same_shape(data_a.shape(), data_b.shape())?;
     let dims_a = data_a.dim();
-    let dims_b = data_a.dim();
-    if dims_a != dims_b {
-        return Err(ArrayError::MismatchedArrayShapes {
-            shape_a: vec![dims_a.0, dims_a.1, dims_a.2],
-            shape_b: vec![dims_b.0, dims_b.1, dims_b.2],
-        });
-    }
 
     // create image buffers
     let mut result = Array3::<f64>::zeros(dims_a);
@@ -240,6 +231,68 @@ where
     Ok(result)
 }
 
+/// Create a boolean mask of the significantly colocalized pixels of a SACA
+/// _z-score_ image.
+///
+/// # Description
+///
+/// This function tests the pixel-wise _z-scores_ computed by `saca_2d` or
+/// `saca_3d` with a two-sided test at the significance level `alpha`, with a
+/// Bonferroni correction for the number of pixels (_i.e._ tests):
+///
+/// ```text
+/// z_crit = Φ⁻¹(1 - α / (2 × N))
+/// ```
+///
+/// where `Φ⁻¹` is the inverse standard normal cumulative distribution function
+/// and `N` is the number of pixels. Pixels with a _z-score_ > `z_crit` are
+/// significantly colocalized. Significantly anti-colocalized pixels can be
+/// selected by negating the _z-scores_.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional SACA _z-score_ image.
+/// * `alpha`: The family-wise significance level, default = 0.05. Must be in
+///   (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<bool>)`: A boolean array of the same shape as the input image
+///   with the significantly colocalized pixels set as `true`.
+/// * `Err(ImgalError)`: If alpha is not in (0, 1).
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TIP.2019.2909194>
+pub fn saca_significance_mask(
+    data: ArrayViewD<f64>,
+    alpha: Option<f64>,
+) -> Result<ArrayD<bool>, ImgalError> {
+    // set optional parameters if needed
+    let alpha = alpha.unwrap_or(0.05);
+
+    // check if the alpha parameter is valid
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "alpha",
+            reason: format!("must be in (0, 1) but got {}", alpha),
+        });
+    }
+
+    // compute the Bonferroni corrected critical z-score and apply it
+    let n = data.len().max(1) as f64;
+    let z_crit = inverse_normal_cdf(1.0 - alpha / (2.0 * n))?;
+    let mut mask = ArrayD::<bool>::default(data.dim());
+    Zip::from(&data).and(&mut mask).par_for_each(|&z, mp| {
+        *mp = z > z_crit;
+    });
+    if !mask.iter().any(|&m| m) {
+        warning::warn(Warning::EmptyMask);
+    }
+
+    Ok(mask)
+}
+
 /// Fill working buffers from 2-dimensional data.
 fn fill_buffers_2d<T>(
     data_a: ArrayView2<T>,
@@ -0,0 +1,3 @@
+//! Configuration functions.
+pub mod threads;
+pub use threads::{ThreadPool, num_threads, set_num_threads, with_num_threads};
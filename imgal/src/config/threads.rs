@@ -0,0 +1,163 @@
+use rayon::ThreadPoolBuilder;
+
+use crate::error::warning;
+use crate::error::{ImgalError, validate};
+use crate::version::algorithm;
+
+/// A bounded pool of worker threads for imgal's parallel functions.
+///
+/// # Description
+///
+/// imgal's parallel functions run on the rayon thread pool they are called in,
+/// which is the global pool with one thread per core unless they are called
+/// inside another pool. A `ThreadPool` bounds the parallelism of the functions
+/// called in `install` without changing the global pool, so an application
+/// that manages its own threads can give imgal a fixed share of the cores. The
+/// worker threads are kept alive, reuse one pool across calls instead of
+/// creating a pool per call.
+///
+/// # Example
+///
+/// ```
+/// use imgal::config::{self, ThreadPool};
+///
+/// let pool = ThreadPool::new(2).unwrap();
+/// assert_eq!(pool.install(config::num_threads), 2);
+/// ```
+#[derive(Debug)]
+pub struct ThreadPool {
+    pool: rayon::ThreadPool,
+}
+
+impl ThreadPool {
+    /// Create a new thread pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_threads`: The number of worker threads. Must be >= 1.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ThreadPool)`: The thread pool.
+    /// * `Err(ImgalError)`: If num_threads is 0. If the worker threads can not
+    ///   be spawned.
+    pub fn new(num_threads: usize) -> Result<Self, ImgalError> {
+        validate::at_least("num_threads", num_threads as f64, 1.0)?;
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(|e| ImgalError::InvalidParameter {
+                param_name: "num_threads",
+                reason: e.to_string(),
+            })?;
+
+        Ok(ThreadPool { pool })
+    }
+
+    /// Run a function in the thread pool.
+    ///
+    /// # Description
+    ///
+    /// The function runs on a worker thread of the pool and blocks the calling
+    /// thread until it returns. The parallel work of all imgal functions called
+    /// in `f` is bounded to the worker threads of the pool. An algorithm
+    /// version pinned on the calling thread (see `version::algorithm::pinned`)
+    /// stays pinned in `f` and the warnings emitted in `f` are reported on the
+    /// calling thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `f`: The function to run.
+    ///
+    /// # Returns
+    ///
+    /// * `R`: The output of `f`.
+    pub fn install<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R + Send,
+        R: Send,
+    {
+        let version = algorithm::thread_pinned();
+        let (output, warnings) = self.pool.install(|| {
+            warning::isolate(|| match version {
+                Some(v) => algorithm::pinned(v, f),
+                None => f(),
+            })
+        });
+        warnings.into_iter().for_each(warning::warn);
+
+        output
+    }
+
+    /// The number of worker threads.
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+}
+
+/// Get the number of threads available to imgal's parallel functions.
+///
+/// # Returns
+///
+/// * `usize`: The number of worker threads of the thread pool the calling
+///   thread runs in, the global pool if it does not run in a `ThreadPool`.
+pub fn num_threads() -> usize {
+    rayon::current_num_threads()
+}
+
+/// Set the number of threads of the global thread pool.
+///
+/// # Description
+///
+/// This function bounds the parallelism of all imgal functions that are not
+/// called inside a `ThreadPool`. The global pool is shared with all rayon users
+/// of the process and is created the first time parallel work runs, after that
+/// its size can not be changed. Call this function once at application start,
+/// before any imgal function runs, or use a `ThreadPool` to bound the
+/// parallelism per call.
+///
+/// # Arguments
+///
+/// * `num_threads`: The number of worker threads. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the global thread pool was created.
+/// * `Err(ImgalError)`: If num_threads is 0. If the global thread pool is
+///   already created.
+pub fn set_num_threads(num_threads: usize) -> Result<(), ImgalError> {
+    validate::at_least("num_threads", num_threads as f64, 1.0)?;
+    ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build_global()
+        .map_err(|_| ImgalError::InvalidParameter {
+            param_name: "num_threads",
+            reason: "can not be set after the global thread pool is created".to_string(),
+        })
+}
+
+/// Run a function in a new thread pool.
+///
+/// # Description
+///
+/// This function creates a `ThreadPool` with `num_threads` worker threads and
+/// runs `f` in it, see `ThreadPool::install`. Creating a pool spawns its
+/// threads, reuse a `ThreadPool` to run many short functions.
+///
+/// # Arguments
+///
+/// * `num_threads`: The number of worker threads. Must be >= 1.
+/// * `f`: The function to run.
+///
+/// # Returns
+///
+/// * `Ok(R)`: The output of `f`.
+/// * `Err(ImgalError)`: If num_threads is 0. If the worker threads can not be
+///   spawned.
+pub fn with_num_threads<F, R>(num_threads: usize, f: F) -> Result<R, ImgalError>
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    Ok(ThreadPool::new(num_threads)?.install(f))
+}
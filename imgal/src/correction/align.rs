@@ -0,0 +1,209 @@
+use ndarray::{Array2, Array3, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::traits::numeric::ToFloat64;
+
+/// Methods to detect the time zero (t0) position of a decay curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignMethod {
+    /// The position of the decay's maximum value.
+    Peak,
+    /// The position where the decay's rising edge first crosses half of its
+    /// baseline corrected maximum, linearly interpolated between bins.
+    RisingEdge,
+}
+
+/// Align 3-dimensional decay data to a common time zero (t0).
+///
+/// # Description
+///
+/// This function detects the t0 position of each decay (see `t0`) and
+/// circularly shifts each decay by a whole number of bins so that its t0 moves
+/// to the `target` bin. This corrects timing skew between pixels (_e.g._
+/// scanner induced) or detector channels before analysis. Decays without
+/// signal are left unshifted.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `method`: The t0 detection method, default = `AlignMethod::RisingEdge`.
+/// * `target`: The bin to move each decay's t0 to. If `None`, then the median
+///   t0 of all decays with signal is used.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The aligned decay data with the same shape as `data`.
+/// * `Err(ImgalError)`: If axis is >= 3. If `target` is outside of the decay
+///   axis.
+pub fn align_decays<T>(
+    data: ArrayView3<T>,
+    method: Option<AlignMethod>,
+    target: Option<usize>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if axis and target parameters are valid
+    validate::axis(a, 3)?;
+    let n = data.len_of(Axis(a));
+    if let Some(t) = target
+        && t >= n
+    {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "target",
+            reason: format!("must be a bin index < {} but got {}", n, t),
+        });
+    }
+
+    // detect the t0 of every decay and find the common target bin
+    let t0_arr = t0_image(data, method, Some(a))?;
+    let target = match target {
+        Some(t) => t as f64,
+        None => {
+            let mut valid: Vec<f64> = t0_arr.iter().copied().filter(|v| v.is_finite()).collect();
+            if valid.is_empty() {
+                return Ok(data.mapv(|v| v.to_f64()));
+            }
+            valid.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            valid[valid.len() / 2].round()
+        }
+    };
+
+    // circularly shift each decay to the target bin
+    let mut aligned = Array3::<f64>::zeros(data.dim());
+    Zip::from(data.lanes(Axis(a)))
+        .and(aligned.lanes_mut(Axis(a)))
+        .and(&t0_arr)
+        .par_for_each(|src, mut dst, &t| {
+            let shift = if t.is_finite() {
+                (target - t.round()) as isize
+            } else {
+                0
+            };
+            let shift = shift.rem_euclid(n as isize) as usize;
+            src.iter().enumerate().for_each(|(i, v)| {
+                dst[(i + shift) % n] = v.to_f64();
+            });
+        });
+
+    Ok(aligned)
+}
+
+/// Detect the time zero (t0) position of a 1-dimensional decay curve.
+///
+/// # Description
+///
+/// This function detects the t0 position, in bins, of a decay curve with the
+/// given method. `AlignMethod::Peak` returns the position of the maximum value.
+/// `AlignMethod::RisingEdge` returns the position where the curve first crosses
+/// half of its baseline (_i.e._ minimum) corrected maximum, linearly
+/// interpolated between the bins on either side of the crossing. The rising
+/// edge is less sensitive to decay shape and noise at the peak.
+///
+/// # Arguments
+///
+/// * `data`: The 1-dimensional decay curve.
+/// * `method`: The t0 detection method, default = `AlignMethod::RisingEdge`.
+///
+/// # Returns
+///
+/// * `Some(f64)`: The t0 position in bins.
+/// * `None`: If the decay curve is empty, flat or not finite.
+pub fn t0<T>(data: &[T], method: Option<AlignMethod>) -> Option<f64>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let method = method.unwrap_or(AlignMethod::RisingEdge);
+
+    // find the baseline and peak of the curve
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut peak = 0;
+    data.iter().enumerate().for_each(|(i, v)| {
+        let v = v.to_f64();
+        if v < min {
+            min = v;
+        }
+        if v > max {
+            max = v;
+            peak = i;
+        }
+    });
+    if !(max - min).is_finite() || max <= min {
+        return None;
+    }
+
+    match method {
+        AlignMethod::Peak => Some(peak as f64),
+        AlignMethod::RisingEdge => {
+            // find the first half maximum crossing before the peak
+            let half = min + 0.5 * (max - min);
+            let i = data[..=peak].iter().position(|v| v.to_f64() >= half)?;
+            if i == 0 {
+                return Some(0.0);
+            }
+            let lo = data[i - 1].to_f64();
+            let hi = data[i].to_f64();
+            Some((i - 1) as f64 + (half - lo) / (hi - lo))
+        }
+    }
+}
+
+/// Detect the time zero (t0) position of every decay in a 3-dimensional image.
+///
+/// # Description
+///
+/// This function detects the t0 position of each decay along the decay axis
+/// with the given method (see `t0`).
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `method`: The t0 detection method, default = `AlignMethod::RisingEdge`.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The t0 position, in bins, of each decay. Decays without
+///   signal are set to NaN.
+/// * `Err(ImgalError)`: If axis is >= 3.
+pub fn t0_image<T>(
+    data: ArrayView3<T>,
+    method: Option<AlignMethod>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if axis parameter is valid
+    validate::axis(a, 3)?;
+
+    // drop the decay axis and detect t0 per lane
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut t0_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    Zip::from(data.lanes(Axis(a)))
+        .and(&mut t0_arr)
+        .par_for_each(|ln, t| {
+            *t = t0(&ln.to_vec(), method).unwrap_or(f64::NAN);
+        });
+
+    // report decays without signal
+    let count = t0_arr.iter().filter(|v| v.is_nan()).count();
+    if count > 0 {
+        warning::warn(Warning::NonFiniteOutput { count });
+    }
+
+    Ok(t0_arr)
+}
@@ -0,0 +1,206 @@
+use ndarray::{ArrayD, ArrayViewD, Axis};
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::filter::match_histogram;
+use crate::traits::numeric::ToFloat64;
+
+/// The number of log-spaced bleaching rates of the exponential fit grid search.
+const RATE_STEPS: usize = 200;
+
+/// Photobleaching correction methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BleachMethod {
+    /// Fit a single exponential decay with offset to the mean intensity of the
+    /// frames and scale each frame by the inverse of the fitted decay.
+    Exponential,
+    /// Match the histogram of each frame to the histogram of the first frame.
+    HistogramMatching,
+}
+
+/// The result of a photobleaching correction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BleachCorrection {
+    /// The corrected image with the same shape as the input image.
+    pub corrected: ArrayD<f64>,
+    /// The bleaching curve, one value per frame. The fitted exponential decay
+    /// with `BleachMethod::Exponential`, otherwise the measured mean intensity
+    /// of each frame.
+    pub curve: Vec<f64>,
+}
+
+/// Correct the photobleaching of a 3 or 4-dimensional time series.
+///
+/// # Description
+///
+/// This function compensates the loss of fluorescence intensity over the time
+/// axis of an image stack (_e.g._ (t, row, col) or (t, pln, row, col)). With
+/// `BleachMethod::Exponential` the mean intensity of each frame is fitted with
+/// a single exponential decay with offset:
+///
+/// ```text
+/// f(t) = a × exp(-b × t) + c
+/// ```
+///
+/// by a grid search over the rate `b` with the amplitude `a` and offset `c`
+/// solved by linear least squares for each rate, refined with a golden section
+/// search. Each frame is then scaled by `f(0) / f(t)`, frames where the fitted
+/// curve is not > 0.0 are left uncorrected. This preserves intensity ratios
+/// within frames and suits quantitative analysis. With
+/// `BleachMethod::HistogramMatching` the histogram of each frame is matched to
+/// the histogram of the first frame (see `filter::match_histogram`), which
+/// also corrects non-linear intensity changes but does not preserve the
+/// intensity ratios and is intended for segmentation and visualization.
+///
+/// # Arguments
+///
+/// * `data`: The 3 or 4-dimensional time series.
+/// * `method`: The correction method, default = `BleachMethod::Exponential`.
+/// * `axis`: The time axis, default = 0.
+///
+/// # Returns
+///
+/// * `Ok(BleachCorrection)`: The corrected image and the bleaching curve.
+/// * `Err(ImgalError)`: If data is not 3 or 4-dimensional. If axis is out of
+///   bounds. If the time axis has fewer than 3 (exponential) or 2 (histogram
+///   matching) frames.
+pub fn bleach<T>(
+    data: ArrayViewD<T>,
+    method: Option<BleachMethod>,
+    axis: Option<usize>,
+) -> Result<BleachCorrection, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let method = method.unwrap_or(BleachMethod::Exponential);
+    let a = axis.unwrap_or(0);
+
+    // check if the data, axis and frame count are valid
+    if data.ndim() != 3 && data.ndim() != 4 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!(
+                "must be 3 or 4-dimensional but got {} dimensions",
+                data.ndim()
+            ),
+        });
+    }
+    validate::axis(a, data.ndim())?;
+    let n = data.len_of(Axis(a));
+    let min_frames = match method {
+        BleachMethod::Exponential => 3,
+        BleachMethod::HistogramMatching => 2,
+    };
+    if n < min_frames {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!(
+                "must have at least {} frames along the time axis but got {}",
+                min_frames, n
+            ),
+        });
+    }
+
+    // measure the mean intensity of each frame
+    let mut corrected = data.mapv(|v| v.to_f64());
+    let means: Vec<f64> = corrected
+        .axis_iter(Axis(a))
+        .map(|frame| frame.mean().unwrap_or(0.0))
+        .collect();
+
+    match method {
+        BleachMethod::Exponential => {
+            let (amp, rate, offset) = fit_exponential(&means);
+            let curve: Vec<f64> = (0..n)
+                .map(|t| amp * (-rate * t as f64).exp() + offset)
+                .collect();
+            corrected
+                .axis_iter_mut(Axis(a))
+                .into_par_iter()
+                .zip(curve.par_iter())
+                .for_each(|(mut frame, &f)| {
+                    if f > 0.0 && curve[0] > 0.0 {
+                        let scale = curve[0] / f;
+                        frame.mapv_inplace(|v| v * scale);
+                    }
+                });
+
+            Ok(BleachCorrection { corrected, curve })
+        }
+        BleachMethod::HistogramMatching => {
+            let reference = corrected.index_axis(Axis(a), 0).to_owned();
+            corrected
+                .axis_iter_mut(Axis(a))
+                .into_par_iter()
+                .skip(1)
+                .for_each(|mut frame| {
+                    let matched = match_histogram(frame.view(), reference.view())
+                        .expect("the reference frame is not empty");
+                    frame.assign(&matched);
+                });
+
+            Ok(BleachCorrection {
+                corrected,
+                curve: means,
+            })
+        }
+    }
+}
+
+/// Fit `y(t) = a × exp(-b × t) + c` to a curve sampled at t = 0, 1, ..., the
+/// returned parameters are (a, b, c).
+fn fit_exponential(y: &[f64]) -> (f64, f64, f64) {
+    // solve the amplitude and offset of a rate by linear least squares
+    let n = y.len() as f64;
+    let y_mean = y.iter().sum::<f64>() / n;
+    let solve = |b: f64| -> (f64, f64, f64) {
+        let x: Vec<f64> = (0..y.len()).map(|t| (-b * t as f64).exp()).collect();
+        let x_mean = x.iter().sum::<f64>() / n;
+        let (cov, var) = x
+            .iter()
+            .zip(y.iter())
+            .fold((0.0, 0.0), |(cov, var), (xi, yi)| {
+                let dx = xi - x_mean;
+                (cov + dx * (yi - y_mean), var + dx * dx)
+            });
+        let amp = if var > 1e-12 { cov / var } else { 0.0 };
+        let offset = y_mean - amp * x_mean;
+        let sse = x
+            .iter()
+            .zip(y.iter())
+            .map(|(xi, yi)| (amp * xi + offset - yi).powi(2))
+            .sum();
+        (amp, offset, sse)
+    };
+
+    // grid search the rate on a log scale, from a negligible to a full decay
+    // within one frame
+    let (lo, hi) = ((1e-4 / n).ln(), 10.0f64.ln());
+    let rate = |k: f64| (lo + k * (hi - lo) / RATE_STEPS as f64).exp();
+    let best = (0..=RATE_STEPS)
+        .map(|k| (k, solve(rate(k as f64)).2))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+        .0;
+
+    // refine the rate with a golden section search between the neighbors
+    let inv_phi = (5.0f64.sqrt() - 1.0) / 2.0;
+    let (mut k_lo, mut k_hi) = (
+        best.saturating_sub(1) as f64,
+        (best + 1).min(RATE_STEPS) as f64,
+    );
+    (0..40).for_each(|_| {
+        let k1 = k_hi - inv_phi * (k_hi - k_lo);
+        let k2 = k_lo + inv_phi * (k_hi - k_lo);
+        if solve(rate(k1)).2 < solve(rate(k2)).2 {
+            k_hi = k2;
+        } else {
+            k_lo = k1;
+        }
+    });
+    let b = rate(0.5 * (k_lo + k_hi));
+    let (amp, offset, _) = solve(b);
+
+    (amp, b, offset)
+}
@@ -0,0 +1,236 @@
+use ndarray::{Array2, ArrayD, ArrayView2, ArrayView3, ArrayViewD, Axis, Ix2, Zip, s};
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::filter::{self, BorderMode, GaussianMethod};
+use crate::traits::numeric::ToFloat64;
+
+/// The number of robust reweighting iterations of the BaSiC-style estimate.
+const BASIC_ITERATIONS: usize = 5;
+
+/// The relative deviation from the flat-field at which a pixel's weight in the
+/// BaSiC-style estimate is halved.
+const BASIC_SCALE: f64 = 0.1;
+
+/// Methods to estimate a flat-field from the images themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlatfieldMethod {
+    /// Blur the (mean projected) image with a wide Gaussian, which keeps the
+    /// slowly varying illumination and removes the objects.
+    Gaussian,
+    /// Retrospectively estimate the flat-field from a stack of images with
+    /// varying content with a robust, smooth per-pixel average of the
+    /// normalized frames, in the style of BaSiC.
+    Basic,
+}
+
+/// The result of a flat-field correction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatfieldCorrection {
+    /// The corrected image with the same shape as the input image.
+    pub corrected: ArrayD<f64>,
+    /// The (row, col) flat-field gain normalized to a mean of 1.0, either
+    /// derived from the given flat-field image or estimated.
+    pub flat: Array2<f64>,
+}
+
+/// Correct the uneven illumination of a 2-dimensional image or a stack of
+/// 2-dimensional images with a flat-field.
+///
+/// # Description
+///
+/// This function divides the dark corrected image by the flat-field gain:
+///
+/// ```text
+/// C = (I - D) / F,  F = (flat - D) / mean(flat - D)
+/// ```
+///
+/// where `D` is the dark (_i.e._ offset) image, 0.0 if not given. Pixels where
+/// the gain is not > 0.0 are set to 0.0. If no flat-field image is given, the
+/// gain is estimated from the dark corrected data with `method`:
+///
+/// * `FlatfieldMethod::Gaussian`: The mean projection over the frames is
+///   blurred with a Gaussian of standard deviation `sigma`.
+/// * `FlatfieldMethod::Basic`: Each frame is normalized by its mean and the
+///   gain is the per-pixel average of the normalized frames, iteratively
+///   reweighted to reject pixels deviating from the current estimate (_i.e._
+///   the objects) and smoothed with a Gaussian of standard deviation `sigma`.
+///   This is a simplified BaSiC-style estimate (no darkfield or baseline drift
+///   estimation) that needs a stack with varying content across the frames.
+///
+/// # Arguments
+///
+/// * `data`: A 2-dimensional (row, col) image or a 3-dimensional
+///   (frame, row, col) stack.
+/// * `flat`: The (row, col) flat-field image (_e.g._ an image of a uniform
+///   sample). If `None`, the flat-field is estimated with `method`.
+/// * `dark`: The (row, col) dark image, subtracted from the data and the
+///   flat-field image. If `None`, no offset is subtracted.
+/// * `method`: The flat-field estimation method, default =
+///   `FlatfieldMethod::Gaussian`. Ignored if `flat` is given.
+/// * `sigma`: The standard deviation of the Gaussian smoothing of the
+///   estimate in pixels, default = max(rows, cols) / 8. Must be > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(FlatfieldCorrection)`: The corrected image and the flat-field gain.
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional or empty. If the flat
+///   or dark image shape does not match the (row, col) shape of the data. If
+///   the dark corrected flat-field has a mean <= 0.0. If sigma is <= 0.0. If
+///   the `Basic` method is used with fewer than 2 frames.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1038/ncomms14836>
+pub fn flatfield<T>(
+    data: ArrayViewD<T>,
+    flat: Option<ArrayView2<f64>>,
+    dark: Option<ArrayView2<f64>>,
+    method: Option<FlatfieldMethod>,
+    sigma: Option<f64>,
+) -> Result<FlatfieldCorrection, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the data, flat and dark parameters are valid
+    if (data.ndim() != 2 && data.ndim() != 3) || data.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!(
+                "must be a non-empty 2 or 3-dimensional array but got shape {:?}",
+                data.shape()
+            ),
+        });
+    }
+    let plane = &data.shape()[data.ndim() - 2..];
+    if let Some(f) = flat.as_ref() {
+        validate::same_shape(f.shape(), plane)?;
+    }
+    if let Some(d) = dark.as_ref() {
+        validate::same_shape(d.shape(), plane)?;
+    }
+
+    // set optional parameters if needed
+    let method = method.unwrap_or(FlatfieldMethod::Gaussian);
+    let sigma = sigma.unwrap_or(plane[0].max(plane[1]) as f64 / 8.0);
+    validate::positive("sigma", sigma)?;
+
+    // subtract the dark image from every frame
+    let mut corrected = data.mapv(|v| v.to_f64()).as_standard_layout().into_owned();
+    let plane_len = plane[0] * plane[1];
+    if let Some(d) = dark.as_ref() {
+        let d = d.as_standard_layout();
+        let d = d.as_slice().unwrap();
+        corrected
+            .as_slice_mut()
+            .unwrap()
+            .par_chunks_mut(plane_len)
+            .for_each(|frame| frame.iter_mut().zip(d.iter()).for_each(|(v, o)| *v -= o));
+    }
+    let stack = corrected
+        .view()
+        .into_shape_with_order((corrected.len() / plane_len, plane[0], plane[1]))
+        .expect("the corrected image is in standard layout");
+
+    // derive or estimate the flat-field gain
+    let mut gain = match flat {
+        Some(f) => match dark.as_ref() {
+            Some(d) => &f - d,
+            None => f.to_owned(),
+        },
+        None => match method {
+            FlatfieldMethod::Gaussian => {
+                let projection = stack.mean_axis(Axis(0)).unwrap();
+                blur(projection.view(), sigma)?
+            }
+            FlatfieldMethod::Basic => {
+                if stack.len_of(Axis(0)) < 2 {
+                    return Err(ImgalError::InvalidParameter {
+                        param_name: "data",
+                        reason: "must be a stack of at least 2 frames to estimate the \
+                                 flat-field with the Basic method"
+                            .to_string(),
+                    });
+                }
+                estimate_basic(stack, sigma)?
+            }
+        },
+    };
+    let mean = gain.mean().unwrap();
+    if !(mean > 0.0 && mean.is_finite()) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "flat",
+            reason: format!("must have a dark corrected mean > 0 but got {}", mean),
+        });
+    }
+    gain.mapv_inplace(|v| v / mean);
+
+    // divide every frame by the gain
+    let gain = gain.as_standard_layout().into_owned();
+    let g = gain.as_slice().unwrap();
+    corrected
+        .as_slice_mut()
+        .unwrap()
+        .par_chunks_mut(plane_len)
+        .for_each(|frame| {
+            frame.iter_mut().zip(g.iter()).for_each(|(v, &g)| {
+                *v = if g > 0.0 { *v / g } else { 0.0 };
+            });
+        });
+
+    Ok(FlatfieldCorrection {
+        corrected,
+        flat: gain,
+    })
+}
+
+/// Blur a 2-dimensional image with a wide Gaussian.
+fn blur(data: ArrayView2<f64>, sigma: f64) -> Result<Array2<f64>, ImgalError> {
+    let blurred = filter::gaussian(
+        data.into_dyn(),
+        &[sigma, sigma],
+        Some(BorderMode::Reflect),
+        Some(GaussianMethod::Recursive),
+    )?;
+
+    Ok(blurred
+        .into_dimensionality::<Ix2>()
+        .expect("the blurred image is 2-dimensional"))
+}
+
+/// Estimate the flat-field of a (frame, row, col) stack with an iteratively
+/// reweighted, smoothed average of the mean normalized frames.
+fn estimate_basic(stack: ArrayView3<f64>, sigma: f64) -> Result<Array2<f64>, ImgalError> {
+    // normalize each frame by its mean
+    let mut normalized = stack.to_owned();
+    normalized.axis_iter_mut(Axis(0)).for_each(|mut frame| {
+        let m = frame.mean().unwrap();
+        if m != 0.0 {
+            frame.mapv_inplace(|v| v / m);
+        }
+    });
+
+    // start from the smoothed per-pixel average and reweight the pixels by
+    // their deviation from the current estimate
+    let (rows, cols) = (stack.shape()[1], stack.shape()[2]);
+    let mut estimate = blur(normalized.mean_axis(Axis(0)).unwrap().view(), sigma)?;
+    for _ in 0..BASIC_ITERATIONS {
+        let mut average = Array2::<f64>::zeros((rows, cols));
+        Zip::indexed(&mut average).par_for_each(|(r, c), a| {
+            let e = estimate[[r, c]];
+            let (sum, weight) =
+                normalized
+                    .slice(s![.., r, c])
+                    .iter()
+                    .fold((0.0, 0.0), |(sum, weight), &v| {
+                        let dev = if e != 0.0 { (v - e) / e } else { 0.0 };
+                        let w = 1.0 / (1.0 + (dev / BASIC_SCALE).powi(2));
+                        (sum + w * v, weight + w)
+                    });
+            *a = if weight > 0.0 { sum / weight } else { e };
+        });
+        estimate = blur(average.view(), sigma)?;
+    }
+
+    Ok(estimate)
+}
@@ -0,0 +1,7 @@
+//! Data correction functions.
+pub mod align;
+pub use align::{AlignMethod, align_decays, t0, t0_image};
+pub mod bleach;
+pub use bleach::{BleachCorrection, BleachMethod, bleach};
+pub mod flatfield;
+pub use flatfield::{FlatfieldCorrection, FlatfieldMethod, flatfield};
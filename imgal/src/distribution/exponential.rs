@@ -0,0 +1,47 @@
+use crate::distribution::sample_normalized;
+use crate::error::{ImgalError, validate};
+
+/// Generate a normalized exponential distribution over a specified range.
+///
+/// # Description
+///
+/// This function creates a discrete exponential distribution by sampling the
+/// continuous exponential decay at evenly spaced points across a given range.
+/// The resulting distribution is normalized so that all values sum to 1.0.
+/// The function implements the exponential probability density function:
+///
+/// ```text
+/// f(x) = exp(-(x - x₀) / τ)  for x >= x₀
+/// f(x) = 0                   for x < x₀
+/// ```
+/// Where:
+/// - `x` is the position along the range.
+/// - `x₀` is the offset (onset).
+/// - `τ` is the tau (decay constant).
+///
+/// # Arguments
+///
+/// * `tau`: The decay constant of the exponential distribution. Must be > 0.0.
+/// * `bins`: The number of discrete points to sample the exponential
+///   distribution. Must be >= 2.
+/// * `range`: The total width of the sampling range. Must be > 0.0.
+/// * `offset`: The onset of the exponential distribution. Must be within the
+///   range.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The normalized exponential distribution.
+/// * `Err(ImgalError)`: If tau or range is <= 0.0. If bins is < 2. If offset
+///   is greater than range.
+pub fn exponential(tau: f64, bins: usize, range: f64, offset: f64) -> Result<Vec<f64>, ImgalError> {
+    // check if the tau parameter is valid
+    validate::positive("tau", tau)?;
+
+    sample_normalized(bins, range, |x| {
+        if x >= offset {
+            (-(x - offset) / tau).exp()
+        } else {
+            0.0
+        }
+    })
+}
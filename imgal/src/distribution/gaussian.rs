@@ -1,4 +1,5 @@
-use crate::statistics::sum;
+use crate::distribution::sample_normalized;
+use crate::error::{ImgalError, validate};
 
 /// Generate a normalized Gaussian distribution over a specified range.
 ///
@@ -19,37 +20,24 @@ use crate::statistics::sum;
 ///
 /// # Arguments
 ///
-/// * `sigma`: The standard deviation of the Gaussian distribution (_i.e._ the width).
+/// * `sigma`: The standard deviation of the Gaussian distribution (_i.e._ the
+///   width). Must be > 0.0.
 /// * `bins`: The number of discrete points to sample the Gaussian distribution.
-/// * `range`: The total width of the sampling range.
+///   Must be >= 2.
+/// * `range`: The total width of the sampling range. Must be > 0.0.
 /// * `center`: The mean (center) of the Gaussian distribution (_i.e._ the peak).
 ///
 /// # Returns
 ///
-/// * `Vec<f64>`: The normalized Gaussian distribution.
-pub fn gaussian(sigma: f64, bins: usize, range: f64, center: f64) -> Vec<f64> {
-    // create data range (i.e. time) and gaussian arrays
-    // let mut r = Array1::<f64>::zeros(bins);
-    // let mut g = Array1::<f64>::zeros(bins);
-    let mut r = vec![0.0; bins];
-    let mut g = vec![0.0; bins];
+/// * `Ok(Vec<f64>)`: The normalized Gaussian distribution.
+/// * `Err(ImgalError)`: If sigma or range is <= 0.0. If bins is < 2.
+pub fn gaussian(sigma: f64, bins: usize, range: f64, center: f64) -> Result<Vec<f64>, ImgalError> {
+    // check if the sigma parameter is valid
+    validate::positive("sigma", sigma)?;
 
-    // calculate range data
-    let width = range / (bins as f64 - 1.0);
-    r.iter_mut().enumerate().for_each(|(i, v)| {
-        *v = i as f64 * width;
-    });
-
-    // calculate the gaussian distrubtion
+    // sample the gaussian distribution over the range
     let sigma_sq_2 = 2.0 * sigma.powi(2);
-    g.iter_mut().enumerate().for_each(|(i, v)| {
-        *v = (-((r[i] - center).powi(2)) / sigma_sq_2).exp();
-    });
-
-    // normalize the gaussian distribution
-    let g_sum = sum(&g);
-    g.iter_mut().for_each(|v| {
-        *v /= g_sum;
-    });
-    g
+    sample_normalized(bins, range, |x| {
+        (-((x - center).powi(2)) / sigma_sq_2).exp()
+    })
 }
@@ -0,0 +1,54 @@
+use crate::distribution::sample_normalized;
+use crate::error::{ImgalError, validate};
+
+/// Generate a normalized log-normal distribution over a specified range.
+///
+/// # Description
+///
+/// This function creates a discrete log-normal distribution by sampling the
+/// continuous log-normal probability density function at evenly spaced points
+/// across a given range. The resulting distribution is normalized so that all
+/// values sum to 1.0. The function implements the log-normal probability
+/// density function:
+///
+/// ```text
+/// f(x) = (1 / x) × exp(-(ln(x / m))² / (2σ²))  for x > 0
+/// f(x) = 0                                    for x <= 0
+/// ```
+/// Where:
+/// - `x` is the position along the range.
+/// - `m` is the median (_i.e._ exp(μ) of the underlying normal distribution).
+/// - `σ` is the sigma (standard deviation of ln(x)).
+///
+/// # Arguments
+///
+/// * `sigma`: The standard deviation of the logarithm of the distribution
+///   (_i.e._ the skew and width). Must be > 0.0.
+/// * `bins`: The number of discrete points to sample the log-normal
+///   distribution. Must be >= 2.
+/// * `range`: The total width of the sampling range. Must be > 0.0.
+/// * `median`: The median of the log-normal distribution. Must be > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The normalized log-normal distribution.
+/// * `Err(ImgalError)`: If sigma, median or range is <= 0.0. If bins is < 2.
+pub fn log_normal(
+    sigma: f64,
+    bins: usize,
+    range: f64,
+    median: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the sigma and median parameters are valid
+    validate::positive("sigma", sigma)?;
+    validate::positive("median", median)?;
+
+    let sigma_sq_2 = 2.0 * sigma.powi(2);
+    sample_normalized(bins, range, |x| {
+        if x > 0.0 {
+            (-(x / median).ln().powi(2) / sigma_sq_2).exp() / x
+        } else {
+            0.0
+        }
+    })
+}
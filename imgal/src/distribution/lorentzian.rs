@@ -0,0 +1,51 @@
+use crate::distribution::sample_normalized;
+use crate::error::{ImgalError, validate};
+
+/// Generate a normalized Lorentzian (Cauchy) distribution over a specified
+/// range.
+///
+/// # Description
+///
+/// This function creates a discrete Lorentzian distribution by sampling the
+/// continuous Lorentzian (Cauchy) probability density function at evenly
+/// spaced points across a given range. The resulting distribution is
+/// normalized so that all values sum to 1.0. The function implements the
+/// Lorentzian probability density function:
+///
+/// ```text
+/// f(x) = 1 / (1 + ((x - x₀) / γ)²)
+/// ```
+/// Where:
+/// - `x` is the position along the range.
+/// - `x₀` is the center (peak).
+/// - `γ` is the gamma (half width at half maximum).
+///
+/// The Lorentzian has much heavier tails than a Gaussian of the same width,
+/// _e.g._ the homogeneously broadened line shape of an emission spectrum.
+///
+/// # Arguments
+///
+/// * `gamma`: The half width at half maximum of the Lorentzian distribution.
+///   Must be > 0.0.
+/// * `bins`: The number of discrete points to sample the Lorentzian
+///   distribution. Must be >= 2.
+/// * `range`: The total width of the sampling range. Must be > 0.0.
+/// * `center`: The center of the Lorentzian distribution (_i.e._ the peak).
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The normalized Lorentzian distribution.
+/// * `Err(ImgalError)`: If gamma or range is <= 0.0. If bins is < 2.
+pub fn lorentzian(
+    gamma: f64,
+    bins: usize,
+    range: f64,
+    center: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the gamma parameter is valid
+    validate::positive("gamma", gamma)?;
+
+    sample_normalized(bins, range, |x| {
+        1.0 / (1.0 + ((x - center) / gamma).powi(2))
+    })
+}
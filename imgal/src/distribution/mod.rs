@@ -1,3 +1,42 @@
-//! Adjustable distribution functions.
+//! Adjustable distribution and random sampling functions.
+pub mod exponential;
+pub use exponential::exponential;
+pub mod gamma;
+pub use gamma::gamma;
 pub mod gaussian;
 pub use gaussian::gaussian;
+pub mod log_normal;
+pub use log_normal::log_normal;
+pub mod lorentzian;
+pub use lorentzian::lorentzian;
+pub mod normal;
+pub use normal::{inverse_normal_cdf, normal_cdf};
+pub mod sample;
+pub use sample::{sample_exponential, sample_gamma, sample_gaussian, sample_poisson};
+
+use crate::error::{ImgalError, validate};
+
+/// Sample a distribution function at evenly spaced points over [0, range] and
+/// normalize the samples so that they sum to 1.0.
+fn sample_normalized<F>(bins: usize, range: f64, f: F) -> Result<Vec<f64>, ImgalError>
+where
+    F: Fn(f64) -> f64,
+{
+    // check if the bins and range parameters are valid
+    validate::at_least("bins", bins as f64, 2.0)?;
+    validate::positive("range", range)?;
+
+    // sample and normalize the distribution
+    let width = range / (bins as f64 - 1.0);
+    let mut d: Vec<f64> = (0..bins).map(|i| f(i as f64 * width)).collect();
+    let d_sum: f64 = d.iter().sum();
+    if !(d_sum.is_finite() && d_sum > 0.0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "range",
+            reason: "must contain a part of the distribution with a finite total > 0.0".to_string(),
+        });
+    }
+    d.iter_mut().for_each(|v| *v /= d_sum);
+
+    Ok(d)
+}
@@ -0,0 +1,130 @@
+use crate::error::ImgalError;
+
+/// Coefficients of the central region rational approximation numerator.
+const A: [f64; 6] = [
+    -3.969683028665376e1,
+    2.209460984245205e2,
+    -2.759285104469687e2,
+    1.38357751867269e2,
+    -3.066479806614716e1,
+    2.506628277459239,
+];
+
+/// Coefficients of the central region rational approximation denominator.
+const B: [f64; 5] = [
+    -5.447609879822406e1,
+    1.615858368580409e2,
+    -1.556989798598866e2,
+    6.680131188771972e1,
+    -1.328068155288572e1,
+];
+
+/// Coefficients of the tail region rational approximation numerator.
+const C: [f64; 6] = [
+    -7.784894002430293e-3,
+    -3.223964580411365e-1,
+    -2.400758277161838,
+    -2.549732539343734,
+    4.374664141464968,
+    2.938163982698783,
+];
+
+/// Coefficients of the tail region rational approximation denominator.
+const D: [f64; 4] = [
+    7.784695709041462e-3,
+    3.224671290700398e-1,
+    2.445134137142996,
+    3.754408661907416,
+];
+
+/// Coefficients of the complementary error function approximation exponent.
+const E: [f64; 10] = [
+    -1.26551223,
+    1.00002368,
+    0.37409196,
+    0.09678418,
+    -0.18628806,
+    0.27886807,
+    -1.13520398,
+    1.48851587,
+    -0.82215223,
+    0.17087277,
+];
+
+/// Compute the inverse of the standard normal cumulative distribution function.
+///
+/// # Description
+///
+/// This function computes the quantile `z` of the standard normal distribution
+/// (mean 0.0, standard deviation 1.0) with `P(Z <= z) = p`, _e.g._ the
+/// critical _z-score_ of a significance test. The quantile is computed with
+/// Acklam's rational approximation, which has a relative error below 1.2e-9.
+///
+/// # Arguments
+///
+/// * `p`: The cumulative probability. Must be in (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The standard normal quantile of `p`.
+/// * `Err(ImgalError)`: If p is not in (0, 1).
+pub fn inverse_normal_cdf(p: f64) -> Result<f64, ImgalError> {
+    // check if the probability parameter is valid
+    if !(p > 0.0 && p < 1.0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "p",
+            reason: format!("must be in (0, 1) but got {}", p),
+        });
+    }
+
+    // evaluate the rational approximation of the lower tail, central region
+    // or upper tail
+    let p_low = 0.02425;
+    let z = if p < p_low {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - p_low {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    Ok(z)
+}
+
+/// Compute the standard normal cumulative distribution function.
+///
+/// # Description
+///
+/// This function computes the probability `P(Z <= z)` of the standard normal
+/// distribution (mean 0.0, standard deviation 1.0):
+///
+/// ```text
+/// Φ(z) = erfc(-z / √2) / 2
+/// ```
+///
+/// The complementary error function is computed with a Chebyshev fitted
+/// approximation, which has a relative error below 1.2e-7.
+///
+/// # Arguments
+///
+/// * `z`: The standard normal quantile (_i.e._ _z-score_).
+///
+/// # Returns
+///
+/// * `f64`: The cumulative probability of `z`.
+pub fn normal_cdf(z: f64) -> f64 {
+    let x = -z / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let poly = E.iter().rev().fold(0.0, |acc, &c| acc * t + c);
+    let erfc = t * (-x * x + poly).exp();
+    let erfc = if x >= 0.0 { erfc } else { 2.0 - erfc };
+
+    erfc / 2.0
+}
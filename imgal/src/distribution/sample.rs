@@ -0,0 +1,196 @@
+use rand::Rng;
+use rand_distr::{Distribution, Exp, Gamma, Normal, Poisson};
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::simulation::rng::{LaneRng, Seed};
+
+/// The number of samples drawn by each generator.
+const CHUNK: usize = 4096;
+
+/// Draw random samples from an exponential distribution.
+///
+/// # Description
+///
+/// This function draws `n` independent samples from the exponential
+/// distribution with mean `tau` (_e.g._ photon arrival times of a
+/// monoexponential decay). The samples are drawn in parallel in chunks of 4096
+/// samples, each chunk with its own generator, see `seed`.
+///
+/// # Arguments
+///
+/// * `n`: The number of samples.
+/// * `tau`: The mean (decay constant) of the exponential distribution. Must be
+///   > 0.0.
+/// * `seed`: Pseudorandom number generator seed, the chunk `i` is seeded with
+///   `seed + i`. If `None`, a thread local generator is used. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context, each chunk is then sampled
+///   with its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The random samples.
+/// * `Err(ImgalError)`: If tau is <= 0.0.
+pub fn sample_exponential(
+    n: usize,
+    tau: f64,
+    seed: impl Into<Seed>,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the tau parameter is valid
+    validate::positive("tau", tau)?;
+    let dist = Exp::new(1.0 / tau).unwrap();
+
+    Ok(draw(n, seed.into(), |rng| dist.sample(rng)))
+}
+
+/// Draw random samples from a gamma distribution.
+///
+/// # Description
+///
+/// This function draws `n` independent samples from the gamma distribution
+/// with the given shape (k) and scale (θ), the mean is kθ and the variance is
+/// kθ². The samples are drawn in parallel in chunks of 4096 samples, each chunk
+/// with its own generator, see `seed`.
+///
+/// # Arguments
+///
+/// * `n`: The number of samples.
+/// * `shape`: The shape of the gamma distribution. Must be > 0.0.
+/// * `scale`: The scale of the gamma distribution. Must be > 0.0.
+/// * `seed`: Pseudorandom number generator seed, the chunk `i` is seeded with
+///   `seed + i`. If `None`, a thread local generator is used. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context, each chunk is then sampled
+///   with its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The random samples.
+/// * `Err(ImgalError)`: If shape or scale is <= 0.0.
+pub fn sample_gamma(
+    n: usize,
+    shape: f64,
+    scale: f64,
+    seed: impl Into<Seed>,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the shape and scale parameters are valid
+    validate::positive("shape", shape)?;
+    validate::positive("scale", scale)?;
+    let dist = Gamma::new(shape, scale).unwrap();
+
+    Ok(draw(n, seed.into(), |rng| dist.sample(rng)))
+}
+
+/// Draw random samples from a Gaussian distribution.
+///
+/// # Description
+///
+/// This function draws `n` independent samples from the Gaussian (normal)
+/// distribution with the given mean and standard deviation (_e.g._ camera read
+/// noise). The samples are drawn in parallel in chunks of 4096 samples, each
+/// chunk with its own generator, see `seed`.
+///
+/// # Arguments
+///
+/// * `n`: The number of samples.
+/// * `mean`: The mean (center) of the Gaussian distribution.
+/// * `sigma`: The standard deviation of the Gaussian distribution. Must be
+///   >= 0.0.
+/// * `seed`: Pseudorandom number generator seed, the chunk `i` is seeded with
+///   `seed + i`. If `None`, a thread local generator is used. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context, each chunk is then sampled
+///   with its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The random samples.
+/// * `Err(ImgalError)`: If mean is not finite. If sigma is < 0.0.
+pub fn sample_gaussian(
+    n: usize,
+    mean: f64,
+    sigma: f64,
+    seed: impl Into<Seed>,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the mean and sigma parameters are valid
+    validate::in_range("mean", mean, f64::MIN, f64::MAX)?;
+    validate::at_least("sigma", sigma, 0.0)?;
+    let dist = Normal::new(mean, sigma).unwrap();
+
+    Ok(draw(n, seed.into(), |rng| dist.sample(rng)))
+}
+
+/// Draw random samples from a Poisson distribution.
+///
+/// # Description
+///
+/// This function draws `n` independent samples from the Poisson distribution
+/// with mean `lambda` (_e.g._ photon counts). The samples are drawn in
+/// parallel in chunks of 4096 samples, each chunk with its own generator, see
+/// `seed`.
+///
+/// # Arguments
+///
+/// * `n`: The number of samples.
+/// * `lambda`: The mean of the Poisson distribution. Must be within
+///   [0.0, 1.844e19], a lambda of 0.0 only draws 0.0.
+/// * `seed`: Pseudorandom number generator seed, the chunk `i` is seeded with
+///   `seed + i`. If `None`, a thread local generator is used. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context, each chunk is then sampled
+///   with its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The random samples.
+/// * `Err(ImgalError)`: If lambda is outside of [0.0, 1.844e19].
+pub fn sample_poisson(
+    n: usize,
+    lambda: f64,
+    seed: impl Into<Seed>,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the lambda parameter is valid
+    validate::in_range("lambda", lambda, 0.0, Poisson::<f64>::MAX_LAMBDA)?;
+
+    if lambda == 0.0 {
+        return Ok(vec![0.0; n]);
+    }
+    let dist = poisson_distribution(lambda)?;
+
+    Ok(draw(n, seed.into(), |rng| dist.sample(rng)))
+}
+
+/// Draw a Poisson distributed value, a lambda <= 0.0 draws 0.0.
+pub(crate) fn draw_poisson<R: Rng>(lambda: f64, rng: &mut R) -> Result<f64, ImgalError> {
+    if lambda > 0.0 {
+        Ok(poisson_distribution(lambda)?.sample(rng))
+    } else {
+        Ok(0.0)
+    }
+}
+
+/// Create a Poisson distribution with mean `lambda`.
+fn poisson_distribution(lambda: f64) -> Result<Poisson<f64>, ImgalError> {
+    Poisson::new(lambda).map_err(|_| ImgalError::InvalidParameter {
+        param_name: "lambda",
+        reason: format!(
+            "must be a finite value in [0, {}] but got {}",
+            Poisson::<f64>::MAX_LAMBDA,
+            lambda
+        ),
+    })
+}
+
+/// Draw samples in parallel chunks, each chunk with the generator of its lane.
+fn draw<F>(n: usize, seed: Seed, sampler: F) -> Vec<f64>
+where
+    F: Fn(&mut LaneRng) -> f64 + Sync,
+{
+    let mut samples = vec![0.0; n];
+    samples
+        .par_chunks_mut(CHUNK)
+        .enumerate()
+        .for_each(|(i, chunk)| {
+            let mut rng = seed.lane_offset(i);
+            chunk.iter_mut().for_each(|v| *v = sampler(&mut rng));
+        });
+
+    samples
+}
@@ -1,8 +1,9 @@
 use std::error;
 use std::fmt;
 
+/// The error type returned by all fallible imgal functions.
 #[derive(Debug, Clone, PartialEq)]
-pub enum ArrayError {
+pub enum ImgalError {
     InvalidArrayGeneric {
         msg: &'static str,
     },
@@ -22,6 +23,10 @@ pub enum ArrayError {
         axis_idx: usize,
         dim_len: usize,
     },
+    InvalidParameter {
+        param_name: &'static str,
+        reason: String,
+    },
     InvalidSum {
         expected: f64,
         got: f64,
@@ -34,47 +39,54 @@ pub enum ArrayError {
         shape_a: Vec<usize>,
         shape_b: Vec<usize>,
     },
+    NumericFailure {
+        operation: &'static str,
+        reason: String,
+    },
 }
 
 // "Dimension size {} of axis {} is out of bounds for dimension size {}."
-impl fmt::Display for ArrayError {
+impl fmt::Display for ImgalError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ArrayError::InvalidArrayGeneric { msg } => {
+            ImgalError::InvalidArrayGeneric { msg } => {
                 write!(f, "{}", msg)
             }
-            ArrayError::InvalidArrayParameterValueEqual { param_name, value } => {
+            ImgalError::InvalidArrayParameterValueEqual { param_name, value } => {
                 write!(
                     f,
                     "Invalid array parameter value, the parameter {} can not equal {}.",
                     param_name, value
                 )
             }
-            ArrayError::InvalidArrayParameterValueGreater { param_name, value } => {
+            ImgalError::InvalidArrayParameterValueGreater { param_name, value } => {
                 write!(
                     f,
                     "Invalid array parameter value, the parameter {} can not be greater than {}.",
                     param_name, value
                 )
             }
-            ArrayError::InvalidArrayParameterValueLess { param_name, value } => {
+            ImgalError::InvalidArrayParameterValueLess { param_name, value } => {
                 write!(
                     f,
                     "Invalid array parameter value, the parameter {} can not be less than {}.",
                     param_name, value
                 )
             }
-            ArrayError::InvalidAxis { axis_idx, dim_len } => {
+            ImgalError::InvalidAxis { axis_idx, dim_len } => {
                 write!(
                     f,
                     "Invalid axis, axis {} is out of bounds for dimension length {}.",
                     axis_idx, dim_len
                 )
             }
-            ArrayError::InvalidSum { expected, got } => {
+            ImgalError::InvalidParameter { param_name, reason } => {
+                write!(f, "Invalid parameter {}, {}.", param_name, reason)
+            }
+            ImgalError::InvalidSum { expected, got } => {
                 write!(f, "Invalid sum, expected {} but got {}.", expected, got)
             }
-            ArrayError::MismatchedArrayLengths {
+            ImgalError::MismatchedArrayLengths {
                 a_arr_len,
                 b_arr_len,
             } => {
@@ -84,15 +96,18 @@ impl fmt::Display for ArrayError {
                     a_arr_len, b_arr_len
                 )
             }
-            ArrayError::MismatchedArrayShapes { shape_a, shape_b } => {
+            ImgalError::MismatchedArrayShapes { shape_a, shape_b } => {
                 write!(
                     f,
                     "Mismatched array shapes, {:?} and {:?}, do not match.",
                     shape_a, shape_b
                 )
             }
+            ImgalError::NumericFailure { operation, reason } => {
+                write!(f, "Numeric failure in {}, {}.", operation, reason)
+            }
         }
     }
 }
 
-impl error::Error for ArrayError {}
+impl error::Error for ImgalError {}
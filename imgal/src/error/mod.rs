@@ -1,3 +1,6 @@
 //! Internal error module.
-pub mod array;
-pub use array::ArrayError;
+pub mod imgal_error;
+pub use imgal_error::ImgalError;
+pub mod validate;
+pub mod warning;
+pub use warning::Warning;
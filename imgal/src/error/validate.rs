@@ -0,0 +1,146 @@
+use crate::error::ImgalError;
+
+/// Validate that a value is at least a minimum value.
+///
+/// # Arguments
+///
+/// * `param_name`: The name of the parameter being validated.
+/// * `value`: The parameter value.
+/// * `min`: The minimum allowed value (inclusive).
+///
+/// # Returns
+///
+/// * `Ok(())`: If `value` is finite and greater than or equal to `min`.
+/// * `Err(ImgalError)`: If `value` is less than `min`, NaN or infinite.
+pub fn at_least(param_name: &'static str, value: f64, min: f64) -> Result<(), ImgalError> {
+    if !value.is_finite() || value < min {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: format!("must be a finite value >= {} but got {}", min, value),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that an axis index is in bounds for the number of dimensions.
+///
+/// # Arguments
+///
+/// * `axis`: The axis index.
+/// * `ndim`: The number of dimensions of the array.
+///
+/// # Returns
+///
+/// * `Ok(())`: If `axis` is less than `ndim`.
+/// * `Err(ImgalError)`: If `axis` is greater than or equal to `ndim`.
+pub fn axis(axis: usize, ndim: usize) -> Result<(), ImgalError> {
+    if axis >= ndim {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: axis,
+            dim_len: ndim,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that a value is within an inclusive range.
+///
+/// # Arguments
+///
+/// * `param_name`: The name of the parameter being validated.
+/// * `value`: The parameter value.
+/// * `min`: The minimum allowed value (inclusive).
+/// * `max`: The maximum allowed value (inclusive).
+///
+/// # Returns
+///
+/// * `Ok(())`: If `value` is finite and within [`min`, `max`].
+/// * `Err(ImgalError)`: If `value` is outside of [`min`, `max`], NaN or
+///   infinite.
+pub fn in_range(
+    param_name: &'static str,
+    value: f64,
+    min: f64,
+    max: f64,
+) -> Result<(), ImgalError> {
+    if !value.is_finite() || value < min || value > max {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: format!(
+                "must be a finite value in [{}, {}] but got {}",
+                min, max, value
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that a value is strictly positive.
+///
+/// # Arguments
+///
+/// * `param_name`: The name of the parameter being validated.
+/// * `value`: The parameter value.
+///
+/// # Returns
+///
+/// * `Ok(())`: If `value` is finite and greater than 0.0.
+/// * `Err(ImgalError)`: If `value` is less than or equal to 0.0, NaN or
+///   infinite.
+pub fn positive(param_name: &'static str, value: f64) -> Result<(), ImgalError> {
+    if !value.is_finite() || value <= 0.0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: format!("must be a finite value > 0 but got {}", value),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that two arrays have the same length.
+///
+/// # Arguments
+///
+/// * `a_arr_len`: The length of the first array.
+/// * `b_arr_len`: The length of the second array.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the array lengths match.
+/// * `Err(ImgalError)`: If the array lengths do not match.
+pub fn same_length(a_arr_len: usize, b_arr_len: usize) -> Result<(), ImgalError> {
+    if a_arr_len != b_arr_len {
+        return Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len,
+            b_arr_len,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that two arrays have the same shape.
+///
+/// # Arguments
+///
+/// * `shape_a`: The shape of the first array.
+/// * `shape_b`: The shape of the second array.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the array shapes match.
+/// * `Err(ImgalError)`: If the array shapes do not match.
+pub fn same_shape(shape_a: &[usize], shape_b: &[usize]) -> Result<(), ImgalError> {
+    if shape_a != shape_b {
+        return Err(ImgalError::MismatchedArrayShapes {
+            shape_a: shape_a.to_vec(),
+            shape_b: shape_b.to_vec(),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+/// Soft failures that do not prevent a function from returning a result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    EmptyMask,
+    EmptyInput {
+        param_name: &'static str,
+    },
+    FractionsNormalized {
+        got: f64,
+    },
+    NonFiniteOutput {
+        count: usize,
+    },
+    NotConverged {
+        operation: &'static str,
+        count: usize,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::EmptyMask => {
+                write!(
+                    f,
+                    "The mask excludes all pixels, the output contains no data."
+                )
+            }
+            Warning::EmptyInput { param_name } => {
+                write!(
+                    f,
+                    "The parameter {} is empty, returning a default value.",
+                    param_name
+                )
+            }
+            Warning::FractionsNormalized { got } => {
+                write!(
+                    f,
+                    "Fractions sum to {} instead of 1.0, the fractions have been normalized.",
+                    got
+                )
+            }
+            Warning::NonFiniteOutput { count } => {
+                write!(
+                    f,
+                    "{} output value(s) are NaN or infinite, check for zero intensity data.",
+                    count
+                )
+            }
+            Warning::NotConverged { operation, count } => {
+                write!(
+                    f,
+                    "The {} reached its iteration limit without converging {} time(s), the result may be inaccurate.",
+                    operation, count
+                )
+            }
+        }
+    }
+}
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<Warning>> = const { RefCell::new(Vec::new()) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Run a function and collect the warnings it emits.
+///
+/// # Description
+///
+/// This function runs `f` and returns its output with the warnings emitted
+/// during the call. Warnings are recorded on the calling thread, functions
+/// that compute in parallel report their warnings from the calling thread once
+/// the parallel work is complete.
+///
+/// Calls can be nested. Warnings that are pending when `f` starts are kept,
+/// and the warnings collected by a nested call are also reported to the
+/// enclosing call, so no warning is lost.
+///
+/// # Arguments
+///
+/// * `f`: The function to run.
+///
+/// # Returns
+///
+/// * `(R, Vec<Warning>)`: The output of `f` and the warnings emitted during
+///   the call, in the order they were emitted.
+pub fn collect<F, R>(f: F) -> (R, Vec<Warning>)
+where
+    F: FnOnce() -> R,
+{
+    let (output, warnings) = isolate(f);
+    if DEPTH.with(|d| d.get()) > 0 {
+        WARNINGS.with(|w| w.borrow_mut().extend(warnings.iter().cloned()));
+    }
+
+    (output, warnings)
+}
+
+/// Run a function and collect the warnings it emits without reporting them to
+/// an enclosing `collect`, the caller is responsible for re-emitting them.
+pub(crate) fn isolate<F, R>(f: F) -> (R, Vec<Warning>)
+where
+    F: FnOnce() -> R,
+{
+    // restores the pending warnings and depth, also if f panics
+    struct Restore(Vec<Warning>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let pending = std::mem::take(&mut self.0);
+            WARNINGS.with(|w| {
+                let mut w = w.borrow_mut();
+                let emitted = std::mem::replace(&mut *w, pending);
+                w.extend(emitted);
+            });
+            DEPTH.with(|d| d.set(d.get() - 1));
+        }
+    }
+
+    let _restore = Restore(take());
+    DEPTH.with(|d| d.set(d.get() + 1));
+    let output = f();
+
+    (output, take())
+}
+
+/// Take all pending warnings on the calling thread.
+///
+/// # Description
+///
+/// Warnings are only pending inside a `collect`, this function drains the
+/// warnings emitted so far in the innermost `collect` on the calling thread.
+/// Taken warnings are not returned by that `collect`.
+///
+/// # Returns
+///
+/// * `Vec<Warning>`: The pending warnings, in the order they were emitted. The
+///   pending warnings are cleared.
+pub fn take() -> Vec<Warning> {
+    WARNINGS.with(|w| std::mem::take(&mut *w.borrow_mut()))
+}
+
+/// Emit a warning on the calling thread.
+///
+/// # Description
+///
+/// Warnings are recorded only while a `collect` is running on the calling
+/// thread and dropped otherwise, so callers that do not collect warnings do not
+/// accumulate them. Warnings emitted from the parallel work on rayon worker
+/// threads are dropped unless the worker itself runs a `collect`, imgal
+/// functions report their warnings from the calling thread once the parallel
+/// work is complete.
+///
+/// # Arguments
+///
+/// * `warning`: The warning to emit.
+pub fn warn(warning: Warning) {
+    if DEPTH.with(|d| d.get()) > 0 {
+        WARNINGS.with(|w| w.borrow_mut().push(warning));
+    }
+}
@@ -0,0 +1,287 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array2, ArrayD, ArrayViewD, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::filter::{gaussian, log};
+use crate::morphology::grayscale::dilation;
+use crate::traits::numeric::ToFloat64;
+
+/// Detect bright blobs in a 2 or 3-dimensional image with the scale
+/// normalized Laplacian of Gaussian (LoG).
+///
+/// # Description
+///
+/// This function filters the image with the negated, scale normalized LoG
+/// (`-σ² × LoG`, see `filter::log`) at `num_sigma` linearly spaced scales
+/// from `min_sigma` to `max_sigma`. Blobs are the local maxima of the scale
+/// space (_i.e._ of the 3ⁿ⁺¹ neighborhood across space and adjacent scales)
+/// above the threshold. The radius of a blob detected at scale σ is `√n × σ`,
+/// where n is the number of image dimensions.
+///
+/// Overlapping blobs are pruned, visiting the blobs from the strongest to the
+/// weakest response, a blob is dropped if its intersection with a stronger
+/// kept blob exceeds the `overlap` fraction of the area (or volume) of the
+/// smaller of the two.
+///
+/// # Arguments
+///
+/// * `data`: The input 2 or 3-dimensional image.
+/// * `min_sigma`: The smallest scale, in pixels, default = 1.0. Must be > 0.0.
+/// * `max_sigma`: The largest scale, in pixels, default = 50.0. Must be >=
+///   `min_sigma`.
+/// * `num_sigma`: The number of scales, default = 10. Must be >= 1.
+/// * `threshold`: The minimum scale normalized response of a blob, default =
+///   0.2.
+/// * `overlap`: The maximum overlap fraction of two kept blobs, default = 0.5.
+///   Must be in [0.0, 1.0].
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The blobs with shape (blobs, ndim + 1), where each row
+///   holds the blob center coordinates followed by the blob radius, in
+///   descending order of response.
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional. If min_sigma is
+///   <= 0.0, max_sigma is < min_sigma or num_sigma is 0. If overlap is not in
+///   [0.0, 1.0].
+///
+/// # Reference
+///
+/// <https://doi.org/10.1023/A:1008045108935>
+pub fn blob_log<T>(
+    data: ArrayViewD<T>,
+    min_sigma: Option<f64>,
+    max_sigma: Option<f64>,
+    num_sigma: Option<usize>,
+    threshold: Option<f64>,
+    overlap: Option<f64>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let min_sigma = min_sigma.unwrap_or(1.0);
+    let max_sigma = max_sigma.unwrap_or(50.0);
+    let num_sigma = num_sigma.unwrap_or(10);
+    let threshold = threshold.unwrap_or(0.2);
+    let overlap = overlap.unwrap_or(0.5);
+
+    // check if the data, sigma and overlap parameters are valid
+    validate_blob_parameters(data.ndim(), min_sigma, max_sigma, overlap)?;
+    if num_sigma == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "num_sigma",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+
+    // filter the image at each scale
+    let ndim = data.ndim();
+    let sigmas: Vec<f64> = if num_sigma == 1 {
+        vec![min_sigma]
+    } else {
+        (0..num_sigma)
+            .map(|i| min_sigma + (max_sigma - min_sigma) * i as f64 / (num_sigma - 1) as f64)
+            .collect()
+    };
+    let stack = sigmas
+        .iter()
+        .map(|&s| {
+            let mut response = log(data.view(), &vec![s; ndim], None)?;
+            response.par_mapv_inplace(|v| -s * s * v);
+            Ok(response)
+        })
+        .collect::<Result<Vec<ArrayD<f64>>, ImgalError>>()?;
+
+    Ok(detect(&stack, &sigmas, threshold, overlap, ndim))
+}
+
+/// Detect bright blobs in a 2 or 3-dimensional image with the scale
+/// normalized difference of Gaussians (DoG).
+///
+/// # Description
+///
+/// This function approximates `blob_log` with the differences of Gaussian
+/// blurs at the geometric scales `σᵢ = min_sigma × sigma_ratioⁱ`, up to the
+/// first scale >= `max_sigma`. The response at scale σᵢ is:
+///
+/// ```text
+/// DoG = (G_σᵢ ∗ I - G_σᵢ₊₁ ∗ I) / (sigma_ratio - 1)
+/// ```
+///
+/// which approximates the negated, scale normalized LoG (`-σ² × LoG`). Blobs
+/// are the local maxima of the scale space above the threshold and
+/// overlapping blobs are pruned, see `blob_log`. The radius of a blob detected
+/// at scale σ is `√n × σ`, where n is the number of image dimensions.
+///
+/// # Arguments
+///
+/// * `data`: The input 2 or 3-dimensional image.
+/// * `min_sigma`: The smallest scale, in pixels, default = 1.0. Must be > 0.0.
+/// * `max_sigma`: The largest scale, in pixels, default = 50.0. Must be >=
+///   `min_sigma`.
+/// * `sigma_ratio`: The ratio of consecutive scales, default = 1.6. Must be
+///   > 1.0.
+/// * `threshold`: The minimum scale normalized response of a blob, default =
+///   0.5.
+/// * `overlap`: The maximum overlap fraction of two kept blobs, default = 0.5.
+///   Must be in [0.0, 1.0].
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The blobs with shape (blobs, ndim + 1), where each row
+///   holds the blob center coordinates followed by the blob radius, in
+///   descending order of response.
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional. If min_sigma is
+///   <= 0.0, max_sigma is < min_sigma or sigma_ratio is <= 1.0. If overlap is
+///   not in [0.0, 1.0].
+pub fn blob_dog<T>(
+    data: ArrayViewD<T>,
+    min_sigma: Option<f64>,
+    max_sigma: Option<f64>,
+    sigma_ratio: Option<f64>,
+    threshold: Option<f64>,
+    overlap: Option<f64>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let min_sigma = min_sigma.unwrap_or(1.0);
+    let max_sigma = max_sigma.unwrap_or(50.0);
+    let sigma_ratio = sigma_ratio.unwrap_or(1.6);
+    let threshold = threshold.unwrap_or(0.5);
+    let overlap = overlap.unwrap_or(0.5);
+
+    // check if the data, sigma and overlap parameters are valid
+    validate_blob_parameters(data.ndim(), min_sigma, max_sigma, overlap)?;
+    if !(sigma_ratio > 1.0 && sigma_ratio.is_finite()) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "sigma_ratio",
+            reason: format!("must be finite and > 1.0 but got {}", sigma_ratio),
+        });
+    }
+
+    // blur the image at each scale and take the scaled differences
+    let ndim = data.ndim();
+    let k = ((max_sigma / min_sigma).ln() / sigma_ratio.ln()).ceil() as usize + 1;
+    let sigmas: Vec<f64> = (0..=k)
+        .map(|i| min_sigma * sigma_ratio.powi(i as i32))
+        .collect();
+    let blurred = sigmas
+        .iter()
+        .map(|&s| gaussian(data.view(), &vec![s; ndim], None, None))
+        .collect::<Result<Vec<ArrayD<f64>>, ImgalError>>()?;
+    let scale = 1.0 / (sigma_ratio - 1.0);
+    let stack: Vec<ArrayD<f64>> = blurred
+        .windows(2)
+        .map(|w| {
+            let mut response = w[0].clone();
+            Zip::from(&mut response)
+                .and(&w[1])
+                .par_for_each(|r, &b| *r = (*r - b) * scale);
+            response
+        })
+        .collect();
+
+    Ok(detect(&stack, &sigmas[..k], threshold, overlap, ndim))
+}
+
+/// Find the scale space maxima of a stack of responses and prune the
+/// overlapping blobs.
+fn detect(
+    stack: &[ArrayD<f64>],
+    sigmas: &[f64],
+    threshold: f64,
+    overlap: f64,
+    ndim: usize,
+) -> Array2<f64> {
+    // the maximum of the 3ⁿ spatial neighborhood at each scale
+    let local_max: Vec<ArrayD<f64>> = stack
+        .iter()
+        .map(|r| dilation(r.view(), &vec![1; ndim]).unwrap())
+        .collect();
+
+    // the maxima of the adjacent scales above the threshold
+    let mut candidates: Vec<(f64, Vec<f64>, f64)> = Vec::new();
+    (0..stack.len()).for_each(|s| {
+        let lo = s.saturating_sub(1);
+        let hi = (s + 1).min(stack.len() - 1);
+        stack[s].indexed_iter().for_each(|(idx, &v)| {
+            if v > threshold && (lo..=hi).all(|t| v >= local_max[t][&idx]) {
+                let center = (0..ndim).map(|ax| idx[ax] as f64).collect();
+                candidates.push((v, center, sigmas[s] * (ndim as f64).sqrt()));
+            }
+        });
+    });
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    // keep the strongest blobs that do not overlap a kept blob too much
+    let mut blobs: Vec<(Vec<f64>, f64)> = Vec::new();
+    candidates.into_iter().for_each(|(_, center, radius)| {
+        let dropped = blobs
+            .iter()
+            .any(|(c, r)| overlap_fraction(&center, radius, c, *r) > overlap);
+        if !dropped {
+            blobs.push((center, radius));
+        }
+    });
+
+    let n = blobs.len();
+    let values: Vec<f64> = blobs
+        .into_iter()
+        .flat_map(|(mut c, r)| {
+            c.push(r);
+            c
+        })
+        .collect();
+
+    Array2::from_shape_vec((n, ndim + 1), values).unwrap()
+}
+
+/// Compute the intersection of two circles (2D) or spheres (3D) as a fraction
+/// of the smaller one.
+fn overlap_fraction(c1: &[f64], r1: f64, c2: &[f64], r2: f64) -> f64 {
+    let d = c1
+        .iter()
+        .zip(c2.iter())
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    let r_min = r1.min(r2);
+    if d >= r1 + r2 {
+        return 0.0;
+    }
+    if d <= (r1 - r2).abs() {
+        return 1.0;
+    }
+    if c1.len() == 2 {
+        let a1 = r1 * r1 * ((d * d + r1 * r1 - r2 * r2) / (2.0 * d * r1)).acos();
+        let a2 = r2 * r2 * ((d * d + r2 * r2 - r1 * r1) / (2.0 * d * r2)).acos();
+        let a3 = 0.5 * ((-d + r1 + r2) * (d + r1 - r2) * (d - r1 + r2) * (d + r1 + r2)).sqrt();
+        (a1 + a2 - a3) / (PI * r_min * r_min)
+    } else {
+        let v =
+            PI * (r1 + r2 - d).powi(2) * (d * d + 2.0 * d * (r1 + r2) - 3.0 * (r1 - r2).powi(2))
+                / (12.0 * d);
+        v / (4.0 / 3.0 * PI * r_min.powi(3))
+    }
+}
+
+/// Check the shared blob detection parameters.
+fn validate_blob_parameters(
+    ndim: usize,
+    min_sigma: f64,
+    max_sigma: f64,
+    overlap: f64,
+) -> Result<(), ImgalError> {
+    if ndim != 2 && ndim != 3 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!("must be 2 or 3-dimensional but got {} dimensions", ndim),
+        });
+    }
+    validate::positive("min_sigma", min_sigma)?;
+    validate::at_least("max_sigma", max_sigma, min_sigma)?;
+    validate::in_range("overlap", overlap, 0.0, 1.0)
+}
@@ -0,0 +1,5 @@
+//! Feature detection functions.
+pub mod blob;
+pub use blob::{blob_dog, blob_log};
+pub mod peak;
+pub use peak::peak_local_max;
@@ -0,0 +1,119 @@
+use ndarray::{Array2, ArrayD, ArrayViewD, Dimension, Slice};
+
+use crate::error::warning::{self, Warning};
+use crate::error::{ImgalError, validate};
+use crate::morphology::grayscale::dilation;
+use crate::traits::numeric::ToFloat64;
+
+/// Find the local maxima (_i.e._ peaks) of an n-dimensional image.
+///
+/// # Description
+///
+/// This function finds the pixels that are the maximum of the box of radius
+/// `min_distance` centered on them and above the threshold:
+///
+/// ```text
+/// threshold = max(threshold_abs, threshold_rel × max(data))
+/// ```
+///
+/// The candidate peaks are then visited from the highest to the lowest value
+/// (ties in row-major order), and a candidate is dropped if it is within a
+/// Chebyshev distance of `min_distance` of a kept peak. Plateaus therefore give
+/// a single peak and the peaks are separated by more than `min_distance`
+/// pixels along at least one axis. A flat image has no peaks.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `min_distance`: The minimum distance between peaks, in pixels, default =
+///   1. Must be >= 1.
+/// * `threshold_abs`: The minimum peak value, default = the minimum of `data`.
+///   Peaks must be strictly above the threshold.
+/// * `threshold_rel`: The minimum peak value relative to the maximum of
+///   `data`, default = 0.0. Must be in [0.0, 1.0].
+/// * `mask`: An optional boolean mask, pixels set to `false` are not peaks.
+///   Must match the shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok(Array2<usize>)`: The peak coordinates with shape (peaks, ndim), in
+///   descending order of peak value.
+/// * `Err(ImgalError)`: If min_distance is < 1. If threshold_rel is not in
+///   [0.0, 1.0]. If the mask shape does not match the data shape.
+pub fn peak_local_max<T>(
+    data: ArrayViewD<T>,
+    min_distance: Option<usize>,
+    threshold_abs: Option<f64>,
+    threshold_rel: Option<f64>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<Array2<usize>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let min_distance = min_distance.unwrap_or(1);
+    let threshold_rel = threshold_rel.unwrap_or(0.0);
+
+    // check if the min_distance, threshold_rel and mask parameters are valid
+    if min_distance == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "min_distance",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+    validate::in_range("threshold_rel", threshold_rel, 0.0, 1.0)?;
+    if let Some(msk) = mask.as_ref() {
+        validate::same_shape(msk.shape(), data.shape())?;
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+    }
+    let ndim = data.ndim();
+    if data.is_empty() {
+        return Ok(Array2::zeros((0, ndim)));
+    }
+
+    // compute the threshold
+    let (min, max) = data
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            let v = v.to_f64();
+            (lo.min(v), hi.max(v))
+        });
+    let threshold = threshold_abs.unwrap_or(min).max(threshold_rel * max);
+
+    // the candidates are the maxima of their box above the threshold
+    let radius = vec![min_distance; ndim];
+    let local_max = dilation(data.view(), &radius)?;
+    let mut candidates: Vec<(f64, usize, Vec<usize>)> = Vec::new();
+    data.indexed_iter()
+        .zip(local_max.iter())
+        .for_each(|((idx, v), &m)| {
+            let v = v.to_f64();
+            let inside = mask.as_ref().is_none_or(|msk| msk[&idx]);
+            if inside && v == m && v > threshold {
+                candidates.push((v, candidates.len(), idx.slice().to_vec()));
+            }
+        });
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    // keep the highest candidates and block the box around each peak
+    let mut blocked = ArrayD::<bool>::default(data.raw_dim());
+    let mut peaks: Vec<usize> = Vec::new();
+    candidates.iter().for_each(|(_, _, idx)| {
+        if blocked[idx.as_slice()] {
+            return;
+        }
+        blocked
+            .slice_each_axis_mut(|ax| {
+                let c = idx[ax.axis.index()];
+                let lo = c.saturating_sub(min_distance);
+                let hi = (c + min_distance + 1).min(ax.len);
+                Slice::from(lo..hi)
+            })
+            .fill(true);
+        peaks.extend_from_slice(idx);
+    });
+
+    Ok(Array2::from_shape_vec((peaks.len() / ndim.max(1), ndim), peaks).unwrap())
+}
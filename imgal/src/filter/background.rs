@@ -0,0 +1,207 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, Zip};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::filter::spatial::validate_sigma;
+use crate::morphology::grayscale;
+use crate::traits::numeric::ToFloat64;
+
+/// Subtract the background of an n-dimensional image with a rolling ball.
+///
+/// # Description
+///
+/// This function estimates the background as the surface traced by the top of
+/// a ball rolled under the intensity landscape of the image (_i.e._ the
+/// grayscale opening), and subtracts it. Objects narrower than the ball are
+/// kept while smooth, uneven illumination is removed.
+///
+/// The ball is approximated by the paraboloid with the same curvature at its
+/// apex:
+///
+/// ```text
+/// z(x) = -Σᵢ xᵢ² / (2rᵢ)
+/// ```
+///
+/// which makes the opening separable into exact 1-dimensional lower envelope
+/// passes, with a cost that does not depend on the radius. The background
+/// never exceeds the image, so the output is >= 0.0.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `radius`: The ball radius along each axis, in pixels, larger than the
+///   objects to keep. Must have one value per axis. A radius of 0.0 does not
+///   roll the ball along that axis (_e.g._ the plane axis of a stack of 2D
+///   images).
+/// * `bright`: If `true`, bright objects on a dark background are kept,
+///   otherwise dark objects on a bright background (the ball is rolled over
+///   the image and the output is the background minus the image). Default =
+///   `true`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The background subtracted image with the same shape as
+///   `data`.
+/// * `Err(ImgalError)`: If radius does not have one value per axis. If a
+///   radius is < 0.0 or not finite.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/MC.1983.1654163>
+pub fn rolling_ball<T>(
+    data: ArrayViewD<T>,
+    radius: &[f64],
+    bright: Option<bool>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bright = bright.unwrap_or(true);
+
+    // check if the radius parameter is valid
+    validate_sigma("radius", radius, data.ndim())?;
+
+    // dark objects are bright objects of the negated image
+    let sign = if bright { 1.0 } else { -1.0 };
+    let input = data.mapv(|v| sign * v.to_f64());
+
+    // open the image, erode with the paraboloid then dilate with it
+    let mut background = input.clone();
+    let axes: Vec<(usize, f64)> = radius
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(_, r)| r > 0.0)
+        .collect();
+    axes.iter().for_each(|&(ax, r)| {
+        map_lanes(&mut background, ax, |ln| lower_envelope(ln, 0.5 / r));
+    });
+    axes.iter().for_each(|&(ax, r)| {
+        map_lanes(&mut background, ax, |ln| {
+            let neg: Vec<f64> = ln.iter().map(|v| -v).collect();
+            lower_envelope(&neg, 0.5 / r).iter().map(|v| -v).collect()
+        });
+    });
+
+    // clamp the rounding errors of the envelope passes
+    Zip::from(&mut background)
+        .and(&input)
+        .par_for_each(|b, &v| *b = (v - *b).max(0.0));
+
+    Ok(background)
+}
+
+/// Apply a morphological top-hat filter to an n-dimensional image.
+///
+/// # Description
+///
+/// This function subtracts the grayscale opening of the image with a flat
+/// box structuring element (_i.e._ the white top-hat), which keeps bright
+/// objects smaller than the box and removes the background. The opening and
+/// closing are computed with `morphology::opening` and `morphology::closing`,
+/// with a cost that does not depend on the box size. Large structuring
+/// elements are therefore as fast as small ones.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `radius`: The box radius along each axis, in pixels, the box has a side
+///   length of 2 × radius + 1. Must have one value per axis. A radius of 0
+///   does not filter that axis.
+/// * `bright`: If `true`, bright objects on a dark background are kept (the
+///   white top-hat), otherwise dark objects on a bright background (the black
+///   top-hat, _i.e._ the grayscale closing minus the image). Default = `true`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The top-hat filtered image with the same shape as
+///   `data`.
+/// * `Err(ImgalError)`: If radius does not have one value per axis.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1016/0167-8655(92)90069-C>
+pub fn top_hat<T>(
+    data: ArrayViewD<T>,
+    radius: &[usize],
+    bright: Option<bool>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bright = bright.unwrap_or(true);
+
+    // subtract the opening, or subtract the image from the closing
+    if bright {
+        let mut output = grayscale::opening(data.view(), radius)?;
+        Zip::from(&mut output)
+            .and(&data)
+            .par_for_each(|o, v| *o = v.to_f64() - *o);
+        Ok(output)
+    } else {
+        let mut output = grayscale::closing(data.view(), radius)?;
+        Zip::from(&mut output)
+            .and(&data)
+            .par_for_each(|o, v| *o -= v.to_f64());
+        Ok(output)
+    }
+}
+
+/// Replace each lane along an axis of an array in parallel.
+pub(crate) fn map_lanes<F>(data: &mut ArrayD<f64>, axis: usize, f: F)
+where
+    F: Fn(&[f64]) -> Vec<f64> + Sync,
+{
+    data.lanes_mut(Axis(axis))
+        .into_iter()
+        .par_bridge()
+        .for_each(|mut ln| {
+            if ln.is_empty() {
+                return;
+            }
+            let out = f(&ln.to_vec());
+            ln.iter_mut().zip(out).for_each(|(v, o)| *v = o);
+        });
+}
+
+/// Compute the lower envelope of the parabolas rooted at each value of a
+/// lane, min_q(f(q) + a(p - q)²), in linear time.
+fn lower_envelope(f: &[f64], a: f64) -> Vec<f64> {
+    let n = f.len();
+    let mut v = vec![0usize; n];
+    let mut z = vec![0.0; n + 1];
+    let mut k = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+
+    // the intersection of the parabolas rooted at q and p
+    let intersect = |q: usize, p: usize| {
+        let (qf, pf) = (q as f64, p as f64);
+        ((f[q] + a * qf * qf) - (f[p] + a * pf * pf)) / (2.0 * a * (qf - pf))
+    };
+    (1..n).for_each(|q| {
+        let mut s = intersect(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersect(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    });
+
+    // evaluate the envelope
+    let mut k = 0;
+    (0..n)
+        .map(|p| {
+            while z[k + 1] < p as f64 {
+                k += 1;
+            }
+            let d = p as f64 - v[k] as f64;
+            f[v[k]] + a * d * d
+        })
+        .collect()
+}
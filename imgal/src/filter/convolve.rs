@@ -1,5 +1,176 @@
+use ndarray::{
+    Array2, Array3, ArrayD, ArrayView2, ArrayView3, ArrayViewD, Axis, Ix2, IxDyn, Slice, Zip,
+};
+use rayon::prelude::*;
 use rustfft::{FftPlanner, num_complex::Complex, num_traits::Zero};
 
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::filter::spatial::{BorderMode, border_index, correlate_axis};
+use crate::kernel::separate;
+use crate::traits::numeric::ToFloat64;
+
+/// The largest kernel (in elements) convolved directly by default.
+const DIRECT_MAX_LEN: usize = 125;
+
+/// The largest relative residual of a kernel convolved as separable passes by
+/// default.
+const SEPARABLE_TOLERANCE: f64 = 1e-10;
+
+/// Methods to compute a spatial convolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvolveMethod {
+    /// Direct summation over the kernel elements. The cost grows linearly with
+    /// the kernel size, which makes it faster for small kernels.
+    Direct,
+    /// Multiplication in the frequency domain with the Fast Fourier Transform
+    /// (FFT). The cost does not depend on the kernel size.
+    Fft,
+}
+
+/// Convolve an n-dimensional image with a kernel.
+///
+/// # Description
+///
+/// This function computes the convolution of an n-dimensional image with a
+/// centered kernel (_e.g._ a `kernel::neighborhood::weighted_circle`) of the
+/// same number of dimensions:
+///
+/// ```text
+/// O(x) = Σⱼ K(j) × I(x + c - j)
+/// ```
+///
+/// where `c` is the kernel center, the index (n - 1) / 2 of each axis (_i.e._
+/// the center of odd shapes). Values past the edges of the image are extended
+/// with the `border` mode. All methods compute the same result. By default
+/// separable 2-dimensional kernels (see `kernel::separate`) are convolved as
+/// two 1-dimensional passes, other kernels with up to 125 elements (_e.g._
+/// 11 × 11 or 5 × 5 × 5) are convolved directly and larger kernels with the
+/// FFT.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `kernel`: The convolution kernel, with the same number of dimensions as
+///   `data`.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+/// * `method`: The convolution method. If `None`, then the method is chosen
+///   from the kernel size.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The convolved image with the same shape as `data`.
+/// * `Err(ImgalError)`: If the kernel is empty or does not have the same number
+///   of dimensions as `data`.
+pub fn convolve<S, T>(
+    data: ArrayViewD<S>,
+    kernel: ArrayViewD<T>,
+    border: Option<BorderMode>,
+    method: Option<ConvolveMethod>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let border = border.unwrap_or(BorderMode::Reflect);
+
+    // check if the kernel parameter is valid
+    if kernel.ndim() != data.ndim() || kernel.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "kernel",
+            reason: format!(
+                "must be non-empty with {} dimensions but got shape {:?}",
+                data.ndim(),
+                kernel.shape()
+            ),
+        });
+    }
+    if data.is_empty() {
+        return Ok(ArrayD::<f64>::zeros(data.raw_dim()));
+    }
+
+    // convolve separable 2-dimensional kernels as two 1-dimensional passes
+    if method.is_none() && kernel.ndim() == 2 {
+        let kernel_2d = kernel
+            .view()
+            .into_dimensionality::<Ix2>()
+            .expect("a 2-dimensional kernel");
+        if let Some((a, b)) = separate(kernel_2d, Some(SEPARABLE_TOLERANCE))? {
+            return Ok(convolve_separable(
+                data.mapv(|v| v.to_f64()),
+                &[a, b],
+                border,
+            ));
+        }
+    }
+    let method = method.unwrap_or(if kernel.len() <= DIRECT_MAX_LEN {
+        ConvolveMethod::Direct
+    } else {
+        ConvolveMethod::Fft
+    });
+
+    // extend the image by the kernel size minus one along each axis, the
+    // convolution of the padded image is then valid over the image shape
+    let shape = data.shape().to_vec();
+    let k_shape = kernel.shape().to_vec();
+    let padded = pad(data, &k_shape, border);
+    let padded_shape = padded.shape().to_vec();
+
+    match method {
+        ConvolveMethod::Direct => {
+            // accumulate the shifted, weighted padded image of each flipped
+            // kernel element
+            let mut output = ArrayD::<f64>::zeros(IxDyn(&shape));
+            kernel.indexed_iter().for_each(|(idx, w)| {
+                let w = w.to_f64();
+                if w == 0.0 {
+                    return;
+                }
+                let window = padded.slice_each_axis(|ax| {
+                    let start = k_shape[ax.axis.index()] - 1 - idx[ax.axis.index()];
+                    Slice::from(start..start + shape[ax.axis.index()])
+                });
+                Zip::from(&mut output)
+                    .and(&window)
+                    .par_for_each(|o, &v| *o += w * v);
+            });
+
+            Ok(output)
+        }
+        ConvolveMethod::Fft => {
+            // compute FFT size of each axis
+            let fft_shape: Vec<usize> = padded_shape
+                .iter()
+                .zip(k_shape.iter())
+                .map(|(p, k)| (p + k - 1).next_power_of_two())
+                .collect();
+
+            // allocate buffers, transform and multiply in the frequency domain
+            let mut a_fft_buf = ArrayD::<Complex<f64>>::zeros(IxDyn(&fft_shape));
+            let mut b_fft_buf = ArrayD::<Complex<f64>>::zeros(IxDyn(&fft_shape));
+            fill_buffer(&mut a_fft_buf, padded.view());
+            fill_buffer(&mut b_fft_buf, kernel);
+            fft_nd(&mut a_fft_buf, false);
+            fft_nd(&mut b_fft_buf, false);
+            Zip::from(&mut a_fft_buf)
+                .and(&b_fft_buf)
+                .par_for_each(|v, b| *v *= b);
+
+            // compute inverse FFT and extract the valid region
+            fft_nd(&mut a_fft_buf, true);
+            let result = a_fft_buf
+                .slice_each_axis(|ax| {
+                    let start = k_shape[ax.axis.index()] - 1;
+                    Slice::from(start..start + shape[ax.axis.index()])
+                })
+                .mapv(|v| v.re);
+
+            Ok(result)
+        }
+    }
+}
+
 /// Convolve two 1-dimensional signals using the Fast Fourier Transform (FFT).
 ///
 /// # Description
@@ -21,9 +192,12 @@ use rustfft::{FftPlanner, num_complex::Complex, num_traits::Zero};
 /// * `Array1<f64>`: The FFT convolved result of the same length as input signal
 ///   `a`.
 pub fn fft_convolve_1d(a: &[f64], b: &[f64]) -> Vec<f64> {
-    // compute FFT size
+    // compute FFT size, an empty signal convolves to zeros
     let n_a = a.len();
     let n_b = b.len();
+    if n_a == 0 || n_b == 0 {
+        return vec![0.0; n_a];
+    }
     let n_fft = n_a + n_b - 1;
     let fft_size = n_fft.next_power_of_two();
 
@@ -66,6 +240,255 @@ pub fn fft_convolve_1d(a: &[f64], b: &[f64]) -> Vec<f64> {
     result
 }
 
+/// Circularly convolve two 1-dimensional signals using the Fast Fourier
+/// Transform (FFT).
+///
+/// # Description
+///
+/// Compute the circular (_i.e._ periodic) convolution of two discrete signals
+/// (`a` and `b`) by transforming them to the frequency domain, multiplying them,
+/// and then transforming the result back into a signal. Unlike
+/// `fft_convolve_1d`, signal that is shifted past the end of `a` wraps around to
+/// the start instead of being trimmed. If `b` is longer than `a`, `b` is wrapped
+/// (summed modulo `a`'s length) before the convolution.
+///
+/// # Arguments
+///
+/// * `a`: The first input signal to FFT convolve, treated as one period of a
+///   periodic signal.
+/// * `b`: The second input signal to FFT convolve.
+///
+/// # Returns
+///
+/// * `Vec<f64>`: The circularly FFT convolved result of the same length as input
+///   signal `a`.
+pub fn fft_circular_convolve_1d(a: &[f64], b: &[f64]) -> Vec<f64> {
+    // the FFT size is the period (i.e. the length of a)
+    let n_a = a.len();
+    if n_a == 0 {
+        return Vec::new();
+    }
+
+    // allocate buffers and fill with input data, wrapping b into n_a bins
+    let mut a_fft_buf: Vec<Complex<f64>> = a.iter().map(|&v| Complex::new(v, 0.0)).collect();
+    let mut b_fft_buf = vec![Complex::zero(); n_a];
+    b.iter().enumerate().for_each(|(i, &v)| {
+        b_fft_buf[i % n_a].re += v;
+    });
+
+    // create FFT planner
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(n_a);
+    let ifft = planner.plan_fft_inverse(n_a);
+
+    // compute forward FFTs
+    fft.process(&mut a_fft_buf);
+    fft.process(&mut b_fft_buf);
+
+    // multiply in the frequency domain
+    a_fft_buf
+        .iter_mut()
+        .zip(b_fft_buf.iter())
+        .for_each(|(v, b)| {
+            *v *= b;
+        });
+
+    // compute inverse FFT
+    ifft.process(&mut a_fft_buf);
+
+    // extract real component and scale
+    let scale = 1.0 / n_a as f64;
+    a_fft_buf.iter().map(|v| v.re * scale).collect()
+}
+
+/// Convolve two 2-dimensional images using the Fast Fourier Transform (FFT).
+///
+/// # Description
+///
+/// Compute the linear convolution of two images (`a` and `b`) by transforming
+/// them to the frequency domain, multiplying them, and then transforming the
+/// result back into an image. Like `fft_convolve_1d`, this function uses
+/// "same-shape" trimming with the first parameter `a`, the returned
+/// convolution is the first `a.dim()` elements of the full convolution.
+///
+/// # Arguments
+///
+/// * `a`: The first input image to FFT convolve. Returned convolution arrays
+///   will be "same-shape" trimmed to `a`'s shape.
+/// * `b`: The second input image (_e.g._ a kernel) to FFT convolve.
+///
+/// # Returns
+///
+/// * `Array2<f64>`: The FFT convolved result with the same shape as input image
+///   `a`.
+pub fn fft_convolve_2d<S, T>(a: ArrayView2<S>, b: ArrayView2<T>) -> Array2<f64>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    fft_convolve_nd(a.into_dyn(), b.into_dyn())
+        .expect("2-dimensional arrays have the same number of dimensions")
+        .into_dimensionality()
+        .unwrap()
+}
+
+/// Convolve two n-dimensional arrays using the Fast Fourier Transform (FFT).
+///
+/// # Description
+///
+/// Compute the linear convolution of two n-dimensional arrays (`a` and `b`) by
+/// transforming them to the frequency domain, multiplying them, and then
+/// transforming the result back. Each axis is zero padded to the next power of
+/// two of the full convolution length to avoid circular wrap around. This
+/// function uses "same-shape" trimming with the first parameter `a`, the
+/// returned convolution is the first `a.shape()` elements of the full
+/// convolution.
+///
+/// # Arguments
+///
+/// * `a`: The first input array to FFT convolve. Returned convolution arrays
+///   will be "same-shape" trimmed to `a`'s shape.
+/// * `b`: The second input array (_e.g._ a kernel) to FFT convolve.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The FFT convolved result with the same shape as input
+///   array `a`. If either array is empty the result is 0.0.
+/// * `Err(ImgalError)`: If `a` and `b` do not have the same number of
+///   dimensions.
+pub fn fft_convolve_nd<S, T>(a: ArrayViewD<S>, b: ArrayViewD<T>) -> Result<ArrayD<f64>, ImgalError>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    // check if the arrays have the same number of dimensions
+    if a.ndim() != b.ndim() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "b",
+            reason: format!(
+                "must have the same number of dimensions as \"a\", {} but got {}",
+                a.ndim(),
+                b.ndim()
+            ),
+        });
+    }
+    if a.is_empty() || b.is_empty() {
+        return Ok(ArrayD::<f64>::zeros(a.raw_dim()));
+    }
+
+    // compute FFT size of each axis
+    let fft_shape: Vec<usize> = a
+        .shape()
+        .iter()
+        .zip(b.shape().iter())
+        .map(|(n_a, n_b)| (n_a + n_b - 1).next_power_of_two())
+        .collect();
+
+    // allocate buffers and fill with input data
+    let mut a_fft_buf = ArrayD::<Complex<f64>>::zeros(IxDyn(&fft_shape));
+    let mut b_fft_buf = ArrayD::<Complex<f64>>::zeros(IxDyn(&fft_shape));
+    fill_buffer(&mut a_fft_buf, a.view());
+    fill_buffer(&mut b_fft_buf, b);
+
+    // compute forward FFTs
+    fft_nd(&mut a_fft_buf, false);
+    fft_nd(&mut b_fft_buf, false);
+
+    // multiply in the frequency domain
+    Zip::from(&mut a_fft_buf)
+        .and(&b_fft_buf)
+        .par_for_each(|v, b| *v *= b);
+
+    // compute inverse FFT and trim to input shape
+    fft_nd(&mut a_fft_buf, true);
+    let result = a_fft_buf
+        .slice_each_axis(|ax| Slice::from(0..a.len_of(ax.axis)))
+        .mapv(|v| v.re);
+
+    Ok(result)
+}
+
+/// Convolve each lane of a 3-dimensional array with a 1-dimensional kernel
+/// using the Fast Fourier Transform (FFT).
+///
+/// # Description
+///
+/// Compute the convolution of every 1-dimensional lane along `axis` (_e.g._
+/// the decay curve of each pixel) with the same `kernel` (_e.g._ an instrument
+/// response function). The FFT is planned and the kernel is transformed once,
+/// then the lanes are convolved in parallel. Like `fft_convolve_1d`, each lane
+/// uses "same-length" trimming, the returned lanes have the same length as the
+/// input lanes.
+///
+/// # Arguments
+///
+/// * `data`: The input 3-dimensional array.
+/// * `kernel`: The 1-dimensional kernel to convolve each lane with.
+/// * `axis`: The lane axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The FFT convolved array with the same shape as `data`.
+///   If the lanes or the kernel are empty the result is 0.0.
+/// * `Err(ImgalError)`: If axis is >= 3.
+pub fn fft_convolve_axis<T>(
+    data: ArrayView3<T>,
+    kernel: &[f64],
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if the axis parameter is valid
+    validate::axis(a, 3)?;
+
+    // allocate output array
+    let mut result = Array3::<f64>::zeros(data.dim());
+    let n_a = data.len_of(Axis(a));
+    let n_b = kernel.len();
+    if n_a == 0 || n_b == 0 {
+        return Ok(result);
+    }
+
+    // compute FFT size, plan the FFTs and transform the kernel once
+    let fft_size = (n_a + n_b - 1).next_power_of_two();
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(fft_size);
+    let ifft = planner.plan_fft_inverse(fft_size);
+    let mut b_fft_buf = vec![Complex::zero(); fft_size];
+    b_fft_buf[..n_b]
+        .iter_mut()
+        .zip(kernel.iter())
+        .for_each(|(v, k)| *v = Complex::new(*k, 0.0));
+    fft.process(&mut b_fft_buf);
+
+    // convolve each lane in parallel
+    let scale = 1.0 / fft_size as f64;
+    Zip::from(result.lanes_mut(Axis(a)))
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|mut r_ln, d_ln| {
+            let mut a_fft_buf = vec![Complex::zero(); fft_size];
+            a_fft_buf[..n_a]
+                .iter_mut()
+                .zip(d_ln.iter())
+                .for_each(|(v, d)| *v = Complex::new(d.to_f64(), 0.0));
+            fft.process(&mut a_fft_buf);
+            a_fft_buf
+                .iter_mut()
+                .zip(b_fft_buf.iter())
+                .for_each(|(v, b)| *v *= b);
+            ifft.process(&mut a_fft_buf);
+            r_ln.iter_mut()
+                .zip(a_fft_buf.iter())
+                .for_each(|(r, v)| *r = v.re * scale);
+        });
+
+    Ok(result)
+}
+
 /// Deconvolve two 1-dimensional signals using the Fast Fourier Transform (FFT).
 ///
 /// # Description
@@ -92,9 +515,12 @@ pub fn fft_deconvolve_1d(a: &[f64], b: &[f64], epsilon: Option<f64>) -> Vec<f64>
     // set optional parameters if needed
     let epsilon = epsilon.unwrap_or(1e-8);
 
-    // compute FFT size
+    // compute FFT size, an empty signal deconvolves to zeros
     let n_a = a.len();
     let n_b = b.len();
+    if n_a == 0 || n_b == 0 {
+        return vec![0.0; n_a];
+    }
     let n_fft = n_a + n_b - 1;
     let fft_size = n_fft.next_power_of_two();
 
@@ -140,3 +566,95 @@ pub fn fft_deconvolve_1d(a: &[f64], b: &[f64], epsilon: Option<f64>) -> Vec<f64>
 
     result
 }
+
+/// Compute the in-place n-dimensional FFT of an array, axis by axis. The
+/// inverse transform is scaled by 1/n.
+pub(crate) fn fft_nd(buf: &mut ArrayD<Complex<f64>>, inverse: bool) {
+    let mut planner = FftPlanner::new();
+    (0..buf.ndim()).for_each(|ax| {
+        let n = buf.len_of(Axis(ax));
+        let fft = if inverse {
+            planner.plan_fft_inverse(n)
+        } else {
+            planner.plan_fft_forward(n)
+        };
+
+        // transform each lane along the axis in parallel
+        buf.lanes_mut(Axis(ax))
+            .into_iter()
+            .par_bridge()
+            .for_each(|mut ln| {
+                let mut lane: Vec<Complex<f64>> = ln.iter().copied().collect();
+                fft.process(&mut lane);
+                ln.iter_mut().zip(lane.iter()).for_each(|(v, l)| *v = *l);
+            });
+    });
+    if inverse {
+        let scale = 1.0 / buf.len() as f64;
+        buf.par_mapv_inplace(|v| v * scale);
+    }
+}
+
+/// Extend an n-dimensional image by a kernel shape minus one along each axis
+/// with a border mode, the convolution of the padded image with the kernel is
+/// then valid over the image shape.
+pub(crate) fn pad<T>(data: ArrayViewD<T>, k_shape: &[usize], border: BorderMode) -> ArrayD<f64>
+where
+    T: ToFloat64,
+{
+    let cval = match border {
+        BorderMode::Constant(c) => c,
+        _ => 0.0,
+    };
+    let shape = data.shape().to_vec();
+    let lead: Vec<isize> = k_shape.iter().map(|&k| (k / 2) as isize).collect();
+    let padded_shape: Vec<usize> = shape
+        .iter()
+        .zip(k_shape.iter())
+        .map(|(n, k)| n + k - 1)
+        .collect();
+    let mut src = vec![0; shape.len()];
+    ArrayD::from_shape_fn(IxDyn(&padded_shape), |idx| {
+        for ax in 0..shape.len() {
+            match border_index(idx[ax] as isize - lead[ax], shape[ax], border) {
+                Some(i) => src[ax] = i,
+                None => return cval,
+            }
+        }
+        data[src.as_slice()].to_f64()
+    })
+}
+
+/// Convolve an array with one 1-dimensional kernel factor per axis.
+fn convolve_separable(
+    mut data: ArrayD<f64>,
+    factors: &[Vec<f64>],
+    border: BorderMode,
+) -> ArrayD<f64> {
+    // a constant border of a later pass is the constant convolved with the
+    // previous factors
+    let mut cval = match border {
+        BorderMode::Constant(c) => Some(c),
+        _ => None,
+    };
+    factors.iter().enumerate().for_each(|(ax, f)| {
+        // flip the factor to correlate, even lengths are padded at the end to
+        // keep the center at (n - 1) / 2
+        let mut flipped: Vec<f64> = f.iter().rev().copied().collect();
+        if f.len() % 2 == 0 {
+            flipped.push(0.0);
+        }
+        let pass_border = cval.map_or(border, BorderMode::Constant);
+        correlate_axis(&mut data, ax, &flipped, pass_border);
+        cval = cval.map(|c| c * f.iter().sum::<f64>());
+    });
+
+    data
+}
+
+/// Copy an array into the leading corner of a zero padded FFT buffer.
+fn fill_buffer<T: ToFloat64>(buf: &mut ArrayD<Complex<f64>>, data: ArrayViewD<T>) {
+    Zip::from(buf.slice_each_axis_mut(|ax| Slice::from(0..data.len_of(ax.axis))))
+        .and(&data)
+        .for_each(|b, v| *b = Complex::new(v.to_f64(), 0.0));
+}
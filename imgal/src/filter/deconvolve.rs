@@ -0,0 +1,110 @@
+use ndarray::{ArrayD, ArrayViewD, IxDyn, Zip};
+use rustfft::{num_complex::Complex, num_traits::Zero};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::filter::convolve::fft_nd;
+use crate::traits::numeric::ToFloat64;
+
+/// Deconvolve an n-dimensional image with a Wiener filter.
+///
+/// # Description
+///
+/// This function compensates the blur of a point spread function (PSF) in a
+/// single, non-iterative step by filtering the image in the frequency domain
+/// with the Wiener filter:
+///
+/// ```text
+/// X(f) = H*(f) × Y(f) / (|H(f)|² + NSR)
+/// ```
+///
+/// where `Y` is the Fourier transform of the image, `H` is the Fourier
+/// transform (_i.e._ the optical transfer function) of the PSF and `NSR` is the
+/// noise-to-signal power ratio. An NSR of 0.0 is the inverse filter, larger
+/// values suppress the amplification of noise at frequencies the PSF does not
+/// transfer, at the cost of a smoother result. Frequencies where both the
+/// transfer function and the NSR are 0.0 are set to 0.0.
+///
+/// The image is treated as periodic. The PSF is normalized to sum to 1.0 and
+/// its center, the index (n - 1) / 2 of each axis (_i.e._ the center of odd
+/// shapes), is moved to the origin.
+///
+/// # Arguments
+///
+/// * `data`: The blurred n-dimensional image.
+/// * `psf`: The centered point spread function, with the same number of
+///   dimensions as `data` and no axis longer than the matching `data` axis.
+/// * `nsr`: The noise-to-signal power ratio. Must be >= 0.0.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The deconvolved image with the same shape as `data`.
+/// * `Err(ImgalError)`: If the PSF does not have the same number of dimensions
+///   as `data`, has an axis longer than `data` or sums to 0.0. If nsr is < 0.0.
+pub fn wiener<S, T>(
+    data: ArrayViewD<S>,
+    psf: ArrayViewD<T>,
+    nsr: f64,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    // check if the psf and nsr parameters are valid
+    validate::at_least("nsr", nsr, 0.0)?;
+    if psf.ndim() != data.ndim()
+        || psf
+            .shape()
+            .iter()
+            .zip(data.shape().iter())
+            .any(|(p, d)| p > d)
+    {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "psf",
+            reason: format!(
+                "must fit in the image shape {:?} but got {:?}",
+                data.shape(),
+                psf.shape()
+            ),
+        });
+    }
+    let total = psf.iter().map(|v| v.to_f64()).sum::<f64>();
+    if total == 0.0 || !total.is_finite() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "psf",
+            reason: format!("must have a finite, non-zero sum but got {}", total),
+        });
+    }
+
+    // move the normalized psf center to the origin of an image sized buffer
+    let shape = data.shape().to_vec();
+    let mut h_buf = ArrayD::<Complex<f64>>::zeros(IxDyn(&shape));
+    let mut target = vec![0; shape.len()];
+    psf.indexed_iter().for_each(|(idx, v)| {
+        target.iter_mut().enumerate().for_each(|(ax, t)| {
+            let center = (psf.shape()[ax] as isize - 1) / 2;
+            *t = (idx[ax] as isize - center).rem_euclid(shape[ax] as isize) as usize;
+        });
+        h_buf[target.as_slice()] = Complex::new(v.to_f64() / total, 0.0);
+    });
+
+    // compute forward FFTs
+    let mut y_buf = data.mapv(|v| Complex::new(v.to_f64(), 0.0));
+    fft_nd(&mut y_buf, false);
+    fft_nd(&mut h_buf, false);
+
+    // apply the Wiener filter in the frequency domain
+    Zip::from(&mut y_buf).and(&h_buf).par_for_each(|y, h| {
+        let denom = h.norm_sqr() + nsr;
+        *y = if denom > 0.0 {
+            h.conj() * *y / denom
+        } else {
+            Complex::zero()
+        };
+    });
+
+    // compute inverse FFT and extract the real component
+    fft_nd(&mut y_buf, true);
+
+    Ok(y_buf.mapv(|v| v.re))
+}
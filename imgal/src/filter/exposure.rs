@@ -0,0 +1,436 @@
+use ndarray::{Array2, ArrayD, ArrayView2, ArrayViewD, Zip, s};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::traits::numeric::ToFloat64;
+
+/// Equalize the histogram of an n-dimensional image.
+///
+/// # Description
+///
+/// This function spreads the intensities of an image evenly over the output
+/// range by mapping each value through the cumulative distribution function
+/// (CDF) of the image histogram:
+///
+/// ```text
+/// O = (CDF(v) - CDF_min) / (N - CDF_min)
+/// ```
+///
+/// where `N` is the number of values. The histogram spans the range of the
+/// image. A constant image maps to 0.0.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `bins`: The number of histogram bins, default = 256.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The equalized image with the same shape as `data`,
+///   with values in [0, 1].
+/// * `Err(ImgalError)`: If bins is 0.
+pub fn equalize_histogram<T>(
+    data: ArrayViewD<T>,
+    bins: Option<usize>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(256);
+
+    // check if the bins parameter is valid
+    validate_bins(bins)?;
+    if data.is_empty() {
+        return Ok(ArrayD::zeros(data.raw_dim()));
+    }
+
+    // compute the cumulative histogram over the image range
+    let binning = Binning::new(data.iter().map(|v| v.to_f64()), bins);
+    let mut cdf = vec![0.0; bins];
+    data.iter()
+        .for_each(|v| cdf[binning.index(v.to_f64())] += 1.0);
+    (1..bins).for_each(|i| cdf[i] += cdf[i - 1]);
+
+    // map each value through the normalized cdf
+    let total = data.len() as f64;
+    let cdf_min = cdf.iter().copied().find(|&c| c > 0.0).unwrap_or(0.0);
+    let range = total - cdf_min;
+    let mut output = data.mapv(|v| v.to_f64());
+    output.par_mapv_inplace(|v| {
+        if range > 0.0 {
+            (cdf[binning.index(v)] - cdf_min) / range
+        } else {
+            0.0
+        }
+    });
+
+    Ok(output)
+}
+
+/// Match the histogram of an n-dimensional image to a reference image.
+///
+/// # Description
+///
+/// This function remaps the intensities of an image so that its cumulative
+/// distribution function (CDF) matches the CDF of a reference image (_i.e._
+/// histogram matching or histogram specification). Each distinct value `v` of
+/// the image is mapped to the reference value at the same quantile:
+///
+/// ```text
+/// O(v) = CDF_ref⁻¹(CDF(v))
+/// ```
+///
+/// where the CDFs are computed exactly from the sorted distinct values, and
+/// the inverse reference CDF is linearly interpolated between the distinct
+/// reference values. Equal values of the image map to equal output values and
+/// the order of the values is preserved. The images do not need to have the
+/// same shape.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image to remap.
+/// * `reference`: The n-dimensional reference image.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The remapped image with the same shape as `data`, with
+///   values in the range of `reference`.
+/// * `Err(ImgalError)`: If the reference image is empty.
+pub fn match_histogram<S, T>(
+    data: ArrayViewD<S>,
+    reference: ArrayViewD<T>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    // check if the reference parameter is valid
+    if reference.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "reference",
+            reason: "must contain at least one value".to_string(),
+        });
+    }
+    if data.is_empty() {
+        return Ok(ArrayD::zeros(data.raw_dim()));
+    }
+
+    // compute the distinct values and cdfs of both images
+    let (values, cdf) = distinct_cdf(data.iter().map(|v| v.to_f64()).collect());
+    let (ref_values, ref_cdf) = distinct_cdf(reference.iter().map(|v| v.to_f64()).collect());
+
+    // map each distinct value to the reference value at the same quantile
+    let last = ref_cdf.len() - 1;
+    let mapped: Vec<f64> = cdf
+        .par_iter()
+        .map(|&q| {
+            let hi = ref_cdf.partition_point(|&c| c < q);
+            if hi == 0 {
+                ref_values[0]
+            } else if hi > last {
+                ref_values[last]
+            } else {
+                let lo = hi - 1;
+                let t = (q - ref_cdf[lo]) / (ref_cdf[hi] - ref_cdf[lo]);
+                ref_values[lo] + t * (ref_values[hi] - ref_values[lo])
+            }
+        })
+        .collect();
+    let mut output = data.mapv(|v| v.to_f64());
+    output.par_mapv_inplace(|v| {
+        let i = values
+            .binary_search_by(|u| u.total_cmp(&v))
+            .unwrap_or_else(|i| i.min(values.len() - 1));
+        mapped[i]
+    });
+
+    Ok(output)
+}
+
+/// Apply contrast limited adaptive histogram equalization (CLAHE) to a
+/// 2-dimensional image.
+///
+/// # Description
+///
+/// This function equalizes the histogram of each tile of a regular grid over
+/// the image independently, which enhances local contrast. The histogram of
+/// each tile is clipped at a limit and the clipped counts are redistributed
+/// evenly over all bins before computing the tile mapping, which limits the
+/// amplification of noise in homogeneous regions. Each pixel is mapped with
+/// the bilinear interpolation of the mappings of the four nearest tile
+/// centers, which removes the tile boundaries. All tile histograms span the
+/// range of the whole image.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `tiles`: The number of tiles along the (row, col) axes, default = (8, 8).
+///   Must be >= 1 and <= the image shape.
+/// * `clip_limit`: The histogram clip limit as a fraction of the tile pixels,
+///   1.0 does not clip (_i.e._ adaptive histogram equalization), default =
+///   0.01. Must be in (0, 1].
+/// * `bins`: The number of histogram bins, default = 256.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The equalized image with the same shape as `data`,
+///   with values in [0, 1].
+/// * `Err(ImgalError)`: If tiles is 0 or larger than the image shape along an
+///   axis. If clip_limit is not in (0, 1]. If bins is 0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1016/S0734-189X(87)80186-X>
+pub fn clahe<T>(
+    data: ArrayView2<T>,
+    tiles: Option<(usize, usize)>,
+    clip_limit: Option<f64>,
+    bins: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let (ty, tx) = tiles.unwrap_or((8, 8));
+    let clip_limit = clip_limit.unwrap_or(0.01);
+    let bins = bins.unwrap_or(256);
+
+    // check if the parameters are valid
+    let (rows, cols) = data.dim();
+    if ty == 0 || tx == 0 || ty > rows || tx > cols {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "tiles",
+            reason: format!(
+                "must be >= 1 and <= the image shape {:?} but got {:?}",
+                (rows, cols),
+                (ty, tx)
+            ),
+        });
+    }
+    if !(clip_limit > 0.0 && clip_limit <= 1.0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "clip_limit",
+            reason: format!("must be in (0, 1] but got {}", clip_limit),
+        });
+    }
+    validate_bins(bins)?;
+
+    // compute the bin of each pixel over the image range
+    let binning = Binning::new(data.iter().map(|v| v.to_f64()), bins);
+    let bin_data = data.mapv(|v| binning.index(v.to_f64()));
+
+    // compute the clipped, equalized mapping of each tile
+    let row_bounds: Vec<usize> = (0..=ty).map(|i| i * rows / ty).collect();
+    let col_bounds: Vec<usize> = (0..=tx).map(|j| j * cols / tx).collect();
+    let maps: Vec<Vec<f64>> = (0..ty * tx)
+        .into_par_iter()
+        .map(|t| {
+            let (i, j) = (t / tx, t % tx);
+            let tile = bin_data.slice(s![
+                row_bounds[i]..row_bounds[i + 1],
+                col_bounds[j]..col_bounds[j + 1]
+            ]);
+            tile_mapping(tile, clip_limit, bins)
+        })
+        .collect();
+
+    // interpolate the mappings of the four nearest tile centers
+    let row_centers: Vec<f64> = row_bounds
+        .windows(2)
+        .map(|w| (w[0] + w[1] - 1) as f64 / 2.0)
+        .collect();
+    let col_centers: Vec<f64> = col_bounds
+        .windows(2)
+        .map(|w| (w[0] + w[1] - 1) as f64 / 2.0)
+        .collect();
+    let mut output = Array2::<f64>::zeros((rows, cols));
+    Zip::indexed(&mut output)
+        .and(&bin_data)
+        .par_for_each(|(r, c), o, &b| {
+            let (i0, i1, wr) = neighbors(&row_centers, r as f64);
+            let (j0, j1, wc) = neighbors(&col_centers, c as f64);
+            let top = (1.0 - wc) * maps[i0 * tx + j0][b] + wc * maps[i0 * tx + j1][b];
+            let bottom = (1.0 - wc) * maps[i1 * tx + j0][b] + wc * maps[i1 * tx + j1][b];
+            *o = (1.0 - wr) * top + wr * bottom;
+        });
+
+    Ok(output)
+}
+
+/// Linearly rescale the intensities of an n-dimensional image between two
+/// percentiles.
+///
+/// # Description
+///
+/// This function maps the `low` percentile of the image to 0.0 and the `high`
+/// percentile to 1.0, and clips the values outside of that range (_i.e._
+/// contrast stretching). Saturating a small fraction of the values at both
+/// ends makes the rescaling robust to hot pixels and outliers. Percentiles are
+/// computed with linear interpolation between the closest ranks. If both
+/// percentiles have the same value, the output is 0.0.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `low`: The percentile mapped to 0.0, default = 2.0. Must be in [0, 100].
+/// * `high`: The percentile mapped to 1.0, default = 98.0. Must be in
+///   [0, 100] and > `low`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The rescaled image with the same shape as `data`, with
+///   values in [0, 1].
+/// * `Err(ImgalError)`: If low or high is not in [0, 100]. If low >= high.
+pub fn rescale_percentile<T>(
+    data: ArrayViewD<T>,
+    low: Option<f64>,
+    high: Option<f64>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let low = low.unwrap_or(2.0);
+    let high = high.unwrap_or(98.0);
+
+    // check if the percentile parameters are valid
+    validate::in_range("low", low, 0.0, 100.0)?;
+    validate::in_range("high", high, 0.0, 100.0)?;
+    if low >= high {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "high",
+            reason: format!("must be > low ({}) but got {}", low, high),
+        });
+    }
+    if data.is_empty() {
+        return Ok(ArrayD::zeros(data.raw_dim()));
+    }
+
+    // compute the percentile values from the sorted data
+    let mut sorted: Vec<f64> = data.iter().map(|v| v.to_f64()).collect();
+    sorted.par_sort_unstable_by(|a, b| a.total_cmp(b));
+    let p_low = percentile(&sorted, low);
+    let p_high = percentile(&sorted, high);
+
+    // rescale and clip to the unit range
+    let range = p_high - p_low;
+    let mut output = data.mapv(|v| v.to_f64());
+    output.par_mapv_inplace(|v| {
+        if range > 0.0 {
+            ((v - p_low) / range).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    });
+
+    Ok(output)
+}
+
+/// Compute the sorted distinct values of non-empty values and the fraction of
+/// the values that are <= each distinct value.
+fn distinct_cdf(mut values: Vec<f64>) -> (Vec<f64>, Vec<f64>) {
+    values.par_sort_unstable_by(|a, b| a.total_cmp(b));
+    let total = values.len() as f64;
+    let mut distinct: Vec<f64> = Vec::new();
+    let mut cdf: Vec<f64> = Vec::new();
+    values.iter().enumerate().for_each(|(i, &v)| {
+        if distinct.last().is_some_and(|d| d.total_cmp(&v).is_eq()) {
+            *cdf.last_mut().unwrap() = (i + 1) as f64 / total;
+        } else {
+            distinct.push(v);
+            cdf.push((i + 1) as f64 / total);
+        }
+    });
+
+    (distinct, cdf)
+}
+
+/// The binning of a histogram over a value range.
+struct Binning {
+    min: f64,
+    width: f64,
+    bins: usize,
+}
+
+impl Binning {
+    /// Create a binning over the range of the values.
+    fn new<I: Iterator<Item = f64>>(values: I, bins: usize) -> Self {
+        let (min, max) = values.fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        Binning {
+            min,
+            width: (max - min) / bins as f64,
+            bins,
+        }
+    }
+
+    /// The bin index of a value.
+    fn index(&self, value: f64) -> usize {
+        if self.width > 0.0 {
+            (((value - self.min) / self.width) as usize).min(self.bins - 1)
+        } else {
+            0
+        }
+    }
+}
+
+/// Check if the number of histogram bins is at least 1.
+fn validate_bins(bins: usize) -> Result<(), ImgalError> {
+    if bins == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "bins",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Compute the clip limited, normalized cumulative histogram of a tile of bin
+/// indices.
+fn tile_mapping(tile: ArrayView2<usize>, clip_limit: f64, bins: usize) -> Vec<f64> {
+    let total = tile.len() as f64;
+    let mut hist = vec![0.0; bins];
+    tile.iter().for_each(|&b| hist[b] += 1.0);
+
+    // clip the histogram and redistribute the excess counts evenly
+    let limit = (clip_limit * total).max(1.0);
+    let excess: f64 = hist.iter().map(|&h| (h - limit).max(0.0)).sum();
+    let share = excess / bins as f64;
+    hist.iter_mut().for_each(|h| *h = h.min(limit) + share);
+
+    // accumulate and normalize the histogram
+    let mut acc = 0.0;
+    hist.iter_mut().for_each(|h| {
+        acc += *h;
+        *h = acc / total;
+    });
+
+    hist
+}
+
+/// Find the two tile centers around a position and the interpolation weight
+/// of the second one, positions past the outer centers use the outer tile.
+fn neighbors(centers: &[f64], pos: f64) -> (usize, usize, f64) {
+    let last = centers.len() - 1;
+    if pos <= centers[0] {
+        return (0, 0, 0.0);
+    }
+    if pos >= centers[last] {
+        return (last, last, 0.0);
+    }
+    let i = centers.partition_point(|&c| c <= pos) - 1;
+
+    (i, i + 1, (pos - centers[i]) / (centers[i + 1] - centers[i]))
+}
+
+/// Compute a percentile of sorted values with linear interpolation between
+/// the closest ranks.
+pub(crate) fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+
+    sorted[lo] + (rank - lo as f64) * (sorted[hi] - sorted[lo])
+}
@@ -0,0 +1,398 @@
+use ndarray::{Array, Array2, ArrayD, ArrayViewD, Axis, IxDyn, Zip};
+use rayon::prelude::*;
+use rustfft::num_complex::Complex;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::filter::convolve::fft_nd;
+use crate::traits::numeric::ToFloat64;
+
+/// Filter the 2-dimensional planes of an n-dimensional array in the frequency
+/// domain.
+///
+/// # Description
+///
+/// This function transforms each 2-dimensional plane (_i.e._ each slice along
+/// the `axes` of an image stack) to the frequency domain, multiplies each
+/// Fourier coefficient by the `transfer` function and transforms the result
+/// back. The transfer function is evaluated at the radial spatial frequency `k`
+/// (in cycles per pixel, in [0, √0.5]) of each coefficient. Planes are filtered
+/// in parallel.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array, with at least 2 dimensions.
+/// * `transfer`: The filter transfer function, H(k), as a function of the
+///   radial spatial frequency. Must return finite values.
+/// * `axes`: The (row, col) axes of the 2-dimensional planes, default = (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered array with the same shape as `data`.
+/// * `Err(ImgalError)`: If an axis is >= the number of dimensions or the axes
+///   are the same. If the transfer function is not finite.
+pub fn frequency_filter<T, F>(
+    data: ArrayViewD<T>,
+    transfer: F,
+    axes: Option<(usize, usize)>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+    F: Fn(f64) -> f64,
+{
+    // set optional parameters if needed
+    let (ax_r, ax_c) = axes.unwrap_or((0, 1));
+
+    // check if the axes are valid
+    validate::axis(ax_r, data.ndim())?;
+    validate::axis(ax_c, data.ndim())?;
+    if ax_r == ax_c {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "axes",
+            reason: format!("the row and col axes must differ but got {}", ax_r),
+        });
+    }
+
+    // compute the transfer function of the plane frequencies
+    let (rows, cols) = (data.len_of(Axis(ax_r)), data.len_of(Axis(ax_c)));
+    let gain = Array2::from_shape_fn((rows, cols), |(r, c)| {
+        let fr = signed_frequency(r, rows);
+        let fc = signed_frequency(c, cols);
+        transfer((fr * fr + fc * fc).sqrt())
+    });
+    if gain.iter().any(|g| !g.is_finite()) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "transfer",
+            reason: "must return finite values".to_string(),
+        });
+    }
+
+    // move the plane axes last and stack the planes, (plane, row, col)
+    let mut order: Vec<usize> = (0..data.ndim())
+        .filter(|&a| a != ax_r && a != ax_c)
+        .collect();
+    let planes: usize = order.iter().map(|&a| data.len_of(Axis(a))).product();
+    order.extend([ax_r, ax_c]);
+    let permuted = data.permuted_axes(IxDyn(&order));
+    let stack_shape = permuted.raw_dim();
+    let mut stack = Array::from_iter(permuted.iter().map(|v| v.to_f64()))
+        .into_shape_with_order((planes, rows, cols))
+        .unwrap();
+
+    // filter each plane in parallel
+    stack
+        .axis_iter_mut(Axis(0))
+        .into_par_iter()
+        .for_each(|mut pl| {
+            let mut buf = pl.mapv(|v| Complex::new(v, 0.0)).into_dyn();
+            fft_nd(&mut buf, false);
+            Zip::from(&mut buf)
+                .and(gain.view().into_dyn())
+                .for_each(|v, g| *v *= g);
+            fft_nd(&mut buf, true);
+            Zip::from(&mut pl)
+                .and(buf.view().into_dimensionality().unwrap())
+                .for_each(|p, v: &Complex<f64>| *p = v.re);
+        });
+
+    // restore the original axis order
+    let mut inverse = vec![0; order.len()];
+    order.iter().enumerate().for_each(|(i, &a)| inverse[a] = i);
+    let result = stack
+        .into_shape_with_order(stack_shape)
+        .unwrap()
+        .permuted_axes(IxDyn(&inverse))
+        .as_standard_layout()
+        .into_owned();
+
+    Ok(result)
+}
+
+/// Apply a Gaussian low-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function attenuates high spatial frequencies with the transfer
+/// function:
+///
+/// ```text
+/// H(k) = exp(-k² / (2 × k₀²))
+/// ```
+///
+/// where `k` is the radial spatial frequency and `k₀` is the cutoff frequency.
+/// See `frequency_filter`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array, with at least 2 dimensions.
+/// * `cutoff`: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// * `axes`: The (row, col) axes of the 2-dimensional planes, default = (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered array with the same shape as `data`.
+/// * `Err(ImgalError)`: If cutoff is <= 0.0. If the axes are invalid.
+pub fn gaussian_low_pass<T>(
+    data: ArrayViewD<T>,
+    cutoff: f64,
+    axes: Option<(usize, usize)>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate::positive("cutoff", cutoff)?;
+
+    frequency_filter(data, |k| gaussian_gain(k, cutoff), axes)
+}
+
+/// Apply a Gaussian high-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function attenuates low spatial frequencies with the transfer
+/// function:
+///
+/// ```text
+/// H(k) = 1 - exp(-k² / (2 × k₀²))
+/// ```
+///
+/// where `k` is the radial spatial frequency and `k₀` is the cutoff frequency.
+/// The mean of each plane is removed. See `frequency_filter`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array, with at least 2 dimensions.
+/// * `cutoff`: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// * `axes`: The (row, col) axes of the 2-dimensional planes, default = (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered array with the same shape as `data`.
+/// * `Err(ImgalError)`: If cutoff is <= 0.0. If the axes are invalid.
+pub fn gaussian_high_pass<T>(
+    data: ArrayViewD<T>,
+    cutoff: f64,
+    axes: Option<(usize, usize)>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate::positive("cutoff", cutoff)?;
+
+    frequency_filter(data, |k| 1.0 - gaussian_gain(k, cutoff), axes)
+}
+
+/// Apply a Gaussian band-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function keeps the spatial frequencies between the `low` and `high`
+/// cutoff frequencies with the product of a Gaussian high-pass filter at `low`
+/// and a Gaussian low-pass filter at `high`. The mean of each plane is removed.
+/// See `gaussian_low_pass` and `gaussian_high_pass`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array, with at least 2 dimensions.
+/// * `low`: The low cutoff frequency, in cycles per pixel. Must be > 0.0.
+/// * `high`: The high cutoff frequency, in cycles per pixel. Must be > `low`.
+/// * `axes`: The (row, col) axes of the 2-dimensional planes, default = (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered array with the same shape as `data`.
+/// * `Err(ImgalError)`: If low is <= 0.0 or high is <= low. If the axes are
+///   invalid.
+pub fn gaussian_band_pass<T>(
+    data: ArrayViewD<T>,
+    low: f64,
+    high: f64,
+    axes: Option<(usize, usize)>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate_band(low, high)?;
+
+    frequency_filter(
+        data,
+        |k| (1.0 - gaussian_gain(k, low)) * gaussian_gain(k, high),
+        axes,
+    )
+}
+
+/// Apply a Butterworth low-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function attenuates high spatial frequencies with the transfer
+/// function:
+///
+/// ```text
+/// H(k) = 1 / (1 + (k / k₀)²ⁿ)
+/// ```
+///
+/// where `k` is the radial spatial frequency, `k₀` is the cutoff frequency and
+/// `n` is the filter order. Higher orders give a sharper transition with more
+/// ringing. See `frequency_filter`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array, with at least 2 dimensions.
+/// * `cutoff`: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// * `order`: The filter order, n, default = 2.
+/// * `axes`: The (row, col) axes of the 2-dimensional planes, default = (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered array with the same shape as `data`.
+/// * `Err(ImgalError)`: If cutoff is <= 0.0 or order is 0. If the axes are
+///   invalid.
+pub fn butterworth_low_pass<T>(
+    data: ArrayViewD<T>,
+    cutoff: f64,
+    order: Option<u32>,
+    axes: Option<(usize, usize)>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let n = order.unwrap_or(2);
+
+    // check if the parameters are valid
+    validate::positive("cutoff", cutoff)?;
+    validate::at_least("order", n as f64, 1.0)?;
+
+    frequency_filter(data, |k| butterworth_gain(k, cutoff, n), axes)
+}
+
+/// Apply a Butterworth high-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function attenuates low spatial frequencies with the transfer
+/// function:
+///
+/// ```text
+/// H(k) = 1 / (1 + (k₀ / k)²ⁿ)
+/// ```
+///
+/// where `k` is the radial spatial frequency, `k₀` is the cutoff frequency and
+/// `n` is the filter order. The mean of each plane is removed. See
+/// `frequency_filter`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array, with at least 2 dimensions.
+/// * `cutoff`: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// * `order`: The filter order, n, default = 2.
+/// * `axes`: The (row, col) axes of the 2-dimensional planes, default = (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered array with the same shape as `data`.
+/// * `Err(ImgalError)`: If cutoff is <= 0.0 or order is 0. If the axes are
+///   invalid.
+pub fn butterworth_high_pass<T>(
+    data: ArrayViewD<T>,
+    cutoff: f64,
+    order: Option<u32>,
+    axes: Option<(usize, usize)>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let n = order.unwrap_or(2);
+
+    // check if the parameters are valid
+    validate::positive("cutoff", cutoff)?;
+    validate::at_least("order", n as f64, 1.0)?;
+
+    frequency_filter(data, |k| 1.0 - butterworth_gain(k, cutoff, n), axes)
+}
+
+/// Apply a Butterworth band-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// # Description
+///
+/// This function keeps the spatial frequencies between the `low` and `high`
+/// cutoff frequencies with the product of a Butterworth high-pass filter at
+/// `low` and a Butterworth low-pass filter at `high`. The mean of each plane is
+/// removed. See `butterworth_low_pass` and `butterworth_high_pass`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array, with at least 2 dimensions.
+/// * `low`: The low cutoff frequency, in cycles per pixel. Must be > 0.0.
+/// * `high`: The high cutoff frequency, in cycles per pixel. Must be > `low`.
+/// * `order`: The filter order, n, default = 2.
+/// * `axes`: The (row, col) axes of the 2-dimensional planes, default = (0, 1).
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered array with the same shape as `data`.
+/// * `Err(ImgalError)`: If low is <= 0.0, high is <= low or order is 0. If the
+///   axes are invalid.
+pub fn butterworth_band_pass<T>(
+    data: ArrayViewD<T>,
+    low: f64,
+    high: f64,
+    order: Option<u32>,
+    axes: Option<(usize, usize)>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let n = order.unwrap_or(2);
+
+    // check if the parameters are valid
+    validate_band(low, high)?;
+    validate::at_least("order", n as f64, 1.0)?;
+
+    frequency_filter(
+        data,
+        |k| (1.0 - butterworth_gain(k, low, n)) * butterworth_gain(k, high, n),
+        axes,
+    )
+}
+
+/// The Gaussian low-pass transfer function.
+fn gaussian_gain(k: f64, cutoff: f64) -> f64 {
+    (-k * k / (2.0 * cutoff * cutoff)).exp()
+}
+
+/// The Butterworth low-pass transfer function.
+fn butterworth_gain(k: f64, cutoff: f64, order: u32) -> f64 {
+    1.0 / (1.0 + (k / cutoff).powi(2 * order as i32))
+}
+
+/// Check if the band-pass cutoff frequencies are valid.
+fn validate_band(low: f64, high: f64) -> Result<(), ImgalError> {
+    validate::positive("low", low)?;
+    if high <= low {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "high",
+            reason: format!("must be greater than low ({}) but got {}", low, high),
+        });
+    }
+
+    Ok(())
+}
+
+/// Compute the signed frequency, in cycles per pixel, of a DFT bin.
+fn signed_frequency(i: usize, n: usize) -> f64 {
+    if i <= n / 2 {
+        i as f64 / n as f64
+    } else {
+        i as f64 / n as f64 - 1.0
+    }
+}
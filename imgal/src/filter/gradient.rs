@@ -0,0 +1,190 @@
+use ndarray::{ArrayD, ArrayViewD, Zip};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::filter::spatial::{BorderMode, correlate_axis};
+use crate::traits::numeric::ToFloat64;
+
+/// The central difference kernel, in intensity units per pixel.
+const DIFFERENCE: [f64; 3] = [-0.5, 0.0, 0.5];
+
+/// The normalized Sobel smoothing kernel.
+const SOBEL_SMOOTH: [f64; 3] = [0.25, 0.5, 0.25];
+
+/// The normalized Scharr smoothing kernel.
+const SCHARR_SMOOTH: [f64; 3] = [3.0 / 16.0, 10.0 / 16.0, 3.0 / 16.0];
+
+/// First derivative operators to compute image gradients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientOperator {
+    /// The Sobel operator, smooths with a [1, 2, 1] kernel across the
+    /// derivative axis.
+    Sobel,
+    /// The Scharr operator, smooths with a [3, 10, 3] kernel across the
+    /// derivative axis. It is more rotationally invariant than the Sobel
+    /// operator.
+    Scharr,
+}
+
+impl GradientOperator {
+    /// The smoothing kernel of the operator.
+    fn smoothing(&self) -> &'static [f64; 3] {
+        match self {
+            GradientOperator::Sobel => &SOBEL_SMOOTH,
+            GradientOperator::Scharr => &SCHARR_SMOOTH,
+        }
+    }
+}
+
+/// Compute the gradient along an axis of an n-dimensional image with the Sobel
+/// operator.
+///
+/// # Description
+///
+/// This function computes the central difference along `axis` and smooths
+/// every other axis with the normalized [1, 2, 1] / 4 kernel. The operator is
+/// separable, each axis is computed as a 1-dimensional pass. The difference
+/// kernel is [-1, 0, 1] / 2, the gradient is in intensity units per pixel
+/// (_i.e._ a ramp with a slope of 1.0 has a gradient of 1.0).
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `axis`: The derivative axis.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The gradient along `axis` with the same shape as
+///   `data`.
+/// * `Err(ImgalError)`: If axis is out of bounds.
+pub fn sobel<T>(
+    data: ArrayViewD<T>,
+    axis: usize,
+    border: Option<BorderMode>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the axis parameter is valid
+    validate::axis(axis, data.ndim())?;
+
+    Ok(gradient(
+        data.mapv(|v| v.to_f64()),
+        axis,
+        GradientOperator::Sobel,
+        border.unwrap_or(BorderMode::Reflect),
+    ))
+}
+
+/// Compute the gradient along an axis of an n-dimensional image with the
+/// Scharr operator.
+///
+/// # Description
+///
+/// This function computes the central difference along `axis` and smooths
+/// every other axis with the normalized [3, 10, 3] / 16 kernel. The Scharr
+/// operator has a better rotational invariance than the Sobel operator, which
+/// gives more accurate gradient directions. The gradient is in intensity units
+/// per pixel, see `sobel`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `axis`: The derivative axis.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The gradient along `axis` with the same shape as
+///   `data`.
+/// * `Err(ImgalError)`: If axis is out of bounds.
+pub fn scharr<T>(
+    data: ArrayViewD<T>,
+    axis: usize,
+    border: Option<BorderMode>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the axis parameter is valid
+    validate::axis(axis, data.ndim())?;
+
+    Ok(gradient(
+        data.mapv(|v| v.to_f64()),
+        axis,
+        GradientOperator::Scharr,
+        border.unwrap_or(BorderMode::Reflect),
+    ))
+}
+
+/// Compute the gradient magnitude of an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the gradient along each axis with a first derivative
+/// operator and combines them into the Euclidean norm:
+///
+/// ```text
+/// |∇I| = √(Σᵢ (∂I / ∂xᵢ)²)
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `operator`: The first derivative operator, default =
+///   `GradientOperator::Sobel`.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The gradient magnitude with the same shape as `data`.
+/// * `Err(ImgalError)`: If data has no axes.
+pub fn gradient_magnitude<T>(
+    data: ArrayViewD<T>,
+    operator: Option<GradientOperator>,
+    border: Option<BorderMode>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let operator = operator.unwrap_or(GradientOperator::Sobel);
+    let border = border.unwrap_or(BorderMode::Reflect);
+
+    // check if the data has at least one axis
+    validate::axis(0, data.ndim())?;
+
+    // accumulate the squared gradient of each axis
+    let input = data.mapv(|v| v.to_f64());
+    let mut output = ArrayD::<f64>::zeros(input.raw_dim());
+    (0..input.ndim()).for_each(|ax| {
+        let g = gradient(input.clone(), ax, operator, border);
+        Zip::from(&mut output)
+            .and(&g)
+            .par_for_each(|o, &v| *o += v * v);
+    });
+    output.par_mapv_inplace(f64::sqrt);
+
+    Ok(output)
+}
+
+/// Compute the gradient along an axis with a separable first derivative
+/// operator.
+fn gradient(
+    mut data: ArrayD<f64>,
+    axis: usize,
+    operator: GradientOperator,
+    border: BorderMode,
+) -> ArrayD<f64> {
+    (0..data.ndim()).for_each(|ax| {
+        let kernel = if ax == axis {
+            &DIFFERENCE
+        } else {
+            operator.smoothing()
+        };
+        correlate_axis(&mut data, ax, kernel, border);
+    });
+
+    data
+}
@@ -0,0 +1,357 @@
+use std::f64::consts::PI;
+
+use ndarray::{ArrayD, ArrayViewD, Zip};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::filter::spatial::{BorderMode, gaussian_derivative, validate_sigma};
+use crate::traits::numeric::ToFloat64;
+
+/// Apply a Laplacian of Gaussian (LoG) filter to an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the sum of the second order Gaussian derivatives
+/// along each axis with a non-zero sigma:
+///
+/// ```text
+/// LoG = Σᵢ ∂²(G_σ ∗ I) / ∂xᵢ²
+/// ```
+///
+/// Bright blobs give strong negative responses and dark blobs give strong
+/// positive responses, the strongest response is at blobs with a radius of
+/// about √n × σ (n is the number of filtered axes). Multiply the result by σ²
+/// to compare responses across scales.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `sigma`: The standard deviation of the Gaussian along each axis, in
+///   pixels. Must have one value per axis. A sigma of 0.0 does not filter or
+///   differentiate that axis (_e.g._ the channel axis of a multi-channel image).
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The LoG filtered image with the same shape as `data`.
+/// * `Err(ImgalError)`: If sigma does not have one value per axis. If a sigma
+///   is < 0.0 or not finite.
+pub fn log<T>(
+    data: ArrayViewD<T>,
+    sigma: &[f64],
+    border: Option<BorderMode>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let border = border.unwrap_or(BorderMode::Reflect);
+
+    // check if the sigma parameter is valid
+    validate_sigma("sigma", sigma, data.ndim())?;
+
+    // sum the second order derivative of each filtered axis
+    let data = data.mapv(|v| v.to_f64());
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    (0..data.ndim())
+        .filter(|&ax| sigma[ax] > 0.0)
+        .for_each(|ax| {
+            let mut order = vec![0; data.ndim()];
+            order[ax] = 2;
+            let d2 = gaussian_derivative(data.view(), sigma, &order, border);
+            Zip::from(&mut output).and(&d2).par_for_each(|o, d| *o += d);
+        });
+
+    Ok(output)
+}
+
+/// Enhance tubular structures in a 2 or 3-dimensional image with the Frangi
+/// vesselness filter.
+///
+/// # Description
+///
+/// This function computes the eigenvalues of the scale normalized (σ²) Hessian
+/// matrix of each pixel at each scale, sorted by magnitude (|λ₁| <= |λ₂| <=
+/// |λ₃|). For bright tubes the large eigenvalues are negative and the
+/// vesselness of a 2-dimensional image is:
+///
+/// ```text
+/// V = exp(-R_B² / (2β²)) × (1 - exp(-S² / (2γ²)))
+/// ```
+///
+/// with the blobness R_B = λ₁ / λ₂ and the structureness S = √(Σ λᵢ²). In 3
+/// dimensions:
+///
+/// ```text
+/// V = (1 - exp(-R_A² / (2α²))) × exp(-R_B² / (2β²)) × (1 - exp(-S² / (2γ²)))
+/// ```
+///
+/// with the plate-likeness R_A = |λ₂| / |λ₃| and the blobness
+/// R_B = |λ₁| / √|λ₂λ₃|. Pixels with a large eigenvalue of the wrong sign are
+/// 0.0. The output is the maximum vesselness over all scales.
+///
+/// # Arguments
+///
+/// * `data`: The input 2 or 3-dimensional image.
+/// * `sigmas`: The scales (_i.e._ isotropic Gaussian standard deviations) in
+///   pixels, matched to the tube radii to enhance. Must be > 0.0.
+/// * `alpha`: The plate-like structure sensitivity, α, of 3-dimensional
+///   images, default = 0.5.
+/// * `beta`: The blob-like structure sensitivity, β, default = 0.5.
+/// * `gamma`: The structureness sensitivity, γ. If `None`, then half of the
+///   maximum structureness of each scale is used.
+/// * `bright`: If `true`, bright tubes on a dark background are enhanced,
+///   otherwise dark tubes on a bright background. Default = `true`.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The vesselness image with the same shape as `data`,
+///   with values in [0, 1].
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional. If sigmas is empty
+///   or a sigma is <= 0.0. If alpha, beta or gamma is <= 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1007/BFb0056195>
+pub fn frangi<T>(
+    data: ArrayViewD<T>,
+    sigmas: &[f64],
+    alpha: Option<f64>,
+    beta: Option<f64>,
+    gamma: Option<f64>,
+    bright: Option<bool>,
+    border: Option<BorderMode>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let alpha = alpha.unwrap_or(0.5);
+    let beta = beta.unwrap_or(0.5);
+    let bright = bright.unwrap_or(true);
+    let border = border.unwrap_or(BorderMode::Reflect);
+
+    // check if the parameters are valid
+    validate_scales(data.ndim(), sigmas)?;
+    validate::positive("alpha", alpha)?;
+    validate::positive("beta", beta)?;
+    if let Some(g) = gamma {
+        validate::positive("gamma", g)?;
+    }
+
+    let data = data.mapv(|v| v.to_f64());
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    sigmas.iter().for_each(|&s| {
+        // sort the eigenvalues by magnitude and find the structureness scale
+        let mut eig = hessian_eigenvalues(data.view(), s, border);
+        eig.par_iter_mut().for_each(|e| {
+            e.sort_unstable_by(|a, b| a.abs().total_cmp(&b.abs()));
+        });
+        let c = gamma.unwrap_or_else(|| {
+            0.5 * eig
+                .iter()
+                .map(|e| e.iter().map(|v| v * v).sum::<f64>().sqrt())
+                .fold(0.0, f64::max)
+        });
+        let (two_a2, two_b2, two_c2) = (
+            2.0 * alpha * alpha,
+            2.0 * beta * beta,
+            (2.0 * c * c).max(f64::MIN_POSITIVE),
+        );
+
+        // keep the maximum vesselness over the scales
+        output
+            .as_slice_mut()
+            .unwrap()
+            .par_iter_mut()
+            .zip(eig.par_iter())
+            .for_each(|(o, e)| {
+                let large = &e[1..];
+                let wrong_sign = large
+                    .iter()
+                    .any(|&l| if bright { l > 0.0 } else { l < 0.0 });
+                if wrong_sign || large.contains(&0.0) {
+                    return;
+                }
+                let s2: f64 = e.iter().map(|v| v * v).sum();
+                let structure = 1.0 - (-s2 / two_c2).exp();
+                let v = if e.len() == 2 {
+                    let rb = e[0] / e[1];
+                    (-rb * rb / two_b2).exp() * structure
+                } else {
+                    let ra = e[1].abs() / e[2].abs();
+                    let rb = e[0].abs() / (e[1] * e[2]).abs().sqrt();
+                    (1.0 - (-ra * ra / two_a2).exp()) * (-rb * rb / two_b2).exp() * structure
+                };
+                *o = o.max(v);
+            });
+    });
+
+    Ok(output)
+}
+
+/// Enhance tubular structures in a 2 or 3-dimensional image with the Sato
+/// tubeness filter.
+///
+/// # Description
+///
+/// This function computes the eigenvalues of the scale normalized (σ²) Hessian
+/// matrix of each pixel at each scale. For bright tubes the tubeness is the
+/// geometric mean of the negated eigenvalues, clipped to 0.0, excluding the
+/// eigenvalue along the tube (_i.e._ the largest one):
+///
+/// ```text
+/// T = (Πᵢ max(-λᵢ, 0))^(1 / (n - 1)),  i = 1..n - 1,  λ₁ <= ... <= λₙ
+/// ```
+///
+/// The output is the maximum tubeness over all scales.
+///
+/// # Arguments
+///
+/// * `data`: The input 2 or 3-dimensional image.
+/// * `sigmas`: The scales (_i.e._ isotropic Gaussian standard deviations) in
+///   pixels, matched to the tube radii to enhance. Must be > 0.0.
+/// * `bright`: If `true`, bright tubes on a dark background are enhanced,
+///   otherwise dark tubes on a bright background. Default = `true`.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The tubeness image with the same shape as `data`.
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional. If sigmas is empty
+///   or a sigma is <= 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1016/S1361-8415(98)80009-1>
+pub fn sato<T>(
+    data: ArrayViewD<T>,
+    sigmas: &[f64],
+    bright: Option<bool>,
+    border: Option<BorderMode>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bright = bright.unwrap_or(true);
+    let border = border.unwrap_or(BorderMode::Reflect);
+
+    // check if the parameters are valid
+    validate_scales(data.ndim(), sigmas)?;
+
+    // dark tubes are bright tubes of the negated image
+    let sign = if bright { 1.0 } else { -1.0 };
+    let data = data.mapv(|v| sign * v.to_f64());
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    sigmas.iter().for_each(|&s| {
+        let eig = hessian_eigenvalues(data.view(), s, border);
+        output
+            .as_slice_mut()
+            .unwrap()
+            .par_iter_mut()
+            .zip(eig.par_iter())
+            .for_each(|(o, e)| {
+                let mut e = e.clone();
+                e.sort_unstable_by(|a, b| a.total_cmp(b));
+                let n = e.len() - 1;
+                let v = e[..n]
+                    .iter()
+                    .map(|l| (-l).max(0.0))
+                    .product::<f64>()
+                    .powf(1.0 / n as f64);
+                *o = o.max(v);
+            });
+    });
+
+    Ok(output)
+}
+
+/// Check if the image is 2 or 3-dimensional and the scales are valid.
+fn validate_scales(ndim: usize, sigmas: &[f64]) -> Result<(), ImgalError> {
+    if ndim != 2 && ndim != 3 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!("must be 2 or 3-dimensional but got {} dimensions", ndim),
+        });
+    }
+    if sigmas.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "sigmas",
+            reason: "must have at least one scale".to_string(),
+        });
+    }
+    sigmas
+        .iter()
+        .try_for_each(|&s| validate::positive("sigmas", s))
+}
+
+/// Compute the eigenvalues of the scale normalized Hessian matrix of each pixel
+/// of a 2 or 3-dimensional image, in standard (row-major) pixel order.
+fn hessian_eigenvalues(data: ArrayViewD<f64>, sigma: f64, border: BorderMode) -> Vec<Vec<f64>> {
+    // compute the upper triangle Hessian elements, (0, 0), (0, 1), ..., (n, n)
+    let n = data.ndim();
+    let sig = vec![sigma; n];
+    let s2 = sigma * sigma;
+    let elements: Vec<Vec<f64>> = (0..n)
+        .flat_map(|i| (i..n).map(move |j| (i, j)))
+        .map(|(i, j)| {
+            let mut order = vec![0; n];
+            order[i] += 1;
+            order[j] += 1;
+            gaussian_derivative(data.view(), &sig, &order, border)
+                .iter()
+                .map(|v| v * s2)
+                .collect()
+        })
+        .collect();
+
+    (0..data.len())
+        .into_par_iter()
+        .map(|p| {
+            if n == 2 {
+                eigenvalues_2x2(elements[0][p], elements[1][p], elements[2][p])
+            } else {
+                eigenvalues_3x3(
+                    [elements[0][p], elements[1][p], elements[2][p]],
+                    [elements[3][p], elements[4][p], elements[5][p]],
+                )
+            }
+        })
+        .collect()
+}
+
+/// Compute the eigenvalues of a symmetric 2 x 2 matrix [[a, b], [b, d]].
+fn eigenvalues_2x2(a: f64, b: f64, d: f64) -> Vec<f64> {
+    let mean = 0.5 * (a + d);
+    let r = (0.25 * (a - d) * (a - d) + b * b).sqrt();
+
+    vec![mean - r, mean + r]
+}
+
+/// Compute the eigenvalues of a symmetric 3 x 3 matrix from its first row,
+/// (a₁₁, a₁₂, a₁₃), and the rest of its upper triangle, (a₂₂, a₂₃, a₃₃).
+fn eigenvalues_3x3(row: [f64; 3], rest: [f64; 3]) -> Vec<f64> {
+    let [a11, a12, a13] = row;
+    let [a22, a23, a33] = rest;
+    let p1 = a12 * a12 + a13 * a13 + a23 * a23;
+    if p1 == 0.0 {
+        return vec![a11, a22, a33];
+    }
+
+    // trigonometric solution of the characteristic polynomial
+    let q = (a11 + a22 + a33) / 3.0;
+    let p2 = (a11 - q).powi(2) + (a22 - q).powi(2) + (a33 - q).powi(2) + 2.0 * p1;
+    let p = (p2 / 6.0).sqrt();
+    let (b11, b22, b33) = ((a11 - q) / p, (a22 - q) / p, (a33 - q) / p);
+    let (b12, b13, b23) = (a12 / p, a13 / p, a23 / p);
+    let det = b11 * (b22 * b33 - b23 * b23) - b12 * (b12 * b33 - b23 * b13)
+        + b13 * (b12 * b23 - b22 * b13);
+    let phi = (0.5 * det).clamp(-1.0, 1.0).acos() / 3.0;
+    let large = q + 2.0 * p * phi.cos();
+    let small = q + 2.0 * p * (phi + 2.0 * PI / 3.0).cos();
+
+    vec![small, 3.0 * q - large - small, large]
+}
@@ -1,3 +1,27 @@
 //! Filter functions.
+pub mod background;
+pub use background::{rolling_ball, top_hat};
 pub mod convolve;
-pub use convolve::{fft_convolve_1d, fft_deconvolve_1d};
+pub use convolve::{
+    ConvolveMethod, convolve, fft_circular_convolve_1d, fft_convolve_1d, fft_convolve_2d,
+    fft_convolve_axis, fft_convolve_nd, fft_deconvolve_1d,
+};
+pub mod deconvolve;
+pub use deconvolve::wiener;
+pub mod exposure;
+pub use exposure::{clahe, equalize_histogram, match_histogram, rescale_percentile};
+pub mod frequency;
+pub use frequency::{
+    butterworth_band_pass, butterworth_high_pass, butterworth_low_pass, frequency_filter,
+    gaussian_band_pass, gaussian_high_pass, gaussian_low_pass,
+};
+pub mod gradient;
+pub use gradient::{GradientOperator, gradient_magnitude, scharr, sobel};
+pub mod hessian;
+pub use hessian::{frangi, log, sato};
+pub mod orientation;
+pub use orientation::max_response;
+pub mod spatial;
+pub use spatial::{
+    BorderMode, GaussianMethod, difference_of_gaussians, gaussian, gaussian_mut, unsharp_mask,
+};
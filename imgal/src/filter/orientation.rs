@@ -0,0 +1,85 @@
+use ndarray::{Array2, ArrayView2, ArrayView4, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::filter::convolve::convolve;
+use crate::filter::spatial::BorderMode;
+use crate::traits::numeric::ToFloat64;
+
+/// Apply a bank of oriented filters to a 2-dimensional image and compute the
+/// maximum response and orientation maps.
+///
+/// # Description
+///
+/// This function convolves the image with every kernel of an oriented filter
+/// bank (see `kernel::orientation_bank`) and keeps, for each pixel, the
+/// largest response over all scales and orientations and the orientation
+/// (angle) of that response. The maximum response map enhances oriented
+/// structures (_e.g._ fibers, edges or textures) regardless of their
+/// direction, and the orientation map gives their local direction.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `bank`: The kernel bank with shape (scales, angles, rows, cols).
+/// * `angles`: The orientation of each kernel along axis 1 of the bank, in
+///   radians.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Array2<f64>))`: The maximum response map and the
+///   orientation map (in radians), both with the same shape as `data`.
+/// * `Err(ImgalError)`: If the bank is empty. If angles does not have one
+///   value per bank orientation.
+pub fn max_response<T>(
+    data: ArrayView2<T>,
+    bank: ArrayView4<f64>,
+    angles: &[f64],
+    border: Option<BorderMode>,
+) -> Result<(Array2<f64>, Array2<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the bank and angles parameters are valid
+    if bank.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "bank",
+            reason: format!("must not be empty but got shape {:?}", bank.shape()),
+        });
+    }
+    if angles.len() != bank.len_of(Axis(1)) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "angles",
+            reason: format!(
+                "must have one value per bank orientation, {} but got {}",
+                bank.len_of(Axis(1)),
+                angles.len()
+            ),
+        });
+    }
+
+    // keep the largest response of each pixel and its orientation
+    let input = data.mapv(|v| v.to_f64()).into_dyn();
+    let mut response = Array2::<f64>::from_elem(data.raw_dim(), f64::NEG_INFINITY);
+    let mut orientation = Array2::<f64>::zeros(data.raw_dim());
+    for scale_bank in bank.outer_iter() {
+        for (kernel, &theta) in scale_bank.outer_iter().zip(angles.iter()) {
+            let filtered = convolve(input.view(), kernel.into_dyn(), border, None)?;
+            Zip::from(&mut response)
+                .and(&mut orientation)
+                .and(
+                    &filtered
+                        .into_dimensionality()
+                        .expect("a 2-dimensional image"),
+                )
+                .par_for_each(|r, o, &f| {
+                    if f > *r {
+                        *r = f;
+                        *o = theta;
+                    }
+                });
+        }
+    }
+
+    Ok((response, orientation))
+}
@@ -0,0 +1,556 @@
+use ndarray::{ArrayD, ArrayViewD, ArrayViewMutD, Axis, Zip};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::traits::numeric::{Float, ToFloat64};
+
+/// Border modes to extend an array past its edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BorderMode {
+    /// Reflect about the edge, repeating the edge value (_i.e._ d c b a | a b
+    /// c d | d c b a).
+    Reflect,
+    /// Extend with a constant value (_i.e._ k k k k | a b c d | k k k k).
+    Constant(f64),
+    /// Wrap around to the opposite edge (_i.e._ a b c d | a b c d | a b c d).
+    Wrap,
+}
+
+/// Methods to compute a 1-dimensional Gaussian filter pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GaussianMethod {
+    /// Direct convolution with a sampled Gaussian kernel truncated at 4 sigma.
+    /// The cost grows linearly with sigma.
+    Direct,
+    /// The Deriche 4th order recursive (_i.e._ infinite impulse response)
+    /// Gaussian approximation. The cost does not depend on sigma, which makes
+    /// it faster for large sigmas at a small loss of accuracy.
+    Recursive,
+}
+
+/// Apply a Gaussian filter to an n-dimensional image.
+///
+/// # Description
+///
+/// This function smooths an n-dimensional image (_e.g._ a 2D image or a 3D
+/// volume) with a Gaussian kernel:
+///
+/// ```text
+/// G(x) = exp(-x² / (2σ²))
+/// ```
+///
+/// The n-dimensional Gaussian is separable, the filter is computed as one
+/// 1-dimensional pass along each axis with a non-zero sigma. The lanes of each
+/// pass are filtered in parallel. Values past the edges of the image are
+/// extended with the `border` mode.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `sigma`: The standard deviation of the Gaussian kernel along each axis,
+///   in pixels. Must have one value per axis. A sigma of 0.0 does not filter
+///   that axis (_e.g._ the channel axis of a multi-channel image).
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+/// * `method`: The 1-dimensional pass method, default = `GaussianMethod::Direct`.
+///   The recursive method requires a sigma >= 0.5, smaller sigmas use the
+///   direct method.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered image with the same shape as `data`.
+/// * `Err(ImgalError)`: If sigma does not have one value per axis. If a sigma
+///   is < 0.0 or not finite.
+pub fn gaussian<T>(
+    data: ArrayViewD<T>,
+    sigma: &[f64],
+    border: Option<BorderMode>,
+    method: Option<GaussianMethod>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let border = border.unwrap_or(BorderMode::Reflect);
+    let method = method.unwrap_or(GaussianMethod::Direct);
+
+    // check if the sigma parameter is valid
+    validate_sigma("sigma", sigma, data.ndim())?;
+
+    // filter each axis with a separable 1-dimensional pass
+    let mut output = data.mapv(|v| v.to_f64());
+    gaussian_passes(output.view_mut(), sigma, border, method);
+
+    Ok(output)
+}
+
+/// Apply a Gaussian filter to an n-dimensional floating point image in place.
+///
+/// # Description
+///
+/// This function smooths an n-dimensional image with a Gaussian kernel, see
+/// `gaussian`. The image keeps its `f32` or `f64` element type, each lane is
+/// filtered in `f64` and written back, so a large `f32` stack is filtered
+/// without an `f64` copy.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional image to filter.
+/// * `sigma`: The standard deviation of the Gaussian kernel along each axis,
+///   in pixels. Must have one value per axis. A sigma of 0.0 does not filter
+///   that axis.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+/// * `method`: The 1-dimensional pass method, default = `GaussianMethod::Direct`.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the image was filtered.
+/// * `Err(ImgalError)`: If sigma does not have one value per axis. If a sigma
+///   is < 0.0 or not finite.
+pub fn gaussian_mut<T>(
+    data: ArrayViewMutD<T>,
+    sigma: &[f64],
+    border: Option<BorderMode>,
+    method: Option<GaussianMethod>,
+) -> Result<(), ImgalError>
+where
+    T: Float,
+{
+    // set optional parameters if needed
+    let border = border.unwrap_or(BorderMode::Reflect);
+    let method = method.unwrap_or(GaussianMethod::Direct);
+
+    // check if the sigma parameter is valid
+    validate_sigma("sigma", sigma, data.ndim())?;
+
+    // filter each axis with a separable 1-dimensional pass
+    gaussian_passes(data, sigma, border, method);
+
+    Ok(())
+}
+
+/// Compute a Gaussian derivative of an n-dimensional image, `order` is the
+/// derivative order (0, 1 or 2) along each axis. The sigma and order slices
+/// must have one value per axis and a non-zero order requires a non-zero
+/// sigma.
+pub(crate) fn gaussian_derivative(
+    data: ArrayViewD<f64>,
+    sigma: &[f64],
+    order: &[usize],
+    border: BorderMode,
+) -> ArrayD<f64> {
+    let mut output = data.to_owned();
+    sigma
+        .iter()
+        .zip(order.iter())
+        .enumerate()
+        .filter(|&(_, (s, _))| *s > 0.0)
+        .for_each(|(ax, (&s, &o))| {
+            apply_pass(
+                output.view_mut(),
+                ax,
+                &Pass::Direct(gaussian_kernel(s, o)),
+                border,
+            );
+        });
+
+    output
+}
+
+/// Sharpen an n-dimensional image with an unsharp mask.
+///
+/// # Description
+///
+/// This function enhances edges and spots by adding the difference between the
+/// image and its Gaussian blurred copy (_i.e._ the "unsharp mask") back to the
+/// image:
+///
+/// ```text
+/// O = I + amount × (I - G_σ ∗ I)
+/// ```
+///
+/// See `gaussian` for the blur.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `sigma`: The standard deviation of the Gaussian blur along each axis, in
+///   pixels. Must have one value per axis.
+/// * `amount`: The sharpening strength, 0.0 returns the input image. Must be
+///   >= 0.0.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+/// * `method`: The 1-dimensional pass method, default = `GaussianMethod::Direct`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The sharpened image with the same shape as `data`.
+/// * `Err(ImgalError)`: If amount is < 0.0. If sigma is invalid, see
+///   `gaussian`.
+pub fn unsharp_mask<T>(
+    data: ArrayViewD<T>,
+    sigma: &[f64],
+    amount: f64,
+    border: Option<BorderMode>,
+    method: Option<GaussianMethod>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate::at_least("amount", amount, 0.0)?;
+
+    let mut output = gaussian(data.view(), sigma, border, method)?;
+    Zip::from(&mut output).and(&data).par_for_each(|o, d| {
+        let v = d.to_f64();
+        *o = v + amount * (v - *o);
+    });
+
+    Ok(output)
+}
+
+/// Apply a difference of Gaussians (DoG) filter to an n-dimensional image.
+///
+/// # Description
+///
+/// This function subtracts a strongly blurred copy of the image from a weakly
+/// blurred copy:
+///
+/// ```text
+/// O = G_σ_low ∗ I - G_σ_high ∗ I
+/// ```
+///
+/// The result is a band-pass filter that enhances structures (_e.g._ spots)
+/// with a size between the two sigmas and removes the smooth background. See
+/// `gaussian` for the blurs.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `sigma_low`: The standard deviation of the weak Gaussian blur along each
+///   axis, in pixels. Must have one value per axis.
+/// * `sigma_high`: The standard deviation of the strong Gaussian blur along
+///   each axis, in pixels. Must have one value per axis, each >= the matching
+///   `sigma_low` value.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+/// * `method`: The 1-dimensional pass method, default = `GaussianMethod::Direct`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The filtered image with the same shape as `data`.
+/// * `Err(ImgalError)`: If a `sigma_high` value is < the matching `sigma_low`
+///   value. If a sigma is invalid, see `gaussian`.
+pub fn difference_of_gaussians<T>(
+    data: ArrayViewD<T>,
+    sigma_low: &[f64],
+    sigma_high: &[f64],
+    border: Option<BorderMode>,
+    method: Option<GaussianMethod>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the sigma parameters are valid
+    if let Some((l, h)) = sigma_low.iter().zip(sigma_high.iter()).find(|(l, h)| h < l) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "sigma_high",
+            reason: format!("must be >= sigma_low ({}) but got {}", l, h),
+        });
+    }
+
+    let mut output = gaussian(data.view(), sigma_low, border, method)?;
+    let high = gaussian(data, sigma_high, border, method)?;
+    Zip::from(&mut output)
+        .and(&high)
+        .par_for_each(|o, h| *o -= h);
+
+    Ok(output)
+}
+
+/// A 1-dimensional Gaussian filter pass.
+enum Pass {
+    /// The normalized kernel, with radius (len - 1) / 2.
+    Direct(Vec<f64>),
+    /// The recursive coefficients and the padding radius.
+    Recursive(Deriche, usize),
+}
+
+impl Pass {
+    /// The number of padded values on each side of a lane.
+    fn radius(&self) -> usize {
+        match self {
+            Pass::Direct(k) => k.len() / 2,
+            Pass::Recursive(_, r) => *r,
+        }
+    }
+
+    /// Filter a padded lane, the values within radius of the ends are not
+    /// valid.
+    fn apply(&self, padded: &[f64]) -> Vec<f64> {
+        match self {
+            Pass::Direct(kernel) => {
+                let r = kernel.len() / 2;
+                let mut out = vec![0.0; padded.len()];
+                (r..padded.len().saturating_sub(r)).for_each(|i| {
+                    out[i] = kernel
+                        .iter()
+                        .zip(padded[i - r..=i + r].iter())
+                        .map(|(k, v)| k * v)
+                        .sum();
+                });
+                out
+            }
+            Pass::Recursive(c, _) => {
+                let n = padded.len();
+                let mut out = vec![0.0; n];
+
+                // causal pass, the history is the steady state of the first value
+                let first = padded[0];
+                let mut x = [first; 4];
+                let mut y = [first * c.causal_gain; 4];
+                (0..n).for_each(|i| {
+                    x = [padded[i], x[0], x[1], x[2]];
+                    let v = c.n[0] * x[0] + c.n[1] * x[1] + c.n[2] * x[2] + c.n[3] * x[3]
+                        - c.d[0] * y[0]
+                        - c.d[1] * y[1]
+                        - c.d[2] * y[2]
+                        - c.d[3] * y[3];
+                    y = [v, y[0], y[1], y[2]];
+                    out[i] = v;
+                });
+
+                // anti-causal pass, the history is the steady state of the last
+                // value
+                let last = padded[n - 1];
+                let mut x = [last; 4];
+                let mut y = [last * c.anti_causal_gain; 4];
+                (0..n).rev().for_each(|i| {
+                    let v = c.m[0] * x[0] + c.m[1] * x[1] + c.m[2] * x[2] + c.m[3] * x[3]
+                        - c.d[0] * y[0]
+                        - c.d[1] * y[1]
+                        - c.d[2] * y[2]
+                        - c.d[3] * y[3];
+                    x = [padded[i], x[0], x[1], x[2]];
+                    y = [v, y[0], y[1], y[2]];
+                    out[i] += v;
+                });
+                out
+            }
+        }
+    }
+}
+
+/// The normalized Deriche 4th order recursive Gaussian coefficients.
+struct Deriche {
+    /// The causal input coefficients, (n00, n11, n22, n33).
+    n: [f64; 4],
+    /// The anti-causal input coefficients, (m11, m22, m33, m44).
+    m: [f64; 4],
+    /// The output coefficients, (d11, d22, d33, d44).
+    d: [f64; 4],
+    /// The causal output of a constant unit input.
+    causal_gain: f64,
+    /// The anti-causal output of a constant unit input.
+    anti_causal_gain: f64,
+}
+
+impl Deriche {
+    /// Compute the Deriche coefficients of a sigma, normalized to a unit sum
+    /// impulse response.
+    fn new(sigma: f64) -> Self {
+        // Deriche's fit of the Gaussian
+        let (a0, a1, b0, b1) = (1.680, 3.735, 1.783, 1.723);
+        let (w0, w1, c0, c1) = (0.6318, 1.997, -0.6803, -0.2598);
+        let (cos0, sin0) = ((w0 / sigma).cos(), (w0 / sigma).sin());
+        let (cos1, sin1) = ((w1 / sigma).cos(), (w1 / sigma).sin());
+        let (e0, e1) = ((-b0 / sigma).exp(), (-b1 / sigma).exp());
+
+        let n00 = a0 + c0;
+        let n11 =
+            e1 * (c1 * sin1 - (c0 + 2.0 * a0) * cos1) + e0 * (a1 * sin0 - (2.0 * c0 + a0) * cos0);
+        let n22 = 2.0 * e0 * e1 * ((a0 + c0) * cos1 * cos0 - a1 * cos1 * sin0 - c1 * cos0 * sin1)
+            + c0 * e0 * e0
+            + a0 * e1 * e1;
+        let n33 = e1 * e0 * e0 * (c1 * sin1 - c0 * cos1) + e0 * e1 * e1 * (a1 * sin0 - a0 * cos0);
+        let d11 = -2.0 * e1 * cos1 - 2.0 * e0 * cos0;
+        let d22 = 4.0 * cos1 * cos0 * e0 * e1 + e1 * e1 + e0 * e0;
+        let d33 = -2.0 * cos0 * e0 * e1 * e1 - 2.0 * cos1 * e1 * e0 * e0;
+        let d44 = e0 * e0 * e1 * e1;
+        let n = [n00, n11, n22, n33];
+        let m = [
+            n11 - d11 * n00,
+            n22 - d22 * n00,
+            n33 - d33 * n00,
+            -d44 * n00,
+        ];
+        let d = [d11, d22, d33, d44];
+
+        // normalize the impulse response to a unit sum
+        let d_sum = 1.0 + d.iter().sum::<f64>();
+        let n_sum = n.iter().sum::<f64>();
+        let m_sum = m.iter().sum::<f64>();
+        let scale = d_sum / (n_sum + m_sum);
+        let n = n.map(|v| v * scale);
+        let m = m.map(|v| v * scale);
+
+        Deriche {
+            n,
+            m,
+            d,
+            causal_gain: n_sum * scale / d_sum,
+            anti_causal_gain: m_sum * scale / d_sum,
+        }
+    }
+}
+
+/// Check if a per axis sigma slice has one finite value >= 0.0 per axis.
+pub(crate) fn validate_sigma(
+    param_name: &'static str,
+    sigma: &[f64],
+    ndim: usize,
+) -> Result<(), ImgalError> {
+    if sigma.len() != ndim {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: format!(
+                "must have one value per axis, {} but got {}",
+                ndim,
+                sigma.len()
+            ),
+        });
+    }
+    if let Some(s) = sigma.iter().find(|s| !s.is_finite() || **s < 0.0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: format!("must be finite and >= 0.0 but got {}", s),
+        });
+    }
+
+    Ok(())
+}
+
+/// Correlate each lane along an axis of an array in place with an odd length
+/// 1-dimensional kernel.
+pub(crate) fn correlate_axis(
+    data: &mut ArrayD<f64>,
+    axis: usize,
+    kernel: &[f64],
+    border: BorderMode,
+) {
+    apply_pass(
+        data.view_mut(),
+        axis,
+        &Pass::Direct(kernel.to_vec()),
+        border,
+    );
+}
+
+/// Filter each lane along an axis of an array in place with a 1-dimensional
+/// pass.
+fn apply_pass<T>(mut data: ArrayViewMutD<T>, axis: usize, pass: &Pass, border: BorderMode)
+where
+    T: Float,
+{
+    data.lanes_mut(Axis(axis))
+        .into_iter()
+        .par_bridge()
+        .for_each(|mut ln| {
+            if ln.is_empty() {
+                return;
+            }
+            let lane: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            let padded = pad_lane(&lane, pass.radius(), border);
+            let filtered = pass.apply(&padded);
+            ln.iter_mut()
+                .zip(filtered.iter().skip(pass.radius()))
+                .for_each(|(v, f)| *v = T::from_f64(*f));
+        });
+}
+
+/// Filter each axis with a non-zero sigma with a separable 1-dimensional
+/// Gaussian pass in place.
+fn gaussian_passes<T>(
+    mut data: ArrayViewMutD<T>,
+    sigma: &[f64],
+    border: BorderMode,
+    method: GaussianMethod,
+) where
+    T: Float,
+{
+    sigma
+        .iter()
+        .enumerate()
+        .filter(|&(_, s)| *s > 0.0)
+        .for_each(|(ax, &s)| {
+            let pass = if method == GaussianMethod::Recursive && s >= 0.5 {
+                Pass::Recursive(Deriche::new(s), gaussian_radius(s, method))
+            } else {
+                Pass::Direct(gaussian_kernel(s, 0))
+            };
+            apply_pass(data.view_mut(), ax, &pass, border);
+        });
+}
+
+/// Create a normalized 1-dimensional Gaussian (order 0) or Gaussian derivative
+/// (order 1 or 2) correlation kernel truncated at 4 sigma.
+fn gaussian_kernel(sigma: f64, order: usize) -> Vec<f64> {
+    let r = gaussian_radius(sigma, GaussianMethod::Direct) as isize;
+    let s2 = sigma * sigma;
+    let kernel: Vec<f64> = (-r..=r)
+        .map(|x| (-((x * x) as f64) / (2.0 * s2)).exp())
+        .collect();
+    let total: f64 = kernel.iter().sum();
+
+    // correlating with g'(-x) and g''(x) computes the convolution derivatives
+    (-r..=r)
+        .zip(kernel)
+        .map(|(x, k)| {
+            let x = x as f64;
+            let g = k / total;
+            match order {
+                0 => g,
+                1 => x / s2 * g,
+                _ => (x * x / (s2 * s2) - 1.0 / s2) * g,
+            }
+        })
+        .collect()
+}
+
+/// Extend a lane by `radius` values on each side with a border mode.
+fn pad_lane(lane: &[f64], radius: usize, border: BorderMode) -> Vec<f64> {
+    let cval = match border {
+        BorderMode::Constant(c) => c,
+        _ => 0.0,
+    };
+    let r = radius as isize;
+
+    (-r..lane.len() as isize + r)
+        .map(|i| border_index(i, lane.len(), border).map_or(cval, |j| lane[j]))
+        .collect()
+}
+
+/// Map an index past the edges of a lane of length `n` to a lane index, `None`
+/// if the index takes the constant border value.
+pub(crate) fn border_index(idx: isize, n: usize, border: BorderMode) -> Option<usize> {
+    let n_i = n as isize;
+    if (0..n_i).contains(&idx) {
+        return Some(idx as usize);
+    }
+    match border {
+        BorderMode::Constant(_) => None,
+        BorderMode::Wrap => Some(idx.rem_euclid(n_i) as usize),
+        BorderMode::Reflect => {
+            let m = idx.rem_euclid(2 * n_i);
+            Some(if m < n_i { m } else { 2 * n_i - 1 - m } as usize)
+        }
+    }
+}
+
+/// The radius of a 1-dimensional Gaussian pass, in pixels. Direct kernels are
+/// truncated at 4 sigma and recursive passes are padded with 6 sigma.
+pub(crate) fn gaussian_radius(sigma: f64, method: GaussianMethod) -> usize {
+    match method {
+        GaussianMethod::Recursive if sigma >= 0.5 => (6.0 * sigma).ceil() as usize,
+        _ => (4.0 * sigma).ceil().max(1.0) as usize,
+    }
+}
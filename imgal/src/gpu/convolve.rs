@@ -0,0 +1,160 @@
+use ndarray::{Array2, ArrayView2, Axis, Ix2, Slice};
+
+use crate::error::ImgalError;
+use crate::filter::convolve::{self, pad};
+use crate::filter::spatial::BorderMode;
+use crate::gpu::device::{self, Context};
+use crate::traits::numeric::ToFloat64;
+
+// convolve a band of rows of the padded image, each invocation computes one
+// output pixel
+const CONVOLVE_2D_SHADER: &str = r#"
+struct Params {
+    width: u32,
+    rows: u32,
+    k_rows: u32,
+    k_cols: u32,
+    padded_width: u32,
+    stride: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> padded: array<f32>;
+@group(0) @binding(2) var<storage, read> kernel: array<f32>;
+@group(0) @binding(3) var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.y * params.stride + id.x;
+    if (i >= params.width * params.rows) {
+        return;
+    }
+    let y = i / params.width;
+    let x = i % params.width;
+    var acc = 0.0;
+    for (var jy = 0u; jy < params.k_rows; jy++) {
+        let row = (y + params.k_rows - 1u - jy) * params.padded_width;
+        for (var jx = 0u; jx < params.k_cols; jx++) {
+            acc += kernel[jy * params.k_cols + jx] * padded[row + x + params.k_cols - 1u - jx];
+        }
+    }
+    output[i] = acc;
+}
+"#;
+
+/// Convolve a 2-dimensional image with a kernel on the GPU.
+///
+/// # Description
+///
+/// This function computes the same convolution as `filter::convolve` with a
+/// direct summation over the kernel elements on the GPU:
+///
+/// ```text
+/// O(x) = Σⱼ K(j) × I(x + c - j)
+/// ```
+///
+/// where `c` is the kernel center, the index (n - 1) / 2 of each axis. Values
+/// past the edges of the image are extended with the `border` mode on the CPU.
+/// The image and kernel are uploaded in single precision (`f32`) and each
+/// output pixel is accumulated in `f32`. Images larger than the device buffers
+/// are convolved in bands of rows. If no GPU is available (see
+/// `gpu::is_available`) or the device fails, the image is convolved by
+/// `filter::convolve` on the CPU.
+///
+/// # Arguments
+///
+/// * `data`: The input 2-dimensional image.
+/// * `kernel`: The 2-dimensional convolution kernel.
+/// * `border`: The border mode, default = `BorderMode::Reflect`.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The convolved image with the same shape as `data`.
+/// * `Err(ImgalError)`: If the kernel is empty.
+pub fn convolve_2d<S, T>(
+    data: ArrayView2<S>,
+    kernel: ArrayView2<T>,
+    border: Option<BorderMode>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let border = border.unwrap_or(BorderMode::Reflect);
+
+    // check if the kernel parameter is valid
+    if kernel.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "kernel",
+            reason: format!(
+                "must be non-empty with 2 dimensions but got shape {:?}",
+                kernel.shape()
+            ),
+        });
+    }
+    if data.is_empty() {
+        return Ok(Array2::<f64>::zeros(data.raw_dim()));
+    }
+
+    // convolve on the GPU, fall back to the CPU if it fails
+    let output = device::context().and_then(|ctx| convolve_bands(ctx, data, kernel, border));
+    match output {
+        Some(output) => Ok(output),
+        None => Ok(
+            convolve::convolve(data.into_dyn(), kernel.into_dyn(), Some(border), None)?
+                .into_dimensionality::<Ix2>()
+                .expect("a 2-dimensional convolution"),
+        ),
+    }
+}
+
+/// Convolve the padded image in bands of rows that fit the device buffers.
+fn convolve_bands<S, T>(
+    ctx: &Context,
+    data: ArrayView2<S>,
+    kernel: ArrayView2<T>,
+    border: BorderMode,
+) -> Option<Array2<f64>>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    let (rows, cols) = data.dim();
+    let (k_rows, k_cols) = kernel.dim();
+    let padded = pad(data.into_dyn(), kernel.shape(), border);
+    let padded_width = cols + k_cols - 1;
+    let max_rows = ctx.max_binding_size() as usize / (size_of::<f32>() * padded_width);
+    let band_rows = max_rows.checked_sub(k_rows - 1).filter(|&n| n > 0)?;
+    let kernel_f32: Vec<f32> = kernel.iter().map(|v| v.to_f64() as f32).collect();
+
+    let mut output = Vec::with_capacity(rows * cols);
+    for start in (0..rows).step_by(band_rows) {
+        let n = band_rows.min(rows - start);
+        let band: Vec<f32> = padded
+            .slice_axis(Axis(0), Slice::from(start..start + n + k_rows - 1))
+            .iter()
+            .map(|&v| v as f32)
+            .collect();
+        let params = [
+            cols as u32,
+            n as u32,
+            k_rows as u32,
+            k_cols as u32,
+            padded_width as u32,
+        ];
+        output.extend(
+            ctx.run(
+                CONVOLVE_2D_SHADER,
+                &params,
+                &[&band, &kernel_f32],
+                n * cols,
+                n * cols,
+            )?
+            .into_iter()
+            .map(|v| v as f64),
+        );
+    }
+
+    Some(Array2::from_shape_vec((rows, cols), output).expect("one value per pixel"))
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, mpsc};
+
+use wgpu::util::DeviceExt;
+
+/// The number of invocations of a workgroup, the shaders must declare the same
+/// `@workgroup_size`.
+pub(crate) const WORKGROUP_SIZE: u32 = 64;
+
+// the process wide device, `None` if no adapter is available
+static CONTEXT: OnceLock<Option<Context>> = OnceLock::new();
+
+/// A GPU device and queue with the compute pipelines of its shaders.
+pub(crate) struct Context {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    limits: wgpu::Limits,
+    pipelines: Mutex<HashMap<&'static str, wgpu::ComputePipeline>>,
+}
+
+/// Check if a GPU is available.
+///
+/// # Description
+///
+/// The GPU functions run on the first adapter (_i.e._ device) that wgpu finds
+/// on the system, a discrete GPU is preferred over an integrated one. The
+/// adapter is requested once per process, when this function or a GPU function
+/// is first called. If no adapter is available the GPU functions compute
+/// their output on the CPU.
+///
+/// # Returns
+///
+/// * `bool`: If the GPU functions run on a GPU.
+pub fn is_available() -> bool {
+    context().is_some()
+}
+
+/// Get the process wide GPU context, requesting the adapter on first use.
+pub(crate) fn context() -> Option<&'static Context> {
+    CONTEXT.get_or_init(Context::request).as_ref()
+}
+
+impl Context {
+    /// Request an adapter and its device.
+    fn request() -> Option<Context> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))
+        .ok()?;
+        let limits = adapter.limits();
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+            label: Some("imgal"),
+            required_limits: limits.clone(),
+            ..Default::default()
+        }))
+        .ok()?;
+
+        // errors are captured per call with error scopes, do not panic on an
+        // error outside of a scope (e.g. a lost device)
+        device.on_uncaptured_error(Arc::new(|_| {}));
+
+        Some(Context {
+            device,
+            queue,
+            limits,
+            pipelines: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The largest storage buffer, in bytes, a shader can bind.
+    pub(crate) fn max_binding_size(&self) -> u64 {
+        self.limits
+            .max_storage_buffer_binding_size
+            .min(self.limits.max_buffer_size)
+    }
+
+    /// Run a compute shader.
+    ///
+    /// # Description
+    ///
+    /// The shader entry point is `main`. Binding 0 of group 0 is a uniform
+    /// buffer with the `params` words, the following bindings are read-only
+    /// storage buffers with the `inputs` and the last binding is a read-write
+    /// storage buffer with `output_len` values. The workgroups are dispatched
+    /// over two dimensions to run at least `invocations` invocations, the
+    /// shader gets its linear invocation index as:
+    ///
+    /// ```text
+    /// i = id.y × stride + id.x
+    /// ```
+    ///
+    /// where `stride` is the last `params` word.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<f32>)`: The output buffer.
+    /// * `None`: If the buffers exceed the device limits or the device fails.
+    pub(crate) fn run(
+        &self,
+        shader: &'static str,
+        params: &[u32],
+        inputs: &[&[f32]],
+        output_len: usize,
+        invocations: usize,
+    ) -> Option<Vec<f32>> {
+        // check the buffer sizes and the dispatch against the device limits
+        let max_bytes = self.max_binding_size() as usize;
+        let output_bytes = output_len * size_of::<f32>();
+        if inputs
+            .iter()
+            .any(|inp| inp.is_empty() || size_of_val(*inp) > max_bytes)
+            || output_len == 0
+            || output_bytes > max_bytes
+        {
+            return None;
+        }
+        let max_groups = self.limits.max_compute_workgroups_per_dimension as usize;
+        let groups = invocations.div_ceil(WORKGROUP_SIZE as usize);
+        let groups_x = groups.clamp(1, max_groups);
+        let groups_y = groups.div_ceil(groups_x).max(1);
+        if groups_y > max_groups {
+            return None;
+        }
+
+        // append the dispatch stride and pad the uniform buffer to 16 bytes
+        let mut words = params.to_vec();
+        words.push(groups_x as u32 * WORKGROUP_SIZE);
+        words.resize(words.len().next_multiple_of(4), 0);
+
+        let device = &self.device;
+        let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let oom_scope = device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let pipeline = self.pipeline(shader);
+        let param_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&words),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let input_bufs: Vec<wgpu::Buffer> = inputs
+            .iter()
+            .map(|inp| {
+                device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice(inp),
+                    usage: wgpu::BufferUsages::STORAGE,
+                })
+            })
+            .collect();
+        let output_buf = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: output_bytes as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buf = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: output_bytes as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut entries = vec![wgpu::BindGroupEntry {
+            binding: 0,
+            resource: param_buf.as_entire_binding(),
+        }];
+        entries.extend(
+            input_bufs
+                .iter()
+                .chain(std::iter::once(&output_buf))
+                .enumerate()
+                .map(|(i, buf)| wgpu::BindGroupEntry {
+                    binding: i as u32 + 1,
+                    resource: buf.as_entire_binding(),
+                }),
+        );
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        // dispatch the shader and copy the output to the read buffer
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(groups_x as u32, groups_y as u32, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output_buf, 0, &read_buf, 0, output_bytes as u64);
+        self.queue.submit([encoder.finish()]);
+        if pollster::block_on(oom_scope.pop()).is_some()
+            || pollster::block_on(scope.pop()).is_some()
+        {
+            return None;
+        }
+
+        // wait for the shader and read the output
+        let (sender, receiver) = mpsc::channel();
+        let slice = read_buf.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |r| {
+            let _ = sender.send(r);
+        });
+        device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+        receiver.recv().ok()?.ok()?;
+        let output = bytemuck::cast_slice(&slice.get_mapped_range().ok()?).to_vec();
+
+        Some(output)
+    }
+
+    /// Get the compute pipeline of a shader, compiling it on first use.
+    fn pipeline(&self, shader: &'static str) -> wgpu::ComputePipeline {
+        let mut pipelines = self.pipelines.lock().unwrap_or_else(|e| e.into_inner());
+        pipelines
+            .entry(shader)
+            .or_insert_with(|| {
+                let module = self
+                    .device
+                    .create_shader_module(wgpu::ShaderModuleDescriptor {
+                        label: None,
+                        source: wgpu::ShaderSource::Wgsl(shader.into()),
+                    });
+                self.device
+                    .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                        label: None,
+                        layout: None,
+                        module: &module,
+                        entry_point: Some("main"),
+                        compilation_options: Default::default(),
+                        cache: None,
+                    })
+            })
+            .clone()
+    }
+}
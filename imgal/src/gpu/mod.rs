@@ -0,0 +1,7 @@
+//! GPU (wgpu) compute functions, enabled with the `gpu` feature.
+pub mod convolve;
+pub use convolve::convolve_2d;
+pub mod device;
+pub use device::is_available;
+pub mod phasor;
+pub use phasor::{calibration_image, time_domain_image};
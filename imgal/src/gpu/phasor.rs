@@ -0,0 +1,370 @@
+use ndarray::{Array2, Array3, ArrayView1, ArrayView2, ArrayView3, Axis, stack};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::gpu::device::{self, Context};
+use crate::phasor::waveform::Waveforms;
+use crate::phasor::{calibration, time_domain};
+use crate::traits::numeric::ToFloat64;
+
+// sum the intensity and the cosine and sine weighted intensity of each decay,
+// the decays are stored bin-major so neighboring invocations read neighboring
+// values
+const TIME_DOMAIN_SHADER: &str = r#"
+struct Params {
+    pixels: u32,
+    bins: u32,
+    stride: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> decays: array<f32>;
+@group(0) @binding(2) var<storage, read> cos_wave: array<f32>;
+@group(0) @binding(3) var<storage, read> sin_wave: array<f32>;
+@group(0) @binding(4) var<storage, read_write> sums: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let p = id.y * params.stride + id.x;
+    if (p >= params.pixels) {
+        return;
+    }
+    var iv = 0.0;
+    var gv = 0.0;
+    var sv = 0.0;
+    for (var k = 0u; k < params.bins; k++) {
+        let v = decays[k * params.pixels + p];
+        iv += v;
+        gv += v * cos_wave[k];
+        sv += v * sin_wave[k];
+    }
+    sums[3u * p] = iv;
+    sums[3u * p + 1u] = gv;
+    sums[3u * p + 2u] = sv;
+}
+"#;
+
+// rotate and scale the interleaved (G, S) coordinates of each pixel
+const CALIBRATION_SHADER: &str = r#"
+struct Params {
+    pixels: u32,
+    g_trans: f32,
+    s_trans: f32,
+    stride: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> coords: array<f32>;
+@group(0) @binding(2) var<storage, read_write> calibrated: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let p = id.y * params.stride + id.x;
+    if (p >= params.pixels) {
+        return;
+    }
+    let g = coords[2u * p];
+    let s = coords[2u * p + 1u];
+    calibrated[2u * p] = g * params.g_trans - s * params.s_trans;
+    calibrated[2u * p + 1u] = g * params.s_trans + s * params.g_trans;
+}
+"#;
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image on the GPU.
+///
+/// # Description
+///
+/// This function computes the same phasor coordinates as
+/// `phasor::time_domain::image` with the Fourier sums of each pixel on the GPU:
+///
+/// ```text
+/// G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+/// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+/// ```
+///
+/// The decays and waveforms are uploaded in single precision (`f32`) and the
+/// sums are accumulated in `f32`, so the coordinates match the CPU function to
+/// about 1e-5. Images larger than the device buffers are processed in batches
+/// of pixels. If no GPU is available (see `gpu::is_available`) or the device
+/// fails, the coordinates are computed by `phasor::time_domain::image` on the
+/// CPU.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `period`: The period (_i.e._ time interval). Must be greater than 0.0.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the decay
+///   axis.
+/// * `harmonic`: The harmonic value, default = 1.0. Must be >= 1.0.
+/// * `axis`: The decay or lifetime axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The real and imaginary coordinates as a 3D (ch, row, col) image,
+///    where G and S are indexed at 0 and 1 respectively on the _channel_ axis.
+/// * `Err(ImgalError)`: If axis is >= 3. If period is <= 0.0 or harmonic is
+///   < 1.0. If the mask shape does not match the image shape.
+pub fn time_domain_image<T>(
+    data: ArrayView3<T>,
+    period: f64,
+    mask: Option<ArrayView2<bool>>,
+    harmonic: Option<f64>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1.0);
+    let a = axis.unwrap_or(2);
+
+    // check if period, harmonic, axis and mask parameters are valid
+    validate::positive("period", period)?;
+    validate::at_least("harmonic", h, 1.0)?;
+    validate::axis(a, 3)?;
+    validate_mask(data.shape(), mask, a)?;
+
+    // compute the sums on the GPU, fall back to the CPU if it fails
+    let wf = time_domain::waveforms(data.len_of(Axis(a)), period, h);
+    let lanes = selected_lanes(&data, mask, a);
+    let sums = device::context().and_then(|ctx| time_domain_sums(ctx, &lanes, &wf));
+    let Some(sums) = sums else {
+        return time_domain::image(data, period, mask, harmonic, axis);
+    };
+
+    // normalize the sums and write the coordinates of the selected pixels
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    let mut g_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut s_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut sums = sums.chunks_exact(3);
+    g_arr
+        .iter_mut()
+        .zip(s_arr.iter_mut())
+        .zip(selection(mask, shape[0] * shape[1]))
+        .filter(|(_, m)| *m)
+        .for_each(|((g, s), _)| {
+            let v = sums.next().expect("one sum per selected pixel");
+            let iv = v[0] as f64;
+            *g = v[1] as f64 / iv;
+            *s = v[2] as f64 / iv;
+        });
+
+    // report pixels with no intensity, their G/S values are NaN
+    if let Some(msk) = mask
+        && !msk.iter().any(|&m| m)
+    {
+        warning::warn(Warning::EmptyMask);
+    }
+    let nan_count = g_arr.iter().filter(|v| !v.is_finite()).count();
+    if nan_count > 0 {
+        warning::warn(Warning::NonFiniteOutput { count: nan_count });
+    }
+
+    // stack G and S arrays, (row, col, ch)
+    Ok(stack(Axis(2), &[g_arr.view(), s_arr.view()]).unwrap())
+}
+
+/// Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional phasor
+/// image on the GPU.
+///
+/// # Description
+///
+/// This function computes the same calibration as `phasor::calibration::image`
+/// on the GPU, rotating and scaling the G and S coordinates by phase (φ) and
+/// modulation (M) respectively:
+///
+/// ```text
+/// g = M * cos(φ)
+/// s = M * sin(φ)
+/// G' = G * g - S * s
+/// S' = G * s + S * g
+/// ```
+///
+/// The coordinates are calibrated in single precision (`f32`). If no GPU is
+/// available (see `gpu::is_available`) or the device fails, the coordinates
+/// are calibrated by `phasor::calibration::image` on the CPU.
+///
+/// # Arguments
+///
+/// * `data`: The 3-dimensional phasor image, where G and S are channels 0 and 1
+///    respectively.
+/// * `modulation`: The modulation to scale the input (G, S) coordinates.
+/// * `phase`: The phase, φ angle, to rotate the input (G, S) coordinates.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the channel
+///   axis.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: A 3-dimensional array with the calibrated phasor
+///    values, where calibrated G and S are channels 0 and 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 3. If the mask shape does not match the
+///   image shape.
+pub fn calibration_image<T>(
+    data: ArrayView3<T>,
+    modulation: f64,
+    phase: f64,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if the axis and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate_mask(data.shape(), mask, a)?;
+
+    // calibrate on the GPU, fall back to the CPU if it fails
+    let g_trans = modulation * phase.cos();
+    let s_trans = modulation * phase.sin();
+    let lanes = selected_lanes(&data, mask, a);
+    let coords = device::context().and_then(|ctx| calibrated_coords(ctx, &lanes, g_trans, s_trans));
+    let Some(coords) = coords else {
+        return calibration::image(data, modulation, phase, mask, axis);
+    };
+
+    // write the calibrated coordinates of the selected pixels
+    if let Some(msk) = mask
+        && !msk.iter().any(|&m| m)
+    {
+        warning::warn(Warning::EmptyMask);
+    }
+    let mut c_data = Array3::<f64>::zeros(data.dim());
+    let mut coords = coords.chunks_exact(2);
+    let pixels = data.len() / data.len_of(Axis(a)).max(1);
+    c_data
+        .lanes_mut(Axis(a))
+        .into_iter()
+        .zip(selection(mask, pixels))
+        .filter(|(_, m)| *m)
+        .for_each(|(mut ln, _)| {
+            let v = coords
+                .next()
+                .expect("one coordinate pair per selected pixel");
+            ln[0] = v[0] as f64;
+            ln[1] = v[1] as f64;
+        });
+
+    Ok(c_data)
+}
+
+/// Sum the intensity and the cosine and sine weighted intensity of each decay
+/// on the GPU, in batches of decays that fit the device buffers.
+fn time_domain_sums<T>(ctx: &Context, lanes: &[ArrayView1<T>], wf: &Waveforms) -> Option<Vec<f32>>
+where
+    T: ToFloat64,
+{
+    let bins = wf.bins();
+    if bins == 0 {
+        return None;
+    }
+    let cos: Vec<f32> = wf.cos().iter().map(|&v| v as f32).collect();
+    let sin: Vec<f32> = wf.sin().iter().map(|&v| v as f32).collect();
+    let batch_len = (ctx.max_binding_size() as usize / (size_of::<f32>() * bins.max(3))).max(1);
+    let mut sums = Vec::with_capacity(3 * lanes.len());
+    for batch in lanes.chunks(batch_len) {
+        // store the decays of the batch bin-major
+        let mut decays = vec![0.0_f32; bins * batch.len()];
+        decays
+            .par_chunks_mut(batch.len())
+            .enumerate()
+            .for_each(|(k, row)| {
+                row.iter_mut()
+                    .zip(batch.iter())
+                    .for_each(|(d, ln)| *d = ln[k].to_f64() as f32);
+            });
+        let params = [batch.len() as u32, bins as u32];
+        sums.extend(ctx.run(
+            TIME_DOMAIN_SHADER,
+            &params,
+            &[&decays, &cos, &sin],
+            3 * batch.len(),
+            batch.len(),
+        )?);
+    }
+
+    Some(sums)
+}
+
+/// Calibrate the (G, S) coordinates of each pixel on the GPU, in batches of
+/// pixels that fit the device buffers.
+fn calibrated_coords<T>(
+    ctx: &Context,
+    lanes: &[ArrayView1<T>],
+    g_trans: f64,
+    s_trans: f64,
+) -> Option<Vec<f32>>
+where
+    T: ToFloat64,
+{
+    let batch_len = (ctx.max_binding_size() as usize / (2 * size_of::<f32>())).max(1);
+    let mut coords = Vec::with_capacity(2 * lanes.len());
+    for batch in lanes.chunks(batch_len) {
+        let pairs: Vec<f32> = batch
+            .iter()
+            .flat_map(|ln| [ln[0].to_f64() as f32, ln[1].to_f64() as f32])
+            .collect();
+        let params = [
+            batch.len() as u32,
+            (g_trans as f32).to_bits(),
+            (s_trans as f32).to_bits(),
+        ];
+        coords.extend(ctx.run(
+            CALIBRATION_SHADER,
+            &params,
+            &[&pairs],
+            2 * batch.len(),
+            batch.len(),
+        )?);
+    }
+
+    Some(coords)
+}
+
+/// Get the lanes along an axis of the pixels selected by a mask, in row-major
+/// pixel order.
+fn selected_lanes<'a, T>(
+    data: &'a ArrayView3<T>,
+    mask: Option<ArrayView2<bool>>,
+    axis: usize,
+) -> Vec<ArrayView1<'a, T>> {
+    let pixels = data.len() / data.len_of(Axis(axis)).max(1);
+    data.lanes(Axis(axis))
+        .into_iter()
+        .zip(selection(mask, pixels))
+        .filter_map(|(ln, m)| m.then_some(ln))
+        .collect()
+}
+
+/// Iterate over the mask values in row-major order, all pixels are selected
+/// without a mask.
+fn selection(mask: Option<ArrayView2<bool>>, pixels: usize) -> impl Iterator<Item = bool> {
+    let mask_iter = mask.into_iter().flat_map(|msk| msk.into_iter().copied());
+    let all_iter = std::iter::repeat_n(true, if mask.is_some() { 0 } else { pixels });
+
+    mask_iter.chain(all_iter)
+}
+
+/// Check that the mask matches the image shape without the axis.
+fn validate_mask(
+    shape: &[usize],
+    mask: Option<ArrayView2<bool>>,
+    axis: usize,
+) -> Result<(), ImgalError> {
+    if let Some(msk) = mask {
+        let mut shape = shape.to_vec();
+        shape.remove(axis);
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use ndarray::ArrayViewD;
 
+use crate::error::warning::{self, Warning};
 use crate::statistics::min_max;
 use crate::traits::numeric::ToFloat64;
+use crate::version::{self, AlgorithmVersion};
 
 /// Compute the image histogram from an n-dimensional array.
 ///
@@ -10,6 +12,9 @@ use crate::traits::numeric::ToFloat64;
 /// This function computes an image (_i.e._ frequency) histogram for the values
 /// in the input n-dimensional array.
 ///
+/// The numerical behavior of this function is versioned, see
+/// `version::AlgorithmVersion`.
+///
 /// # Arguments
 ///
 /// * `data`: The input n-dimensional array to construct the histogram from.
@@ -28,6 +33,8 @@ where
 
     // return an empty histogram if bins is zero or array is zero
     if data.is_empty() || bins == 0 {
+        let param_name = if bins == 0 { "bins" } else { "data" };
+        warning::warn(Warning::EmptyInput { param_name });
         return vec![0; 1];
     }
 
@@ -35,13 +42,25 @@ where
     let (min, max) = min_max(data.view());
 
     // construct histogram
+    let ver = version::algorithm::current();
     let mut hist = vec![0; bins];
     let bin_width: f64 = (max.to_f64() - min.to_f64()) / bins as f64;
     data.iter().for_each(|&v| {
-        let bin_index: usize = ((v.to_f64() - min.to_f64()) / bin_width) as usize;
-        let bin_index = bin_index.min(bins - 1);
-        hist[bin_index] += 1;
+        hist[bin_index(v.to_f64(), min.to_f64(), bin_width, bins, ver)] += 1;
     });
 
     hist
 }
+
+/// Get the histogram bin index of a value for the given algorithm version.
+pub(crate) fn bin_index(
+    value: f64,
+    min: f64,
+    bin_width: f64,
+    bins: usize,
+    version: AlgorithmVersion,
+) -> usize {
+    match version {
+        AlgorithmVersion::V1 => (((value - min) / bin_width) as usize).min(bins - 1),
+    }
+}
@@ -0,0 +1,77 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, IxDyn, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::integration::{composite_simpson, midpoint, trapezoid};
+use crate::traits::numeric::ToFloat64;
+
+/// Numerical integration rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrationMethod {
+    /// The rectangle (_i.e._ midpoint) rule, see `midpoint`.
+    Rectangle,
+    /// The trapezoid rule, see `trapezoid`.
+    Trapezoid,
+    /// Simpson's 1/3 rule with a trapezoid for an odd number of subintervals,
+    /// see `composite_simpson`.
+    Simpson,
+}
+
+/// Integrate an n-dimensional array along one axis.
+///
+/// # Description
+///
+/// This function integrates each 1-dimensional lane of `data` along `axis`
+/// with the chosen integration rule, the lanes are integrated in parallel.
+/// For example, integrating a (row, col, t) decay stack along the decay axis
+/// gives the (row, col) intensity image.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional array to integrate.
+/// * `axis`: The axis to integrate along.
+/// * `delta_x`: The width between data points along `axis`, default = 1.0.
+/// * `method`: The integration rule, default = `IntegrationMethod::Simpson`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The integrals with the shape of `data` without `axis`.
+/// * `Err(ImgalError)`: If axis is out of bounds. If the axis has less than 2
+///   points with the `Trapezoid` or `Simpson` rule, or less than 1 point with
+///   the `Rectangle` rule.
+pub fn integrate_axis<T>(
+    data: ArrayViewD<T>,
+    axis: usize,
+    delta_x: Option<f64>,
+    method: Option<IntegrationMethod>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let method = method.unwrap_or(IntegrationMethod::Simpson);
+
+    // check if the axis parameter is valid
+    validate::axis(axis, data.ndim())?;
+    let min_points = match method {
+        IntegrationMethod::Rectangle => 1.0,
+        _ => 2.0,
+    };
+    validate::at_least("data", data.len_of(Axis(axis)) as f64, min_points)?;
+
+    // integrate each lane along the axis
+    let mut shape = data.shape().to_vec();
+    shape.remove(axis);
+    let mut output = ArrayD::<f64>::zeros(IxDyn(&shape));
+    Zip::from(&mut output)
+        .and(data.lanes(Axis(axis)))
+        .par_for_each(|o, ln| {
+            let buf: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            *o = match method {
+                IntegrationMethod::Rectangle => midpoint(&buf, delta_x),
+                IntegrationMethod::Trapezoid => trapezoid(&buf, delta_x),
+                IntegrationMethod::Simpson => composite_simpson(&buf, delta_x),
+            };
+        });
+
+    Ok(output)
+}
@@ -1,7 +1,34 @@
 //! Numerical integration functions.
+pub mod axis;
+pub use axis::{IntegrationMethod, integrate_axis};
+
 pub mod rectangle;
 pub use rectangle::midpoint;
 
 pub mod simpson;
 pub use simpson::composite_simpson;
+pub use simpson::composite_simpson_nonuniform;
 pub use simpson::simpson;
+
+pub mod trapezoid;
+pub use trapezoid::cumulative_trapezoid;
+pub use trapezoid::cumulative_trapezoid_axis;
+pub use trapezoid::trapezoid;
+pub use trapezoid::trapezoid_nonuniform;
+
+use crate::error::{ImgalError, validate};
+
+/// Check if the x-coordinates match the data length, have at least 2 points
+/// and are finite and strictly increasing.
+fn validate_coordinates(len: usize, x: &[f64]) -> Result<(), ImgalError> {
+    validate::same_length(len, x.len())?;
+    validate::at_least("x", x.len() as f64, 2.0)?;
+    if x.iter().any(|v| !v.is_finite()) || x.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "x",
+            reason: "must be finite and strictly increasing".to_string(),
+        });
+    }
+
+    Ok(())
+}
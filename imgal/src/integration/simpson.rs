@@ -1,4 +1,5 @@
-use crate::error::ArrayError;
+use crate::error::ImgalError;
+use crate::integration::validate_coordinates;
 use crate::traits::numeric::ToFloat64;
 
 /// Integrate a curve with Simpson's 1/3 rule and the trapezoid rule.
@@ -38,7 +39,7 @@ where
     // find the number of subintervals
     let n: usize = x.len() - 1;
     // check for even number of subintervals
-    if n % 2 == 0 {
+    if n.is_multiple_of(2) {
         simpson(x, delta_x).unwrap()
     } else {
         // compute the even subintervals with Simpson's rule
@@ -49,6 +50,61 @@ where
     }
 }
 
+/// Integrate a curve sampled at non-uniform x-coordinates with Simpson's 1/3
+/// rule and the trapezoid rule.
+///
+/// # Description
+///
+/// Approximates the definite integral using Simpson's 1/3 rule with explicit,
+/// possibly irregularly spaced, x-coordinates (_e.g._ the non-linear time bins
+/// of some TCSPC systems). Each pair of subintervals of widths h₀ and h₁ is
+/// integrated with the quadratic through its 3 points:
+///
+/// ```text
+/// ∫(f(x)dx) ≈ ((h₀ + h₁)/6) * [(2 - h₁/h₀)f(x₀) + ((h₀ + h₁)²/(h₀h₁))f(x₁) + (2 - h₀/h₁)f(x₂)]
+/// ```
+///
+/// which reduces to `composite_simpson` for evenly spaced points. If there is
+/// an odd number of subintervals, the final subinterval is integrated using the
+/// trapezoid rule.
+///
+/// # Arguments
+///
+/// * `y`: The 1-dimensional data to integrate.
+/// * `x`: The x-coordinate (_e.g._ bin center) of each data point. Must be the
+///   same length as `y`, finite and strictly increasing.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The computed integral.
+/// * `Err(ImgalError)`: If `y` and `x` lengths do not match or have less than
+///   2 points. If `x` is not finite and strictly increasing.
+pub fn composite_simpson_nonuniform<T>(y: &[T], x: &[f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate_coordinates(y.len(), x)?;
+
+    // compute the pairs of subintervals with Simpson's rule
+    let n: usize = x.len() - 1;
+    let mut integral: f64 = 0.0;
+    for i in (0..n - 1).step_by(2) {
+        let h0 = x[i + 1] - x[i];
+        let h1 = x[i + 2] - x[i + 1];
+        let hs = h0 + h1;
+        integral += (hs / 6.0)
+            * ((2.0 - h1 / h0) * y[i].to_f64()
+                + (hs * hs / (h0 * h1)) * y[i + 1].to_f64()
+                + (2.0 - h0 / h1) * y[i + 2].to_f64());
+    }
+    // compute the last subinterval with a trapezoid
+    if !n.is_multiple_of(2) {
+        integral += ((x[n] - x[n - 1]) / 2.0) * (y[n - 1].to_f64() + y[n].to_f64());
+    }
+
+    Ok(integral)
+}
+
 /// Integrate a curve with Simpson's 1/3 rule.
 ///
 /// # Description
@@ -70,8 +126,8 @@ where
 /// # Returns
 ///
 /// * `Ok(f64)`: The computed integral.
-/// * `Err(ArrayError)`: If the number of subintervals is odd.
-pub fn simpson<T>(x: &[T], delta_x: Option<f64>) -> Result<f64, ArrayError>
+/// * `Err(ImgalError)`: If the number of subintervals is odd.
+pub fn simpson<T>(x: &[T], delta_x: Option<f64>) -> Result<f64, ImgalError>
 where
     T: ToFloat64,
 {
@@ -80,18 +136,19 @@ where
     // find the number of subintervals
     let n: usize = x.len() - 1;
     // check for even number of subintervals
-    if n % 2 == 0 {
-        // compute integal with Simpson's rule
-        let mut coef: f64;
-        let mut integral: f64 = (x[0] + x[n]).to_f64();
-        for i in 1..n {
-            coef = if i % 2 == 1 { 4.0 } else { 2.0 };
-            integral += coef * x[i].to_f64();
-        }
+    if n.is_multiple_of(2) {
+        // compute integal with Simpson's rule, the interior points alternate
+        // between the 4.0 and 2.0 coefficients
+        let integral: f64 = x[1..n]
+            .iter()
+            .zip([4.0, 2.0].iter().cycle())
+            .fold((x[0] + x[n]).to_f64(), |acc, (v, coef)| {
+                acc + coef * v.to_f64()
+            });
         Ok((d_x / 3.0) * integral)
     } else {
-        return Err(ArrayError::InvalidArrayGeneric {
+        Err(ImgalError::InvalidArrayGeneric {
             msg: "An odd number of subintervals is not allowed in Simpson's 1/3 rule integration.",
-        });
+        })
     }
 }
@@ -0,0 +1,167 @@
+use ndarray::{ArrayD, ArrayViewD, Axis, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::integration::validate_coordinates;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the running integral of a curve with the trapezoid rule.
+///
+/// # Description
+///
+/// Computes the cumulative integral from the first point to each point using
+/// the trapezoid rule:
+///
+/// ```text
+/// F(xₖ) ≈ Σᵢ₌₀ᵏ⁻¹ (Δx/2) * [f(xᵢ) + f(xᵢ₊₁)]
+/// ```
+///
+/// where F(x₀) = 0.0. Normalizing by the last value gives the cumulative
+/// distribution function (CDF) of, for example, a photon arrival histogram.
+///
+/// # Arguments
+///
+/// * `x`: The 1-dimensional data to integrate.
+/// * `delta_x`: The width between data points, default = 1.0.
+///
+/// # Returns
+///
+/// * `Vec<f64>`: The running integral with the same length as `x`.
+pub fn cumulative_trapezoid<T>(x: &[T], delta_x: Option<f64>) -> Vec<f64>
+where
+    T: ToFloat64,
+{
+    // set default delta x if necessary
+    let d_x: f64 = delta_x.unwrap_or(1.0);
+    let mut output = Vec::with_capacity(x.len());
+    let mut integral: f64 = 0.0;
+    let mut prev: Option<f64> = None;
+    x.iter().for_each(|v| {
+        let v = v.to_f64();
+        if let Some(p) = prev {
+            integral += (d_x / 2.0) * (p + v);
+        }
+        output.push(integral);
+        prev = Some(v);
+    });
+
+    output
+}
+
+/// Compute the running integral of an n-dimensional array along one axis with
+/// the trapezoid rule.
+///
+/// # Description
+///
+/// This function computes the running integral of each 1-dimensional lane of
+/// `data` along `axis` (see `cumulative_trapezoid`), the lanes are integrated
+/// in parallel. For example, the running integral of a (row, col, t) decay
+/// stack along the decay axis gives the gated intensity images of all gates
+/// starting at the first time bin.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional array to integrate.
+/// * `axis`: The axis to integrate along.
+/// * `delta_x`: The width between data points along `axis`, default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The running integral with the same shape as `data`.
+/// * `Err(ImgalError)`: If axis is out of bounds.
+pub fn cumulative_trapezoid_axis<T>(
+    data: ArrayViewD<T>,
+    axis: usize,
+    delta_x: Option<f64>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the axis parameter is valid
+    validate::axis(axis, data.ndim())?;
+
+    // integrate each lane along the axis
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    Zip::from(output.lanes_mut(Axis(axis)))
+        .and(data.lanes(Axis(axis)))
+        .par_for_each(|mut o_ln, ln| {
+            let buf: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+            o_ln.iter_mut()
+                .zip(cumulative_trapezoid(&buf, delta_x))
+                .for_each(|(o, v)| *o = v);
+        });
+
+    Ok(output)
+}
+
+/// Integrate a curve with the trapezoid rule.
+///
+/// # Description
+///
+/// Approximates the definite integral using the trapezoid rule
+/// with pre-computed x-values:
+///
+/// ```text
+/// ∫(f(x)dx) ≈ (Δx/2) * [f(x₀) + 2f(x₁) + 2f(x₂) + ... + 2f(xₙ₋₁) + f(xₙ)]
+/// ```
+///
+/// Where "n" is the number of evenly spaced points in the data. Curves with
+/// less than 2 points have an integral of 0.0.
+///
+/// # Arguments
+///
+/// * `x`: The 1-dimensional data to integrate.
+/// * `delta_x`: The width between data points, default = 1.0.
+///
+/// # Returns
+///
+/// * `f64`: The computed integral.
+pub fn trapezoid<T>(x: &[T], delta_x: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    // set default delta x if necessary
+    let d_x: f64 = delta_x.unwrap_or(1.0);
+    if x.len() < 2 {
+        return 0.0;
+    }
+    // compute the interior points with double weight
+    let n: usize = x.len() - 1;
+    let interior: f64 = x[1..n].iter().map(|v| v.to_f64()).sum();
+    (d_x / 2.0) * (x[0].to_f64() + 2.0 * interior + x[n].to_f64())
+}
+
+/// Integrate a curve sampled at non-uniform x-coordinates with the trapezoid
+/// rule.
+///
+/// # Description
+///
+/// Approximates the definite integral using the trapezoid rule with explicit,
+/// possibly irregularly spaced, x-coordinates (_e.g._ the non-linear time bins
+/// of some TCSPC systems):
+///
+/// ```text
+/// ∫(f(x)dx) ≈ Σᵢ ((xᵢ₊₁ - xᵢ)/2) * [f(xᵢ) + f(xᵢ₊₁)]
+/// ```
+///
+/// # Arguments
+///
+/// * `y`: The 1-dimensional data to integrate.
+/// * `x`: The x-coordinate (_e.g._ bin center) of each data point. Must be the
+///   same length as `y`, finite and strictly increasing.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The computed integral.
+/// * `Err(ImgalError)`: If `y` and `x` lengths do not match or have less than
+///   2 points. If `x` is not finite and strictly increasing.
+pub fn trapezoid_nonuniform<T>(y: &[T], x: &[f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate_coordinates(y.len(), x)?;
+
+    Ok(x.windows(2)
+        .zip(y.windows(2))
+        .map(|(xw, yw)| (xw[1] - xw[0]) / 2.0 * (yw[0].to_f64() + yw[1].to_f64()))
+        .sum())
+}
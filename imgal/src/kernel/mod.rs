@@ -1,2 +1,7 @@
 //! Kernel and neighborhood functions.
 pub mod neighborhood;
+pub mod orientation;
+pub use orientation::{OrientedFilter, orientation_bank};
+pub mod parametric;
+pub mod separable;
+pub use separable::{KernelFactors, separate};
@@ -1,6 +1,6 @@
 use ndarray::{Array2, Array3};
 
-use crate::error::ArrayError;
+use crate::error::ImgalError;
 
 /// Create a 2-dimensional square kernel with a circle neighborhood.
 ///
@@ -20,11 +20,11 @@ use crate::error::ArrayError;
 /// * `Ok(Array2<bool>)`: A 2-dimensional square boolean array with side lengths
 ///    of "radius * 2 + 1" where `true` values represent points inside or on the
 ///    circle boundary of the specified radius.
-/// * `Err(ArrayError)`: If radius is <= 0.
-pub fn circle(radius: usize) -> Result<Array2<bool>, ArrayError> {
+/// * `Err(ImgalError)`: If radius is <= 0.
+pub fn circle(radius: usize) -> Result<Array2<bool>, ImgalError> {
     // check if radius parameter is valid
     if radius == 0 {
-        return Err(ArrayError::InvalidArrayParameterValueLess {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
             param_name: "radius",
             value: 0,
         });
@@ -64,11 +64,11 @@ pub fn circle(radius: usize) -> Result<Array2<bool>, ArrayError> {
 /// * `Ok(Array3<bool>)`: A 3-dimensional cube boolean array with side lengths
 ///   of "radius * 2 + 1" where `true` values represent points inside or on the
 ///   sphere boundary of the specified radius.
-/// * `Err(ArrayError)`: If radius is <= 0.
-pub fn sphere(radius: usize) -> Result<Array3<bool>, ArrayError> {
+/// * `Err(ImgalError)`: If radius is <= 0.
+pub fn sphere(radius: usize) -> Result<Array3<bool>, ImgalError> {
     // check if radius parameter is valid
     if radius == 0 {
-        return Err(ArrayError::InvalidArrayParameterValueEqual {
+        return Err(ImgalError::InvalidArrayParameterValueEqual {
             param_name: "radius",
             value: 0,
         });
@@ -119,15 +119,15 @@ pub fn sphere(radius: usize) -> Result<Array3<bool>, ArrayError> {
 ///
 /// * `Ok(Array2<f64>)`: A 2-dimensional square array with side lengths
 ///    of "radius * 2 + 1" with a weighted circular neighborhood.
-/// * `Err(ArrayError)`: If circle radius is <= 0.
+/// * `Err(ImgalError)`: If circle radius is <= 0.
 pub fn weighted_circle(
     circle_radius: usize,
     falloff_radius: f64,
     initial_value: Option<f64>,
-) -> Result<Array2<f64>, ArrayError> {
+) -> Result<Array2<f64>, ImgalError> {
     // check if circle_radius parameter is valid
     if circle_radius == 0 {
-        return Err(ArrayError::InvalidArrayParameterValueLess {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
             param_name: "circle_radius",
             value: 0,
         });
@@ -188,15 +188,15 @@ pub fn weighted_circle(
 ///
 /// * `OK(Array3<f64>)`: A 3-dimensional cube array with side lengths of
 ///    "radius * 2 + 1" with a weighted spherical neighborhood.
-/// * `Err(ArrayError)`: If the sphere radius is <= 0.
+/// * `Err(ImgalError)`: If the sphere radius is <= 0.
 pub fn weighted_sphere(
     sphere_radius: usize,
     falloff_radius: f64,
     initial_value: Option<f64>,
-) -> Result<Array3<f64>, ArrayError> {
+) -> Result<Array3<f64>, ImgalError> {
     // check if the sphere_radius parameter is valid
     if sphere_radius == 0 {
-        return Err(ArrayError::InvalidArrayParameterValueLess {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
             param_name: "sphere_radius",
             value: 0,
         });
@@ -0,0 +1,105 @@
+use ndarray::{Array4, s};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::kernel::parametric;
+
+/// Oriented filter types of a kernel bank.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrientedFilter {
+    /// A zero mean, even Gabor kernel with a wavelength of the given multiple
+    /// of the scale (_e.g._ 2.0), responds to textures and lines with a period
+    /// of about the wavelength.
+    Gabor(f64),
+    /// The scale normalized first Gaussian derivative along the orientation,
+    /// σ × ∂G/∂θ, responds to edges with the intensity increasing along the
+    /// orientation.
+    Edge,
+    /// The negated scale normalized second Gaussian derivative along the
+    /// orientation, -σ² × ∂²G/∂θ², responds to bright lines perpendicular to
+    /// the orientation.
+    Ridge,
+}
+
+/// Create a bank of oriented 2-dimensional filter kernels.
+///
+/// # Description
+///
+/// This function creates one kernel per scale and orientation. Orientations
+/// are angles in radians from the column axis towards the row axis (_i.e._
+/// θ = 0.0 is along the columns and θ = π / 2 along the rows). The scale is
+/// the standard deviation of the Gaussian envelope in pixels. All kernels have
+/// the same size, 2 × ceil(4 × largest scale) + 1, and are centered. The
+/// Gaussian derivative kernels are steerable: the response at any orientation
+/// is a linear combination of the responses at 0.0 and π / 2 (and π / 4 for
+/// `OrientedFilter::Ridge`).
+///
+/// # Arguments
+///
+/// * `filter`: The oriented filter type.
+/// * `angles`: The orientations in radians. Must not be empty.
+/// * `scales`: The scales in pixels. Must not be empty and > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Array4<f64>)`: The kernel bank with shape (scales, angles, size,
+///   size), the kernel of scale `i` and angle `j` is `bank[[i, j, .., ..]]`.
+/// * `Err(ImgalError)`: If angles or scales is empty. If a scale is <= 0.0. If
+///   the Gabor wavelength multiple is <= 0.0.
+pub fn orientation_bank(
+    filter: OrientedFilter,
+    angles: &[f64],
+    scales: &[f64],
+) -> Result<Array4<f64>, ImgalError> {
+    // check if the parameters are valid
+    if angles.is_empty() || scales.is_empty() {
+        let param_name = if angles.is_empty() {
+            "angles"
+        } else {
+            "scales"
+        };
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: "must not be empty".to_string(),
+        });
+    }
+    scales
+        .iter()
+        .try_for_each(|&sc| validate::positive("scales", sc))?;
+    if let OrientedFilter::Gabor(w) = filter {
+        validate::positive("wavelength", w)?;
+    }
+
+    // create each kernel with the size of the largest scale
+    let max_scale = scales.iter().copied().fold(0.0, f64::max);
+    let r = (4.0 * max_scale).ceil() as usize;
+    let size = 2 * r + 1;
+    let mut bank = Array4::<f64>::zeros((scales.len(), angles.len(), size, size));
+    for (i, &sigma) in scales.iter().enumerate() {
+        for (j, &theta) in angles.iter().enumerate() {
+            let mut k = bank.slice_mut(s![i, j, .., ..]);
+            match filter {
+                OrientedFilter::Gabor(w) => {
+                    let g = parametric::gabor(sigma, w * sigma, theta, None, None, Some(r), None)?;
+                    let mean = g.mean().unwrap_or(0.0);
+                    k.assign(&g.mapv(|v| v - mean));
+                }
+                OrientedFilter::Edge | OrientedFilter::Ridge => {
+                    let g = parametric::gaussian(&[sigma, sigma], Some(&[r, r]), Some(true))?;
+                    let (sin, cos) = theta.sin_cos();
+                    k.indexed_iter_mut().for_each(|((row, col), v)| {
+                        let u = (col as f64 - r as f64) * cos + (row as f64 - r as f64) * sin;
+                        let w = g[[row, col]];
+                        *v = if filter == OrientedFilter::Edge {
+                            -u / sigma * w
+                        } else {
+                            (1.0 - u * u / (sigma * sigma)) * w
+                        };
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(bank)
+}
@@ -0,0 +1,333 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array2, ArrayD, IxDyn};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+
+/// Create an n-dimensional (anisotropic) Gaussian kernel.
+///
+/// # Description
+///
+/// This function creates a kernel sampled from the Gaussian function:
+///
+/// ```text
+/// G(x) = exp(-Σᵢ xᵢ² / (2σᵢ²))
+/// ```
+///
+/// centered at the middle of the kernel, with a peak value of 1.0. An axis
+/// with a sigma of 0.0 has a length of 1.
+///
+/// # Arguments
+///
+/// * `sigma`: The standard deviation along each axis, in pixels. The number of
+///   values sets the number of dimensions. Must be >= 0.0.
+/// * `radius`: The kernel radius along each axis, each axis has a length of
+///   2 × radius + 1. If `None`, then the radius is 4 × sigma rounded up.
+/// * `normalize`: If `true`, the kernel is scaled to sum to 1.0, default =
+///   `false`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The Gaussian kernel.
+/// * `Err(ImgalError)`: If sigma is empty or a sigma is < 0.0. If radius does
+///   not have one value per axis.
+pub fn gaussian(
+    sigma: &[f64],
+    radius: Option<&[usize]>,
+    normalize: Option<bool>,
+) -> Result<ArrayD<f64>, ImgalError> {
+    // check if the sigma and radius parameters are valid
+    validate_ndim("sigma", sigma.len())?;
+    sigma
+        .iter()
+        .try_for_each(|&s| validate::at_least("sigma", s, 0.0))?;
+    let radius: Vec<usize> = match radius {
+        Some(r) => {
+            validate_per_axis("radius", r.len(), sigma.len())?;
+            r.to_vec()
+        }
+        None => sigma.iter().map(|s| (4.0 * s).ceil() as usize).collect(),
+    };
+
+    // sample the Gaussian, axes with a zero sigma only have their center
+    let kernel = centered(&radius, |x| {
+        let exponent: f64 = x
+            .iter()
+            .zip(sigma.iter())
+            .filter(|&(_, s)| *s > 0.0)
+            .map(|(x, s)| x * x / (2.0 * s * s))
+            .sum();
+        (-exponent).exp()
+    });
+
+    Ok(scale(kernel, normalize))
+}
+
+/// Create an n-dimensional discrete Laplacian kernel.
+///
+/// # Description
+///
+/// This function creates the 3 × ... × 3 finite difference Laplacian stencil,
+/// the sum of the second differences along each axis. The center is -2 × ndim
+/// and the direct neighbors along each axis are 1.0, the kernel sums to 0.0.
+///
+/// # Arguments
+///
+/// * `ndim`: The number of dimensions. Must be >= 1.
+/// * `normalize`: If `true`, the kernel is scaled so that the positive weights
+///   sum to 1.0, default = `false`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The Laplacian kernel.
+/// * `Err(ImgalError)`: If ndim is 0.
+pub fn laplacian(ndim: usize, normalize: Option<bool>) -> Result<ArrayD<f64>, ImgalError> {
+    // check if the ndim parameter is valid
+    validate_ndim("ndim", ndim)?;
+
+    let kernel = centered(&vec![1; ndim], |x| {
+        let dist: f64 = x.iter().map(|v| v.abs()).sum();
+        if dist == 0.0 {
+            -2.0 * ndim as f64
+        } else if dist == 1.0 {
+            1.0
+        } else {
+            0.0
+        }
+    });
+
+    Ok(scale(kernel, normalize))
+}
+
+/// Create an n-dimensional disk (_i.e._ ball) kernel.
+///
+/// # Description
+///
+/// This function creates a kernel that is 1.0 inside or on the boundary of a
+/// disk of the given radius around the kernel center and 0.0 outside, using
+/// the Euclidean distance. Each axis has a length of 2 × floor(radius) + 1.
+///
+/// # Arguments
+///
+/// * `radius`: The disk radius in pixels. Must be >= 0.0.
+/// * `ndim`: The number of dimensions. Must be >= 1.
+/// * `normalize`: If `true`, the kernel is scaled to sum to 1.0 (_i.e._ a
+///   mean filter), default = `false`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The disk kernel.
+/// * `Err(ImgalError)`: If radius is < 0.0. If ndim is 0.
+pub fn disk(radius: f64, ndim: usize, normalize: Option<bool>) -> Result<ArrayD<f64>, ImgalError> {
+    // check if the radius parameter is valid
+    validate::at_least("radius", radius, 0.0)?;
+
+    ring(0.0, radius, ndim, normalize)
+}
+
+/// Create an n-dimensional ring (_i.e._ shell) kernel.
+///
+/// # Description
+///
+/// This function creates a kernel that is 1.0 where the Euclidean distance to
+/// the kernel center is within [inner_radius, outer_radius] and 0.0
+/// elsewhere. Each axis has a length of 2 × floor(outer_radius) + 1.
+///
+/// # Arguments
+///
+/// * `inner_radius`: The inner ring radius in pixels. Must be >= 0.0.
+/// * `outer_radius`: The outer ring radius in pixels. Must be >=
+///   `inner_radius`.
+/// * `ndim`: The number of dimensions. Must be >= 1.
+/// * `normalize`: If `true`, the kernel is scaled to sum to 1.0, default =
+///   `false`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The ring kernel.
+/// * `Err(ImgalError)`: If inner_radius is < 0.0 or outer_radius is <
+///   inner_radius. If ndim is 0.
+pub fn ring(
+    inner_radius: f64,
+    outer_radius: f64,
+    ndim: usize,
+    normalize: Option<bool>,
+) -> Result<ArrayD<f64>, ImgalError> {
+    // check if the parameters are valid
+    validate::at_least("inner_radius", inner_radius, 0.0)?;
+    validate::at_least("outer_radius", outer_radius, inner_radius)?;
+    validate_ndim("ndim", ndim)?;
+
+    let r = outer_radius.floor() as usize;
+    let kernel = centered(&vec![r; ndim], |x| {
+        let dist = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if dist >= inner_radius && dist <= outer_radius {
+            1.0
+        } else {
+            0.0
+        }
+    });
+
+    Ok(scale(kernel, normalize))
+}
+
+/// Create a 2-dimensional Gabor kernel.
+///
+/// # Description
+///
+/// This function creates a Gabor kernel, a sinusoidal plane wave under a
+/// Gaussian envelope, which responds to edges and textures with a given
+/// orientation and spatial frequency:
+///
+/// ```text
+/// G(x, y) = exp(-(x'² + γ²y'²) / (2σ²)) × cos(2πx' / λ + ψ)
+/// x' = x cos(θ) + y sin(θ)
+/// y' = -x sin(θ) + y cos(θ)
+/// ```
+///
+/// where `x` is the column offset and `y` the row offset from the kernel
+/// center.
+///
+/// # Arguments
+///
+/// * `sigma`: The standard deviation of the Gaussian envelope, σ, in pixels.
+///   Must be > 0.0.
+/// * `wavelength`: The wavelength of the sinusoid, λ, in pixels. Must be >
+///   0.0.
+/// * `theta`: The orientation of the wave normal, θ, in radians from the
+///   column axis.
+/// * `phase`: The phase offset of the sinusoid, ψ, in radians, default = 0.0
+///   (_i.e._ the symmetric, even kernel).
+/// * `aspect`: The spatial aspect ratio of the envelope, γ, default = 1.0.
+///   Must be > 0.0.
+/// * `radius`: The kernel radius, the kernel has side lengths of
+///   2 × radius + 1. If `None`, then the radius is 4 × σ / min(γ, 1.0) rounded
+///   up (_i.e._ the largest extent of the envelope).
+/// * `normalize`: If `true`, the kernel is scaled to sum to 1.0, or so that its
+///   positive weights sum to 1.0 if it sums to 0.0, default = `false`.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The Gabor kernel.
+/// * `Err(ImgalError)`: If sigma, wavelength or aspect is <= 0.0.
+pub fn gabor(
+    sigma: f64,
+    wavelength: f64,
+    theta: f64,
+    phase: Option<f64>,
+    aspect: Option<f64>,
+    radius: Option<usize>,
+    normalize: Option<bool>,
+) -> Result<Array2<f64>, ImgalError> {
+    // set optional parameters if needed
+    let phase = phase.unwrap_or(0.0);
+    let aspect = aspect.unwrap_or(1.0);
+
+    // check if the parameters are valid
+    validate::positive("sigma", sigma)?;
+    validate::positive("wavelength", wavelength)?;
+    validate::positive("aspect", aspect)?;
+    let r = radius.unwrap_or((4.0 * sigma / aspect.min(1.0)).ceil() as usize);
+
+    let (sin, cos) = theta.sin_cos();
+    let kernel = centered(&[r, r], |x| {
+        let (y, x) = (x[0], x[1]);
+        let xr = x * cos + y * sin;
+        let yr = -x * sin + y * cos;
+        (-(xr * xr + aspect * aspect * yr * yr) / (2.0 * sigma * sigma)).exp()
+            * (2.0 * PI * xr / wavelength + phase).cos()
+    });
+
+    Ok(scale(kernel, normalize)
+        .into_dimensionality()
+        .expect("a 2-dimensional kernel"))
+}
+
+/// Create an n-dimensional box (_i.e._ hyper-rectangle) kernel.
+///
+/// # Description
+///
+/// This function creates a kernel filled with 1.0. Each axis has a length of
+/// 2 × radius + 1.
+///
+/// # Arguments
+///
+/// * `radius`: The kernel radius along each axis. The number of values sets
+///   the number of dimensions.
+/// * `normalize`: If `true`, the kernel is scaled to sum to 1.0 (_i.e._ a
+///   mean filter), default = `false`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The box kernel.
+/// * `Err(ImgalError)`: If radius is empty.
+pub fn rectangle(radius: &[usize], normalize: Option<bool>) -> Result<ArrayD<f64>, ImgalError> {
+    // check if the radius parameter is valid
+    validate_ndim("radius", radius.len())?;
+
+    Ok(scale(centered(radius, |_| 1.0), normalize))
+}
+
+/// Create a kernel with a length of 2 × radius + 1 along each axis from a
+/// function of the offsets to the kernel center.
+fn centered<F>(radius: &[usize], f: F) -> ArrayD<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let shape: Vec<usize> = radius.iter().map(|r| 2 * r + 1).collect();
+    let mut offsets = vec![0.0; radius.len()];
+
+    ArrayD::from_shape_fn(IxDyn(&shape), |idx| {
+        offsets
+            .iter_mut()
+            .zip(radius.iter())
+            .enumerate()
+            .for_each(|(ax, (o, &r))| *o = idx[ax] as f64 - r as f64);
+        f(&offsets)
+    })
+}
+
+/// Scale a kernel to sum to 1.0 if requested, kernels that sum to 0.0 are
+/// scaled so that their positive weights sum to 1.0.
+fn scale(mut kernel: ArrayD<f64>, normalize: Option<bool>) -> ArrayD<f64> {
+    if !normalize.unwrap_or(false) {
+        return kernel;
+    }
+    let total: f64 = kernel.sum();
+    let positive: f64 = kernel.iter().filter(|&&v| v > 0.0).sum();
+    let norm = if total.abs() > 1e-12 * positive {
+        total
+    } else {
+        positive
+    };
+    if norm != 0.0 {
+        kernel.mapv_inplace(|v| v / norm);
+    }
+
+    kernel
+}
+
+/// Check if a number of dimensions is at least 1.
+fn validate_ndim(param_name: &'static str, ndim: usize) -> Result<(), ImgalError> {
+    if ndim == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: "must have at least one dimension".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check if a per axis parameter has one value per axis.
+fn validate_per_axis(param_name: &'static str, len: usize, ndim: usize) -> Result<(), ImgalError> {
+    if len != ndim {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: format!("must have one value per axis, {} but got {}", ndim, len),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use ndarray::ArrayView2;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::traits::numeric::ToFloat64;
+
+/// The maximum number of Jacobi sweeps of the singular value decomposition.
+const MAX_SWEEPS: usize = 64;
+
+/// The 1-dimensional factors of a separable 2-dimensional kernel, along axis 0
+/// and axis 1.
+pub type KernelFactors = (Vec<f64>, Vec<f64>);
+
+/// Decompose a 2-dimensional kernel into two 1-dimensional factors.
+///
+/// # Description
+///
+/// This function tests whether a 2-dimensional kernel is (approximately) rank
+/// 1, the outer product of a factor along axis 0 (rows) and a factor along
+/// axis 1 (columns):
+///
+/// ```text
+/// K[i, j] ≈ a[i] × b[j]
+/// ```
+///
+/// The factors are computed from the largest singular value `σ` and its
+/// singular vectors `u` and `v` of a singular value decomposition (SVD) with
+/// the one-sided Jacobi method, `a = √σ × u` and `b = √σ × v`, with the sign
+/// of the largest value of `a` positive. A separable kernel can be convolved
+/// as two 1-dimensional passes, which costs rows + cols instead of rows × cols
+/// operations per pixel.
+///
+/// # Arguments
+///
+/// * `kernel`: The 2-dimensional kernel.
+/// * `tolerance`: The largest relative (Frobenius norm) residual of the rank 1
+///   approximation, ‖K - a ⊗ b‖ / ‖K‖, of a separable kernel, default = 1e-6.
+///   Must be >= 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Some(KernelFactors))`: The factors along axis 0 and axis 1 of
+///   a separable kernel.
+/// * `Ok(None)`: If the kernel is not separable within the tolerance.
+/// * `Err(ImgalError)`: If the kernel is empty. If tolerance is < 0.0.
+pub fn separate<T>(
+    kernel: ArrayView2<T>,
+    tolerance: Option<f64>,
+) -> Result<Option<KernelFactors>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let tolerance = tolerance.unwrap_or(1e-6);
+
+    // check if the parameters are valid
+    validate::at_least("tolerance", tolerance, 0.0)?;
+    if kernel.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "kernel",
+            reason: format!("must not be empty but got shape {:?}", kernel.shape()),
+        });
+    }
+
+    // orthogonalize the columns with Jacobi rotations, the column norms are
+    // then the singular values
+    let (m, n) = kernel.dim();
+    let mut u: Vec<Vec<f64>> = (0..n)
+        .map(|j| kernel.column(j).iter().map(|v| v.to_f64()).collect())
+        .collect();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|j| (0..n).map(|i| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha: f64 = u[p].iter().map(|x| x * x).sum();
+                let beta: f64 = u[q].iter().map(|x| x * x).sum();
+                let gamma: f64 = u[p].iter().zip(u[q].iter()).map(|(x, y)| x * y).sum();
+                if gamma == 0.0 || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+                rotate(&mut u, p, q, c, s);
+                rotate(&mut v, p, q, c, s);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    // build the factors from the largest singular value
+    let norms: Vec<f64> = u
+        .iter()
+        .map(|col| col.iter().map(|x| x * x).sum::<f64>().sqrt())
+        .collect();
+    let (k, &sigma) = norms
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .expect("a non-empty kernel");
+    let (mut a, mut b) = if sigma > 0.0 {
+        let scale = sigma.sqrt();
+        (
+            u[k].iter().map(|x| x / sigma * scale).collect::<Vec<f64>>(),
+            v[k].iter().map(|x| x * scale).collect::<Vec<f64>>(),
+        )
+    } else {
+        (vec![0.0; m], vec![0.0; n])
+    };
+    let peak = a
+        .iter()
+        .copied()
+        .max_by(|x, y| x.abs().total_cmp(&y.abs()))
+        .unwrap_or(0.0);
+    if peak < 0.0 {
+        a.iter_mut().for_each(|x| *x = -*x);
+        b.iter_mut().for_each(|x| *x = -*x);
+    }
+
+    // check the residual of the rank 1 approximation
+    let mut residual = 0.0;
+    let mut total = 0.0;
+    kernel.indexed_iter().for_each(|((i, j), w)| {
+        let w = w.to_f64();
+        residual += (w - a[i] * b[j]).powi(2);
+        total += w * w;
+    });
+    if residual.sqrt() <= tolerance * total.sqrt() {
+        Ok(Some((a, b)))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Apply a Jacobi rotation to the columns p and q.
+fn rotate(cols: &mut [Vec<f64>], p: usize, q: usize, c: f64, s: f64) {
+    let (left, right) = cols.split_at_mut(q);
+    left[p]
+        .iter_mut()
+        .zip(right[0].iter_mut())
+        .for_each(|(x, y)| {
+            let t = *x;
+            *x = c * t - s * *y;
+            *y = s * t + c * *y;
+        });
+}
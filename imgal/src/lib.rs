@@ -12,16 +12,33 @@
 //! ## Crate Status
 //!
 //! This crate is still under active development and it's API is not stable.
+pub mod anisotropy;
+pub mod chunked;
 pub mod colocalization;
+pub mod config;
+pub mod correction;
 pub mod distribution;
 pub mod error;
+pub mod feature;
 pub mod filter;
+#[cfg(feature = "gpu")]
+pub mod gpu;
 pub mod image;
 pub mod integration;
 pub mod kernel;
+pub mod lifetime;
+pub mod measure;
+pub mod morphology;
+pub mod operator;
 pub mod parameter;
 pub mod phasor;
+pub mod pipeline;
+pub mod register;
+pub mod roi;
 pub mod simulation;
 pub mod statistics;
 pub mod threshold;
 pub mod traits;
+pub mod transform;
+pub mod unmix;
+pub mod version;
@@ -0,0 +1,80 @@
+use ndarray::{Array2, ArrayView2, ArrayView3, Axis, Ix2, Slice, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::integration::{IntegrationMethod, integrate_axis};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the intensity image of a 3-dimensional decay stack.
+///
+/// # Description
+///
+/// This function sums or integrates the decay of each pixel, optionally only
+/// within a time gate, to produce the intensity (_e.g._ photon count) image.
+/// The integrals are in units of time bins, with `IntegrationMethod::Rectangle`
+/// the intensity is the sum of the decay (_i.e._ the photon count of TCSPC
+/// histograms).
+///
+/// # Arguments
+///
+/// * `data`: I(t), the decay data image.
+/// * `axis`: The decay or lifetime axis, default = 2.
+/// * `method`: The integration rule, default = `IntegrationMethod::Rectangle`.
+/// * `gate`: The (start, end) time bins of the gate, `end` is exclusive. If
+///   `None`, the entire decay is used.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   set to 0.0. Must match the shape of `data` without the decay axis.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional intensity image.
+/// * `Err(ImgalError)`: If axis is >= 3. If the gate is empty or exceeds the
+///   decay axis. If the gate has less than 2 time bins with the `Trapezoid` or
+///   `Simpson` rule. If the mask shape does not match the image shape.
+pub fn intensity_image<T>(
+    data: ArrayView3<T>,
+    axis: Option<usize>,
+    method: Option<IntegrationMethod>,
+    gate: Option<(usize, usize)>,
+    mask: Option<ArrayView2<bool>>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+    let method = method.unwrap_or(IntegrationMethod::Rectangle);
+
+    // check if the axis, gate and mask parameters are valid
+    validate::axis(a, 3)?;
+    let n = data.len_of(Axis(a));
+    let (start, end) = gate.unwrap_or((0, n));
+    if start >= end || end > n {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "gate",
+            reason: format!(
+                "must be a non-empty (start, end) range within the {} time bins but got ({}, {})",
+                n, start, end
+            ),
+        });
+    }
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(msk) = mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // integrate the gated decays
+    let gated = data.slice_axis(Axis(a), Slice::from(start..end));
+    let mut intensity = integrate_axis(gated.into_dyn(), a, Some(1.0), Some(method))?
+        .into_dimensionality::<Ix2>()
+        .expect("the intensity image is 2-dimensional");
+    if let Some(msk) = mask {
+        Zip::from(&mut intensity).and(msk).for_each(|v, &m| {
+            if !m {
+                *v = 0.0;
+            }
+        });
+    }
+
+    Ok(intensity)
+}
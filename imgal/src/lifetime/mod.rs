@@ -0,0 +1,3 @@
+//! Fluorescence lifetime image functions.
+pub mod intensity;
+pub use intensity::intensity_image;
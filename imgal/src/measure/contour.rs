@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use ndarray::{Array2, ArrayView2};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Find the iso-valued contours of a 2-dimensional image with marching
+/// squares.
+///
+/// # Description
+///
+/// This function traces the lines where the image crosses the `level` value,
+/// with sub-pixel precision. Each square cell of four neighboring pixels is
+/// classified by which corners are above the level (_i.e._ strictly greater),
+/// and the contour crosses each cell edge between an above and a not above
+/// corner at the linearly interpolated position. Saddle cells (two diagonal
+/// corners above) are resolved with the mean of the four corners: if the mean
+/// is above the level the above corners are joined.
+///
+/// The cell segments are joined into contours. Contours that enclose a region
+/// are closed (_i.e._ the last point repeats the first point), contours that
+/// end at the image border are open. All contours are oriented with the
+/// values above the level on the right-hand side, when the image is displayed
+/// with rows increasing downward (_i.e._ closed contours run clockwise around
+/// bright objects). For a binary mask, pass the mask as 0 and 1 values with a
+/// level of 0.5.
+///
+/// # Arguments
+///
+/// * `data`: The 2-dimensional image.
+/// * `level`: The contour value, default = the midpoint of the minimum and
+///   maximum of `data`.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Array2<f64>>)`: The contours, each with shape (points, 2) holding
+///   the (row, col) coordinates of the points along the contour.
+/// * `Err(ImgalError)`: If level is not finite.
+pub fn find_contours<T>(
+    data: ArrayView2<T>,
+    level: Option<f64>,
+) -> Result<Vec<Array2<f64>>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let values = data.mapv(|v| v.to_f64());
+    let level = level.unwrap_or_else(|| {
+        let (min, max) = values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+        (min + max) / 2.0
+    });
+
+    // check if the level parameter is valid
+    if !level.is_finite() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "level",
+            reason: format!("must be finite but got {}", level),
+        });
+    }
+    let (rows, cols) = values.dim();
+    if rows < 2 || cols < 2 {
+        return Ok(Vec::new());
+    }
+
+    // the crossing point of a cell edge, horizontal edges go from (r, c) to
+    // (r, c + 1) and vertical edges from (r, c) to (r + 1, c)
+    let edge = |r: usize, c: usize, vertical: bool| Edge { r, c, vertical };
+    let point = |e: &Edge| {
+        let (r2, c2) = if e.vertical {
+            (e.r + 1, e.c)
+        } else {
+            (e.r, e.c + 1)
+        };
+        let (v1, v2) = (values[[e.r, e.c]], values[[r2, c2]]);
+        let t = (level - v1) / (v2 - v1);
+        (
+            e.r as f64 + t * (r2 - e.r) as f64,
+            e.c as f64 + t * (c2 - e.c) as f64,
+        )
+    };
+    let above_corner = |e: &Edge| {
+        if values[[e.r, e.c]] > level {
+            (e.r as f64, e.c as f64)
+        } else if e.vertical {
+            ((e.r + 1) as f64, e.c as f64)
+        } else {
+            (e.r as f64, (e.c + 1) as f64)
+        }
+    };
+
+    // link the oriented segments of each cell by their start edge
+    let mut next: HashMap<Edge, Edge> = HashMap::new();
+    for r in 0..rows - 1 {
+        for c in 0..cols - 1 {
+            let (ul, ur) = (values[[r, c]] > level, values[[r, c + 1]] > level);
+            let (ll, lr) = (values[[r + 1, c]] > level, values[[r + 1, c + 1]] > level);
+            let top = edge(r, c, false);
+            let bottom = edge(r + 1, c, false);
+            let left = edge(r, c, true);
+            let right = edge(r, c + 1, true);
+            let mut segments: Vec<(Edge, Edge)> = Vec::new();
+            if ul == lr && ur == ll && ul != ur {
+                // a saddle cell
+                let mean = (values[[r, c]]
+                    + values[[r, c + 1]]
+                    + values[[r + 1, c]]
+                    + values[[r + 1, c + 1]])
+                    / 4.0;
+                if (mean > level) == ul {
+                    segments.push((top, right));
+                    segments.push((left, bottom));
+                } else {
+                    segments.push((top, left));
+                    segments.push((right, bottom));
+                }
+            } else {
+                let crossed: Vec<Edge> = [
+                    (top, ul != ur),
+                    (bottom, ll != lr),
+                    (left, ul != ll),
+                    (right, ur != lr),
+                ]
+                .into_iter()
+                .filter(|&(_, x)| x)
+                .map(|(e, _)| e)
+                .collect();
+                if crossed.len() == 2 {
+                    segments.push((crossed[0], crossed[1]));
+                }
+            }
+
+            // orient each segment with the above corners on the right
+            segments.into_iter().for_each(|(a, b)| {
+                let (pa, pb) = (point(&a), point(&b));
+                let side = |corner: (f64, f64), from: (f64, f64)| {
+                    (pb.1 - pa.1) * (corner.0 - from.0) - (pb.0 - pa.0) * (corner.1 - from.1)
+                };
+                let mut s = side(above_corner(&a), pa);
+                if s == 0.0 {
+                    s = side(above_corner(&b), pb);
+                }
+                if s >= 0.0 {
+                    next.insert(a, b);
+                } else {
+                    next.insert(b, a);
+                }
+            });
+        }
+    }
+
+    // follow the links, open contours from their border start edge first
+    let mut ends: Vec<Edge> = next.values().copied().collect();
+    ends.sort_unstable();
+    let mut starts: Vec<Edge> = next.keys().copied().collect();
+    starts.sort_unstable();
+    let (open, closed): (Vec<Edge>, Vec<Edge>) = starts
+        .into_iter()
+        .partition(|s| ends.binary_search(s).is_err());
+    let mut contours: Vec<Array2<f64>> = Vec::new();
+    for start in open.into_iter().chain(closed) {
+        if !next.contains_key(&start) {
+            continue;
+        }
+        let mut coords: Vec<f64> = Vec::new();
+        let mut e = start;
+        let (r, c) = point(&e);
+        coords.extend([r, c]);
+        while let Some(n) = next.remove(&e) {
+            let (r, c) = point(&n);
+            coords.extend([r, c]);
+            e = n;
+        }
+        contours.push(Array2::from_shape_vec((coords.len() / 2, 2), coords).unwrap());
+    }
+
+    Ok(contours)
+}
+
+/// A cell edge, from (r, c) to the next pixel along the row or column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Edge {
+    r: usize,
+    c: usize,
+    vertical: bool,
+}
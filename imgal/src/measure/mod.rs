@@ -0,0 +1,5 @@
+//! Object measurement functions.
+pub mod contour;
+pub use contour::find_contours;
+pub mod regionprops;
+pub use regionprops::{RegionProps, regionprops};
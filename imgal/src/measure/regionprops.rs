@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+#[cfg(feature = "arrow")]
+use std::sync::Arc;
+
+#[cfg(feature = "arrow")]
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, UInt64Array};
+use ndarray::{ArrayView2, ArrayView3, Axis};
+
+use crate::error::{ImgalError, validate};
+#[cfg(feature = "arrow")]
+use crate::traits::arrow::ArrowLabel;
+use crate::traits::numeric::ToFloat64;
+
+/// A table of region properties, with one entry per object in each column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionProps<L> {
+    /// The object labels, in ascending order.
+    pub label: Vec<L>,
+    /// The number of pixels of each object.
+    pub area: Vec<usize>,
+    /// The centroid row of each object.
+    pub centroid_row: Vec<f64>,
+    /// The centroid column of each object.
+    pub centroid_col: Vec<f64>,
+    /// The first row of the bounding box of each object.
+    pub bbox_min_row: Vec<usize>,
+    /// The first column of the bounding box of each object.
+    pub bbox_min_col: Vec<usize>,
+    /// The row after the last row of the bounding box of each object.
+    pub bbox_max_row: Vec<usize>,
+    /// The column after the last column of the bounding box of each object.
+    pub bbox_max_col: Vec<usize>,
+    /// The crack perimeter of each object.
+    pub perimeter: Vec<f64>,
+    /// The eccentricity of the ellipse with the same second moments as each
+    /// object.
+    pub eccentricity: Vec<f64>,
+    /// The mean intensity of each object, if an intensity image is given.
+    pub mean_intensity: Option<Vec<f64>>,
+    /// The minimum intensity of each object, if an intensity image is given.
+    pub min_intensity: Option<Vec<f64>>,
+    /// The maximum intensity of each object, if an intensity image is given.
+    pub max_intensity: Option<Vec<f64>>,
+    /// The mean real component, G, of each object, if a phasor image is given.
+    pub mean_g: Option<Vec<f64>>,
+    /// The mean imaginary component, S, of each object, if a phasor image is
+    /// given.
+    pub mean_s: Option<Vec<f64>>,
+}
+
+impl<L> RegionProps<L> {
+    /// The number of objects in the table.
+    pub fn len(&self) -> usize {
+        self.label.len()
+    }
+
+    /// Whether the table has no objects.
+    pub fn is_empty(&self) -> bool {
+        self.label.is_empty()
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl<L: ArrowLabel> RegionProps<L> {
+    /// Convert the table into an Apache Arrow record batch.
+    ///
+    /// # Description
+    ///
+    /// Each property becomes a non-nullable column named after its field, in
+    /// field order. The `label` column keeps the integer type of the labels,
+    /// the pixel counts and bounding boxes are `UInt64` and all other
+    /// properties are `Float64`. The intensity and phasor columns are only
+    /// included if they were measured. The record batch can be exported
+    /// without copying through the Arrow C data interface (see
+    /// `arrow_array::ffi`).
+    ///
+    /// # Returns
+    ///
+    /// * `RecordBatch`: The region properties with one row per object.
+    pub fn to_record_batch(&self) -> RecordBatch {
+        let counts = |v: &[usize]| -> ArrayRef {
+            Arc::new(UInt64Array::from_iter_values(v.iter().map(|&c| c as u64)))
+        };
+        let values = |v: &[f64]| -> ArrayRef { Arc::new(Float64Array::from(v.to_vec())) };
+        let mut columns: Vec<(&str, ArrayRef)> = vec![
+            ("label", L::to_arrow(&self.label)),
+            ("area", counts(&self.area)),
+            ("centroid_row", values(&self.centroid_row)),
+            ("centroid_col", values(&self.centroid_col)),
+            ("bbox_min_row", counts(&self.bbox_min_row)),
+            ("bbox_min_col", counts(&self.bbox_min_col)),
+            ("bbox_max_row", counts(&self.bbox_max_row)),
+            ("bbox_max_col", counts(&self.bbox_max_col)),
+            ("perimeter", values(&self.perimeter)),
+            ("eccentricity", values(&self.eccentricity)),
+        ];
+        let optional = [
+            ("mean_intensity", &self.mean_intensity),
+            ("min_intensity", &self.min_intensity),
+            ("max_intensity", &self.max_intensity),
+            ("mean_g", &self.mean_g),
+            ("mean_s", &self.mean_s),
+        ];
+        optional.into_iter().for_each(|(name, column)| {
+            if let Some(v) = column {
+                columns.push((name, values(v)));
+            }
+        });
+
+        RecordBatch::try_from_iter(columns)
+            .expect("The region property columns have one entry per object.")
+    }
+}
+
+/// Measure the properties of each object of a 2-dimensional label image.
+///
+/// # Description
+///
+/// This function measures each object (_i.e._ labeled region) of a label image
+/// and returns the measurements as a struct-of-arrays table, with the objects
+/// ordered by ascending label. Pixels with the label 0 (_i.e._ the default
+/// value of the label type) are background. The following properties are
+/// computed per object:
+///
+/// * `area`: The number of pixels.
+/// * `centroid`: The mean row and column of the pixels.
+/// * `bbox`: The bounding box, from the first row and column to the row and
+///   column after the last (_i.e._ half-open intervals).
+/// * `perimeter`: The crack perimeter, the number of pixel edges shared with
+///   a pixel of another label or the image border. The crack perimeter of
+///   smooth shapes overestimates the true perimeter by up to a factor of 4/π.
+/// * `eccentricity`: The eccentricity of the ellipse with the same second
+///   central moments as the object, 0.0 for a circle and approaching 1.0 for
+///   a line:
+///
+/// ```text
+/// e = √(1 - λ₂ / λ₁)
+/// ```
+///
+/// where `λ₁ >= λ₂` are the eigenvalues of the pixel coordinate covariance
+/// matrix. The eccentricity of a single pixel is 0.0.
+///
+/// With an intensity image the mean, minimum and maximum intensity of each
+/// object are computed, and with a phasor image the mean G and S coordinates
+/// of each object.
+///
+/// # Arguments
+///
+/// * `labels`: The 2-dimensional label image.
+/// * `intensity`: An optional intensity image. Must match the shape of
+///   `labels`.
+/// * `phasor`: An optional phasor (G, S) image, where G and S are indexed at 0
+///   and 1 on the channel axis. Must match the shape of `labels` without the
+///   channel axis.
+/// * `axis`: The channel axis of the phasor image, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(RegionProps<L>)`: The table of region properties.
+/// * `Err(ImgalError)`: If the intensity image shape does not match the label
+///   image shape. If axis is >= 3, the channel axis does not have a length of
+///   2 or the phasor image shape does not match the label image shape.
+pub fn regionprops<L, T>(
+    labels: ArrayView2<L>,
+    intensity: Option<ArrayView2<T>>,
+    phasor: Option<ArrayView3<f64>>,
+    axis: Option<usize>,
+) -> Result<RegionProps<L>, ImgalError>
+where
+    L: ToFloat64 + Eq + Hash,
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if the intensity, phasor and axis parameters are valid
+    if let Some(img) = intensity {
+        validate::same_shape(img.shape(), labels.shape())?;
+    }
+    if let Some(ph) = phasor {
+        validate::axis(a, 3)?;
+        validate::same_length(2, ph.len_of(Axis(a)))?;
+        let mut shape = ph.shape().to_vec();
+        shape.remove(a);
+        validate::same_shape(&shape, labels.shape())?;
+    }
+    let phasor = phasor.map(|ph| {
+        let mut order: Vec<usize> = (0..3).filter(|&ax| ax != a).collect();
+        order.push(a);
+        ph.permuted_axes([order[0], order[1], order[2]])
+    });
+
+    // accumulate the moments, extent and perimeter of each object
+    let background = L::default();
+    let (rows, cols) = labels.dim();
+    let mut regions: HashMap<L, Region> = HashMap::new();
+    labels.indexed_iter().for_each(|((r, c), &l)| {
+        if l == background {
+            return;
+        }
+        let region = regions.entry(l).or_insert_with(|| Region::new(r, c));
+        region.add(r, c);
+        let edges = [
+            r == 0 || labels[[r - 1, c]] != l,
+            r + 1 == rows || labels[[r + 1, c]] != l,
+            c == 0 || labels[[r, c - 1]] != l,
+            c + 1 == cols || labels[[r, c + 1]] != l,
+        ];
+        region.perimeter += edges.iter().filter(|&&e| e).count();
+        if let Some(img) = intensity {
+            region.add_intensity(img[[r, c]].to_f64());
+        }
+        if let Some(ph) = phasor.as_ref() {
+            region.g_sum += ph[[r, c, 0]];
+            region.s_sum += ph[[r, c, 1]];
+        }
+    });
+
+    // sort the objects by label and fill the table columns
+    let mut regions: Vec<(L, Region)> = regions.into_iter().collect();
+    regions.sort_by(|a, b| a.0.to_f64().total_cmp(&b.0.to_f64()));
+    let column = |f: &dyn Fn(&Region) -> f64| regions.iter().map(|(_, rg)| f(rg)).collect();
+    let with_intensity = intensity.is_some();
+    let with_phasor = phasor.is_some();
+
+    Ok(RegionProps {
+        label: regions.iter().map(|(l, _)| *l).collect(),
+        area: regions.iter().map(|(_, rg)| rg.area).collect(),
+        centroid_row: column(&|rg| rg.row_sum / rg.area as f64),
+        centroid_col: column(&|rg| rg.col_sum / rg.area as f64),
+        bbox_min_row: regions.iter().map(|(_, rg)| rg.min_row).collect(),
+        bbox_min_col: regions.iter().map(|(_, rg)| rg.min_col).collect(),
+        bbox_max_row: regions.iter().map(|(_, rg)| rg.max_row + 1).collect(),
+        bbox_max_col: regions.iter().map(|(_, rg)| rg.max_col + 1).collect(),
+        perimeter: column(&|rg| rg.perimeter as f64),
+        eccentricity: column(&Region::eccentricity),
+        mean_intensity: with_intensity.then(|| column(&|rg| rg.intensity_sum / rg.area as f64)),
+        min_intensity: with_intensity.then(|| column(&|rg| rg.intensity_min)),
+        max_intensity: with_intensity.then(|| column(&|rg| rg.intensity_max)),
+        mean_g: with_phasor.then(|| column(&|rg| rg.g_sum / rg.area as f64)),
+        mean_s: with_phasor.then(|| column(&|rg| rg.s_sum / rg.area as f64)),
+    })
+}
+
+/// The running sums and extent of a labeled object.
+struct Region {
+    area: usize,
+    row_sum: f64,
+    col_sum: f64,
+    row_sq_sum: f64,
+    col_sq_sum: f64,
+    row_col_sum: f64,
+    min_row: usize,
+    min_col: usize,
+    max_row: usize,
+    max_col: usize,
+    perimeter: usize,
+    intensity_sum: f64,
+    intensity_min: f64,
+    intensity_max: f64,
+    g_sum: f64,
+    s_sum: f64,
+}
+
+impl Region {
+    /// Create an empty region starting at a pixel.
+    fn new(row: usize, col: usize) -> Self {
+        Region {
+            area: 0,
+            row_sum: 0.0,
+            col_sum: 0.0,
+            row_sq_sum: 0.0,
+            col_sq_sum: 0.0,
+            row_col_sum: 0.0,
+            min_row: row,
+            min_col: col,
+            max_row: row,
+            max_col: col,
+            perimeter: 0,
+            intensity_sum: 0.0,
+            intensity_min: f64::INFINITY,
+            intensity_max: f64::NEG_INFINITY,
+            g_sum: 0.0,
+            s_sum: 0.0,
+        }
+    }
+
+    /// Add a pixel to the region.
+    fn add(&mut self, row: usize, col: usize) {
+        let (r, c) = (row as f64, col as f64);
+        self.area += 1;
+        self.row_sum += r;
+        self.col_sum += c;
+        self.row_sq_sum += r * r;
+        self.col_sq_sum += c * c;
+        self.row_col_sum += r * c;
+        self.min_row = self.min_row.min(row);
+        self.min_col = self.min_col.min(col);
+        self.max_row = self.max_row.max(row);
+        self.max_col = self.max_col.max(col);
+    }
+
+    /// Add the intensity of a pixel to the region.
+    fn add_intensity(&mut self, value: f64) {
+        self.intensity_sum += value;
+        self.intensity_min = self.intensity_min.min(value);
+        self.intensity_max = self.intensity_max.max(value);
+    }
+
+    /// The eccentricity of the ellipse with the same second central moments.
+    fn eccentricity(&self) -> f64 {
+        let n = self.area as f64;
+        let (mr, mc) = (self.row_sum / n, self.col_sum / n);
+        let vr = self.row_sq_sum / n - mr * mr;
+        let vc = self.col_sq_sum / n - mc * mc;
+        let cov = self.row_col_sum / n - mr * mc;
+        let half_trace = (vr + vc) / 2.0;
+        let root = (((vr - vc) / 2.0).powi(2) + cov * cov).sqrt();
+        let (l1, l2) = (half_trace + root, (half_trace - root).max(0.0));
+        if l1 > 0.0 {
+            (1.0 - l2 / l1).sqrt()
+        } else {
+            0.0
+        }
+    }
+}
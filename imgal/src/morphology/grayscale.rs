@@ -0,0 +1,219 @@
+use ndarray::{ArrayD, ArrayViewD, Zip};
+
+use crate::error::ImgalError;
+use crate::filter::background::map_lanes;
+use crate::traits::numeric::ToFloat64;
+
+/// Erode an n-dimensional image with a flat box structuring element.
+///
+/// # Description
+///
+/// This function computes the grayscale erosion of the image, the minimum of
+/// the pixel values inside the box centered on each pixel. Pixels past the
+/// image border are ignored. The box is separable and each 1-dimensional
+/// minimum pass uses the van Herk/Gil-Werman algorithm, with about three
+/// comparisons per pixel regardless of the box size.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `radius`: The box radius along each axis, in pixels, the box has a side
+///   length of 2 × radius + 1. Must have one value per axis. A radius of 0
+///   does not filter that axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The eroded image with the same shape as `data`.
+/// * `Err(ImgalError)`: If radius does not have one value per axis.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1016/0167-8655(92)90069-C>
+pub fn erosion<T>(data: ArrayViewD<T>, radius: &[usize]) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate_radius(radius, data.ndim())?;
+    let mut output = data.mapv(|v| v.to_f64());
+    box_pass(&mut output, radius, false);
+
+    Ok(output)
+}
+
+/// Dilate an n-dimensional image with a flat box structuring element.
+///
+/// # Description
+///
+/// This function computes the grayscale dilation of the image, the maximum of
+/// the pixel values inside the box centered on each pixel, see `erosion`.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `radius`: The box radius along each axis, in pixels, the box has a side
+///   length of 2 × radius + 1. Must have one value per axis. A radius of 0
+///   does not filter that axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The dilated image with the same shape as `data`.
+/// * `Err(ImgalError)`: If radius does not have one value per axis.
+pub fn dilation<T>(data: ArrayViewD<T>, radius: &[usize]) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate_radius(radius, data.ndim())?;
+    let mut output = data.mapv(|v| v.to_f64());
+    box_pass(&mut output, radius, true);
+
+    Ok(output)
+}
+
+/// Open an n-dimensional image with a flat box structuring element.
+///
+/// # Description
+///
+/// This function computes the grayscale opening of the image, the dilation of
+/// the erosion. The opening removes bright structures that do not fit in the
+/// box and never exceeds the image, which makes it an estimate of the
+/// background under bright objects.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `radius`: The box radius along each axis, in pixels, the box has a side
+///   length of 2 × radius + 1. Must have one value per axis. A radius of 0
+///   does not filter that axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The opened image with the same shape as `data`.
+/// * `Err(ImgalError)`: If radius does not have one value per axis.
+pub fn opening<T>(data: ArrayViewD<T>, radius: &[usize]) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate_radius(radius, data.ndim())?;
+    let mut output = data.mapv(|v| v.to_f64());
+    box_pass(&mut output, radius, false);
+    box_pass(&mut output, radius, true);
+
+    Ok(output)
+}
+
+/// Close an n-dimensional image with a flat box structuring element.
+///
+/// # Description
+///
+/// This function computes the grayscale closing of the image, the erosion of
+/// the dilation. The closing fills dark structures that do not fit in the box
+/// and is never below the image.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `radius`: The box radius along each axis, in pixels, the box has a side
+///   length of 2 × radius + 1. Must have one value per axis. A radius of 0
+///   does not filter that axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The closed image with the same shape as `data`.
+/// * `Err(ImgalError)`: If radius does not have one value per axis.
+pub fn closing<T>(data: ArrayViewD<T>, radius: &[usize]) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    validate_radius(radius, data.ndim())?;
+    let mut output = data.mapv(|v| v.to_f64());
+    box_pass(&mut output, radius, true);
+    box_pass(&mut output, radius, false);
+
+    Ok(output)
+}
+
+/// Compute the morphological gradient of an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the morphological (Beucher) gradient of the image,
+/// the dilation minus the erosion with a flat box structuring element. The
+/// gradient is the local intensity range, large at the edges of objects and
+/// 0.0 in flat regions.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional image.
+/// * `radius`: The box radius along each axis, in pixels, the box has a side
+///   length of 2 × radius + 1. Must have one value per axis. A radius of 0
+///   does not filter that axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The gradient image with the same shape as `data`.
+/// * `Err(ImgalError)`: If radius does not have one value per axis.
+pub fn gradient<T>(data: ArrayViewD<T>, radius: &[usize]) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let mut output = dilation(data.view(), radius)?;
+    let eroded = erosion(data, radius)?;
+    Zip::from(&mut output)
+        .and(&eroded)
+        .par_for_each(|d, &e| *d -= e);
+
+    Ok(output)
+}
+
+/// Apply a separable box minimum (or maximum) filter in place.
+fn box_pass(data: &mut ArrayD<f64>, radius: &[usize], max: bool) {
+    radius
+        .iter()
+        .enumerate()
+        .filter(|&(_, &r)| r > 0)
+        .for_each(|(ax, &r)| map_lanes(data, ax, |ln| running_extremum(ln, r, max)));
+}
+
+/// Check that a box radius has one value per axis.
+fn validate_radius(radius: &[usize], ndim: usize) -> Result<(), ImgalError> {
+    if radius.len() != ndim {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "radius",
+            reason: format!(
+                "must have one value per axis, {} but got {}",
+                ndim,
+                radius.len()
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Compute the running minimum (or maximum) of a lane over a window of
+/// 2 × radius + 1 values, values past the ends are ignored.
+fn running_extremum(lane: &[f64], radius: usize, max: bool) -> Vec<f64> {
+    let (ident, op): (f64, fn(f64, f64) -> f64) = if max {
+        (f64::NEG_INFINITY, f64::max)
+    } else {
+        (f64::INFINITY, f64::min)
+    };
+    let w = 2 * radius + 1;
+    let n = (lane.len() + 2 * radius).div_ceil(w) * w;
+    let mut padded = vec![ident; n];
+    padded[radius..radius + lane.len()].copy_from_slice(lane);
+
+    // the block-wise prefix and suffix extrema
+    let mut g = padded.clone();
+    let mut h = padded.clone();
+    (1..n)
+        .filter(|i| i % w != 0)
+        .for_each(|i| g[i] = op(g[i - 1], g[i]));
+    (0..n - 1)
+        .rev()
+        .filter(|i| (i + 1) % w != 0)
+        .for_each(|i| h[i] = op(h[i + 1], h[i]));
+
+    // the window starting at j spans at most two blocks
+    (0..lane.len()).map(|j| op(h[j], g[j + w - 1])).collect()
+}
@@ -0,0 +1,219 @@
+use ndarray::{ArrayD, ArrayViewD, IxDyn};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+
+/// The pixel connectivity of connected components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Connectivity {
+    /// Pixels are connected if they share a face (_i.e._ 4-connectivity in 2D
+    /// and 6-connectivity in 3D).
+    Face,
+    /// Pixels are connected if they share a face, edge or corner (_i.e._
+    /// 8-connectivity in 2D and 26-connectivity in 3D).
+    Full,
+}
+
+/// Label the connected components of an n-dimensional boolean mask.
+///
+/// # Description
+///
+/// This function assigns a unique label to each connected component (_i.e._
+/// object) of the `true` pixels of a boolean mask with a union-find (disjoint
+/// set) implementation. The mask is scanned in row-major order and each
+/// foreground pixel is joined to its previously scanned foreground neighbors.
+/// The labels are numbered consecutively from 1 in the order of the first
+/// pixel of each object, background pixels are set to 0.
+///
+/// In parallel, the mask is split along the first axis into blocks that are
+/// labeled independently, the components that cross the block boundaries are
+/// merged afterwards. The output is identical to the sequential labeling.
+///
+/// # Arguments
+///
+/// * `mask`: An n-dimensional boolean mask, with at least 1 dimension.
+/// * `connectivity`: The pixel connectivity, default = `Connectivity::Full`.
+/// * `parallel`: If `true`, label blocks of the mask in parallel, default =
+///   `true`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<u32>)`: The label image with the same shape as `mask`.
+/// * `Err(ImgalError)`: If the mask has 0 dimensions. If the mask has more
+///   objects than fit in a `u32` label.
+pub fn label(
+    mask: ArrayViewD<bool>,
+    connectivity: Option<Connectivity>,
+    parallel: Option<bool>,
+) -> Result<ArrayD<u32>, ImgalError> {
+    // set optional parameters if needed
+    let connectivity = connectivity.unwrap_or(Connectivity::Full);
+    let parallel = parallel.unwrap_or(true);
+
+    // check if the mask parameter is valid
+    let shape = mask.shape().to_vec();
+    if shape.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "mask",
+            reason: "must have at least 1 dimension but got 0".to_string(),
+        });
+    }
+
+    // flatten the mask in row-major order and create the neighbor offsets
+    let mask = mask.as_standard_layout();
+    let mask = mask.as_slice().unwrap();
+    let neighbors = neighbor_offsets(&shape, connectivity);
+    let plane = shape[1..].iter().product::<usize>();
+
+    // join each pixel to its scanned neighbors, in blocks of planes if parallel
+    let mut parent: Vec<usize> = (0..mask.len()).collect();
+    let blocks = if parallel {
+        rayon::current_num_threads().min(shape[0]).max(1)
+    } else {
+        1
+    };
+    let block_planes = shape[0].div_ceil(blocks).max(1);
+    let block_len = (block_planes * plane).max(1);
+    parent
+        .par_chunks_mut(block_len)
+        .enumerate()
+        .for_each(|(b, p)| {
+            let start = b * block_len;
+            (start..start + p.len()).for_each(|i| {
+                if mask[i] {
+                    join_neighbors(i, mask, p, start, &shape, &neighbors, start);
+                }
+            });
+        });
+
+    // merge the components that cross the block boundaries
+    (1..blocks).for_each(|b| {
+        let start = b * block_len;
+        (start..(start + plane).min(mask.len())).for_each(|i| {
+            if mask[i] {
+                join_neighbors(i, mask, &mut parent, 0, &shape, &neighbors, 0);
+            }
+        });
+    });
+
+    // number the components by their root, the smallest index of the component
+    let mut labels = vec![0u32; mask.len()];
+    let mut count: u32 = 0;
+    for i in 0..mask.len() {
+        if !mask[i] {
+            continue;
+        }
+        let root = find(&mut parent, 0, i);
+        labels[i] = if root == i {
+            count = count.checked_add(1).ok_or(ImgalError::InvalidParameter {
+                param_name: "mask",
+                reason: format!("must have at most {} objects", u32::MAX),
+            })?;
+            count
+        } else {
+            labels[root]
+        };
+    }
+
+    Ok(ArrayD::from_shape_vec(IxDyn(&shape), labels).unwrap())
+}
+
+/// A neighbor scanned before a pixel, as per axis steps and a flat offset.
+struct Neighbor {
+    steps: Vec<isize>,
+    offset: usize,
+}
+
+/// Create the neighbors that precede a pixel in row-major order.
+fn neighbor_offsets(shape: &[usize], connectivity: Connectivity) -> Vec<Neighbor> {
+    let ndim = shape.len();
+    let mut strides = vec![1isize; ndim];
+    (0..ndim.saturating_sub(1)).rev().for_each(|a| {
+        strides[a] = strides[a + 1] * shape[a + 1] as isize;
+    });
+    (0..3usize.pow(ndim as u32))
+        .filter_map(|code| {
+            let mut c = code;
+            let mut steps = vec![0isize; ndim];
+            (0..ndim).rev().for_each(|a| {
+                steps[a] = (c % 3) as isize - 1;
+                c /= 3;
+            });
+            let nonzero = steps.iter().filter(|&&s| s != 0).count();
+            let first = steps.iter().copied().find(|&s| s != 0);
+            let connected = match connectivity {
+                Connectivity::Face => nonzero == 1,
+                Connectivity::Full => nonzero >= 1,
+            };
+            if connected && first == Some(-1) {
+                let offset = -steps
+                    .iter()
+                    .zip(strides.iter())
+                    .map(|(s, st)| s * st)
+                    .sum::<isize>();
+                Some(Neighbor {
+                    steps,
+                    offset: offset as usize,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Join a foreground pixel to its scanned foreground neighbors at or after
+/// `lower`, `parent` holds the parents of the pixels from `base` onward.
+fn join_neighbors(
+    i: usize,
+    mask: &[bool],
+    parent: &mut [usize],
+    base: usize,
+    shape: &[usize],
+    neighbors: &[Neighbor],
+    lower: usize,
+) {
+    let mut index = vec![0; shape.len()];
+    let mut r = i;
+    (0..shape.len()).rev().for_each(|a| {
+        index[a] = r % shape[a];
+        r /= shape[a];
+    });
+    neighbors.iter().for_each(|n| {
+        let inside = n
+            .steps
+            .iter()
+            .zip(index.iter().zip(shape.iter()))
+            .all(|(&s, (&x, &len))| {
+                let y = x as isize + s;
+                y >= 0 && (y as usize) < len
+            });
+        if inside && i - n.offset >= lower && mask[i - n.offset] {
+            union(parent, base, i, i - n.offset);
+        }
+    });
+}
+
+/// Find the root of a pixel with path halving.
+fn find(parent: &mut [usize], base: usize, mut i: usize) -> usize {
+    while parent[i - base] != i {
+        let grand = parent[parent[i - base] - base];
+        parent[i - base] = grand;
+        i = grand;
+    }
+
+    i
+}
+
+/// Join the sets of two pixels, the smallest root becomes the root.
+fn union(parent: &mut [usize], base: usize, a: usize, b: usize) {
+    let ra = find(parent, base, a);
+    let rb = find(parent, base, b);
+    if ra < rb {
+        parent[rb - base] = ra;
+    } else if rb < ra {
+        parent[ra - base] = rb;
+    }
+}
@@ -0,0 +1,7 @@
+//! Morphology functions.
+pub mod grayscale;
+pub use grayscale::{closing, dilation, erosion, gradient, opening};
+pub mod label;
+pub use label::{Connectivity, label};
+pub mod skeleton;
+pub use skeleton::{SkeletonAnalysis, analyze_skeleton, skeletonize};
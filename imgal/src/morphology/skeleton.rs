@@ -0,0 +1,350 @@
+use ndarray::{Array2, ArrayD, ArrayViewD, IxDyn};
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+
+/// The endpoints, branch points and length of a skeleton.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkeletonAnalysis {
+    /// The endpoint coordinates with shape (endpoints, ndim), pixels with a
+    /// single skeleton neighbor.
+    pub endpoints: Array2<usize>,
+    /// The branch point coordinates with shape (branch points, ndim), pixels
+    /// with three or more skeleton neighbors.
+    pub branch_points: Array2<usize>,
+    /// The total length of the skeleton, scaled by the pixel spacing.
+    pub length: f64,
+}
+
+/// Skeletonize a 2 or 3-dimensional boolean mask.
+///
+/// # Description
+///
+/// This function thins the `true` objects of a mask to their 1 pixel wide
+/// medial lines (_i.e._ the curve skeleton) while preserving their topology.
+/// Each iteration peels the border pixels facing each of the 2n axis
+/// directions in turn, a border pixel is removed if it is simple and not an
+/// endpoint. A pixel is simple if removing it does not change the topology of
+/// its neighborhood, which is tested with the topological numbers of
+/// Bertrand and Malandain: the foreground pixels of the 3ⁿ neighborhood must
+/// form a single component with full (8 or 26) connectivity and the
+/// background pixels adjacent to the pixel must form a single component with
+/// face (4 or 6) connectivity. The thinning stops when no pixel is removed.
+///
+/// # Arguments
+///
+/// * `mask`: The 2 or 3-dimensional boolean mask.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<bool>)`: The skeleton with the same shape as `mask`.
+/// * `Err(ImgalError)`: If the mask is not 2 or 3-dimensional.
+pub fn skeletonize(mask: ArrayViewD<bool>) -> Result<ArrayD<bool>, ImgalError> {
+    // check if the mask parameter is valid
+    let shape = mask.shape().to_vec();
+    validate_ndim(shape.len())?;
+
+    // thin the flattened mask in row-major order
+    let hood = Neighborhood::new(&shape);
+    let mut x: Vec<bool> = mask.iter().copied().collect();
+    loop {
+        let mut changed = false;
+        for dir in hood.faces.iter() {
+            // collect the simple border pixels facing the direction in parallel
+            let candidates: Vec<usize> = (0..x.len())
+                .into_par_iter()
+                .filter(|&p| x[p] && hood.removable(&x, p, Some(*dir)))
+                .collect();
+
+            // remove the candidates that are still removable one by one
+            candidates.into_iter().for_each(|p| {
+                if hood.removable(&x, p, None) {
+                    x[p] = false;
+                    changed = true;
+                }
+            });
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    Ok(ArrayD::from_shape_vec(IxDyn(&shape), x).unwrap())
+}
+
+/// Find the endpoints and branch points of a skeleton and measure its length.
+///
+/// # Description
+///
+/// This function analyzes a skeleton (_e.g._ the output of `skeletonize`)
+/// with full (8 or 26) connectivity. Endpoints have a single skeleton neighbor
+/// and branch points have three or more. The length is the sum of the
+/// distances between neighboring skeleton pixels, scaled by the pixel
+/// spacing. A diagonal step is not counted if the two pixels are also joined
+/// through a common neighbor with shorter steps, so the corners of a staircase
+/// are not counted twice.
+///
+/// # Arguments
+///
+/// * `skeleton`: The 2 or 3-dimensional skeleton.
+/// * `spacing`: The pixel spacing per axis, default = 1.0 for every axis. Each
+///   spacing must be > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(SkeletonAnalysis)`: The endpoints, branch points and length.
+/// * `Err(ImgalError)`: If the skeleton is not 2 or 3-dimensional. If the
+///   spacing does not have one value per axis or a spacing is <= 0.0.
+pub fn analyze_skeleton(
+    skeleton: ArrayViewD<bool>,
+    spacing: Option<&[f64]>,
+) -> Result<SkeletonAnalysis, ImgalError> {
+    // set optional parameters if needed
+    let shape = skeleton.shape().to_vec();
+    let ndim = shape.len();
+    let spacing = spacing.map_or_else(|| vec![1.0; ndim], |s| s.to_vec());
+
+    // check if the skeleton and spacing parameters are valid
+    validate_ndim(ndim)?;
+    validate::same_length(spacing.len(), ndim)?;
+    spacing
+        .iter()
+        .try_for_each(|&s| validate::positive("spacing", s))?;
+
+    let hood = Neighborhood::new(&shape);
+    let x: Vec<bool> = skeleton.iter().copied().collect();
+    let mut endpoints: Vec<usize> = Vec::new();
+    let mut branch_points: Vec<usize> = Vec::new();
+    let mut length = 0.0;
+    (0..x.len()).filter(|&p| x[p]).for_each(|p| {
+        let values = hood.values(&x, p);
+        let neighbors = (0..values.len())
+            .filter(|&o| o != hood.center && values[o])
+            .count();
+        match neighbors {
+            1 => endpoints.extend(hood.index(p)),
+            n if n >= 3 => branch_points.extend(hood.index(p)),
+            _ => {}
+        }
+
+        // count each step once, from the pixel that precedes in row-major order
+        (hood.center + 1..values.len())
+            .filter(|&o| values[o])
+            .for_each(|o| {
+                let order = hood.order[o];
+                let shortcut = (0..values.len()).any(|c| {
+                    values[c]
+                        && c != hood.center
+                        && c != o
+                        && hood.order[c] < order
+                        && hood.adjacent(c, o)
+                        && hood.step_order(c, o) < order
+                });
+                if !shortcut {
+                    length += hood.offsets[o]
+                        .iter()
+                        .zip(spacing.iter())
+                        .map(|(&d, s)| (d as f64 * s).powi(2))
+                        .sum::<f64>()
+                        .sqrt();
+                }
+            });
+    });
+    let to_array =
+        |coords: Vec<usize>| Array2::from_shape_vec((coords.len() / ndim, ndim), coords).unwrap();
+
+    Ok(SkeletonAnalysis {
+        endpoints: to_array(endpoints),
+        branch_points: to_array(branch_points),
+        length,
+    })
+}
+
+/// The 3ⁿ neighborhood of the pixels of a flattened 2 or 3-dimensional array.
+struct Neighborhood {
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    /// The per axis steps of each neighbor, in row-major order.
+    offsets: Vec<Vec<isize>>,
+    /// The number of non-zero steps of each neighbor.
+    order: Vec<usize>,
+    /// The index of the pixel itself.
+    center: usize,
+    /// The face neighbors.
+    faces: Vec<usize>,
+}
+
+impl Neighborhood {
+    /// Create the neighborhood of an array shape.
+    fn new(shape: &[usize]) -> Self {
+        let ndim = shape.len();
+        let mut strides = vec![1; ndim];
+        (0..ndim - 1)
+            .rev()
+            .for_each(|a| strides[a] = strides[a + 1] * shape[a + 1]);
+        let offsets: Vec<Vec<isize>> = (0..3usize.pow(ndim as u32))
+            .map(|code| {
+                let mut c = code;
+                let mut steps = vec![0isize; ndim];
+                (0..ndim).rev().for_each(|a| {
+                    steps[a] = (c % 3) as isize - 1;
+                    c /= 3;
+                });
+                steps
+            })
+            .collect();
+        let order: Vec<usize> = offsets
+            .iter()
+            .map(|s| s.iter().filter(|&&d| d != 0).count())
+            .collect();
+        let faces = (0..offsets.len()).filter(|&o| order[o] == 1).collect();
+
+        Neighborhood {
+            shape: shape.to_vec(),
+            strides,
+            center: offsets.len() / 2,
+            offsets,
+            order,
+            faces,
+        }
+    }
+
+    /// The multi-index of a flat index.
+    fn index(&self, p: usize) -> Vec<usize> {
+        self.strides
+            .iter()
+            .zip(self.shape.iter())
+            .map(|(&st, &len)| (p / st) % len)
+            .collect()
+    }
+
+    /// The values of the neighborhood of a pixel, pixels past the border are
+    /// `false`.
+    fn values(&self, x: &[bool], p: usize) -> Vec<bool> {
+        let index = self.index(p);
+        self.offsets
+            .iter()
+            .map(|steps| {
+                let mut q = 0;
+                for ((&i, &s), (&len, &st)) in index
+                    .iter()
+                    .zip(steps.iter())
+                    .zip(self.shape.iter().zip(self.strides.iter()))
+                {
+                    let j = i as isize + s;
+                    if j < 0 || j as usize >= len {
+                        return false;
+                    }
+                    q += j as usize * st;
+                }
+                x[q]
+            })
+            .collect()
+    }
+
+    /// Whether two neighbors are adjacent with full connectivity.
+    fn adjacent(&self, a: usize, b: usize) -> bool {
+        a != b
+            && self.offsets[a]
+                .iter()
+                .zip(self.offsets[b].iter())
+                .all(|(i, j)| (i - j).abs() <= 1)
+    }
+
+    /// The number of non-zero steps between two neighbors.
+    fn step_order(&self, a: usize, b: usize) -> usize {
+        self.offsets[a]
+            .iter()
+            .zip(self.offsets[b].iter())
+            .filter(|(i, j)| i != j)
+            .count()
+    }
+
+    /// Whether a foreground pixel can be removed: it is simple, not an endpoint
+    /// and, if a direction is given, its neighbor in that direction is
+    /// background.
+    fn removable(&self, x: &[bool], p: usize, direction: Option<usize>) -> bool {
+        let values = self.values(x, p);
+        if direction.is_some_and(|d| values[d]) {
+            return false;
+        }
+        let foreground: Vec<usize> = (0..values.len())
+            .filter(|&o| o != self.center && values[o])
+            .collect();
+        if foreground.len() <= 1 {
+            return false;
+        }
+
+        // the foreground neighbors form one fully connected component
+        if self.components(&foreground, |a, b| self.adjacent(a, b)) != 1 {
+            return false;
+        }
+
+        // the background neighbors with at most 2 non-zero steps that are face
+        // connected to the pixel form one face connected component
+        let background: Vec<usize> = (0..values.len())
+            .filter(|&o| o != self.center && !values[o] && self.order[o] <= 2)
+            .collect();
+        let labels = self.component_labels(&background, |a, b| self.step_order(a, b) == 1);
+        let mut touching: Vec<usize> = background
+            .iter()
+            .zip(labels.iter())
+            .filter(|&(&o, _)| self.order[o] == 1)
+            .map(|(_, &l)| l)
+            .collect();
+        touching.sort_unstable();
+        touching.dedup();
+
+        touching.len() == 1
+    }
+
+    /// Count the connected components of a set of neighbors.
+    fn components<F>(&self, set: &[usize], connected: F) -> usize
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        self.component_labels(set, connected)
+            .into_iter()
+            .max()
+            .map_or(0, |m| m + 1)
+    }
+
+    /// Label the connected components of a set of neighbors.
+    fn component_labels<F>(&self, set: &[usize], connected: F) -> Vec<usize>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let mut labels = vec![usize::MAX; set.len()];
+        let mut count = 0;
+        (0..set.len()).for_each(|start| {
+            if labels[start] != usize::MAX {
+                return;
+            }
+            labels[start] = count;
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                (0..set.len()).for_each(|j| {
+                    if labels[j] == usize::MAX && connected(set[i], set[j]) {
+                        labels[j] = count;
+                        stack.push(j);
+                    }
+                });
+            }
+            count += 1;
+        });
+
+        labels
+    }
+}
+
+/// Check that an array is 2 or 3-dimensional.
+fn validate_ndim(ndim: usize) -> Result<(), ImgalError> {
+    if ndim != 2 && ndim != 3 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "mask",
+            reason: format!("must be 2 or 3-dimensional but got {} dimensions", ndim),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use ndarray::{ArrayD, ArrayView1, ArrayViewD, ArrayViewMut1, Axis, IxDyn, Zip};
+
+use crate::error::warning::{self, Warning};
+use crate::error::{ImgalError, validate};
+use crate::operator::{LaneOp, PixelOp};
+use crate::traits::numeric::ToFloat64;
+
+/// Apply a pixel operator to each pixel of an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the output value of each pixel with the given pixel
+/// operator, in parallel. Registered operators can be looked up by name with
+/// `operator::pixel_op`.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional input image.
+/// * `op`: The pixel operator.
+/// * `mask`: An optional boolean mask, only pixels set to `true` are computed
+///   and all other output pixels are set to 0.0. Must match the shape of
+///   `data`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The output image with the same shape as `data`.
+/// * `Err(ImgalError)`: If the mask shape does not match the shape of `data`.
+pub fn pixel<T, O>(
+    data: ArrayViewD<T>,
+    op: &O,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+    O: PixelOp + ?Sized,
+{
+    // check if the mask is valid
+    if let Some(msk) = &mask {
+        validate::same_shape(msk.shape(), data.shape())?;
+    }
+
+    // compute each pixel, optionally only in the mask area
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    match mask {
+        Some(msk) => {
+            if !msk.iter().any(|&m| m) {
+                warning::warn(Warning::EmptyMask);
+            }
+            Zip::from(&mut output)
+                .and(&data)
+                .and(&msk)
+                .par_for_each(|o, v, &m| {
+                    if m {
+                        *o = op.compute(v.to_f64());
+                    }
+                });
+        }
+        None => {
+            Zip::from(&mut output)
+                .and(&data)
+                .par_for_each(|o, v| *o = op.compute(v.to_f64()));
+        }
+    }
+
+    Ok(output)
+}
+
+/// Apply a lane operator to each lane of an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the output values of each lane (_i.e._ the
+/// 1-dimensional slice along `axis`, _e.g._ the decay curve of a pixel) with
+/// the given lane operator, in parallel. The output image has the shape of
+/// the input image with the length of `axis` replaced by the number of output
+/// values per lane (see `LaneOp::output_len`). Registered operators can be
+/// looked up by name with `operator::lane_op`.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional input image.
+/// * `op`: The lane operator.
+/// * `mask`: An optional boolean mask with one value per lane, only lanes set
+///   to `true` are computed and the outputs of all other lanes are set to 0.0.
+///   Must match the shape of `data` without `axis`.
+/// * `axis`: The lane axis, default = the last axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The output image with the output values of each lane
+///   along `axis`.
+/// * `Err(ImgalError)`: If `axis` is >= the number of dimensions of `data`. If
+///   the mask shape does not match the shape of `data` without `axis`.
+pub fn lane<T, O>(
+    data: ArrayViewD<T>,
+    op: &O,
+    mask: Option<ArrayViewD<bool>>,
+    axis: Option<usize>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+    O: LaneOp + ?Sized,
+{
+    // set optional parameters if needed
+    let ndim = data.ndim();
+    let a = axis.unwrap_or(ndim.saturating_sub(1));
+
+    // check if the axis and mask are valid
+    validate::axis(a, ndim)?;
+    if let Some(msk) = &mask {
+        let mut shape = data.shape().to_vec();
+        shape.remove(a);
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // replace the lane axis with the output values of each lane
+    let n_out = op.output_len(data.len_of(Axis(a)));
+    let mut shape = data.shape().to_vec();
+    shape[a] = n_out;
+    let mut output = ArrayD::<f64>::zeros(IxDyn(&shape));
+
+    // compute each lane, optionally only in the mask area
+    let compute = |ln: ArrayView1<T>, mut out: ArrayViewMut1<f64>| {
+        let values: Vec<f64> = ln.iter().map(|v| v.to_f64()).collect();
+        let mut buf = vec![0.0; n_out];
+        op.compute(&values, &mut buf);
+        out.iter_mut().zip(buf).for_each(|(o, b)| *o = b);
+    };
+    let lanes = data.lanes(Axis(a));
+    let out_lanes = output.lanes_mut(Axis(a));
+    match mask {
+        Some(msk) => {
+            if !msk.iter().any(|&m| m) {
+                warning::warn(Warning::EmptyMask);
+            }
+            Zip::from(lanes)
+                .and(out_lanes)
+                .and(&msk)
+                .par_for_each(|ln, out, &m| {
+                    if m {
+                        compute(ln, out);
+                    }
+                });
+        }
+        None => {
+            Zip::from(lanes).and(out_lanes).par_for_each(compute);
+        }
+    }
+
+    Ok(output)
+}
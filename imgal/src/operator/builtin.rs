@@ -0,0 +1,57 @@
+use crate::operator::{LaneOp, PixelOp};
+
+/// The natural logarithm of one plus the pixel value, ln(1 + x).
+///
+/// Registered as the `log1p` pixel operator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Log1p;
+
+impl PixelOp for Log1p {
+    fn name(&self) -> &str {
+        "log1p"
+    }
+
+    fn compute(&self, value: f64) -> f64 {
+        value.ln_1p()
+    }
+}
+
+/// The sum of the lane values (_e.g._ the photon count of a decay curve).
+///
+/// Registered as the `sum` lane operator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum;
+
+impl LaneOp for Sum {
+    fn name(&self) -> &str {
+        "sum"
+    }
+
+    fn output_len(&self, _lane_len: usize) -> usize {
+        1
+    }
+
+    fn compute(&self, lane: &[f64], out: &mut [f64]) {
+        out[0] = lane.iter().sum();
+    }
+}
+
+/// The mean of the lane values, NaN for empty lanes.
+///
+/// Registered as the `mean` lane operator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mean;
+
+impl LaneOp for Mean {
+    fn name(&self) -> &str {
+        "mean"
+    }
+
+    fn output_len(&self, _lane_len: usize) -> usize {
+        1
+    }
+
+    fn compute(&self, lane: &[f64], out: &mut [f64]) {
+        out[0] = lane.iter().sum::<f64>() / lane.len() as f64;
+    }
+}
@@ -0,0 +1,11 @@
+//! Custom per-pixel and per-lane operator functions.
+pub mod apply;
+pub use apply::{lane, pixel};
+pub mod builtin;
+pub use builtin::{Log1p, Mean, Sum};
+pub mod op;
+pub use op::{LaneOp, PixelOp};
+pub mod registry;
+pub use registry::{
+    lane_op, lane_op_names, pixel_op, pixel_op_names, register_lane_op, register_pixel_op,
+};
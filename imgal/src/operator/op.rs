@@ -0,0 +1,41 @@
+/// A custom per-pixel operator.
+///
+/// # Description
+///
+/// A pixel operator maps the value of each pixel to one output value,
+/// independently of all other pixels. Pixel operators run in parallel over an
+/// n-dimensional image with `operator::pixel`, over the chunks of an image with
+/// `chunked::pixel_op` and can be registered by name with
+/// `operator::register_pixel_op`, which also makes them callable from the
+/// Python bindings.
+pub trait PixelOp: Send + Sync {
+    /// The unique name of the operator.
+    fn name(&self) -> &str;
+
+    /// Compute the output value of one pixel.
+    fn compute(&self, value: f64) -> f64;
+}
+
+/// A custom per-lane operator.
+///
+/// # Description
+///
+/// A lane operator maps the values of each lane (_i.e._ the 1-dimensional
+/// slice along an axis, _e.g._ the decay curve of a pixel) to a fixed number
+/// of output values, independently of all other lanes. Lane operators run in
+/// parallel over an n-dimensional image with `operator::lane`, over the chunks
+/// of an image with `chunked::lane_op` and can be registered by name with
+/// `operator::register_lane_op`, which also makes them callable from the
+/// Python bindings.
+pub trait LaneOp: Send + Sync {
+    /// The unique name of the operator.
+    fn name(&self) -> &str;
+
+    /// The number of output values of a lane with `lane_len` values.
+    fn output_len(&self, lane_len: usize) -> usize;
+
+    /// Compute the output values of one lane.
+    ///
+    /// `out` holds `output_len(lane.len())` values, all set to 0.0.
+    fn compute(&self, lane: &[f64], out: &mut [f64]);
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use crate::error::ImgalError;
+use crate::operator::builtin::{Log1p, Mean, Sum};
+use crate::operator::{LaneOp, PixelOp};
+
+/// The names of the built-in pixel operators.
+const BUILTIN_PIXEL_OPS: [&str; 1] = ["log1p"];
+
+/// The names of the built-in lane operators.
+const BUILTIN_LANE_OPS: [&str; 2] = ["mean", "sum"];
+
+/// The process wide registry of pixel operators, without the built-in ones.
+static PIXEL_OPS: RwLock<BTreeMap<String, Arc<dyn PixelOp>>> = RwLock::new(BTreeMap::new());
+
+/// The process wide registry of lane operators, without the built-in ones.
+static LANE_OPS: RwLock<BTreeMap<String, Arc<dyn LaneOp>>> = RwLock::new(BTreeMap::new());
+
+/// Register a pixel operator by its name.
+///
+/// # Description
+///
+/// This function adds a pixel operator to the process wide registry, where it
+/// can be looked up with `pixel_op` (_e.g._ by the Python bindings). The
+/// built-in `log1p` operator is always registered.
+///
+/// # Arguments
+///
+/// * `op`: The pixel operator.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the operator was registered.
+/// * `Err(ImgalError)`: If the operator name is empty or a pixel operator with
+///   the same name is already registered.
+pub fn register_pixel_op<O>(op: O) -> Result<(), ImgalError>
+where
+    O: PixelOp + 'static,
+{
+    let name = op.name().to_string();
+    let mut ops = write_lock(&PIXEL_OPS);
+    check_name(
+        &name,
+        BUILTIN_PIXEL_OPS.contains(&name.as_str()) || ops.contains_key(&name),
+    )?;
+    ops.insert(name, Arc::new(op));
+
+    Ok(())
+}
+
+/// Register a lane operator by its name.
+///
+/// # Description
+///
+/// This function adds a lane operator to the process wide registry, where it
+/// can be looked up with `lane_op` (_e.g._ by the Python bindings). The
+/// built-in `mean` and `sum` operators are always registered.
+///
+/// # Arguments
+///
+/// * `op`: The lane operator.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the operator was registered.
+/// * `Err(ImgalError)`: If the operator name is empty or a lane operator with
+///   the same name is already registered.
+pub fn register_lane_op<O>(op: O) -> Result<(), ImgalError>
+where
+    O: LaneOp + 'static,
+{
+    let name = op.name().to_string();
+    let mut ops = write_lock(&LANE_OPS);
+    check_name(
+        &name,
+        BUILTIN_LANE_OPS.contains(&name.as_str()) || ops.contains_key(&name),
+    )?;
+    ops.insert(name, Arc::new(op));
+
+    Ok(())
+}
+
+/// Get a registered pixel operator by name.
+///
+/// # Arguments
+///
+/// * `name`: The operator name.
+///
+/// # Returns
+///
+/// * `Option<Arc<dyn PixelOp>>`: The pixel operator, `None` if no pixel
+///   operator with the name is registered.
+pub fn pixel_op(name: &str) -> Option<Arc<dyn PixelOp>> {
+    match name {
+        "log1p" => Some(Arc::new(Log1p)),
+        _ => read_lock(&PIXEL_OPS).get(name).cloned(),
+    }
+}
+
+/// Get a registered lane operator by name.
+///
+/// # Arguments
+///
+/// * `name`: The operator name.
+///
+/// # Returns
+///
+/// * `Option<Arc<dyn LaneOp>>`: The lane operator, `None` if no lane operator
+///   with the name is registered.
+pub fn lane_op(name: &str) -> Option<Arc<dyn LaneOp>> {
+    match name {
+        "mean" => Some(Arc::new(Mean)),
+        "sum" => Some(Arc::new(Sum)),
+        _ => read_lock(&LANE_OPS).get(name).cloned(),
+    }
+}
+
+/// Get the names of all registered pixel operators, in ascending order.
+pub fn pixel_op_names() -> Vec<String> {
+    let mut names: Vec<String> = read_lock(&PIXEL_OPS).keys().cloned().collect();
+    names.extend(BUILTIN_PIXEL_OPS.map(String::from));
+    names.sort();
+
+    names
+}
+
+/// Get the names of all registered lane operators, in ascending order.
+pub fn lane_op_names() -> Vec<String> {
+    let mut names: Vec<String> = read_lock(&LANE_OPS).keys().cloned().collect();
+    names.extend(BUILTIN_LANE_OPS.map(String::from));
+    names.sort();
+
+    names
+}
+
+/// Check that an operator name is not empty and not taken.
+fn check_name(name: &str, taken: bool) -> Result<(), ImgalError> {
+    if name.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "op",
+            reason: "The operator name can not be empty.".to_string(),
+        });
+    }
+    if taken {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "op",
+            reason: format!("An operator named \"{}\" is already registered.", name),
+        });
+    }
+
+    Ok(())
+}
+
+/// Lock a registry for reading, a registry poisoned by a panic is still valid.
+fn read_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Lock a registry for writing, a registry poisoned by a panic is still valid.
+fn write_lock<T>(lock: &RwLock<T>) -> std::sync::RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
@@ -27,3 +27,95 @@ where
 {
     wavelength.to_f64() / (2.0 * na)
 }
+
+/// Compute the axial (z) diffraction limit.
+///
+/// # Description
+///
+/// This function computes the axial resolution of a widefield microscope
+/// using:
+///
+/// ```text
+/// d = 2 * wavelength * n / NA²
+/// ```
+///
+/// Where NA is the numerical aperture of the objective and n is the refractive
+/// index of the immersion medium.
+///
+/// # Arguments
+///
+/// * `wavelength`: The wavelength of light in nanometers.
+/// * `na`: The numerical aperture.
+/// * `refractive_index`: The refractive index of the immersion medium (_e.g._
+///   1.0 for air, 1.33 for water and 1.518 for oil).
+///
+/// # Returns
+///
+/// * `f64`: The axial diffraction limit.
+pub fn axial_diffraction_limit<T>(wavelength: T, na: f64, refractive_index: f64) -> f64
+where
+    T: ToFloat64,
+{
+    2.0 * wavelength.to_f64() * refractive_index / na.powi(2)
+}
+
+/// Compute the Airy disk radius.
+///
+/// # Description
+///
+/// This function computes the radius of the Airy disk, the distance from the
+/// center of the point spread function to its first minimum, using:
+///
+/// ```text
+/// r = 1.22 * wavelength * M / 2 * NA
+/// ```
+///
+/// Where NA is the numerical aperture of the objective and M is the total
+/// magnification. With a magnification of 1.0 the radius is in the sample
+/// plane and equal to the Rayleigh criterion, otherwise the radius is in the
+/// image (_e.g._ detector or pinhole) plane.
+///
+/// # Arguments
+///
+/// * `wavelength`: The wavelength of light in nanometers.
+/// * `na`: The numerical aperture.
+/// * `magnification`: The total magnification, default = 1.0.
+///
+/// # Returns
+///
+/// * `f64`: The Airy disk radius.
+pub fn airy_disk_radius<T>(wavelength: T, na: f64, magnification: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    let m = magnification.unwrap_or(1.0);
+    1.22 * wavelength.to_f64() * m / (2.0 * na)
+}
+
+/// Compute the Rayleigh criterion.
+///
+/// # Description
+///
+/// This function computes Lord Rayleigh's criterion, the smallest distance at
+/// which two point sources can be resolved, for a microscope using:
+///
+/// ```text
+/// d = 0.61 * wavelength / NA
+/// ```
+///
+/// Where NA is the numerical aperture of the objective.
+///
+/// # Arguments
+///
+/// * `wavelength`: The wavelength of light in nanometers.
+/// * `na`: The numerical aperture.
+///
+/// # Returns
+///
+/// * `f64`: Rayleigh's criterion.
+pub fn rayleigh_criterion<T>(wavelength: T, na: f64) -> f64
+where
+    T: ToFloat64,
+{
+    0.61 * wavelength.to_f64() / na
+}
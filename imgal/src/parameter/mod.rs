@@ -1,6 +1,14 @@
 //! Microscopy and imaging related parameter functions.
 pub mod diffraction;
-pub use diffraction::abbe_diffraction_limit;
+pub use diffraction::{
+    abbe_diffraction_limit, airy_disk_radius, axial_diffraction_limit, rayleigh_criterion,
+};
 
 pub mod omega;
 pub use omega::omega;
+
+pub mod precision;
+pub use precision::{lifetime_precision, phasor_precision, photon_budget, snr};
+
+pub mod sampling;
+pub use sampling::{nyquist_axial_step, nyquist_pixel_size};
@@ -0,0 +1,144 @@
+use crate::parameter::omega;
+use crate::phasor::plot::monoexponential_coordinates;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the expected lifetime precision for a photon count.
+///
+/// # Description
+///
+/// This function computes the expected standard deviation of a fluorescence
+/// lifetime estimate from the number of detected photons using:
+///
+/// ```text
+/// σ_τ = F * τ / √N
+/// ```
+///
+/// Where N is the photon count and F is the F-value (figure of merit) of the
+/// instrument and analysis, the ratio of the achieved relative precision to
+/// that of an ideal photon counting measurement. An ideal measurement has
+/// F = 1.0, real time-correlated single photon counting systems typically
+/// have F-values between 1.0 and 1.5.
+///
+/// # Arguments
+///
+/// * `photon_count`: N, the number of detected photons.
+/// * `tau`: The lifetime.
+/// * `f_value`: The F-value, default = 1.0.
+///
+/// # Returns
+///
+/// * `f64`: The standard deviation of the lifetime, in the units of `tau`.
+pub fn lifetime_precision<T>(photon_count: T, tau: f64, f_value: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    let f = f_value.unwrap_or(1.0);
+    f * tau / photon_count.to_f64().sqrt()
+}
+
+/// Compute the expected phasor coordinate precision for a photon count.
+///
+/// # Description
+///
+/// This function computes the expected standard deviations of the G and S
+/// phasor coordinates of a monoexponential decay measured with N photons. The
+/// phasor coordinates are the means of cos(nωt) and sin(nωt) over the photon
+/// arrival times, their shot noise variances are:
+///
+/// ```text
+/// σ²_G = ((1 + G₂ₙ) / 2 - Gₙ²) / N
+/// σ²_S = ((1 - G₂ₙ) / 2 - Sₙ²) / N
+/// ```
+///
+/// Where (Gₙ, Sₙ) are the phasor coordinates at harmonic n and G₂ₙ is the G
+/// coordinate at harmonic 2n. Background, the instrument response and time
+/// binning are not included, the values are the best achievable precision.
+///
+/// # Arguments
+///
+/// * `photon_count`: N, the number of detected photons.
+/// * `tau`: The lifetime of the monoexponential decay.
+/// * `period`: The period, in the same units as `tau`.
+/// * `harmonic`: The harmonic value, default = 1.0.
+///
+/// # Returns
+///
+/// * `(f64, f64)`: The standard deviations of the (G, S) phasor coordinates.
+pub fn phasor_precision<T>(
+    photon_count: T,
+    tau: f64,
+    period: f64,
+    harmonic: Option<f64>,
+) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    let h = harmonic.unwrap_or(1.0);
+    let n = photon_count.to_f64();
+    let w = omega(period) * h;
+    let (g, s) = monoexponential_coordinates(tau, w);
+    let (g_2, _) = monoexponential_coordinates(tau, 2.0 * w);
+    let var_g = ((1.0 + g_2) / 2.0 - g.powi(2)) / n;
+    let var_s = ((1.0 - g_2) / 2.0 - s.powi(2)) / n;
+
+    (var_g.max(0.0).sqrt(), var_s.max(0.0).sqrt())
+}
+
+/// Compute the photon count needed to reach a lifetime precision.
+///
+/// # Description
+///
+/// This function computes the photon budget needed to estimate a lifetime
+/// with a given relative precision (σ_τ / τ), see `lifetime_precision`:
+///
+/// ```text
+/// N = (F / (σ_τ / τ))²
+/// ```
+///
+/// # Arguments
+///
+/// * `relative_precision`: The target relative standard deviation of the
+///   lifetime, _e.g._ 0.05 for 5%.
+/// * `f_value`: The F-value, default = 1.0.
+///
+/// # Returns
+///
+/// * `f64`: The number of photons needed.
+pub fn photon_budget(relative_precision: f64, f_value: Option<f64>) -> f64 {
+    let f = f_value.unwrap_or(1.0);
+    (f / relative_precision).powi(2)
+}
+
+/// Compute the signal-to-noise ratio of a pixel.
+///
+/// # Description
+///
+/// This function computes the shot noise limited signal-to-noise ratio (SNR)
+/// of a pixel using:
+///
+/// ```text
+/// SNR = S / √(S + B + σ²)
+/// ```
+///
+/// Where S is the signal, B is the background and σ is the read noise, all in
+/// photons (or photoelectrons). Photon counting detectors have no read noise.
+///
+/// # Arguments
+///
+/// * `signal`: S, the signal in photons.
+/// * `background`: B, the background in photons, default = 0.0.
+/// * `read_noise`: σ, the root mean square read noise in photons, default =
+///   0.0.
+///
+/// # Returns
+///
+/// * `f64`: The signal-to-noise ratio.
+pub fn snr<T>(signal: T, background: Option<f64>, read_noise: Option<f64>) -> f64
+where
+    T: ToFloat64,
+{
+    let b = background.unwrap_or(0.0);
+    let r = read_noise.unwrap_or(0.0);
+    let s = signal.to_f64();
+    s / (s + b + r.powi(2)).sqrt()
+}
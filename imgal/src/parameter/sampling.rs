@@ -0,0 +1,63 @@
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the Nyquist axial step size.
+///
+/// # Description
+///
+/// This function computes the largest z-step size that samples the axial
+/// extent of a widefield point spread function at the Nyquist rate using:
+///
+/// ```text
+/// Δz = wavelength / 2 * n * (1 - cos(α))
+/// ```
+///
+/// Where α = asin(NA / n) is the half aperture angle of the objective, NA is
+/// the numerical aperture and n is the refractive index of the immersion
+/// medium. The numerical aperture must not exceed the refractive index,
+/// otherwise the step size is NaN.
+///
+/// # Arguments
+///
+/// * `wavelength`: The emission wavelength of light in nanometers.
+/// * `na`: The numerical aperture.
+/// * `refractive_index`: The refractive index of the immersion medium.
+///
+/// # Returns
+///
+/// * `f64`: The Nyquist axial step size.
+pub fn nyquist_axial_step<T>(wavelength: T, na: f64, refractive_index: f64) -> f64
+where
+    T: ToFloat64,
+{
+    let cos_alpha = (1.0 - (na / refractive_index).powi(2)).sqrt();
+    wavelength.to_f64() / (2.0 * refractive_index * (1.0 - cos_alpha))
+}
+
+/// Compute the Nyquist pixel size.
+///
+/// # Description
+///
+/// This function computes the largest pixel size, in the sample plane, that
+/// samples the lateral cutoff frequency of a widefield microscope at the
+/// Nyquist rate using:
+///
+/// ```text
+/// Δx = wavelength / 4 * NA
+/// ```
+///
+/// Where NA is the numerical aperture of the objective.
+///
+/// # Arguments
+///
+/// * `wavelength`: The emission wavelength of light in nanometers.
+/// * `na`: The numerical aperture.
+///
+/// # Returns
+///
+/// * `f64`: The Nyquist pixel size.
+pub fn nyquist_pixel_size<T>(wavelength: T, na: f64) -> f64
+where
+    T: ToFloat64,
+{
+    wavelength.to_f64() / (4.0 * na)
+}
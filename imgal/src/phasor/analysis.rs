@@ -0,0 +1,321 @@
+use ndarray::{Array2, Array3, ArrayView3, Axis, Ix2, Zip};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::phasor::{calibration, time_domain};
+use crate::threshold::manual_mask;
+use crate::traits::numeric::ToFloat64;
+
+/// A circular phasor cursor selecting the (G, S) coordinates within `radius`
+/// of the (`g`, `s`) center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhasorCursor {
+    /// The real component, G, of the cursor center.
+    pub g: f64,
+    /// The imaginary component, S, of the cursor center.
+    pub s: f64,
+    /// The cursor radius.
+    pub radius: f64,
+}
+
+/// The cached stages of a `PhasorAnalysis`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// The integrated decay intensity image, depends on the data only.
+    Intensity,
+    /// The uncalibrated (G, S) image, depends on the period and harmonic.
+    Phasor,
+    /// The intensity threshold mask, depends on the threshold.
+    Mask,
+    /// The calibrated and masked (G, S) image, depends on the `Phasor` and
+    /// `Mask` stages and the calibration.
+    Calibration,
+    /// The phasor cursor selection mask, depends on the `Calibration` stage
+    /// and the cursor.
+    Selection,
+}
+
+/// An incremental time domain phasor analysis.
+///
+/// # Description
+///
+/// A `PhasorAnalysis` holds a 3-dimensional decay image and caches each stage
+/// of the phasor workflow (see `Stage`). Changing a single parameter (_e.g._
+/// the threshold, calibration or cursor) only invalidates the stages that
+/// depend on it, the next stage request recomputes only those stages. This
+/// keeps interactive tools responsive, _e.g._ moving a cursor only recomputes
+/// the selection mask and not the phasor transform.
+///
+/// Stages are computed lazily on request. Pixels outside of the threshold mask
+/// are set to 0.0 in the calibrated phasor image and are never selected.
+#[derive(Debug, Clone)]
+pub struct PhasorAnalysis {
+    data: Array3<f64>,
+    period: f64,
+    harmonic: f64,
+    threshold: f64,
+    calibration: Option<(f64, f64)>,
+    cursor: Option<PhasorCursor>,
+    intensity: Option<Array2<f64>>,
+    phasor: Option<Array3<f64>>,
+    mask: Option<Array2<bool>>,
+    calibrated: Option<Array3<f64>>,
+    selection: Option<Array2<bool>>,
+}
+
+impl PhasorAnalysis {
+    /// Create a new phasor analysis of a 3-dimensional decay image.
+    ///
+    /// # Arguments
+    ///
+    /// * `data`: I(t), the decay data image.
+    /// * `period`: The period (_i.e._ time interval). Must be greater than 0.0.
+    /// * `harmonic`: The harmonic value, default = 1.0. Must be >= 1.0.
+    /// * `axis`: The decay or lifetime axis, default = 2.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PhasorAnalysis)`: A new phasor analysis with a threshold of 0.0, no
+    ///   calibration and no cursor.
+    /// * `Err(ImgalError)`: If axis is >= 3. If period is <= 0.0 or harmonic is
+    ///   < 1.0.
+    pub fn new<T>(
+        data: ArrayView3<T>,
+        period: f64,
+        harmonic: Option<f64>,
+        axis: Option<usize>,
+    ) -> Result<Self, ImgalError>
+    where
+        T: ToFloat64,
+    {
+        // set optional parameters if needed
+        let h = harmonic.unwrap_or(1.0);
+        let a = axis.unwrap_or(2);
+
+        // check if period, harmonic and axis parameters are valid
+        validate::axis(a, 3)?;
+        validate::positive("period", period)?;
+        validate::at_least("harmonic", h, 1.0)?;
+
+        // move the decay axis last, (row, col, tau)
+        let mut order = vec![0, 1, 2];
+        order.remove(a);
+        order.push(a);
+        let data = data
+            .permuted_axes((order[0], order[1], order[2]))
+            .mapv(|v| v.to_f64());
+
+        Ok(PhasorAnalysis {
+            data,
+            period,
+            harmonic: h,
+            threshold: 0.0,
+            calibration: None,
+            cursor: None,
+            intensity: None,
+            phasor: None,
+            mask: None,
+            calibrated: None,
+            selection: None,
+        })
+    }
+
+    /// Set the period, invalidates the `Phasor` stage and the stages after it.
+    pub fn set_period(&mut self, period: f64) -> Result<(), ImgalError> {
+        validate::positive("period", period)?;
+        if period != self.period {
+            self.period = period;
+            self.invalidate(Stage::Phasor);
+        }
+
+        Ok(())
+    }
+
+    /// Set the harmonic, invalidates the `Phasor` stage and the stages after
+    /// it.
+    pub fn set_harmonic(&mut self, harmonic: f64) -> Result<(), ImgalError> {
+        validate::at_least("harmonic", harmonic, 1.0)?;
+        if harmonic != self.harmonic {
+            self.harmonic = harmonic;
+            self.invalidate(Stage::Phasor);
+        }
+
+        Ok(())
+    }
+
+    /// Set the intensity threshold, pixels with an integrated intensity greater
+    /// than the threshold are analyzed. Invalidates the `Mask` stage and the
+    /// stages after it.
+    pub fn set_threshold(&mut self, threshold: f64) -> Result<(), ImgalError> {
+        if !threshold.is_finite() {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "threshold",
+                reason: format!("must be finite but got {}", threshold),
+            });
+        }
+        if threshold != self.threshold {
+            self.threshold = threshold;
+            self.invalidate(Stage::Mask);
+        }
+
+        Ok(())
+    }
+
+    /// Set the (modulation, phase) calibration, if `None` the phasor image is
+    /// not calibrated. Invalidates the `Calibration` stage and the stages after
+    /// it.
+    pub fn set_calibration(&mut self, calibration: Option<(f64, f64)>) -> Result<(), ImgalError> {
+        if let Some((m, p)) = calibration
+            && (!m.is_finite() || !p.is_finite())
+        {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "calibration",
+                reason: format!("must be finite but got ({}, {})", m, p),
+            });
+        }
+        if calibration != self.calibration {
+            self.calibration = calibration;
+            self.invalidate(Stage::Calibration);
+        }
+
+        Ok(())
+    }
+
+    /// Set the phasor cursor, if `None` no pixels are selected. Invalidates the
+    /// `Selection` stage.
+    pub fn set_cursor(&mut self, cursor: Option<PhasorCursor>) -> Result<(), ImgalError> {
+        if let Some(c) = cursor {
+            validate::positive("radius", c.radius)?;
+        }
+        if cursor != self.cursor {
+            self.cursor = cursor;
+            self.invalidate(Stage::Selection);
+        }
+
+        Ok(())
+    }
+
+    /// Whether a stage is cached and will not be recomputed on request.
+    pub fn is_cached(&self, stage: Stage) -> bool {
+        match stage {
+            Stage::Intensity => self.intensity.is_some(),
+            Stage::Phasor => self.phasor.is_some(),
+            Stage::Mask => self.mask.is_some(),
+            Stage::Calibration => self.calibrated.is_some(),
+            Stage::Selection => self.selection.is_some(),
+        }
+    }
+
+    /// The 2-dimensional integrated decay intensity image.
+    pub fn intensity(&mut self) -> &Array2<f64> {
+        self.intensity
+            .get_or_insert_with(|| self.data.sum_axis(Axis(2)))
+    }
+
+    /// The uncalibrated (G, S) image as a 3D (row, col, ch) image. Pixels
+    /// without intensity are set to 0.0.
+    pub fn phasor(&mut self) -> &Array3<f64> {
+        if self.phasor.is_none() {
+            let nonzero = self.intensity().mapv(|v| v != 0.0);
+            let gs = time_domain::image(
+                self.data.view(),
+                self.period,
+                Some(nonzero.view()),
+                Some(self.harmonic),
+                Some(2),
+            )
+            .expect("the period, harmonic and axis are validated when set");
+            self.phasor = Some(gs);
+        }
+
+        self.phasor.as_ref().unwrap()
+    }
+
+    /// The 2-dimensional intensity threshold mask.
+    pub fn mask(&mut self) -> &Array2<bool> {
+        if self.mask.is_none() {
+            let threshold = self.threshold;
+            let mask = manual_mask(self.intensity().view().into_dyn(), threshold)
+                .into_dimensionality::<Ix2>()
+                .unwrap();
+            self.mask = Some(mask);
+        }
+
+        self.mask.as_ref().unwrap()
+    }
+
+    /// The calibrated (G, S) image as a 3D (row, col, ch) image. Pixels outside
+    /// of the threshold mask are set to 0.0.
+    pub fn calibrated(&mut self) -> &Array3<f64> {
+        if self.calibrated.is_none() {
+            let mask = self.mask().clone();
+            let calibration = self.calibration;
+            let phasor = self.phasor();
+            let gs = match calibration {
+                Some((m, p)) => calibration::image(phasor.view(), m, p, Some(mask.view()), Some(2))
+                    .expect("the mask matches the phasor image shape"),
+                None => {
+                    let mut gs = phasor.clone();
+                    Zip::from(gs.lanes_mut(Axis(2)))
+                        .and(&mask)
+                        .par_for_each(|mut ln, &m| {
+                            if !m {
+                                ln.fill(0.0);
+                            }
+                        });
+                    gs
+                }
+            };
+            self.calibrated = Some(gs);
+        }
+
+        self.calibrated.as_ref().unwrap()
+    }
+
+    /// The 2-dimensional phasor cursor selection mask, `None` if no cursor is
+    /// set. Only pixels inside of the threshold mask are selected.
+    pub fn selection(&mut self) -> Option<&Array2<bool>> {
+        let cursor = self.cursor?;
+        if self.selection.is_none() {
+            let mask = self.mask().clone();
+            let gs = self.calibrated();
+            let r_sq = cursor.radius * cursor.radius;
+            let mut sel = Array2::<bool>::default(mask.dim());
+            Zip::from(&mut sel)
+                .and(gs.lanes(Axis(2)))
+                .and(&mask)
+                .par_for_each(|s, ln, &m| {
+                    let dg = ln[0] - cursor.g;
+                    let ds = ln[1] - cursor.s;
+                    *s = m && dg * dg + ds * ds <= r_sq;
+                });
+            self.selection = Some(sel);
+        }
+
+        self.selection.as_ref()
+    }
+
+    /// Invalidate a stage and the stages that depend on it.
+    fn invalidate(&mut self, stage: Stage) {
+        match stage {
+            Stage::Intensity => {
+                self.intensity = None;
+                self.invalidate(Stage::Mask);
+                self.invalidate(Stage::Phasor);
+            }
+            Stage::Phasor => {
+                self.phasor = None;
+                self.invalidate(Stage::Calibration);
+            }
+            Stage::Mask => {
+                self.mask = None;
+                self.invalidate(Stage::Calibration);
+            }
+            Stage::Calibration => {
+                self.calibrated = None;
+                self.invalidate(Stage::Selection);
+            }
+            Stage::Selection => self.selection = None,
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use ndarray::{Array3, ArrayView3, ArrayViewMut3, Axis, Zip};
-use rayon::prelude::*;
+use ndarray::{
+    Array3, ArrayView1, ArrayView2, ArrayView3, ArrayViewMut1, ArrayViewMut3, Axis, Zip,
+};
 
+use crate::error::warning::{self, Warning};
+use crate::error::{ImgalError, validate};
 use crate::phasor::plot;
-use crate::traits::numeric::ToFloat64;
+use crate::traits::numeric::{Float, ToFloat64};
 
 /// Calibrate a real and imaginary (G, S) coordinates.
 ///
@@ -65,41 +68,61 @@ pub fn coordinates(g: f64, s: f64, modulation: f64, phase: f64) -> (f64, f64) {
 ///    respectively.
 /// * `modulation`: The modulation to scale the input (G, S) coordinates.
 /// * `phase`: The phase, φ angle, to rotate the input (G, S) coordinates.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the channel
+///   axis.
 /// * `axis`: The channel axis, default = 2.
 ///
 /// # Returns
 ///
-/// * `Array3<f64>`: A 3-dimensional array with the calibrated phasor values,
-///    where calibrated G and S are channels 0 and 1 respectively.
+/// * `Ok(Array3<f64>)`: A 3-dimensional array with the calibrated phasor
+///    values, where calibrated G and S are channels 0 and 1 respectively.
+/// * `Err(ImgalError)`: If axis is >= 3. If the mask shape does not match the
+///   image shape.
 pub fn image<T>(
     data: ArrayView3<T>,
     modulation: f64,
     phase: f64,
+    mask: Option<ArrayView2<bool>>,
     axis: Option<usize>,
-) -> Array3<f64>
+) -> Result<Array3<f64>, ImgalError>
 where
     T: ToFloat64,
 {
     // set optional parameters if needed
     let a = axis.unwrap_or(2);
 
+    // check if the axis and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate_mask(data.shape(), mask, a)?;
+
     // allocate new array of the same shape for calibrated data
     let shape = data.dim();
     let mut c_data = Array3::<f64>::zeros(shape);
 
-    // read input data and save calibration to the new array
+    // read input data and save calibration to the new array, optionally only
+    // in mask area
     let g_trans = modulation * phase.cos();
     let s_trans = modulation * phase.sin();
-    let src_lanes = data.lanes(Axis(a));
-    let dst_lanes = c_data.lanes_mut(Axis(a));
-    Zip::from(src_lanes)
-        .and(dst_lanes)
-        .par_for_each(|s_ln, mut d_ln| {
-            d_ln[0] = s_ln[0].to_f64() * g_trans - s_ln[1].to_f64() * s_trans;
-            d_ln[1] = s_ln[0].to_f64() * s_trans + s_ln[1].to_f64() * g_trans;
+    let calibrate = |s_ln: ArrayView1<T>, mut d_ln: ArrayViewMut1<f64>| {
+        d_ln[0] = s_ln[0].to_f64() * g_trans - s_ln[1].to_f64() * s_trans;
+        d_ln[1] = s_ln[0].to_f64() * s_trans + s_ln[1].to_f64() * g_trans;
+    };
+    let lanes = Zip::from(data.lanes(Axis(a))).and(c_data.lanes_mut(Axis(a)));
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        lanes.and(msk).par_for_each(|s_ln, d_ln, m| {
+            if *m {
+                calibrate(s_ln, d_ln);
+            }
         });
+    } else {
+        lanes.par_for_each(calibrate);
+    }
 
-    c_data
+    Ok(c_data)
 }
 
 /// Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional phasor
@@ -120,7 +143,9 @@ where
 /// Where G' and S' are the calibrated real and imaginary values after rotation
 /// and scaling.
 ///
-/// This function mutates the input array and does not create a new array.
+/// This function mutates the input array and does not create a new array. The
+/// phasor image keeps its `f32` or `f64` element type, each coordinate pair is
+/// calibrated in `f64`.
 ///
 /// # Arguments
 ///
@@ -128,22 +153,58 @@ where
 ///    respectively.
 /// * `modulation`: The modulation to scale the input (G, S) coordinates.
 /// * `phase`: The phase, φ angle, to rotate the input (G, S) coordinates.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and left unchanged. Must match the shape of `data` without the
+///   channel axis.
 /// * `axis`: The channel axis, default = 2.
-pub fn image_mut(mut data: ArrayViewMut3<f64>, modulation: f64, phase: f64, axis: Option<usize>) {
+///
+/// # Returns
+///
+/// * `Ok(())`: If the image was calibrated.
+/// * `Err(ImgalError)`: If axis is >= 3. If the mask shape does not match the
+///   image shape.
+pub fn image_mut<T>(
+    mut data: ArrayViewMut3<T>,
+    modulation: f64,
+    phase: f64,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<(), ImgalError>
+where
+    T: Float,
+{
     // set optional axis parameter if needed
     let a = axis.unwrap_or(2);
 
+    // check if the axis and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate_mask(data.shape(), mask, a)?;
+
     // initialize calibration parameters
     let g_trans = modulation * phase.cos();
     let s_trans = modulation * phase.sin();
+    let calibrate = |mut ln: ArrayViewMut1<T>| {
+        let (g, s) = (ln[0].to_f64(), ln[1].to_f64());
+        ln[0] = T::from_f64(g * g_trans - s * s_trans);
+        ln[1] = T::from_f64(g * s_trans + s * g_trans);
+    };
+
+    // calibrate each lane, optionally only in mask area
+    let lanes = Zip::from(data.lanes_mut(Axis(a)));
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        lanes.and(msk).par_for_each(|ln, m| {
+            if *m {
+                calibrate(ln);
+            }
+        });
+    } else {
+        lanes.par_for_each(calibrate);
+    }
 
-    let lanes = data.lanes_mut(Axis(a));
-    lanes.into_iter().par_bridge().for_each(|mut ln| {
-        let g_cal = ln[0] * g_trans - ln[1] * s_trans;
-        let s_cal = ln[0] * s_trans + ln[1] * g_trans;
-        ln[0] = g_cal;
-        ln[1] = s_cal;
-    });
+    Ok(())
 }
 
 /// Find the modulation and phase calibration values.
@@ -181,3 +242,19 @@ pub fn modulation_and_phase(g: f64, s: f64, tau: f64, omega: f64) -> (f64, f64)
 
     (d_mod, d_phs)
 }
+
+/// Check if an optional mask matches the shape of the image without the
+/// channel axis.
+fn validate_mask(
+    shape: &[usize],
+    mask: Option<ArrayView2<bool>>,
+    axis: usize,
+) -> Result<(), ImgalError> {
+    if let Some(msk) = mask {
+        let mut shape = shape.to_vec();
+        shape.remove(axis);
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    Ok(())
+}
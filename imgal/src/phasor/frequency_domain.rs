@@ -0,0 +1,231 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array2, Array3, ArrayView1, ArrayView2, ArrayView3, Axis, Zip, stack};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the real and imaginary (G, S) coordinates of a 1-dimensional set of
+/// homodyne phase samples.
+///
+/// # Description
+///
+/// Gated or modulated (_e.g._ frequency domain) cameras sample the emission at
+/// `k` phase offsets (φₖ) of the excitation modulation. Each sample follows the
+/// homodyne equation:
+///
+/// ```text
+/// I(φₖ) = DC × (1 + M × cos(φₖ - ϕ))
+///       = DC + DC × G × cos(φₖ) + DC × S × sin(φₖ)
+/// ```
+///
+/// where `G = M × cos(ϕ)` and `S = M × sin(ϕ)`. This function subtracts the
+/// camera `bias` (_i.e._ dark offset) from each sample and solves for DC, G and
+/// S by linear least squares. For `k` evenly spaced phases this reduces to the
+/// discrete Fourier transform:
+///
+/// ```text
+/// G = (2/k) × Σₖ I(φₖ) × cos(φₖ) / ((1/k) × Σₖ I(φₖ))
+/// S = (2/k) × Σₖ I(φₖ) × sin(φₖ) / ((1/k) × Σₖ I(φₖ))
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: I(φ), the phase samples. At least 3 samples are required.
+/// * `phases`: The phase offset, in radians, of each sample. If `None`, the
+///   samples are assumed to be evenly spaced over one modulation period
+///   (_i.e._ φₖ = 2πk/n).
+/// * `bias`: The camera bias (_i.e._ dark offset) subtracted from each sample,
+///   default = 0.0.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The real and imaginary coordinates, (G, S). If the bias
+///   corrected samples have no intensity, G and S are NaN.
+/// * `Err(ImgalError)`: If `data` has less than 3 samples. If `phases` and
+///   `data` lengths do not match or the phases do not span enough distinct
+///   angles to solve for G and S.
+pub fn coordinates<T>(
+    data: &[T],
+    phases: Option<&[f64]>,
+    bias: Option<f64>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let b = bias.unwrap_or(0.0);
+
+    let weights = homodyne_weights(data.len(), phases)?;
+
+    Ok(homodyne_lane(ArrayView1::from(data), &weights, b))
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional
+/// homodyne phase image.
+///
+/// # Description
+///
+/// This function computes the phasor coordinates of each pixel of a stack of
+/// phase images acquired by a gated or modulated camera, see `coordinates` for
+/// the homodyne equations. The least squares weights are computed once and
+/// shared by all pixels.
+///
+/// # Arguments
+///
+/// * `data`: I(φ), the phase image stack. At least 3 phase images are required.
+/// * `phases`: The phase offset, in radians, of each phase image. If `None`,
+///   the phase images are assumed to be evenly spaced over one modulation
+///   period (_i.e._ φₖ = 2πk/n).
+/// * `bias`: The camera bias (_i.e._ dark offset) subtracted from each sample,
+///   default = 0.0.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the phase
+///   axis.
+/// * `axis`: The phase axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The real and imaginary coordinates as a 3D (row, col, ch)
+///   image, where G and S are indexed at 0 and 1 respectively on the _channel_
+///   axis.
+/// * `Err(ImgalError)`: If axis is >= 3. If the phase axis has less than 3
+///   samples. If `phases` does not match the phase axis length or does not span
+///   enough distinct angles. If the mask shape does not match the image shape.
+pub fn image<T>(
+    data: ArrayView3<T>,
+    phases: Option<&[f64]>,
+    bias: Option<f64>,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let b = bias.unwrap_or(0.0);
+    let a = axis.unwrap_or(2);
+
+    // check if axis and mask parameters are valid
+    validate::axis(a, 3)?;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(msk) = mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+    let weights = homodyne_weights(data.len_of(Axis(a)), phases)?;
+
+    // compute phasor coordinates per lane, optionally only in mask area
+    let mut g_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut s_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let lanes = data.lanes(Axis(a));
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        Zip::from(lanes)
+            .and(msk)
+            .and(&mut g_arr)
+            .and(&mut s_arr)
+            .par_for_each(|ln, m, g, s| {
+                if *m {
+                    (*g, *s) = homodyne_lane(ln, &weights, b);
+                }
+            });
+    } else {
+        Zip::from(lanes)
+            .and(&mut g_arr)
+            .and(&mut s_arr)
+            .par_for_each(|ln, g, s| {
+                (*g, *s) = homodyne_lane(ln, &weights, b);
+            });
+    }
+
+    // report pixels with no intensity, their G/S values are NaN
+    let nan_count = g_arr.iter().filter(|v| !v.is_finite()).count();
+    if nan_count > 0 {
+        warning::warn(Warning::NonFiniteOutput { count: nan_count });
+    }
+
+    // stack G and S arrays, (row, col, ch)
+    Ok(stack(Axis(2), &[g_arr.view(), s_arr.view()]).unwrap())
+}
+
+/// Compute the bias corrected G and S coordinates of a single phase lane.
+fn homodyne_lane<T>(lane: ArrayView1<T>, weights: &[Vec<f64>; 3], bias: f64) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    let mut dc = 0.0;
+    let mut gv = 0.0;
+    let mut sv = 0.0;
+    lane.iter().enumerate().for_each(|(k, v)| {
+        let vf = (*v).to_f64() - bias;
+        dc += weights[0][k] * vf;
+        gv += weights[1][k] * vf;
+        sv += weights[2][k] * vf;
+    });
+    if dc == 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+
+    (gv / dc, sv / dc)
+}
+
+/// Compute the least squares weights that map phase samples to the DC, cosine
+/// and sine homodyne coefficients.
+fn homodyne_weights(n: usize, phases: Option<&[f64]>) -> Result<[Vec<f64>; 3], ImgalError> {
+    validate::at_least("phases", n as f64, 3.0)?;
+
+    // evenly spaced phases, the least squares solution is the DFT
+    let Some(ph) = phases else {
+        let nf = n as f64;
+        let dc = vec![1.0 / nf; n];
+        let cos = (0..n)
+            .map(|k| 2.0 / nf * (2.0 * PI * k as f64 / nf).cos())
+            .collect();
+        let sin = (0..n)
+            .map(|k| 2.0 / nf * (2.0 * PI * k as f64 / nf).sin())
+            .collect();
+        return Ok([dc, cos, sin]);
+    };
+    validate::same_length(n, ph.len())?;
+
+    // build the normal equations (AᵀA) for the design rows [1, cos(φ), sin(φ)]
+    let rows: Vec<[f64; 3]> = ph.iter().map(|p| [1.0, p.cos(), p.sin()]).collect();
+    let mut ata = [[0.0; 3]; 3];
+    rows.iter().for_each(|r| {
+        for i in 0..3 {
+            for j in 0..3 {
+                ata[i][j] += r[i] * r[j];
+            }
+        }
+    });
+
+    // invert AᵀA by cofactor expansion, it is singular if the phases do not
+    // span at least 3 distinct angles
+    let cof = |i: usize, j: usize| {
+        let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+        let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+        ata[r0][c0] * ata[r1][c1] - ata[r0][c1] * ata[r1][c0]
+    };
+    let det = (0..3).map(|j| ata[0][j] * cof(0, j)).sum::<f64>();
+    if det.abs() < 1e-12 * (n * n * n) as f64 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "phases",
+            reason: "must contain at least 3 distinct phase angles".to_string(),
+        });
+    }
+    let inv = [0, 1, 2].map(|i| [0, 1, 2].map(|j| cof(j, i) / det));
+
+    // weights are the rows of (AᵀA)⁻¹Aᵀ
+    let weights = [0, 1, 2].map(|i| {
+        rows.iter()
+            .map(|r| inv[i][0] * r[0] + inv[i][1] * r[1] + inv[i][2] * r[2])
+            .collect()
+    });
+
+    Ok(weights)
+}
@@ -0,0 +1,246 @@
+use ndarray::{Array2, ArrayView2, ArrayView3, Axis, Zip};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+
+/// Compute the bound fraction of a two component (_e.g._ free and bound NADH)
+/// phasor coordinate.
+///
+/// # Description
+///
+/// Phasor coordinates are linear in the fractional intensities of their
+/// components, so a mixture of free and bound species lies on the line between
+/// the free (F) and bound (B) reference coordinates. This function projects the
+/// phasor coordinate (P) onto that line:
+///
+/// ```text
+/// f_bound = ((P - F) · (B - F)) / |B - F|²
+/// ```
+///
+/// The bound fraction is clamped to [0, 1], coordinates beyond either reference
+/// are assigned to that reference.
+///
+/// # Arguments
+///
+/// * `g`: The real component, G, of a calibrated phasor coordinate.
+/// * `s`: The imaginary component, S, of a calibrated phasor coordinate.
+/// * `free`: The (G, S) coordinates of the free species, _e.g._ free NADH.
+/// * `bound`: The (G, S) coordinates of the bound species, _e.g._ protein bound
+///   NADH.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The fractional intensity of the bound species. NaN if `g` or `s`
+///   is NaN.
+/// * `Err(ImgalError)`: If the free and bound reference coordinates are equal
+///   or not finite.
+pub fn bound_fraction(
+    g: f64,
+    s: f64,
+    free: (f64, f64),
+    bound: (f64, f64),
+) -> Result<f64, ImgalError> {
+    let trajectory = Trajectory::new(free, bound)?;
+
+    Ok(trajectory.bound_fraction(g, s))
+}
+
+/// Compute a bound fraction image from a 3-dimensional phasor image.
+///
+/// # Description
+///
+/// This function computes the bound fraction of each pixel of a calibrated
+/// phasor (G, S) image, see `bound_fraction`.
+///
+/// # Arguments
+///
+/// * `data`: The G/S 3-dimensional array.
+/// * `free`: The (G, S) coordinates of the free species, _e.g._ free NADH.
+/// * `bound`: The (G, S) coordinates of the bound species, _e.g._ protein bound
+///   NADH.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the channel
+///   axis.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional bound fraction image.
+/// * `Err(ImgalError)`: If axis is >= 3. If the channel axis does not have a
+///   length of 2. If the mask shape does not match the image shape. If the free
+///   and bound reference coordinates are equal or not finite.
+pub fn bound_fraction_image(
+    data: ArrayView3<f64>,
+    free: (f64, f64),
+    bound: (f64, f64),
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError> {
+    let trajectory = Trajectory::new(free, bound)?;
+
+    map_image(data, mask, axis, |g, s| trajectory.bound_fraction(g, s))
+}
+
+/// Compute the metabolic index of a two component phasor coordinate.
+///
+/// # Description
+///
+/// The metabolic index is the ratio of the free to bound fractional
+/// intensities:
+///
+/// ```text
+/// MI = f_free / f_bound = (1 - f_bound) / f_bound
+/// ```
+///
+/// where the bound fraction is computed with `bound_fraction`. For NADH, higher
+/// values indicate a shift towards glycolysis and lower values a shift towards
+/// oxidative phosphorylation.
+///
+/// # Arguments
+///
+/// * `g`: The real component, G, of a calibrated phasor coordinate.
+/// * `s`: The imaginary component, S, of a calibrated phasor coordinate.
+/// * `free`: The (G, S) coordinates of the free species, _e.g._ free NADH.
+/// * `bound`: The (G, S) coordinates of the bound species, _e.g._ protein bound
+///   NADH.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The metabolic index. Infinite if the coordinate lies on (or
+///   beyond) the free reference.
+/// * `Err(ImgalError)`: If the free and bound reference coordinates are equal
+///   or not finite.
+pub fn metabolic_index(
+    g: f64,
+    s: f64,
+    free: (f64, f64),
+    bound: (f64, f64),
+) -> Result<f64, ImgalError> {
+    let trajectory = Trajectory::new(free, bound)?;
+
+    Ok(trajectory.metabolic_index(g, s))
+}
+
+/// Compute a metabolic index image from a 3-dimensional phasor image.
+///
+/// # Description
+///
+/// This function computes the metabolic index of each pixel of a calibrated
+/// phasor (G, S) image, see `metabolic_index`.
+///
+/// # Arguments
+///
+/// * `data`: The G/S 3-dimensional array.
+/// * `free`: The (G, S) coordinates of the free species, _e.g._ free NADH.
+/// * `bound`: The (G, S) coordinates of the bound species, _e.g._ protein bound
+///   NADH.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the channel
+///   axis.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional metabolic index image.
+/// * `Err(ImgalError)`: If axis is >= 3. If the channel axis does not have a
+///   length of 2. If the mask shape does not match the image shape. If the free
+///   and bound reference coordinates are equal or not finite.
+pub fn metabolic_index_image(
+    data: ArrayView3<f64>,
+    free: (f64, f64),
+    bound: (f64, f64),
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError> {
+    let trajectory = Trajectory::new(free, bound)?;
+
+    map_image(data, mask, axis, |g, s| trajectory.metabolic_index(g, s))
+}
+
+/// The line between the free and bound reference phasor coordinates.
+struct Trajectory {
+    free: (f64, f64),
+    direction: (f64, f64),
+    length_sq: f64,
+}
+
+impl Trajectory {
+    /// Create a new trajectory from the free and bound reference coordinates.
+    fn new(free: (f64, f64), bound: (f64, f64)) -> Result<Self, ImgalError> {
+        let direction = (bound.0 - free.0, bound.1 - free.1);
+        let length_sq = direction.0 * direction.0 + direction.1 * direction.1;
+        if !length_sq.is_finite() || length_sq == 0.0 {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "bound",
+                reason: "must be finite and differ from the free reference coordinates".to_string(),
+            });
+        }
+
+        Ok(Trajectory {
+            free,
+            direction,
+            length_sq,
+        })
+    }
+
+    /// Project a coordinate onto the trajectory, clamped to [0, 1].
+    fn bound_fraction(&self, g: f64, s: f64) -> f64 {
+        let dot = (g - self.free.0) * self.direction.0 + (s - self.free.1) * self.direction.1;
+
+        (dot / self.length_sq).clamp(0.0, 1.0)
+    }
+
+    /// Compute the ratio of the free to bound fractions of a coordinate.
+    fn metabolic_index(&self, g: f64, s: f64) -> f64 {
+        let fb = self.bound_fraction(g, s);
+
+        (1.0 - fb) / fb
+    }
+}
+
+/// Map each (G, S) lane of a phasor image to a value.
+fn map_image<F>(
+    data: ArrayView3<f64>,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+    f: F,
+) -> Result<Array2<f64>, ImgalError>
+where
+    F: Fn(f64, f64) -> f64 + Sync,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if axis, channel and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate::same_length(2, data.len_of(Axis(a)))?;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(msk) = mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // compute the value per lane, optionally only in mask area
+    let mut out = Array2::<f64>::zeros((shape[0], shape[1]));
+    let lanes = data.lanes(Axis(a));
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        Zip::from(lanes)
+            .and(msk)
+            .and(&mut out)
+            .par_for_each(|ln, m, o| {
+                if *m {
+                    *o = f(ln[0], ln[1]);
+                }
+            });
+    } else {
+        Zip::from(lanes)
+            .and(&mut out)
+            .par_for_each(|ln, o| *o = f(ln[0], ln[1]));
+    }
+
+    Ok(out)
+}
@@ -1,4 +1,10 @@
-//! Phasor compute, calibration, and plot functions.
+//! Phasor compute, calibration, metabolic mapping, spectral unmixing,
+//! incremental analysis, and plot functions.
+pub mod analysis;
 pub mod calibration;
+pub mod frequency_domain;
+pub mod metabolic;
 pub mod plot;
+pub mod spectral;
 pub mod time_domain;
+pub mod waveform;
@@ -1,9 +1,11 @@
 use std::collections::HashSet;
 use std::f64;
 
-use ndarray::{Array2, ArrayView3, Axis, Zip};
+use ndarray::{Array2, ArrayView1, ArrayView2, ArrayView3, Axis, Zip};
 
-use crate::error::ArrayError;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
 
 /// Compute the modulation of phasor G and S coordinates.
 ///
@@ -103,36 +105,35 @@ pub fn monoexponential_coordinates(tau: f64, omega: f64) -> (f64, f64) {
 ///    The `g_coords` and `s_coords` array lengths must match.
 /// * `s_coords`: A 1-dimensional array of `s` coordiantes in the `data` array.
 /// *  The `s_coords` and `g_coords` array lengths must match.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to `false`. Must match the shape of `data` without the
+///   channel axis.
 /// * `axis`: The channel axis, default = 2.
 ///
 /// # Returns
 ///
 /// * `Ok(Array2<bool>)`: A 2-dimensional boolean mask where `true` pixels
 ///    represent values found in the `g_coords` and `s_coords` arrays.
-/// * `Err(ArrayError)`: If "g" and "s" coordinate array lengths do not match.
+/// * `Err(ImgalError)`: If "g" and "s" coordinate array lengths do not match.
+///   If axis is >= 3. If the mask shape does not match the image shape.
 pub fn map_mask(
     data: ArrayView3<f64>,
     g_coords: &[f64],
     s_coords: &[f64],
+    mask: Option<ArrayView2<bool>>,
     axis: Option<usize>,
-) -> Result<Array2<bool>, ArrayError> {
+) -> Result<Array2<bool>, ImgalError> {
     // check g and s coords array lengths
     let gl = g_coords.len();
-    let sl = s_coords.len();
-    if gl != sl {
-        return Err(ArrayError::MismatchedArrayLengths {
-            a_arr_len: gl,
-            b_arr_len: sl,
-        });
-    }
+    validate::same_length(gl, s_coords.len())?;
 
-    // check if axis parameter is valid
+    // check if axis and mask parameters are valid
     let a = axis.unwrap_or(2);
-    if a >= 3 {
-        return Err(ArrayError::InvalidAxis {
-            axis_idx: a,
-            dim_len: 3,
-        });
+    validate::axis(a, 3)?;
+    if let Some(msk) = mask {
+        let mut shape = data.shape().to_vec();
+        shape.remove(a);
+        validate::same_shape(msk.shape(), &shape)?;
     }
 
     // use a hash set of G/S coordinates for fast lookup
@@ -146,19 +147,26 @@ pub fn map_mask(
     shape.remove(a);
     let mut map_arr = Array2::<bool>::default((shape[0], shape[1]));
 
-    // check each pixel for matches in (g, s)
-    let lanes = data.lanes(Axis(a));
-    Zip::from(lanes)
-        .and(map_arr.view_mut())
-        .par_for_each(|ln, p| {
-            let dg = ln[0];
-            let ds = ln[1];
-            if !dg.is_nan() || !ds.is_nan() || dg != 0.0 && ds != 0.0 {
-                if coords_set.contains(&(dg.to_bits(), ds.to_bits())) {
-                    *p = true;
-                }
-            }
+    // check each pixel for matches in (g, s), optionally only in mask area
+    let is_match = |ln: ArrayView1<f64>| {
+        let dg = ln[0];
+        let ds = ln[1];
+        (!dg.is_nan() || !ds.is_nan() || dg != 0.0 && ds != 0.0)
+            && coords_set.contains(&(dg.to_bits(), ds.to_bits()))
+    };
+    let lanes = Zip::from(data.lanes(Axis(a))).and(map_arr.view_mut());
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        lanes.and(msk).par_for_each(|ln, p, m| {
+            *p = *m && is_match(ln);
         });
+    } else {
+        lanes.par_for_each(|ln, p| {
+            *p = is_match(ln);
+        });
+    }
 
     // return output
     Ok(map_arr)
@@ -0,0 +1,481 @@
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+use ndarray::{Array2, Array3, ArrayView1, ArrayView2, ArrayView3, Axis, Zip, stack};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::phasor::analysis::PhasorCursor;
+use crate::phasor::plot;
+use crate::phasor::waveform::Waveforms;
+use crate::traits::numeric::ToFloat64;
+use crate::version::AlgorithmVersion;
+
+/// Compute the real and imaginary (G, S) coordinates of a 1-dimensional
+/// emission spectrum.
+///
+/// # Description
+///
+/// The spectral phasor coordinates are the normalized cosine and sine Fourier
+/// coefficients of the spectrum over its `K` wavelength channels:
+///
+/// ```text
+/// G = Σₖ I(λₖ) × cos(2πnk/K) / Σₖ I(λₖ)
+/// S = Σₖ I(λₖ) × sin(2πnk/K) / Σₖ I(λₖ)
+/// ```
+///
+/// where `n` is the harmonic. The phase encodes the spectral position (_e.g._
+/// the emission maximum) and the modulation the spectral width. Use the second
+/// harmonic to separate spectra that overlap on the first harmonic.
+///
+/// # Arguments
+///
+/// * `data`: I(λ), the spectrum, one value per wavelength channel. At least 2
+///   channels are required.
+/// * `harmonic`: The harmonic value, default = 1.0. Must be >= 1.0.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The real and imaginary coordinates, (G, S). If the
+///   spectrum has no intensity, G and S are NaN.
+/// * `Err(ImgalError)`: If `data` has less than 2 channels. If harmonic is
+///   < 1.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1364/OE.20.012729>
+pub fn coordinates<T>(data: &[T], harmonic: Option<f64>) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1.0);
+
+    // check if the data and harmonic parameters are valid
+    validate::at_least("data", data.len() as f64, 2.0)?;
+    validate::at_least("harmonic", h, 1.0)?;
+
+    let wf = waveforms(data.len(), h);
+    let (g, s) = spectral_lane(ArrayView1::from(data), wf.cos(), wf.sin());
+    if !g.is_finite() {
+        warning::warn(Warning::NonFiniteOutput { count: 1 });
+    }
+
+    Ok((g, s))
+}
+
+/// Select the pixels of a 3-dimensional phasor image within a phasor cursor.
+///
+/// # Description
+///
+/// This function returns a 2-dimensional boolean mask where `true` indicates
+/// pixels whose (G, S) coordinates lie within `cursor.radius` of the cursor
+/// center. Pixels with non-finite coordinates are never selected.
+///
+/// # Arguments
+///
+/// * `data`: The G/S 3-dimensional array.
+/// * `cursor`: The circular phasor cursor.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   never selected. Must match the shape of `data` without the channel axis.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array2<bool>)`: The 2-dimensional cursor selection mask.
+/// * `Err(ImgalError)`: If axis is >= 3. If the channel axis does not have a
+///   length of 2. If the cursor radius is <= 0.0. If the mask shape does not
+///   match the image shape.
+pub fn cursor_mask(
+    data: ArrayView3<f64>,
+    cursor: PhasorCursor,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array2<bool>, ImgalError> {
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if axis, channel, cursor and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate::same_length(2, data.len_of(Axis(a)))?;
+    validate::positive("radius", cursor.radius)?;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(msk) = mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // select the pixels within the cursor radius
+    let r_sq = cursor.radius * cursor.radius;
+    let mut sel = Array2::<bool>::default((shape[0], shape[1]));
+    Zip::from(&mut sel)
+        .and(data.lanes(Axis(a)))
+        .par_for_each(|s, ln| {
+            let dg = ln[0] - cursor.g;
+            let ds = ln[1] - cursor.s;
+            *s = dg * dg + ds * ds <= r_sq;
+        });
+    if let Some(msk) = mask {
+        Zip::from(&mut sel).and(msk).for_each(|s, &m| *s &= m);
+    }
+
+    Ok(sel)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional
+/// hyperspectral image.
+///
+/// # Description
+///
+/// This function computes the spectral phasor coordinates of each pixel of a
+/// hyperspectral image cube, see `coordinates`. The cosine and sine waveforms
+/// are computed once and shared by all pixels.
+///
+/// # Arguments
+///
+/// * `data`: I(λ), the hyperspectral image. At least 2 wavelength channels are
+///   required.
+/// * `harmonic`: The harmonic value, default = 1.0. Must be >= 1.0.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the
+///   wavelength axis.
+/// * `axis`: The wavelength axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The real and imaginary coordinates as a 3D (row, col, ch)
+///   image, where G and S are indexed at 0 and 1 respectively on the _channel_
+///   axis.
+/// * `Err(ImgalError)`: If axis is >= 3. If the wavelength axis has less than 2
+///   channels. If harmonic is < 1.0. If the mask shape does not match the image
+///   shape.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1364/OE.20.012729>
+pub fn image<T>(
+    data: ArrayView3<T>,
+    harmonic: Option<f64>,
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1.0);
+    let a = axis.unwrap_or(2);
+
+    // check if harmonic, axis and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate::at_least("harmonic", h, 1.0)?;
+    let n = data.len_of(Axis(a));
+    validate::at_least("data", n as f64, 2.0)?;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(msk) = mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // compute phasor coordinates per lane, optionally only in mask area
+    let wf = waveforms(n, h);
+    let mut g_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let mut s_arr = Array2::<f64>::zeros((shape[0], shape[1]));
+    let lanes = data.lanes(Axis(a));
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        Zip::from(lanes)
+            .and(msk)
+            .and(&mut g_arr)
+            .and(&mut s_arr)
+            .par_for_each(|ln, m, g, s| {
+                if *m {
+                    (*g, *s) = spectral_lane(ln, wf.cos(), wf.sin());
+                }
+            });
+    } else {
+        Zip::from(lanes)
+            .and(&mut g_arr)
+            .and(&mut s_arr)
+            .par_for_each(|ln, g, s| {
+                (*g, *s) = spectral_lane(ln, wf.cos(), wf.sin());
+            });
+    }
+
+    // report pixels with no intensity, their G/S values are NaN
+    let nan_count = g_arr.iter().filter(|v| !v.is_finite()).count();
+    if nan_count > 0 {
+        warning::warn(Warning::NonFiniteOutput { count: nan_count });
+    }
+
+    // stack G and S arrays, (row, col, ch)
+    Ok(stack(Axis(2), &[g_arr.view(), s_arr.view()]).unwrap())
+}
+
+/// Compute the modulation and phase calibration values from a reference
+/// spectrum.
+///
+/// # Description
+///
+/// Spectral phasors depend on the detector (_e.g._ the wavelength range and
+/// channel response). Measuring a reference with a known spectral phasor
+/// position gives the modulation (M) and phase (φ) that map the measured
+/// position onto the known position:
+///
+/// ```text
+/// M = M_known / M_measured
+/// φ = φ_known - φ_measured
+/// ```
+///
+/// Apply the calibration values with `calibration::coordinates` or
+/// `calibration::image`.
+///
+/// # Arguments
+///
+/// * `g`: The real component, G, of the measured reference.
+/// * `s`: The imaginary component, S, of the measured reference.
+/// * `g_known`: The real component, G, of the known reference position.
+/// * `s_known`: The imaginary component, S, of the known reference position.
+///
+/// # Returns
+///
+/// * `(f64, f64)`: The modulation and phase calibration values, (M, φ).
+pub fn modulation_and_phase(g: f64, s: f64, g_known: f64, s_known: f64) -> (f64, f64) {
+    let m = plot::modulation(g_known, s_known) / plot::modulation(g, s);
+    let p = plot::phase(g_known, s_known) - plot::phase(g, s);
+
+    (m, p)
+}
+
+/// Unmix a phasor coordinate into the fractional intensities of 2 or 3
+/// components.
+///
+/// # Description
+///
+/// Phasor coordinates are linear in the fractional intensities of their
+/// components, so a mixture lies inside the line (2 components) or triangle
+/// (3 components) spanned by the pure component coordinates (Cᵢ). The
+/// fractions (fᵢ) solve:
+///
+/// ```text
+/// P = Σᵢ fᵢ × Cᵢ,  Σᵢ fᵢ = 1
+/// ```
+///
+/// With 2 components the coordinate (P) is projected onto the line between the
+/// components. The fractions are not clamped, coordinates outside of the line
+/// or triangle have fractions outside of [0, 1].
+///
+/// # Arguments
+///
+/// * `g`: The real component, G, of a calibrated phasor coordinate.
+/// * `s`: The imaginary component, S, of a calibrated phasor coordinate.
+/// * `components`: The (G, S) coordinates of the 2 or 3 pure components.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The fractional intensity of each component, in the order
+///   of `components`. NaN if `g` or `s` is NaN.
+/// * `Err(ImgalError)`: If there are not 2 or 3 components. If the component
+///   coordinates are not finite, equal (2 components) or collinear
+///   (3 components).
+///
+/// # Reference
+///
+/// <https://doi.org/10.1364/OE.20.012729>
+pub fn unmix(g: f64, s: f64, components: &[(f64, f64)]) -> Result<Vec<f64>, ImgalError> {
+    let unmixer = Unmixer::new(components)?;
+
+    Ok(unmixer.fractions(g, s))
+}
+
+/// Unmix a 3-dimensional phasor image into the fractional intensities of 2 or
+/// 3 components.
+///
+/// # Description
+///
+/// This function unmixes each pixel of a calibrated phasor (G, S) image, see
+/// `unmix`.
+///
+/// # Arguments
+///
+/// * `data`: The G/S 3-dimensional array.
+/// * `components`: The (G, S) coordinates of the 2 or 3 pure components.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the channel
+///   axis.
+/// * `axis`: The channel axis, default = 2.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The fractions as a 3D (row, col, component) image, in
+///   the order of `components`.
+/// * `Err(ImgalError)`: If axis is >= 3. If the channel axis does not have a
+///   length of 2. If the mask shape does not match the image shape. If there
+///   are not 2 or 3 components or the component coordinates are degenerate.
+pub fn unmix_image(
+    data: ArrayView3<f64>,
+    components: &[(f64, f64)],
+    mask: Option<ArrayView2<bool>>,
+    axis: Option<usize>,
+) -> Result<Array3<f64>, ImgalError> {
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if axis, channel, mask and component parameters are valid
+    validate::axis(a, 3)?;
+    validate::same_length(2, data.len_of(Axis(a)))?;
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(msk) = mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+    let unmixer = Unmixer::new(components)?;
+
+    // compute the fractions per lane, optionally only in mask area
+    let mut out = Array3::<f64>::zeros((shape[0], shape[1], components.len()));
+    let lanes = data.lanes(Axis(a));
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        Zip::from(out.lanes_mut(Axis(2)))
+            .and(lanes)
+            .and(msk)
+            .par_for_each(|mut o, ln, m| {
+                if *m {
+                    o.assign(&ArrayView1::from(&unmixer.fractions(ln[0], ln[1])));
+                }
+            });
+    } else {
+        Zip::from(out.lanes_mut(Axis(2)))
+            .and(lanes)
+            .par_for_each(|mut o, ln| {
+                o.assign(&ArrayView1::from(&unmixer.fractions(ln[0], ln[1])));
+            });
+    }
+
+    Ok(out)
+}
+
+/// The precomputed linear unmixing of 2 or 3 phasor components.
+enum Unmixer {
+    /// The first component and the direction to the second component, scaled
+    /// by the inverse squared length.
+    Line { origin: (f64, f64), dir: (f64, f64) },
+    /// The inverse of the [G; S; 1] component matrix.
+    Triangle { inverse: [[f64; 3]; 3] },
+}
+
+impl Unmixer {
+    /// Create a new unmixer, validating the component coordinates.
+    fn new(components: &[(f64, f64)]) -> Result<Self, ImgalError> {
+        if components.len() != 2 && components.len() != 3 {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "components",
+                reason: format!("must have 2 or 3 components but got {}", components.len()),
+            });
+        }
+        if components
+            .iter()
+            .any(|(g, s)| !g.is_finite() || !s.is_finite())
+        {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "components",
+                reason: "must have finite (G, S) coordinates".to_string(),
+            });
+        }
+
+        if let [c0, c1] = components {
+            let d = (c1.0 - c0.0, c1.1 - c0.1);
+            let len_sq = d.0 * d.0 + d.1 * d.1;
+            if len_sq == 0.0 {
+                return Err(ImgalError::InvalidParameter {
+                    param_name: "components",
+                    reason: "must have distinct (G, S) coordinates".to_string(),
+                });
+            }
+            return Ok(Unmixer::Line {
+                origin: *c0,
+                dir: (d.0 / len_sq, d.1 / len_sq),
+            });
+        }
+
+        // invert the [G; S; 1] matrix by its adjugate
+        let (g, s): (Vec<f64>, Vec<f64>) = components.iter().copied().unzip();
+        let det = g[0] * (s[1] - s[2]) - g[1] * (s[0] - s[2]) + g[2] * (s[0] - s[1]);
+        let scale = (g[1] - g[0]).abs().max((g[2] - g[0]).abs())
+            * (s[1] - s[0]).abs().max((s[2] - s[0]).abs());
+        if det.abs() <= 1e-12 * scale || scale == 0.0 {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "components",
+                reason: "must not have collinear (G, S) coordinates".to_string(),
+            });
+        }
+        let inverse = [
+            [
+                (s[1] - s[2]) / det,
+                (g[2] - g[1]) / det,
+                (g[1] * s[2] - g[2] * s[1]) / det,
+            ],
+            [
+                (s[2] - s[0]) / det,
+                (g[0] - g[2]) / det,
+                (g[2] * s[0] - g[0] * s[2]) / det,
+            ],
+            [
+                (s[0] - s[1]) / det,
+                (g[1] - g[0]) / det,
+                (g[0] * s[1] - g[1] * s[0]) / det,
+            ],
+        ];
+
+        Ok(Unmixer::Triangle { inverse })
+    }
+
+    /// Compute the component fractions of a (G, S) coordinate.
+    fn fractions(&self, g: f64, s: f64) -> Vec<f64> {
+        match self {
+            Unmixer::Line { origin, dir } => {
+                let f = (g - origin.0) * dir.0 + (s - origin.1) * dir.1;
+                vec![1.0 - f, f]
+            }
+            Unmixer::Triangle { inverse } => inverse
+                .iter()
+                .map(|row| row[0] * g + row[1] * s + row[2])
+                .collect(),
+        }
+    }
+}
+
+/// Compute the normalized G and S coordinates of a single spectrum lane.
+fn spectral_lane<T>(lane: ArrayView1<T>, cos_buf: &[f64], sin_buf: &[f64]) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    let mut iv = 0.0;
+    let mut gv = 0.0;
+    let mut sv = 0.0;
+    lane.iter()
+        .zip(cos_buf.iter())
+        .zip(sin_buf.iter())
+        .for_each(|((v, cosv), sinv)| {
+            let vf = (*v).to_f64();
+            iv += vf;
+            gv += vf * cosv;
+            sv += vf * sinv;
+        });
+    if iv == 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+
+    (gv / iv, sv / iv)
+}
+
+/// Get the shared cosine and sine waveforms of the given harmonic over `n`
+/// wavelength channels, the channels are sampled at their index.
+fn waveforms(n: usize, harmonic: f64) -> Arc<Waveforms> {
+    Waveforms::cached(n, 2.0 * PI * harmonic / n as f64, AlgorithmVersion::V1)
+}
@@ -1,11 +1,88 @@
 use std::f64;
+use std::sync::Arc;
 
-use ndarray::{Array2, Array3, ArrayView2, ArrayView3, Axis, Zip, stack};
+use ndarray::{Array2, Array3, ArrayView1, ArrayView2, ArrayView3, Axis, Zip, stack};
 
-use crate::error::ArrayError;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
 use crate::integration::midpoint;
 use crate::parameter::omega;
+use crate::phasor::waveform::Waveforms;
 use crate::traits::numeric::ToFloat64;
+use crate::version;
+
+/// Compute the real and imaginary (G, S) coordinates of a batch of
+/// 1-dimensional decay curves.
+///
+/// # Description
+///
+/// This function computes the phasor coordinates of each decay curve of a
+/// 2-dimensional array (_e.g._ thousands of plate reader or cuvette
+/// measurements) in parallel, see `real` and `imaginary`:
+///
+/// ```text
+/// G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+/// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+/// ```
+///
+/// Curves without intensity have NaN coordinates.
+///
+/// The numerical behavior of this function is versioned, see
+/// `version::AlgorithmVersion`.
+///
+/// # Arguments
+///
+/// * `curves`: I(t), the 2-dimensional array of decay curves.
+/// * `period`: The period (_i.e._ time interval). Must be greater than 0.0.
+/// * `harmonic`: The harmonic value, default = 1.0. Must be >= 1.0.
+/// * `axis`: The decay or lifetime axis, default = 1 (_i.e._ one curve per
+///   row).
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The real and imaginary coordinates as a 2D (curve, ch)
+///   array, where G and S are indexed at 0 and 1 respectively on the _channel_
+///   axis.
+/// * `Err(ImgalError)`: If axis is >= 2. If period is <= 0.0 or harmonic is
+///   < 1.0.
+pub fn batch<T>(
+    curves: ArrayView2<T>,
+    period: f64,
+    harmonic: Option<f64>,
+    axis: Option<usize>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let h = harmonic.unwrap_or(1.0);
+    let a = axis.unwrap_or(1);
+
+    // check if period, harmonic and axis parameters are valid
+    validate::positive("period", period)?;
+    validate::at_least("harmonic", h, 1.0)?;
+    validate::axis(a, 2)?;
+
+    // get the shared waveforms
+    let wf = waveforms(curves.len_of(Axis(a)), period, h);
+
+    // compute phasor coordinates per curve
+    let mut gs_arr = Array2::<f64>::zeros((curves.len_of(Axis(1 - a)), 2));
+    Zip::from(gs_arr.rows_mut())
+        .and(curves.lanes(Axis(a)))
+        .par_for_each(|mut gs, ln| {
+            (gs[0], gs[1]) = curve_coordinates(ln, &wf);
+        });
+
+    // report curves with no intensity, their G/S values are NaN
+    let nan_count = gs_arr.column(0).iter().filter(|v| !v.is_finite()).count();
+    if nan_count > 0 {
+        warning::warn(Warning::NonFiniteOutput { count: nan_count });
+    }
+
+    Ok(gs_arr)
+}
 
 /// Compute the histogram quality value from a 1-dimensional decay array.
 ///
@@ -94,6 +171,9 @@ where
 /// * `data`: The 3-dimensional decay data.
 /// * `count_threshold`: The minimum bin count value a bin must exceed to be
 ///    considered valid.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the decay
+///   axis.
 /// * `axis`: The decay or lifetime axis, default = 2.
 ///
 /// # Returns
@@ -107,40 +187,52 @@ where
 ///    distribution across the histogram. Values exceeding 10.0 indicate high
 ///    quality histograms with high photon counts that are distributed across
 ///    the histogram.
-/// * `Err(ArrayError)`: If axis is >= 3.
+/// * `Err(ImgalError)`: If axis is >= 3. If the mask shape does not match the
+///   image shape.
 pub fn histogram_quality_image<T>(
     data: ArrayView3<T>,
     count_threshold: T,
+    mask: Option<ArrayView2<bool>>,
     axis: Option<usize>,
-) -> Result<Array2<f64>, ArrayError>
+) -> Result<Array2<f64>, ImgalError>
 where
     T: ToFloat64,
 {
     // set optional parameter if needed
     let a = axis.unwrap_or(2);
 
-    // check if axis parameter is valid
-    if a >= 3 {
-        return Err(ArrayError::InvalidAxis {
-            axis_idx: a,
-            dim_len: 3,
-        });
-    }
-
-    // create output array and zip iterate
+    // check if axis and mask parameters are valid
+    validate::axis(a, 3)?;
     let mut shape = data.shape().to_vec();
     shape.remove(a);
+    if let Some(msk) = mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // create output array and zip iterate, optionally only in mask area
     let mut q_arr = Array2::<f64>::zeros((shape[0], shape[1]));
-    let lanes = data.lanes(Axis(a));
-    Zip::from(lanes)
-        .and(q_arr.view_mut())
-        .par_for_each(|ln, p| {
-            if let Some(l) = ln.as_slice() {
-                *p = histogram_quality(l, count_threshold);
-            } else {
-                *p = histogram_quality(&ln.to_vec(), count_threshold);
+    let quality = |ln: ArrayView1<T>| {
+        if let Some(l) = ln.as_slice() {
+            histogram_quality(l, count_threshold)
+        } else {
+            histogram_quality(&ln.to_vec(), count_threshold)
+        }
+    };
+    let lanes = Zip::from(data.lanes(Axis(a))).and(q_arr.view_mut());
+    if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
+        lanes.and(msk).par_for_each(|ln, p, m| {
+            if *m {
+                *p = quality(ln);
             }
         });
+    } else {
+        lanes.par_for_each(|ln, p| {
+            *p = quality(ln);
+        });
+    }
 
     Ok(q_arr)
 }
@@ -158,25 +250,32 @@ where
 /// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
 /// ```
 ///
+/// The numerical behavior of this function is versioned, see
+/// `version::AlgorithmVersion`.
+///
 /// # Arguments
 ///
 /// * `data`: I(t), the decay data image.
-/// * `period`: The period (_i.e._ time interval).
-/// * `harmonic`: The harmonic value, default = 1.0.
+/// * `period`: The period (_i.e._ time interval). Must be greater than 0.0.
+/// * `mask`: An optional 2-dimensional boolean mask, pixels set to `false` are
+///   skipped and set to 0.0. Must match the shape of `data` without the decay
+///   axis.
+/// * `harmonic`: The harmonic value, default = 1.0. Must be >= 1.0.
 /// * `axis`: The decay or lifetime axis, default = 2.
 ///
 /// # Returns
 ///
 /// * `Ok(Array3<f64>)`: The real and imaginary coordinates as a 3D (ch, row, col) image,
 ///    where G and S are indexed at 0 and 1 respectively on the _channel_ axis.
-/// * `Err(ArrayError)`: If axis is >= 3.
+/// * `Err(ImgalError)`: If axis is >= 3. If period is <= 0.0 or harmonic is
+///   < 1.0. If the mask shape does not match the image shape.
 pub fn image<T>(
     data: ArrayView3<T>,
     period: f64,
     mask: Option<ArrayView2<bool>>,
     harmonic: Option<f64>,
     axis: Option<usize>,
-) -> Result<Array3<f64>, ArrayError>
+) -> Result<Array3<f64>, ImgalError>
 where
     T: ToFloat64,
 {
@@ -184,23 +283,19 @@ where
     let h = harmonic.unwrap_or(1.0);
     let a = axis.unwrap_or(2);
 
-    // check if axis parameter is valid
-    if a >= 3 {
-        return Err(ArrayError::InvalidAxis {
-            axis_idx: a,
-            dim_len: 3,
-        });
+    // check if period, harmonic, axis and mask parameters are valid
+    validate::positive("period", period)?;
+    validate::at_least("harmonic", h, 1.0)?;
+    validate::axis(a, 3)?;
+    if let Some(msk) = mask {
+        let mut shape = data.shape().to_vec();
+        shape.remove(a);
+        validate::same_shape(msk.shape(), &shape)?;
     }
 
     // initialize phasor parameters
-    let w = omega(period);
     let n: usize = data.len_of(Axis(a));
     let dt: f64 = period / n as f64;
-    let h_w_dt: f64 = h * w * dt;
-
-    // initialize buffers
-    let mut w_cos_buf: Vec<f64> = Vec::with_capacity(n);
-    let mut w_sin_buf: Vec<f64> = Vec::with_capacity(n);
 
     // drop specified axis and create new G and S output arrays with new shape
     let mut shape = data.shape().to_vec();
@@ -208,15 +303,15 @@ where
     let mut g_arr = Array2::<f64>::zeros((shape[0], shape[1]));
     let mut s_arr = Array2::<f64>::zeros((shape[0], shape[1]));
 
-    // load the waveform buffers
-    for i in 0..n {
-        w_cos_buf.push(f64::cos(h_w_dt * (i as f64)));
-        w_sin_buf.push(f64::sin(h_w_dt * (i as f64)));
-    }
+    // get the shared waveforms
+    let wf = waveforms(n, period, h);
 
     // compute phasor coordinates per lane, optionally only in mask area
     let lanes = data.lanes(Axis(a));
     if let Some(msk) = mask {
+        if !msk.iter().any(|&m| m) {
+            warning::warn(Warning::EmptyMask);
+        }
         Zip::from(lanes)
             .and(msk)
             .and(&mut g_arr)
@@ -227,8 +322,8 @@ where
                     let mut gv = 0.0;
                     let mut sv = 0.0;
                     ln.iter()
-                        .zip(w_cos_buf.iter())
-                        .zip(w_sin_buf.iter())
+                        .zip(wf.cos().iter())
+                        .zip(wf.sin().iter())
                         .for_each(|((v, cosv), sinv)| {
                             // midpoint integration
                             let vf: f64 = (*v).to_f64();
@@ -259,8 +354,8 @@ where
                 let mut gv = 0.0;
                 let mut sv = 0.0;
                 ln.iter()
-                    .zip(w_cos_buf.iter())
-                    .zip(w_sin_buf.iter())
+                    .zip(wf.cos().iter())
+                    .zip(wf.sin().iter())
                     .for_each(|((v, cosv), sinv)| {
                         // midpoint integration
                         let vf: f64 = (*v).to_f64();
@@ -278,6 +373,12 @@ where
             });
     }
 
+    // report pixels with no intensity, their G/S values are NaN
+    let nan_count = g_arr.iter().filter(|v| !v.is_finite()).count();
+    if nan_count > 0 {
+        warning::warn(Warning::NonFiniteOutput { count: nan_count });
+    }
+
     // stack G and S arrays, (row, col, ch)
     Ok(stack(Axis(2), &[g_arr.view(), s_arr.view()]).unwrap())
 }
@@ -295,6 +396,9 @@ where
 ///
 /// Where 'n' and 'ω' are harmonic and omega values respectively.
 ///
+/// The numerical behavior of this function is versioned, see
+/// `version::AlgorithmVersion`.
+///
 /// # Arguments
 ///
 /// * `data`: I(t), the 1-dimensonal decay curve.
@@ -310,18 +414,21 @@ where
 {
     // set optional parameters if needed
     let h: f64 = harmonic.unwrap_or(1.0);
-    let w: f64 = omega(period);
 
     // integrate sine transform (imaginary)
     let n: usize = data.len();
     let dt: f64 = period / (n as f64);
-    let h_w_dt: f64 = h * w * dt;
-    let mut buf = Vec::with_capacity(n);
-    for i in 0..n {
-        buf.push(data[i].to_f64() * f64::sin(h_w_dt * (i as f64)));
-    }
+    let wf = waveforms(n, period, h);
+    let buf: Vec<f64> = data
+        .iter()
+        .zip(wf.sin().iter())
+        .map(|(v, sinv)| v.to_f64() * sinv)
+        .collect();
     let i_sin_integral: f64 = midpoint(&buf, Some(dt));
     let i_integral: f64 = midpoint(data, Some(dt));
+    if i_integral == 0.0 {
+        warning::warn(Warning::NonFiniteOutput { count: 1 });
+    }
     i_sin_integral / i_integral
 }
 
@@ -338,6 +445,9 @@ where
 ///
 /// Where 'n' and 'ω' are harmonic and omega values respectively.
 ///
+/// The numerical behavior of this function is versioned, see
+/// `version::AlgorithmVersion`.
+///
 /// # Arguments
 ///
 /// * `data`: I(t), the 1-dimensional decay curve.
@@ -353,17 +463,53 @@ where
 {
     // set optional parameters if needed
     let h: f64 = harmonic.unwrap_or(1.0);
-    let w: f64 = omega(period);
 
     // integrate cosine transform (real)
     let n: usize = data.len();
     let dt: f64 = period / (n as f64);
-    let h_w_dt: f64 = h * w * dt;
-    let mut buf = Vec::with_capacity(n);
-    for i in 0..n {
-        buf.push(data[i].to_f64() * f64::cos(h_w_dt * (i as f64)));
-    }
+    let wf = waveforms(n, period, h);
+    let buf: Vec<f64> = data
+        .iter()
+        .zip(wf.cos().iter())
+        .map(|(v, cosv)| v.to_f64() * cosv)
+        .collect();
     let i_cos_integral: f64 = midpoint(&buf, Some(dt));
     let i_integral: f64 = midpoint(data, Some(dt));
+    if i_integral == 0.0 {
+        warning::warn(Warning::NonFiniteOutput { count: 1 });
+    }
     i_cos_integral / i_integral
 }
+
+/// Get the shared cosine and sine waveforms of `n` time bins over one period,
+/// for the algorithm version in effect on the calling thread. Look the
+/// waveforms up once per call and pass them to the per curve work.
+pub(crate) fn waveforms(n: usize, period: f64, harmonic: f64) -> Arc<Waveforms> {
+    let dt: f64 = period / n as f64;
+    let h_w_dt: f64 = harmonic * omega(period) * dt;
+
+    Waveforms::cached(n, h_w_dt, version::algorithm::current())
+}
+
+/// Compute the normalized G and S coordinates of a single decay curve with
+/// shared waveforms, the integrals share the same data point width, which
+/// cancels in the normalization.
+pub(crate) fn curve_coordinates<T>(lane: ArrayView1<T>, wf: &Waveforms) -> (f64, f64)
+where
+    T: ToFloat64,
+{
+    let mut iv = 0.0;
+    let mut gv = 0.0;
+    let mut sv = 0.0;
+    lane.iter()
+        .zip(wf.cos().iter())
+        .zip(wf.sin().iter())
+        .for_each(|((v, cosv), sinv)| {
+            let vf: f64 = (*v).to_f64();
+            iv += vf;
+            gv += vf * cosv;
+            sv += vf * sinv;
+        });
+
+    (gv / iv, sv / iv)
+}
@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::version::AlgorithmVersion;
+
+// the number of waveforms kept in the process wide cache
+const CACHE_CAPACITY: usize = 32;
+
+// the process wide cache, the most recently computed waveforms are at the back
+static CACHE: Mutex<VecDeque<Arc<Waveforms>>> = Mutex::new(VecDeque::new());
+
+/// The cosine and sine waveforms of a phasor transform.
+///
+/// # Description
+///
+/// The phasor transforms correlate each decay curve or spectrum with a cosine
+/// and sine waveform sampled at each of its `bins` time bins or channels:
+///
+/// ```text
+/// cos(φ × k), sin(φ × k)
+/// ```
+///
+/// where φ is the phase step per bin (_i.e._ nωΔt for a decay) and k is the
+/// sample position of bin k for the algorithm version. The waveforms only
+/// depend on the number of bins, the phase step and the algorithm version, so
+/// they are computed once per configuration and shared by all pixels and calls
+/// through a small process wide cache, see `cached`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waveforms {
+    bins: usize,
+    step: f64,
+    version: AlgorithmVersion,
+    cos: Vec<f64>,
+    sin: Vec<f64>,
+}
+
+impl Waveforms {
+    /// Compute the waveforms.
+    ///
+    /// # Arguments
+    ///
+    /// * `bins`: The number of time bins or channels.
+    /// * `step`: The phase step per bin, in radians.
+    /// * `version`: The algorithm version of the sample positions.
+    ///
+    /// # Returns
+    ///
+    /// * `Waveforms`: The cosine and sine waveforms.
+    pub fn new(bins: usize, step: f64, version: AlgorithmVersion) -> Self {
+        let (cos, sin) = (0..bins)
+            .map(|k| {
+                let phase = step * bin_position(k, version);
+                (f64::cos(phase), f64::sin(phase))
+            })
+            .unzip();
+
+        Waveforms {
+            bins,
+            step,
+            version,
+            cos,
+            sin,
+        }
+    }
+
+    /// Get the waveforms from the process wide cache.
+    ///
+    /// # Description
+    ///
+    /// This function returns the cached waveforms of a configuration, the
+    /// waveforms are computed and cached if they are not. The cache keeps the
+    /// waveforms of the 32 most recently computed configurations.
+    ///
+    /// # Arguments
+    ///
+    /// * `bins`: The number of time bins or channels.
+    /// * `step`: The phase step per bin, in radians.
+    /// * `version`: The algorithm version of the sample positions.
+    ///
+    /// # Returns
+    ///
+    /// * `Arc<Waveforms>`: The shared cosine and sine waveforms.
+    pub fn cached(bins: usize, step: f64, version: AlgorithmVersion) -> Arc<Waveforms> {
+        let is_match = |w: &Arc<Waveforms>| {
+            w.bins == bins && w.step.to_bits() == step.to_bits() && w.version == version
+        };
+        if let Some(w) = lock_cache().iter().find(|w| is_match(w)) {
+            return Arc::clone(w);
+        }
+
+        // compute the waveforms without holding the cache lock
+        let waveforms = Arc::new(Waveforms::new(bins, step, version));
+        let mut cache = lock_cache();
+        if let Some(w) = cache.iter().find(|w| is_match(w)) {
+            return Arc::clone(w);
+        }
+        if cache.len() == CACHE_CAPACITY {
+            cache.pop_front();
+        }
+        cache.push_back(Arc::clone(&waveforms));
+
+        waveforms
+    }
+
+    /// The number of time bins or channels.
+    pub fn bins(&self) -> usize {
+        self.bins
+    }
+
+    /// The phase step per bin, in radians.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// The cosine waveform.
+    pub fn cos(&self) -> &[f64] {
+        &self.cos
+    }
+
+    /// The sine waveform.
+    pub fn sin(&self) -> &[f64] {
+        &self.sin
+    }
+}
+
+/// Lock the process wide cache, a cache poisoned by a panic is still valid.
+fn lock_cache() -> std::sync::MutexGuard<'static, VecDeque<Arc<Waveforms>>> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Get the waveform sample position, in units of time bins, of a time bin for
+/// the given algorithm version.
+fn bin_position(i: usize, version: AlgorithmVersion) -> f64 {
+    match version {
+        AlgorithmVersion::V1 => i as f64,
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::error::ImgalError;
+use crate::pipeline::{Step, Value};
+use crate::version::AlgorithmVersion;
+use crate::version::algorithm;
+
+/// A named node of a `Pipeline`, the `step` computed from the values of the
+/// `inputs` nodes or pipeline inputs.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Node {
+    /// The name of the node, unique within the pipeline.
+    pub name: String,
+    /// The processing step of the node.
+    pub step: Step,
+    /// The names of the pipeline inputs or earlier nodes the step reads, in
+    /// the order of the step's inputs.
+    pub inputs: Vec<String>,
+}
+
+/// A graph of processing steps.
+///
+/// # Description
+///
+/// A `Pipeline` composes processing steps (see `Step`), _e.g._ filter →
+/// threshold → label → measure or phasor → calibrate → cursor, into a directed
+/// acyclic graph of named nodes. Nodes can only read the pipeline inputs and
+/// earlier nodes, so the nodes are always in execution order. A node's output
+/// can feed any number of later nodes (_e.g._ a phasor image feeding several
+/// cursors).
+///
+/// All nodes of a run share the same thread pool (see `set_num_threads`) and
+/// the algorithm version the pipeline was created with (see
+/// `version::AlgorithmVersion`). With the `serde` feature the pipeline
+/// description (inputs, nodes, number of threads and algorithm version) can be
+/// written to and read from JSON.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pipeline {
+    inputs: Vec<String>,
+    nodes: Vec<Node>,
+    num_threads: Option<usize>,
+    algorithm_version: AlgorithmVersion,
+}
+
+impl Pipeline {
+    /// Create a new pipeline without nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs`: The names of the pipeline inputs.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Pipeline)`: A new pipeline using the global thread pool and the
+    ///   algorithm version in effect on the calling thread (see
+    ///   `version::algorithm::current`).
+    /// * `Err(ImgalError)`: If an input name is empty or not unique.
+    pub fn new(inputs: &[&str]) -> Result<Self, ImgalError> {
+        let mut pipeline = Pipeline {
+            inputs: Vec::with_capacity(inputs.len()),
+            nodes: Vec::new(),
+            num_threads: None,
+            algorithm_version: algorithm::current(),
+        };
+        for name in inputs {
+            pipeline.check_name(name)?;
+            pipeline.inputs.push(name.to_string());
+        }
+
+        Ok(pipeline)
+    }
+
+    /// Add a node to the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The name of the node.
+    /// * `step`: The processing step of the node.
+    /// * `inputs`: The names of the pipeline inputs or earlier nodes the step
+    ///   reads, in the order of the step's inputs.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If the node was added.
+    /// * `Err(ImgalError)`: If the name is empty or not unique. If the number of
+    ///   inputs is not within the step's arity (see `Step::arity`). If an input
+    ///   is not a pipeline input or earlier node.
+    pub fn add(&mut self, name: &str, step: Step, inputs: &[&str]) -> Result<(), ImgalError> {
+        self.check_name(name)?;
+        let (min, max) = step.arity();
+        if inputs.len() < min || inputs.len() > max {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "inputs",
+                reason: format!(
+                    "the \"{}\" step takes {} to {} inputs but got {}",
+                    step.name(),
+                    min,
+                    max,
+                    inputs.len()
+                ),
+            });
+        }
+        if let Some(unknown) = inputs.iter().find(|i| !self.contains(i)) {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "inputs",
+                reason: format!("\"{}\" is not a pipeline input or earlier node", unknown),
+            });
+        }
+        self.nodes.push(Node {
+            name: name.to_string(),
+            step,
+            inputs: inputs.iter().map(|i| i.to_string()).collect(),
+        });
+
+        Ok(())
+    }
+
+    /// Set the number of threads of the pipeline runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_threads`: The number of threads, if `None` the global thread pool
+    ///   is used (see `config::set_num_threads`). Must be >= 1.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If the number of threads was set.
+    /// * `Err(ImgalError)`: If `num_threads` is < 1.
+    pub fn set_num_threads(&mut self, num_threads: Option<usize>) -> Result<(), ImgalError> {
+        if num_threads == Some(0) {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "num_threads",
+                reason: "must be >= 1 but got 0".to_string(),
+            });
+        }
+        self.num_threads = num_threads;
+
+        Ok(())
+    }
+
+    /// Set the algorithm version of the pipeline runs.
+    pub fn set_algorithm_version(&mut self, version: AlgorithmVersion) {
+        self.algorithm_version = version;
+    }
+
+    /// The names of the pipeline inputs.
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// The nodes of the pipeline, in execution order.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// The number of threads of the pipeline runs, `None` for the global
+    /// thread pool.
+    pub fn num_threads(&self) -> Option<usize> {
+        self.num_threads
+    }
+
+    /// The algorithm version of the pipeline runs.
+    pub fn algorithm_version(&self) -> AlgorithmVersion {
+        self.algorithm_version
+    }
+
+    /// Whether all steps of the pipeline can run on image chunks (see
+    /// `Step::is_chunkable`).
+    pub fn is_chunkable(&self) -> bool {
+        self.nodes.iter().all(|n| n.step.is_chunkable())
+    }
+
+    /// Run the pipeline.
+    ///
+    /// # Description
+    ///
+    /// This function computes the nodes in order with the pipeline's thread
+    /// pool and algorithm version. Each step is parallelized internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs`: The value of each pipeline input, by name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BTreeMap<String, Value>)`: The output value of each node, by name.
+    /// * `Err(ImgalError)`: If an input value is missing or not a pipeline
+    ///   input. If a step fails, _e.g._ an input value has the wrong kind or
+    ///   shape.
+    pub fn run(
+        &self,
+        inputs: BTreeMap<String, Value>,
+    ) -> Result<BTreeMap<String, Value>, ImgalError> {
+        if let Some(missing) = self.inputs.iter().find(|i| !inputs.contains_key(*i)) {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "inputs",
+                reason: format!("missing the value of the pipeline input \"{}\"", missing),
+            });
+        }
+        if let Some(unknown) = inputs.keys().find(|k| !self.inputs.contains(k)) {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "inputs",
+                reason: format!("\"{}\" is not a pipeline input", unknown),
+            });
+        }
+
+        algorithm::pinned(self.algorithm_version, || match self.num_threads {
+            Some(n) => config::with_num_threads(n, || self.execute(inputs))?,
+            None => self.execute(inputs),
+        })
+    }
+
+    /// Run the pipeline on images split into chunks.
+    ///
+    /// # Description
+    ///
+    /// This function runs the pipeline (see `run`) on each chunk of the inputs
+    /// (_e.g._ blocks of rows of an out-of-core acquisition). All steps must be
+    /// chunkable (see `Step::is_chunkable`), so the output chunks are equal to
+    /// the chunks of the full outputs. The inputs must be split along an axis
+    /// other than the decay, channel or lane axis of the steps.
+    ///
+    /// The returned iterator is lazy, each chunk is read and computed when the
+    /// iterator is advanced and can be written before the next one is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunks`: The value of each pipeline input, by name, for each chunk.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(impl Iterator<Item = Result<BTreeMap<String, Value>, ImgalError>>)`:
+    ///   An iterator over the output values of each chunk, see `run`.
+    /// * `Err(ImgalError)`: If a step of the pipeline is not chunkable.
+    pub fn run_chunked<I>(
+        &self,
+        chunks: I,
+    ) -> Result<impl Iterator<Item = Result<BTreeMap<String, Value>, ImgalError>>, ImgalError>
+    where
+        I: IntoIterator<Item = BTreeMap<String, Value>>,
+    {
+        if let Some(node) = self.nodes.iter().find(|n| !n.step.is_chunkable()) {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "pipeline",
+                reason: format!(
+                    "the \"{}\" step of node \"{}\" needs the full image and can not run on chunks",
+                    node.step.name(),
+                    node.name
+                ),
+            });
+        }
+
+        Ok(chunks.into_iter().map(move |chunk| self.run(chunk)))
+    }
+
+    /// Write the pipeline description to JSON.
+    ///
+    /// # Returns
+    ///
+    /// * `String`: The pretty printed JSON description of the pipeline inputs,
+    ///   nodes, number of threads and algorithm version.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a pipeline description is valid JSON")
+    }
+
+    /// Read a pipeline description from JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `json`: The JSON description, as written by `to_json`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Pipeline)`: The pipeline.
+    /// * `Err(ImgalError)`: If the JSON is not a pipeline description. If the
+    ///   description is not a valid pipeline (see `new`, `add` and
+    ///   `set_num_threads`).
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, ImgalError> {
+        let description: Pipeline =
+            serde_json::from_str(json).map_err(|e| ImgalError::InvalidParameter {
+                param_name: "json",
+                reason: e.to_string(),
+            })?;
+
+        // rebuild the pipeline to validate the graph
+        let inputs: Vec<&str> = description.inputs.iter().map(|i| i.as_str()).collect();
+        let mut pipeline = Pipeline::new(&inputs)?;
+        for node in description.nodes {
+            let inputs: Vec<&str> = node.inputs.iter().map(|i| i.as_str()).collect();
+            pipeline.add(&node.name, node.step, &inputs)?;
+        }
+        pipeline.set_num_threads(description.num_threads)?;
+        pipeline.set_algorithm_version(description.algorithm_version);
+
+        Ok(pipeline)
+    }
+
+    /// Compute the nodes in order.
+    fn execute(
+        &self,
+        inputs: BTreeMap<String, Value>,
+    ) -> Result<BTreeMap<String, Value>, ImgalError> {
+        let mut values = inputs;
+        for node in &self.nodes {
+            let args: Vec<&Value> = node.inputs.iter().map(|i| &values[i]).collect();
+            let output = node.step.run(&args)?;
+            values.insert(node.name.clone(), output);
+        }
+        values.retain(|k, _| !self.inputs.contains(k));
+
+        Ok(values)
+    }
+
+    /// Whether a name is a pipeline input or node.
+    fn contains(&self, name: &str) -> bool {
+        self.inputs.iter().any(|i| i == name) || self.nodes.iter().any(|n| n.name == name)
+    }
+
+    /// Check that a new input or node name is not empty and unique.
+    fn check_name(&self, name: &str) -> Result<(), ImgalError> {
+        if name.is_empty() {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "name",
+                reason: "must not be empty".to_string(),
+            });
+        }
+        if self.contains(name) {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "name",
+                reason: format!("\"{}\" is already a pipeline input or node", name),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+//! Processing pipeline graph functions.
+pub mod graph;
+pub use graph::{Node, Pipeline};
+pub mod step;
+pub use step::Step;
+pub mod value;
+pub use value::Value;
@@ -0,0 +1,423 @@
+use ndarray::{Array2, ArrayD, ArrayView1, ArrayView2, ArrayView3, Axis, Ix2, Ix3, Zip};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::warning::{self, Warning};
+use crate::error::{ImgalError, validate};
+use crate::filter;
+use crate::measure;
+use crate::morphology::{self, Connectivity};
+use crate::operator;
+use crate::phasor::{calibration, time_domain};
+use crate::pipeline::Value;
+use crate::threshold;
+
+/// A processing step of a `Pipeline` node.
+///
+/// # Description
+///
+/// Each step computes one output value from the values of the node inputs, in
+/// order. Optional inputs (_e.g._ a mask) can be left out. A step with a mask
+/// input only computes the pixels or lanes set to `true`, see the function
+/// each step calls.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Step {
+    /// Blur an image with a Gaussian, see `filter::gaussian`. Inputs: image.
+    /// Output: image.
+    Gaussian {
+        /// The standard deviation of each axis.
+        sigma: Vec<f64>,
+    },
+    /// Threshold an image at a fixed value, see `threshold::manual_mask`.
+    /// Inputs: image. Output: mask.
+    ManualThreshold {
+        /// The threshold value.
+        threshold: f64,
+    },
+    /// Threshold an image with Otsu's method, see `threshold::otsu_mask`.
+    /// Inputs: image, optional mask. Output: mask.
+    OtsuThreshold {
+        /// The number of histogram bins, default = 256.
+        bins: Option<usize>,
+    },
+    /// Threshold an image at a percentile of its pixel values, see
+    /// `threshold::percentile`. Inputs: image, optional mask. Output: mask.
+    PercentileThreshold {
+        /// The percentile, in [0, 100].
+        p: f64,
+    },
+    /// Label the connected components of a mask, see `morphology::label`.
+    /// Inputs: mask. Output: labels.
+    Label {
+        /// The pixel connectivity, default = `Connectivity::Full`.
+        connectivity: Option<Connectivity>,
+    },
+    /// Measure the region properties of 2-dimensional objects, see
+    /// `measure::regionprops`. Inputs: labels, optional intensity image,
+    /// optional (G, S) image. Output: table.
+    Regionprops {
+        /// The channel axis of the (G, S) image, default = 2.
+        axis: Option<usize>,
+    },
+    /// Compute the (G, S) image of 3-dimensional decay data, see
+    /// `phasor::time_domain::image`. Inputs: image, optional 2-dimensional
+    /// mask. Output: image.
+    PhasorImage {
+        /// The period (_i.e._ time interval).
+        period: f64,
+        /// The harmonic value, default = 1.0.
+        harmonic: Option<f64>,
+        /// The decay or lifetime axis, default = 2.
+        axis: Option<usize>,
+    },
+    /// Calibrate a (G, S) image, see `phasor::calibration::image`. Inputs:
+    /// image, optional 2-dimensional mask. Output: image.
+    PhasorCalibrate {
+        /// The modulation to calibrate with.
+        modulation: f64,
+        /// The phase to calibrate with.
+        phase: f64,
+        /// The channel axis, default = 2.
+        axis: Option<usize>,
+    },
+    /// Select the pixels of a (G, S) image within a circular phasor cursor.
+    /// Inputs: image, optional 2-dimensional mask. Output: 2-dimensional mask,
+    /// only pixels inside of the input mask are selected.
+    PhasorCursor {
+        /// The real component, G, of the cursor center.
+        g: f64,
+        /// The imaginary component, S, of the cursor center.
+        s: f64,
+        /// The cursor radius.
+        radius: f64,
+        /// The channel axis, default = 2.
+        axis: Option<usize>,
+    },
+    /// Apply a registered pixel operator, see `operator::pixel`. Inputs:
+    /// image, optional mask. Output: image.
+    PixelOp {
+        /// The name of the pixel operator.
+        name: String,
+    },
+    /// Apply a registered lane operator, see `operator::lane`. Inputs: image,
+    /// optional mask. Output: image.
+    LaneOp {
+        /// The name of the lane operator.
+        name: String,
+        /// The lane axis, default = the last axis.
+        axis: Option<usize>,
+    },
+}
+
+impl Step {
+    /// The name of the step, as used in the pipeline JSON description.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Step::Gaussian { .. } => "gaussian",
+            Step::ManualThreshold { .. } => "manual_threshold",
+            Step::OtsuThreshold { .. } => "otsu_threshold",
+            Step::PercentileThreshold { .. } => "percentile_threshold",
+            Step::Label { .. } => "label",
+            Step::Regionprops { .. } => "regionprops",
+            Step::PhasorImage { .. } => "phasor_image",
+            Step::PhasorCalibrate { .. } => "phasor_calibrate",
+            Step::PhasorCursor { .. } => "phasor_cursor",
+            Step::PixelOp { .. } => "pixel_op",
+            Step::LaneOp { .. } => "lane_op",
+        }
+    }
+
+    /// The minimum and maximum number of inputs of the step.
+    pub fn arity(&self) -> (usize, usize) {
+        match self {
+            Step::Gaussian { .. } | Step::ManualThreshold { .. } | Step::Label { .. } => (1, 1),
+            Step::Regionprops { .. } => (1, 3),
+            _ => (1, 2),
+        }
+    }
+
+    /// Whether the output of each pixel only depends on the same pixel (or
+    /// lane) of the inputs. Only these steps can run on image chunks, see
+    /// `Pipeline::run_chunked`.
+    pub fn is_chunkable(&self) -> bool {
+        matches!(
+            self,
+            Step::ManualThreshold { .. }
+                | Step::PhasorImage { .. }
+                | Step::PhasorCalibrate { .. }
+                | Step::PhasorCursor { .. }
+                | Step::PixelOp { .. }
+                | Step::LaneOp { .. }
+        )
+    }
+
+    /// Compute the output value of the step, the number of inputs is within
+    /// the step's arity.
+    pub(crate) fn run(&self, inputs: &[&Value]) -> Result<Value, ImgalError> {
+        match self {
+            Step::Gaussian { sigma } => {
+                let data = self.image(inputs, 0)?;
+                Ok(Value::Image(filter::gaussian(
+                    data.view(),
+                    sigma,
+                    None,
+                    None,
+                )?))
+            }
+            Step::ManualThreshold { threshold } => {
+                let data = self.image(inputs, 0)?;
+                Ok(Value::Mask(threshold::manual_mask(data.view(), *threshold)))
+            }
+            Step::OtsuThreshold { bins } => {
+                let data = self.image(inputs, 0)?;
+                let mask = self.optional_mask(inputs, 1)?.map(|m| m.view());
+                Ok(Value::Mask(threshold::otsu_mask(data.view(), mask, *bins)?))
+            }
+            Step::PercentileThreshold { p } => {
+                let data = self.image(inputs, 0)?;
+                let mask = self.optional_mask(inputs, 1)?;
+                let t = threshold::percentile(data.view(), *p, mask.map(|m| m.view()))?;
+
+                // create output mask of same shape and apply threshold
+                let mut output = ArrayD::<bool>::default(data.dim());
+                Zip::from(data)
+                    .and(&mut output)
+                    .par_for_each(|&v, op| *op = v > t);
+                if let Some(msk) = mask {
+                    Zip::from(&mut output).and(msk).par_for_each(|op, &m| {
+                        *op &= m;
+                    });
+                }
+                if !output.iter().any(|&m| m) {
+                    warning::warn(Warning::EmptyMask);
+                }
+
+                Ok(Value::Mask(output))
+            }
+            Step::Label { connectivity } => {
+                let mask = self.mask(inputs, 0)?;
+                Ok(Value::Labels(morphology::label(
+                    mask.view(),
+                    *connectivity,
+                    None,
+                )?))
+            }
+            Step::Regionprops { axis } => {
+                let labels = match inputs[0] {
+                    Value::Labels(labels) => view2(labels, "labels")?,
+                    v => return Err(self.mismatched(0, "labels", v)),
+                };
+                let intensity = match inputs.get(1) {
+                    Some(_) => Some(view2(self.image(inputs, 1)?, "intensity")?),
+                    None => None,
+                };
+                let phasor = match inputs.get(2) {
+                    Some(_) => Some(view3(self.image(inputs, 2)?, "phasor")?),
+                    None => None,
+                };
+                Ok(Value::Table(Box::new(measure::regionprops(
+                    labels, intensity, phasor, *axis,
+                )?)))
+            }
+            Step::PhasorImage {
+                period,
+                harmonic,
+                axis,
+            } => {
+                let data = view3(self.image(inputs, 0)?, "data")?;
+                let mask = self.optional_mask2(inputs, 1)?;
+                Ok(Value::Image(
+                    time_domain::image(data, *period, mask, *harmonic, *axis)?.into_dyn(),
+                ))
+            }
+            Step::PhasorCalibrate {
+                modulation,
+                phase,
+                axis,
+            } => {
+                let data = view3(self.image(inputs, 0)?, "data")?;
+                let mask = self.optional_mask2(inputs, 1)?;
+                Ok(Value::Image(
+                    calibration::image(data, *modulation, *phase, mask, *axis)?.into_dyn(),
+                ))
+            }
+            Step::PhasorCursor { g, s, radius, axis } => {
+                let data = view3(self.image(inputs, 0)?, "data")?;
+                let mask = self.optional_mask2(inputs, 1)?;
+                Ok(Value::Mask(
+                    cursor(data, mask, *g, *s, *radius, *axis)?.into_dyn(),
+                ))
+            }
+            Step::PixelOp { name } => {
+                let op = operator::pixel_op(name).ok_or_else(|| unknown_op("pixel", name))?;
+                let data = self.image(inputs, 0)?;
+                let mask = self.optional_mask(inputs, 1)?.map(|m| m.view());
+                Ok(Value::Image(operator::pixel(
+                    data.view(),
+                    op.as_ref(),
+                    mask,
+                )?))
+            }
+            Step::LaneOp { name, axis } => {
+                let op = operator::lane_op(name).ok_or_else(|| unknown_op("lane", name))?;
+                let data = self.image(inputs, 0)?;
+                let mask = self.optional_mask(inputs, 1)?.map(|m| m.view());
+                Ok(Value::Image(operator::lane(
+                    data.view(),
+                    op.as_ref(),
+                    mask,
+                    *axis,
+                )?))
+            }
+        }
+    }
+
+    /// Get an image input.
+    fn image<'a>(&self, inputs: &[&'a Value], index: usize) -> Result<&'a ArrayD<f64>, ImgalError> {
+        match inputs[index] {
+            Value::Image(data) => Ok(data),
+            v => Err(self.mismatched(index, "image", v)),
+        }
+    }
+
+    /// Get a mask input.
+    fn mask<'a>(&self, inputs: &[&'a Value], index: usize) -> Result<&'a ArrayD<bool>, ImgalError> {
+        match inputs[index] {
+            Value::Mask(mask) => Ok(mask),
+            v => Err(self.mismatched(index, "mask", v)),
+        }
+    }
+
+    /// Get an optional mask input.
+    fn optional_mask<'a>(
+        &self,
+        inputs: &[&'a Value],
+        index: usize,
+    ) -> Result<Option<&'a ArrayD<bool>>, ImgalError> {
+        match inputs.get(index) {
+            Some(_) => Ok(Some(self.mask(inputs, index)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get an optional 2-dimensional mask input.
+    fn optional_mask2<'a>(
+        &self,
+        inputs: &[&'a Value],
+        index: usize,
+    ) -> Result<Option<ArrayView2<'a, bool>>, ImgalError> {
+        match self.optional_mask(inputs, index)? {
+            Some(mask) => Ok(Some(view2(mask, "mask")?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The error of an input with the wrong kind of value.
+    fn mismatched(&self, index: usize, expected: &str, value: &Value) -> ImgalError {
+        ImgalError::InvalidParameter {
+            param_name: "inputs",
+            reason: format!(
+                "input {} of the \"{}\" step must be a {} value but got a {} value",
+                index,
+                self.name(),
+                expected,
+                value.kind()
+            ),
+        }
+    }
+}
+
+/// Select the pixels of a (G, S) image within a circular phasor cursor.
+fn cursor(
+    data: ArrayView3<f64>,
+    mask: Option<ArrayView2<bool>>,
+    g: f64,
+    s: f64,
+    radius: f64,
+    axis: Option<usize>,
+) -> Result<Array2<bool>, ImgalError> {
+    // set optional parameters if needed
+    let a = axis.unwrap_or(2);
+
+    // check if the axis, radius and mask parameters are valid
+    validate::axis(a, 3)?;
+    validate::positive("radius", radius)?;
+    let n = data.len_of(Axis(a));
+    if n != 2 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!("must have 2 (G, S) channels along axis {} but got {}", a, n),
+        });
+    }
+    let mut shape = data.shape().to_vec();
+    shape.remove(a);
+    if let Some(msk) = &mask {
+        validate::same_shape(msk.shape(), &shape)?;
+    }
+
+    // select the pixels within the cursor radius
+    let r_sq = radius * radius;
+    let inside = |ln: ArrayView1<f64>| {
+        let dg = ln[0] - g;
+        let ds = ln[1] - s;
+        dg * dg + ds * ds <= r_sq
+    };
+    let mut output = Array2::<bool>::default((shape[0], shape[1]));
+    match mask {
+        Some(msk) => {
+            Zip::from(&mut output)
+                .and(data.lanes(Axis(a)))
+                .and(&msk)
+                .par_for_each(|o, ln, &m| *o = m && inside(ln));
+        }
+        None => {
+            Zip::from(&mut output)
+                .and(data.lanes(Axis(a)))
+                .par_for_each(|o, ln| *o = inside(ln));
+        }
+    }
+
+    Ok(output)
+}
+
+/// View an n-dimensional array as a 2-dimensional array.
+fn view2<'a, A>(
+    data: &'a ArrayD<A>,
+    param_name: &'static str,
+) -> Result<ArrayView2<'a, A>, ImgalError> {
+    data.view()
+        .into_dimensionality::<Ix2>()
+        .map_err(|_| ImgalError::InvalidParameter {
+            param_name,
+            reason: format!(
+                "must be a 2-dimensional array but got shape {:?}",
+                data.shape()
+            ),
+        })
+}
+
+/// View an n-dimensional array as a 3-dimensional array.
+fn view3<'a, A>(
+    data: &'a ArrayD<A>,
+    param_name: &'static str,
+) -> Result<ArrayView3<'a, A>, ImgalError> {
+    data.view()
+        .into_dimensionality::<Ix3>()
+        .map_err(|_| ImgalError::InvalidParameter {
+            param_name,
+            reason: format!(
+                "must be a 3-dimensional array but got shape {:?}",
+                data.shape()
+            ),
+        })
+}
+
+/// The error of an operator name that is not registered.
+fn unknown_op(kind: &str, name: &str) -> ImgalError {
+    ImgalError::InvalidParameter {
+        param_name: "name",
+        reason: format!("no {} operator is registered as \"{}\"", kind, name),
+    }
+}
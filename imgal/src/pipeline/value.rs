@@ -0,0 +1,84 @@
+use ndarray::ArrayD;
+
+use crate::measure::RegionProps;
+
+/// A value passed between the nodes of a `Pipeline`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// An n-dimensional image.
+    Image(ArrayD<f64>),
+    /// An n-dimensional boolean mask.
+    Mask(ArrayD<bool>),
+    /// An n-dimensional object label image, the label 0 is the background.
+    Labels(ArrayD<u32>),
+    /// A table of region properties, with one entry per object.
+    Table(Box<RegionProps<u32>>),
+}
+
+impl Value {
+    /// The kind of the value, "image", "mask", "labels" or "table".
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Image(_) => "image",
+            Value::Mask(_) => "mask",
+            Value::Labels(_) => "labels",
+            Value::Table(_) => "table",
+        }
+    }
+
+    /// The image, `None` if the value is not an image.
+    pub fn as_image(&self) -> Option<&ArrayD<f64>> {
+        match self {
+            Value::Image(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// The boolean mask, `None` if the value is not a mask.
+    pub fn as_mask(&self) -> Option<&ArrayD<bool>> {
+        match self {
+            Value::Mask(mask) => Some(mask),
+            _ => None,
+        }
+    }
+
+    /// The label image, `None` if the value is not a label image.
+    pub fn as_labels(&self) -> Option<&ArrayD<u32>> {
+        match self {
+            Value::Labels(labels) => Some(labels),
+            _ => None,
+        }
+    }
+
+    /// The region properties table, `None` if the value is not a table.
+    pub fn as_table(&self) -> Option<&RegionProps<u32>> {
+        match self {
+            Value::Table(table) => Some(table.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<ArrayD<f64>> for Value {
+    fn from(data: ArrayD<f64>) -> Self {
+        Value::Image(data)
+    }
+}
+
+impl From<ArrayD<bool>> for Value {
+    fn from(mask: ArrayD<bool>) -> Self {
+        Value::Mask(mask)
+    }
+}
+
+impl From<ArrayD<u32>> for Value {
+    fn from(labels: ArrayD<u32>) -> Self {
+        Value::Labels(labels)
+    }
+}
+
+impl From<RegionProps<u32>> for Value {
+    fn from(table: RegionProps<u32>) -> Self {
+        Value::Table(Box::new(table))
+    }
+}
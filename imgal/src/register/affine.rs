@@ -0,0 +1,445 @@
+use ndarray::{Array2, ArrayView2};
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, Warning, validate, warning};
+use crate::filter::BorderMode;
+use crate::traits::numeric::ToFloat64;
+use crate::transform::affine::{Interpolation, Sampler, invert_affine, warp_affine};
+
+/// The number of intensity bins per image of the mutual information metric.
+const MI_BINS: usize = 32;
+
+/// The minimum fraction of reference pixels the transformed moving image must
+/// overlap to evaluate the metric.
+const MIN_OVERLAP: f64 = 0.1;
+
+/// The simplex size, in pixels, at which the optimization of a pyramid level
+/// stops.
+const TOLERANCE: f64 = 1e-3;
+
+/// Similarity metrics between the reference and the transformed moving image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// The mean squared error, for images of the same modality and intensity
+    /// scale.
+    Mse,
+    /// The normalized cross-correlation, invariant to linear intensity changes.
+    Ncc,
+    /// The mutual information, for images of different modalities.
+    MutualInformation,
+}
+
+/// Transformation models of the image registration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// A (row, col) translation, 2 parameters.
+    Translation,
+    /// A rotation about the image center and a translation, 3 parameters.
+    Rigid,
+    /// A general linear transformation about the image center (_i.e._
+    /// rotation, scaling and shearing) and a translation, 6 parameters.
+    Affine,
+}
+
+/// The result of an intensity-based affine image registration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffineRegistration {
+    /// The 3 x 3 homogeneous matrix that maps moving image (row, col, 1)
+    /// coordinates to reference image coordinates, see
+    /// `transform::warp_affine`.
+    pub matrix: Array2<f64>,
+    /// The moving image transformed into the reference image frame, pixels
+    /// outside of the moving image are 0.0.
+    pub registered: Array2<f64>,
+    /// The final metric value, the MSE, the NCC or the MI in nats.
+    pub metric: f64,
+}
+
+/// Register two 2-dimensional images with an iterative intensity-based affine
+/// or rigid registration.
+///
+/// # Description
+///
+/// This function searches the transform of the moving image that optimizes the
+/// similarity `metric` between the reference image and the transformed moving
+/// image, evaluated over the pixels where the two images overlap. The
+/// transform parameters are optimized with the derivative free Nelder-Mead
+/// simplex method on a multi-resolution pyramid (2 × 2 block means), from the
+/// coarsest to the full resolution level, which enlarges the capture range and
+/// reduces the cost of the search. Rotations, scalings and shearings are about
+/// the image center. Transforms that overlap less than 10% of the reference
+/// image are rejected. The mutual information is computed from a joint
+/// histogram with 32 bins per image spanning the intensity range of each
+/// image.
+///
+/// # Arguments
+///
+/// * `reference`: The 2-dimensional reference image.
+/// * `moving`: The 2-dimensional moving image. Must match the shape of
+///   `reference`.
+/// * `transform`: The transformation model, default = `Transform::Affine`.
+/// * `metric`: The similarity metric, default = `Metric::Mse`.
+/// * `levels`: The maximum number of pyramid levels, default = 3. Levels with
+///   an axis shorter than 8 pixels are skipped. Must be >= 1.
+/// * `interpolation`: The interpolation method of the moving image, default =
+///   `Interpolation::Linear`.
+/// * `max_iterations`: The maximum number of simplex iterations per pyramid
+///   level, default = 300. Must be >= 1. A `Warning::NotConverged` is emitted
+///   if a level reaches the limit before the simplex converges.
+///
+/// # Returns
+///
+/// * `Ok(AffineRegistration)`: The transform matrix, the registered image and
+///   the final metric value.
+/// * `Err(ImgalError)`: If the image shapes do not match or an axis is shorter
+///   than 4 pixels. If levels or max_iterations is 0. If the optimized
+///   transform is singular.
+pub fn affine<S, T>(
+    reference: ArrayView2<S>,
+    moving: ArrayView2<T>,
+    transform: Option<Transform>,
+    metric: Option<Metric>,
+    levels: Option<usize>,
+    interpolation: Option<Interpolation>,
+    max_iterations: Option<usize>,
+) -> Result<AffineRegistration, ImgalError>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let transform = transform.unwrap_or(Transform::Affine);
+    let metric = metric.unwrap_or(Metric::Mse);
+    let levels = levels.unwrap_or(3);
+    let interpolation = interpolation.unwrap_or(Interpolation::Linear);
+    let max_iterations = max_iterations.unwrap_or(300);
+
+    // check if the image shapes and the parameters are valid
+    validate::same_shape(moving.shape(), reference.shape())?;
+    let (rows, cols) = reference.dim();
+    if rows < 4 || cols < 4 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "reference",
+            reason: format!("must be at least 4 x 4 pixels but got {} x {}", rows, cols),
+        });
+    }
+    if levels == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "levels",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+    if max_iterations == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "max_iterations",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+
+    // scale the intensities to [0, 1] for the mutual information bins
+    let moving = moving
+        .mapv(|v| v.to_f64())
+        .as_standard_layout()
+        .into_owned();
+    let mut ref_level = reference.mapv(|v| v.to_f64());
+    let mut mov_level = moving.clone();
+    if metric == Metric::MutualInformation {
+        normalize(&mut ref_level);
+        normalize(&mut mov_level);
+    }
+
+    // build the image pyramid, from full to coarsest resolution
+    let mut pyramid = vec![(ref_level, mov_level)];
+    while pyramid.len() < levels {
+        let (r, m) = pyramid.last().unwrap();
+        if r.nrows() / 2 < 8 || r.ncols() / 2 < 8 {
+            break;
+        }
+        let next = (downsample(r), downsample(m));
+        pyramid.push(next);
+    }
+
+    // optimize the transform parameters from the coarsest level, the
+    // parameters are in pixels and double at each finer level
+    let n_params = match transform {
+        Transform::Translation => 2,
+        Transform::Rigid => 3,
+        Transform::Affine => 6,
+    };
+    let mut params = vec![0.0; n_params];
+    let mut not_converged = 0;
+    pyramid.iter().rev().enumerate().for_each(|(i, (r, m))| {
+        if i > 0 {
+            params.iter_mut().for_each(|p| *p *= 2.0);
+        }
+        let cost = |p: &[f64]| {
+            let inverse = params_to_inverse(transform, p, r.dim());
+            match similarity(r, m, &inverse, interpolation, metric) {
+                Some(v) if metric == Metric::Mse => v,
+                Some(v) => -v,
+                None => f64::INFINITY,
+            }
+        };
+        let (p, converged) = nelder_mead(cost, &params, 1.0, max_iterations);
+        params = p;
+        if !converged {
+            not_converged += 1;
+        }
+    });
+    if not_converged > 0 {
+        warning::warn(Warning::NotConverged {
+            operation: "affine registration",
+            count: not_converged,
+        });
+    }
+
+    // transform the moving image with the final parameters
+    let inverse = params_to_inverse(transform, &params, (rows, cols));
+    let (ref_full, mov_full) = &pyramid[0];
+    let value = similarity(ref_full, mov_full, &inverse, interpolation, metric).unwrap_or(f64::NAN);
+    let mut inverse_matrix = Array2::<f64>::eye(3);
+    (0..2).for_each(|r| (0..3).for_each(|c| inverse_matrix[[r, c]] = inverse[r][c]));
+    let matrix =
+        invert_affine(inverse_matrix.view()).ok_or_else(|| ImgalError::NumericFailure {
+            operation: "affine registration",
+            reason: "the optimized transform is singular".to_string(),
+        })?;
+    let registered = warp_affine(
+        moving.view().into_dyn(),
+        matrix.view(),
+        Some(interpolation),
+        None,
+    )?
+    .into_dimensionality()
+    .expect("the registered image is 2-dimensional");
+
+    Ok(AffineRegistration {
+        matrix,
+        registered,
+        metric: value,
+    })
+}
+
+/// Compute the metric between the reference image and the moving image
+/// sampled at the inverse mapped positions, `None` if the images overlap too
+/// little.
+fn similarity(
+    reference: &Array2<f64>,
+    moving: &Array2<f64>,
+    inverse: &[[f64; 3]; 2],
+    interpolation: Interpolation,
+    metric: Metric,
+) -> Option<f64> {
+    // collect the overlapping intensity pairs of each row
+    let sampler = Sampler::new(
+        moving.as_slice().unwrap(),
+        moving.shape(),
+        interpolation,
+        BorderMode::Constant(0.0),
+    );
+    let sampler = &sampler;
+    let cols = reference.ncols();
+    let pairs: Vec<(f64, f64)> = (0..reference.nrows())
+        .into_par_iter()
+        .flat_map_iter(|r| {
+            (0..cols).filter_map(move |c| {
+                let (y, x) = map_point(inverse, r, c);
+                sampler.sample(&[y, x]).map(|m| (reference[[r, c]], m))
+            })
+        })
+        .collect();
+    if (pairs.len() as f64) < MIN_OVERLAP * reference.len() as f64 {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let value = match metric {
+        Metric::Mse => pairs.iter().map(|(a, b)| (a - b) * (a - b)).sum::<f64>() / n,
+        Metric::Ncc => {
+            let mean_a = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+            let mean_b = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+            let (cov, var_a, var_b) =
+                pairs
+                    .iter()
+                    .fold((0.0, 0.0, 0.0), |(cov, va, vb), &(a, b)| {
+                        let (da, db) = (a - mean_a, b - mean_b);
+                        (cov + da * db, va + da * da, vb + db * db)
+                    });
+            if var_a > 0.0 && var_b > 0.0 {
+                cov / (var_a * var_b).sqrt()
+            } else {
+                0.0
+            }
+        }
+        Metric::MutualInformation => {
+            // the intensities are scaled to [0, 1]
+            let bin = |v: f64| ((v * MI_BINS as f64).max(0.0) as usize).min(MI_BINS - 1);
+            let mut hist = Array2::<f64>::zeros((MI_BINS, MI_BINS));
+            pairs
+                .iter()
+                .for_each(|&(a, b)| hist[[bin(a), bin(b)]] += 1.0 / n);
+            let p_a = hist.sum_axis(ndarray::Axis(1));
+            let p_b = hist.sum_axis(ndarray::Axis(0));
+            hist.indexed_iter()
+                .filter(|&(_, &p)| p > 0.0)
+                .map(|((i, j), &p)| p * (p / (p_a[i] * p_b[j])).ln())
+                .sum::<f64>()
+                .max(0.0)
+        }
+    };
+
+    Some(value)
+}
+
+/// Map the (row, col) position with the first two rows of an affine matrix.
+fn map_point(m: &[[f64; 3]; 2], r: usize, c: usize) -> (f64, f64) {
+    let (r, c) = (r as f64, c as f64);
+
+    (
+        m[0][0] * r + m[0][1] * c + m[0][2],
+        m[1][0] * r + m[1][1] * c + m[1][2],
+    )
+}
+
+/// Convert the transform parameters to the affine matrix that maps reference
+/// positions to moving image positions. The linear parameters are scaled by
+/// the image radius so that all parameters are in pixels.
+fn params_to_inverse(transform: Transform, p: &[f64], shape: (usize, usize)) -> [[f64; 3]; 2] {
+    let center = ((shape.0 - 1) as f64 / 2.0, (shape.1 - 1) as f64 / 2.0);
+    let radius = shape.0.max(shape.1) as f64 / 2.0;
+    let (a, t) = match transform {
+        Transform::Translation => ([[1.0, 0.0], [0.0, 1.0]], (p[0], p[1])),
+        Transform::Rigid => {
+            let (sin, cos) = (p[0] / radius).sin_cos();
+            ([[cos, -sin], [sin, cos]], (p[1], p[2]))
+        }
+        Transform::Affine => (
+            [
+                [1.0 + p[0] / radius, p[1] / radius],
+                [p[2] / radius, 1.0 + p[3] / radius],
+            ],
+            (p[4], p[5]),
+        ),
+    };
+
+    // p_mov = A × (p_ref - center) + center + t
+    [
+        [
+            a[0][0],
+            a[0][1],
+            center.0 + t.0 - a[0][0] * center.0 - a[0][1] * center.1,
+        ],
+        [
+            a[1][0],
+            a[1][1],
+            center.1 + t.1 - a[1][0] * center.0 - a[1][1] * center.1,
+        ],
+    ]
+}
+
+/// Scale an image to the range [0, 1], constant images become 0.0.
+fn normalize(data: &mut Array2<f64>) {
+    let (min, max) = data
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+    let range = max - min;
+    data.mapv_inplace(|v| if range > 0.0 { (v - min) / range } else { 0.0 });
+}
+
+/// Downsample an image by 2 along each axis with 2 x 2 block means.
+fn downsample(data: &Array2<f64>) -> Array2<f64> {
+    Array2::from_shape_fn((data.nrows() / 2, data.ncols() / 2), |(r, c)| {
+        0.25 * (data[[2 * r, 2 * c]]
+            + data[[2 * r, 2 * c + 1]]
+            + data[[2 * r + 1, 2 * c]]
+            + data[[2 * r + 1, 2 * c + 1]])
+    })
+}
+
+/// Minimize a function with the Nelder-Mead simplex method, starting from a
+/// simplex with edges of length `step` around `start`. Returns the best vertex
+/// and whether the simplex converged within `max_iterations`.
+fn nelder_mead<F>(f: F, start: &[f64], step: f64, max_iterations: usize) -> (Vec<f64>, bool)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = start.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = (0..=n)
+        .map(|i| {
+            let mut x = start.to_vec();
+            if i > 0 {
+                x[i - 1] += step;
+            }
+            let v = f(&x);
+            (x, v)
+        })
+        .collect();
+
+    // the largest distance of a vertex to the best vertex along any axis, of
+    // a sorted simplex
+    let size = |simplex: &[(Vec<f64>, f64)]| {
+        simplex[1..]
+            .iter()
+            .flat_map(|(x, _)| {
+                x.iter()
+                    .zip(simplex[0].0.iter())
+                    .map(|(a, b)| (a - b).abs())
+            })
+            .fold(0.0, f64::max)
+    };
+
+    for _ in 0..max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if size(&simplex) < TOLERANCE {
+            break;
+        }
+        let best = simplex[0].0.clone();
+
+        // move the worst vertex along the line through the centroid of the rest
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let (worst, f_worst) = simplex[n].clone();
+        let along = |t: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(worst.iter())
+                .map(|(c, w)| c + t * (w - c))
+                .collect()
+        };
+        let reflected = along(-1.0);
+        let f_reflected = f(&reflected);
+        if f_reflected < simplex[0].1 {
+            let expanded = along(-2.0);
+            let f_expanded = f(&expanded);
+            simplex[n] = if f_expanded < f_reflected {
+                (expanded, f_expanded)
+            } else {
+                (reflected, f_reflected)
+            };
+        } else if f_reflected < simplex[n - 1].1 {
+            simplex[n] = (reflected, f_reflected);
+        } else {
+            let t = if f_reflected < f_worst { -0.5 } else { 0.5 };
+            let contracted = along(t);
+            let f_contracted = f(&contracted);
+            if f_contracted < f_reflected.min(f_worst) {
+                simplex[n] = (contracted, f_contracted);
+            } else {
+                // shrink the simplex towards the best vertex
+                simplex[1..].iter_mut().for_each(|(x, v)| {
+                    x.iter_mut()
+                        .zip(best.iter())
+                        .for_each(|(xi, bi)| *xi = bi + 0.5 * (*xi - bi));
+                    *v = f(x);
+                });
+            }
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let converged = size(&simplex) < TOLERANCE;
+
+    (simplex.swap_remove(0).0, converged)
+}
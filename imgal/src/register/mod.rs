@@ -0,0 +1,5 @@
+//! Image registration functions.
+pub mod affine;
+pub use affine::{AffineRegistration, Metric, Transform, affine};
+pub mod translation;
+pub use translation::{PhaseCorrelation, apply_shift, phase_correlation};
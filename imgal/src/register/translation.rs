@@ -0,0 +1,240 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array2, ArrayD, ArrayView2, Zip};
+use rustfft::num_complex::Complex;
+
+use crate::error::{ImgalError, validate};
+use crate::filter::convolve::fft_nd;
+use crate::traits::numeric::ToFloat64;
+
+/// The translation between two images estimated by phase correlation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhaseCorrelation {
+    /// The (row, col) shift to apply to the moving image to register it with
+    /// the reference image, see `apply_shift`.
+    pub shift: (f64, f64),
+    /// The height of the phase correlation peak, in [0, 1]. Values near 1.0
+    /// indicate a pure translation, low values an unreliable estimate.
+    pub peak: f64,
+}
+
+/// Estimate the translation between two 2-dimensional images by phase
+/// correlation.
+///
+/// # Description
+///
+/// This function computes the normalized cross-power spectrum of the two
+/// images:
+///
+/// ```text
+/// R = F_ref × F_mov* / |F_ref × F_mov*|
+/// ```
+///
+/// whose inverse Fourier transform is a sharp peak at the shift between the
+/// images. The integer peak is refined to `1 / upsample` of a pixel by
+/// evaluating the inverse transform on an upsampled grid of 1.5 × 1.5 pixels
+/// around it with a matrix-multiply discrete Fourier transform, which is
+/// exact and much cheaper than zero padding the whole spectrum. The images
+/// are treated as periodic and shifts are reported in [-n / 2, n / 2) along
+/// each axis.
+///
+/// # Arguments
+///
+/// * `reference`: The 2-dimensional reference image.
+/// * `moving`: The 2-dimensional moving image. Must match the shape of
+///   `reference`.
+/// * `upsample`: The sub-pixel precision factor, default = 10 (_i.e._ 0.1
+///   pixel). An upsample of 1 gives integer shifts. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(PhaseCorrelation)`: The shift and the correlation peak height.
+/// * `Err(ImgalError)`: If the image shapes do not match or are empty. If
+///   upsample is 0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1364/OL.33.000156>
+pub fn phase_correlation<S, T>(
+    reference: ArrayView2<S>,
+    moving: ArrayView2<T>,
+    upsample: Option<usize>,
+) -> Result<PhaseCorrelation, ImgalError>
+where
+    S: ToFloat64,
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let upsample = upsample.unwrap_or(10);
+
+    // check if the image shapes and the upsample parameter are valid
+    validate::same_shape(moving.shape(), reference.shape())?;
+    if reference.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "reference",
+            reason: "must not be empty".to_string(),
+        });
+    }
+    if upsample == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "upsample",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+
+    // compute the normalized cross-power spectrum
+    let mut f_ref: ArrayD<Complex<f64>> =
+        reference.mapv(|v| Complex::new(v.to_f64(), 0.0)).into_dyn();
+    let mut f_mov: ArrayD<Complex<f64>> = moving.mapv(|v| Complex::new(v.to_f64(), 0.0)).into_dyn();
+    fft_nd(&mut f_ref, false);
+    fft_nd(&mut f_mov, false);
+    Zip::from(&mut f_ref).and(&f_mov).par_for_each(|r, m| {
+        let p = *r * m.conj();
+        let norm = p.norm();
+        *r = if norm > 0.0 {
+            p / norm
+        } else {
+            Complex::new(0.0, 0.0)
+        };
+    });
+    let spectrum = f_ref.into_dimensionality::<ndarray::Ix2>().unwrap();
+
+    // find the integer correlation peak
+    let mut corr = spectrum.clone().into_dyn();
+    fft_nd(&mut corr, true);
+    let (rows, cols) = spectrum.dim();
+    let (mut best, mut peak) = ((0, 0), f64::NEG_INFINITY);
+    corr.indexed_iter().for_each(|(idx, v)| {
+        if v.re > peak {
+            peak = v.re;
+            best = (idx[0], idx[1]);
+        }
+    });
+    let wrap = |i: usize, n: usize| {
+        if i >= n.div_ceil(2) {
+            i as f64 - n as f64
+        } else {
+            i as f64
+        }
+    };
+    let mut shift = (wrap(best.0, rows), wrap(best.1, cols));
+
+    // refine the peak on an upsampled grid around the integer peak
+    if upsample > 1 {
+        let u = upsample as f64;
+        let size = (1.5 * u).ceil() as usize;
+        let half = (size / 2) as f64;
+        let row_kernel = dft_kernel(rows, size, shift.0, half, u);
+        let col_kernel = dft_kernel(cols, size, shift.1, half, u);
+        let upsampled = row_kernel.dot(&spectrum).dot(&col_kernel.t());
+        let (mut best_up, mut peak_up) = ((0, 0), f64::NEG_INFINITY);
+        upsampled.indexed_iter().for_each(|((r, c), v)| {
+            if v.re > peak_up {
+                peak_up = v.re;
+                best_up = (r, c);
+            }
+        });
+        shift = (
+            shift.0 + (best_up.0 as f64 - half) / u,
+            shift.1 + (best_up.1 as f64 - half) / u,
+        );
+        peak = peak_up / (rows * cols) as f64;
+    }
+
+    Ok(PhaseCorrelation {
+        shift,
+        peak: peak.clamp(0.0, 1.0),
+    })
+}
+
+/// Translate a 2-dimensional image by a sub-pixel shift.
+///
+/// # Description
+///
+/// This function moves the image content by `shift` pixels, the output value
+/// at (row, col) is the input sampled at (row - shift.0, col - shift.1) with
+/// bilinear interpolation. Integer shifts are exact. Output pixels that sample
+/// outside of the image are set to `fill`. Apply the shift estimated by
+/// `phase_correlation` to the moving image to register it with the reference
+/// image.
+///
+/// # Arguments
+///
+/// * `data`: The 2-dimensional image.
+/// * `shift`: The (row, col) shift in pixels. Must be finite.
+/// * `fill`: The value of the output pixels outside of the input image,
+///   default = 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The shifted image with the same shape as `data`.
+/// * `Err(ImgalError)`: If a shift is not finite.
+pub fn apply_shift<T>(
+    data: ArrayView2<T>,
+    shift: (f64, f64),
+    fill: Option<f64>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let fill = fill.unwrap_or(0.0);
+
+    // check if the shift parameter is valid
+    if !(shift.0.is_finite() && shift.1.is_finite()) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "shift",
+            reason: format!("must be finite but got {:?}", shift),
+        });
+    }
+
+    // sample the input at the shifted positions
+    let (rows, cols) = data.dim();
+    let sample = |r: isize, c: isize| {
+        if r < 0 || c < 0 || r as usize >= rows || c as usize >= cols {
+            None
+        } else {
+            Some(data[[r as usize, c as usize]].to_f64())
+        }
+    };
+    let mut output = Array2::<f64>::zeros((rows, cols));
+    Zip::indexed(&mut output).par_for_each(|(r, c), o| {
+        let (y, x) = (r as f64 - shift.0, c as f64 - shift.1);
+        let (y0, x0) = (y.floor(), x.floor());
+        let (ty, tx) = (y - y0, x - x0);
+        let (y0, x0) = (y0 as isize, x0 as isize);
+
+        // weight the four neighbors, skipping the zero weights at the border
+        let mut value = 0.0;
+        let mut inside = true;
+        [
+            (0, 0, (1.0 - ty) * (1.0 - tx)),
+            (0, 1, (1.0 - ty) * tx),
+            (1, 0, ty * (1.0 - tx)),
+            (1, 1, ty * tx),
+        ]
+        .into_iter()
+        .filter(|&(_, _, w)| w > 0.0)
+        .for_each(|(dy, dx, w)| match sample(y0 + dy, x0 + dx) {
+            Some(v) => value += w * v,
+            None => inside = false,
+        });
+        *o = if inside { value } else { fill };
+    });
+
+    Ok(output)
+}
+
+/// Create the matrix DFT kernel that evaluates the inverse transform along an
+/// axis of length `n` at `size` points spaced `1 / u` apart around `center`.
+fn dft_kernel(n: usize, size: usize, center: f64, half: f64, u: f64) -> Array2<Complex<f64>> {
+    Array2::from_shape_fn((size, n), |(j, k)| {
+        let freq = if k >= n.div_ceil(2) {
+            k as f64 - n as f64
+        } else {
+            k as f64
+        };
+        let x = center + (j as f64 - half) / u;
+        Complex::from_polar(1.0, 2.0 * PI * freq * x / n as f64)
+    })
+}
@@ -0,0 +1,223 @@
+use ndarray::{ArrayView1, ArrayView4, ArrayView5, Axis};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::parameter::omega;
+use crate::phasor::time_domain;
+use crate::roi::manager::{Manager, Roi};
+use crate::roi::table::{ResultsRow, ResultsTable};
+use crate::traits::numeric::ToFloat64;
+
+/// Measure per ROI, per channel and per time point summary statistics of a
+/// 4-dimensional multi-channel time-lapse.
+///
+/// # Description
+///
+/// This function computes the `mean`, `median` and `integrated` (_i.e._ summed)
+/// intensity of the pixels of each ROI in each channel of each time point.
+/// ROIs and time points are measured in parallel and the results table rows
+/// are ordered by ROI (insertion order), then time point, then channel, then
+/// measurement. The time point index is stored in the `slice` column. ROIs
+/// without pixels have a NaN mean and median and an integrated intensity of
+/// 0.0.
+///
+/// # Arguments
+///
+/// * `manager`: The ROI manager.
+/// * `data`: The 4-dimensional time-lapse with shape (t, row, col, ch). The
+///   (row, col) shape must match the ROI shape.
+/// * `group`: Only measure ROIs tagged with this group. If `None`, all ROIs are
+///   measured.
+///
+/// # Returns
+///
+/// * `Ok(ResultsTable)`: The tidy table of measurements.
+/// * `Err(ImgalError)`: If the image shape does not match the ROI shape.
+pub fn summary_4d<T>(
+    manager: &Manager,
+    data: ArrayView4<T>,
+    group: Option<&str>,
+) -> Result<ResultsTable, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the image shape is valid
+    let (frames, rows, cols, channels) = data.dim();
+    let Some(shape) = manager.shape() else {
+        return Ok(ResultsTable::new());
+    };
+    validate::same_shape(&[rows, cols], &[shape.0, shape.1])?;
+
+    // measure each (ROI, frame) pair in parallel, keeping the row order
+    let rois = manager.select(group);
+    let results: Vec<Vec<ResultsRow>> = (0..rois.len() * frames)
+        .into_par_iter()
+        .map(|i| {
+            let roi = rois[i / frames];
+            let t = i % frames;
+            let frame = data.index_axis(Axis(0), t);
+            (0..channels)
+                .flat_map(|ch| {
+                    let plane = frame.index_axis(Axis(2), ch);
+                    let px: Vec<f64> = plane
+                        .iter()
+                        .zip(roi.mask.iter())
+                        .filter(|&(_, &m)| m)
+                        .map(|(v, _)| v.to_f64())
+                        .collect();
+                    intensity_rows(roi, t, ch, px)
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(collect_table(results))
+}
+
+/// Measure per ROI, per channel and per time point summary statistics and
+/// lifetimes of a 5-dimensional multi-channel lifetime time-lapse.
+///
+/// # Description
+///
+/// This function computes the `mean`, `median` and `integrated` (_i.e._ summed)
+/// intensity of the pixels of each ROI in each channel of each time point,
+/// where the intensity of a pixel is the sum of its decay curve. The `lifetime`
+/// is the phase lifetime of the ROI summed decay curve:
+///
+/// ```text
+/// τφ = S / (ω × G)
+/// ```
+///
+/// where G and S are the first harmonic time domain phasor coordinates of the
+/// summed decay curve. The lifetime is not calibrated for the instrument
+/// response, calibrate the phasor coordinates of the data first if needed. ROIs
+/// and time points are measured in parallel and the results table rows are
+/// ordered by ROI (insertion order), then time point, then channel, then
+/// measurement. The time point index is stored in the `slice` column. ROIs
+/// without pixels or counts have a NaN mean, median and lifetime and an
+/// integrated intensity of 0.0.
+///
+/// # Arguments
+///
+/// * `manager`: The ROI manager.
+/// * `data`: The 5-dimensional lifetime time-lapse with shape
+///   (t, row, col, ch, tau). The (row, col) shape must match the ROI shape.
+/// * `period`: The period (_i.e._ time interval) of the decay axis.
+/// * `group`: Only measure ROIs tagged with this group. If `None`, all ROIs are
+///   measured.
+///
+/// # Returns
+///
+/// * `Ok(ResultsTable)`: The tidy table of measurements.
+/// * `Err(ImgalError)`: If the image shape does not match the ROI shape. If
+///   period is <= 0.0.
+pub fn summary_5d<T>(
+    manager: &Manager,
+    data: ArrayView5<T>,
+    period: f64,
+    group: Option<&str>,
+) -> Result<ResultsTable, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the period and image shape are valid
+    validate::positive("period", period)?;
+    let (frames, rows, cols, channels, samples) = data.dim();
+    let Some(shape) = manager.shape() else {
+        return Ok(ResultsTable::new());
+    };
+    validate::same_shape(&[rows, cols], &[shape.0, shape.1])?;
+    let w = omega(period);
+    let wf = time_domain::waveforms(samples, period, 1.0);
+
+    // measure each (ROI, frame) pair in parallel, keeping the row order
+    let rois = manager.select(group);
+    let results: Vec<Vec<ResultsRow>> = (0..rois.len() * frames)
+        .into_par_iter()
+        .map(|i| {
+            let roi = rois[i / frames];
+            let t = i % frames;
+            let frame = data.index_axis(Axis(0), t);
+            (0..channels)
+                .flat_map(|ch| {
+                    let stack = frame.index_axis(Axis(2), ch);
+                    let mut decay = vec![0.0; samples];
+                    let mut px: Vec<f64> = Vec::new();
+                    stack
+                        .lanes(Axis(2))
+                        .into_iter()
+                        .zip(roi.mask.iter())
+                        .filter(|&(_, &m)| m)
+                        .for_each(|(ln, _)| {
+                            let mut total = 0.0;
+                            ln.iter().zip(decay.iter_mut()).for_each(|(v, d)| {
+                                let vf = v.to_f64();
+                                *d += vf;
+                                total += vf;
+                            });
+                            px.push(total);
+                        });
+                    let lifetime = if decay.iter().sum::<f64>() == 0.0 {
+                        f64::NAN
+                    } else {
+                        let (g, s) = time_domain::curve_coordinates(ArrayView1::from(&decay), &wf);
+                        s / (w * g)
+                    };
+                    let mut rows = intensity_rows(roi, t, ch, px);
+                    rows.push(new_row(roi, t, ch, "lifetime", lifetime));
+                    rows
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(collect_table(results))
+}
+
+/// Flatten the per (ROI, frame) rows into a results table.
+fn collect_table(results: Vec<Vec<ResultsRow>>) -> ResultsTable {
+    let mut table = ResultsTable::new();
+    results.into_iter().flatten().for_each(|r| table.push(r));
+
+    table
+}
+
+/// Compute the mean, median and integrated intensity rows of the ROI pixels.
+fn intensity_rows(roi: &Roi, slice: usize, channel: usize, mut px: Vec<f64>) -> Vec<ResultsRow> {
+    let n = px.len();
+    let integrated = px.iter().sum::<f64>();
+    let mean = if n == 0 {
+        f64::NAN
+    } else {
+        integrated / n as f64
+    };
+    let median = if n == 0 {
+        f64::NAN
+    } else {
+        px.sort_unstable_by(|a, b| a.total_cmp(b));
+        if n.is_multiple_of(2) {
+            (px[n / 2 - 1] + px[n / 2]) / 2.0
+        } else {
+            px[n / 2]
+        }
+    };
+
+    vec![
+        new_row(roi, slice, channel, "mean", mean),
+        new_row(roi, slice, channel, "median", median),
+        new_row(roi, slice, channel, "integrated", integrated),
+    ]
+}
+
+/// Create a results table row for an ROI.
+fn new_row(roi: &Roi, slice: usize, channel: usize, measurement: &str, value: f64) -> ResultsRow {
+    ResultsRow {
+        roi: roi.name.clone(),
+        groups: roi.groups.clone(),
+        slice,
+        channel,
+        measurement: measurement.to_string(),
+        value,
+    }
+}
@@ -0,0 +1,269 @@
+use ndarray::{Array2, ArrayView3, Axis};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::roi::table::{ResultsRow, ResultsTable};
+use crate::traits::numeric::ToFloat64;
+
+/// A named region of interest (ROI) with group tags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Roi {
+    /// The unique ROI name.
+    pub name: String,
+    /// The 2-dimensional boolean mask, pixels set to `true` are in the ROI.
+    pub mask: Array2<bool>,
+    /// The group tags of the ROI (_e.g._ "nucleus" or "cytoplasm").
+    pub groups: Vec<String>,
+}
+
+impl Roi {
+    /// Whether the ROI is tagged with the group.
+    pub fn in_group(&self, group: &str) -> bool {
+        self.groups.iter().any(|g| g == group)
+    }
+
+    /// The number of pixels in the ROI.
+    pub fn area(&self) -> usize {
+        self.mask.iter().filter(|&&m| m).count()
+    }
+}
+
+/// A region of interest (ROI) manager.
+///
+/// # Description
+///
+/// The ROI manager holds named 2-dimensional ROI masks, each tagged with zero
+/// or more groups, in insertion order. All ROIs share the same shape. A
+/// measurement function can be applied to the pixels of every ROI in every
+/// slice of an image stack, producing a tidy `ResultsTable`. This mirrors the
+/// ImageJ ROI Manager workflow without a user interface.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Manager {
+    rois: Vec<Roi>,
+}
+
+impl Manager {
+    /// Create a new empty ROI manager.
+    pub fn new() -> Self {
+        Manager { rois: Vec::new() }
+    }
+
+    /// Add a named ROI to the manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The unique ROI name.
+    /// * `mask`: The 2-dimensional boolean mask, pixels set to `true` are in
+    ///   the ROI. Must match the shape of the ROIs already in the manager.
+    /// * `groups`: The group tags of the ROI.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If the ROI was added.
+    /// * `Err(ImgalError)`: If an ROI with the same name exists. If the mask
+    ///   shape does not match the existing ROI shape.
+    pub fn add(
+        &mut self,
+        name: &str,
+        mask: Array2<bool>,
+        groups: &[&str],
+    ) -> Result<(), ImgalError> {
+        if self.get(name).is_some() {
+            return Err(ImgalError::InvalidParameter {
+                param_name: "name",
+                reason: format!("an ROI named \"{}\" already exists", name),
+            });
+        }
+        if let Some(first) = self.rois.first() {
+            validate::same_shape(first.mask.shape(), mask.shape())?;
+        }
+        let mut tags: Vec<String> = Vec::with_capacity(groups.len());
+        groups.iter().for_each(|g| {
+            if !tags.iter().any(|t| t == g) {
+                tags.push(g.to_string());
+            }
+        });
+        self.rois.push(Roi {
+            name: name.to_string(),
+            mask,
+            groups: tags,
+        });
+
+        Ok(())
+    }
+
+    /// Get an ROI by name.
+    pub fn get(&self, name: &str) -> Option<&Roi> {
+        self.rois.iter().find(|r| r.name == name)
+    }
+
+    /// Remove an ROI by name, returning it if it was in the manager.
+    pub fn remove(&mut self, name: &str) -> Option<Roi> {
+        let i = self.rois.iter().position(|r| r.name == name)?;
+
+        Some(self.rois.remove(i))
+    }
+
+    /// Tag an ROI with a group, returning `false` if the ROI does not exist.
+    pub fn tag(&mut self, name: &str, group: &str) -> bool {
+        match self.rois.iter_mut().find(|r| r.name == name) {
+            Some(r) => {
+                if !r.in_group(group) {
+                    r.groups.push(group.to_string());
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a group tag from an ROI, returning `false` if the ROI does not
+    /// exist.
+    pub fn untag(&mut self, name: &str, group: &str) -> bool {
+        match self.rois.iter_mut().find(|r| r.name == name) {
+            Some(r) => {
+                r.groups.retain(|g| g != group);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The ROI names, in insertion order.
+    pub fn names(&self) -> Vec<&str> {
+        self.rois.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    /// The unique group tags of all ROIs, in order of first appearance.
+    pub fn groups(&self) -> Vec<&str> {
+        let mut groups: Vec<&str> = Vec::new();
+        self.rois
+            .iter()
+            .flat_map(|r| r.groups.iter())
+            .for_each(|g| {
+                if !groups.contains(&g.as_str()) {
+                    groups.push(g);
+                }
+            });
+
+        groups
+    }
+
+    /// The ROIs tagged with a group, in insertion order.
+    pub fn group(&self, group: &str) -> Vec<&Roi> {
+        self.rois.iter().filter(|r| r.in_group(group)).collect()
+    }
+
+    /// Iterate over the ROIs, in insertion order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Roi> {
+        self.rois.iter()
+    }
+
+    /// The number of ROIs in the manager.
+    pub fn len(&self) -> usize {
+        self.rois.len()
+    }
+
+    /// Whether the manager has no ROIs.
+    pub fn is_empty(&self) -> bool {
+        self.rois.is_empty()
+    }
+
+    /// The ROIs to measure, all ROIs if `group` is `None`.
+    pub(crate) fn select(&self, group: Option<&str>) -> Vec<&Roi> {
+        self.rois
+            .iter()
+            .filter(|r| group.is_none_or(|g| r.in_group(g)))
+            .collect()
+    }
+
+    /// The shared row and col shape of the ROIs, `None` if the manager is
+    /// empty.
+    pub fn shape(&self) -> Option<(usize, usize)> {
+        self.rois.first().map(|r| r.mask.dim())
+    }
+
+    /// Measure every ROI in every slice of an image stack.
+    ///
+    /// # Description
+    ///
+    /// This function gathers the pixel values of each ROI in each slice of the
+    /// stack and passes them to the `measure` function, which returns one or
+    /// more named measurements. ROIs and slices are measured in parallel and
+    /// the results table rows are ordered by ROI (insertion order), then slice,
+    /// then measurement. ROIs without pixels are measured with an empty slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `data`: The 3-dimensional image stack. Must match the ROI shape
+    ///   without the stack axis.
+    /// * `measure`: The measurement function, takes the ROI pixel values and
+    ///   returns (name, value) pairs.
+    /// * `group`: Only measure ROIs tagged with this group. If `None`, all ROIs
+    ///   are measured.
+    /// * `axis`: The stack (_e.g._ time or channel) axis, default = 2.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResultsTable)`: The tidy table of measurements.
+    /// * `Err(ImgalError)`: If axis is >= 3. If the image shape does not match
+    ///   the ROI shape.
+    pub fn measure<T, F, S>(
+        &self,
+        data: ArrayView3<T>,
+        measure: F,
+        group: Option<&str>,
+        axis: Option<usize>,
+    ) -> Result<ResultsTable, ImgalError>
+    where
+        T: ToFloat64,
+        F: Fn(&[f64]) -> Vec<(S, f64)> + Sync,
+        S: Into<String>,
+    {
+        // set optional parameters if needed
+        let a = axis.unwrap_or(2);
+
+        // check if axis and image shape are valid
+        validate::axis(a, 3)?;
+        let mut table = ResultsTable::new();
+        let Some((rows, cols)) = self.shape() else {
+            return Ok(table);
+        };
+        let mut shape = data.shape().to_vec();
+        shape.remove(a);
+        validate::same_shape(&shape, &[rows, cols])?;
+
+        // measure each (ROI, slice) pair in parallel, keeping the row order
+        let rois = self.select(group);
+        let slices = data.len_of(Axis(a));
+        let results: Vec<Vec<ResultsRow>> = (0..rois.len() * slices)
+            .into_par_iter()
+            .map(|i| {
+                let roi = rois[i / slices];
+                let sl = i % slices;
+                let plane = data.index_axis(Axis(a), sl);
+                let px: Vec<f64> = plane
+                    .iter()
+                    .zip(roi.mask.iter())
+                    .filter(|&(_, &m)| m)
+                    .map(|(v, _)| v.to_f64())
+                    .collect();
+                measure(&px)
+                    .into_iter()
+                    .map(|(name, value)| ResultsRow {
+                        roi: roi.name.clone(),
+                        groups: roi.groups.clone(),
+                        slice: sl,
+                        channel: 0,
+                        measurement: name.into(),
+                        value,
+                    })
+                    .collect()
+            })
+            .collect();
+        results.into_iter().flatten().for_each(|r| table.push(r));
+
+        Ok(table)
+    }
+}
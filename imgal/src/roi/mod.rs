@@ -0,0 +1,6 @@
+//! Region of interest (ROI) management and measurement functions.
+pub mod composite;
+pub mod manager;
+pub use manager::{Manager, Roi};
+pub mod table;
+pub use table::{ResultsRow, ResultsTable};
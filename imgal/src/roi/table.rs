@@ -0,0 +1,195 @@
+use std::fmt::Write;
+#[cfg(feature = "arrow")]
+use std::sync::Arc;
+
+#[cfg(feature = "arrow")]
+use arrow_array::builder::{ListBuilder, StringBuilder};
+#[cfg(feature = "arrow")]
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, UInt64Array};
+
+/// A single measurement of a tidy results table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultsRow {
+    /// The ROI name.
+    pub roi: String,
+    /// The group tags of the ROI.
+    pub groups: Vec<String>,
+    /// The index of the measured slice (_e.g._ time point) along the stack
+    /// axis.
+    pub slice: usize,
+    /// The index of the measured channel, 0 for single channel data.
+    pub channel: usize,
+    /// The measurement name.
+    pub measurement: String,
+    /// The measured value.
+    pub value: f64,
+}
+
+/// A tidy (_i.e._ long format) table of ROI measurements.
+///
+/// Each row holds one measurement of one ROI in one slice, mirroring the
+/// columns `roi`, `groups`, `slice`, `channel`, `measurement` and `value`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultsTable {
+    rows: Vec<ResultsRow>,
+}
+
+impl ResultsTable {
+    /// Create a new empty results table.
+    pub fn new() -> Self {
+        ResultsTable { rows: Vec::new() }
+    }
+
+    /// Append a row to the results table.
+    pub fn push(&mut self, row: ResultsRow) {
+        self.rows.push(row);
+    }
+
+    /// The rows of the results table.
+    pub fn rows(&self) -> &[ResultsRow] {
+        &self.rows
+    }
+
+    /// The number of rows in the results table.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the results table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Iterate over the rows of the results table.
+    pub fn iter(&self) -> std::slice::Iter<'_, ResultsRow> {
+        self.rows.iter()
+    }
+
+    /// Get the rows of a single ROI.
+    pub fn roi(&self, name: &str) -> Vec<&ResultsRow> {
+        self.rows.iter().filter(|r| r.roi == name).collect()
+    }
+
+    /// Get the values of a single measurement, in row order.
+    pub fn values(&self, measurement: &str) -> Vec<f64> {
+        self.rows
+            .iter()
+            .filter(|r| r.measurement == measurement)
+            .map(|r| r.value)
+            .collect()
+    }
+
+    /// Format the results table as comma separated values (CSV).
+    ///
+    /// # Description
+    ///
+    /// The first line holds the column names. Group tags are joined with `;`
+    /// and text fields containing commas, quotes or line breaks are quoted.
+    ///
+    /// # Returns
+    ///
+    /// * `String`: The CSV formatted results table.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("roi,groups,slice,channel,measurement,value\n");
+        self.rows.iter().for_each(|r| {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                csv_field(&r.roi),
+                csv_field(&r.groups.join(";")),
+                r.slice,
+                r.channel,
+                csv_field(&r.measurement),
+                r.value
+            );
+        });
+
+        csv
+    }
+
+    /// Convert the results table into an Apache Arrow record batch.
+    ///
+    /// # Description
+    ///
+    /// The record batch has the columns `roi` (`Utf8`), `groups` (`List` of
+    /// `Utf8`), `slice` and `channel` (`UInt64`), `measurement` (`Utf8`) and
+    /// `value` (`Float64`), in row order. The record batch can be exported
+    /// without copying through the Arrow C data interface (see
+    /// `arrow_array::ffi`).
+    ///
+    /// # Returns
+    ///
+    /// * `RecordBatch`: The results table with one row per measurement.
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(&self) -> RecordBatch {
+        let mut groups = ListBuilder::new(StringBuilder::new());
+        self.rows.iter().for_each(|r| {
+            r.groups
+                .iter()
+                .for_each(|g| groups.values().append_value(g));
+            groups.append(true);
+        });
+        let columns: Vec<(&str, ArrayRef)> = vec![
+            (
+                "roi",
+                Arc::new(StringArray::from_iter_values(
+                    self.rows.iter().map(|r| &r.roi),
+                )),
+            ),
+            ("groups", Arc::new(groups.finish())),
+            (
+                "slice",
+                Arc::new(UInt64Array::from_iter_values(
+                    self.rows.iter().map(|r| r.slice as u64),
+                )),
+            ),
+            (
+                "channel",
+                Arc::new(UInt64Array::from_iter_values(
+                    self.rows.iter().map(|r| r.channel as u64),
+                )),
+            ),
+            (
+                "measurement",
+                Arc::new(StringArray::from_iter_values(
+                    self.rows.iter().map(|r| &r.measurement),
+                )),
+            ),
+            (
+                "value",
+                Arc::new(Float64Array::from_iter_values(
+                    self.rows.iter().map(|r| r.value),
+                )),
+            ),
+        ];
+
+        RecordBatch::try_from_iter(columns).expect("The results columns have one entry per row.")
+    }
+}
+
+impl IntoIterator for ResultsTable {
+    type Item = ResultsRow;
+    type IntoIter = std::vec::IntoIter<ResultsRow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ResultsTable {
+    type Item = &'a ResultsRow;
+    type IntoIter = std::slice::Iter<'a, ResultsRow>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
+
+/// Quote a CSV text field if needed.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
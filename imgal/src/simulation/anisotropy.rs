@@ -0,0 +1,297 @@
+use ndarray::{Array1, Array3, Zip};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::filter::fft_convolve_1d;
+use crate::simulation::decay::{self, FRACTIONS_SUM_TOLERANCE};
+use crate::simulation::instrument::{self, GaussianIrf};
+use crate::statistics::sum;
+
+/// Time-resolved anisotropy decay parameters.
+///
+/// The isotropic decay of the fluorophore is described by its lifetimes and
+/// fractional intensities (see `simulation::decay::ideal_exponential_1d`), its
+/// rotational diffusion by the fundamental anisotropy and rotational
+/// correlation times (see `anisotropy_decay_1d`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnisotropyDecay<'a> {
+    /// An array of lifetimes.
+    pub taus: &'a [f64],
+    /// An array of fractional intensities for each tau in the `taus` array.
+    pub fractions: &'a [f64],
+    /// The total intensity count (_e.g._ photon count) of the isotropic decay
+    /// curve.
+    pub total_counts: f64,
+    /// The fundamental (_i.e._ initial) anisotropy, must be within [-0.2, 0.4].
+    pub r0: f64,
+    /// An array of rotational correlation times.
+    pub rotational_times: &'a [f64],
+    /// An array of fractional amplitudes for each rotational time in the
+    /// `rotational_times` array.
+    pub rotational_fractions: &'a [f64],
+}
+
+/// Simulate a 1-dimensional anisotropy decay curve.
+///
+/// # Description
+///
+/// This function generates the time-resolved fluorescence anisotropy r(t) of a
+/// fluorophore with one or more rotational correlation times (theta), each
+/// weighted by a fractional amplitude:
+///
+/// ```text
+/// r(t) = r₀ × Σⱼ βⱼ × exp(-t/θⱼ)
+/// ```
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `r0`: The fundamental (_i.e._ initial) anisotropy, must be within
+///   [-0.2, 0.4].
+/// * `rotational_times`: An array of rotational correlation times. Rotational
+///   times set to 0.0 will be skipped.
+/// * `rotational_fractions`: An array of fractional amplitudes for each
+///   rotational time in the `rotational_times` array. Must be the same length as
+///   the `rotational_times` array and sum to 1.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The 1-dimensional anisotropy decay curve.
+/// * `Err(ImgalError)`: If rotational times and fractions array lengths do not
+///   match. If rotational fractions do not sum to 1.0. If r0 is outside of
+///   [-0.2, 0.4]. If samples is 0 or period is <= 0.0.
+pub fn anisotropy_decay_1d(
+    samples: usize,
+    period: f64,
+    r0: f64,
+    rotational_times: &[f64],
+    rotational_fractions: &[f64],
+) -> Result<Vec<f64>, ImgalError> {
+    // check samples, period, r0 and rotational array lengths
+    validate::at_least("samples", samples as f64, 1.0)?;
+    validate::positive("period", period)?;
+    validate::in_range("r0", r0, -0.2, 0.4)?;
+    validate::same_length(rotational_times.len(), rotational_fractions.len())?;
+
+    // check rotational fractions sum to 1.0, normalize round off with a warning
+    let fs = sum(rotational_fractions);
+    if (fs - 1.0).abs() > FRACTIONS_SUM_TOLERANCE {
+        return Err(ImgalError::InvalidSum {
+            expected: 1.0,
+            got: fs,
+        });
+    }
+    if fs != 1.0 {
+        warning::warn(Warning::FractionsNormalized { got: fs });
+    }
+
+    // compute the anisotropy decay curve
+    let mut r_arr = vec![0.0; samples];
+    let time_arr = Array1::linspace(0.0, period, samples);
+    rotational_times
+        .iter()
+        .zip(rotational_fractions.iter())
+        .filter(|&(&th, &b)| th != 0.0 && b != 0.0)
+        .for_each(|(th, b)| {
+            let amp = r0 * b / fs;
+            Zip::from(&mut r_arr).and(&time_arr).for_each(|r, t| {
+                *r += amp * (-t / th).exp();
+            });
+        });
+
+    Ok(r_arr)
+}
+
+/// Simulate 1-dimensional Gaussian IRF convolved parallel and perpendicular
+/// polarization decay curves.
+///
+/// # Description
+///
+/// This function generates the parallel and perpendicular polarization decay
+/// channels of a time-resolved anisotropy measurement (see
+/// `ideal_anisotropy_1d`) and convolves each channel with a Gaussian instrument
+/// response function (IRF).
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `params`: The isotropic decay and rotational diffusion parameters.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
+/// * `g_factor`: The instrument G-factor (_i.e._ the perpendicular to parallel
+///   detection sensitivity ratio), default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, Vec<f64>))`: The Gaussian IRF convolved parallel and
+///   perpendicular decay curves respectively.
+/// * `Err(ImgalError)`: If the decay or anisotropy parameters are invalid. If
+///   g_factor is <= 0.0.
+pub fn gaussian_anisotropy_1d(
+    samples: usize,
+    period: f64,
+    params: AnisotropyDecay,
+    irf: GaussianIrf,
+    g_factor: Option<f64>,
+) -> Result<(Vec<f64>, Vec<f64>), ImgalError> {
+    let irf = instrument::gaussian_irf_1d(samples, period, irf.center, irf.width)?;
+
+    irf_anisotropy_1d(&irf, samples, period, params, g_factor)
+}
+
+/// Simulate 3-dimensional Gaussian IRF convolved parallel and perpendicular
+/// polarization decay images.
+///
+/// # Description
+///
+/// This function generates Gaussian IRF convolved parallel and perpendicular
+/// polarization decay curves (see `gaussian_anisotropy_1d`) and broadcasts
+/// each into a 3-dimensional image.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `params`: The isotropic decay and rotational diffusion parameters.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
+/// * `g_factor`: The instrument G-factor, default = 1.0.
+/// * `shape`: The row and col shape to broadcast the decay curves into.
+///
+/// # Returns
+///
+/// * `Ok((Array3<f64>, Array3<f64>))`: The Gaussian IRF convolved parallel and
+///   perpendicular decay images respectively.
+/// * `Err(ImgalError)`: If the decay or anisotropy parameters are invalid. If
+///   g_factor is <= 0.0.
+pub fn gaussian_anisotropy_3d(
+    samples: usize,
+    period: f64,
+    params: AnisotropyDecay,
+    irf: GaussianIrf,
+    g_factor: Option<f64>,
+    shape: (usize, usize),
+) -> Result<(Array3<f64>, Array3<f64>), ImgalError> {
+    // create 1-dimensional polarized decay curves and broadcast
+    let (par, perp) = gaussian_anisotropy_1d(samples, period, params, irf, g_factor)?;
+    let dims = (shape.0, shape.1, samples);
+    let par = Array1::from_vec(par).broadcast(dims).unwrap().to_owned();
+    let perp = Array1::from_vec(perp).broadcast(dims).unwrap().to_owned();
+
+    Ok((par, perp))
+}
+
+/// Simulate ideal 1-dimensional parallel and perpendicular polarization decay
+/// curves.
+///
+/// # Description
+///
+/// This function generates the parallel (I∥) and perpendicular (I⊥)
+/// polarization decay channels of a time-resolved anisotropy measurement from
+/// the isotropic decay I(t) (see `simulation::decay::ideal_exponential_1d`) and
+/// the anisotropy decay r(t) (see `anisotropy_decay_1d`):
+///
+/// ```text
+/// I∥(t) = I(t) × (1 + 2r(t)) / 3
+/// I⊥(t) = I(t) × (1 - r(t)) / (3G)
+/// ```
+///
+/// where G is the instrument G-factor, such that the anisotropy is recovered
+/// with r(t) = (I∥ - G×I⊥) / (I∥ + 2G×I⊥).
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `params`: The isotropic decay and rotational diffusion parameters.
+/// * `g_factor`: The instrument G-factor (_i.e._ the perpendicular to parallel
+///   detection sensitivity ratio), default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, Vec<f64>))`: The parallel and perpendicular decay curves
+///   respectively.
+/// * `Err(ImgalError)`: If the decay or anisotropy parameters are invalid. If
+///   g_factor is <= 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1007/978-0-387-46312-4_11>
+pub fn ideal_anisotropy_1d(
+    samples: usize,
+    period: f64,
+    params: AnisotropyDecay,
+    g_factor: Option<f64>,
+) -> Result<(Vec<f64>, Vec<f64>), ImgalError> {
+    // set optional parameters if needed
+    let g = g_factor.unwrap_or(1.0);
+    validate::positive("g_factor", g)?;
+
+    // create the isotropic and anisotropy decay curves
+    let i_arr = decay::ideal_exponential_1d(
+        samples,
+        period,
+        params.taus,
+        params.fractions,
+        params.total_counts,
+    )?;
+    let r_arr = anisotropy_decay_1d(
+        samples,
+        period,
+        params.r0,
+        params.rotational_times,
+        params.rotational_fractions,
+    )?;
+
+    // split the isotropic decay into polarized channels
+    let mut par = vec![0.0; samples];
+    let mut perp = vec![0.0; samples];
+    Zip::from(&mut par)
+        .and(&mut perp)
+        .and(&i_arr)
+        .and(&r_arr)
+        .for_each(|pa, pe, &i, &r| {
+            *pa = i * (1.0 + 2.0 * r) / 3.0;
+            *pe = i * (1.0 - r) / (3.0 * g);
+        });
+
+    Ok((par, perp))
+}
+
+/// Simulate 1-dimensional IRF convolved parallel and perpendicular
+/// polarization decay curves.
+///
+/// # Description
+///
+/// This function generates the parallel and perpendicular polarization decay
+/// channels of a time-resolved anisotropy measurement (see
+/// `ideal_anisotropy_1d`) and convolves each channel with the input instrument
+/// response function (IRF).
+///
+/// # Arguments
+///
+/// * `irf`: The IRF as a 1-dimensonal array.
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `params`: The isotropic decay and rotational diffusion parameters.
+/// * `g_factor`: The instrument G-factor, default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, Vec<f64>))`: The IRF convolved parallel and perpendicular
+///   decay curves respectively.
+/// * `Err(ImgalError)`: If the decay or anisotropy parameters are invalid. If
+///   g_factor is <= 0.0.
+pub fn irf_anisotropy_1d(
+    irf: &[f64],
+    samples: usize,
+    period: f64,
+    params: AnisotropyDecay,
+    g_factor: Option<f64>,
+) -> Result<(Vec<f64>, Vec<f64>), ImgalError> {
+    // create ideal polarized decay curves and convolve each with the irf
+    let (par, perp) = ideal_anisotropy_1d(samples, period, params, g_factor)?;
+
+    Ok((fft_convolve_1d(&par, irf), fft_convolve_1d(&perp, irf)))
+}
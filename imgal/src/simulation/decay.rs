@@ -1,9 +1,145 @@
-use ndarray::{Array1, Array3, Zip};
+use ndarray::{Array1, Array3, Axis, Zip};
+use rand::distr::{Distribution, Uniform, weighted::WeightedIndex};
+use rayon::prelude::*;
 
-use crate::error::ArrayError;
-use crate::filter::fft_convolve_1d;
-use crate::simulation::instrument;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::error::warning::{self, Warning};
+use crate::filter::{fft_circular_convolve_1d, fft_convolve_1d};
+use crate::simulation::instrument::{self, GaussianIrf};
+use crate::simulation::rng::Seed;
 use crate::statistics::sum;
+use crate::traits::numeric::ToFloat64;
+
+/// Maximum deviation of the fractions sum from 1.0 before it is an error.
+pub(crate) const FRACTIONS_SUM_TOLERANCE: f64 = 1e-6;
+
+/// Number of efficiency nodes used to integrate a Gaussian FRET efficiency
+/// distribution.
+const FRET_GAUSSIAN_NODES: usize = 64;
+
+/// FRET efficiency distributions of the donor population in the presence of
+/// acceptor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FretEfficiency {
+    /// All FRET donors share a single efficiency.
+    Single(f64),
+    /// Donor efficiencies follow a Gaussian distribution truncated to [0, 1)
+    /// with the given mean and standard deviation.
+    Gaussian { mean: f64, sigma: f64 },
+    /// Donors belong to one of two populations with their own efficiency,
+    /// `second_fraction` is the fraction of FRET donors in the second
+    /// population.
+    TwoPopulation {
+        first: f64,
+        second: f64,
+        second_fraction: f64,
+    },
+}
+
+/// FRET donor population parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fret {
+    /// The unquenched donor lifetime.
+    pub donor_tau: f64,
+    /// The FRET efficiency distribution of the donors, all efficiencies must
+    /// be in [0, 1).
+    pub efficiency: FretEfficiency,
+    /// The fraction of donors that do not undergo FRET, must be in [0, 1].
+    pub donor_only_fraction: f64,
+}
+
+/// Simulate a 1-dimensional FRET donor decay curve.
+///
+/// # Description
+///
+/// This function generates an ideal donor decay curve in the presence of
+/// acceptor. Donors undergoing Förster resonance energy transfer (FRET) with
+/// efficiency `E` are quenched to a lifetime of `τ_DA = τ_D × (1 - E)`, while
+/// a `donor_only_fraction` of donors (_e.g._ without an acceptor nearby) keep
+/// the unquenched donor lifetime. The decay is the amplitude (_i.e._ molecule)
+/// weighted sum over the donor population:
+///
+/// ```text
+/// I(t) = x_D × exp(-t/τ_D) + (1 - x_D) × Σₖ pₖ × exp(-t/(τ_D × (1 - Eₖ)))
+/// ```
+///
+/// where `x_D` is the donor only fraction and `pₖ` is the probability of
+/// efficiency `Eₖ`. Sweeping the efficiency with a fixed donor only fraction
+/// traces the FRET trajectory in phasor space.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `fret`: The donor lifetime, FRET efficiency distribution and donor only
+///    fraction of the donor population.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The 1-dimensional FRET donor decay curve.
+/// * `Err(ImgalError)`: If samples is 0 or period is <= 0.0. If donor_tau is
+///    <= 0.0. If an efficiency, fraction or sigma is out of range. If
+///    total_counts is < 0.0.
+pub fn fret_exponential_1d(
+    samples: usize,
+    period: f64,
+    fret: Fret,
+    total_counts: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    validate::at_least("samples", samples as f64, 1.0)?;
+    validate::positive("period", period)?;
+    validate::at_least("total_counts", total_counts, 0.0)?;
+    let (taus, alphas) = fret_components(fret)?;
+
+    Ok(multi_exponential_1d(
+        samples,
+        period,
+        &taus,
+        &alphas,
+        total_counts,
+        false,
+    ))
+}
+
+/// Simulate a 3-dimensional FRET donor decay curve.
+///
+/// # Description
+///
+/// This function generates an ideal FRET donor decay curve with
+/// `fret_exponential_1d` and broadcasts it into a 3-dimensional image.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `fret`: The donor lifetime, FRET efficiency distribution and donor only
+///    fraction of the donor population.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+/// * `shape`: The row and col shape to broadcast the decay curve into.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The 3-dimensional FRET donor decay curve.
+/// * `Err(ImgalError)`: If samples is 0 or period is <= 0.0. If donor_tau is
+///    <= 0.0. If an efficiency, fraction or sigma is out of range. If
+///    total_counts is < 0.0.
+pub fn fret_exponential_3d(
+    samples: usize,
+    period: f64,
+    fret: Fret,
+    total_counts: f64,
+    shape: (usize, usize),
+) -> Result<Array3<f64>, ImgalError> {
+    let i_arr = fret_exponential_1d(samples, period, fret, total_counts)?;
+    let i_arr = Array1::from_vec(i_arr);
+    let dims = (shape.0, shape.1, samples);
+
+    Ok(i_arr.broadcast(dims).unwrap().to_owned())
+}
 
 /// Simulate a 1-dimensional Gaussian IRF convolved monoexponential or
 /// multiexponential decay curve.
@@ -30,28 +166,31 @@ use crate::statistics::sum;
 ///    must have the same length. Tau values set to 0.0 will be skipped.
 /// * `fractions`: An array of fractional intensities for each tau in the `taus`
 ///    array. The `fractions` array must be the same length as the `taus` array
-///    and sum to 1.0. Fraction values set to 0.0 will be skipped.
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
 /// * `total_counts`: The total intensity count (_e.g._ photon count) of the
 ///    decay curve.
-/// * `irf_center`: The temporal position of the IRF peak within the time range.
-/// * `irf_width`: The full width at half maximum (FWHM) of the IRF.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
 ///
 /// # Returns
 ///
 /// * `Ok(Vec<f64>)`: The 1-dimensonal Gaussian IRF convolved monoexponential
 ///    or multiexponential decay curve.
-/// * `Err(ArrayError)`: If taus and fractions array lengths do not match. If
-///    fractions array does not sum to 1.0.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If irf_center is
+///    outside of [0.0, period] or irf_width is <= 0.0.
 pub fn gaussian_exponential_1d(
     samples: usize,
     period: f64,
     taus: &[f64],
     fractions: &[f64],
     total_counts: f64,
-    irf_center: f64,
-    irf_width: f64,
-) -> Result<Vec<f64>, ArrayError> {
-    let irf = instrument::gaussian_irf_1d(samples, period, irf_center, irf_width);
+    irf: GaussianIrf,
+) -> Result<Vec<f64>, ImgalError> {
+    validate::in_range("irf_center", irf.center, 0.0, period)?;
+    let irf = instrument::gaussian_irf_1d(samples, period, irf.center, irf.width)?;
     let i_arr = ideal_exponential_1d(samples, period, taus, fractions, total_counts)?;
 
     Ok(fft_convolve_1d(&i_arr, &irf))
@@ -82,39 +221,230 @@ pub fn gaussian_exponential_1d(
 ///    must have the same length. Tau values set to 0.0 will be skipped.
 /// * `fractions`: An array of fractional intensities for each tau in the `taus`
 ///    array. The `fractions` array must be the same length as the `taus` array
-///    and sum to 1.0. Fraction values set to 0.0 will be skipped.
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
 /// * `total_counts`: The total intensity count (_e.g._ photon count) of the
 ///    decay curve.
-/// * `irf_center`: The temporal position of the IRF peak within the time range.
-/// * `irf_width`: The full width at half maximum (FWHM) of the IRF.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
 /// * `shape`: The row and col shape to broadcast the decay curve into.
 ///
 /// # Returns
 ///
 /// * `Ok(Array3<f64>)`: The 3-dimensional Gaussian IRF convolved monoexponential
 ///    or multiexponential decay curve.
-/// * `Err(ArrayError)`: If taus and fractions array lengths do not match. If
-///    fractions array does not sum to 1.0.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If irf_center is
+///    outside of [0.0, period] or irf_width is <= 0.0.
 pub fn gaussian_exponential_3d(
     samples: usize,
     period: f64,
     taus: &[f64],
     fractions: &[f64],
     total_counts: f64,
-    irf_center: f64,
-    irf_width: f64,
+    irf: GaussianIrf,
     shape: (usize, usize),
-) -> Result<Array3<f64>, ArrayError> {
+) -> Result<Array3<f64>, ImgalError> {
     // create 1-dimensional gaussian IRF convolved curve and broadcast
-    let i_arr = gaussian_exponential_1d(
-        samples,
-        period,
-        taus,
-        fractions,
-        total_counts,
-        irf_center,
-        irf_width,
-    )?;
+    let i_arr = gaussian_exponential_1d(samples, period, taus, fractions, total_counts, irf)?;
+    let i_arr = Array1::from_vec(i_arr);
+    let dims = (shape.0, shape.1, samples);
+
+    Ok(i_arr.broadcast(dims).unwrap().to_owned())
+}
+
+/// Simulate a 1-dimensional Gaussian IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// # Description
+///
+/// This function generates a 1-dimensonal Gaussian instrument response function
+/// (IRF) circularly convolved periodic monoexponential or multiexponential decay
+/// curve. Unlike the linear convolution used by `gaussian_exponential_1d`, the
+/// decay is treated as periodic: fluorescence that has not decayed by the end of
+/// the period (_i.e._ when tau is comparable to the period) is carried over into
+/// the next period and the IRF is circularly convolved, so IRF signal shifted
+/// past the end of the period wraps around to the start. This models the
+/// incomplete decay observed with high repetition rate pulsed lasers:
+///
+/// ```text
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+/// ```
+///
+/// where T is the period.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `taus`: An array of lifetimes. For a monoexponential decay curve use a
+///    single tau value and a fractional intensity of 1.0. For a
+///    multiexponential decay curve use two or more tau values, matched with
+///    their respective fractional intensity. The `taus` and `fractions` arrays
+///    must have the same length. Tau values set to 0.0 will be skipped.
+/// * `fractions`: An array of fractional intensities for each tau in the `taus`
+///    array. The `fractions` array must be the same length as the `taus` array
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The 1-dimensonal Gaussian IRF circularly convolved periodic
+///    monoexponential or multiexponential decay curve.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If irf_center is
+///    outside of [0.0, period] or irf_width is <= 0.0.
+pub fn gaussian_exponential_periodic_1d(
+    samples: usize,
+    period: f64,
+    taus: &[f64],
+    fractions: &[f64],
+    total_counts: f64,
+    irf: GaussianIrf,
+) -> Result<Vec<f64>, ImgalError> {
+    validate::in_range("irf_center", irf.center, 0.0, period)?;
+    let irf = instrument::gaussian_irf_1d(samples, period, irf.center, irf.width)?;
+    let i_arr = ideal_exponential_periodic_1d(samples, period, taus, fractions, total_counts)?;
+
+    Ok(fft_circular_convolve_1d(&i_arr, &irf))
+}
+
+/// Simulate a 3-dimensional Gaussian IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// # Description
+///
+/// This function generates a 3-dimensonal Gaussian instrument response function
+/// (IRF) circularly convolved periodic monoexponential or multiexponential decay
+/// curve. See `gaussian_exponential_periodic_1d` for details on the periodic
+/// decay model.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `taus`: An array of lifetimes. For a monoexponential decay curve use a
+///    single tau value and a fractional intensity of 1.0. For a
+///    multiexponential decay curve use two or more tau values, matched with
+///    their respective fractional intensity. The `taus` and `fractions` arrays
+///    must have the same length. Tau values set to 0.0 will be skipped.
+/// * `fractions`: An array of fractional intensities for each tau in the `taus`
+///    array. The `fractions` array must be the same length as the `taus` array
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
+/// * `shape`: The row and col shape to broadcast the decay curve into.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The 3-dimensional Gaussian IRF circularly convolved
+///    periodic monoexponential or multiexponential decay curve.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If irf_center is
+///    outside of [0.0, period] or irf_width is <= 0.0.
+pub fn gaussian_exponential_periodic_3d(
+    samples: usize,
+    period: f64,
+    taus: &[f64],
+    fractions: &[f64],
+    total_counts: f64,
+    irf: GaussianIrf,
+    shape: (usize, usize),
+) -> Result<Array3<f64>, ImgalError> {
+    // create 1-dimensional gaussian IRF convolved periodic curve and broadcast
+    let i_arr =
+        gaussian_exponential_periodic_1d(samples, period, taus, fractions, total_counts, irf)?;
+    let i_arr = Array1::from_vec(i_arr);
+    let dims = (shape.0, shape.1, samples);
+
+    Ok(i_arr.broadcast(dims).unwrap().to_owned())
+}
+
+/// Simulate a 1-dimensional Gaussian IRF convolved FRET donor decay curve.
+///
+/// # Description
+///
+/// This function generates an ideal FRET donor decay curve with
+/// `fret_exponential_1d` and convolves it with a Gaussian instrument response
+/// function (IRF).
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `fret`: The donor lifetime, FRET efficiency distribution and donor only
+///    fraction of the donor population.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The 1-dimensional Gaussian IRF convolved FRET donor decay
+///    curve.
+/// * `Err(ImgalError)`: If samples is 0 or period is <= 0.0. If donor_tau is
+///    <= 0.0. If an efficiency, fraction or sigma is out of range. If
+///    total_counts is < 0.0. If irf_center is outside of [0.0, period] or
+///    irf_width is <= 0.0.
+pub fn gaussian_fret_exponential_1d(
+    samples: usize,
+    period: f64,
+    fret: Fret,
+    total_counts: f64,
+    irf: GaussianIrf,
+) -> Result<Vec<f64>, ImgalError> {
+    validate::in_range("irf_center", irf.center, 0.0, period)?;
+    let irf = instrument::gaussian_irf_1d(samples, period, irf.center, irf.width)?;
+    let i_arr = fret_exponential_1d(samples, period, fret, total_counts)?;
+
+    Ok(fft_convolve_1d(&i_arr, &irf))
+}
+
+/// Simulate a 3-dimensional Gaussian IRF convolved FRET donor decay curve.
+///
+/// # Description
+///
+/// This function generates a Gaussian IRF convolved FRET donor decay curve with
+/// `gaussian_fret_exponential_1d` and broadcasts it into a 3-dimensional image.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `fret`: The donor lifetime, FRET efficiency distribution and donor only
+///    fraction of the donor population.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+/// * `irf`: The Gaussian IRF center and full width at half maximum (FWHM).
+/// * `shape`: The row and col shape to broadcast the decay curve into.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The 3-dimensional Gaussian IRF convolved FRET donor
+///    decay curve.
+/// * `Err(ImgalError)`: If samples is 0 or period is <= 0.0. If donor_tau is
+///    <= 0.0. If an efficiency, fraction or sigma is out of range. If
+///    total_counts is < 0.0. If irf_center is outside of [0.0, period] or
+///    irf_width is <= 0.0.
+pub fn gaussian_fret_exponential_3d(
+    samples: usize,
+    period: f64,
+    fret: Fret,
+    total_counts: f64,
+    irf: GaussianIrf,
+    shape: (usize, usize),
+) -> Result<Array3<f64>, ImgalError> {
+    let i_arr = gaussian_fret_exponential_1d(samples, period, fret, total_counts, irf)?;
     let i_arr = Array1::from_vec(i_arr);
     let dims = (shape.0, shape.1, samples);
 
@@ -148,7 +478,8 @@ pub fn gaussian_exponential_3d(
 ///    must have the same length. Tau values set to 0.0 will be skipped.
 /// * `fractions`: An array of fractional intensities for each tau in the `taus`
 ///    array. The `fractions` array must be the same length as the `taus` array
-///    and sum to 1.0. Fraction values set to 0.0 will be skipped.
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
 /// * `total_counts`: The total intensity count (_e.g._ photon count) of the
 ///    decay curve.
 ///
@@ -156,8 +487,10 @@ pub fn gaussian_exponential_3d(
 ///
 /// * `Ok(Vec<f64>)`: The 1-dimensonal monoexponential or multiexponential
 ///    decay curve.
-/// * `Err(ArrayError)`: If taus and fractions array lengths do not match. If
-///    fractions array does not sum to 1.0.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0.
 ///
 /// # Reference
 ///
@@ -168,49 +501,8 @@ pub fn ideal_exponential_1d(
     taus: &[f64],
     fractions: &[f64],
     total_counts: f64,
-) -> Result<Vec<f64>, ArrayError> {
-    // check taus and fractions array lengths
-    let tl = taus.len();
-    let fl = fractions.len();
-    if tl != fl {
-        return Err(ArrayError::MismatchedArrayLengths {
-            a_arr_len: tl,
-            b_arr_len: fl,
-        });
-    }
-
-    // create fractions array and check sum to 1.0
-    let fs = sum(fractions);
-    if fs != 1.0 {
-        return Err(ArrayError::InvalidSum {
-            expected: 1.0,
-            got: fs,
-        });
-    }
-
-    // create taus array and compute pre-exponential factors
-    let frac_arr = Array1::from_vec(fractions.to_vec());
-    let taus_arr = Array1::from_vec(taus.to_vec());
-    let alph_arr = &frac_arr / &taus_arr;
-
-    // create the time array and compute the intensity decay curve
-    let mut i_arr = vec![0.0; samples];
-    let time_arr = Array1::linspace(0.0, period, samples);
-    alph_arr
-        .iter()
-        .zip(taus_arr.iter())
-        .filter(|&(&al, &ta)| al != 0.0 && ta != 0.0)
-        .for_each(|(al, ta)| {
-            Zip::from(&mut i_arr).and(&time_arr).for_each(|i, t| {
-                *i += al * (-t / ta).exp();
-            });
-        });
-
-    // scale the histogram to total_counts
-    let scale = total_counts / sum(&i_arr);
-    i_arr.iter_mut().for_each(|v| *v *= scale);
-
-    Ok(i_arr)
+) -> Result<Vec<f64>, ImgalError> {
+    exponential_decay_1d(samples, period, taus, fractions, total_counts, false)
 }
 
 /// Simulate an ideal 3-dimensional monoexponential or multiexponential decay
@@ -240,7 +532,8 @@ pub fn ideal_exponential_1d(
 ///    must have the same length. Tau values set to 0.0 will be skipped.
 /// * `fractions`: An array of fractional intensities for each tau in the `taus`
 ///    array. The `fractions` array must be the same length as the `taus` array
-///    and sum to 1.0. Fraction values set to 0.0 will be skipped.
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
 /// * `total_counts`: The total intensity count (_e.g._ photon count) of the
 ///    decay curve.
 /// * `shape`: The row and col shape to broadcast the decay curve into.
@@ -249,8 +542,10 @@ pub fn ideal_exponential_1d(
 ///
 /// * `Ok(Array3<f64>)`: The 3-dimensonal monoexponential or multiexponential
 ///    decay curve.
-/// * `Err(ArrayError)`: If taus and fractions array lengths do not match. If
-///    fractions array does not sum to 1.0.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0.
 ///
 /// # Reference
 ///
@@ -262,7 +557,7 @@ pub fn ideal_exponential_3d(
     fractions: &[f64],
     total_counts: f64,
     shape: (usize, usize),
-) -> Result<Array3<f64>, ArrayError> {
+) -> Result<Array3<f64>, ImgalError> {
     // create 1-dimensional decay curve and broadcast
     let i_arr = ideal_exponential_1d(samples, period, taus, fractions, total_counts)?;
     let i_arr = Array1::from_vec(i_arr);
@@ -271,6 +566,61 @@ pub fn ideal_exponential_3d(
     Ok(i_arr.broadcast(dims).unwrap().to_owned())
 }
 
+/// Simulate an ideal 1-dimensional periodic monoexponential or multiexponential
+/// decay curve.
+///
+/// # Description
+///
+/// This function generates a 1-dimensonal ideal exponential decay curve that
+/// includes the incomplete decay tails of all previous excitation periods.
+/// Each exponential component is characterized by a lifetime (tau) and
+/// fractional intensity, and the tail of each component is accumulated with
+/// the geometric series of its decay across periods:
+///
+/// ```text
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+/// ```
+///
+/// where αᵢ are the pre-exponential factors derived from the fractional
+/// intensities and lifetimes, and T is the period. The curve is sampled at
+/// `t = i × T / samples`, so the bin following the last bin is the first bin of
+/// the next period. When τᵢ is much smaller than T the tails vanish and the
+/// result is a plain exponential decay on this time grid.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `taus`: An array of lifetimes. For a monoexponential decay curve use a
+///    single tau value and a fractional intensity of 1.0. For a
+///    multiexponential decay curve use two or more tau values, matched with
+///    their respective fractional intensity. The `taus` and `fractions` arrays
+///    must have the same length. Tau values set to 0.0 will be skipped.
+/// * `fractions`: An array of fractional intensities for each tau in the `taus`
+///    array. The `fractions` array must be the same length as the `taus` array
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The 1-dimensonal periodic monoexponential or
+///    multiexponential decay curve.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0.
+pub fn ideal_exponential_periodic_1d(
+    samples: usize,
+    period: f64,
+    taus: &[f64],
+    fractions: &[f64],
+    total_counts: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    exponential_decay_1d(samples, period, taus, fractions, total_counts, true)
+}
+
 /// Simulate a 1-dimensional IRF convolved monoexponential or multiexponential
 /// decay curve.
 ///
@@ -297,7 +647,8 @@ pub fn ideal_exponential_3d(
 ///    must have the same length. Tau values set to 0.0 will be skipped.
 /// * `fractions`: An array of fractional intensities for each tau in the `taus`
 ///    array. The `fractions` array must be the same length as the `taus` array
-///    and sum to 1.0. Fraction values set to 0.0 will be skipped.
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
 /// * `total_counts`: The total intensity count (_e.g._ photon count) of the
 ///    decay curve.
 ///
@@ -305,8 +656,11 @@ pub fn ideal_exponential_3d(
 ///
 /// * `Ok(Vec<f64>)`: The 1-dimensional IRF convolved monoexponential or
 ///    multiexponential decay curve.
-/// * `Err(ArrayError)`: If taus and fractions array lengths do not match. If
-///    fractions array does not sum to 1.0.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If the IRF is empty, not
+///    finite or does not sum to > 0.0.
 pub fn irf_exponential_1d(
     irf: &[f64],
     samples: usize,
@@ -314,8 +668,9 @@ pub fn irf_exponential_1d(
     taus: &[f64],
     fractions: &[f64],
     total_counts: f64,
-) -> Result<Vec<f64>, ArrayError> {
+) -> Result<Vec<f64>, ImgalError> {
     // create ideal decay curve and convolve with input irf
+    validate_irf(irf)?;
     let i_arr = ideal_exponential_1d(samples, period, taus, fractions, total_counts)?;
 
     Ok(fft_convolve_1d(&i_arr, irf))
@@ -347,7 +702,8 @@ pub fn irf_exponential_1d(
 ///    must have the same length. Tau values set to 0.0 will be skipped.
 /// * `fractions`: An array of fractional intensities for each tau in the `taus`
 ///    array. The `fractions` array must be the same length as the `taus` array
-///    and sum to 1.0. Fraction values set to 0.0 will be skipped.
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
 /// * `total_counts`: The total intensity count (_e.g._ photon count) of the
 ///    decay curve.
 /// * `shape`: The row and col shape to broadcast the decay curve into.
@@ -356,8 +712,11 @@ pub fn irf_exponential_1d(
 ///
 /// * `Ok(Array3<f64>)`: The 3-dimensional IRF convolved monoexponential or
 ///    multiexponential decay curve.
-/// * `Err(ArrayError)`: If taus and fractions array lengths do not match. If
-///    fractions array does not sum to 1.0.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If the IRF is empty, not
+///    finite or does not sum to > 0.0.
 pub fn irf_exponential_3d(
     irf: &[f64],
     samples: usize,
@@ -366,7 +725,7 @@ pub fn irf_exponential_3d(
     fractions: &[f64],
     total_counts: f64,
     shape: (usize, usize),
-) -> Result<Array3<f64>, ArrayError> {
+) -> Result<Array3<f64>, ImgalError> {
     // create 1-dimensional IRF convolved decay curve to broadcast
     let i_arr = irf_exponential_1d(irf, samples, period, taus, fractions, total_counts)?;
     let i_arr = Array1::from_vec(i_arr);
@@ -374,3 +733,481 @@ pub fn irf_exponential_3d(
 
     Ok(i_arr.broadcast(dims).unwrap().to_owned())
 }
+
+/// Simulate a 1-dimensional IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// # Description
+///
+/// This function generates a 1-dimensonal instrument response function (IRF)
+/// circularly convolved periodic monoexponential or multiexponential decay
+/// curve. Unlike the linear convolution used by `irf_exponential_1d`, the
+/// decay is treated as periodic: fluorescence that has not decayed by the end of
+/// the period (_i.e._ when tau is comparable to the period) is carried over into
+/// the next period and the IRF is circularly convolved, so IRF signal shifted
+/// past the end of the period wraps around to the start. This models the
+/// incomplete decay observed with high repetition rate pulsed lasers:
+///
+/// ```text
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+/// ```
+///
+/// where T is the period.
+///
+/// # Arguments
+///
+/// * `irf`: The IRF as a 1-dimensonal array. IRFs longer than `samples` are
+///    wrapped into one period.
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `taus`: An array of lifetimes. For a monoexponential decay curve use a
+///    single tau value and a fractional intensity of 1.0. For a
+///    multiexponential decay curve use two or more tau values, matched with
+///    their respective fractional intensity. The `taus` and `fractions` arrays
+///    must have the same length. Tau values set to 0.0 will be skipped.
+/// * `fractions`: An array of fractional intensities for each tau in the `taus`
+///    array. The `fractions` array must be the same length as the `taus` array
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The 1-dimensional IRF circularly convolved periodic
+///    monoexponential or multiexponential decay curve.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If the IRF is empty, not
+///    finite or does not sum to > 0.0.
+pub fn irf_exponential_periodic_1d(
+    irf: &[f64],
+    samples: usize,
+    period: f64,
+    taus: &[f64],
+    fractions: &[f64],
+    total_counts: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    // create periodic decay curve and circularly convolve with input irf
+    validate_irf(irf)?;
+    let i_arr = ideal_exponential_periodic_1d(samples, period, taus, fractions, total_counts)?;
+
+    Ok(fft_circular_convolve_1d(&i_arr, irf))
+}
+
+/// Simulate a 3-dimensional IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// # Description
+///
+/// This function generates a 3-dimensonal instrument response function (IRF)
+/// circularly convolved periodic monoexponential or multiexponential decay
+/// curve. See `irf_exponential_periodic_1d` for details on the periodic decay
+/// model.
+///
+/// # Arguments
+///
+/// * `irf`: The IRF as a 1-dimensonal array. IRFs longer than `samples` are
+///    wrapped into one period.
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `taus`: An array of lifetimes. For a monoexponential decay curve use a
+///    single tau value and a fractional intensity of 1.0. For a
+///    multiexponential decay curve use two or more tau values, matched with
+///    their respective fractional intensity. The `taus` and `fractions` arrays
+///    must have the same length. Tau values set to 0.0 will be skipped.
+/// * `fractions`: An array of fractional intensities for each tau in the `taus`
+///    array. The `fractions` array must be the same length as the `taus` array
+///    and sum to 1.0. Fractions that sum to 1.0 within floating point round off
+///    are normalized with a warning. Fraction values set to 0.0 will be skipped.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///    decay curve.
+/// * `shape`: The row and col shape to broadcast the decay curve into.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The 3-dimensional IRF circularly convolved periodic
+///    monoexponential or multiexponential decay curve.
+/// * `Err(ImgalError)`: If taus and fractions array lengths do not match. If
+///    fractions array does not sum to 1.0. If samples is 0 or period is <= 0.0.
+///    If a tau is < 0.0, a fraction is outside of [0.0, 1.0] or total_counts
+///    is < 0.0. If no tau > 0.0 has a fraction > 0.0. If the IRF is empty, not
+///    finite or does not sum to > 0.0.
+pub fn irf_exponential_periodic_3d(
+    irf: &[f64],
+    samples: usize,
+    period: f64,
+    taus: &[f64],
+    fractions: &[f64],
+    total_counts: f64,
+    shape: (usize, usize),
+) -> Result<Array3<f64>, ImgalError> {
+    // create 1-dimensional IRF convolved periodic decay curve to broadcast
+    let i_arr = irf_exponential_periodic_1d(irf, samples, period, taus, fractions, total_counts)?;
+    let i_arr = Array1::from_vec(i_arr);
+    let dims = (shape.0, shape.1, samples);
+
+    Ok(i_arr.broadcast(dims).unwrap().to_owned())
+}
+
+/// Sample photon arrival times from a 1-dimensional decay curve.
+///
+/// # Description
+///
+/// This function treats the input decay curve (_e.g._ an IRF convolved decay)
+/// as a probability distribution over its time bins and draws exactly `photons`
+/// photon arrival times from it. Each photon is assigned to a bin with
+/// probability proportional to the bin value and its arrival time is drawn
+/// uniformly within the bin. Bin `i` spans `[i × T / n, (i + 1) × T / n)`,
+/// where T is the period and n is the number of bins, the same time bins the
+/// periodic decay curves of this module are sampled at. Negative bin values
+/// (_e.g._ from FFT round off) are treated as 0.0.
+///
+/// # Arguments
+///
+/// * `data`: The decay curve to sample from.
+/// * `period`: The period (_i.e._ time interval) spanned by `data`.
+/// * `photons`: The exact number of photons to draw.
+/// * `seed`: Pseudorandom number generator seed, default = 0. Pass `&mut rng`
+///    to draw the next stream of an `Rng` context instead.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The sorted photon arrival times in the range
+///    [0.0, `period`).
+/// * `Err(ImgalError)`: If `data` is empty or has no positive values. If
+///    period is <= 0.0.
+pub fn sample_photons<T>(
+    data: &[T],
+    period: f64,
+    photons: usize,
+    seed: impl Into<Seed>,
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let mut rng = seed.into().single();
+
+    // check period and build the decay curve probability distribution
+    validate::positive("period", period)?;
+    let dist = photon_distribution(data)?;
+
+    // draw each photon's bin and a uniform arrival time within the bin
+    let bin_width = bin_width(period, data.len());
+    let jitter = Uniform::new(0.0, 1.0).unwrap();
+    let mut times: Vec<f64> = (0..photons)
+        .map(|_| (dist.sample(&mut rng) as f64 + jitter.sample(&mut rng)) * bin_width)
+        .collect();
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    Ok(times)
+}
+
+/// Sample a 1-dimensional photon histogram from a decay curve.
+///
+/// # Description
+///
+/// This function treats the input decay curve (_e.g._ an IRF convolved decay)
+/// as a probability distribution over its time bins and draws exactly `photons`
+/// photons from it, returning the resulting photon count histogram. Unlike
+/// applying Poisson noise to a scaled decay curve, the total photon count is
+/// exact and the histogram follows a multinomial distribution, which gives
+/// statistically correct low count data. Negative bin values (_e.g._ from FFT
+/// round off) are treated as 0.0.
+///
+/// # Arguments
+///
+/// * `data`: The decay curve to sample from.
+/// * `photons`: The exact number of photons to draw.
+/// * `seed`: Pseudorandom number generator seed, default = 0. Pass `&mut rng`
+///    to draw the next stream of an `Rng` context instead.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The photon count histogram with the same length as `data`
+///    and a sum equal to `photons`.
+/// * `Err(ImgalError)`: If `data` is empty or has no positive values.
+pub fn sample_photons_1d<T>(
+    data: &[T],
+    photons: usize,
+    seed: impl Into<Seed>,
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let mut rng = seed.into().single();
+
+    // build the decay curve probability distribution and bin the photons
+    let dist = photon_distribution(data)?;
+    let mut hist = vec![0.0; data.len()];
+    (0..photons).for_each(|_| hist[dist.sample(&mut rng)] += 1.0);
+
+    Ok(hist)
+}
+
+/// Sample a 3-dimensional photon histogram image from a decay curve.
+///
+/// # Description
+///
+/// This function draws exactly `photons` photons per pixel from the input
+/// decay curve, treated as a probability distribution over its time bins (see
+/// `sample_photons_1d`). Each pixel is sampled independently.
+///
+/// # Arguments
+///
+/// * `data`: The decay curve to sample from.
+/// * `photons`: The exact number of photons to draw per pixel.
+/// * `shape`: The row and col shape of the output image.
+/// * `seed`: Pseudorandom number generator seed. If `Some`, each pixel is
+///    sampled with a generator seeded from `seed` and the pixel index, giving
+///    reproducible output. If `None`, then each pixel is sampled with a thread
+///    local generator. Pass `&mut rng` to draw the next stream of an `Rng`
+///    context instead.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The 3-dimensional (row, col, t) photon count histogram
+///    image, where each pixel sums to `photons`.
+/// * `Err(ImgalError)`: If `data` is empty or has no positive values.
+pub fn sample_photons_3d<T>(
+    data: &[T],
+    photons: usize,
+    shape: (usize, usize),
+    seed: impl Into<Seed>,
+) -> Result<Array3<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let seed = seed.into();
+
+    // build the decay curve probability distribution
+    let dist = photon_distribution(data)?;
+    let mut hist = Array3::<f64>::zeros((shape.0, shape.1, data.len()));
+
+    // bin the photons of each pixel in parallel
+    hist.lanes_mut(Axis(2))
+        .into_iter()
+        .enumerate()
+        .par_bridge()
+        .for_each(|(i, mut ln)| {
+            let mut rng = seed.lane_offset(i);
+            (0..photons).for_each(|_| ln[dist.sample(&mut rng)] += 1.0);
+        });
+
+    Ok(hist)
+}
+
+/// Compute the width of the time bins of a curve with `samples` bins spanning
+/// one period.
+fn bin_width(period: f64, samples: usize) -> f64 {
+    period / samples as f64
+}
+
+/// Simulate a 1-dimensional exponential decay curve, optionally accumulating
+/// the incomplete decay tails of previous periods.
+fn exponential_decay_1d(
+    samples: usize,
+    period: f64,
+    taus: &[f64],
+    fractions: &[f64],
+    total_counts: f64,
+    periodic: bool,
+) -> Result<Vec<f64>, ImgalError> {
+    // check samples, period, total_counts and taus and fractions arrays
+    validate::at_least("samples", samples as f64, 1.0)?;
+    validate::positive("period", period)?;
+    validate::at_least("total_counts", total_counts, 0.0)?;
+    validate::same_length(taus.len(), fractions.len())?;
+    taus.iter()
+        .try_for_each(|&t| validate::at_least("taus", t, 0.0))?;
+    fractions
+        .iter()
+        .try_for_each(|&f| validate::in_range("fractions", f, 0.0, 1.0))?;
+    if !taus
+        .iter()
+        .zip(fractions.iter())
+        .any(|(&t, &f)| t > 0.0 && f > 0.0)
+    {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "taus",
+            reason: "must have at least one tau > 0.0 with a fraction > 0.0".to_string(),
+        });
+    }
+
+    // create fractions array and check sum to 1.0, fractions that are off by
+    // floating point round off are normalized with a warning
+    let fs = sum(fractions);
+    if (fs - 1.0).abs() > FRACTIONS_SUM_TOLERANCE {
+        return Err(ImgalError::InvalidSum {
+            expected: 1.0,
+            got: fs,
+        });
+    }
+    let mut frac_arr = Array1::from_vec(fractions.to_vec());
+    if fs != 1.0 {
+        warning::warn(Warning::FractionsNormalized { got: fs });
+        frac_arr /= fs;
+    }
+
+    // create taus array and compute pre-exponential factors
+    let alph_arr: Vec<f64> = frac_arr
+        .iter()
+        .zip(taus.iter())
+        .map(|(f, t)| f / t)
+        .collect();
+
+    Ok(multi_exponential_1d(
+        samples,
+        period,
+        taus,
+        &alph_arr,
+        total_counts,
+        periodic,
+    ))
+}
+
+/// Compute the lifetimes and amplitudes of a FRET donor population.
+fn fret_components(fret: Fret) -> Result<(Vec<f64>, Vec<f64>), ImgalError> {
+    let Fret {
+        donor_tau,
+        efficiency,
+        donor_only_fraction,
+    } = fret;
+    validate::positive("donor_tau", donor_tau)?;
+    validate::in_range("donor_only_fraction", donor_only_fraction, 0.0, 1.0)?;
+
+    // discretize the efficiency distribution into efficiencies and weights
+    let (effs, weights): (Vec<f64>, Vec<f64>) = match efficiency {
+        FretEfficiency::Single(e) => {
+            validate_efficiency("efficiency", e)?;
+            (vec![e], vec![1.0])
+        }
+        FretEfficiency::Gaussian { mean, sigma } => {
+            validate_efficiency("mean", mean)?;
+            validate::at_least("sigma", sigma, 0.0)?;
+            if sigma == 0.0 {
+                (vec![mean], vec![1.0])
+            } else {
+                // integrate the distribution within 4 sigma, truncated to [0, 1)
+                let lo = (mean - 4.0 * sigma).max(0.0);
+                let hi = (mean + 4.0 * sigma).min(1.0 - f64::EPSILON);
+                let step = (hi - lo) / (FRET_GAUSSIAN_NODES - 1) as f64;
+                (0..FRET_GAUSSIAN_NODES)
+                    .map(|i| {
+                        let e = lo + i as f64 * step;
+                        (e, (-0.5 * ((e - mean) / sigma).powi(2)).exp())
+                    })
+                    .unzip()
+            }
+        }
+        FretEfficiency::TwoPopulation {
+            first,
+            second,
+            second_fraction,
+        } => {
+            validate_efficiency("first", first)?;
+            validate_efficiency("second", second)?;
+            validate::in_range("second_fraction", second_fraction, 0.0, 1.0)?;
+            (
+                vec![first, second],
+                vec![1.0 - second_fraction, second_fraction],
+            )
+        }
+    };
+
+    // donor only molecules keep the donor lifetime, FRET donors are quenched
+    let w_sum = sum(&weights);
+    let mut taus = vec![donor_tau];
+    let mut alphas = vec![donor_only_fraction];
+    effs.iter().zip(weights.iter()).for_each(|(e, w)| {
+        taus.push(donor_tau * (1.0 - e));
+        alphas.push((1.0 - donor_only_fraction) * w / w_sum);
+    });
+
+    Ok((taus, alphas))
+}
+
+/// Compute a multiexponential decay curve from lifetimes and pre-exponential
+/// factors, scaled to total_counts.
+fn multi_exponential_1d(
+    samples: usize,
+    period: f64,
+    taus: &[f64],
+    alphas: &[f64],
+    total_counts: f64,
+    periodic: bool,
+) -> Vec<f64> {
+    // create the time array and compute the intensity decay curve, periodic
+    // curves are sampled at t = i × T / samples so the bin following the last
+    // bin is the first bin of the next period
+    let mut i_arr = vec![0.0; samples];
+    let time_arr = if periodic {
+        let dt = bin_width(period, samples);
+        Array1::from_iter((0..samples).map(|i| i as f64 * dt))
+    } else {
+        Array1::linspace(0.0, period, samples)
+    };
+    alphas
+        .iter()
+        .zip(taus.iter())
+        .filter(|&(&al, &ta)| al != 0.0 && ta != 0.0)
+        .for_each(|(al, ta)| {
+            // accumulate the incomplete decay from all previous periods with
+            // the geometric series Σₖ exp(-(t + kT)/τ) = exp(-t/τ) / (1 - exp(-T/τ))
+            let tail = if periodic {
+                1.0 / (1.0 - (-period / ta).exp())
+            } else {
+                1.0
+            };
+            Zip::from(&mut i_arr).and(&time_arr).for_each(|i, t| {
+                *i += al * tail * (-t / ta).exp();
+            });
+        });
+
+    // scale the histogram to total_counts
+    let scale = total_counts / sum(&i_arr);
+    i_arr.iter_mut().for_each(|v| *v *= scale);
+
+    i_arr
+}
+
+/// Create a discrete photon arrival probability distribution from a decay
+/// curve, clamping negative values to 0.0.
+fn photon_distribution<T>(data: &[T]) -> Result<WeightedIndex<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let weights: Vec<f64> = data.iter().map(|v| v.to_f64().max(0.0)).collect();
+    WeightedIndex::new(&weights).map_err(|_| ImgalError::InvalidParameter {
+        param_name: "data",
+        reason: "must be a non-empty curve with at least one finite positive value".to_string(),
+    })
+}
+
+/// Validate that a FRET efficiency is in [0, 1).
+fn validate_efficiency(param_name: &'static str, value: f64) -> Result<(), ImgalError> {
+    if !(0.0..1.0).contains(&value) {
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: format!("must be a FRET efficiency in [0, 1) but got {}", value),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check that an IRF is a non-empty, finite curve with a sum > 0.0.
+fn validate_irf(irf: &[f64]) -> Result<(), ImgalError> {
+    if irf.is_empty() || irf.iter().any(|v| !v.is_finite()) || sum(irf) <= 0.0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "irf",
+            reason: "must be a non-empty finite curve with a sum > 0.0".to_string(),
+        });
+    }
+
+    Ok(())
+}
@@ -1,6 +1,290 @@
-use std::f64::consts::LN_2;
+use std::f64::consts::{FRAC_2_PI, FRAC_PI_4, LN_2, PI};
+
+use ndarray::{Array2, Array3, ArrayViewMut, Axis, Dimension};
+use rayon::prelude::*;
 
 use crate::distribution::gaussian;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::filter::fft_convolve_1d;
+use crate::statistics::sum;
+
+/// Number of pupil radius samples used to integrate the scalar diffraction
+/// PSF models, must be odd for Simpson's rule.
+const PUPIL_SAMPLES: usize = 513;
+
+/// Number of radial profile samples per pixel, the PSF image is interpolated
+/// from the radial profile.
+const RADIAL_OVERSAMPLING: f64 = 8.0;
+
+/// Gibson–Lanni objective and sample parameters.
+///
+/// All lengths share the unit of the PSF `pixel_size`, `z_step` and
+/// `wavelength` (_e.g._ micrometers). The coverslip is assumed to match its
+/// design thickness and refractive index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GibsonLanni {
+    /// The refractive index of the sample (_e.g._ 1.33 for water).
+    pub sample_ri: f64,
+    /// The refractive index of the immersion medium.
+    pub immersion_ri: f64,
+    /// The design refractive index of the immersion medium.
+    pub immersion_ri_design: f64,
+    /// The design working distance (_i.e._ immersion thickness).
+    pub working_distance: f64,
+    /// The depth of the point source below the coverslip.
+    pub particle_depth: f64,
+}
+
+impl Default for GibsonLanni {
+    /// An oil immersion objective imaging an aqueous sample at the coverslip.
+    fn default() -> Self {
+        GibsonLanni {
+            sample_ri: 1.33,
+            immersion_ri: 1.515,
+            immersion_ri_design: 1.515,
+            working_distance: 150.0,
+            particle_depth: 0.0,
+        }
+    }
+}
+
+/// Gaussian instrument response function (IRF) parameters.
+///
+/// Both values share the unit of the decay `period` (_e.g._ nanoseconds).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaussianIrf {
+    /// The temporal position of the IRF peak within the time range.
+    pub center: f64,
+    /// The full width at half maximum (FWHM) of the IRF.
+    pub width: f64,
+}
+
+/// Simulate a 2-dimensional Born–Wolf (_i.e._ Airy) point spread function (PSF).
+///
+/// # Description
+///
+/// This function creates the in-focus scalar diffraction PSF of a circular
+/// aperture, the Airy pattern:
+///
+/// ```text
+/// I(r) = (2 × J₁(v) / v)²
+/// v = 2π × NA × r / λ
+/// ```
+///
+/// where `J₁` is the Bessel function of the first kind of order 1. The PSF is
+/// centered in the image and normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the PSF image.
+/// * `pixel_size`: The lateral pixel size.
+/// * `wavelength`: The emission wavelength, in the same unit as `pixel_size`.
+/// * `na`: The numerical aperture of the objective.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The normalized 2-dimensional PSF.
+/// * `Err(ImgalError)`: If pixel_size, wavelength or na is <= 0.0.
+pub fn born_wolf_psf_2d(
+    shape: (usize, usize),
+    pixel_size: f64,
+    wavelength: f64,
+    na: f64,
+) -> Result<Array2<f64>, ImgalError> {
+    // check the optical parameters
+    validate::positive("pixel_size", pixel_size)?;
+    validate::positive("wavelength", wavelength)?;
+    validate::positive("na", na)?;
+
+    let k_na = 2.0 * PI * na / wavelength;
+    let mut psf = radial_image(shape, pixel_size, |r| {
+        let v = k_na * r;
+        if v == 0.0 {
+            1.0
+        } else {
+            (2.0 * bessel_j1(v) / v).powi(2)
+        }
+    });
+    normalize(psf.view_mut());
+
+    Ok(psf)
+}
+
+/// Simulate a 3-dimensional Born–Wolf point spread function (PSF).
+///
+/// # Description
+///
+/// This function creates the scalar diffraction PSF of a circular aperture
+/// with paraxial defocus, integrating over the normalized pupil radius `ρ`:
+///
+/// ```text
+/// I(r, z) = |∫₀¹ J₀(k × NA × r × ρ) × exp(-½ × i × k × z × NA² × ρ² / n) × ρ dρ|²
+/// ```
+///
+/// where `k = 2π/λ`, `n` is the refractive index of the immersion medium and
+/// `J₀` is the Bessel function of the first kind of order 0. The PSF is
+/// centered in the volume and normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `shape`: The pln, row and col shape of the PSF volume.
+/// * `pixel_size`: The lateral pixel size.
+/// * `z_step`: The axial distance between planes.
+/// * `wavelength`: The emission wavelength, in the same unit as `pixel_size`.
+/// * `na`: The numerical aperture of the objective.
+/// * `refractive_index`: The refractive index of the immersion medium.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The normalized 3-dimensional PSF with shape
+///   (pln, row, col).
+/// * `Err(ImgalError)`: If pixel_size, z_step, wavelength or na is <= 0.0. If
+///   na is greater than refractive_index.
+pub fn born_wolf_psf_3d(
+    shape: (usize, usize, usize),
+    pixel_size: f64,
+    z_step: f64,
+    wavelength: f64,
+    na: f64,
+    refractive_index: f64,
+) -> Result<Array3<f64>, ImgalError> {
+    // check the optical parameters
+    validate::positive("z_step", z_step)?;
+    validate::positive("wavelength", wavelength)?;
+    validate::positive("na", na)?;
+    validate::at_least("refractive_index", refractive_index, na)?;
+
+    let k = 2.0 * PI / wavelength;
+    let defocus = 0.5 * k * na * na / refractive_index;
+    scalar_psf_3d(shape, pixel_size, z_step, k * na, |rho, z| {
+        -defocus * z * rho * rho
+    })
+}
+
+/// Simulate a 1-dimensional double peak instrument response function (IRF).
+///
+/// # Description
+///
+/// This function creates an IRF with a primary Gaussian peak and a secondary
+/// Gaussian peak (_e.g._ an optical reflection artifact) of the same width.
+/// The secondary peak is positioned `peak_offset` after the primary peak and
+/// scaled by `peak_ratio` relative to the primary peak before the combined
+/// curve is normalized to sum to 1.0:
+///
+/// ```text
+/// IRF(t) = G(t, c) + r × G(t, c + Δ)
+/// ```
+///
+/// Where `G` is the Gaussian IRF, `c` is the IRF center, `r` is the peak ratio
+/// and `Δ` is the peak offset.
+///
+/// # Arguments
+///
+/// * `bins`: The number of discrete points to sample the IRF.
+/// * `time_range`: The total time range over which to simulate the IRF.
+/// * `irf_center`: The temporal position of the primary IRF peak within the
+///   time range.
+/// * `irf_width`: The full width at half maximum (FWHM) of both IRF peaks.
+/// * `peak_offset`: The temporal offset of the secondary peak relative to the
+///   primary peak.
+/// * `peak_ratio`: The amplitude of the secondary peak relative to the primary
+///   peak. Must be >= 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The simulated 1-dimensional double peak IRF curve.
+/// * `Err(ImgalError)`: If time_range or irf_width is <= 0.0. If bins is < 2.
+///   If peak_ratio is < 0.0. If either peak lies entirely outside of the time
+///   range.
+pub fn double_peak_irf_1d(
+    bins: usize,
+    time_range: f64,
+    irf_center: f64,
+    irf_width: f64,
+    peak_offset: f64,
+    peak_ratio: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the peak_ratio parameter is valid
+    validate::at_least("peak_ratio", peak_ratio, 0.0)?;
+
+    let primary = gaussian_irf_1d(bins, time_range, irf_center, irf_width)?;
+    let secondary = gaussian_irf_1d(bins, time_range, irf_center + peak_offset, irf_width)?;
+
+    // combine both peaks and normalize the IRF
+    let mut irf: Vec<f64> = primary
+        .iter()
+        .zip(secondary.iter())
+        .map(|(p, s)| p + peak_ratio * s)
+        .collect();
+    let irf_sum = sum(&irf);
+    irf.iter_mut().for_each(|v| *v /= irf_sum);
+
+    Ok(irf)
+}
+
+/// Simulate a 1-dimensional exponentially modified Gaussian instrument response
+/// function (IRF).
+///
+/// # Description
+///
+/// This function creates an asymmetric IRF by convolving a Gaussian IRF with
+/// an exponential decay tail, producing the exponentially modified Gaussian
+/// (EMG) shape typical of detectors with a slow diffusion tail (_e.g._ PMTs and
+/// SPADs):
+///
+/// ```text
+/// IRF(t) = G(t) ⊛ exp(-t/τ)
+/// ```
+///
+/// Where `G` is the Gaussian IRF and `τ` is the tail time constant. The
+/// resulting IRF is normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `bins`: The number of discrete points to sample the IRF.
+/// * `time_range`: The total time range over which to simulate the IRF.
+/// * `irf_center`: The temporal position of the Gaussian component peak within
+///   the time range.
+/// * `irf_width`: The full width at half maximum (FWHM) of the Gaussian
+///   component.
+/// * `tail_tau`: The time constant of the exponential tail. If `tail_tau` is
+///   0.0, the Gaussian IRF is returned. Must be >= 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The simulated 1-dimensional exponentially modified
+///   Gaussian IRF curve.
+/// * `Err(ImgalError)`: If time_range or irf_width is <= 0.0. If bins is < 2.
+///   If tail_tau is < 0.0. If the IRF lies entirely outside of the time range.
+pub fn exponentially_modified_gaussian_irf_1d(
+    bins: usize,
+    time_range: f64,
+    irf_center: f64,
+    irf_width: f64,
+    tail_tau: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the tail_tau parameter is valid
+    validate::at_least("tail_tau", tail_tau, 0.0)?;
+
+    let g = gaussian_irf_1d(bins, time_range, irf_center, irf_width)?;
+    if tail_tau == 0.0 {
+        return Ok(g);
+    }
+
+    // create the exponential tail on the same time axis as the Gaussian
+    let dt = time_range / (bins as f64 - 1.0);
+    let tail: Vec<f64> = (0..bins)
+        .map(|i| (-(i as f64 * dt) / tail_tau).exp())
+        .collect();
+
+    // convolve and normalize the IRF
+    let mut irf = fft_convolve_1d(&g, &tail);
+    let irf_sum = sum(&irf);
+    irf.iter_mut().for_each(|v| *v /= irf_sum);
+
+    Ok(irf)
+}
 
 /// Simulate a 1-dimensional Gaussian instrument response function (IRF).
 ///
@@ -20,12 +304,434 @@ use crate::distribution::gaussian;
 /// * `bins`: The number of discrete points to sample the Gaussian distribution.
 /// * `time_range`: The total time range over which to simulate the IRF.
 /// * `irf_center`: The temporal position of the IRF peak within the time range.
-/// * `irf_width`: The full width at half maximum (FWHM) of the IRF.
+/// * `irf_width`: The full width at half maximum (FWHM) of the IRF. Must be
+///   > 0.0.
 ///
 /// # Returns
 ///
-/// * `Vec<f64>`: The simulated 1-dimensional IRF curve.
-pub fn gaussian_irf_1d(bins: usize, time_range: f64, irf_center: f64, irf_width: f64) -> Vec<f64> {
+/// * `Ok(Vec<f64>)`: The simulated 1-dimensional IRF curve.
+/// * `Err(ImgalError)`: If time_range or irf_width is <= 0.0. If bins is < 2.
+///   If the IRF lies entirely outside of the time range.
+pub fn gaussian_irf_1d(
+    bins: usize,
+    time_range: f64,
+    irf_center: f64,
+    irf_width: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    // check if the irf_width parameter is valid
+    validate::positive("irf_width", irf_width)?;
+
     let sigma = irf_width / (2.0 * (2.0 * LN_2).sqrt());
     gaussian(sigma, bins, time_range, irf_center)
 }
+
+/// Simulate a 2-dimensional Gaussian point spread function (PSF).
+///
+/// # Description
+///
+/// This function creates a Gaussian approximation of the in-focus widefield
+/// PSF with the lateral standard deviation:
+///
+/// ```text
+/// σ_xy = 0.21 × λ / NA
+/// ```
+///
+/// The PSF is centered in the image and normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the PSF image.
+/// * `pixel_size`: The lateral pixel size.
+/// * `wavelength`: The emission wavelength, in the same unit as `pixel_size`.
+/// * `na`: The numerical aperture of the objective.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The normalized 2-dimensional PSF.
+/// * `Err(ImgalError)`: If pixel_size, wavelength or na is <= 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1364/AO.46.001819>
+pub fn gaussian_psf_2d(
+    shape: (usize, usize),
+    pixel_size: f64,
+    wavelength: f64,
+    na: f64,
+) -> Result<Array2<f64>, ImgalError> {
+    // check the optical parameters
+    validate::positive("pixel_size", pixel_size)?;
+    validate::positive("wavelength", wavelength)?;
+    validate::positive("na", na)?;
+
+    let sigma = 0.21 * wavelength / na;
+    let mut psf = radial_image(shape, pixel_size, |r| (-0.5 * (r / sigma).powi(2)).exp());
+    normalize(psf.view_mut());
+
+    Ok(psf)
+}
+
+/// Simulate a 3-dimensional Gaussian point spread function (PSF).
+///
+/// # Description
+///
+/// This function creates a Gaussian approximation of the widefield PSF with
+/// the lateral and axial standard deviations:
+///
+/// ```text
+/// σ_xy = 0.21 × λ / NA
+/// σ_z  = 0.66 × λ × n / NA²
+/// ```
+///
+/// where `n` is the refractive index of the immersion medium. The PSF is
+/// centered in the volume and normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `shape`: The pln, row and col shape of the PSF volume.
+/// * `pixel_size`: The lateral pixel size.
+/// * `z_step`: The axial distance between planes.
+/// * `wavelength`: The emission wavelength, in the same unit as `pixel_size`.
+/// * `na`: The numerical aperture of the objective.
+/// * `refractive_index`: The refractive index of the immersion medium.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The normalized 3-dimensional PSF with shape
+///   (pln, row, col).
+/// * `Err(ImgalError)`: If pixel_size, z_step, wavelength or na is <= 0.0. If
+///   na is greater than refractive_index.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1364/AO.46.001819>
+pub fn gaussian_psf_3d(
+    shape: (usize, usize, usize),
+    pixel_size: f64,
+    z_step: f64,
+    wavelength: f64,
+    na: f64,
+    refractive_index: f64,
+) -> Result<Array3<f64>, ImgalError> {
+    // check the optical parameters
+    validate::positive("pixel_size", pixel_size)?;
+    validate::positive("z_step", z_step)?;
+    validate::positive("wavelength", wavelength)?;
+    validate::positive("na", na)?;
+    validate::at_least("refractive_index", refractive_index, na)?;
+
+    let sigma_xy = 0.21 * wavelength / na;
+    let sigma_z = 0.66 * wavelength * refractive_index / (na * na);
+    let zc = (shape.0 as f64 - 1.0) / 2.0;
+    let mut psf = Array3::<f64>::zeros(shape);
+    let lateral = radial_image((shape.1, shape.2), pixel_size, |r| {
+        (-0.5 * (r / sigma_xy).powi(2)).exp()
+    });
+    psf.axis_iter_mut(Axis(0))
+        .enumerate()
+        .for_each(|(p, mut pln)| {
+            let z = (p as f64 - zc) * z_step;
+            let axial = (-0.5 * (z / sigma_z).powi(2)).exp();
+            pln.assign(&lateral);
+            pln *= axial;
+        });
+    normalize(psf.view_mut());
+
+    Ok(psf)
+}
+
+/// Simulate a 3-dimensional Gibson–Lanni point spread function (PSF).
+///
+/// # Description
+///
+/// This function creates the scalar diffraction PSF of an objective imaging a
+/// point source below the coverslip, including the spherical aberration caused
+/// by refractive index mismatch between the sample and the immersion medium.
+/// The pupil phase is given by the optical path difference (OPD) between the
+/// experimental and design conditions:
+///
+/// ```text
+/// OPD(ρ, z) = zₚ × √(nₛ² - (NA × ρ)²)
+///           + (tᵢ + z) × √(nᵢ² - (NA × ρ)²)
+///           - tᵢ × √(nᵢ*² - (NA × ρ)²)
+/// I(r, z) = |∫₀¹ J₀(k × NA × r × ρ) × exp(i × k × OPD(ρ, z)) × ρ dρ|²
+/// ```
+///
+/// where `zₚ` is the particle depth, `tᵢ` is the working distance, `nₛ`, `nᵢ`
+/// and `nᵢ*` are the sample, immersion and design immersion refractive indices
+/// and `z` is the focal plane position. With matched refractive indices and
+/// `zₚ = 0.0` the model reduces to the aberration free (non-paraxial) PSF. The
+/// PSF is centered in the volume and normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `shape`: The pln, row and col shape of the PSF volume.
+/// * `pixel_size`: The lateral pixel size.
+/// * `z_step`: The axial distance between planes.
+/// * `wavelength`: The emission wavelength, in the same unit as `pixel_size`.
+/// * `na`: The numerical aperture of the objective.
+/// * `params`: The Gibson–Lanni objective and sample parameters.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The normalized 3-dimensional PSF with shape
+///   (pln, row, col).
+/// * `Err(ImgalError)`: If pixel_size, z_step, wavelength or na is <= 0.0. If
+///   na is greater than any refractive index. If the particle depth is < 0.0.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1364/JOSAA.8.001601>
+pub fn gibson_lanni_psf_3d(
+    shape: (usize, usize, usize),
+    pixel_size: f64,
+    z_step: f64,
+    wavelength: f64,
+    na: f64,
+    params: GibsonLanni,
+) -> Result<Array3<f64>, ImgalError> {
+    // check the optical parameters
+    validate::positive("z_step", z_step)?;
+    validate::positive("wavelength", wavelength)?;
+    validate::positive("na", na)?;
+    validate::at_least("sample_ri", params.sample_ri, na)?;
+    validate::at_least("immersion_ri", params.immersion_ri, na)?;
+    validate::at_least("immersion_ri_design", params.immersion_ri_design, na)?;
+    validate::at_least("particle_depth", params.particle_depth, 0.0)?;
+
+    let k = 2.0 * PI / wavelength;
+    let root = |n: f64, rho: f64| (n * n - (na * rho).powi(2)).max(0.0).sqrt();
+    scalar_psf_3d(shape, pixel_size, z_step, k * na, |rho, z| {
+        let opd = params.particle_depth * root(params.sample_ri, rho)
+            + (params.working_distance + z) * root(params.immersion_ri, rho)
+            - params.working_distance * root(params.immersion_ri_design, rho);
+        k * opd
+    })
+}
+
+/// Create a 1-dimensional instrument response function (IRF) from measured data.
+///
+/// # Description
+///
+/// This function resamples a measured IRF (_e.g._ the decay histogram of a
+/// scattering sample) to the requested number of bins using linear
+/// interpolation. Negative values (_e.g._ from background subtraction) are
+/// clamped to 0.0 and the resampled IRF is normalized to sum to 1.0, making it
+/// suitable for use with `simulation::decay::irf_exponential_1d` and
+/// `simulation::decay::irf_exponential_3d`.
+///
+/// # Arguments
+///
+/// * `data`: The measured IRF, spanning the same time range as the simulation.
+///   Must contain at least 2 points.
+/// * `bins`: The number of discrete points of the resampled IRF.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The resampled and normalized 1-dimensional IRF curve.
+/// * `Err(ImgalError)`: If `data` has less than 2 points. If the resampled IRF
+///   sums to 0.0.
+pub fn measured_irf_1d(data: &[f64], bins: usize) -> Result<Vec<f64>, ImgalError> {
+    // check the measured IRF has enough points to interpolate
+    let dl = data.len();
+    if dl < 2 {
+        return Err(ImgalError::InvalidArrayParameterValueLess {
+            param_name: "data",
+            value: 2,
+        });
+    }
+
+    // linearly interpolate the measured IRF onto the new bins
+    let scale = if bins > 1 {
+        (dl - 1) as f64 / (bins - 1) as f64
+    } else {
+        0.0
+    };
+    let mut irf: Vec<f64> = (0..bins)
+        .map(|i| {
+            let pos = i as f64 * scale;
+            let lo = (pos.floor() as usize).min(dl - 2);
+            let frac = pos - lo as f64;
+            let v = data[lo] * (1.0 - frac) + data[lo + 1] * frac;
+            v.max(0.0)
+        })
+        .collect();
+
+    // normalize the resampled IRF
+    let irf_sum = sum(&irf);
+    if irf_sum <= 0.0 {
+        return Err(ImgalError::InvalidArrayGeneric {
+            msg: "The measured IRF must contain positive values.",
+        });
+    }
+    irf.iter_mut().for_each(|v| *v /= irf_sum);
+
+    Ok(irf)
+}
+
+/// Compute the Bessel function of the first kind of order 0.
+///
+/// Rational and asymptotic approximations with an absolute error < 1e-8.
+fn bessel_j0(x: f64) -> f64 {
+    let ax = x.abs();
+    if ax < 8.0 {
+        let y = x * x;
+        let n = 57568490574.0
+            + y * (-13362590354.0
+                + y * (651619640.7 + y * (-11214424.18 + y * (77392.33017 + y * -184.9052456))));
+        let d = 57568490411.0
+            + y * (1029532985.0 + y * (9494680.718 + y * (59272.64853 + y * (267.8532712 + y))));
+        n / d
+    } else {
+        let z = 8.0 / ax;
+        let y = z * z;
+        let xx = ax - FRAC_PI_4;
+        let p = 1.0
+            + y * (-0.1098628627e-2
+                + y * (0.2734510407e-4 + y * (-0.2073370639e-5 + y * 0.2093887211e-6)));
+        let q = -0.1562499995e-1
+            + y * (0.1430488765e-3
+                + y * (-0.6911147651e-5 + y * (0.7621095161e-6 - y * 0.934935152e-7)));
+        (FRAC_2_PI / ax).sqrt() * (xx.cos() * p - z * xx.sin() * q)
+    }
+}
+
+/// Compute the Bessel function of the first kind of order 1.
+///
+/// Rational and asymptotic approximations with an absolute error < 1e-8.
+fn bessel_j1(x: f64) -> f64 {
+    let ax = x.abs();
+    if ax < 8.0 {
+        let y = x * x;
+        let n = x
+            * (72362614232.0
+                + y * (-7895059235.0
+                    + y * (242396853.1
+                        + y * (-2972611.439 + y * (15704.48260 + y * -30.16036606)))));
+        let d = 144725228442.0
+            + y * (2300535178.0 + y * (18583304.74 + y * (99447.43394 + y * (376.9991397 + y))));
+        n / d
+    } else {
+        let z = 8.0 / ax;
+        let y = z * z;
+        let xx = ax - 3.0 * FRAC_PI_4;
+        let p = 1.0
+            + y * (0.183105e-2
+                + y * (-0.3516396496e-4 + y * (0.2457520174e-5 + y * -0.240337019e-6)));
+        let q = 0.04687499995
+            + y * (-0.2002690873e-3
+                + y * (0.8449199096e-5 + y * (-0.88228987e-6 + y * 0.105787412e-6)));
+        let ans = (FRAC_2_PI / ax).sqrt() * (xx.cos() * p - z * xx.sin() * q);
+        if x < 0.0 { -ans } else { ans }
+    }
+}
+
+/// Normalize an array to sum to 1.0.
+fn normalize<D: Dimension>(mut data: ArrayViewMut<f64, D>) {
+    let total = data.sum();
+    if total > 0.0 {
+        data /= total;
+    }
+}
+
+/// Create a 2-dimensional image from a radially symmetric function centered in
+/// the image, `f` takes the distance from the center.
+fn radial_image<F>(shape: (usize, usize), pixel_size: f64, f: F) -> Array2<f64>
+where
+    F: Fn(f64) -> f64,
+{
+    let rc = (shape.0 as f64 - 1.0) / 2.0;
+    let cc = (shape.1 as f64 - 1.0) / 2.0;
+    Array2::from_shape_fn(shape, |(r, c)| {
+        let dr = (r as f64 - rc) * pixel_size;
+        let dc = (c as f64 - cc) * pixel_size;
+        f((dr * dr + dc * dc).sqrt())
+    })
+}
+
+/// Compute a normalized 3-dimensional scalar diffraction PSF from the pupil
+/// phase function `phase(ρ, z)`.
+///
+/// Each plane is computed as a radial profile by integrating the pupil with
+/// Simpson's rule, then linearly interpolated onto the pixel grid.
+fn scalar_psf_3d<F>(
+    shape: (usize, usize, usize),
+    pixel_size: f64,
+    z_step: f64,
+    k_na: f64,
+    phase: F,
+) -> Result<Array3<f64>, ImgalError>
+where
+    F: Fn(f64, f64) -> f64 + Sync,
+{
+    validate::positive("pixel_size", pixel_size)?;
+
+    // radial profile sampling, out to the corner of a plane
+    let (pc, rc, cc) = (
+        (shape.0 as f64 - 1.0) / 2.0,
+        (shape.1 as f64 - 1.0) / 2.0,
+        (shape.2 as f64 - 1.0) / 2.0,
+    );
+    let r_max = (rc * rc + cc * cc).sqrt() * pixel_size;
+    let dr = pixel_size / RADIAL_OVERSAMPLING;
+    let n_r = (r_max / dr).ceil() as usize + 2;
+
+    // Simpson's rule weights over the normalized pupil radius
+    let h = 1.0 / (PUPIL_SAMPLES - 1) as f64;
+    let rho: Vec<f64> = (0..PUPIL_SAMPLES).map(|i| i as f64 * h).collect();
+    let simpson: Vec<f64> = (0..PUPIL_SAMPLES)
+        .map(|i| {
+            let w = if i == 0 || i == PUPIL_SAMPLES - 1 {
+                1.0
+            } else if i % 2 == 1 {
+                4.0
+            } else {
+                2.0
+            };
+            w * h / 3.0 * rho[i]
+        })
+        .collect();
+
+    let mut psf = Array3::<f64>::zeros(shape);
+    psf.axis_iter_mut(Axis(0))
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(p, mut pln)| {
+            // pupil phase of this plane
+            let z = (p as f64 - pc) * z_step;
+            let (cos_ph, sin_ph): (Vec<f64>, Vec<f64>) = rho
+                .iter()
+                .map(|&rh| {
+                    let ph = phase(rh, z);
+                    (ph.cos(), ph.sin())
+                })
+                .unzip();
+
+            // radial intensity profile
+            let profile: Vec<f64> = (0..n_r)
+                .map(|i| {
+                    let v = k_na * i as f64 * dr;
+                    let (mut re, mut im) = (0.0, 0.0);
+                    rho.iter().enumerate().for_each(|(j, &rh)| {
+                        let b = bessel_j0(v * rh) * simpson[j];
+                        re += b * cos_ph[j];
+                        im += b * sin_ph[j];
+                    });
+                    re * re + im * im
+                })
+                .collect();
+
+            // interpolate the radial profile onto the plane
+            pln.indexed_iter_mut().for_each(|((r, c), px)| {
+                let y = (r as f64 - rc) * pixel_size;
+                let x = (c as f64 - cc) * pixel_size;
+                let pos = (y * y + x * x).sqrt() / dr;
+                let lo = (pos.floor() as usize).min(n_r - 2);
+                let frac = pos - lo as f64;
+                *px = profile[lo] * (1.0 - frac) + profile[lo + 1] * frac;
+            });
+        });
+    normalize(psf.view_mut());
+
+    Ok(psf)
+}
@@ -1,4 +1,11 @@
-//! Decay, instrument, and noise simulation functions.
+//! Anisotropy, decay, instrument, noise, pattern, random number, spectra, and
+//! time-lapse simulation functions.
+pub mod anisotropy;
 pub mod decay;
 pub mod instrument;
 pub mod noise;
+pub mod pattern;
+pub mod rng;
+pub use rng::{Rng, Seed};
+pub mod spectra;
+pub mod timelapse;
@@ -1,12 +1,15 @@
-use ndarray::{Array3, ArrayView1, ArrayView3, ArrayViewMut3, Axis, Zip};
-use rand::SeedableRng;
-use rand::prelude::*;
-use rand::rngs::StdRng;
-use rand_distr::{Distribution, Poisson};
+use ndarray::{
+    Array3, ArrayD, ArrayView, ArrayView3, ArrayViewD, ArrayViewMut, ArrayViewMut3, ArrayViewMutD,
+    Axis, Dimension,
+};
+use rand::Rng;
 use rayon::prelude::*;
 
-use crate::error::ArrayError;
-use crate::traits::numeric::ToFloat64;
+use crate::distribution::sample::draw_poisson;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::simulation::rng::Seed;
+use crate::traits::numeric::{FromFloat64, ToFloat64};
 
 /// Simulate Poisson noise on a 1-dimensional array.
 ///
@@ -22,33 +25,24 @@ use crate::traits::numeric::ToFloat64;
 ///
 /// * `data`: The input 1-dimensional array.
 /// * `scale`: The scale factor.
-/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
+/// * `seed`: Pseudorandom number generator seed, default = 0. Pass `&mut rng`
+///    to draw the next stream of an `Rng` context instead.
 ///
 /// # Returns
 ///
-/// * `Vec<f64>`: A 1-dimensonal array of the input data with Poisson noise applied.
-pub fn poisson_1d<T>(data: &[T], scale: f64, seed: Option<u64>) -> Vec<f64>
+/// * `Ok(Vec<f64>)`: A 1-dimensonal array of the input data with Poisson noise
+///    applied.
+/// * `Err(ImgalError)`: If a scaled value exceeds the largest Poisson lambda.
+pub fn poisson_1d<T>(data: &[T], scale: f64, seed: impl Into<Seed>) -> Result<Vec<f64>, ImgalError>
 where
     T: ToFloat64,
 {
     // set optional parameters if needed
-    let s = seed.unwrap_or(0);
-    let mut rng = StdRng::seed_from_u64(s);
-
-    let mut n_data = vec![0.0; data.len()];
-    n_data.iter_mut().zip(data.iter()).for_each(|(n, &d)| {
-        if d.to_f64() > 0.0 {
-            let l: f64 = d.to_f64() * scale;
-            let p = Poisson::new(l).unwrap();
-            *n = p.sample(&mut rng);
-        } else {
-            *n = 0.0;
-        }
-    });
-
-    n_data
+    let mut rng = seed.into().single();
+
+    data.iter()
+        .map(|d| sample_poisson(d.to_f64(), scale, &mut rng))
+        .collect()
 }
 
 /// Simulate Poisson noise on a 1-dimensional array.
@@ -65,24 +59,26 @@ where
 ///
 /// * `data`: The input 1-dimensional array view to mutate.
 /// * `scale`: The scale factor.
-/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
-pub fn poisson_1d_mut(data: &mut [f64], scale: f64, seed: Option<u64>) {
+/// * `seed`: Pseudorandom number generator seed, default = 0. Pass `&mut rng`
+///    to draw the next stream of an `Rng` context instead.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the noise was applied.
+/// * `Err(ImgalError)`: If a scaled value exceeds the largest Poisson lambda.
+pub fn poisson_1d_mut(
+    data: &mut [f64],
+    scale: f64,
+    seed: impl Into<Seed>,
+) -> Result<(), ImgalError> {
     // set optional parameters if needed
-    let s = seed.unwrap_or(0);
-    let mut rng = StdRng::seed_from_u64(s);
+    let mut rng = seed.into().single();
 
     // mutate the 1d data array
-    data.iter_mut().for_each(|x| {
-        if *x > 0.0 {
-            let l = *x * scale;
-            let p = Poisson::new(l).unwrap();
-            *x = p.sample(&mut rng);
-        } else {
-            *x = 0.0;
-        }
-    });
+    data.iter_mut().try_for_each(|x| {
+        *x = sample_poisson(*x, scale, &mut rng)?;
+        Ok(())
+    })
 }
 
 /// Simulate Poisson noise on a 3-dimensional array.
@@ -100,75 +96,40 @@ pub fn poisson_1d_mut(data: &mut [f64], scale: f64, seed: Option<u64>) {
 /// * `data`: The input 3-dimensional array.
 /// * `scale`: The scale factor.
 /// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
+///    homogenous noise to each lane of the input array. If `None`, then
+///    heterogenous noise is applied to the input array. Pass `&mut rng` to draw
+///    the next stream of an `Rng` context, each lane is then sampled with its
+///    own reproducible generator.
 /// * `axis`: The signal data axis, default = 2.
 ///
 /// # Returns
 ///
 /// * `Ok(Array3<f64>)`: A 3-dimensional array of the input data with Poisson noise
 ///    applied.
-/// * `Err(ArrayError)`: If axis >= 3.
+/// * `Err(ImgalError)`: If axis >= 3. If scale is <= 0.0. If a scaled value
+///    exceeds the largest Poisson lambda.
 pub fn poisson_3d<T>(
     data: ArrayView3<T>,
     scale: f64,
-    seed: Option<u64>,
+    seed: impl Into<Seed>,
     axis: Option<usize>,
-) -> Result<Array3<f64>, ArrayError>
+) -> Result<Array3<f64>, ImgalError>
 where
     T: ToFloat64,
 {
     // set optional parameters if needed
     let a = axis.unwrap_or(2);
 
-    // check if axis parameter is valid
-    if a >= 3 {
-        return Err(ArrayError::InvalidAxis {
-            axis_idx: a,
-            dim_len: 3,
-        });
-    }
+    // check if scale and axis parameters are valid
+    validate::positive("scale", scale)?;
+    validate::axis(a, 3)?;
 
     // allocate new array of same shape for noise data
     let shape = data.dim();
     let mut n_data = Array3::<f64>::zeros(shape);
 
     // apply and store Poisson noise data in new array
-    let src_lanes = data.lanes(Axis(a));
-    let dst_lanes = n_data.lanes_mut(Axis(a));
-    if let Some(s) = seed {
-        // apply noise with one seed, homogenous noise
-        Zip::from(src_lanes)
-            .and(dst_lanes)
-            .par_for_each(|s_ln, d_ln| {
-                let mut rng = StdRng::seed_from_u64(s);
-                Zip::from(s_ln).and(d_ln).for_each(|s, d| {
-                    if (*s).to_f64() > 0.0 {
-                        let l = (*s).to_f64() * scale;
-                        let p = Poisson::new(l).unwrap();
-                        *d = p.sample(&mut rng);
-                    } else {
-                        *d = 0.0;
-                    }
-                });
-            });
-    } else {
-        // apply noise with variable seeds, hetergenous noise
-        Zip::from(src_lanes)
-            .and(dst_lanes)
-            .par_for_each(|s_ln, d_ln| {
-                let mut rng = rand::rng();
-                Zip::from(s_ln).and(d_ln).for_each(|s, d| {
-                    if (*s).to_f64() > 0.0 {
-                        let l = (*s).to_f64() * scale;
-                        let p = Poisson::new(l).unwrap();
-                        *d = p.sample(&mut rng);
-                    } else {
-                        *d = 0.0
-                    }
-                });
-            });
-    }
+    poisson_lanes(data, n_data.view_mut(), scale, seed.into(), a)?;
 
     Ok(n_data)
 }
@@ -179,7 +140,9 @@ where
 ///
 /// This function applies Poisson noise (_i.e._ shot noise) on a 3-dimensional
 /// array of data. An element-wise lambda value (scaled by the `scale` parameter)
-/// is used to simulate Poisson noise with variable signal strength.
+/// is used to simulate Poisson noise with variable signal strength. The array
+/// keeps its element type, noisy values are rounded and saturated at the
+/// bounds of integer types.
 ///
 /// This function mutates the input array and does not create a new array.
 ///
@@ -188,45 +151,196 @@ where
 /// * `data`: The input 3-dimensional array to mutate.
 /// * `scale`: The scale factor.
 /// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
-///    homogenous noise to the input array. If `None`, then heterogenous noise
-///    is applied to the input array.
+///    homogenous noise to each lane of the input array. If `None`, then
+///    heterogenous noise is applied to the input array. Pass `&mut rng` to draw
+///    the next stream of an `Rng` context, each lane is then sampled with its
+///    own reproducible generator.
 /// * `axis`: The signal data axis, default = 2.
-pub fn poisson_3d_mut(
-    mut data: ArrayViewMut3<f64>,
+///
+/// # Returns
+///
+/// * `Ok(())`: If the noise was applied.
+/// * `Err(ImgalError)`: If axis >= 3. If scale is <= 0.0. If a scaled value
+///    exceeds the largest Poisson lambda.
+pub fn poisson_3d_mut<T>(
+    mut data: ArrayViewMut3<T>,
     scale: f64,
-    seed: Option<u64>,
+    seed: impl Into<Seed>,
     axis: Option<usize>,
-) {
+) -> Result<(), ImgalError>
+where
+    T: ToFloat64 + FromFloat64,
+{
     // set optional parameters if needed
     let a = axis.unwrap_or(2);
 
+    // check if scale and axis parameters are valid
+    validate::positive("scale", scale)?;
+    validate::axis(a, 3)?;
+
     // apply noise to each lane
-    let lanes = data.lanes_mut(Axis(a));
-    if let Some(s) = seed {
-        // apply noise with one seed, homogeneous noise
-        lanes.into_iter().par_bridge().for_each(|mut ln| {
-            if let Some(l) = ln.as_slice_mut() {
-                poisson_1d_mut(l, scale, Some(s));
-            } else {
-                let mut l = ln.to_vec();
-                poisson_1d_mut(&mut l, scale, Some(s));
-                let l = ArrayView1::from(&l);
-                ln.assign(&l);
-            }
-        });
+    poisson_lanes_mut(data.view_mut(), scale, seed.into(), a)
+}
+
+/// Simulate Poisson noise on an n-dimensional array.
+///
+/// # Description
+///
+/// This function applies Poisson noise (_i.e._ shot noise) on an n-dimensional
+/// array of data. An element-wise lambda value (scaled by the `scale` parameter)
+/// is used to simulate Poisson noise with variable signal strength.
+///
+/// The output array has the same element type as the input array, _e.g._ a
+/// `u16` acquisition stays `u16` without an intermediate `f64` copy. Noisy
+/// values are rounded and saturated at the bounds of integer types.
+///
+/// This function creates a new array and does not mutate the input array.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array.
+/// * `scale`: The scale factor.
+/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
+///    homogenous noise to each lane of the input array. If `None`, then
+///    heterogenous noise is applied to the input array. Pass `&mut rng` to draw
+///    the next stream of an `Rng` context, each lane is then sampled with its
+///    own reproducible generator.
+/// * `axis`: The signal data axis, default = the last axis.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<T>)`: An n-dimensional array of the input data with Poisson
+///    noise applied.
+/// * `Err(ImgalError)`: If axis is >= the number of dimensions. If scale is
+///    <= 0.0. If a scaled value exceeds the largest Poisson lambda.
+pub fn poisson<T>(
+    data: ArrayViewD<T>,
+    scale: f64,
+    seed: impl Into<Seed>,
+    axis: Option<usize>,
+) -> Result<ArrayD<T>, ImgalError>
+where
+    T: ToFloat64 + FromFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(data.ndim().saturating_sub(1));
+
+    // check if scale and axis parameters are valid
+    validate::positive("scale", scale)?;
+    validate::axis(a, data.ndim())?;
+
+    // allocate new array of same shape and type for noise data
+    let mut n_data = ArrayD::<T>::default(data.raw_dim());
+    poisson_lanes(data, n_data.view_mut(), scale, seed.into(), a)?;
+
+    Ok(n_data)
+}
+
+/// Simulate Poisson noise on an n-dimensional array.
+///
+/// # Description
+///
+/// This function applies Poisson noise (_i.e._ shot noise) on an n-dimensional
+/// array of data. An element-wise lambda value (scaled by the `scale` parameter)
+/// is used to simulate Poisson noise with variable signal strength. Noisy
+/// values are rounded and saturated at the bounds of integer types.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// # Arguments
+///
+/// * `data`: The input n-dimensional array to mutate.
+/// * `scale`: The scale factor.
+/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
+///    homogenous noise to each lane of the input array. If `None`, then
+///    heterogenous noise is applied to the input array. Pass `&mut rng` to draw
+///    the next stream of an `Rng` context, each lane is then sampled with its
+///    own reproducible generator.
+/// * `axis`: The signal data axis, default = the last axis.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the noise was applied.
+/// * `Err(ImgalError)`: If axis is >= the number of dimensions. If scale is
+///    <= 0.0. If a scaled value exceeds the largest Poisson lambda.
+pub fn poisson_mut<T>(
+    data: ArrayViewMutD<T>,
+    scale: f64,
+    seed: impl Into<Seed>,
+    axis: Option<usize>,
+) -> Result<(), ImgalError>
+where
+    T: ToFloat64 + FromFloat64,
+{
+    // set optional parameters if needed
+    let a = axis.unwrap_or(data.ndim().saturating_sub(1));
+
+    // check if scale and axis parameters are valid
+    validate::positive("scale", scale)?;
+    validate::axis(a, data.ndim())?;
+
+    // apply noise to each lane in place
+    poisson_lanes_mut(data, scale, seed.into(), a)
+}
+
+/// Apply Poisson noise to each lane of the source array and store it in the
+/// destination array.
+fn poisson_lanes<S, T, D>(
+    src: ArrayView<S, D>,
+    mut dst: ArrayViewMut<T, D>,
+    scale: f64,
+    seed: Seed,
+    axis: usize,
+) -> Result<(), ImgalError>
+where
+    S: ToFloat64,
+    T: FromFloat64,
+    D: Dimension,
+{
+    src.lanes(Axis(axis))
+        .into_iter()
+        .zip(dst.lanes_mut(Axis(axis)))
+        .enumerate()
+        .par_bridge()
+        .try_for_each(|(i, (s_ln, mut d_ln))| {
+            let mut rng = seed.lane(i);
+            s_ln.iter().zip(d_ln.iter_mut()).try_for_each(|(s, d)| {
+                *d = T::from_f64(sample_poisson((*s).to_f64(), scale, &mut rng)?);
+                Ok(())
+            })
+        })
+}
+
+/// Apply Poisson noise to each lane of an array in place.
+fn poisson_lanes_mut<T, D>(
+    mut data: ArrayViewMut<T, D>,
+    scale: f64,
+    seed: Seed,
+    axis: usize,
+) -> Result<(), ImgalError>
+where
+    T: ToFloat64 + FromFloat64,
+    D: Dimension,
+{
+    data.lanes_mut(Axis(axis))
+        .into_iter()
+        .enumerate()
+        .par_bridge()
+        .try_for_each(|(i, mut ln)| {
+            let mut rng = seed.lane(i);
+            ln.iter_mut().try_for_each(|v| {
+                *v = T::from_f64(sample_poisson(v.to_f64(), scale, &mut rng)?);
+                Ok(())
+            })
+        })
+}
+
+/// Sample a Poisson distributed value with lambda = value × scale, values
+/// <= 0.0 are set to 0.0.
+fn sample_poisson<R: Rng>(value: f64, scale: f64, rng: &mut R) -> Result<f64, ImgalError> {
+    if value > 0.0 {
+        draw_poisson(value * scale, rng)
     } else {
-        // apply noise with variable seeds, hetergeneous noise
-        lanes.into_iter().par_bridge().for_each(|mut ln| {
-            let mut rng = rand::rng();
-            let s = rng.next_u64();
-            if let Some(l) = ln.as_slice_mut() {
-                poisson_1d_mut(l, scale, Some(s));
-            } else {
-                let mut l = ln.to_vec();
-                poisson_1d_mut(&mut l, scale, Some(s));
-                let l = ArrayView1::from(&l);
-                ln.assign(&l);
-            }
-        });
+        Ok(0.0)
     }
 }
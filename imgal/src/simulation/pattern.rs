@@ -0,0 +1,341 @@
+use std::f64::consts::PI;
+
+use ndarray::{Array2, Axis, Zip};
+use rand::Rng;
+use rand_distr::StandardNormal;
+use rayon::prelude::*;
+use rustfft::{FftPlanner, num_complex::Complex};
+
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::simulation::rng::Seed;
+
+/// Simulate a 2-dimensional checkerboard pattern.
+///
+/// # Description
+///
+/// This function creates a checkerboard of alternating square tiles with
+/// values of 0.0 and 1.0. The top left tile has a value of 0.0.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the image.
+/// * `square_size`: The side length of each square tile, in pixels.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional checkerboard image.
+/// * `Err(ImgalError)`: If square_size is 0.
+pub fn checkerboard_2d(
+    shape: (usize, usize),
+    square_size: usize,
+) -> Result<Array2<f64>, ImgalError> {
+    validate::at_least("square_size", square_size as f64, 1.0)?;
+
+    Ok(Array2::from_shape_fn(shape, |(r, c)| {
+        ((r / square_size + c / square_size) % 2) as f64
+    }))
+}
+
+/// Simulate a 2-dimensional sinusoidal grating.
+///
+/// # Description
+///
+/// This function creates a sinusoidal grating with values in [0, 1]:
+///
+/// ```text
+/// I(r, c) = 0.5 + 0.5 × cos(2π × f × (c × cos(θ) + r × sin(θ)) + φ)
+/// ```
+///
+/// where `f` is the spatial frequency in cycles per pixel, `θ` is the
+/// orientation angle and `φ` is the phase offset.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the image.
+/// * `frequency`: The spatial frequency in cycles per pixel. Must be in the
+///   range [0, 0.5] (_i.e._ at or below the Nyquist frequency).
+/// * `angle`: The orientation angle of the wave vector, in radians, measured
+///   from the col axis, default = 0.0 (_i.e._ vertical stripes).
+/// * `phase`: The phase offset, in radians, default = 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional grating image.
+/// * `Err(ImgalError)`: If frequency is outside of [0, 0.5]. If angle or phase
+///   is not finite.
+pub fn sinusoidal_grating_2d(
+    shape: (usize, usize),
+    frequency: f64,
+    angle: Option<f64>,
+    phase: Option<f64>,
+) -> Result<Array2<f64>, ImgalError> {
+    // set optional parameters if needed
+    let theta = angle.unwrap_or(0.0);
+    let phi = phase.unwrap_or(0.0);
+
+    // check if the parameters are valid
+    validate::in_range("frequency", frequency, 0.0, 0.5)?;
+    if !theta.is_finite() || !phi.is_finite() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "angle",
+            reason: format!(
+                "the angle and phase must be finite but got {} and {}",
+                theta, phi
+            ),
+        });
+    }
+    let (kc, kr) = (
+        2.0 * PI * frequency * theta.cos(),
+        2.0 * PI * frequency * theta.sin(),
+    );
+
+    Ok(Array2::from_shape_fn(shape, |(r, c)| {
+        0.5 + 0.5 * (kc * c as f64 + kr * r as f64 + phi).cos()
+    }))
+}
+
+/// Simulate a 2-dimensional noise field with a power law power spectrum.
+///
+/// # Description
+///
+/// This function creates a noise field whose radially averaged power spectrum
+/// follows a power law:
+///
+/// ```text
+/// P(k) ∝ 1 / kᵝ
+/// ```
+///
+/// where `k` is the radial spatial frequency and `β` is the spectral exponent,
+/// _e.g._ 0.0 for white, 1.0 for pink and 2.0 for brown noise. See
+/// `noise_field_2d`.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the image.
+/// * `exponent`: The spectral exponent, β. Must be >= 0.0.
+/// * `seed`: Pseudorandom number generator seed, default = 0. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context instead.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional noise field with zero mean and unit
+///   standard deviation.
+/// * `Err(ImgalError)`: If exponent is < 0.0. If the image has no pixels.
+pub fn power_law_noise_2d(
+    shape: (usize, usize),
+    exponent: f64,
+    seed: impl Into<Seed>,
+) -> Result<Array2<f64>, ImgalError> {
+    validate::at_least("exponent", exponent, 0.0)?;
+
+    noise_field_2d(shape, |k| k.powf(-exponent), seed)
+}
+
+/// Simulate a 2-dimensional noise field with a given power spectrum.
+///
+/// # Description
+///
+/// This function filters white Gaussian noise in the frequency domain by the
+/// square root of the `power_spectrum` function, which is evaluated at the
+/// radial spatial frequency `k` (in cycles per pixel, in (0, √0.5]) of each
+/// Fourier coefficient. The zero frequency (_i.e._ mean) coefficient is
+/// removed and the noise field is scaled to zero mean and unit standard
+/// deviation.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the image.
+/// * `power_spectrum`: The power spectrum, P(k), as a function of the radial
+///   spatial frequency. Must return finite values >= 0.0.
+/// * `seed`: Pseudorandom number generator seed, default = 0. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context instead.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional noise field with zero mean and unit
+///   standard deviation. If the filtered noise has no variance the noise field
+///   is 0.0.
+/// * `Err(ImgalError)`: If the image has no pixels. If the power spectrum is
+///   negative or not finite.
+pub fn noise_field_2d<F>(
+    shape: (usize, usize),
+    power_spectrum: F,
+    seed: impl Into<Seed>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    F: Fn(f64) -> f64,
+{
+    // set optional parameters if needed
+    let mut rng = seed.into().single();
+
+    // check if the shape is valid
+    let (rows, cols) = shape;
+    validate::at_least("shape", (rows * cols) as f64, 1.0)?;
+
+    // white Gaussian noise
+    let mut buf = Array2::from_shape_simple_fn(shape, || {
+        Complex::new(rng.sample::<f64, _>(StandardNormal), 0.0)
+    });
+
+    // filter the noise spectrum by the amplitude spectrum, √P(k)
+    fft_2d(&mut buf, false);
+    let mut invalid = false;
+    Zip::indexed(&mut buf).for_each(|(r, c), v| {
+        let fr = signed_frequency(r, rows);
+        let fc = signed_frequency(c, cols);
+        let k = (fr * fr + fc * fc).sqrt();
+        if k == 0.0 {
+            *v = Complex::new(0.0, 0.0);
+            return;
+        }
+        let p = power_spectrum(k);
+        if !p.is_finite() || p < 0.0 {
+            invalid = true;
+            return;
+        }
+        *v *= p.sqrt();
+    });
+    if invalid {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "power_spectrum",
+            reason: "must return finite values >= 0.0".to_string(),
+        });
+    }
+    fft_2d(&mut buf, true);
+
+    // normalize to zero mean and unit standard deviation
+    let mut out = buf.mapv(|v| v.re);
+    let n = out.len() as f64;
+    let mean = out.sum() / n;
+    let std = (out.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if std > 0.0 {
+        out.mapv_inplace(|v| (v - mean) / std);
+    } else {
+        out.fill(0.0);
+    }
+
+    Ok(out)
+}
+
+/// Simulate a 2-dimensional image of randomly placed Gaussian blobs.
+///
+/// # Description
+///
+/// This function places `round(density × rows × cols)` Gaussian blobs at
+/// uniformly random (subpixel) positions. Each blob has a peak value of 1.0
+/// and overlapping blobs are summed:
+///
+/// ```text
+/// I(r, c) = Σᵢ exp(-((r - rᵢ)² + (c - cᵢ)²) / (2σ²))
+/// ```
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the image.
+/// * `density`: The number of blobs per pixel, _e.g._ 0.001 places 10 blobs
+///   in a 100 × 100 image. Must be >= 0.0.
+/// * `sigma`: The standard deviation (_i.e._ size) of each blob, in pixels.
+///   Must be > 0.0.
+/// * `seed`: Pseudorandom number generator seed, default = 0. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context instead.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The 2-dimensional blob image.
+/// * `Err(ImgalError)`: If density is < 0.0 or sigma is <= 0.0.
+pub fn blobs_2d(
+    shape: (usize, usize),
+    density: f64,
+    sigma: f64,
+    seed: impl Into<Seed>,
+) -> Result<Array2<f64>, ImgalError> {
+    // set optional parameters if needed
+    let mut rng = seed.into().single();
+
+    // check if the parameters are valid
+    validate::at_least("density", density, 0.0)?;
+    validate::positive("sigma", sigma)?;
+
+    // draw the blob centers
+    let (rows, cols) = shape;
+    let count = (density * (rows * cols) as f64).round() as usize;
+    let centers: Vec<(f64, f64)> = (0..count)
+        .map(|_| {
+            (
+                rng.random::<f64>() * rows as f64,
+                rng.random::<f64>() * cols as f64,
+            )
+        })
+        .collect();
+
+    // render each blob within 4 sigma of its center
+    let reach = (4.0 * sigma).ceil();
+    let inv_two_sigma_sq = 1.0 / (2.0 * sigma * sigma);
+    let mut out = Array2::<f64>::zeros(shape);
+    out.axis_iter_mut(Axis(0))
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(r, mut row)| {
+            let rf = r as f64;
+            centers
+                .iter()
+                .filter(|(cr, _)| (rf - cr).abs() <= reach)
+                .for_each(|&(cr, cc)| {
+                    let c_min = (cc - reach).max(0.0) as usize;
+                    let c_max = ((cc + reach) as usize).min(cols.saturating_sub(1));
+                    (c_min..=c_max).for_each(|c| {
+                        let d_sq = (rf - cr).powi(2) + (c as f64 - cc).powi(2);
+                        row[c] += (-d_sq * inv_two_sigma_sq).exp();
+                    });
+                });
+        });
+
+    Ok(out)
+}
+
+/// Compute the signed frequency, in cycles per pixel, of a DFT bin.
+fn signed_frequency(i: usize, n: usize) -> f64 {
+    if i <= n / 2 {
+        i as f64 / n as f64
+    } else {
+        i as f64 / n as f64 - 1.0
+    }
+}
+
+/// Compute the in-place 2-dimensional FFT of an array, the inverse transform is
+/// scaled by 1/n.
+fn fft_2d(buf: &mut Array2<Complex<f64>>, inverse: bool) {
+    let (rows, cols) = buf.dim();
+    let mut planner = FftPlanner::new();
+    let (row_fft, col_fft) = if inverse {
+        (
+            planner.plan_fft_inverse(cols),
+            planner.plan_fft_inverse(rows),
+        )
+    } else {
+        (
+            planner.plan_fft_forward(cols),
+            planner.plan_fft_forward(rows),
+        )
+    };
+
+    // transform each row then each col
+    let mut lane = Vec::with_capacity(rows.max(cols));
+    buf.lanes_mut(Axis(1)).into_iter().for_each(|mut ln| {
+        lane.clear();
+        lane.extend(ln.iter());
+        row_fft.process(&mut lane);
+        ln.iter_mut().zip(lane.iter()).for_each(|(v, l)| *v = *l);
+    });
+    buf.lanes_mut(Axis(0)).into_iter().for_each(|mut ln| {
+        lane.clear();
+        lane.extend(ln.iter());
+        col_fft.process(&mut lane);
+        ln.iter_mut().zip(lane.iter()).for_each(|(v, l)| *v = *l);
+    });
+    if inverse {
+        let scale = 1.0 / (rows * cols) as f64;
+        buf.mapv_inplace(|v| v * scale);
+    }
+}
@@ -0,0 +1,208 @@
+use rand::RngCore;
+use rand::SeedableRng;
+use rand::rand_core::impls;
+use rand::rngs::{StdRng, ThreadRng};
+
+/// The SplitMix64 increment (_i.e._ the golden ratio scaled to 64 bits).
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// A seeded random number generator context for multi-step simulations.
+///
+/// # Description
+///
+/// The `Rng` context hands out independent random streams to the simulation
+/// functions that take a `seed` parameter (pass `&mut rng` instead of a seed).
+/// Each call draws the next stream, and each stream derives one generator per
+/// lane (_e.g._ pixel) from the lane index with a counter-based generator
+/// (SplitMix64). The random numbers of a lane therefore only depend on the
+/// context seed, the call order and the lane index, so a whole multi-step
+/// simulation is exactly reproducible from a single seed regardless of how the
+/// lanes are scheduled across threads.
+///
+/// # Example
+///
+/// ```
+/// use imgal::simulation::{Rng, decay, noise};
+///
+/// let mut rng = Rng::new(42);
+/// let curve = vec![1.0, 4.0, 2.0, 1.0];
+/// let photons = decay::sample_photons_3d(&curve, 100, (4, 4), &mut rng).unwrap();
+/// let noisy = noise::poisson_3d(photons.view(), 1.0, &mut rng, None).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    seed: u64,
+    counter: u64,
+}
+
+impl Rng {
+    /// Create a new random number generator context from a seed.
+    pub fn new(seed: u64) -> Self {
+        Rng { seed, counter: 0 }
+    }
+
+    /// The context seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The number of streams drawn from the context.
+    pub fn streams(&self) -> u64 {
+        self.counter
+    }
+
+    /// Draw the next independent random stream.
+    pub fn stream(&mut self) -> Stream {
+        let key = mix(self.seed ^ mix(self.counter.wrapping_add(GOLDEN_GAMMA)));
+        self.counter += 1;
+
+        Stream { key }
+    }
+}
+
+/// An independent random stream drawn from an `Rng` context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stream {
+    key: u64,
+}
+
+impl Stream {
+    /// Create the counter-based generator of a lane.
+    pub fn lane(&self, index: u64) -> CounterRng {
+        CounterRng {
+            key: mix(self.key ^ mix(index.wrapping_mul(GOLDEN_GAMMA))),
+            counter: 0,
+        }
+    }
+}
+
+/// A counter-based (SplitMix64) random number generator, the n-th output is a
+/// function of the key and n only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterRng {
+    key: u64,
+    counter: u64,
+}
+
+impl RngCore for CounterRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.counter = self.counter.wrapping_add(1);
+        mix(self
+            .key
+            .wrapping_add(self.counter.wrapping_mul(GOLDEN_GAMMA)))
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+/// The source of randomness of a simulation function, either a per-call seed
+/// or a stream of an `Rng` context.
+///
+/// # Description
+///
+/// Simulation functions take any value that converts into a `Seed`:
+///
+/// * `Option<u64>`: A per-call seed, see each function for its seeding
+///   semantics. If `None`, a thread local generator is used.
+/// * `&mut Rng`: The next stream of an `Rng` context, lane `i` of the output
+///   uses the counter-based generator of lane `i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seed {
+    Fixed(Option<u64>),
+    Stream(Stream),
+}
+
+impl From<Option<u64>> for Seed {
+    fn from(seed: Option<u64>) -> Self {
+        Seed::Fixed(seed)
+    }
+}
+
+impl From<u64> for Seed {
+    fn from(seed: u64) -> Self {
+        Seed::Fixed(Some(seed))
+    }
+}
+
+impl From<&mut Rng> for Seed {
+    fn from(rng: &mut Rng) -> Self {
+        Seed::Stream(rng.stream())
+    }
+}
+
+impl Seed {
+    /// Create a single generator, a `None` seed defaults to 0.
+    pub(crate) fn single(&self) -> LaneRng {
+        match self {
+            Seed::Fixed(s) => LaneRng::Std(Box::new(StdRng::seed_from_u64(s.unwrap_or(0)))),
+            Seed::Stream(st) => LaneRng::Counter(st.lane(0)),
+        }
+    }
+
+    /// Create the generator of a lane, a fixed seed seeds every lane with the
+    /// same seed.
+    pub(crate) fn lane(&self, index: usize) -> LaneRng {
+        match self {
+            Seed::Fixed(Some(s)) => LaneRng::Std(Box::new(StdRng::seed_from_u64(*s))),
+            Seed::Fixed(None) => LaneRng::Thread(rand::rng()),
+            Seed::Stream(st) => LaneRng::Counter(st.lane(index as u64)),
+        }
+    }
+
+    /// Create the generator of a lane, a fixed seed seeds each lane with the
+    /// seed offset by the lane index.
+    pub(crate) fn lane_offset(&self, index: usize) -> LaneRng {
+        match self {
+            Seed::Fixed(Some(s)) => LaneRng::Std(Box::new(StdRng::seed_from_u64(
+                s.wrapping_add(index as u64),
+            ))),
+            _ => self.lane(index),
+        }
+    }
+}
+
+/// The generator of a lane.
+pub(crate) enum LaneRng {
+    Std(Box<StdRng>),
+    Thread(ThreadRng),
+    Counter(CounterRng),
+}
+
+impl RngCore for LaneRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            LaneRng::Std(r) => r.next_u32(),
+            LaneRng::Thread(r) => r.next_u32(),
+            LaneRng::Counter(r) => r.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            LaneRng::Std(r) => r.next_u64(),
+            LaneRng::Thread(r) => r.next_u64(),
+            LaneRng::Counter(r) => r.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        match self {
+            LaneRng::Std(r) => r.fill_bytes(dst),
+            LaneRng::Thread(r) => r.fill_bytes(dst),
+            LaneRng::Counter(r) => r.fill_bytes(dst),
+        }
+    }
+}
+
+/// The SplitMix64 output mixing function.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,285 @@
+use std::f64::consts::LN_2;
+
+use ndarray::{Array3, ArrayView3, Axis, Zip};
+
+use crate::distribution::gaussian;
+use crate::error::ImgalError;
+use crate::error::validate;
+use crate::simulation::noise;
+use crate::simulation::rng::Seed;
+use crate::statistics::sum;
+
+/// Simulate a 1-dimensional Gaussian emission spectrum.
+///
+/// # Description
+///
+/// This function creates a Gaussian emission spectrum sampled at `channels`
+/// evenly spaced wavelengths spanning `wavelength_range` (inclusive). The
+/// spectrum is normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `channels`: The number of spectral channels.
+/// * `wavelength_range`: The first and last channel wavelengths.
+/// * `peak`: The emission peak wavelength.
+/// * `width`: The full width at half maximum (FWHM) of the emission peak.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The normalized 1-dimensional emission spectrum.
+/// * `Err(ImgalError)`: If channels is < 2. If the wavelength range is empty. If
+///   width is <= 0.0. If the peak lies entirely outside of the wavelength range.
+pub fn gaussian_spectrum_1d(
+    channels: usize,
+    wavelength_range: (f64, f64),
+    peak: f64,
+    width: f64,
+) -> Result<Vec<f64>, ImgalError> {
+    // check channels, wavelength range and width parameters
+    validate::at_least("channels", channels as f64, 2.0)?;
+    validate::positive("wavelength_range", wavelength_range.1 - wavelength_range.0)?;
+    validate::positive("width", width)?;
+
+    let sigma = width / (2.0 * (2.0 * LN_2).sqrt());
+
+    gaussian(
+        sigma,
+        channels,
+        wavelength_range.1 - wavelength_range.0,
+        peak - wavelength_range.0,
+    )
+}
+
+/// Simulate a 3-dimensional set of linear gradient abundance maps.
+///
+/// # Description
+///
+/// This function creates abundance maps for `components` spectral components
+/// that vary smoothly along the column axis. Each component peaks at an evenly
+/// spaced column and falls off linearly to zero at its neighbors' peaks, so the
+/// abundances of every pixel sum to 1.0. For two components this is a linear
+/// ramp from the first to the second component.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the abundance maps.
+/// * `components`: The number of spectral components.
+///
+/// # Returns
+///
+/// * `Array3<f64>`: The abundance maps with shape (row, col, component).
+pub fn gradient_abundances_3d(shape: (usize, usize), components: usize) -> Array3<f64> {
+    let mut abundances = Array3::<f64>::zeros((shape.0, shape.1, components));
+    if components == 0 {
+        return abundances;
+    }
+    if components == 1 {
+        abundances.fill(1.0);
+        return abundances;
+    }
+
+    // position of each column in component units, i.e. [0, components - 1]
+    let segments = (components - 1) as f64;
+    let denom = (shape.1.max(2) - 1) as f64;
+    abundances
+        .lanes_mut(Axis(2))
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, mut ln)| {
+            let col = i % shape.1;
+            let pos = col as f64 / denom * segments;
+            ln.iter_mut().enumerate().for_each(|(k, v)| {
+                *v = (1.0 - (pos - k as f64).abs()).max(0.0);
+            });
+        });
+
+    abundances
+}
+
+/// Simulate a 3-dimensional hyperspectral image as a linear mixture of spectra.
+///
+/// # Description
+///
+/// This function generates a hyperspectral image cube where each pixel is the
+/// linear mixture of the input emission spectra weighted by the pixel's
+/// abundances:
+///
+/// ```text
+/// I(x, y, λ) = N × Σₖ aₖ(x, y) × Sₖ(λ)
+/// ```
+///
+/// where `N` is the total counts, `aₖ` is the abundance map and `Sₖ` is the
+/// normalized spectrum of component `k`.
+///
+/// # Arguments
+///
+/// * `spectra`: The emission spectra of each component, each is normalized to
+///   sum to 1.0. All spectra must have the same number of channels.
+/// * `abundances`: The abundance maps with shape (row, col, component), see
+///   `gradient_abundances_3d`.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of a pixel
+///   with abundances that sum to 1.0.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The hyperspectral image with shape (row, col, channel).
+/// * `Err(ImgalError)`: If no spectra are given. If the spectra lengths do not
+///   match. If a spectrum has no positive values. If the number of abundance
+///   maps does not match the number of spectra.
+pub fn linear_mixture_3d(
+    spectra: &[Vec<f64>],
+    abundances: ArrayView3<f64>,
+    total_counts: f64,
+) -> Result<Array3<f64>, ImgalError> {
+    // check the spectra and abundance maps match
+    if spectra.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "spectra",
+            reason: "must contain at least one spectrum".to_string(),
+        });
+    }
+    let channels = spectra[0].len();
+    spectra
+        .iter()
+        .try_for_each(|sp| validate::same_length(channels, sp.len()))?;
+    validate::same_length(spectra.len(), abundances.len_of(Axis(2)))?;
+
+    // normalize the spectra
+    let norm_spectra = spectra
+        .iter()
+        .map(|sp| {
+            let sp_sum = sum(sp);
+            if sp_sum <= 0.0 || !sp_sum.is_finite() {
+                return Err(ImgalError::InvalidParameter {
+                    param_name: "spectra",
+                    reason: "each spectrum must have a finite positive sum".to_string(),
+                });
+            }
+            Ok(sp.iter().map(|v| v / sp_sum).collect::<Vec<f64>>())
+        })
+        .collect::<Result<Vec<Vec<f64>>, ImgalError>>()?;
+
+    // mix the spectra per pixel
+    let (rows, cols, _) = abundances.dim();
+    let mut cube = Array3::<f64>::zeros((rows, cols, channels));
+    Zip::from(cube.lanes_mut(Axis(2)))
+        .and(abundances.lanes(Axis(2)))
+        .par_for_each(|mut px, ab| {
+            ab.iter().zip(norm_spectra.iter()).for_each(|(&a, sp)| {
+                px.iter_mut().zip(sp.iter()).for_each(|(v, s)| {
+                    *v += total_counts * a * s;
+                });
+            });
+        });
+
+    Ok(cube)
+}
+
+/// Simulate a 3-dimensional hyperspectral image as a linear mixture of spectra
+/// with Poisson noise.
+///
+/// # Description
+///
+/// This function generates a hyperspectral image cube with
+/// `linear_mixture_3d` and applies Poisson noise (_i.e._ shot noise) to each
+/// pixel spectrum with `simulation::noise::poisson_3d`.
+///
+/// # Arguments
+///
+/// * `spectra`: The emission spectra of each component, each is normalized to
+///   sum to 1.0. All spectra must have the same number of channels.
+/// * `abundances`: The abundance maps with shape (row, col, component).
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of a pixel
+///   with abundances that sum to 1.0.
+/// * `seed`: Pseudorandom number generator seed. Set the `seed` value to apply
+///   homogenous noise to the image. If `None`, then heterogenous noise is
+///   applied to the image. Pass `&mut rng` to draw the next stream of an `Rng`
+///   context instead.
+///
+/// # Returns
+///
+/// * `Ok(Array3<f64>)`: The noisy hyperspectral image with shape
+///   (row, col, channel).
+/// * `Err(ImgalError)`: If the spectra or abundance maps are invalid, see
+///   `linear_mixture_3d`.
+pub fn linear_mixture_poisson_3d(
+    spectra: &[Vec<f64>],
+    abundances: ArrayView3<f64>,
+    total_counts: f64,
+    seed: impl Into<Seed>,
+) -> Result<Array3<f64>, ImgalError> {
+    let cube = linear_mixture_3d(spectra, abundances, total_counts)?;
+
+    noise::poisson_3d(cube.view(), 1.0, seed, Some(2))
+}
+
+/// Create a 1-dimensional emission spectrum from registered (_e.g._ measured
+/// or library) spectral data.
+///
+/// # Description
+///
+/// This function resamples a registered emission spectrum onto `channels`
+/// evenly spaced wavelengths spanning `wavelength_range` (inclusive) using
+/// linear interpolation. Channels outside of the registered wavelengths are
+/// set to 0.0, negative values are clamped to 0.0 and the spectrum is
+/// normalized to sum to 1.0.
+///
+/// # Arguments
+///
+/// * `data`: The registered spectrum intensities.
+/// * `wavelengths`: The wavelength of each value in `data`, in ascending order.
+/// * `channels`: The number of spectral channels.
+/// * `wavelength_range`: The first and last channel wavelengths.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The resampled and normalized 1-dimensional emission
+///   spectrum.
+/// * `Err(ImgalError)`: If `data` and `wavelengths` lengths do not match or have
+///   less than 2 points. If channels is < 2. If the resampled spectrum has no
+///   positive values.
+pub fn registered_spectrum_1d(
+    data: &[f64],
+    wavelengths: &[f64],
+    channels: usize,
+    wavelength_range: (f64, f64),
+) -> Result<Vec<f64>, ImgalError> {
+    // check the registered spectrum and channel parameters
+    validate::same_length(data.len(), wavelengths.len())?;
+    validate::at_least("data", data.len() as f64, 2.0)?;
+    validate::at_least("channels", channels as f64, 2.0)?;
+    validate::positive("wavelength_range", wavelength_range.1 - wavelength_range.0)?;
+
+    // linearly interpolate the registered spectrum onto the channels
+    let step = (wavelength_range.1 - wavelength_range.0) / (channels - 1) as f64;
+    let last = wavelengths.len() - 1;
+    let mut spectrum: Vec<f64> = (0..channels)
+        .map(|i| {
+            let wl = wavelength_range.0 + i as f64 * step;
+            if wl < wavelengths[0] || wl > wavelengths[last] {
+                return 0.0;
+            }
+            let hi = wavelengths.partition_point(|&w| w < wl).clamp(1, last);
+            let lo = hi - 1;
+            let span = wavelengths[hi] - wavelengths[lo];
+            let frac = if span > 0.0 {
+                (wl - wavelengths[lo]) / span
+            } else {
+                0.0
+            };
+            (data[lo] * (1.0 - frac) + data[hi] * frac).max(0.0)
+        })
+        .collect();
+
+    // normalize the resampled spectrum
+    let sp_sum = sum(&spectrum);
+    if sp_sum <= 0.0 || !sp_sum.is_finite() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must have positive values within the wavelength range".to_string(),
+        });
+    }
+    spectrum.iter_mut().for_each(|v| *v /= sp_sum);
+
+    Ok(spectrum)
+}
@@ -0,0 +1,269 @@
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::distribution::{inverse_normal_cdf, normal_cdf};
+use crate::error::{ImgalError, validate};
+use crate::simulation::rng::Seed;
+use crate::statistics::quantile::quantile_sorted;
+
+/// The bootstrap estimate and confidence intervals of a statistic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bootstrap {
+    /// The statistic of the original data.
+    pub estimate: f64,
+    /// The lower and upper bounds of the percentile confidence interval.
+    pub percentile: (f64, f64),
+    /// The lower and upper bounds of the bias-corrected and accelerated (BCa)
+    /// confidence interval.
+    pub bca: (f64, f64),
+    /// The statistic of each resampled data set.
+    pub replicates: Vec<f64>,
+}
+
+/// Compute bootstrap confidence intervals of a statistic.
+///
+/// # Description
+///
+/// This function estimates the sampling distribution of a statistic by
+/// resampling the data with replacement and computing the statistic of each
+/// resampled data set (_i.e._ replicate). The percentile interval is given by
+/// the `(1 - c) / 2` and `(1 + c) / 2` quantiles of the replicates, where `c`
+/// is the confidence level. The bias-corrected and accelerated (BCa) interval
+/// adjusts these quantiles for the median bias and the skewness of the
+/// replicates:
+///
+/// ```text
+/// z₀ = Φ⁻¹(#{θ* < θ} / B)
+/// a  = Σ(θ̄₍.₎ - θ₍ᵢ₎)³ / (6 × (Σ(θ̄₍.₎ - θ₍ᵢ₎)²)^(3/2))
+/// α  = Φ(z₀ + (z₀ + z_α) / (1 - a × (z₀ + z_α)))
+/// ```
+///
+/// where `θ` is the estimate, `θ*` are the `B` replicates (ties count as
+/// half), `θ₍ᵢ₎` is the statistic of the data without the i-th value
+/// (_i.e._ the jackknife) and `θ̄₍.₎` their mean. The jackknife computes the
+/// statistic once per value, which dominates the run time for large data
+/// sets. The replicates and the jackknife are computed in parallel,
+/// non-finite replicates are excluded from the intervals.
+///
+/// # Arguments
+///
+/// * `data`: The 1-dimensional data. Must have at least 2 values.
+/// * `statistic`: The function computing the statistic of a data set.
+/// * `iterations`: The number of replicates, default = 1000. Must be >= 1.
+/// * `confidence`: The confidence level, default = 0.95. Must be in (0, 1).
+/// * `seed`: Pseudorandom number generator seed, the replicate `i` is seeded
+///   with `seed + i`. If `None`, a thread local generator is used. Pass
+///   `&mut rng` to draw the next stream of an `Rng` context, each replicate is
+///   then resampled with its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok(Bootstrap)`: The estimate, the percentile and BCa confidence intervals
+///   and the replicates.
+/// * `Err(ImgalError)`: If the data has less than 2 values. If iterations is 0.
+///   If confidence is not in (0, 1). If the statistic of the data is not
+///   finite.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1080/01621459.1987.10478410>
+pub fn bootstrap<F>(
+    data: &[f64],
+    statistic: F,
+    iterations: Option<usize>,
+    confidence: Option<f64>,
+    seed: impl Into<Seed>,
+) -> Result<Bootstrap, ImgalError>
+where
+    F: Fn(&[f64]) -> f64 + Sync,
+{
+    resample(
+        data.len(),
+        |indices| {
+            let values: Vec<f64> = indices.iter().map(|&i| data[i]).collect();
+            statistic(&values)
+        },
+        iterations,
+        confidence,
+        seed.into(),
+    )
+}
+
+/// Compute bootstrap confidence intervals of a statistic of paired data.
+///
+/// # Description
+///
+/// This function computes the bootstrap confidence intervals of a statistic
+/// of two paired data sets (_e.g._ a correlation coefficient of the pixel
+/// intensities of two channels), see `bootstrap`. The pairs are resampled
+/// together, the i-th value of `data_a` stays paired with the i-th value of
+/// `data_b`.
+///
+/// # Arguments
+///
+/// * `data_a`: The first 1-dimensional data set. Must have at least 2 values
+///   and be the same length as `data_b`.
+/// * `data_b`: The second 1-dimensional data set. Must be the same length as
+///   `data_a`.
+/// * `statistic`: The function computing the statistic of the paired data sets.
+/// * `iterations`: The number of replicates, default = 1000. Must be >= 1.
+/// * `confidence`: The confidence level, default = 0.95. Must be in (0, 1).
+/// * `seed`: Pseudorandom number generator seed, the replicate `i` is seeded
+///   with `seed + i`. If `None`, a thread local generator is used. Pass
+///   `&mut rng` to draw the next stream of an `Rng` context.
+///
+/// # Returns
+///
+/// * `Ok(Bootstrap)`: The estimate, the percentile and BCa confidence intervals
+///   and the replicates.
+/// * `Err(ImgalError)`: If the data set lengths do not match. If the data sets
+///   have less than 2 values. If iterations is 0. If confidence is not in
+///   (0, 1). If the statistic of the data is not finite.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1080/01621459.1987.10478410>
+pub fn paired_bootstrap<F>(
+    data_a: &[f64],
+    data_b: &[f64],
+    statistic: F,
+    iterations: Option<usize>,
+    confidence: Option<f64>,
+    seed: impl Into<Seed>,
+) -> Result<Bootstrap, ImgalError>
+where
+    F: Fn(&[f64], &[f64]) -> f64 + Sync,
+{
+    // check if the input lengths are valid
+    validate::same_length(data_a.len(), data_b.len())?;
+
+    resample(
+        data_a.len(),
+        |indices| {
+            let (a, b): (Vec<f64>, Vec<f64>) =
+                indices.iter().map(|&i| (data_a[i], data_b[i])).unzip();
+            statistic(&a, &b)
+        },
+        iterations,
+        confidence,
+        seed.into(),
+    )
+}
+
+/// Bootstrap a statistic of the data selected by a list of value indices.
+fn resample<F>(
+    n: usize,
+    statistic: F,
+    iterations: Option<usize>,
+    confidence: Option<f64>,
+    seed: Seed,
+) -> Result<Bootstrap, ImgalError>
+where
+    F: Fn(&[usize]) -> f64 + Sync,
+{
+    // set optional parameters if needed
+    let iterations = iterations.unwrap_or(1000);
+    let confidence = confidence.unwrap_or(0.95);
+
+    // check if the data, iterations and confidence parameters are valid
+    if n < 2 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!("must have at least 2 values but got {}", n),
+        });
+    }
+    if iterations == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "iterations",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "confidence",
+            reason: format!("must be in (0, 1) but got {}", confidence),
+        });
+    }
+    let all: Vec<usize> = (0..n).collect();
+    let estimate = statistic(&all);
+    if !estimate.is_finite() {
+        return Err(ImgalError::NumericFailure {
+            operation: "bootstrap",
+            reason: format!("the statistic of the data is {}", estimate),
+        });
+    }
+
+    // compute the statistic of each resampled data set in parallel
+    let replicates: Vec<f64> = (0..iterations)
+        .into_par_iter()
+        .map(|i| {
+            let mut rng = seed.lane_offset(i);
+            let indices: Vec<usize> = (0..n).map(|_| rng.random_range(0..n)).collect();
+            statistic(&indices)
+        })
+        .collect();
+    let mut sorted: Vec<f64> = replicates
+        .iter()
+        .copied()
+        .filter(|r| r.is_finite())
+        .collect();
+    if sorted.is_empty() {
+        return Ok(Bootstrap {
+            estimate,
+            percentile: (f64::NAN, f64::NAN),
+            bca: (f64::NAN, f64::NAN),
+            replicates,
+        });
+    }
+    sorted.par_sort_unstable_by(|a, b| a.total_cmp(b));
+    let alpha = (1.0 - confidence) / 2.0;
+    let percentile = (
+        quantile_sorted(&sorted, alpha),
+        quantile_sorted(&sorted, 1.0 - alpha),
+    );
+
+    // estimate the bias correction from the replicates below the estimate,
+    // the proportion is clamped to the resolution of the replicates
+    let b = sorted.len() as f64;
+    let below = sorted.iter().filter(|&&r| r < estimate).count() as f64;
+    let tied = sorted.iter().filter(|&&r| r == estimate).count() as f64;
+    let prop = ((below + tied / 2.0) / b).clamp(0.5 / b, 1.0 - 0.5 / b);
+    let z_0 = inverse_normal_cdf(prop)?;
+
+    // estimate the acceleration from the jackknife
+    let jackknife: Vec<f64> = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let indices: Vec<usize> = (0..n).filter(|&j| j != i).collect();
+            statistic(&indices)
+        })
+        .collect();
+    let mean = jackknife.iter().sum::<f64>() / n as f64;
+    let (num, den) = jackknife.iter().fold((0.0, 0.0), |acc, &t| {
+        let d = mean - t;
+        (acc.0 + d * d * d, acc.1 + d * d)
+    });
+    let accel = if den > 0.0 {
+        num / (6.0 * den.powf(1.5))
+    } else {
+        0.0
+    };
+
+    // adjust the interval quantiles
+    let z_alpha = inverse_normal_cdf(alpha)?;
+    let adjust = |z: f64| {
+        let s = z_0 + z;
+        normal_cdf(z_0 + s / (1.0 - accel * s)).clamp(0.0, 1.0)
+    };
+    let bca = (
+        quantile_sorted(&sorted, adjust(z_alpha)),
+        quantile_sorted(&sorted, adjust(-z_alpha)),
+    );
+
+    Ok(Bootstrap {
+        estimate,
+        percentile,
+        bca,
+        replicates,
+    })
+}
@@ -0,0 +1,159 @@
+use ndarray::ArrayViewD;
+
+use crate::error::ImgalError;
+use crate::statistics::histogram::masked_values;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the circular mean of phase angles.
+///
+/// # Description
+///
+/// This function computes the mean direction of a set of phase angles (_e.g._
+/// a phasor phase image), the direction of the resultant of their unit
+/// vectors:
+///
+/// ```text
+/// θ̄ = atan2(Σsin(θᵢ), Σcos(θᵢ))
+/// ```
+///
+/// Unlike the arithmetic mean, the circular mean is independent of where the
+/// angles wrap, _e.g._ the mean of 350° and 10° is 0° and not 180°. Non-finite
+/// angles (_e.g._ the phase of pixels without intensity) are ignored.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional array of phase angles in radians.
+/// * `mask`: An optional boolean mask of the same shape as `data`, only
+///   `true` values are included.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The circular mean in radians, within (-π, π]. NaN if the mean
+///   resultant length is (numerically) 0.0, _e.g._ evenly spread angles.
+/// * `Err(ImgalError)`: If the mask shape does not match the data shape. If
+///   there are no finite (masked) angles.
+pub fn circular_mean<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // the direction of a (numerically) zero resultant is undefined
+    let (c, s, n) = resultant(data, mask)?;
+    if c.hypot(s) <= 1e-12 * n {
+        return Ok(f64::NAN);
+    }
+
+    Ok(s.atan2(c))
+}
+
+/// Compute the circular variance of phase angles.
+///
+/// # Description
+///
+/// This function computes the circular variance of a set of phase angles from
+/// the mean resultant length R̄:
+///
+/// ```text
+/// R̄ = √((Σcos(θᵢ))² + (Σsin(θᵢ))²) / n
+/// V = 1 - R̄
+/// ```
+///
+/// The circular variance is 0.0 if all angles are equal and 1.0 if the angles
+/// are spread such that their resultant is 0.0. Non-finite angles are
+/// ignored.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional array of phase angles in radians.
+/// * `mask`: An optional boolean mask of the same shape as `data`, only
+///   `true` values are included.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The circular variance, within [0, 1].
+/// * `Err(ImgalError)`: If the mask shape does not match the data shape. If
+///   there are no finite (masked) angles.
+pub fn circular_variance<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    let (c, s, n) = resultant(data, mask)?;
+    let r = c.hypot(s) / n;
+
+    Ok((1.0 - r).clamp(0.0, 1.0))
+}
+
+/// Test phase angles for uniformity with the Rayleigh test.
+///
+/// # Description
+///
+/// This function tests the null hypothesis that a set of phase angles is
+/// uniformly distributed around the circle against the alternative of a
+/// single preferred direction (_i.e._ a unimodal distribution). The test
+/// statistic and its p-value are:
+///
+/// ```text
+/// Z = n × R̄²
+/// p = exp(√(1 + 4n + 4(n² - R²)) - (1 + 2n))
+/// ```
+///
+/// Where R = n × R̄ is the resultant length, see `circular_variance`. The
+/// p-value uses Zar's approximation, which is accurate for n >= 10.
+/// Non-finite angles are ignored.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional array of phase angles in radians.
+/// * `mask`: An optional boolean mask of the same shape as `data`, only
+///   `true` values are included.
+///
+/// # Returns
+///
+/// * `Ok((f64, f64))`: The Rayleigh Z statistic and the p-value.
+/// * `Err(ImgalError)`: If the mask shape does not match the data shape. If
+///   there are no finite (masked) angles.
+pub fn rayleigh_test<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<(f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    let (c, s, n) = resultant(data, mask)?;
+    let r_sq = c.powi(2) + s.powi(2);
+    let z = r_sq / n;
+    let p = ((1.0 + 4.0 * n + 4.0 * (n.powi(2) - r_sq)).sqrt() - (1.0 + 2.0 * n)).exp();
+
+    Ok((z, p.clamp(0.0, 1.0)))
+}
+
+/// Compute the cosine and sine sums and the count of the finite (masked)
+/// angles.
+fn resultant<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<(f64, f64, f64), ImgalError>
+where
+    T: ToFloat64,
+{
+    let values = masked_values(data, mask)?;
+    let (c, s, n) = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((0.0, 0.0, 0usize), |(c, s, n), v| {
+            (c + v.cos(), s + v.sin(), n + 1)
+        });
+    if n == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must have at least 1 finite angle but got 0".to_string(),
+        });
+    }
+
+    Ok((c, s, n as f64))
+}
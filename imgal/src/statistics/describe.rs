@@ -0,0 +1,101 @@
+use ndarray::ArrayViewD;
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::statistics::histogram::masked_values;
+use crate::statistics::running::RunningStats;
+use crate::traits::numeric::ToFloat64;
+
+/// Descriptive statistics of a set of values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Description {
+    /// The number of values.
+    pub count: usize,
+    /// The arithmetic mean.
+    pub mean: f64,
+    /// The sample variance (_i.e._ with `n - 1` degrees of freedom).
+    pub variance: f64,
+    /// The (biased) sample skewness `g₁`.
+    pub skewness: f64,
+    /// The (biased) sample excess kurtosis `g₂`, 0.0 for a normal distribution.
+    pub kurtosis: f64,
+    /// The minimum value.
+    pub min: f64,
+    /// The maximum value.
+    pub max: f64,
+    /// The median value, the mean of the two middle values for an even count.
+    pub median: f64,
+}
+
+/// Compute the descriptive statistics of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the count, mean, variance, skewness, excess kurtosis,
+/// minimum, maximum and median of the (masked) values of the input
+/// n-dimensional array. The central moments are accumulated in a single
+/// parallel pass by merging `RunningStats` accumulators. The skewness and kurtosis are:
+///
+/// ```text
+/// g₁ = √n × M₃ / M₂^(3/2)
+/// g₂ = n × M₄ / M₂² - 3
+/// ```
+///
+/// where `Mₖ` is the sum of the k-th power deviations from the mean. The
+/// variance is 0.0 for a single value, the skewness and kurtosis are 0.0 if
+/// all values are equal.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional array.
+/// * `mask`: An optional boolean mask, values set to `false` are excluded from
+///   the statistics. Must match the shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok(Description)`: The descriptive statistics.
+/// * `Err(ImgalError)`: If the mask shape does not match the data shape. If the
+///   data is empty or the mask excludes all values.
+pub fn describe<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<Description, ImgalError>
+where
+    T: ToFloat64,
+{
+    let mut values = masked_values(data, mask)?;
+
+    // accumulate the moments in parallel
+    let stats = values
+        .par_iter()
+        .fold(RunningStats::new, |mut acc, &v| {
+            acc.push(v);
+            acc
+        })
+        .reduce(RunningStats::new, |mut a, b| {
+            a.merge(&b);
+            a
+        });
+
+    // select the middle value(s) for the median
+    let count = values.len();
+    let mid = count / 2;
+    let (lower, upper, _) = values.select_nth_unstable_by(mid, |a, b| a.total_cmp(b));
+    let median = if count % 2 == 0 {
+        let below = lower.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (below + *upper) / 2.0
+    } else {
+        *upper
+    };
+
+    Ok(Description {
+        count,
+        mean: stats.mean(),
+        variance: stats.variance(),
+        skewness: stats.skewness(),
+        kurtosis: stats.kurtosis(),
+        min: stats.min(),
+        max: stats.max(),
+        median,
+    })
+}
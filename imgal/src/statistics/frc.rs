@@ -0,0 +1,255 @@
+use ndarray::{Array2, ArrayView2, Axis};
+use rand::RngCore;
+use rayon::prelude::*;
+use rustfft::num_complex::Complex;
+
+use crate::error::{ImgalError, validate};
+use crate::filter::convolve::fft_nd;
+use crate::simulation::rng::Seed;
+use crate::traits::numeric::ToFloat64;
+
+/// Fourier ring correlation resolution criteria.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrcCriterion {
+    /// The fixed 1/7 ≈ 0.143 threshold.
+    OneSeventh,
+    /// The `k`-sigma threshold `k / √(n / 2)`, where `n` is the number of
+    /// pixels in the ring, _i.e._ `k` standard deviations of the correlation of
+    /// pure noise (_e.g._ 3.0).
+    Sigma(f64),
+}
+
+/// A Fourier ring correlation curve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frc {
+    /// The spatial frequency of each ring, in cycles per pixel.
+    pub frequencies: Vec<f64>,
+    /// The correlation of each ring.
+    pub correlation: Vec<f64>,
+    /// The number of pixels of each ring.
+    pub counts: Vec<usize>,
+}
+
+/// Compute the Fourier ring correlation (FRC) of two 2-dimensional images.
+///
+/// # Description
+///
+/// This function computes the normalized cross-correlation of the Fourier
+/// transforms of two independent images of the same sample (_e.g._ two
+/// consecutive acquisitions or the two halves of a split photon count image,
+/// see `split_photons`) over rings of equal spatial frequency:
+///
+/// ```text
+/// FRC(r) = Re(Σ F₁(k) × F₂*(k)) / √(Σ |F₁(k)|² × Σ |F₂(k)|²)
+/// ```
+///
+/// where the sums are over the frequencies `k` of ring `r`. Ring `r` holds the
+/// frequencies with a radius in `[(r - 0.5) / n, (r + 0.5) / n)` cycles per
+/// pixel, where `n` is the shorter image axis, up to the Nyquist frequency of
+/// 0.5 cycles per pixel. The FRC drops from 1.0 at low frequencies to 0.0 at
+/// frequencies that only hold noise, the frequency at which it crosses a
+/// threshold is the image resolution (see `frc_resolution`). Rings with no
+/// signal in either image have a correlation of 0.0.
+///
+/// # Arguments
+///
+/// * `data_a`: The first 2-dimensional image. Must have the same shape as
+///   `data_b`.
+/// * `data_b`: The second 2-dimensional image. Must have the same shape as
+///   `data_a`.
+///
+/// # Returns
+///
+/// * `Ok(Frc)`: The frequency, correlation and pixel count of each ring.
+/// * `Err(ImgalError)`: If the image shapes do not match. If an image axis is
+///   shorter than 2.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1038/nmeth.2448>
+pub fn frc<T>(data_a: ArrayView2<T>, data_b: ArrayView2<T>) -> Result<Frc, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the image parameters are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    let (rows, cols) = data_a.dim();
+    let n = rows.min(cols);
+    if n < 2 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data_a",
+            reason: format!("must have axes of length >= 2 but got {:?}", data_a.shape()),
+        });
+    }
+
+    // compute the forward FFTs
+    let mut fa = data_a.mapv(|v| Complex::new(v.to_f64(), 0.0)).into_dyn();
+    let mut fb = data_b.mapv(|v| Complex::new(v.to_f64(), 0.0)).into_dyn();
+    fft_nd(&mut fa, false);
+    fft_nd(&mut fb, false);
+
+    // accumulate the cross and auto power of each ring
+    let rings = n / 2 + 1;
+    let mut cross = vec![0.0; rings];
+    let mut power_a = vec![0.0; rings];
+    let mut power_b = vec![0.0; rings];
+    let mut counts = vec![0; rings];
+    let freq = |k: usize, len: usize| {
+        let k = if k <= len / 2 {
+            k as f64
+        } else {
+            k as f64 - len as f64
+        };
+        k / len as f64
+    };
+    fa.indexed_iter().zip(fb.iter()).for_each(|((idx, a), b)| {
+        let r = freq(idx[0], rows).hypot(freq(idx[1], cols)) * n as f64;
+        let ring = r.round() as usize;
+        if ring < rings {
+            cross[ring] += (a * b.conj()).re;
+            power_a[ring] += a.norm_sqr();
+            power_b[ring] += b.norm_sqr();
+            counts[ring] += 1;
+        }
+    });
+
+    let correlation = (0..rings)
+        .map(|r| {
+            let denom = (power_a[r] * power_b[r]).sqrt();
+            if denom > 0.0 { cross[r] / denom } else { 0.0 }
+        })
+        .collect();
+
+    Ok(Frc {
+        frequencies: (0..rings).map(|r| r as f64 / n as f64).collect(),
+        correlation,
+        counts,
+    })
+}
+
+/// Read the resolution out of a Fourier ring correlation curve.
+///
+/// # Description
+///
+/// This function finds the first frequency (after the zero frequency ring) at
+/// which the FRC curve drops below the threshold of the criterion, linearly
+/// interpolated between the last ring above and the first ring below the
+/// threshold. The resolution is the inverse of that frequency.
+///
+/// # Arguments
+///
+/// * `frc`: The Fourier ring correlation curve, see `frc`.
+/// * `criterion`: The resolution criterion, default = `FrcCriterion::OneSeventh`.
+/// * `pixel_size`: The pixel size, default = 1.0 (_i.e._ the resolution is in
+///   pixels).
+///
+/// # Returns
+///
+/// * `Option<f64>`: The resolution in units of `pixel_size`. `None` if the FRC
+///   curve does not cross the threshold up to the Nyquist frequency.
+pub fn frc_resolution(
+    frc: &Frc,
+    criterion: Option<FrcCriterion>,
+    pixel_size: Option<f64>,
+) -> Option<f64> {
+    // set optional parameters if needed
+    let criterion = criterion.unwrap_or(FrcCriterion::OneSeventh);
+    let pixel_size = pixel_size.unwrap_or(1.0);
+
+    // the threshold difference of each ring
+    let excess = |r: usize| {
+        let threshold = match criterion {
+            FrcCriterion::OneSeventh => 1.0 / 7.0,
+            FrcCriterion::Sigma(k) => k / (frc.counts[r] as f64 / 2.0).sqrt(),
+        };
+        frc.correlation[r] - threshold
+    };
+
+    // find the first ring below the threshold
+    let r = (1..frc.correlation.len()).find(|&r| excess(r) < 0.0)?;
+    let (f_lo, f_hi) = (frc.frequencies[r - 1], frc.frequencies[r]);
+    let frequency = if r == 1 {
+        f_hi
+    } else {
+        let (e_lo, e_hi) = (excess(r - 1), excess(r));
+        f_lo + e_lo / (e_lo - e_hi) * (f_hi - f_lo)
+    };
+
+    Some(pixel_size / frequency)
+}
+
+/// Split a 2-dimensional photon count image into two independent halves.
+///
+/// # Description
+///
+/// This function assigns each detected photon to one of two images with equal
+/// probability (_i.e._ binomial thinning), the count `n` of each pixel is split
+/// into `k ~ Binomial(n, 0.5)` and `n - k` photons. For shot noise limited
+/// (Poisson) data the two halves are statistically independent images of the
+/// same sample with half the photons, which allows a Fourier ring correlation
+/// (see `frc`) from a single acquisition.
+///
+/// # Arguments
+///
+/// * `data`: The 2-dimensional photon count image. Each value must be a
+///   non-negative integer.
+/// * `seed`: Pseudorandom number generator seed, the row `i` is seeded with
+///   `seed + i`. If `None`, a thread local generator is used. Pass `&mut rng`
+///   to draw the next stream of an `Rng` context, each row is then split with
+///   its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok((Array2<f64>, Array2<f64>))`: The two halves, which sum to `data`.
+/// * `Err(ImgalError)`: If a value is negative or not an integer.
+pub fn split_photons<T>(
+    data: ArrayView2<T>,
+    seed: impl Into<Seed>,
+) -> Result<(Array2<f64>, Array2<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the photon counts are valid
+    if let Some(v) = data
+        .iter()
+        .map(|v| v.to_f64())
+        .find(|v| !(v.is_finite() && *v >= 0.0 && v.fract() == 0.0))
+    {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!("must contain non-negative integer counts but got {}", v),
+        });
+    }
+
+    // split the photons of each row in parallel
+    let seed = seed.into();
+    let mut half_a = Array2::<f64>::zeros(data.dim());
+    half_a
+        .axis_iter_mut(Axis(0))
+        .into_par_iter()
+        .zip(data.axis_iter(Axis(0)))
+        .enumerate()
+        .for_each(|(i, (mut row_a, row))| {
+            let mut rng = seed.lane_offset(i);
+            row_a.iter_mut().zip(row.iter()).for_each(|(a, v)| {
+                // each random bit assigns one photon
+                let mut n = v.to_f64() as u64;
+                let mut k = 0;
+                while n > 0 {
+                    let take = n.min(64);
+                    let bits = rng.next_u64();
+                    let bits = if take == 64 {
+                        bits
+                    } else {
+                        bits & ((1 << take) - 1)
+                    };
+                    k += bits.count_ones() as u64;
+                    n -= take;
+                }
+                *a = k as f64;
+            });
+        });
+    let half_b = data.mapv(|v| v.to_f64()) - &half_a;
+
+    Ok((half_a, half_b))
+}
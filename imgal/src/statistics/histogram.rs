@@ -0,0 +1,215 @@
+use ndarray::{ArrayViewD, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::statistics::quantile::quantile_sorted;
+use crate::traits::numeric::ToFloat64;
+
+/// Histogram binning rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binning {
+    /// A fixed number of bins. Must be >= 1.
+    Fixed(usize),
+    /// The Freedman–Diaconis rule, a bin width of `2 × IQR / ∛n` where `IQR` is
+    /// the interquartile range of the `n` values. Falls back to the Sturges rule
+    /// if the interquartile range is 0.0.
+    FreedmanDiaconis,
+    /// The Sturges rule, `⌈log₂(n)⌉ + 1` bins for `n` values.
+    Sturges,
+}
+
+/// A histogram with (weighted) bin counts and bin edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// The (weighted) count of each bin.
+    pub counts: Vec<f64>,
+    /// The bin edges, one more than the number of bins. Each bin includes its
+    /// lower edge, the last bin also includes its upper edge.
+    pub edges: Vec<f64>,
+}
+
+/// Compute the histogram of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the frequency histogram of the (masked) values of
+/// the input n-dimensional array. The bins have equal widths and span the
+/// minimum and maximum values, the number of bins is either fixed or chosen
+/// automatically from the data (see `Binning`). If all (masked) values are
+/// equal, every value is counted in the first bin and all edges are equal.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional array.
+/// * `bins`: The binning rule, default = `Binning::Fixed(256)`.
+/// * `mask`: An optional boolean mask, values set to `false` are excluded from
+///   the histogram. Must match the shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok(Histogram)`: The bin counts and edges.
+/// * `Err(ImgalError)`: If the number of fixed bins is 0. If the mask shape does
+///   not match the data shape. If the data is empty or the mask excludes all
+///   values.
+pub fn histogram<T>(
+    data: ArrayViewD<T>,
+    bins: Option<Binning>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<Histogram, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(Binning::Fixed(256));
+
+    let values = masked_values(data, mask)?;
+    let n = bin_count(&values, bins)?;
+
+    Ok(bin_values(&values, None, n))
+}
+
+/// Compute the weighted histogram of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the histogram of the (masked) values of the input
+/// n-dimensional array where each value adds its weight to its bin instead of
+/// 1.0. The bins are constructed as in `histogram`, automatic binning rules
+/// use the number of (masked) values and ignore the weights.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional array.
+/// * `weights`: The non-negative weight of each value. Must match the shape of
+///   `data`.
+/// * `bins`: The binning rule, default = `Binning::Fixed(256)`.
+/// * `mask`: An optional boolean mask, values set to `false` are excluded from
+///   the histogram. Must match the shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok(Histogram)`: The weighted bin counts and edges.
+/// * `Err(ImgalError)`: If the number of fixed bins is 0. If the weights or mask
+///   shape does not match the data shape. If a weight is negative or not
+///   finite. If the data is empty or the mask excludes all values.
+pub fn weighted_histogram<T>(
+    data: ArrayViewD<T>,
+    weights: ArrayViewD<f64>,
+    bins: Option<Binning>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<Histogram, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(Binning::Fixed(256));
+
+    let (values, w) = masked_weighted_values(data, weights, mask)?;
+    let n = bin_count(&values, bins)?;
+
+    Ok(bin_values(&values, Some(&w), n))
+}
+
+/// Collect the (masked) values of an n-dimensional array.
+pub(crate) fn masked_values<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the mask parameter is valid
+    if let Some(msk) = mask.as_ref() {
+        validate::same_shape(msk.shape(), data.shape())?;
+    }
+
+    let values: Vec<f64> = match mask {
+        Some(msk) => Zip::from(&data)
+            .and(&msk)
+            .fold(Vec::new(), |mut acc, v, &m| {
+                if m {
+                    acc.push(v.to_f64());
+                }
+                acc
+            }),
+        None => data.iter().map(|v| v.to_f64()).collect(),
+    };
+    if values.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must contain at least one unmasked pixel".to_string(),
+        });
+    }
+
+    Ok(values)
+}
+
+/// Collect the (masked) values of an n-dimensional array and their weights.
+pub(crate) fn masked_weighted_values<T>(
+    data: ArrayViewD<T>,
+    weights: ArrayViewD<f64>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<(Vec<f64>, Vec<f64>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the weights parameter is valid
+    validate::same_shape(weights.shape(), data.shape())?;
+    weights
+        .iter()
+        .try_for_each(|&w| validate::at_least("weights", w, 0.0))?;
+
+    let values = masked_values(data, mask.clone())?;
+    let w = masked_values(weights, mask)?;
+
+    Ok((values, w))
+}
+
+/// Get the number of bins of a binning rule for non-empty values.
+fn bin_count(values: &[f64], bins: Binning) -> Result<usize, ImgalError> {
+    let sturges = (values.len() as f64).log2().ceil() as usize + 1;
+    match bins {
+        Binning::Fixed(0) => Err(ImgalError::InvalidParameter {
+            param_name: "bins",
+            reason: "must be >= 1 but got 0".to_string(),
+        }),
+        Binning::Fixed(n) => Ok(n),
+        Binning::Sturges => Ok(sturges),
+        Binning::FreedmanDiaconis => {
+            let mut sorted = values.to_vec();
+            sorted.sort_unstable_by(|a, b| a.total_cmp(b));
+            let iqr = quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25);
+            let range = sorted[sorted.len() - 1] - sorted[0];
+            if iqr > 0.0 {
+                let width = 2.0 * iqr / (values.len() as f64).cbrt();
+                Ok(((range / width).ceil() as usize).max(1))
+            } else {
+                Ok(sturges)
+            }
+        }
+    }
+}
+
+/// Construct the histogram of non-empty values over their [min, max] range.
+fn bin_values(values: &[f64], weights: Option<&[f64]>, bins: usize) -> Histogram {
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+
+    // count the (weighted) values per bin
+    let bin_width = (max - min) / bins as f64;
+    let mut counts = vec![0.0; bins];
+    values.iter().enumerate().for_each(|(i, &v)| {
+        let bin = if bin_width > 0.0 {
+            (((v - min) / bin_width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        counts[bin] += weights.map_or(1.0, |w| w[i]);
+    });
+    let mut edges: Vec<f64> = (0..=bins).map(|k| min + k as f64 * bin_width).collect();
+    edges[bins] = max;
+
+    Histogram { counts, edges }
+}
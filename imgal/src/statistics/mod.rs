@@ -1,13 +1,37 @@
 //! Statistics functions.
+pub mod bootstrap;
+pub use bootstrap::{Bootstrap, bootstrap, paired_bootstrap};
+pub mod circular;
+pub use circular::{circular_mean, circular_variance, rayleigh_test};
+pub mod describe;
+pub use describe::{Description, describe};
+pub mod frc;
+pub use frc::{Frc, FrcCriterion, frc, frc_resolution, split_photons};
+pub mod histogram;
+pub use histogram::{Binning, Histogram, histogram, weighted_histogram};
 pub mod kendall_tau;
 pub use kendall_tau::weighted_kendall_tau_b;
 pub mod min_max;
 pub use min_max::max;
 pub use min_max::min;
 pub use min_max::min_max;
+pub mod mutual_information;
+pub use mutual_information::{joint_histogram, mutual_information};
+pub mod permutation;
+pub use permutation::{Alternative, paired_permutation_test, permutation_test};
+pub mod quantile;
+pub use quantile::{quantile, weighted_quantile};
+pub mod rank;
+pub use rank::rank;
+pub mod running;
+pub use running::RunningStats;
 pub mod sample;
 pub use sample::effective_sample_size;
+pub mod spearman;
+pub use spearman::spearman;
 pub mod sum;
 pub use sum::sum;
 pub mod sort;
 pub use sort::weighted_merge_sort_mut;
+pub mod weighted;
+pub use weighted::{weighted_covariance, weighted_mean, weighted_pearson, weighted_variance};
@@ -0,0 +1,190 @@
+use ndarray::{Array2, ArrayViewD, Axis, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::filter::{self, BorderMode};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the joint histogram of two n-dimensional images.
+///
+/// # Description
+///
+/// This function counts the co-occurrences of the binned intensities of two
+/// images, the bin `[i, j]` holds the number of pixels with the intensity of
+/// image `A` in bin `i` and the intensity of image `B` in bin `j`. The bins of
+/// each image have equal widths and span the minimum and maximum (masked)
+/// intensities of that image. The histogram can optionally be smoothed with a
+/// Gaussian kernel (_i.e._ a Parzen window estimate), which reduces the noise
+/// of sparse histograms.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `bins`: The number of bins per image, default = 32. Must be >= 1.
+/// * `sigma`: The standard deviation of the Gaussian smoothing kernel in bins,
+///   default = 0.0 (_i.e._ no smoothing). Must be >= 0.0.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded from
+///   the histogram. Must match the shape of the images.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The (smoothed) joint histogram with shape
+///   `(bins, bins)`, indexed by the bins of image `A` and `B`.
+/// * `Err(ImgalError)`: If the shapes of image `A`, `B` and the mask do not
+///   match. If bins is 0. If sigma is < 0.0. If the images are empty or the
+///   mask excludes all pixels.
+pub fn joint_histogram<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    bins: Option<usize>,
+    sigma: Option<f64>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(32);
+    let sigma = sigma.unwrap_or(0.0);
+
+    // check if the image, mask, bins and sigma parameters are valid
+    validate::same_shape(data_a.shape(), data_b.shape())?;
+    if let Some(msk) = mask.as_ref() {
+        validate::same_shape(msk.shape(), data_a.shape())?;
+    }
+    if bins == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "bins",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+    validate::at_least("sigma", sigma, 0.0)?;
+
+    // collect the (masked) intensity pairs
+    let mut pairs: Vec<(f64, f64)> = Vec::new();
+    match mask {
+        Some(msk) => Zip::from(&data_a)
+            .and(&data_b)
+            .and(&msk)
+            .for_each(|a, b, &m| {
+                if m {
+                    pairs.push((a.to_f64(), b.to_f64()));
+                }
+            }),
+        None => Zip::from(&data_a)
+            .and(&data_b)
+            .for_each(|a, b| pairs.push((a.to_f64(), b.to_f64()))),
+    }
+    if pairs.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must contain at least one unmasked pixel".to_string(),
+        });
+    }
+
+    // count the binned intensity pairs
+    let index_a = bin_index(pairs.iter().map(|p| p.0), bins);
+    let index_b = bin_index(pairs.iter().map(|p| p.1), bins);
+    let mut hist = Array2::<f64>::zeros((bins, bins));
+    pairs
+        .iter()
+        .for_each(|&(a, b)| hist[[index_a(a), index_b(b)]] += 1.0);
+    if sigma == 0.0 {
+        return Ok(hist);
+    }
+
+    // smooth the histogram
+    let smoothed = filter::gaussian(
+        hist.view().into_dyn(),
+        &[sigma, sigma],
+        Some(BorderMode::Reflect),
+        None,
+    )?;
+
+    Ok(smoothed
+        .into_dimensionality()
+        .expect("the smoothed histogram is 2-dimensional"))
+}
+
+/// Compute the mutual information of two n-dimensional images.
+///
+/// # Description
+///
+/// This function computes the mutual information (MI) of the intensities of
+/// two images from their joint histogram (see `joint_histogram`):
+///
+/// ```text
+/// MI = Σᵢⱼ p(i, j) × ln(p(i, j) / (p(i) × p(j)))
+/// ```
+///
+/// where `p(i, j)` is the normalized joint histogram and `p(i)` and `p(j)` are
+/// its marginals. The MI is 0.0 for independent intensities and grows with any
+/// (not only linear) statistical dependence between the images, which makes it
+/// useful both as a colocalization measure and as a similarity metric for the
+/// registration of images from different modalities. The MI is given in nats.
+///
+/// # Arguments
+///
+/// * `data_a`: The n-dimensional input image, `A`. Image `A` must have the same
+///   shape as image `B`.
+/// * `data_b`: The n-dimensional input image, `B`. Image `B` must have the same
+///   shape as image `A`.
+/// * `bins`: The number of bins per image, default = 32. Must be >= 1.
+/// * `sigma`: The standard deviation of the Gaussian smoothing kernel of the
+///   joint histogram in bins, default = 0.0 (_i.e._ no smoothing). Must be
+///   >= 0.0.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded. Must
+///   match the shape of the images.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The mutual information in nats.
+/// * `Err(ImgalError)`: If the shapes of image `A`, `B` and the mask do not
+///   match. If bins is 0. If sigma is < 0.0. If the images are empty or the
+///   mask excludes all pixels.
+pub fn mutual_information<T>(
+    data_a: ArrayViewD<T>,
+    data_b: ArrayViewD<T>,
+    bins: Option<usize>,
+    sigma: Option<f64>,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    let hist = joint_histogram(data_a, data_b, bins, sigma, mask)?;
+
+    // compute the normalized joint and marginal histograms
+    let total = hist.sum();
+    let p_ab = hist / total;
+    let p_a = p_ab.sum_axis(Axis(1));
+    let p_b = p_ab.sum_axis(Axis(0));
+
+    Ok(p_ab
+        .indexed_iter()
+        .filter(|&(_, &p)| p > 0.0)
+        .map(|((i, j), &p)| p * (p / (p_a[i] * p_b[j])).ln())
+        .sum::<f64>()
+        .max(0.0))
+}
+
+/// Create the bin index function over the range of non-empty values.
+fn bin_index<I>(values: I, bins: usize) -> impl Fn(f64) -> usize
+where
+    I: Iterator<Item = f64>,
+{
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    let width = (max - min) / bins as f64;
+
+    move |v| {
+        if width > 0.0 {
+            (((v - min) / width) as usize).min(bins - 1)
+        } else {
+            0
+        }
+    }
+}
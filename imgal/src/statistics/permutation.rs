@@ -0,0 +1,193 @@
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::simulation::rng::{LaneRng, Seed};
+
+/// The alternative hypothesis of a permutation test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alternative {
+    /// The observed statistic differs from the null distribution in either
+    /// direction.
+    TwoSided,
+    /// The observed statistic is greater than the null distribution.
+    Greater,
+    /// The observed statistic is less than the null distribution.
+    Less,
+}
+
+/// Test if two samples differ with a permutation test.
+///
+/// # Description
+///
+/// This function tests the null hypothesis that two independent samples come
+/// from the same distribution. The samples are pooled and the sample labels
+/// are shuffled, each permutation assigns the first `data_a.len()` pooled
+/// values to sample `A` and the rest to sample `B` and computes the statistic
+/// (_e.g._ the difference of the means). The p-value is the fraction of
+/// permuted statistics that are at least as extreme as the observed statistic,
+/// with the observed labeling counted as one of the permutations:
+///
+/// ```text
+/// p_greater = (1 + #{t_perm >= t}) / (1 + permutations)
+/// p_less = (1 + #{t_perm <= t}) / (1 + permutations)
+/// p_two_sided = min(1, 2 × min(p_greater, p_less))
+/// ```
+///
+/// The permutations are computed in parallel.
+///
+/// # Arguments
+///
+/// * `data_a`: The first sample. Must have at least 1 value.
+/// * `data_b`: The second sample. Must have at least 1 value.
+/// * `statistic`: The function computing the test statistic of the two samples.
+/// * `permutations`: The number of permutations, default = 1000. Must be >= 1.
+/// * `alternative`: The alternative hypothesis, default =
+///   `Alternative::TwoSided`.
+/// * `seed`: Pseudorandom number generator seed, the permutation `i` is seeded
+///   with `seed + i`. If `None`, a thread local generator is used. Pass
+///   `&mut rng` to draw the next stream of an `Rng` context, each permutation
+///   is then shuffled with its own reproducible generator.
+///
+/// # Returns
+///
+/// * `Ok((f64, Vec<f64>))`: The p-value and the statistic of each permutation
+///   (_i.e._ the null distribution).
+/// * `Err(ImgalError)`: If either sample is empty. If permutations is 0.
+pub fn permutation_test<F>(
+    data_a: &[f64],
+    data_b: &[f64],
+    statistic: F,
+    permutations: Option<usize>,
+    alternative: Option<Alternative>,
+    seed: impl Into<Seed>,
+) -> Result<(f64, Vec<f64>), ImgalError>
+where
+    F: Fn(&[f64], &[f64]) -> f64 + Sync,
+{
+    // check if the sample parameters are valid
+    if data_a.is_empty() || data_b.is_empty() {
+        let param_name = if data_a.is_empty() {
+            "data_a"
+        } else {
+            "data_b"
+        };
+        return Err(ImgalError::InvalidParameter {
+            param_name,
+            reason: "must have at least 1 value but got 0".to_string(),
+        });
+    }
+
+    let mut pooled = data_a.to_vec();
+    pooled.extend_from_slice(data_b);
+    let split = data_a.len();
+
+    permute(
+        statistic(data_a, data_b),
+        |rng| {
+            let mut p = pooled.clone();
+            p.shuffle(rng);
+            statistic(&p[..split], &p[split..])
+        },
+        permutations,
+        alternative,
+        seed.into(),
+    )
+}
+
+/// Test if a statistic of paired data is significant with a permutation test.
+///
+/// # Description
+///
+/// This function tests the null hypothesis that two paired data sets are
+/// independent (_e.g._ a correlation coefficient of the pixel intensities of
+/// two channels is 0.0). Each permutation shuffles `data_b`, which breaks the
+/// pairing with `data_a`, and computes the statistic. The p-value is computed
+/// as in `permutation_test`. The permutations are computed in parallel.
+///
+/// # Arguments
+///
+/// * `data_a`: The first data set. Must be the same length as `data_b`.
+/// * `data_b`: The second data set, that is shuffled. Must be the same length
+///   as `data_a`.
+/// * `statistic`: The function computing the test statistic of the paired data
+///   sets.
+/// * `permutations`: The number of permutations, default = 1000. Must be >= 1.
+/// * `alternative`: The alternative hypothesis, default =
+///   `Alternative::TwoSided`.
+/// * `seed`: Pseudorandom number generator seed, the permutation `i` is seeded
+///   with `seed + i`. If `None`, a thread local generator is used. Pass
+///   `&mut rng` to draw the next stream of an `Rng` context.
+///
+/// # Returns
+///
+/// * `Ok((f64, Vec<f64>))`: The p-value and the statistic of each permutation
+///   (_i.e._ the null distribution).
+/// * `Err(ImgalError)`: If the data set lengths do not match. If permutations is
+///   0.
+pub fn paired_permutation_test<F>(
+    data_a: &[f64],
+    data_b: &[f64],
+    statistic: F,
+    permutations: Option<usize>,
+    alternative: Option<Alternative>,
+    seed: impl Into<Seed>,
+) -> Result<(f64, Vec<f64>), ImgalError>
+where
+    F: Fn(&[f64], &[f64]) -> f64 + Sync,
+{
+    // check if the input lengths are valid
+    validate::same_length(data_a.len(), data_b.len())?;
+
+    permute(
+        statistic(data_a, data_b),
+        |rng| {
+            let mut b = data_b.to_vec();
+            b.shuffle(rng);
+            statistic(data_a, &b)
+        },
+        permutations,
+        alternative,
+        seed.into(),
+    )
+}
+
+/// Compute the p-value of an observed statistic against permuted statistics.
+fn permute<F>(
+    observed: f64,
+    permuted: F,
+    permutations: Option<usize>,
+    alternative: Option<Alternative>,
+    seed: Seed,
+) -> Result<(f64, Vec<f64>), ImgalError>
+where
+    F: Fn(&mut LaneRng) -> f64 + Sync,
+{
+    // set optional parameters if needed
+    let permutations = permutations.unwrap_or(1000);
+    let alternative = alternative.unwrap_or(Alternative::TwoSided);
+
+    // check if the permutations parameter is valid
+    if permutations == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "permutations",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+
+    // compute the statistic of each permutation in parallel
+    let null: Vec<f64> = (0..permutations)
+        .into_par_iter()
+        .map(|i| permuted(&mut seed.lane_offset(i)))
+        .collect();
+    let total = (1 + permutations) as f64;
+    let greater = (1 + null.iter().filter(|&&t| t >= observed).count()) as f64 / total;
+    let less = (1 + null.iter().filter(|&&t| t <= observed).count()) as f64 / total;
+    let p_value = match alternative {
+        Alternative::TwoSided => (2.0 * greater.min(less)).min(1.0),
+        Alternative::Greater => greater,
+        Alternative::Less => less,
+    };
+
+    Ok((p_value, null))
+}
@@ -0,0 +1,142 @@
+use ndarray::ArrayViewD;
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::statistics::histogram::{masked_values, masked_weighted_values};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute a quantile of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the `q` quantile of the (masked) values of the input
+/// n-dimensional array with linear interpolation between the closest ranks
+/// (_i.e._ at the position `q × (n - 1)` of the sorted values).
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional array.
+/// * `q`: The quantile. Must be in [0, 1].
+/// * `mask`: An optional boolean mask, values set to `false` are excluded from
+///   the quantile. Must match the shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The quantile value.
+/// * `Err(ImgalError)`: If q is not in [0, 1]. If the mask shape does not match
+///   the data shape. If the data is empty or the mask excludes all values.
+pub fn quantile<T>(
+    data: ArrayViewD<T>,
+    q: f64,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the quantile parameter is valid
+    validate::in_range("q", q, 0.0, 1.0)?;
+
+    let mut values = masked_values(data, mask)?;
+    values.par_sort_unstable_by(|a, b| a.total_cmp(b));
+
+    Ok(quantile_sorted(&values, q))
+}
+
+/// Compute a weighted quantile of an n-dimensional array.
+///
+/// # Description
+///
+/// This function computes the `q` quantile of the (masked) values of the input
+/// n-dimensional array where each value counts as its weight. The sorted
+/// values with a positive weight `wₖ` are placed at the centers of their
+/// cumulative weight, rescaled so that the smallest value is at 0.0 and the
+/// largest value is at 1.0:
+///
+/// ```text
+/// cₖ = Σⱼ<ₖ wⱼ + wₖ / 2
+/// pₖ = (cₖ - c₀) / (cₙ₋₁ - c₀)
+/// ```
+///
+/// and the quantile is linearly interpolated between the closest positions.
+/// With equal weights this is the same as `quantile`.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional array.
+/// * `weights`: The non-negative weight of each value. Must match the shape of
+///   `data`.
+/// * `q`: The quantile. Must be in [0, 1].
+/// * `mask`: An optional boolean mask, values set to `false` are excluded from
+///   the quantile. Must match the shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted quantile value.
+/// * `Err(ImgalError)`: If q is not in [0, 1]. If the weights or mask shape does
+///   not match the data shape. If a weight is negative or not finite. If the
+///   data is empty, the mask excludes all values or the (masked) weights sum to
+///   0.0.
+pub fn weighted_quantile<T>(
+    data: ArrayViewD<T>,
+    weights: ArrayViewD<f64>,
+    q: f64,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the quantile parameter is valid
+    validate::in_range("q", q, 0.0, 1.0)?;
+
+    // sort the values with a positive weight
+    let (values, w) = masked_weighted_values(data, weights, mask)?;
+    let mut pairs: Vec<(f64, f64)> = values
+        .into_iter()
+        .zip(w)
+        .filter(|&(_, w)| w > 0.0)
+        .collect();
+    if pairs.is_empty() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "weights",
+            reason: "must have a positive sum".to_string(),
+        });
+    }
+    pairs.par_sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+    // place the values at the centers of their cumulative weight
+    let mut cum = 0.0;
+    let centers: Vec<f64> = pairs
+        .iter()
+        .map(|&(_, w)| {
+            let c = cum + w / 2.0;
+            cum += w;
+            c
+        })
+        .collect();
+    let last = pairs.len() - 1;
+    let span = centers[last] - centers[0];
+    if span == 0.0 {
+        return Ok(pairs[0].0);
+    }
+
+    // interpolate between the closest positions
+    let pos = centers[0] + q * span;
+    let hi = centers.partition_point(|&c| c < pos).min(last);
+    if hi == 0 || centers[hi] == pos {
+        return Ok(pairs[hi].0);
+    }
+    let lo = hi - 1;
+    let t = (pos - centers[lo]) / (centers[hi] - centers[lo]);
+
+    Ok(pairs[lo].0 + t * (pairs[hi].0 - pairs[lo].0))
+}
+
+/// Compute a quantile of non-empty sorted values with linear interpolation
+/// between the closest ranks.
+pub(crate) fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+
+    sorted[lo] + (rank - lo as f64) * (sorted[hi] - sorted[lo])
+}
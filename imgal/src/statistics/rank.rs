@@ -0,0 +1,70 @@
+use crate::error::{ImgalError, validate};
+use crate::traits::numeric::ToFloat64;
+
+/// Rank data with tie handling.
+///
+/// # Description
+///
+/// This function computes the 1-based rank of each element of the input data
+/// in ascending order. Tied elements get the average of the ranks they span
+/// (_i.e._ fractional ranking), _e.g._ `[10, 20, 20, 30]` is ranked
+/// `[1.0, 2.5, 2.5, 4.0]`.
+///
+/// With weights, each element counts as `wᵢ` observations, the rank of an
+/// element is the total weight of the smaller elements plus the average rank
+/// within its tie group:
+///
+/// ```text
+/// rᵢ = W_less + (W_tied + 1) / 2
+/// ```
+///
+/// which reduces to fractional ranking for unit weights. NaN values are ranked
+/// above all other values.
+///
+/// # Arguments
+///
+/// * `data`: The data to rank.
+/// * `weights`: The optional non-negative weight of each element. Must be the
+///   same length as `data`. If `None`, every element has a weight of 1.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The rank of each element, in the order of `data`.
+/// * `Err(ImgalError)`: If the weights and data lengths do not match. If a
+///   weight is negative or not finite.
+pub fn rank<T>(data: &[T], weights: Option<&[f64]>) -> Result<Vec<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the weights parameter is valid
+    if let Some(w) = weights {
+        validate::same_length(data.len(), w.len())?;
+        w.iter()
+            .try_for_each(|&v| validate::at_least("weights", v, 0.0))?;
+    }
+    let weight = |i: usize| weights.map_or(1.0, |w| w[i]);
+
+    // sort the element indices by value
+    let mut indices: Vec<usize> = (0..data.len()).collect();
+    indices.sort_by(|&a, &b| data[a].to_f64().total_cmp(&data[b].to_f64()));
+
+    // assign the average rank to each tie group
+    let mut ranks = vec![0.0; data.len()];
+    let mut w_less = 0.0;
+    let mut i = 0;
+    while i < indices.len() {
+        let value = data[indices[i]].to_f64();
+        let mut j = i;
+        let mut w_tied = 0.0;
+        while j < indices.len() && data[indices[j]].to_f64().total_cmp(&value).is_eq() {
+            w_tied += weight(indices[j]);
+            j += 1;
+        }
+        let r = w_less + (w_tied + 1.0) / 2.0;
+        indices[i..j].iter().for_each(|&k| ranks[k] = r);
+        w_less += w_tied;
+        i = j;
+    }
+
+    Ok(ranks)
+}
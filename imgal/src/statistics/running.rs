@@ -0,0 +1,235 @@
+use ndarray::{ArrayViewD, Zip};
+
+use crate::error::{ImgalError, validate};
+use crate::traits::numeric::ToFloat64;
+
+/// A mergeable accumulator of running statistics.
+///
+/// # Description
+///
+/// The running statistics accumulate the count, mean, central moments and
+/// range of a stream of values in a single pass with Welford's online
+/// algorithm, generalized to the third and fourth central moments. Two
+/// accumulators over disjoint values can be merged exactly, so chunks of a
+/// dataset (_e.g._ the planes of an out-of-core image or the parts of a
+/// parallel reduction) can be accumulated independently and combined. The
+/// update avoids the cancellation of the naive sum of powers.
+///
+/// # Example
+///
+/// ```
+/// use imgal::statistics::RunningStats;
+///
+/// let mut a = RunningStats::new();
+/// a.extend([1.0, 2.0, 3.0]);
+/// let b: RunningStats = [4.0, 5.0].into_iter().collect();
+/// a.merge(&b);
+/// assert_eq!(a.count(), 5);
+/// assert_eq!(a.mean(), 3.0);
+/// assert_eq!(a.variance(), 2.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    n: u64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        RunningStats {
+            n: 0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl RunningStats {
+    /// Create a new empty accumulator.
+    pub fn new() -> Self {
+        RunningStats::default()
+    }
+
+    /// Add a value.
+    pub fn push(&mut self, value: f64) {
+        let n = self.n as f64 + 1.0;
+        let d = value - self.mean;
+        let d_n = d / n;
+        let d_n2 = d_n * d_n;
+        let term = d * d_n * (n - 1.0);
+        self.mean += d_n;
+        self.m4 +=
+            term * d_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * d_n2 * self.m2 - 4.0 * d_n * self.m3;
+        self.m3 += term * d_n * (n - 2.0) - 3.0 * d_n * self.m2;
+        self.m2 += term;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.n += 1;
+    }
+
+    /// Add the (masked) values of an n-dimensional array.
+    ///
+    /// # Arguments
+    ///
+    /// * `data`: An n-dimensional array.
+    /// * `mask`: An optional boolean mask, values set to `false` are skipped.
+    ///   Must match the shape of `data`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())`: If the values were added.
+    /// * `Err(ImgalError)`: If the mask shape does not match the data shape.
+    pub fn push_array<T>(
+        &mut self,
+        data: ArrayViewD<T>,
+        mask: Option<ArrayViewD<bool>>,
+    ) -> Result<(), ImgalError>
+    where
+        T: ToFloat64,
+    {
+        let chunk = match mask {
+            Some(msk) => {
+                validate::same_shape(msk.shape(), data.shape())?;
+                Zip::from(&data).and(&msk).par_fold(
+                    RunningStats::new,
+                    |mut acc, v, &m| {
+                        if m {
+                            acc.push(v.to_f64());
+                        }
+                        acc
+                    },
+                    merged,
+                )
+            }
+            None => Zip::from(&data).par_fold(
+                RunningStats::new,
+                |mut acc, v| {
+                    acc.push(v.to_f64());
+                    acc
+                },
+                merged,
+            ),
+        };
+        self.merge(&chunk);
+
+        Ok(())
+    }
+
+    /// Merge the statistics of another accumulator over disjoint values.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.n == 0 {
+            return;
+        }
+        if self.n == 0 {
+            *self = *other;
+            return;
+        }
+        let (na, nb) = (self.n as f64, other.n as f64);
+        let n = na + nb;
+        let d = other.mean - self.mean;
+        let d2 = d * d;
+        let m2 = self.m2 + other.m2 + d2 * na * nb / n;
+        let m3 = self.m3
+            + other.m3
+            + d2 * d * na * nb * (na - nb) / (n * n)
+            + 3.0 * d * (na * other.m2 - nb * self.m2) / n;
+        let m4 = self.m4
+            + other.m4
+            + d2 * d2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+            + 6.0 * d2 * (na * na * other.m2 + nb * nb * self.m2) / (n * n)
+            + 4.0 * d * (na * other.m3 - nb * self.m3) / n;
+        self.mean += d * nb / n;
+        self.m2 = m2;
+        self.m3 = m3;
+        self.m4 = m4;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.n += other.n;
+    }
+
+    /// The number of values.
+    pub fn count(&self) -> u64 {
+        self.n
+    }
+
+    /// The arithmetic mean, NaN if there are no values.
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 { f64::NAN } else { self.mean }
+    }
+
+    /// The sample variance (_i.e._ with `n - 1` degrees of freedom), 0.0 if
+    /// there are less than 2 values.
+    pub fn variance(&self) -> f64 {
+        if self.n < 2 {
+            0.0
+        } else {
+            self.m2 / (self.n - 1) as f64
+        }
+    }
+
+    /// The sample standard deviation, see `variance`.
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// The (biased) sample skewness `g₁ = √n × M₃ / M₂^(3/2)`, 0.0 if all
+    /// values are equal.
+    pub fn skewness(&self) -> f64 {
+        if self.m2 > 0.0 {
+            (self.n as f64).sqrt() * self.m3 / self.m2.powf(1.5)
+        } else {
+            0.0
+        }
+    }
+
+    /// The (biased) sample excess kurtosis `g₂ = n × M₄ / M₂² - 3`, 0.0 if all
+    /// values are equal.
+    pub fn kurtosis(&self) -> f64 {
+        if self.m2 > 0.0 {
+            self.n as f64 * self.m4 / (self.m2 * self.m2) - 3.0
+        } else {
+            0.0
+        }
+    }
+
+    /// The minimum value, NaN if there are no values.
+    pub fn min(&self) -> f64 {
+        if self.n == 0 { f64::NAN } else { self.min }
+    }
+
+    /// The maximum value, NaN if there are no values.
+    pub fn max(&self) -> f64 {
+        if self.n == 0 { f64::NAN } else { self.max }
+    }
+}
+
+impl Extend<f64> for RunningStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|v| self.push(v));
+    }
+}
+
+impl FromIterator<f64> for RunningStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = RunningStats::new();
+        stats.extend(iter);
+
+        stats
+    }
+}
+
+/// Merge two accumulators into a new accumulator.
+fn merged(mut a: RunningStats, b: RunningStats) -> RunningStats {
+    a.merge(&b);
+
+    a
+}
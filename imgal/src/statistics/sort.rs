@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 
-use crate::error::ArrayError;
+use crate::error::ImgalError;
+use crate::error::validate;
 use crate::traits::numeric::ToFloat64;
 
 /// Sort 1-dimensional arrays of values and their associated weights.
@@ -22,24 +23,18 @@ use crate::traits::numeric::ToFloat64;
 /// # Returns
 ///
 /// * `OK(f64)`: The number of swaps needed to sort the input array.
-/// * `Err(ArrayError)`: If the data and weights array lengths do not match.
+/// * `Err(ImgalError)`: If the data and weights array lengths do not match.
 ///
 /// # Reference
 ///
 /// <https://doi.org/10.1109/TIP.2019.2909194>
-pub fn weighted_merge_sort_mut<T>(data: &mut [T], weights: &mut [f64]) -> Result<f64, ArrayError>
+pub fn weighted_merge_sort_mut<T>(data: &mut [T], weights: &mut [f64]) -> Result<f64, ImgalError>
 where
     T: ToFloat64,
 {
     // ensure input arrays are same length
     let dl = data.len();
-    let wl = weights.len();
-    if dl != wl {
-        return Err(ArrayError::MismatchedArrayLengths {
-            a_arr_len: dl,
-            b_arr_len: wl,
-        });
-    };
+    validate::same_length(dl, weights.len())?;
 
     // counters for weighted inversions (i.e. swaps)
     let mut swap = 0.0;
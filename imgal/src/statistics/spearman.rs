@@ -0,0 +1,56 @@
+use crate::error::{ImgalError, validate};
+use crate::statistics::{rank, weighted_pearson};
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the (weighted) Spearman rank correlation coefficient.
+///
+/// # Description
+///
+/// This function calculates the Spearman rank correlation coefficient between
+/// two datasets, the Pearson correlation coefficient of their ranks:
+///
+/// ```text
+/// ρ = Σwᵢ(rᵢ - r̄)(sᵢ - s̄) / √(Σwᵢ(rᵢ - r̄)² × Σwᵢ(sᵢ - s̄)²)
+/// ```
+///
+/// where `r` and `s` are the tie-aware ranks of `data_a` and `data_b` (see
+/// `rank`) and `r̄` and `s̄` are their weighted means. With weights, each
+/// observation pair counts as `wᵢ` observations in both the ranking and the
+/// correlation. Unlike the Pearson correlation coefficient, the Spearman
+/// coefficient measures any monotonic relationship and is robust to outliers.
+///
+/// # Arguments
+///
+/// * `data_a`: The first dataset for correlation analysis. Must be the same
+///   length as `data_b`.
+/// * `data_b`: The second dataset for correlation analysis. Must be the same
+///   length as `data_a`.
+/// * `weights`: The optional non-negative weight of each observation pair.
+///   Must be the same length as both input datasets. If `None`, every pair has
+///   a weight of 1.0.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The Spearman rank correlation coefficient, ranging between -1.0
+///   (negative correlation), 0.0 (no correlation) and 1.0 (positive
+///   correlation). 0.0 if there are less than 2 observations or either dataset
+///   is constant.
+/// * `Err(ImgalError)`: If the input lengths do not match. If a weight is
+///   negative or not finite.
+pub fn spearman<T>(data_a: &[T], data_b: &[T], weights: Option<&[f64]>) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the input lengths are valid
+    validate::same_length(data_a.len(), data_b.len())?;
+
+    // rank both datasets
+    let ranks_a = rank(data_a, weights)?;
+    let ranks_b = rank(data_b, weights)?;
+
+    // compute the weighted Pearson correlation coefficient of the ranks
+    match weights {
+        Some(w) => weighted_pearson(&ranks_a, &ranks_b, w),
+        None => weighted_pearson(&ranks_a, &ranks_b, &vec![1.0; data_a.len()]),
+    }
+}
@@ -0,0 +1,219 @@
+use ndarray::{Array2, ArrayView2, Axis};
+
+use crate::error::ImgalError;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute the weighted mean.
+///
+/// # Description
+///
+/// This function computes the weighted arithmetic mean of a dataset:
+///
+/// ```text
+/// μ = Σwᵢxᵢ / Σwᵢ
+/// ```
+///
+/// # Arguments
+///
+/// * `data`: The dataset. Must be the same length as `weights`.
+/// * `weights`: The associated weights for each observation. Must be the same
+///    length as `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted mean. 0.0 if the dataset is empty or the weights
+///    sum to 0.0.
+/// * `Err(ImgalError)`: If input array lengths do not match.
+pub fn weighted_mean<T>(data: &[T], weights: &[f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check array lengths match
+    check_lengths(data.len(), &[weights.len()])?;
+
+    Ok(mean(data, weights))
+}
+
+/// Compute the weighted variance.
+///
+/// # Description
+///
+/// This function computes the unbiased weighted variance of a dataset, where
+/// each observation contributes its weight (_i.e._ reliability weights):
+///
+/// ```text
+/// σ² = Σwᵢ × Σwᵢ(xᵢ - μ)² / n₀
+/// ```
+///
+/// Where `μ` is the weighted mean and `n₀` = total weighted pairs =
+/// `(Σwᵢ)² - Σwᵢ²`, the same normalization as `weighted_kendall_tau_b`. With
+/// unit weights this is the sample variance with `n - 1` degrees of freedom.
+///
+/// # Arguments
+///
+/// * `data`: The dataset. Must be the same length as `weights`.
+/// * `weights`: The associated weights for each observation. Must be the same
+///    length as `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted variance. 0.0 if there are less than 2
+///    observations or no weighted pairs.
+/// * `Err(ImgalError)`: If input array lengths do not match.
+pub fn weighted_variance<T>(data: &[T], weights: &[f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check array lengths match
+    check_lengths(data.len(), &[weights.len()])?;
+
+    Ok(covariance(data, data, weights))
+}
+
+/// Compute the weighted covariance matrix of several variables.
+///
+/// # Description
+///
+/// This function computes the unbiased weighted covariance of each pair of
+/// variables (rows) over the weighted observations (columns):
+///
+/// ```text
+/// Cⱼₖ = Σwᵢ × Σwᵢ(xⱼᵢ - μⱼ)(xₖᵢ - μₖ) / n₀
+/// ```
+///
+/// Where `μⱼ` is the weighted mean of variable `j` and `n₀` = total weighted
+/// pairs = `(Σwᵢ)² - Σwᵢ²`, see `weighted_variance`.
+///
+/// # Arguments
+///
+/// * `data`: The 2-dimensional dataset with shape `(variables, observations)`.
+/// * `weights`: The associated weights for each observation. Must be the same
+///    length as the observation axis of `data`.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)`: The symmetric weighted covariance matrix with shape
+///    `(variables, variables)`. All 0.0 if there are less than 2 observations
+///    or no weighted pairs.
+/// * `Err(ImgalError)`: If the number of observations and weights do not match.
+pub fn weighted_covariance<T>(
+    data: ArrayView2<T>,
+    weights: &[f64],
+) -> Result<Array2<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check array lengths match
+    check_lengths(data.len_of(Axis(1)), &[weights.len()])?;
+
+    // compute the upper triangle and mirror it
+    let rows: Vec<Vec<f64>> = data
+        .axis_iter(Axis(0))
+        .map(|r| r.iter().map(|v| v.to_f64()).collect())
+        .collect();
+    let k = rows.len();
+    let mut cov = Array2::<f64>::zeros((k, k));
+    (0..k).for_each(|j| {
+        (j..k).for_each(|l| {
+            let c = covariance(&rows[j], &rows[l], weights);
+            cov[[j, l]] = c;
+            cov[[l, j]] = c;
+        });
+    });
+
+    Ok(cov)
+}
+
+/// Compute the weighted Pearson correlation coefficient.
+///
+/// # Description
+///
+/// This function computes the Pearson correlation coefficient of two datasets
+/// from their weighted covariance and variances:
+///
+/// ```text
+/// r = C_ab / √(σ²_a × σ²_b)
+/// ```
+///
+/// See `weighted_variance` and `weighted_covariance` for the weighting
+/// conventions. The normalization `n₀` cancels, so `r` does not depend on the
+/// scale of the weights.
+///
+/// # Arguments
+///
+/// * `data_a`: The first dataset for correlation analysis. Must be the same
+///    length as `data_b`.
+/// * `data_b`: The second dataset for correlation analysis. Must be the same
+///    length as `data_a`.
+/// * `weights`: The associated weights for each observation pair. Must be the
+///    same length as both input datasets.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The weighted Pearson correlation coefficient, ranging between
+///    -1.0 (negative correlation), 0.0 (no correlation) and 1.0 (positive
+///    correlation). 0.0 if there are less than 2 observations or either
+///    dataset is constant.
+/// * `Err(ImgalError)`: If input array lengths do not match.
+pub fn weighted_pearson<T>(data_a: &[T], data_b: &[T], weights: &[f64]) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check array lengths match
+    check_lengths(data_a.len(), &[data_b.len(), weights.len()])?;
+
+    let var_a = covariance(data_a, data_a, weights);
+    let var_b = covariance(data_b, data_b, weights);
+    let denom = (var_a * var_b).sqrt();
+    if denom > 0.0 {
+        Ok((covariance(data_a, data_b, weights) / denom).clamp(-1.0, 1.0))
+    } else {
+        Ok(0.0)
+    }
+}
+
+/// Check that the other array lengths match the data length.
+fn check_lengths(len: usize, others: &[usize]) -> Result<(), ImgalError> {
+    match others.iter().find(|&&o| o != len) {
+        Some(&o) => Err(ImgalError::MismatchedArrayLengths {
+            a_arr_len: len,
+            b_arr_len: o,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Compute the weighted mean of equal length data and weights, 0.0 if the
+/// weights sum to 0.0.
+fn mean<T: ToFloat64>(data: &[T], weights: &[f64]) -> f64 {
+    let total_w: f64 = weights.iter().sum();
+    if total_w == 0.0 {
+        return 0.0;
+    }
+
+    data.iter()
+        .zip(weights)
+        .map(|(v, w)| w * v.to_f64())
+        .sum::<f64>()
+        / total_w
+}
+
+/// Compute the unbiased weighted covariance of equal length data and weights,
+/// 0.0 if there are no weighted pairs.
+fn covariance<S: ToFloat64, T: ToFloat64>(data_a: &[S], data_b: &[T], weights: &[f64]) -> f64 {
+    let total_w: f64 = weights.iter().sum();
+    let pairs = total_w * total_w - weights.iter().map(|w| w * w).sum::<f64>();
+    if data_a.len() < 2 || pairs <= 0.0 {
+        return 0.0;
+    }
+    let mean_a = mean(data_a, weights);
+    let mean_b = mean(data_b, weights);
+    let sum: f64 = data_a
+        .iter()
+        .zip(data_b)
+        .zip(weights)
+        .map(|((a, b), w)| w * (a.to_f64() - mean_a) * (b.to_f64() - mean_b))
+        .sum();
+
+    total_w * sum / pairs
+}
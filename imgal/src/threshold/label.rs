@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use ndarray::{ArrayD, ArrayViewD, Zip};
+use rayon::prelude::*;
+
+use crate::error::warning::{self, Warning};
+use crate::error::{ImgalError, validate};
+use crate::threshold::otsu::otsu_threshold;
+use crate::threshold::percentile::percentile_threshold;
+use crate::traits::numeric::ToFloat64;
+
+/// Automatic threshold methods.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThresholdMethod {
+    /// The Otsu threshold with 256 histogram bins, see `threshold::otsu`.
+    Otsu,
+    /// The percentile threshold with the given percentile, see
+    /// `threshold::percentile`.
+    Percentile(f64),
+}
+
+/// Create a boolean mask with an independent threshold inside each labeled
+/// region.
+///
+/// # Description
+///
+/// This function computes a threshold value from the pixels of each labeled
+/// region (_e.g._ each segmented cell) with the given method, and creates a
+/// threshold mask (as a boolean array) where each pixel is compared to the
+/// threshold value of its own region. Pixels with the label 0 (_i.e._ the
+/// default value of the label type) are unlabeled and set to `false`.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional image or array.
+/// * `labels`: The label image, pixels with the same label belong to the same
+///   region. Must match the shape of `data`.
+/// * `method`: The threshold method applied to each region.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<bool>)`: A boolean array of the same shape as the input image
+///   with pixels that are greater than the threshold value of their region set
+///   as `true` and all other pixels set as `false`.
+/// * `Err(ImgalError)`: If the labels shape does not match the image shape. If
+///   the percentile is not in [0, 100].
+pub fn per_label<T, L>(
+    data: ArrayViewD<T>,
+    labels: ArrayViewD<L>,
+    method: ThresholdMethod,
+) -> Result<ArrayD<bool>, ImgalError>
+where
+    T: ToFloat64,
+    L: Copy + Eq + Hash + Default + Send + Sync,
+{
+    // check if the labels and method parameters are valid
+    validate::same_shape(labels.shape(), data.shape())?;
+    if let ThresholdMethod::Percentile(p) = method {
+        validate::in_range("p", p, 0.0, 100.0)?;
+    }
+
+    // group the pixel values by region
+    let background = L::default();
+    let mut regions: HashMap<L, Vec<f64>> = HashMap::new();
+    Zip::from(&data).and(&labels).for_each(|v, &l| {
+        if l != background {
+            regions.entry(l).or_default().push(v.to_f64());
+        }
+    });
+
+    // compute the threshold value of each region
+    let thresholds: HashMap<L, f64> = regions
+        .into_par_iter()
+        .map(|(l, mut values)| {
+            let t = match method {
+                ThresholdMethod::Otsu => otsu_threshold(&values, 256),
+                ThresholdMethod::Percentile(p) => percentile_threshold(&mut values, p),
+            };
+            (l, t)
+        })
+        .collect();
+
+    // create output mask of same shape and apply the region thresholds
+    let mut mask = ArrayD::<bool>::default(data.dim());
+    Zip::from(&data)
+        .and(&labels)
+        .and(&mut mask)
+        .par_for_each(|v, l, mp| {
+            *mp = thresholds.get(l).is_some_and(|&t| v.to_f64() > t);
+        });
+    if !mask.iter().any(|&m| m) {
+        warning::warn(Warning::EmptyMask);
+    }
+
+    Ok(mask)
+}
@@ -1,5 +1,6 @@
 use ndarray::{ArrayD, ArrayViewD, Zip};
 
+use crate::error::warning::{self, Warning};
 use crate::traits::numeric::ToFloat64;
 
 /// Create a boolean mask from a threshold value.
@@ -28,6 +29,9 @@ where
     Zip::from(data).and(&mut mask).par_for_each(|&ip, mp| {
         *mp = ip > threshold;
     });
+    if !mask.iter().any(|&m| m) {
+        warning::warn(Warning::EmptyMask);
+    }
 
     mask
 }
@@ -1,3 +1,9 @@
 //! Threshold functions.
+pub mod label;
+pub use label::{ThresholdMethod, per_label};
 pub mod manual;
 pub use manual::manual_mask;
+pub mod otsu;
+pub use otsu::{multi_otsu, otsu, otsu_mask};
+pub mod percentile;
+pub use percentile::percentile;
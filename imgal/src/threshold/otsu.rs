@@ -0,0 +1,336 @@
+use ndarray::{ArrayD, ArrayViewD, Zip};
+
+use crate::error::warning::{self, Warning};
+use crate::error::{ImgalError, validate};
+use crate::image::histogram::bin_index;
+use crate::statistics::histogram::masked_values;
+use crate::traits::numeric::ToFloat64;
+use crate::version::{self, AlgorithmVersion};
+
+/// Compute the Otsu threshold value of an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the threshold value that separates the image
+/// histogram into a background and a foreground class with the maximum
+/// between-class variance (Otsu's method):
+///
+/// ```text
+/// σ²(k) = ω₀(k) × ω₁(k) × (μ₀(k) - μ₁(k))²
+/// ```
+///
+/// where `ω₀` and `ω₁` are the pixel counts and `μ₀` and `μ₁` are the mean bin
+/// positions of the classes below and above bin `k`. The histogram spans the
+/// minimum and maximum values of the (masked) image and the threshold is the
+/// upper edge of the last background bin. If several bins share the maximum
+/// variance, the first one is used.
+///
+/// The histogram binning of this function is versioned, see
+/// `version::AlgorithmVersion`.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional image or array.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded from
+///   the histogram. Must match the shape of `data`.
+/// * `bins`: The number of histogram bins, default = 256. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The Otsu threshold value. If all (masked) pixels have the same
+///   value, that value is returned.
+/// * `Err(ImgalError)`: If bins is 0. If the mask shape does not match the image
+///   shape. If the image is empty or the mask excludes all pixels.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TSMC.1979.4310076>
+pub fn otsu<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+    bins: Option<usize>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(256);
+
+    // check if the bins parameter is valid and collect the (masked) values
+    validate_bins(bins)?;
+    let values = masked_values(data, mask)?;
+
+    Ok(otsu_threshold(&values, bins))
+}
+
+/// Create a boolean mask with the Otsu threshold value.
+///
+/// # Description
+///
+/// This function computes the Otsu threshold value of the input image (see
+/// `otsu`) and creates a threshold mask (as a boolean array) at that value.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional image or array.
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded from
+///   the histogram and set to `false` in the output. Must match the shape of
+///   `data`.
+/// * `bins`: The number of histogram bins, default = 256. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<bool>)`: A boolean array of the same shape as the input image
+///   with pixels that are greater than the threshold value set as `true` and
+///   pixels that are below the threshold value set as `false`.
+/// * `Err(ImgalError)`: If bins is 0. If the mask shape does not match the image
+///   shape. If the image is empty or the mask excludes all pixels.
+pub fn otsu_mask<T>(
+    data: ArrayViewD<T>,
+    mask: Option<ArrayViewD<bool>>,
+    bins: Option<usize>,
+) -> Result<ArrayD<bool>, ImgalError>
+where
+    T: ToFloat64,
+{
+    let threshold = otsu(data.view(), mask.clone(), bins)?;
+
+    // create output mask of same shape and apply threshold
+    let mut output = ArrayD::<bool>::default(data.dim());
+    Zip::from(&data).and(&mut output).par_for_each(|v, op| {
+        *op = v.to_f64() > threshold;
+    });
+    if let Some(msk) = mask {
+        Zip::from(&mut output).and(&msk).par_for_each(|op, &m| {
+            *op &= m;
+        });
+    }
+    if !output.iter().any(|&m| m) {
+        warning::warn(Warning::EmptyMask);
+    }
+
+    Ok(output)
+}
+
+/// Compute the multi-class Otsu threshold values and label image of an
+/// n-dimensional image.
+///
+/// # Description
+///
+/// This function generalizes Otsu's method to more than two classes, it
+/// computes the `classes - 1` threshold values that split the image histogram
+/// into `classes` intensity classes with the maximum between-class variance:
+///
+/// ```text
+/// σ² = Σ ωᵢ × (μᵢ - μ)²
+/// ```
+///
+/// where `ωᵢ` and `μᵢ` are the pixel count and mean bin position of class `i`
+/// and `μ` is the mean bin position of the image. The optimal split is found
+/// exactly by dynamic programming over the histogram bins, each class spans at
+/// least one bin. The histogram spans the minimum and maximum values of the
+/// image and each threshold is the upper edge of the last bin of a class. With
+/// 2 classes the threshold is equal to `otsu`.
+///
+/// The histogram binning of this function is versioned, see
+/// `version::AlgorithmVersion`.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional image or array.
+/// * `classes`: The number of intensity classes. Must be >= 2, <= 256 and
+///   <= bins.
+/// * `bins`: The number of histogram bins, default = 256. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok((Vec<f64>, ArrayD<u8>))`: The `classes - 1` ascending threshold values
+///   and a label image of the same shape as the input image. Each pixel is
+///   labeled with the number of threshold values it is greater than (_i.e._
+///   0 for the darkest and `classes - 1` for the brightest class). If all
+///   pixels have the same value, every threshold is that value.
+/// * `Err(ImgalError)`: If bins is 0. If classes is < 2, > 256 or > bins. If
+///   the image is empty.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1109/TSMC.1979.4310076>
+pub fn multi_otsu<T>(
+    data: ArrayViewD<T>,
+    classes: usize,
+    bins: Option<usize>,
+) -> Result<(Vec<f64>, ArrayD<u8>), ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let bins = bins.unwrap_or(256);
+
+    // check if the bins and classes parameters are valid
+    validate_bins(bins)?;
+    validate::in_range("classes", classes as f64, 2.0, 256.0_f64.min(bins as f64))?;
+
+    // construct the histogram of the pixel values
+    let values = masked_values(data.view(), None)?;
+    let hist = Histogram::new(&values, bins);
+    let thresholds = if hist.bin_width == 0.0 {
+        vec![hist.min; classes - 1]
+    } else {
+        // prefix sums of the bin counts and bin positions
+        let mut w = vec![0.0; bins + 1];
+        let mut m = vec![0.0; bins + 1];
+        hist.counts.iter().enumerate().for_each(|(i, &c)| {
+            w[i + 1] = w[i] + c;
+            m[i + 1] = m[i] + i as f64 * c;
+        });
+        // the between-class variance contribution of the bins [i, j)
+        let cost = |i: usize, j: usize| {
+            let wi = w[j] - w[i];
+            if wi > 0.0 {
+                let mi = m[j] - m[i];
+                mi * mi / wi
+            } else {
+                0.0
+            }
+        };
+
+        // best[c][j] is the maximum variance of the bins [0, j) in c + 1 classes
+        let mut best = vec![vec![f64::NEG_INFINITY; bins + 1]; classes];
+        let mut split = vec![vec![0; bins + 1]; classes];
+        (1..=bins).for_each(|j| best[0][j] = cost(0, j));
+        for c in 1..classes {
+            for j in (c + 1)..=bins {
+                for i in c..j {
+                    let var = best[c - 1][i] + cost(i, j);
+                    if var > best[c][j] {
+                        best[c][j] = var;
+                        split[c][j] = i;
+                    }
+                }
+            }
+        }
+
+        // trace back the class boundaries
+        let mut edges = vec![0; classes - 1];
+        let mut j = bins;
+        for c in (1..classes).rev() {
+            j = split[c][j];
+            edges[c - 1] = j;
+        }
+        edges.iter().map(|&e| hist.edge(e)).collect()
+    };
+
+    // label each pixel with its class
+    let mut labels = ArrayD::<u8>::zeros(data.dim());
+    Zip::from(&data).and(&mut labels).par_for_each(|v, lp| {
+        let v = v.to_f64();
+        *lp = thresholds.iter().filter(|&&t| v > t).count() as u8;
+    });
+
+    Ok((thresholds, labels))
+}
+
+/// Compute the Otsu threshold value of non-empty values.
+pub(crate) fn otsu_threshold(values: &[f64], bins: usize) -> f64 {
+    histogram_otsu_threshold(&Histogram::new(values, bins))
+}
+
+/// Compute the Otsu threshold value of a non-empty histogram.
+pub(crate) fn histogram_otsu_threshold(hist: &Histogram) -> f64 {
+    if hist.bin_width == 0.0 {
+        return hist.min;
+    }
+
+    // find the bin with the maximum between-class variance
+    let bins = hist.counts.len();
+    let total: f64 = hist.counts.iter().sum();
+    let total_sum: f64 = hist
+        .counts
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| i as f64 * c)
+        .sum();
+    let mut w_0 = 0.0;
+    let mut sum_0 = 0.0;
+    let mut best = (0, f64::NEG_INFINITY);
+    for (k, &c) in hist.counts.iter().enumerate().take(bins - 1) {
+        w_0 += c;
+        sum_0 += k as f64 * c;
+        let w_1 = total - w_0;
+        if w_0 == 0.0 || w_1 == 0.0 {
+            continue;
+        }
+        let mu_diff = sum_0 / w_0 - (total_sum - sum_0) / w_1;
+        let var = w_0 * w_1 * mu_diff * mu_diff;
+        if var > best.1 {
+            best = (k, var);
+        }
+    }
+
+    hist.edge(best.0 + 1)
+}
+
+/// Check if the number of histogram bins is at least 1.
+pub(crate) fn validate_bins(bins: usize) -> Result<(), ImgalError> {
+    if bins == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "bins",
+            reason: "must be >= 1 but got 0".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// The histogram of a set of values.
+pub(crate) struct Histogram {
+    counts: Vec<f64>,
+    min: f64,
+    bin_width: f64,
+    version: AlgorithmVersion,
+}
+
+impl Histogram {
+    /// Construct the histogram over the [min, max] range of non-empty values,
+    /// the bin width is 0.0 if all values are equal.
+    fn new(values: &[f64], bins: usize) -> Self {
+        let (min, max) = values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+
+        // count the values per bin
+        let mut hist = Histogram::with_range(min, max, bins);
+        values.iter().for_each(|&v| hist.add(v));
+
+        hist
+    }
+
+    /// Construct an empty histogram over the [min, max] range, binned with the
+    /// algorithm version in effect on the calling thread.
+    pub(crate) fn with_range(min: f64, max: f64, bins: usize) -> Self {
+        Histogram {
+            counts: vec![0.0; bins],
+            min,
+            bin_width: (max - min) / bins as f64,
+            version: version::algorithm::current(),
+        }
+    }
+
+    /// Count a value, values outside of the range are counted in the first or
+    /// last bin.
+    pub(crate) fn add(&mut self, value: f64) {
+        let bins = self.counts.len();
+        if self.bin_width > 0.0 {
+            self.counts[bin_index(value, self.min, self.bin_width, bins, self.version)] += 1.0;
+        } else {
+            self.counts[0] += 1.0;
+        }
+    }
+
+    /// The lower edge value of a bin.
+    fn edge(&self, bin: usize) -> f64 {
+        self.min + bin as f64 * self.bin_width
+    }
+}
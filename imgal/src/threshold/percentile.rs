@@ -0,0 +1,51 @@
+use ndarray::ArrayViewD;
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::filter::exposure;
+use crate::statistics::histogram::masked_values;
+use crate::traits::numeric::ToFloat64;
+
+/// Compute a percentile threshold value of an n-dimensional image.
+///
+/// # Description
+///
+/// This function computes the `p` percentile of the (masked) pixel values with
+/// linear interpolation between the closest ranks, _e.g._ a `p` of 95.0 keeps
+/// the brightest 5% of the pixels above the threshold.
+///
+/// # Arguments
+///
+/// * `data`: An n-dimensional image or array.
+/// * `p`: The percentile. Must be in [0, 100].
+/// * `mask`: An optional boolean mask, pixels set to `false` are excluded from
+///   the percentile. Must match the shape of `data`.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The percentile threshold value.
+/// * `Err(ImgalError)`: If p is not in [0, 100]. If the mask shape does not
+///   match the image shape. If the image is empty or the mask excludes all
+///   pixels.
+pub fn percentile<T>(
+    data: ArrayViewD<T>,
+    p: f64,
+    mask: Option<ArrayViewD<bool>>,
+) -> Result<f64, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the percentile parameter is valid
+    validate::in_range("p", p, 0.0, 100.0)?;
+
+    let mut values = masked_values(data, mask)?;
+
+    Ok(percentile_threshold(&mut values, p))
+}
+
+/// Compute a percentile of non-empty values, the values are sorted in place.
+pub(crate) fn percentile_threshold(values: &mut [f64], p: f64) -> f64 {
+    values.par_sort_unstable_by(|a, b| a.total_cmp(b));
+
+    exposure::percentile(values, p)
+}
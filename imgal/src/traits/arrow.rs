@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, Int8Array, Int16Array, Int32Array, Int64Array, UInt8Array, UInt16Array, UInt32Array,
+    UInt64Array,
+};
+
+/// Label types that can be stored as an Apache Arrow column.
+pub trait ArrowLabel: Sized {
+    fn to_arrow(values: &[Self]) -> ArrayRef;
+}
+
+// integer labels to the Arrow array of the same width, usize to u64
+impl ArrowLabel for u8 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(UInt8Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for u16 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(UInt16Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for u32 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(UInt32Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for u64 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(UInt64Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for i8 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(Int8Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for i16 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(Int16Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for i32 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(Int32Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for i64 {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(Int64Array::from(values.to_vec()))
+    }
+}
+
+impl ArrowLabel for usize {
+    fn to_arrow(values: &[Self]) -> ArrayRef {
+        Arc::new(UInt64Array::from_iter_values(
+            values.iter().map(|&v| v as u64),
+        ))
+    }
+}
@@ -1,2 +1,4 @@
 //! Internal trait module.
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod numeric;
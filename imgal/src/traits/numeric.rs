@@ -84,3 +84,78 @@ impl ToFloat64 for f64 {
         self
     }
 }
+
+pub trait FromFloat64: Copy + Send + Sync {
+    fn from_f64(value: f64) -> Self;
+}
+
+// f64 to integer, rounded to the nearest integer and saturated at the type
+// bounds (NaN is mapped to 0)
+impl FromFloat64 for u8 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as u8
+    }
+}
+
+impl FromFloat64 for u16 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as u16
+    }
+}
+
+impl FromFloat64 for u32 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as u32
+    }
+}
+
+impl FromFloat64 for u64 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as u64
+    }
+}
+
+impl FromFloat64 for i8 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as i8
+    }
+}
+
+impl FromFloat64 for i16 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as i16
+    }
+}
+
+impl FromFloat64 for i32 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as i32
+    }
+}
+
+impl FromFloat64 for i64 {
+    fn from_f64(value: f64) -> Self {
+        value.round() as i64
+    }
+}
+
+// f64 to float, there is precision loss with f32
+impl FromFloat64 for f32 {
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl FromFloat64 for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+/// Floating point element types. Generic over `Float`, an array keeps its
+/// `f32` or `f64` storage while each value is computed in `f64`.
+pub trait Float: ToFloat64 + FromFloat64 {}
+
+impl Float for f32 {}
+
+impl Float for f64 {}
@@ -0,0 +1,283 @@
+use ndarray::{Array2, ArrayD, ArrayView2, ArrayViewD, s};
+use rayon::prelude::*;
+
+use crate::error::ImgalError;
+use crate::filter::spatial::{BorderMode, border_index};
+use crate::traits::numeric::ToFloat64;
+
+/// Interpolation methods to sample an image between pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// The value of the nearest pixel.
+    Nearest,
+    /// The (bi/tri)linear interpolation of the 2 nearest pixels per axis.
+    Linear,
+    /// The (bi/tri)cubic Catmull-Rom interpolation of the 4 nearest pixels per
+    /// axis.
+    Cubic,
+}
+
+/// Transform a 2 or 3-dimensional image with an affine matrix.
+///
+/// # Description
+///
+/// This function maps the image with the homogeneous affine `matrix`, which
+/// maps input (row, col, 1) or (pln, row, col, 1) coordinates to output
+/// coordinates. The output value at each pixel is the input sampled at the
+/// inverse mapped position:
+///
+/// ```text
+/// output(p) = input(M⁻¹ × p)
+/// ```
+///
+/// With a constant `border`, output pixels that sample outside of the input
+/// image are set to the constant value, otherwise the input is extended with
+/// the border mode. Apply the matrix estimated by `register::affine` to the
+/// moving image to register it with the reference image.
+///
+/// # Arguments
+///
+/// * `data`: The 2 or 3-dimensional image.
+/// * `matrix`: The (n + 1) x (n + 1) affine matrix of an n-dimensional image.
+///   The last row must be (0, ..., 0, 1) and the matrix must be invertible.
+/// * `interpolation`: The interpolation method, default =
+///   `Interpolation::Linear`.
+/// * `border`: The border mode, default = `BorderMode::Constant(0.0)`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The transformed image with the same shape as `data`.
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional. If the matrix is not
+///   a finite, invertible affine matrix matching the number of dimensions.
+pub fn warp_affine<T>(
+    data: ArrayViewD<T>,
+    matrix: ArrayView2<f64>,
+    interpolation: Option<Interpolation>,
+    border: Option<BorderMode>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let interpolation = interpolation.unwrap_or(Interpolation::Linear);
+    let border = border.unwrap_or(BorderMode::Constant(0.0));
+
+    // check if the data and matrix parameters are valid
+    validate_ndim(data.ndim())?;
+    let n = data.ndim();
+    let invalid = || ImgalError::InvalidParameter {
+        param_name: "matrix",
+        reason: format!(
+            "must be a finite, invertible {} x {} affine matrix with the last row (0, ..., 0, 1)",
+            n + 1,
+            n + 1
+        ),
+    };
+    if matrix.dim() != (n + 1, n + 1)
+        || matrix.iter().any(|v| !v.is_finite())
+        || matrix
+            .row(n)
+            .iter()
+            .enumerate()
+            .any(|(i, &v)| v != if i == n { 1.0 } else { 0.0 })
+    {
+        return Err(invalid());
+    }
+    let inverse = invert_affine(matrix).ok_or_else(invalid)?;
+
+    // sample the input at the inverse mapped position of each output pixel
+    let data = data.mapv(|v| v.to_f64());
+    let data = data.as_standard_layout();
+    let shape = data.shape().to_vec();
+    let sampler = Sampler::new(data.as_slice().unwrap(), &shape, interpolation, border);
+    let cval = match border {
+        BorderMode::Constant(c) => c,
+        _ => 0.0,
+    };
+    let mut output = ArrayD::<f64>::zeros(data.raw_dim());
+    output
+        .as_slice_mut()
+        .unwrap()
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, o)| {
+            let mut idx = [0.0; 3];
+            let mut rem = i;
+            (0..n).rev().for_each(|ax| {
+                idx[ax] = (rem % shape[ax]) as f64;
+                rem /= shape[ax];
+            });
+            let mut point = [0.0; 3];
+            (0..n).for_each(|r| {
+                point[r] = (0..n).map(|c| inverse[[r, c]] * idx[c]).sum::<f64>() + inverse[[r, n]];
+            });
+            *o = sampler.sample(&point[..n]).unwrap_or(cval);
+        });
+
+    Ok(output)
+}
+
+/// An interpolating sampler of a 2 or 3-dimensional image in standard
+/// (row-major) layout.
+pub(crate) struct Sampler<'a> {
+    data: &'a [f64],
+    shape: [usize; 3],
+    strides: [usize; 3],
+    interpolation: Interpolation,
+    border: BorderMode,
+}
+
+impl<'a> Sampler<'a> {
+    /// Create a sampler of the image with the given shape.
+    pub(crate) fn new(
+        data: &'a [f64],
+        shape: &[usize],
+        interpolation: Interpolation,
+        border: BorderMode,
+    ) -> Self {
+        let mut sh = [1; 3];
+        let mut strides = [0; 3];
+        let mut stride = 1;
+        (0..shape.len()).rev().for_each(|ax| {
+            sh[ax] = shape[ax];
+            strides[ax] = stride;
+            stride *= shape[ax];
+        });
+
+        Sampler {
+            data,
+            shape: sh,
+            strides,
+            interpolation,
+            border,
+        }
+    }
+
+    /// Sample the image at a sub-pixel position, `None` if the position is
+    /// outside of the image with a constant border or not finite.
+    pub(crate) fn sample(&self, point: &[f64]) -> Option<f64> {
+        // the (offset, weight) taps of each axis, unused axes have one tap
+        let mut taps = [[(0usize, 0.0f64); 4]; 3];
+        let mut counts = [1usize; 3];
+        taps.iter_mut().for_each(|t| t[0] = (0, 1.0));
+        for (ax, &p) in point.iter().enumerate() {
+            let n = self.shape[ax];
+            let p = match self.border {
+                BorderMode::Constant(_) => {
+                    // snap positions within rounding error of the edge inside
+                    let max = (n - 1) as f64;
+                    if !(p >= -1e-9 && p <= max + 1e-9) {
+                        return None;
+                    }
+                    p.clamp(0.0, max)
+                }
+                _ if p.is_finite() => p,
+                _ => return None,
+            };
+            let f = p.floor();
+            let t = p - f;
+            let f = f as isize;
+            let index = |i: isize| self.index(i, n) * self.strides[ax];
+            match self.interpolation {
+                Interpolation::Nearest => {
+                    taps[ax][0] = (index(p.round() as isize), 1.0);
+                }
+                Interpolation::Linear => {
+                    taps[ax][0] = (index(f), 1.0 - t);
+                    taps[ax][1] = (index(f + 1), t);
+                    counts[ax] = 2;
+                }
+                Interpolation::Cubic => {
+                    catmull_rom(t)
+                        .into_iter()
+                        .enumerate()
+                        .for_each(|(k, w)| taps[ax][k] = (index(f + k as isize - 1), w));
+                    counts[ax] = 4;
+                }
+            }
+        }
+
+        // sum the weighted taps
+        let mut value = 0.0;
+        taps[0][..counts[0]].iter().for_each(|&(o0, w0)| {
+            taps[1][..counts[1]].iter().for_each(|&(o1, w1)| {
+                taps[2][..counts[2]].iter().for_each(|&(o2, w2)| {
+                    value += w0 * w1 * w2 * self.data[o0 + o1 + o2];
+                });
+            });
+        });
+
+        Some(value)
+    }
+
+    /// Map a tap index to an image index, constant borders replicate the edge
+    /// for the taps of positions inside of the image.
+    fn index(&self, idx: isize, n: usize) -> usize {
+        match self.border {
+            BorderMode::Constant(_) => idx.clamp(0, n as isize - 1) as usize,
+            border => border_index(idx, n, border).unwrap(),
+        }
+    }
+}
+
+/// Invert an (n + 1) x (n + 1) homogeneous affine matrix, `None` if singular.
+pub(crate) fn invert_affine(matrix: ArrayView2<f64>) -> Option<Array2<f64>> {
+    // invert the linear part with Gauss-Jordan elimination
+    let n = matrix.nrows() - 1;
+    let mut a = matrix.slice(s![..n, ..n]).to_owned();
+    let mut inv = Array2::<f64>::eye(n);
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[[i, col]].abs().total_cmp(&a[[j, col]].abs()))
+            .unwrap();
+        let d = a[[pivot, col]];
+        if d == 0.0 || !d.is_finite() {
+            return None;
+        }
+        (0..n).for_each(|c| {
+            a.swap([pivot, c], [col, c]);
+            inv.swap([pivot, c], [col, c]);
+        });
+        a.row_mut(col).mapv_inplace(|v| v / d);
+        inv.row_mut(col).mapv_inplace(|v| v / d);
+        for r in (0..n).filter(|&r| r != col) {
+            let factor = a[[r, col]];
+            (0..n).for_each(|c| {
+                a[[r, c]] -= factor * a[[col, c]];
+                inv[[r, c]] -= factor * inv[[col, c]];
+            });
+        }
+    }
+
+    // the inverse translation is -A⁻¹ × t
+    let mut output = Array2::<f64>::eye(n + 1);
+    output.slice_mut(s![..n, ..n]).assign(&inv);
+    let t = inv.dot(&matrix.slice(s![..n, n]));
+    output.slice_mut(s![..n, n]).assign(&(-t));
+
+    Some(output)
+}
+
+/// Check if the image is 2 or 3-dimensional.
+pub(crate) fn validate_ndim(ndim: usize) -> Result<(), ImgalError> {
+    if ndim != 2 && ndim != 3 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: format!("must be 2 or 3-dimensional but got {} dimensions", ndim),
+        });
+    }
+
+    Ok(())
+}
+
+/// Compute the Catmull-Rom weights of the 4 taps around a fractional offset.
+fn catmull_rom(t: f64) -> [f64; 4] {
+    let (t2, t3) = (t * t, t * t * t);
+
+    [
+        0.5 * (-t3 + 2.0 * t2 - t),
+        0.5 * (3.0 * t3 - 5.0 * t2 + 2.0),
+        0.5 * (-3.0 * t3 + 4.0 * t2 + t),
+        0.5 * (t3 - t2),
+    ]
+}
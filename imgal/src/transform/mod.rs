@@ -0,0 +1,5 @@
+//! Geometric transform functions.
+pub mod affine;
+pub use affine::{Interpolation, warp_affine};
+pub mod resize;
+pub use resize::{rescale, resize};
@@ -0,0 +1,161 @@
+use ndarray::{ArrayD, ArrayViewD, IxDyn};
+use rayon::prelude::*;
+
+use crate::error::{ImgalError, validate};
+use crate::filter::{self, BorderMode};
+use crate::traits::numeric::ToFloat64;
+use crate::transform::affine::{Interpolation, Sampler, validate_ndim};
+
+/// Resize a 2 or 3-dimensional image to a new shape.
+///
+/// # Description
+///
+/// This function resamples the image on a grid with the new `shape`, aligning
+/// the outer edges of the edge pixels of the input and output images. The
+/// output pixel at index `j` of an axis samples the input at:
+///
+/// ```text
+/// x = (j + 0.5) × n_in / n_out - 0.5
+/// ```
+///
+/// Positions past the edges reflect the input. When an axis is downsampled and
+/// `anti_aliasing` is enabled, the image is smoothed first with a Gaussian of
+/// σ = (n_in / n_out - 1) / 2 along that axis to suppress aliasing.
+///
+/// # Arguments
+///
+/// * `data`: The 2 or 3-dimensional image.
+/// * `shape`: The output shape. Must have one value per axis and each value
+///   must be >= 1.
+/// * `interpolation`: The interpolation method, default =
+///   `Interpolation::Linear`.
+/// * `anti_aliasing`: If `true`, downsampled axes are smoothed before
+///   resampling, default = `true`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The resized image with the given shape.
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional. If shape does not
+///   have one value per axis or a value is 0.
+pub fn resize<T>(
+    data: ArrayViewD<T>,
+    shape: &[usize],
+    interpolation: Option<Interpolation>,
+    anti_aliasing: Option<bool>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // set optional parameters if needed
+    let interpolation = interpolation.unwrap_or(Interpolation::Linear);
+    let anti_aliasing = anti_aliasing.unwrap_or(true);
+
+    // check if the data and shape parameters are valid
+    validate_ndim(data.ndim())?;
+    if shape.len() != data.ndim() || shape.contains(&0) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "shape",
+            reason: format!("must have {} values >= 1 but got {:?}", data.ndim(), shape),
+        });
+    }
+
+    // smooth the downsampled axes
+    let n = data.ndim();
+    let factors: Vec<f64> = data
+        .shape()
+        .iter()
+        .zip(shape.iter())
+        .map(|(&i, &o)| i as f64 / o as f64)
+        .collect();
+    let sigma: Vec<f64> = factors.iter().map(|f| ((f - 1.0) / 2.0).max(0.0)).collect();
+    let data = if anti_aliasing && sigma.iter().any(|&s| s > 0.0) {
+        filter::gaussian(data, &sigma, Some(BorderMode::Reflect), None)?
+    } else {
+        data.mapv(|v| v.to_f64())
+    };
+
+    // sample the input at the scaled position of each output pixel
+    let data = data.as_standard_layout();
+    let sampler = Sampler::new(
+        data.as_slice().unwrap(),
+        data.shape(),
+        interpolation,
+        BorderMode::Reflect,
+    );
+    let mut output = ArrayD::<f64>::zeros(IxDyn(shape));
+    output
+        .as_slice_mut()
+        .unwrap()
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, o)| {
+            let mut point = [0.0; 3];
+            let mut rem = i;
+            (0..n).rev().for_each(|ax| {
+                let j = (rem % shape[ax]) as f64;
+                point[ax] = (j + 0.5) * factors[ax] - 0.5;
+                rem /= shape[ax];
+            });
+            *o = sampler.sample(&point[..n]).unwrap();
+        });
+
+    Ok(output)
+}
+
+/// Rescale a 2 or 3-dimensional image by a factor per axis.
+///
+/// # Description
+///
+/// This function resizes the image (see `resize`) to the shape
+/// `round(n × scale)` of each axis, with at least 1 pixel per axis. Scales
+/// > 1.0 upsample and scales < 1.0 downsample an axis.
+///
+/// # Arguments
+///
+/// * `data`: The 2 or 3-dimensional image.
+/// * `scale`: The scale factor of each axis. Must have one value per axis and
+///   each value must be > 0.0.
+/// * `interpolation`: The interpolation method, default =
+///   `Interpolation::Linear`.
+/// * `anti_aliasing`: If `true`, downsampled axes are smoothed before
+///   resampling, default = `true`.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The rescaled image.
+/// * `Err(ImgalError)`: If data is not 2 or 3-dimensional. If scale does not
+///   have one value per axis or a value is <= 0.0.
+pub fn rescale<T>(
+    data: ArrayViewD<T>,
+    scale: &[f64],
+    interpolation: Option<Interpolation>,
+    anti_aliasing: Option<bool>,
+) -> Result<ArrayD<f64>, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the scale parameter is valid
+    validate_ndim(data.ndim())?;
+    if scale.len() != data.ndim() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "scale",
+            reason: format!(
+                "must have one value per axis, {} but got {}",
+                data.ndim(),
+                scale.len()
+            ),
+        });
+    }
+    scale
+        .iter()
+        .try_for_each(|&s| validate::positive("scale", s))?;
+
+    let shape: Vec<usize> = data
+        .shape()
+        .iter()
+        .zip(scale.iter())
+        .map(|(&n, &s)| ((n as f64 * s).round() as usize).max(1))
+        .collect();
+
+    resize(data, &shape, interpolation, anti_aliasing)
+}
@@ -0,0 +1,250 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ndarray::{Array2, ArrayD, ArrayView1, ArrayView2, ArrayViewD, Axis, IxDyn, Zip};
+
+use crate::error::{ImgalError, Warning, validate, warning};
+use crate::traits::numeric::ToFloat64;
+
+/// The result of a linear spectral unmixing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearUnmixing {
+    /// The non-negative abundance of each reference spectrum, with the same
+    /// shape as the input image except the channel axis, which has one entry
+    /// per reference spectrum.
+    pub abundances: ArrayD<f64>,
+    /// The Euclidean norm of the fit residual of each pixel, with the shape of
+    /// the input image without the channel axis.
+    pub residual: ArrayD<f64>,
+}
+
+/// Unmix a multichannel image into non-negative abundances of reference
+/// spectra.
+///
+/// # Description
+///
+/// This function models the spectrum (b) of each pixel as a non-negative linear
+/// combination of the reference spectra, the columns of `spectra` (A), and
+/// solves the non-negative least squares (NNLS) problem:
+///
+/// ```text
+/// minimize |A × x - b|²  subject to  x ≥ 0
+/// ```
+///
+/// with the Lawson-Hanson active set method. The Gram matrix AᵀA is computed
+/// once and shared by all pixels, the pixels are solved in parallel. The
+/// active set iterations of a pixel are limited to 3 × the number of
+/// components, a `Warning::NotConverged` reports the pixels that reach the
+/// limit. Unlike
+/// an unconstrained least squares fit, NNLS never assigns negative abundances
+/// to absent components. See `phasor::spectral::unmix` for the phasor based
+/// unmixing of up to 3 components.
+///
+/// # Arguments
+///
+/// * `data`: The n-dimensional multichannel (_e.g._ hyperspectral) image.
+/// * `spectra`: The (channel, component) matrix of reference spectra, one
+///   column per component. Must have one row per channel of `data`, at most as
+///   many components as channels and linearly independent columns.
+/// * `axis`: The channel axis, default = the last axis.
+///
+/// # Returns
+///
+/// * `Ok(LinearUnmixing)`: The abundances and the residual norm of each pixel.
+/// * `Err(ImgalError)`: If data is 0-dimensional. If axis is out of bounds. If
+///   the number of spectra rows does not match the number of channels. If the
+///   spectra are empty, not finite, or not linearly independent.
+///
+/// # Reference
+///
+/// <https://doi.org/10.1137/1.9781611971217>
+pub fn linear<T>(
+    data: ArrayViewD<T>,
+    spectra: ArrayView2<f64>,
+    axis: Option<usize>,
+) -> Result<LinearUnmixing, ImgalError>
+where
+    T: ToFloat64,
+{
+    // check if the data and axis parameters are valid
+    if data.ndim() == 0 {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            reason: "must be at least 1-dimensional but got 0 dimensions".to_string(),
+        });
+    }
+    let a = axis.unwrap_or(data.ndim() - 1);
+    validate::axis(a, data.ndim())?;
+
+    // check if the spectra parameter is valid
+    let (channels, components) = spectra.dim();
+    validate::same_length(data.len_of(Axis(a)), channels)?;
+    if components == 0 || components > channels || spectra.iter().any(|v| !v.is_finite()) {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "spectra",
+            reason: format!(
+                "must have between 1 and {} finite component columns but got {}",
+                channels, components
+            ),
+        });
+    }
+    let gram = spectra.t().dot(&spectra);
+    let all: Vec<usize> = (0..components).collect();
+    if solve(&gram, &all, &vec![0.0; components]).is_none() {
+        return Err(ImgalError::InvalidParameter {
+            param_name: "spectra",
+            reason: "must have linearly independent component columns".to_string(),
+        });
+    }
+    let tol =
+        10.0 * f64::EPSILON * gram.iter().fold(0.0f64, |m, v| m.max(v.abs())) * components as f64;
+
+    // solve the nnls problem of each pixel
+    let mut shape = data.shape().to_vec();
+    shape[a] = components;
+    let mut abundances = ArrayD::<f64>::zeros(IxDyn(&shape));
+    shape.remove(a);
+    let mut residual = ArrayD::<f64>::zeros(IxDyn(&shape));
+    let not_converged = AtomicUsize::new(0);
+    Zip::from(abundances.lanes_mut(Axis(a)))
+        .and(data.lanes(Axis(a)))
+        .and(&mut residual)
+        .par_for_each(|mut x_ln, b_ln, res| {
+            let b: Vec<f64> = b_ln.iter().map(|v| v.to_f64()).collect();
+            let atb = spectra.t().dot(&ArrayView1::from(&b));
+            let (x, converged) = nnls(&gram, atb.as_slice().unwrap(), tol);
+            if !converged {
+                not_converged.fetch_add(1, Ordering::Relaxed);
+            }
+            *res = spectra
+                .outer_iter()
+                .zip(b.iter())
+                .map(|(row, bv)| {
+                    let fit: f64 = row.iter().zip(x.iter()).map(|(r, xv)| r * xv).sum();
+                    (fit - bv).powi(2)
+                })
+                .sum::<f64>()
+                .sqrt();
+            x_ln.iter_mut().zip(x).for_each(|(o, v)| *o = v);
+        });
+
+    // report the pixels that reached the iteration limit
+    let count = not_converged.into_inner();
+    if count > 0 {
+        warning::warn(Warning::NotConverged {
+            operation: "linear unmixing",
+            count,
+        });
+    }
+
+    Ok(LinearUnmixing {
+        abundances,
+        residual,
+    })
+}
+
+/// Solve the non-negative least squares problem from the normal equations,
+/// AᵀA and Aᵀb, with the Lawson-Hanson active set method. Returns the solution
+/// and whether the method converged within its iteration limit.
+fn nnls(gram: &Array2<f64>, atb: &[f64], tol: f64) -> (Vec<f64>, bool) {
+    let n = atb.len();
+    let mut x = vec![0.0; n];
+    let mut passive = vec![false; n];
+    let gradient = |x: &[f64]| -> Vec<f64> {
+        (0..n)
+            .map(|i| atb[i] - (0..n).map(|j| gram[[i, j]] * x[j]).sum::<f64>())
+            .collect()
+    };
+
+    for _ in 0..3 * n {
+        // move the active variable with the largest gradient to the passive set
+        let w = gradient(&x);
+        let Some(j) = (0..n)
+            .filter(|&i| !passive[i] && w[i] > tol)
+            .max_by(|&i, &k| w[i].total_cmp(&w[k]))
+        else {
+            break;
+        };
+        passive[j] = true;
+
+        // solve the unconstrained problem on the passive set, stepping back
+        // towards the feasible region while a variable is not positive
+        loop {
+            let set: Vec<usize> = (0..n).filter(|&i| passive[i]).collect();
+            let rhs: Vec<f64> = set.iter().map(|&i| atb[i]).collect();
+            let z = solve(gram, &set, &rhs).unwrap_or_else(|| vec![0.0; set.len()]);
+            if z.iter().all(|&v| v > 0.0) {
+                x.iter_mut().for_each(|v| *v = 0.0);
+                set.iter().zip(z).for_each(|(&i, v)| x[i] = v);
+                break;
+            }
+            let (alpha, k) = set
+                .iter()
+                .zip(z.iter())
+                .filter(|&(_, &zv)| zv <= 0.0)
+                .map(|(&i, &zv)| (x[i] / (x[i] - zv), i))
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .unwrap();
+            set.iter()
+                .zip(z.iter())
+                .for_each(|(&i, &zv)| x[i] += alpha * (zv - x[i]));
+            x[k] = 0.0;
+            set.iter().for_each(|&i| {
+                if x[i] <= tol {
+                    x[i] = 0.0;
+                    passive[i] = false;
+                }
+            });
+            if !passive.iter().any(|&p| p) {
+                break;
+            }
+        }
+    }
+
+    // the solution is optimal if no active variable has a positive gradient
+    let w = gradient(&x);
+    let converged = (0..n).all(|i| passive[i] || w[i] <= tol);
+
+    (x, converged)
+}
+
+/// Solve the linear system of the rows and columns `set` of `matrix` with
+/// Gaussian elimination, `None` if the system is singular.
+fn solve(matrix: &Array2<f64>, set: &[usize], rhs: &[f64]) -> Option<Vec<f64>> {
+    let n = set.len();
+    let mut m: Vec<Vec<f64>> = set
+        .iter()
+        .zip(rhs.iter())
+        .map(|(&r, &b)| {
+            let mut row: Vec<f64> = set.iter().map(|&c| matrix[[r, c]]).collect();
+            row.push(b);
+            row
+        })
+        .collect();
+    let scale = m
+        .iter()
+        .flat_map(|row| row[..n].iter())
+        .fold(0.0f64, |s, v| s.max(v.abs()));
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| m[i][col].abs().total_cmp(&m[j][col].abs()))?;
+        if m[pivot][col].abs() <= 1e-12 * scale {
+            return None;
+        }
+        m.swap(pivot, col);
+        let (upper, lower) = m.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        lower.iter_mut().for_each(|row| {
+            let factor = row[col] / pivot_row[col];
+            row[col..]
+                .iter_mut()
+                .zip(&pivot_row[col..])
+                .for_each(|(v, p)| *v -= factor * p);
+        });
+    }
+    let mut x = vec![0.0; n];
+    for r in (0..n).rev() {
+        let sum: f64 = (r + 1..n).map(|c| m[r][c] * x[c]).sum();
+        x[r] = (m[r][n] - sum) / m[r][r];
+    }
+
+    Some(x)
+}
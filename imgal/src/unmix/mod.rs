@@ -0,0 +1,3 @@
+//! Spectral unmixing functions.
+pub mod linear;
+pub use linear::{LinearUnmixing, linear};
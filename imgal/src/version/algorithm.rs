@@ -0,0 +1,157 @@
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Numerical behavior versions of imgal's algorithms.
+///
+/// # Description
+///
+/// Each variant describes the exact numerical behavior of the versioned
+/// algorithms (_e.g._ the phasor integration scheme and image histogram
+/// binning). When a versioned algorithm's numerical behavior changes, a new
+/// variant is added and becomes `AlgorithmVersion::LATEST`, while older
+/// variants keep their behavior. `V1` is currently the only version, so
+/// pinning a version with `set` or `pinned` does not change any result yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum AlgorithmVersion {
+    /// The behavior of imgal 0.1. Phasor transforms sample the cosine and sine
+    /// waveforms at the start of each time bin and integrate with the
+    /// midpoint (_i.e._ rectangle) rule. Image and Otsu threshold histograms
+    /// use `bins` equal width bins over [min, max] with the max value placed in
+    /// the last bin.
+    V1 = 1,
+}
+
+impl AlgorithmVersion {
+    /// The newest algorithm version, used unless another version is pinned.
+    pub const LATEST: AlgorithmVersion = AlgorithmVersion::V1;
+
+    /// Get the algorithm version from its number.
+    ///
+    /// # Arguments
+    ///
+    /// * `number`: The algorithm version number (_e.g._ 1 for `V1`).
+    ///
+    /// # Returns
+    ///
+    /// * `Some(AlgorithmVersion)`: The algorithm version.
+    /// * `None`: If no algorithm version has the given number.
+    pub fn from_number(number: u8) -> Option<AlgorithmVersion> {
+        match number {
+            1 => Some(AlgorithmVersion::V1),
+            _ => None,
+        }
+    }
+
+    /// Get the algorithm version number.
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+}
+
+impl Default for AlgorithmVersion {
+    fn default() -> Self {
+        AlgorithmVersion::LATEST
+    }
+}
+
+impl fmt::Display for AlgorithmVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.number())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AlgorithmVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u8(self.number())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AlgorithmVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let number = u8::deserialize(deserializer)?;
+        AlgorithmVersion::from_number(number).ok_or_else(|| {
+            serde::de::Error::custom(format!("unknown algorithm version {}", number))
+        })
+    }
+}
+
+// the process wide version, 0 means unpinned (i.e. latest)
+static GLOBAL_VERSION: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    static THREAD_VERSION: Cell<Option<AlgorithmVersion>> = const { Cell::new(None) };
+}
+
+/// Get the algorithm version in effect on the calling thread.
+///
+/// # Description
+///
+/// The version pinned on the calling thread with `pinned` takes precedence,
+/// followed by the process wide version pinned with `set`, falling back to
+/// `AlgorithmVersion::LATEST`. Versioned algorithms read the version once on
+/// the calling thread, so the version also applies to their parallel work.
+///
+/// # Returns
+///
+/// * `AlgorithmVersion`: The algorithm version in effect.
+pub fn current() -> AlgorithmVersion {
+    THREAD_VERSION.with(|v| v.get()).unwrap_or_else(|| {
+        AlgorithmVersion::from_number(GLOBAL_VERSION.load(Ordering::Relaxed))
+            .unwrap_or(AlgorithmVersion::LATEST)
+    })
+}
+
+/// Get the algorithm version pinned on the calling thread with `pinned`, if
+/// any.
+pub(crate) fn thread_pinned() -> Option<AlgorithmVersion> {
+    THREAD_VERSION.with(|v| v.get())
+}
+
+/// Run a function with an algorithm version pinned on the calling thread.
+///
+/// # Arguments
+///
+/// * `version`: The algorithm version to pin while `f` runs.
+/// * `f`: The function to run.
+///
+/// # Returns
+///
+/// * `R`: The output of `f`. The previously pinned thread version is restored
+///   afterwards, also if `f` panics.
+pub fn pinned<F, R>(version: AlgorithmVersion, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    // restore the previous version on drop, so a panic in f does not leak the
+    // pinned version to later work on this thread
+    struct Restore(Option<AlgorithmVersion>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            THREAD_VERSION.with(|v| v.set(self.0));
+        }
+    }
+
+    let _restore = Restore(THREAD_VERSION.with(|v| v.replace(Some(version))));
+    f()
+}
+
+/// Pin the process wide algorithm version.
+///
+/// # Arguments
+///
+/// * `version`: The algorithm version to pin for all threads. If `None`, then
+///   the process wide version is unpinned and `AlgorithmVersion::LATEST` is
+///   used.
+pub fn set(version: Option<AlgorithmVersion>) {
+    GLOBAL_VERSION.store(version.map_or(0, |v| v.number()), Ordering::Relaxed);
+}
@@ -0,0 +1,3 @@
+//! Algorithm version functions.
+pub mod algorithm;
+pub use algorithm::AlgorithmVersion;
@@ -0,0 +1,146 @@
+use ndarray::{Array2, Array3, ArrayD, IxDyn};
+
+use imgal::anisotropy;
+use imgal::simulation;
+use imgal::simulation::anisotropy::AnisotropyDecay;
+
+// simulated anisotropy decay parameters, unit is nanoseconds
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 1] = [4.0];
+const FRACTIONS: [f64; 1] = [1.0];
+const TOTAL_COUNTS: f64 = 5000.0;
+const R0: f64 = 0.4;
+const ROTATIONAL_TIMES: [f64; 1] = [1.5];
+const ROTATIONAL_FRACTIONS: [f64; 1] = [1.0];
+const G_FACTOR: f64 = 1.2;
+
+// helper functions
+fn get_polarized_decays(rotational_time: f64) -> (Vec<f64>, Vec<f64>) {
+    simulation::anisotropy::ideal_anisotropy_1d(
+        SAMPLES,
+        PERIOD,
+        AnisotropyDecay {
+            taus: &TAUS,
+            fractions: &FRACTIONS,
+            total_counts: TOTAL_COUNTS,
+            r0: R0,
+            rotational_times: &[rotational_time],
+            rotational_fractions: &ROTATIONAL_FRACTIONS,
+        },
+        Some(G_FACTOR),
+    )
+    .unwrap()
+}
+
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+#[test]
+fn steady_state_g_factor() {
+    // a reference with r = 0.1 and a G-factor of 1.2
+    let r = 0.1;
+    let perp = ArrayD::from_elem(IxDyn(&[4, 4]), 100.0);
+    let par = perp.mapv(|v| v * G_FACTOR * (1.0 + 2.0 * r) / (1.0 - r));
+    let g = anisotropy::g_factor(par.view(), perp.view(), Some(r)).unwrap();
+    assert!(ensure_within_tolerance(g, G_FACTOR, 1e-12));
+
+    // check invalid parameters
+    assert!(anisotropy::g_factor(par.view(), perp.view(), Some(0.5)).is_err());
+    let empty = ArrayD::<f64>::zeros(IxDyn(&[4, 4]));
+    assert!(anisotropy::g_factor(par.view(), empty.view(), None).is_err());
+}
+
+#[test]
+fn steady_state_steady_state() {
+    // a 2-channel image with a known anisotropy and an empty pixel
+    let mut perp = Array2::<f64>::from_elem((5, 5), 100.0);
+    let mut par = perp.mapv(|v| v * G_FACTOR * (1.0 + 2.0 * 0.25) / (1.0 - 0.25));
+    par[[0, 0]] = 0.0;
+    perp[[0, 0]] = 0.0;
+    let r = anisotropy::steady_state(
+        par.view().into_dyn(),
+        perp.view().into_dyn(),
+        Some(G_FACTOR),
+    )
+    .unwrap();
+
+    // check the anisotropy and the empty pixel
+    assert_eq!(r.shape(), [5, 5]);
+    assert!(ensure_within_tolerance(r[[2, 3]], 0.25, 1e-12));
+    assert!(r[[0, 0]].is_nan());
+
+    // check invalid parameters
+    let other = Array2::<f64>::zeros((5, 4));
+    assert!(
+        anisotropy::steady_state(par.view().into_dyn(), other.view().into_dyn(), None).is_err()
+    );
+    assert!(
+        anisotropy::steady_state(par.view().into_dyn(), perp.view().into_dyn(), Some(0.0)).is_err()
+    );
+}
+
+#[test]
+fn time_resolved_decay() {
+    // recover the simulated anisotropy decay
+    let (par, perp) = get_polarized_decays(ROTATIONAL_TIMES[0]);
+    let expected = simulation::anisotropy::anisotropy_decay_1d(
+        SAMPLES,
+        PERIOD,
+        R0,
+        &ROTATIONAL_TIMES,
+        &ROTATIONAL_FRACTIONS,
+    )
+    .unwrap();
+    let r = anisotropy::decay(&par, &perp, Some(G_FACTOR)).unwrap();
+    assert_eq!(r.len(), SAMPLES);
+    r.iter()
+        .zip(expected.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-9)));
+
+    // check invalid parameters
+    assert!(anisotropy::decay(&par, &perp[..10], None).is_err());
+}
+
+#[test]
+fn time_resolved_decay_image() {
+    // broadcast the polarized decays into (row, col, t) images
+    let (par, perp) = get_polarized_decays(ROTATIONAL_TIMES[0]);
+    let par = Array3::from_shape_fn((4, 5, SAMPLES), |(_, _, t)| par[t]);
+    let perp = Array3::from_shape_fn((4, 5, SAMPLES), |(_, _, t)| perp[t]);
+    let mut mask = Array2::<bool>::from_elem((4, 5), true);
+    mask[[0, 0]] = false;
+    let r = anisotropy::decay_image(
+        par.view(),
+        perp.view(),
+        Some(G_FACTOR),
+        Some(mask.view()),
+        None,
+    )
+    .unwrap();
+
+    // check the initial anisotropy in and out of the mask
+    assert_eq!(r.shape(), [4, 5, SAMPLES]);
+    assert!(ensure_within_tolerance(r[[2, 3, 0]], R0, 1e-9));
+    assert_eq!(r[[0, 0, 0]], 0.0);
+
+    // check invalid parameters
+    assert!(anisotropy::decay_image(par.view(), perp.view(), None, None, Some(3)).is_err());
+    let small = Array2::<bool>::from_elem((4, 4), true);
+    assert!(
+        anisotropy::decay_image(par.view(), perp.view(), None, Some(small.view()), None).is_err()
+    );
+}
+
+#[test]
+fn time_resolved_tail_g_factor() {
+    // a fast rotating reference, the anisotropy has decayed in the tail
+    let (par, perp) = get_polarized_decays(0.05);
+    let g = anisotropy::tail_g_factor(&par, &perp, SAMPLES / 2).unwrap();
+    assert!(ensure_within_tolerance(g, G_FACTOR, 1e-9));
+
+    // check invalid parameters
+    assert!(anisotropy::tail_g_factor(&par, &perp, SAMPLES).is_err());
+    assert!(anisotropy::tail_g_factor(&par, &perp[..10], 0).is_err());
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "arrow")]
+use arrow_array::cast::AsArray;
+use arrow_array::ffi::{from_ffi, to_ffi};
+use arrow_array::types::{Float64Type, UInt32Type, UInt64Type};
+use arrow_array::{Array, RecordBatch, StructArray};
+use ndarray::{Array2, s};
+
+use imgal::measure;
+use imgal::roi::{ResultsRow, ResultsTable};
+
+// helper functions
+fn two_objects() -> Array2<u32> {
+    // a 2x4 rectangle and a single pixel
+    let mut labels = Array2::<u32>::zeros((6, 8));
+    labels.slice_mut(s![1..3, 2..6]).fill(1);
+    labels[[5, 7]] = 4;
+
+    labels
+}
+
+fn row(roi: &str, groups: &[&str], measurement: &str, value: f64) -> ResultsRow {
+    ResultsRow {
+        roi: roi.to_string(),
+        groups: groups.iter().map(|g| g.to_string()).collect(),
+        slice: 0,
+        channel: 1,
+        measurement: measurement.to_string(),
+        value,
+    }
+}
+
+#[test]
+fn arrow_regionprops_record_batch() {
+    let labels = two_objects();
+    let props = measure::regionprops::<u32, f64>(labels.view(), None, None, None).unwrap();
+    let batch = props.to_record_batch();
+
+    // check the columns and their types
+    let names: Vec<&str> = batch
+        .schema_ref()
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "label",
+            "area",
+            "centroid_row",
+            "centroid_col",
+            "bbox_min_row",
+            "bbox_min_col",
+            "bbox_max_row",
+            "bbox_max_col",
+            "perimeter",
+            "eccentricity"
+        ]
+    );
+    assert_eq!(batch.num_rows(), 2);
+    assert!(batch.schema_ref().fields().iter().all(|f| !f.is_nullable()));
+
+    // check the values
+    let label = batch.column(0).as_primitive::<UInt32Type>();
+    assert_eq!(label.values(), &[1, 4]);
+    let area = batch.column(1).as_primitive::<UInt64Type>();
+    assert_eq!(area.values(), &[8, 1]);
+    let perimeter = batch.column(8).as_primitive::<Float64Type>();
+    assert_eq!(perimeter.values(), &[12.0, 4.0]);
+}
+
+#[test]
+fn arrow_regionprops_record_batch_intensity() {
+    let labels = two_objects();
+    let intensity = labels.mapv(|v| v as f64 * 2.0);
+    let props = measure::regionprops(labels.view(), Some(intensity.view()), None, None).unwrap();
+    let batch = props.to_record_batch();
+
+    // the intensity columns are appended, the phasor columns are missing
+    assert_eq!(batch.num_columns(), 13);
+    let mean = batch
+        .column_by_name("mean_intensity")
+        .unwrap()
+        .as_primitive::<Float64Type>();
+    assert_eq!(mean.values(), &[2.0, 8.0]);
+    assert!(batch.column_by_name("mean_g").is_none());
+}
+
+#[test]
+fn arrow_results_table_record_batch() {
+    let mut table = ResultsTable::new();
+    table.push(row("cell 1", &["nucleus", "bright"], "mean", 1.5));
+    table.push(row("cell 2", &[], "mean", 2.5));
+    let batch = table.to_record_batch();
+
+    // check the tidy columns
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.num_columns(), 6);
+    let roi = batch.column_by_name("roi").unwrap().as_string::<i32>();
+    assert_eq!(roi.value(0), "cell 1");
+    assert_eq!(roi.value(1), "cell 2");
+    let groups = batch.column_by_name("groups").unwrap().as_list::<i32>();
+    assert_eq!(groups.value_length(0), 2);
+    assert_eq!(groups.value(0).as_string::<i32>().value(1), "bright");
+    assert_eq!(groups.value_length(1), 0);
+    let channel = batch
+        .column_by_name("channel")
+        .unwrap()
+        .as_primitive::<UInt64Type>();
+    assert_eq!(channel.values(), &[1, 1]);
+    let value = batch
+        .column_by_name("value")
+        .unwrap()
+        .as_primitive::<Float64Type>();
+    assert_eq!(value.values(), &[1.5, 2.5]);
+}
+
+#[test]
+fn arrow_record_batch_c_data_interface() {
+    let labels = two_objects();
+    let props = measure::regionprops::<u32, f64>(labels.view(), None, None, None).unwrap();
+    let batch = props.to_record_batch();
+
+    // export through the C data interface and import it back
+    let data = StructArray::from(batch.clone()).into_data();
+    let (array, schema) = to_ffi(&data).unwrap();
+    let imported = unsafe { from_ffi(array, &schema) }.unwrap();
+    let roundtrip = RecordBatch::from(StructArray::from(imported));
+    assert_eq!(roundtrip, batch);
+    assert_eq!(roundtrip.column(0).len(), 2);
+}
@@ -0,0 +1,150 @@
+use ndarray::{Array3, ArrayD, Axis, Slice, concatenate, s};
+
+use imgal::chunked;
+use imgal::filter::{self, BorderMode};
+use imgal::phasor::time_domain;
+use imgal::simulation::{decay, pattern};
+use imgal::threshold;
+
+// simulated biexponential decay parameters
+const SAMPLES: usize = 64;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+
+// helper functions
+fn split(data: &ArrayD<f64>, axis: usize, lengths: &[usize]) -> Vec<ArrayD<f64>> {
+    let mut start = 0;
+    lengths
+        .iter()
+        .map(|&n| {
+            let chunk = data
+                .slice_axis(Axis(axis), Slice::from(start..start + n))
+                .to_owned();
+            start += n;
+            chunk
+        })
+        .collect()
+}
+
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+#[test]
+fn filter_gaussian() {
+    // chunks of uneven lengths, some shorter than the halo
+    let data = pattern::blobs_2d((40, 24), 0.05, 1.5, 3)
+        .unwrap()
+        .into_dyn();
+    let lengths = [7, 2, 1, 16, 14];
+    for border in [BorderMode::Reflect, BorderMode::Constant(0.5)] {
+        let expected = filter::gaussian(data.view(), &[2.0, 1.0], Some(border), None).unwrap();
+        let chunks = split(&data, 0, &lengths);
+        let outputs: Vec<ArrayD<f64>> =
+            chunked::gaussian(chunks, &[2.0, 1.0], Some(border), None, 0)
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        // check the filtered chunks match the filtered full image
+        assert_eq!(outputs.len(), lengths.len());
+        let views: Vec<_> = outputs.iter().map(|o| o.view()).collect();
+        let result = concatenate(Axis(0), &views).unwrap();
+        assert_eq!(result.shape(), expected.shape());
+        result
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+    }
+
+    // check invalid parameters
+    let chunks = split(&data, 0, &lengths);
+    assert!(
+        chunked::gaussian(chunks.clone(), &[2.0, 1.0], Some(BorderMode::Wrap), None, 0).is_err()
+    );
+    assert!(chunked::gaussian(chunks.clone(), &[2.0, 1.0], None, None, 2).is_err());
+    let mut mismatched = chunks;
+    mismatched[2] = ArrayD::zeros(vec![1, 20]);
+    let outputs: Vec<_> = chunked::gaussian(mismatched, &[2.0, 1.0], None, None, 0)
+        .unwrap()
+        .collect();
+    assert!(outputs.last().unwrap().is_err());
+}
+
+#[test]
+fn filter_halo_map() {
+    // a moving sum of 3 elements needs a halo of 1
+    let data = ArrayD::from_shape_fn(vec![10, 2], |ix| (ix[0] * 2 + ix[1]) as f64);
+    let moving_sum = |block: ndarray::ArrayViewD<f64>| {
+        let n = block.len_of(Axis(0));
+        Ok(ArrayD::from_shape_fn(block.raw_dim(), |ix| {
+            (ix[0].saturating_sub(1)..(ix[0] + 2).min(n))
+                .map(|i| block[[i, ix[1]]])
+                .sum::<f64>()
+        }))
+    };
+    let expected = moving_sum(data.view()).unwrap();
+    let outputs: Vec<ArrayD<f64>> =
+        chunked::halo_map(split(&data, 0, &[3, 1, 6]), 0, 1, moving_sum)
+            .collect::<Result<_, _>>()
+            .unwrap();
+    let views: Vec<_> = outputs.iter().map(|o| o.view()).collect();
+    assert_eq!(concatenate(Axis(0), &views).unwrap(), expected);
+
+    // check the output shape of the function
+    let mut outputs = chunked::halo_map(split(&data, 0, &[5, 5]), 0, 1, |block| {
+        Ok(block.slice_axis(Axis(0), Slice::from(1..)).to_owned())
+    });
+    assert!(outputs.next().unwrap().is_err());
+    assert!(outputs.next().is_none());
+}
+
+#[test]
+fn phasor_time_domain_image() {
+    // split a decay image into blocks of rows
+    let data =
+        decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, (6, 5))
+            .unwrap();
+    let expected = time_domain::image(data.view(), PERIOD, None, None, None).unwrap();
+    let chunks: Vec<Array3<f64>> = (0..3)
+        .map(|i| data.slice(s![2 * i..2 * i + 2, .., ..]).to_owned())
+        .collect();
+
+    // check the phasor chunks match the full phasor image
+    chunked::time_domain_image(chunks, PERIOD, None, None)
+        .enumerate()
+        .for_each(|(i, gs)| {
+            assert_eq!(gs.unwrap(), expected.slice(s![2 * i..2 * i + 2, .., ..]));
+        });
+
+    // check invalid parameters
+    let chunks = vec![data.clone()];
+    assert!(
+        chunked::time_domain_image(chunks, 0.0, None, None)
+            .next()
+            .unwrap()
+            .is_err()
+    );
+}
+
+#[test]
+fn threshold_otsu() {
+    // the chunked threshold value and masks match the full image
+    let data = pattern::blobs_2d((30, 20), 0.05, 2.0, 11)
+        .unwrap()
+        .into_dyn();
+    let expected = threshold::otsu(data.view(), None, None).unwrap();
+    let value = chunked::otsu(|| split(&data, 1, &[8, 8, 4]), None).unwrap();
+    assert_eq!(value, expected);
+    let expected_mask = threshold::otsu_mask(data.view(), None, None).unwrap();
+    let masks: Vec<ArrayD<bool>> =
+        chunked::manual_mask(split(&data, 1, &[8, 8, 4]), value).collect();
+    let views: Vec<_> = masks.iter().map(|m| m.view()).collect();
+    assert_eq!(concatenate(Axis(1), &views).unwrap(), expected_mask);
+
+    // check invalid parameters
+    assert!(chunked::otsu(|| split(&data, 1, &[8]), Some(0)).is_err());
+    assert!(chunked::otsu(Vec::<ArrayD<f64>>::new, None).is_err());
+}
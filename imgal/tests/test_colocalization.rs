@@ -0,0 +1,553 @@
+use ndarray::{Array2, s};
+
+use imgal::colocalization::{self, Metric};
+use imgal::error::ImgalError;
+use imgal::simulation::Rng;
+
+// helper functions
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+fn spots(shape: (usize, usize), offset: usize) -> Array2<f64> {
+    // a grid of bright 3 x 3 spots on a dim background
+    let mut data = Array2::<f64>::from_elem(shape, 1.0);
+    for r in (2..shape.0 - 4).step_by(8) {
+        for c in (2..shape.1 - 4 - offset).step_by(8) {
+            data.slice_mut(s![r..r + 3, c + offset..c + offset + 3])
+                .fill(10.0 + (r + c) as f64);
+        }
+    }
+
+    data
+}
+
+#[test]
+fn colocalization_pearson() {
+    let data_a = spots((40, 40), 0);
+    let data_b = data_a.mapv(|v| 2.0 * v + 5.0);
+    let data_c = data_a.mapv(|v| -v);
+
+    let r = colocalization::pearson(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(ensure_within_tolerance(r, 1.0, 1e-12));
+    let r = colocalization::pearson(
+        data_a.view().into_dyn(),
+        data_c.view().into_dyn(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(ensure_within_tolerance(r, -1.0, 1e-12));
+}
+
+#[test]
+fn colocalization_pearson_mask_threshold() {
+    // the images only correlate on the spots
+    let data_a = spots((40, 40), 0);
+    let mut data_b = data_a.clone();
+    data_b
+        .slice_mut(s![.., ..20])
+        .mapv_inplace(|v| if v > 1.0 { v } else { 50.0 });
+    let full = colocalization::pearson(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    // a threshold on image A excludes the background
+    let r = colocalization::pearson(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        None,
+        Some(1.0),
+        None,
+    )
+    .unwrap();
+    assert!(full < 0.9);
+    assert!(ensure_within_tolerance(r, 1.0, 1e-12));
+
+    // a mask of the unchanged half
+    let mut mask = Array2::<bool>::from_elem((40, 40), false);
+    mask.slice_mut(s![.., 20..]).fill(true);
+    let r = colocalization::pearson(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        Some(mask.view().into_dyn()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(ensure_within_tolerance(r, 1.0, 1e-12));
+}
+
+#[test]
+fn colocalization_pearson_errors() {
+    let data_a = spots((40, 40), 0);
+    let data_b = Array2::<f64>::ones((40, 40));
+
+    assert!(matches!(
+        colocalization::pearson(
+            data_a.view().into_dyn(),
+            data_b.view().into_dyn(),
+            None,
+            None,
+            None
+        ),
+        Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            ..
+        })
+    ));
+    let data_b = Array2::<f64>::ones((40, 20));
+    assert!(matches!(
+        colocalization::pearson(
+            data_a.view().into_dyn(),
+            data_b.view().into_dyn(),
+            None,
+            None,
+            None
+        ),
+        Err(ImgalError::MismatchedArrayShapes { .. })
+    ));
+}
+
+#[test]
+fn colocalization_costes_test() {
+    // colocalized spots are significant
+    let data_a = spots((40, 40), 0);
+    let data_b = data_a.mapv(|v| v + 1.0);
+    let (p, coefficients) = colocalization::costes_test(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        &[4, 4],
+        Some(100),
+        None,
+        Some(42),
+    )
+    .unwrap();
+    assert_eq!(coefficients.len(), 100);
+    assert!(ensure_within_tolerance(p, 1.0 / 101.0, 1e-12));
+    assert!(coefficients.iter().all(|&r| r < 0.9));
+
+    // shifted spots are not colocalized
+    let data_b = spots((40, 40), 4);
+    let (p, _) = colocalization::costes_test(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        &[4, 4],
+        Some(100),
+        None,
+        Some(42),
+    )
+    .unwrap();
+    assert!(p > 0.5);
+}
+
+#[test]
+fn colocalization_costes_test_seed() {
+    // the same seed or rng context seed gives the same scrambles
+    let data_a = spots((40, 40), 0);
+    let data_b = spots((40, 40), 1);
+    let run = |seed: Option<u64>| {
+        colocalization::costes_test(
+            data_a.view().into_dyn(),
+            data_b.view().into_dyn(),
+            &[5, 5],
+            Some(20),
+            None,
+            seed,
+        )
+        .unwrap()
+    };
+    assert_eq!(run(Some(7)), run(Some(7)));
+    let mut rng_a = Rng::new(3);
+    let mut rng_b = Rng::new(3);
+    let out_a = colocalization::costes_test(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        &[5, 5],
+        Some(20),
+        None,
+        &mut rng_a,
+    )
+    .unwrap();
+    let out_b = colocalization::costes_test(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        &[5, 5],
+        Some(20),
+        None,
+        &mut rng_b,
+    )
+    .unwrap();
+    assert_eq!(out_a, out_b);
+}
+
+#[test]
+fn colocalization_costes_test_errors() {
+    let data_a = spots((40, 40), 0);
+
+    assert!(matches!(
+        colocalization::costes_test(
+            data_a.view().into_dyn(),
+            data_a.view().into_dyn(),
+            &[0, 4],
+            None,
+            None,
+            None
+        ),
+        Err(ImgalError::InvalidParameter {
+            param_name: "block",
+            ..
+        })
+    ));
+    assert!(matches!(
+        colocalization::costes_test(
+            data_a.view().into_dyn(),
+            data_a.view().into_dyn(),
+            &[4, 4],
+            Some(0),
+            None,
+            None
+        ),
+        Err(ImgalError::InvalidParameter {
+            param_name: "iterations",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn colocalization_manders() {
+    // image B covers half of the spots of image A and has an extra spot
+    let data_a = spots((40, 40), 0);
+    let mut data_b = data_a.clone();
+    data_b.slice_mut(s![.., 24..]).fill(0.0);
+    data_b.slice_mut(s![38..40, 38..40]).fill(20.0);
+    let (m1, m2) = colocalization::manders(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        None,
+        Some(1.0),
+        Some(1.0),
+    )
+    .unwrap();
+    let total_a: f64 = data_a.iter().filter(|&&v| v > 1.0).sum();
+    let left_a: f64 = data_a
+        .slice(s![.., ..24])
+        .iter()
+        .filter(|&&v| v > 1.0)
+        .sum();
+    let total_b: f64 = data_b.iter().filter(|&&v| v > 1.0).sum();
+    assert!(ensure_within_tolerance(m1, left_a / total_a, 1e-12));
+    assert!(ensure_within_tolerance(m2, left_a / total_b, 1e-12));
+
+    // without thresholds the background of image A colocalizes with image B
+    let (m1, _) = colocalization::manders(
+        data_a.view().into_dyn(),
+        data_a.view().into_dyn(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(m1, 1.0);
+}
+
+#[test]
+fn colocalization_manders_errors() {
+    let data_a = spots((40, 40), 0);
+    let data_b = Array2::<f64>::zeros((40, 40));
+
+    assert!(matches!(
+        colocalization::manders(
+            data_a.view().into_dyn(),
+            data_b.view().into_dyn(),
+            None,
+            None,
+            None
+        ),
+        Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn colocalization_costes_threshold() {
+    // correlated spots on an uncorrelated background
+    let data_a =
+        spots((40, 40), 0) + Array2::from_shape_fn((40, 40), |(r, c)| ((r * 7 + c * 3) % 5) as f64);
+    let data_b = spots((40, 40), 0).mapv(|v| 2.0 * v)
+        + Array2::from_shape_fn((40, 40), |(r, c)| ((r * 3 + c * 11) % 5) as f64);
+    let (t_a, t_b) =
+        colocalization::costes_threshold(data_a.view().into_dyn(), data_b.view().into_dyn(), None)
+            .unwrap();
+
+    // the thresholds separate the spots from the background
+    assert!(t_a < 11.0 && t_b < 22.0);
+    assert!(t_a > 1.0 || t_b > 2.0);
+
+    // the pixels below either threshold are not positively correlated
+    let below = ndarray::Zip::from(&data_a)
+        .and(&data_b)
+        .map_collect(|&a, &b| a <= t_a || b <= t_b);
+    let r = colocalization::pearson(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        Some(below.view().into_dyn()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(r <= 0.0);
+}
+
+#[test]
+fn colocalization_costes_threshold_errors() {
+    let data_a = spots((40, 40), 0);
+    let data_b = data_a.mapv(|v| -v);
+
+    assert!(matches!(
+        colocalization::costes_threshold(data_a.view().into_dyn(), data_b.view().into_dyn(), None),
+        Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn colocalization_saca_significance_mask() {
+    // colocalized spots in the top half, independent spots in the bottom half
+    let data_a = spots((40, 40), 0);
+    let mut data_b = data_a.mapv(|v| v * 2.0);
+    data_b
+        .slice_mut(s![20.., ..])
+        .assign(&spots((20, 40), 4).mapv(|v| v * 2.0));
+    let z = colocalization::saca_2d(data_a.view(), data_b.view(), 0.0, 0.0).unwrap();
+    let mask = colocalization::saca_significance_mask(z.view().into_dyn(), None).unwrap();
+
+    assert_eq!(mask.shape(), &[40, 40]);
+    assert!(mask[[3, 3]]);
+    assert!(
+        mask.iter().filter(|&&m| m).count()
+            > 2 * mask.slice(s![22.., ..]).iter().filter(|&&m| m).count()
+    );
+}
+
+#[test]
+fn colocalization_saca_significance_mask_alpha() {
+    // the critical z-score increases with the number of pixels
+    let z = Array2::<f64>::from_elem((10, 10), 4.0);
+    let mask = colocalization::saca_significance_mask(z.view().into_dyn(), Some(0.05)).unwrap();
+    assert!(mask.iter().all(|&m| m));
+    let z = Array2::<f64>::from_elem((1000, 1000), 4.0);
+    let mask = colocalization::saca_significance_mask(z.view().into_dyn(), Some(0.05)).unwrap();
+    assert!(mask.iter().all(|&m| !m));
+    assert!(matches!(
+        colocalization::saca_significance_mask(z.view().into_dyn(), Some(1.0)),
+        Err(ImgalError::InvalidParameter {
+            param_name: "alpha",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn colocalization_object() {
+    // two objects in A, B overlaps half of object 1 and is far from object 2
+    let mut labels_a = Array2::<u16>::zeros((20, 20));
+    let mut labels_b = Array2::<u16>::zeros((20, 20));
+    labels_a.slice_mut(s![2..6, 2..6]).fill(1);
+    labels_a.slice_mut(s![14..18, 14..18]).fill(2);
+    labels_b.slice_mut(s![2..6, 4..8]).fill(7);
+    let table = colocalization::object(
+        labels_a.view().into_dyn(),
+        labels_b.view().into_dyn(),
+        Some(&[1.0, 2.0]),
+    )
+    .unwrap();
+
+    // 3 objects with 6 measurements each
+    assert_eq!(table.len(), 18);
+    let value = |roi: &str, channel: usize, measurement: &str| {
+        table
+            .iter()
+            .find(|r| r.roi == roi && r.channel == channel && r.measurement == measurement)
+            .unwrap()
+            .value
+    };
+    assert_eq!(value("1", 0, "area"), 16.0);
+    assert_eq!(value("1", 0, "centroid_0"), 3.5);
+    assert_eq!(value("1", 0, "centroid_1"), 3.5);
+    assert_eq!(value("1", 0, "overlap"), 0.5);
+    assert_eq!(value("2", 0, "overlap"), 0.0);
+    assert_eq!(value("7", 1, "overlap"), 0.5);
+
+    // the column distance is scaled by the spacing
+    assert_eq!(value("1", 0, "nearest_distance"), 4.0);
+    assert_eq!(value("1", 0, "nearest_label"), 7.0);
+    assert_eq!(value("7", 1, "nearest_label"), 1.0);
+    assert_eq!(table.rows()[0].roi, "1");
+    assert_eq!(table.rows()[17].roi, "7");
+}
+
+#[test]
+fn colocalization_object_empty() {
+    // objects without a partner have no nearest neighbor
+    let mut labels_a = Array2::<u8>::zeros((10, 10));
+    labels_a.slice_mut(s![2..4, 2..4]).fill(1);
+    let labels_b = Array2::<u8>::zeros((10, 10));
+    let table =
+        colocalization::object(labels_a.view().into_dyn(), labels_b.view().into_dyn(), None)
+            .unwrap();
+
+    assert_eq!(table.len(), 6);
+    assert!(table.values("nearest_distance")[0].is_nan());
+    assert!(matches!(
+        colocalization::object(
+            labels_a.view().into_dyn(),
+            labels_b.view().into_dyn(),
+            Some(&[1.0, 0.0])
+        ),
+        Err(ImgalError::InvalidParameter {
+            param_name: "spacing",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn colocalization_ccf() {
+    // image B is image A shifted by 3 columns
+    let data_a = Array2::from_shape_fn((40, 40), |(r, c)| ((r * 31 + c * 17).pow(2) % 97) as f64);
+    let data_b = Array2::from_shape_fn(
+        (40, 40),
+        |(r, c)| if c >= 3 { data_a[[r, c - 3]] } else { 0.0 },
+    );
+    let (curve, peak) =
+        colocalization::ccf(data_a.view().into_dyn(), data_b.view().into_dyn(), 10, None).unwrap();
+
+    assert_eq!(curve.len(), 21);
+    assert_eq!(peak, 3);
+    assert!(curve[13] > 0.9);
+    assert!(curve[10] < curve[13]);
+
+    // shift along the rows
+    let (_, peak) = colocalization::ccf(
+        data_a.view().into_dyn(),
+        data_a.view().into_dyn(),
+        5,
+        Some(0),
+    )
+    .unwrap();
+    assert_eq!(peak, 0);
+}
+
+#[test]
+fn colocalization_ccf_errors() {
+    let data_a = spots((40, 40), 0);
+
+    assert!(matches!(
+        colocalization::ccf(data_a.view().into_dyn(), data_a.view().into_dyn(), 40, None),
+        Err(ImgalError::InvalidParameter {
+            param_name: "max_shift",
+            ..
+        })
+    ));
+    assert!(matches!(
+        colocalization::ccf(
+            data_a.view().into_dyn(),
+            data_a.view().into_dyn(),
+            4,
+            Some(2)
+        ),
+        Err(ImgalError::InvalidAxis { .. })
+    ));
+}
+
+#[test]
+fn colocalization_tile_map() {
+    // correlated left half, anti-correlated right half
+    let data_a = Array2::from_shape_fn((20, 30), |(r, c)| ((r * 31 + c * 17).pow(2) % 97) as f64);
+    let data_b = Array2::from_shape_fn((20, 30), |(r, c)| {
+        if c < 20 {
+            data_a[[r, c]]
+        } else {
+            100.0 - data_a[[r, c]]
+        }
+    });
+    let map = colocalization::tile_map(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        &[10, 20],
+        |a, b| Metric::Pearson.compute(a, b),
+    )
+    .unwrap();
+
+    assert_eq!(map.shape(), &[2, 2]);
+    assert!(ensure_within_tolerance(map[[0, 0]], 1.0, 1e-12));
+    assert!(ensure_within_tolerance(map[[1, 1]], -1.0, 1e-12));
+
+    // the tile size must be valid
+    assert!(matches!(
+        colocalization::tile_map(
+            data_a.view().into_dyn(),
+            data_b.view().into_dyn(),
+            &[0, 20],
+            |a, b| Metric::Pearson.compute(a, b),
+        ),
+        Err(ImgalError::InvalidParameter {
+            param_name: "tile",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn colocalization_label_map() {
+    let data_a = spots((40, 40), 0);
+    let mut data_b = data_a.clone();
+    data_b.slice_mut(s![20.., ..]).fill(0.0);
+    let mut labels = Array2::<u16>::zeros((40, 40));
+    labels.slice_mut(s![..20, ..]).fill(1);
+    labels.slice_mut(s![20..39, ..]).fill(2);
+    let map = colocalization::label_map(
+        data_a.view().into_dyn(),
+        data_b.view().into_dyn(),
+        labels.view().into_dyn(),
+        |a, b| Metric::MandersM1.compute(a, b),
+    )
+    .unwrap();
+
+    assert_eq!(map[[0, 0]], 1.0);
+    assert_eq!(map[[25, 0]], 0.0);
+    assert!(map[[39, 0]].is_nan());
+}
+
+#[test]
+fn colocalization_metric() {
+    let a = [1.0, 2.0, 3.0, 4.0];
+    let b = [0.0, 3.0, 5.0, 9.0];
+
+    assert!(ensure_within_tolerance(
+        Metric::MandersM1.compute(&a, &b),
+        0.9,
+        1e-12
+    ));
+    assert_eq!(Metric::MandersM2.compute(&a, &b), 1.0);
+    assert!(Metric::Pearson.compute(&a, &[1.0; 4]).is_nan());
+}
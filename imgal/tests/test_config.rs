@@ -0,0 +1,46 @@
+use imgal::config::{self, ThreadPool};
+use imgal::error::warning::{self, Warning};
+use imgal::filter;
+use imgal::simulation::pattern;
+use imgal::threshold;
+use imgal::version::{self, AlgorithmVersion};
+
+#[test]
+fn threads_thread_pool() {
+    // functions called in the pool are bounded to its threads
+    let pool = ThreadPool::new(2).unwrap();
+    assert_eq!(pool.num_threads(), 2);
+    assert_eq!(pool.install(config::num_threads), 2);
+
+    // results do not depend on the number of threads
+    let data = pattern::blobs_2d((64, 64), 0.01, 2.0, 5)
+        .unwrap()
+        .into_dyn();
+    let expected = filter::gaussian(data.view(), &[2.0, 2.0], None, None).unwrap();
+    let result = pool.install(|| filter::gaussian(data.view(), &[2.0, 2.0], None, None).unwrap());
+    assert_eq!(result, expected);
+
+    // the pinned algorithm version is kept in the pool
+    let v = version::algorithm::pinned(AlgorithmVersion::V1, || {
+        pool.install(version::algorithm::current)
+    });
+    assert_eq!(v, AlgorithmVersion::V1);
+
+    // warnings are reported on the calling thread
+    let empty = ndarray::Array2::<f64>::zeros((4, 4)).into_dyn();
+    let (_, warnings) =
+        warning::collect(|| pool.install(|| threshold::manual_mask(empty.view(), 1.0)));
+    assert_eq!(warnings, vec![Warning::EmptyMask]);
+
+    // check invalid parameters
+    assert!(ThreadPool::new(0).is_err());
+}
+
+#[test]
+fn threads_with_num_threads() {
+    assert_eq!(config::with_num_threads(3, config::num_threads).unwrap(), 3);
+
+    // check invalid parameters
+    assert!(config::with_num_threads(0, config::num_threads).is_err());
+    assert!(config::set_num_threads(0).is_err());
+}
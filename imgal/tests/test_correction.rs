@@ -0,0 +1,254 @@
+use ndarray::{Array2, Array3, Array4, Axis, s};
+
+use imgal::correction::{self, AlignMethod, BleachMethod, FlatfieldMethod};
+use imgal::simulation::decay;
+use imgal::simulation::instrument::GaussianIrf;
+
+// simulated bioexponential decay parameters, unit is nanoseconds
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+const IRF: GaussianIrf = GaussianIrf {
+    center: 3.0,
+    width: 0.5,
+};
+
+// helper functions
+fn get_decay() -> Vec<f64> {
+    decay::gaussian_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, IRF).unwrap()
+}
+
+fn bleached_stack(frames: usize) -> Array3<f64> {
+    // a fixed pattern whose intensity decays with an offset
+    Array3::from_shape_fn((frames, 6, 5), |(t, r, c)| {
+        let pattern = 10.0 + (r * 5 + c) as f64;
+        pattern * (0.8 * (-0.3 * t as f64).exp() + 0.2)
+    })
+}
+
+fn vignette(shape: (usize, usize)) -> Array2<f64> {
+    // a smooth illumination falloff towards the corners
+    let center = ((shape.0 - 1) as f64 / 2.0, (shape.1 - 1) as f64 / 2.0);
+    Array2::from_shape_fn(shape, |(r, c)| {
+        let d2 = ((r as f64 - center.0) / shape.0 as f64).powi(2)
+            + ((c as f64 - center.1) / shape.1 as f64).powi(2);
+        1.0 - 0.8 * d2
+    })
+}
+
+#[test]
+fn align_align_decays() {
+    // create a skewed decay image, each row is shifted by its row index
+    let i = get_decay();
+    let mut data = Array3::<f64>::zeros((4, 3, SAMPLES));
+    data.lanes_mut(Axis(2))
+        .into_iter()
+        .enumerate()
+        .for_each(|(k, mut ln)| {
+            let shift = k / 3;
+            (0..SAMPLES).for_each(|t| ln[(t + shift) % SAMPLES] = i[t]);
+        });
+
+    // align every decay to the first decay
+    let target = correction::t0(&i, None).unwrap().round() as usize;
+    let aligned = correction::align_decays(data.view(), None, Some(target), None).unwrap();
+    aligned.lanes(Axis(2)).into_iter().for_each(|ln| {
+        assert_eq!(ln.to_vec(), i);
+    });
+
+    // default target is the median t0, an invalid target is an error
+    let aligned = correction::align_decays(data.view(), Some(AlignMethod::Peak), None, None);
+    assert!(aligned.is_ok());
+    assert!(correction::align_decays(data.view(), None, Some(SAMPLES), None).is_err());
+}
+
+#[test]
+fn align_t0() {
+    let i = get_decay();
+    let peak = correction::t0(&i, Some(AlignMethod::Peak)).unwrap();
+    let edge = correction::t0(&i, Some(AlignMethod::RisingEdge)).unwrap();
+
+    // the rising edge is before the peak, both near the IRF center
+    let irf_bin = IRF.center / PERIOD * SAMPLES as f64;
+    assert!(edge < peak);
+    assert!((edge - irf_bin).abs() < 5.0);
+    assert_eq!(peak, 67.0);
+
+    // flat curves have no t0
+    assert_eq!(correction::t0(&[1.0; 10], None), None);
+}
+
+#[test]
+fn align_t0_image() {
+    let i = get_decay();
+    let mut data = Array3::<f64>::zeros((2, 2, SAMPLES));
+    data.slice_mut(s![0, 0, ..])
+        .assign(&ndarray::Array1::from_vec(i.clone()));
+    let t0_arr = correction::t0_image(data.view(), None, None).unwrap();
+
+    assert_eq!(t0_arr[[0, 0]], correction::t0(&i, None).unwrap());
+    assert!(t0_arr[[1, 1]].is_nan());
+}
+
+#[test]
+fn bleach_exponential() {
+    // the fitted curve follows the frame means and the frames are restored
+    let data = bleached_stack(12);
+    let result = correction::bleach(data.view().into_dyn(), None, None).unwrap();
+    assert_eq!(result.curve.len(), 12);
+    data.axis_iter(Axis(0))
+        .zip(result.curve.iter())
+        .for_each(|(frame, f)| assert!((frame.mean().unwrap() - f).abs() < 1e-6));
+    let first = data.index_axis(Axis(0), 0);
+    result.corrected.axis_iter(Axis(0)).for_each(|frame| {
+        frame
+            .iter()
+            .zip(first.iter())
+            .for_each(|(a, b)| assert!((a - b).abs() < 1e-6));
+    });
+
+    // the time axis of a 4-dimensional stack can be moved
+    let stack = Array4::from_shape_fn((2, 6, 5, 12), |(p, r, c, t)| {
+        data[[t, r, c]] * (p + 1) as f64
+    });
+    let result = correction::bleach(stack.view().into_dyn(), None, Some(3)).unwrap();
+    assert_eq!(result.corrected.shape(), stack.shape());
+    assert!((result.corrected[[1, 2, 3, 11]] - stack[[1, 2, 3, 0]]).abs() < 1e-6);
+
+    let short = bleached_stack(2);
+    assert!(correction::bleach(short.view().into_dyn(), None, None).is_err());
+    assert!(correction::bleach(short.view().into_dyn(), None, Some(3)).is_err());
+    let flat = ndarray::Array2::<f64>::zeros((4, 4));
+    assert!(correction::bleach(flat.view().into_dyn(), None, None).is_err());
+}
+
+#[test]
+fn bleach_histogram_matching() {
+    // each frame is matched to the first frame and the curve is measured
+    let data = bleached_stack(5);
+    let result = correction::bleach(
+        data.view().into_dyn(),
+        Some(BleachMethod::HistogramMatching),
+        None,
+    )
+    .unwrap();
+    let first = data.index_axis(Axis(0), 0);
+    result.corrected.axis_iter(Axis(0)).for_each(|frame| {
+        frame
+            .iter()
+            .zip(first.iter())
+            .for_each(|(a, b)| assert!((a - b).abs() < 1e-9));
+    });
+    assert_eq!(result.curve[4], data.index_axis(Axis(0), 4).mean().unwrap());
+
+    // two frames are enough to match histograms
+    let short = bleached_stack(2);
+    assert!(
+        correction::bleach(
+            short.view().into_dyn(),
+            Some(BleachMethod::HistogramMatching),
+            None
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn flatfield_given() {
+    // a given flat and dark image restore a uniform sample exactly
+    let shape = (16, 20);
+    let flat = vignette(shape).mapv(|v| 100.0 * v + 10.0);
+    let dark = Array2::from_elem(shape, 10.0);
+    let data = Array3::from_shape_fn((3, 16, 20), |(t, r, c)| {
+        (t + 1) as f64 * (flat[[r, c]] - 10.0) + 10.0
+    });
+    let result = correction::flatfield(
+        data.view().into_dyn(),
+        Some(flat.view()),
+        Some(dark.view()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert!((result.flat.mean().unwrap() - 1.0).abs() < 1e-12);
+    result
+        .corrected
+        .axis_iter(Axis(0))
+        .enumerate()
+        .for_each(|(t, frame)| {
+            let expected = frame.mean().unwrap();
+            assert!(
+                (expected - (t + 1) as f64 * 100.0 * vignette(shape).mean().unwrap()).abs() < 1e-6
+            );
+            frame
+                .iter()
+                .for_each(|v| assert!((v - expected).abs() < 1e-9));
+        });
+
+    let wrong = Array2::<f64>::ones((4, 4));
+    assert!(
+        correction::flatfield(data.view().into_dyn(), Some(wrong.view()), None, None, None)
+            .is_err()
+    );
+    let empty = Array2::<f64>::zeros(shape);
+    assert!(
+        correction::flatfield(data.view().into_dyn(), Some(empty.view()), None, None, None)
+            .is_err()
+    );
+}
+
+#[test]
+fn flatfield_estimated() {
+    // a Gaussian estimate of a smooth illumination flattens a uniform sample
+    let shape = (48, 48);
+    let gain = vignette(shape);
+    let data = gain.mapv(|v| 50.0 * v);
+    let result =
+        correction::flatfield(data.view().into_dyn(), None, None, None, Some(2.0)).unwrap();
+    let corrected = result.corrected.slice(s![8..40, 8..40]).to_owned();
+    let mean = corrected.mean().unwrap();
+    corrected
+        .iter()
+        .for_each(|v| assert!((v - mean).abs() / mean < 0.01));
+
+    // the BaSiC-style estimate rejects the objects of varying frames
+    let frames = 12;
+    let data = Array3::from_shape_fn((frames, 48, 48), |(t, r, c)| {
+        let object = if (r + 5 * t) % 16 < 3 && (c + 7 * t) % 12 < 3 {
+            400.0
+        } else {
+            0.0
+        };
+        (50.0 + object) * gain[[r, c]]
+    });
+    let result = correction::flatfield(
+        data.view().into_dyn(),
+        None,
+        None,
+        Some(FlatfieldMethod::Basic),
+        Some(3.0),
+    )
+    .unwrap();
+    let expected = &gain / gain.mean().unwrap();
+    result
+        .flat
+        .slice(s![6..42, 6..42])
+        .iter()
+        .zip(expected.slice(s![6..42, 6..42]).iter())
+        .for_each(|(a, b)| assert!((a - b).abs() < 0.02));
+
+    let single = data.index_axis(Axis(0), 0).to_owned();
+    assert!(
+        correction::flatfield(
+            single.view().into_dyn(),
+            None,
+            None,
+            Some(FlatfieldMethod::Basic),
+            None
+        )
+        .is_err()
+    );
+    assert!(correction::flatfield(single.view().into_dyn(), None, None, None, Some(0.0)).is_err());
+}
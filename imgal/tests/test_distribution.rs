@@ -1,12 +1,224 @@
 use imgal::distribution;
 use imgal::integration::midpoint;
+use imgal::simulation::Rng;
+
+// helper functions
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+fn mean_and_variance(data: &[f64]) -> (f64, f64) {
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let var = data.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, var)
+}
+
+fn argmax(data: &[f64]) -> usize {
+    (0..data.len())
+        .max_by(|&i, &j| data[i].total_cmp(&data[j]))
+        .unwrap()
+}
+
+#[test]
+fn distribution_exponential() {
+    // an exponential starting at 2.0 over (0, 10) with 101 bins
+    let exp_arr = distribution::exponential(1.5, 101, 10.0, 2.0).unwrap();
+    assert!(ensure_within_tolerance(exp_arr.iter().sum(), 1.0, 1e-12));
+    assert!(exp_arr[..20].iter().all(|&v| v == 0.0));
+    assert_eq!(argmax(&exp_arr), 20);
+    assert!(ensure_within_tolerance(
+        exp_arr[35] / exp_arr[20],
+        (-1.0_f64).exp(),
+        1e-12
+    ));
+
+    // check invalid parameters
+    assert!(distribution::exponential(0.0, 101, 10.0, 2.0).is_err());
+    assert!(distribution::exponential(1.5, 1, 10.0, 2.0).is_err());
+    assert!(distribution::exponential(1.5, 101, 10.0, 12.0).is_err());
+}
+
+#[test]
+fn distribution_gamma() {
+    // a gamma distribution peaks at offset + (shape - 1) * scale
+    let gamma_arr = distribution::gamma(3.0, 0.5, 101, 10.0, 1.0).unwrap();
+    assert!(ensure_within_tolerance(gamma_arr.iter().sum(), 1.0, 1e-12));
+    assert_eq!(argmax(&gamma_arr), 20);
+    assert!(gamma_arr[..11].iter().all(|&v| v == 0.0));
+
+    // a shape of 1.0 is an exponential distribution
+    let gamma_arr = distribution::gamma(1.0, 1.5, 101, 10.0, 2.0).unwrap();
+    let exp_arr = distribution::exponential(1.5, 101, 10.0, 2.0).unwrap();
+    gamma_arr
+        .iter()
+        .zip(exp_arr.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+
+    // check invalid parameters
+    assert!(distribution::gamma(0.5, 0.5, 101, 10.0, 1.0).is_err());
+    assert!(distribution::gamma(3.0, 0.0, 101, 10.0, 1.0).is_err());
+}
 
 #[test]
 fn distribution_gaussian() {
     // create a gaussian distribution
-    let gauss_arr = distribution::gaussian(2.0, 256, 4.0, 2.0);
+    let gauss_arr = distribution::gaussian(2.0, 256, 4.0, 2.0).unwrap();
 
     // assert a value and integrate the curve
     assert_eq!(gauss_arr[100], 0.004465507286912305);
     assert_eq!(midpoint(&gauss_arr, None), 1.0000000000000007);
+
+    // check invalid parameters and a distribution outside of the range
+    assert!(distribution::gaussian(0.0, 256, 4.0, 2.0).is_err());
+    assert!(distribution::gaussian(2.0, 1, 4.0, 2.0).is_err());
+    assert!(distribution::gaussian(2.0, 256, 0.0, 2.0).is_err());
+    assert!(distribution::gaussian(1e-3, 256, 4.0, 1e6).is_err());
+}
+
+#[test]
+fn distribution_inverse_normal_cdf() {
+    // known quantiles in the central region and both tails
+    assert_eq!(distribution::inverse_normal_cdf(0.5).unwrap(), 0.0);
+    assert!((distribution::inverse_normal_cdf(0.975).unwrap() - 1.959963984540054).abs() < 1e-8);
+    assert!((distribution::inverse_normal_cdf(0.01).unwrap() + 2.326347874040841).abs() < 1e-8);
+    assert!((distribution::inverse_normal_cdf(1e-6).unwrap() + 4.753424308822899).abs() < 1e-7);
+    assert!(distribution::inverse_normal_cdf(0.0).is_err());
+    assert!(distribution::inverse_normal_cdf(1.0).is_err());
+}
+
+#[test]
+fn distribution_normal_cdf() {
+    // known probabilities in the center and both tails
+    assert!((distribution::normal_cdf(0.0) - 0.5).abs() < 1e-7);
+    assert!((distribution::normal_cdf(1.959963984540054) - 0.975).abs() < 1e-7);
+    assert!((distribution::normal_cdf(-2.326347874040841) - 0.01).abs() < 1e-8);
+    assert!(distribution::normal_cdf(-10.0) < 1e-20);
+
+    // check the round trip with the inverse
+    let z = distribution::inverse_normal_cdf(0.3).unwrap();
+    assert!((distribution::normal_cdf(z) - 0.3).abs() < 1e-7);
+}
+
+#[test]
+fn distribution_log_normal() {
+    // a log-normal distribution peaks at median * exp(-σ²)
+    let sigma: f64 = 0.5;
+    let ln_arr = distribution::log_normal(sigma, 1001, 10.0, 2.0).unwrap();
+    assert!(ensure_within_tolerance(ln_arr.iter().sum(), 1.0, 1e-12));
+    assert_eq!(ln_arr[0], 0.0);
+    let peak = 2.0 * (-sigma.powi(2)).exp();
+    assert_eq!(argmax(&ln_arr), (peak * 100.0).round() as usize);
+
+    // check invalid parameters
+    assert!(distribution::log_normal(0.0, 1001, 10.0, 2.0).is_err());
+    assert!(distribution::log_normal(0.5, 1001, 10.0, 0.0).is_err());
+    assert!(distribution::log_normal(0.5, 1001, -1.0, 2.0).is_err());
+}
+
+#[test]
+fn distribution_lorentzian() {
+    // the lorentzian is half of the peak at center +/- gamma
+    let lor_arr = distribution::lorentzian(1.0, 101, 10.0, 5.0).unwrap();
+    assert!(ensure_within_tolerance(lor_arr.iter().sum(), 1.0, 1e-12));
+    assert_eq!(argmax(&lor_arr), 50);
+    assert!(ensure_within_tolerance(
+        lor_arr[40],
+        lor_arr[50] / 2.0,
+        1e-12
+    ));
+    assert!(ensure_within_tolerance(
+        lor_arr[60],
+        lor_arr[50] / 2.0,
+        1e-12
+    ));
+
+    // check invalid parameters
+    assert!(distribution::lorentzian(-1.0, 101, 10.0, 5.0).is_err());
+    assert!(distribution::lorentzian(1.0, 0, 10.0, 5.0).is_err());
+}
+
+#[test]
+fn sample_sample_exponential() {
+    // the mean is tau and the variance is tau²
+    let samples = distribution::sample_exponential(100000, 2.5, Some(42)).unwrap();
+    let (mean, var) = mean_and_variance(&samples);
+    assert_eq!(samples.len(), 100000);
+    assert!(samples.iter().all(|&v| v >= 0.0));
+    assert!(ensure_within_tolerance(mean, 2.5, 0.05));
+    assert!(ensure_within_tolerance(var, 6.25, 0.25));
+
+    // check invalid parameters
+    assert!(distribution::sample_exponential(10, 0.0, None).is_err());
+}
+
+#[test]
+fn sample_sample_gamma() {
+    // the mean is kθ and the variance is kθ²
+    let samples = distribution::sample_gamma(100000, 3.0, 0.5, Some(42)).unwrap();
+    let (mean, var) = mean_and_variance(&samples);
+    assert!(ensure_within_tolerance(mean, 1.5, 0.02));
+    assert!(ensure_within_tolerance(var, 0.75, 0.03));
+
+    // check invalid parameters
+    assert!(distribution::sample_gamma(10, 0.0, 0.5, None).is_err());
+    assert!(distribution::sample_gamma(10, 3.0, -1.0, None).is_err());
+}
+
+#[test]
+fn sample_sample_gaussian() {
+    let samples = distribution::sample_gaussian(100000, 10.0, 2.0, Some(42)).unwrap();
+    let (mean, var) = mean_and_variance(&samples);
+    assert!(ensure_within_tolerance(mean, 10.0, 0.05));
+    assert!(ensure_within_tolerance(var, 4.0, 0.1));
+
+    // a sigma of 0.0 only draws the mean
+    let samples = distribution::sample_gaussian(10, 10.0, 0.0, None).unwrap();
+    assert!(samples.iter().all(|&v| v == 10.0));
+
+    // check invalid parameters
+    assert!(distribution::sample_gaussian(10, 10.0, -1.0, None).is_err());
+    assert!(distribution::sample_gaussian(10, f64::NAN, 1.0, None).is_err());
+}
+
+#[test]
+fn sample_sample_poisson() {
+    // the mean and the variance are lambda
+    let samples = distribution::sample_poisson(100000, 7.0, Some(42)).unwrap();
+    let (mean, var) = mean_and_variance(&samples);
+    assert!(samples.iter().all(|&v| v >= 0.0 && v.fract() == 0.0));
+    assert!(ensure_within_tolerance(mean, 7.0, 0.05));
+    assert!(ensure_within_tolerance(var, 7.0, 0.2));
+
+    // a lambda of 0.0 only draws 0.0
+    let samples = distribution::sample_poisson(10, 0.0, None).unwrap();
+    assert!(samples.iter().all(|&v| v == 0.0));
+
+    // check invalid parameters
+    assert!(distribution::sample_poisson(10, -1.0, None).is_err());
+    assert!(distribution::sample_poisson(10, f64::INFINITY, None).is_err());
+}
+
+#[test]
+fn sample_seeds() {
+    // the same seed reproduces the samples, other seeds do not
+    let a = distribution::sample_gaussian(10000, 0.0, 1.0, Some(7)).unwrap();
+    let b = distribution::sample_gaussian(10000, 0.0, 1.0, Some(7)).unwrap();
+    let c = distribution::sample_gaussian(10000, 0.0, 1.0, Some(8)).unwrap();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    // each call draws the next stream of an rng context
+    let mut rng = Rng::new(7);
+    let a = distribution::sample_poisson(10000, 5.0, &mut rng).unwrap();
+    let b = distribution::sample_poisson(10000, 5.0, &mut rng).unwrap();
+    assert_ne!(a, b);
+    let mut rng = Rng::new(7);
+    let c = distribution::sample_poisson(10000, 5.0, &mut rng).unwrap();
+    assert_eq!(a, c);
+    assert!(
+        distribution::sample_exponential(0, 1.0, &mut rng)
+            .unwrap()
+            .is_empty()
+    );
 }
@@ -0,0 +1,133 @@
+use ndarray::Array2;
+
+use imgal::config::ThreadPool;
+use imgal::error::{ImgalError, Warning, validate, warning};
+use imgal::{statistics, threshold};
+
+#[test]
+fn imgal_error_numeric_failure() {
+    // a statistic that is not finite can not be bootstrapped
+    let data: Vec<f64> = (0..20).map(|v| v as f64).collect();
+    let err = statistics::bootstrap(&data, |_| f64::NAN, None, None, Some(7)).unwrap_err();
+
+    assert!(matches!(err, ImgalError::NumericFailure { .. }));
+    assert_eq!(
+        err.to_string(),
+        "Numeric failure in bootstrap, the statistic of the data is NaN."
+    );
+}
+
+#[test]
+fn validate_at_least() {
+    assert!(validate::at_least("harmonic", 1.0, 1.0).is_ok());
+    assert!(validate::at_least("harmonic", 0.5, 1.0).is_err());
+    assert!(validate::at_least("harmonic", f64::NAN, 1.0).is_err());
+}
+
+#[test]
+fn validate_positive() {
+    assert!(validate::positive("period", 12.5).is_ok());
+    assert!(validate::positive("period", f64::INFINITY).is_err());
+    assert_eq!(
+        validate::positive("period", 0.0),
+        Err(ImgalError::InvalidParameter {
+            param_name: "period",
+            reason: "must be a finite value > 0 but got 0".to_string(),
+        })
+    );
+}
+
+#[test]
+fn validate_same_shape() {
+    assert!(validate::same_shape(&[10, 10], &[10, 10]).is_ok());
+    assert_eq!(
+        validate::same_shape(&[10, 10], &[10, 12]),
+        Err(ImgalError::MismatchedArrayShapes {
+            shape_a: vec![10, 10],
+            shape_b: vec![10, 12],
+        })
+    );
+}
+
+#[test]
+fn warning_collect() {
+    // threshold above every pixel to create an empty mask
+    let data = Array2::<f64>::zeros((10, 10));
+    let (mask, warnings) = warning::collect(|| threshold::manual_mask(data.view().into_dyn(), 1.0));
+
+    assert!(mask.iter().all(|&m| !m));
+    assert_eq!(warnings, vec![Warning::EmptyMask]);
+    assert!(warning::take().is_empty());
+}
+
+#[test]
+fn warning_take() {
+    // emit warnings manually and drain them inside a collect
+    let (warnings, remaining) = warning::collect(|| {
+        warning::warn(Warning::NonFiniteOutput { count: 3 });
+        warning::warn(Warning::EmptyMask);
+        warning::take()
+    });
+
+    assert_eq!(
+        warnings,
+        vec![Warning::NonFiniteOutput { count: 3 }, Warning::EmptyMask]
+    );
+    assert!(remaining.is_empty());
+}
+
+#[test]
+fn warning_outside_collect() {
+    // warnings emitted outside of a collect are dropped, also on a rayon worker
+    // thread of the caller
+    let data = Array2::<f64>::zeros((10, 10));
+    warning::warn(Warning::EmptyMask);
+    let (mask, _) = rayon::join(
+        || threshold::manual_mask(data.view().into_dyn(), 1.0),
+        || (),
+    );
+
+    assert!(mask.iter().all(|&m| !m));
+    assert!(warning::take().is_empty());
+}
+
+#[test]
+fn warning_collect_nested() {
+    // warnings pending before and collected inside a nested call are kept
+    let (inner, outer) = warning::collect(|| {
+        warning::warn(Warning::NonFiniteOutput { count: 1 });
+        let (_, inner) = warning::collect(|| warning::warn(Warning::EmptyMask));
+        warning::warn(Warning::FractionsNormalized { got: 2.0 });
+        inner
+    });
+
+    assert_eq!(inner, vec![Warning::EmptyMask]);
+    assert_eq!(
+        outer,
+        vec![
+            Warning::NonFiniteOutput { count: 1 },
+            Warning::EmptyMask,
+            Warning::FractionsNormalized { got: 2.0 }
+        ]
+    );
+    assert!(warning::take().is_empty());
+}
+
+#[test]
+fn warning_collect_thread_pool() {
+    // warnings of a nested thread pool call are reported once to the caller
+    let pool = ThreadPool::new(2).unwrap();
+    let data = Array2::<f64>::zeros((10, 10));
+    let ((), warnings) = warning::collect(|| {
+        warning::warn(Warning::NonFiniteOutput { count: 1 });
+        let (_, inner) = warning::collect(|| {
+            pool.install(|| threshold::manual_mask(data.view().into_dyn(), 1.0))
+        });
+        assert_eq!(inner, vec![Warning::EmptyMask]);
+    });
+
+    assert_eq!(
+        warnings,
+        vec![Warning::NonFiniteOutput { count: 1 }, Warning::EmptyMask]
+    );
+}
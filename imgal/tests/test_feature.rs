@@ -0,0 +1,131 @@
+use ndarray::{Array2, Array3, array};
+
+use imgal::feature;
+
+// helper functions
+fn spots() -> Array2<f64> {
+    // three peaks of decreasing height, two of them close together
+    let centers = [(5.0, 5.0, 10.0), (5.0, 8.0, 6.0), (15.0, 14.0, 3.0)];
+    Array2::from_shape_fn((20, 20), |(r, c)| {
+        centers
+            .iter()
+            .map(|&(cr, cc, h)| {
+                let d2 = (r as f64 - cr).powi(2) + (c as f64 - cc).powi(2);
+                h * (-d2 / 2.0).exp()
+            })
+            .sum()
+    })
+}
+
+fn gaussian_blobs() -> Array2<f64> {
+    // a small and a large blob, with radii √2 × 2 and √2 × 5
+    let blobs = [(15.0, 15.0, 2.0), (40.0, 45.0, 5.0)];
+    Array2::from_shape_fn((64, 64), |(r, c)| {
+        blobs
+            .iter()
+            .map(|&(cr, cc, s)| {
+                let d2 = (r as f64 - cr).powi(2) + (c as f64 - cc).powi(2);
+                (-d2 / (2.0 * s * s)).exp()
+            })
+            .sum()
+    })
+}
+
+#[test]
+fn feature_peak_local_max() {
+    let data = spots();
+
+    // all peaks, sorted by value
+    let peaks = feature::peak_local_max(data.view().into_dyn(), None, None, None, None).unwrap();
+    assert_eq!(peaks, array![[5, 5], [5, 8], [15, 14]]);
+
+    // the close peak is suppressed by a larger minimum distance
+    let peaks = feature::peak_local_max(data.view().into_dyn(), Some(3), None, None, None).unwrap();
+    assert_eq!(peaks, array![[5, 5], [15, 14]]);
+
+    // the low peak is below the relative threshold
+    let peaks =
+        feature::peak_local_max(data.view().into_dyn(), None, None, Some(0.5), None).unwrap();
+    assert_eq!(peaks, array![[5, 5], [5, 8]]);
+
+    // the masked peaks are excluded
+    let mut mask = Array2::from_elem((20, 20), true);
+    mask[[5, 5]] = false;
+    let peaks = feature::peak_local_max(
+        data.view().into_dyn(),
+        None,
+        Some(4.0),
+        None,
+        Some(mask.view().into_dyn()),
+    )
+    .unwrap();
+    assert_eq!(peaks, array![[5, 8]]);
+
+    assert!(feature::peak_local_max(data.view().into_dyn(), Some(0), None, None, None).is_err());
+    assert!(feature::peak_local_max(data.view().into_dyn(), None, None, Some(1.5), None).is_err());
+}
+
+#[test]
+fn feature_peak_local_max_plateau() {
+    // a plateau gives a single peak and a flat image gives none
+    let mut data = Array3::<u8>::zeros((6, 6, 6));
+    data.slice_mut(ndarray::s![2..4, 2..4, 2..4]).fill(9);
+    let peaks = feature::peak_local_max(data.view().into_dyn(), None, None, None, None).unwrap();
+    assert_eq!(peaks, array![[2, 2, 2]]);
+
+    let flat = Array3::<u8>::from_elem((6, 6, 6), 4);
+    let peaks = feature::peak_local_max(flat.view().into_dyn(), None, None, None, None).unwrap();
+    assert_eq!(peaks.dim(), (0, 3));
+}
+
+#[test]
+fn feature_blob_log() {
+    let data = gaussian_blobs();
+    let blobs = feature::blob_log(
+        data.view().into_dyn(),
+        Some(1.0),
+        Some(8.0),
+        Some(15),
+        Some(0.1),
+        None,
+    )
+    .unwrap();
+
+    // one blob per spot at the matching scale
+    assert_eq!(blobs.nrows(), 2);
+    let mut rows: Vec<Vec<f64>> = blobs.rows().into_iter().map(|r| r.to_vec()).collect();
+    rows.sort_by(|a, b| a[2].total_cmp(&b[2]));
+    assert_eq!(&rows[0][..2], &[15.0, 15.0]);
+    assert_eq!(&rows[1][..2], &[40.0, 45.0]);
+    assert!((rows[0][2] - 2.0 * 2f64.sqrt()).abs() < 0.5);
+    assert!((rows[1][2] - 5.0 * 2f64.sqrt()).abs() < 0.5);
+
+    assert!(feature::blob_log(data.view().into_dyn(), None, None, Some(0), None, None).is_err());
+    let line = ndarray::Array1::<f64>::zeros(8);
+    assert!(feature::blob_log(line.view().into_dyn(), None, None, None, None, None).is_err());
+}
+
+#[test]
+fn feature_blob_dog() {
+    let data = gaussian_blobs();
+    let blobs = feature::blob_dog(
+        data.view().into_dyn(),
+        Some(1.0),
+        Some(8.0),
+        None,
+        Some(0.1),
+        None,
+    )
+    .unwrap();
+
+    // one blob per spot, the radius within a scale step
+    assert_eq!(blobs.nrows(), 2);
+    let mut rows: Vec<Vec<f64>> = blobs.rows().into_iter().map(|r| r.to_vec()).collect();
+    rows.sort_by(|a, b| a[2].total_cmp(&b[2]));
+    assert_eq!(&rows[0][..2], &[15.0, 15.0]);
+    assert_eq!(&rows[1][..2], &[40.0, 45.0]);
+    assert!(rows[0][2] / (2.0 * 2f64.sqrt()) < 1.6 && rows[0][2] / (2.0 * 2f64.sqrt()) > 1.0 / 1.6);
+    assert!(rows[1][2] / (5.0 * 2f64.sqrt()) < 1.6 && rows[1][2] / (5.0 * 2f64.sqrt()) > 1.0 / 1.6);
+
+    assert!(feature::blob_dog(data.view().into_dyn(), None, None, Some(1.0), None, None).is_err());
+}
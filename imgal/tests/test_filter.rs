@@ -1,5 +1,11 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use ndarray::{Array1, Array2, Array3, Axis, s};
+
 use imgal::filter;
-use imgal::simulation::{decay, instrument};
+use imgal::kernel;
+use imgal::simulation::instrument::GaussianIrf;
+use imgal::simulation::{decay, instrument, pattern};
 use imgal::statistics::sum;
 
 // simulated bioexponential decay parameters, unit is nanoseconds
@@ -10,6 +16,10 @@ const FRACTIONS: [f64; 2] = [0.7, 0.3];
 const TOTAL_COUNTS: f64 = 5000.0;
 const IRF_CENTER: f64 = 3.0;
 const IRF_WIDTH: f64 = 0.5;
+const IRF: GaussianIrf = GaussianIrf {
+    center: IRF_CENTER,
+    width: IRF_WIDTH,
+};
 
 // helper functions
 fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
@@ -20,7 +30,7 @@ fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
 fn filter_fft_convolve_1d() {
     // simulate two signals to convolve
     let a = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
-    let b = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH);
+    let b = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
     let conv = filter::fft_convolve_1d(&a, &b);
 
     // check curve photon count and a point on the curve (near max)
@@ -30,21 +40,37 @@ fn filter_fft_convolve_1d() {
         1e-12
     ));
     assert!(ensure_within_tolerance(conv[68], 135.7148429095218, 1e-12));
+    // an empty signal convolves to zeros
+    assert_eq!(filter::fft_convolve_1d(&a, &[]), vec![0.0; SAMPLES]);
+    assert!(filter::fft_convolve_1d(&[], &b).is_empty());
+}
+
+#[test]
+fn filter_fft_circular_convolve_1d() {
+    // convolve with a shifted impulse, signal past the end wraps to the start
+    let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let b = [0.0, 0.0, 1.0];
+    let conv = filter::fft_circular_convolve_1d(&a, &b);
+    let expected = [4.0, 5.0, 1.0, 2.0, 3.0];
+
+    assert_eq!(conv.len(), a.len());
+    conv.iter()
+        .zip(expected.iter())
+        .for_each(|(c, e)| assert!(ensure_within_tolerance(*c, *e, 1e-12)));
+
+    // kernels longer than the signal are wrapped into one period
+    let b = [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+    let conv = filter::fft_circular_convolve_1d(&a, &b);
+    conv.iter()
+        .zip(expected.iter())
+        .for_each(|(c, e)| assert!(ensure_within_tolerance(*c, *e, 1e-12)));
 }
 
 #[test]
 fn filter_fft_deconvolve_1d() {
     // simulate two signals to deconvolve
-    let a = decay::gaussian_exponential_1d(
-        SAMPLES,
-        PERIOD,
-        &TAUS,
-        &FRACTIONS,
-        TOTAL_COUNTS,
-        IRF_CENTER,
-        IRF_WIDTH,
-    )
-    .unwrap();
+    let a = decay::gaussian_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, IRF)
+        .unwrap();
     let b = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
     let dconv = filter::fft_deconvolve_1d(&a, &b, None);
 
@@ -60,3 +86,998 @@ fn filter_fft_deconvolve_1d() {
         1e-12
     ));
 }
+
+#[test]
+fn filter_fft_convolve_2d() {
+    // convolve with a shifted impulse, the image is shifted and trimmed
+    let a = Array2::from_shape_fn((6, 5), |(r, c)| (r * 5 + c) as f64);
+    let mut b = Array2::<f64>::zeros((3, 3));
+    b[[1, 2]] = 2.0;
+    let conv = filter::fft_convolve_2d(a.view(), b.view());
+
+    assert_eq!(conv.dim(), (6, 5));
+    conv.indexed_iter().for_each(|((r, c), v)| {
+        let expected = if r >= 1 && c >= 2 {
+            2.0 * a[[r - 1, c - 2]]
+        } else {
+            0.0
+        };
+        assert!(ensure_within_tolerance(*v, expected, 1e-9));
+    });
+}
+
+#[test]
+fn filter_fft_convolve_nd() {
+    // an n-dimensional convolution of a 1-dimensional lane matches the 1d function
+    let a = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    let b = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
+    let a_3d = Array3::from_shape_vec((1, 1, SAMPLES), a.clone()).unwrap();
+    let b_3d = Array3::from_shape_vec((1, 1, SAMPLES), b.clone()).unwrap();
+    let conv = filter::fft_convolve_nd(a_3d.view().into_dyn(), b_3d.view().into_dyn()).unwrap();
+    let conv_1d = filter::fft_convolve_1d(&a, &b);
+
+    assert_eq!(conv.shape(), [1, 1, SAMPLES]);
+    conv.iter()
+        .zip(conv_1d.iter())
+        .for_each(|(c, e)| assert!(ensure_within_tolerance(*c, *e, 1e-9)));
+
+    // the arrays must have the same number of dimensions
+    assert!(
+        filter::fft_convolve_nd(a_3d.view().into_dyn(), b_3d.slice(s![0, .., ..]).into_dyn())
+            .is_err()
+    );
+}
+
+#[test]
+fn filter_gaussian_low_pass() {
+    // a constant image is unchanged and the mean is preserved
+    let flat = Array2::<f64>::from_elem((16, 16), 3.0);
+    let result = filter::gaussian_low_pass(flat.view().into_dyn(), 0.05, None).unwrap();
+    result
+        .iter()
+        .for_each(|v| assert!(ensure_within_tolerance(*v, 3.0, 1e-9)));
+
+    // a fine checkerboard is smoothed to its mean
+    let board = pattern::checkerboard_2d((16, 16), 1).unwrap();
+    let result = filter::gaussian_low_pass(board.view().into_dyn(), 0.05, None).unwrap();
+    assert!(ensure_within_tolerance(result.mean().unwrap(), 0.5, 1e-9));
+    result
+        .iter()
+        .for_each(|v| assert!(ensure_within_tolerance(*v, 0.5, 1e-6)));
+
+    // each plane of a stack is filtered independently along the given axes
+    let grating = pattern::sinusoidal_grating_2d((16, 16), 0.25, None, None).unwrap();
+    let mut stack = Array3::<f64>::zeros((2, 16, 16));
+    stack.index_axis_mut(Axis(0), 0).assign(&board);
+    stack.index_axis_mut(Axis(0), 1).assign(&grating);
+    let result = filter::gaussian_low_pass(stack.view().into_dyn(), 0.2, Some((1, 2))).unwrap();
+    let plane = filter::gaussian_low_pass(grating.view().into_dyn(), 0.2, None).unwrap();
+    assert_eq!(result.shape(), [2, 16, 16]);
+    result
+        .index_axis(Axis(0), 1)
+        .iter()
+        .zip(plane.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+
+    // a transposed stack gives the transposed result
+    let t_stack = stack.view().permuted_axes([2, 0, 1]);
+    let t_result = filter::gaussian_low_pass(t_stack.into_dyn(), 0.2, Some((2, 0))).unwrap();
+    t_result
+        .iter()
+        .zip(
+            result
+                .view()
+                .permuted_axes(ndarray::IxDyn(&[2, 0, 1]))
+                .iter(),
+        )
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+
+    assert!(filter::gaussian_low_pass(board.view().into_dyn(), 0.0, None).is_err());
+    assert!(filter::gaussian_low_pass(board.view().into_dyn(), 0.1, Some((1, 1))).is_err());
+    assert!(filter::gaussian_low_pass(board.view().into_dyn(), 0.1, Some((0, 2))).is_err());
+}
+
+#[test]
+fn filter_gaussian_high_pass() {
+    // the mean is removed and the fine detail is kept
+    let board = pattern::checkerboard_2d((16, 16), 1).unwrap();
+    let flat = Array2::<f64>::from_elem((16, 16), 3.0);
+    let result = filter::gaussian_high_pass(flat.view().into_dyn(), 0.05, None).unwrap();
+    result
+        .iter()
+        .for_each(|v| assert!(ensure_within_tolerance(*v, 0.0, 1e-9)));
+    let result = filter::gaussian_high_pass(board.view().into_dyn(), 0.05, None).unwrap();
+    result
+        .iter()
+        .zip(board.iter())
+        .for_each(|(r, b)| assert!(ensure_within_tolerance(*r, b - 0.5, 1e-6)));
+}
+
+#[test]
+fn filter_gaussian_band_pass() {
+    // a grating inside the band is kept and the mean is removed
+    let grating = pattern::sinusoidal_grating_2d((32, 32), 0.125, None, None).unwrap();
+    let result = filter::gaussian_band_pass(grating.view().into_dyn(), 0.01, 0.3, None).unwrap();
+    assert!(ensure_within_tolerance(result.mean().unwrap(), 0.0, 1e-9));
+    let gain = (1.0 - (-(0.125_f64.powi(2)) / (2.0 * 0.01_f64.powi(2))).exp())
+        * (-(0.125_f64.powi(2)) / (2.0 * 0.3_f64.powi(2))).exp();
+    result
+        .iter()
+        .zip(grating.iter())
+        .for_each(|(r, g)| assert!(ensure_within_tolerance(*r, (g - 0.5) * gain, 1e-9)));
+
+    assert!(filter::gaussian_band_pass(grating.view().into_dyn(), 0.3, 0.1, None).is_err());
+}
+
+#[test]
+fn filter_butterworth_low_pass() {
+    // the gain is 0.5 at the cutoff frequency
+    let grating = pattern::sinusoidal_grating_2d((32, 32), 0.125, None, None).unwrap();
+    let result =
+        filter::butterworth_low_pass(grating.view().into_dyn(), 0.125, Some(3), None).unwrap();
+    result
+        .iter()
+        .zip(grating.iter())
+        .for_each(|(r, g)| assert!(ensure_within_tolerance(*r, 0.5 + (g - 0.5) * 0.5, 1e-9)));
+
+    assert!(filter::butterworth_low_pass(grating.view().into_dyn(), 0.1, Some(0), None).is_err());
+}
+
+#[test]
+fn filter_butterworth_high_pass() {
+    // the low and high-pass filters sum to the input
+    let blobs = pattern::blobs_2d((24, 24), 0.02, 1.5, Some(3)).unwrap();
+    let low = filter::butterworth_low_pass(blobs.view().into_dyn(), 0.1, None, None).unwrap();
+    let high = filter::butterworth_high_pass(blobs.view().into_dyn(), 0.1, None, None).unwrap();
+    assert!(ensure_within_tolerance(high.mean().unwrap(), 0.0, 1e-9));
+    low.iter()
+        .zip(high.iter())
+        .zip(blobs.iter())
+        .for_each(|((l, h), b)| assert!(ensure_within_tolerance(l + h, *b, 1e-9)));
+}
+
+#[test]
+fn filter_butterworth_band_pass() {
+    // gratings outside of the band are attenuated
+    let low = pattern::sinusoidal_grating_2d((32, 32), 1.0 / 32.0, None, None).unwrap();
+    let mid = pattern::sinusoidal_grating_2d((32, 32), 0.125, None, None).unwrap();
+    let high = pattern::sinusoidal_grating_2d((32, 32), 0.5, None, None).unwrap();
+    let amplitude = |g: &Array2<f64>| {
+        let r =
+            filter::butterworth_band_pass(g.view().into_dyn(), 0.08, 0.2, Some(4), None).unwrap();
+        r.iter().fold(0.0_f64, |m, v| m.max(v.abs()))
+    };
+
+    assert!(amplitude(&mid) > 0.45);
+    assert!(amplitude(&low) < 0.01);
+    assert!(amplitude(&high) < 0.01);
+    assert!(filter::butterworth_band_pass(mid.view().into_dyn(), 0.0, 0.2, None, None).is_err());
+}
+
+#[test]
+fn filter_gaussian() {
+    // the impulse response is a normalized, separable Gaussian
+    let mut impulse = Array2::<f64>::zeros((21, 21));
+    impulse[[10, 10]] = 1.0;
+    let sigma = 1.5;
+    let result = filter::gaussian(impulse.view().into_dyn(), &[sigma, sigma], None, None).unwrap();
+    let g = |x: f64| (-x * x / (2.0 * sigma * sigma)).exp();
+    let norm: f64 = (-6..=6).map(|x| g(x as f64)).sum();
+    assert!(ensure_within_tolerance(result.sum(), 1.0, 1e-12));
+    assert!(ensure_within_tolerance(
+        result[[10, 10]],
+        1.0 / (norm * norm),
+        1e-12
+    ));
+    assert!(ensure_within_tolerance(
+        result[[12, 9]],
+        g(2.0) * g(1.0) / (norm * norm),
+        1e-12
+    ));
+
+    // a sigma of 0.0 leaves an axis unfiltered
+    let result = filter::gaussian(impulse.view().into_dyn(), &[0.0, sigma], None, None).unwrap();
+    assert_eq!(result.index_axis(Axis(0), 9).sum(), 0.0);
+    assert!(ensure_within_tolerance(
+        result.index_axis(Axis(0), 10).sum(),
+        1.0,
+        1e-12
+    ));
+
+    // 3-dimensional volumes are filtered along each axis
+    let volume = Array3::<f64>::from_elem((5, 6, 7), 2.0);
+    let result = filter::gaussian(volume.view().into_dyn(), &[1.0, 2.0, 3.0], None, None).unwrap();
+    assert_eq!(result.shape(), [5, 6, 7]);
+    result
+        .iter()
+        .for_each(|v| assert!(ensure_within_tolerance(*v, 2.0, 1e-12)));
+
+    assert!(filter::gaussian(impulse.view().into_dyn(), &[1.0], None, None).is_err());
+    assert!(filter::gaussian(impulse.view().into_dyn(), &[1.0, -1.0], None, None).is_err());
+}
+
+#[test]
+fn filter_gaussian_mut() {
+    // the in place filter matches the allocating filter
+    let data = pattern::blobs_2d((24, 32), 0.02, 2.0, 7).unwrap();
+    let expected = filter::gaussian(data.view().into_dyn(), &[1.5, 2.0], None, None).unwrap();
+    let mut result = data.clone().into_dyn();
+    filter::gaussian_mut(result.view_mut(), &[1.5, 2.0], None, None).unwrap();
+    result
+        .iter()
+        .zip(expected.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+
+    // f32 images keep their element type
+    let mut result = data.mapv(|v| v as f32).into_dyn();
+    filter::gaussian_mut(
+        result.view_mut(),
+        &[1.5, 2.0],
+        None,
+        Some(filter::GaussianMethod::Recursive),
+    )
+    .unwrap();
+    let expected = filter::gaussian(
+        data.view().into_dyn(),
+        &[1.5, 2.0],
+        None,
+        Some(filter::GaussianMethod::Recursive),
+    )
+    .unwrap();
+    result
+        .iter()
+        .zip(expected.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a as f64, *b, 1e-5)));
+
+    assert!(filter::gaussian_mut(result.view_mut(), &[1.0], None, None).is_err());
+}
+
+#[test]
+fn filter_gaussian_border() {
+    let ramp = Array2::from_shape_fn((1, 20), |(_, c)| c as f64);
+    let sigma = [0.0, 1.0];
+
+    // reflect keeps a constant edge, a constant border pulls the edge towards it
+    let reflect = filter::gaussian(ramp.view().into_dyn(), &sigma, None, None).unwrap();
+    let constant = filter::gaussian(
+        ramp.view().into_dyn(),
+        &sigma,
+        Some(filter::BorderMode::Constant(100.0)),
+        None,
+    )
+    .unwrap();
+    let wrap = filter::gaussian(
+        ramp.view().into_dyn(),
+        &sigma,
+        Some(filter::BorderMode::Wrap),
+        None,
+    )
+    .unwrap();
+    assert!(reflect[[0, 0]] > 0.0 && reflect[[0, 0]] < 1.0);
+    assert!(constant[[0, 0]] > 10.0);
+    assert!(constant[[0, 19]] > 10.0);
+
+    // wrap treats the image as periodic, which preserves the sum
+    assert!(ensure_within_tolerance(wrap.sum(), ramp.sum(), 1e-9));
+    assert!(wrap[[0, 0]] > reflect[[0, 0]]);
+    assert!(wrap[[0, 19]] < reflect[[0, 19]]);
+
+    // the interior is the same for every border mode
+    assert!(ensure_within_tolerance(reflect[[0, 10]], 10.0, 1e-9));
+    assert!(ensure_within_tolerance(
+        wrap[[0, 10]],
+        reflect[[0, 10]],
+        1e-9
+    ));
+}
+
+#[test]
+fn filter_gaussian_recursive() {
+    // the recursive approximation matches the direct filter for large sigmas
+    let blobs = pattern::blobs_2d((64, 64), 0.01, 2.0, Some(1)).unwrap();
+    let sigma = [8.0, 8.0];
+    let direct = filter::gaussian(
+        blobs.view().into_dyn(),
+        &sigma,
+        Some(filter::BorderMode::Wrap),
+        Some(filter::GaussianMethod::Direct),
+    )
+    .unwrap();
+    let recursive = filter::gaussian(
+        blobs.view().into_dyn(),
+        &sigma,
+        Some(filter::BorderMode::Wrap),
+        Some(filter::GaussianMethod::Recursive),
+    )
+    .unwrap();
+    let max = direct.fold(0.0_f64, |m, v| m.max(*v));
+    direct.iter().zip(recursive.iter()).for_each(|(d, r)| {
+        assert!(
+            ensure_within_tolerance(*d, *r, 0.02 * max),
+            "{} {} {}",
+            d,
+            r,
+            max
+        )
+    });
+    assert!(ensure_within_tolerance(
+        recursive.sum(),
+        blobs.sum(),
+        0.01 * blobs.sum()
+    ));
+}
+
+#[test]
+fn filter_wiener() {
+    // blur an image by circular convolution with a centered 5 x 5 PSF
+    let image = pattern::blobs_2d((16, 16), 0.03, 1.0, Some(5)).unwrap();
+    let psf = instrument::gaussian_psf_2d((5, 5), 1.0, 4.0, 1.0).unwrap();
+    let blurred = Array2::from_shape_fn((16, 16), |(r, c)| {
+        let mut v = 0.0;
+        psf.indexed_iter().for_each(|((pr, pc), p)| {
+            let sr = (r as isize - pr as isize + 2).rem_euclid(16) as usize;
+            let sc = (c as isize - pc as isize + 2).rem_euclid(16) as usize;
+            v += p * image[[sr, sc]];
+        });
+        v
+    });
+
+    // the inverse filter recovers the noise free image
+    let restored = filter::wiener(blurred.view().into_dyn(), psf.view().into_dyn(), 0.0).unwrap();
+    assert_eq!(restored.shape(), [16, 16]);
+    restored
+        .iter()
+        .zip(image.iter())
+        .for_each(|(r, i)| assert!(ensure_within_tolerance(*r, *i, 1e-6)));
+
+    // a larger noise-to-signal ratio gives a smoother result, scaled by the
+    // zero frequency gain 1 / (1 + NSR)
+    let smooth = filter::wiener(blurred.view().into_dyn(), psf.view().into_dyn(), 0.1).unwrap();
+    let error = |a: &ndarray::ArrayD<f64>| {
+        a.iter()
+            .zip(image.iter())
+            .map(|(x, i)| (x - i).powi(2))
+            .sum::<f64>()
+    };
+    assert!(error(&smooth) > error(&restored));
+    assert!(ensure_within_tolerance(
+        smooth.sum(),
+        image.sum() / 1.1,
+        1e-9
+    ));
+
+    assert!(filter::wiener(blurred.view().into_dyn(), psf.view().into_dyn(), -1.0).is_err());
+    assert!(filter::wiener(psf.view().into_dyn(), blurred.view().into_dyn(), 0.1).is_err());
+    let zero = Array2::<f64>::zeros((3, 3));
+    assert!(filter::wiener(blurred.view().into_dyn(), zero.view().into_dyn(), 0.1).is_err());
+}
+
+#[test]
+fn filter_fft_convolve_axis() {
+    // each lane matches the 1-dimensional convolution
+    let i = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, (3, 4))
+        .unwrap();
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
+    let conv = filter::fft_convolve_axis(i.view(), &irf, None).unwrap();
+    let lane = filter::fft_convolve_1d(&i.slice(s![1, 2, ..]).to_vec(), &irf);
+    assert_eq!(conv.dim(), (3, 4, SAMPLES));
+    conv.slice(s![1, 2, ..])
+        .iter()
+        .zip(lane.iter())
+        .for_each(|(c, l)| assert!(ensure_within_tolerance(*c, *l, 1e-9)));
+    assert!(ensure_within_tolerance(
+        conv.slice(s![0, 0, ..]).sum(),
+        4960.5567668085005,
+        1e-6
+    ));
+
+    // the lane axis can be chosen
+    let t = i.view().permuted_axes([2, 0, 1]);
+    let conv_t = filter::fft_convolve_axis(t, &irf, Some(0)).unwrap();
+    conv_t
+        .iter()
+        .zip(conv.view().permuted_axes([2, 0, 1]).iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-9)));
+
+    assert!(filter::fft_convolve_axis(i.view(), &irf, Some(3)).is_err());
+}
+
+#[test]
+fn filter_unsharp_mask() {
+    // the mask is added back, flat regions are unchanged
+    let blobs = pattern::blobs_2d((24, 24), 0.01, 1.0, Some(2)).unwrap();
+    let sigma = [2.0, 2.0];
+    let blur = filter::gaussian(blobs.view().into_dyn(), &sigma, None, None).unwrap();
+    let sharp = filter::unsharp_mask(blobs.view().into_dyn(), &sigma, 1.5, None, None).unwrap();
+    sharp
+        .iter()
+        .zip(blobs.iter())
+        .zip(blur.iter())
+        .for_each(|((s, i), b)| assert!(ensure_within_tolerance(*s, i + 1.5 * (i - b), 1e-12)));
+    let peak = blobs.fold(0.0_f64, |m, v| m.max(*v));
+    assert!(sharp.fold(0.0_f64, |m, v| m.max(*v)) > peak);
+
+    // an amount of 0.0 returns the input
+    let same = filter::unsharp_mask(blobs.view().into_dyn(), &sigma, 0.0, None, None).unwrap();
+    assert_eq!(same, blobs.into_dyn());
+
+    let flat = Array3::<f64>::from_elem((4, 5, 6), 1.0);
+    let result =
+        filter::unsharp_mask(flat.view().into_dyn(), &[1.0, 1.0, 1.0], 2.0, None, None).unwrap();
+    result
+        .iter()
+        .for_each(|v| assert!(ensure_within_tolerance(*v, 1.0, 1e-12)));
+    assert!(
+        filter::unsharp_mask(flat.view().into_dyn(), &[1.0, 1.0, 1.0], -1.0, None, None).is_err()
+    );
+}
+
+#[test]
+fn filter_difference_of_gaussians() {
+    // a spot is enhanced and a flat background is removed
+    let mut image = Array2::<f64>::from_elem((41, 41), 5.0);
+    image[[20, 20]] += 10.0;
+    let dog = filter::difference_of_gaussians(
+        image.view().into_dyn(),
+        &[1.0, 1.0],
+        &[3.0, 3.0],
+        None,
+        None,
+    )
+    .unwrap();
+    let max = dog.iter().cloned().fold(f64::MIN, f64::max);
+    assert_eq!(dog[[20, 20]], max);
+    assert!(ensure_within_tolerance(dog[[0, 0]], 0.0, 1e-9));
+    assert!(dog[[20, 24]] < 0.0);
+
+    // equal sigmas cancel
+    let zero = filter::difference_of_gaussians(
+        image.view().into_dyn(),
+        &[2.0, 2.0],
+        &[2.0, 2.0],
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(zero.iter().all(|v| *v == 0.0));
+
+    assert!(
+        filter::difference_of_gaussians(
+            image.view().into_dyn(),
+            &[3.0, 3.0],
+            &[1.0, 1.0],
+            None,
+            None
+        )
+        .is_err()
+    );
+    assert!(
+        filter::difference_of_gaussians(image.view().into_dyn(), &[1.0, 1.0], &[3.0], None, None)
+            .is_err()
+    );
+}
+
+// helper function, a bright Gaussian line along the col axis of a 2D image
+fn bright_line(shape: (usize, usize), row: f64, width: f64) -> Array2<f64> {
+    Array2::from_shape_fn(shape, |(r, _)| {
+        (-(r as f64 - row).powi(2) / (2.0 * width * width)).exp()
+    })
+}
+
+#[test]
+fn filter_log() {
+    // the LoG of a quadratic ramp is its constant second derivative
+    let ramp = Array2::from_shape_fn((16, 32), |(_, c)| (c as f64 - 16.0).powi(2));
+    let result = filter::log(ramp.view().into_dyn(), &[0.0, 2.0], None).unwrap();
+    assert!(ensure_within_tolerance(result[[8, 16]], 2.0, 1e-2));
+
+    // a bright blob gives a strong negative response at its center
+    let mut blob = Array2::<f64>::zeros((31, 31));
+    blob[[15, 15]] = 1.0;
+    let blob = filter::gaussian(blob.view().into_dyn(), &[2.0, 2.0], None, None).unwrap();
+    let result = filter::log(blob.view(), &[2.0, 2.0], None).unwrap();
+    let min = result.iter().cloned().fold(f64::MAX, f64::min);
+    assert_eq!(result[[15, 15]], min);
+    assert!(min < 0.0);
+
+    // a constant image has no response
+    let flat = Array3::<f64>::from_elem((6, 6, 6), 4.0);
+    let result = filter::log(flat.view().into_dyn(), &[1.0, 1.0, 1.0], None).unwrap();
+    assert!(result.iter().all(|v| v.abs() < 1e-3));
+
+    assert!(filter::log(flat.view().into_dyn(), &[1.0, 1.0], None).is_err());
+}
+
+#[test]
+fn filter_frangi() {
+    // a bright line is enhanced, the background is not
+    let line = bright_line((32, 32), 16.0, 1.5);
+    let result = filter::frangi(
+        line.view().into_dyn(),
+        &[1.0, 2.0],
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(result[[16, 16]] > 0.5);
+    assert!(result[[2, 16]] < 0.01);
+    assert!(result.iter().all(|v| (0.0..=1.0).contains(v)));
+
+    // dark line detection does not respond to a bright line
+    let dark = filter::frangi(
+        line.view().into_dyn(),
+        &[1.0, 2.0],
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+    )
+    .unwrap();
+    assert_eq!(dark[[16, 16]], 0.0);
+
+    // a bright tube along the first axis of a volume is enhanced
+    let tube = Array3::from_shape_fn((16, 21, 21), |(_, r, c)| {
+        (-((r as f64 - 10.0).powi(2) + (c as f64 - 10.0).powi(2)) / 4.5).exp()
+    });
+    let result = filter::frangi(
+        tube.view().into_dyn(),
+        &[1.5, 2.0],
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(result[[8, 10, 10]] > 0.5);
+    assert!(result[[8, 10, 10]] > 10.0 * result[[8, 1, 1]]);
+
+    assert!(filter::frangi(line.view().into_dyn(), &[], None, None, None, None, None).is_err());
+    assert!(filter::frangi(line.view().into_dyn(), &[0.0], None, None, None, None, None).is_err());
+    let flat = Array2::<f64>::zeros((4, 4));
+    assert!(
+        filter::frangi(
+            flat.view()
+                .into_shape_with_order((1, 4, 4, 1))
+                .unwrap()
+                .into_dyn(),
+            &[1.0],
+            None,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn filter_sato() {
+    // a bright line is enhanced, the background is not
+    let line = bright_line((32, 32), 16.0, 1.5);
+    let result = filter::sato(line.view().into_dyn(), &[1.0, 2.0], None, None).unwrap();
+    let max = result.iter().cloned().fold(0.0, f64::max);
+    assert_eq!(result[[16, 16]], max);
+    assert!(max > 0.0);
+    assert!(result[[2, 16]] < 0.01 * max);
+
+    // dark line detection responds to an inverted line like to the bright line
+    let dark = line.mapv(|v| 1.0 - v);
+    let result = filter::sato(dark.view().into_dyn(), &[1.0, 2.0], Some(false), None).unwrap();
+    assert!(ensure_within_tolerance(result[[16, 16]], max, 1e-2));
+
+    // a bright tube along the first axis of a volume is enhanced
+    let tube = Array3::from_shape_fn((16, 21, 21), |(_, r, c)| {
+        (-((r as f64 - 10.0).powi(2) + (c as f64 - 10.0).powi(2)) / 4.5).exp()
+    });
+    let result = filter::sato(tube.view().into_dyn(), &[1.5], None, None).unwrap();
+    assert!(result[[8, 10, 10]] > 0.0);
+    assert!(result[[8, 10, 10]] > 10.0 * result[[8, 1, 1]]);
+
+    assert!(filter::sato(line.row(0).into_dyn(), &[1.0], None, None).is_err());
+}
+
+#[test]
+fn filter_sobel() {
+    // the gradient of a plane is its slope along each axis
+    let plane = Array2::from_shape_fn((10, 12), |(r, c)| r as f64 + 3.0 * c as f64);
+    let gr = filter::sobel(plane.view().into_dyn(), 0, None).unwrap();
+    let gc = filter::sobel(plane.view().into_dyn(), 1, None).unwrap();
+    assert!(ensure_within_tolerance(gr[[5, 6]], 1.0, 1e-12));
+    assert!(ensure_within_tolerance(gc[[5, 6]], 3.0, 1e-12));
+
+    // the reflect border repeats the edge value, halving the edge difference
+    assert!(ensure_within_tolerance(gc[[5, 0]], 1.5, 1e-12));
+
+    // the impulse response is the outer product of the difference and
+    // smoothing kernels
+    let mut point = Array2::<f64>::zeros((5, 5));
+    point[[2, 2]] = 16.0;
+    let result = filter::sobel(point.view().into_dyn(), 1, None).unwrap();
+    assert_eq!(result[[2, 1]], 4.0);
+    assert_eq!(result[[1, 1]], 2.0);
+    assert_eq!(result[[1, 3]], -2.0);
+    assert_eq!(result[[2, 2]], 0.0);
+
+    assert!(filter::sobel(plane.view().into_dyn(), 2, None).is_err());
+}
+
+#[test]
+fn filter_scharr() {
+    let plane = Array3::from_shape_fn((6, 6, 6), |(p, r, c)| {
+        2.0 * p as f64 - r as f64 + 0.5 * c as f64
+    });
+    let gp = filter::scharr(plane.view().into_dyn(), 0, None).unwrap();
+    let gr = filter::scharr(plane.view().into_dyn(), 1, None).unwrap();
+    assert!(ensure_within_tolerance(gp[[3, 3, 3]], 2.0, 1e-12));
+    assert!(ensure_within_tolerance(gr[[3, 3, 3]], -1.0, 1e-12));
+
+    // the smoothing weights are [3, 10, 3] / 16
+    let mut point = Array2::<f64>::zeros((5, 5));
+    point[[2, 2]] = 32.0;
+    let result = filter::scharr(point.view().into_dyn(), 0, None).unwrap();
+    assert_eq!(result[[1, 2]], 10.0);
+    assert_eq!(result[[1, 1]], 3.0);
+    assert_eq!(result[[3, 3]], -3.0);
+
+    assert!(filter::scharr(plane.view().into_dyn(), 3, None).is_err());
+}
+
+#[test]
+fn filter_gradient_magnitude() {
+    let plane = Array2::from_shape_fn((10, 12), |(r, c)| 3.0 * r as f64 + 4.0 * c as f64);
+    for op in [
+        filter::GradientOperator::Sobel,
+        filter::GradientOperator::Scharr,
+    ] {
+        let result = filter::gradient_magnitude(plane.view().into_dyn(), Some(op), None).unwrap();
+        assert!(ensure_within_tolerance(result[[5, 6]], 5.0, 1e-12));
+    }
+
+    // a constant image has no gradient, including at the borders
+    let flat = Array3::<u16>::from_elem((4, 5, 6), 100);
+    let result = filter::gradient_magnitude(flat.view().into_dyn(), None, None).unwrap();
+    assert_eq!(result.shape(), &[4, 5, 6]);
+    assert!(result.iter().all(|&v| v == 0.0));
+
+    // the wrap border sees a step at the edges of a ramp
+    let ramp = Array2::from_shape_fn((3, 8), |(_, c)| c as f64);
+    let result =
+        filter::gradient_magnitude(ramp.view().into_dyn(), None, Some(filter::BorderMode::Wrap))
+            .unwrap();
+    assert!(ensure_within_tolerance(result[[1, 0]], 3.0, 1e-12));
+    assert!(ensure_within_tolerance(result[[1, 4]], 1.0, 1e-12));
+}
+
+// helper function, a deterministic pseudo-random test signal
+fn scrambled(len: usize) -> Vec<f64> {
+    (0..len).map(|i| ((i * 7919 + 13) % 97) as f64).collect()
+}
+
+#[test]
+fn filter_rolling_ball() {
+    // a narrow spot on a shallow ramp is kept, the ramp is removed
+    let mut img = Array2::from_shape_fn((40, 40), |(r, c)| 10.0 + 0.1 * r as f64 + 0.2 * c as f64);
+    img[[20, 20]] += 50.0;
+    let result = filter::rolling_ball(img.view().into_dyn(), &[20.0, 20.0], None).unwrap();
+    assert!(ensure_within_tolerance(result[[20, 20]], 50.0, 0.1));
+    assert!(ensure_within_tolerance(result[[10, 30]], 0.0, 1e-9));
+    assert!(result.iter().all(|&v| v >= 0.0));
+
+    // the opening matches a brute force paraboloid opening
+    let data = scrambled(50);
+    let a = 0.5 / 4.0;
+    let erode: Vec<f64> = (0..50)
+        .map(|x| {
+            (0..50)
+                .map(|y| data[y] + a * (x as f64 - y as f64).powi(2))
+                .fold(f64::MAX, f64::min)
+        })
+        .collect();
+    let result =
+        filter::rolling_ball(Array1::from(data.clone()).view().into_dyn(), &[4.0], None).unwrap();
+    (0..50).for_each(|x| {
+        let open = (0..50)
+            .map(|y| erode[y] - a * (x as f64 - y as f64).powi(2))
+            .fold(f64::MIN, f64::max);
+        assert!(ensure_within_tolerance(result[x], data[x] - open, 1e-9));
+    });
+
+    // a dark spot on a bright background with a zero radius plane axis
+    let mut stack = Array3::<f64>::from_elem((2, 16, 16), 100.0);
+    stack[[1, 8, 8]] = 40.0;
+    let result =
+        filter::rolling_ball(stack.view().into_dyn(), &[0.0, 5.0, 5.0], Some(false)).unwrap();
+    assert!(ensure_within_tolerance(result[[1, 8, 8]], 60.0, 0.2));
+    assert_eq!(result[[0, 8, 8]], 0.0);
+
+    assert!(filter::rolling_ball(img.view().into_dyn(), &[5.0], None).is_err());
+    assert!(filter::rolling_ball(img.view().into_dyn(), &[5.0, -1.0], None).is_err());
+}
+
+#[test]
+fn filter_top_hat() {
+    // the opening matches a brute force flat box opening
+    let img = Array2::from_shape_vec((12, 9), scrambled(108)).unwrap();
+    let (rr, rc) = (2_isize, 1_isize);
+    let window = |r: usize, c: usize, max: bool, src: &Array2<f64>| {
+        let mut v = if max { f64::MIN } else { f64::MAX };
+        (-rr..=rr).for_each(|dr| {
+            (-rc..=rc).for_each(|dc| {
+                let (i, j) = (r as isize + dr, c as isize + dc);
+                if (0..12).contains(&i) && (0..9).contains(&j) {
+                    let x = src[[i as usize, j as usize]];
+                    v = if max { v.max(x) } else { v.min(x) };
+                }
+            })
+        });
+        v
+    };
+    let erode = Array2::from_shape_fn((12, 9), |(r, c)| window(r, c, false, &img));
+    let open = Array2::from_shape_fn((12, 9), |(r, c)| window(r, c, true, &erode));
+    let result = filter::top_hat(img.view().into_dyn(), &[2, 1], None).unwrap();
+    result
+        .iter()
+        .zip(img.iter().zip(open.iter()))
+        .for_each(|(&t, (&v, &o))| assert!(ensure_within_tolerance(t, v - o, 1e-12)));
+
+    // bright and dark objects smaller than the box
+    let mut img = Array2::<u8>::from_elem((20, 20), 50);
+    img[[5, 5]] = 200;
+    img[[14, 14]] = 10;
+    let white = filter::top_hat(img.view().into_dyn(), &[3, 3], None).unwrap();
+    let black = filter::top_hat(img.view().into_dyn(), &[3, 3], Some(false)).unwrap();
+    assert_eq!(white[[5, 5]], 150.0);
+    assert_eq!(white[[14, 14]], 0.0);
+    assert_eq!(black[[14, 14]], 40.0);
+    assert_eq!(black[[5, 5]], 0.0);
+
+    assert!(filter::top_hat(img.view().into_dyn(), &[3, 3, 3], None).is_err());
+}
+
+#[test]
+fn filter_equalize_histogram() {
+    // a skewed distribution is spread evenly over the unit range
+    let data = Array1::from_iter((0..1000).map(|i| (i as f64 / 1000.0).powi(2) * 255.0));
+    let result = filter::equalize_histogram(data.view().into_dyn(), None).unwrap();
+    assert_eq!(result[0], 0.0);
+    assert_eq!(result[999], 1.0);
+    let values: Vec<f64> = result.iter().copied().collect();
+    assert!(values.windows(2).all(|w| w[0] <= w[1]));
+    let mean = result.sum() / 1000.0;
+    assert!(ensure_within_tolerance(mean, 0.5, 0.05));
+
+    // a constant image maps to 0.0
+    let flat = Array2::<u16>::from_elem((4, 4), 7);
+    let result = filter::equalize_histogram(flat.view().into_dyn(), Some(16)).unwrap();
+    assert!(result.iter().all(|&v| v == 0.0));
+
+    assert!(filter::equalize_histogram(flat.view().into_dyn(), Some(0)).is_err());
+}
+
+#[test]
+fn filter_match_histogram() {
+    // equal sized images map rank to rank
+    let data = Array2::from_shape_vec((2, 3), vec![5u8, 1, 3, 3, 9, 7]).unwrap();
+    let reference = Array1::from_vec(vec![40.0, 10.0, 30.0, 20.0, 60.0, 50.0]);
+    let result =
+        filter::match_histogram(data.view().into_dyn(), reference.view().into_dyn()).unwrap();
+    assert_eq!(result.shape(), &[2, 3]);
+    assert_eq!(
+        result.iter().copied().collect::<Vec<f64>>(),
+        vec![40.0, 10.0, 30.0, 30.0, 60.0, 50.0]
+    );
+
+    // the reference quantiles are interpolated between distinct values
+    let data = Array1::from_vec(vec![0, 1, 2, 3]);
+    let reference = Array1::from_vec(vec![100.0, 0.0]);
+    let result =
+        filter::match_histogram(data.view().into_dyn(), reference.view().into_dyn()).unwrap();
+    assert_eq!(
+        result.into_raw_vec_and_offset().0,
+        vec![0.0, 0.0, 50.0, 100.0]
+    );
+
+    let empty = Array1::<f64>::zeros(0);
+    assert!(filter::match_histogram(data.view().into_dyn(), empty.view().into_dyn()).is_err());
+}
+
+#[test]
+fn filter_clahe() {
+    // a single unclipped tile is the normalized cumulative histogram
+    let img = Array2::from_shape_vec((8, 8), (0..64).map(|v| v as u8).collect()).unwrap();
+    let result = filter::clahe(img.view(), Some((1, 1)), Some(1.0), Some(64)).unwrap();
+    result
+        .iter()
+        .zip(img.iter())
+        .for_each(|(&r, &v)| assert!(ensure_within_tolerance(r, (v as f64 + 1.0) / 64.0, 1e-12)));
+
+    // the dim half of an image is stretched like the bright half
+    let img = Array2::from_shape_fn((32, 64), |(r, c)| {
+        let base = if c < 32 { 0.0 } else { 200.0 };
+        base + ((r * 32 + c % 32) % 10) as f32
+    });
+    let result = filter::clahe(img.view(), Some((2, 2)), Some(1.0), None).unwrap();
+    assert!(result.iter().all(|&v| (0.0..=1.0).contains(&v)));
+    let dim_range = result
+        .slice(s![.., ..16])
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    assert!(dim_range.1 - dim_range.0 > 0.5);
+
+    // clipping limits the contrast enhancement
+    let clipped = filter::clahe(img.view(), Some((2, 2)), Some(0.01), None).unwrap();
+    let clipped_range = clipped
+        .slice(s![.., ..16])
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    assert!(clipped_range.1 - clipped_range.0 < dim_range.1 - dim_range.0);
+
+    assert!(filter::clahe(img.view(), Some((0, 2)), None, None).is_err());
+    assert!(filter::clahe(img.view(), Some((2, 65)), None, None).is_err());
+    assert!(filter::clahe(img.view(), None, Some(0.0), None).is_err());
+    assert!(filter::clahe(img.view(), None, Some(1.5), None).is_err());
+}
+
+#[test]
+fn filter_rescale_percentile() {
+    let ramp = Array1::from_iter((0..=100).map(|v| v as f32));
+    let result = filter::rescale_percentile(ramp.view().into_dyn(), None, None).unwrap();
+    assert_eq!(result[0], 0.0);
+    assert_eq!(result[2], 0.0);
+    assert!(ensure_within_tolerance(result[50], 0.5, 1e-12));
+    assert_eq!(result[98], 1.0);
+    assert_eq!(result[100], 1.0);
+
+    // percentiles between ranks are interpolated
+    let data = Array1::from(vec![0.0, 10.0]);
+    let result =
+        filter::rescale_percentile(data.view().into_dyn(), Some(25.0), Some(75.0)).unwrap();
+    assert_eq!(result.iter().copied().collect::<Vec<f64>>(), vec![0.0, 1.0]);
+
+    assert!(filter::rescale_percentile(ramp.view().into_dyn(), Some(50.0), Some(50.0)).is_err());
+    assert!(filter::rescale_percentile(ramp.view().into_dyn(), Some(-1.0), None).is_err());
+    assert!(filter::rescale_percentile(ramp.view().into_dyn(), None, Some(101.0)).is_err());
+}
+
+#[test]
+fn filter_convolve() {
+    // an off-center impulse kernel shifts the image
+    let data = Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    let kernel = Array1::from(vec![1.0, 0.0, 0.0]);
+    let result =
+        filter::convolve(data.view().into_dyn(), kernel.view().into_dyn(), None, None).unwrap();
+    assert_eq!(
+        result.iter().copied().collect::<Vec<f64>>(),
+        vec![2.0, 3.0, 4.0, 5.0, 5.0]
+    );
+    let result = filter::convolve(
+        data.view().into_dyn(),
+        kernel.view().into_dyn(),
+        Some(filter::BorderMode::Constant(-1.0)),
+        None,
+    )
+    .unwrap();
+    assert_eq!(result[4], -1.0);
+    let result = filter::convolve(
+        data.view().into_dyn(),
+        kernel.view().into_dyn(),
+        Some(filter::BorderMode::Wrap),
+        None,
+    )
+    .unwrap();
+    assert_eq!(result[4], 1.0);
+
+    // the direct and FFT methods agree for odd and even kernels
+    let img = Array3::from_shape_vec((6, 7, 8), scrambled(336)).unwrap();
+    for k_shape in [(3, 3, 3), (2, 4, 5)] {
+        let n = k_shape.0 * k_shape.1 * k_shape.2;
+        let kernel = Array3::from_shape_vec(k_shape, scrambled(n + 5)[5..].to_vec()).unwrap();
+        for border in [
+            filter::BorderMode::Reflect,
+            filter::BorderMode::Constant(2.5),
+            filter::BorderMode::Wrap,
+        ] {
+            let direct = filter::convolve(
+                img.view().into_dyn(),
+                kernel.view().into_dyn(),
+                Some(border),
+                Some(filter::ConvolveMethod::Direct),
+            )
+            .unwrap();
+            let fft = filter::convolve(
+                img.view().into_dyn(),
+                kernel.view().into_dyn(),
+                Some(border),
+                Some(filter::ConvolveMethod::Fft),
+            )
+            .unwrap();
+            assert_eq!(direct.shape(), &[6, 7, 8]);
+            direct
+                .iter()
+                .zip(fft.iter())
+                .for_each(|(d, f)| assert!(ensure_within_tolerance(*d, *f, 1e-6)));
+        }
+    }
+
+    // a normalized box kernel preserves a constant image
+    let flat = Array2::<u8>::from_elem((20, 20), 9);
+    let kernel = Array2::<f64>::from_elem((13, 13), 1.0 / 169.0);
+    let result =
+        filter::convolve(flat.view().into_dyn(), kernel.view().into_dyn(), None, None).unwrap();
+    assert!(
+        result
+            .iter()
+            .all(|&v| ensure_within_tolerance(v, 9.0, 1e-9))
+    );
+
+    let empty = Array2::<f64>::zeros((0, 3));
+    assert!(filter::convolve(flat.view().into_dyn(), empty.view().into_dyn(), None, None).is_err());
+    assert!(filter::convolve(flat.view().into_dyn(), data.view().into_dyn(), None, None).is_err());
+}
+
+#[test]
+fn filter_convolve_separable() {
+    // separable kernels use 1-dimensional passes and match the direct method
+    let img = Array2::from_shape_vec((9, 11), scrambled(99)).unwrap();
+    let a = [1.0, 3.0, -2.0, 0.5];
+    let b = [2.0, -1.0, 4.0];
+    let kernel = Array2::from_shape_fn((4, 3), |(i, j)| a[i] * b[j]);
+    for border in [
+        filter::BorderMode::Reflect,
+        filter::BorderMode::Constant(3.0),
+        filter::BorderMode::Wrap,
+    ] {
+        let auto = filter::convolve(
+            img.view().into_dyn(),
+            kernel.view().into_dyn(),
+            Some(border),
+            None,
+        )
+        .unwrap();
+        let direct = filter::convolve(
+            img.view().into_dyn(),
+            kernel.view().into_dyn(),
+            Some(border),
+            Some(filter::ConvolveMethod::Direct),
+        )
+        .unwrap();
+        auto.iter()
+            .zip(direct.iter())
+            .for_each(|(x, d)| assert!(ensure_within_tolerance(*x, *d, 1e-9)));
+    }
+}
+
+#[test]
+fn filter_max_response() {
+    let angles = [0.0, FRAC_PI_4, FRAC_PI_2, 3.0 * FRAC_PI_4];
+    let ridge = kernel::orientation_bank(kernel::OrientedFilter::Ridge, &angles, &[1.5]).unwrap();
+
+    // a bright line along the rows is detected with the column orientation
+    let line = bright_line((32, 32), 16.0, 1.5);
+    let (response, orientation) =
+        filter::max_response(line.t(), ridge.view(), &angles, None).unwrap();
+    assert_eq!(orientation[[16, 16]], 0.0);
+    assert!(response[[16, 16]] > 0.1);
+    assert!(response[[16, 16]] > 10.0 * response[[16, 2]]);
+
+    // a bright line along the columns is detected with the row orientation
+    let (_, orientation) = filter::max_response(line.view(), ridge.view(), &angles, None).unwrap();
+    assert_eq!(orientation[[16, 16]], FRAC_PI_2);
+
+    // an edge increasing along the rows
+    let edge =
+        kernel::orientation_bank(kernel::OrientedFilter::Edge, &angles, &[1.0, 2.0]).unwrap();
+    let step = Array2::from_shape_fn((24, 24), |(r, _)| if r < 12 { 0.0 } else { 1.0 });
+    let (response, orientation) =
+        filter::max_response(step.view(), edge.view(), &angles, None).unwrap();
+    assert_eq!(orientation[[12, 12]], FRAC_PI_2);
+    assert!(response[[12, 12]] > 0.0);
+
+    assert!(filter::max_response(step.view(), edge.view(), &angles[..2], None).is_err());
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "gpu")]
+use ndarray::{Array2, Array3, s};
+
+use imgal::filter::{self, BorderMode};
+use imgal::gpu;
+use imgal::phasor::{calibration, time_domain};
+use imgal::simulation::{decay, pattern};
+
+// simulated biexponential decay parameters
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+
+// helper functions
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+#[test]
+fn convolve_2d() {
+    // odd and even kernels with each border mode match the CPU convolution
+    let data = pattern::blobs_2d((37, 23), 0.05, 1.5, 5).unwrap();
+    let kernels = [
+        Array2::from_shape_fn((5, 3), |(i, j)| (i * 3 + j) as f64 / 10.0),
+        Array2::from_shape_fn((4, 6), |(i, j)| ((i + 1) * (j + 2)) as f64 / 50.0),
+    ];
+    let borders = [
+        BorderMode::Reflect,
+        BorderMode::Wrap,
+        BorderMode::Constant(0.5),
+    ];
+    for kernel in kernels.iter() {
+        for border in borders {
+            let expected = filter::convolve(
+                data.view().into_dyn(),
+                kernel.view().into_dyn(),
+                Some(border),
+                None,
+            )
+            .unwrap();
+            let result = gpu::convolve_2d(data.view(), kernel.view(), Some(border)).unwrap();
+            assert_eq!(result.shape(), data.shape());
+            result
+                .iter()
+                .zip(expected.iter())
+                .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-4)));
+        }
+    }
+
+    // check empty inputs
+    let empty = Array2::<f64>::zeros((0, 0));
+    assert!(gpu::convolve_2d(data.view(), empty.view(), None).is_err());
+    let result = gpu::convolve_2d(empty.view(), kernels[0].view(), None).unwrap();
+    assert_eq!(result.shape(), &[0, 0]);
+}
+
+#[test]
+fn phasor_calibration_image() {
+    // the calibrated coordinates match the CPU calibration
+    let data = Array3::from_shape_fn((6, 5, 2), |(r, c, ch)| {
+        0.1 * (r as f64 + 1.0) - 0.05 * c as f64 + 0.2 * ch as f64
+    });
+    let mut mask = Array2::from_elem((6, 5), true);
+    mask[[2, 3]] = false;
+    for m in [None, Some(mask.view())] {
+        let expected = calibration::image(data.view(), 0.9, 0.3, m, None).unwrap();
+        let result = gpu::calibration_image(data.view(), 0.9, 0.3, m, None).unwrap();
+        result
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-6)));
+    }
+
+    // check invalid parameters
+    assert!(gpu::calibration_image(data.view(), 0.9, 0.3, None, Some(3)).is_err());
+    let bad_mask = Array2::from_elem((5, 6), true);
+    assert!(gpu::calibration_image(data.view(), 0.9, 0.3, Some(bad_mask.view()), None).is_err());
+}
+
+#[test]
+fn phasor_time_domain_image() {
+    // the coordinates match the CPU transform along each axis
+    let data =
+        decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, (9, 7))
+            .unwrap();
+    let mut mask = Array2::from_elem((9, 7), true);
+    mask.slice_mut(s![..3, ..]).fill(false);
+    for m in [None, Some(mask.view())] {
+        let expected = time_domain::image(data.view(), PERIOD, m, Some(2.0), None).unwrap();
+        let result = gpu::time_domain_image(data.view(), PERIOD, m, Some(2.0), None).unwrap();
+        result
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-5)));
+    }
+    let permuted = data.view().permuted_axes([2, 0, 1]);
+    let expected = time_domain::image(permuted, PERIOD, None, None, Some(0)).unwrap();
+    let result = gpu::time_domain_image(permuted, PERIOD, None, None, Some(0)).unwrap();
+    result
+        .iter()
+        .zip(expected.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-5)));
+
+    // pixels without intensity are NaN
+    let mut dark = data.clone();
+    dark.slice_mut(s![0, 0, ..]).fill(0.0);
+    let result = gpu::time_domain_image(dark.view(), PERIOD, None, None, None).unwrap();
+    assert!(result[[0, 0, 0]].is_nan());
+
+    // check invalid parameters
+    assert!(gpu::time_domain_image(data.view(), 0.0, None, None, None).is_err());
+    assert!(gpu::time_domain_image(data.view(), PERIOD, None, Some(0.5), None).is_err());
+    assert!(gpu::time_domain_image(data.view(), PERIOD, None, None, Some(3)).is_err());
+}
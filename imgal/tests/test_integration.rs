@@ -1,9 +1,115 @@
+use ndarray::{Array3, Axis};
+
 use imgal::distribution::gaussian;
-use imgal::integration;
+use imgal::integration::{self, IntegrationMethod};
 
 // helper functions
 fn get_gaussian_distribution(bins: usize) -> Vec<f64> {
-    gaussian(2.0, bins, 4.0, 2.0)
+    gaussian(2.0, bins, 4.0, 2.0).unwrap()
+}
+
+#[test]
+fn integration_composite_simpson_nonuniform() {
+    // matches the uniform rule on evenly spaced points
+    let gauss_arr = get_gaussian_distribution(512);
+    let x: Vec<f64> = (0..512).map(|i| i as f64 * 0.5).collect();
+    let integral = integration::composite_simpson_nonuniform(&gauss_arr, &x).unwrap();
+    assert!((integral - integration::composite_simpson(&gauss_arr, Some(0.5))).abs() < 1e-12);
+
+    // exact for a quadratic on an irregular grid, f(x) = x² over [0, 3] = 9
+    let x = [0.0, 0.3, 1.0, 1.2, 3.0];
+    let y: Vec<f64> = x.iter().map(|v| v * v).collect();
+    let integral = integration::composite_simpson_nonuniform(&y, &x).unwrap();
+    assert!((integral - 9.0).abs() < 1e-12);
+
+    // integer values are summed as floats in the last trapezoid, f(x) = 200
+    // over [0, 1.2] = 240
+    let integral = integration::composite_simpson_nonuniform(&[200u8; 4], &x[..4]).unwrap();
+    assert!((integral - 240.0).abs() < 1e-12);
+
+    // check invalid parameters
+    assert!(integration::composite_simpson_nonuniform(&y, &x[..4]).is_err());
+    assert!(integration::composite_simpson_nonuniform(&y, &[0.0, 1.0, 1.0, 2.0, 3.0]).is_err());
+    assert!(integration::composite_simpson_nonuniform(&[1.0], &[0.0]).is_err());
+}
+
+#[test]
+fn integration_cumulative_trapezoid() {
+    // the running integral of f(x) = 2x is x²
+    let y: Vec<f64> = (0..=10).map(|i| 2.0 * i as f64 * 0.5).collect();
+    let output = integration::cumulative_trapezoid(&y, Some(0.5));
+    assert_eq!(output.len(), 11);
+    assert_eq!(output[0], 0.0);
+    output
+        .iter()
+        .enumerate()
+        .for_each(|(i, &v)| assert!((v - (i as f64 * 0.5).powi(2)).abs() < 1e-12));
+
+    // the last value is the full integral
+    let gauss_arr = get_gaussian_distribution(512);
+    let output = integration::cumulative_trapezoid(&gauss_arr, None);
+    assert!((output[511] - integration::trapezoid(&gauss_arr, None)).abs() < 1e-12);
+    assert!(integration::cumulative_trapezoid::<f64>(&[], None).is_empty());
+}
+
+#[test]
+fn integration_cumulative_trapezoid_axis() {
+    // a (t, row, col) stack with the gaussian distribution along the t axis
+    let gauss_arr = get_gaussian_distribution(64);
+    let data = Array3::from_shape_fn((64, 2, 3), |(t, _, _)| gauss_arr[t]);
+    let expected = integration::cumulative_trapezoid(&gauss_arr, Some(0.25));
+    let output =
+        integration::cumulative_trapezoid_axis(data.view().into_dyn(), 0, Some(0.25)).unwrap();
+
+    // check the shape and a lane
+    assert_eq!(output.shape(), [64, 2, 3]);
+    (0..64).for_each(|t| assert_eq!(output[[t, 1, 2]], expected[t]));
+    assert!(integration::cumulative_trapezoid_axis(data.view().into_dyn(), 3, None).is_err());
+}
+
+#[test]
+fn integration_integrate_axis() {
+    // a (row, t, col) stack with the gaussian distribution along the t axis
+    let gauss_arr = get_gaussian_distribution(512);
+    let data = Array3::from_shape_fn((3, 512, 4), |(_, t, _)| gauss_arr[t]);
+
+    // check each method matches its 1-dimensional integral
+    [
+        (
+            IntegrationMethod::Rectangle,
+            integration::midpoint(&gauss_arr, Some(0.5)),
+        ),
+        (
+            IntegrationMethod::Trapezoid,
+            integration::trapezoid(&gauss_arr, Some(0.5)),
+        ),
+        (
+            IntegrationMethod::Simpson,
+            integration::composite_simpson(&gauss_arr, Some(0.5)),
+        ),
+    ]
+    .into_iter()
+    .for_each(|(method, expected)| {
+        let output =
+            integration::integrate_axis(data.view().into_dyn(), 1, Some(0.5), Some(method))
+                .unwrap();
+        assert_eq!(output.shape(), [3, 4]);
+        assert!(output.iter().all(|&v| (v - expected).abs() < 1e-12));
+    });
+
+    // check invalid parameters
+    assert!(integration::integrate_axis(data.view().into_dyn(), 3, None, None).is_err());
+    let single = data.index_axis(Axis(1), 0).insert_axis(Axis(1));
+    assert!(integration::integrate_axis(single.into_dyn(), 1, None, None).is_err());
+    assert!(
+        integration::integrate_axis(
+            single.into_dyn(),
+            1,
+            None,
+            Some(IntegrationMethod::Rectangle)
+        )
+        .is_ok()
+    );
 }
 
 #[test]
@@ -32,3 +138,25 @@ fn integration_simpson() {
         0.9986128844345734
     );
 }
+
+#[test]
+fn integration_trapezoid() {
+    // the trapezoid rule is exact for a linear curve
+    let ramp: Vec<f64> = (0..=10).map(|i| i as f64).collect();
+    assert_eq!(integration::trapezoid(&ramp, None), 50.0);
+    assert_eq!(integration::trapezoid(&[1.0, 3.0], Some(2.0)), 4.0);
+    assert_eq!(integration::trapezoid(&[1.0], None), 0.0);
+}
+
+#[test]
+fn integration_trapezoid_nonuniform() {
+    // exact for a linear curve on an irregular grid, f(x) = 2x over [1, 4] = 15
+    let x = [1.0, 1.5, 3.25, 4.0];
+    let y: Vec<f64> = x.iter().map(|v| 2.0 * v).collect();
+    let integral = integration::trapezoid_nonuniform(&y, &x).unwrap();
+    assert!((integral - 15.0).abs() < 1e-12);
+
+    // check invalid parameters
+    assert!(integration::trapezoid_nonuniform(&y, &[1.0, 0.5, 3.25, 4.0]).is_err());
+    assert!(integration::trapezoid_nonuniform(&y, &[1.0, f64::NAN, 3.25, 4.0]).is_err());
+}
@@ -1,4 +1,8 @@
-use imgal::kernel::neighborhood;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use ndarray::{Array2, ArrayView2, Ix2, s};
+
+use imgal::kernel::{self, neighborhood, parametric};
 
 // kernel parameters
 const RADIUS: usize = 5;
@@ -47,3 +51,215 @@ fn neighborhood_weighted_sphere() {
     assert_eq!(k[[2, 5, 1]], 0.2857142857142857);
     assert_eq!(k[[8, 9, 10]], 0.0);
 }
+
+#[test]
+fn parametric_gaussian() {
+    // create an anisotropic Gaussian kernel with a flat axis
+    let k = parametric::gaussian(&[1.0, 2.0, 0.0], None, None).unwrap();
+
+    assert_eq!(k.shape(), [9, 17, 1]);
+    assert_eq!(k[[4, 8, 0]], 1.0);
+    assert_eq!(k[[5, 8, 0]], (-0.5_f64).exp());
+    assert_eq!(k[[4, 10, 0]], (-0.5_f64).exp());
+
+    // create a normalized Gaussian kernel with a custom radius
+    let k = parametric::gaussian(&[1.5, 1.5], Some(&[2, 3]), Some(true)).unwrap();
+    assert_eq!(k.shape(), [5, 7]);
+    assert!((k.sum() - 1.0).abs() < 1e-12);
+
+    assert!(parametric::gaussian(&[], None, None).is_err());
+    assert!(parametric::gaussian(&[-1.0], None, None).is_err());
+    assert!(parametric::gaussian(&[1.0, 1.0], Some(&[2]), None).is_err());
+}
+
+#[test]
+fn parametric_laplacian() {
+    // create a 2D and a normalized 3D Laplacian kernel
+    let k = parametric::laplacian(2, None).unwrap();
+
+    assert_eq!(k.shape(), [3, 3]);
+    assert_eq!(k[[1, 1]], -4.0);
+    assert_eq!(k[[0, 1]], 1.0);
+    assert_eq!(k[[0, 0]], 0.0);
+    assert_eq!(k.sum(), 0.0);
+
+    let k = parametric::laplacian(3, Some(true)).unwrap();
+    assert_eq!(k.shape(), [3, 3, 3]);
+    assert_eq!(k[[1, 1, 1]], -1.0);
+    assert_eq!(k[[1, 1, 2]], 1.0 / 6.0);
+
+    assert!(parametric::laplacian(0, None).is_err());
+}
+
+#[test]
+fn parametric_disk() {
+    // create a disk kernel matching the circle neighborhood
+    let k = parametric::disk(RADIUS as f64, 2, None).unwrap();
+    let circle = neighborhood::circle(RADIUS).unwrap();
+
+    assert_eq!(k.shape(), [11, 11]);
+    k.iter()
+        .zip(circle.iter())
+        .for_each(|(&v, &c)| assert_eq!(v, if c { 1.0 } else { 0.0 }));
+
+    // create a normalized 3D disk (i.e. ball) kernel
+    let k = parametric::disk(1.5, 3, Some(true)).unwrap();
+    assert_eq!(k.shape(), [3, 3, 3]);
+    assert!((k.sum() - 1.0).abs() < 1e-12);
+    assert_eq!(k[[0, 0, 0]], 0.0);
+    assert_eq!(k[[0, 0, 1]], 1.0 / 19.0);
+
+    assert!(parametric::disk(-1.0, 2, None).is_err());
+    assert!(parametric::disk(2.0, 0, None).is_err());
+}
+
+#[test]
+fn parametric_ring() {
+    // create a ring kernel
+    let k = parametric::ring(2.0, 3.0, 2, None).unwrap();
+
+    assert_eq!(k.shape(), [7, 7]);
+    assert_eq!(k[[3, 3]], 0.0);
+    assert_eq!(k[[3, 4]], 0.0);
+    assert_eq!(k[[3, 5]], 1.0);
+    assert_eq!(k[[3, 6]], 1.0);
+    assert_eq!(k[[0, 0]], 0.0);
+
+    assert!(parametric::ring(3.0, 2.0, 2, None).is_err());
+}
+
+#[test]
+fn parametric_gabor() {
+    // create an even Gabor kernel with a horizontal wave normal
+    let k = parametric::gabor(2.0, 4.0, 0.0, None, None, None, None).unwrap();
+
+    assert_eq!(k.shape(), [17, 17]);
+    assert_eq!(k[[8, 8]], 1.0);
+    assert!((k[[8, 10]] + (-0.5_f64).exp()).abs() < 1e-12);
+    assert!((k[[6, 8]] - (-0.5_f64).exp()).abs() < 1e-12);
+
+    // rotating by 90 degrees transposes the kernel
+    let r = parametric::gabor(
+        2.0,
+        4.0,
+        std::f64::consts::FRAC_PI_2,
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!((r[[10, 8]] - k[[8, 10]]).abs() < 1e-12);
+
+    // an odd Gabor kernel sums to 0.0 and is normalized by its positive weights
+    let k = parametric::gabor(
+        2.0,
+        4.0,
+        0.0,
+        Some(std::f64::consts::FRAC_PI_2),
+        Some(0.5),
+        Some(6),
+        Some(true),
+    )
+    .unwrap();
+    assert_eq!(k.shape(), [13, 13]);
+    let positive: f64 = k.iter().filter(|&&v| v > 0.0).sum();
+    assert!((positive - 1.0).abs() < 1e-12);
+
+    assert!(parametric::gabor(0.0, 4.0, 0.0, None, None, None, None).is_err());
+    assert!(parametric::gabor(2.0, 4.0, 0.0, None, Some(0.0), None, None).is_err());
+}
+
+#[test]
+fn parametric_rectangle() {
+    // create a normalized box kernel
+    let k = parametric::rectangle(&[1, 2], Some(true)).unwrap();
+
+    assert_eq!(k.shape(), [3, 5]);
+    assert!(k.iter().all(|&v| v == 1.0 / 15.0));
+
+    assert!(parametric::rectangle(&[], None).is_err());
+}
+
+#[test]
+fn kernel_separate() {
+    // an anisotropic Gaussian kernel is the outer product of its factors
+    let k = parametric::gaussian(&[1.0, 2.0], None, None)
+        .unwrap()
+        .into_dimensionality::<Ix2>()
+        .unwrap();
+    let (a, b) = kernel::separate(k.view(), None).unwrap().unwrap();
+
+    assert_eq!(a.len(), 9);
+    assert_eq!(b.len(), 17);
+    assert!(a.iter().all(|&v| v > 0.0));
+    k.indexed_iter()
+        .for_each(|((i, j), &v)| assert!((v - a[i] * b[j]).abs() < 1e-12));
+
+    // a negated kernel keeps the largest value of the row factor positive
+    let neg = k.mapv(|v| -v);
+    let (a, b) = kernel::separate(neg.view(), None).unwrap().unwrap();
+    assert!(a[4] > 0.0);
+    assert!(b[8] < 0.0);
+
+    // a Laplacian kernel is not separable, a near rank 1 kernel within a
+    // tolerance
+    let lap = parametric::laplacian(2, None)
+        .unwrap()
+        .into_dimensionality::<Ix2>()
+        .unwrap();
+    assert!(kernel::separate(lap.view(), None).unwrap().is_none());
+    let mut near = Array2::<f64>::ones((3, 4));
+    near[[1, 1]] = 1.001;
+    assert!(kernel::separate(near.view(), None).unwrap().is_none());
+    assert!(kernel::separate(near.view(), Some(1e-3)).unwrap().is_some());
+
+    // a zero kernel has zero factors
+    let zero = Array2::<u8>::zeros((2, 3));
+    let (a, b) = kernel::separate(zero.view(), None).unwrap().unwrap();
+    assert_eq!(a, vec![0.0; 2]);
+    assert_eq!(b, vec![0.0; 3]);
+
+    assert!(kernel::separate(Array2::<f64>::zeros((0, 3)).view(), None).is_err());
+    assert!(kernel::separate(near.view(), Some(-1.0)).is_err());
+}
+
+#[test]
+fn kernel_orientation_bank() {
+    // create a bank of edge kernels, sized for the largest scale
+    let angles = [0.0, FRAC_PI_4, FRAC_PI_2];
+    let bank =
+        kernel::orientation_bank(kernel::OrientedFilter::Edge, &angles, &[1.0, 2.0]).unwrap();
+
+    assert_eq!(bank.shape(), [2, 3, 17, 17]);
+    let k: ArrayView2<f64> = bank.slice(s![1, 0, .., ..]);
+    assert!(k.sum().abs() < 1e-12);
+    assert!(k[[8, 9]] < 0.0);
+    assert_eq!(k[[8, 9]], -k[[8, 7]]);
+
+    // the edge kernels are steerable
+    let (k0, k45, k90) = (
+        bank.slice(s![1, 0, .., ..]),
+        bank.slice(s![1, 1, .., ..]),
+        bank.slice(s![1, 2, .., ..]),
+    );
+    k45.indexed_iter().for_each(|(idx, &v)| {
+        let steered = FRAC_PI_4.cos() * k0[idx] + FRAC_PI_4.sin() * k90[idx];
+        assert!((v - steered).abs() < 1e-12);
+    });
+
+    // ridge kernels are symmetric with a positive center, Gabor kernels are
+    // zero mean
+    let ridge = kernel::orientation_bank(kernel::OrientedFilter::Ridge, &[0.0], &[2.0]).unwrap();
+    assert!(ridge[[0, 0, 8, 8]] > 0.0);
+    assert_eq!(ridge[[0, 0, 8, 6]], ridge[[0, 0, 8, 10]]);
+    assert!(ridge.sum().abs() < 1e-3);
+    let gabor =
+        kernel::orientation_bank(kernel::OrientedFilter::Gabor(2.0), &angles, &[1.5]).unwrap();
+    assert_eq!(gabor.shape(), [1, 3, 13, 13]);
+    assert!(gabor.slice(s![0, 1, .., ..]).sum().abs() < 1e-12);
+
+    assert!(kernel::orientation_bank(kernel::OrientedFilter::Edge, &[], &[1.0]).is_err());
+    assert!(kernel::orientation_bank(kernel::OrientedFilter::Edge, &angles, &[0.0]).is_err());
+    assert!(kernel::orientation_bank(kernel::OrientedFilter::Gabor(0.0), &angles, &[1.0]).is_err());
+}
@@ -0,0 +1,73 @@
+use ndarray::{Array2, Array3, Axis, s};
+
+use imgal::integration::{self, IntegrationMethod};
+use imgal::lifetime;
+use imgal::simulation::decay;
+
+// simulated monoexponential decay parameters, unit is nanoseconds
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 1] = [2.0];
+const FRACTIONS: [f64; 1] = [1.0];
+const TOTAL_COUNTS: f64 = 5000.0;
+const SHAPE: (usize, usize) = (6, 5);
+
+// helper functions
+fn get_decay_image() -> Array3<f64> {
+    decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE).unwrap()
+}
+
+#[test]
+fn intensity_intensity_image() {
+    let data = get_decay_image();
+    let counts: f64 = data.slice(s![0, 0, ..]).sum();
+
+    // the default sums the decay axis
+    let intensity = lifetime::intensity_image(data.view(), None, None, None, None).unwrap();
+    assert_eq!(intensity.dim(), SHAPE);
+    assert!((intensity[[3, 2]] - counts).abs() < 1e-9);
+
+    // a gate sums only the gated time bins
+    let gated: f64 = data.slice(s![0, 0, 10..50]).sum();
+    let intensity =
+        lifetime::intensity_image(data.view(), None, None, Some((10, 50)), None).unwrap();
+    assert!((intensity[[3, 2]] - gated).abs() < 1e-9);
+
+    // integrate a (t, row, col) stack in the mask area
+    let permuted = data.view().permuted_axes([2, 0, 1]);
+    let mut mask = Array2::<bool>::from_elem(SHAPE, true);
+    mask[[0, 0]] = false;
+    let intensity = lifetime::intensity_image(
+        permuted,
+        Some(0),
+        Some(IntegrationMethod::Trapezoid),
+        None,
+        Some(mask.view()),
+    )
+    .unwrap();
+    let lane = data.index_axis(Axis(0), 0).index_axis(Axis(0), 0).to_vec();
+    assert_eq!(intensity[[0, 0]], 0.0);
+    assert!((intensity[[3, 2]] - integration::trapezoid(&lane, None)).abs() < 1e-9);
+}
+
+#[test]
+fn intensity_intensity_image_invalid_parameters() {
+    let data = get_decay_image();
+
+    // check the axis, gate and mask parameters
+    assert!(lifetime::intensity_image(data.view(), Some(3), None, None, None).is_err());
+    assert!(lifetime::intensity_image(data.view(), None, None, Some((50, 50)), None).is_err());
+    assert!(lifetime::intensity_image(data.view(), None, None, Some((0, 300)), None).is_err());
+    assert!(
+        lifetime::intensity_image(
+            data.view(),
+            None,
+            Some(IntegrationMethod::Simpson),
+            Some((5, 6)),
+            None
+        )
+        .is_err()
+    );
+    let mask = Array2::<bool>::from_elem((5, 5), true);
+    assert!(lifetime::intensity_image(data.view(), None, None, None, Some(mask.view())).is_err());
+}
@@ -0,0 +1,118 @@
+use ndarray::{Array2, Array3, s};
+
+use imgal::error::ImgalError;
+use imgal::measure;
+
+// helper functions
+fn two_objects() -> Array2<u32> {
+    // a 2x4 rectangle and a single pixel
+    let mut labels = Array2::<u32>::zeros((6, 8));
+    labels.slice_mut(s![1..3, 2..6]).fill(1);
+    labels[[5, 7]] = 4;
+
+    labels
+}
+
+#[test]
+fn measure_regionprops() {
+    let labels = two_objects();
+    let props = measure::regionprops::<u32, f64>(labels.view(), None, None, None).unwrap();
+
+    // check the shape measurements
+    assert_eq!(props.len(), 2);
+    assert_eq!(props.label, vec![1, 4]);
+    assert_eq!(props.area, vec![8, 1]);
+    assert_eq!(props.centroid_row, vec![1.5, 5.0]);
+    assert_eq!(props.centroid_col, vec![3.5, 7.0]);
+    assert_eq!(props.bbox_min_row, vec![1, 5]);
+    assert_eq!(props.bbox_min_col, vec![2, 7]);
+    assert_eq!(props.bbox_max_row, vec![3, 6]);
+    assert_eq!(props.bbox_max_col, vec![6, 8]);
+    assert_eq!(props.perimeter, vec![12.0, 4.0]);
+    assert_eq!(props.eccentricity[1], 0.0);
+    // row variance 0.25 and column variance 1.25
+    assert!((props.eccentricity[0] - (1.0f64 - 0.2).sqrt()).abs() < 1e-12);
+    assert!(props.mean_intensity.is_none());
+    assert!(props.mean_g.is_none());
+}
+
+#[test]
+fn measure_regionprops_intensity_phasor() {
+    let labels = two_objects();
+    let intensity = Array2::from_shape_fn((6, 8), |(r, c)| (r * 8 + c) as u16);
+    let mut phasor = Array3::<f64>::zeros((2, 6, 8));
+    phasor.slice_mut(s![0, .., ..]).fill(0.5);
+    phasor
+        .slice_mut(s![1, .., ..])
+        .assign(&intensity.mapv(|v| v as f64 / 100.0));
+
+    let props = measure::regionprops(
+        labels.view(),
+        Some(intensity.view()),
+        Some(phasor.view()),
+        Some(0),
+    )
+    .unwrap();
+
+    // check the intensity and phasor means
+    assert_eq!(props.mean_intensity.unwrap(), vec![15.5, 47.0]);
+    assert_eq!(props.min_intensity.unwrap(), vec![10.0, 47.0]);
+    assert_eq!(props.max_intensity.unwrap(), vec![21.0, 47.0]);
+    assert_eq!(props.mean_g.unwrap(), vec![0.5, 0.5]);
+    let mean_s = props.mean_s.unwrap();
+    assert!((mean_s[0] - 0.155).abs() < 1e-12);
+    assert!((mean_s[1] - 0.47).abs() < 1e-12);
+}
+
+#[test]
+fn measure_regionprops_invalid_phasor() {
+    let labels = two_objects();
+    let phasor = Array3::<f64>::zeros((6, 8, 3));
+
+    assert!(matches!(
+        measure::regionprops::<u32, f64>(labels.view(), None, Some(phasor.view()), None),
+        Err(ImgalError::MismatchedArrayLengths { .. })
+    ));
+}
+
+#[test]
+fn measure_find_contours() {
+    // a square block gives one closed clockwise contour with cut corners
+    let mut data = Array2::<u8>::zeros((8, 8));
+    data.slice_mut(s![2..5, 2..5]).fill(1);
+    let contours = measure::find_contours(data.view(), Some(0.5)).unwrap();
+    assert_eq!(contours.len(), 1);
+    let contour = &contours[0];
+    assert_eq!(contour.row(0), contour.row(contour.nrows() - 1));
+    let area = (0..contour.nrows() - 1)
+        .map(|i| {
+            let (r0, c0) = (contour[[i, 0]], contour[[i, 1]]);
+            let (r1, c1) = (contour[[i + 1, 0]], contour[[i + 1, 1]]);
+            c0 * r1 - c1 * r0
+        })
+        .sum::<f64>()
+        / 2.0;
+    assert_eq!(area, 8.5);
+
+    // a circle has points at the radius and the default level is the midpoint
+    let circle = Array2::from_shape_fn((32, 32), |(r, c)| {
+        let d = ((r as f64 - 15.5).powi(2) + (c as f64 - 15.5).powi(2)).sqrt();
+        10.0 - d
+    });
+    let contours = measure::find_contours(circle.view(), Some(0.0)).unwrap();
+    assert_eq!(contours.len(), 1);
+    contours[0].rows().into_iter().for_each(|p| {
+        let d = ((p[0] - 15.5).powi(2) + (p[1] - 15.5).powi(2)).sqrt();
+        assert!((d - 10.0).abs() < 0.05);
+    });
+
+    // a region touching the border gives an open contour
+    let mut data = Array2::<f64>::zeros((6, 6));
+    data.slice_mut(s![.., 3..]).fill(2.0);
+    let contours = measure::find_contours(data.view(), None).unwrap();
+    assert_eq!(contours.len(), 1);
+    assert_eq!(contours[0].nrows(), 6);
+    assert!(contours[0].column(1).iter().all(|&c| c == 2.5));
+
+    assert!(measure::find_contours(data.view(), Some(f64::NAN)).is_err());
+}
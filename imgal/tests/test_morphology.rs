@@ -0,0 +1,194 @@
+use ndarray::{Array1, Array2, Array3, array};
+
+use imgal::morphology::skeleton::SkeletonAnalysis;
+use imgal::morphology::{self, Connectivity};
+
+// helper functions
+fn box_extremum(data: &Array2<f64>, radius: (usize, usize), max: bool) -> Array2<f64> {
+    let (rows, cols) = data.dim();
+    Array2::from_shape_fn((rows, cols), |(r, c)| {
+        let window = data.slice(ndarray::s![
+            r.saturating_sub(radius.0)..(r + radius.0 + 1).min(rows),
+            c.saturating_sub(radius.1)..(c + radius.1 + 1).min(cols)
+        ]);
+        let fold = if max { f64::max } else { f64::min };
+        window.iter().copied().reduce(fold).unwrap()
+    })
+}
+
+#[test]
+fn morphology_erosion_dilation() {
+    // the separable passes match a brute force box filter
+    let data = Array2::from_shape_fn((11, 13), |(r, c)| ((r * 7 + c * 13) % 17) as f64);
+    let eroded = morphology::erosion(data.view().into_dyn(), &[2, 3]).unwrap();
+    let dilated = morphology::dilation(data.view().into_dyn(), &[2, 3]).unwrap();
+    assert_eq!(eroded, box_extremum(&data, (2, 3), false).into_dyn());
+    assert_eq!(dilated, box_extremum(&data, (2, 3), true).into_dyn());
+
+    // a radius of 0 leaves the axis unfiltered
+    let rows = morphology::erosion(data.view().into_dyn(), &[0, 1]).unwrap();
+    assert_eq!(rows, box_extremum(&data, (0, 1), false).into_dyn());
+
+    assert!(morphology::erosion(data.view().into_dyn(), &[1]).is_err());
+}
+
+#[test]
+fn morphology_opening_closing() {
+    let data = Array2::from_shape_fn((11, 13), |(r, c)| ((r * 5 + c * 3) % 11) as u16);
+    let opened = morphology::opening(data.view().into_dyn(), &[1, 1]).unwrap();
+    let closed = morphology::closing(data.view().into_dyn(), &[1, 1]).unwrap();
+
+    // the opening is below and the closing above the image
+    data.iter()
+        .zip(opened.iter().zip(closed.iter()))
+        .for_each(|(&v, (&o, &c))| assert!(o <= v as f64 && v as f64 <= c));
+
+    // opening and closing are idempotent
+    let reopened = morphology::opening(opened.view(), &[1, 1]).unwrap();
+    let reclosed = morphology::closing(closed.view(), &[1, 1]).unwrap();
+    assert_eq!(reopened, opened);
+    assert_eq!(reclosed, closed);
+}
+
+#[test]
+fn morphology_gradient() {
+    // a step edge gives a band of width 2 × radius
+    let data = Array1::from_iter((0..10).map(|i| if i < 5 { 2.0 } else { 9.0 }));
+    let result = morphology::gradient(data.view().into_dyn(), &[1]).unwrap();
+    assert_eq!(
+        result.into_raw_vec_and_offset().0,
+        vec![0.0, 0.0, 0.0, 0.0, 7.0, 7.0, 0.0, 0.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn morphology_label_2d() {
+    // two diagonal pixels and a u-shaped object
+    let mask = array![
+        [true, false, false, true, false, true],
+        [false, true, false, true, false, true],
+        [false, false, false, true, true, true],
+    ];
+
+    // 4-connectivity splits the diagonal pixels
+    let face = morphology::label(mask.view().into_dyn(), Some(Connectivity::Face), None).unwrap();
+    assert_eq!(
+        face.into_dimensionality::<ndarray::Ix2>().unwrap(),
+        array![[1, 0, 0, 2, 0, 2], [0, 3, 0, 2, 0, 2], [0, 0, 0, 2, 2, 2]]
+    );
+
+    // 8-connectivity joins them
+    let full = morphology::label(mask.view().into_dyn(), None, None).unwrap();
+    assert_eq!(
+        full.into_dimensionality::<ndarray::Ix2>().unwrap(),
+        array![[1, 0, 0, 2, 0, 2], [0, 1, 0, 2, 0, 2], [0, 0, 0, 2, 2, 2]]
+    );
+}
+
+#[test]
+fn morphology_label_3d() {
+    // two voxels touching by a corner across planes
+    let mut mask = Array3::<bool>::default((2, 2, 2));
+    mask[[0, 0, 0]] = true;
+    mask[[1, 1, 1]] = true;
+    mask[[1, 0, 0]] = true;
+
+    let face = morphology::label(mask.view().into_dyn(), Some(Connectivity::Face), None).unwrap();
+    assert_eq!(face.iter().copied().max(), Some(2));
+    assert_eq!(face[[1, 0, 0]], face[[0, 0, 0]]);
+    let full = morphology::label(mask.view().into_dyn(), None, None).unwrap();
+    assert_eq!(full.iter().copied().max(), Some(1));
+}
+
+#[test]
+fn morphology_label_parallel() {
+    // a deterministic pseudo-random mask with objects crossing block boundaries
+    let mask = Array2::from_shape_fn((257, 131), |(r, c)| {
+        (r.wrapping_mul(2654435761) ^ c.wrapping_mul(40503)) % 7 < 3
+    });
+
+    // the parallel labeling matches the sequential labeling
+    for connectivity in [Connectivity::Face, Connectivity::Full] {
+        let seq =
+            morphology::label(mask.view().into_dyn(), Some(connectivity), Some(false)).unwrap();
+        let par =
+            morphology::label(mask.view().into_dyn(), Some(connectivity), Some(true)).unwrap();
+        assert_eq!(seq, par);
+        assert!(seq.iter().zip(mask.iter()).all(|(&l, &m)| (l > 0) == m));
+    }
+}
+
+#[test]
+fn morphology_skeletonize_2d() {
+    // a thick bar thins to a connected line along its center rows
+    let mut mask = Array2::<bool>::default((9, 20));
+    mask.slice_mut(ndarray::s![2..7, 2..18]).fill(true);
+    let skeleton = morphology::skeletonize(mask.view().into_dyn()).unwrap();
+    let labels = morphology::label(skeleton.view(), None, None).unwrap();
+    assert_eq!(labels.iter().copied().max(), Some(1));
+    assert!(skeleton.iter().zip(mask.iter()).all(|(&s, &m)| !s || m));
+    let analysis = morphology::analyze_skeleton(skeleton.view(), None).unwrap();
+    assert_eq!(analysis.endpoints.nrows(), 2);
+    assert_eq!(analysis.branch_points.nrows(), 0);
+    assert!(skeleton.iter().filter(|&&s| s).count() <= 16);
+
+    // a ring keeps its hole
+    let ring = Array2::from_shape_fn((15, 15), |(r, c)| {
+        let d = ((r as f64 - 7.0).powi(2) + (c as f64 - 7.0).powi(2)).sqrt();
+        (3.0..=6.0).contains(&d)
+    });
+    let skeleton = morphology::skeletonize(ring.view().into_dyn()).unwrap();
+    let background = skeleton.mapv(|s| !s);
+    let holes = morphology::label(background.view(), Some(Connectivity::Face), None).unwrap();
+    assert_eq!(holes.iter().copied().max(), Some(2));
+    let analysis = morphology::analyze_skeleton(skeleton.view(), None).unwrap();
+    assert_eq!(analysis.endpoints.nrows(), 0);
+
+    let line = ndarray::Array1::<bool>::from_elem(5, true);
+    assert!(morphology::skeletonize(line.view().into_dyn()).is_err());
+}
+
+#[test]
+fn morphology_skeletonize_3d() {
+    // a thick rod thins to a line
+    let mut mask = Array3::<bool>::default((7, 7, 16));
+    mask.slice_mut(ndarray::s![1..6, 1..6, 1..15]).fill(true);
+    let skeleton = morphology::skeletonize(mask.view().into_dyn()).unwrap();
+    let labels = morphology::label(skeleton.view(), None, None).unwrap();
+    assert_eq!(labels.iter().copied().max(), Some(1));
+    let analysis = morphology::analyze_skeleton(skeleton.view(), None).unwrap();
+    assert_eq!(analysis.endpoints.nrows(), 2);
+    assert!(skeleton.iter().filter(|&&s| s).count() < 20);
+}
+
+#[test]
+fn morphology_analyze_skeleton() {
+    // a line, a diagonal and a cross
+    let mut skeleton = Array2::<bool>::default((12, 12));
+    (0..10).for_each(|c| skeleton[[0, c]] = true);
+    (0..5).for_each(|i| skeleton[[3 + i, i]] = true);
+    (6..11).for_each(|i| {
+        skeleton[[8, i]] = true;
+        skeleton[[i, 8]] = true;
+    });
+    let analysis = morphology::analyze_skeleton(skeleton.view().into_dyn(), None).unwrap();
+    let SkeletonAnalysis {
+        endpoints,
+        branch_points,
+        length,
+    } = analysis;
+    assert_eq!(endpoints.nrows(), 8);
+    assert!(
+        branch_points
+            .rows()
+            .into_iter()
+            .any(|r| r.to_vec() == vec![8, 8])
+    );
+    assert!((length - (9.0 + 4.0 * 2f64.sqrt() + 8.0)).abs() < 1e-12);
+
+    // the spacing scales the length
+    let analysis =
+        morphology::analyze_skeleton(skeleton.view().into_dyn(), Some(&[2.0, 0.5])).unwrap();
+    let diagonal = 4.0 * (4.0f64 + 0.25).sqrt();
+    assert!((analysis.length - (4.5 + diagonal + 4.0 * 2.0 + 4.0 * 0.5)).abs() < 1e-12);
+}
@@ -0,0 +1,156 @@
+use ndarray::{Array2, Array3, ArrayD, Axis, Slice, array};
+
+use imgal::chunked;
+use imgal::error::warning::{self, Warning};
+use imgal::operator::{self, LaneOp, Log1p, Mean, PixelOp, Sum};
+
+// custom test operators
+struct Double;
+
+impl PixelOp for Double {
+    fn name(&self) -> &str {
+        "test_double"
+    }
+
+    fn compute(&self, value: f64) -> f64 {
+        2.0 * value
+    }
+}
+
+struct CumSum;
+
+impl LaneOp for CumSum {
+    fn name(&self) -> &str {
+        "test_cumsum"
+    }
+
+    fn output_len(&self, lane_len: usize) -> usize {
+        lane_len
+    }
+
+    fn compute(&self, lane: &[f64], out: &mut [f64]) {
+        let mut acc = 0.0;
+        lane.iter().zip(out.iter_mut()).for_each(|(&v, o)| {
+            acc += v;
+            *o = acc;
+        });
+    }
+}
+
+// helper functions
+fn get_data() -> Array3<u16> {
+    Array3::from_shape_fn((4, 3, 5), |(r, c, t)| (r * 15 + c * 5 + t) as u16)
+}
+
+#[test]
+fn operator_pixel() {
+    let data = get_data().into_dyn();
+    let output = operator::pixel(data.view(), &Log1p, None).unwrap();
+
+    assert_eq!(output.shape(), data.shape());
+    assert_eq!(output[[0, 0, 0]], 0.0);
+    assert_eq!(output[[3, 2, 4]], 59.0_f64.ln_1p());
+}
+
+#[test]
+fn operator_pixel_mask() {
+    let data = get_data().into_dyn();
+    let mask = data.mapv(|v| v % 2 == 0);
+    let output = operator::pixel(data.view(), &Double, Some(mask.view())).unwrap();
+
+    assert_eq!(output[[0, 0, 2]], 4.0);
+    assert_eq!(output[[0, 0, 3]], 0.0);
+
+    // empty mask and mismatched mask shape
+    let empty = ArrayD::from_elem(data.shape(), false);
+    let (output, warnings) =
+        warning::collect(|| operator::pixel(data.view(), &Double, Some(empty.view())));
+    assert!(output.unwrap().iter().all(|&v| v == 0.0));
+    assert_eq!(warnings, vec![Warning::EmptyMask]);
+    let wrong = ArrayD::from_elem(vec![4, 3], true);
+    assert!(operator::pixel(data.view(), &Double, Some(wrong.view())).is_err());
+}
+
+#[test]
+fn operator_lane() {
+    let data = get_data().into_dyn();
+
+    // one value per lane along the default (last) axis
+    let output = operator::lane(data.view(), &Sum, None, None).unwrap();
+    assert_eq!(output.shape(), &[4, 3, 1]);
+    assert_eq!(output[[1, 2, 0]], (25..30).sum::<usize>() as f64);
+
+    // a lane per value along the first axis
+    let output = operator::lane(data.view(), &CumSum, None, Some(0)).unwrap();
+    assert_eq!(output.shape(), data.shape());
+    assert_eq!(output[[3, 1, 2]], (7 + 22 + 37 + 52) as f64);
+
+    // invalid axis
+    assert!(operator::lane(data.view(), &Mean, None, Some(3)).is_err());
+}
+
+#[test]
+fn operator_lane_mask() {
+    let data = get_data().into_dyn();
+    let mask: Array2<bool> = array![
+        [true, false, false],
+        [false, true, false],
+        [false, false, false],
+        [false, false, true]
+    ];
+    let output = operator::lane(data.view(), &Mean, Some(mask.view().into_dyn()), None).unwrap();
+
+    assert_eq!(output[[0, 0, 0]], 2.0);
+    assert_eq!(output[[0, 1, 0]], 0.0);
+    assert_eq!(output[[3, 2, 0]], 57.0);
+
+    // the mask must match the shape without the lane axis
+    let wrong = ArrayD::from_elem(vec![4, 5], true);
+    assert!(operator::lane(data.view(), &Mean, Some(wrong.view()), None).is_err());
+}
+
+#[test]
+fn operator_registry() {
+    // built-in operators are always available
+    assert_eq!(operator::pixel_op("log1p").unwrap().name(), "log1p");
+    assert_eq!(operator::lane_op("mean").unwrap().name(), "mean");
+    assert!(operator::pixel_op("test_unknown").is_none());
+
+    // register custom operators and look them up by name
+    operator::register_pixel_op(Double).unwrap();
+    operator::register_lane_op(CumSum).unwrap();
+    assert!(operator::pixel_op_names().contains(&"test_double".to_string()));
+    assert!(operator::lane_op_names().contains(&"test_cumsum".to_string()));
+    let op = operator::pixel_op("test_double").unwrap();
+    let data = get_data().into_dyn();
+    let output = operator::pixel(data.view(), op.as_ref(), None).unwrap();
+    assert_eq!(output[[1, 0, 0]], 30.0);
+
+    // names must be unique and not empty
+    assert!(operator::register_pixel_op(Double).is_err());
+    assert!(operator::register_pixel_op(Log1p).is_err());
+    assert!(operator::register_lane_op(Sum).is_err());
+}
+
+#[test]
+fn chunked_operator() {
+    let data = get_data().into_dyn();
+    let chunks: Vec<ArrayD<u16>> = [0..1, 1..3, 3..4]
+        .into_iter()
+        .map(|r| data.slice_axis(Axis(0), Slice::from(r)).to_owned())
+        .collect();
+
+    // pixel operator chunks equal the full output
+    let expected = operator::pixel(data.view(), &Log1p, None).unwrap();
+    let outputs: Vec<ArrayD<f64>> = chunked::pixel_op(chunks.clone(), &Log1p).collect();
+    let views: Vec<_> = outputs.iter().map(|o| o.view()).collect();
+    assert_eq!(ndarray::concatenate(Axis(0), &views).unwrap(), expected);
+
+    // lane operator chunks equal the full output
+    let expected = operator::lane(data.view(), &Mean, None, None).unwrap();
+    let outputs: Vec<ArrayD<f64>> = chunked::lane_op(chunks, &Mean, None)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let views: Vec<_> = outputs.iter().map(|o| o.view()).collect();
+    assert_eq!(ndarray::concatenate(Axis(0), &views).unwrap(), expected);
+}
@@ -1,14 +1,107 @@
+use std::f64::consts::PI;
+
 use imgal::parameter;
 
+// helper functions
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
 #[test]
 fn parameter_abbe_diffraction_limit() {
     let l = parameter::abbe_diffraction_limit(570, 1.45);
     assert_eq!(l, 196.55172413793105)
 }
 
+#[test]
+fn parameter_airy_disk_radius() {
+    // test in the sample plane and in the image plane of a 60x objective
+    let r = parameter::airy_disk_radius(500, 1.22, None);
+    assert!(ensure_within_tolerance(r, 250.0, 1e-9));
+    let r = parameter::airy_disk_radius(500, 1.22, Some(60.0));
+    assert!(ensure_within_tolerance(r, 15000.0, 1e-9));
+    assert!(ensure_within_tolerance(
+        parameter::airy_disk_radius(570, 1.45, None),
+        parameter::rayleigh_criterion(570, 1.45),
+        1e-9
+    ));
+}
+
+#[test]
+fn parameter_axial_diffraction_limit() {
+    let d = parameter::axial_diffraction_limit(500, 1.4, 1.518);
+    assert!(ensure_within_tolerance(d, 774.4897959183675, 1e-9));
+}
+
+#[test]
+fn parameter_lifetime_precision() {
+    // test an ideal and a real instrument
+    let s = parameter::lifetime_precision(10000, 4.0, None);
+    assert!(ensure_within_tolerance(s, 0.04, 1e-12));
+    let s = parameter::lifetime_precision(10000, 4.0, Some(1.2));
+    assert!(ensure_within_tolerance(s, 0.048, 1e-12));
+}
+
+#[test]
+fn parameter_nyquist_axial_step() {
+    // a 1.0 NA objective in air has a 90 degree half aperture angle
+    let z = parameter::nyquist_axial_step(500, 1.0, 1.0);
+    assert!(ensure_within_tolerance(z, 250.0, 1e-9));
+    let z = parameter::nyquist_axial_step(520, 1.4, 1.518);
+    assert!(ensure_within_tolerance(z, 279.2070058203112, 1e-6));
+    assert!(parameter::nyquist_axial_step(520, 1.6, 1.518).is_nan());
+}
+
+#[test]
+fn parameter_nyquist_pixel_size() {
+    let x = parameter::nyquist_pixel_size(520, 1.3);
+    assert!(ensure_within_tolerance(x, 100.0, 1e-9));
+}
+
 #[test]
 fn parameter_omega() {
     // test with 12.5 nanoseconds
     let w = parameter::omega(12.5);
     assert_eq!(w, 0.5026548245743669)
 }
+
+#[test]
+fn parameter_phasor_precision() {
+    // with ωτ = 1, (G, S) = (0.5, 0.5) and G₂ = 0.2
+    let (sg, ss) = parameter::phasor_precision(100, 1.0, 2.0 * PI, None);
+    assert!(ensure_within_tolerance(sg, 0.0035_f64.sqrt(), 1e-12));
+    assert!(ensure_within_tolerance(ss, 0.0015_f64.sqrt(), 1e-12));
+
+    // the second harmonic of τ = 0.5 is the same phasor
+    let (sg_2, ss_2) = parameter::phasor_precision(100, 0.5, 2.0 * PI, Some(2.0));
+    assert!(ensure_within_tolerance(sg, sg_2, 1e-12));
+    assert!(ensure_within_tolerance(ss, ss_2, 1e-12));
+}
+
+#[test]
+fn parameter_photon_budget() {
+    let n = parameter::photon_budget(0.01, None);
+    assert!(ensure_within_tolerance(n, 10000.0, 1e-6));
+    let n = parameter::photon_budget(0.04 / 4.0, Some(1.2));
+    assert!(ensure_within_tolerance(n, 14400.0, 1e-6));
+}
+
+#[test]
+fn parameter_rayleigh_criterion() {
+    let d = parameter::rayleigh_criterion(500, 1.22);
+    assert!(ensure_within_tolerance(d, 250.0, 1e-9));
+}
+
+#[test]
+fn parameter_snr() {
+    // shot noise only, with background and with read noise
+    assert!(ensure_within_tolerance(
+        parameter::snr(10000, None, None),
+        100.0,
+        1e-12
+    ));
+    let s = parameter::snr(900.0, Some(100.0), None);
+    assert!(ensure_within_tolerance(s, 900.0 / 1000.0_f64.sqrt(), 1e-12));
+    let s = parameter::snr(900.0, Some(100.0), Some(10.0));
+    assert!(ensure_within_tolerance(s, 900.0 / 1100.0_f64.sqrt(), 1e-12));
+}
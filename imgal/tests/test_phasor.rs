@@ -1,8 +1,12 @@
-use ndarray::{Array2, Axis, s};
+use ndarray::{Array2, Array3, Axis, s};
 
 use imgal::parameter::omega;
-use imgal::phasor::{calibration, plot, time_domain};
+use imgal::phasor::analysis::{PhasorAnalysis, PhasorCursor, Stage};
+use imgal::phasor::waveform::Waveforms;
+use imgal::phasor::{calibration, frequency_domain, metabolic, plot, spectral, time_domain};
+use imgal::simulation::instrument::GaussianIrf;
 use imgal::simulation::{decay, noise};
+use imgal::version::AlgorithmVersion;
 
 // simulated bioexponential decay parameters
 const SAMPLES: usize = 256;
@@ -10,8 +14,10 @@ const PERIOD: f64 = 12.5;
 const TAUS: [f64; 2] = [1.0, 3.0];
 const FRACTIONS: [f64; 2] = [0.7, 0.3];
 const TOTAL_COUNTS: f64 = 5000.0;
-const IRF_CENTER: f64 = 3.0;
-const IRF_WIDTH: f64 = 0.5;
+const IRF: GaussianIrf = GaussianIrf {
+    center: 3.0,
+    width: 0.5,
+};
 const SHAPE: (usize, usize) = (10, 10);
 const MODULATION: f64 = 0.7;
 const PHASE: f64 = -0.981;
@@ -21,6 +27,13 @@ fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
     (a - b).abs() < tolerance
 }
 
+fn get_homodyne_samples(phases: &[f64], g: f64, s: f64, dc: f64, bias: f64) -> Vec<f64> {
+    phases
+        .iter()
+        .map(|p| dc * (1.0 + g * p.cos() + s * p.sin()) + bias)
+        .collect()
+}
+
 fn get_circle_mask(shape: (usize, usize), center: (isize, isize), radius: isize) -> Array2<bool> {
     // set circle parameters
     let (row, col) = shape;
@@ -69,8 +82,7 @@ fn calibration_image() {
         &TAUS,
         &FRACTIONS,
         TOTAL_COUNTS,
-        IRF_CENTER,
-        IRF_WIDTH,
+        IRF,
         SHAPE,
     )
     .unwrap();
@@ -79,7 +91,7 @@ fn calibration_image() {
     let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None).unwrap();
 
     // calibrate the phasor image
-    let cal_gs_arr = calibration::image(gs_arr.view(), MODULATION, PHASE, None);
+    let cal_gs_arr = calibration::image(gs_arr.view(), MODULATION, PHASE, None, None).unwrap();
 
     // pick a point in the calibrated data
     let g_mean = cal_gs_arr.index_axis(Axis(2), 0).mean().unwrap();
@@ -87,6 +99,21 @@ fn calibration_image() {
 
     assert!(ensure_within_tolerance(g_mean, 0.2536762376620283, 1e-12));
     assert!(ensure_within_tolerance(s_mean, 0.48199495552386873, 1e-12));
+
+    // calibrate only in the mask area
+    let mask = get_circle_mask(SHAPE, (5, 5), 2);
+    let cal_masked =
+        calibration::image(gs_arr.view(), MODULATION, PHASE, Some(mask.view()), None).unwrap();
+    assert_eq!(cal_masked[[5, 5, 0]], cal_gs_arr[[5, 5, 0]]);
+    assert_eq!(cal_masked[[0, 0, 0]], 0.0);
+    assert_eq!(cal_masked[[0, 0, 1]], 0.0);
+
+    // check invalid parameters
+    let small = Array2::<bool>::from_elem((4, 4), true);
+    assert!(
+        calibration::image(gs_arr.view(), MODULATION, PHASE, Some(small.view()), None).is_err()
+    );
+    assert!(calibration::image(gs_arr.view(), MODULATION, PHASE, None, Some(3)).is_err());
 }
 
 #[test]
@@ -98,8 +125,7 @@ fn calibration_image_mut() {
         &TAUS,
         &FRACTIONS,
         TOTAL_COUNTS,
-        IRF_CENTER,
-        IRF_WIDTH,
+        IRF,
         SHAPE,
     )
     .unwrap();
@@ -108,7 +134,8 @@ fn calibration_image_mut() {
     let mut gs_arr = time_domain::image(sim_data.view(), PERIOD, None, None, None).unwrap();
 
     // calibrate the phasor image
-    calibration::image_mut(gs_arr.view_mut(), MODULATION, PHASE, None);
+    let uncal_gs_arr = gs_arr.clone();
+    calibration::image_mut(gs_arr.view_mut(), MODULATION, PHASE, None, None).unwrap();
 
     // pick a point in the calibrated data
     let g_mean = gs_arr.index_axis(Axis(2), 0).mean().unwrap();
@@ -116,6 +143,35 @@ fn calibration_image_mut() {
 
     assert!(ensure_within_tolerance(g_mean, 0.2536762376620283, 1e-12));
     assert!(ensure_within_tolerance(s_mean, 0.48199495552386873, 1e-12));
+
+    // calibrate only in the mask area, other pixels are unchanged
+    let mut masked_arr = uncal_gs_arr.clone();
+    let mask = get_circle_mask(SHAPE, (5, 5), 2);
+    calibration::image_mut(
+        masked_arr.view_mut(),
+        MODULATION,
+        PHASE,
+        Some(mask.view()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(masked_arr[[5, 5, 0]], gs_arr[[5, 5, 0]]);
+    assert_eq!(masked_arr[[0, 0, 0]], uncal_gs_arr[[0, 0, 0]]);
+    assert_eq!(masked_arr[[0, 0, 1]], uncal_gs_arr[[0, 0, 1]]);
+
+    // f32 images are calibrated in place and keep their element type
+    let mut f32_arr = uncal_gs_arr.mapv(|v| v as f32);
+    calibration::image_mut(f32_arr.view_mut(), MODULATION, PHASE, None, None).unwrap();
+    assert!(ensure_within_tolerance(
+        f32_arr[[5, 5, 0]] as f64,
+        gs_arr[[5, 5, 0]],
+        1e-6
+    ));
+    assert!(ensure_within_tolerance(
+        f32_arr[[5, 5, 1]] as f64,
+        gs_arr[[5, 5, 1]],
+        1e-6
+    ));
 }
 
 #[test]
@@ -128,6 +184,130 @@ fn calibration_modulation_and_phase() {
 }
 
 // test the phasor::plot module
+#[test]
+fn frequency_domain_coordinates() {
+    // sample a monoexponential phasor with 4 evenly spaced phases
+    let (g, s) = plot::monoexponential_coordinates(2.5, omega(PERIOD));
+    let phases: Vec<f64> = (0..4)
+        .map(|k| k as f64 * std::f64::consts::FRAC_PI_2)
+        .collect();
+    let data = get_homodyne_samples(&phases, g, s, 1000.0, 0.0);
+    let (fg, fs) = frequency_domain::coordinates(&data, None, None).unwrap();
+    assert!(ensure_within_tolerance(fg, g, 1e-12));
+    assert!(ensure_within_tolerance(fs, s, 1e-12));
+
+    // unevenly spaced phases with a camera bias
+    let phases = [0.1, 0.9, 2.0, 3.3, 5.0];
+    let data = get_homodyne_samples(&phases, g, s, 1000.0, 100.0);
+    let (fg, fs) = frequency_domain::coordinates(&data, Some(&phases), Some(100.0)).unwrap();
+    assert!(ensure_within_tolerance(fg, g, 1e-12));
+    assert!(ensure_within_tolerance(fs, s, 1e-12));
+
+    // ignoring the bias lowers the modulation
+    let (bg, bs) = frequency_domain::coordinates(&data, Some(&phases), None).unwrap();
+    assert!(plot::modulation(bg, bs) < plot::modulation(g, s));
+
+    // check too few samples and degenerate phases
+    assert!(frequency_domain::coordinates(&data[..2], None, None).is_err());
+    assert!(frequency_domain::coordinates(&data, Some(&[0.0, 0.0, 1.0, 1.0, 0.0]), None).is_err());
+    assert!(frequency_domain::coordinates(&data, Some(&phases[..4]), None).is_err());
+}
+
+#[test]
+fn frequency_domain_image() {
+    // create a phase image stack with a monoexponential phasor, (phase, row, col)
+    let (g, s) = plot::monoexponential_coordinates(2.5, omega(PERIOD));
+    let phases: Vec<f64> = (0..3)
+        .map(|k| k as f64 * 2.0 * std::f64::consts::PI / 3.0)
+        .collect();
+    let samples = get_homodyne_samples(&phases, g, s, 500.0, 0.0);
+    let mut data = Array3::<f64>::zeros((3, SHAPE.0, SHAPE.1));
+    data.axis_iter_mut(Axis(0))
+        .zip(samples.iter())
+        .for_each(|(mut img, v)| img.fill(*v));
+    let mask = get_circle_mask(SHAPE, (5, 5), 3);
+    let gs = frequency_domain::image(data.view(), None, None, Some(mask.view()), Some(0)).unwrap();
+
+    // check coordinates in and out of the mask
+    assert_eq!(gs.shape(), [10, 10, 2]);
+    assert!(ensure_within_tolerance(gs[[5, 5, 0]], g, 1e-12));
+    assert!(ensure_within_tolerance(gs[[5, 5, 1]], s, 1e-12));
+    assert_eq!(gs[[0, 0, 0]], 0.0);
+    assert_eq!(gs[[0, 0, 1]], 0.0);
+}
+
+#[test]
+fn metabolic_bound_fraction() {
+    // free and bound NADH references from monoexponential decays
+    let free_decay = decay::ideal_exponential_1d(SAMPLES, PERIOD, &[0.4], &[1.0], 1.0).unwrap();
+    let bound_decay = decay::ideal_exponential_1d(SAMPLES, PERIOD, &[3.4], &[1.0], 1.0).unwrap();
+    let free = (
+        time_domain::real(&free_decay, PERIOD, None),
+        time_domain::imaginary(&free_decay, PERIOD, None),
+    );
+    let bound = (
+        time_domain::real(&bound_decay, PERIOD, None),
+        time_domain::imaginary(&bound_decay, PERIOD, None),
+    );
+
+    // a 60/40 free/bound intensity mixture lies 40% of the way to the bound
+    // reference
+    let mix: Vec<f64> = free_decay
+        .iter()
+        .zip(bound_decay.iter())
+        .map(|(f, b)| 0.6 * f + 0.4 * b)
+        .collect();
+    let g = time_domain::real(&mix, PERIOD, None);
+    let s = time_domain::imaginary(&mix, PERIOD, None);
+    let fb = metabolic::bound_fraction(g, s, free, bound).unwrap();
+    let mi = metabolic::metabolic_index(g, s, free, bound).unwrap();
+    assert!(ensure_within_tolerance(fb, 0.4, 1e-9));
+    assert!(ensure_within_tolerance(mi, 1.5, 1e-8));
+
+    // coordinates beyond the references are clamped
+    assert_eq!(
+        metabolic::bound_fraction(1.0, 0.0, free, bound).unwrap(),
+        0.0
+    );
+    assert_eq!(
+        metabolic::metabolic_index(free.0, free.1, free, bound).unwrap(),
+        f64::INFINITY
+    );
+
+    // check equal references
+    assert!(metabolic::bound_fraction(g, s, free, free).is_err());
+}
+
+#[test]
+fn metabolic_bound_fraction_image() {
+    // create a (ch, row, col) phasor image with a bound fraction gradient
+    let free = (0.9, 0.3);
+    let bound = (0.3, 0.45);
+    let mut data = Array3::<f64>::zeros((2, SHAPE.0, SHAPE.1));
+    for c in 0..SHAPE.1 {
+        let f = c as f64 / (SHAPE.1 - 1) as f64;
+        data.slice_mut(s![0, .., c])
+            .fill(free.0 + f * (bound.0 - free.0));
+        data.slice_mut(s![1, .., c])
+            .fill(free.1 + f * (bound.1 - free.1));
+    }
+    let mask = get_circle_mask(SHAPE, (5, 5), 3);
+    let fb = metabolic::bound_fraction_image(data.view(), free, bound, None, Some(0)).unwrap();
+    let mi = metabolic::metabolic_index_image(data.view(), free, bound, Some(mask.view()), Some(0))
+        .unwrap();
+
+    // check fractions along the gradient and the masked metabolic index
+    assert_eq!(fb.shape(), [10, 10]);
+    assert!(ensure_within_tolerance(fb[[4, 0]], 0.0, 1e-12));
+    assert!(ensure_within_tolerance(fb[[4, 3]], 1.0 / 3.0, 1e-12));
+    assert!(ensure_within_tolerance(fb[[4, 9]], 1.0, 1e-12));
+    assert!(ensure_within_tolerance(mi[[5, 3]], 2.0, 1e-9));
+    assert_eq!(mi[[0, 0]], 0.0);
+
+    // check the channel axis length
+    assert!(metabolic::bound_fraction_image(data.view(), free, bound, None, None).is_err());
+}
+
 #[test]
 fn plot_modulation() {
     let m = plot::modulation(0.71, 0.43);
@@ -160,12 +340,11 @@ fn plot_map_image() {
         &TAUS,
         &FRACTIONS,
         TOTAL_COUNTS,
-        IRF_CENTER,
-        IRF_WIDTH,
+        IRF,
         (50, 50),
     )
     .unwrap();
-    noise::poisson_3d_mut(i.view_mut(), 0.3, None, None);
+    noise::poisson_3d_mut(i.view_mut(), 0.3, None, None).unwrap();
 
     // compute phasor array and select coordinates to map back
     let gs_arr = time_domain::image(i.view(), PERIOD, None, None, None).unwrap();
@@ -173,13 +352,177 @@ fn plot_map_image() {
     let s_coords = gs_arr.slice(s![25..30, 25..30, 1]).flatten().to_vec();
 
     // map the coords back to the image
-    let mask = plot::map_mask(gs_arr.view(), &g_coords, &s_coords, None).unwrap();
+    let mask = plot::map_mask(gs_arr.view(), &g_coords, &s_coords, None, None).unwrap();
 
     // check a spot in mask and outside of it
     assert_eq!(mask[[28, 28]], true);
     assert_eq!(mask[[5, 5]], false);
+
+    // map the coords back only in a region of the image
+    let mut region = Array2::<bool>::from_elem((50, 50), true);
+    region[[28, 28]] = false;
+    let masked = plot::map_mask(
+        gs_arr.view(),
+        &g_coords,
+        &s_coords,
+        Some(region.view()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(masked[[28, 28]], false);
+    assert_eq!(masked[[27, 27]], true);
 }
+// test the phasor::spectral module
+#[test]
+fn spectral_coordinates() {
+    // a single channel spectrum lies on the universal circle at its channel
+    let mut data = vec![0.0; 16];
+    data[4] = 10.0;
+    let (g, s) = spectral::coordinates(&data, None).unwrap();
+    assert!(ensure_within_tolerance(g, 0.0, 1e-12));
+    assert!(ensure_within_tolerance(s, 1.0, 1e-12));
+
+    // the second harmonic doubles the phase
+    let (g, s) = spectral::coordinates(&data, Some(2.0)).unwrap();
+    assert!(ensure_within_tolerance(g, -1.0, 1e-12));
+    assert!(ensure_within_tolerance(s, 0.0, 1e-12));
+
+    // a flat spectrum is at the origin, an empty spectrum is NaN
+    let (g, s) = spectral::coordinates(&[1.0; 8], None).unwrap();
+    assert!(g.abs() < 1e-12 && s.abs() < 1e-12);
+    assert!(spectral::coordinates(&[0.0; 8], None).unwrap().0.is_nan());
+
+    // check invalid parameters
+    assert!(spectral::coordinates(&[1.0], None).is_err());
+    assert!(spectral::coordinates(&data, Some(0.5)).is_err());
+}
+
+#[test]
+fn spectral_image() {
+    // create a hyperspectral cube with a gaussian spectrum, (ch, row, col)
+    let spectrum: Vec<f64> = (0..32)
+        .map(|k| 100.0 * (-((k as f64 - 12.0) / 3.0).powi(2)).exp())
+        .collect();
+    let (g, s) = spectral::coordinates(&spectrum, None).unwrap();
+    let mut data = Array3::<f64>::zeros((32, SHAPE.0, SHAPE.1));
+    data.axis_iter_mut(Axis(0))
+        .zip(spectrum.iter())
+        .for_each(|(mut img, v)| img.fill(*v));
+    let mask = get_circle_mask(SHAPE, (5, 5), 3);
+    let gs = spectral::image(data.view(), None, Some(mask.view()), Some(0)).unwrap();
+
+    // check coordinates in and out of the mask
+    assert_eq!(gs.shape(), [10, 10, 2]);
+    assert!(ensure_within_tolerance(gs[[5, 5, 0]], g, 1e-12));
+    assert!(ensure_within_tolerance(gs[[5, 5, 1]], s, 1e-12));
+    assert_eq!(gs[[0, 0, 0]], 0.0);
+
+    // select the pixels with a cursor
+    let cursor = PhasorCursor { g, s, radius: 0.05 };
+    let sel = spectral::cursor_mask(gs.view(), cursor, None, None).unwrap();
+    assert_eq!(sel, mask);
+
+    // calibrate the phasor image onto a known reference position
+    let (m, p) = spectral::modulation_and_phase(g, s, 0.5, 0.5);
+    let cal = calibration::image(gs.view(), m, p, None, None).unwrap();
+    assert!(ensure_within_tolerance(cal[[5, 5, 0]], 0.5, 1e-12));
+    assert!(ensure_within_tolerance(cal[[5, 5, 1]], 0.5, 1e-12));
+}
+
+#[test]
+fn spectral_unmix() {
+    // two components, a 30/70 mixture
+    let comps = [(0.8, 0.2), (0.2, 0.6)];
+    let f = spectral::unmix(0.3 * 0.8 + 0.7 * 0.2, 0.3 * 0.2 + 0.7 * 0.6, &comps).unwrap();
+    assert!(ensure_within_tolerance(f[0], 0.3, 1e-12));
+    assert!(ensure_within_tolerance(f[1], 0.7, 1e-12));
+
+    // three components, a 20/30/50 mixture
+    let comps = [(0.8, 0.2), (0.2, 0.6), (-0.3, -0.4)];
+    let w = [0.2, 0.3, 0.5];
+    let g: f64 = comps.iter().zip(w.iter()).map(|(c, w)| c.0 * w).sum();
+    let s: f64 = comps.iter().zip(w.iter()).map(|(c, w)| c.1 * w).sum();
+    let f = spectral::unmix(g, s, &comps).unwrap();
+    f.iter()
+        .zip(w.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+
+    // unmix an image, masked pixels are 0.0
+    let mut gs = Array3::<f64>::zeros((SHAPE.0, SHAPE.1, 2));
+    gs.slice_mut(s![.., .., 0]).fill(g);
+    gs.slice_mut(s![.., .., 1]).fill(s);
+    let mask = get_circle_mask(SHAPE, (5, 5), 3);
+    let fractions = spectral::unmix_image(gs.view(), &comps, Some(mask.view()), None).unwrap();
+    assert_eq!(fractions.shape(), [10, 10, 3]);
+    assert!(ensure_within_tolerance(fractions[[5, 5, 2]], 0.5, 1e-12));
+    assert_eq!(fractions[[0, 0, 2]], 0.0);
+
+    // check invalid components
+    assert!(spectral::unmix(g, s, &[(0.5, 0.5)]).is_err());
+    assert!(spectral::unmix(g, s, &[(0.5, 0.5), (0.5, 0.5)]).is_err());
+    assert!(spectral::unmix(g, s, &[(0.0, 0.0), (0.5, 0.5), (1.0, 1.0)]).is_err());
+}
+
 // test the phasor::time_domain module
+#[test]
+fn time_domain_batch() {
+    // a batch of the same decay curve at different intensities and an empty curve
+    let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    let curves = Array2::from_shape_fn((4, SAMPLES), |(r, t)| i[t] * r as f64);
+    let gs = time_domain::batch(curves.view(), PERIOD, None, None).unwrap();
+
+    // check each curve matches the 1-dimensional coordinates
+    let g = time_domain::real(&i, PERIOD, None);
+    let s = time_domain::imaginary(&i, PERIOD, None);
+    assert_eq!(gs.shape(), [4, 2]);
+    assert!(gs[[0, 0]].is_nan() && gs[[0, 1]].is_nan());
+    (1..4).for_each(|r| {
+        assert!(ensure_within_tolerance(gs[[r, 0]], g, 1e-12));
+        assert!(ensure_within_tolerance(gs[[r, 1]], s, 1e-12));
+    });
+
+    // check the transposed batch and the harmonic
+    let gs_t = time_domain::batch(curves.t(), PERIOD, Some(2.0), Some(0)).unwrap();
+    assert!(ensure_within_tolerance(
+        gs_t[[2, 0]],
+        time_domain::real(&i, PERIOD, Some(2.0)),
+        1e-12
+    ));
+
+    // check invalid parameters
+    assert!(time_domain::batch(curves.view(), 0.0, None, None).is_err());
+    assert!(time_domain::batch(curves.view(), PERIOD, Some(0.5), None).is_err());
+    assert!(time_domain::batch(curves.view(), PERIOD, None, Some(2)).is_err());
+}
+
+#[test]
+fn time_domain_histogram_quality_image() {
+    // get simulated data and circle mask
+    let i = decay::gaussian_exponential_3d(
+        SAMPLES,
+        PERIOD,
+        &TAUS,
+        &FRACTIONS,
+        TOTAL_COUNTS,
+        IRF,
+        SHAPE,
+    )
+    .unwrap();
+    let mask = get_circle_mask(SHAPE, (5, 5), 2);
+
+    // compute the quality map with and without a mask
+    let q = time_domain::histogram_quality_image(i.view(), 1.0, None, None).unwrap();
+    let q_masked =
+        time_domain::histogram_quality_image(i.view(), 1.0, Some(mask.view()), None).unwrap();
+    assert!(q[[0, 0]] > 0.0);
+    assert_eq!(q_masked[[5, 5]], q[[5, 5]]);
+    assert_eq!(q_masked[[0, 0]], 0.0);
+
+    // check invalid parameters
+    let small = Array2::<bool>::from_elem((4, 4), true);
+    assert!(time_domain::histogram_quality_image(i.view(), 1.0, Some(small.view()), None).is_err());
+}
+
 #[test]
 fn time_domain_image() {
     // get simulated data
@@ -189,8 +532,7 @@ fn time_domain_image() {
         &TAUS,
         &FRACTIONS,
         TOTAL_COUNTS,
-        IRF_CENTER,
-        IRF_WIDTH,
+        IRF,
         (100, 100),
     )
     .unwrap();
@@ -247,6 +589,17 @@ fn time_domain_image() {
     ));
 }
 
+#[test]
+fn time_domain_image_invalid_parameters() {
+    let i = Array3::<f64>::zeros((20, 20, SAMPLES));
+    let mask = Array2::<bool>::from_elem((10, 10), true);
+
+    // invalid period, harmonic and mismatched mask shape
+    assert!(time_domain::image(i.view(), 0.0, None, None, None).is_err());
+    assert!(time_domain::image(i.view(), PERIOD, None, Some(0.0), None).is_err());
+    assert!(time_domain::image(i.view(), PERIOD, Some(mask.view()), None, None).is_err());
+}
+
 #[test]
 fn time_domain_imaginary() {
     let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
@@ -262,3 +615,80 @@ fn time_domain_real() {
 
     assert_eq!(g, 0.660137605034518);
 }
+
+#[test]
+fn analysis_phasor_analysis() {
+    // simulate a monoexponential decay image with a dim top half
+    let mut data =
+        decay::ideal_exponential_3d(SAMPLES, PERIOD, &[2.0], &[1.0], TOTAL_COUNTS, SHAPE).unwrap();
+    data.slice_mut(s![..5, .., ..]).mapv_inplace(|v| v * 0.1);
+    let mut analysis = PhasorAnalysis::new(data.view(), PERIOD, None, None).unwrap();
+    let expected = time_domain::image(data.view(), PERIOD, None, None, None).unwrap();
+
+    // stages are computed lazily
+    assert!(!analysis.is_cached(Stage::Phasor));
+    assert_eq!(analysis.phasor(), &expected);
+    assert!(analysis.selection().is_none());
+    assert_eq!(analysis.calibrated(), &expected);
+    assert!(analysis.is_cached(Stage::Calibration));
+
+    // the threshold only invalidates the mask and the stages after it
+    analysis.set_threshold(TOTAL_COUNTS * 0.5).unwrap();
+    assert!(analysis.is_cached(Stage::Phasor));
+    assert!(!analysis.is_cached(Stage::Mask));
+    assert!(!analysis.is_cached(Stage::Calibration));
+    assert_eq!(analysis.mask().iter().filter(|&&m| m).count(), 50);
+    assert_eq!(analysis.calibrated()[[0, 0, 0]], 0.0);
+
+    // the cursor only invalidates the selection
+    let (g, s) = (expected[[9, 9, 0]], expected[[9, 9, 1]]);
+    analysis
+        .set_cursor(Some(PhasorCursor { g, s, radius: 0.01 }))
+        .unwrap();
+    assert!(analysis.is_cached(Stage::Calibration));
+    let selection = analysis.selection().unwrap();
+    assert!(!selection[[0, 0]]);
+    assert!(selection[[9, 9]]);
+    assert_eq!(selection.iter().filter(|&&m| m).count(), 50);
+
+    // the calibration moves the coordinates out of the cursor
+    analysis.set_calibration(Some((0.5, 0.3))).unwrap();
+    assert!(analysis.is_cached(Stage::Mask));
+    assert!(!analysis.is_cached(Stage::Selection));
+    let calibrated = calibration::coordinates(g, s, 0.5, 0.3);
+    assert!(ensure_within_tolerance(
+        analysis.calibrated()[[9, 9, 0]],
+        calibrated.0,
+        1e-12
+    ));
+    assert!(!analysis.selection().unwrap().iter().any(|&m| m));
+
+    // invalid parameters
+    assert!(analysis.set_threshold(f64::NAN).is_err());
+    assert!(
+        analysis
+            .set_cursor(Some(PhasorCursor { g, s, radius: 0.0 }))
+            .is_err()
+    );
+    assert!(PhasorAnalysis::new(data.view(), 0.0, None, None).is_err());
+}
+
+#[test]
+fn waveform_cached() {
+    // waveforms of the same configuration are shared
+    let step = 2.0 * std::f64::consts::PI / SAMPLES as f64;
+    let a = Waveforms::cached(SAMPLES, step, AlgorithmVersion::V1);
+    let b = Waveforms::cached(SAMPLES, step, AlgorithmVersion::V1);
+    let c = Waveforms::cached(SAMPLES, 2.0 * step, AlgorithmVersion::V1);
+    assert!(std::sync::Arc::ptr_eq(&a, &b));
+    assert!(!std::sync::Arc::ptr_eq(&a, &c));
+    assert_eq!(*a, Waveforms::new(SAMPLES, step, AlgorithmVersion::V1));
+
+    // check the sampled waveforms
+    assert_eq!(a.bins(), SAMPLES);
+    assert_eq!(a.cos()[0], 1.0);
+    assert_eq!(a.sin()[0], 0.0);
+    assert!(ensure_within_tolerance(a.cos()[SAMPLES / 4], 0.0, 1e-12));
+    assert!(ensure_within_tolerance(a.sin()[SAMPLES / 4], 1.0, 1e-12));
+    assert!(ensure_within_tolerance(c.cos()[SAMPLES / 4], -1.0, 1e-12));
+}
@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+
+use ndarray::{Array2, ArrayD, Axis, Ix2, Ix3, Slice, concatenate};
+
+use imgal::filter;
+use imgal::measure;
+use imgal::morphology;
+use imgal::phasor::{calibration, time_domain};
+use imgal::pipeline::{Pipeline, Step, Value};
+use imgal::simulation::{decay, pattern};
+use imgal::threshold;
+
+// simulated monoexponential decay parameters
+const SAMPLES: usize = 64;
+const PERIOD: f64 = 12.5;
+const TAU: f64 = 2.0;
+const TOTAL_COUNTS: f64 = 5000.0;
+
+// helper functions
+fn inputs(values: Vec<(&str, Value)>) -> BTreeMap<String, Value> {
+    values
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect()
+}
+
+fn get_decay_data() -> (ArrayD<f64>, ArrayD<bool>) {
+    let data = decay::ideal_exponential_3d(SAMPLES, PERIOD, &[TAU], &[1.0], TOTAL_COUNTS, (6, 5))
+        .unwrap()
+        .into_dyn();
+    let mask = Array2::from_shape_fn((6, 5), |(r, c)| (r + c) % 3 != 0).into_dyn();
+
+    (data, mask)
+}
+
+fn get_phasor_pipeline() -> Pipeline {
+    let mut pipeline = Pipeline::new(&["decay", "mask"]).unwrap();
+    pipeline
+        .add(
+            "phasor",
+            Step::PhasorImage {
+                period: PERIOD,
+                harmonic: None,
+                axis: None,
+            },
+            &["decay", "mask"],
+        )
+        .unwrap();
+    pipeline
+        .add(
+            "calibrated",
+            Step::PhasorCalibrate {
+                modulation: 0.9,
+                phase: 0.1,
+                axis: None,
+            },
+            &["phasor", "mask"],
+        )
+        .unwrap();
+    pipeline
+        .add(
+            "cursor",
+            Step::PhasorCursor {
+                g: 0.5,
+                s: 0.4,
+                radius: 0.5,
+                axis: None,
+            },
+            &["calibrated", "mask"],
+        )
+        .unwrap();
+
+    pipeline
+}
+
+#[test]
+fn pipeline_segmentation() {
+    // filter -> threshold -> label -> measure
+    let data = pattern::blobs_2d((40, 30), 0.05, 2.0, 7)
+        .unwrap()
+        .into_dyn();
+    let mut pipeline = Pipeline::new(&["image"]).unwrap();
+    pipeline
+        .add(
+            "smooth",
+            Step::Gaussian {
+                sigma: vec![1.0, 1.0],
+            },
+            &["image"],
+        )
+        .unwrap();
+    pipeline
+        .add("mask", Step::OtsuThreshold { bins: None }, &["smooth"])
+        .unwrap();
+    pipeline
+        .add("labels", Step::Label { connectivity: None }, &["mask"])
+        .unwrap();
+    pipeline
+        .add(
+            "props",
+            Step::Regionprops { axis: None },
+            &["labels", "image"],
+        )
+        .unwrap();
+    let outputs = pipeline
+        .run(inputs(vec![("image", Value::Image(data.clone()))]))
+        .unwrap();
+
+    // the pipeline outputs equal the chained function outputs
+    let smooth = filter::gaussian(data.view(), &[1.0, 1.0], None, None).unwrap();
+    let mask = threshold::otsu_mask(smooth.view(), None, None).unwrap();
+    let labels = morphology::label(mask.view(), None, None).unwrap();
+    let props = measure::regionprops(
+        labels.view().into_dimensionality::<Ix2>().unwrap(),
+        Some(data.view().into_dimensionality::<Ix2>().unwrap()),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(outputs.len(), 4);
+    assert_eq!(outputs["smooth"].as_image(), Some(&smooth));
+    assert_eq!(outputs["mask"].as_mask(), Some(&mask));
+    assert_eq!(outputs["labels"].as_labels(), Some(&labels));
+    assert_eq!(outputs["props"].as_table(), Some(&props));
+    assert!(!props.label.is_empty());
+}
+
+#[test]
+fn pipeline_phasor() {
+    // phasor -> calibrate -> cursor
+    let (data, mask) = get_decay_data();
+    let pipeline = get_phasor_pipeline();
+    let outputs = pipeline
+        .run(inputs(vec![
+            ("decay", Value::Image(data.clone())),
+            ("mask", Value::Mask(mask.clone())),
+        ]))
+        .unwrap();
+
+    let data = data.into_dimensionality::<Ix3>().unwrap();
+    let mask = mask.into_dimensionality::<Ix2>().unwrap();
+    let gs = time_domain::image(data.view(), PERIOD, Some(mask.view()), None, None).unwrap();
+    let gs = calibration::image(gs.view(), 0.9, 0.1, Some(mask.view()), None).unwrap();
+    assert_eq!(outputs["calibrated"].as_image(), Some(&gs.into_dyn()));
+
+    // only pixels inside of the mask are selected
+    let cursor = outputs["cursor"].as_mask().unwrap();
+    assert_eq!(cursor.shape(), &[6, 5]);
+    assert_eq!(cursor, &mask.into_dyn());
+}
+
+#[test]
+fn pipeline_operator() {
+    // a registered operator node feeding a threshold node
+    let data = ArrayD::from_shape_fn(vec![4, 3, 5], |idx| (idx[0] + idx[1] + idx[2]) as f64);
+    let mut pipeline = Pipeline::new(&["data"]).unwrap();
+    pipeline
+        .add(
+            "sum",
+            Step::LaneOp {
+                name: "sum".to_string(),
+                axis: Some(2),
+            },
+            &["data"],
+        )
+        .unwrap();
+    pipeline
+        .add(
+            "log",
+            Step::PixelOp {
+                name: "log1p".to_string(),
+            },
+            &["sum"],
+        )
+        .unwrap();
+    pipeline
+        .add("bright", Step::PercentileThreshold { p: 50.0 }, &["log"])
+        .unwrap();
+    let outputs = pipeline
+        .run(inputs(vec![("data", Value::Image(data))]))
+        .unwrap();
+
+    assert_eq!(outputs["sum"].as_image().unwrap()[[3, 2, 0]], 35.0);
+    assert_eq!(
+        outputs["log"].as_image().unwrap()[[0, 0, 0]],
+        10.0_f64.ln_1p()
+    );
+    assert_eq!(
+        outputs["bright"]
+            .as_mask()
+            .unwrap()
+            .iter()
+            .filter(|&&m| m)
+            .count(),
+        6
+    );
+
+    // unregistered operators fail at run time
+    let mut pipeline = Pipeline::new(&["data"]).unwrap();
+    pipeline
+        .add(
+            "op",
+            Step::PixelOp {
+                name: "test_unknown".to_string(),
+            },
+            &["data"],
+        )
+        .unwrap();
+    let data = ArrayD::<f64>::zeros(vec![2, 2]);
+    assert!(
+        pipeline
+            .run(inputs(vec![("data", Value::Image(data))]))
+            .is_err()
+    );
+}
+
+#[test]
+fn pipeline_invalid() {
+    let mut pipeline = Pipeline::new(&["image"]).unwrap();
+    assert!(Pipeline::new(&["image", "image"]).is_err());
+    assert!(Pipeline::new(&[""]).is_err());
+
+    // duplicate names, unknown inputs and wrong number of inputs
+    let step = Step::ManualThreshold { threshold: 1.0 };
+    assert!(pipeline.add("image", step.clone(), &["image"]).is_err());
+    assert!(pipeline.add("mask", step.clone(), &["unknown"]).is_err());
+    assert!(pipeline.add("mask", step.clone(), &[]).is_err());
+    assert!(
+        pipeline
+            .add("mask", step.clone(), &["image", "image"])
+            .is_err()
+    );
+    pipeline.add("mask", step, &["image"]).unwrap();
+    assert_eq!(pipeline.nodes().len(), 1);
+    assert!(pipeline.set_num_threads(Some(0)).is_err());
+
+    // missing, unknown and wrong kinds of input values
+    let data = ArrayD::<f64>::zeros(vec![3, 3]);
+    assert!(pipeline.run(BTreeMap::new()).is_err());
+    assert!(
+        pipeline
+            .run(inputs(vec![
+                ("image", Value::Image(data.clone())),
+                ("other", Value::Image(data.clone())),
+            ]))
+            .is_err()
+    );
+    assert!(
+        pipeline
+            .run(inputs(vec![("image", Value::Mask(data.mapv(|v| v > 0.0)))]))
+            .is_err()
+    );
+}
+
+#[test]
+fn pipeline_threads() {
+    let (data, mask) = get_decay_data();
+    let values = inputs(vec![
+        ("decay", Value::Image(data)),
+        ("mask", Value::Mask(mask)),
+    ]);
+    let mut pipeline = get_phasor_pipeline();
+    let expected = pipeline.run(values.clone()).unwrap();
+    pipeline.set_num_threads(Some(2)).unwrap();
+
+    assert_eq!(pipeline.num_threads(), Some(2));
+    assert_eq!(pipeline.run(values).unwrap(), expected);
+}
+
+#[test]
+fn pipeline_chunked() {
+    let (data, mask) = get_decay_data();
+    let pipeline = get_phasor_pipeline();
+    assert!(pipeline.is_chunkable());
+    let expected = pipeline
+        .run(inputs(vec![
+            ("decay", Value::Image(data.clone())),
+            ("mask", Value::Mask(mask.clone())),
+        ]))
+        .unwrap();
+
+    // split the rows into chunks of uneven lengths
+    let chunks: Vec<BTreeMap<String, Value>> = [0..2, 2..3, 3..6]
+        .into_iter()
+        .map(|r| {
+            inputs(vec![
+                (
+                    "decay",
+                    Value::Image(data.slice_axis(Axis(0), Slice::from(r.clone())).to_owned()),
+                ),
+                (
+                    "mask",
+                    Value::Mask(mask.slice_axis(Axis(0), Slice::from(r)).to_owned()),
+                ),
+            ])
+        })
+        .collect();
+    let outputs: Vec<BTreeMap<String, Value>> = pipeline
+        .run_chunked(chunks)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let gs: Vec<_> = outputs
+        .iter()
+        .map(|o| o["calibrated"].as_image().unwrap().view())
+        .collect();
+    let cursor: Vec<_> = outputs
+        .iter()
+        .map(|o| o["cursor"].as_mask().unwrap().view())
+        .collect();
+    assert_eq!(
+        &concatenate(Axis(0), &gs).unwrap(),
+        expected["calibrated"].as_image().unwrap()
+    );
+    assert_eq!(
+        &concatenate(Axis(0), &cursor).unwrap(),
+        expected["cursor"].as_mask().unwrap()
+    );
+
+    // steps that need the full image can not run on chunks
+    let mut pipeline = Pipeline::new(&["image"]).unwrap();
+    pipeline
+        .add("mask", Step::OtsuThreshold { bins: None }, &["image"])
+        .unwrap();
+    assert!(!pipeline.is_chunkable());
+    assert!(pipeline.run_chunked(Vec::new()).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn pipeline_json() {
+    let mut pipeline = get_phasor_pipeline();
+    pipeline.set_num_threads(Some(2)).unwrap();
+    pipeline
+        .add(
+            "labels",
+            Step::Label {
+                connectivity: Some(morphology::Connectivity::Face),
+            },
+            &["cursor"],
+        )
+        .unwrap();
+    let json = pipeline.to_json();
+
+    // the description round trips
+    assert!(json.contains("\"phasor_image\""));
+    assert!(json.contains("\"algorithm_version\": 1"));
+    assert_eq!(Pipeline::from_json(&json).unwrap(), pipeline);
+
+    // invalid JSON and invalid graphs are rejected
+    assert!(Pipeline::from_json("{").is_err());
+    let cyclic = json.replace("[\n        \"decay\",", "[\n        \"cursor\",");
+    assert_ne!(cyclic, json);
+    assert!(Pipeline::from_json(&cyclic).is_err());
+    let unknown = json.replace("\"algorithm_version\": 1", "\"algorithm_version\": 99");
+    assert!(Pipeline::from_json(&unknown).is_err());
+}
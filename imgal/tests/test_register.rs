@@ -0,0 +1,197 @@
+use ndarray::Array2;
+
+use imgal::error::{Warning, warning};
+use imgal::register;
+use imgal::transform;
+
+// helper functions
+fn spots(offset: (f64, f64)) -> Array2<f64> {
+    // a few blurred spots, moved by the offset
+    let centers = [(12.0, 20.0), (30.0, 9.0), (41.0, 37.0), (20.0, 44.0)];
+    Array2::from_shape_fn((56, 60), |(r, c)| {
+        centers
+            .iter()
+            .map(|&(cr, cc)| {
+                let d2 = (r as f64 - cr - offset.0).powi(2) + (c as f64 - cc - offset.1).powi(2);
+                (-d2 / 2.0).exp()
+            })
+            .sum()
+    })
+}
+
+#[test]
+fn register_phase_correlation() {
+    // an integer shift is found exactly
+    let reference = spots((0.0, 0.0));
+    let moving = spots((-3.0, 5.0));
+    let result = register::phase_correlation(reference.view(), moving.view(), Some(1)).unwrap();
+    assert_eq!(result.shift, (3.0, -5.0));
+    assert!(result.peak > 0.5);
+
+    // a sub-pixel shift is found to the upsampled precision
+    let moving = spots((1.3, -2.6));
+    let result = register::phase_correlation(reference.view(), moving.view(), None).unwrap();
+    assert!((result.shift.0 + 1.3).abs() <= 0.1);
+    assert!((result.shift.1 - 2.6).abs() <= 0.1);
+
+    let small = Array2::<f64>::zeros((4, 4));
+    assert!(register::phase_correlation(reference.view(), small.view(), None).is_err());
+    assert!(register::phase_correlation(reference.view(), moving.view(), Some(0)).is_err());
+}
+
+#[test]
+fn register_apply_shift() {
+    // applying the estimated shift registers the moving image
+    let reference = spots((0.0, 0.0));
+    let moving = spots((-3.0, 5.0));
+    let result = register::phase_correlation(reference.view(), moving.view(), None).unwrap();
+    let registered = register::apply_shift(moving.view(), result.shift, None).unwrap();
+    registered
+        .slice(ndarray::s![4..50, 4..52])
+        .iter()
+        .zip(reference.slice(ndarray::s![4..50, 4..52]).iter())
+        .for_each(|(a, b)| assert!((a - b).abs() < 1e-12));
+
+    // fractional shifts interpolate and the uncovered border is filled
+    let data = Array2::from_shape_fn((3, 4), |(r, c)| (r * 4 + c) as u8);
+    let shifted = register::apply_shift(data.view(), (0.0, 0.5), Some(-1.0)).unwrap();
+    assert_eq!(shifted.row(1).to_vec(), vec![-1.0, 4.5, 5.5, 6.5]);
+    let shifted = register::apply_shift(data.view(), (-1.0, 0.0), None).unwrap();
+    assert_eq!(shifted.row(0).to_vec(), vec![4.0, 5.0, 6.0, 7.0]);
+    assert_eq!(shifted.row(2).to_vec(), vec![0.0; 4]);
+
+    assert!(register::apply_shift(data.view(), (f64::NAN, 0.0), None).is_err());
+}
+
+fn rotation(degrees: f64, shift: (f64, f64), shape: (usize, usize)) -> Array2<f64> {
+    // a rotation about the image center followed by a shift
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let center = ((shape.0 - 1) as f64 / 2.0, (shape.1 - 1) as f64 / 2.0);
+    ndarray::array![
+        [
+            cos,
+            -sin,
+            center.0 - cos * center.0 + sin * center.1 + shift.0
+        ],
+        [
+            sin,
+            cos,
+            center.1 - sin * center.0 - cos * center.1 + shift.1
+        ],
+        [0.0, 0.0, 1.0]
+    ]
+}
+
+fn blobs(shape: (usize, usize)) -> Array2<f64> {
+    // a few wide blobs of different heights
+    let centers = [(20.0, 22.0, 1.0), (44.0, 18.0, 0.6), (36.0, 50.0, 0.8)];
+    Array2::from_shape_fn(shape, |(r, c)| {
+        centers
+            .iter()
+            .map(|&(cr, cc, h)| {
+                let d2 = (r as f64 - cr).powi(2) + (c as f64 - cc).powi(2);
+                h * (-d2 / 50.0).exp()
+            })
+            .sum()
+    })
+}
+
+#[test]
+fn register_affine() {
+    // a rotated and shifted image is registered with each metric
+    let shape = (64, 72);
+    let reference = blobs(shape);
+    let forward = rotation(5.0, (2.0, -3.0), shape);
+    let moving = transform::warp_affine(reference.view().into_dyn(), forward.view(), None, None)
+        .unwrap()
+        .into_dimensionality::<ndarray::Ix2>()
+        .unwrap();
+    let expected = rotation(-5.0, (0.0, 0.0), shape).dot(&rotation(0.0, (-2.0, 3.0), shape));
+    [
+        (register::Transform::Rigid, register::Metric::Mse),
+        (register::Transform::Affine, register::Metric::Ncc),
+        (
+            register::Transform::Rigid,
+            register::Metric::MutualInformation,
+        ),
+    ]
+    .into_iter()
+    .for_each(|(t, m)| {
+        let result = register::affine(
+            reference.view(),
+            moving.view(),
+            Some(t),
+            Some(m),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        result
+            .matrix
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(a, b)| assert!((a - b).abs() < 0.1, "{:?} {:?}", t, m));
+        assert!(result.metric.is_finite());
+    });
+
+    // a translation is recovered with the normalized cross-correlation despite
+    // an intensity scaling
+    let moving = register::apply_shift(reference.view(), (1.5, -2.0), None).unwrap() * 3.0 + 1.0;
+    let result = register::affine(
+        reference.view(),
+        moving.view(),
+        Some(register::Transform::Translation),
+        Some(register::Metric::Ncc),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!((result.matrix[[0, 2]] + 1.5).abs() < 0.05);
+    assert!((result.matrix[[1, 2]] - 2.0).abs() < 0.05);
+    assert!(result.metric > 0.99);
+
+    let small = Array2::<f64>::zeros((3, 8));
+    assert!(register::affine(small.view(), small.view(), None, None, None, None, None).is_err());
+    assert!(
+        register::affine(
+            reference.view(),
+            moving.view(),
+            None,
+            None,
+            Some(0),
+            None,
+            None
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn register_affine_not_converged() {
+    // a single simplex iteration can not converge and is reported
+    let shape = (64, 72);
+    let reference = blobs(shape);
+    let moving = register::apply_shift(reference.view(), (1.5, -2.0), None).unwrap();
+    let (result, warnings) = warning::collect(|| {
+        register::affine(
+            reference.view(),
+            moving.view(),
+            Some(register::Transform::Translation),
+            None,
+            Some(1),
+            None,
+            Some(1),
+        )
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(
+        warnings,
+        vec![Warning::NotConverged {
+            operation: "affine registration",
+            count: 1
+        }]
+    );
+}
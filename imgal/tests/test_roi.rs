@@ -0,0 +1,184 @@
+use ndarray::{Array2, Array3, Array4, Array5};
+
+use imgal::error::ImgalError;
+use imgal::parameter::omega;
+use imgal::phasor::time_domain;
+use imgal::roi::{Manager, ResultsRow, ResultsTable, composite};
+use imgal::simulation::decay;
+
+// helper functions
+fn get_rect_mask(
+    shape: (usize, usize),
+    rows: (usize, usize),
+    cols: (usize, usize),
+) -> Array2<bool> {
+    Array2::from_shape_fn(shape, |(r, c)| {
+        r >= rows.0 && r < rows.1 && c >= cols.0 && c < cols.1
+    })
+}
+
+fn get_manager() -> Manager {
+    let mut manager = Manager::new();
+    manager
+        .add("a", get_rect_mask((4, 4), (0, 2), (0, 2)), &["nucleus"])
+        .unwrap();
+    manager
+        .add(
+            "b",
+            get_rect_mask((4, 4), (2, 4), (2, 4)),
+            &["cytoplasm", "cell"],
+        )
+        .unwrap();
+    manager
+}
+
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+fn mean_and_area(px: &[f64]) -> Vec<(&'static str, f64)> {
+    vec![
+        ("mean", px.iter().sum::<f64>() / px.len() as f64),
+        ("area", px.len() as f64),
+    ]
+}
+
+#[test]
+fn composite_summary_4d() {
+    let manager = get_manager();
+    let data = Array4::from_shape_fn((2, 4, 4, 2), |(t, r, c, ch)| {
+        (r * 4 + c + t * 100 + ch * 1000) as f64
+    });
+    let table = composite::summary_4d(&manager, data.view(), None).unwrap();
+
+    // 2 ROIs x 2 frames x 2 channels x 3 measurements
+    assert_eq!(table.len(), 24);
+    assert_eq!(table.rows()[3].channel, 1);
+    assert_eq!(table.rows()[6].slice, 1);
+    assert_eq!(
+        table.values("mean"),
+        vec![2.5, 1002.5, 102.5, 1102.5, 12.5, 1012.5, 112.5, 1112.5]
+    );
+    assert_eq!(table.values("median"), table.values("mean"));
+    assert_eq!(table.roi("a")[2].value, 10.0);
+}
+
+#[test]
+fn composite_summary_5d() {
+    let manager = get_manager();
+    let curve = decay::ideal_exponential_1d(256, 12.5, &[2.0], &[1.0], 1000.0).unwrap();
+    let data = Array5::from_shape_fn(
+        (1, 4, 4, 1, 256),
+        |(_, r, _, _, k)| {
+            if r < 2 { curve[k] } else { 0.0 }
+        },
+    );
+    let table = composite::summary_5d(&manager, data.view(), 12.5, None).unwrap();
+    let lifetimes = table.values("lifetime");
+
+    assert_eq!(table.len(), 8);
+    let g = time_domain::real(&curve, 12.5, None);
+    let s = time_domain::imaginary(&curve, 12.5, None);
+    let tau_phi = s / (omega(12.5) * g);
+
+    assert!(ensure_within_tolerance(lifetimes[0], tau_phi, 1e-9));
+    assert!(ensure_within_tolerance(lifetimes[0], 2.0, 0.1));
+    assert!(lifetimes[1].is_nan());
+    assert!(ensure_within_tolerance(
+        table.values("integrated")[0],
+        4000.0,
+        1e-6
+    ));
+    assert!(composite::summary_5d(&manager, data.view(), 0.0, None).is_err());
+}
+
+#[test]
+fn manager_add_and_groups() {
+    let mut manager = get_manager();
+    manager.tag("a", "cell");
+    manager.tag("a", "cell");
+
+    assert_eq!(manager.len(), 2);
+    assert_eq!(manager.names(), vec!["a", "b"]);
+    assert_eq!(manager.groups(), vec!["nucleus", "cell", "cytoplasm"]);
+    assert_eq!(manager.group("cell").len(), 2);
+    assert_eq!(manager.get("b").unwrap().area(), 4);
+    assert!(manager.untag("a", "cell"));
+    assert!(!manager.tag("c", "cell"));
+    assert_eq!(manager.remove("a").unwrap().name, "a");
+    assert_eq!(manager.names(), vec!["b"]);
+}
+
+#[test]
+fn manager_add_invalid() {
+    let mut manager = get_manager();
+
+    assert!(matches!(
+        manager.add("a", Array2::from_elem((4, 4), true), &[]),
+        Err(ImgalError::InvalidParameter {
+            param_name: "name",
+            ..
+        })
+    ));
+    assert!(matches!(
+        manager.add("c", Array2::from_elem((3, 4), true), &[]),
+        Err(ImgalError::MismatchedArrayShapes { .. })
+    ));
+}
+
+#[test]
+fn manager_measure() {
+    let manager = get_manager();
+    let data = Array3::from_shape_fn((4, 4, 3), |(r, c, t)| (r * 4 + c + t * 100) as f64);
+    let table = manager
+        .measure(data.view(), mean_and_area, None, None)
+        .unwrap();
+
+    // 2 ROIs x 3 slices x 2 measurements, ordered by ROI, slice, measurement
+    assert_eq!(table.len(), 12);
+    assert_eq!(table.rows()[0].roi, "a");
+    assert_eq!(table.rows()[0].measurement, "mean");
+    assert_eq!(table.rows()[2].slice, 1);
+    assert_eq!(table.rows()[6].roi, "b");
+    assert_eq!(
+        table.values("mean"),
+        vec![2.5, 102.5, 202.5, 12.5, 112.5, 212.5]
+    );
+    assert_eq!(table.values("area"), vec![4.0; 6]);
+}
+
+#[test]
+fn manager_measure_group_and_axis() {
+    let manager = get_manager();
+    let data = Array3::from_shape_fn((3, 4, 4), |(t, r, c)| (r * 4 + c + t * 100) as f64);
+    let table = manager
+        .measure(data.view(), mean_and_area, Some("cytoplasm"), Some(0))
+        .unwrap();
+
+    assert_eq!(table.len(), 6);
+    assert!(table.iter().all(|r| r.roi == "b"));
+    assert_eq!(table.values("mean"), vec![12.5, 112.5, 212.5]);
+    assert!(
+        manager
+            .measure(data.view(), mean_and_area, None, None)
+            .is_err()
+    );
+}
+
+#[test]
+fn table_to_csv() {
+    let mut table = ResultsTable::new();
+    table.push(ResultsRow {
+        roi: "cell, 1".to_string(),
+        groups: vec!["a".to_string(), "b".to_string()],
+        slice: 0,
+        channel: 2,
+        measurement: "mean".to_string(),
+        value: 1.5,
+    });
+
+    assert_eq!(
+        table.to_csv(),
+        "roi,groups,slice,channel,measurement,value\n\"cell, 1\",a;b,0,2,mean,1.5\n"
+    );
+}
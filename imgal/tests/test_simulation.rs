@@ -1,7 +1,10 @@
-use ndarray::s;
+use ndarray::{Array3, Axis, s};
 
+use imgal::error::{Warning, warning};
 use imgal::integration::midpoint;
-use imgal::simulation::{decay, instrument, noise};
+use imgal::simulation::anisotropy::AnisotropyDecay;
+use imgal::simulation::instrument::GaussianIrf;
+use imgal::simulation::{Rng, anisotropy, decay, instrument, noise, pattern, spectra, timelapse};
 use imgal::statistics::sum;
 
 // simulated bioexponential decay parameters
@@ -12,6 +15,10 @@ const FRACTIONS: [f64; 2] = [0.7, 0.3];
 const TOTAL_COUNTS: f64 = 5000.0;
 const IRF_CENTER: f64 = 3.0;
 const IRF_WIDTH: f64 = 0.5;
+const IRF: GaussianIrf = GaussianIrf {
+    center: IRF_CENTER,
+    width: IRF_WIDTH,
+};
 const SHAPE: (usize, usize) = (10, 10);
 
 // helper functions
@@ -20,24 +27,258 @@ fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
 }
 
 #[test]
-fn decay_gaussian_exponential_1d() {
-    // simulate decay data
-    let i = decay::gaussian_exponential_1d(
+fn decay_fret_exponential_1d() {
+    // a single efficiency without donor only molecules is a quenched donor
+    let donor_tau = 4.0;
+    let f = decay::fret_exponential_1d(
         SAMPLES,
         PERIOD,
-        &TAUS,
-        &FRACTIONS,
+        decay::Fret {
+            donor_tau,
+            efficiency: decay::FretEfficiency::Single(0.5),
+            donor_only_fraction: 0.0,
+        },
+        TOTAL_COUNTS,
+    )
+    .unwrap();
+    let q = decay::ideal_exponential_1d(SAMPLES, PERIOD, &[2.0], &[1.0], TOTAL_COUNTS).unwrap();
+    f.iter()
+        .zip(q.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-9)));
+
+    // donor only molecules are amplitude weighted, 0.3 and 0.7 amplitudes at
+    // 4.0 and 2.0 ns are 1.2/2.6 and 1.4/2.6 intensity fractions
+    let f = decay::fret_exponential_1d(
+        SAMPLES,
+        PERIOD,
+        decay::Fret {
+            donor_tau,
+            efficiency: decay::FretEfficiency::Single(0.5),
+            donor_only_fraction: 0.3,
+        },
+        TOTAL_COUNTS,
+    )
+    .unwrap();
+    let q = decay::ideal_exponential_1d(
+        SAMPLES,
+        PERIOD,
+        &[4.0, 2.0],
+        &[1.2 / 2.6, 1.4 / 2.6],
+        TOTAL_COUNTS,
+    )
+    .unwrap();
+    f.iter()
+        .zip(q.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-9)));
+
+    // two populations with an equal split
+    let f = decay::fret_exponential_1d(
+        SAMPLES,
+        PERIOD,
+        decay::Fret {
+            donor_tau,
+            efficiency: decay::FretEfficiency::TwoPopulation {
+                first: 0.25,
+                second: 0.75,
+                second_fraction: 0.5,
+            },
+            donor_only_fraction: 0.0,
+        },
+        TOTAL_COUNTS,
+    )
+    .unwrap();
+    let q = decay::ideal_exponential_1d(SAMPLES, PERIOD, &[3.0, 1.0], &[0.75, 0.25], TOTAL_COUNTS)
+        .unwrap();
+    f.iter()
+        .zip(q.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-9)));
+
+    // a narrow Gaussian distribution approaches the single efficiency decay
+    let g = decay::fret_exponential_1d(
+        SAMPLES,
+        PERIOD,
+        decay::Fret {
+            donor_tau,
+            efficiency: decay::FretEfficiency::Gaussian {
+                mean: 0.5,
+                sigma: 0.001,
+            },
+            donor_only_fraction: 0.0,
+        },
+        TOTAL_COUNTS,
+    )
+    .unwrap();
+    let s = decay::fret_exponential_1d(
+        SAMPLES,
+        PERIOD,
+        decay::Fret {
+            donor_tau,
+            efficiency: decay::FretEfficiency::Single(0.5),
+            donor_only_fraction: 0.0,
+        },
         TOTAL_COUNTS,
-        IRF_CENTER,
-        IRF_WIDTH,
     )
     .unwrap();
+    assert!(ensure_within_tolerance(sum(&g), TOTAL_COUNTS, 1e-9));
+    g.iter()
+        .zip(s.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-2)));
+
+    // check invalid efficiencies and fractions
+    let single = |e: f64, x: f64| {
+        decay::fret_exponential_1d(
+            SAMPLES,
+            PERIOD,
+            decay::Fret {
+                donor_tau,
+                efficiency: decay::FretEfficiency::Single(e),
+                donor_only_fraction: x,
+            },
+            TOTAL_COUNTS,
+        )
+    };
+    assert!(single(1.0, 0.0).is_err());
+    assert!(single(-0.1, 0.0).is_err());
+    assert!(single(0.5, 1.5).is_err());
+}
+
+#[test]
+fn decay_gaussian_fret_exponential_3d() {
+    // simulate a Gaussian IRF convolved FRET donor image
+    let eff = decay::FretEfficiency::Gaussian {
+        mean: 0.4,
+        sigma: 0.1,
+    };
+    let i = decay::gaussian_fret_exponential_3d(
+        SAMPLES,
+        PERIOD,
+        decay::Fret {
+            donor_tau: 4.0,
+            efficiency: eff,
+            donor_only_fraction: 0.2,
+        },
+        TOTAL_COUNTS,
+        IRF,
+        SHAPE,
+    )
+    .unwrap();
+    let c = decay::gaussian_fret_exponential_1d(
+        SAMPLES,
+        PERIOD,
+        decay::Fret {
+            donor_tau: 4.0,
+            efficiency: eff,
+            donor_only_fraction: 0.2,
+        },
+        TOTAL_COUNTS,
+        IRF,
+    )
+    .unwrap();
+
+    // check the shape and that each pixel holds the 1-dimensional curve
+    assert_eq!(i.shape(), [10, 10, SAMPLES]);
+    i.slice(s![7, 2, ..])
+        .iter()
+        .zip(c.iter())
+        .for_each(|(a, b)| assert_eq!(a, b));
+}
+
+#[test]
+fn decay_gaussian_exponential_1d() {
+    // simulate decay data
+    let i = decay::gaussian_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, IRF)
+        .unwrap();
 
     // check curve photon count and a point on the curve (near max)
     assert!(ensure_within_tolerance(sum(&i), 4960.5567668085005, 1e-12));
     assert!(ensure_within_tolerance(i[68], 135.7148429095218, 1e-12));
 }
 
+// test the simulation::anisotropy module
+#[test]
+fn anisotropy_anisotropy_decay_1d() {
+    let r = anisotropy::anisotropy_decay_1d(SAMPLES, PERIOD, 0.4, &[2.0], &[1.0]).unwrap();
+
+    assert_eq!(r.len(), SAMPLES);
+    assert!(ensure_within_tolerance(r[0], 0.4, 1e-12));
+    assert!(r.windows(2).all(|w| w[0] > w[1]));
+
+    // r0 outside of the physical range is invalid
+    assert!(anisotropy::anisotropy_decay_1d(SAMPLES, PERIOD, 0.5, &[2.0], &[1.0]).is_err());
+}
+
+#[test]
+fn anisotropy_ideal_anisotropy_1d() {
+    let g = 1.2;
+    let (par, perp) = anisotropy::ideal_anisotropy_1d(
+        SAMPLES,
+        PERIOD,
+        AnisotropyDecay {
+            taus: &TAUS,
+            fractions: &FRACTIONS,
+            total_counts: TOTAL_COUNTS,
+            r0: 0.38,
+            rotational_times: &[1.5, 8.0],
+            rotational_fractions: &[0.6, 0.4],
+        },
+        Some(g),
+    )
+    .unwrap();
+    let r =
+        anisotropy::anisotropy_decay_1d(SAMPLES, PERIOD, 0.38, &[1.5, 8.0], &[0.6, 0.4]).unwrap();
+
+    // the polarized channels recover the isotropic decay and anisotropy
+    let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    (0..SAMPLES).for_each(|k| {
+        let total = par[k] + 2.0 * g * perp[k];
+        assert!(ensure_within_tolerance(total, i[k], 1e-9));
+        assert!(ensure_within_tolerance(
+            (par[k] - g * perp[k]) / total,
+            r[k],
+            1e-12
+        ));
+    });
+}
+
+#[test]
+fn anisotropy_gaussian_anisotropy_3d() {
+    let (par, perp) = anisotropy::gaussian_anisotropy_3d(
+        SAMPLES,
+        PERIOD,
+        AnisotropyDecay {
+            taus: &TAUS,
+            fractions: &FRACTIONS,
+            total_counts: TOTAL_COUNTS,
+            r0: 0.4,
+            rotational_times: &[2.0],
+            rotational_fractions: &[1.0],
+        },
+        IRF,
+        None,
+        SHAPE,
+    )
+    .unwrap();
+    let (par_1d, _) = anisotropy::gaussian_anisotropy_1d(
+        SAMPLES,
+        PERIOD,
+        AnisotropyDecay {
+            taus: &TAUS,
+            fractions: &FRACTIONS,
+            total_counts: TOTAL_COUNTS,
+            r0: 0.4,
+            rotational_times: &[2.0],
+            rotational_fractions: &[1.0],
+        },
+        IRF,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(par.shape(), [10, 10, SAMPLES]);
+    assert_eq!(perp.shape(), [10, 10, SAMPLES]);
+    assert_eq!(par.slice(s![5, 5, ..]).to_vec(), par_1d);
+}
+
 // test the simulation::decay module
 #[test]
 fn decay_gaussian_exponential_3d() {
@@ -48,8 +289,7 @@ fn decay_gaussian_exponential_3d() {
         &TAUS,
         &FRACTIONS,
         TOTAL_COUNTS,
-        IRF_CENTER,
-        IRF_WIDTH,
+        IRF,
         SHAPE,
     )
     .unwrap();
@@ -78,6 +318,24 @@ fn decay_ideal_exponential_1d() {
     assert!(ensure_within_tolerance(i[30], 53.625382823015336, 1e-12));
 }
 
+#[test]
+fn decay_ideal_exponential_1d_fractions_warning() {
+    // fractions that sum to 1.0 within round off are normalized with a warning
+    let taus = [1.0, 2.0, 3.0];
+    let fractions = [0.1, 0.2, 0.7 + 1e-9];
+    let (i, warnings) = warning::collect(|| {
+        decay::ideal_exponential_1d(SAMPLES, PERIOD, &taus, &fractions, TOTAL_COUNTS)
+    });
+
+    // fractions that do not sum to 1.0 are an error
+    let err = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &[0.5, 0.3], TOTAL_COUNTS);
+
+    assert!(ensure_within_tolerance(sum(&i.unwrap()), 5000.0, 1e-9));
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0], Warning::FractionsNormalized { .. }));
+    assert!(err.is_err());
+}
+
 #[test]
 fn decay_ideal_exponential_3d() {
     // simulate decay data
@@ -98,10 +356,59 @@ fn decay_ideal_exponential_3d() {
     ));
 }
 
+#[test]
+fn decay_invalid_parameters() {
+    // check the decay parameters, lengths, taus, fractions and counts
+    let ideal = |taus: &[f64], fractions: &[f64], counts: f64| {
+        decay::ideal_exponential_1d(SAMPLES, PERIOD, taus, fractions, counts)
+    };
+    assert!(ideal(&TAUS, &[1.0], TOTAL_COUNTS).is_err());
+    assert!(ideal(&[-1.0, 3.0], &FRACTIONS, TOTAL_COUNTS).is_err());
+    assert!(ideal(&[f64::NAN, 3.0], &FRACTIONS, TOTAL_COUNTS).is_err());
+    assert!(ideal(&TAUS, &[1.2, -0.2], TOTAL_COUNTS).is_err());
+    assert!(ideal(&[0.0, 0.0], &FRACTIONS, TOTAL_COUNTS).is_err());
+    assert!(ideal(&[1.0, 0.0], &[0.0, 1.0], TOTAL_COUNTS).is_err());
+    assert!(ideal(&TAUS, &FRACTIONS, -1.0).is_err());
+    assert!(
+        decay::ideal_exponential_periodic_1d(SAMPLES, 0.0, &TAUS, &FRACTIONS, TOTAL_COUNTS)
+            .is_err()
+    );
+
+    // check the IRF center and the IRF curve
+    assert!(
+        decay::gaussian_exponential_1d(
+            SAMPLES,
+            PERIOD,
+            &TAUS,
+            &FRACTIONS,
+            TOTAL_COUNTS,
+            GaussianIrf {
+                center: PERIOD + 1.0,
+                width: IRF_WIDTH
+            }
+        )
+        .is_err()
+    );
+    assert!(
+        decay::irf_exponential_1d(&[], SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).is_err()
+    );
+    assert!(
+        decay::irf_exponential_periodic_1d(
+            &[0.0, f64::NAN],
+            SAMPLES,
+            PERIOD,
+            &TAUS,
+            &FRACTIONS,
+            TOTAL_COUNTS
+        )
+        .is_err()
+    );
+}
+
 #[test]
 fn decay_irf_exponential_1d() {
     // simulate IRF data to convolve decay data
-    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH);
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
     let i =
         decay::irf_exponential_1d(&irf, SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
 
@@ -113,7 +420,7 @@ fn decay_irf_exponential_1d() {
 #[test]
 fn decay_irf_exponential_3d() {
     // simulate IRF data to convolve decay data
-    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH);
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
     let i = decay::irf_exponential_3d(
         &irf,
         SAMPLES,
@@ -140,10 +447,189 @@ fn decay_irf_exponential_3d() {
 }
 
 // test the simulation::instrument module
+#[test]
+fn decay_gaussian_exponential_periodic_1d() {
+    // simulate periodic decay data, circular convolution preserves all counts
+    let i = decay::gaussian_exponential_periodic_1d(
+        SAMPLES,
+        PERIOD,
+        &TAUS,
+        &FRACTIONS,
+        TOTAL_COUNTS,
+        IRF,
+    )
+    .unwrap();
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
+
+    assert_eq!(i.len(), SAMPLES);
+    assert!(ensure_within_tolerance(
+        sum(&i),
+        TOTAL_COUNTS * sum(&irf),
+        1e-9
+    ));
+
+    // the tail of the previous period is present before the IRF peak
+    assert!(i[0] > i[20]);
+}
+
+#[test]
+fn decay_gaussian_exponential_periodic_3d() {
+    let i = decay::gaussian_exponential_periodic_3d(
+        SAMPLES,
+        PERIOD,
+        &TAUS,
+        &FRACTIONS,
+        TOTAL_COUNTS,
+        IRF,
+        SHAPE,
+    )
+    .unwrap();
+
+    assert_eq!(i.shape(), [10, 10, SAMPLES]);
+}
+
+#[test]
+fn decay_ideal_exponential_periodic_1d() {
+    // short lifetimes fully decay within the period, leaving a plain
+    // exponential decay sampled at t = i × T / samples
+    let short_periodic =
+        decay::ideal_exponential_periodic_1d(SAMPLES, PERIOD, &[0.1], &[1.0], TOTAL_COUNTS)
+            .unwrap();
+    let dt = PERIOD / SAMPLES as f64;
+    let short: Vec<f64> = (0..SAMPLES)
+        .map(|i| (-(i as f64) * dt / 0.1).exp())
+        .collect();
+    let scale = TOTAL_COUNTS / sum(&short);
+    short
+        .iter()
+        .zip(short_periodic.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a * scale, *b, 1e-9)));
+
+    // long lifetimes carry incomplete decay into the next period, increasing
+    // the relative intensity of the long component at the end of the period
+    let taus = [1.0, 10.0];
+    let long =
+        decay::ideal_exponential_1d(SAMPLES, PERIOD, &taus, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    let long_periodic =
+        decay::ideal_exponential_periodic_1d(SAMPLES, PERIOD, &taus, &FRACTIONS, TOTAL_COUNTS)
+            .unwrap();
+    assert!(ensure_within_tolerance(
+        sum(&long_periodic),
+        TOTAL_COUNTS,
+        1e-9
+    ));
+    assert!(long_periodic[SAMPLES - 1] > long[SAMPLES - 1]);
+}
+
+#[test]
+fn decay_ideal_exponential_periodic_1d_wrap() {
+    // the decay continues from the last bin into the first bin of the next
+    // period, one bin later, where it is the carried over part of the first bin
+    let i = decay::ideal_exponential_periodic_1d(SAMPLES, PERIOD, &[3.0], &[1.0], TOTAL_COUNTS)
+        .unwrap();
+    let ratio = (-PERIOD / SAMPLES as f64 / 3.0).exp();
+    let carried = (-PERIOD / 3.0).exp();
+    assert!(ensure_within_tolerance(i[1] / i[0], ratio, 1e-12));
+    assert!(ensure_within_tolerance(
+        i[SAMPLES - 1] * ratio / i[0],
+        carried,
+        1e-12
+    ));
+}
+
+#[test]
+fn decay_irf_exponential_periodic_1d() {
+    // a periodic IRF convolved curve matches the gaussian variant
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
+    let i =
+        decay::irf_exponential_periodic_1d(&irf, SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS)
+            .unwrap();
+    let g = decay::gaussian_exponential_periodic_1d(
+        SAMPLES,
+        PERIOD,
+        &TAUS,
+        &FRACTIONS,
+        TOTAL_COUNTS,
+        IRF,
+    )
+    .unwrap();
+
+    i.iter()
+        .zip(g.iter())
+        .for_each(|(a, b)| assert!(ensure_within_tolerance(*a, *b, 1e-12)));
+}
+
+#[test]
+fn decay_irf_exponential_periodic_3d() {
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
+    let i = decay::irf_exponential_periodic_3d(
+        &irf,
+        SAMPLES,
+        PERIOD,
+        &TAUS,
+        &FRACTIONS,
+        TOTAL_COUNTS,
+        SHAPE,
+    )
+    .unwrap();
+
+    assert_eq!(i.shape(), [10, 10, SAMPLES]);
+}
+
+#[test]
+fn decay_sample_photons() {
+    let i = decay::gaussian_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, IRF)
+        .unwrap();
+    let times = decay::sample_photons(&i, PERIOD, 1000, Some(42)).unwrap();
+
+    // exact photon count, sorted and within the period
+    assert_eq!(times.len(), 1000);
+    assert!(times.windows(2).all(|w| w[0] <= w[1]));
+    assert!(times[0] >= 0.0 && times[999] < PERIOD);
+
+    // photons of a single sample arrive within the time bin of that sample
+    let dt = PERIOD / SAMPLES as f64;
+    let mut pulse = vec![0.0; SAMPLES];
+    pulse[10] = 1.0;
+    let times = decay::sample_photons(&pulse, PERIOD, 100, Some(7)).unwrap();
+    assert!(times.iter().all(|&t| t >= 10.0 * dt && t < 11.0 * dt));
+
+    // an empty curve can not be sampled
+    assert!(decay::sample_photons(&[0.0; 10], PERIOD, 10, None).is_err());
+}
+
+#[test]
+fn decay_sample_photons_1d() {
+    let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    let hist = decay::sample_photons_1d(&i, 50, Some(42)).unwrap();
+
+    // exact photon count with integer bins
+    assert_eq!(hist.len(), SAMPLES);
+    assert_eq!(sum(&hist), 50.0);
+    assert!(hist.iter().all(|v| v.fract() == 0.0));
+    assert_eq!(hist, decay::sample_photons_1d(&i, 50, Some(42)).unwrap());
+}
+
+#[test]
+fn decay_sample_photons_3d() {
+    let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    let hist = decay::sample_photons_3d(&i, 100, SHAPE, Some(42)).unwrap();
+
+    // every pixel has exactly the requested number of photons
+    assert_eq!(hist.shape(), [10, 10, SAMPLES]);
+    hist.lanes(Axis(2))
+        .into_iter()
+        .for_each(|ln| assert_eq!(ln.sum(), 100.0));
+    assert_eq!(
+        hist,
+        decay::sample_photons_3d(&i, 100, SHAPE, Some(42)).unwrap()
+    );
+}
+
 #[test]
 fn instrument_gaussian_irf_1d() {
     // simulate IRF data
-    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH);
+    let irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
 
     // bin width for integration check
     let dt = PERIOD / SAMPLES as f64;
@@ -155,9 +641,202 @@ fn instrument_gaussian_irf_1d() {
         1e-12
     ));
     assert!(ensure_within_tolerance(irf[62], 0.09054417121965984, 1e-12));
+
+    // check invalid parameters
+    assert!(instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, 0.0).is_err());
+    assert!(instrument::gaussian_irf_1d(1, PERIOD, IRF_CENTER, IRF_WIDTH).is_err());
+    assert!(instrument::gaussian_irf_1d(SAMPLES, -1.0, IRF_CENTER, IRF_WIDTH).is_err());
+}
+
+#[test]
+fn instrument_double_peak_irf_1d() {
+    // simulate a double peak IRF with a 20% reflection 2 ns after the main peak
+    let irf =
+        instrument::double_peak_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH, 2.0, 0.2).unwrap();
+
+    // check the curve is normalized and both peaks are present
+    let dt = PERIOD / (SAMPLES - 1) as f64;
+    let main_idx = (IRF_CENTER / dt).round() as usize;
+    let refl_idx = ((IRF_CENTER + 2.0) / dt).round() as usize;
+    assert_eq!(irf.len(), SAMPLES);
+    assert!(ensure_within_tolerance(sum(&irf), 1.0, 1e-12));
+    assert!(irf[main_idx] > irf[refl_idx]);
+    assert!(irf[refl_idx] > irf[(main_idx + refl_idx) / 2]);
+    assert!(ensure_within_tolerance(
+        irf[refl_idx] / irf[main_idx],
+        0.2,
+        1e-2
+    ));
+
+    // check invalid parameters
+    assert!(
+        instrument::double_peak_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH, 2.0, -0.2).is_err()
+    );
+}
+
+#[test]
+fn instrument_exponentially_modified_gaussian_irf_1d() {
+    // simulate an EMG IRF and a Gaussian IRF with the same Gaussian component
+    let irf = instrument::exponentially_modified_gaussian_irf_1d(
+        SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH, 0.3,
+    )
+    .unwrap();
+    let g_irf = instrument::gaussian_irf_1d(SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
+
+    // find the peak positions of both IRFs
+    let argmax = |v: &[f64]| {
+        v.iter()
+            .enumerate()
+            .fold(
+                (0, f64::MIN),
+                |acc, (i, &x)| if x > acc.1 { (i, x) } else { acc },
+            )
+            .0
+    };
+
+    // check the curve is normalized and the tail shifts the peak later
+    assert_eq!(irf.len(), SAMPLES);
+    assert!(ensure_within_tolerance(sum(&irf), 1.0, 1e-12));
+    assert!(argmax(&irf) > argmax(&g_irf));
+    assert!(irf[argmax(&g_irf) + 20] > g_irf[argmax(&g_irf) + 20]);
+
+    // check invalid parameters
+    assert!(
+        instrument::exponentially_modified_gaussian_irf_1d(
+            SAMPLES, PERIOD, IRF_CENTER, IRF_WIDTH, -0.3,
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn instrument_measured_irf_1d() {
+    // resample a coarse "measured" IRF onto the simulation bins
+    let measured = instrument::gaussian_irf_1d(64, PERIOD, IRF_CENTER, IRF_WIDTH).unwrap();
+    let irf = instrument::measured_irf_1d(&measured, SAMPLES).unwrap();
+
+    // check the curve is normalized and the peak is preserved
+    let dt = PERIOD / (SAMPLES - 1) as f64;
+    let peak = irf
+        .iter()
+        .enumerate()
+        .fold(
+            (0, f64::MIN),
+            |acc, (i, &x)| if x > acc.1 { (i, x) } else { acc },
+        )
+        .0;
+    assert_eq!(irf.len(), SAMPLES);
+    assert!(ensure_within_tolerance(sum(&irf), 1.0, 1e-12));
+    assert!(ensure_within_tolerance(peak as f64 * dt, IRF_CENTER, 0.2));
+    assert!(instrument::measured_irf_1d(&[1.0], SAMPLES).is_err());
+    assert!(instrument::measured_irf_1d(&[0.0, -1.0, 0.0], SAMPLES).is_err());
 }
 
 // test the simulation::noise module
+#[test]
+fn instrument_born_wolf_psf_2d() {
+    // sample the Airy pattern so its first zero lands 3 pixels from the center
+    let first_zero = 3.8317059702075125 * 0.5 / (2.0 * std::f64::consts::PI * 1.0);
+    let psf = instrument::born_wolf_psf_2d((15, 15), first_zero / 3.0, 0.5, 1.0).unwrap();
+
+    // check normalization, peak position and the first zero
+    assert!(ensure_within_tolerance(psf.sum(), 1.0, 1e-12));
+    assert_eq!(psf[[7, 7]], psf.iter().cloned().fold(0.0, f64::max));
+    assert!(psf[[7, 10]] / psf[[7, 7]] < 1e-6);
+    assert!(ensure_within_tolerance(psf[[7, 4]], psf[[10, 7]], 1e-15));
+}
+
+#[test]
+fn instrument_born_wolf_psf_3d() {
+    // the first axial zero of the paraxial PSF is at z = 2 × λ × n / NA²
+    let psf = instrument::born_wolf_psf_3d((9, 15, 15), 0.05, 0.25, 0.5, 1.0, 1.0).unwrap();
+    assert_eq!(psf.shape(), [9, 15, 15]);
+    assert!(ensure_within_tolerance(psf.sum(), 1.0, 1e-12));
+    assert!(psf[[8, 7, 7]] / psf[[4, 7, 7]] < 1e-6);
+    assert!(ensure_within_tolerance(
+        psf[[1, 7, 9]],
+        psf[[7, 7, 9]],
+        1e-12
+    ));
+
+    // the focal plane matches the Airy pattern, within the radial profile
+    // interpolation error
+    let airy = instrument::born_wolf_psf_2d((15, 15), 0.05, 0.5, 1.0).unwrap();
+    let focal = psf.slice(s![4, .., ..]);
+    airy.iter().zip(focal.iter()).for_each(|(a, f)| {
+        assert!(ensure_within_tolerance(
+            a / airy[[7, 7]],
+            f / psf[[4, 7, 7]],
+            1e-3
+        ))
+    });
+
+    // check invalid parameters
+    assert!(instrument::born_wolf_psf_3d((9, 15, 15), 0.05, 0.25, 0.5, 1.4, 1.33).is_err());
+}
+
+#[test]
+fn instrument_gaussian_psf_2d() {
+    // sigma = 0.21 × 0.5 / 1.05 = 0.1, one pixel
+    let psf = instrument::gaussian_psf_2d((11, 11), 0.1, 0.5, 1.05).unwrap();
+    assert!(ensure_within_tolerance(psf.sum(), 1.0, 1e-12));
+    assert!(ensure_within_tolerance(
+        psf[[5, 6]] / psf[[5, 5]],
+        (-0.5_f64).exp(),
+        1e-12
+    ));
+
+    // check invalid parameters
+    assert!(instrument::gaussian_psf_2d((11, 11), 0.1, 0.5, 0.0).is_err());
+}
+
+#[test]
+fn instrument_gaussian_psf_3d() {
+    // sigma_z = 0.66 × 0.5 × 1.0 / 1.0² = 0.33
+    let psf = instrument::gaussian_psf_3d((11, 11, 11), 0.1, 0.33, 0.5, 1.0, 1.0).unwrap();
+    assert_eq!(psf.shape(), [11, 11, 11]);
+    assert!(ensure_within_tolerance(psf.sum(), 1.0, 1e-12));
+    assert!(ensure_within_tolerance(
+        psf[[6, 5, 5]] / psf[[5, 5, 5]],
+        (-0.5_f64).exp(),
+        1e-12
+    ));
+}
+
+#[test]
+fn instrument_gibson_lanni_psf_3d() {
+    // matched refractive indices at the coverslip are axially symmetric
+    let matched = instrument::GibsonLanni {
+        sample_ri: 1.515,
+        ..Default::default()
+    };
+    let psf = instrument::gibson_lanni_psf_3d((17, 15, 15), 0.05, 0.1, 0.5, 1.4, matched).unwrap();
+    assert!(ensure_within_tolerance(psf.sum(), 1.0, 1e-12));
+    assert!(ensure_within_tolerance(
+        psf[[3, 7, 7]],
+        psf[[13, 7, 7]],
+        1e-12
+    ));
+
+    // imaging deep into an aqueous sample shifts and broadens the axial peak
+    let deep = instrument::GibsonLanni {
+        particle_depth: 2.0,
+        ..Default::default()
+    };
+    let psf = instrument::gibson_lanni_psf_3d((17, 15, 15), 0.05, 0.1, 0.5, 1.2, deep).unwrap();
+    let axial: Vec<f64> = (0..17).map(|p| psf[[p, 7, 7]]).collect();
+    let peak = axial
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .unwrap()
+        .0;
+    assert_ne!(peak, 8);
+
+    // check the NA can not exceed the sample refractive index
+    assert!(instrument::gibson_lanni_psf_3d((17, 15, 15), 0.05, 0.1, 0.5, 1.4, deep).is_err());
+}
+
 #[test]
 fn noise_poisson_1d() {
     // create test data
@@ -166,11 +845,11 @@ fn noise_poisson_1d() {
     let seed = Some(42);
 
     // apply noise and test if deterministic with seed
-    let result_a = noise::poisson_1d(&data, scale, seed);
-    let result_b = noise::poisson_1d(&data, scale, seed);
+    let result_a = noise::poisson_1d(&data, scale, seed).unwrap();
+    let result_b = noise::poisson_1d(&data, scale, seed).unwrap();
 
     // apply noise and test if not equal with different seed
-    let result_c = noise::poisson_1d(&data, scale, Some(30));
+    let result_c = noise::poisson_1d(&data, scale, Some(30)).unwrap();
 
     assert_eq!(result_a, result_b);
     assert_ne!(data, result_a);
@@ -187,10 +866,47 @@ fn noise_poisson_1d_mut() {
     let seed = Some(42);
 
     // mutate decay data with noise
-    noise::poisson_1d_mut(&mut data_a, scale, seed);
+    noise::poisson_1d_mut(&mut data_a, scale, seed).unwrap();
 
     assert_ne!(data_a, data_b);
     assert!(data_a.iter().all(|&x| x >= 0.0));
+
+    // a scaled value above the largest Poisson lambda is an error
+    assert!(noise::poisson_1d_mut(&mut [1e20], 1.0, seed).is_err());
+}
+
+#[test]
+fn noise_poisson() {
+    // simulate a 4-dimensional u16 acquisition
+    let i = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE)
+        .unwrap();
+    let i_f64 = i.broadcast((3, 10, 10, SAMPLES)).unwrap().to_owned();
+    let i_u16 = i_f64.mapv(|v| v.round() as u16).into_dyn();
+    let seed = Some(42);
+
+    // the output type matches the input type and is deterministic with seed
+    let result_a: ndarray::ArrayD<u16> = noise::poisson(i_u16.view(), 1.0, seed, None).unwrap();
+    let result_b = noise::poisson(i_u16.view(), 1.0, seed, None).unwrap();
+    let mut result_c = i_u16.clone();
+    noise::poisson_mut(result_c.view_mut(), 1.0, seed, None).unwrap();
+
+    assert_eq!(result_a.shape(), [3, 10, 10, 256]);
+    assert_eq!(result_a, result_b);
+    assert_eq!(result_a, result_c);
+    assert_ne!(result_a, i_u16);
+
+    // the f64 output matches the 3-dimensional function
+    let result_d = noise::poisson(i.view().into_dyn(), 0.5, seed, Some(2)).unwrap();
+    let result_e = noise::poisson_3d(i.view(), 0.5, seed, None).unwrap();
+    assert_eq!(result_d, result_e.into_dyn());
+
+    // integer outputs saturate at the type bounds
+    let bright = ndarray::ArrayD::from_elem(ndarray::IxDyn(&[4, 4]), 250_u8);
+    let result_f = noise::poisson(bright.view(), 10.0, seed, None).unwrap();
+    assert!(result_f.iter().all(|&v| v == u8::MAX));
+
+    assert!(noise::poisson(i_u16.view(), 1.0, seed, Some(4)).is_err());
+    assert!(noise::poisson(i_u16.view(), 0.0, seed, None).is_err());
 }
 
 #[test]
@@ -225,8 +941,303 @@ fn noise_poisson_3d_mut() {
     let seed = Some(42);
 
     // mutate decay data with noise
-    noise::poisson_3d_mut(i_a.view_mut(), scale, seed, None);
+    noise::poisson_3d_mut(i_a.view_mut(), scale, seed, None).unwrap();
 
     assert_ne!(i_a, i_b);
     assert!(i_a.iter().all(|&x| x >= 0.0));
+
+    // check invalid parameters
+    assert!(noise::poisson_3d_mut(i_a.view_mut(), scale, seed, Some(3)).is_err());
+    assert!(noise::poisson_3d_mut(i_a.view_mut(), 0.0, seed, None).is_err());
+
+    // f32 data keeps its element type
+    let mut i_c = i_b.mapv(|v| v as f32);
+    noise::poisson_3d_mut(i_c.view_mut(), scale, seed, None).unwrap();
+    assert!(i_c.iter().all(|&x| x >= 0.0 && x.fract() == 0.0));
+}
+
+#[test]
+fn spectra_gaussian_spectrum_1d() {
+    // simulate a 32 channel spectrum from 500 to 655 nm, 5 nm per channel
+    let sp = spectra::gaussian_spectrum_1d(32, (500.0, 655.0), 550.0, 40.0).unwrap();
+
+    // check the spectrum is normalized and peaks at 550 nm
+    assert_eq!(sp.len(), 32);
+    assert!(ensure_within_tolerance(sum(&sp), 1.0, 1e-12));
+    let peak = sp
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .unwrap()
+        .0;
+    assert_eq!(peak, 10);
+
+    // check invalid parameters
+    assert!(spectra::gaussian_spectrum_1d(32, (500.0, 655.0), 550.0, 0.0).is_err());
+    assert!(spectra::gaussian_spectrum_1d(32, (655.0, 500.0), 550.0, 40.0).is_err());
+}
+
+#[test]
+fn spectra_registered_spectrum_1d() {
+    // resample a triangular spectrum measured every 10 nm onto 5 nm channels
+    let wl = [500.0, 510.0, 520.0, 530.0, 540.0];
+    let data = [0.0, 1.0, 2.0, 1.0, 0.0];
+    let sp = spectra::registered_spectrum_1d(&data, &wl, 11, (490.0, 540.0)).unwrap();
+
+    // check interpolated values, 490 and 495 nm are outside the measured range
+    let expected = [0.0, 0.0, 0.0, 0.5, 1.0, 1.5, 2.0, 1.5, 1.0, 0.5, 0.0];
+    sp.iter()
+        .zip(expected.iter())
+        .for_each(|(s, e)| assert!(ensure_within_tolerance(*s, e / 8.0, 1e-12)));
+
+    // check a spectrum without signal in range and mismatched lengths
+    assert!(spectra::registered_spectrum_1d(&data, &wl, 11, (600.0, 650.0)).is_err());
+    assert!(spectra::registered_spectrum_1d(&data[..4], &wl, 11, (490.0, 540.0)).is_err());
+}
+
+#[test]
+fn spectra_linear_mixture_3d() {
+    // simulate two component abundance maps and spectra
+    let ab = spectra::gradient_abundances_3d(SHAPE, 2);
+    let sp_a = spectra::gaussian_spectrum_1d(32, (500.0, 655.0), 530.0, 30.0).unwrap();
+    let sp_b = spectra::gaussian_spectrum_1d(32, (500.0, 655.0), 610.0, 30.0).unwrap();
+    let cube =
+        spectra::linear_mixture_3d(&[sp_a.clone(), sp_b.clone()], ab.view(), 1000.0).unwrap();
+
+    // check abundances sum to 1.0 and ramp across the columns
+    assert_eq!(ab.shape(), [10, 10, 2]);
+    ab.lanes(Axis(2))
+        .into_iter()
+        .for_each(|ln| assert!(ensure_within_tolerance(ln.sum(), 1.0, 1e-12)));
+    assert_eq!(ab[[3, 0, 0]], 1.0);
+    assert_eq!(ab[[3, 9, 1]], 1.0);
+
+    // check pure and mixed pixel spectra
+    assert_eq!(cube.shape(), [10, 10, 32]);
+    cube.slice(s![0, 0, ..])
+        .iter()
+        .zip(sp_a.iter())
+        .for_each(|(c, a)| assert!(ensure_within_tolerance(*c, 1000.0 * a, 1e-9)));
+    cube.slice(s![0, 9, ..])
+        .iter()
+        .zip(sp_b.iter())
+        .for_each(|(c, b)| assert!(ensure_within_tolerance(*c, 1000.0 * b, 1e-9)));
+    assert!(ensure_within_tolerance(
+        cube.slice(s![5, 3, ..]).sum(),
+        1000.0,
+        1e-9
+    ));
+
+    // check mismatched spectra and abundance maps
+    assert!(spectra::linear_mixture_3d(std::slice::from_ref(&sp_a), ab.view(), 1000.0).is_err());
+    assert!(spectra::linear_mixture_3d(&[sp_a, sp_b[..16].to_vec()], ab.view(), 1000.0).is_err());
+}
+
+#[test]
+fn spectra_linear_mixture_poisson_3d() {
+    // simulate a noisy three component hyperspectral image
+    let ab = spectra::gradient_abundances_3d(SHAPE, 3);
+    let sp: Vec<Vec<f64>> = [520.0, 570.0, 620.0]
+        .iter()
+        .map(|&p| spectra::gaussian_spectrum_1d(32, (500.0, 655.0), p, 30.0).unwrap())
+        .collect();
+    let a = spectra::linear_mixture_poisson_3d(&sp, ab.view(), 5000.0, Some(42)).unwrap();
+    let b = spectra::linear_mixture_poisson_3d(&sp, ab.view(), 5000.0, Some(42)).unwrap();
+
+    // check noise is reproducible, integer valued and preserves counts
+    assert_eq!(a, b);
+    assert!(a.iter().all(|v| v.fract() == 0.0 && *v >= 0.0));
+    assert!(ensure_within_tolerance(a.sum() / 100.0, 5000.0, 50.0));
+}
+
+#[test]
+fn timelapse_photobleaching_4d() {
+    // simulate a bleaching time-lapse of a decay image
+    let data = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE)
+        .unwrap();
+    let tl = timelapse::photobleaching_4d(data.view(), 5, 0.1, None).unwrap();
+
+    // check the shape and the total counts of each frame
+    assert_eq!(tl.shape(), [5, 10, 10, SAMPLES]);
+    tl.axis_iter(Axis(0)).enumerate().for_each(|(t, frame)| {
+        let expected = TOTAL_COUNTS * (-0.1 * t as f64).exp();
+        assert!(ensure_within_tolerance(
+            frame.slice(s![3, 4, ..]).sum(),
+            expected,
+            1e-9
+        ));
+    });
+
+    // check invalid parameters
+    assert!(timelapse::photobleaching_4d(data.view(), 0, 0.1, None).is_err());
+    assert!(timelapse::photobleaching_4d(data.view(), 5, -0.1, None).is_err());
+}
+
+#[test]
+fn timelapse_timelapse_4d() {
+    // create a (tau, row, col) image with a single bright pixel
+    let mut data = Array3::<f64>::zeros((4, SHAPE.0, SHAPE.1));
+    data.slice_mut(s![.., 2, 2]).fill(10.0);
+
+    // integer drift moves the pixel by the drift per frame
+    let tl = timelapse::timelapse_4d(data.view(), 3, 0.0, Some((1.0, 2.0)), None, Some(0)).unwrap();
+    assert_eq!(tl.shape(), [3, 10, 10, 4]);
+    assert_eq!(tl[[0, 2, 2, 0]], 10.0);
+    assert_eq!(tl[[2, 4, 6, 3]], 10.0);
+    assert_eq!(tl.slice(s![2, .., .., 0]).sum(), 10.0);
+
+    // subpixel drift splits the pixel between its neighbors
+    let tl = timelapse::timelapse_4d(data.view(), 2, 0.0, Some((0.0, 0.5)), None, Some(0)).unwrap();
+    assert!(ensure_within_tolerance(tl[[1, 2, 2, 0]], 5.0, 1e-12));
+    assert!(ensure_within_tolerance(tl[[1, 2, 3, 0]], 5.0, 1e-12));
+
+    // motion blur smears the pixel along the drift path of each frame
+    let tl =
+        timelapse::timelapse_4d(data.view(), 2, 0.0, Some((0.0, 2.0)), Some(2), Some(0)).unwrap();
+    assert!(ensure_within_tolerance(tl[[0, 2, 2, 1]], 5.0, 1e-12));
+    assert!(ensure_within_tolerance(tl[[0, 2, 3, 1]], 5.0, 1e-12));
+    assert!(ensure_within_tolerance(tl[[1, 2, 4, 1]], 5.0, 1e-12));
+    assert!(ensure_within_tolerance(tl[[1, 2, 5, 1]], 5.0, 1e-12));
+
+    // pixels drifting out of the image are lost
+    let tl =
+        timelapse::timelapse_4d(data.view(), 2, 0.0, Some((-5.0, 0.0)), None, Some(0)).unwrap();
+    assert_eq!(tl.slice(s![1, .., .., ..]).sum(), 0.0);
+
+    // check invalid parameters
+    assert!(
+        timelapse::timelapse_4d(data.view(), 2, 0.0, Some((f64::NAN, 0.0)), None, None).is_err()
+    );
+    assert!(timelapse::timelapse_4d(data.view(), 2, 0.0, None, Some(0), None).is_err());
+}
+
+#[test]
+fn pattern_checkerboard_2d() {
+    let board = pattern::checkerboard_2d((6, 8), 2).unwrap();
+
+    assert_eq!(board[[0, 0]], 0.0);
+    assert_eq!(board[[1, 1]], 0.0);
+    assert_eq!(board[[0, 2]], 1.0);
+    assert_eq!(board[[2, 0]], 1.0);
+    assert_eq!(board[[2, 2]], 0.0);
+    assert_eq!(board.sum(), 24.0);
+    assert!(pattern::checkerboard_2d((6, 8), 0).is_err());
+}
+
+#[test]
+fn pattern_sinusoidal_grating_2d() {
+    let grating = pattern::sinusoidal_grating_2d((4, 20), 0.1, None, None).unwrap();
+    let rotated =
+        pattern::sinusoidal_grating_2d((20, 4), 0.1, Some(std::f64::consts::FRAC_PI_2), None)
+            .unwrap();
+
+    // vertical stripes with a period of 10 pixels
+    assert!(ensure_within_tolerance(grating[[0, 0]], 1.0, 1e-12));
+    assert!(ensure_within_tolerance(grating[[3, 5]], 0.0, 1e-12));
+    assert!(ensure_within_tolerance(grating[[2, 10]], 1.0, 1e-12));
+    assert!(ensure_within_tolerance(rotated[[5, 0]], 0.0, 1e-12));
+    assert!(ensure_within_tolerance(rotated[[5, 3]], 0.0, 1e-12));
+    assert!(pattern::sinusoidal_grating_2d((4, 20), 0.6, None, None).is_err());
+}
+
+#[test]
+fn pattern_power_law_noise_2d() {
+    // lag-1 autocorrelation along the cols
+    let lag_corr = |arr: &ndarray::Array2<f64>| {
+        let (rows, cols) = arr.dim();
+        let mut acc = 0.0;
+        for r in 0..rows {
+            for c in 0..cols - 1 {
+                acc += arr[[r, c]] * arr[[r, c + 1]];
+            }
+        }
+        acc / (rows * (cols - 1)) as f64
+    };
+    let white = pattern::power_law_noise_2d((64, 64), 0.0, Some(7)).unwrap();
+    let brown = pattern::power_law_noise_2d((64, 64), 2.0, Some(7)).unwrap();
+    let n = white.len() as f64;
+
+    assert!(ensure_within_tolerance(white.sum() / n, 0.0, 1e-9));
+    assert!(ensure_within_tolerance(
+        white.iter().map(|v| v * v).sum::<f64>() / n,
+        1.0,
+        1e-9
+    ));
+    assert!(lag_corr(&white).abs() < 0.1);
+    assert!(lag_corr(&brown) > 0.5);
+    assert_eq!(
+        white,
+        pattern::power_law_noise_2d((64, 64), 0.0, Some(7)).unwrap()
+    );
+    assert!(pattern::power_law_noise_2d((64, 64), -1.0, None).is_err());
+    assert!(pattern::noise_field_2d((8, 8), |_| -1.0, None).is_err());
+}
+
+#[test]
+fn pattern_blobs_2d() {
+    let empty = pattern::blobs_2d((32, 32), 0.0, 2.0, None).unwrap();
+    let single = pattern::blobs_2d((64, 64), 1.0 / 4096.0, 2.0, Some(3)).unwrap();
+    let max = single.iter().cloned().fold(f64::MIN, f64::max);
+
+    // a single blob integrates to ~2πσ² when it is inside of the image
+    assert_eq!(empty.sum(), 0.0);
+    assert!(max > 0.8 && max <= 1.0);
+    assert!(single.sum() <= 2.0 * std::f64::consts::PI * 4.0 + 1e-6);
+    assert!(pattern::blobs_2d((32, 32), 0.01, 0.0, None).is_err());
+    assert!(pattern::blobs_2d((32, 32), -0.01, 2.0, None).is_err());
+}
+
+#[test]
+fn rng_reproducible_pipeline() {
+    let curve = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, 1.0).unwrap();
+
+    // run a multi-step simulation with an Rng context
+    let pipeline = |seed: u64| {
+        let mut rng = Rng::new(seed);
+        let photons = decay::sample_photons_3d(&curve, 500, (6, 6), &mut rng).unwrap();
+        let noisy = noise::poisson_3d(photons.view(), 1.0, &mut rng, None).unwrap();
+        let field = pattern::power_law_noise_2d((8, 8), 1.0, &mut rng).unwrap();
+        assert_eq!(rng.streams(), 3);
+        (photons, noisy, field)
+    };
+    let (photons_a, noisy_a, field_a) = pipeline(7);
+    let (photons_b, noisy_b, field_b) = pipeline(7);
+    let (photons_c, _, _) = pipeline(8);
+
+    // the same seed reproduces every step, a new seed does not
+    assert_eq!(photons_a, photons_b);
+    assert_eq!(noisy_a, noisy_b);
+    assert_eq!(field_a, field_b);
+    assert_ne!(photons_a, photons_c);
+    assert_ne!(photons_a, noisy_a);
+
+    // each pixel of a stream gets its own generator
+    assert_ne!(photons_a.slice(s![0, 0, ..]), photons_a.slice(s![0, 1, ..]));
+    photons_a
+        .lanes(Axis(2))
+        .into_iter()
+        .for_each(|ln| assert_eq!(ln.sum(), 500.0));
+}
+
+#[test]
+fn rng_streams() {
+    let mut rng = Rng::new(42);
+    let a = rng.stream();
+    let b = rng.stream();
+
+    // streams are deterministic, independent and lanes differ
+    assert_eq!(a, Rng::new(42).stream());
+    assert_ne!(a, b);
+    assert_eq!(a.lane(3), a.lane(3));
+    assert_ne!(a.lane(3), a.lane(4));
+    assert_ne!(a.lane(0), b.lane(0));
+
+    // lane results do not depend on the number of lanes sampled
+    let i = decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, SHAPE)
+        .unwrap();
+    let small = i.slice(s![..2, ..2, ..]).to_owned();
+    let full = noise::poisson_3d(i.view(), 1.0, &mut Rng::new(1), None).unwrap();
+    let part = noise::poisson_3d(small.view(), 1.0, &mut Rng::new(1), None).unwrap();
+    assert_eq!(full.slice(s![0, 0, ..]), part.slice(s![0, 0, ..]));
+    assert_eq!(full.slice(s![0, 1, ..]), part.slice(s![0, 1, ..]));
 }
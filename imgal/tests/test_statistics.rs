@@ -1,4 +1,10 @@
-use imgal::statistics;
+use std::f64::consts::PI;
+
+use ndarray::{Array, Array2};
+
+use imgal::filter;
+use imgal::simulation::{Rng, noise};
+use imgal::statistics::{self, Alternative, Binning, FrcCriterion};
 
 #[test]
 fn statistics_sum() {
@@ -25,3 +31,483 @@ fn statistics_weighted_merge_sort_mut() {
     assert_eq!(w, [0.51, 0.32, 12.83, 9.25, 4.24]);
     assert_eq!(s, 47.64239999999998);
 }
+
+#[test]
+fn statistics_rank() {
+    // rank data with ties, with and without weights
+    let data = [30, 10, 20, 20];
+    let r = statistics::rank(&data, None).unwrap();
+    let w = statistics::rank(&data, Some(&[1.0, 2.0, 1.0, 1.0])).unwrap();
+
+    // check tied elements share the average rank
+    assert_eq!(r, vec![4.0, 1.0, 2.5, 2.5]);
+    assert_eq!(w, vec![5.0, 1.5, 3.5, 3.5]);
+    assert!(statistics::rank(&data, Some(&[1.0, -1.0, 1.0, 1.0])).is_err());
+}
+
+#[test]
+fn statistics_spearman() {
+    // create monotonic, reversed and tied data
+    let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let b = [1.0, 4.0, 9.0, 16.0, 100.0];
+    let c = [5.0, 4.0, 3.0, 2.0, 1.0];
+    let d = [1.0, 2.0, 2.0, 3.0, 5.0];
+
+    // check against known values
+    assert_eq!(statistics::spearman(&a, &b, None).unwrap(), 1.0);
+    assert_eq!(statistics::spearman(&a, &c, None).unwrap(), -1.0);
+    assert!((statistics::spearman(&a, &d, None).unwrap() - 0.9746794344808963).abs() < 1e-12);
+    assert_eq!(statistics::spearman(&a, &[2.0; 5], None).unwrap(), 0.0);
+
+    // check unit weights match the unweighted coefficient
+    let w = statistics::spearman(&a, &d, Some(&[1.0; 5])).unwrap();
+    assert!((w - statistics::spearman(&a, &d, None).unwrap()).abs() < 1e-12);
+    assert!(statistics::spearman(&a, &d[..4], None).is_err());
+}
+
+#[test]
+fn statistics_histogram() {
+    // create data with a masked outlier
+    let data = Array::from_shape_vec(vec![2, 4], vec![0.0, 1.0, 1.0, 2.0, 3.0, 3.0, 4.0, 50.0])
+        .unwrap()
+        .into_dyn();
+    let mask = data.mapv(|v| v < 10.0);
+
+    // compute fixed and automatic histograms
+    let fixed =
+        statistics::histogram(data.view(), Some(Binning::Fixed(4)), Some(mask.view())).unwrap();
+    let sturges = statistics::histogram(data.view(), Some(Binning::Sturges), None).unwrap();
+    let fd = statistics::histogram(data.view(), Some(Binning::FreedmanDiaconis), None).unwrap();
+
+    // check bin counts and edges
+    assert_eq!(fixed.counts, vec![1.0, 2.0, 1.0, 3.0]);
+    assert_eq!(fixed.edges, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(sturges.counts.len(), 4);
+    assert_eq!(fd.counts.len(), 23);
+    assert_eq!(fd.counts.iter().sum::<f64>(), 8.0);
+    assert!(statistics::histogram(data.view(), Some(Binning::Fixed(0)), None).is_err());
+}
+
+#[test]
+fn statistics_weighted_histogram() {
+    // create data and weights
+    let data = Array::from_vec(vec![0.0, 1.0, 2.0, 3.0]).into_dyn();
+    let weights = Array::from_vec(vec![0.5, 2.0, 1.0, 0.25]).into_dyn();
+
+    // compute the weighted histogram
+    let hist =
+        statistics::weighted_histogram(data.view(), weights.view(), Some(Binning::Fixed(2)), None)
+            .unwrap();
+
+    // check bin counts hold the summed weights
+    assert_eq!(hist.counts, vec![2.5, 1.25]);
+    assert_eq!(hist.edges, vec![0.0, 1.5, 3.0]);
+    let negative = Array::from_vec(vec![0.5, -2.0, 1.0, 0.25]).into_dyn();
+    assert!(statistics::weighted_histogram(data.view(), negative.view(), None, None).is_err());
+}
+
+#[test]
+fn statistics_quantile() {
+    // create data with a masked outlier
+    let data = Array::from_vec(vec![4.0, 1.0, 3.0, 2.0, 100.0]).into_dyn();
+    let mask = data.mapv(|v| v < 10.0);
+
+    // check interpolated quantiles
+    assert_eq!(statistics::quantile(data.view(), 0.5, None).unwrap(), 3.0);
+    assert_eq!(
+        statistics::quantile(data.view(), 0.5, Some(mask.view())).unwrap(),
+        2.5
+    );
+    assert_eq!(
+        statistics::quantile(data.view(), 1.0, Some(mask.view())).unwrap(),
+        4.0
+    );
+    assert!(statistics::quantile(data.view(), 1.5, None).is_err());
+}
+
+#[test]
+fn statistics_weighted_quantile() {
+    // create data with equal and unequal weights
+    let data = Array::from_vec(vec![4.0, 1.0, 3.0, 2.0]).into_dyn();
+    let equal = Array::from_elem(4, 2.0).into_dyn();
+    let unequal = Array::from_vec(vec![1.0, 1.0, 0.0, 2.0]).into_dyn();
+
+    // check equal weights match the unweighted quantile
+    for q in [0.0, 0.1, 0.5, 0.9, 1.0] {
+        let w = statistics::weighted_quantile(data.view(), equal.view(), q, None).unwrap();
+        let u = statistics::quantile(data.view(), q, None).unwrap();
+        assert!((w - u).abs() < 1e-12);
+    }
+
+    // check zero weights are ignored, centers are at 0.5, 2.0 and 3.5
+    let median = statistics::weighted_quantile(data.view(), unequal.view(), 0.5, None).unwrap();
+    assert_eq!(median, 2.0);
+    let q = statistics::weighted_quantile(data.view(), unequal.view(), 0.75, None).unwrap();
+    assert_eq!(q, 3.0);
+}
+
+#[test]
+fn statistics_circular_mean() {
+    // angles around ±π wrap to π, not to the arithmetic mean of 0.0
+    let data = Array::from_vec(vec![PI - 0.1, -PI + 0.1, PI - 0.2, -PI + 0.2]).into_dyn();
+    let m = statistics::circular_mean(data.view(), None).unwrap();
+    assert!((m.abs() - PI).abs() < 1e-12);
+
+    // check the mask and non-finite angles are ignored
+    let data = Array::from_vec(vec![0.5, 0.7, f64::NAN, 3.0]).into_dyn();
+    let mask = Array::from_vec(vec![true, true, true, false]).into_dyn();
+    let m = statistics::circular_mean(data.view(), Some(mask.view())).unwrap();
+    assert!((m - 0.6).abs() < 1e-12);
+
+    // check uniformly spread and invalid angles
+    let data = Array::from_vec(vec![0.0, PI / 2.0, PI, 3.0 * PI / 2.0]).into_dyn();
+    assert!(
+        statistics::circular_mean(data.view(), None)
+            .unwrap()
+            .is_nan()
+    );
+    let data = Array::from_vec(vec![f64::NAN]).into_dyn();
+    assert!(statistics::circular_mean(data.view(), None).is_err());
+}
+
+#[test]
+fn statistics_circular_variance() {
+    // equal angles, opposite angles and a known spread
+    let data = Array::from_elem(vec![2, 3], 1.2).into_dyn();
+    assert!(statistics::circular_variance(data.view(), None).unwrap() < 1e-12);
+    let data = Array::from_vec(vec![0.5, 0.5 + PI]).into_dyn();
+    assert!((statistics::circular_variance(data.view(), None).unwrap() - 1.0).abs() < 1e-12);
+    let data = Array::from_vec(vec![-PI / 3.0, PI / 3.0]).into_dyn();
+    assert!((statistics::circular_variance(data.view(), None).unwrap() - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn statistics_rayleigh_test() {
+    // concentrated angles reject uniformity
+    let data = Array::from_elem(vec![10], 2.0).into_dyn();
+    let (z, p) = statistics::rayleigh_test(data.view(), None).unwrap();
+    assert!((z - 10.0).abs() < 1e-12);
+    assert!((p - (41.0_f64.sqrt() - 21.0).exp()).abs() < 1e-15);
+
+    // evenly spread angles do not
+    let data = Array::from_shape_fn(vec![12], |i| i[0] as f64 * PI / 6.0);
+    let (z, p) = statistics::rayleigh_test(data.view(), None).unwrap();
+    assert!(z < 1e-12);
+    assert!((p - 1.0).abs() < 1e-9);
+
+    // check invalid parameters
+    let mask = Array::from_elem(vec![11], true).into_dyn();
+    assert!(statistics::rayleigh_test(data.view(), Some(mask.view())).is_err());
+}
+
+#[test]
+fn statistics_describe() {
+    // create skewed data with a masked outlier
+    let data = Array::from_shape_vec(vec![2, 4], vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 900.0])
+        .unwrap()
+        .into_dyn();
+    let mask = data.mapv(|v| v < 100.0);
+
+    // describe the masked values
+    let d = statistics::describe(data.view(), Some(mask.view())).unwrap();
+
+    // check against known values
+    assert_eq!(d.count, 7);
+    assert!((d.mean - 31.0 / 7.0).abs() < 1e-12);
+    assert!((d.variance - 2.285714285714285).abs() < 1e-12);
+    assert!((d.skewness - 0.14671422938545015).abs() < 1e-12);
+    assert!((d.kurtosis - -0.06640624999999911).abs() < 1e-12);
+    assert_eq!((d.min, d.max, d.median), (2.0, 7.0, 4.0));
+
+    // check the median of an even count and a constant image
+    let d = statistics::describe(data.view(), None).unwrap();
+    assert_eq!(d.median, 4.5);
+    let d = statistics::describe(Array::from_elem(vec![3, 3], 2u8).view(), None).unwrap();
+    assert_eq!((d.variance, d.skewness, d.kurtosis), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn statistics_running_stats() {
+    // accumulate chunks of skewed data separately
+    let data: Vec<f64> = (0..1000).map(|i| ((i * 37) % 101) as f64).collect();
+    let arr = Array::from_shape_vec(vec![10, 100], data.clone()).unwrap();
+    let mut a = statistics::RunningStats::new();
+    a.extend(data[..300].iter().copied());
+    let mut b = statistics::RunningStats::new();
+    b.push_array(arr.slice(ndarray::s![3.., ..]).into_dyn(), None)
+        .unwrap();
+    a.merge(&b);
+
+    // check against a single pass over all values
+    let d = statistics::describe(arr.view().into_dyn(), None).unwrap();
+    assert_eq!(a.count(), 1000);
+    assert!((a.mean() - d.mean).abs() < 1e-10);
+    assert!((a.variance() - d.variance).abs() < 1e-8);
+    assert!((a.skewness() - d.skewness).abs() < 1e-10);
+    assert!((a.kurtosis() - d.kurtosis).abs() < 1e-10);
+    assert_eq!((a.min(), a.max()), (d.min, d.max));
+
+    // check the empty accumulator and merging into it
+    let mut e = statistics::RunningStats::new();
+    assert!(e.mean().is_nan());
+    assert_eq!(e.variance(), 0.0);
+    e.merge(&a);
+    assert_eq!(e, a);
+}
+
+#[test]
+fn statistics_bootstrap() {
+    // create skewed data
+    let data: Vec<f64> = (0..40).map(|i| (i as f64 / 10.0).exp()).collect();
+    let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+
+    // bootstrap the mean
+    let b = statistics::bootstrap(&data, mean, Some(500), None, Some(7)).unwrap();
+    let c = statistics::bootstrap(&data, mean, Some(500), None, Some(7)).unwrap();
+
+    // check the intervals bracket the estimate and are reproducible
+    assert_eq!(b.estimate, mean(&data));
+    assert_eq!(b.replicates.len(), 500);
+    assert!(b.percentile.0 < b.estimate && b.estimate < b.percentile.1);
+    assert!(b.bca.0 < b.estimate && b.estimate < b.bca.1);
+    assert_eq!(b, c);
+
+    // check the BCa interval shifts toward the right skewed tail
+    assert!(b.bca.1 - b.estimate > b.estimate - b.bca.0);
+    assert!(statistics::bootstrap(&data[..1], mean, None, None, None).is_err());
+    assert!(statistics::bootstrap(&data, mean, None, Some(1.0), None).is_err());
+}
+
+#[test]
+fn statistics_paired_bootstrap() {
+    // create monotonic paired data
+    let a: Vec<f64> = (0..30).map(|i| i as f64).collect();
+    let b: Vec<f64> = a.iter().map(|v| v * v).collect();
+
+    // bootstrap the Spearman rank correlation coefficient
+    let spearman = |x: &[f64], y: &[f64]| statistics::spearman(x, y, None).unwrap();
+    let boot = statistics::paired_bootstrap(&a, &b, spearman, Some(200), None, Some(3)).unwrap();
+
+    // check pairs stay together
+    assert_eq!(boot.estimate, 1.0);
+    assert_eq!(boot.percentile, (1.0, 1.0));
+    assert_eq!(boot.bca, (1.0, 1.0));
+    assert!(statistics::paired_bootstrap(&a, &b[..10], spearman, None, None, None).is_err());
+}
+
+#[test]
+fn statistics_permutation_test() {
+    // create two shifted samples
+    let a: Vec<f64> = (0..20).map(|i| (i % 7) as f64 + 3.0).collect();
+    let b: Vec<f64> = (0..20).map(|i| (i % 5) as f64).collect();
+    let mean_diff = |x: &[f64], y: &[f64]| {
+        x.iter().sum::<f64>() / x.len() as f64 - y.iter().sum::<f64>() / y.len() as f64
+    };
+
+    // test the difference of the means
+    let (p, null) =
+        statistics::permutation_test(&a, &b, mean_diff, Some(500), None, Some(11)).unwrap();
+    let (p_less, _) = statistics::permutation_test(
+        &a,
+        &b,
+        mean_diff,
+        Some(500),
+        Some(Alternative::Less),
+        Some(11),
+    )
+    .unwrap();
+
+    // check the shift is significant in the expected direction only
+    assert_eq!(null.len(), 500);
+    assert_eq!(p, 2.0 / 501.0);
+    assert_eq!(p_less, 1.0);
+    assert!(statistics::permutation_test(&a, &[], mean_diff, None, None, None).is_err());
+}
+
+#[test]
+fn statistics_paired_permutation_test() {
+    // create correlated and independent paired data
+    let a: Vec<f64> = (0..50).map(|i| i as f64).collect();
+    let b: Vec<f64> = a.iter().map(|v| v + (v * 7.0) % 5.0).collect();
+    let c: Vec<f64> = (0..50).map(|i| ((i * 37) % 50) as f64).collect();
+    let spearman = |x: &[f64], y: &[f64]| statistics::spearman(x, y, None).unwrap();
+
+    // test the Spearman rank correlation coefficient
+    let (p_b, _) = statistics::paired_permutation_test(
+        &a,
+        &b,
+        spearman,
+        Some(200),
+        Some(Alternative::Greater),
+        Some(5),
+    )
+    .unwrap();
+    let (p_c, _) =
+        statistics::paired_permutation_test(&a, &c, spearman, Some(200), None, Some(5)).unwrap();
+
+    // check only the correlated data is significant
+    assert_eq!(p_b, 1.0 / 201.0);
+    assert!(p_c > 0.05);
+    assert!(statistics::paired_permutation_test(&a, &c[..3], spearman, None, None, None).is_err());
+}
+
+#[test]
+fn statistics_joint_histogram() {
+    // create two images with known intensity pairs
+    let a = Array::from_shape_vec(vec![2, 3], vec![0.0, 0.0, 1.0, 1.0, 1.0, 2.0]).unwrap();
+    let b = Array::from_shape_vec(vec![2, 3], vec![5.0, 5.0, 5.0, 7.0, 7.0, 9.0]).unwrap();
+
+    // count the pairs with 3 bins per image
+    let hist = statistics::joint_histogram(a.view(), b.view(), Some(3), None, None).unwrap();
+
+    // check the co-occurrence counts
+    assert_eq!(hist.shape(), &[3, 3]);
+    assert_eq!(hist[[0, 0]], 2.0);
+    assert_eq!(hist[[1, 0]], 1.0);
+    assert_eq!(hist[[1, 1]], 2.0);
+    assert_eq!(hist[[2, 2]], 1.0);
+    assert_eq!(hist.sum(), 6.0);
+
+    // check smoothing spreads the counts
+    let smooth = statistics::joint_histogram(a.view(), b.view(), Some(3), Some(1.0), None).unwrap();
+    assert!(smooth[[0, 2]] > 0.0);
+    assert!(statistics::joint_histogram(a.view(), b.view(), Some(0), None, None).is_err());
+}
+
+#[test]
+fn statistics_mutual_information() {
+    // create identical and independent images with 4 equally likely levels
+    let a = Array::from_shape_fn(vec![16, 16], |idx| (idx[1] % 4) as u8);
+    let b = Array::from_shape_fn(vec![16, 16], |idx| (idx[0] % 4) as u8);
+
+    // compute the mutual information
+    let same = statistics::mutual_information(a.view(), a.view(), Some(4), None, None).unwrap();
+    let independent =
+        statistics::mutual_information(a.view(), b.view(), Some(4), None, None).unwrap();
+    let smooth =
+        statistics::mutual_information(a.view(), a.view(), Some(4), Some(1.0), None).unwrap();
+
+    // check against known values
+    assert!((same - 4.0_f64.ln()).abs() < 1e-12);
+    assert!(independent.abs() < 1e-12);
+    assert!(smooth > 0.0 && smooth < same);
+}
+
+/// Create a blurred random spot image, the same spots for the same seed.
+fn blurred_spots(seed: u64) -> Array2<f64> {
+    let mut img = Array2::<f64>::zeros((64, 64));
+    let mut state = seed;
+    (0..80).for_each(|_| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let r = (state >> 33) as usize % 64;
+        let c = (state >> 13) as usize % 64;
+        img[[r, c]] += 100.0;
+    });
+    filter::gaussian(img.view().into_dyn(), &[2.0, 2.0], None, None)
+        .unwrap()
+        .into_dimensionality()
+        .unwrap()
+}
+
+#[test]
+fn statistics_frc() {
+    // split a photon count image into two independent halves
+    let mut rng = Rng::new(3);
+    let spots = blurred_spots(3);
+    let counts: Array2<f64> = noise::poisson(spots.view().into_dyn(), 1.0, &mut rng, None)
+        .unwrap()
+        .into_dimensionality()
+        .unwrap();
+    let (a, b) = statistics::split_photons(counts.view(), Some(9)).unwrap();
+    assert_eq!(&a + &b, counts);
+
+    // compute the FRC curve of the halves
+    let curve = statistics::frc(a.view(), b.view()).unwrap();
+    assert_eq!(curve.frequencies.len(), 33);
+    assert_eq!(curve.frequencies[32], 0.5);
+    assert!(curve.correlation[1] > 0.9);
+    assert!(curve.correlation[30].abs() < 0.3);
+
+    // check the resolution is in between the spot blur and the Nyquist limit
+    let res = statistics::frc_resolution(&curve, None, Some(0.1)).unwrap();
+    assert!((0.3..2.0).contains(&res));
+    let sigma = statistics::frc_resolution(&curve, Some(FrcCriterion::Sigma(3.0)), Some(0.1));
+    assert!(sigma.unwrap() > 0.2);
+
+    // identical images never cross the threshold
+    let same = statistics::frc(a.view(), a.view()).unwrap();
+    assert_eq!(statistics::frc_resolution(&same, None, None), None);
+    assert!(statistics::split_photons(Array2::from_elem((2, 2), 1.5).view(), None).is_err());
+}
+
+#[test]
+fn statistics_weighted_mean_variance() {
+    // create data with unit and frequency weights
+    let data = [2.0, 4.0, 4.0, 5.0];
+    let ones = [1.0; 4];
+    let w = [1.0, 2.0, 0.0, 1.0];
+
+    // check unit weights give the sample mean and variance
+    assert_eq!(statistics::weighted_mean(&data, &ones).unwrap(), 3.75);
+    assert_eq!(
+        statistics::weighted_variance(&data, &ones).unwrap(),
+        1.5833333333333333
+    );
+
+    // check against known weighted values
+    assert_eq!(statistics::weighted_mean(&data, &w).unwrap(), 3.75);
+    assert_eq!(statistics::weighted_variance(&data, &w).unwrap(), 1.9);
+    assert_eq!(
+        statistics::weighted_variance(&data[..1], &w[..1]).unwrap(),
+        0.0
+    );
+    assert!(statistics::weighted_mean(&data, &w[..2]).is_err());
+}
+
+#[test]
+fn statistics_weighted_covariance() {
+    // create three variables, the third is the first reversed
+    let data = Array2::from_shape_vec(
+        (3, 4),
+        vec![1.0, 2.0, 3.0, 4.0, 2.0, 1.0, 4.0, 3.0, 4.0, 3.0, 2.0, 1.0],
+    )
+    .unwrap();
+    let w = [1.0, 1.0, 2.0, 1.0];
+
+    // compute the covariance matrix
+    let cov = statistics::weighted_covariance(data.view(), &w).unwrap();
+
+    // check symmetry and agreement with the weighted variance and correlation
+    assert_eq!(cov.shape(), &[3, 3]);
+    assert_eq!(cov[[0, 1]], cov[[1, 0]]);
+    let var = statistics::weighted_variance(&[1.0, 2.0, 3.0, 4.0], &w).unwrap();
+    assert!((cov[[0, 0]] - var).abs() < 1e-12);
+    assert!((cov[[0, 2]] + var).abs() < 1e-12);
+    let r = statistics::weighted_pearson(&[1.0, 2.0, 3.0, 4.0], &[2.0, 1.0, 4.0, 3.0], &w).unwrap();
+    assert!((r - cov[[0, 1]] / (cov[[0, 0]] * cov[[1, 1]]).sqrt()).abs() < 1e-12);
+    assert!(statistics::weighted_covariance(data.view(), &w[..3]).is_err());
+}
+
+#[test]
+fn statistics_weighted_pearson() {
+    // create linear and reversed data
+    let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let b = [3.0, 5.0, 7.0, 9.0, 11.0];
+    let w = [0.5, 1.0, 2.0, 1.0, 0.25];
+
+    // check perfect correlations do not depend on the weights
+    assert!((statistics::weighted_pearson(&a, &b, &w).unwrap() - 1.0).abs() < 1e-12);
+    let reversed: Vec<f64> = a.iter().rev().copied().collect();
+    assert!((statistics::weighted_pearson(&a, &reversed, &[1.0; 5]).unwrap() + 1.0).abs() < 1e-12);
+    assert_eq!(
+        statistics::weighted_pearson(&a, &[1.0; 5], &w).unwrap(),
+        0.0
+    );
+
+    // check a zero weight removes an outlier
+    let c = [1.0, 2.0, 3.0, 4.0, -50.0];
+    let r = statistics::weighted_pearson(&a, &c, &[1.0, 1.0, 1.0, 1.0, 0.0]).unwrap();
+    assert!((r - 1.0).abs() < 1e-12);
+}
@@ -0,0 +1,240 @@
+use ndarray::{Array1, Array2, s};
+
+use imgal::error::ImgalError;
+use imgal::error::warning::{self, Warning};
+use imgal::threshold::{self, ThresholdMethod};
+
+// helper functions
+fn bimodal() -> Array2<u16> {
+    // dim background with a bright square in the center
+    let mut data = Array2::<u16>::from_shape_fn((20, 20), |(r, c)| 10 + ((r + c) % 3) as u16);
+    data.slice_mut(s![5..15, 5..15]).mapv_inplace(|v| v + 90);
+
+    data
+}
+
+#[test]
+fn threshold_otsu() {
+    let data = bimodal();
+    let t = threshold::otsu(data.view().into_dyn(), None, None).unwrap();
+
+    // the threshold separates the two modes
+    assert!((12.0..100.0).contains(&t));
+}
+
+#[test]
+fn threshold_otsu_constant() {
+    let data = Array2::<f64>::from_elem((5, 5), 3.0);
+
+    assert_eq!(
+        threshold::otsu(data.view().into_dyn(), None, None).unwrap(),
+        3.0
+    );
+}
+
+#[test]
+fn threshold_otsu_masked() {
+    // exclude the dim background, the threshold splits the bright square
+    let mut data = bimodal().mapv(|v| v as f64);
+    data.slice_mut(s![5..15, 5..10]).fill(200.0);
+    let mut mask = Array2::<bool>::from_elem((20, 20), false);
+    mask.slice_mut(s![5..15, 5..15]).fill(true);
+    let t = threshold::otsu(data.view().into_dyn(), Some(mask.view().into_dyn()), None).unwrap();
+
+    assert!((102.0..200.0).contains(&t));
+}
+
+#[test]
+fn threshold_otsu_errors() {
+    let data = bimodal();
+
+    assert!(matches!(
+        threshold::otsu(data.view().into_dyn(), None, Some(0)),
+        Err(ImgalError::InvalidParameter {
+            param_name: "bins",
+            ..
+        })
+    ));
+    let mask = Array2::<bool>::from_elem((20, 10), true);
+    assert!(matches!(
+        threshold::otsu(data.view().into_dyn(), Some(mask.view().into_dyn()), None),
+        Err(ImgalError::MismatchedArrayShapes { .. })
+    ));
+    let mask = Array2::<bool>::from_elem((20, 20), false);
+    assert!(matches!(
+        threshold::otsu(data.view().into_dyn(), Some(mask.view().into_dyn()), None),
+        Err(ImgalError::InvalidParameter {
+            param_name: "data",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn threshold_otsu_mask() {
+    let data = bimodal();
+    let (mask, warnings) =
+        warning::collect(|| threshold::otsu_mask(data.view().into_dyn(), None, None).unwrap());
+
+    assert!(warnings.is_empty());
+    assert_eq!(mask.iter().filter(|&&m| m).count(), 100);
+    assert!(mask[[10, 10]]);
+    assert!(!mask[[0, 0]]);
+
+    // masked out pixels are false in the output
+    let mut roi = Array2::<bool>::from_elem((20, 20), true);
+    roi.slice_mut(s![.., 10..]).fill(false);
+    let mask =
+        threshold::otsu_mask(data.view().into_dyn(), Some(roi.view().into_dyn()), None).unwrap();
+
+    assert_eq!(mask.iter().filter(|&&m| m).count(), 50);
+}
+
+#[test]
+fn threshold_otsu_mask_empty() {
+    let data = Array2::<f64>::from_elem((5, 5), 3.0);
+    let (mask, warnings) =
+        warning::collect(|| threshold::otsu_mask(data.view().into_dyn(), None, None).unwrap());
+
+    assert!(mask.iter().all(|&m| !m));
+    assert_eq!(warnings, vec![Warning::EmptyMask]);
+}
+
+#[test]
+fn threshold_multi_otsu() {
+    // background, cytoplasm and nucleus intensity tiers
+    let mut data = Array2::<f64>::from_shape_fn((30, 30), |(r, c)| 10.0 + ((r + c) % 3) as f64);
+    data.slice_mut(s![5..25, 5..25]).mapv_inplace(|v| v + 50.0);
+    data.slice_mut(s![12..18, 12..18])
+        .mapv_inplace(|v| v + 100.0);
+    let (thresholds, labels) = threshold::multi_otsu(data.view().into_dyn(), 3, None).unwrap();
+
+    assert_eq!(thresholds.len(), 2);
+    assert!((12.0..60.0).contains(&thresholds[0]));
+    assert!((62.0..160.0).contains(&thresholds[1]));
+    assert_eq!(labels[[0, 0]], 0);
+    assert_eq!(labels[[8, 8]], 1);
+    assert_eq!(labels[[15, 15]], 2);
+    assert_eq!(labels.iter().filter(|&&l| l == 2).count(), 36);
+}
+
+#[test]
+fn threshold_multi_otsu_two_classes() {
+    // two classes match the Otsu threshold
+    let data = bimodal();
+    let (thresholds, labels) = threshold::multi_otsu(data.view().into_dyn(), 2, None).unwrap();
+    let t = threshold::otsu(data.view().into_dyn(), None, None).unwrap();
+    let mask = threshold::otsu_mask(data.view().into_dyn(), None, None).unwrap();
+
+    assert_eq!(thresholds, vec![t]);
+    assert_eq!(labels.mapv(|l| l == 1), mask);
+}
+
+#[test]
+fn threshold_multi_otsu_errors() {
+    let data = bimodal();
+
+    assert!(matches!(
+        threshold::multi_otsu(data.view().into_dyn(), 1, None),
+        Err(ImgalError::InvalidParameter {
+            param_name: "classes",
+            ..
+        })
+    ));
+    assert!(matches!(
+        threshold::multi_otsu(data.view().into_dyn(), 5, Some(4)),
+        Err(ImgalError::InvalidParameter {
+            param_name: "classes",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn threshold_percentile() {
+    let data = Array1::from_iter((0..101).map(|v| v as f64));
+
+    assert_eq!(
+        threshold::percentile(data.view().into_dyn(), 90.0, None).unwrap(),
+        90.0
+    );
+    assert_eq!(
+        threshold::percentile(data.view().into_dyn(), 2.5, None).unwrap(),
+        2.5
+    );
+
+    // only the masked values are used
+    let mask = data.mapv(|v| v >= 50.0);
+    assert_eq!(
+        threshold::percentile(data.view().into_dyn(), 0.0, Some(mask.view().into_dyn())).unwrap(),
+        50.0
+    );
+    assert!(matches!(
+        threshold::percentile(data.view().into_dyn(), 101.0, None),
+        Err(ImgalError::InvalidParameter {
+            param_name: "p",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn threshold_per_label() {
+    // two cells with different brightness, each with a bright spot
+    let mut data = Array2::<f64>::zeros((10, 20));
+    let mut labels = Array2::<u16>::zeros((10, 20));
+    data.slice_mut(s![.., ..10]).fill(10.0);
+    data.slice_mut(s![.., 10..]).fill(100.0);
+    data.slice_mut(s![4..6, 4..6]).fill(30.0);
+    data.slice_mut(s![4..6, 14..16]).fill(300.0);
+    labels.slice_mut(s![1..9, 1..9]).fill(1);
+    labels.slice_mut(s![1..9, 11..19]).fill(2);
+    let mask = threshold::per_label(
+        data.view().into_dyn(),
+        labels.view().into_dyn(),
+        ThresholdMethod::Otsu,
+    )
+    .unwrap();
+
+    // a global threshold would select the whole bright cell
+    assert_eq!(mask.iter().filter(|&&m| m).count(), 8);
+    assert!(mask[[4, 4]] && mask[[5, 15]]);
+    assert!(!mask[[2, 12]]);
+
+    // unlabeled pixels are false
+    let mask = threshold::per_label(
+        data.view().into_dyn(),
+        labels.view().into_dyn(),
+        ThresholdMethod::Percentile(0.0),
+    )
+    .unwrap();
+    assert!(!mask[[0, 0]] && !mask[[0, 15]]);
+    assert!(mask[[4, 4]] && !mask[[2, 2]]);
+}
+
+#[test]
+fn threshold_per_label_errors() {
+    let data = Array2::<f64>::zeros((10, 10));
+    let labels = Array2::<u16>::zeros((10, 5));
+
+    assert!(matches!(
+        threshold::per_label(
+            data.view().into_dyn(),
+            labels.view().into_dyn(),
+            ThresholdMethod::Otsu
+        ),
+        Err(ImgalError::MismatchedArrayShapes { .. })
+    ));
+    let labels = Array2::<u16>::ones((10, 10));
+    assert!(matches!(
+        threshold::per_label(
+            data.view().into_dyn(),
+            labels.view().into_dyn(),
+            ThresholdMethod::Percentile(-1.0)
+        ),
+        Err(ImgalError::InvalidParameter {
+            param_name: "p",
+            ..
+        })
+    ));
+}
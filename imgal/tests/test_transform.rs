@@ -0,0 +1,127 @@
+use ndarray::{Array2, Array3, array, s};
+
+use imgal::filter::BorderMode;
+use imgal::transform::{self, Interpolation};
+
+#[test]
+fn transform_warp_affine() {
+    // the identity and integer shifts are exact
+    let data = Array2::from_shape_fn((4, 5), |(r, c)| (r * 5 + c) as u16).into_dyn();
+    let identity = Array2::<f64>::eye(3);
+    let warped = transform::warp_affine(data.view(), identity.view(), None, None).unwrap();
+    assert_eq!(warped, data.mapv(|v| v as f64));
+    let shift = array![[1.0, 0.0, 0.0], [0.0, 1.0, 2.0], [0.0, 0.0, 1.0]];
+    let warped = transform::warp_affine(
+        data.view(),
+        shift.view(),
+        Some(Interpolation::Cubic),
+        Some(BorderMode::Constant(-1.0)),
+    )
+    .unwrap();
+    assert_eq!(
+        warped.slice(s![0, ..]).to_vec(),
+        vec![-1.0, -1.0, 0.0, 1.0, 2.0]
+    );
+    assert_eq!(
+        warped.slice(s![1, ..]).to_vec(),
+        vec![-1.0, -1.0, 5.0, 6.0, 7.0]
+    );
+
+    // the wrap border mode fills the uncovered pixels from the opposite edge
+    let warped = transform::warp_affine(
+        data.view(),
+        shift.view(),
+        Some(Interpolation::Nearest),
+        Some(BorderMode::Wrap),
+    )
+    .unwrap();
+    assert_eq!(
+        warped.slice(s![0, ..]).to_vec(),
+        vec![3.0, 4.0, 0.0, 1.0, 2.0]
+    );
+
+    // a rotation by 90 degrees about the center of a square image
+    let square = Array2::from_shape_fn((3, 3), |(r, c)| (r * 3 + c) as f64).into_dyn();
+    let rotation = array![[0.0, -1.0, 2.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+    let warped = transform::warp_affine(
+        square.view(),
+        rotation.view(),
+        Some(Interpolation::Nearest),
+        None,
+    )
+    .unwrap();
+    assert_eq!(warped.slice(s![.., 0]).to_vec(), vec![2.0, 1.0, 0.0]);
+
+    // a half pixel shift of a volume interpolates linearly
+    let volume = Array3::from_shape_fn((2, 3, 4), |(p, _, c)| (10 * p + c) as f64).into_dyn();
+    let shift = array![
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.5],
+        [0.0, 0.0, 0.0, 1.0]
+    ];
+    let warped = transform::warp_affine(volume.view(), shift.view(), None, None).unwrap();
+    assert_eq!(
+        warped.slice(s![1, 2, ..]).to_vec(),
+        vec![0.0, 10.5, 11.5, 12.5]
+    );
+
+    let singular = array![[1.0, 1.0, 0.0], [1.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    assert!(transform::warp_affine(data.view(), singular.view(), None, None).is_err());
+    assert!(transform::warp_affine(volume.view(), identity.view(), None, None).is_err());
+    let line = ndarray::Array1::<f64>::zeros(4).into_dyn();
+    assert!(transform::warp_affine(line.view(), identity.view(), None, None).is_err());
+}
+
+#[test]
+fn transform_resize() {
+    // linear downsampling by 2 without anti-aliasing averages 2 x 2 blocks
+    let data = Array2::from_shape_fn((4, 6), |(r, c)| (r * 6 + c) as f64).into_dyn();
+    let resized = transform::resize(data.view(), &[2, 3], None, Some(false)).unwrap();
+    assert_eq!(resized.shape(), &[2, 3]);
+    assert_eq!(resized[[0, 0]], 3.5);
+    assert_eq!(resized[[1, 2]], 19.5);
+
+    // resizing to the same shape is the identity
+    let resized =
+        transform::resize(data.view(), &[4, 6], Some(Interpolation::Cubic), None).unwrap();
+    assert_eq!(resized, data);
+
+    // nearest upsampling repeats pixels and anti-aliasing keeps a constant image
+    let small = array![[1u8, 2], [3, 4]].into_dyn();
+    let resized =
+        transform::resize(small.view(), &[4, 4], Some(Interpolation::Nearest), None).unwrap();
+    assert_eq!(resized.slice(s![3, ..]).to_vec(), vec![3.0, 3.0, 4.0, 4.0]);
+    let flat = Array3::from_elem((8, 8, 8), 5.0).into_dyn();
+    let resized = transform::resize(flat.view(), &[3, 8, 2], None, None).unwrap();
+    assert!(resized.iter().all(|&v| (v - 5.0).abs() < 1e-12));
+
+    assert!(transform::resize(data.view(), &[2], None, None).is_err());
+    assert!(transform::resize(data.view(), &[2, 0], None, None).is_err());
+}
+
+#[test]
+fn transform_rescale() {
+    let data = Array3::<f64>::zeros((5, 10, 3)).into_dyn();
+    let rescaled = transform::rescale(data.view(), &[2.0, 0.25, 1.0], None, None).unwrap();
+    assert_eq!(rescaled.shape(), &[10, 3, 3]);
+    let rescaled = transform::rescale(data.view(), &[0.01, 1.0, 1.0], None, None).unwrap();
+    assert_eq!(rescaled.shape(), &[1, 10, 3]);
+
+    assert!(transform::rescale(data.view(), &[1.0, 1.0], None, None).is_err());
+    assert!(transform::rescale(data.view(), &[1.0, 0.0, 1.0], None, None).is_err());
+}
+
+#[test]
+fn transform_non_standard_layout() {
+    // transposed (column-major) inputs are sampled in their logical order
+    let data = Array2::from_shape_fn((4, 6), |(r, c)| (r * 6 + c) as f64);
+    let transposed = data.t().to_owned();
+    let column_major = transposed.t();
+    let identity = Array2::<f64>::eye(3);
+    let warped =
+        transform::warp_affine(column_major.into_dyn(), identity.view(), None, None).unwrap();
+    assert_eq!(warped, data.clone().into_dyn());
+    let resized = transform::resize(column_major.into_dyn(), &[4, 6], None, None).unwrap();
+    assert_eq!(resized, data.into_dyn());
+}
@@ -0,0 +1,79 @@
+use ndarray::{Array2, Array3, Axis, array};
+
+use imgal::error::warning;
+use imgal::unmix;
+
+// helper functions
+fn get_spectra() -> Array2<f64> {
+    // three overlapping gaussian emission spectra over 16 channels
+    Array2::from_shape_fn((16, 3), |(k, j)| {
+        let center = 4.0 + 4.0 * j as f64;
+        (-((k as f64 - center) / 2.5).powi(2)).exp()
+    })
+}
+
+fn ensure_within_tolerance(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+#[test]
+fn linear_mixture() {
+    // a (row, col, ch) image of known non-negative mixtures
+    let spectra = get_spectra();
+    let weights = [[2.0, 0.0, 1.0], [0.5, 3.0, 0.0], [0.0, 0.0, 0.0]];
+    let data = Array3::from_shape_fn((3, 4, 16), |(r, _, k)| {
+        (0..3).map(|j| weights[r][j] * spectra[[k, j]]).sum::<f64>()
+    });
+    let result = unmix::linear(data.view().into_dyn(), spectra.view(), None).unwrap();
+
+    // check the abundances and the residual of the exact mixtures
+    assert_eq!(result.abundances.shape(), [3, 4, 3]);
+    assert_eq!(result.residual.shape(), [3, 4]);
+    (0..3).for_each(|r| {
+        (0..3).for_each(|j| {
+            assert!(ensure_within_tolerance(
+                result.abundances[[r, 2, j]],
+                weights[r][j],
+                1e-9
+            ));
+        });
+        assert!(result.residual[[r, 2]] < 1e-9);
+    });
+}
+
+#[test]
+fn linear_non_negative() {
+    // a spectrum with a negative component in the unconstrained solution
+    let spectra = get_spectra();
+    let mut data = spectra.column(0).to_owned() * 2.0 - spectra.column(1).to_owned() * 0.5;
+    data.mapv_inplace(|v| v.max(0.0));
+    let data = data.insert_axis(Axis(0));
+    let (result, warnings) =
+        warning::collect(|| unmix::linear(data.view().into_dyn(), spectra.view(), Some(1)));
+    let result = result.unwrap();
+
+    // check the abundances are non-negative and the residual is reported, the
+    // active set method converges
+    assert!(warnings.is_empty());
+    assert!(result.abundances.iter().all(|&v| v >= 0.0));
+    assert_eq!(result.abundances[[0, 1]], 0.0);
+    assert!(result.abundances[[0, 0]] > 1.0);
+    assert!(result.residual[[0]] > 0.0);
+}
+
+#[test]
+fn linear_invalid_parameters() {
+    let spectra = get_spectra();
+    let data = Array2::<f64>::zeros((4, 16));
+
+    // check the channel count, axis and spectra
+    assert!(unmix::linear(data.view().into_dyn(), spectra.view(), Some(0)).is_err());
+    assert!(unmix::linear(data.view().into_dyn(), spectra.view(), Some(2)).is_err());
+    let dependent = Array2::from_shape_fn((16, 2), |(k, _)| k as f64);
+    assert!(unmix::linear(data.view().into_dyn(), dependent.view(), None).is_err());
+    let small = array![[1.0, 0.0], [0.0, 1.0]];
+    let data = Array2::<f64>::zeros((4, 2));
+    assert!(unmix::linear(data.view().into_dyn(), small.view(), None).is_ok());
+    let empty = Array2::<f64>::zeros((2, 0));
+    assert!(unmix::linear(data.view().into_dyn(), empty.view(), None).is_err());
+}
@@ -0,0 +1,65 @@
+use ndarray::Array1;
+
+use imgal::image;
+use imgal::phasor::time_domain;
+use imgal::simulation::decay;
+use imgal::threshold;
+use imgal::version::{self, AlgorithmVersion};
+
+// simulated bioexponential decay parameters, unit is nanoseconds
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+
+#[test]
+fn algorithm_current() {
+    assert_eq!(AlgorithmVersion::default(), AlgorithmVersion::LATEST);
+    assert_eq!(version::algorithm::current(), AlgorithmVersion::LATEST);
+    assert_eq!(AlgorithmVersion::from_number(1), Some(AlgorithmVersion::V1));
+    assert_eq!(AlgorithmVersion::from_number(0), None);
+    assert_eq!(AlgorithmVersion::V1.to_string(), "v1");
+}
+
+#[test]
+fn algorithm_pinned() {
+    let i = decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    let data = Array1::from_vec(i.clone());
+
+    // results with V1 pinned match the V1 behavior
+    let (g, hist) = version::algorithm::pinned(AlgorithmVersion::V1, || {
+        assert_eq!(version::algorithm::current(), AlgorithmVersion::V1);
+        (
+            time_domain::real(&i, PERIOD, None),
+            image::histogram(data.view().into_dyn(), Some(16)),
+        )
+    });
+
+    assert_eq!(g, 0.660137605034518);
+    assert_eq!(hist.iter().sum::<i64>(), SAMPLES as i64);
+    assert_eq!(version::algorithm::current(), AlgorithmVersion::LATEST);
+}
+
+#[test]
+fn algorithm_pinned_panic() {
+    // the panic propagates and the previous version is restored
+    let result = std::panic::catch_unwind(|| {
+        version::algorithm::pinned(AlgorithmVersion::V1, || panic!("pinned panic"))
+    });
+
+    assert!(result.is_err());
+    assert_eq!(version::algorithm::current(), AlgorithmVersion::LATEST);
+}
+
+#[test]
+fn algorithm_pinned_otsu() {
+    // the Otsu threshold histogram follows the pinned version
+    let data = Array1::from_iter((0..100).map(|v| v as f64)).into_dyn();
+    let expected = threshold::otsu(data.view(), None, Some(16)).unwrap();
+    let t = version::algorithm::pinned(AlgorithmVersion::V1, || {
+        threshold::otsu(data.view(), None, Some(16)).unwrap()
+    });
+
+    assert_eq!(t, expected);
+}
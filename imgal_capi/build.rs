@@ -0,0 +1,13 @@
+fn main() {
+    // regenerate the C header with cbindgen, only with the "header" feature so
+    // regular builds do not depend on cbindgen
+    #[cfg(feature = "header")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        println!("cargo:rerun-if-changed=src");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        cbindgen::generate(&crate_dir)
+            .expect("Failed to generate the C header.")
+            .write_to_file(format!("{}/include/imgal_capi.h", crate_dir));
+    }
+}
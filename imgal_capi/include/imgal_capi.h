@@ -0,0 +1,568 @@
+/*
+ * C ABI of the imgal image algorithm library.
+ *
+ * Generated with cbindgen from the imgal_capi crate, do not edit. Rebuild with
+ * "cargo build -p imgal_capi --features header" to regenerate.
+ */
+
+#ifndef IMGAL_CAPI_H
+#define IMGAL_CAPI_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Apache Arrow C data interface, as defined by the Arrow specification.
+#ifndef ARROW_C_DATA_INTERFACE
+#define ARROW_C_DATA_INTERFACE
+
+#define ARROW_FLAG_DICTIONARY_ORDERED 1
+#define ARROW_FLAG_NULLABLE 2
+#define ARROW_FLAG_MAP_KEYS_SORTED 4
+
+struct ArrowSchema {
+  // Array type description
+  const char* format;
+  const char* name;
+  const char* metadata;
+  int64_t flags;
+  int64_t n_children;
+  struct ArrowSchema** children;
+  struct ArrowSchema* dictionary;
+
+  // Release callback
+  void (*release)(struct ArrowSchema*);
+  // Opaque producer-specific data
+  void* private_data;
+};
+
+struct ArrowArray {
+  // Array data description
+  int64_t length;
+  int64_t null_count;
+  int64_t offset;
+  int64_t n_buffers;
+  int64_t n_children;
+  const void** buffers;
+  struct ArrowArray** children;
+  struct ArrowArray* dictionary;
+
+  // Release callback
+  void (*release)(struct ArrowArray*);
+  // Opaque producer-specific data
+  void* private_data;
+};
+
+#endif  // ARROW_C_DATA_INTERFACE
+
+// The version of the C ABI, incremented on every breaking change.
+#define IMGAL_ABI_VERSION 2
+
+// The status code returned by every fallible C ABI function.
+typedef enum ImgalStatus {
+  // The call succeeded.
+  IMGAL_STATUS_OK = 0,
+  // A required pointer argument is null.
+  IMGAL_STATUS_NULL_POINTER = 1,
+  // An array descriptor is invalid (_e.g._ negative strides) or an output
+  // buffer length does not match the output size.
+  IMGAL_STATUS_INVALID_ARRAY = 2,
+  // The imgal function rejected its arguments.
+  IMGAL_STATUS_INVALID_PARAMETER = 3,
+  // The imgal function panicked.
+  IMGAL_STATUS_PANIC = 4,
+} ImgalStatus;
+
+// An opaque context of a C ABI consumer.
+//
+// # Description
+//
+// A context owns an optional bounded thread pool that the functions called
+// with the context run in, and the error message of the last failed call
+// made with the context. Functions called with a null context run in the
+// global thread pool and record their error message per thread (see
+// `imgal_last_error`). A context can be shared across threads, its last
+// error is then the message of the last failed call of any thread.
+typedef struct ImgalContext ImgalContext;
+
+// A read-only n-dimensional `f64` array descriptor.
+//
+// The element at index (i₀, i₁, ...) is read at `data + Σ iₖ × strides[k]`.
+// The strides are in elements and must be >= 0.
+typedef struct ImgalArray {
+  // The pointer to the first element, may be null if the array is empty.
+  const double *data;
+  // The number of dimensions.
+  size_t ndim;
+  // The length of each axis, `ndim` values.
+  const size_t *shape;
+  // The stride of each axis in elements, `ndim` values. If null, the array
+  // is C-contiguous (_i.e._ row major).
+  const ptrdiff_t *strides;
+} ImgalArray;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Get the semantic version of the library.
+//
+// # Returns
+//
+// * `*const c_char`: The nul terminated "major.minor.patch" version, valid
+//   for the lifetime of the program.
+const char *imgal_version(void);
+
+// Get the version of the C ABI.
+//
+// # Returns
+//
+// * `u32`: The ABI version, incremented on every breaking change of a
+//   function signature or descriptor layout.
+uint32_t imgal_abi_version(void);
+
+// Compute object-based colocalization measurements of two label images.
+//
+// # Description
+//
+// C ABI interface for `imgal::colocalization::object`. The per-object
+// measurements are returned as a tidy Apache Arrow record batch (_i.e._ a
+// struct array) with the columns `roi` (`utf8`, the object label), `groups`
+// (`list<utf8>`), `slice` and `channel` (`uint64`, 0 for the objects of
+// image `A` and 1 for the objects of image `B`), `measurement` (`utf8`) and
+// `value` (`float64`).
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `labels_a`: The n-dimensional label image, `A`, with non-negative integer
+//   values. Image `A` must have the same shape as image `B`.
+// * `labels_b`: The n-dimensional label image, `B`, with non-negative integer
+//   values. Image `B` must have the same shape as image `A`.
+// * `spacing`: The pixel spacing per axis used for the centroid distances, may
+//   be null for a spacing of 1.0 along every axis.
+// * `spacing_len`: The number of spacing values, 0 if `spacing` is null.
+// * `out_array`: The output Arrow C data interface array.
+// * `out_schema`: The output Arrow C data interface schema.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the record batch was exported to
+//   `out_array` and `out_schema`, otherwise the error status. The caller
+//   must release both structs after a successful call.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `labels_a` and `labels_b` must point
+// to valid descriptors, `spacing` must be null or point to `spacing_len`
+// values and `out_array` and `out_schema` must point to writable structs.
+enum ImgalStatus imgal_colocalization_object(const struct ImgalContext *ctx,
+                                             const struct ImgalArray *labels_a,
+                                             const struct ImgalArray *labels_b,
+                                             const double *spacing,
+                                             size_t spacing_len,
+                                             struct ArrowArray *out_array,
+                                             struct ArrowSchema *out_schema);
+
+// Create a new context.
+//
+// # Arguments
+//
+// * `num_threads`: The number of worker threads of the context's thread
+//   pool. If 0, the functions called with the context run in the global
+//   thread pool.
+//
+// # Returns
+//
+// * `*mut ImgalContext`: The context, free it with `imgal_context_free`.
+//   Null if the worker threads can not be spawned, the error message is then
+//   available with `imgal_last_error`.
+struct ImgalContext *imgal_context_new(size_t num_threads);
+
+// Free a context.
+//
+// # Arguments
+//
+// * `ctx`: The context to free. May be null.
+//
+// # Safety
+//
+// `ctx` must be null or a context created with `imgal_context_new` that is
+// not used after this call.
+void imgal_context_free(struct ImgalContext *ctx);
+
+// Get the number of threads available to the functions called with a
+// context.
+//
+// # Arguments
+//
+// * `ctx`: The context. If null, the number of threads of the global thread
+//   pool is returned.
+//
+// # Returns
+//
+// * `usize`: The number of threads.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context.
+size_t imgal_context_num_threads(const struct ImgalContext *ctx);
+
+// Get the message of the last failed call made with a context.
+//
+// # Arguments
+//
+// * `ctx`: The context. If null, the message of the last failed call on the
+//   current thread is returned (see `imgal_last_error`).
+//
+// # Returns
+//
+// * `*const c_char`: A nul terminated UTF-8 message, valid until the next
+//   failed call made with the context. Null if no call failed yet.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context.
+const char *imgal_context_last_error(const struct ImgalContext *ctx);
+
+// Get the message of the last failed call on the current thread made without
+// a context.
+//
+// # Returns
+//
+// * `*const c_char`: A nul terminated UTF-8 message, valid until the next
+//   failed call on the same thread. Null if no call failed yet.
+const char *imgal_last_error(void);
+
+// Filter an n-dimensional image with a Gaussian kernel.
+//
+// # Description
+//
+// C ABI interface for `imgal::filter::gaussian` with the default reflect
+// border and direct method.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `data`: The input n-dimensional image.
+// * `sigma`: The standard deviation of the Gaussian kernel along each axis,
+//   in pixels, `sigma_len` values. Must have one value per axis.
+// * `sigma_len`: The number of sigma values.
+// * `out`: The output buffer of the C-contiguous filtered image, with the
+//   same shape as `data`.
+// * `out_len`: The length of the output buffer.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the filtered image was written to
+//   `out`, otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `data` must point to a valid
+// descriptor, `sigma` must point to `sigma_len` values and `out` must point to
+// `out_len` writable values.
+enum ImgalStatus imgal_filter_gaussian(const struct ImgalContext *ctx,
+                                       const struct ImgalArray *data,
+                                       const double *sigma,
+                                       size_t sigma_len,
+                                       double *out,
+                                       size_t out_len);
+
+// Measure the properties of each object of a 2-dimensional label image.
+//
+// # Description
+//
+// C ABI interface for `imgal::measure::regionprops`. The region properties
+// are returned as an Apache Arrow record batch (_i.e._ a struct array) with
+// one row per object, ordered by ascending label. The columns are `label`
+// (`uint64`), `area`, `centroid_row`, `centroid_col`, `bbox_min_row`,
+// `bbox_min_col`, `bbox_max_row`, `bbox_max_col`, `perimeter` and
+// `eccentricity`, followed by `mean_intensity`, `min_intensity` and
+// `max_intensity` with an intensity image and `mean_g` and `mean_s` with a
+// phasor image.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `labels`: The 2-dimensional label image, with non-negative integer
+//   values. Pixels with the label 0 are background.
+// * `intensity`: The intensity image, may be null. Must match the shape of
+//   `labels`.
+// * `phasor`: The 3-dimensional phasor (G, S) image, may be null. Must match
+//   the shape of `labels` without the channel axis.
+// * `axis`: The channel axis of the phasor image, ignored without a phasor
+//   image.
+// * `out_array`: The output Arrow C data interface array.
+// * `out_schema`: The output Arrow C data interface schema.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the record batch was exported to
+//   `out_array` and `out_schema`, otherwise the error status. The caller
+//   must release both structs after a successful call.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `labels` must point to a valid
+// descriptor, `intensity` and `phasor` must be null or point to valid
+// descriptors and `out_array` and `out_schema` must point to writable structs.
+enum ImgalStatus imgal_measure_regionprops(const struct ImgalContext *ctx,
+                                           const struct ImgalArray *labels,
+                                           const struct ImgalArray *intensity,
+                                           const struct ImgalArray *phasor,
+                                           size_t axis,
+                                           struct ArrowArray *out_array,
+                                           struct ArrowSchema *out_schema);
+
+// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+// image.
+//
+// # Description
+//
+// C ABI interface for `imgal::phasor::time_domain::image`.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `data`: I(t), the 3-dimensional decay data image.
+// * `period`: The period (_i.e._ time interval).
+// * `harmonic`: The harmonic value. Must be >= 1.0.
+// * `axis`: The decay or lifetime axis. Must be < 3.
+// * `mask`: An optional C-contiguous mask of the non-decay axes, pixels set
+//   to 0 are not computed. May be null.
+// * `out`: The output buffer of the C-contiguous (row, col, 2) G and S image.
+// * `out_len`: The length of the output buffer.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the coordinates were written to
+//   `out`, otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `data` must point to a valid
+// descriptor, `mask` must be null or point to one value per pixel and `out`
+// must point to `out_len` writable values.
+enum ImgalStatus imgal_phasor_time_domain_image(const struct ImgalContext *ctx,
+                                                const struct ImgalArray *data,
+                                                double period,
+                                                double harmonic,
+                                                size_t axis,
+                                                const uint8_t *mask,
+                                                double *out,
+                                                size_t out_len);
+
+// Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional
+// phasor image.
+//
+// # Description
+//
+// C ABI interface for `imgal::phasor::calibration::image`.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `data`: The 3-dimensional phasor image, where G and S are indexed at 0
+//   and 1 respectively on the channel axis.
+// * `modulation`: The modulation to scale the input (G, S) coordinates.
+// * `phase`: The phase, φ angle, to rotate the input (G, S) coordinates.
+// * `axis`: The channel axis. Must be < 3.
+// * `mask`: An optional C-contiguous mask of the non-channel axes, pixels set
+//   to 0 are not calibrated. May be null.
+// * `out`: The output buffer of the C-contiguous calibrated image, with the
+//   same shape as `data`.
+// * `out_len`: The length of the output buffer.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the calibrated coordinates were
+//   written to `out`, otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `data` must point to a valid
+// descriptor, `mask` must be null or point to one value per pixel and `out`
+// must point to `out_len` writable values.
+enum ImgalStatus imgal_phasor_calibration_image(const struct ImgalContext *ctx,
+                                                const struct ImgalArray *data,
+                                                double modulation,
+                                                double phase,
+                                                size_t axis,
+                                                const uint8_t *mask,
+                                                double *out,
+                                                size_t out_len);
+
+// Compute the modulation and phase calibration values from the measured
+// coordinates of a reference with a known monoexponential lifetime.
+//
+// # Description
+//
+// C ABI interface for `imgal::phasor::calibration::modulation_and_phase`.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `g`: The measured real (G) value.
+// * `s`: The measured imaginary (S) value.
+// * `tau`: The lifetime, τ.
+// * `omega`: The angular frequency, ω.
+// * `out_modulation`: The output modulation, M.
+// * `out_phase`: The output phase, φ.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the calibration values were written
+//   to `out_modulation` and `out_phase`, otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context and `out_modulation` and `out_phase`
+// must each point to one writable value.
+enum ImgalStatus imgal_phasor_modulation_and_phase(const struct ImgalContext *ctx,
+                                                   double g,
+                                                   double s,
+                                                   double tau,
+                                                   double omega,
+                                                   double *out_modulation,
+                                                   double *out_phase);
+
+// Simulate an ideal 1-dimensional monoexponential or multiexponential decay
+// curve.
+//
+// # Description
+//
+// C ABI interface for `imgal::simulation::decay::ideal_exponential_1d`.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `samples`: The number of discrete points that make up the decay curve.
+// * `period`: The period (_i.e._ time interval).
+// * `taus`: The lifetimes of each component, `components` values.
+// * `fractions`: The fractional intensities of each component, `components`
+//   values. Must sum to 1.0.
+// * `components`: The number of decay components.
+// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+//   decay curve.
+// * `out`: The output buffer of the decay curve.
+// * `out_len`: The length of the output buffer. Must equal `samples`.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the decay curve was written to
+//   `out`, otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `taus` and `fractions` must point to
+// `components` values and `out` must point to `out_len` writable values.
+enum ImgalStatus imgal_simulation_ideal_exponential_1d(const struct ImgalContext *ctx,
+                                                       size_t samples,
+                                                       double period,
+                                                       const double *taus,
+                                                       const double *fractions,
+                                                       size_t components,
+                                                       double total_counts,
+                                                       double *out,
+                                                       size_t out_len);
+
+// Simulate Poisson noise on an n-dimensional array.
+//
+// # Description
+//
+// C ABI interface for `imgal::simulation::noise::poisson` with a fixed
+// seed.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `data`: The input n-dimensional array.
+// * `scale`: The scale factor.
+// * `seed`: The pseudorandom number generator seed, the same seed applies
+//   the same noise.
+// * `out`: The output buffer of the C-contiguous noisy array, with the same
+//   shape as `data`.
+// * `out_len`: The length of the output buffer.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the noisy array was written to
+//   `out`, otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `data` must point to a valid
+// descriptor and `out` must point to `out_len` writable values.
+enum ImgalStatus imgal_simulation_poisson_noise(const struct ImgalContext *ctx,
+                                                const struct ImgalArray *data,
+                                                double scale,
+                                                uint64_t seed,
+                                                double *out,
+                                                size_t out_len);
+
+// Create a mask from a threshold value.
+//
+// # Description
+//
+// C ABI interface for `imgal::threshold::manual_mask`.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `data`: The input n-dimensional image.
+// * `threshold`: The image pixel threshold value.
+// * `out`: The output buffer of the C-contiguous mask, with the same shape as
+//   `data`. Pixels greater than the threshold value are set to 1, all other
+//   pixels to 0.
+// * `out_len`: The length of the output buffer.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the mask was written to `out`,
+//   otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `data` must point to a valid
+// descriptor and `out` must point to `out_len` writable values.
+enum ImgalStatus imgal_threshold_manual_mask(const struct ImgalContext *ctx,
+                                             const struct ImgalArray *data,
+                                             double threshold,
+                                             uint8_t *out,
+                                             size_t out_len);
+
+// Compute the Otsu threshold value of an n-dimensional image.
+//
+// # Description
+//
+// C ABI interface for `imgal::threshold::otsu` without a mask.
+//
+// # Arguments
+//
+// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+// * `data`: The input n-dimensional image.
+// * `bins`: The number of histogram bins. Must be >= 1.
+// * `out`: The output threshold value.
+//
+// # Returns
+//
+// * `ImgalStatus`: `ImgalStatus::Ok` if the threshold value was written to
+//   `out`, otherwise the error status.
+//
+// # Safety
+//
+// `ctx` must be null or a valid context, `data` must point to a valid
+// descriptor and `out` must point to one writable value.
+enum ImgalStatus imgal_threshold_otsu(const struct ImgalContext *ctx,
+                                      const struct ImgalArray *data,
+                                      size_t bins,
+                                      double *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* IMGAL_CAPI_H */
@@ -0,0 +1,187 @@
+use std::ptr::{self, NonNull};
+use std::slice;
+
+use arrow_array::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
+use arrow_array::{Array, RecordBatch, StructArray};
+use ndarray::{ArrayD, ArrayViewD, IxDyn, ShapeBuilder};
+
+use crate::error::{CallError, ImgalStatus};
+
+/// A read-only n-dimensional `f64` array descriptor.
+///
+/// The element at index (i₀, i₁, ...) is read at `data + Σ iₖ × strides[k]`.
+/// The strides are in elements and must be >= 0.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ImgalArray {
+    /// The pointer to the first element, may be null if the array is empty.
+    pub data: *const f64,
+    /// The number of dimensions.
+    pub ndim: usize,
+    /// The length of each axis, `ndim` values.
+    pub shape: *const usize,
+    /// The stride of each axis in elements, `ndim` values. If null, the array
+    /// is C-contiguous (_i.e._ row major).
+    pub strides: *const isize,
+}
+
+/// Borrow the array of a descriptor as an n-dimensional view.
+///
+/// # Safety
+///
+/// `arr` must be null or point to a valid descriptor whose elements stay alive
+/// and unmodified for `'a`.
+pub(crate) unsafe fn view<'a>(
+    arr: *const ImgalArray,
+    name: &str,
+) -> Result<ArrayViewD<'a, f64>, CallError> {
+    let arr = unsafe { arr.as_ref() }.ok_or_else(|| CallError::null(name))?;
+    let shape = unsafe { slice_or_empty(arr.shape, arr.ndim, name)? };
+    let len: usize = shape.iter().product();
+    let data = if arr.data.is_null() {
+        if len != 0 {
+            return Err(CallError::null(name));
+        }
+        NonNull::<f64>::dangling().as_ptr() as *const f64
+    } else {
+        arr.data
+    };
+    if arr.strides.is_null() {
+        return Ok(unsafe { ArrayViewD::from_shape_ptr(IxDyn(shape), data) });
+    }
+    let strides = unsafe { slice::from_raw_parts(arr.strides, arr.ndim) };
+    if strides.iter().any(|&s| s < 0) {
+        return Err(CallError::new(
+            ImgalStatus::InvalidArray,
+            format!("The array \"{}\" has negative strides.", name),
+        ));
+    }
+    let strides: Vec<usize> = strides.iter().map(|&s| s as usize).collect();
+
+    Ok(unsafe { ArrayViewD::from_shape_ptr(IxDyn(shape).strides(IxDyn(&strides)), data) })
+}
+
+/// Borrow the array of an optional descriptor as an n-dimensional view.
+///
+/// # Safety
+///
+/// `arr` must be null or point to a valid descriptor whose elements stay alive
+/// and unmodified for `'a`.
+pub(crate) unsafe fn optional_view<'a>(
+    arr: *const ImgalArray,
+    name: &str,
+) -> Result<Option<ArrayViewD<'a, f64>>, CallError> {
+    if arr.is_null() {
+        return Ok(None);
+    }
+
+    unsafe { view(arr, name) }.map(Some)
+}
+
+/// Convert the values of a label image descriptor into integer labels.
+///
+/// # Safety
+///
+/// `arr` must be null or point to a valid descriptor whose elements stay alive
+/// and unmodified for the call.
+pub(crate) unsafe fn labels(arr: *const ImgalArray, name: &str) -> Result<ArrayD<u64>, CallError> {
+    let view = unsafe { view(arr, name)? };
+    if view.iter().any(|&v| !(v >= 0.0 && v.fract() == 0.0)) {
+        return Err(CallError::new(
+            ImgalStatus::InvalidArray,
+            format!(
+                "The label image \"{}\" has negative or non-integer values.",
+                name
+            ),
+        ));
+    }
+
+    Ok(view.mapv(|v| v as u64))
+}
+
+/// Borrow a pointer and length as a slice, a null pointer is only valid with
+/// a length of 0.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to `len` initialized values that stay alive for
+/// `'a`.
+pub(crate) unsafe fn slice_or_empty<'a, T>(
+    ptr: *const T,
+    len: usize,
+    name: &str,
+) -> Result<&'a [T], CallError> {
+    if ptr.is_null() {
+        if len == 0 {
+            return Ok(&[]);
+        }
+        return Err(CallError::null(name));
+    }
+
+    Ok(unsafe { slice::from_raw_parts(ptr, len) })
+}
+
+/// Write the values of an output in order into a caller allocated buffer.
+///
+/// # Safety
+///
+/// `out` must be null or point to `out_len` writable values.
+pub(crate) unsafe fn write<'a, T, I>(
+    out: *mut T,
+    out_len: usize,
+    values: I,
+) -> Result<(), CallError>
+where
+    T: Copy + 'a,
+    I: ExactSizeIterator<Item = &'a T>,
+{
+    if out.is_null() {
+        return Err(CallError::null("out"));
+    }
+    if values.len() != out_len {
+        return Err(CallError::new(
+            ImgalStatus::InvalidArray,
+            format!(
+                "The output buffer holds {} values, the output has {} values.",
+                out_len,
+                values.len()
+            ),
+        ));
+    }
+    let buf = unsafe { slice::from_raw_parts_mut(out, out_len) };
+    buf.iter_mut().zip(values).for_each(|(b, v)| *b = *v);
+
+    Ok(())
+}
+
+/// Export a record batch through the Arrow C data interface.
+///
+/// The record batch is moved into the caller allocated structs as a struct
+/// array without copying its buffers. The caller owns both structs and must
+/// call their `release` callbacks.
+///
+/// # Safety
+///
+/// `out_array` and `out_schema` must be null or point to writable structs,
+/// their previous contents are overwritten without being released.
+pub(crate) unsafe fn export(
+    batch: RecordBatch,
+    out_array: *mut FFI_ArrowArray,
+    out_schema: *mut FFI_ArrowSchema,
+) -> Result<(), CallError> {
+    if out_array.is_null() {
+        return Err(CallError::null("out_array"));
+    }
+    if out_schema.is_null() {
+        return Err(CallError::null("out_schema"));
+    }
+    let data = StructArray::from(batch).into_data();
+    let schema = FFI_ArrowSchema::try_from(data.data_type())
+        .map_err(|e| CallError::new(ImgalStatus::InvalidParameter, e.to_string()))?;
+    unsafe {
+        ptr::write(out_schema, schema);
+        ptr::write(out_array, FFI_ArrowArray::new(&data));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use arrow_array::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
+
+use imgal::colocalization;
+
+use crate::array::{self, ImgalArray};
+use crate::context::ImgalContext;
+use crate::error::{ImgalStatus, guard};
+
+/// Compute object-based colocalization measurements of two label images.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::colocalization::object`. The per-object
+/// measurements are returned as a tidy Apache Arrow record batch (_i.e._ a
+/// struct array) with the columns `roi` (`utf8`, the object label), `groups`
+/// (`list<utf8>`), `slice` and `channel` (`uint64`, 0 for the objects of
+/// image `A` and 1 for the objects of image `B`), `measurement` (`utf8`) and
+/// `value` (`float64`).
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `labels_a`: The n-dimensional label image, `A`, with non-negative integer
+///   values. Image `A` must have the same shape as image `B`.
+/// * `labels_b`: The n-dimensional label image, `B`, with non-negative integer
+///   values. Image `B` must have the same shape as image `A`.
+/// * `spacing`: The pixel spacing per axis used for the centroid distances, may
+///   be null for a spacing of 1.0 along every axis.
+/// * `spacing_len`: The number of spacing values, 0 if `spacing` is null.
+/// * `out_array`: The output Arrow C data interface array.
+/// * `out_schema`: The output Arrow C data interface schema.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the record batch was exported to
+///   `out_array` and `out_schema`, otherwise the error status. The caller
+///   must release both structs after a successful call.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `labels_a` and `labels_b` must point
+/// to valid descriptors, `spacing` must be null or point to `spacing_len`
+/// values and `out_array` and `out_schema` must point to writable structs.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_colocalization_object(
+    ctx: *const ImgalContext,
+    labels_a: *const ImgalArray,
+    labels_b: *const ImgalArray,
+    spacing: *const f64,
+    spacing_len: usize,
+    out_array: *mut FFI_ArrowArray,
+    out_schema: *mut FFI_ArrowSchema,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let lbl_a = unsafe { array::labels(labels_a, "labels_a")? };
+        let lbl_b = unsafe { array::labels(labels_b, "labels_b")? };
+        let sp = if spacing.is_null() {
+            None
+        } else {
+            Some(unsafe { array::slice_or_empty(spacing, spacing_len, "spacing")? })
+        };
+        let table = colocalization::object(lbl_a.view(), lbl_b.view(), sp)?;
+        unsafe { array::export(table.to_record_batch(), out_array, out_schema) }
+    })
+}
@@ -0,0 +1,159 @@
+use std::ffi::{CString, c_char};
+use std::ptr;
+use std::sync::Mutex;
+
+use imgal::config::{self, ThreadPool};
+
+use crate::error;
+
+/// An opaque context of a C ABI consumer.
+///
+/// # Description
+///
+/// A context owns an optional bounded thread pool that the functions called
+/// with the context run in, and the error message of the last failed call
+/// made with the context. Functions called with a null context run in the
+/// global thread pool and record their error message per thread (see
+/// `imgal_last_error`). A context can be shared across threads, its last
+/// error is then the message of the last failed call of any thread.
+pub struct ImgalContext {
+    pool: Option<ThreadPool>,
+    last_error: Mutex<Option<CString>>,
+}
+
+impl ImgalContext {
+    /// Run a function in the thread pool of the context.
+    pub(crate) fn install<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+        R: Send,
+    {
+        match &self.pool {
+            Some(pool) => {
+                let f = AssertSend(f);
+                pool.install(move || f.call())
+            }
+            None => f(),
+        }
+    }
+
+    /// Record the error message of a failed call.
+    pub(crate) fn set_last_error(&self, msg: CString) {
+        let mut last = self.last_error.lock().unwrap_or_else(|e| e.into_inner());
+        *last = Some(msg);
+    }
+}
+
+/// Create a new context.
+///
+/// # Arguments
+///
+/// * `num_threads`: The number of worker threads of the context's thread
+///   pool. If 0, the functions called with the context run in the global
+///   thread pool.
+///
+/// # Returns
+///
+/// * `*mut ImgalContext`: The context, free it with `imgal_context_free`.
+///   Null if the worker threads can not be spawned, the error message is then
+///   available with `imgal_last_error`.
+#[unsafe(no_mangle)]
+pub extern "C" fn imgal_context_new(num_threads: usize) -> *mut ImgalContext {
+    let mut ctx = ptr::null_mut();
+    error::guard(None, || {
+        let pool = match num_threads {
+            0 => None,
+            n => Some(ThreadPool::new(n)?),
+        };
+        ctx = Box::into_raw(Box::new(ImgalContext {
+            pool,
+            last_error: Mutex::new(None),
+        }));
+
+        Ok(())
+    });
+
+    ctx
+}
+
+/// Free a context.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to free. May be null.
+///
+/// # Safety
+///
+/// `ctx` must be null or a context created with `imgal_context_new` that is
+/// not used after this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_context_free(ctx: *mut ImgalContext) {
+    if !ctx.is_null() {
+        drop(unsafe { Box::from_raw(ctx) });
+    }
+}
+
+/// Get the number of threads available to the functions called with a
+/// context.
+///
+/// # Arguments
+///
+/// * `ctx`: The context. If null, the number of threads of the global thread
+///   pool is returned.
+///
+/// # Returns
+///
+/// * `usize`: The number of threads.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_context_num_threads(ctx: *const ImgalContext) -> usize {
+    match unsafe { ctx.as_ref() }.and_then(|c| c.pool.as_ref()) {
+        Some(pool) => pool.num_threads(),
+        None => config::num_threads(),
+    }
+}
+
+/// Get the message of the last failed call made with a context.
+///
+/// # Arguments
+///
+/// * `ctx`: The context. If null, the message of the last failed call on the
+///   current thread is returned (see `imgal_last_error`).
+///
+/// # Returns
+///
+/// * `*const c_char`: A nul terminated UTF-8 message, valid until the next
+///   failed call made with the context. Null if no call failed yet.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_context_last_error(ctx: *const ImgalContext) -> *const c_char {
+    match unsafe { ctx.as_ref() } {
+        Some(c) => c
+            .last_error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map_or(ptr::null(), |m| m.as_ptr()),
+        None => error::imgal_last_error(),
+    }
+}
+
+/// A function that is sent to a worker thread of a context's thread pool.
+///
+/// The C ABI functions capture raw pointers, the calling thread blocks until
+/// the worker thread returns so the pointers stay valid.
+struct AssertSend<F>(F);
+
+unsafe impl<F> Send for AssertSend<F> {}
+
+impl<F: FnOnce() -> R, R> AssertSend<F> {
+    fn call(self) -> R {
+        (self.0)()
+    }
+}
@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+use std::ffi::{CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use imgal::error::ImgalError;
+
+use crate::context::ImgalContext;
+
+/// The status code returned by every fallible C ABI function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImgalStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument is null.
+    NullPointer = 1,
+    /// An array descriptor is invalid (_e.g._ negative strides) or an output
+    /// buffer length does not match the output size.
+    InvalidArray = 2,
+    /// The imgal function rejected its arguments.
+    InvalidParameter = 3,
+    /// The imgal function panicked.
+    Panic = 4,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Get the message of the last failed call on the current thread made without
+/// a context.
+///
+/// # Returns
+///
+/// * `*const c_char`: A nul terminated UTF-8 message, valid until the next
+///   failed call on the same thread. Null if no call failed yet.
+#[unsafe(no_mangle)]
+pub extern "C" fn imgal_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+/// An error of a C ABI call, a status code and a message.
+pub(crate) struct CallError {
+    status: ImgalStatus,
+    msg: String,
+}
+
+impl CallError {
+    pub(crate) fn new(status: ImgalStatus, msg: impl Into<String>) -> Self {
+        CallError {
+            status,
+            msg: msg.into(),
+        }
+    }
+
+    pub(crate) fn null(name: &str) -> Self {
+        CallError::new(
+            ImgalStatus::NullPointer,
+            format!("The pointer argument \"{}\" is null.", name),
+        )
+    }
+}
+
+impl From<ImgalError> for CallError {
+    fn from(e: ImgalError) -> Self {
+        CallError::new(ImgalStatus::InvalidParameter, e.to_string())
+    }
+}
+
+/// Run the body of a C ABI function in the thread pool of its context, record
+/// its error message and convert panics into `ImgalStatus::Panic` so they
+/// never unwind across the ABI.
+pub(crate) fn guard<F>(ctx: Option<&ImgalContext>, f: F) -> ImgalStatus
+where
+    F: FnOnce() -> Result<(), CallError>,
+{
+    let run = || match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let msg = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Unknown panic.".to_string());
+            Err(CallError::new(ImgalStatus::Panic, msg))
+        }
+    };
+    let result = match ctx {
+        Some(c) => c.install(run),
+        None => run(),
+    };
+    let err = match result {
+        Ok(()) => return ImgalStatus::Ok,
+        Err(e) => e,
+    };
+    let msg = CString::new(err.msg.replace('\0', "")).unwrap_or_default();
+    match ctx {
+        Some(c) => c.set_last_error(msg),
+        None => LAST_ERROR.with(|e| *e.borrow_mut() = Some(msg)),
+    }
+
+    err.status
+}
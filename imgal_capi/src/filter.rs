@@ -0,0 +1,50 @@
+use imgal::filter;
+
+use crate::array::{self, ImgalArray};
+use crate::context::ImgalContext;
+use crate::error::{ImgalStatus, guard};
+
+/// Filter an n-dimensional image with a Gaussian kernel.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::filter::gaussian` with the default reflect
+/// border and direct method.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `data`: The input n-dimensional image.
+/// * `sigma`: The standard deviation of the Gaussian kernel along each axis,
+///   in pixels, `sigma_len` values. Must have one value per axis.
+/// * `sigma_len`: The number of sigma values.
+/// * `out`: The output buffer of the C-contiguous filtered image, with the
+///   same shape as `data`.
+/// * `out_len`: The length of the output buffer.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the filtered image was written to
+///   `out`, otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `data` must point to a valid
+/// descriptor, `sigma` must point to `sigma_len` values and `out` must point to
+/// `out_len` writable values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_filter_gaussian(
+    ctx: *const ImgalContext,
+    data: *const ImgalArray,
+    sigma: *const f64,
+    sigma_len: usize,
+    out: *mut f64,
+    out_len: usize,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let arr = unsafe { array::view(data, "data")? };
+        let s = unsafe { array::slice_or_empty(sigma, sigma_len, "sigma")? };
+        let output = filter::gaussian(arr, s, None, None)?;
+        unsafe { array::write(out, out_len, output.iter()) }
+    })
+}
@@ -0,0 +1,55 @@
+//! A stable C ABI over the core `imgal` functions.
+//!
+//! Every function takes an optional `ImgalContext` (the thread pool to run in
+//! and the error state), its n-dimensional inputs as `ImgalArray` descriptors
+//! (a data pointer with a shape and strides), writes its output into a caller
+//! allocated buffer and returns an `ImgalStatus` code. Measurement tables are
+//! returned as Apache Arrow record batches through the Arrow C data interface
+//! (`struct ArrowArray` and `struct ArrowSchema`). The matching C header
+//! `include/imgal_capi.h` is generated with cbindgen by building with the
+//! `header` feature.
+use std::ffi::c_char;
+
+pub mod array;
+pub use array::ImgalArray;
+pub mod colocalization;
+pub mod context;
+pub use context::{
+    ImgalContext, imgal_context_free, imgal_context_last_error, imgal_context_new,
+    imgal_context_num_threads,
+};
+pub mod error;
+pub use error::{ImgalStatus, imgal_last_error};
+pub mod filter;
+pub mod measure;
+pub mod phasor;
+pub mod simulation;
+pub mod threshold;
+
+/// The version of the C ABI, incremented on every breaking change.
+pub const ABI_VERSION: u32 = 2;
+
+/// The semantic version of the library as a nul terminated string.
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// Get the semantic version of the library.
+///
+/// # Returns
+///
+/// * `*const c_char`: The nul terminated "major.minor.patch" version, valid
+///   for the lifetime of the program.
+#[unsafe(no_mangle)]
+pub extern "C" fn imgal_version() -> *const c_char {
+    VERSION.as_ptr() as *const c_char
+}
+
+/// Get the version of the C ABI.
+///
+/// # Returns
+///
+/// * `u32`: The ABI version, incremented on every breaking change of a
+///   function signature or descriptor layout.
+#[unsafe(no_mangle)]
+pub extern "C" fn imgal_abi_version() -> u32 {
+    ABI_VERSION
+}
@@ -0,0 +1,90 @@
+use arrow_array::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
+use ndarray::{ArrayView, ArrayViewD, Dimension, Ix2, Ix3};
+
+use imgal::measure;
+
+use crate::array::{self, ImgalArray};
+use crate::context::ImgalContext;
+use crate::error::{CallError, ImgalStatus, guard};
+
+/// Measure the properties of each object of a 2-dimensional label image.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::measure::regionprops`. The region properties
+/// are returned as an Apache Arrow record batch (_i.e._ a struct array) with
+/// one row per object, ordered by ascending label. The columns are `label`
+/// (`uint64`), `area`, `centroid_row`, `centroid_col`, `bbox_min_row`,
+/// `bbox_min_col`, `bbox_max_row`, `bbox_max_col`, `perimeter` and
+/// `eccentricity`, followed by `mean_intensity`, `min_intensity` and
+/// `max_intensity` with an intensity image and `mean_g` and `mean_s` with a
+/// phasor image.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `labels`: The 2-dimensional label image, with non-negative integer
+///   values. Pixels with the label 0 are background.
+/// * `intensity`: The intensity image, may be null. Must match the shape of
+///   `labels`.
+/// * `phasor`: The 3-dimensional phasor (G, S) image, may be null. Must match
+///   the shape of `labels` without the channel axis.
+/// * `axis`: The channel axis of the phasor image, ignored without a phasor
+///   image.
+/// * `out_array`: The output Arrow C data interface array.
+/// * `out_schema`: The output Arrow C data interface schema.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the record batch was exported to
+///   `out_array` and `out_schema`, otherwise the error status. The caller
+///   must release both structs after a successful call.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `labels` must point to a valid
+/// descriptor, `intensity` and `phasor` must be null or point to valid
+/// descriptors and `out_array` and `out_schema` must point to writable structs.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_measure_regionprops(
+    ctx: *const ImgalContext,
+    labels: *const ImgalArray,
+    intensity: *const ImgalArray,
+    phasor: *const ImgalArray,
+    axis: usize,
+    out_array: *mut FFI_ArrowArray,
+    out_schema: *mut FFI_ArrowSchema,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let lbl = unsafe { array::labels(labels, "labels")? };
+        let lbl = dim::<_, Ix2>(lbl.view(), "labels")?;
+        let img = unsafe { array::optional_view(intensity, "intensity")? }
+            .map(|v| dim::<_, Ix2>(v, "intensity"))
+            .transpose()?;
+        let ph = unsafe { array::optional_view(phasor, "phasor")? }
+            .map(|v| dim::<_, Ix3>(v, "phasor"))
+            .transpose()?;
+        let props = measure::regionprops(lbl, img, ph, Some(axis))?;
+        unsafe { array::export(props.to_record_batch(), out_array, out_schema) }
+    })
+}
+
+/// Convert an n-dimensional view into a view with a fixed number of
+/// dimensions.
+fn dim<'a, A, D: Dimension>(
+    arr: ArrayViewD<'a, A>,
+    name: &str,
+) -> Result<ArrayView<'a, A, D>, CallError> {
+    let ndim = arr.ndim();
+    arr.into_dimensionality::<D>().map_err(|_| {
+        CallError::new(
+            ImgalStatus::InvalidArray,
+            format!(
+                "The array \"{}\" must be {}-dimensional, got {} dimensions.",
+                name,
+                D::NDIM.unwrap_or(ndim),
+                ndim
+            ),
+        )
+    })
+}
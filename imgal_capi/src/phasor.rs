@@ -0,0 +1,203 @@
+use ndarray::{Array2, Ix3};
+
+use imgal::phasor::{calibration, time_domain};
+
+use crate::array::{self, ImgalArray};
+use crate::context::ImgalContext;
+use crate::error::{CallError, ImgalStatus, guard};
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
+/// image.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::phasor::time_domain::image`.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `data`: I(t), the 3-dimensional decay data image.
+/// * `period`: The period (_i.e._ time interval).
+/// * `harmonic`: The harmonic value. Must be >= 1.0.
+/// * `axis`: The decay or lifetime axis. Must be < 3.
+/// * `mask`: An optional C-contiguous mask of the non-decay axes, pixels set
+///   to 0 are not computed. May be null.
+/// * `out`: The output buffer of the C-contiguous (row, col, 2) G and S image.
+/// * `out_len`: The length of the output buffer.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the coordinates were written to
+///   `out`, otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `data` must point to a valid
+/// descriptor, `mask` must be null or point to one value per pixel and `out`
+/// must point to `out_len` writable values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_phasor_time_domain_image(
+    ctx: *const ImgalContext,
+    data: *const ImgalArray,
+    period: f64,
+    harmonic: f64,
+    axis: usize,
+    mask: *const u8,
+    out: *mut f64,
+    out_len: usize,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let arr = dim3(unsafe { array::view(data, "data")? })?;
+        let msk = unsafe { lane_mask(mask, arr.shape(), axis)? };
+        let output = time_domain::image(
+            arr,
+            period,
+            msk.as_ref().map(|m| m.view()),
+            Some(harmonic),
+            Some(axis),
+        )?;
+        unsafe { array::write(out, out_len, output.iter()) }
+    })
+}
+
+/// Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional
+/// phasor image.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::phasor::calibration::image`.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `data`: The 3-dimensional phasor image, where G and S are indexed at 0
+///   and 1 respectively on the channel axis.
+/// * `modulation`: The modulation to scale the input (G, S) coordinates.
+/// * `phase`: The phase, φ angle, to rotate the input (G, S) coordinates.
+/// * `axis`: The channel axis. Must be < 3.
+/// * `mask`: An optional C-contiguous mask of the non-channel axes, pixels set
+///   to 0 are not calibrated. May be null.
+/// * `out`: The output buffer of the C-contiguous calibrated image, with the
+///   same shape as `data`.
+/// * `out_len`: The length of the output buffer.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the calibrated coordinates were
+///   written to `out`, otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `data` must point to a valid
+/// descriptor, `mask` must be null or point to one value per pixel and `out`
+/// must point to `out_len` writable values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_phasor_calibration_image(
+    ctx: *const ImgalContext,
+    data: *const ImgalArray,
+    modulation: f64,
+    phase: f64,
+    axis: usize,
+    mask: *const u8,
+    out: *mut f64,
+    out_len: usize,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let arr = dim3(unsafe { array::view(data, "data")? })?;
+        let msk = unsafe { lane_mask(mask, arr.shape(), axis)? };
+        let output = calibration::image(
+            arr,
+            modulation,
+            phase,
+            msk.as_ref().map(|m| m.view()),
+            Some(axis),
+        )?;
+        unsafe { array::write(out, out_len, output.iter()) }
+    })
+}
+
+/// Compute the modulation and phase calibration values from the measured
+/// coordinates of a reference with a known monoexponential lifetime.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::phasor::calibration::modulation_and_phase`.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `g`: The measured real (G) value.
+/// * `s`: The measured imaginary (S) value.
+/// * `tau`: The lifetime, τ.
+/// * `omega`: The angular frequency, ω.
+/// * `out_modulation`: The output modulation, M.
+/// * `out_phase`: The output phase, φ.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the calibration values were written
+///   to `out_modulation` and `out_phase`, otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context and `out_modulation` and `out_phase`
+/// must each point to one writable value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_phasor_modulation_and_phase(
+    ctx: *const ImgalContext,
+    g: f64,
+    s: f64,
+    tau: f64,
+    omega: f64,
+    out_modulation: *mut f64,
+    out_phase: *mut f64,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let m =
+            unsafe { out_modulation.as_mut() }.ok_or_else(|| CallError::null("out_modulation"))?;
+        let p = unsafe { out_phase.as_mut() }.ok_or_else(|| CallError::null("out_phase"))?;
+        (*m, *p) = calibration::modulation_and_phase(g, s, tau, omega);
+
+        Ok(())
+    })
+}
+
+/// Convert an n-dimensional view into a 3-dimensional view.
+fn dim3(arr: ndarray::ArrayViewD<f64>) -> Result<ndarray::ArrayView3<f64>, CallError> {
+    let ndim = arr.ndim();
+    arr.into_dimensionality::<Ix3>().map_err(|_| {
+        CallError::new(
+            ImgalStatus::InvalidArray,
+            format!(
+                "The array \"data\" must be 3-dimensional, got {} dimensions.",
+                ndim
+            ),
+        )
+    })
+}
+
+/// Copy an optional C-contiguous u8 mask of the lanes along `axis` into a
+/// boolean mask.
+///
+/// # Safety
+///
+/// `mask` must be null or point to one value per lane.
+unsafe fn lane_mask(
+    mask: *const u8,
+    shape: &[usize],
+    axis: usize,
+) -> Result<Option<Array2<bool>>, CallError> {
+    if mask.is_null() {
+        return Ok(None);
+    }
+    if axis >= 3 {
+        // the axis is reported by the imgal function without a mask
+        return Ok(None);
+    }
+    let mut dims = shape.to_vec();
+    dims.remove(axis);
+    let values = unsafe { array::slice_or_empty(mask, dims[0] * dims[1], "mask")? };
+    let m = Array2::from_shape_fn((dims[0], dims[1]), |(r, c)| values[r * dims[1] + c] != 0);
+
+    Ok(Some(m))
+}
@@ -0,0 +1,99 @@
+use imgal::simulation::{decay, noise};
+
+use crate::array::{self, ImgalArray};
+use crate::context::ImgalContext;
+use crate::error::{ImgalStatus, guard};
+
+/// Simulate an ideal 1-dimensional monoexponential or multiexponential decay
+/// curve.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::simulation::decay::ideal_exponential_1d`.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval).
+/// * `taus`: The lifetimes of each component, `components` values.
+/// * `fractions`: The fractional intensities of each component, `components`
+///   values. Must sum to 1.0.
+/// * `components`: The number of decay components.
+/// * `total_counts`: The total intensity count (_e.g._ photon count) of the
+///   decay curve.
+/// * `out`: The output buffer of the decay curve.
+/// * `out_len`: The length of the output buffer. Must equal `samples`.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the decay curve was written to
+///   `out`, otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `taus` and `fractions` must point to
+/// `components` values and `out` must point to `out_len` writable values.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn imgal_simulation_ideal_exponential_1d(
+    ctx: *const ImgalContext,
+    samples: usize,
+    period: f64,
+    taus: *const f64,
+    fractions: *const f64,
+    components: usize,
+    total_counts: f64,
+    out: *mut f64,
+    out_len: usize,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let t = unsafe { array::slice_or_empty(taus, components, "taus")? };
+        let f = unsafe { array::slice_or_empty(fractions, components, "fractions")? };
+        let output = decay::ideal_exponential_1d(samples, period, t, f, total_counts)?;
+        unsafe { array::write(out, out_len, output.iter()) }
+    })
+}
+
+/// Simulate Poisson noise on an n-dimensional array.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::simulation::noise::poisson` with a fixed
+/// seed.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `data`: The input n-dimensional array.
+/// * `scale`: The scale factor.
+/// * `seed`: The pseudorandom number generator seed, the same seed applies
+///   the same noise.
+/// * `out`: The output buffer of the C-contiguous noisy array, with the same
+///   shape as `data`.
+/// * `out_len`: The length of the output buffer.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the noisy array was written to
+///   `out`, otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `data` must point to a valid
+/// descriptor and `out` must point to `out_len` writable values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_simulation_poisson_noise(
+    ctx: *const ImgalContext,
+    data: *const ImgalArray,
+    scale: f64,
+    seed: u64,
+    out: *mut f64,
+    out_len: usize,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let arr = unsafe { array::view(data, "data")? };
+        let output = noise::poisson(arr, scale, seed, None)?;
+        unsafe { array::write(out, out_len, output.iter()) }
+    })
+}
@@ -0,0 +1,84 @@
+use imgal::threshold;
+
+use crate::array::{self, ImgalArray};
+use crate::context::ImgalContext;
+use crate::error::{CallError, ImgalStatus, guard};
+
+/// Create a mask from a threshold value.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::threshold::manual_mask`.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `data`: The input n-dimensional image.
+/// * `threshold`: The image pixel threshold value.
+/// * `out`: The output buffer of the C-contiguous mask, with the same shape as
+///   `data`. Pixels greater than the threshold value are set to 1, all other
+///   pixels to 0.
+/// * `out_len`: The length of the output buffer.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the mask was written to `out`,
+///   otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `data` must point to a valid
+/// descriptor and `out` must point to `out_len` writable values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_threshold_manual_mask(
+    ctx: *const ImgalContext,
+    data: *const ImgalArray,
+    threshold: f64,
+    out: *mut u8,
+    out_len: usize,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let arr = unsafe { array::view(data, "data")? };
+        let output = threshold::manual_mask(arr, threshold).mapv(u8::from);
+        unsafe { array::write(out, out_len, output.iter()) }
+    })
+}
+
+/// Compute the Otsu threshold value of an n-dimensional image.
+///
+/// # Description
+///
+/// C ABI interface for `imgal::threshold::otsu` without a mask.
+///
+/// # Arguments
+///
+/// * `ctx`: The context to run in, may be null (see `ImgalContext`).
+/// * `data`: The input n-dimensional image.
+/// * `bins`: The number of histogram bins. Must be >= 1.
+/// * `out`: The output threshold value.
+///
+/// # Returns
+///
+/// * `ImgalStatus`: `ImgalStatus::Ok` if the threshold value was written to
+///   `out`, otherwise the error status.
+///
+/// # Safety
+///
+/// `ctx` must be null or a valid context, `data` must point to a valid
+/// descriptor and `out` must point to one writable value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imgal_threshold_otsu(
+    ctx: *const ImgalContext,
+    data: *const ImgalArray,
+    bins: usize,
+    out: *mut f64,
+) -> ImgalStatus {
+    guard(unsafe { ctx.as_ref() }, || {
+        let arr = unsafe { array::view(data, "data")? };
+        let t = threshold::otsu(arr, None, Some(bins))?;
+        let out = unsafe { out.as_mut() }.ok_or_else(|| CallError::null("out"))?;
+        *out = t;
+
+        Ok(())
+    })
+}
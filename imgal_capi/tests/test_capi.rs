@@ -0,0 +1,531 @@
+use std::ffi::CStr;
+use std::ptr;
+
+use arrow_array::cast::AsArray;
+use arrow_array::ffi::{FFI_ArrowArray, FFI_ArrowSchema, from_ffi};
+use arrow_array::types::{Float64Type, UInt64Type};
+use arrow_array::{RecordBatch, StructArray};
+use ndarray::{Array2, Array3, ArrayD, ArrayViewD, s};
+
+use imgal::colocalization;
+use imgal::filter;
+use imgal::measure;
+use imgal::phasor::{calibration, time_domain};
+use imgal::simulation::{decay, noise};
+use imgal::threshold;
+use imgal_capi::colocalization::imgal_colocalization_object;
+use imgal_capi::filter::imgal_filter_gaussian;
+use imgal_capi::measure::imgal_measure_regionprops;
+use imgal_capi::phasor::{
+    imgal_phasor_calibration_image, imgal_phasor_modulation_and_phase,
+    imgal_phasor_time_domain_image,
+};
+use imgal_capi::simulation::{
+    imgal_simulation_ideal_exponential_1d, imgal_simulation_poisson_noise,
+};
+use imgal_capi::threshold::{imgal_threshold_manual_mask, imgal_threshold_otsu};
+use imgal_capi::{
+    ABI_VERSION, ImgalArray, ImgalStatus, imgal_abi_version, imgal_context_free,
+    imgal_context_last_error, imgal_context_new, imgal_context_num_threads, imgal_last_error,
+    imgal_version,
+};
+
+// simulated biexponential decay parameters
+const SAMPLES: usize = 64;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+
+// helper functions
+struct Descriptor {
+    shape: Vec<usize>,
+    strides: Vec<isize>,
+}
+
+impl Descriptor {
+    fn new(view: &ArrayViewD<f64>) -> Self {
+        Descriptor {
+            shape: view.shape().to_vec(),
+            strides: view.strides().to_vec(),
+        }
+    }
+
+    fn array(&self, view: &ArrayViewD<f64>) -> ImgalArray {
+        ImgalArray {
+            data: view.as_ptr(),
+            ndim: self.shape.len(),
+            shape: self.shape.as_ptr(),
+            strides: self.strides.as_ptr(),
+        }
+    }
+}
+
+fn last_error() -> String {
+    let msg = imgal_last_error();
+    assert!(!msg.is_null());
+    unsafe { CStr::from_ptr(msg) }.to_str().unwrap().to_string()
+}
+
+fn decay_image() -> Array3<f64> {
+    decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, (5, 4)).unwrap()
+}
+
+fn import_record_batch(array: FFI_ArrowArray, schema: FFI_ArrowSchema) -> RecordBatch {
+    let data = unsafe { from_ffi(array, &schema) }.unwrap();
+    RecordBatch::from(StructArray::from(data))
+}
+
+fn two_objects() -> Array2<f64> {
+    // a 2x4 rectangle and a single pixel
+    let mut labels = Array2::<f64>::zeros((6, 8));
+    labels.slice_mut(s![1..3, 2..6]).fill(1.0);
+    labels[[5, 7]] = 4.0;
+
+    labels
+}
+
+#[test]
+fn capi_colocalization_object() {
+    let labels_a = two_objects().into_dyn();
+    let mut labels_b = Array2::<f64>::zeros((6, 8)).into_dyn();
+    labels_b.slice_mut(s![1..3, 4..8]).fill(2.0);
+    let (view_a, view_b) = (labels_a.view(), labels_b.view());
+    let (desc_a, desc_b) = (Descriptor::new(&view_a), Descriptor::new(&view_b));
+
+    // the exported table matches the core results table
+    let spacing = [2.0, 1.0];
+    let mut array = FFI_ArrowArray::empty();
+    let mut schema = FFI_ArrowSchema::empty();
+    let status = unsafe {
+        imgal_colocalization_object(
+            ptr::null(),
+            &desc_a.array(&view_a),
+            &desc_b.array(&view_b),
+            spacing.as_ptr(),
+            spacing.len(),
+            &mut array,
+            &mut schema,
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    let batch = import_record_batch(array, schema);
+    let expected = colocalization::object(
+        labels_a.mapv(|v| v as u64).view(),
+        labels_b.mapv(|v| v as u64).view(),
+        Some(&spacing),
+    )
+    .unwrap()
+    .to_record_batch();
+    assert_eq!(batch, expected);
+    let roi = batch.column_by_name("roi").unwrap().as_string::<i32>();
+    assert_eq!(roi.value(0), "1");
+
+    // negative labels are rejected
+    labels_b[[0, 0]] = -1.0;
+    let view_b = labels_b.view();
+    let mut array = FFI_ArrowArray::empty();
+    let mut schema = FFI_ArrowSchema::empty();
+    let status = unsafe {
+        imgal_colocalization_object(
+            ptr::null(),
+            &desc_a.array(&view_a),
+            &desc_b.array(&view_b),
+            ptr::null(),
+            0,
+            &mut array,
+            &mut schema,
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidArray);
+    assert!(last_error().contains("labels_b"));
+}
+
+#[test]
+fn capi_context() {
+    // the functions called with a context run in its thread pool
+    let ctx = imgal_context_new(2);
+    assert!(!ctx.is_null());
+    assert_eq!(unsafe { imgal_context_num_threads(ctx) }, 2);
+    let data = Array2::from_shape_fn((8, 8), |(r, c)| (r * c) as f64).into_dyn();
+    let view = data.view();
+    let desc = Descriptor::new(&view);
+    let mut t = 0.0;
+    let status = unsafe { imgal_threshold_otsu(ctx, &desc.array(&view), 64, &mut t) };
+    assert_eq!(status, ImgalStatus::Ok);
+    assert_eq!(t, threshold::otsu(view.clone(), None, Some(64)).unwrap());
+
+    // errors are recorded on the context
+    assert!(unsafe { imgal_context_last_error(ctx) }.is_null());
+    let status = unsafe { imgal_threshold_otsu(ctx, &desc.array(&view), 0, &mut t) };
+    assert_eq!(status, ImgalStatus::InvalidParameter);
+    let msg = unsafe { CStr::from_ptr(imgal_context_last_error(ctx)) };
+    assert!(msg.to_str().unwrap().contains("bins"));
+    unsafe { imgal_context_free(ctx) };
+
+    // a context without threads uses the global pool
+    let ctx = imgal_context_new(0);
+    assert_eq!(
+        unsafe { imgal_context_num_threads(ctx) },
+        imgal::config::num_threads()
+    );
+    unsafe { imgal_context_free(ctx) };
+}
+
+#[test]
+fn capi_version() {
+    assert_eq!(imgal_abi_version(), ABI_VERSION);
+    let version = unsafe { CStr::from_ptr(imgal_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn capi_filter_gaussian() {
+    // a strided (transposed) view matches the core function
+    let data = Array2::from_shape_fn((6, 9), |(r, c)| ((r * 7 + c * 3) % 11) as f64);
+    let view = data.t().into_dyn();
+    let desc = Descriptor::new(&view);
+    let sigma = [1.0, 0.5];
+    let mut out = vec![0.0; data.len()];
+    let status = unsafe {
+        imgal_filter_gaussian(
+            ptr::null(),
+            &desc.array(&view),
+            sigma.as_ptr(),
+            sigma.len(),
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    let expected = filter::gaussian(view.clone(), &sigma, None, None).unwrap();
+    assert_eq!(out, expected.iter().copied().collect::<Vec<f64>>());
+
+    // one sigma per axis is required
+    let status = unsafe {
+        imgal_filter_gaussian(
+            ptr::null(),
+            &desc.array(&view),
+            sigma.as_ptr(),
+            1,
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidParameter);
+    assert!(!last_error().is_empty());
+}
+
+#[test]
+fn capi_invalid_arrays() {
+    let data = Array2::<f64>::zeros((3, 4)).into_dyn();
+    let view = data.view();
+    let mut desc = Descriptor::new(&view);
+    let mut out = vec![0.0; data.len()];
+
+    // a null descriptor and a null output
+    let status = unsafe {
+        imgal_simulation_poisson_noise(ptr::null(), ptr::null(), 1.0, 0, out.as_mut_ptr(), 12)
+    };
+    assert_eq!(status, ImgalStatus::NullPointer);
+    assert!(last_error().contains("data"));
+    let status = unsafe {
+        imgal_simulation_poisson_noise(ptr::null(), &desc.array(&view), 1.0, 0, ptr::null_mut(), 12)
+    };
+    assert_eq!(status, ImgalStatus::NullPointer);
+
+    // an output buffer of the wrong length
+    let status = unsafe {
+        imgal_simulation_poisson_noise(
+            ptr::null(),
+            &desc.array(&view),
+            1.0,
+            0,
+            out.as_mut_ptr(),
+            11,
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidArray);
+
+    // negative strides
+    desc.strides[0] = -4;
+    let status = unsafe {
+        imgal_simulation_poisson_noise(
+            ptr::null(),
+            &desc.array(&view),
+            1.0,
+            0,
+            out.as_mut_ptr(),
+            12,
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidArray);
+
+    // a null strides pointer is C-contiguous
+    let arr = ImgalArray {
+        strides: ptr::null(),
+        ..desc.array(&view)
+    };
+    let status =
+        unsafe { imgal_simulation_poisson_noise(ptr::null(), &arr, 1.0, 0, out.as_mut_ptr(), 12) };
+    assert_eq!(status, ImgalStatus::Ok);
+}
+
+#[test]
+fn capi_measure_regionprops() {
+    let labels = two_objects().into_dyn();
+    let intensity = labels.mapv(|v| v * 2.0);
+    let (view, img) = (labels.view(), intensity.view());
+    let desc = Descriptor::new(&view);
+
+    // the exported table matches the core region properties
+    let mut array = FFI_ArrowArray::empty();
+    let mut schema = FFI_ArrowSchema::empty();
+    let status = unsafe {
+        imgal_measure_regionprops(
+            ptr::null(),
+            &desc.array(&view),
+            &desc.array(&img),
+            ptr::null(),
+            2,
+            &mut array,
+            &mut schema,
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    let batch = import_record_batch(array, schema);
+    let lbl = labels.mapv(|v| v as u64).into_dimensionality().unwrap();
+    let img = intensity.into_dimensionality().unwrap();
+    let expected = measure::regionprops(lbl.view(), Some(img.view()), None, None)
+        .unwrap()
+        .to_record_batch();
+    assert_eq!(batch, expected);
+    let label = batch.column(0).as_primitive::<UInt64Type>();
+    assert_eq!(label.values(), &[1, 4]);
+    let mean = batch
+        .column_by_name("mean_intensity")
+        .unwrap()
+        .as_primitive::<Float64Type>();
+    assert_eq!(mean.values(), &[2.0, 8.0]);
+
+    // non-integer labels, a 3-dimensional label image and a null output
+    let mut fractional = labels.clone();
+    fractional[[0, 0]] = 0.5;
+    let fractional_view = fractional.view();
+    let status = unsafe {
+        imgal_measure_regionprops(
+            ptr::null(),
+            &desc.array(&fractional_view),
+            ptr::null(),
+            ptr::null(),
+            2,
+            &mut FFI_ArrowArray::empty(),
+            &mut FFI_ArrowSchema::empty(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidArray);
+    let volume = ArrayD::<f64>::zeros(vec![2, 6, 8]);
+    let volume_view = volume.view();
+    let volume_desc = Descriptor::new(&volume_view);
+    let status = unsafe {
+        imgal_measure_regionprops(
+            ptr::null(),
+            &volume_desc.array(&volume_view),
+            ptr::null(),
+            ptr::null(),
+            2,
+            &mut FFI_ArrowArray::empty(),
+            &mut FFI_ArrowSchema::empty(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidArray);
+    assert!(last_error().contains("2-dimensional"));
+    let status = unsafe {
+        imgal_measure_regionprops(
+            ptr::null(),
+            &desc.array(&view),
+            ptr::null(),
+            ptr::null(),
+            2,
+            ptr::null_mut(),
+            &mut FFI_ArrowSchema::empty(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::NullPointer);
+}
+
+#[test]
+fn capi_phasor_calibration_image() {
+    let data = Array3::from_shape_fn((4, 3, 2), |(r, c, ch)| {
+        0.1 * (r as f64 + 1.0) - 0.05 * c as f64 + 0.2 * ch as f64
+    });
+    let view = data.view().into_dyn();
+    let desc = Descriptor::new(&view);
+    let mask: Vec<u8> = (0..12).map(|i| (i % 5 != 0) as u8).collect();
+    let mut out = vec![0.0; data.len()];
+    let status = unsafe {
+        imgal_phasor_calibration_image(
+            ptr::null(),
+            &desc.array(&view),
+            0.9,
+            0.3,
+            2,
+            mask.as_ptr(),
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    let bool_mask = Array2::from_shape_fn((4, 3), |(r, c)| mask[r * 3 + c] != 0);
+    let expected =
+        calibration::image(data.view(), 0.9, 0.3, Some(bool_mask.view()), Some(2)).unwrap();
+    assert_eq!(out, expected.iter().copied().collect::<Vec<f64>>());
+}
+
+#[test]
+fn capi_phasor_modulation_and_phase() {
+    let (mut m, mut p) = (0.0, 0.0);
+    let status = unsafe {
+        imgal_phasor_modulation_and_phase(ptr::null(), 0.5, 0.4, 4.0, 0.5, &mut m, &mut p)
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    assert_eq!(
+        (m, p),
+        calibration::modulation_and_phase(0.5, 0.4, 4.0, 0.5)
+    );
+
+    // the output pointers must not be null
+    let status = unsafe {
+        imgal_phasor_modulation_and_phase(ptr::null(), 0.5, 0.4, 4.0, 0.5, &mut m, ptr::null_mut())
+    };
+    assert_eq!(status, ImgalStatus::NullPointer);
+    assert!(last_error().contains("out_phase"));
+}
+
+#[test]
+fn capi_phasor_time_domain_image() {
+    let data = decay_image();
+    let view = data.view().into_dyn();
+    let desc = Descriptor::new(&view);
+    let mut out = vec![0.0; 5 * 4 * 2];
+    let status = unsafe {
+        imgal_phasor_time_domain_image(
+            ptr::null(),
+            &desc.array(&view),
+            PERIOD,
+            1.0,
+            2,
+            ptr::null(),
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    let expected = time_domain::image(data.view(), PERIOD, None, None, None).unwrap();
+    assert_eq!(out, expected.iter().copied().collect::<Vec<f64>>());
+
+    // 2-dimensional data and an invalid period are rejected
+    let plane = data.slice(s![.., .., 0]).into_dyn();
+    let plane_desc = Descriptor::new(&plane);
+    let status = unsafe {
+        imgal_phasor_time_domain_image(
+            ptr::null(),
+            &plane_desc.array(&plane),
+            PERIOD,
+            1.0,
+            2,
+            ptr::null(),
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidArray);
+    let status = unsafe {
+        imgal_phasor_time_domain_image(
+            ptr::null(),
+            &desc.array(&view),
+            0.0,
+            1.0,
+            2,
+            ptr::null(),
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::InvalidParameter);
+    assert!(last_error().contains("period"));
+}
+
+#[test]
+fn capi_simulation() {
+    // the decay curve matches the core function
+    let mut curve = vec![0.0; SAMPLES];
+    let status = unsafe {
+        imgal_simulation_ideal_exponential_1d(
+            ptr::null(),
+            SAMPLES,
+            PERIOD,
+            TAUS.as_ptr(),
+            FRACTIONS.as_ptr(),
+            TAUS.len(),
+            TOTAL_COUNTS,
+            curve.as_mut_ptr(),
+            curve.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    let expected =
+        decay::ideal_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS).unwrap();
+    assert_eq!(curve, expected);
+
+    // the same seed applies the same noise as the core function
+    let data = ArrayD::from_shape_vec(vec![SAMPLES], curve).unwrap();
+    let view = data.view();
+    let desc = Descriptor::new(&view);
+    let mut out = vec![0.0; SAMPLES];
+    let status = unsafe {
+        imgal_simulation_poisson_noise(
+            ptr::null(),
+            &desc.array(&view),
+            1.0,
+            42,
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    let expected = noise::poisson(data.view(), 1.0, 42, None).unwrap();
+    assert_eq!(out, expected.into_raw_vec_and_offset().0);
+}
+
+#[test]
+fn capi_threshold() {
+    let mut data = Array2::<f64>::from_shape_fn((10, 10), |(r, c)| 10.0 + ((r + c) % 3) as f64);
+    data.slice_mut(s![3..7, 3..7]).mapv_inplace(|v| v + 90.0);
+    let view = data.view().into_dyn();
+    let desc = Descriptor::new(&view);
+
+    // the Otsu threshold matches the core function
+    let mut t = 0.0;
+    let status = unsafe { imgal_threshold_otsu(ptr::null(), &desc.array(&view), 256, &mut t) };
+    assert_eq!(status, ImgalStatus::Ok);
+    assert_eq!(t, threshold::otsu(view.clone(), None, None).unwrap());
+
+    // the mask is 1 above the threshold
+    let mut out = vec![0_u8; data.len()];
+    let status = unsafe {
+        imgal_threshold_manual_mask(
+            ptr::null(),
+            &desc.array(&view),
+            t,
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    };
+    assert_eq!(status, ImgalStatus::Ok);
+    assert_eq!(out.iter().filter(|&&m| m == 1).count(), 16);
+
+    // zero bins are rejected
+    let status = unsafe { imgal_threshold_otsu(ptr::null(), &desc.array(&view), 0, &mut t) };
+    assert_eq!(status, ImgalStatus::InvalidParameter);
+}
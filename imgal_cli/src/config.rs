@@ -0,0 +1,251 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::CliError;
+
+/// A batch pipeline, read from a TOML or JSON pipeline file.
+///
+/// The `pipeline` key selects the pipeline, the remaining keys are the
+/// parameters of that pipeline:
+///
+/// ```toml
+/// pipeline = "phasor"
+/// period = 12.5
+///
+/// [calibration]
+/// reference = "fluorescein.tif"
+/// tau = 4.0
+///
+/// [[cursors]]
+/// name = "free_nadh"
+/// g = 0.8
+/// s = 0.35
+/// radius = 0.05
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "pipeline", rename_all = "snake_case")]
+pub enum Pipeline {
+    /// Phasor transform, calibration and cursor masks of decay stacks.
+    Phasor(PhasorPipeline),
+    /// Threshold, connected component labeling and region properties of 2D
+    /// images.
+    Threshold(ThresholdPipeline),
+    /// Colocalization metrics of two channels of multi-channel images.
+    Colocalization(ColocalizationPipeline),
+}
+
+/// The parameters of the phasor pipeline.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PhasorPipeline {
+    /// The period (_i.e._ time interval) of the decays.
+    pub period: f64,
+    /// The harmonic value, default = 1.0.
+    #[serde(default)]
+    pub harmonic: Option<f64>,
+    /// The decay axis of the input stacks, default = 0 (_i.e._ one TIFF page
+    /// per time bin).
+    #[serde(default)]
+    pub axis: Option<usize>,
+    /// Pixels with a total photon count <= `min_photons` are excluded, set to
+    /// 0.0 in the phasor image and not selected by the cursors.
+    #[serde(default)]
+    pub min_photons: Option<f64>,
+    /// The optional calibration of the phasor coordinates.
+    #[serde(default)]
+    pub calibration: Option<Calibration>,
+    /// The phasor cursors, each writes one channel of the cursor mask image.
+    #[serde(default)]
+    pub cursors: Vec<Cursor>,
+}
+
+/// The phasor calibration, either known values or a reference decay stack of
+/// a monoexponential dye with a known lifetime.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Calibration {
+    /// The known modulation and phase calibration values.
+    Values { modulation: f64, phase: f64 },
+    /// A reference decay stack, relative paths are relative to the pipeline
+    /// file, and the lifetime, τ, of the reference dye.
+    Reference { reference: PathBuf, tau: f64 },
+}
+
+/// A named circular phasor cursor.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Cursor {
+    /// The cursor name, used as the channel name and CSV column prefix.
+    pub name: String,
+    /// The real component, G, of the cursor center.
+    pub g: f64,
+    /// The imaginary component, S, of the cursor center.
+    pub s: f64,
+    /// The cursor radius.
+    pub radius: f64,
+}
+
+/// The parameters of the threshold pipeline.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdPipeline {
+    /// The threshold method, default = `ThresholdKind::Otsu`.
+    #[serde(default)]
+    pub method: ThresholdKind,
+    /// The threshold value of the `manual` method.
+    #[serde(default)]
+    pub value: Option<f64>,
+    /// The percentile of the `percentile` method, default = 50.0.
+    #[serde(default)]
+    pub p: Option<f64>,
+    /// The number of histogram bins of the `otsu` method, default = 256.
+    #[serde(default)]
+    pub bins: Option<usize>,
+    /// The pixel connectivity of the objects, default = `"full"`.
+    #[serde(default)]
+    pub connectivity: ConnectivityKind,
+}
+
+/// The threshold methods of the threshold pipeline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdKind {
+    /// A fixed threshold value.
+    Manual,
+    /// Otsu's method.
+    #[default]
+    Otsu,
+    /// A percentile of the pixel values.
+    Percentile,
+}
+
+/// The pixel connectivity of the labeled objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectivityKind {
+    /// 4-connectivity.
+    Face,
+    /// 8-connectivity.
+    #[default]
+    Full,
+}
+
+/// The parameters of the colocalization pipeline.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColocalizationPipeline {
+    /// The TIFF page of channel `A`, default = 0.
+    #[serde(default)]
+    pub channel_a: Option<usize>,
+    /// The TIFF page of channel `B`, default = 1.
+    #[serde(default)]
+    pub channel_b: Option<usize>,
+    /// If `true`, compute the Manders' coefficients above the Costes
+    /// thresholds, otherwise above 0, default = `true`.
+    #[serde(default)]
+    pub costes: Option<bool>,
+}
+
+impl Pipeline {
+    /// Read a pipeline file.
+    ///
+    /// # Description
+    ///
+    /// The file format is chosen by the file extension, `.toml` or `.json`.
+    /// Relative paths in the pipeline (_e.g._ a calibration reference) are
+    /// resolved against the directory of the pipeline file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path of the pipeline file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Pipeline)`: The pipeline.
+    /// * `Err(CliError)`: If the file can not be read, has an unsupported
+    ///   extension or is not a valid pipeline.
+    pub fn from_path(path: &Path) -> Result<Self, CliError> {
+        let text = fs::read_to_string(path).map_err(|e| CliError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        let mut pipeline = match ext.as_deref() {
+            Some("toml") => Self::from_toml(&text),
+            Some("json") => Self::from_json(&text),
+            _ => Err("the extension must be \".toml\" or \".json\"".to_string()),
+        }
+        .map_err(|reason| CliError::Config {
+            path: path.to_path_buf(),
+            reason,
+        })?;
+
+        // resolve relative paths against the pipeline file directory
+        if let Pipeline::Phasor(PhasorPipeline {
+            calibration: Some(Calibration::Reference { reference, .. }),
+            ..
+        }) = &mut pipeline
+            && reference.is_relative()
+            && let Some(dir) = path.parent()
+        {
+            *reference = dir.join(&reference);
+        }
+
+        Ok(pipeline)
+    }
+
+    /// Parse a TOML pipeline.
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let pipeline: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        pipeline.validate()?;
+
+        Ok(pipeline)
+    }
+
+    /// Parse a JSON pipeline.
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let pipeline: Self = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        pipeline.validate()?;
+
+        Ok(pipeline)
+    }
+
+    /// Check the parameter combinations that can not be expressed by the
+    /// pipeline types, the parameter values are checked by `imgal`.
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Pipeline::Phasor(p) => {
+                for (i, c) in p.cursors.iter().enumerate() {
+                    if c.name.is_empty() {
+                        return Err(format!("cursor {} has an empty name", i));
+                    }
+                    if p.cursors[..i].iter().any(|o| o.name == c.name) {
+                        return Err(format!("the cursor name \"{}\" is not unique", c.name));
+                    }
+                }
+            }
+            Pipeline::Threshold(p) => {
+                if p.method == ThresholdKind::Manual && p.value.is_none() {
+                    return Err("the \"manual\" method requires a threshold \"value\"".to_string());
+                }
+            }
+            Pipeline::Colocalization(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// The pipeline name, as given by the `pipeline` key.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pipeline::Phasor(_) => "phasor",
+            Pipeline::Threshold(_) => "threshold",
+            Pipeline::Colocalization(_) => "colocalization",
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use imgal::error::ImgalError;
+
+/// The error type returned by the command-line tool.
+#[derive(Debug)]
+pub enum CliError {
+    /// The pipeline file could not be parsed or has invalid values.
+    Config { path: PathBuf, reason: String },
+    /// An input image is not supported by the pipeline.
+    InvalidInput { path: PathBuf, reason: String },
+    /// A file or directory could not be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// A TIFF file could not be decoded or encoded.
+    Tiff { path: PathBuf, reason: String },
+    /// An imgal function failed.
+    Imgal(ImgalError),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Config { path, reason } => {
+                write!(f, "Invalid pipeline file {}: {}", path.display(), reason)
+            }
+            CliError::InvalidInput { path, reason } => {
+                write!(f, "Invalid input image {}: {}", path.display(), reason)
+            }
+            CliError::Io { path, source } => {
+                write!(f, "Failed to access {}: {}", path.display(), source)
+            }
+            CliError::Tiff { path, reason } => {
+                write!(f, "Invalid TIFF file {}: {}", path.display(), reason)
+            }
+            CliError::Imgal(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for CliError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CliError::Io { source, .. } => Some(source),
+            CliError::Imgal(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ImgalError> for CliError {
+    fn from(e: ImgalError) -> Self {
+        CliError::Imgal(e)
+    }
+}
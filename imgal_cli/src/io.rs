@@ -0,0 +1,294 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use ndarray::{ArrayD, ArrayView3, IxDyn};
+use tiff::ColorType;
+use tiff::decoder::{Decoder, DecodingResult, Limits};
+use tiff::encoder::{TiffEncoder, TiffValue, colortype};
+use tiff::tags::Tag;
+
+use crate::error::CliError;
+
+/// A pixel type that can be written to an OME-TIFF file.
+pub trait OmePixel: Copy
+where
+    [Self]: TiffValue,
+{
+    /// The TIFF color type of a single channel page.
+    type Color: colortype::ColorType<Inner = Self>;
+    /// The OME pixel type name.
+    const OME_TYPE: &'static str;
+}
+
+impl OmePixel for u8 {
+    type Color = colortype::Gray8;
+    const OME_TYPE: &'static str = "uint8";
+}
+
+impl OmePixel for u32 {
+    type Color = colortype::Gray32;
+    const OME_TYPE: &'static str = "uint32";
+}
+
+impl OmePixel for f32 {
+    type Color = colortype::Gray32Float;
+    const OME_TYPE: &'static str = "float";
+}
+
+/// List the TIFF files of a directory.
+///
+/// # Arguments
+///
+/// * `dir`: The input directory.
+///
+/// # Returns
+///
+/// * `Ok(Vec<PathBuf>)`: The `.tif` and `.tiff` files of the directory (not
+///   recursive), sorted by path.
+/// * `Err(CliError)`: If the directory can not be read.
+pub fn list_tiffs(dir: &Path) -> Result<Vec<PathBuf>, CliError> {
+    let io_error = |e| CliError::Io {
+        path: dir.to_path_buf(),
+        source: e,
+    };
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let is_tiff = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("tif") || e.eq_ignore_ascii_case("tiff"));
+        if is_tiff && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Read a grayscale TIFF file as a 2 or 3-dimensional image.
+///
+/// # Description
+///
+/// This function decodes every page (_i.e._ image file directory) of a
+/// (multi-page) TIFF or OME-TIFF file and converts the pixel values to `f64`.
+/// The pages must be single channel grayscale images of the same size. A
+/// single page is returned as a 2-dimensional (row, col) image, multiple pages
+/// as a 3-dimensional (page, row, col) stack. The OME metadata is not
+/// interpreted, the pages are stacked in file order.
+///
+/// # Arguments
+///
+/// * `path`: The path of the TIFF file.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The image or stack.
+/// * `Err(CliError)`: If the file can not be read or decoded. If a page is not
+///   a grayscale image or the pages differ in size.
+pub fn read_tiff(path: &Path) -> Result<ArrayD<f64>, CliError> {
+    let tiff_error = |e: tiff::TiffError| CliError::Tiff {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    };
+    let file = File::open(path).map_err(|e| CliError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let mut decoder = Decoder::new(BufReader::new(file))
+        .map_err(tiff_error)?
+        .with_limits(Limits::unlimited());
+
+    // decode and convert each page
+    let (width, height) = decoder.dimensions().map_err(tiff_error)?;
+    let mut pages = 0;
+    let mut data: Vec<f64> = Vec::new();
+    loop {
+        if decoder.dimensions().map_err(tiff_error)? != (width, height) {
+            return Err(CliError::Tiff {
+                path: path.to_path_buf(),
+                reason: format!("page {} differs in size from the first page", pages),
+            });
+        }
+        match decoder.colortype().map_err(tiff_error)? {
+            ColorType::Gray(_) => {}
+            c => {
+                return Err(CliError::Tiff {
+                    path: path.to_path_buf(),
+                    reason: format!(
+                        "page {} has the color type {:?}, only grayscale pages are supported",
+                        pages, c
+                    ),
+                });
+            }
+        }
+        match decoder.read_image().map_err(tiff_error)? {
+            DecodingResult::U8(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::U16(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::U32(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::U64(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::I8(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::I16(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::I32(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::I64(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::F32(v) => data.extend(v.iter().map(|&p| p as f64)),
+            DecodingResult::F64(v) => data.extend(v),
+        }
+        pages += 1;
+        if !decoder.more_images() {
+            break;
+        }
+        decoder.next_image().map_err(tiff_error)?;
+    }
+
+    let (rows, cols) = (height as usize, width as usize);
+    let shape = if pages == 1 {
+        vec![rows, cols]
+    } else {
+        vec![pages, rows, cols]
+    };
+
+    Ok(ArrayD::from_shape_vec(IxDyn(&shape), data).unwrap())
+}
+
+/// Write a multi-channel image as an OME-TIFF file.
+///
+/// # Description
+///
+/// This function writes each channel of a (ch, row, col) image as one TIFF
+/// page and stores the OME-XML metadata, with the dimension order `XYCZT` and
+/// the given channel names, in the image description of the first page.
+///
+/// # Arguments
+///
+/// * `path`: The path of the output file, by convention ending in `.ome.tif`.
+/// * `data`: The 3-dimensional (ch, row, col) image.
+/// * `channels`: The channel names, one per channel.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the file was written.
+/// * `Err(CliError)`: If the file can not be written.
+pub fn write_ome_tiff<T>(
+    path: &Path,
+    data: ArrayView3<T>,
+    channels: &[&str],
+) -> Result<(), CliError>
+where
+    T: OmePixel,
+    [T]: TiffValue,
+{
+    let tiff_error = |e: tiff::TiffError| CliError::Tiff {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    };
+    let file = File::create(path).map_err(|e| CliError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let (ch, rows, cols) = data.dim();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let xml = ome_xml(&name, T::OME_TYPE, (ch, rows, cols), channels);
+
+    // write one page per channel, the metadata in the first page
+    let mut encoder = TiffEncoder::new(BufWriter::new(file)).map_err(tiff_error)?;
+    for (c, plane) in data.outer_iter().enumerate() {
+        let pixels: Vec<T> = plane.iter().copied().collect();
+        let mut image = encoder
+            .new_image::<T::Color>(cols as u32, rows as u32)
+            .map_err(tiff_error)?;
+        if c == 0 {
+            image
+                .encoder()
+                .write_tag(Tag::ImageDescription, xml.as_str())
+                .map_err(tiff_error)?;
+        }
+        image.write_data(&pixels).map_err(tiff_error)?;
+    }
+
+    Ok(())
+}
+
+/// Write a table as a CSV file.
+///
+/// # Arguments
+///
+/// * `path`: The path of the output file.
+/// * `header`: The column names.
+/// * `rows`: The rows of the table, each with one field per column.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the file was written.
+/// * `Err(CliError)`: If the file can not be written.
+pub fn write_csv(path: &Path, header: &[String], rows: &[Vec<String>]) -> Result<(), CliError> {
+    let io_error = |e| CliError::Io {
+        path: path.to_path_buf(),
+        source: e,
+    };
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+    for fields in std::iter::once(header).chain(rows.iter().map(|r| r.as_slice())) {
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        writeln!(writer, "{}", line.join(",")).map_err(io_error)?;
+    }
+
+    writer.flush().map_err(io_error)
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Create the OME-XML metadata of a single (ch, row, col) image.
+fn ome_xml(name: &str, pixel_type: &str, dim: (usize, usize, usize), channels: &[&str]) -> String {
+    let (ch, rows, cols) = dim;
+    let channel_xml: String = (0..ch)
+        .map(|c| {
+            let channel_name = channels.get(c).copied().unwrap_or_default();
+            format!(
+                "<Channel ID=\"Channel:0:{}\" Name=\"{}\" SamplesPerPixel=\"1\"/>",
+                c,
+                xml_escape(channel_name)
+            )
+        })
+        .collect();
+    format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+            "<OME xmlns=\"http://www.openmicroscopy.org/Schemas/OME/2016-06\" Creator=\"imgal-cli {}\">",
+            "<Image ID=\"Image:0\" Name=\"{}\">",
+            "<Pixels ID=\"Pixels:0\" DimensionOrder=\"XYCZT\" Type=\"{}\" BigEndian=\"{}\" ",
+            "SizeX=\"{}\" SizeY=\"{}\" SizeC=\"{}\" SizeZ=\"1\" SizeT=\"1\">",
+            "{}<TiffData IFD=\"0\" PlaneCount=\"{}\"/>",
+            "</Pixels></Image></OME>"
+        ),
+        env!("CARGO_PKG_VERSION"),
+        xml_escape(name),
+        pixel_type,
+        cfg!(target_endian = "big"),
+        cols,
+        rows,
+        ch,
+        channel_xml,
+        ch
+    )
+}
+
+/// Escape the XML special characters of an attribute value.
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,13 @@
+//! Batch processing of image directories with `imgal` pipelines.
+//!
+//! A pipeline file (TOML or JSON) selects one of the `phasor`, `threshold` or
+//! `colocalization` pipelines and its parameters, which `pipeline::run`
+//! applies to every TIFF file of an input directory. The per-file images are
+//! written as OME-TIFF files and the measurements as CSV tables.
+pub mod config;
+pub use config::Pipeline;
+pub mod error;
+pub use error::CliError;
+pub mod io;
+pub mod pipeline;
+pub use pipeline::{FileReport, run};
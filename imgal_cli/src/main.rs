@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use imgal::config::set_num_threads;
+use imgal_cli::{Pipeline, run};
+
+/// Batch process directories of TIFF images with imgal pipelines.
+#[derive(Parser)]
+#[command(name = "imgal-cli", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a pipeline over every TIFF file of a directory.
+    Run {
+        /// The TOML or JSON pipeline file.
+        pipeline: PathBuf,
+        /// The input directory.
+        #[arg(short, long)]
+        input: PathBuf,
+        /// The output directory, created if needed.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// The number of threads, default = all available cores.
+        #[arg(short, long)]
+        threads: Option<usize>,
+    },
+    /// Check a pipeline file without running it.
+    Check {
+        /// The TOML or JSON pipeline file.
+        pipeline: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run {
+            pipeline,
+            input,
+            output,
+            threads,
+        } => {
+            let pipeline = match Pipeline::from_path(&pipeline) {
+                Ok(p) => p,
+                Err(e) => return fail(e),
+            };
+            if let Some(n) = threads
+                && let Err(e) = set_num_threads(n)
+            {
+                return fail(e);
+            }
+            let reports = run(&pipeline, &input, &output, |r| {
+                for w in &r.warnings {
+                    eprintln!("warning: {}: {}", r.path.display(), w);
+                }
+                match &r.error {
+                    Some(e) => eprintln!("error: {}: {}", r.path.display(), e),
+                    None => eprintln!("processed {}", r.path.display()),
+                }
+            });
+            match reports {
+                Ok(reports) => {
+                    let failed = reports.iter().filter(|r| r.error.is_some()).count();
+                    eprintln!(
+                        "{} of {} files processed with the {} pipeline",
+                        reports.len() - failed,
+                        reports.len(),
+                        pipeline.name()
+                    );
+                    if failed > 0 {
+                        ExitCode::FAILURE
+                    } else {
+                        ExitCode::SUCCESS
+                    }
+                }
+                Err(e) => fail(e),
+            }
+        }
+        Command::Check { pipeline } => match Pipeline::from_path(&pipeline) {
+            Ok(p) => {
+                println!("{:#?}", p);
+                ExitCode::SUCCESS
+            }
+            Err(e) => fail(e),
+        },
+    }
+}
+
+/// Print an error and return the failure exit code.
+fn fail<E: std::fmt::Display>(e: E) -> ExitCode {
+    eprintln!("error: {}", e);
+    ExitCode::FAILURE
+}
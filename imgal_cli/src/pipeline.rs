@@ -0,0 +1,435 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ndarray::{Array2, Array3, ArrayD, Axis, Ix2, Ix3, Zip, stack};
+
+use imgal::colocalization;
+use imgal::error::{ImgalError, Warning, warning};
+use imgal::measure::{RegionProps, regionprops};
+use imgal::morphology::{Connectivity, label};
+use imgal::parameter::omega;
+use imgal::phasor::analysis::PhasorCursor;
+use imgal::phasor::{calibration, spectral, time_domain};
+use imgal::threshold;
+
+use crate::config::{
+    Calibration, ColocalizationPipeline, ConnectivityKind, PhasorPipeline, Pipeline, ThresholdKind,
+    ThresholdPipeline,
+};
+use crate::error::CliError;
+use crate::io;
+
+/// The outcome of processing one input file.
+#[derive(Debug)]
+pub struct FileReport {
+    /// The input file.
+    pub path: PathBuf,
+    /// The warnings emitted while processing the file.
+    pub warnings: Vec<Warning>,
+    /// The error, if the file could not be processed.
+    pub error: Option<CliError>,
+}
+
+/// Run a pipeline over every TIFF file of a directory.
+///
+/// # Description
+///
+/// This function runs the pipeline on each `.tif` and `.tiff` file of the
+/// input directory, in path order, and writes the per-file outputs and a
+/// summary table, `<pipeline>.csv` with one row per processed file, to the
+/// output directory:
+///
+/// * `phasor`: `<name>_phasor.ome.tif`, the float32 (G, S) image, and
+///   `<name>_cursors.ome.tif`, one uint8 mask channel per cursor (255 for the
+///   selected pixels). The summary has the number of pixels, the mean G and S
+///   and the pixel count and fraction of each cursor.
+/// * `threshold`: `<name>_labels.ome.tif`, the uint32 object labels, and
+///   `<name>_regionprops.csv`, the region properties of each object. The
+///   summary has the threshold value and the number of objects.
+/// * `colocalization`: The summary has the Pearson correlation coefficient,
+///   the Manders' M1 and M2 coefficients and the thresholds they were computed
+///   above.
+///
+/// A file that fails does not stop the batch, its error is reported and it is
+/// left out of the summary.
+///
+/// # Arguments
+///
+/// * `pipeline`: The pipeline to run.
+/// * `input`: The input directory.
+/// * `output`: The output directory, created if needed.
+/// * `progress`: Called with the report of each file after it was processed.
+///
+/// # Returns
+///
+/// * `Ok(Vec<FileReport>)`: The report of each input file.
+/// * `Err(CliError)`: If a directory or the summary can not be accessed. If
+///   the phasor calibration reference can not be read.
+pub fn run<F>(
+    pipeline: &Pipeline,
+    input: &Path,
+    output: &Path,
+    mut progress: F,
+) -> Result<Vec<FileReport>, CliError>
+where
+    F: FnMut(&FileReport),
+{
+    fs::create_dir_all(output).map_err(|e| CliError::Io {
+        path: output.to_path_buf(),
+        source: e,
+    })?;
+    let files = io::list_tiffs(input)?;
+
+    // compute the values shared by all files
+    let cal = match pipeline {
+        Pipeline::Phasor(p) => phasor_calibration(p)?,
+        _ => None,
+    };
+
+    let mut rows = Vec::new();
+    let mut reports = Vec::new();
+    for path in files {
+        let (result, warnings) = warning::collect(|| {
+            let data = io::read_tiff(&path)?;
+            let name = stem(&path);
+            match pipeline {
+                Pipeline::Phasor(p) => phasor(p, cal, &path, data, &output.join(&name)),
+                Pipeline::Threshold(p) => threshold(p, &path, data, &output.join(&name)),
+                Pipeline::Colocalization(p) => colocalization(p, &path, data),
+            }
+        });
+        let error = match result {
+            Ok(row) => {
+                let file = path.file_name().unwrap_or_default().to_string_lossy();
+                rows.push([vec![file.into_owned()], row].concat());
+                None
+            }
+            Err(e) => Some(e),
+        };
+        let report = FileReport {
+            path,
+            warnings,
+            error,
+        };
+        progress(&report);
+        reports.push(report);
+    }
+
+    // write the summary table
+    let header = [vec!["file".to_string()], summary_header(pipeline)].concat();
+    io::write_csv(
+        &output.join(format!("{}.csv", pipeline.name())),
+        &header,
+        &rows,
+    )?;
+
+    Ok(reports)
+}
+
+/// The summary table columns of a pipeline, without the file column.
+fn summary_header(pipeline: &Pipeline) -> Vec<String> {
+    let columns: &[&str] = match pipeline {
+        Pipeline::Phasor(_) => &["pixels", "mean_g", "mean_s"],
+        Pipeline::Threshold(_) => &["threshold", "objects"],
+        Pipeline::Colocalization(_) => &["pearson", "m1", "m2", "threshold_a", "threshold_b"],
+    };
+    let mut header: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+    if let Pipeline::Phasor(p) = pipeline {
+        for c in &p.cursors {
+            header.push(format!("{}_pixels", c.name));
+            header.push(format!("{}_fraction", c.name));
+        }
+    }
+
+    header
+}
+
+/// Compute the modulation and phase calibration values of a phasor pipeline.
+fn phasor_calibration(p: &PhasorPipeline) -> Result<Option<(f64, f64)>, CliError> {
+    match &p.calibration {
+        None => Ok(None),
+        Some(Calibration::Values { modulation, phase }) => Ok(Some((*modulation, *phase))),
+        Some(Calibration::Reference { reference, tau }) => {
+            let data = decay_stack(reference, io::read_tiff(reference)?)?;
+            let a = decay_axis(p)?;
+
+            // sum the decays of all pixels into a single reference decay
+            let (lo, hi) = match a {
+                0 => (1, 2),
+                1 => (0, 2),
+                _ => (0, 1),
+            };
+            let curve = data.sum_axis(Axis(hi)).sum_axis(Axis(lo));
+            let curve = curve.as_slice().unwrap();
+            let g = time_domain::real(curve, p.period, p.harmonic);
+            let s = time_domain::imaginary(curve, p.period, p.harmonic);
+            let w = p.harmonic.unwrap_or(1.0) * omega(p.period);
+
+            Ok(Some(calibration::modulation_and_phase(g, s, *tau, w)))
+        }
+    }
+}
+
+/// Run the phasor pipeline on one decay stack.
+fn phasor(
+    p: &PhasorPipeline,
+    cal: Option<(f64, f64)>,
+    path: &Path,
+    data: ArrayD<f64>,
+    prefix: &Path,
+) -> Result<Vec<String>, CliError> {
+    let data = decay_stack(path, data)?;
+    let a = decay_axis(p)?;
+
+    // exclude the pixels at or below the photon count threshold
+    let mask = p
+        .min_photons
+        .map(|t| data.sum_axis(Axis(a)).mapv(|v| v > t));
+    let msk = mask.as_ref().map(|m| m.view());
+
+    // transform, calibrate and select the phasor coordinates
+    let mut gs = time_domain::image(data.view(), p.period, msk, p.harmonic, Some(a))?;
+    if let Some((modulation, phase)) = cal {
+        gs = calibration::image(gs.view(), modulation, phase, msk, None)?;
+    }
+    let cursors = p
+        .cursors
+        .iter()
+        .map(|c| {
+            let cursor = PhasorCursor {
+                g: c.g,
+                s: c.s,
+                radius: c.radius,
+            };
+            spectral::cursor_mask(gs.view(), cursor, msk, None)
+        })
+        .collect::<Result<Vec<Array2<bool>>, ImgalError>>()?;
+
+    // write the (G, S) and cursor images
+    let gs_out = gs.view().permuted_axes([2, 0, 1]).mapv(|v| v as f32);
+    io::write_ome_tiff(
+        &with_suffix(prefix, "_phasor.ome.tif"),
+        gs_out.view(),
+        &["G", "S"],
+    )?;
+    if !cursors.is_empty() {
+        let views: Vec<_> = cursors.iter().map(|c| c.view()).collect();
+        let masks = stack(Axis(0), &views)
+            .unwrap()
+            .mapv(|m| if m { 255u8 } else { 0 });
+        let names: Vec<&str> = p.cursors.iter().map(|c| c.name.as_str()).collect();
+        io::write_ome_tiff(
+            &with_suffix(prefix, "_cursors.ome.tif"),
+            masks.view(),
+            &names,
+        )?;
+    }
+
+    // summarize the (masked) pixels with finite coordinates
+    let used = Array2::from_shape_fn((gs.dim().0, gs.dim().1), |(r, c)| {
+        mask.as_ref().is_none_or(|m| m[[r, c]]) && gs[[r, c, 0]].is_finite()
+    });
+    let pixels = used.iter().filter(|&&u| u).count();
+    let (mut sum_g, mut sum_s) = (0.0, 0.0);
+    Zip::from(&used).and(gs.lanes(Axis(2))).for_each(|&u, ln| {
+        if u {
+            sum_g += ln[0];
+            sum_s += ln[1];
+        }
+    });
+    let mut row = vec![
+        pixels.to_string(),
+        (sum_g / pixels as f64).to_string(),
+        (sum_s / pixels as f64).to_string(),
+    ];
+    for c in &cursors {
+        let selected = Zip::from(c)
+            .and(&used)
+            .fold(0, |n, &m, &u| n + usize::from(m && u));
+        row.push(selected.to_string());
+        row.push((selected as f64 / pixels as f64).to_string());
+    }
+
+    Ok(row)
+}
+
+/// Run the threshold pipeline on one image.
+fn threshold(
+    p: &ThresholdPipeline,
+    path: &Path,
+    data: ArrayD<f64>,
+    prefix: &Path,
+) -> Result<Vec<String>, CliError> {
+    let data = data
+        .into_dimensionality::<Ix2>()
+        .map_err(|_| CliError::InvalidInput {
+            path: path.to_path_buf(),
+            reason: "the threshold pipeline requires single page (2-dimensional) images"
+                .to_string(),
+        })?;
+
+    // threshold and label the objects
+    let t = match p.method {
+        ThresholdKind::Manual => p.value.unwrap_or_default(), // checked by the parser
+        ThresholdKind::Otsu => threshold::otsu(data.view().into_dyn(), None, p.bins)?,
+        ThresholdKind::Percentile => {
+            threshold::percentile(data.view().into_dyn(), p.p.unwrap_or(50.0), None)?
+        }
+    };
+    let connectivity = match p.connectivity {
+        ConnectivityKind::Face => Connectivity::Face,
+        ConnectivityKind::Full => Connectivity::Full,
+    };
+    let mask = threshold::manual_mask(data.view().into_dyn(), t);
+    let labels = label(mask.view(), Some(connectivity), None)?
+        .into_dimensionality::<Ix2>()
+        .unwrap();
+    let props = regionprops(labels.view(), Some(data.view()), None, None)?;
+
+    // write the label image and the region properties
+    io::write_ome_tiff(
+        &with_suffix(prefix, "_labels.ome.tif"),
+        labels.view().insert_axis(Axis(0)),
+        &["labels"],
+    )?;
+    let (header, rows) = regionprops_table(&props);
+    io::write_csv(&with_suffix(prefix, "_regionprops.csv"), &header, &rows)?;
+
+    Ok(vec![t.to_string(), props.len().to_string()])
+}
+
+/// Run the colocalization pipeline on one multi-channel image.
+fn colocalization(
+    p: &ColocalizationPipeline,
+    path: &Path,
+    data: ArrayD<f64>,
+) -> Result<Vec<String>, CliError> {
+    let ca = p.channel_a.unwrap_or(0);
+    let cb = p.channel_b.unwrap_or(1);
+    let pages = if data.ndim() == 3 {
+        data.len_of(Axis(0))
+    } else {
+        1
+    };
+    if data.ndim() != 3 || ca >= pages || cb >= pages {
+        return Err(CliError::InvalidInput {
+            path: path.to_path_buf(),
+            reason: format!(
+                "the channels {} and {} are not pages of the {} page image",
+                ca, cb, pages
+            ),
+        });
+    }
+    let a = data.index_axis(Axis(0), ca);
+    let b = data.index_axis(Axis(0), cb);
+
+    // compute the metrics, the Manders' coefficients above the thresholds
+    let r = colocalization::pearson(a.clone(), b.clone(), None, None, None)?;
+    let (ta, tb) = if p.costes.unwrap_or(true) {
+        colocalization::costes_threshold(a.clone(), b.clone(), None)?
+    } else {
+        (0.0, 0.0)
+    };
+    let (m1, m2) = colocalization::manders(a, b, None, Some(ta), Some(tb))?;
+
+    Ok([r, m1, m2, ta, tb].iter().map(|v| v.to_string()).collect())
+}
+
+/// Convert the input of a phasor pipeline to a 3-dimensional decay stack.
+fn decay_stack(path: &Path, data: ArrayD<f64>) -> Result<Array3<f64>, CliError> {
+    data.into_dimensionality::<Ix3>()
+        .map_err(|_| CliError::InvalidInput {
+            path: path.to_path_buf(),
+            reason: "the phasor pipeline requires multi-page (3-dimensional) decay stacks"
+                .to_string(),
+        })
+}
+
+/// Get the validated decay axis of a phasor pipeline.
+fn decay_axis(p: &PhasorPipeline) -> Result<usize, CliError> {
+    let a = p.axis.unwrap_or(0);
+    if a >= 3 {
+        return Err(ImgalError::InvalidAxis {
+            axis_idx: a,
+            dim_len: 3,
+        }
+        .into());
+    }
+
+    Ok(a)
+}
+
+/// Convert region properties to a table with one row per object.
+fn regionprops_table(props: &RegionProps<u32>) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut header: Vec<String> = [
+        "label",
+        "area",
+        "centroid_row",
+        "centroid_col",
+        "bbox_min_row",
+        "bbox_min_col",
+        "bbox_max_row",
+        "bbox_max_col",
+        "perimeter",
+        "eccentricity",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect();
+    let intensity = [
+        ("mean_intensity", &props.mean_intensity),
+        ("min_intensity", &props.min_intensity),
+        ("max_intensity", &props.max_intensity),
+    ];
+    for (h, col) in &intensity {
+        if col.is_some() {
+            header.push(h.to_string());
+        }
+    }
+    let rows = (0..props.len())
+        .map(|i| {
+            let mut row = vec![
+                props.label[i].to_string(),
+                props.area[i].to_string(),
+                props.centroid_row[i].to_string(),
+                props.centroid_col[i].to_string(),
+                props.bbox_min_row[i].to_string(),
+                props.bbox_min_col[i].to_string(),
+                props.bbox_max_row[i].to_string(),
+                props.bbox_max_col[i].to_string(),
+                props.perimeter[i].to_string(),
+                props.eccentricity[i].to_string(),
+            ];
+            for (_, col) in &intensity {
+                if let Some(v) = col {
+                    row.push(v[i].to_string());
+                }
+            }
+            row
+        })
+        .collect();
+
+    (header, rows)
+}
+
+/// Get the name of an input file without the `.tif`, `.tiff` or `.ome.tif`
+/// extension.
+pub fn stem(path: &Path) -> String {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let lower = name.to_ascii_lowercase();
+    for ext in [".ome.tiff", ".ome.tif", ".tiff", ".tif"] {
+        if lower.ends_with(ext) {
+            return name[..name.len() - ext.len()].to_string();
+        }
+    }
+
+    name.into_owned()
+}
+
+/// Append a suffix to the file name of an output path prefix.
+fn with_suffix(prefix: &Path, suffix: &str) -> PathBuf {
+    let mut name = prefix.as_os_str().to_os_string();
+    name.push(suffix);
+
+    PathBuf::from(name)
+}
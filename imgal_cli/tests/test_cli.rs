@@ -0,0 +1,317 @@
+use std::env;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use ndarray::{Array2, Array3, Axis};
+use tiff::decoder::Decoder;
+use tiff::tags::Tag;
+
+use imgal::parameter::omega;
+use imgal::phasor::{plot, time_domain};
+use imgal::simulation::decay;
+use imgal_cli::config::{Calibration, ConnectivityKind, Pipeline, ThresholdKind};
+use imgal_cli::{io, pipeline, run};
+
+// simulated monoexponential decay parameters
+const SAMPLES: usize = 64;
+const PERIOD: f64 = 12.5;
+const TAU: f64 = 2.0;
+const TOTAL_COUNTS: f64 = 5000.0;
+
+// helper functions
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("imgal_cli_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn read_csv(path: &Path) -> Vec<Vec<String>> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|l| l.split(',').map(|f| f.to_string()).collect())
+        .collect()
+}
+
+fn write_decay_stack(path: &Path) -> Array3<f64> {
+    // (row, col, t) counts written as one page per time bin
+    let data = decay::ideal_exponential_3d(SAMPLES, PERIOD, &[TAU], &[1.0], TOTAL_COUNTS, (6, 5))
+        .unwrap()
+        .mapv(f64::round);
+    let pages = data.view().permuted_axes([2, 0, 1]).mapv(|v| v as u32);
+    io::write_ome_tiff(path, pages.view(), &[]).unwrap();
+    data
+}
+
+fn run_quiet(pipeline: &Pipeline, input: &Path, output: &Path) -> Vec<pipeline::FileReport> {
+    run(pipeline, input, output, |_| {}).unwrap()
+}
+
+#[test]
+fn config_parse_toml_and_json() {
+    let toml = r#"
+        pipeline = "threshold"
+        method = "percentile"
+        p = 95.0
+        connectivity = "face"
+    "#;
+    let json =
+        r#"{"pipeline": "threshold", "method": "percentile", "p": 95.0, "connectivity": "face"}"#;
+    let p = Pipeline::from_toml(toml).unwrap();
+    assert_eq!(p, Pipeline::from_json(json).unwrap());
+    match p {
+        Pipeline::Threshold(t) => {
+            assert_eq!(t.method, ThresholdKind::Percentile);
+            assert_eq!(t.p, Some(95.0));
+            assert_eq!(t.connectivity, ConnectivityKind::Face);
+            assert_eq!(t.bins, None);
+        }
+        _ => panic!("expected the threshold pipeline"),
+    }
+
+    // unknown keys, missing manual values and duplicate cursors are rejected
+    assert!(Pipeline::from_toml("pipeline = \"threshold\"\nmethdo = \"otsu\"").is_err());
+    assert!(Pipeline::from_toml("pipeline = \"threshold\"\nmethod = \"manual\"").is_err());
+    assert!(Pipeline::from_toml("pipeline = \"segment\"").is_err());
+    let cursors = r#"
+        pipeline = "phasor"
+        period = 12.5
+        [[cursors]]
+        name = "a"
+        g = 0.5
+        s = 0.3
+        radius = 0.1
+        [[cursors]]
+        name = "a"
+        g = 0.6
+        s = 0.3
+        radius = 0.1
+    "#;
+    assert!(Pipeline::from_toml(cursors).is_err());
+}
+
+#[test]
+fn config_from_path() {
+    let dir = temp_dir("config_from_path");
+    let path = dir.join("pipeline.toml");
+    let text = "pipeline = \"phasor\"\nperiod = 12.5\n\n[calibration]\nreference = \"ref.tif\"\ntau = 4.0\n";
+    fs::write(&path, text).unwrap();
+
+    // relative references are resolved against the pipeline file directory
+    match Pipeline::from_path(&path).unwrap() {
+        Pipeline::Phasor(p) => {
+            assert_eq!(p.period, 12.5);
+            assert_eq!(
+                p.calibration,
+                Some(Calibration::Reference {
+                    reference: dir.join("ref.tif"),
+                    tau: 4.0
+                })
+            );
+        }
+        _ => panic!("expected the phasor pipeline"),
+    }
+
+    // the file format is chosen by the extension
+    let yaml = dir.join("pipeline.yaml");
+    fs::write(&yaml, text).unwrap();
+    assert!(Pipeline::from_path(&yaml).is_err());
+}
+
+#[test]
+fn io_ome_tiff_round_trip() {
+    let dir = temp_dir("io_ome_tiff_round_trip");
+    let stack = Array3::from_shape_fn((3, 4, 5), |(c, r, col)| (c * 100 + r * 10 + col) as u32);
+    let path = dir.join("stack.ome.tif");
+    io::write_ome_tiff(&path, stack.view(), &["a", "b & c", "d"]).unwrap();
+    let data = io::read_tiff(&path).unwrap();
+    assert_eq!(data.shape(), &[3, 4, 5]);
+    assert_eq!(data, stack.mapv(|v| v as f64).into_dyn());
+
+    // the OME-XML is stored in the first page
+    let mut decoder = Decoder::new(File::open(&path).unwrap()).unwrap();
+    let xml = decoder.get_tag_ascii_string(Tag::ImageDescription).unwrap();
+    assert!(xml.contains("DimensionOrder=\"XYCZT\""));
+    assert!(xml.contains("Type=\"uint32\""));
+    assert!(xml.contains("SizeC=\"3\""));
+    assert!(xml.contains("Name=\"b &amp; c\""));
+
+    // a single page is read as a 2-dimensional image
+    let plane = Array2::from_shape_fn((4, 5), |(r, c)| (r + c) as f32 / 2.0);
+    let path = dir.join("plane.tif");
+    io::write_ome_tiff(&path, plane.view().insert_axis(Axis(0)), &["plane"]).unwrap();
+    assert_eq!(
+        io::read_tiff(&path).unwrap(),
+        plane.mapv(|v| v as f64).into_dyn()
+    );
+    assert_eq!(pipeline::stem(&path), "plane");
+    assert_eq!(pipeline::stem(Path::new("a/b.c.OME.TIFF")), "b.c");
+
+    // files are listed in path order, without other extensions
+    fs::write(dir.join("notes.txt"), "").unwrap();
+    let files = io::list_tiffs(&dir).unwrap();
+    assert_eq!(
+        files,
+        vec![dir.join("plane.tif"), dir.join("stack.ome.tif")]
+    );
+}
+
+#[test]
+fn pipeline_phasor() {
+    let dir = temp_dir("pipeline_phasor");
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    fs::create_dir_all(&input).unwrap();
+    let data = write_decay_stack(&input.join("decay.tif"));
+    let gs = time_domain::image(data.view(), PERIOD, None, None, None).unwrap();
+    let text = format!(
+        "pipeline = \"phasor\"\nperiod = {}\nmin_photons = 10.0\n\n[[cursors]]\nname = \"all\"\ng = {}\ns = {}\nradius = 0.05\n\n[[cursors]]\nname = \"none\"\ng = 0.1\ns = 0.1\nradius = 0.05\n",
+        PERIOD,
+        gs[[0, 0, 0]],
+        gs[[0, 0, 1]]
+    );
+    let p = Pipeline::from_toml(&text).unwrap();
+    let reports = run_quiet(&p, &input, &output);
+    assert_eq!(reports.len(), 1);
+    assert!(reports[0].error.is_none());
+
+    // the phasor image has one (G, S) channel each
+    let phasor = io::read_tiff(&output.join("decay_phasor.ome.tif")).unwrap();
+    assert_eq!(phasor.shape(), &[2, 6, 5]);
+    assert!((phasor[[0, 3, 2]] - gs[[3, 2, 0]]).abs() < 1e-6);
+    assert!((phasor[[1, 3, 2]] - gs[[3, 2, 1]]).abs() < 1e-6);
+    let cursors = io::read_tiff(&output.join("decay_cursors.ome.tif")).unwrap();
+    assert_eq!(cursors.shape(), &[2, 6, 5]);
+    assert!(cursors.index_axis(Axis(0), 0).iter().all(|&v| v == 255.0));
+    assert!(cursors.index_axis(Axis(0), 1).iter().all(|&v| v == 0.0));
+
+    // the summary has one row per file
+    let csv = read_csv(&output.join("phasor.csv"));
+    assert_eq!(
+        csv[0],
+        [
+            "file",
+            "pixels",
+            "mean_g",
+            "mean_s",
+            "all_pixels",
+            "all_fraction",
+            "none_pixels",
+            "none_fraction"
+        ]
+    );
+    assert_eq!(csv[1][0], "decay.tif");
+    assert_eq!(csv[1][1], "30");
+    assert!((csv[1][2].parse::<f64>().unwrap() - gs[[0, 0, 0]]).abs() < 1e-12);
+    assert_eq!(csv[1][4..], ["30", "1", "0", "0"]);
+}
+
+#[test]
+fn pipeline_phasor_calibration_reference() {
+    let dir = temp_dir("pipeline_phasor_calibration_reference");
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    fs::create_dir_all(&input).unwrap();
+    write_decay_stack(&input.join("reference.tif"));
+    let path = dir.join("pipeline.json");
+    let text = format!(
+        "{{\"pipeline\": \"phasor\", \"period\": {}, \"calibration\": {{\"reference\": \"input/reference.tif\", \"tau\": {}}}}}",
+        PERIOD, TAU
+    );
+    fs::write(&path, text).unwrap();
+    let p = Pipeline::from_path(&path).unwrap();
+    run_quiet(&p, &input, &output);
+
+    // the calibrated reference is on its theoretical coordinates
+    let (g, s) = plot::monoexponential_coordinates(TAU, omega(PERIOD));
+    let csv = read_csv(&output.join("phasor.csv"));
+    assert!((csv[1][2].parse::<f64>().unwrap() - g).abs() < 1e-9);
+    assert!((csv[1][3].parse::<f64>().unwrap() - s).abs() < 1e-9);
+}
+
+#[test]
+fn pipeline_threshold() {
+    let dir = temp_dir("pipeline_threshold");
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    fs::create_dir_all(&input).unwrap();
+
+    // two bright squares on a dark background
+    let image = Array2::from_shape_fn((20, 20), |(r, c)| {
+        let a = (2..6).contains(&r) && (2..6).contains(&c);
+        let b = (10..16).contains(&r) && (12..15).contains(&c);
+        if a || b { 200u32 } else { 10 }
+    });
+    io::write_ome_tiff(
+        &input.join("cells.tif"),
+        image.view().insert_axis(Axis(0)),
+        &[],
+    )
+    .unwrap();
+
+    // a stack is not a valid threshold input, the batch continues
+    let stack = Array3::<u32>::zeros((2, 4, 4));
+    io::write_ome_tiff(&input.join("stack.tif"), stack.view(), &[]).unwrap();
+
+    let p = Pipeline::from_toml("pipeline = \"threshold\"\nmethod = \"otsu\"").unwrap();
+    let reports = run_quiet(&p, &input, &output);
+    assert_eq!(reports.len(), 2);
+    assert!(reports[0].error.is_none());
+    assert!(reports[1].error.is_some());
+
+    // the labels and region properties of both objects
+    let labels = io::read_tiff(&output.join("cells_labels.ome.tif")).unwrap();
+    assert_eq!(labels[[3, 3]], 1.0);
+    assert_eq!(labels[[12, 13]], 2.0);
+    assert_eq!(labels[[0, 0]], 0.0);
+    let props = read_csv(&output.join("cells_regionprops.csv"));
+    assert_eq!(props.len(), 3);
+    assert_eq!(props[0][..3], ["label", "area", "centroid_row"]);
+    assert_eq!(props[0].last().unwrap(), "max_intensity");
+    assert_eq!(props[1][..2], ["1", "16"]);
+    assert_eq!(props[2][..2], ["2", "18"]);
+    let csv = read_csv(&output.join("threshold.csv"));
+    assert_eq!(csv.len(), 2);
+    assert_eq!(csv[1][0], "cells.tif");
+    assert_eq!(csv[1][2], "2");
+}
+
+#[test]
+fn pipeline_colocalization() {
+    let dir = temp_dir("pipeline_colocalization");
+    let (input, output) = (dir.join("input"), dir.join("output"));
+    fs::create_dir_all(&input).unwrap();
+    let stack = Array3::from_shape_fn((3, 8, 8), |(c, r, col)| match c {
+        0 => (r * 8 + col + 1) as f32,
+        1 => 7.0,
+        _ => 2.0 * (r * 8 + col + 1) as f32,
+    });
+    io::write_ome_tiff(&input.join("channels.tif"), stack.view(), &[]).unwrap();
+    let json = r#"{"pipeline": "colocalization", "channel_b": 2, "costes": false}"#;
+    let p = Pipeline::from_json(json).unwrap();
+    let reports = run_quiet(&p, &input, &output);
+    assert!(reports[0].error.is_none());
+
+    // perfectly correlated channels above a 0.0 threshold
+    let csv = read_csv(&output.join("colocalization.csv"));
+    assert_eq!(
+        csv[0],
+        ["file", "pearson", "m1", "m2", "threshold_a", "threshold_b"]
+    );
+    assert!((csv[1][1].parse::<f64>().unwrap() - 1.0).abs() < 1e-12);
+    assert_eq!(csv[1][2..], ["1", "1", "0", "0"]);
+
+    // channels outside of the image fail
+    let p = Pipeline::from_json(r#"{"pipeline": "colocalization", "channel_b": 3}"#).unwrap();
+    let reports = run_quiet(&p, &input, &output);
+    assert!(reports[0].error.is_some());
+    assert_eq!(read_csv(&output.join("colocalization.csv")).len(), 1);
+}
+
+#[test]
+fn config_example_pipelines() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("pipelines");
+    let names: Vec<&str> = ["colocalization.json", "phasor.toml", "threshold.toml"]
+        .iter()
+        .map(|f| Pipeline::from_path(&dir.join(f)).unwrap().name())
+        .collect();
+    assert_eq!(names, ["colocalization", "phasor", "threshold"]);
+}
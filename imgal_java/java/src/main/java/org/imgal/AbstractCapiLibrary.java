@@ -0,0 +1,137 @@
+package org.imgal;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.Linker;
+import java.lang.foreign.MemoryLayout;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.StructLayout;
+import java.lang.foreign.SymbolLookup;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+import java.io.InputStream;
+import java.net.URL;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.StandardCopyOption;
+
+/**
+ * Abstract class for the bindings of the imgal C ABI (imgal_capi).
+ *
+ * @author Edward Evans
+ */
+public abstract class AbstractCapiLibrary {
+	private static String libName = System.mapLibraryName("imgal_capi");
+	public static final SymbolLookup capiLookup;
+	public static final Linker linker = Linker.nativeLinker();
+
+	/** The ImgalArray descriptor, see imgal_capi.h. */
+	public static final StructLayout IMGAL_ARRAY = MemoryLayout.structLayout(
+			ValueLayout.ADDRESS.withName("data"),
+			ValueLayout.JAVA_LONG.withName("ndim"),
+			ValueLayout.ADDRESS.withName("shape"),
+			ValueLayout.ADDRESS.withName("strides"));
+
+	// copy the Rust library from resources and then load it (for SymbolLookup)
+	static {
+		try {
+			URL url = AbstractCapiLibrary.class.getResource("/native/" + libName);
+			Path tmpLib = Files.createTempFile("libimgal_capi", libName.substring(libName.lastIndexOf('.')));
+			try (InputStream is = url.openStream()) {
+				Files.copy(is, tmpLib, StandardCopyOption.REPLACE_EXISTING);
+			}
+			tmpLib.toFile().deleteOnExit();
+			capiLookup = SymbolLookup.libraryLookup(tmpLib, Arena.global());
+		} catch (Exception e) {
+			throw new RuntimeException("Failed to load library.", e);
+		}
+	}
+
+	/** The C ABI version these bindings are written against. */
+	public static final int ABI_VERSION = 2;
+
+	static final MethodHandle lastErrorMH = downcall("imgal_last_error",
+			FunctionDescriptor.of(ValueLayout.ADDRESS));
+	static final MethodHandle versionMH = downcall("imgal_version",
+			FunctionDescriptor.of(ValueLayout.ADDRESS));
+	static final MethodHandle abiVersionMH = downcall("imgal_abi_version",
+			FunctionDescriptor.of(ValueLayout.JAVA_INT));
+
+	// refuse to bind to a library with a different C ABI
+	static {
+		try {
+			int abi = (int) abiVersionMH.invokeExact();
+			if (abi != ABI_VERSION) {
+				throw new IllegalStateException("The imgal_capi library has ABI version " + abi
+						+ ", the bindings require ABI version " + ABI_VERSION + ".");
+			}
+		} catch (Throwable e) {
+			throw new RuntimeException("Failed to load library.", e);
+		}
+	}
+
+	/**
+	 * Get the semantic version of the imgal_capi library.
+	 *
+	 * @return The "major.minor.patch" version.
+	 */
+	public static String version() throws Throwable {
+		MemorySegment v = (MemorySegment) versionMH.invokeExact();
+
+		return v.reinterpret(Long.MAX_VALUE).getString(0);
+	}
+
+	/**
+	 * Create a downcall MethodHandle for an imgal C ABI function.
+	 *
+	 * @param name The function name.
+	 * @param fnSig The function descriptor.
+	 * @return The downcall handle.
+	 */
+	static MethodHandle downcall(String name, FunctionDescriptor fnSig) {
+		return linker.downcallHandle(capiLookup.findOrThrow(name), fnSig);
+	}
+
+	/**
+	 * Allocate a C-contiguous ImgalArray descriptor of an array.
+	 *
+	 * @param arena The arena to allocate the descriptor and data in.
+	 * @param data The array elements in row major order.
+	 * @param shape The length of each axis.
+	 * @return The descriptor.
+	 */
+	static MemorySegment imgalArray(Arena arena, double[] data, long[] shape) {
+		long len = 1;
+		for (long s : shape) {
+			len *= s;
+		}
+		if (len != data.length) {
+			throw new IllegalArgumentException("The shape does not match the number of elements.");
+		}
+		MemorySegment desc = arena.allocate(IMGAL_ARRAY);
+		desc.set(ValueLayout.ADDRESS, offset("data"), arena.allocateFrom(ValueLayout.JAVA_DOUBLE, data));
+		desc.set(ValueLayout.JAVA_LONG, offset("ndim"), (long) shape.length);
+		desc.set(ValueLayout.ADDRESS, offset("shape"), arena.allocateFrom(ValueLayout.JAVA_LONG, shape));
+		desc.set(ValueLayout.ADDRESS, offset("strides"), MemorySegment.NULL);
+
+		return desc;
+	}
+
+	/**
+	 * Throw an ImgalException if a C ABI call failed.
+	 *
+	 * @param status The ImgalStatus code returned by the function.
+	 */
+	static void check(int status) throws Throwable {
+		if (status == 0) {
+			return;
+		}
+		MemorySegment msg = (MemorySegment) lastErrorMH.invokeExact();
+		String message = msg.equals(MemorySegment.NULL) ? "" : msg.reinterpret(Long.MAX_VALUE).getString(0);
+		throw new ImgalException(status, message);
+	}
+
+	private static long offset(String field) {
+		return IMGAL_ARRAY.byteOffset(MemoryLayout.PathElement.groupElement(field));
+	}
+}
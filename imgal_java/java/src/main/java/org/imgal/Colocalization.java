@@ -0,0 +1,56 @@
+package org.imgal;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+
+/**
+ * Interface for native colocalization.
+ *
+ * @author Edward Evans
+ */
+public class Colocalization extends AbstractCapiLibrary {
+	// declare method handles
+	static final MethodHandle objectMH = downcall("imgal_colocalization_object",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS));
+
+	/**
+	 * Compute object-based colocalization measurements of two label images.
+	 *
+	 * The tidy table of per-object measurements is exported through the Apache
+	 * Arrow C data interface (see Measure).
+	 *
+	 * @param labelsA The label image A in row major order, with non-negative
+	 *        integer values.
+	 * @param labelsB The label image B in row major order, with non-negative
+	 *        integer values.
+	 * @param shape The shape of both label images.
+	 * @param spacing The pixel spacing per axis, or null for a spacing of 1.0
+	 *        along every axis.
+	 * @param arrayAddress The address of the output "struct ArrowArray".
+	 * @param schemaAddress The address of the output "struct ArrowSchema".
+	 */
+	public static void object(double[] labelsA, double[] labelsB, long[] shape, double[] spacing,
+			long arrayAddress, long schemaAddress) throws Throwable {
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment descA = imgalArray(arena, labelsA, shape);
+			MemorySegment descB = imgalArray(arena, labelsB, shape);
+			MemorySegment sp = spacing == null ? MemorySegment.NULL
+					: arena.allocateFrom(ValueLayout.JAVA_DOUBLE, spacing);
+			int status = (int) objectMH.invokeExact(
+					MemorySegment.NULL, descA, descB, sp, spacing == null ? 0L : (long) spacing.length,
+					MemorySegment.ofAddress(arrayAddress), MemorySegment.ofAddress(schemaAddress));
+			check(status);
+		}
+	}
+}
@@ -0,0 +1,50 @@
+package org.imgal;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+
+/**
+ * Interface for native filters.
+ *
+ * @author Edward Evans
+ */
+public class Filter extends AbstractCapiLibrary {
+	// declare method handles
+	static final MethodHandle gaussianMH = downcall("imgal_filter_gaussian",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG));
+
+	/**
+	 * Filter an n-dimensional image with a Gaussian kernel.
+	 *
+	 * @param data The input image in row major order.
+	 * @param shape The shape of the input image.
+	 * @param sigma The standard deviation of the Gaussian kernel along each
+	 *        axis, in pixels. Must have one value per axis.
+	 * @return The filtered image in row major order, with the same shape as the
+	 *         input image.
+	 */
+	public static double[] gaussian(double[] data, long[] shape, double[] sigma) throws Throwable {
+		double[] output = new double[data.length];
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment desc = imgalArray(arena, data, shape);
+			MemorySegment s = arena.allocateFrom(ValueLayout.JAVA_DOUBLE, sigma);
+			MemorySegment out = arena.allocate(ValueLayout.JAVA_DOUBLE, Math.max(output.length, 1));
+			int status = (int) gaussianMH.invokeExact(
+					MemorySegment.NULL, desc, s, (long) sigma.length, out, (long) output.length);
+			check(status);
+			MemorySegment.copy(out, ValueLayout.JAVA_DOUBLE, 0, output, 0, output.length);
+		}
+
+		return output;
+	}
+}
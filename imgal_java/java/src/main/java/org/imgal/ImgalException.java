@@ -0,0 +1,31 @@
+package org.imgal;
+
+/**
+ * Exception thrown when an imgal C ABI function returns an error status.
+ *
+ * @author Edward Evans
+ */
+public class ImgalException extends RuntimeException {
+	private final int status;
+
+	/**
+	 * Create an exception from a status code and error message.
+	 *
+	 * @param status The ImgalStatus code returned by the function.
+	 * @param message The message of the failed call.
+	 */
+	public ImgalException(int status, String message) {
+		super(message);
+		this.status = status;
+	}
+
+	/**
+	 * Get the ImgalStatus code of the failed call.
+	 *
+	 * @return The status code, 1 for a null pointer, 2 for an invalid array,
+	 *         3 for an invalid parameter and 4 for a panic.
+	 */
+	public int getStatus() {
+		return status;
+	}
+}
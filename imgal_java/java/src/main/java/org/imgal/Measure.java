@@ -0,0 +1,57 @@
+package org.imgal;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+
+/**
+ * Interface for native measurements.
+ *
+ * The measurement tables are exported through the Apache Arrow C data
+ * interface into caller allocated "struct ArrowArray" and "struct ArrowSchema"
+ * structs, e.g. allocated with Arrow Java's
+ * "org.apache.arrow.c.ArrowArray.allocateNew" and
+ * "org.apache.arrow.c.ArrowSchema.allocateNew" and imported with
+ * "org.apache.arrow.c.Data.importVectorSchemaRoot".
+ *
+ * @author Edward Evans
+ */
+public class Measure extends AbstractCapiLibrary {
+	// declare method handles
+	static final MethodHandle regionpropsMH = downcall("imgal_measure_regionprops",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS));
+
+	/**
+	 * Measure the properties of each object of a 2-dimensional label image.
+	 *
+	 * @param labels The label image in row major order, with non-negative
+	 *        integer values. Pixels with the label 0 are background.
+	 * @param shape The (row, col) shape of the label image.
+	 * @param intensity The intensity image in row major order, or null. Must
+	 *        match the shape of the label image.
+	 * @param arrayAddress The address of the output "struct ArrowArray".
+	 * @param schemaAddress The address of the output "struct ArrowSchema".
+	 */
+	public static void regionprops(double[] labels, long[] shape, double[] intensity,
+			long arrayAddress, long schemaAddress) throws Throwable {
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment desc = imgalArray(arena, labels, shape);
+			MemorySegment img = intensity == null ? MemorySegment.NULL
+					: imgalArray(arena, intensity, shape);
+			int status = (int) regionpropsMH.invokeExact(
+					MemorySegment.NULL, desc, img, MemorySegment.NULL, 2L,
+					MemorySegment.ofAddress(arrayAddress), MemorySegment.ofAddress(schemaAddress));
+			check(status);
+		}
+	}
+}
@@ -0,0 +1,119 @@
+package org.imgal;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+
+/**
+ * Interface for native phasor analysis.
+ *
+ * @author Edward Evans
+ */
+public class Phasor extends AbstractCapiLibrary {
+	// declare method handles
+	static final MethodHandle timeDomainImageMH = downcall("imgal_phasor_time_domain_image",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG));
+	static final MethodHandle calibrationImageMH = downcall("imgal_phasor_calibration_image",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG));
+
+	/**
+	 * Compute the real and imaginary (G, S) coordinates of a 3-dimensional
+	 * decay image.
+	 *
+	 * @param data I(t), the decay data image in row major order.
+	 * @param shape The shape of the decay data image.
+	 * @param period The period (i.e. time interval).
+	 * @param harmonic The harmonic value, must be >= 1.0.
+	 * @param axis The decay or lifetime axis.
+	 * @param mask An optional row major mask of the non-decay axes, pixels set
+	 *        to false are not computed. May be null.
+	 * @return The (row, col, 2) G and S image in row major order.
+	 */
+	public static double[] timeDomainImage(double[] data, long[] shape, double period,
+			double harmonic, int axis, boolean[] mask) throws Throwable {
+		long pixels = 1;
+		for (int i = 0; i < shape.length; i++) {
+			if (i != axis) {
+				pixels *= shape[i];
+			}
+		}
+		double[] output = new double[(int) (pixels * 2)];
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment desc = imgalArray(arena, data, shape);
+			MemorySegment out = arena.allocate(ValueLayout.JAVA_DOUBLE, Math.max(output.length, 1));
+			MemorySegment msk = maskSegment(arena, mask);
+			int status = (int) timeDomainImageMH.invokeExact(
+					MemorySegment.NULL, desc, period, harmonic, (long) axis, msk, out, (long) output.length);
+			check(status);
+			MemorySegment.copy(out, ValueLayout.JAVA_DOUBLE, 0, output, 0, output.length);
+		}
+
+		return output;
+	}
+
+	/**
+	 * Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional
+	 * phasor image.
+	 *
+	 * @param data The phasor image in row major order, where G and S are indexed
+	 *        at 0 and 1 respectively on the channel axis.
+	 * @param shape The shape of the phasor image.
+	 * @param modulation The modulation to scale the input (G, S) coordinates.
+	 * @param phase The phase, φ angle, to rotate the input (G, S) coordinates.
+	 * @param axis The channel axis.
+	 * @param mask An optional row major mask of the non-channel axes, pixels set
+	 *        to false are not calibrated. May be null.
+	 * @return The calibrated image in row major order, with the same shape as
+	 *         the input image.
+	 */
+	public static double[] calibrationImage(double[] data, long[] shape, double modulation,
+			double phase, int axis, boolean[] mask) throws Throwable {
+		double[] output = new double[data.length];
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment desc = imgalArray(arena, data, shape);
+			MemorySegment out = arena.allocate(ValueLayout.JAVA_DOUBLE, Math.max(output.length, 1));
+			MemorySegment msk = maskSegment(arena, mask);
+			int status = (int) calibrationImageMH.invokeExact(
+					MemorySegment.NULL, desc, modulation, phase, (long) axis, msk, out, (long) output.length);
+			check(status);
+			MemorySegment.copy(out, ValueLayout.JAVA_DOUBLE, 0, output, 0, output.length);
+		}
+
+		return output;
+	}
+
+	/**
+	 * Allocate an optional mask as bytes of 0 and 1.
+	 */
+	private static MemorySegment maskSegment(Arena arena, boolean[] mask) {
+		if (mask == null) {
+			return MemorySegment.NULL;
+		}
+		byte[] bytes = new byte[mask.length];
+		for (int i = 0; i < mask.length; i++) {
+			bytes[i] = (byte) (mask[i] ? 1 : 0);
+		}
+
+		return arena.allocateFrom(ValueLayout.JAVA_BYTE, bytes);
+	}
+}
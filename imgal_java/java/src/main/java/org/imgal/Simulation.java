@@ -0,0 +1,96 @@
+package org.imgal;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+
+/**
+ * Interface for native simulation.
+ *
+ * @author Edward Evans
+ */
+public class Simulation extends AbstractCapiLibrary {
+	// declare method handles
+	static final MethodHandle idealExponential1dMH = downcall("imgal_simulation_ideal_exponential_1d",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG));
+	static final MethodHandle poissonNoiseMH = downcall("imgal_simulation_poisson_noise",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG));
+
+	/**
+	 * Simulate an ideal 1-dimensional monoexponential or multiexponential decay
+	 * curve.
+	 *
+	 * @param samples The number of discrete points that make up the decay curve.
+	 * @param period The period (i.e. time interval).
+	 * @param taus The lifetimes of each component.
+	 * @param fractions The fractional intensities of each component, must sum
+	 *        to 1.0.
+	 * @param totalCounts The total intensity count (e.g. photon count) of the
+	 *        decay curve.
+	 * @return The decay curve.
+	 */
+	public static double[] idealExponential1d(int samples, double period, double[] taus,
+			double[] fractions, double totalCounts) throws Throwable {
+		if (taus.length != fractions.length) {
+			throw new IllegalArgumentException("The taus and fractions must have the same length.");
+		}
+		double[] output = new double[samples];
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment t = arena.allocateFrom(ValueLayout.JAVA_DOUBLE, taus);
+			MemorySegment f = arena.allocateFrom(ValueLayout.JAVA_DOUBLE, fractions);
+			MemorySegment out = arena.allocate(ValueLayout.JAVA_DOUBLE, Math.max(samples, 1));
+			int status = (int) idealExponential1dMH.invokeExact(
+					MemorySegment.NULL, (long) samples, period, t, f, (long) taus.length,
+					totalCounts, out, (long) samples);
+			check(status);
+			MemorySegment.copy(out, ValueLayout.JAVA_DOUBLE, 0, output, 0, output.length);
+		}
+
+		return output;
+	}
+
+	/**
+	 * Simulate Poisson noise on an n-dimensional array.
+	 *
+	 * @param data The input array in row major order.
+	 * @param shape The shape of the input array.
+	 * @param scale The scale factor.
+	 * @param seed The pseudorandom number generator seed, the same seed applies
+	 *        the same noise.
+	 * @return The noisy array in row major order, with the same shape as the
+	 *         input array.
+	 */
+	public static double[] poissonNoise(double[] data, long[] shape, double scale, long seed)
+			throws Throwable {
+		double[] output = new double[data.length];
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment desc = imgalArray(arena, data, shape);
+			MemorySegment out = arena.allocate(ValueLayout.JAVA_DOUBLE, Math.max(output.length, 1));
+			int status = (int) poissonNoiseMH.invokeExact(
+					MemorySegment.NULL, desc, scale, seed, out, (long) output.length);
+			check(status);
+			MemorySegment.copy(out, ValueLayout.JAVA_DOUBLE, 0, output, 0, output.length);
+		}
+
+		return output;
+	}
+}
@@ -0,0 +1,77 @@
+package org.imgal;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+
+/**
+ * Interface for native thresholds.
+ *
+ * @author Edward Evans
+ */
+public class Threshold extends AbstractCapiLibrary {
+	// declare method handles
+	static final MethodHandle manualMaskMH = downcall("imgal_threshold_manual_mask",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_DOUBLE,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG));
+	static final MethodHandle otsuMH = downcall("imgal_threshold_otsu",
+			FunctionDescriptor.of(
+					ValueLayout.JAVA_INT,
+					ValueLayout.ADDRESS,
+					ValueLayout.ADDRESS,
+					ValueLayout.JAVA_LONG,
+					ValueLayout.ADDRESS));
+
+	/**
+	 * Create a boolean mask from a threshold value.
+	 *
+	 * @param data The input image in row major order.
+	 * @param shape The shape of the input image.
+	 * @param threshold The image pixel threshold value.
+	 * @return The row major mask with pixels greater than the threshold value
+	 *         set to true.
+	 */
+	public static boolean[] manualMask(double[] data, long[] shape, double threshold)
+			throws Throwable {
+		boolean[] output = new boolean[data.length];
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment desc = imgalArray(arena, data, shape);
+			MemorySegment out = arena.allocate(ValueLayout.JAVA_BYTE, Math.max(output.length, 1));
+			int status = (int) manualMaskMH.invokeExact(
+					MemorySegment.NULL, desc, threshold, out, (long) output.length);
+			check(status);
+			for (int i = 0; i < output.length; i++) {
+				output[i] = out.get(ValueLayout.JAVA_BYTE, i) != 0;
+			}
+		}
+
+		return output;
+	}
+
+	/**
+	 * Compute the Otsu threshold value of an n-dimensional image.
+	 *
+	 * @param data The input image in row major order.
+	 * @param shape The shape of the input image.
+	 * @param bins The number of histogram bins, must be >= 1.
+	 * @return The Otsu threshold value.
+	 */
+	public static double otsu(double[] data, long[] shape, int bins) throws Throwable {
+		try (Arena arena = Arena.ofConfined()) {
+			MemorySegment desc = imgalArray(arena, data, shape);
+			MemorySegment out = arena.allocate(ValueLayout.JAVA_DOUBLE);
+			int status = (int) otsuMH.invokeExact(
+					MemorySegment.NULL, desc, (long) bins, out);
+			check(status);
+
+			return out.get(ValueLayout.JAVA_DOUBLE, 0);
+		}
+	}
+}
@@ -0,0 +1,164 @@
+package org.imgal;
+
+import static org.junit.jupiter.api.Assertions.assertArrayEquals;
+import static org.junit.jupiter.api.Assertions.assertEquals;
+import static org.junit.jupiter.api.Assertions.assertThrows;
+import static org.junit.jupiter.api.Assertions.assertTrue;
+
+import java.lang.foreign.Arena;
+import java.lang.foreign.FunctionDescriptor;
+import java.lang.foreign.MemorySegment;
+import java.lang.foreign.ValueLayout;
+import java.lang.invoke.MethodHandle;
+import java.util.Arrays;
+
+import org.junit.jupiter.api.Test;
+
+/**
+ * Tests for the imgal C ABI bindings.
+ *
+ * @author Edward Evans
+ */
+public class CapiTest {
+	// simulated biexponential decay parameters
+	static final int SAMPLES = 64;
+	static final double PERIOD = 12.5;
+	static final double[] TAUS = { 1.0, 3.0 };
+	static final double[] FRACTIONS = { 0.7, 0.3 };
+	static final double TOTAL_COUNTS = 5000.0;
+
+	@Test
+	public void version() throws Throwable {
+		// the library reports a "major.minor.patch" version
+		assertTrue(AbstractCapiLibrary.version().matches("\\d+\\.\\d+\\.\\d+.*"));
+	}
+
+	@Test
+	public void filterGaussian() throws Throwable {
+		// a constant image stays constant
+		double[] data = new double[6 * 5];
+		Arrays.fill(data, 3.0);
+		double[] output = Filter.gaussian(data, new long[] { 6, 5 }, new double[] { 1.0, 1.0 });
+		for (double v : output) {
+			assertEquals(3.0, v, 1e-12);
+		}
+
+		// one sigma per axis is required
+		ImgalException e = assertThrows(ImgalException.class,
+				() -> Filter.gaussian(data, new long[] { 6, 5 }, new double[] { 1.0 }));
+		assertEquals(3, e.getStatus());
+		assertTrue(!e.getMessage().isEmpty());
+	}
+
+	@Test
+	public void measureRegionprops() throws Throwable {
+		// a 2x2 square and a single pixel
+		double[] labels = { 1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 2 };
+		try (Arena arena = Arena.ofConfined()) {
+			// the Arrow C data interface structs, release is at byte 64 and 56
+			MemorySegment array = arena.allocate(80);
+			MemorySegment schema = arena.allocate(72);
+			Measure.regionprops(labels, new long[] { 3, 4 }, null, array.address(), schema.address());
+			assertEquals(2, array.get(ValueLayout.JAVA_LONG, 0));
+			assertEquals(10, array.get(ValueLayout.JAVA_LONG, 32));
+			assertEquals(10, schema.get(ValueLayout.JAVA_LONG, 32));
+			releaseArrow(array, 64);
+			releaseArrow(schema, 56);
+			assertEquals(MemorySegment.NULL, array.get(ValueLayout.ADDRESS, 64));
+
+			// both label images are measured against each other
+			Colocalization.object(labels, labels, new long[] { 3, 4 }, null, array.address(),
+					schema.address());
+			assertTrue(array.get(ValueLayout.JAVA_LONG, 0) > 0);
+			assertEquals(6, schema.get(ValueLayout.JAVA_LONG, 32));
+			releaseArrow(array, 64);
+			releaseArrow(schema, 56);
+		}
+
+		// fractional labels are rejected
+		ImgalException e = assertThrows(ImgalException.class,
+				() -> Measure.regionprops(new double[] { 0.5 }, new long[] { 1, 1 }, null, 0L, 0L));
+		assertEquals(2, e.getStatus());
+	}
+
+	@Test
+	public void phasorTimeDomainImage() throws Throwable {
+		// every pixel of a monoexponential decay image has the same coordinates
+		// inside the universal semicircle
+		double[] curve = Simulation.idealExponential1d(SAMPLES, PERIOD, new double[] { 2.0 },
+				new double[] { 1.0 }, TOTAL_COUNTS);
+		double[] data = new double[3 * 2 * SAMPLES];
+		for (int p = 0; p < 6; p++) {
+			System.arraycopy(curve, 0, data, p * SAMPLES, SAMPLES);
+		}
+		double[] gs = Phasor.timeDomainImage(data, new long[] { 3, 2, SAMPLES }, PERIOD, 1.0, 2, null);
+		assertEquals(3 * 2 * 2, gs.length);
+		for (int p = 0; p < 6; p++) {
+			double g = gs[2 * p];
+			double s = gs[2 * p + 1];
+			assertEquals(gs[0], g, 1e-12);
+			assertEquals(gs[1], s, 1e-12);
+			assertTrue(s > 0.0 && (g - 0.5) * (g - 0.5) + s * s <= 0.25);
+		}
+
+		// the calibration with a modulation of 1.0 and a phase of 0.0 is the identity
+		double[] calibrated = Phasor.calibrationImage(gs, new long[] { 3, 2, 2 }, 1.0, 0.0, 2, null);
+		assertArrayEquals(gs, calibrated, 1e-12);
+
+		// an invalid period is rejected
+		ImgalException e = assertThrows(ImgalException.class,
+				() -> Phasor.timeDomainImage(data, new long[] { 3, 2, SAMPLES }, 0.0, 1.0, 2, null));
+		assertEquals(3, e.getStatus());
+	}
+
+	@Test
+	public void simulation() throws Throwable {
+		// the decay curve sums to the total counts
+		double[] curve = Simulation.idealExponential1d(SAMPLES, PERIOD, TAUS, FRACTIONS, TOTAL_COUNTS);
+		assertEquals(SAMPLES, curve.length);
+		double sum = 0.0;
+		for (double v : curve) {
+			sum += v;
+		}
+		assertEquals(TOTAL_COUNTS, sum, 1.0);
+
+		// the same seed applies the same noise
+		long[] shape = { SAMPLES };
+		double[] a = Simulation.poissonNoise(curve, shape, 1.0, 42);
+		double[] b = Simulation.poissonNoise(curve, shape, 1.0, 42);
+		assertArrayEquals(a, b);
+	}
+
+	@Test
+	public void threshold() throws Throwable {
+		// dim background with a bright square in the center
+		double[] data = new double[10 * 10];
+		for (int r = 0; r < 10; r++) {
+			for (int c = 0; c < 10; c++) {
+				boolean inside = r >= 3 && r < 7 && c >= 3 && c < 7;
+				data[r * 10 + c] = 10.0 + (r + c) % 3 + (inside ? 90.0 : 0.0);
+			}
+		}
+		long[] shape = { 10, 10 };
+		double t = Threshold.otsu(data, shape, 256);
+		assertTrue(t > 12.0 && t < 100.0);
+		boolean[] mask = Threshold.manualMask(data, shape, t);
+		int count = 0;
+		for (boolean m : mask) {
+			count += m ? 1 : 0;
+		}
+		assertEquals(16, count);
+
+		// the shape must match the number of elements
+		assertThrows(IllegalArgumentException.class,
+				() -> Threshold.otsu(data, new long[] { 10, 9 }, 256));
+	}
+
+	/** Call the release callback of an Arrow C data interface struct. */
+	static void releaseArrow(MemorySegment struct, long releaseOffset) throws Throwable {
+		MemorySegment release = struct.get(ValueLayout.ADDRESS, releaseOffset);
+		MethodHandle mh = AbstractCapiLibrary.linker.downcallHandle(release,
+				FunctionDescriptor.ofVoid(ValueLayout.ADDRESS));
+		mh.invokeExact(struct);
+	}
+}
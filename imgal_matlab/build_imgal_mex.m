@@ -0,0 +1,42 @@
+function build_imgal_mex(libdir)
+%BUILD_IMGAL_MEX Compile the imgal MEX functions against the C ABI library.
+%   BUILD_IMGAL_MEX() compiles the MEX functions in the "mex" folder against
+%   the imgal_capi library built with "cargo build --release -p imgal_capi"
+%   in "../target/release", and copies the library next to the MEX files.
+%   Add the "mex" folder to the MATLAB path to use the functions.
+%
+%   BUILD_IMGAL_MEX(LIBDIR) uses the imgal_capi library in LIBDIR.
+%
+%   Requires MATLAB R2018a or newer and a supported C compiler (see
+%   "mex -setup C").
+root = fileparts(mfilename('fullpath'));
+if nargin < 1
+    libdir = fullfile(root, '..', 'target', 'release');
+end
+include = fullfile(root, '..', 'imgal_capi', 'include');
+outdir = fullfile(root, 'mex');
+
+% the library and linker flags of the platform
+if ispc
+    lib = 'imgal_capi.dll';
+    link = {fullfile(libdir, 'imgal_capi.dll.lib')};
+elseif ismac
+    lib = 'libimgal_capi.dylib';
+    link = {['-L' libdir], '-limgal_capi', 'LDFLAGS=$LDFLAGS -Wl,-rpath,@loader_path'};
+else
+    lib = 'libimgal_capi.so';
+    link = {['-L' libdir], '-limgal_capi', 'LDFLAGS=$LDFLAGS -Wl,-rpath,''$ORIGIN'''};
+end
+if ~isfile(fullfile(libdir, lib))
+    error('imgal:build', 'The imgal_capi library "%s" was not found in "%s".', lib, libdir);
+end
+
+% compile each MEX function and copy the library next to them
+sources = dir(fullfile(outdir, '*.c'));
+for k = 1:numel(sources)
+    src = fullfile(sources(k).folder, sources(k).name);
+    mex('-R2018a', ['-I' include], ['-I' outdir], link{:}, '-outdir', outdir, src);
+end
+copyfile(fullfile(libdir, lib), outdir);
+fprintf('Built %d imgal MEX functions in %s\n', numel(sources), outdir);
+end
@@ -0,0 +1,180 @@
+/*
+ * Shared helpers of the imgal MEX functions.
+ *
+ * MATLAB arrays are column major, the helpers describe them to the C ABI with
+ * column major strides (so inputs are not copied) and convert the C-contiguous
+ * outputs of the C ABI back to column major MATLAB arrays.
+ */
+#ifndef IMGAL_MEX_H
+#define IMGAL_MEX_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "mex.h"
+#include "imgal_capi.h"
+
+/* The maximum number of dimensions of an input array. */
+#define IMGAL_MEX_MAX_DIMS 32
+
+/* A column major MATLAB array described as an ImgalArray. */
+typedef struct ImgalMexArray {
+    ImgalArray array;
+    size_t shape[IMGAL_MEX_MAX_DIMS];
+    ptrdiff_t strides[IMGAL_MEX_MAX_DIMS];
+} ImgalMexArray;
+
+/* Raise a MATLAB error with the imgal error message if a call failed. */
+static void imgal_mex_check(ImgalStatus status)
+{
+    const char *id;
+    const char *msg;
+
+    switch (status) {
+    case IMGAL_STATUS_OK:
+        return;
+    case IMGAL_STATUS_NULL_POINTER:
+        id = "imgal:NullPointer";
+        break;
+    case IMGAL_STATUS_INVALID_ARRAY:
+        id = "imgal:InvalidArray";
+        break;
+    case IMGAL_STATUS_INVALID_PARAMETER:
+        id = "imgal:InvalidParameter";
+        break;
+    default:
+        id = "imgal:Panic";
+        break;
+    }
+    msg = imgal_last_error();
+    mexErrMsgIdAndTxt(id, "%s", msg != NULL ? msg : "Unknown imgal error.");
+}
+
+/* Check that an argument is a real, full double array. */
+static void imgal_mex_require_double(const mxArray *a, const char *name)
+{
+    if (!mxIsDouble(a) || mxIsComplex(a) || mxIsSparse(a)) {
+        mexErrMsgIdAndTxt("imgal:InvalidArray",
+                          "The argument \"%s\" must be a real, full double array.", name);
+    }
+}
+
+/* Get a real double scalar argument. */
+static double imgal_mex_scalar(const mxArray *a, const char *name)
+{
+    imgal_mex_require_double(a, name);
+    if (mxGetNumberOfElements(a) != 1) {
+        mexErrMsgIdAndTxt("imgal:InvalidParameter",
+                          "The argument \"%s\" must be a scalar.", name);
+    }
+    return mxGetScalar(a);
+}
+
+/* Get an optional real double scalar argument, empty ([]) or missing uses the
+ * default value. */
+static double imgal_mex_scalar_or(int nrhs, const mxArray *prhs[], int i, const char *name,
+                                  double value)
+{
+    if (nrhs <= i || mxIsEmpty(prhs[i])) {
+        return value;
+    }
+    return imgal_mex_scalar(prhs[i], name);
+}
+
+/* Get an optional 1-based MATLAB dimension argument as a 0-based axis. */
+static size_t imgal_mex_axis_or(int nrhs, const mxArray *prhs[], int i, size_t value)
+{
+    double dim = imgal_mex_scalar_or(nrhs, prhs, i, "axis", (double)value + 1.0);
+    if (dim < 1.0 || dim != (double)(size_t)dim) {
+        mexErrMsgIdAndTxt("imgal:InvalidParameter",
+                          "The argument \"axis\" must be a positive integer dimension.");
+    }
+    return (size_t)dim - 1;
+}
+
+/* Describe a double array with column major strides, without a copy. */
+static void imgal_mex_array(const mxArray *a, const char *name, ImgalMexArray *out)
+{
+    const mwSize *dims;
+    size_t ndim;
+    size_t k;
+    ptrdiff_t stride = 1;
+
+    imgal_mex_require_double(a, name);
+    ndim = mxGetNumberOfDimensions(a);
+    if (ndim > IMGAL_MEX_MAX_DIMS) {
+        mexErrMsgIdAndTxt("imgal:InvalidArray",
+                          "The argument \"%s\" has more than %d dimensions.", name,
+                          IMGAL_MEX_MAX_DIMS);
+    }
+    dims = mxGetDimensions(a);
+    for (k = 0; k < ndim; k++) {
+        out->shape[k] = (size_t)dims[k];
+        out->strides[k] = stride;
+        stride *= (ptrdiff_t)dims[k];
+    }
+    out->array.data = mxGetNumberOfElements(a) > 0 ? mxGetDoubles(a) : NULL;
+    out->array.ndim = ndim;
+    out->array.shape = out->shape;
+    out->array.strides = out->strides;
+}
+
+/* Copy an optional (rows, cols) logical or double mask into a C-contiguous u8
+ * mask, empty ([]) or missing masks return NULL. Free with mxFree. */
+static uint8_t *imgal_mex_mask(int nrhs, const mxArray *prhs[], int i, size_t rows,
+                               size_t cols)
+{
+    const mxArray *m;
+    uint8_t *out;
+    size_t r;
+    size_t c;
+
+    if (nrhs <= i || mxIsEmpty(prhs[i])) {
+        return NULL;
+    }
+    m = prhs[i];
+    if (mxGetNumberOfDimensions(m) != 2 || mxGetM(m) != rows || mxGetN(m) != cols) {
+        mexErrMsgIdAndTxt("imgal:InvalidArray",
+                          "The argument \"mask\" must be a %zu-by-%zu array.", rows, cols);
+    }
+    if (!mxIsLogical(m)) {
+        imgal_mex_require_double(m, "mask");
+    }
+    out = (uint8_t *)mxMalloc(rows * cols);
+    for (r = 0; r < rows; r++) {
+        for (c = 0; c < cols; c++) {
+            size_t src = r + rows * c;
+            out[r * cols + c] =
+                mxIsLogical(m) ? (mxGetLogicals(m)[src] != 0) : (mxGetDoubles(m)[src] != 0.0);
+        }
+    }
+    return out;
+}
+
+/* Copy a C-contiguous 3-dimensional buffer into a column major double array of
+ * the same shape. */
+static mxArray *imgal_mex_from_c_order(const double *buf, size_t d0, size_t d1, size_t d2)
+{
+    mwSize dims[3];
+    mxArray *out;
+    double *dst;
+    size_t i;
+    size_t j;
+    size_t k;
+
+    dims[0] = (mwSize)d0;
+    dims[1] = (mwSize)d1;
+    dims[2] = (mwSize)d2;
+    out = mxCreateNumericArray(3, dims, mxDOUBLE_CLASS, mxREAL);
+    dst = mxGetDoubles(out);
+    for (i = 0; i < d0; i++) {
+        for (j = 0; j < d1; j++) {
+            for (k = 0; k < d2; k++) {
+                dst[i + d0 * (j + d1 * k)] = buf[(i * d1 + j) * d2 + k];
+            }
+        }
+    }
+    return out;
+}
+
+#endif /* IMGAL_MEX_H */
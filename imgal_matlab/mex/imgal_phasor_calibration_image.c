@@ -0,0 +1,63 @@
+/*
+ * cal = imgal_phasor_calibration_image(gs, modulation, phase, axis, mask)
+ *
+ * MEX interface for imgal_phasor_calibration_image, see
+ * imgal_phasor_calibration_image.m for the MATLAB documentation.
+ */
+#include "imgal_mex.h"
+
+void mexFunction(int nlhs, mxArray *plhs[], int nrhs, const mxArray *prhs[])
+{
+    ImgalMexArray data;
+    double modulation;
+    double phase;
+    size_t axis;
+    size_t dims[2];
+    size_t n = 0;
+    size_t k;
+    size_t len;
+    uint8_t *mask;
+    double *out;
+    ImgalStatus status;
+
+    if (nrhs < 3 || nrhs > 5) {
+        mexErrMsgIdAndTxt("imgal:InvalidArguments",
+                          "Usage: cal = imgal_phasor_calibration_image(gs, modulation, phase, "
+                          "axis, mask)");
+    }
+    if (nlhs > 1) {
+        mexErrMsgIdAndTxt("imgal:InvalidArguments", "Too many output arguments.");
+    }
+    imgal_mex_array(prhs[0], "gs", &data);
+    modulation = imgal_mex_scalar(prhs[1], "modulation");
+    phase = imgal_mex_scalar(prhs[2], "phase");
+    axis = imgal_mex_axis_or(nrhs, prhs, 3, 2);
+    if (data.array.ndim != 3) {
+        mexErrMsgIdAndTxt("imgal:InvalidArray",
+                          "The argument \"gs\" must be a 3-dimensional array.");
+    }
+    if (axis >= 3) {
+        mexErrMsgIdAndTxt("imgal:InvalidParameter", "The argument \"axis\" must be 1, 2 or 3.");
+    }
+
+    /* the output has the same shape as the input */
+    for (k = 0; k < 3; k++) {
+        if (k != axis) {
+            dims[n++] = data.shape[k];
+        }
+    }
+    mask = imgal_mex_mask(nrhs, prhs, 4, dims[0], dims[1]);
+    len = data.shape[0] * data.shape[1] * data.shape[2];
+    out = (double *)mxMalloc(len * sizeof(double));
+    status = imgal_phasor_calibration_image(NULL, &data.array, modulation, phase, axis, mask, out,
+                                            len);
+    if (mask != NULL) {
+        mxFree(mask);
+    }
+    if (status != IMGAL_STATUS_OK) {
+        mxFree(out);
+        imgal_mex_check(status);
+    }
+    plhs[0] = imgal_mex_from_c_order(out, data.shape[0], data.shape[1], data.shape[2]);
+    mxFree(out);
+}
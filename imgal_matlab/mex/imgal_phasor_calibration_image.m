@@ -0,0 +1,16 @@
+%IMGAL_PHASOR_CALIBRATION_IMAGE Calibrate the coordinates of a phasor image.
+%   CAL = IMGAL_PHASOR_CALIBRATION_IMAGE(GS, MODULATION, PHASE) scales the
+%   (G, S) coordinates of the 3-D phasor image GS, with G and S in
+%   GS(:, :, 1) and GS(:, :, 2), by MODULATION and rotates them by PHASE.
+%   CAL has the same size as GS.
+%
+%   CAL = IMGAL_PHASOR_CALIBRATION_IMAGE(GS, MODULATION, PHASE, AXIS, MASK)
+%   also sets the G, S dimension AXIS (1, 2 or 3, default 3) and a logical
+%   MASK of the other dimensions, coordinates where MASK is false are not
+%   calibrated. Pass [] to use a default value.
+%
+%   Example:
+%       [m, phi] = imgal_phasor_modulation_and_phase(g_ref, s_ref, 4.0, 2*pi/12.5);
+%       cal = imgal_phasor_calibration_image(gs, m, phi);
+%
+%   See also IMGAL_PHASOR_TIME_DOMAIN_IMAGE, IMGAL_PHASOR_MODULATION_AND_PHASE.
@@ -0,0 +1,30 @@
+/*
+ * [modulation, phase] = imgal_phasor_modulation_and_phase(g, s, tau, omega)
+ *
+ * MEX interface for imgal_phasor_modulation_and_phase, see
+ * imgal_phasor_modulation_and_phase.m for the MATLAB documentation.
+ */
+#include "imgal_mex.h"
+
+void mexFunction(int nlhs, mxArray *plhs[], int nrhs, const mxArray *prhs[])
+{
+    double modulation;
+    double phase;
+
+    if (nrhs != 4) {
+        mexErrMsgIdAndTxt("imgal:InvalidArguments",
+                          "Usage: [modulation, phase] = imgal_phasor_modulation_and_phase(g, s, "
+                          "tau, omega)");
+    }
+    if (nlhs > 2) {
+        mexErrMsgIdAndTxt("imgal:InvalidArguments", "Too many output arguments.");
+    }
+    imgal_mex_check(imgal_phasor_modulation_and_phase(
+        NULL, imgal_mex_scalar(prhs[0], "g"), imgal_mex_scalar(prhs[1], "s"),
+        imgal_mex_scalar(prhs[2], "tau"), imgal_mex_scalar(prhs[3], "omega"), &modulation,
+        &phase));
+    plhs[0] = mxCreateDoubleScalar(modulation);
+    if (nlhs > 1) {
+        plhs[1] = mxCreateDoubleScalar(phase);
+    }
+}
@@ -0,0 +1,7 @@
+%IMGAL_PHASOR_MODULATION_AND_PHASE Phasor calibration values of a reference.
+%   [MODULATION, PHASE] = IMGAL_PHASOR_MODULATION_AND_PHASE(G, S, TAU, OMEGA)
+%   computes the modulation and phase calibration values from the measured
+%   (G, S) coordinates of a reference with the known monoexponential lifetime
+%   TAU, at the angular frequency OMEGA (i.e. 2*pi*harmonic/period).
+%
+%   See also IMGAL_PHASOR_CALIBRATION_IMAGE, IMGAL_PHASOR_TIME_DOMAIN_IMAGE.
@@ -0,0 +1,61 @@
+/*
+ * gs = imgal_phasor_time_domain_image(data, period, harmonic, axis, mask)
+ *
+ * MEX interface for imgal_phasor_time_domain_image, see
+ * imgal_phasor_time_domain_image.m for the MATLAB documentation.
+ */
+#include "imgal_mex.h"
+
+void mexFunction(int nlhs, mxArray *plhs[], int nrhs, const mxArray *prhs[])
+{
+    ImgalMexArray data;
+    double period;
+    double harmonic;
+    size_t axis;
+    size_t dims[2];
+    size_t n = 0;
+    size_t k;
+    uint8_t *mask;
+    double *out;
+    ImgalStatus status;
+
+    if (nrhs < 2 || nrhs > 5) {
+        mexErrMsgIdAndTxt("imgal:InvalidArguments",
+                          "Usage: gs = imgal_phasor_time_domain_image(data, period, "
+                          "harmonic, axis, mask)");
+    }
+    if (nlhs > 1) {
+        mexErrMsgIdAndTxt("imgal:InvalidArguments", "Too many output arguments.");
+    }
+    imgal_mex_array(prhs[0], "data", &data);
+    period = imgal_mex_scalar(prhs[1], "period");
+    harmonic = imgal_mex_scalar_or(nrhs, prhs, 2, "harmonic", 1.0);
+    axis = imgal_mex_axis_or(nrhs, prhs, 3, 2);
+    if (data.array.ndim != 3) {
+        mexErrMsgIdAndTxt("imgal:InvalidArray",
+                          "The argument \"data\" must be a 3-dimensional array.");
+    }
+    if (axis >= 3) {
+        mexErrMsgIdAndTxt("imgal:InvalidParameter", "The argument \"axis\" must be 1, 2 or 3.");
+    }
+
+    /* the output has the non-decay dimensions in order and G, S last */
+    for (k = 0; k < 3; k++) {
+        if (k != axis) {
+            dims[n++] = data.shape[k];
+        }
+    }
+    mask = imgal_mex_mask(nrhs, prhs, 4, dims[0], dims[1]);
+    out = (double *)mxMalloc(dims[0] * dims[1] * 2 * sizeof(double));
+    status = imgal_phasor_time_domain_image(NULL, &data.array, period, harmonic, axis, mask, out,
+                                            dims[0] * dims[1] * 2);
+    if (mask != NULL) {
+        mxFree(mask);
+    }
+    if (status != IMGAL_STATUS_OK) {
+        mxFree(out);
+        imgal_mex_check(status);
+    }
+    plhs[0] = imgal_mex_from_c_order(out, dims[0], dims[1], 2);
+    mxFree(out);
+}
@@ -0,0 +1,17 @@
+%IMGAL_PHASOR_TIME_DOMAIN_IMAGE Phasor coordinates of a 3-D decay image.
+%   GS = IMGAL_PHASOR_TIME_DOMAIN_IMAGE(DATA, PERIOD) computes the real and
+%   imaginary (G, S) coordinates of each decay of the 3-D double array DATA,
+%   with the decays along the third dimension, and the period (i.e. time
+%   interval) PERIOD. GS has the non-decay dimensions of DATA, in order, with
+%   G and S in GS(:, :, 1) and GS(:, :, 2).
+%
+%   GS = IMGAL_PHASOR_TIME_DOMAIN_IMAGE(DATA, PERIOD, HARMONIC, AXIS, MASK)
+%   also sets the harmonic (default 1), the decay dimension AXIS (1, 2 or 3,
+%   default 3) and a logical MASK of the non-decay dimensions, decays where
+%   MASK is false are set to (0, 0). Pass [] to use a default value.
+%
+%   Example:
+%       gs = imgal_phasor_time_domain_image(double(flim), 12.5);
+%       scatter(reshape(gs(:, :, 1), [], 1), reshape(gs(:, :, 2), [], 1), '.');
+%
+%   See also IMGAL_PHASOR_CALIBRATION_IMAGE, IMGAL_PHASOR_MODULATION_AND_PHASE.
@@ -0,0 +1,37 @@
+use pyo3::prelude::*;
+
+use crate::functions::anisotropy_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "anisotropy" submodule.
+pub fn register_anisotropy_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let anisotropy_module = PyModule::new(parent_module.py(), "anisotropy")?;
+
+    // add module to Python's sys.modules
+    py_import_module("anisotropy");
+
+    // add anisotropy submodule functions
+    anisotropy_module.add_function(wrap_pyfunction!(
+        anisotropy_functions::anisotropy_decay,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        anisotropy_functions::anisotropy_decay_image,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        anisotropy_functions::anisotropy_g_factor,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        anisotropy_functions::anisotropy_steady_state,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        anisotropy_functions::anisotropy_tail_g_factor,
+        &anisotropy_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&anisotropy_module)
+}
@@ -19,6 +19,42 @@ pub fn register_colocalization_module(parent_module: &Bound<'_, PyModule>) -> Py
         colocalization_functions::colocalization_saca_3d,
         &colocalization_module
     )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_saca_significance_mask,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_pearson,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_costes_test,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_manders,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_costes_threshold,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_object,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_ccf,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_tile_map,
+        &colocalization_module
+    )?)?;
+    colocalization_module.add_function(wrap_pyfunction!(
+        colocalization_functions::colocalization_label_map,
+        &colocalization_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&colocalization_module)
@@ -0,0 +1,28 @@
+use pyo3::prelude::*;
+
+use crate::functions::config_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "config" submodule.
+pub fn register_config_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let config_module = PyModule::new(parent_module.py(), "config")?;
+
+    // add module to Python's sys.modules
+    py_import_module("config");
+
+    // add config submodule functions
+    config_module.add_function(wrap_pyfunction!(
+        config_functions::config_num_threads,
+        &config_module
+    )?)?;
+    config_module.add_function(wrap_pyfunction!(
+        config_functions::config_set_num_threads,
+        &config_module
+    )?)?;
+
+    // add config submodule classes
+    config_module.add_class::<config_functions::ThreadPool>()?;
+
+    // attach to parent module
+    parent_module.add_submodule(&config_module)
+}
@@ -0,0 +1,40 @@
+use pyo3::prelude::*;
+
+use crate::functions::correction_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "correction" submodule.
+pub fn register_correction_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let correction_module = PyModule::new(parent_module.py(), "correction")?;
+
+    // add module to Python's sys.modules
+    py_import_module("correction");
+
+    // add correction submodule classes
+    correction_module.add_class::<correction_functions::BleachResult>()?;
+
+    // add correction submodule functions
+    correction_module.add_function(wrap_pyfunction!(
+        correction_functions::correction_align_decays,
+        &correction_module
+    )?)?;
+    correction_module.add_function(wrap_pyfunction!(
+        correction_functions::correction_bleach,
+        &correction_module
+    )?)?;
+    correction_module.add_function(wrap_pyfunction!(
+        correction_functions::correction_flatfield,
+        &correction_module
+    )?)?;
+    correction_module.add_function(wrap_pyfunction!(
+        correction_functions::correction_t0,
+        &correction_module
+    )?)?;
+    correction_module.add_function(wrap_pyfunction!(
+        correction_functions::correction_t0_image,
+        &correction_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&correction_module)
+}
@@ -11,10 +11,50 @@ pub fn register_distribution_module(parent_module: &Bound<'_, PyModule>) -> PyRe
     py_import_module("distribution");
 
     // add distribution submodule functions
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_exponential,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_gamma,
+        &distribution_module
+    )?)?;
     distribution_module.add_function(wrap_pyfunction!(
         distribution_functions::distribution_gaussian,
         &distribution_module
     )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_log_normal,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_lorentzian,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_inverse_normal_cdf,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_normal_cdf,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_sample_exponential,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_sample_gamma,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_sample_gaussian,
+        &distribution_module
+    )?)?;
+    distribution_module.add_function(wrap_pyfunction!(
+        distribution_functions::distribution_sample_poisson,
+        &distribution_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&distribution_module)
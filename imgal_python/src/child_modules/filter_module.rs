@@ -11,14 +11,126 @@ pub fn register_filter_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
     py_import_module("filter");
 
     // add filters submodule functions
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_convolve,
+        &filter_module
+    )?)?;
     filter_module.add_function(wrap_pyfunction!(
         filter_functions::filter_fft_convolve_1d,
         &filter_module
     )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_fft_convolve_2d,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_fft_convolve_nd,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_fft_convolve_axis,
+        &filter_module
+    )?)?;
     filter_module.add_function(wrap_pyfunction!(
         filter_functions::filter_fft_deconvolve_1d,
         &filter_module
     )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gaussian_low_pass,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gaussian_high_pass,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gaussian_band_pass,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_butterworth_low_pass,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_butterworth_high_pass,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_butterworth_band_pass,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gaussian,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gaussian_mut,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_unsharp_mask,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_difference_of_gaussians,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_wiener,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_log,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_frangi,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_sato,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_sobel,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_scharr,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_gradient_magnitude,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_rolling_ball,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_top_hat,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_equalize_histogram,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_match_histogram,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_clahe,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_rescale_percentile,
+        &filter_module
+    )?)?;
+    filter_module.add_function(wrap_pyfunction!(
+        filter_functions::filter_max_response,
+        &filter_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&filter_module)
@@ -15,6 +15,22 @@ pub fn register_integration_module(parent_module: &Bound<'_, PyModule>) -> PyRes
         integration_functions::integration_composite_simpson,
         &integration_module
     )?)?;
+    integration_module.add_function(wrap_pyfunction!(
+        integration_functions::integration_composite_simpson_nonuniform,
+        &integration_module
+    )?)?;
+    integration_module.add_function(wrap_pyfunction!(
+        integration_functions::integration_cumulative_trapezoid,
+        &integration_module
+    )?)?;
+    integration_module.add_function(wrap_pyfunction!(
+        integration_functions::integration_cumulative_trapezoid_axis,
+        &integration_module
+    )?)?;
+    integration_module.add_function(wrap_pyfunction!(
+        integration_functions::integration_integrate_axis,
+        &integration_module
+    )?)?;
     integration_module.add_function(wrap_pyfunction!(
         integration_functions::integration_midpoint,
         &integration_module
@@ -23,6 +39,14 @@ pub fn register_integration_module(parent_module: &Bound<'_, PyModule>) -> PyRes
         integration_functions::integration_simpson,
         &integration_module
     )?)?;
+    integration_module.add_function(wrap_pyfunction!(
+        integration_functions::integration_trapezoid,
+        &integration_module
+    )?)?;
+    integration_module.add_function(wrap_pyfunction!(
+        integration_functions::integration_trapezoid_nonuniform,
+        &integration_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&integration_module)
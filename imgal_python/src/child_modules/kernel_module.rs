@@ -7,10 +7,22 @@ use crate::utils::py_import_module;
 pub fn register_kernel_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let kernel_module = PyModule::new(parent_module.py(), "kernel")?;
     let neighborhood_module = PyModule::new(parent_module.py(), "neighborhood")?;
+    let parametric_module = PyModule::new(parent_module.py(), "parametric")?;
 
     // add module to Python's sys.modules
     py_import_module("kernel");
     py_import_module("kernel.neighborhood");
+    py_import_module("kernel.parametric");
+
+    // add kernel module functions
+    kernel_module.add_function(wrap_pyfunction!(
+        kernel_functions::kernel_orientation_bank,
+        &kernel_module
+    )?)?;
+    kernel_module.add_function(wrap_pyfunction!(
+        kernel_functions::kernel_separate,
+        &kernel_module
+    )?)?;
 
     // add kernel::neighborhood submodule functions
     neighborhood_module.add_function(wrap_pyfunction!(
@@ -30,7 +42,34 @@ pub fn register_kernel_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
         &neighborhood_module
     )?)?;
 
+    // add kernel::parametric submodule functions
+    parametric_module.add_function(wrap_pyfunction!(
+        kernel_functions::parametric_gaussian,
+        &parametric_module
+    )?)?;
+    parametric_module.add_function(wrap_pyfunction!(
+        kernel_functions::parametric_laplacian,
+        &parametric_module
+    )?)?;
+    parametric_module.add_function(wrap_pyfunction!(
+        kernel_functions::parametric_disk,
+        &parametric_module
+    )?)?;
+    parametric_module.add_function(wrap_pyfunction!(
+        kernel_functions::parametric_ring,
+        &parametric_module
+    )?)?;
+    parametric_module.add_function(wrap_pyfunction!(
+        kernel_functions::parametric_gabor,
+        &parametric_module
+    )?)?;
+    parametric_module.add_function(wrap_pyfunction!(
+        kernel_functions::parametric_rectangle,
+        &parametric_module
+    )?)?;
+
     // attach kernel submodules before attaching to the parent module
     kernel_module.add_submodule(&neighborhood_module)?;
+    kernel_module.add_submodule(&parametric_module)?;
     parent_module.add_submodule(&kernel_module)
 }
@@ -0,0 +1,21 @@
+use pyo3::prelude::*;
+
+use crate::functions::lifetime_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "lifetime" submodule.
+pub fn register_lifetime_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let lifetime_module = PyModule::new(parent_module.py(), "lifetime")?;
+
+    // add module to Python's sys.modules
+    py_import_module("lifetime");
+
+    // add lifetime submodule functions
+    lifetime_module.add_function(wrap_pyfunction!(
+        lifetime_functions::lifetime_intensity_image,
+        &lifetime_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&lifetime_module)
+}
@@ -0,0 +1,28 @@
+use pyo3::prelude::*;
+
+use crate::functions::measure_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "measure" submodule.
+pub fn register_measure_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let measure_module = PyModule::new(parent_module.py(), "measure")?;
+
+    // add module to Python's sys.modules
+    py_import_module("measure");
+
+    // add measure submodule classes
+    measure_module.add_class::<measure_functions::ArrowTable>()?;
+
+    // add measure submodule functions
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_find_contours,
+        &measure_module
+    )?)?;
+    measure_module.add_function(wrap_pyfunction!(
+        measure_functions::measure_regionprops,
+        &measure_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&measure_module)
+}
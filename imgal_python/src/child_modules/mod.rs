@@ -1,11 +1,24 @@
+pub mod anisotropy_module;
 pub mod colocalization_module;
+pub mod config_module;
+pub mod correction_module;
 pub mod distribution_module;
+pub mod feature_module;
 pub mod filter_module;
 pub mod image_module;
 pub mod integration_module;
 pub mod kernel_module;
+pub mod lifetime_module;
+pub mod measure_module;
+pub mod morphology_module;
+pub mod operator_module;
 pub mod parameter_module;
 pub mod phasor_module;
+pub mod register_module;
+pub mod roi_module;
 pub mod simulation_module;
 pub mod statistics_module;
 pub mod threshold_module;
+pub mod transform_module;
+pub mod unmix_module;
+pub mod version_module;
@@ -0,0 +1,49 @@
+use pyo3::prelude::*;
+
+use crate::functions::morphology_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "morphology" submodule.
+pub fn register_morphology_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let morphology_module = PyModule::new(parent_module.py(), "morphology")?;
+
+    // add module to Python's sys.modules
+    py_import_module("morphology");
+
+    // add morphology submodule functions
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_analyze_skeleton,
+        &morphology_module
+    )?)?;
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_closing,
+        &morphology_module
+    )?)?;
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_dilation,
+        &morphology_module
+    )?)?;
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_erosion,
+        &morphology_module
+    )?)?;
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_gradient,
+        &morphology_module
+    )?)?;
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_label,
+        &morphology_module
+    )?)?;
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_opening,
+        &morphology_module
+    )?)?;
+    morphology_module.add_function(wrap_pyfunction!(
+        morphology_functions::morphology_skeletonize,
+        &morphology_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&morphology_module)
+}
@@ -0,0 +1,33 @@
+use pyo3::prelude::*;
+
+use crate::functions::operator_functions;
+use crate::utils::py_import_module;
+
+/// Python binding for the "operator" submodule.
+pub fn register_operator_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let operator_module = PyModule::new(parent_module.py(), "operator")?;
+
+    // add module to Python's sys.modules
+    py_import_module("operator");
+
+    // add operator submodule functions
+    operator_module.add_function(wrap_pyfunction!(
+        operator_functions::operator_pixel,
+        &operator_module
+    )?)?;
+    operator_module.add_function(wrap_pyfunction!(
+        operator_functions::operator_lane,
+        &operator_module
+    )?)?;
+    operator_module.add_function(wrap_pyfunction!(
+        operator_functions::operator_pixel_op_names,
+        &operator_module
+    )?)?;
+    operator_module.add_function(wrap_pyfunction!(
+        operator_functions::operator_lane_op_names,
+        &operator_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&operator_module)
+}
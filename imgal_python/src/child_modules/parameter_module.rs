@@ -15,10 +15,46 @@ pub fn register_parameter_module(parent_module: &Bound<'_, PyModule>) -> PyResul
         parameter_functions::parameter_abbe_diffraction_limit,
         &parameter_module
     )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_airy_disk_radius,
+        &parameter_module
+    )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_axial_diffraction_limit,
+        &parameter_module
+    )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_lifetime_precision,
+        &parameter_module
+    )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_nyquist_axial_step,
+        &parameter_module
+    )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_nyquist_pixel_size,
+        &parameter_module
+    )?)?;
     parameter_module.add_function(wrap_pyfunction!(
         parameter_functions::parameter_omega,
         &parameter_module
     )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_phasor_precision,
+        &parameter_module
+    )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_photon_budget,
+        &parameter_module
+    )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_rayleigh_criterion,
+        &parameter_module
+    )?)?;
+    parameter_module.add_function(wrap_pyfunction!(
+        parameter_functions::parameter_snr,
+        &parameter_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&parameter_module)
@@ -6,17 +6,86 @@ use crate::utils::py_import_module;
 /// Python binding for the "phasor" submodule.
 pub fn register_phasor_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let phasor_module = PyModule::new(parent_module.py(), "phasor")?;
+    let analysis_module = PyModule::new(parent_module.py(), "analysis")?;
     let calibration_module = PyModule::new(parent_module.py(), "calibration")?;
+    let frequency_domain_module = PyModule::new(parent_module.py(), "frequency_domain")?;
+    let metabolic_module = PyModule::new(parent_module.py(), "metabolic")?;
     let plot_module = PyModule::new(parent_module.py(), "plot")?;
+    let spectral_module = PyModule::new(parent_module.py(), "spectral")?;
     let time_domain_module = PyModule::new(parent_module.py(), "time_domain")?;
 
     // add module to python's sys.modules
     py_import_module("phasor");
+    py_import_module("phasor.analysis");
     py_import_module("phasor.calibration");
+    py_import_module("phasor.frequency_domain");
+    py_import_module("phasor.metabolic");
     py_import_module("phasor.plot");
+    py_import_module("phasor.spectral");
     py_import_module("phasor.time_domain");
 
+    // add phasor::analysis submodule classes
+    analysis_module.add_class::<phasor_functions::PhasorAnalysis>()?;
+
+    // add phasor::frequency_domain submodule functions
+    frequency_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::frequency_domain_coordinates,
+        &frequency_domain_module
+    )?)?;
+    frequency_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::frequency_domain_image,
+        &frequency_domain_module
+    )?)?;
+
+    // add phasor::metabolic submodule functions
+    metabolic_module.add_function(wrap_pyfunction!(
+        phasor_functions::metabolic_bound_fraction,
+        &metabolic_module
+    )?)?;
+    metabolic_module.add_function(wrap_pyfunction!(
+        phasor_functions::metabolic_bound_fraction_image,
+        &metabolic_module
+    )?)?;
+    metabolic_module.add_function(wrap_pyfunction!(
+        phasor_functions::metabolic_metabolic_index,
+        &metabolic_module
+    )?)?;
+    metabolic_module.add_function(wrap_pyfunction!(
+        phasor_functions::metabolic_metabolic_index_image,
+        &metabolic_module
+    )?)?;
+
+    // add phasor::spectral submodule functions
+    spectral_module.add_function(wrap_pyfunction!(
+        phasor_functions::spectral_coordinates,
+        &spectral_module
+    )?)?;
+    spectral_module.add_function(wrap_pyfunction!(
+        phasor_functions::spectral_cursor_mask,
+        &spectral_module
+    )?)?;
+    spectral_module.add_function(wrap_pyfunction!(
+        phasor_functions::spectral_image,
+        &spectral_module
+    )?)?;
+    spectral_module.add_function(wrap_pyfunction!(
+        phasor_functions::spectral_modulation_and_phase,
+        &spectral_module
+    )?)?;
+    spectral_module.add_function(wrap_pyfunction!(
+        phasor_functions::spectral_unmix,
+        &spectral_module
+    )?)?;
+    spectral_module.add_function(wrap_pyfunction!(
+        phasor_functions::spectral_unmix_image,
+        &spectral_module
+    )?)?;
+
     // add phasor::time_domain submodule functions
+    time_domain_module.add_function(wrap_pyfunction!(
+        phasor_functions::time_domain_batch,
+        &time_domain_module
+    )?)?;
     time_domain_module.add_function(wrap_pyfunction!(
         phasor_functions::time_domain_histogram_quality,
         &time_domain_module
@@ -38,6 +107,9 @@ pub fn register_phasor_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
         &time_domain_module
     )?)?;
 
+    // add phasor::calibration submodule classes
+    calibration_module.add_class::<phasor_functions::CalibrationResult>()?;
+
     // add phasor::calibration submodule functions
     calibration_module.add_function(wrap_pyfunction!(
         phasor_functions::calibration_coordinates,
@@ -75,8 +147,12 @@ pub fn register_phasor_module(parent_module: &Bound<'_, PyModule>) -> PyResult<(
     )?)?;
 
     // attach phasor submodule before attaching to the parent module
+    phasor_module.add_submodule(&analysis_module)?;
     phasor_module.add_submodule(&calibration_module)?;
+    phasor_module.add_submodule(&frequency_domain_module)?;
+    phasor_module.add_submodule(&metabolic_module)?;
     phasor_module.add_submodule(&plot_module)?;
+    phasor_module.add_submodule(&spectral_module)?;
     phasor_module.add_submodule(&time_domain_module)?;
     parent_module.add_submodule(&phasor_module)
 }
@@ -0,0 +1,29 @@
+use pyo3::prelude::*;
+
+use crate::functions::register_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "register" submodule.
+pub fn register_register_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let register_module = PyModule::new(parent_module.py(), "register")?;
+
+    // add module to Python's sys.modules
+    py_import_module("register");
+
+    // add register submodule functions
+    register_module.add_function(wrap_pyfunction!(
+        register_functions::register_affine,
+        &register_module
+    )?)?;
+    register_module.add_function(wrap_pyfunction!(
+        register_functions::register_apply_shift,
+        &register_module
+    )?)?;
+    register_module.add_function(wrap_pyfunction!(
+        register_functions::register_phase_correlation,
+        &register_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&register_module)
+}
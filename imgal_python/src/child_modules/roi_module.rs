@@ -0,0 +1,31 @@
+use pyo3::prelude::*;
+
+use crate::functions::roi_functions;
+use crate::utils::py_import_module;
+
+/// Python binding for the "roi" submodule.
+pub fn register_roi_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let roi_module = PyModule::new(parent_module.py(), "roi")?;
+    let composite_module = PyModule::new(parent_module.py(), "composite")?;
+
+    // add module to python's sys.modules
+    py_import_module("roi");
+    py_import_module("roi.composite");
+
+    // add roi::composite submodule functions
+    composite_module.add_function(wrap_pyfunction!(
+        roi_functions::composite_summary_4d,
+        &composite_module
+    )?)?;
+    composite_module.add_function(wrap_pyfunction!(
+        roi_functions::composite_summary_5d,
+        &composite_module
+    )?)?;
+
+    // add roi module classes
+    roi_module.add_class::<roi_functions::RoiManager>()?;
+
+    // attach roi submodule before attaching to the parent module
+    roi_module.add_submodule(&composite_module)?;
+    parent_module.add_submodule(&roi_module)
+}
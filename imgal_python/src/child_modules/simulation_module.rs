@@ -6,17 +6,57 @@ use crate::utils::py_import_module;
 /// Python bindings for the "simulation" submodule.
 pub fn register_simulation_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
     let simulation_module = PyModule::new(parent_module.py(), "simulation")?;
+    let anisotropy_module = PyModule::new(parent_module.py(), "anisotropy")?;
     let decay_module = PyModule::new(parent_module.py(), "decay")?;
     let instrument_module = PyModule::new(parent_module.py(), "instrument")?;
     let noise_module = PyModule::new(parent_module.py(), "noise")?;
+    let pattern_module = PyModule::new(parent_module.py(), "pattern")?;
+    let rng_module = PyModule::new(parent_module.py(), "rng")?;
+    let spectra_module = PyModule::new(parent_module.py(), "spectra")?;
+    let timelapse_module = PyModule::new(parent_module.py(), "timelapse")?;
 
     // add module to python's sys.modules
     py_import_module("simulation");
+    py_import_module("simulation.anisotropy");
     py_import_module("simulation.decay");
     py_import_module("simulation.instrument");
     py_import_module("simulation.noise");
+    py_import_module("simulation.pattern");
+    py_import_module("simulation.rng");
+    py_import_module("simulation.spectra");
+    py_import_module("simulation.timelapse");
+
+    // add simulation::anisotropy submodule functions
+    anisotropy_module.add_function(wrap_pyfunction!(
+        simulation_functions::anisotropy_anisotropy_decay_1d,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        simulation_functions::anisotropy_gaussian_anisotropy_1d,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        simulation_functions::anisotropy_gaussian_anisotropy_3d,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        simulation_functions::anisotropy_ideal_anisotropy_1d,
+        &anisotropy_module
+    )?)?;
+    anisotropy_module.add_function(wrap_pyfunction!(
+        simulation_functions::anisotropy_irf_anisotropy_1d,
+        &anisotropy_module
+    )?)?;
 
     // add simulation::decay submodule functions
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_fret_exponential_1d,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_fret_exponential_3d,
+        &decay_module
+    )?)?;
     decay_module.add_function(wrap_pyfunction!(
         simulation_functions::decay_gaussian_exponential_1d,
         &decay_module
@@ -25,6 +65,22 @@ pub fn register_simulation_module(parent_module: &Bound<'_, PyModule>) -> PyResu
         simulation_functions::decay_gaussian_exponential_3d,
         &decay_module
     )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_gaussian_exponential_periodic_1d,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_gaussian_exponential_periodic_3d,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_gaussian_fret_exponential_1d,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_gaussian_fret_exponential_3d,
+        &decay_module
+    )?)?;
     decay_module.add_function(wrap_pyfunction!(
         simulation_functions::decay_ideal_exponential_1d,
         &decay_module
@@ -37,6 +93,10 @@ pub fn register_simulation_module(parent_module: &Bound<'_, PyModule>) -> PyResu
         simulation_functions::decay_ideal_exponential_3d,
         &decay_module
     )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_ideal_exponential_periodic_1d,
+        &decay_module
+    )?)?;
     decay_module.add_function(wrap_pyfunction!(
         simulation_functions::decay_irf_exponential_1d,
         &decay_module
@@ -45,14 +105,74 @@ pub fn register_simulation_module(parent_module: &Bound<'_, PyModule>) -> PyResu
         simulation_functions::decay_irf_exponential_3d,
         &decay_module
     )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_irf_exponential_periodic_1d,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_irf_exponential_periodic_3d,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_sample_photons,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_sample_photons_1d,
+        &decay_module
+    )?)?;
+    decay_module.add_function(wrap_pyfunction!(
+        simulation_functions::decay_sample_photons_3d,
+        &decay_module
+    )?)?;
 
     // add simulation::instrument submodule functions
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_born_wolf_psf_2d,
+        &instrument_module
+    )?)?;
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_born_wolf_psf_3d,
+        &instrument_module
+    )?)?;
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_double_peak_irf_1d,
+        &instrument_module
+    )?)?;
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_exponentially_modified_gaussian_irf_1d,
+        &instrument_module
+    )?)?;
     instrument_module.add_function(wrap_pyfunction!(
         simulation_functions::instrument_gaussian_irf_1d,
         &instrument_module
     )?)?;
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_gaussian_psf_2d,
+        &instrument_module
+    )?)?;
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_gaussian_psf_3d,
+        &instrument_module
+    )?)?;
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_gibson_lanni_psf_3d,
+        &instrument_module
+    )?)?;
+    instrument_module.add_function(wrap_pyfunction!(
+        simulation_functions::instrument_measured_irf_1d,
+        &instrument_module
+    )?)?;
 
     // add simulation::noise submodule functions
+    noise_module.add_function(wrap_pyfunction!(
+        simulation_functions::noise_poisson,
+        &noise_module
+    )?)?;
+    noise_module.add_function(wrap_pyfunction!(
+        simulation_functions::noise_poisson_mut,
+        &noise_module
+    )?)?;
     noise_module.add_function(wrap_pyfunction!(
         simulation_functions::noise_poisson_1d,
         &noise_module
@@ -70,9 +190,67 @@ pub fn register_simulation_module(parent_module: &Bound<'_, PyModule>) -> PyResu
         &noise_module
     )?)?;
 
+    // add simulation::pattern submodule functions
+    pattern_module.add_function(wrap_pyfunction!(
+        simulation_functions::pattern_blobs_2d,
+        &pattern_module
+    )?)?;
+    pattern_module.add_function(wrap_pyfunction!(
+        simulation_functions::pattern_checkerboard_2d,
+        &pattern_module
+    )?)?;
+    pattern_module.add_function(wrap_pyfunction!(
+        simulation_functions::pattern_power_law_noise_2d,
+        &pattern_module
+    )?)?;
+    pattern_module.add_function(wrap_pyfunction!(
+        simulation_functions::pattern_sinusoidal_grating_2d,
+        &pattern_module
+    )?)?;
+
+    // add simulation::rng submodule classes
+    rng_module.add_class::<simulation_functions::SimulationRng>()?;
+
+    // add simulation::spectra submodule functions
+    spectra_module.add_function(wrap_pyfunction!(
+        simulation_functions::spectra_gaussian_spectrum_1d,
+        &spectra_module
+    )?)?;
+    spectra_module.add_function(wrap_pyfunction!(
+        simulation_functions::spectra_gradient_abundances_3d,
+        &spectra_module
+    )?)?;
+    spectra_module.add_function(wrap_pyfunction!(
+        simulation_functions::spectra_linear_mixture_3d,
+        &spectra_module
+    )?)?;
+    spectra_module.add_function(wrap_pyfunction!(
+        simulation_functions::spectra_linear_mixture_poisson_3d,
+        &spectra_module
+    )?)?;
+    spectra_module.add_function(wrap_pyfunction!(
+        simulation_functions::spectra_registered_spectrum_1d,
+        &spectra_module
+    )?)?;
+
+    // add simulation::timelapse submodule functions
+    timelapse_module.add_function(wrap_pyfunction!(
+        simulation_functions::timelapse_photobleaching_4d,
+        &timelapse_module
+    )?)?;
+    timelapse_module.add_function(wrap_pyfunction!(
+        simulation_functions::timelapse_timelapse_4d,
+        &timelapse_module
+    )?)?;
+
     // attach simulation submodules before attaching to the parent module
+    simulation_module.add_submodule(&anisotropy_module)?;
     simulation_module.add_submodule(&decay_module)?;
     simulation_module.add_submodule(&instrument_module)?;
     simulation_module.add_submodule(&noise_module)?;
+    simulation_module.add_submodule(&pattern_module)?;
+    simulation_module.add_submodule(&rng_module)?;
+    simulation_module.add_submodule(&spectra_module)?;
+    simulation_module.add_submodule(&timelapse_module)?;
     parent_module.add_submodule(&simulation_module)
 }
@@ -11,10 +11,38 @@ pub fn register_statistics_module(parent_module: &Bound<'_, PyModule>) -> PyResu
     py_import_module("statistics");
 
     // add statistics submodule functions
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_bootstrap,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_circular_mean,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_circular_variance,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_describe,
+        &statistics_module
+    )?)?;
     statistics_module.add_function(wrap_pyfunction!(
         statistics_functions::statistics_effective_sample_size,
         &statistics_module
     )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_frc,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_histogram,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_joint_histogram,
+        &statistics_module
+    )?)?;
     statistics_module.add_function(wrap_pyfunction!(
         statistics_functions::statistics_max,
         &statistics_module
@@ -27,18 +55,81 @@ pub fn register_statistics_module(parent_module: &Bound<'_, PyModule>) -> PyResu
         statistics_functions::statistics_min_max,
         &statistics_module
     )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_mutual_information,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_paired_bootstrap,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_paired_permutation_test,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_permutation_test,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_quantile,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_rayleigh_test,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_rank,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_spearman,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_split_photons,
+        &statistics_module
+    )?)?;
     statistics_module.add_function(wrap_pyfunction!(
         statistics_functions::statistics_sum,
         &statistics_module
     )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_covariance,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_histogram,
+        &statistics_module
+    )?)?;
     statistics_module.add_function(wrap_pyfunction!(
         statistics_functions::statistics_weighted_kendall_tau_b,
         &statistics_module
     )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_mean,
+        &statistics_module
+    )?)?;
     statistics_module.add_function(wrap_pyfunction!(
         statistics_functions::statistics_weighted_merge_sort_mut,
         &statistics_module
     )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_pearson,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_quantile,
+        &statistics_module
+    )?)?;
+    statistics_module.add_function(wrap_pyfunction!(
+        statistics_functions::statistics_weighted_variance,
+        &statistics_module
+    )?)?;
+
+    // add statistics module classes
+    statistics_module.add_class::<statistics_functions::RunningStats>()?;
 
     // attach to parent module
     parent_module.add_submodule(&statistics_module)
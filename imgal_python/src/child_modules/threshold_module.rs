@@ -15,6 +15,26 @@ pub fn register_threshold_module(parent_module: &Bound<'_, PyModule>) -> PyResul
         threshold_functions::threshold_manual_mask,
         &threshold_module
     )?)?;
+    threshold_module.add_function(wrap_pyfunction!(
+        threshold_functions::threshold_otsu,
+        &threshold_module
+    )?)?;
+    threshold_module.add_function(wrap_pyfunction!(
+        threshold_functions::threshold_otsu_mask,
+        &threshold_module
+    )?)?;
+    threshold_module.add_function(wrap_pyfunction!(
+        threshold_functions::threshold_multi_otsu,
+        &threshold_module
+    )?)?;
+    threshold_module.add_function(wrap_pyfunction!(
+        threshold_functions::threshold_percentile,
+        &threshold_module
+    )?)?;
+    threshold_module.add_function(wrap_pyfunction!(
+        threshold_functions::threshold_per_label,
+        &threshold_module
+    )?)?;
 
     // attach to parent module
     parent_module.add_submodule(&threshold_module)
@@ -0,0 +1,29 @@
+use pyo3::prelude::*;
+
+use crate::functions::transform_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "transform" submodule.
+pub fn register_transform_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let transform_module = PyModule::new(parent_module.py(), "transform")?;
+
+    // add module to Python's sys.modules
+    py_import_module("transform");
+
+    // add transform submodule functions
+    transform_module.add_function(wrap_pyfunction!(
+        transform_functions::transform_rescale,
+        &transform_module
+    )?)?;
+    transform_module.add_function(wrap_pyfunction!(
+        transform_functions::transform_resize,
+        &transform_module
+    )?)?;
+    transform_module.add_function(wrap_pyfunction!(
+        transform_functions::transform_warp_affine,
+        &transform_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&transform_module)
+}
@@ -0,0 +1,21 @@
+use pyo3::prelude::*;
+
+use crate::functions::unmix_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "unmix" submodule.
+pub fn register_unmix_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let unmix_module = PyModule::new(parent_module.py(), "unmix")?;
+
+    // add module to Python's sys.modules
+    py_import_module("unmix");
+
+    // add unmix submodule functions
+    unmix_module.add_function(wrap_pyfunction!(
+        unmix_functions::unmix_linear,
+        &unmix_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&unmix_module)
+}
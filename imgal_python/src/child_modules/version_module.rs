@@ -0,0 +1,29 @@
+use pyo3::prelude::*;
+
+use crate::functions::version_functions;
+use crate::utils::py_import_module;
+
+/// Python bindings for the "version" submodule.
+pub fn register_version_module(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let version_module = PyModule::new(parent_module.py(), "version")?;
+
+    // add module to Python's sys.modules
+    py_import_module("version");
+
+    // add version submodule functions
+    version_module.add_function(wrap_pyfunction!(
+        version_functions::version_algorithm_version,
+        &version_module
+    )?)?;
+    version_module.add_function(wrap_pyfunction!(
+        version_functions::version_latest_algorithm_version,
+        &version_module
+    )?)?;
+    version_module.add_function(wrap_pyfunction!(
+        version_functions::version_set_algorithm_version,
+        &version_module
+    )?)?;
+
+    // attach to parent module
+    parent_module.add_submodule(&version_module)
+}
@@ -1,48 +1,71 @@
-use pyo3::PyErr;
-use pyo3::exceptions::{PyException, PyIndexError, PyValueError};
+use std::ffi::CString;
 
-use imgal::error::ArrayError;
+use pyo3::exceptions::{PyArithmeticError, PyException, PyIndexError, PyUserWarning, PyValueError};
+use pyo3::prelude::*;
 
-/// Map ArrayError types to Python exceptions.
-pub fn map_array_error(err: ArrayError) -> PyErr {
+use imgal::error::ImgalError;
+use imgal::error::warning;
+
+/// Map ImgalError types to Python exceptions.
+pub fn map_imgal_error(err: ImgalError) -> PyErr {
     match err {
-        ArrayError::InvalidArrayGeneric { msg } => PyException::new_err(format!("{}", msg)),
-        ArrayError::InvalidArrayParameterValueEqual { param_name, value } => {
+        ImgalError::InvalidArrayGeneric { msg } => PyException::new_err(msg),
+        ImgalError::InvalidArrayParameterValueEqual { param_name, value } => {
             PyValueError::new_err(format!(
                 "Invalid array parameter value, the parameter {} can not equal {}.",
                 param_name, value
             ))
         }
-        ArrayError::InvalidArrayParameterValueGreater { param_name, value } => {
+        ImgalError::InvalidArrayParameterValueGreater { param_name, value } => {
             PyValueError::new_err(format!(
                 "Invalid array parameter value, the parameter {} can not be greater than {}.",
                 param_name, value
             ))
         }
-        ArrayError::InvalidArrayParameterValueLess { param_name, value } => {
+        ImgalError::InvalidArrayParameterValueLess { param_name, value } => {
             PyValueError::new_err(format!(
                 "Invalid array parameter value, the parameter {} can not be less than {}.",
                 param_name, value
             ))
         }
-        ArrayError::InvalidAxis { axis_idx, dim_len } => PyIndexError::new_err(format!(
+        ImgalError::InvalidAxis { axis_idx, dim_len } => PyIndexError::new_err(format!(
             "Axis {} is out of bounds for dimension length {}.",
             axis_idx, dim_len
         )),
-        ArrayError::InvalidSum { expected, got } => PyValueError::new_err(format!(
+        ImgalError::InvalidParameter { param_name, reason } => {
+            PyValueError::new_err(format!("Invalid parameter {}, {}.", param_name, reason))
+        }
+        ImgalError::InvalidSum { expected, got } => PyValueError::new_err(format!(
             "Invalid sum, expected {} but got {}.",
             expected, got
         )),
-        ArrayError::MismatchedArrayLengths {
+        ImgalError::MismatchedArrayLengths {
             a_arr_len,
             b_arr_len,
         } => PyValueError::new_err(format!(
             "Mismatched array lengths, {} and {}, do not match.",
             a_arr_len, b_arr_len
         )),
-        ArrayError::MismatchedArrayShapes { shape_a, shape_b } => PyValueError::new_err(format!(
+        ImgalError::MismatchedArrayShapes { shape_a, shape_b } => PyValueError::new_err(format!(
             "Mismatched array shapes, {:?} and {:?}, do not match.",
             shape_a, shape_b
         )),
+        ImgalError::NumericFailure { operation, reason } => {
+            PyArithmeticError::new_err(format!("Numeric failure in {}, {}.", operation, reason))
+        }
     }
 }
+
+/// Run an imgal function and emit its warnings with Python's `warnings.warn`.
+pub fn with_warnings<F, R>(py: Python, f: F) -> PyResult<R>
+where
+    F: FnOnce() -> R,
+{
+    let (output, warnings) = warning::collect(f);
+    for w in warnings {
+        let msg = CString::new(w.to_string()).expect("Failed to create 'CString' warning message.");
+        PyErr::warn(py, &py.get_type::<PyUserWarning>(), &msg, 1)?;
+    }
+
+    Ok(output)
+}
@@ -0,0 +1,157 @@
+use numpy::{IntoPyArray, PyArray3, PyArrayDyn, PyReadonlyArray3, PyReadonlyArrayDyn};
+use pyo3::prelude::*;
+
+use crate::error::{map_imgal_error, with_warnings};
+use crate::utils::{DLPackArray2, DLPackArray3, DLPackArrayDyn, dispatch_dtype};
+use imgal::anisotropy;
+
+/// Compute the 1-dimensional anisotropy decay curve of two polarization decay
+/// curves.
+///
+/// This function computes the time-resolved anisotropy r(t) from the parallel
+/// I∥(t) and perpendicular I⊥(t) polarization decay curves:
+///
+/// r(t) = (I∥(t) - G × I⊥(t)) / (I∥(t) + 2G × I⊥(t))
+///
+/// where G is the instrument G-factor. Time bins without intensity are NaN.
+///
+/// :param parallel: I∥(t), the parallel polarization decay curve.
+/// :param perpendicular: I⊥(t), the perpendicular polarization decay curve.
+///     Must be the same length as "parallel".
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :return: The anisotropy decay curve, r(t).
+#[pyfunction]
+#[pyo3(name = "decay")]
+#[pyo3(signature = (parallel, perpendicular, g_factor=None))]
+pub fn anisotropy_decay(
+    parallel: Vec<f64>,
+    perpendicular: Vec<f64>,
+    g_factor: Option<f64>,
+) -> PyResult<Vec<f64>> {
+    anisotropy::decay(&parallel, &perpendicular, g_factor).map_err(map_imgal_error)
+}
+
+/// Compute the anisotropy decay curve of each pixel of two 3-dimensional
+/// polarization decay images.
+///
+/// This function computes the time-resolved anisotropy r(t) of each pixel of
+/// dual-channel decay stacks, see "decay". Time bins without intensity are NaN.
+///
+/// :param parallel: I∥(t), the parallel polarization decay image.
+/// :param perpendicular: I⊥(t), the perpendicular polarization decay image.
+///     Must match the shape and dtype of "parallel".
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The anisotropy decay image with the same shape as the input images.
+#[pyfunction]
+#[pyo3(name = "decay_image")]
+#[pyo3(signature = (parallel, perpendicular, g_factor=None, mask=None, axis=None))]
+pub fn anisotropy_decay_image<'py>(
+    py: Python<'py>,
+    parallel: Bound<'py, PyAny>,
+    perpendicular: Bound<'py, PyAny>,
+    g_factor: Option<f64>,
+    mask: Option<DLPackArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    // pattern match and extract allowed array types
+    dispatch_dtype!(parallel, PyReadonlyArray3::<T>, |arr_par| {
+        let arr_perp = perpendicular.extract::<DLPackArray3<T>>()?;
+        with_warnings(py, || {
+            anisotropy::decay_image(arr_par.as_array(), arr_perp.as_array(), g_factor, msk, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Estimate the instrument G-factor from a reference with a known anisotropy.
+///
+/// The G-factor is the ratio of the detection sensitivities of the
+/// perpendicular and parallel polarization channels, estimated from the total
+/// channel intensities of a reference with a known anisotropy (e.g. a free dye
+/// with r ≈ 0.0):
+///
+/// G = (ΣI∥ / ΣI⊥) × (1 - r) / (1 + 2r)
+///
+/// :param parallel: I∥, the parallel polarization channel of the reference.
+/// :param perpendicular: I⊥, the perpendicular polarization channel of the
+///     reference. Must match the shape and dtype of "parallel".
+/// :param reference: The known anisotropy of the reference, default = 0.0.
+/// :return: The G-factor.
+#[pyfunction]
+#[pyo3(name = "g_factor")]
+#[pyo3(signature = (parallel, perpendicular, reference=None))]
+pub fn anisotropy_g_factor<'py>(
+    parallel: Bound<'py, PyAny>,
+    perpendicular: Bound<'py, PyAny>,
+    reference: Option<f64>,
+) -> PyResult<f64> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(parallel, PyReadonlyArrayDyn::<T>, |arr_par| {
+        let arr_perp = perpendicular.extract::<DLPackArrayDyn<T>>()?;
+        anisotropy::g_factor(arr_par.as_array(), arr_perp.as_array(), reference)
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the steady-state anisotropy image of two polarization channels.
+///
+/// This function computes the anisotropy of each pixel from its parallel (I∥)
+/// and perpendicular (I⊥) polarization intensities:
+///
+/// r = (I∥ - G × I⊥) / (I∥ + 2G × I⊥)
+///
+/// where G is the instrument G-factor. Pixels without intensity are NaN.
+///
+/// :param parallel: I∥, the n-dimensional parallel polarization channel.
+/// :param perpendicular: I⊥, the n-dimensional perpendicular polarization
+///     channel. Must match the shape and dtype of "parallel".
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :return: The anisotropy image with the same shape as the channels.
+#[pyfunction]
+#[pyo3(name = "steady_state")]
+#[pyo3(signature = (parallel, perpendicular, g_factor=None))]
+pub fn anisotropy_steady_state<'py>(
+    py: Python<'py>,
+    parallel: Bound<'py, PyAny>,
+    perpendicular: Bound<'py, PyAny>,
+    g_factor: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(parallel, PyReadonlyArrayDyn::<T>, |arr_par| {
+        let arr_perp = perpendicular.extract::<DLPackArrayDyn<T>>()?;
+        with_warnings(py, || {
+            anisotropy::steady_state(arr_par.as_array(), arr_perp.as_array(), g_factor)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Estimate the instrument G-factor by tail matching a reference decay.
+///
+/// The anisotropy of a reference with a rotational correlation time much
+/// shorter than its lifetime (e.g. a free dye) decays to 0.0, so its
+/// polarization decay curves only differ by the G-factor at late times:
+///
+/// G = Σₜ₌ₛₜₐᵣₜ I∥(t) / Σₜ₌ₛₜₐᵣₜ I⊥(t)
+///
+/// :param parallel: I∥(t), the parallel polarization decay curve of the
+///     reference.
+/// :param perpendicular: I⊥(t), the perpendicular polarization decay curve of
+///     the reference. Must be the same length as "parallel".
+/// :param start: The first time bin of the tail.
+/// :return: The G-factor.
+#[pyfunction]
+#[pyo3(name = "tail_g_factor")]
+pub fn anisotropy_tail_g_factor(
+    parallel: Vec<f64>,
+    perpendicular: Vec<f64>,
+    start: usize,
+) -> PyResult<f64> {
+    anisotropy::tail_g_factor(&parallel, &perpendicular, start).map_err(map_imgal_error)
+}
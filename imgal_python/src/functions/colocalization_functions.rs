@@ -1,11 +1,19 @@
 use std::f64;
 
-use numpy::{IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3};
-use pyo3::exceptions::PyTypeError;
+use numpy::{
+    IntoPyArray, PyArray2, PyArray3, PyArrayDyn, PyReadonlyArray2, PyReadonlyArray3,
+    PyReadonlyArrayDyn,
+};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use crate::error::map_array_error;
-use imgal::colocalization;
+use crate::error::{map_imgal_error, with_warnings};
+use crate::functions::measure_functions::ArrowTable;
+use crate::functions::roi_functions::table_to_dict;
+use crate::functions::simulation_functions::{PySeed, rng_seed};
+use crate::functions::threshold_functions::extract_labels;
+use crate::utils::{DLPackArray2, DLPackArray3, DLPackArrayDyn, dispatch_dtype};
+use imgal::colocalization::{self, Metric};
 
 /// Compute colocalization strength using 2-dimensional Spatially Adaptive
 /// Colocalization Analysis (SACA)
@@ -42,46 +50,17 @@ pub fn colocalization_saca_2d<'py>(
     threshold_a: f64,
     threshold_b: f64,
 ) -> PyResult<Bound<'py, PyArray2<f64>>> {
-    if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<u8>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<u8>>()?;
+    dispatch_dtype!(data_a, PyReadonlyArray2::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArray2<T>>()?;
         colocalization::saca_2d(
             arr_a.as_array(),
             arr_b.as_array(),
-            threshold_a as u8,
-            threshold_b as u8,
+            threshold_a as T,
+            threshold_b as T,
         )
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<u16>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<u16>>()?;
-        colocalization::saca_2d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as u16,
-            threshold_b as u16,
-        )
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<f32>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<f32>>()?;
-        colocalization::saca_2d(
-            arr_a.as_array(),
-            arr_b.as_array(),
-            threshold_a as f32,
-            threshold_b as f32,
-        )
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray2<f64>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray2<f64>>()?;
-        colocalization::saca_2d(arr_a.as_array(), arr_b.as_array(), threshold_a, threshold_b)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+        .map_err(map_imgal_error)
+    })
 }
 
 /// Compute colocalization strength using 3-dimensional Spatially Adaptive
@@ -119,44 +98,383 @@ pub fn colocalization_saca_3d<'py>(
     threshold_a: f64,
     threshold_b: f64,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
-    if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<u8>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<u8>>()?;
+    dispatch_dtype!(data_a, PyReadonlyArray3::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArray3<T>>()?;
         colocalization::saca_3d(
             arr_a.as_array(),
             arr_b.as_array(),
-            threshold_a as u8,
-            threshold_b as u8,
+            threshold_a as T,
+            threshold_b as T,
         )
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<u16>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<u16>>()?;
-        colocalization::saca_3d(
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Create a boolean mask of the significantly colocalized pixels of a SACA
+/// z-score image.
+///
+/// This function tests the pixel-wise z-scores computed by "saca_2d" or
+/// "saca_3d" with a two-sided test at the significance level "alpha", with a
+/// Bonferroni correction for the number of pixels (i.e. tests). Pixels with a
+/// z-score > Φ⁻¹(1 - α / (2 × N)) are significantly colocalized. Significantly
+/// anti-colocalized pixels can be selected by negating the z-scores.
+///
+/// :param data: The n-dimensional SACA z-score image.
+/// :param alpha: The family-wise significance level, default = 0.05. Must be in
+///     (0, 1).
+/// :return: A boolean array of the same shape as the input image with the
+///     significantly colocalized pixels set as "True".
+#[pyfunction]
+#[pyo3(name = "saca_significance_mask")]
+#[pyo3(signature = (data, alpha=None))]
+pub fn colocalization_saca_significance_mask<'py>(
+    py: Python<'py>,
+    data: DLPackArrayDyn<f64>,
+    alpha: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
+    with_warnings(py, || {
+        colocalization::saca_significance_mask(data.as_array(), alpha)
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Compute the Pearson correlation coefficient of two n-dimensional images.
+///
+/// This function computes the Pearson correlation coefficient (PCC) of the
+/// pixel intensities of image "A" and "B". Only the pixels inside the optional
+/// mask and above the optional threshold values of both images are used.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded.
+///     Must match the shape of the images.
+/// :param threshold_a: An optional pixel intensity threshold value for image
+///     "A", pixels with a value <= "threshold_a" are excluded.
+/// :param threshold_b: An optional pixel intensity threshold value for image
+///     "B", pixels with a value <= "threshold_b" are excluded.
+/// :return: The Pearson correlation coefficient in [-1, 1].
+#[pyfunction]
+#[pyo3(name = "pearson")]
+#[pyo3(signature = (data_a, data_b, mask=None, threshold_a=None, threshold_b=None))]
+pub fn colocalization_pearson<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+    threshold_a: Option<f64>,
+    threshold_b: Option<f64>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        colocalization::pearson(
             arr_a.as_array(),
             arr_b.as_array(),
-            threshold_a as u16,
-            threshold_b as u16,
+            msk,
+            threshold_a.map(|t| t as T),
+            threshold_b.map(|t| t as T),
         )
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<f32>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<f32>>()?;
-        colocalization::saca_3d(
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Test the significance of the Pearson correlation coefficient of two
+/// n-dimensional images with Costes block scrambling.
+///
+/// This function randomizes image "A" by shuffling blocks of pixels (e.g.
+/// blocks the size of the point spread function) and computes the Pearson
+/// correlation coefficient of each scrambled image "A" with image "B". The
+/// p-value is the fraction of scrambled coefficients that are >= the observed
+/// coefficient, with the observed image counted as one of the permutations.
+/// Only the pixels covered by whole blocks are used.
+///
+/// :param data_a: The n-dimensional input image, "A", that is scrambled. Image
+///     "A" must have the same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param block: The block size per axis. Each block size must be >= 1 and <=
+///     the matching image axis length.
+/// :param iterations: The number of scrambled images, default = 200. Must be
+///     >= 1.
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded.
+///     The mask is not scrambled. Must match the shape of the images.
+/// :param seed: Pseudorandom number generator seed, the iteration "i" is seeded
+///     with "seed + i". If "None", a thread local generator is used. Pass an
+///     "Rng" context to draw its next stream.
+/// :return: The p-value and the Pearson correlation coefficient of each
+///     scrambled image.
+#[pyfunction]
+#[pyo3(name = "costes_test")]
+#[pyo3(signature = (data_a, data_b, block, iterations=None, mask=None, seed=None))]
+pub fn colocalization_costes_test<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    block: Vec<usize>,
+    iterations: Option<usize>,
+    mask: Option<DLPackArrayDyn<bool>>,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<(f64, Vec<f64>)> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        colocalization::costes_test(
+            arr_a.as_array(),
+            arr_b.as_array(),
+            &block,
+            iterations,
+            msk,
+            rng_seed(seed),
+        )
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the Manders split colocalization coefficients of two n-dimensional
+/// images.
+///
+/// This function computes the Manders split coefficients M1 and M2, the
+/// fraction of the intensity of image "A" that colocalizes with signal in
+/// image "B" (i.e. pixels with b > threshold_b) and vice versa. The intensity
+/// sums of "A" ("B") only include the pixels above "threshold_a"
+/// ("threshold_b"). Unbiased threshold values can be computed with
+/// "costes_threshold".
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded.
+///     Must match the shape of the images.
+/// :param threshold_a: The pixel intensity threshold value of image "A",
+///     default = 0.
+/// :param threshold_b: The pixel intensity threshold value of image "B",
+///     default = 0.
+/// :return: The M1 and M2 coefficients in [0, 1].
+#[pyfunction]
+#[pyo3(name = "manders")]
+#[pyo3(signature = (data_a, data_b, mask=None, threshold_a=None, threshold_b=None))]
+pub fn colocalization_manders<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+    threshold_a: Option<f64>,
+    threshold_b: Option<f64>,
+) -> PyResult<(f64, f64)> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        colocalization::manders(
             arr_a.as_array(),
             arr_b.as_array(),
-            threshold_a as f32,
-            threshold_b as f32,
+            msk,
+            threshold_a.map(|t| t as T),
+            threshold_b.map(|t| t as T),
         )
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the Costes automatic threshold values of two n-dimensional images.
+///
+/// This function fits the intensities of image "B" against image "A" with an
+/// orthogonal (total least squares) regression line and lowers the threshold
+/// of image "A" (with the threshold of image "B" on the regression line) until
+/// the Pearson correlation coefficient of the pixels below either threshold
+/// value is <= 0.0, which gives unbiased thresholds for "manders".
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded.
+///     Must match the shape of the images.
+/// :return: The threshold values of image "A" and "B".
+#[pyfunction]
+#[pyo3(name = "costes_threshold")]
+#[pyo3(signature = (data_a, data_b, mask=None))]
+pub fn colocalization_costes_threshold<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<(f64, f64)> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        colocalization::costes_threshold(arr_a.as_array(), arr_b.as_array(), msk)
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute object-based colocalization measurements of two label images.
+///
+/// This function measures each object (i.e. labeled region) of label image
+/// "A" against the objects of label image "B" and vice versa. Pixels with the
+/// label 0 are background, binary masks can be passed as label images where
+/// all foreground pixels form one object. The measurements are "area",
+/// "centroid_<axis>" (in pixels), "overlap" (the fraction of the object pixels
+/// that overlap with any object of the other image), "nearest_distance" (the
+/// distance to the nearest object centroid of the other image, scaled by the
+/// pixel spacing) and "nearest_label".
+///
+/// :param labels_a: The n-dimensional integer label image, "A". Image "A" must
+///     have the same shape as image "B".
+/// :param labels_b: The n-dimensional integer label image, "B". Image "B" must
+///     have the same shape as image "A".
+/// :param spacing: The pixel spacing per axis used for the centroid distances,
+///     default = 1.0 for every axis. Each spacing must be > 0.0.
+/// :param arrow: If True, return the table as an "imgal.measure.ArrowTable"
+///     instead of a dict, default = False.
+/// :return: A dict of columns ("roi", "groups", "slice", "channel",
+///     "measurement" and "value") with one row per object measurement, where
+///     "roi" is the object label and "channel" is 0 for the objects of image
+///     "A" and 1 for the objects of image "B", or an "ArrowTable" with the
+///     same columns.
+#[pyfunction]
+#[pyo3(name = "object")]
+#[pyo3(signature = (labels_a, labels_b, spacing=None, arrow=false))]
+pub fn colocalization_object<'py>(
+    py: Python<'py>,
+    labels_a: Bound<'py, PyAny>,
+    labels_b: Bound<'py, PyAny>,
+    spacing: Option<Vec<f64>>,
+    arrow: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let lbl_a = extract_labels(&labels_a)?;
+    let lbl_b = extract_labels(&labels_b)?;
+    let table = colocalization::object(lbl_a.view(), lbl_b.view(), spacing.as_deref())
+        .map_err(map_imgal_error)?;
+    if arrow {
+        return Ok(Bound::new(py, ArrowTable::new(table.to_record_batch()))?.into_any());
+    }
+
+    Ok(table_to_dict(py, &table)?.into_any())
+}
+
+/// Compute the Van Steensel cross-correlation function (CCF) of two
+/// n-dimensional images.
+///
+/// This function computes the Pearson correlation coefficient of image "A" and
+/// image "B" shifted by "dx" pixels along "axis", for every shift "dx" in
+/// [-max_shift, max_shift]. Only the overlapping pixels of the shifted images
+/// are used. Colocalized signals give a CCF that peaks at a shift of 0, a peak
+/// at a non-zero shift indicates an offset between the channels (e.g.
+/// chromatic aberration).
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B", that is shifted. Image
+///     "B" must have the same shape as image "A".
+/// :param max_shift: The maximum shift, in pixels. Must be < the length of
+///     "axis".
+/// :param axis: The shift axis, default = the last axis.
+/// :return: The CCF curve of length 2 × max_shift + 1, where index "i" holds
+///     the shift "i - max_shift", and the shift of the CCF peak.
+#[pyfunction]
+#[pyo3(name = "ccf")]
+#[pyo3(signature = (data_a, data_b, max_shift, axis=None))]
+pub fn colocalization_ccf<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    max_shift: usize,
+    axis: Option<usize>,
+) -> PyResult<(Vec<f64>, isize)> {
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        colocalization::ccf(arr_a.as_array(), arr_b.as_array(), max_shift, axis)
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute a colocalization metric per tile of two n-dimensional images.
+///
+/// This function splits the images into tiles and computes the colocalization
+/// metric of the pixel intensities of each tile, in parallel. The result is a
+/// low resolution map of the metric with one pixel per tile. Tiles at the end
+/// of an axis that is not a multiple of the tile size are smaller.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param tile: The tile size per axis. Each tile size must be >= 1.
+/// :param metric: The colocalization metric, "pearson", "manders_m1" or
+///     "manders_m2", default = "pearson". The Manders coefficients use
+///     thresholds of 0.
+/// :return: The metric map, with the shape of the images divided by the tile
+///     size (rounded up). Undefined metric values are NaN.
+#[pyfunction]
+#[pyo3(name = "tile_map")]
+#[pyo3(signature = (data_a, data_b, tile, metric=None))]
+pub fn colocalization_tile_map<'py>(
+    py: Python<'py>,
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    tile: Vec<usize>,
+    metric: Option<&str>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let m = parse_metric(metric)?;
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        colocalization::tile_map(arr_a.as_array(), arr_b.as_array(), &tile, |a, b| {
+            m.compute(a, b)
+        })
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Compute a colocalization metric per labeled region of two n-dimensional
+/// images.
+///
+/// This function computes the colocalization metric of the pixel intensities
+/// of each labeled region (e.g. each segmented cell), in parallel, and maps
+/// the metric value back onto the pixels of the region. Pixels with the label
+/// 0 are unlabeled and set to NaN.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape as image "A".
+/// :param labels: The integer label image, pixels with the same label belong
+///     to the same region. Must match the shape of the images.
+/// :param metric: The colocalization metric, "pearson", "manders_m1" or
+///     "manders_m2", default = "pearson". The Manders coefficients use
+///     thresholds of 0.
+/// :return: The metric map with the same shape as the images.
+#[pyfunction]
+#[pyo3(name = "label_map")]
+#[pyo3(signature = (data_a, data_b, labels, metric=None))]
+pub fn colocalization_label_map<'py>(
+    py: Python<'py>,
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    labels: Bound<'py, PyAny>,
+    metric: Option<&str>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let m = parse_metric(metric)?;
+    let lbl = extract_labels(&labels)?;
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        colocalization::label_map(arr_a.as_array(), arr_b.as_array(), lbl.view(), |a, b| {
+            m.compute(a, b)
+        })
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
-    } else if let Ok(arr_a) = data_a.extract::<PyReadonlyArray3<f64>>() {
-        let arr_b = data_b.extract::<PyReadonlyArray3<f64>>()?;
-        colocalization::saca_3d(arr_a.as_array(), arr_b.as_array(), threshold_a, threshold_b)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Parse an optional colocalization metric name.
+fn parse_metric(metric: Option<&str>) -> PyResult<Metric> {
+    match metric {
+        None | Some("pearson") => Ok(Metric::Pearson),
+        Some("manders_m1") => Ok(Metric::MandersM1),
+        Some("manders_m2") => Ok(Metric::MandersM2),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown colocalization metric \"{}\", supported metrics are \"pearson\", \"manders_m1\", and \"manders_m2\".",
+            m
+        ))),
     }
 }
@@ -0,0 +1,86 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+
+use crate::error::map_imgal_error;
+use imgal::config;
+
+/// A bounded pool of worker threads for imgal's parallel functions.
+///
+/// imgal functions called in "install" run their parallel work on the worker
+/// threads of the pool instead of one thread per core, so an application that
+/// manages its own threads can give imgal a fixed share of the cores. Reuse
+/// one pool across calls instead of creating a pool per call.
+///
+/// :param num_threads: The number of worker threads. Must be >= 1.
+#[pyclass(name = "ThreadPool", module = "imgal.config")]
+pub struct ThreadPool {
+    inner: config::ThreadPool,
+}
+
+#[pymethods]
+impl ThreadPool {
+    #[new]
+    fn new(num_threads: usize) -> PyResult<Self> {
+        config::ThreadPool::new(num_threads)
+            .map(|inner| ThreadPool { inner })
+            .map_err(map_imgal_error)
+    }
+
+    /// Call a function in the thread pool.
+    ///
+    /// The function runs on a worker thread of the pool, the calling thread
+    /// waits until it returns.
+    ///
+    /// :param func: The function to call.
+    /// :param args: The positional arguments of "func".
+    /// :param kwargs: The keyword arguments of "func".
+    /// :return: The output of "func".
+    #[pyo3(signature = (func, *args, **kwargs))]
+    fn install(
+        &self,
+        py: Python,
+        func: Py<PyAny>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let args = args.clone().unbind();
+        let kwargs = kwargs.map(|k| k.clone().unbind());
+        py.allow_threads(|| {
+            self.inner.install(|| {
+                Python::with_gil(|py| {
+                    func.call(py, args.bind(py), kwargs.as_ref().map(|k| k.bind(py)))
+                })
+            })
+        })
+    }
+
+    /// The number of worker threads.
+    #[getter]
+    fn num_threads(&self) -> usize {
+        self.inner.num_threads()
+    }
+}
+
+/// Get the number of threads available to imgal's parallel functions.
+///
+/// :return: The number of worker threads of the thread pool the caller runs
+///     in, the global pool if it does not run in a "ThreadPool".
+#[pyfunction]
+#[pyo3(name = "num_threads")]
+pub fn config_num_threads() -> usize {
+    config::num_threads()
+}
+
+/// Set the number of threads of the global thread pool.
+///
+/// This function bounds the parallelism of all imgal functions that are not
+/// called in a "ThreadPool". The size of the global pool can not be changed
+/// after the first parallel work runs, call this function right after
+/// importing imgal or use a "ThreadPool" to bound the parallelism per call.
+///
+/// :param num_threads: The number of worker threads. Must be >= 1.
+#[pyfunction]
+#[pyo3(name = "set_num_threads")]
+pub fn config_set_num_threads(num_threads: usize) -> PyResult<()> {
+    config::set_num_threads(num_threads).map_err(map_imgal_error)
+}
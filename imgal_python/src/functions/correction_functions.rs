@@ -0,0 +1,263 @@
+use numpy::{
+    IntoPyArray, PyArray1, PyArray2, PyArray3, PyArrayDyn, PyReadonlyArray1, PyReadonlyArray3,
+    PyReadonlyArrayDyn, PyUntypedArrayMethods,
+};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::error::{map_imgal_error, with_warnings};
+use crate::utils::{DLPackArray2, contiguous, dispatch_dtype};
+use imgal::correction::{self, AlignMethod, BleachMethod, FlatfieldMethod};
+
+/// Align 3-dimensional decay data to a common time zero (t0).
+///
+/// This function detects the t0 position of each decay and circularly shifts
+/// each decay by a whole number of bins so that its t0 moves to the "target"
+/// bin. This corrects timing skew between pixels (e.g. scanner induced) or
+/// detector channels before analysis. Decays without signal are left unshifted.
+///
+/// :param data: I(t), the decay data image.
+/// :param method: The t0 detection method, "peak" or "rising_edge", default =
+///     "rising_edge".
+/// :param target: The bin to move each decay's t0 to. If None, then the median
+///     t0 of all decays with signal is used.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The aligned decay data with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "align_decays")]
+#[pyo3(signature = (data, method=None, target=None, axis=None))]
+pub fn correction_align_decays<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    method: Option<&str>,
+    target: Option<usize>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let method = parse_align_method(method)?;
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        with_warnings(py, || {
+            correction::align_decays(arr.as_array(), method, target, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// The result of a photobleaching correction.
+///
+/// The fields can also be read by name, e.g. "result["curve"]".
+#[pyclass(name = "BleachResult", module = "imgal.correction")]
+pub struct BleachResult {
+    /// The corrected image with the same shape as the input image.
+    #[pyo3(get)]
+    corrected: Py<PyArrayDyn<f64>>,
+    /// The bleaching curve, one value per frame.
+    #[pyo3(get)]
+    curve: Py<PyArray1<f64>>,
+}
+
+#[pymethods]
+impl BleachResult {
+    fn __repr__(&self, py: Python<'_>) -> String {
+        format!(
+            "BleachResult(corrected=<array of shape {:?}>, curve=<array of {} frames>)",
+            self.corrected.bind(py).shape(),
+            self.curve.bind(py).len()
+        )
+    }
+
+    fn __getitem__<'py>(&self, py: Python<'py>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+        match key {
+            "corrected" => Ok(self.corrected.bind(py).clone().into_any()),
+            "curve" => Ok(self.curve.bind(py).clone().into_any()),
+            _ => Err(PyKeyError::new_err(format!(
+                "Unknown field \"{}\", the fields are \"corrected\" and \"curve\".",
+                key
+            ))),
+        }
+    }
+}
+
+/// Correct the photobleaching of a 3 or 4-dimensional time series.
+///
+/// This function compensates the loss of fluorescence intensity over the time
+/// axis of an image stack (e.g. (t, row, col) or (t, pln, row, col)). With
+/// "exponential" the mean intensity of each frame is fitted with a single
+/// exponential decay with offset, f(t) = a × exp(-b × t) + c, and each frame is
+/// scaled by f(0) / f(t). Frames where the fitted curve is not > 0.0 are left
+/// uncorrected. This preserves intensity ratios within frames and suits
+/// quantitative analysis. With "histogram" the histogram of each frame is
+/// matched to the histogram of the first frame, which also corrects non-linear
+/// intensity changes but does not preserve the intensity ratios and is
+/// intended for segmentation and visualization.
+///
+/// :param data: The 3 or 4-dimensional time series.
+/// :param method: The correction method, "exponential" or "histogram",
+///     default = "exponential".
+/// :param axis: The time axis, default = 0.
+/// :return: A "BleachResult" with the "corrected" image and the bleaching
+///     "curve", one value per frame. The curve is the fitted exponential decay
+///     with "exponential", otherwise the measured mean intensity of each frame.
+#[pyfunction]
+#[pyo3(name = "bleach")]
+#[pyo3(signature = (data, method=None, axis=None))]
+pub fn correction_bleach<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    method: Option<&str>,
+    axis: Option<usize>,
+) -> PyResult<BleachResult> {
+    let method = parse_bleach_method(method)?;
+
+    // pattern match and extract allowed array types
+    let result = dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        correction::bleach(arr.as_array(), method, axis)
+    })
+    .map_err(map_imgal_error)?;
+
+    Ok(BleachResult {
+        corrected: result.corrected.into_pyarray(py).unbind(),
+        curve: result.curve.into_pyarray(py).unbind(),
+    })
+}
+
+/// Correct the uneven illumination of a 2-dimensional image or a stack of
+/// 2-dimensional images with a flat-field.
+///
+/// This function divides the dark corrected image by the flat-field gain,
+/// C = (I - D) / F with F = (flat - D) / mean(flat - D), where D is the dark
+/// (i.e. offset) image, 0.0 if not given. Pixels where the gain is not > 0.0
+/// are set to 0.0. If no flat-field image is given, the gain is estimated from
+/// the dark corrected data with "method". "gaussian" blurs the mean projection
+/// over the frames with a Gaussian of standard deviation "sigma". "basic"
+/// normalizes each frame by its mean and averages the normalized frames per
+/// pixel, iteratively reweighted to reject pixels deviating from the current
+/// estimate (i.e. the objects) and smoothed with a Gaussian of standard
+/// deviation "sigma". This is a simplified BaSiC-style estimate (no darkfield or
+/// baseline drift estimation) that needs a stack with varying content across
+/// the frames.
+///
+/// :param data: A 2-dimensional (row, col) image or a 3-dimensional
+///     (frame, row, col) stack.
+/// :param flat: The float64 (row, col) flat-field image (e.g. an image of a
+///     uniform sample). If None, the flat-field is estimated with "method".
+/// :param dark: The float64 (row, col) dark image, subtracted from the data and
+///     the flat-field image. If None, no offset is subtracted.
+/// :param method: The flat-field estimation method, "gaussian" or "basic",
+///     default = "gaussian". Ignored if "flat" is given.
+/// :param sigma: The standard deviation of the Gaussian smoothing of the
+///     estimate in pixels, default = max(rows, cols) / 8. Must be > 0.0.
+/// :return: A dict with the "corrected" image and the "flat" (row, col)
+///     flat-field gain normalized to a mean of 1.0.
+#[pyfunction]
+#[pyo3(name = "flatfield")]
+#[pyo3(signature = (data, flat=None, dark=None, method=None, sigma=None))]
+pub fn correction_flatfield<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    flat: Option<DLPackArray2<f64>>,
+    dark: Option<DLPackArray2<f64>>,
+    method: Option<&str>,
+    sigma: Option<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let method = parse_flatfield_method(method)?;
+    let flat = flat.as_ref().map(|f| f.as_array());
+    let dark = dark.as_ref().map(|d| d.as_array());
+
+    // pattern match and extract allowed array types
+    let result = dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        correction::flatfield(arr.as_array(), flat, dark, method, sigma)
+    })
+    .map_err(map_imgal_error)?;
+    let dict = PyDict::new(py);
+    dict.set_item("corrected", result.corrected.into_pyarray(py))?;
+    dict.set_item("flat", result.flat.into_pyarray(py))?;
+
+    Ok(dict)
+}
+
+/// Detect the time zero (t0) position of a 1-dimensional decay curve.
+///
+/// "peak" returns the position of the maximum value. "rising_edge" returns the
+/// position where the curve first crosses half of its baseline (i.e. minimum)
+/// corrected maximum, linearly interpolated between bins.
+///
+/// :param data: The 1-dimensional decay curve.
+/// :param method: The t0 detection method, "peak" or "rising_edge", default =
+///     "rising_edge".
+/// :return: The t0 position in bins, or None if the decay curve is empty, flat
+///     or not finite.
+#[pyfunction]
+#[pyo3(name = "t0")]
+#[pyo3(signature = (data, method=None))]
+pub fn correction_t0(data: Bound<'_, PyAny>, method: Option<&str>) -> PyResult<Option<f64>> {
+    let method = parse_align_method(method)?;
+    dispatch_dtype!(data, PyReadonlyArray1::<T>, |arr| {
+        Ok(correction::t0(&contiguous(arr.as_array()), method))
+    })
+}
+
+/// Detect the time zero (t0) position of every decay in a 3-dimensional image.
+///
+/// :param data: I(t), the decay data image.
+/// :param method: The t0 detection method, "peak" or "rising_edge", default =
+///     "rising_edge".
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The t0 position, in bins, of each decay. Decays without signal are
+///     set to NaN.
+#[pyfunction]
+#[pyo3(name = "t0_image")]
+#[pyo3(signature = (data, method=None, axis=None))]
+pub fn correction_t0_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    method: Option<&str>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let method = parse_align_method(method)?;
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        with_warnings(py, || correction::t0_image(arr.as_array(), method, axis))?
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Parse an optional bleaching correction method name.
+fn parse_bleach_method(method: Option<&str>) -> PyResult<Option<BleachMethod>> {
+    match method {
+        None => Ok(None),
+        Some("exponential") => Ok(Some(BleachMethod::Exponential)),
+        Some("histogram") => Ok(Some(BleachMethod::HistogramMatching)),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown bleach method \"{}\", supported methods are \"exponential\" and \"histogram\".",
+            m
+        ))),
+    }
+}
+
+/// Parse an optional flat-field estimation method name.
+fn parse_flatfield_method(method: Option<&str>) -> PyResult<Option<FlatfieldMethod>> {
+    match method {
+        None => Ok(None),
+        Some("gaussian") => Ok(Some(FlatfieldMethod::Gaussian)),
+        Some("basic") => Ok(Some(FlatfieldMethod::Basic)),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown flat-field method \"{}\", supported methods are \"gaussian\" and \"basic\".",
+            m
+        ))),
+    }
+}
+
+/// Parse an optional t0 detection method name.
+fn parse_align_method(method: Option<&str>) -> PyResult<Option<AlignMethod>> {
+    match method {
+        None => Ok(None),
+        Some("peak") => Ok(Some(AlignMethod::Peak)),
+        Some("rising_edge") => Ok(Some(AlignMethod::RisingEdge)),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown t0 method \"{}\", supported methods are \"peak\" and \"rising_edge\".",
+            m
+        ))),
+    }
+}
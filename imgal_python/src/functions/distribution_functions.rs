@@ -1,8 +1,75 @@
 use numpy::{IntoPyArray, PyArray1};
 use pyo3::prelude::*;
 
+use crate::error::map_imgal_error;
+use crate::functions::simulation_functions::{PySeed, rng_seed};
 use imgal::distribution;
 
+/// Generate a normalized exponential distribution over a specified range.
+///
+/// This function creates a discrete exponential distribution by sampling the
+/// continuous exponential decay at evenly spaced points across a given range.
+/// The resulting distribution is normalized so that all values sum to 1.0:
+///
+/// f(x) = exp(-(x - x₀) / τ) for x >= x₀, otherwise 0
+///
+/// where "x₀" is the offset (onset) and "τ" is the tau (decay constant).
+///
+/// :param tau: The decay constant of the exponential distribution. Must be > 0.0.
+/// :param bins: The number of discrete points to sample the exponential
+///     distribution. Must be >= 2.
+/// :param range: The total width of the sampling range. Must be > 0.0.
+/// :param offset: The onset of the exponential distribution. Must be within the
+///     range.
+/// :return: The normalized exponential distribution.
+#[pyfunction]
+#[pyo3(name = "exponential")]
+pub fn distribution_exponential(
+    py: Python,
+    tau: f64,
+    bins: usize,
+    range: f64,
+    offset: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    distribution::exponential(tau, bins, range, offset)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Generate a normalized gamma distribution over a specified range.
+///
+/// This function creates a discrete gamma distribution by sampling the
+/// continuous gamma probability density function at evenly spaced points
+/// across a given range. The resulting distribution is normalized so that all
+/// values sum to 1.0:
+///
+/// f(x) = (x - x₀)ᵏ⁻¹ × exp(-(x - x₀) / θ) for x > x₀, otherwise 0
+///
+/// where "x₀" is the offset (onset), "k" is the shape and "θ" is the scale.
+/// The distribution peaks at x₀ + (k - 1)θ.
+///
+/// :param shape: The shape of the gamma distribution. Must be >= 1.0.
+/// :param scale: The scale of the gamma distribution. Must be > 0.0.
+/// :param bins: The number of discrete points to sample the gamma distribution.
+///     Must be >= 2.
+/// :param range: The total width of the sampling range. Must be > 0.0.
+/// :param offset: The onset of the gamma distribution. Must be within the range.
+/// :return: The normalized gamma distribution.
+#[pyfunction]
+#[pyo3(name = "gamma")]
+pub fn distribution_gamma(
+    py: Python,
+    shape: f64,
+    scale: f64,
+    bins: usize,
+    range: f64,
+    offset: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    distribution::gamma(shape, scale, bins, range, offset)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
 /// Generate a normalized Gaussian distribution over a specified range.
 ///
 /// This function creates a discrete Gaussian distribution by sampling the continuous
@@ -32,6 +99,221 @@ pub fn distribution_gaussian(
     range: f64,
     center: f64,
 ) -> PyResult<Bound<PyArray1<f64>>> {
-    let output = distribution::gaussian(sigma, bins, range, center);
-    Ok(output.into_pyarray(py))
+    distribution::gaussian(sigma, bins, range, center)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Generate a normalized log-normal distribution over a specified range.
+///
+/// This function creates a discrete log-normal distribution by sampling the
+/// continuous log-normal probability density function at evenly spaced points
+/// across a given range. The resulting distribution is normalized so that all
+/// values sum to 1.0:
+///
+/// f(x) = (1 / x) × exp(-(ln(x / m))² / (2σ²)) for x > 0, otherwise 0
+///
+/// where "m" is the median and "σ" is the sigma (standard deviation of ln(x)).
+///
+/// :param sigma: The standard deviation of the logarithm of the distribution
+///     (i.e. the skew and width). Must be > 0.0.
+/// :param bins: The number of discrete points to sample the log-normal
+///     distribution. Must be >= 2.
+/// :param range: The total width of the sampling range. Must be > 0.0.
+/// :param median: The median of the log-normal distribution. Must be > 0.0.
+/// :return: The normalized log-normal distribution.
+#[pyfunction]
+#[pyo3(name = "log_normal")]
+pub fn distribution_log_normal(
+    py: Python,
+    sigma: f64,
+    bins: usize,
+    range: f64,
+    median: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    distribution::log_normal(sigma, bins, range, median)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Generate a normalized Lorentzian (Cauchy) distribution over a specified
+/// range.
+///
+/// This function creates a discrete Lorentzian distribution by sampling the
+/// continuous Lorentzian (Cauchy) probability density function at evenly
+/// spaced points across a given range. The resulting distribution is
+/// normalized so that all values sum to 1.0:
+///
+/// f(x) = 1 / (1 + ((x - x₀) / γ)²)
+///
+/// where "x₀" is the center (peak) and "γ" is the gamma (half width at half
+/// maximum).
+///
+/// :param gamma: The half width at half maximum of the Lorentzian distribution.
+///     Must be > 0.0.
+/// :param bins: The number of discrete points to sample the Lorentzian
+///     distribution. Must be >= 2.
+/// :param range: The total width of the sampling range. Must be > 0.0.
+/// :param center: The center of the Lorentzian distribution (i.e. the peak).
+/// :return: The normalized Lorentzian distribution.
+#[pyfunction]
+#[pyo3(name = "lorentzian")]
+pub fn distribution_lorentzian(
+    py: Python,
+    gamma: f64,
+    bins: usize,
+    range: f64,
+    center: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    distribution::lorentzian(gamma, bins, range, center)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Compute the inverse of the standard normal cumulative distribution function.
+///
+/// This function computes the quantile "z" of the standard normal distribution
+/// (mean 0.0, standard deviation 1.0) with P(Z <= z) = p, e.g. the critical
+/// z-score of a significance test. The quantile is computed with Acklam's
+/// rational approximation, which has a relative error below 1.2e-9.
+///
+/// :param p: The cumulative probability. Must be in (0, 1).
+/// :return: The standard normal quantile of "p".
+#[pyfunction]
+#[pyo3(name = "inverse_normal_cdf")]
+pub fn distribution_inverse_normal_cdf(p: f64) -> PyResult<f64> {
+    distribution::inverse_normal_cdf(p).map_err(map_imgal_error)
+}
+
+/// Compute the standard normal cumulative distribution function.
+///
+/// This function computes the probability P(Z <= z) of the standard normal
+/// distribution (mean 0.0, standard deviation 1.0). The complementary error
+/// function is computed with a Chebyshev fitted approximation, which has a
+/// relative error below 1.2e-7.
+///
+/// :param z: The standard normal quantile (i.e. z-score).
+/// :return: The cumulative probability of "z".
+#[pyfunction]
+#[pyo3(name = "normal_cdf")]
+pub fn distribution_normal_cdf(z: f64) -> f64 {
+    distribution::normal_cdf(z)
+}
+
+/// Draw random samples from an exponential distribution.
+///
+/// This function draws "n" independent samples from the exponential
+/// distribution with mean "tau" (e.g. photon arrival times of a
+/// monoexponential decay).
+/// The samples are drawn in parallel in chunks of 4096 samples, each chunk with
+/// its own generator.
+///
+/// :param n: The number of samples.
+/// :param tau: The mean (decay constant) of the exponential distribution. Must
+///     be > 0.0.
+/// :param seed: Pseudorandom number generator seed, the chunk "i" is seeded
+///     with "seed + i". If "None", a thread local generator is used. Pass an
+///     "Rng" context to draw its next stream instead.
+/// :return: The random samples.
+#[pyfunction]
+#[pyo3(name = "sample_exponential")]
+#[pyo3(signature = (n, tau, seed=None))]
+pub fn distribution_sample_exponential<'py>(
+    py: Python<'py>,
+    n: usize,
+    tau: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    distribution::sample_exponential(n, tau, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Draw random samples from a gamma distribution.
+///
+/// This function draws "n" independent samples from the gamma distribution
+/// with the given shape (k) and scale (θ), the mean is kθ and the variance is
+/// kθ².
+/// The samples are drawn in parallel in chunks of 4096 samples, each chunk with
+/// its own generator.
+///
+/// :param n: The number of samples.
+/// :param shape: The shape of the gamma distribution. Must be > 0.0.
+/// :param scale: The scale of the gamma distribution. Must be > 0.0.
+/// :param seed: Pseudorandom number generator seed, the chunk "i" is seeded
+///     with "seed + i". If "None", a thread local generator is used. Pass an
+///     "Rng" context to draw its next stream instead.
+/// :return: The random samples.
+#[pyfunction]
+#[pyo3(name = "sample_gamma")]
+#[pyo3(signature = (n, shape, scale, seed=None))]
+pub fn distribution_sample_gamma<'py>(
+    py: Python<'py>,
+    n: usize,
+    shape: f64,
+    scale: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    distribution::sample_gamma(n, shape, scale, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Draw random samples from a Gaussian distribution.
+///
+/// This function draws "n" independent samples from the Gaussian (normal)
+/// distribution with the given mean and standard deviation (e.g. camera read
+/// noise).
+/// The samples are drawn in parallel in chunks of 4096 samples, each chunk with
+/// its own generator.
+///
+/// :param n: The number of samples.
+/// :param mean: The mean (center) of the Gaussian distribution.
+/// :param sigma: The standard deviation of the Gaussian distribution. Must be
+///     >= 0.0.
+/// :param seed: Pseudorandom number generator seed, the chunk "i" is seeded
+///     with "seed + i". If "None", a thread local generator is used. Pass an
+///     "Rng" context to draw its next stream instead.
+/// :return: The random samples.
+#[pyfunction]
+#[pyo3(name = "sample_gaussian")]
+#[pyo3(signature = (n, mean, sigma, seed=None))]
+pub fn distribution_sample_gaussian<'py>(
+    py: Python<'py>,
+    n: usize,
+    mean: f64,
+    sigma: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    distribution::sample_gaussian(n, mean, sigma, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Draw random samples from a Poisson distribution.
+///
+/// This function draws "n" independent samples from the Poisson distribution
+/// with mean "lambda" (e.g. photon counts).
+/// The samples are drawn in parallel in chunks of 4096 samples, each chunk with
+/// its own generator.
+///
+/// :param n: The number of samples.
+/// :param lambda: The mean of the Poisson distribution. Must be within
+///     [0.0, 1.844e19], a lambda of 0.0 only draws 0.0.
+/// :param seed: Pseudorandom number generator seed, the chunk "i" is seeded
+///     with "seed + i". If "None", a thread local generator is used. Pass an
+///     "Rng" context to draw its next stream instead.
+/// :return: The random samples.
+#[pyfunction]
+#[pyo3(name = "sample_poisson")]
+#[pyo3(signature = (n, lambda, seed=None))]
+pub fn distribution_sample_poisson<'py>(
+    py: Python<'py>,
+    n: usize,
+    lambda: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    distribution::sample_poisson(n, lambda, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
 }
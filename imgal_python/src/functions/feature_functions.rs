@@ -0,0 +1,163 @@
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArrayDyn};
+use pyo3::prelude::*;
+
+use crate::error::{map_imgal_error, with_warnings};
+use crate::utils::{DLPackArrayDyn, dispatch_dtype};
+use imgal::feature;
+
+/// Detect bright blobs in a 2 or 3-dimensional image with the scale
+/// normalized difference of Gaussians (DoG).
+///
+/// This function approximates "blob_log" with the differences of Gaussian
+/// blurs at the geometric scales σᵢ = min_sigma × sigma_ratioⁱ, up to the first
+/// scale >= "max_sigma". The response at scale σᵢ is:
+///
+/// DoG = (G_σᵢ ∗ I - G_σᵢ₊₁ ∗ I) / (sigma_ratio - 1)
+///
+/// Blobs are the local maxima of the scale space above the threshold and
+/// overlapping blobs are pruned, see "blob_log". The radius of a blob
+/// detected at scale σ is √n × σ, where n is the number of image dimensions.
+///
+/// :param data: The input 2 or 3-dimensional image.
+/// :param min_sigma: The smallest scale, in pixels, default = 1.0. Must be
+///     > 0.0.
+/// :param max_sigma: The largest scale, in pixels, default = 50.0. Must be >=
+///     "min_sigma".
+/// :param sigma_ratio: The ratio of consecutive scales, default = 1.6. Must
+///     be > 1.0.
+/// :param threshold: The minimum scale normalized response of a blob,
+///     default = 0.5.
+/// :param overlap: The maximum overlap fraction of two kept blobs, default =
+///     0.5. Must be in [0.0, 1.0].
+/// :return: The blobs with shape (blobs, ndim + 1), where each row holds the
+///     blob center coordinates followed by the blob radius, in descending
+///     order of response.
+#[pyfunction]
+#[pyo3(name = "blob_dog")]
+#[pyo3(signature = (data, min_sigma=None, max_sigma=None, sigma_ratio=None, threshold=None, overlap=None))]
+pub fn feature_blob_dog<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    min_sigma: Option<f64>,
+    max_sigma: Option<f64>,
+    sigma_ratio: Option<f64>,
+    threshold: Option<f64>,
+    overlap: Option<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        feature::blob_dog(
+            arr.as_array(),
+            min_sigma,
+            max_sigma,
+            sigma_ratio,
+            threshold,
+            overlap,
+        )
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Detect bright blobs in a 2 or 3-dimensional image with the scale
+/// normalized Laplacian of Gaussian (LoG).
+///
+/// This function filters the image with the negated, scale normalized LoG
+/// (-σ² × LoG) at "num_sigma" linearly spaced scales from "min_sigma" to
+/// "max_sigma". Blobs are the local maxima of the scale space (i.e. across
+/// space and adjacent scales) above the threshold. The radius of a blob
+/// detected at scale σ is √n × σ, where n is the number of image dimensions.
+/// Overlapping blobs are pruned from the strongest to the weakest response,
+/// a blob is dropped if more than the "overlap" fraction of the smaller of it
+/// and a stronger kept blob lies in their intersection.
+///
+/// :param data: The input 2 or 3-dimensional image.
+/// :param min_sigma: The smallest scale, in pixels, default = 1.0. Must be
+///     > 0.0.
+/// :param max_sigma: The largest scale, in pixels, default = 50.0. Must be >=
+///     "min_sigma".
+/// :param num_sigma: The number of scales, default = 10. Must be >= 1.
+/// :param threshold: The minimum scale normalized response of a blob,
+///     default = 0.2.
+/// :param overlap: The maximum overlap fraction of two kept blobs, default =
+///     0.5. Must be in [0.0, 1.0].
+/// :return: The blobs with shape (blobs, ndim + 1), where each row holds the
+///     blob center coordinates followed by the blob radius, in descending
+///     order of response.
+#[pyfunction]
+#[pyo3(name = "blob_log")]
+#[pyo3(signature = (data, min_sigma=None, max_sigma=None, num_sigma=None, threshold=None, overlap=None))]
+pub fn feature_blob_log<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    min_sigma: Option<f64>,
+    max_sigma: Option<f64>,
+    num_sigma: Option<usize>,
+    threshold: Option<f64>,
+    overlap: Option<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        feature::blob_log(
+            arr.as_array(),
+            min_sigma,
+            max_sigma,
+            num_sigma,
+            threshold,
+            overlap,
+        )
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Find the local maxima (i.e. peaks) of an n-dimensional image.
+///
+/// This function finds the pixels that are the maximum of the box of radius
+/// "min_distance" centered on them and strictly above the threshold:
+///
+/// threshold = max(threshold_abs, threshold_rel × max(data))
+///
+/// The candidate peaks are visited from the highest to the lowest value, and a
+/// candidate is dropped if it is within a Chebyshev distance of
+/// "min_distance" of a kept peak. Plateaus give a single peak and a flat image
+/// has no peaks.
+///
+/// :param data: The input n-dimensional image.
+/// :param min_distance: The minimum distance between peaks, in pixels,
+///     default = 1. Must be >= 1.
+/// :param threshold_abs: The minimum peak value, default = the minimum of
+///     "data".
+/// :param threshold_rel: The minimum peak value relative to the maximum of
+///     "data", default = 0.0. Must be in [0.0, 1.0].
+/// :param mask: An optional boolean mask, pixels set to "False" are not peaks.
+///     Must match the shape of "data".
+/// :return: The peak coordinates with shape (peaks, ndim), in descending order
+///     of peak value.
+#[pyfunction]
+#[pyo3(name = "peak_local_max")]
+#[pyo3(signature = (data, min_distance=None, threshold_abs=None, threshold_rel=None, mask=None))]
+pub fn feature_peak_local_max<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    min_distance: Option<usize>,
+    threshold_abs: Option<f64>,
+    threshold_rel: Option<f64>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<Bound<'py, PyArray2<usize>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    let output = dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        with_warnings(py, || {
+            feature::peak_local_max(
+                arr.as_array(),
+                min_distance,
+                threshold_abs,
+                threshold_rel,
+                msk,
+            )
+        })?
+        .map_err(map_imgal_error)?
+    });
+
+    Ok(output.into_pyarray(py))
+}
@@ -1,7 +1,55 @@
-use numpy::{IntoPyArray, PyArray1};
+use numpy::{
+    IntoPyArray, PyArray1, PyArray2, PyArray3, PyArrayDyn, PyReadonlyArray2, PyReadonlyArray3,
+    PyReadonlyArrayDyn, PyReadwriteArrayDyn,
+};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use imgal::filter;
+use crate::error::map_imgal_error;
+use crate::utils::{
+    DLPackArray2, DLPackArray4, DLPackArrayDyn, dispatch_dtype, dispatch_dtype_mut,
+};
+use imgal::filter::{self, BorderMode, ConvolveMethod, GaussianMethod, GradientOperator};
+
+/// Convolve an n-dimensional image with a kernel.
+///
+/// This function computes the convolution of an n-dimensional image with a
+/// centered kernel of the same number of dimensions. The kernel center is the
+/// index (n - 1) / 2 of each axis (i.e. the center of odd shapes). Values past
+/// the edges of the image are extended with the "border" mode. By default
+/// kernels with up to 125 elements are convolved directly and larger kernels
+/// with the Fast Fourier Transform (FFT).
+///
+/// :param data: The input n-dimensional image.
+/// :param kernel: The convolution kernel, with the same number of dimensions as
+///     "data".
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :param method: The convolution method, "direct" or "fft". If None, then the
+///     method is chosen from the kernel size.
+/// :return: The convolved image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "convolve")]
+#[pyo3(signature = (data, kernel, border=None, cval=None, method=None))]
+pub fn filter_convolve<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    kernel: DLPackArrayDyn<f64>,
+    border: Option<&str>,
+    cval: Option<f64>,
+    method: Option<&str>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+    let method = parse_convolve_method(method)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::convolve(arr.as_array(), kernel.as_array(), border, method)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
 
 /// Convolve two 1-dimensional signals using the Fast Fourier Transform (FFT).
 ///
@@ -26,6 +74,84 @@ pub fn filter_fft_convolve_1d(
     Ok(output.into_pyarray(py))
 }
 
+/// Convolve two 2-dimensional images using the Fast Fourier Transform (FFT).
+///
+/// Compute the linear convolution of two images ("a" and "b") by transforming
+/// them to the frequency domain, multiplying them, and then transforming the
+/// result back into an image. This function uses "same-shape" trimming with
+/// the first parameter "a", the returned convolution is the first "a.shape"
+/// elements of the full convolution.
+///
+/// :param a: The first input image to FFT convolve. Returned convolution arrays
+///     will be "same-shape" trimmed to "a"'s shape.
+/// :param b: The second input image (e.g. a kernel) to FFT convolve.
+/// :return: The FFT convolved result with the same shape as input image "a".
+#[pyfunction]
+#[pyo3(name = "fft_convolve_2d")]
+pub fn filter_fft_convolve_2d<'py>(
+    py: Python<'py>,
+    a: DLPackArray2<f64>,
+    b: DLPackArray2<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let output = filter::fft_convolve_2d(a.as_array(), b.as_array());
+    Ok(output.into_pyarray(py))
+}
+
+/// Convolve two n-dimensional arrays using the Fast Fourier Transform (FFT).
+///
+/// Compute the linear convolution of two n-dimensional arrays ("a" and "b") by
+/// transforming them to the frequency domain, multiplying them, and then
+/// transforming the result back. Each axis is zero padded to avoid circular
+/// wrap around. This function uses "same-shape" trimming with the first
+/// parameter "a".
+///
+/// :param a: The first input array to FFT convolve. Returned convolution arrays
+///     will be "same-shape" trimmed to "a"'s shape.
+/// :param b: The second input array (e.g. a kernel) to FFT convolve. Must have
+///     the same number of dimensions as "a".
+/// :return: The FFT convolved result with the same shape as input array "a".
+#[pyfunction]
+#[pyo3(name = "fft_convolve_nd")]
+pub fn filter_fft_convolve_nd<'py>(
+    py: Python<'py>,
+    a: DLPackArrayDyn<f64>,
+    b: DLPackArrayDyn<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    filter::fft_convolve_nd(a.as_array(), b.as_array())
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Convolve each lane of a 3-dimensional array with a 1-dimensional kernel
+/// using the Fast Fourier Transform (FFT).
+///
+/// Compute the convolution of every 1-dimensional lane along "axis" (e.g. the
+/// decay curve of each pixel) with the same "kernel" (e.g. an instrument
+/// response function). The lanes are convolved in parallel and use
+/// "same-length" trimming, the returned lanes have the same length as the
+/// input lanes.
+///
+/// :param data: The input 3-dimensional array.
+/// :param kernel: The 1-dimensional kernel to convolve each lane with.
+/// :param axis: The lane axis, default = 2.
+/// :return: The FFT convolved array with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "fft_convolve_axis")]
+#[pyo3(signature = (data, kernel, axis=None))]
+pub fn filter_fft_convolve_axis<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    kernel: Vec<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        filter::fft_convolve_axis(arr.as_array(), &kernel, axis)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
 /// Deconvolve two 1-dimensional signals using the Fast Fourier Transform (FFT).
 ///
 /// Compute the deconvolution of two discrete signals (`a` and `b`) by transforming
@@ -52,3 +178,892 @@ pub fn filter_fft_deconvolve_1d(
     let output = filter::fft_deconvolve_1d(&a, &b, epsilon);
     Ok(output.into_pyarray(py))
 }
+
+/// Apply a Gaussian low-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// This function attenuates high spatial frequencies of each 2-dimensional
+/// plane (i.e. each slice along "axes" of an image stack) with the transfer
+/// function H(k) = exp(-k² / (2 × k₀²)), where "k" is the radial spatial
+/// frequency and "k₀" is the cutoff frequency. Planes are filtered in
+/// parallel.
+///
+/// :param data: The input n-dimensional array, with at least 2 dimensions.
+/// :param cutoff: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// :param axes: The (row, col) axes of the 2-dimensional planes, default =
+///     (0, 1).
+/// :return: The filtered array with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "gaussian_low_pass")]
+#[pyo3(signature = (data, cutoff, axes=None))]
+pub fn filter_gaussian_low_pass<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    cutoff: f64,
+    axes: Option<(usize, usize)>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::gaussian_low_pass(arr.as_array(), cutoff, axes)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Gaussian high-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// This function attenuates low spatial frequencies of each 2-dimensional plane
+/// (i.e. each slice along "axes" of an image stack) with the transfer function
+/// H(k) = 1 - exp(-k² / (2 × k₀²)), where "k" is the radial spatial frequency
+/// and "k₀" is the cutoff frequency. The mean of each plane is removed.
+///
+/// :param data: The input n-dimensional array, with at least 2 dimensions.
+/// :param cutoff: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// :param axes: The (row, col) axes of the 2-dimensional planes, default =
+///     (0, 1).
+/// :return: The filtered array with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "gaussian_high_pass")]
+#[pyo3(signature = (data, cutoff, axes=None))]
+pub fn filter_gaussian_high_pass<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    cutoff: f64,
+    axes: Option<(usize, usize)>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::gaussian_high_pass(arr.as_array(), cutoff, axes)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Gaussian band-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// This function keeps the spatial frequencies between the "low" and "high"
+/// cutoff frequencies of each 2-dimensional plane with the product of a
+/// Gaussian high-pass filter at "low" and a Gaussian low-pass filter at
+/// "high". The mean of each plane is removed.
+///
+/// :param data: The input n-dimensional array, with at least 2 dimensions.
+/// :param low: The low cutoff frequency, in cycles per pixel. Must be > 0.0.
+/// :param high: The high cutoff frequency, in cycles per pixel. Must be > "low".
+/// :param axes: The (row, col) axes of the 2-dimensional planes, default =
+///     (0, 1).
+/// :return: The filtered array with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "gaussian_band_pass")]
+#[pyo3(signature = (data, low, high, axes=None))]
+pub fn filter_gaussian_band_pass<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    low: f64,
+    high: f64,
+    axes: Option<(usize, usize)>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::gaussian_band_pass(arr.as_array(), low, high, axes)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Butterworth low-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// This function attenuates high spatial frequencies of each 2-dimensional
+/// plane (i.e. each slice along "axes" of an image stack) with the transfer
+/// function H(k) = 1 / (1 + (k / k₀)²ⁿ), where "k" is the radial spatial
+/// frequency, "k₀" is the cutoff frequency and "n" is the filter order.
+///
+/// :param data: The input n-dimensional array, with at least 2 dimensions.
+/// :param cutoff: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// :param order: The filter order, n, default = 2.
+/// :param axes: The (row, col) axes of the 2-dimensional planes, default =
+///     (0, 1).
+/// :return: The filtered array with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "butterworth_low_pass")]
+#[pyo3(signature = (data, cutoff, order=None, axes=None))]
+pub fn filter_butterworth_low_pass<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    cutoff: f64,
+    order: Option<u32>,
+    axes: Option<(usize, usize)>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::butterworth_low_pass(arr.as_array(), cutoff, order, axes)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Butterworth high-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// This function attenuates low spatial frequencies of each 2-dimensional plane
+/// (i.e. each slice along "axes" of an image stack) with the transfer function
+/// H(k) = 1 / (1 + (k₀ / k)²ⁿ), where "k" is the radial spatial frequency, "k₀"
+/// is the cutoff frequency and "n" is the filter order. The mean of each plane
+/// is removed.
+///
+/// :param data: The input n-dimensional array, with at least 2 dimensions.
+/// :param cutoff: The cutoff frequency, k₀, in cycles per pixel. Must be > 0.0.
+/// :param order: The filter order, n, default = 2.
+/// :param axes: The (row, col) axes of the 2-dimensional planes, default =
+///     (0, 1).
+/// :return: The filtered array with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "butterworth_high_pass")]
+#[pyo3(signature = (data, cutoff, order=None, axes=None))]
+pub fn filter_butterworth_high_pass<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    cutoff: f64,
+    order: Option<u32>,
+    axes: Option<(usize, usize)>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::butterworth_high_pass(arr.as_array(), cutoff, order, axes)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Butterworth band-pass filter to the 2-dimensional planes of an
+/// n-dimensional array.
+///
+/// This function keeps the spatial frequencies between the "low" and "high"
+/// cutoff frequencies of each 2-dimensional plane with the product of a
+/// Butterworth high-pass filter at "low" and a Butterworth low-pass filter at
+/// "high". The mean of each plane is removed.
+///
+/// :param data: The input n-dimensional array, with at least 2 dimensions.
+/// :param low: The low cutoff frequency, in cycles per pixel. Must be > 0.0.
+/// :param high: The high cutoff frequency, in cycles per pixel. Must be > "low".
+/// :param order: The filter order, n, default = 2.
+/// :param axes: The (row, col) axes of the 2-dimensional planes, default =
+///     (0, 1).
+/// :return: The filtered array with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "butterworth_band_pass")]
+#[pyo3(signature = (data, low, high, order=None, axes=None))]
+pub fn filter_butterworth_band_pass<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    low: f64,
+    high: f64,
+    order: Option<u32>,
+    axes: Option<(usize, usize)>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::butterworth_band_pass(arr.as_array(), low, high, order, axes)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Gaussian filter to an n-dimensional image.
+///
+/// This function smooths an n-dimensional image (e.g. a 2D image or a 3D
+/// volume) with a Gaussian kernel, G(x) = exp(-x² / (2σ²)). The filter is
+/// computed as one separable 1-dimensional pass along each axis with a non-zero
+/// sigma. Values past the edges of the image are extended with the "border"
+/// mode.
+///
+/// :param data: The input n-dimensional image.
+/// :param sigma: The standard deviation of the Gaussian kernel along each axis,
+///     in pixels. Must have one value per axis. A sigma of 0.0 does not filter
+///     that axis (e.g. the channel axis of a multi-channel image).
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :param method: The 1-dimensional pass method, "direct" (truncated kernel
+///     convolution) or "recursive" (Deriche recursive approximation, faster for
+///     large sigmas), default = "direct".
+/// :return: The filtered image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "gaussian")]
+#[pyo3(signature = (data, sigma, border=None, cval=None, method=None))]
+pub fn filter_gaussian<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma: Vec<f64>,
+    border: Option<&str>,
+    cval: Option<f64>,
+    method: Option<&str>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+    let method = parse_gaussian_method(method)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::gaussian(arr.as_array(), &sigma, border, method)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Gaussian filter to an n-dimensional image in place.
+///
+/// This function smooths an n-dimensional image with a Gaussian kernel, see
+/// "gaussian". The image keeps its float32 or float64 dtype, so a large float32
+/// stack is filtered without a float64 copy.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// :param data: The n-dimensional image to filter.
+/// :param sigma: The standard deviation of the Gaussian kernel along each axis,
+///     in pixels. Must have one value per axis. A sigma of 0.0 does not filter
+///     that axis.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :param method: The 1-dimensional pass method, "direct" or "recursive",
+///     default = "direct".
+#[pyfunction]
+#[pyo3(name = "gaussian_mut")]
+#[pyo3(signature = (data, sigma, border=None, cval=None, method=None))]
+pub fn filter_gaussian_mut<'py>(
+    data: Bound<'py, PyAny>,
+    sigma: Vec<f64>,
+    border: Option<&str>,
+    cval: Option<f64>,
+    method: Option<&str>,
+) -> PyResult<()> {
+    let border = parse_border_mode(border, cval)?;
+    let method = parse_gaussian_method(method)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype_mut!(data, PyReadwriteArrayDyn::<T>, [f32, f64], |mut arr| {
+        filter::gaussian_mut(arr.as_array_mut(), &sigma, border, method).map_err(map_imgal_error)
+    })
+}
+
+/// Sharpen an n-dimensional image with an unsharp mask.
+///
+/// This function enhances edges and spots by adding the difference between the
+/// image and its Gaussian blurred copy (i.e. the "unsharp mask") back to the
+/// image, O = I + amount × (I - G_σ ∗ I).
+///
+/// :param data: The input n-dimensional image.
+/// :param sigma: The standard deviation of the Gaussian blur along each axis,
+///     in pixels. Must have one value per axis.
+/// :param amount: The sharpening strength, 0.0 returns the input image. Must be
+///     >= 0.0.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :param method: The 1-dimensional Gaussian pass method, "direct" or
+///     "recursive", default = "direct".
+/// :return: The sharpened image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "unsharp_mask")]
+#[pyo3(signature = (data, sigma, amount, border=None, cval=None, method=None))]
+pub fn filter_unsharp_mask<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma: Vec<f64>,
+    amount: f64,
+    border: Option<&str>,
+    cval: Option<f64>,
+    method: Option<&str>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+    let method = parse_gaussian_method(method)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::unsharp_mask(arr.as_array(), &sigma, amount, border, method)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a difference of Gaussians (DoG) filter to an n-dimensional image.
+///
+/// This function subtracts a strongly blurred copy of the image from a weakly
+/// blurred copy, O = G_σ_low ∗ I - G_σ_high ∗ I. The result is a band-pass
+/// filter that enhances structures (e.g. spots) with a size between the two
+/// sigmas and removes the smooth background.
+///
+/// :param data: The input n-dimensional image.
+/// :param sigma_low: The standard deviation of the weak Gaussian blur along
+///     each axis, in pixels. Must have one value per axis.
+/// :param sigma_high: The standard deviation of the strong Gaussian blur along
+///     each axis, in pixels. Must have one value per axis, each >= the matching
+///     "sigma_low" value.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :param method: The 1-dimensional Gaussian pass method, "direct" or
+///     "recursive", default = "direct".
+/// :return: The filtered image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "difference_of_gaussians")]
+#[pyo3(signature = (data, sigma_low, sigma_high, border=None, cval=None, method=None))]
+pub fn filter_difference_of_gaussians<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma_low: Vec<f64>,
+    sigma_high: Vec<f64>,
+    border: Option<&str>,
+    cval: Option<f64>,
+    method: Option<&str>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+    let method = parse_gaussian_method(method)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::difference_of_gaussians(arr.as_array(), &sigma_low, &sigma_high, border, method)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Deconvolve an n-dimensional image with a Wiener filter.
+///
+/// This function compensates the blur of a point spread function (PSF) in a
+/// single, non-iterative step by filtering the image in the frequency domain
+/// with the Wiener filter, X(f) = H*(f) × Y(f) / (|H(f)|² + NSR). An NSR of 0.0
+/// is the inverse filter, larger values suppress noise amplification at the
+/// cost of a smoother result. The image is treated as periodic, the PSF is
+/// normalized to sum to 1.0 and its center, the index (n - 1) / 2 of each axis,
+/// is moved to the origin.
+///
+/// :param data: The blurred n-dimensional image.
+/// :param psf: The centered point spread function, with the same number of
+///     dimensions as "data" and no axis longer than the matching "data" axis.
+/// :param nsr: The noise-to-signal power ratio. Must be >= 0.0.
+/// :return: The deconvolved image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "wiener")]
+pub fn filter_wiener<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    psf: DLPackArrayDyn<f64>,
+    nsr: f64,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::wiener(arr.as_array(), psf.as_array(), nsr)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a Laplacian of Gaussian (LoG) filter to an n-dimensional image.
+///
+/// This function computes the sum of the second order Gaussian derivatives
+/// along each axis with a non-zero sigma. Bright blobs give strong negative
+/// responses and dark blobs give strong positive responses.
+///
+/// :param data: The input n-dimensional image.
+/// :param sigma: The standard deviation of the Gaussian along each axis, in
+///     pixels. Must have one value per axis. A sigma of 0.0 does not filter or
+///     differentiate that axis.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :return: The LoG filtered image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "log")]
+#[pyo3(signature = (data, sigma, border=None, cval=None))]
+pub fn filter_log<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigma: Vec<f64>,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::log(arr.as_array(), &sigma, border)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Enhance tubular structures in a 2 or 3-dimensional image with the Frangi
+/// vesselness filter.
+///
+/// This function computes the eigenvalues of the scale normalized Hessian
+/// matrix of each pixel at each scale and combines them into a vesselness
+/// measure that is high inside tubes and low for blobs, plates and the
+/// background. The output is the maximum vesselness over all scales.
+///
+/// :param data: The input 2 or 3-dimensional image.
+/// :param sigmas: The scales (i.e. isotropic Gaussian standard deviations) in
+///     pixels, matched to the tube radii to enhance. Must be > 0.0.
+/// :param alpha: The plate-like structure sensitivity of 3-dimensional images,
+///     default = 0.5.
+/// :param beta: The blob-like structure sensitivity, default = 0.5.
+/// :param gamma: The structureness sensitivity. If None, then half of the
+///     maximum structureness of each scale is used.
+/// :param bright: If True, bright tubes on a dark background are enhanced,
+///     otherwise dark tubes on a bright background. Default = True.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :return: The vesselness image with the same shape as "data", with values in
+///     [0, 1].
+#[pyfunction]
+#[pyo3(name = "frangi")]
+#[pyo3(signature = (data, sigmas, alpha=None, beta=None, gamma=None, bright=None, border=None, cval=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn filter_frangi<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigmas: Vec<f64>,
+    alpha: Option<f64>,
+    beta: Option<f64>,
+    gamma: Option<f64>,
+    bright: Option<bool>,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::frangi(arr.as_array(), &sigmas, alpha, beta, gamma, bright, border)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Enhance tubular structures in a 2 or 3-dimensional image with the Sato
+/// tubeness filter.
+///
+/// This function computes the eigenvalues of the scale normalized Hessian
+/// matrix of each pixel at each scale. The tubeness is the geometric mean of
+/// the cross-sectional eigenvalues of the right sign, and the output is the
+/// maximum tubeness over all scales.
+///
+/// :param data: The input 2 or 3-dimensional image.
+/// :param sigmas: The scales (i.e. isotropic Gaussian standard deviations) in
+///     pixels, matched to the tube radii to enhance. Must be > 0.0.
+/// :param bright: If True, bright tubes on a dark background are enhanced,
+///     otherwise dark tubes on a bright background. Default = True.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :return: The tubeness image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "sato")]
+#[pyo3(signature = (data, sigmas, bright=None, border=None, cval=None))]
+pub fn filter_sato<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    sigmas: Vec<f64>,
+    bright: Option<bool>,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::sato(arr.as_array(), &sigmas, bright, border)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the gradient along an axis of an n-dimensional image with the
+/// Sobel operator.
+///
+/// This function computes the central difference along "axis" and smooths
+/// every other axis with the normalized [1, 2, 1] / 4 kernel. The gradient is in
+/// intensity units per pixel (i.e. a ramp with a slope of 1.0 has a gradient
+/// of 1.0).
+///
+/// :param data: The input n-dimensional image.
+/// :param axis: The derivative axis.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :return: The gradient along "axis" with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "sobel")]
+#[pyo3(signature = (data, axis, border=None, cval=None))]
+pub fn filter_sobel<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: usize,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::sobel(arr.as_array(), axis, border)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the gradient along an axis of an n-dimensional image with the
+/// Scharr operator.
+///
+/// This function computes the central difference along "axis" and smooths
+/// every other axis with the normalized [3, 10, 3] / 16 kernel. The gradient is in
+/// intensity units per pixel (i.e. a ramp with a slope of 1.0 has a gradient
+/// of 1.0).
+///
+/// :param data: The input n-dimensional image.
+/// :param axis: The derivative axis.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :return: The gradient along "axis" with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "scharr")]
+#[pyo3(signature = (data, axis, border=None, cval=None))]
+pub fn filter_scharr<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: usize,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::scharr(arr.as_array(), axis, border)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the gradient magnitude of an n-dimensional image.
+///
+/// This function computes the gradient along each axis with a first derivative
+/// operator and combines them into the Euclidean norm, √(Σᵢ (∂I / ∂xᵢ)²).
+///
+/// :param data: The input n-dimensional image.
+/// :param operator: The first derivative operator, "sobel" or "scharr",
+///     default = "sobel".
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :return: The gradient magnitude with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "gradient_magnitude")]
+#[pyo3(signature = (data, operator=None, border=None, cval=None))]
+pub fn filter_gradient_magnitude<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    operator: Option<&str>,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let operator = parse_gradient_operator(operator)?;
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::gradient_magnitude(arr.as_array(), operator, border)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Subtract the background of an n-dimensional image with a rolling ball.
+///
+/// This function estimates the background as the surface traced by the top of
+/// a ball rolled under the intensity landscape of the image (i.e. the
+/// grayscale opening), and subtracts it. The ball is approximated by the
+/// paraboloid with the same curvature at its apex, which makes the cost
+/// independent of the radius.
+///
+/// :param data: The input n-dimensional image.
+/// :param radius: The ball radius along each axis, in pixels, larger than the
+///     objects to keep. Must have one value per axis. A radius of 0.0 does not
+///     roll the ball along that axis.
+/// :param bright: If True, bright objects on a dark background are kept,
+///     otherwise dark objects on a bright background. Default = True.
+/// :return: The background subtracted image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "rolling_ball")]
+#[pyo3(signature = (data, radius, bright=None))]
+pub fn filter_rolling_ball<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Vec<f64>,
+    bright: Option<bool>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::rolling_ball(arr.as_array(), &radius, bright)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a morphological top-hat filter to an n-dimensional image.
+///
+/// This function subtracts the grayscale opening of the image with a flat box
+/// structuring element (i.e. the white top-hat), which keeps bright objects
+/// smaller than the box and removes the background. The cost does not depend
+/// on the box size.
+///
+/// :param data: The input n-dimensional image.
+/// :param radius: The box radius along each axis, in pixels, the box has a side
+///     length of 2 × radius + 1. Must have one value per axis. A radius of 0
+///     does not filter that axis.
+/// :param bright: If True, bright objects on a dark background are kept (the
+///     white top-hat), otherwise dark objects on a bright background (the black
+///     top-hat). Default = True.
+/// :return: The top-hat filtered image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "top_hat")]
+#[pyo3(signature = (data, radius, bright=None))]
+pub fn filter_top_hat<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Vec<usize>,
+    bright: Option<bool>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::top_hat(arr.as_array(), &radius, bright)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Equalize the histogram of an n-dimensional image.
+///
+/// This function spreads the intensities of an image evenly over the output
+/// range by mapping each value through the cumulative distribution function
+/// (CDF) of the image histogram, O = (CDF(v) - CDF_min) / (N - CDF_min). A
+/// constant image maps to 0.0.
+///
+/// :param data: The input n-dimensional image.
+/// :param bins: The number of histogram bins, default = 256.
+/// :return: The equalized image with the same shape as "data", with values in
+///     [0, 1].
+#[pyfunction]
+#[pyo3(name = "equalize_histogram")]
+#[pyo3(signature = (data, bins=None))]
+pub fn filter_equalize_histogram<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    bins: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::equalize_histogram(arr.as_array(), bins)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Match the histogram of an n-dimensional image to a reference image.
+///
+/// This function remaps the intensities of an image so that its cumulative
+/// distribution function (CDF) matches the CDF of a reference image (i.e.
+/// histogram matching or histogram specification). Each distinct value "v" of
+/// the image is mapped to the reference value at the same quantile,
+/// O(v) = CDF_ref⁻¹(CDF(v)), where the inverse reference CDF is linearly
+/// interpolated between the distinct reference values. The images do not need
+/// to have the same shape.
+///
+/// :param data: The input n-dimensional image to remap.
+/// :param reference: The n-dimensional reference image, with the same dtype as
+///     "data".
+/// :return: The remapped image with the same shape as "data", with values in
+///     the range of "reference".
+#[pyfunction]
+#[pyo3(name = "match_histogram")]
+pub fn filter_match_histogram<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    reference: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        let ref_arr = reference.extract::<DLPackArrayDyn<T>>()?;
+        filter::match_histogram(arr.as_array(), ref_arr.as_array())
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply contrast limited adaptive histogram equalization (CLAHE) to a
+/// 2-dimensional image.
+///
+/// This function equalizes the clip limited histogram of each tile of a regular
+/// grid over the image, and maps each pixel with the bilinear interpolation of
+/// the mappings of the four nearest tile centers.
+///
+/// :param data: The input 2-dimensional image.
+/// :param tiles: The number of tiles along the (row, col) axes, default =
+///     (8, 8). Must be >= 1 and <= the image shape.
+/// :param clip_limit: The histogram clip limit as a fraction of the tile
+///     pixels, 1.0 does not clip, default = 0.01. Must be in (0, 1].
+/// :param bins: The number of histogram bins, default = 256.
+/// :return: The equalized image with the same shape as "data", with values in
+///     [0, 1].
+#[pyfunction]
+#[pyo3(name = "clahe")]
+#[pyo3(signature = (data, tiles=None, clip_limit=None, bins=None))]
+pub fn filter_clahe<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    tiles: Option<(usize, usize)>,
+    clip_limit: Option<f64>,
+    bins: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+        filter::clahe(arr.as_array(), tiles, clip_limit, bins)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Linearly rescale the intensities of an n-dimensional image between two
+/// percentiles.
+///
+/// This function maps the "low" percentile of the image to 0.0 and the "high"
+/// percentile to 1.0, and clips the values outside of that range (i.e.
+/// contrast stretching). If both percentiles have the same value, the output
+/// is 0.0.
+///
+/// :param data: The input n-dimensional image.
+/// :param low: The percentile mapped to 0.0, default = 2.0. Must be in
+///     [0, 100].
+/// :param high: The percentile mapped to 1.0, default = 98.0. Must be in
+///     [0, 100] and > "low".
+/// :return: The rescaled image with the same shape as "data", with values in
+///     [0, 1].
+#[pyfunction]
+#[pyo3(name = "rescale_percentile")]
+#[pyo3(signature = (data, low=None, high=None))]
+pub fn filter_rescale_percentile<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    low: Option<f64>,
+    high: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        filter::rescale_percentile(arr.as_array(), low, high)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// The maximum response and orientation maps of an oriented filter bank.
+type ResponseMaps<'py> = (Bound<'py, PyArray2<f64>>, Bound<'py, PyArray2<f64>>);
+
+/// Apply a bank of oriented filters to a 2-dimensional image and compute the
+/// maximum response and orientation maps.
+///
+/// This function convolves the image with every kernel of an oriented filter
+/// bank (see "kernel.orientation_bank") and keeps, for each pixel, the largest
+/// response over all scales and orientations and the orientation (angle) of
+/// that response.
+///
+/// :param data: The input 2-dimensional image.
+/// :param bank: The kernel bank with shape (scales, angles, rows, cols).
+/// :param angles: The orientation of each kernel along axis 1 of the bank, in
+///     radians.
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "reflect".
+/// :param cval: The border value of the "constant" border mode, default = 0.0.
+/// :return: The maximum response map and the orientation map (in radians), both
+///     with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "max_response")]
+#[pyo3(signature = (data, bank, angles, border=None, cval=None))]
+pub fn filter_max_response<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    bank: DLPackArray4<f64>,
+    angles: Vec<f64>,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<ResponseMaps<'py>> {
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+        filter::max_response(arr.as_array(), bank.as_array(), &angles, border)
+            .map(|(response, orientation)| {
+                (response.into_pyarray(py), orientation.into_pyarray(py))
+            })
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Parse an optional border mode name.
+pub(crate) fn parse_border_mode(
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Option<BorderMode>> {
+    match border {
+        None => Ok(None),
+        Some("reflect") => Ok(Some(BorderMode::Reflect)),
+        Some("constant") => Ok(Some(BorderMode::Constant(cval.unwrap_or(0.0)))),
+        Some("wrap") => Ok(Some(BorderMode::Wrap)),
+        Some(b) => Err(PyValueError::new_err(format!(
+            "Unknown border mode \"{}\", supported modes are \"reflect\", \"constant\", and \"wrap\".",
+            b
+        ))),
+    }
+}
+
+/// Parse an optional Gaussian filter method name.
+fn parse_gaussian_method(method: Option<&str>) -> PyResult<Option<GaussianMethod>> {
+    match method {
+        None => Ok(None),
+        Some("direct") => Ok(Some(GaussianMethod::Direct)),
+        Some("recursive") => Ok(Some(GaussianMethod::Recursive)),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown Gaussian method \"{}\", supported methods are \"direct\" and \"recursive\".",
+            m
+        ))),
+    }
+}
+
+/// Parse an optional gradient operator name.
+fn parse_gradient_operator(operator: Option<&str>) -> PyResult<Option<GradientOperator>> {
+    match operator {
+        None => Ok(None),
+        Some("sobel") => Ok(Some(GradientOperator::Sobel)),
+        Some("scharr") => Ok(Some(GradientOperator::Scharr)),
+        Some(o) => Err(PyValueError::new_err(format!(
+            "Unknown gradient operator \"{}\", supported operators are \"sobel\" and \"scharr\".",
+            o
+        ))),
+    }
+}
+
+/// Parse an optional convolution method name.
+fn parse_convolve_method(method: Option<&str>) -> PyResult<Option<ConvolveMethod>> {
+    match method {
+        None => Ok(None),
+        Some("direct") => Ok(Some(ConvolveMethod::Direct)),
+        Some("fft") => Ok(Some(ConvolveMethod::Fft)),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown convolution method \"{}\", supported methods are \"direct\" and \"fft\".",
+            m
+        ))),
+    }
+}
@@ -1,7 +1,8 @@
 use numpy::PyReadonlyArrayDyn;
-use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
 
+use crate::error::with_warnings;
+use crate::utils::dispatch_dtype;
 use imgal::image;
 
 /// Compute the image histogram from an n-dimensional array.
@@ -17,21 +18,12 @@ use imgal::image;
 #[pyfunction]
 #[pyo3(name = "histogram")]
 #[pyo3(signature = (data, bins=None))]
-pub fn image_histogram<'py>(data: Bound<'py, PyAny>, bins: Option<usize>) -> PyResult<Vec<i64>> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        return Ok(image::histogram(arr.as_array(), bins));
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+pub fn image_histogram<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    bins: Option<usize>,
+) -> PyResult<Vec<i64>> {
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        with_warnings(py, || image::histogram(arr.as_array(), bins))
+    })
 }
@@ -1,6 +1,10 @@
+use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use imgal::integration;
+use crate::error::map_imgal_error;
+use crate::utils::dispatch_dtype;
+use imgal::integration::{self, IntegrationMethod};
 
 /// Integrate a curve with Simpson's 1/3 rule and the trapezoid rule.
 ///
@@ -26,6 +30,108 @@ pub fn integration_composite_simpson(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
     integration::composite_simpson(&x, delta_x)
 }
 
+/// Integrate a curve sampled at non-uniform x-coordinates with Simpson's 1/3
+/// rule and the trapezoid rule.
+///
+/// Approximates the definite integral using Simpson's 1/3 rule with explicit,
+/// possibly irregularly spaced, x-coordinates (e.g. the non-linear time bins
+/// of some TCSPC systems). Each pair of subintervals of widths h₀ and h₁ is
+/// integrated with the quadratic through its 3 points:
+///
+/// ∫(f(x)dx) ≈ ((h₀ + h₁)/6) * [(2 - h₁/h₀)f(x₀) + ((h₀ + h₁)²/(h₀h₁))f(x₁) + (2 - h₀/h₁)f(x₂)]
+///
+/// If there is an odd number of subintervals, the final subinterval is
+/// integrated using the trapezoid rule.
+///
+/// :param y: The 1-dimensional data to integrate.
+/// :param x: The x-coordinate (e.g. bin center) of each data point. Must be the
+///     same length as "y", finite and strictly increasing.
+/// :return: The computed integral.
+#[pyfunction]
+#[pyo3(name = "composite_simpson_nonuniform")]
+pub fn integration_composite_simpson_nonuniform(y: Vec<f64>, x: Vec<f64>) -> PyResult<f64> {
+    integration::composite_simpson_nonuniform(&y, &x).map_err(map_imgal_error)
+}
+
+/// Compute the running integral of a curve with the trapezoid rule.
+///
+/// Computes the cumulative integral from the first point to each point using
+/// the trapezoid rule:
+///
+/// F(xₖ) ≈ Σᵢ₌₀ᵏ⁻¹ (Δx/2) * [f(xᵢ) + f(xᵢ₊₁)]
+///
+/// where F(x₀) = 0.0.
+///
+/// :param x: The 1-dimensional data to integrate.
+/// :param delta_x: The width between data points, default = 1.0.
+/// :return: The running integral with the same length as "x".
+#[pyfunction]
+#[pyo3(name = "cumulative_trapezoid")]
+#[pyo3(signature = (x, delta_x=None))]
+pub fn integration_cumulative_trapezoid(x: Vec<f64>, delta_x: Option<f64>) -> Vec<f64> {
+    integration::cumulative_trapezoid(&x, delta_x)
+}
+
+/// Compute the running integral of an n-dimensional array along one axis with
+/// the trapezoid rule.
+///
+/// This function computes the running integral of each 1-dimensional lane of
+/// "data" along "axis", the lanes are integrated in parallel.
+///
+/// :param data: The n-dimensional array to integrate.
+/// :param axis: The axis to integrate along.
+/// :param delta_x: The width between data points along "axis", default = 1.0.
+/// :return: The running integral with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "cumulative_trapezoid_axis")]
+#[pyo3(signature = (data, axis, delta_x=None))]
+pub fn integration_cumulative_trapezoid_axis<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: usize,
+    delta_x: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        integration::cumulative_trapezoid_axis(arr.as_array(), axis, delta_x)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Integrate an n-dimensional array along one axis.
+///
+/// This function integrates each 1-dimensional lane of "data" along "axis"
+/// with the chosen integration rule, the lanes are integrated in parallel.
+/// For example, integrating a (row, col, t) decay stack along the decay axis
+/// gives the (row, col) intensity image.
+///
+/// :param data: The n-dimensional array to integrate.
+/// :param axis: The axis to integrate along.
+/// :param delta_x: The width between data points along "axis", default = 1.0.
+/// :param method: The integration rule, "rectangle", "trapezoid" or "simpson",
+///     default = "simpson".
+/// :return: The integrals with the shape of "data" without "axis".
+#[pyfunction]
+#[pyo3(name = "integrate_axis")]
+#[pyo3(signature = (data, axis, delta_x=None, method=None))]
+pub fn integration_integrate_axis<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: usize,
+    delta_x: Option<f64>,
+    method: Option<&str>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let method = parse_integration_method(method)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        integration::integrate_axis(arr.as_array(), axis, delta_x, method)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
 /// Integrate a curve with the midpoint rule.
 ///
 /// Approximates the definite integral using the midpoint rule
@@ -59,6 +165,60 @@ pub fn integration_midpoint(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
 #[pyfunction]
 #[pyo3(name = "simpson")]
 #[pyo3(signature = (x, delta_x=None))]
-pub fn integration_simpson(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
-    integration::simpson(&x, delta_x).unwrap()
+pub fn integration_simpson(x: Vec<f64>, delta_x: Option<f64>) -> PyResult<f64> {
+    integration::simpson(&x, delta_x).map_err(map_imgal_error)
+}
+
+/// Integrate a curve with the trapezoid rule.
+///
+/// Approximates the definite integral using the trapezoid rule
+/// with pre-computed x-values:
+///
+/// ∫(f(x)dx) ≈ (Δx/2) * [f(x₀) + 2f(x₁) + 2f(x₂) + ... + 2f(xₙ₋₁) + f(xₙ)]
+///
+/// Where "n" is the number of evenly spaced points in the data.
+///
+/// :param x: The 1-dimensional data to integrate.
+/// :param delta_x: The width between data points, default = 1.0.
+/// :return: The computed integral.
+#[pyfunction]
+#[pyo3(name = "trapezoid")]
+#[pyo3(signature = (x, delta_x=None))]
+pub fn integration_trapezoid(x: Vec<f64>, delta_x: Option<f64>) -> f64 {
+    integration::trapezoid(&x, delta_x)
+}
+
+/// Integrate a curve sampled at non-uniform x-coordinates with the trapezoid
+/// rule.
+///
+/// Approximates the definite integral using the trapezoid rule with explicit,
+/// possibly irregularly spaced, x-coordinates (e.g. the non-linear time bins
+/// of some TCSPC systems):
+///
+/// ∫(f(x)dx) ≈ Σᵢ ((xᵢ₊₁ - xᵢ)/2) * [f(xᵢ) + f(xᵢ₊₁)]
+///
+/// :param y: The 1-dimensional data to integrate.
+/// :param x: The x-coordinate (e.g. bin center) of each data point. Must be the
+///     same length as "y", finite and strictly increasing.
+/// :return: The computed integral.
+#[pyfunction]
+#[pyo3(name = "trapezoid_nonuniform")]
+pub fn integration_trapezoid_nonuniform(y: Vec<f64>, x: Vec<f64>) -> PyResult<f64> {
+    integration::trapezoid_nonuniform(&y, &x).map_err(map_imgal_error)
+}
+
+/// Parse an optional integration method name.
+pub(crate) fn parse_integration_method(
+    method: Option<&str>,
+) -> PyResult<Option<IntegrationMethod>> {
+    match method {
+        None => Ok(None),
+        Some("rectangle") => Ok(Some(IntegrationMethod::Rectangle)),
+        Some("trapezoid") => Ok(Some(IntegrationMethod::Trapezoid)),
+        Some("simpson") => Ok(Some(IntegrationMethod::Simpson)),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown integration method \"{}\", supported methods are \"rectangle\", \"trapezoid\" and \"simpson\".",
+            m
+        ))),
+    }
 }
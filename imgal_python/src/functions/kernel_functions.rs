@@ -1,8 +1,10 @@
-use numpy::{IntoPyArray, PyArray2, PyArray3};
+use numpy::{IntoPyArray, PyArray2, PyArray3, PyArray4, PyArrayDyn};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use crate::error::map_array_error;
-use imgal::kernel;
+use crate::error::map_imgal_error;
+use crate::utils::DLPackArray2;
+use imgal::kernel::{self, KernelFactors, OrientedFilter};
 
 /// Create a 2-dimensional square kernel with a circle neighborhood.
 ///
@@ -20,7 +22,7 @@ use imgal::kernel;
 pub fn neighborhood_circle(py: Python, radius: usize) -> PyResult<Bound<PyArray2<bool>>> {
     kernel::neighborhood::circle(radius)
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+        .map_err(map_imgal_error)
 }
 
 /// Create a 3-dimensional cube kernel with a sphere neighborhood.
@@ -39,7 +41,7 @@ pub fn neighborhood_circle(py: Python, radius: usize) -> PyResult<Bound<PyArray2
 pub fn neighborhood_sphere(py: Python, radius: usize) -> PyResult<Bound<PyArray3<bool>>> {
     kernel::neighborhood::sphere(radius)
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+        .map_err(map_imgal_error)
 }
 
 /// Create a 2-dimensional square kernel with a weighted circle neighborhood.
@@ -74,7 +76,7 @@ pub fn neighborhood_weighted_circle(
 ) -> PyResult<Bound<PyArray2<f64>>> {
     kernel::neighborhood::weighted_circle(circle_radius, falloff_radius, initial_value)
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+        .map_err(map_imgal_error)
 }
 
 /// Create a 3-dimensional cube kernel with a weighted sphere neighborhood.
@@ -109,5 +111,235 @@ pub fn neighborhood_weighted_sphere(
 ) -> PyResult<Bound<PyArray3<f64>>> {
     kernel::neighborhood::weighted_sphere(sphere_radius, falloff_radius, initial_value)
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+        .map_err(map_imgal_error)
+}
+
+/// Create an n-dimensional (anisotropic) Gaussian kernel.
+///
+/// This function creates a kernel sampled from the Gaussian function,
+/// G(x) = exp(-Σᵢ xᵢ² / (2σᵢ²)), centered at the middle of the kernel, with a
+/// peak value of 1.0. An axis with a sigma of 0.0 has a length of 1.
+///
+/// :param sigma: The standard deviation along each axis, in pixels. The number
+///     of values sets the number of dimensions. Must be >= 0.0.
+/// :param radius: The kernel radius along each axis, each axis has a length of
+///     2 × radius + 1. If None, then the radius is 4 × sigma rounded up.
+/// :param normalize: If True, the kernel is scaled to sum to 1.0, default =
+///     False.
+/// :return: The Gaussian kernel.
+#[pyfunction]
+#[pyo3(name = "gaussian")]
+#[pyo3(signature = (sigma, radius=None, normalize=None))]
+pub fn parametric_gaussian(
+    py: Python,
+    sigma: Vec<f64>,
+    radius: Option<Vec<usize>>,
+    normalize: Option<bool>,
+) -> PyResult<Bound<PyArrayDyn<f64>>> {
+    kernel::parametric::gaussian(&sigma, radius.as_deref(), normalize)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Create an n-dimensional discrete Laplacian kernel.
+///
+/// This function creates the 3 × ... × 3 finite difference Laplacian stencil.
+/// The center is -2 × ndim and the direct neighbors along each axis are 1.0,
+/// the kernel sums to 0.0.
+///
+/// :param ndim: The number of dimensions. Must be >= 1.
+/// :param normalize: If True, the kernel is scaled so that the positive weights
+///     sum to 1.0, default = False.
+/// :return: The Laplacian kernel.
+#[pyfunction]
+#[pyo3(name = "laplacian")]
+#[pyo3(signature = (ndim, normalize=None))]
+pub fn parametric_laplacian(
+    py: Python,
+    ndim: usize,
+    normalize: Option<bool>,
+) -> PyResult<Bound<PyArrayDyn<f64>>> {
+    kernel::parametric::laplacian(ndim, normalize)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Create an n-dimensional disk (i.e. ball) kernel.
+///
+/// This function creates a kernel that is 1.0 inside or on the boundary of a
+/// disk of the given radius around the kernel center and 0.0 outside. Each axis
+/// has a length of 2 × floor(radius) + 1.
+///
+/// :param radius: The disk radius in pixels. Must be >= 0.0.
+/// :param ndim: The number of dimensions. Must be >= 1.
+/// :param normalize: If True, the kernel is scaled to sum to 1.0, default =
+///     False.
+/// :return: The disk kernel.
+#[pyfunction]
+#[pyo3(name = "disk")]
+#[pyo3(signature = (radius, ndim, normalize=None))]
+pub fn parametric_disk(
+    py: Python,
+    radius: f64,
+    ndim: usize,
+    normalize: Option<bool>,
+) -> PyResult<Bound<PyArrayDyn<f64>>> {
+    kernel::parametric::disk(radius, ndim, normalize)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Create an n-dimensional ring (i.e. shell) kernel.
+///
+/// This function creates a kernel that is 1.0 where the Euclidean distance to
+/// the kernel center is within [inner_radius, outer_radius] and 0.0 elsewhere.
+/// Each axis has a length of 2 × floor(outer_radius) + 1.
+///
+/// :param inner_radius: The inner ring radius in pixels. Must be >= 0.0.
+/// :param outer_radius: The outer ring radius in pixels. Must be >=
+///     "inner_radius".
+/// :param ndim: The number of dimensions. Must be >= 1.
+/// :param normalize: If True, the kernel is scaled to sum to 1.0, default =
+///     False.
+/// :return: The ring kernel.
+#[pyfunction]
+#[pyo3(name = "ring")]
+#[pyo3(signature = (inner_radius, outer_radius, ndim, normalize=None))]
+pub fn parametric_ring(
+    py: Python,
+    inner_radius: f64,
+    outer_radius: f64,
+    ndim: usize,
+    normalize: Option<bool>,
+) -> PyResult<Bound<PyArrayDyn<f64>>> {
+    kernel::parametric::ring(inner_radius, outer_radius, ndim, normalize)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Create a 2-dimensional Gabor kernel.
+///
+/// This function creates a Gabor kernel, a sinusoidal plane wave under a
+/// Gaussian envelope, G(x, y) = exp(-(x'² + γ²y'²) / (2σ²)) × cos(2πx' / λ + ψ),
+/// where x' and y' are the column and row offsets rotated by θ.
+///
+/// :param sigma: The standard deviation of the Gaussian envelope in pixels.
+///     Must be > 0.0.
+/// :param wavelength: The wavelength of the sinusoid in pixels. Must be > 0.0.
+/// :param theta: The orientation of the wave normal in radians from the column
+///     axis.
+/// :param phase: The phase offset of the sinusoid in radians, default = 0.0.
+/// :param aspect: The spatial aspect ratio of the envelope, default = 1.0. Must
+///     be > 0.0.
+/// :param radius: The kernel radius, the kernel has side lengths of
+///     2 × radius + 1. If None, then the radius is 4 × sigma / min(aspect, 1.0)
+///     rounded up.
+/// :param normalize: If True, the kernel is scaled to sum to 1.0, or so that
+///     its positive weights sum to 1.0 if it sums to 0.0, default = False.
+/// :return: The Gabor kernel.
+#[pyfunction]
+#[pyo3(name = "gabor")]
+#[pyo3(signature = (sigma, wavelength, theta, phase=None, aspect=None, radius=None, normalize=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn parametric_gabor(
+    py: Python,
+    sigma: f64,
+    wavelength: f64,
+    theta: f64,
+    phase: Option<f64>,
+    aspect: Option<f64>,
+    radius: Option<usize>,
+    normalize: Option<bool>,
+) -> PyResult<Bound<PyArray2<f64>>> {
+    kernel::parametric::gabor(sigma, wavelength, theta, phase, aspect, radius, normalize)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Create an n-dimensional box (i.e. hyper-rectangle) kernel.
+///
+/// This function creates a kernel filled with 1.0. Each axis has a length of
+/// 2 × radius + 1.
+///
+/// :param radius: The kernel radius along each axis. The number of values sets
+///     the number of dimensions.
+/// :param normalize: If True, the kernel is scaled to sum to 1.0, default =
+///     False.
+/// :return: The box kernel.
+#[pyfunction]
+#[pyo3(name = "rectangle")]
+#[pyo3(signature = (radius, normalize=None))]
+pub fn parametric_rectangle(
+    py: Python,
+    radius: Vec<usize>,
+    normalize: Option<bool>,
+) -> PyResult<Bound<PyArrayDyn<f64>>> {
+    kernel::parametric::rectangle(&radius, normalize)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Decompose a 2-dimensional kernel into two 1-dimensional factors.
+///
+/// This function tests whether a 2-dimensional kernel is (approximately) rank
+/// 1, the outer product of a factor along axis 0 (rows) and a factor along
+/// axis 1 (columns), K[i, j] ≈ a[i] × b[j]. The factors are computed from the
+/// largest singular value and its singular vectors.
+///
+/// :param kernel: The 2-dimensional kernel.
+/// :param tolerance: The largest relative (Frobenius norm) residual of the
+///     rank 1 approximation of a separable kernel, default = 1e-6. Must be
+///     >= 0.0.
+/// :return: The factors (as lists) along axis 0 and axis 1 of a separable
+///     kernel, or None if the kernel is not separable within the tolerance.
+#[pyfunction]
+#[pyo3(name = "separate")]
+#[pyo3(signature = (kernel, tolerance=None))]
+pub fn kernel_separate(
+    kernel: DLPackArray2<f64>,
+    tolerance: Option<f64>,
+) -> PyResult<Option<KernelFactors>> {
+    kernel::separate(kernel.as_array(), tolerance).map_err(map_imgal_error)
+}
+
+/// Create a bank of oriented 2-dimensional filter kernels.
+///
+/// This function creates one kernel per scale and orientation. Orientations
+/// are angles in radians from the column axis towards the row axis. The scale
+/// is the standard deviation of the Gaussian envelope in pixels. All kernels
+/// have the same size, 2 × ceil(4 × largest scale) + 1, and are centered.
+///
+/// :param filter: The oriented filter type, "gabor" (a zero mean, even Gabor
+///     kernel), "edge" (the scale normalized first Gaussian derivative along
+///     the orientation) or "ridge" (the negated scale normalized second
+///     Gaussian derivative along the orientation).
+/// :param angles: The orientations in radians. Must not be empty.
+/// :param scales: The scales in pixels. Must not be empty and > 0.0.
+/// :param wavelength: The wavelength of the "gabor" kernels as a multiple of
+///     the scale, default = 2.0.
+/// :return: The kernel bank with shape (scales, angles, size, size).
+#[pyfunction]
+#[pyo3(name = "orientation_bank")]
+#[pyo3(signature = (filter, angles, scales, wavelength=None))]
+pub fn kernel_orientation_bank<'py>(
+    py: Python<'py>,
+    filter: &str,
+    angles: Vec<f64>,
+    scales: Vec<f64>,
+    wavelength: Option<f64>,
+) -> PyResult<Bound<'py, PyArray4<f64>>> {
+    let filter = match filter {
+        "gabor" => OrientedFilter::Gabor(wavelength.unwrap_or(2.0)),
+        "edge" => OrientedFilter::Edge,
+        "ridge" => OrientedFilter::Ridge,
+        f => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown oriented filter \"{}\", supported filters are \"gabor\", \"edge\", and \"ridge\".",
+                f
+            )));
+        }
+    };
+    kernel::orientation_bank(filter, &angles, &scales)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
 }
@@ -0,0 +1,45 @@
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray3};
+use pyo3::prelude::*;
+
+use crate::error::map_imgal_error;
+use crate::functions::integration_functions::parse_integration_method;
+use crate::utils::{DLPackArray2, dispatch_dtype};
+use imgal::lifetime;
+
+/// Compute the intensity image of a 3-dimensional decay stack.
+///
+/// This function sums or integrates the decay of each pixel, optionally only
+/// within a time gate, to produce the intensity (e.g. photon count) image. The
+/// integrals are in units of time bins, with the "rectangle" rule the intensity
+/// is the sum of the decay (i.e. the photon count of TCSPC histograms).
+///
+/// :param data: I(t), the decay data image.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :param method: The integration rule, "rectangle", "trapezoid" or "simpson",
+///     default = "rectangle".
+/// :param gate: The (start, end) time bins of the gate, "end" is exclusive. If
+///     "None", the entire decay is used.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are set to 0.0.
+/// :return: The 2-dimensional intensity image.
+#[pyfunction]
+#[pyo3(name = "intensity_image")]
+#[pyo3(signature = (data, axis=None, method=None, gate=None, mask=None))]
+pub fn lifetime_intensity_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    axis: Option<usize>,
+    method: Option<&str>,
+    gate: Option<(usize, usize)>,
+    mask: Option<DLPackArray2<bool>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let method = parse_integration_method(method)?;
+    let msk = mask.as_ref().map(|m| m.as_array());
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        lifetime::intensity_image(arr.as_array(), axis, method, gate, msk)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
@@ -0,0 +1,231 @@
+use std::ffi::CString;
+
+use arrow_array::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
+use arrow_array::ffi_stream::FFI_ArrowArrayStream;
+use arrow_array::{Array, RecordBatch, RecordBatchIterator, StructArray};
+use numpy::ndarray::Ix2;
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyCapsule, PyDict};
+
+use crate::error::map_imgal_error;
+use crate::functions::threshold_functions::extract_labels;
+use crate::utils::{DLPackArray3, dispatch_dtype};
+use imgal::measure::{self, RegionProps};
+
+/// A measurement table in the Apache Arrow format.
+///
+/// The table implements the Arrow PyCapsule interface and can be passed
+/// without copying to any library that supports it, e.g. "pyarrow.table(t)",
+/// "pyarrow.record_batch(t)" or "polars.DataFrame(t)".
+#[pyclass(name = "ArrowTable", module = "imgal.measure", frozen)]
+pub struct ArrowTable {
+    batch: RecordBatch,
+}
+
+impl ArrowTable {
+    pub(crate) fn new(batch: RecordBatch) -> Self {
+        ArrowTable { batch }
+    }
+}
+
+#[pymethods]
+impl ArrowTable {
+    /// The number of rows of the table.
+    #[getter]
+    fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    /// The column names of the table.
+    #[getter]
+    fn column_names(&self) -> Vec<String> {
+        self.batch
+            .schema_ref()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ArrowTable(rows={}, columns={:?})",
+            self.batch.num_rows(),
+            self.column_names()
+        )
+    }
+
+    /// Export the schema of the table as an "arrow_schema" PyCapsule.
+    fn __arrow_c_schema__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyCapsule>> {
+        let schema = FFI_ArrowSchema::try_from(self.batch.schema_ref().as_ref())
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        PyCapsule::new(py, schema, Some(capsule_name("arrow_schema")))
+    }
+
+    /// Export the table as a pair of "arrow_schema" and "arrow_array"
+    /// PyCapsules holding a struct array. The requested schema is ignored.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<(Bound<'py, PyCapsule>, Bound<'py, PyCapsule>)> {
+        let _ = requested_schema;
+        let data = StructArray::from(self.batch.clone()).into_data();
+        let schema = FFI_ArrowSchema::try_from(data.data_type())
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let array = FFI_ArrowArray::new(&data);
+
+        Ok((
+            PyCapsule::new(py, schema, Some(capsule_name("arrow_schema")))?,
+            PyCapsule::new(py, array, Some(capsule_name("arrow_array")))?,
+        ))
+    }
+
+    /// Export the table as an "arrow_array_stream" PyCapsule with a single
+    /// record batch. The requested schema is ignored.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let reader = RecordBatchIterator::new(vec![Ok(self.batch.clone())], self.batch.schema());
+        let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+        PyCapsule::new(py, stream, Some(capsule_name("arrow_array_stream")))
+    }
+}
+
+/// Find the iso-valued contours of a 2-dimensional image with marching
+/// squares.
+///
+/// This function traces the lines where the image crosses the "level" value,
+/// with sub-pixel precision, interpolating linearly along the edges between
+/// pixels. Saddle cells are resolved with the mean of the four corners.
+/// Contours that enclose a region are closed (i.e. the last point repeats the
+/// first point), contours that end at the image border are open. All contours
+/// are oriented with the values above the level on the right-hand side, when
+/// the image is displayed with rows increasing downward (i.e. closed contours
+/// run clockwise around bright objects). Boolean masks are traced as 0 and 1
+/// values.
+///
+/// :param data: The 2-dimensional image or boolean mask.
+/// :param level: The contour value, default = the midpoint of the minimum and
+///     maximum of "data" (i.e. 0.5 for a non-empty mask).
+/// :return: The list of contours, each with shape (points, 2) holding the
+///     (row, col) coordinates of the points along the contour.
+#[pyfunction]
+#[pyo3(name = "find_contours")]
+#[pyo3(signature = (data, level=None))]
+pub fn measure_find_contours<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    level: Option<f64>,
+) -> PyResult<Vec<Bound<'py, PyArray2<f64>>>> {
+    let contours = dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+        measure::find_contours(arr.as_array(), level).map_err(map_imgal_error)?
+    });
+
+    Ok(contours.into_iter().map(|c| c.into_pyarray(py)).collect())
+}
+
+/// Measure the properties of each object of a 2-dimensional label image.
+///
+/// This function measures each object (i.e. labeled region) of a label image,
+/// with the objects ordered by ascending label. Pixels with the label 0 are
+/// background. The properties are "area" (the number of pixels), the
+/// "centroid_row" and "centroid_col", the half-open bounding box
+/// ("bbox_min_row", "bbox_min_col", "bbox_max_row" and "bbox_max_col"), the
+/// crack "perimeter" (the number of pixel edges shared with another label or
+/// the image border) and the "eccentricity" of the ellipse with the same
+/// second central moments:
+///
+/// e = √(1 - λ₂ / λ₁)
+///
+/// where λ₁ >= λ₂ are the eigenvalues of the pixel coordinate covariance
+/// matrix. With an intensity image the "mean_intensity", "min_intensity" and
+/// "max_intensity" are added, and with a phasor image the "mean_g" and
+/// "mean_s" coordinates.
+///
+/// :param labels: The 2-dimensional integer label image.
+/// :param intensity: An optional intensity image. Must match the shape of
+///     "labels".
+/// :param phasor: An optional phasor (G, S) image, where G and S are indexed
+///     at 0 and 1 on the channel axis. Must match the shape of "labels"
+///     without the channel axis.
+/// :param axis: The channel axis of the phasor image, default = 2.
+/// :param arrow: If True, return the table as an "ArrowTable" with one row per
+///     object instead of a dict, default = False.
+/// :return: A dict of numpy arrays with one entry per object, including the
+///     object "label", or an "ArrowTable" with the same columns.
+#[pyfunction]
+#[pyo3(name = "regionprops")]
+#[pyo3(signature = (labels, intensity=None, phasor=None, axis=None, arrow=false))]
+pub fn measure_regionprops<'py>(
+    py: Python<'py>,
+    labels: Bound<'py, PyAny>,
+    intensity: Option<Bound<'py, PyAny>>,
+    phasor: Option<DLPackArray3<f64>>,
+    axis: Option<usize>,
+    arrow: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let lbl = extract_labels(&labels)?
+        .into_dimensionality::<Ix2>()
+        .map_err(|_| PyValueError::new_err("The label image must be 2-dimensional."))?;
+    let ph = phasor.as_ref().map(|p| p.as_array());
+    let props = match intensity {
+        None => measure::regionprops::<u64, f64>(lbl.view(), None, ph, axis),
+        Some(data) => {
+            dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+                measure::regionprops(lbl.view(), Some(arr.as_array()), ph, axis)
+            })
+        }
+    }
+    .map_err(map_imgal_error)?;
+    if arrow {
+        return Ok(Bound::new(py, ArrowTable::new(props.to_record_batch()))?.into_any());
+    }
+
+    Ok(regionprops_to_dict(py, props)?.into_any())
+}
+
+/// Convert a region properties table into a Python dictionary of arrays.
+fn regionprops_to_dict(py: Python, props: RegionProps<u64>) -> PyResult<Bound<PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("label", props.label.into_pyarray(py))?;
+    dict.set_item("area", props.area.into_pyarray(py))?;
+    dict.set_item("centroid_row", props.centroid_row.into_pyarray(py))?;
+    dict.set_item("centroid_col", props.centroid_col.into_pyarray(py))?;
+    dict.set_item("bbox_min_row", props.bbox_min_row.into_pyarray(py))?;
+    dict.set_item("bbox_min_col", props.bbox_min_col.into_pyarray(py))?;
+    dict.set_item("bbox_max_row", props.bbox_max_row.into_pyarray(py))?;
+    dict.set_item("bbox_max_col", props.bbox_max_col.into_pyarray(py))?;
+    dict.set_item("perimeter", props.perimeter.into_pyarray(py))?;
+    dict.set_item("eccentricity", props.eccentricity.into_pyarray(py))?;
+    let optional = [
+        ("mean_intensity", props.mean_intensity),
+        ("min_intensity", props.min_intensity),
+        ("max_intensity", props.max_intensity),
+        ("mean_g", props.mean_g),
+        ("mean_s", props.mean_s),
+    ];
+    for (name, column) in optional {
+        if let Some(values) = column {
+            dict.set_item(name, values.into_pyarray(py))?;
+        }
+    }
+
+    Ok(dict)
+}
+
+/// Create a nul terminated PyCapsule name.
+fn capsule_name(name: &str) -> CString {
+    CString::new(name).expect("The capsule name has no nul bytes.")
+}
@@ -1,11 +1,24 @@
+pub mod anisotropy_functions;
 pub mod colocalization_functions;
+pub mod config_functions;
+pub mod correction_functions;
 pub mod distribution_functions;
+pub mod feature_functions;
 pub mod filter_functions;
 pub mod image_functions;
 pub mod integration_functions;
 pub mod kernel_functions;
+pub mod lifetime_functions;
+pub mod measure_functions;
+pub mod morphology_functions;
+pub mod operator_functions;
 pub mod parameter_functions;
 pub mod phasor_functions;
+pub mod register_functions;
+pub mod roi_functions;
 pub mod simulation_functions;
 pub mod statistics_functions;
 pub mod threshold_functions;
+pub mod transform_functions;
+pub mod unmix_functions;
+pub mod version_functions;
@@ -0,0 +1,239 @@
+use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::error::map_imgal_error;
+use crate::utils::{DLPackArrayDyn, dispatch_dtype};
+use imgal::morphology::{self, Connectivity};
+
+/// Find the endpoints and branch points of a skeleton and measure its length.
+///
+/// This function analyzes a 2 or 3-dimensional skeleton (e.g. the output of
+/// "skeletonize") with full (8 or 26) connectivity. Endpoints have a single
+/// skeleton neighbor and branch points have three or more. The length is the
+/// sum of the distances between neighboring skeleton pixels, scaled by the
+/// pixel spacing, where a diagonal step is not counted if the two pixels are
+/// also joined through a common neighbor with shorter steps.
+///
+/// :param skeleton: The 2 or 3-dimensional skeleton.
+/// :param spacing: The pixel spacing per axis, default = 1.0 for every axis.
+///     Each spacing must be > 0.0.
+/// :return: A dict with the "endpoints" and "branch_points" coordinates, each
+///     with shape (points, ndim), and the total "length".
+#[pyfunction]
+#[pyo3(name = "analyze_skeleton")]
+#[pyo3(signature = (skeleton, spacing=None))]
+pub fn morphology_analyze_skeleton<'py>(
+    py: Python<'py>,
+    skeleton: DLPackArrayDyn<bool>,
+    spacing: Option<Vec<f64>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let analysis = morphology::analyze_skeleton(skeleton.as_array(), spacing.as_deref())
+        .map_err(map_imgal_error)?;
+    let dict = PyDict::new(py);
+    dict.set_item("endpoints", analysis.endpoints.into_pyarray(py))?;
+    dict.set_item("branch_points", analysis.branch_points.into_pyarray(py))?;
+    dict.set_item("length", analysis.length)?;
+
+    Ok(dict)
+}
+
+/// Close an n-dimensional image with a flat box structuring element.
+///
+/// This function computes the grayscale closing of the image, the erosion of
+/// the dilation. The closing fills dark structures that do not fit in the box
+/// and is never below the image.
+///
+/// :param data: The input n-dimensional image.
+/// :param radius: The box radius along each axis, in pixels, the box has a
+///     side length of 2 × radius + 1. Must have one value per axis. A radius
+///     of 0 does not filter that axis.
+/// :return: The closed image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "closing")]
+pub fn morphology_closing<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Vec<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        morphology::closing(arr.as_array(), &radius)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Dilate an n-dimensional image with a flat box structuring element.
+///
+/// This function computes the grayscale dilation of the image, the maximum of
+/// the pixel values inside the box centered on each pixel. Pixels past the
+/// image border are ignored. Each 1-dimensional pass uses the van
+/// Herk/Gil-Werman algorithm, with a cost that does not depend on the box
+/// size.
+///
+/// :param data: The input n-dimensional image.
+/// :param radius: The box radius along each axis, in pixels, the box has a
+///     side length of 2 × radius + 1. Must have one value per axis. A radius
+///     of 0 does not filter that axis.
+/// :return: The dilated image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "dilation")]
+pub fn morphology_dilation<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Vec<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        morphology::dilation(arr.as_array(), &radius)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Erode an n-dimensional image with a flat box structuring element.
+///
+/// This function computes the grayscale erosion of the image, the minimum of
+/// the pixel values inside the box centered on each pixel. Pixels past the
+/// image border are ignored. Each 1-dimensional pass uses the van
+/// Herk/Gil-Werman algorithm, with a cost that does not depend on the box
+/// size.
+///
+/// :param data: The input n-dimensional image.
+/// :param radius: The box radius along each axis, in pixels, the box has a
+///     side length of 2 × radius + 1. Must have one value per axis. A radius
+///     of 0 does not filter that axis.
+/// :return: The eroded image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "erosion")]
+pub fn morphology_erosion<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Vec<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        morphology::erosion(arr.as_array(), &radius)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the morphological gradient of an n-dimensional image.
+///
+/// This function computes the morphological (Beucher) gradient of the image,
+/// the dilation minus the erosion with a flat box structuring element, which
+/// is large at the edges of objects and 0.0 in flat regions.
+///
+/// :param data: The input n-dimensional image.
+/// :param radius: The box radius along each axis, in pixels, the box has a
+///     side length of 2 × radius + 1. Must have one value per axis. A radius
+///     of 0 does not filter that axis.
+/// :return: The gradient image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "gradient")]
+pub fn morphology_gradient<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Vec<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        morphology::gradient(arr.as_array(), &radius)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Label the connected components of an n-dimensional boolean mask.
+///
+/// This function assigns a unique label to each connected component (i.e.
+/// object) of the "True" pixels of a boolean mask with a union-find (disjoint
+/// set) implementation. The labels are numbered consecutively from 1 in the
+/// row-major order of the first pixel of each object, background pixels are
+/// set to 0. In parallel, blocks of the mask along the first axis are labeled
+/// independently and merged afterwards, with the same output.
+///
+/// :param mask: An n-dimensional boolean mask, with at least 1 dimension.
+/// :param connectivity: The pixel connectivity, "face" (i.e. 4-connectivity
+///     in 2D and 6-connectivity in 3D) or "full" (i.e. 8-connectivity in 2D
+///     and 26-connectivity in 3D), default = "full".
+/// :param parallel: If "True", label blocks of the mask in parallel,
+///     default = True.
+/// :return: The u32 label image with the same shape as "mask".
+#[pyfunction]
+#[pyo3(name = "label")]
+#[pyo3(signature = (mask, connectivity=None, parallel=None))]
+pub fn morphology_label<'py>(
+    py: Python<'py>,
+    mask: DLPackArrayDyn<bool>,
+    connectivity: Option<&str>,
+    parallel: Option<bool>,
+) -> PyResult<Bound<'py, PyArrayDyn<u32>>> {
+    let connectivity = parse_connectivity(connectivity)?;
+    morphology::label(mask.as_array(), Some(connectivity), parallel)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Open an n-dimensional image with a flat box structuring element.
+///
+/// This function computes the grayscale opening of the image, the dilation of
+/// the erosion. The opening removes bright structures that do not fit in the
+/// box and never exceeds the image.
+///
+/// :param data: The input n-dimensional image.
+/// :param radius: The box radius along each axis, in pixels, the box has a
+///     side length of 2 × radius + 1. Must have one value per axis. A radius
+///     of 0 does not filter that axis.
+/// :return: The opened image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "opening")]
+pub fn morphology_opening<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    radius: Vec<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        morphology::opening(arr.as_array(), &radius)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Skeletonize a 2 or 3-dimensional boolean mask.
+///
+/// This function thins the "True" objects of a mask to their 1 pixel wide
+/// medial lines (i.e. the curve skeleton) while preserving their topology.
+/// Each iteration peels the border pixels facing each of the 2n axis
+/// directions in turn, a border pixel is removed if it is simple (i.e. its
+/// removal does not change the topology of its neighborhood) and not an
+/// endpoint. The thinning stops when no pixel is removed.
+///
+/// :param mask: The 2 or 3-dimensional boolean mask.
+/// :return: The skeleton with the same shape as "mask".
+#[pyfunction]
+#[pyo3(name = "skeletonize")]
+pub fn morphology_skeletonize<'py>(
+    py: Python<'py>,
+    mask: DLPackArrayDyn<bool>,
+) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
+    morphology::skeletonize(mask.as_array())
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Parse a pixel connectivity.
+fn parse_connectivity(connectivity: Option<&str>) -> PyResult<Connectivity> {
+    match connectivity {
+        None | Some("full") => Ok(Connectivity::Full),
+        Some("face") => Ok(Connectivity::Face),
+        Some(c) => Err(PyValueError::new_err(format!(
+            "Unknown connectivity \"{}\", supported connectivities are \"face\" and \"full\".",
+            c
+        ))),
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::error::{map_imgal_error, with_warnings};
+use crate::utils::{DLPackArrayDyn, dispatch_dtype};
+use imgal::operator::{self, LaneOp, PixelOp};
+
+/// Apply a registered pixel operator to each pixel of an n-dimensional image.
+///
+/// This function looks up the pixel operator by name, either a built-in
+/// operator or a custom operator registered by a Rust crate linked into this
+/// module, and computes the output value of each pixel in parallel.
+///
+/// :param name: The name of the pixel operator, see "pixel_op_names".
+/// :param data: The n-dimensional input image.
+/// :param mask: An optional boolean mask, only pixels set to "True" are
+///     computed and all other output pixels are set to 0.0. Must match the
+///     shape of "data".
+/// :return: The output image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "pixel")]
+#[pyo3(signature = (name, data, mask=None))]
+pub fn operator_pixel<'py>(
+    py: Python<'py>,
+    name: &str,
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let op = find_pixel_op(name)?;
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        with_warnings(py, || operator::pixel(arr.as_array(), op.as_ref(), msk))?
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Apply a registered lane operator to each lane of an n-dimensional image.
+///
+/// This function looks up the lane operator by name, either a built-in
+/// operator or a custom operator registered by a Rust crate linked into this
+/// module, and computes the output values of each lane (i.e. the 1-dimensional
+/// slice along "axis", e.g. the decay curve of a pixel) in parallel.
+///
+/// :param name: The name of the lane operator, see "lane_op_names".
+/// :param data: The n-dimensional input image.
+/// :param mask: An optional boolean mask with one value per lane, only lanes
+///     set to "True" are computed and the outputs of all other lanes are set to
+///     0.0. Must match the shape of "data" without "axis".
+/// :param axis: The lane axis, default = the last axis.
+/// :return: The output image with the output values of each lane along "axis".
+#[pyfunction]
+#[pyo3(name = "lane")]
+#[pyo3(signature = (name, data, mask=None, axis=None))]
+pub fn operator_lane<'py>(
+    py: Python<'py>,
+    name: &str,
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let op = find_lane_op(name)?;
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        with_warnings(py, || {
+            operator::lane(arr.as_array(), op.as_ref(), msk, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// List the names of the available pixel operators.
+///
+/// :return: The sorted names of the built-in and registered pixel operators.
+#[pyfunction]
+#[pyo3(name = "pixel_op_names")]
+pub fn operator_pixel_op_names() -> Vec<String> {
+    operator::pixel_op_names()
+}
+
+/// List the names of the available lane operators.
+///
+/// :return: The sorted names of the built-in and registered lane operators.
+#[pyfunction]
+#[pyo3(name = "lane_op_names")]
+pub fn operator_lane_op_names() -> Vec<String> {
+    operator::lane_op_names()
+}
+
+/// Look up a pixel operator by name.
+fn find_pixel_op(name: &str) -> PyResult<Arc<dyn PixelOp>> {
+    operator::pixel_op(name).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "Unknown pixel operator \"{}\", available operators are {:?}.",
+            name,
+            operator::pixel_op_names()
+        ))
+    })
+}
+
+/// Look up a lane operator by name.
+fn find_lane_op(name: &str) -> PyResult<Arc<dyn LaneOp>> {
+    operator::lane_op(name).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "Unknown lane operator \"{}\", available operators are {:?}.",
+            name,
+            operator::lane_op_names()
+        ))
+    })
+}
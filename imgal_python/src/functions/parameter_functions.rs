@@ -19,6 +19,108 @@ pub fn parameter_abbe_diffraction_limit(wavelength: f64, na: f64) -> f64 {
     parameter::abbe_diffraction_limit(wavelength, na)
 }
 
+/// Compute the Airy disk radius.
+///
+/// Compute the radius of the Airy disk, the distance from the center of the
+/// point spread function to its first minimum, using:
+///
+/// r = 1.22 * wavelength * M / 2 * NA
+///
+/// Where "NA" is the numerical aperture of the objective and "M" is the total
+/// magnification. With a magnification of 1.0 the radius is in the sample
+/// plane, otherwise the radius is in the image plane.
+///
+/// :param wavelength: The wavelength of light.
+/// :param na: The numerical aperture.
+/// :param magnification: The total magnification, default = 1.0.
+/// :return: The Airy disk radius.
+#[pyfunction]
+#[pyo3(name = "airy_disk_radius")]
+#[pyo3(signature = (wavelength, na, magnification=None))]
+pub fn parameter_airy_disk_radius(wavelength: f64, na: f64, magnification: Option<f64>) -> f64 {
+    parameter::airy_disk_radius(wavelength, na, magnification)
+}
+
+/// Compute the axial (z) diffraction limit.
+///
+/// Compute the axial resolution of a widefield microscope using:
+///
+/// d = 2 * wavelength * n / NA²
+///
+/// Where "NA" is the numerical aperture of the objective and "n" is the
+/// refractive index of the immersion medium.
+///
+/// :param wavelength: The wavelength of light.
+/// :param na: The numerical aperture.
+/// :param refractive_index: The refractive index of the immersion medium.
+/// :return: The axial diffraction limit.
+#[pyfunction]
+#[pyo3(name = "axial_diffraction_limit")]
+pub fn parameter_axial_diffraction_limit(wavelength: f64, na: f64, refractive_index: f64) -> f64 {
+    parameter::axial_diffraction_limit(wavelength, na, refractive_index)
+}
+
+/// Compute the expected lifetime precision for a photon count.
+///
+/// Compute the expected standard deviation of a fluorescence lifetime
+/// estimate from the number of detected photons using:
+///
+/// σ_τ = F * τ / √N
+///
+/// Where "N" is the photon count and "F" is the F-value (figure of merit) of
+/// the instrument and analysis. An ideal measurement has F = 1.0.
+///
+/// :param photon_count: N, the number of detected photons.
+/// :param tau: The lifetime.
+/// :param f_value: The F-value, default = 1.0.
+/// :return: The standard deviation of the lifetime, in the units of "tau".
+#[pyfunction]
+#[pyo3(name = "lifetime_precision")]
+#[pyo3(signature = (photon_count, tau, f_value=None))]
+pub fn parameter_lifetime_precision(photon_count: f64, tau: f64, f_value: Option<f64>) -> f64 {
+    parameter::lifetime_precision(photon_count, tau, f_value)
+}
+
+/// Compute the Nyquist axial step size.
+///
+/// Compute the largest z-step size that samples a widefield point spread
+/// function at the Nyquist rate using:
+///
+/// Δz = wavelength / 2 * n * (1 - cos(α))
+///
+/// Where "α = asin(NA / n)" is the half aperture angle of the objective. The
+/// numerical aperture must not exceed the refractive index, otherwise the step
+/// size is NaN.
+///
+/// :param wavelength: The emission wavelength of light.
+/// :param na: The numerical aperture.
+/// :param refractive_index: The refractive index of the immersion medium.
+/// :return: The Nyquist axial step size.
+#[pyfunction]
+#[pyo3(name = "nyquist_axial_step")]
+pub fn parameter_nyquist_axial_step(wavelength: f64, na: f64, refractive_index: f64) -> f64 {
+    parameter::nyquist_axial_step(wavelength, na, refractive_index)
+}
+
+/// Compute the Nyquist pixel size.
+///
+/// Compute the largest pixel size, in the sample plane, that samples the
+/// lateral cutoff frequency of a widefield microscope at the Nyquist rate
+/// using:
+///
+/// Δx = wavelength / 4 * NA
+///
+/// Where "NA" is the numerical aperture of the objective.
+///
+/// :param wavelength: The emission wavelength of light.
+/// :param na: The numerical aperture.
+/// :return: The Nyquist pixel size.
+#[pyfunction]
+#[pyo3(name = "nyquist_pixel_size")]
+pub fn parameter_nyquist_pixel_size(wavelength: f64, na: f64) -> f64 {
+    parameter::nyquist_pixel_size(wavelength, na)
+}
+
 /// Compute the angular frequency (omega) value.
 ///
 /// Compute the angular frequency, omega (ω), using the following equation:
@@ -35,3 +137,88 @@ pub fn parameter_omega(period: Bound<PyAny>) -> PyResult<f64> {
     let p: f64 = period.extract()?;
     Ok(parameter::omega(p))
 }
+
+/// Compute the expected phasor coordinate precision for a photon count.
+///
+/// Compute the expected shot noise standard deviations of the G and S phasor
+/// coordinates of a monoexponential decay measured with N photons using:
+///
+/// σ²_G = ((1 + G₂ₙ) / 2 - Gₙ²) / N
+/// σ²_S = ((1 - G₂ₙ) / 2 - Sₙ²) / N
+///
+/// Where "(Gₙ, Sₙ)" are the phasor coordinates at harmonic n and "G₂ₙ" is the G
+/// coordinate at harmonic 2n.
+///
+/// :param photon_count: N, the number of detected photons.
+/// :param tau: The lifetime of the monoexponential decay.
+/// :param period: The period, in the same units as "tau".
+/// :param harmonic: The harmonic value, default = 1.0.
+/// :return: The standard deviations of the (G, S) phasor coordinates.
+#[pyfunction]
+#[pyo3(name = "phasor_precision")]
+#[pyo3(signature = (photon_count, tau, period, harmonic=None))]
+pub fn parameter_phasor_precision(
+    photon_count: f64,
+    tau: f64,
+    period: f64,
+    harmonic: Option<f64>,
+) -> (f64, f64) {
+    parameter::phasor_precision(photon_count, tau, period, harmonic)
+}
+
+/// Compute the photon count needed to reach a lifetime precision.
+///
+/// Compute the photon budget needed to estimate a lifetime with a given
+/// relative precision (σ_τ / τ) using:
+///
+/// N = (F / (σ_τ / τ))²
+///
+/// :param relative_precision: The target relative standard deviation of the
+///     lifetime, e.g. 0.05 for 5%.
+/// :param f_value: The F-value, default = 1.0.
+/// :return: The number of photons needed.
+#[pyfunction]
+#[pyo3(name = "photon_budget")]
+#[pyo3(signature = (relative_precision, f_value=None))]
+pub fn parameter_photon_budget(relative_precision: f64, f_value: Option<f64>) -> f64 {
+    parameter::photon_budget(relative_precision, f_value)
+}
+
+/// Compute the Rayleigh criterion.
+///
+/// Compute Lord Rayleigh's criterion, the smallest distance at which two point
+/// sources can be resolved, using:
+///
+/// d = 0.61 * wavelength / NA
+///
+/// Where "NA" is the numerical aperture of the objective.
+///
+/// :param wavelength: The wavelength of light.
+/// :param na: The numerical aperture.
+/// :return: Rayleigh's criterion.
+#[pyfunction]
+#[pyo3(name = "rayleigh_criterion")]
+pub fn parameter_rayleigh_criterion(wavelength: f64, na: f64) -> f64 {
+    parameter::rayleigh_criterion(wavelength, na)
+}
+
+/// Compute the signal-to-noise ratio of a pixel.
+///
+/// Compute the shot noise limited signal-to-noise ratio (SNR) using:
+///
+/// SNR = S / √(S + B + σ²)
+///
+/// Where "S" is the signal, "B" is the background and "σ" is the read noise,
+/// all in photons.
+///
+/// :param signal: S, the signal in photons.
+/// :param background: B, the background in photons, default = 0.0.
+/// :param read_noise: σ, the root mean square read noise in photons,
+///     default = 0.0.
+/// :return: The signal-to-noise ratio.
+#[pyfunction]
+#[pyo3(name = "snr")]
+#[pyo3(signature = (signal, background=None, read_noise=None))]
+pub fn parameter_snr(signal: f64, background: Option<f64>, read_noise: Option<f64>) -> f64 {
+    parameter::snr(signal, background, read_noise)
+}
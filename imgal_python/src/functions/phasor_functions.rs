@@ -1,11 +1,209 @@
 use numpy::{
     IntoPyArray, PyArray2, PyArray3, PyReadonlyArray2, PyReadonlyArray3, PyReadwriteArray3,
 };
-use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::{PyIndexError, PyValueError};
 use pyo3::prelude::*;
 
-use crate::error::map_array_error;
-use imgal::phasor::{calibration, plot, time_domain};
+use crate::error::{map_imgal_error, with_warnings};
+use crate::utils::{DLPackArray2, DLPackArray3, dispatch_dtype, dispatch_dtype_mut};
+use imgal::phasor::analysis::{self, PhasorCursor, Stage};
+use imgal::phasor::{calibration, frequency_domain, metabolic, plot, spectral, time_domain};
+
+/// An incremental time domain phasor analysis.
+///
+/// The analysis holds a 3-dimensional decay image and caches each stage of the
+/// phasor workflow: "intensity", "phasor", "mask", "calibration" and
+/// "selection". Changing a single parameter (e.g. the threshold, calibration
+/// or cursor) only invalidates the stages that depend on it, the next stage
+/// request recomputes only those stages.
+///
+/// :param data: I(t), the decay data image.
+/// :param period: The period (i.e. time interval).
+/// :param harmonic: The harmonic value, default = 1.0.
+/// :param axis: The decay or lifetime axis, default = 2.
+#[pyclass(name = "PhasorAnalysis", module = "imgal.phasor.analysis")]
+pub struct PhasorAnalysis {
+    inner: analysis::PhasorAnalysis,
+}
+
+#[pymethods]
+impl PhasorAnalysis {
+    #[new]
+    #[pyo3(signature = (data, period, harmonic=None, axis=None))]
+    fn new(
+        data: Bound<'_, PyAny>,
+        period: f64,
+        harmonic: Option<f64>,
+        axis: Option<usize>,
+    ) -> PyResult<Self> {
+        // pattern match and extract allowed array types
+        let inner = dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+            analysis::PhasorAnalysis::new(arr.as_array(), period, harmonic, axis)
+        })
+        .map_err(map_imgal_error)?;
+
+        Ok(PhasorAnalysis { inner })
+    }
+
+    /// Set the period, invalidates the "phasor" stage and the stages after it.
+    ///
+    /// :param period: The period (i.e. time interval).
+    fn set_period(&mut self, period: f64) -> PyResult<()> {
+        self.inner.set_period(period).map_err(map_imgal_error)
+    }
+
+    /// Set the harmonic, invalidates the "phasor" stage and the stages after it.
+    ///
+    /// :param harmonic: The harmonic value.
+    fn set_harmonic(&mut self, harmonic: f64) -> PyResult<()> {
+        self.inner.set_harmonic(harmonic).map_err(map_imgal_error)
+    }
+
+    /// Set the intensity threshold, invalidates the "mask" stage and the
+    /// stages after it.
+    ///
+    /// :param threshold: Pixels with an integrated intensity greater than the
+    ///     threshold are analyzed.
+    fn set_threshold(&mut self, threshold: f64) -> PyResult<()> {
+        self.inner.set_threshold(threshold).map_err(map_imgal_error)
+    }
+
+    /// Set the calibration, invalidates the "calibration" stage and the
+    /// stages after it.
+    ///
+    /// :param modulation: The modulation to scale the (G, S) coordinates. If
+    ///     None, the phasor image is not calibrated.
+    /// :param phase: The phase, φ angle, to rotate the (G, S) coordinates.
+    #[pyo3(signature = (modulation=None, phase=None))]
+    fn set_calibration(&mut self, modulation: Option<f64>, phase: Option<f64>) -> PyResult<()> {
+        let calibration = match (modulation, phase) {
+            (Some(m), Some(p)) => Some((m, p)),
+            (None, None) => None,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "Both modulation and phase must be set to calibrate.",
+                ));
+            }
+        };
+        self.inner
+            .set_calibration(calibration)
+            .map_err(map_imgal_error)
+    }
+
+    /// Set the circular phasor cursor, invalidates the "selection" stage.
+    ///
+    /// :param g: The real component, G, of the cursor center. If None, the
+    ///     cursor is removed.
+    /// :param s: The imaginary component, S, of the cursor center.
+    /// :param radius: The cursor radius.
+    #[pyo3(signature = (g=None, s=None, radius=None))]
+    fn set_cursor(&mut self, g: Option<f64>, s: Option<f64>, radius: Option<f64>) -> PyResult<()> {
+        let cursor = match (g, s, radius) {
+            (Some(g), Some(s), Some(radius)) => Some(PhasorCursor { g, s, radius }),
+            (None, None, None) => None,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "The cursor g, s and radius must all be set.",
+                ));
+            }
+        };
+        self.inner.set_cursor(cursor).map_err(map_imgal_error)
+    }
+
+    /// Whether a stage is cached and will not be recomputed on request.
+    ///
+    /// :param stage: The stage name, "intensity", "phasor", "mask",
+    ///     "calibration" or "selection".
+    /// :return: True if the stage is cached.
+    fn is_cached(&self, stage: &str) -> PyResult<bool> {
+        let st = match stage {
+            "intensity" => Stage::Intensity,
+            "phasor" => Stage::Phasor,
+            "mask" => Stage::Mask,
+            "calibration" => Stage::Calibration,
+            "selection" => Stage::Selection,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown stage \"{}\", supported stages are intensity, phasor, mask, calibration, and selection.",
+                    stage
+                )));
+            }
+        };
+
+        Ok(self.inner.is_cached(st))
+    }
+
+    /// The 2-dimensional integrated decay intensity image.
+    fn intensity<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.inner.intensity().clone().into_pyarray(py)
+    }
+
+    /// The uncalibrated (G, S) image as a 3D (row, col, ch) image.
+    fn phasor<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyArray3<f64>> {
+        self.inner.phasor().clone().into_pyarray(py)
+    }
+
+    /// The 2-dimensional intensity threshold mask.
+    fn mask<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyArray2<bool>> {
+        self.inner.mask().clone().into_pyarray(py)
+    }
+
+    /// The calibrated and masked (G, S) image as a 3D (row, col, ch) image.
+    fn calibrated<'py>(&mut self, py: Python<'py>) -> Bound<'py, PyArray3<f64>> {
+        self.inner.calibrated().clone().into_pyarray(py)
+    }
+
+    /// The 2-dimensional phasor cursor selection mask, None if no cursor is
+    /// set.
+    fn selection<'py>(&mut self, py: Python<'py>) -> Option<Bound<'py, PyArray2<bool>>> {
+        self.inner.selection().map(|s| s.clone().into_pyarray(py))
+    }
+}
+
+/// The modulation and phase calibration values.
+///
+/// The result unpacks like the (M, φ) tuple, e.g.
+/// "modulation, phase = modulation_and_phase(...)".
+#[pyclass(name = "CalibrationResult", module = "imgal.phasor.calibration")]
+#[derive(Clone, Copy)]
+pub struct CalibrationResult {
+    /// The modulation, M, to scale the (G, S) coordinates.
+    #[pyo3(get)]
+    modulation: f64,
+    /// The phase, φ angle, to rotate the (G, S) coordinates.
+    #[pyo3(get)]
+    phase: f64,
+}
+
+#[pymethods]
+impl CalibrationResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "CalibrationResult(modulation={}, phase={})",
+            self.modulation, self.phase
+        )
+    }
+
+    fn __len__(&self) -> usize {
+        2
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<f64> {
+        match index {
+            0 | -2 => Ok(self.modulation),
+            1 | -1 => Ok(self.phase),
+            _ => Err(PyIndexError::new_err(
+                "CalibrationResult index out of range.",
+            )),
+        }
+    }
+}
+
+impl From<(f64, f64)> for CalibrationResult {
+    fn from((modulation, phase): (f64, f64)) -> Self {
+        CalibrationResult { modulation, phase }
+    }
+}
 
 /// Calibrate a real and imaginary (G, S) coordinates.
 ///
@@ -53,37 +251,31 @@ pub fn calibration_coordinates(g: f64, s: f64, modulation: f64, phase: f64) -> (
 ///     and 1 respectively.
 /// :param modulation: The modulation to scale the input (G, S) coordinates.
 /// :param phase: The phase, φ angle, to rotate the input (G, S) coordinates.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
 /// :param axis: The channel axis, default = 2.
 /// :return: A 3-dimensional array with the calibrated phasor values, where
 ///     calibrated G and S are channels 0 and 1 respectively.
 #[pyfunction]
 #[pyo3(name = "image")]
-#[pyo3(signature = (data, modulation, phase, axis=None))]
+#[pyo3(signature = (data, modulation, phase, mask=None, axis=None))]
 pub fn calibration_image<'py>(
     py: Python<'py>,
     data: Bound<'py, PyAny>,
     modulation: f64,
     phase: f64,
+    mask: Option<DLPackArray2<bool>>,
     axis: Option<usize>,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u8>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<u16>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f32>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f64>>() {
-        let output = calibration::image(arr.as_array(), modulation, phase, axis);
-        return Ok(output.into_pyarray(py));
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        with_warnings(py, || {
+            calibration::image(arr.as_array(), modulation, phase, msk, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
 }
 
 /// Calibrate the real and imaginary (G, S) coordinates of a 3-dimensional phasor
@@ -98,25 +290,35 @@ pub fn calibration_image<'py>(
 /// S' = G * s + S * g
 ///
 /// Where G' and S' are the calibrated real and imaginary values after rotation
-/// and scaling. This function mutates the input data and does not create a new
-/// array.
+/// and scaling. The image keeps its float32 or float64 dtype. This function
+/// mutates the input data and does not create a new array.
 ///
 /// :param data: The 3-dimensional phasor image, where G and S are channels 0 and 1
 ///     respectively.
 /// :param modulation: The modulation to scale the input (G, S) coordinates.
 /// :param phase: The phase, φ angle, to rotate the intput (G, S) coorindates.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and left unchanged.
 /// :param axis: The channel axis, default = 2.
 #[pyfunction]
 #[pyo3(name = "image_mut")]
-#[pyo3(signature = (data, modulation, phase, axis=None))]
+#[pyo3(signature = (data, modulation, phase, mask=None, axis=None))]
 pub fn calibration_image_mut(
-    mut data: PyReadwriteArray3<f64>,
+    py: Python,
+    data: Bound<PyAny>,
     modulation: f64,
     phase: f64,
+    mask: Option<DLPackArray2<bool>>,
     axis: Option<usize>,
-) {
-    let arr = data.as_array_mut();
-    calibration::image_mut(arr, modulation, phase, axis);
+) -> PyResult<()> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    // pattern match and extract allowed array types
+    dispatch_dtype_mut!(data, PyReadwriteArray3::<T>, [f32, f64], |mut arr| {
+        with_warnings(py, || {
+            calibration::image_mut(arr.as_array_mut(), modulation, phase, msk, axis)
+        })?
+        .map_err(map_imgal_error)
+    })
 }
 
 /// Find the modulation and phase calibration values.
@@ -131,11 +333,194 @@ pub fn calibration_image_mut(
 /// :param tau: The lifetime, τ.
 /// :param omega: The angular frequency, ω.
 /// :param axis: The channel axis, default = 2.
-/// :return: The modulation and phase calibration values, (M, φ).
+/// :return: The modulation and phase calibration values, (M, φ), as a
+///     "CalibrationResult".
 #[pyfunction]
 #[pyo3(name = "modulation_and_phase")]
-pub fn calibration_modulation_and_phase(g: f64, s: f64, tau: f64, omega: f64) -> (f64, f64) {
-    calibration::modulation_and_phase(g, s, tau, omega)
+pub fn calibration_modulation_and_phase(g: f64, s: f64, tau: f64, omega: f64) -> CalibrationResult {
+    calibration::modulation_and_phase(g, s, tau, omega).into()
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 1-dimensional set of
+/// homodyne phase samples.
+///
+/// Gated or modulated cameras sample the emission at k phase offsets (φₖ) of
+/// the excitation modulation. Each sample follows the homodyne equation:
+///
+/// I(φₖ) = DC + DC × G × cos(φₖ) + DC × S × sin(φₖ)
+///
+/// The camera bias is subtracted from each sample and DC, G and S are solved
+/// by linear least squares, which reduces to the discrete Fourier transform
+/// for evenly spaced phases.
+///
+/// :param data: I(φ), the phase samples. At least 3 samples are required.
+/// :param phases: The phase offset, in radians, of each sample. If "None", the
+///     samples are assumed to be evenly spaced over one modulation period.
+/// :param bias: The camera bias (i.e. dark offset) subtracted from each sample,
+///     default = 0.0.
+/// :return: The real and imaginary coordinates, (G, S).
+#[pyfunction]
+#[pyo3(name = "coordinates")]
+#[pyo3(signature = (data, phases=None, bias=None))]
+pub fn frequency_domain_coordinates(
+    data: Vec<f64>,
+    phases: Option<Vec<f64>>,
+    bias: Option<f64>,
+) -> PyResult<(f64, f64)> {
+    frequency_domain::coordinates(&data, phases.as_deref(), bias).map_err(map_imgal_error)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional
+/// homodyne phase image.
+///
+/// This function computes the phasor coordinates of each pixel of a stack of
+/// phase images acquired by a gated or modulated camera, see "coordinates" for
+/// the homodyne equations.
+///
+/// :param data: I(φ), the phase image stack. At least 3 phase images are
+///     required.
+/// :param phases: The phase offset, in radians, of each phase image. If "None",
+///     the phase images are assumed to be evenly spaced over one modulation
+///     period.
+/// :param bias: The camera bias (i.e. dark offset) subtracted from each sample,
+///     default = 0.0.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
+/// :param axis: The phase axis, default = 2.
+/// :return: The real and imaginary coordinates as a 3-dimensional (row, col, ch)
+///     image, where G and S are indexed at 0 and 1 respectively on the channel axis.
+#[pyfunction]
+#[pyo3(name = "image")]
+#[pyo3(signature = (data, phases=None, bias=None, mask=None, axis=None))]
+pub fn frequency_domain_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    phases: Option<Vec<f64>>,
+    bias: Option<f64>,
+    mask: Option<DLPackArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let ph = phases.as_deref();
+    let msk = mask.as_ref().map(|m| m.as_array());
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        with_warnings(py, || {
+            frequency_domain::image(arr.as_array(), ph, bias, msk, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the bound fraction of a two component (e.g. free and bound NADH)
+/// phasor coordinate.
+///
+/// Phasor coordinates are linear in the fractional intensities of their
+/// components, so a mixture of free and bound species lies on the line between
+/// the free (F) and bound (B) reference coordinates. The phasor coordinate (P)
+/// is projected onto that line and clamped to [0, 1]:
+///
+/// f_bound = ((P - F) · (B - F)) / |B - F|²
+///
+/// :param g: The real component, G, of a calibrated phasor coordinate.
+/// :param s: The imaginary component, S, of a calibrated phasor coordinate.
+/// :param free: The (G, S) coordinates of the free species.
+/// :param bound: The (G, S) coordinates of the bound species.
+/// :return: The fractional intensity of the bound species.
+#[pyfunction]
+#[pyo3(name = "bound_fraction")]
+pub fn metabolic_bound_fraction(
+    g: f64,
+    s: f64,
+    free: (f64, f64),
+    bound: (f64, f64),
+) -> PyResult<f64> {
+    metabolic::bound_fraction(g, s, free, bound).map_err(map_imgal_error)
+}
+
+/// Compute a bound fraction image from a 3-dimensional phasor image.
+///
+/// This function computes the bound fraction of each pixel of a calibrated
+/// phasor (G, S) image, see "bound_fraction".
+///
+/// :param data: The G/S 3-dimensional array.
+/// :param free: The (G, S) coordinates of the free species.
+/// :param bound: The (G, S) coordinates of the bound species.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
+/// :param axis: The channel axis, default = 2.
+/// :return: The 2-dimensional bound fraction image.
+#[pyfunction]
+#[pyo3(name = "bound_fraction_image")]
+#[pyo3(signature = (data, free, bound, mask=None, axis=None))]
+pub fn metabolic_bound_fraction_image<'py>(
+    py: Python<'py>,
+    data: DLPackArray3<f64>,
+    free: (f64, f64),
+    bound: (f64, f64),
+    mask: Option<DLPackArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    with_warnings(py, || {
+        metabolic::bound_fraction_image(data.as_array(), free, bound, msk, axis)
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Compute the metabolic index of a two component phasor coordinate.
+///
+/// The metabolic index is the ratio of the free to bound fractional
+/// intensities:
+///
+/// MI = f_free / f_bound = (1 - f_bound) / f_bound
+///
+/// :param g: The real component, G, of a calibrated phasor coordinate.
+/// :param s: The imaginary component, S, of a calibrated phasor coordinate.
+/// :param free: The (G, S) coordinates of the free species.
+/// :param bound: The (G, S) coordinates of the bound species.
+/// :return: The metabolic index.
+#[pyfunction]
+#[pyo3(name = "metabolic_index")]
+pub fn metabolic_metabolic_index(
+    g: f64,
+    s: f64,
+    free: (f64, f64),
+    bound: (f64, f64),
+) -> PyResult<f64> {
+    metabolic::metabolic_index(g, s, free, bound).map_err(map_imgal_error)
+}
+
+/// Compute a metabolic index image from a 3-dimensional phasor image.
+///
+/// This function computes the metabolic index of each pixel of a calibrated
+/// phasor (G, S) image, see "metabolic_index".
+///
+/// :param data: The G/S 3-dimensional array.
+/// :param free: The (G, S) coordinates of the free species.
+/// :param bound: The (G, S) coordinates of the bound species.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
+/// :param axis: The channel axis, default = 2.
+/// :return: The 2-dimensional metabolic index image.
+#[pyfunction]
+#[pyo3(name = "metabolic_index_image")]
+#[pyo3(signature = (data, free, bound, mask=None, axis=None))]
+pub fn metabolic_metabolic_index_image<'py>(
+    py: Python<'py>,
+    data: DLPackArray3<f64>,
+    free: (f64, f64),
+    bound: (f64, f64),
+    mask: Option<DLPackArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    with_warnings(py, || {
+        metabolic::metabolic_index_image(data.as_array(), free, bound, msk, axis)
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
 }
 
 /// Compute the modulation of phasor G and S coordinates.
@@ -201,22 +586,225 @@ pub fn plot_monoexponential_coordinates(tau: f64, omega: f64) -> (f64, f64) {
 ///     The "g_coords" and "s_coords" array lengths must match.
 /// :param s_coords: A 1-dimensional array of "s" coordiantes in the "data" array.
 /// *   The "s_coords" and "g_coords" array lengths must match.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to "False".
 /// :param axis: The channel axis, default = 2.
 /// :return: A 2-dimensional boolean mask where "true" pixels
 ///     represent values found in the "g_coords" and "s_coords" arrays.
 #[pyfunction]
 #[pyo3(name = "map_mask")]
-#[pyo3(signature = (data, g_coords, s_coords, axis=None))]
+#[pyo3(signature = (data, g_coords, s_coords, mask=None, axis=None))]
 pub fn plot_map_mask<'py>(
     py: Python<'py>,
-    data: PyReadonlyArray3<f64>,
+    data: DLPackArray3<f64>,
     g_coords: Vec<f64>,
     s_coords: Vec<f64>,
+    mask: Option<DLPackArray2<bool>>,
     axis: Option<usize>,
 ) -> PyResult<Bound<'py, PyArray2<bool>>> {
-    plot::map_mask(data.as_array(), &g_coords, &s_coords, axis)
+    let msk = mask.as_ref().map(|m| m.as_array());
+    with_warnings(py, || {
+        plot::map_mask(data.as_array(), &g_coords, &s_coords, msk, axis)
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 1-dimensional
+/// emission spectrum.
+///
+/// The spectral phasor coordinates are the normalized cosine and sine Fourier
+/// coefficients of the spectrum over its "K" wavelength channels:
+///
+/// G = Σₖ I(λₖ) × cos(2πnk/K) / Σₖ I(λₖ)
+/// S = Σₖ I(λₖ) × sin(2πnk/K) / Σₖ I(λₖ)
+///
+/// :param data: I(λ), the spectrum, one value per wavelength channel. At least
+///     2 channels are required.
+/// :param harmonic: The harmonic value, default = 1.0.
+/// :return: The real and imaginary coordinates, (G, S).
+#[pyfunction]
+#[pyo3(name = "coordinates")]
+#[pyo3(signature = (data, harmonic=None))]
+pub fn spectral_coordinates(
+    py: Python,
+    data: Vec<f64>,
+    harmonic: Option<f64>,
+) -> PyResult<(f64, f64)> {
+    with_warnings(py, || spectral::coordinates(&data, harmonic))?.map_err(map_imgal_error)
+}
+
+/// Select the pixels of a 3-dimensional phasor image within a phasor cursor.
+///
+/// :param data: The G/S 3-dimensional array.
+/// :param g: The real component, G, of the cursor center.
+/// :param s: The imaginary component, S, of the cursor center.
+/// :param radius: The cursor radius.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are never selected.
+/// :param axis: The channel axis, default = 2.
+/// :return: The 2-dimensional cursor selection mask.
+#[pyfunction]
+#[pyo3(name = "cursor_mask")]
+#[pyo3(signature = (data, g, s, radius, mask=None, axis=None))]
+pub fn spectral_cursor_mask<'py>(
+    py: Python<'py>,
+    data: DLPackArray3<f64>,
+    g: f64,
+    s: f64,
+    radius: f64,
+    mask: Option<DLPackArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<bool>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    let cursor = PhasorCursor { g, s, radius };
+    spectral::cursor_mask(data.as_array(), cursor, msk, axis)
         .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+        .map_err(map_imgal_error)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a 3-dimensional
+/// hyperspectral image.
+///
+/// This function computes the spectral phasor coordinates of each pixel of a
+/// hyperspectral image cube, see "coordinates".
+///
+/// :param data: I(λ), the hyperspectral image. At least 2 wavelength channels
+///     are required.
+/// :param harmonic: The harmonic value, default = 1.0.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
+/// :param axis: The wavelength axis, default = 2.
+/// :return: The real and imaginary coordinates as a 3-dimensional (row, col, ch)
+///     image, where G and S are indexed at 0 and 1 respectively on the channel axis.
+#[pyfunction]
+#[pyo3(name = "image")]
+#[pyo3(signature = (data, harmonic=None, mask=None, axis=None))]
+pub fn spectral_image<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    harmonic: Option<f64>,
+    mask: Option<DLPackArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        with_warnings(py, || spectral::image(arr.as_array(), harmonic, msk, axis))?
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the modulation and phase calibration values from a reference
+/// spectrum.
+///
+/// The modulation (M) and phase (φ) map the measured reference position onto
+/// its known position:
+///
+/// M = M_known / M_measured
+/// φ = φ_known - φ_measured
+///
+/// :param g: The real component, G, of the measured reference.
+/// :param s: The imaginary component, S, of the measured reference.
+/// :param g_known: The real component, G, of the known reference position.
+/// :param s_known: The imaginary component, S, of the known reference position.
+/// :return: The modulation and phase calibration values, (M, φ), as a
+///     "CalibrationResult".
+#[pyfunction]
+#[pyo3(name = "modulation_and_phase")]
+pub fn spectral_modulation_and_phase(
+    g: f64,
+    s: f64,
+    g_known: f64,
+    s_known: f64,
+) -> CalibrationResult {
+    spectral::modulation_and_phase(g, s, g_known, s_known).into()
+}
+
+/// Unmix a phasor coordinate into the fractional intensities of 2 or 3
+/// components.
+///
+/// Phasor coordinates are linear in the fractional intensities of their
+/// components. The fractions (fᵢ) of the pure component coordinates (Cᵢ) solve:
+///
+/// P = Σᵢ fᵢ × Cᵢ,  Σᵢ fᵢ = 1
+///
+/// :param g: The real component, G, of a calibrated phasor coordinate.
+/// :param s: The imaginary component, S, of a calibrated phasor coordinate.
+/// :param components: The (G, S) coordinates of the 2 or 3 pure components.
+/// :return: The fractional intensity of each component.
+#[pyfunction]
+#[pyo3(name = "unmix")]
+pub fn spectral_unmix(g: f64, s: f64, components: Vec<(f64, f64)>) -> PyResult<Vec<f64>> {
+    spectral::unmix(g, s, &components).map_err(map_imgal_error)
+}
+
+/// Unmix a 3-dimensional phasor image into the fractional intensities of 2 or
+/// 3 components.
+///
+/// :param data: The G/S 3-dimensional array.
+/// :param components: The (G, S) coordinates of the 2 or 3 pure components.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
+/// :param axis: The channel axis, default = 2.
+/// :return: The fractions as a 3-dimensional (row, col, component) image.
+#[pyfunction]
+#[pyo3(name = "unmix_image")]
+#[pyo3(signature = (data, components, mask=None, axis=None))]
+pub fn spectral_unmix_image<'py>(
+    py: Python<'py>,
+    data: DLPackArray3<f64>,
+    components: Vec<(f64, f64)>,
+    mask: Option<DLPackArray2<bool>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    with_warnings(py, || {
+        spectral::unmix_image(data.as_array(), &components, msk, axis)
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a batch of
+/// 1-dimensional decay curves.
+///
+/// This function computes the phasor coordinates of each decay curve of a
+/// 2-dimensional array (e.g. thousands of plate reader or cuvette
+/// measurements) in parallel:
+///
+/// S = ∫(I(t) * sin(nωt) * dt) / ∫(I(t) * dt)
+/// G = ∫(I(t) * cos(nωt) * dt) / ∫(I(t) * dt)
+///
+/// Curves without intensity have NaN coordinates.
+///
+/// :param data: I(t), the 2-dimensional array of decay curves.
+/// :param period: The period.
+/// :param harmonic: The harmonic value, default = 1.0.
+/// :param axis: The decay or lifetime axis, default = 1 (i.e. one curve per
+///     row).
+/// :return: The real and imaginary coordinates as a 2-dimensional (curve, ch)
+///     array, where G and S are indexed at 0 and 1 respectively on the channel
+///     axis.
+#[pyfunction]
+#[pyo3(name = "batch")]
+#[pyo3(signature = (data, period, harmonic=None, axis=None))]
+pub fn time_domain_batch<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    harmonic: Option<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+        with_warnings(py, || {
+            time_domain::batch(arr.as_array(), period, harmonic, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
 }
 
 /// Compute the histogram quality value from a 1-dimensional decay array.
@@ -276,6 +864,8 @@ pub fn time_domain_histogram_quality(data: Vec<f64>, count_threshold: f64) -> f6
 /// :param data: The 3-dimensional decay data.
 /// :param count_threshold: The minimum bin count value a bin must exceed to be
 ///     considered valid.
+/// :param mask: An optional 2-dimensional boolean mask, pixels set to "False"
+///     are skipped and set to 0.0.
 /// :param axis: The decay or lifetime axis, default = 2.
 /// :return: The 2-dimensional pixel-wise histogram quality, "q",
 ///     value map. A "q" value of 0.0 occurs when no bins exceed the threshold,
@@ -288,38 +878,22 @@ pub fn time_domain_histogram_quality(data: Vec<f64>, count_threshold: f64) -> f6
 ///     the histogram.
 #[pyfunction]
 #[pyo3(name = "histogram_quality_image")]
-#[pyo3(signature = (data, count_threshold, axis=None))]
+#[pyo3(signature = (data, count_threshold, mask=None, axis=None))]
 pub fn time_domain_histogram_quality_image<'py>(
     py: Python<'py>,
     data: Bound<'py, PyAny>,
     count_threshold: f64,
+    mask: Option<DLPackArray2<bool>>,
     axis: Option<usize>,
 ) -> PyResult<Bound<'py, PyArray2<f64>>> {
-    // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u8>>() {
-        return time_domain::histogram_quality_image(arr.as_array(), count_threshold as u8, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error);
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u16>>() {
-        return time_domain::histogram_quality_image(arr.as_array(), count_threshold as u16, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error);
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<f32>>() {
-        return time_domain::histogram_quality_image(arr.as_array(), count_threshold as f32, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error);
-    }
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<f64>>() {
-        return time_domain::histogram_quality_image(arr.as_array(), count_threshold, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error);
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        with_warnings(py, || {
+            time_domain::histogram_quality_image(arr.as_array(), count_threshold as T, msk, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
 }
 
 /// Compute the real and imaginary (G, S) coordinates of a 3-dimensional decay
@@ -344,56 +918,19 @@ pub fn time_domain_image<'py>(
     py: Python<'py>,
     data: Bound<'py, PyAny>,
     period: f64,
-    mask: Option<PyReadonlyArray2<bool>>,
+    mask: Option<DLPackArray2<bool>>,
     harmonic: Option<f64>,
     axis: Option<usize>,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u8>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        }
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<u16>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        }
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f32>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        }
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f64>>() {
-        if let Some(m) = mask {
-            return time_domain::image(arr.as_array(), period, Some(m.as_array()), harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        } else {
-            return time_domain::image(arr.as_array(), period, None, harmonic, axis)
-                .map(|output| output.into_pyarray(py))
-                .map_err(map_array_error);
-        }
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        with_warnings(py, || {
+            time_domain::image(arr.as_array(), period, msk, harmonic, axis)
+        })?
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
 }
 
 /// Compute the imaginary (S) component of a 1-dimensional decay curve.
@@ -412,8 +949,13 @@ pub fn time_domain_image<'py>(
 #[pyfunction]
 #[pyo3(name = "imaginary")]
 #[pyo3(signature = (data, period, harmonic=None))]
-pub fn time_domain_imaginary(data: Vec<f64>, period: f64, harmonic: Option<f64>) -> f64 {
-    time_domain::imaginary(&data, period, harmonic)
+pub fn time_domain_imaginary(
+    py: Python,
+    data: Vec<f64>,
+    period: f64,
+    harmonic: Option<f64>,
+) -> PyResult<f64> {
+    with_warnings(py, || time_domain::imaginary(&data, period, harmonic))
 }
 
 /// Compute the real (G) component of a 1-dimensional decay curve.
@@ -432,6 +974,11 @@ pub fn time_domain_imaginary(data: Vec<f64>, period: f64, harmonic: Option<f64>)
 #[pyfunction]
 #[pyo3(name = "real")]
 #[pyo3(signature = (data, period, harmonic=None))]
-pub fn time_domain_real(data: Vec<f64>, period: f64, harmonic: Option<f64>) -> f64 {
-    time_domain::real(&data, period, harmonic)
+pub fn time_domain_real(
+    py: Python,
+    data: Vec<f64>,
+    period: f64,
+    harmonic: Option<f64>,
+) -> PyResult<f64> {
+    with_warnings(py, || time_domain::real(&data, period, harmonic))
 }
@@ -0,0 +1,179 @@
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::error::map_imgal_error;
+use crate::functions::transform_functions::parse_interpolation;
+use crate::utils::{DLPackArray2, dispatch_dtype};
+use imgal::register::{self, Metric, Transform};
+
+/// Register two 2-dimensional images with an iterative intensity-based affine
+/// or rigid registration.
+///
+/// This function searches the transform of the moving image that optimizes the
+/// similarity "metric" between the reference image and the transformed moving
+/// image, evaluated over the pixels where the two images overlap. The
+/// transform parameters are optimized with the derivative free Nelder-Mead
+/// simplex method on a multi-resolution pyramid (2 x 2 block means), from the
+/// coarsest to the full resolution level. Rotations, scalings and shearings are
+/// about the image center. Transforms that overlap less than 10% of the
+/// reference image are rejected. The mutual information is computed from a
+/// joint histogram with 32 bins per image.
+///
+/// :param reference: The 2-dimensional reference image.
+/// :param moving: The 2-dimensional moving image. Must match the shape and
+///     dtype of "reference".
+/// :param transform: The transformation model, "translation", "rigid" or
+///     "affine", default = "affine".
+/// :param metric: The similarity metric, "mse" (mean squared error), "ncc"
+///     (normalized cross-correlation) or "mi" (mutual information), default =
+///     "mse".
+/// :param levels: The maximum number of pyramid levels, default = 3. Levels
+///     with an axis shorter than 8 pixels are skipped. Must be >= 1.
+/// :param interpolation: The interpolation method, "nearest", "linear" or
+///     "cubic", default = "linear".
+/// :param max_iterations: The maximum number of simplex iterations per
+///     pyramid level, default = 300. Must be >= 1.
+/// :return: A dict with the 3 x 3 "matrix" that maps moving image (row, col,
+///     1) coordinates to reference image coordinates (see "transform.warp_affine"), the
+///     "registered" moving image and the final "metric" value.
+#[pyfunction]
+#[pyo3(name = "affine")]
+#[pyo3(signature = (reference, moving, transform=None, metric=None, levels=None, interpolation=None, max_iterations=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn register_affine<'py>(
+    py: Python<'py>,
+    reference: Bound<'py, PyAny>,
+    moving: Bound<'py, PyAny>,
+    transform: Option<&str>,
+    metric: Option<&str>,
+    levels: Option<usize>,
+    interpolation: Option<&str>,
+    max_iterations: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let transform = parse_transform(transform)?;
+    let metric = parse_metric(metric)?;
+    let interpolation = parse_interpolation(interpolation)?;
+
+    // pattern match and extract allowed array types
+    let result = dispatch_dtype!(reference, PyReadonlyArray2::<T>, |arr_ref| {
+        let arr_mov = moving.extract::<DLPackArray2<T>>()?;
+        register::affine(
+            arr_ref.as_array(),
+            arr_mov.as_array(),
+            Some(transform),
+            Some(metric),
+            levels,
+            Some(interpolation),
+            max_iterations,
+        )
+    })
+    .map_err(map_imgal_error)?;
+    let dict = PyDict::new(py);
+    dict.set_item("matrix", result.matrix.into_pyarray(py))?;
+    dict.set_item("registered", result.registered.into_pyarray(py))?;
+    dict.set_item("metric", result.metric)?;
+
+    Ok(dict)
+}
+
+/// Translate a 2-dimensional image by a sub-pixel shift.
+///
+/// This function moves the image content by "shift" pixels, the output value
+/// at (row, col) is the input sampled at (row - shift[0], col - shift[1]) with
+/// bilinear interpolation. Integer shifts are exact. Output pixels that sample
+/// outside of the image are set to "fill". Apply the shift estimated by
+/// "phase_correlation" to the moving image to register it with the reference
+/// image.
+///
+/// :param data: The 2-dimensional image.
+/// :param shift: The (row, col) shift in pixels. Must be finite.
+/// :param fill: The value of the output pixels outside of the input image,
+///     default = 0.0.
+/// :return: The shifted image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "apply_shift")]
+#[pyo3(signature = (data, shift, fill=None))]
+pub fn register_apply_shift<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    shift: (f64, f64),
+    fill: Option<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+        register::apply_shift(arr.as_array(), shift, fill)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Estimate the translation between two 2-dimensional images by phase
+/// correlation.
+///
+/// This function computes the normalized cross-power spectrum of the two
+/// images:
+///
+/// R = F_ref × F_mov* / |F_ref × F_mov*|
+///
+/// whose inverse Fourier transform is a sharp peak at the shift between the
+/// images. The integer peak is refined to 1 / upsample of a pixel with a
+/// matrix-multiply discrete Fourier transform on an upsampled grid around it.
+/// The images are treated as periodic and shifts are reported in
+/// [-n / 2, n / 2) along each axis.
+///
+/// :param reference: The 2-dimensional reference image.
+/// :param moving: The 2-dimensional moving image. Must match the shape and
+///     dtype of "reference".
+/// :param upsample: The sub-pixel precision factor, default = 10 (i.e. 0.1
+///     pixel). An upsample of 1 gives integer shifts. Must be >= 1.
+/// :return: A dict with the (row, col) "shift" to apply to the moving image
+///     (see "apply_shift") and the correlation "peak" height in [0, 1].
+#[pyfunction]
+#[pyo3(name = "phase_correlation")]
+#[pyo3(signature = (reference, moving, upsample=None))]
+pub fn register_phase_correlation<'py>(
+    py: Python<'py>,
+    reference: Bound<'py, PyAny>,
+    moving: Bound<'py, PyAny>,
+    upsample: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    // pattern match and extract allowed array types
+    let result = dispatch_dtype!(reference, PyReadonlyArray2::<T>, |arr_ref| {
+        let arr_mov = moving.extract::<DLPackArray2<T>>()?;
+        register::phase_correlation(arr_ref.as_array(), arr_mov.as_array(), upsample)
+    })
+    .map_err(map_imgal_error)?;
+    let dict = PyDict::new(py);
+    dict.set_item("shift", result.shift)?;
+    dict.set_item("peak", result.peak)?;
+
+    Ok(dict)
+}
+
+/// Parse a similarity metric name.
+fn parse_metric(metric: Option<&str>) -> PyResult<Metric> {
+    match metric {
+        None | Some("mse") => Ok(Metric::Mse),
+        Some("ncc") => Ok(Metric::Ncc),
+        Some("mi") => Ok(Metric::MutualInformation),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown metric \"{}\", supported metrics are \"mse\", \"ncc\" and \"mi\".",
+            m
+        ))),
+    }
+}
+
+/// Parse a transformation model name.
+fn parse_transform(transform: Option<&str>) -> PyResult<Transform> {
+    match transform {
+        None | Some("affine") => Ok(Transform::Affine),
+        Some("rigid") => Ok(Transform::Rigid),
+        Some("translation") => Ok(Transform::Translation),
+        Some(t) => Err(PyValueError::new_err(format!(
+            "Unknown transform \"{}\", supported transforms are \"translation\", \"rigid\" and \"affine\".",
+            t
+        ))),
+    }
+}
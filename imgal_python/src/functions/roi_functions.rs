@@ -0,0 +1,273 @@
+use std::sync::Mutex;
+
+use numpy::{PyReadonlyArray3, PyReadonlyArray4, PyReadonlyArray5};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::error::map_imgal_error;
+use crate::utils::{DLPackArray2, dispatch_dtype};
+use imgal::roi::{self, ResultsTable, composite};
+use imgal::traits::numeric::ToFloat64;
+
+/// A region of interest (ROI) manager.
+///
+/// The ROI manager holds named 2-dimensional ROI masks, each tagged with zero
+/// or more groups, in insertion order. All ROIs share the same shape. A
+/// measurement function can be applied to the pixels of every ROI in every
+/// slice of an image stack, producing a tidy results table.
+#[pyclass(name = "Manager", module = "imgal.roi")]
+#[derive(Default)]
+pub struct RoiManager {
+    inner: roi::Manager,
+}
+
+#[pymethods]
+impl RoiManager {
+    #[new]
+    fn new() -> Self {
+        RoiManager::default()
+    }
+
+    /// Add a named ROI to the manager.
+    ///
+    /// :param name: The unique ROI name.
+    /// :param mask: The 2-dimensional boolean mask, pixels set to True are in
+    ///     the ROI. Must match the shape of the ROIs already in the manager.
+    /// :param groups: The group tags of the ROI.
+    #[pyo3(signature = (name, mask, groups=None))]
+    fn add(
+        &mut self,
+        name: &str,
+        mask: DLPackArray2<bool>,
+        groups: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let grps = groups.unwrap_or_default();
+        let tags: Vec<&str> = grps.iter().map(|g| g.as_str()).collect();
+        self.inner
+            .add(name, mask.as_array().to_owned(), &tags)
+            .map_err(map_imgal_error)
+    }
+
+    /// Remove an ROI by name.
+    ///
+    /// :param name: The ROI name.
+    /// :return: True if the ROI was removed, False if it does not exist.
+    fn remove(&mut self, name: &str) -> bool {
+        self.inner.remove(name).is_some()
+    }
+
+    /// Tag an ROI with a group.
+    ///
+    /// :param name: The ROI name.
+    /// :param group: The group tag.
+    /// :return: False if the ROI does not exist.
+    fn tag(&mut self, name: &str, group: &str) -> bool {
+        self.inner.tag(name, group)
+    }
+
+    /// Remove a group tag from an ROI.
+    ///
+    /// :param name: The ROI name.
+    /// :param group: The group tag.
+    /// :return: False if the ROI does not exist.
+    fn untag(&mut self, name: &str, group: &str) -> bool {
+        self.inner.untag(name, group)
+    }
+
+    /// The ROI names, in insertion order.
+    fn names(&self) -> Vec<String> {
+        self.inner.names().iter().map(|n| n.to_string()).collect()
+    }
+
+    /// The unique group tags of all ROIs, in order of first appearance.
+    fn groups(&self) -> Vec<String> {
+        self.inner.groups().iter().map(|g| g.to_string()).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Measure every ROI in every slice of an image stack.
+    ///
+    /// The measurement function is called with a 1-dimensional list of the ROI
+    /// pixel values of one slice and must return a dict of measurement names
+    /// and values. The results table rows are ordered by ROI (insertion order),
+    /// then slice, then measurement.
+    ///
+    /// :param data: The 3-dimensional image stack.
+    /// :param measure: The measurement function, e.g.
+    ///     lambda px: {"mean": sum(px) / len(px)}.
+    /// :param group: Only measure ROIs tagged with this group. If None, all
+    ///     ROIs are measured.
+    /// :param axis: The stack (e.g. time or channel) axis, default = 2.
+    /// :return: The tidy results table as a dict of columns, "roi", "groups",
+    ///     "slice", "channel", "measurement" and "value".
+    #[pyo3(signature = (data, measure, group=None, axis=None))]
+    fn measure<'py>(
+        &self,
+        py: Python<'py>,
+        data: Bound<'py, PyAny>,
+        measure: Py<PyAny>,
+        group: Option<&str>,
+        axis: Option<usize>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        // pattern match and extract allowed array types
+        let table = dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+            measure_stack(py, &self.inner, arr, &measure, group, axis)?
+        });
+
+        table_to_dict(py, &table)
+    }
+}
+
+/// Measure per ROI, per channel and per time point summary statistics of a
+/// 4-dimensional multi-channel time-lapse.
+///
+/// This function computes the "mean", "median" and "integrated" (i.e. summed)
+/// intensity of the pixels of each ROI in each channel of each time point.
+/// The time point index is stored in the "slice" column.
+///
+/// :param manager: The ROI manager.
+/// :param data: The 4-dimensional time-lapse with shape (t, row, col, ch).
+/// :param group: Only measure ROIs tagged with this group. If None, all ROIs
+///     are measured.
+/// :return: The tidy results table as a dict of columns, "roi", "groups",
+///     "slice", "channel", "measurement" and "value".
+#[pyfunction]
+#[pyo3(name = "summary_4d")]
+#[pyo3(signature = (manager, data, group=None))]
+pub fn composite_summary_4d<'py>(
+    py: Python<'py>,
+    manager: PyRef<'py, RoiManager>,
+    data: Bound<'py, PyAny>,
+    group: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    // pattern match and extract allowed array types
+    let table = dispatch_dtype!(data, PyReadonlyArray4::<T>, |arr| {
+        composite::summary_4d(&manager.inner, arr.as_array(), group)
+    })
+    .map_err(map_imgal_error)?;
+
+    table_to_dict(py, &table)
+}
+
+/// Measure per ROI, per channel and per time point summary statistics and
+/// lifetimes of a 5-dimensional multi-channel lifetime time-lapse.
+///
+/// This function computes the "mean", "median" and "integrated" (i.e. summed)
+/// intensity of the pixels of each ROI in each channel of each time point,
+/// where the intensity of a pixel is the sum of its decay curve. The
+/// "lifetime" is the phase lifetime of the ROI summed decay curve:
+///
+/// τφ = S / (ω * G)
+///
+/// :param manager: The ROI manager.
+/// :param data: The 5-dimensional lifetime time-lapse with shape
+///     (t, row, col, ch, tau).
+/// :param period: The period (i.e. time interval) of the decay axis.
+/// :param group: Only measure ROIs tagged with this group. If None, all ROIs
+///     are measured.
+/// :return: The tidy results table as a dict of columns, "roi", "groups",
+///     "slice", "channel", "measurement" and "value".
+#[pyfunction]
+#[pyo3(name = "summary_5d")]
+#[pyo3(signature = (manager, data, period, group=None))]
+pub fn composite_summary_5d<'py>(
+    py: Python<'py>,
+    manager: PyRef<'py, RoiManager>,
+    data: Bound<'py, PyAny>,
+    period: f64,
+    group: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    // pattern match and extract allowed array types
+    let table = dispatch_dtype!(data, PyReadonlyArray5::<T>, |arr| {
+        composite::summary_5d(&manager.inner, arr.as_array(), period, group)
+    })
+    .map_err(map_imgal_error)?;
+
+    table_to_dict(py, &table)
+}
+
+/// Convert a tidy results table into a dict of columns.
+pub(crate) fn table_to_dict<'py>(
+    py: Python<'py>,
+    table: &ResultsTable,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item(
+        "roi",
+        table.iter().map(|r| r.roi.clone()).collect::<Vec<_>>(),
+    )?;
+    dict.set_item(
+        "groups",
+        table.iter().map(|r| r.groups.clone()).collect::<Vec<_>>(),
+    )?;
+    dict.set_item("slice", table.iter().map(|r| r.slice).collect::<Vec<_>>())?;
+    dict.set_item(
+        "channel",
+        table.iter().map(|r| r.channel).collect::<Vec<_>>(),
+    )?;
+    dict.set_item(
+        "measurement",
+        table
+            .iter()
+            .map(|r| r.measurement.clone())
+            .collect::<Vec<_>>(),
+    )?;
+    dict.set_item("value", table.iter().map(|r| r.value).collect::<Vec<_>>())?;
+
+    Ok(dict)
+}
+
+/// Measure an image stack with a Python measurement function.
+///
+/// The first Python exception raised by the measurement function is returned
+/// and the remaining ROIs yield no measurements.
+fn measure_stack<T>(
+    py: Python,
+    manager: &roi::Manager,
+    data: PyReadonlyArray3<T>,
+    measure: &Py<PyAny>,
+    group: Option<&str>,
+    axis: Option<usize>,
+) -> PyResult<ResultsTable>
+where
+    T: ToFloat64 + numpy::Element,
+{
+    let arr = data.as_array();
+    let err: Mutex<Option<PyErr>> = Mutex::new(None);
+    let table = py
+        .allow_threads(|| {
+            manager.measure(
+                arr,
+                |px| {
+                    Python::with_gil(|py| {
+                        if err.lock().unwrap().is_some() {
+                            return Vec::new();
+                        }
+                        let result = measure.call1(py, (px.to_vec(),)).and_then(|r| {
+                            r.downcast_bound::<PyDict>(py)?
+                                .iter()
+                                .map(|(k, v)| Ok((k.extract::<String>()?, v.extract::<f64>()?)))
+                                .collect::<PyResult<Vec<(String, f64)>>>()
+                        });
+                        match result {
+                            Ok(values) => values,
+                            Err(e) => {
+                                err.lock().unwrap().get_or_insert(e);
+                                Vec::new()
+                            }
+                        }
+                    })
+                },
+                group,
+                axis,
+            )
+        })
+        .map_err(map_imgal_error)?;
+    match err.into_inner().unwrap() {
+        Some(e) => Err(e),
+        None => Ok(table),
+    }
+}
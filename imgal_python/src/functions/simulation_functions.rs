@@ -1,11 +1,442 @@
 use numpy::{
-    IntoPyArray, PyArray1, PyArray3, PyReadonlyArray1, PyReadonlyArray3, PyReadwriteArray1,
-    PyReadwriteArray3,
+    IntoPyArray, PyArray1, PyArray2, PyArray3, PyArray4, PyReadonlyArray1, PyReadonlyArray3,
+    PyReadonlyArrayDyn, PyReadwriteArray1, PyReadwriteArray3, PyReadwriteArrayDyn,
 };
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
-use crate::error::map_array_error;
+use crate::error::{map_imgal_error, with_warnings};
+use crate::utils::{DLPackArray3, contiguous, dispatch_dtype, dispatch_dtype_mut, with_slice_mut};
 use imgal::simulation;
+use imgal::simulation::anisotropy::AnisotropyDecay;
+use imgal::simulation::decay::{Fret, FretEfficiency};
+use imgal::simulation::instrument::{GaussianIrf, GibsonLanni};
+use imgal::simulation::rng::{self, Seed};
+
+/// Parallel and perpendicular polarization decay channel arrays.
+type PolarizedPair<'py, A> = (Bound<'py, A>, Bound<'py, A>);
+
+/// Simulate a 1-dimensional anisotropy decay curve.
+///
+/// This function generates the time-resolved fluorescence anisotropy r(t) of a
+/// fluorophore with one or more rotational correlation times (theta), each
+/// weighted by a fractional amplitude:
+///
+/// r(t) = r₀ × Σⱼ βⱼ × exp(-t/θⱼ)
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param r0: The fundamental (i.e. initial) anisotropy, must be within
+///     [-0.2, 0.4].
+/// :param rotational_times: An array of rotational correlation times.
+/// :param rotational_fractions: An array of fractional amplitudes for each
+///     rotational time in the "rotational_times" array that sum to 1.0.
+/// :return: The 1-dimensional anisotropy decay curve.
+#[pyfunction]
+#[pyo3(name = "anisotropy_decay_1d")]
+pub fn anisotropy_anisotropy_decay_1d(
+    py: Python,
+    samples: usize,
+    period: f64,
+    r0: f64,
+    rotational_times: Vec<f64>,
+    rotational_fractions: Vec<f64>,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    with_warnings(py, || {
+        simulation::anisotropy::anisotropy_decay_1d(
+            samples,
+            period,
+            r0,
+            &rotational_times,
+            &rotational_fractions,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate 1-dimensional Gaussian IRF convolved parallel and perpendicular
+/// polarization decay curves.
+///
+/// This function generates the parallel and perpendicular polarization decay
+/// channels of a time-resolved anisotropy measurement and convolves each
+/// channel with a Gaussian instrument response function (IRF).
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param taus: An array of lifetimes. The "taus" and "fractions" arrays must
+///     have the same length.
+/// :param fractions: An array of fractional intensities for each tau in the
+///     "taus" array that sum to 1.0.
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     isotropic decay curve.
+/// :param r0: The fundamental (i.e. initial) anisotropy, must be within
+///     [-0.2, 0.4].
+/// :param rotational_times: An array of rotational correlation times.
+/// :param rotational_fractions: An array of fractional amplitudes for each
+///     rotational time in the "rotational_times" array that sum to 1.0.
+/// :param irf_center: The temporal position of the IRF peak within the time range.
+/// :param irf_width: The full width at half maximum (FWHM) of the IRF.
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :return: The Gaussian IRF convolved parallel and perpendicular decay curves
+///     respectively.
+#[pyfunction]
+#[pyo3(name = "gaussian_anisotropy_1d")]
+#[pyo3(signature = (samples, period, taus, fractions, total_counts, r0, rotational_times, rotational_fractions, irf_center, irf_width, g_factor=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn anisotropy_gaussian_anisotropy_1d<'py>(
+    py: Python<'py>,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+    r0: f64,
+    rotational_times: Vec<f64>,
+    rotational_fractions: Vec<f64>,
+    irf_center: f64,
+    irf_width: f64,
+    g_factor: Option<f64>,
+) -> PyResult<PolarizedPair<'py, PyArray1<f64>>> {
+    with_warnings(py, || {
+        simulation::anisotropy::gaussian_anisotropy_1d(
+            samples,
+            period,
+            AnisotropyDecay {
+                taus: &taus,
+                fractions: &fractions,
+                total_counts,
+                r0,
+                rotational_times: &rotational_times,
+                rotational_fractions: &rotational_fractions,
+            },
+            GaussianIrf {
+                center: irf_center,
+                width: irf_width,
+            },
+            g_factor,
+        )
+    })?
+    .map(|(par, perp)| (par.into_pyarray(py), perp.into_pyarray(py)))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate 3-dimensional Gaussian IRF convolved parallel and perpendicular
+/// polarization decay images.
+///
+/// This function generates Gaussian IRF convolved parallel and perpendicular
+/// polarization decay curves and broadcasts each into a 3-dimensional image.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param taus: An array of lifetimes. The "taus" and "fractions" arrays must
+///     have the same length.
+/// :param fractions: An array of fractional intensities for each tau in the
+///     "taus" array that sum to 1.0.
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     isotropic decay curve.
+/// :param r0: The fundamental (i.e. initial) anisotropy, must be within
+///     [-0.2, 0.4].
+/// :param rotational_times: An array of rotational correlation times.
+/// :param rotational_fractions: An array of fractional amplitudes for each
+///     rotational time in the "rotational_times" array that sum to 1.0.
+/// :param irf_center: The temporal position of the IRF peak within the time range.
+/// :param irf_width: The full width at half maximum (FWHM) of the IRF.
+/// :param shape: The row and col shape to broadcast the decay curves into.
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :return: The Gaussian IRF convolved parallel and perpendicular decay images
+///     respectively.
+#[pyfunction]
+#[pyo3(name = "gaussian_anisotropy_3d")]
+#[pyo3(signature = (samples, period, taus, fractions, total_counts, r0, rotational_times, rotational_fractions, irf_center, irf_width, shape, g_factor=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn anisotropy_gaussian_anisotropy_3d<'py>(
+    py: Python<'py>,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+    r0: f64,
+    rotational_times: Vec<f64>,
+    rotational_fractions: Vec<f64>,
+    irf_center: f64,
+    irf_width: f64,
+    shape: (usize, usize),
+    g_factor: Option<f64>,
+) -> PyResult<PolarizedPair<'py, PyArray3<f64>>> {
+    with_warnings(py, || {
+        simulation::anisotropy::gaussian_anisotropy_3d(
+            samples,
+            period,
+            AnisotropyDecay {
+                taus: &taus,
+                fractions: &fractions,
+                total_counts,
+                r0,
+                rotational_times: &rotational_times,
+                rotational_fractions: &rotational_fractions,
+            },
+            GaussianIrf {
+                center: irf_center,
+                width: irf_width,
+            },
+            g_factor,
+            shape,
+        )
+    })?
+    .map(|(par, perp)| (par.into_pyarray(py), perp.into_pyarray(py)))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate ideal 1-dimensional parallel and perpendicular polarization decay
+/// curves.
+///
+/// This function generates the parallel (I∥) and perpendicular (I⊥)
+/// polarization decay channels of a time-resolved anisotropy measurement from
+/// the isotropic decay I(t) and the anisotropy decay r(t):
+///
+/// I∥(t) = I(t) × (1 + 2r(t)) / 3
+/// I⊥(t) = I(t) × (1 - r(t)) / (3G)
+///
+/// where G is the instrument G-factor.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param taus: An array of lifetimes. The "taus" and "fractions" arrays must
+///     have the same length.
+/// :param fractions: An array of fractional intensities for each tau in the
+///     "taus" array that sum to 1.0.
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     isotropic decay curve.
+/// :param r0: The fundamental (i.e. initial) anisotropy, must be within
+///     [-0.2, 0.4].
+/// :param rotational_times: An array of rotational correlation times.
+/// :param rotational_fractions: An array of fractional amplitudes for each
+///     rotational time in the "rotational_times" array that sum to 1.0.
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :return: The parallel and perpendicular decay curves respectively.
+#[pyfunction]
+#[pyo3(name = "ideal_anisotropy_1d")]
+#[pyo3(signature = (samples, period, taus, fractions, total_counts, r0, rotational_times, rotational_fractions, g_factor=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn anisotropy_ideal_anisotropy_1d<'py>(
+    py: Python<'py>,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+    r0: f64,
+    rotational_times: Vec<f64>,
+    rotational_fractions: Vec<f64>,
+    g_factor: Option<f64>,
+) -> PyResult<PolarizedPair<'py, PyArray1<f64>>> {
+    with_warnings(py, || {
+        simulation::anisotropy::ideal_anisotropy_1d(
+            samples,
+            period,
+            AnisotropyDecay {
+                taus: &taus,
+                fractions: &fractions,
+                total_counts,
+                r0,
+                rotational_times: &rotational_times,
+                rotational_fractions: &rotational_fractions,
+            },
+            g_factor,
+        )
+    })?
+    .map(|(par, perp)| (par.into_pyarray(py), perp.into_pyarray(py)))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate 1-dimensional IRF convolved parallel and perpendicular
+/// polarization decay curves.
+///
+/// This function generates the parallel and perpendicular polarization decay
+/// channels of a time-resolved anisotropy measurement and convolves each
+/// channel with the input instrument response function (IRF).
+///
+/// :param irf: The IRF as a 1-dimensonal array.
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param taus: An array of lifetimes. The "taus" and "fractions" arrays must
+///     have the same length.
+/// :param fractions: An array of fractional intensities for each tau in the
+///     "taus" array that sum to 1.0.
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     isotropic decay curve.
+/// :param r0: The fundamental (i.e. initial) anisotropy, must be within
+///     [-0.2, 0.4].
+/// :param rotational_times: An array of rotational correlation times.
+/// :param rotational_fractions: An array of fractional amplitudes for each
+///     rotational time in the "rotational_times" array that sum to 1.0.
+/// :param g_factor: The instrument G-factor, default = 1.0.
+/// :return: The IRF convolved parallel and perpendicular decay curves
+///     respectively.
+#[pyfunction]
+#[pyo3(name = "irf_anisotropy_1d")]
+#[pyo3(signature = (irf, samples, period, taus, fractions, total_counts, r0, rotational_times, rotational_fractions, g_factor=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn anisotropy_irf_anisotropy_1d<'py>(
+    py: Python<'py>,
+    irf: Vec<f64>,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+    r0: f64,
+    rotational_times: Vec<f64>,
+    rotational_fractions: Vec<f64>,
+    g_factor: Option<f64>,
+) -> PyResult<PolarizedPair<'py, PyArray1<f64>>> {
+    with_warnings(py, || {
+        simulation::anisotropy::irf_anisotropy_1d(
+            &irf,
+            samples,
+            period,
+            AnisotropyDecay {
+                taus: &taus,
+                fractions: &fractions,
+                total_counts,
+                r0,
+                rotational_times: &rotational_times,
+                rotational_fractions: &rotational_fractions,
+            },
+            g_factor,
+        )
+    })?
+    .map(|(par, perp)| (par.into_pyarray(py), perp.into_pyarray(py)))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 1-dimensional FRET donor decay curve.
+///
+/// This function generates an ideal donor decay curve in the presence of
+/// acceptor. Donors undergoing Förster resonance energy transfer (FRET) with
+/// efficiency E are quenched to a lifetime of τ_DA = τ_D × (1 - E), while a
+/// "donor_only_fraction" of donors keep the unquenched donor lifetime:
+///
+/// I(t) = x_D × exp(-t/τ_D) + (1 - x_D) × Σₖ pₖ × exp(-t/(τ_D × (1 - Eₖ)))
+///
+/// The efficiency distribution is a single value by default. Set
+/// "efficiency_sigma" for a Gaussian distribution centered on "efficiency", or
+/// set "second_efficiency" and "second_fraction" for two populations.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param donor_tau: The unquenched donor lifetime.
+/// :param efficiency: The FRET efficiency (or mean efficiency) of the donors,
+///     must be in [0, 1).
+/// :param donor_only_fraction: The fraction of donors that do not undergo FRET,
+///     must be in [0, 1].
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     decay curve.
+/// :param efficiency_sigma: The standard deviation of a Gaussian efficiency
+///     distribution, default = None.
+/// :param second_efficiency: The FRET efficiency of a second donor population,
+///     default = None.
+/// :param second_fraction: The fraction of FRET donors in the second population,
+///     default = None.
+/// :return: The 1-dimensional FRET donor decay curve.
+#[pyfunction]
+#[pyo3(name = "fret_exponential_1d")]
+#[pyo3(signature = (samples, period, donor_tau, efficiency, donor_only_fraction, total_counts, efficiency_sigma=None, second_efficiency=None, second_fraction=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn decay_fret_exponential_1d(
+    py: Python,
+    samples: usize,
+    period: f64,
+    donor_tau: f64,
+    efficiency: f64,
+    donor_only_fraction: f64,
+    total_counts: f64,
+    efficiency_sigma: Option<f64>,
+    second_efficiency: Option<f64>,
+    second_fraction: Option<f64>,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    let efficiency = parse_fret_efficiency(
+        efficiency,
+        efficiency_sigma,
+        second_efficiency,
+        second_fraction,
+    )?;
+    simulation::decay::fret_exponential_1d(
+        samples,
+        period,
+        Fret {
+            donor_tau,
+            efficiency,
+            donor_only_fraction,
+        },
+        total_counts,
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional FRET donor decay curve.
+///
+/// This function generates an ideal FRET donor decay curve and broadcasts it
+/// into a 3-dimensional image. See "fret_exponential_1d" for the efficiency
+/// distribution parameters.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param donor_tau: The unquenched donor lifetime.
+/// :param efficiency: The FRET efficiency (or mean efficiency) of the donors,
+///     must be in [0, 1).
+/// :param donor_only_fraction: The fraction of donors that do not undergo FRET,
+///     must be in [0, 1].
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     decay curve.
+/// :param shape: The row and col shape to broadcast the decay curve into.
+/// :param efficiency_sigma: The standard deviation of a Gaussian efficiency
+///     distribution, default = None.
+/// :param second_efficiency: The FRET efficiency of a second donor population,
+///     default = None.
+/// :param second_fraction: The fraction of FRET donors in the second population,
+///     default = None.
+/// :return: The 3-dimensional FRET donor decay curve.
+#[pyfunction]
+#[pyo3(name = "fret_exponential_3d")]
+#[pyo3(signature = (samples, period, donor_tau, efficiency, donor_only_fraction, total_counts, shape, efficiency_sigma=None, second_efficiency=None, second_fraction=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn decay_fret_exponential_3d(
+    py: Python,
+    samples: usize,
+    period: f64,
+    donor_tau: f64,
+    efficiency: f64,
+    donor_only_fraction: f64,
+    total_counts: f64,
+    shape: (usize, usize),
+    efficiency_sigma: Option<f64>,
+    second_efficiency: Option<f64>,
+    second_fraction: Option<f64>,
+) -> PyResult<Bound<PyArray3<f64>>> {
+    let efficiency = parse_fret_efficiency(
+        efficiency,
+        efficiency_sigma,
+        second_efficiency,
+        second_fraction,
+    )?;
+    simulation::decay::fret_exponential_3d(
+        samples,
+        period,
+        Fret {
+            donor_tau,
+            efficiency,
+            donor_only_fraction,
+        },
+        total_counts,
+        shape,
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
 
 /// Simulate a 1-dimensional Gaussian IRF convolved monoexponential or
 /// multiexponential decay curve.
@@ -35,6 +466,7 @@ use imgal::simulation;
 ///     or multiexponential decay curve.
 #[pyfunction]
 #[pyo3(name = "gaussian_exponential_1d")]
+#[allow(clippy::too_many_arguments)]
 pub fn decay_gaussian_exponential_1d(
     py: Python,
     samples: usize,
@@ -45,17 +477,21 @@ pub fn decay_gaussian_exponential_1d(
     irf_center: f64,
     irf_width: f64,
 ) -> PyResult<Bound<PyArray1<f64>>> {
-    simulation::decay::gaussian_exponential_1d(
-        samples,
-        period,
-        &taus,
-        &fractions,
-        total_counts,
-        irf_center,
-        irf_width,
-    )
+    with_warnings(py, || {
+        simulation::decay::gaussian_exponential_1d(
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+            GaussianIrf {
+                center: irf_center,
+                width: irf_width,
+            },
+        )
+    })?
     .map(|output| output.into_pyarray(py))
-    .map_err(map_array_error)
+    .map_err(map_imgal_error)
 }
 
 /// Simulate a 3-dimensional Gaussian IRF convolved monoexponential or
@@ -87,6 +523,7 @@ pub fn decay_gaussian_exponential_1d(
 ///     or multiexponential decay curve.
 #[pyfunction]
 #[pyo3(name = "gaussian_exponential_3d")]
+#[allow(clippy::too_many_arguments)]
 pub fn decay_gaussian_exponential_3d(
     py: Python,
     samples: usize,
@@ -98,18 +535,284 @@ pub fn decay_gaussian_exponential_3d(
     irf_width: f64,
     shape: (usize, usize),
 ) -> PyResult<Bound<PyArray3<f64>>> {
-    simulation::decay::gaussian_exponential_3d(
+    with_warnings(py, || {
+        simulation::decay::gaussian_exponential_3d(
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+            GaussianIrf {
+                center: irf_center,
+                width: irf_width,
+            },
+            shape,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 1-dimensional Gaussian IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// This function generates a 1-dimensonal Gaussian instrument response function
+/// (IRF) circularly convolved periodic monoexponential or multiexponential decay
+/// curve.
+///
+/// The decay is treated as periodic: fluorescence that has not decayed by the
+/// end of the period is carried over into the next period and the IRF is
+/// circularly convolved, modeling the incomplete decay observed when tau is
+/// comparable to the period:
+///
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+///
+/// where T is the period.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (_i.e._ time interval).
+/// :param taus: An array of lifetimes. For a monoexponential decay curve use a
+///     single tau value and a fractional intensity of 1.0. For a
+///     multiexponential decay curve use two or more tau values, matched with
+///     their respective fractional intensity. The "taus" and "fractions" arrays
+///     must have the same length. Tau values set to 0.0 will be skipped.
+/// :param fractions: An array of fractional intensities for each tau in the "taus"
+///     array. The "fractions" array must be the same length as the "taus" array
+///     and sum to 1.0. Fraction values set to 0.0 will be skipped.
+/// :param total_counts: The total intensity count (_e.g._ photon count) of the
+///     decay curve.
+/// :param irf_center: The temporal position of the IRF peak within the time range.
+/// :param irf_width: The full width at half maximum (FWHM) of the IRF.
+/// :return: The 1-dimensonal Gaussian IRF circularly convolved periodic
+///     monoexponential or multiexponential decay curve.
+#[pyfunction]
+#[pyo3(name = "gaussian_exponential_periodic_1d")]
+#[allow(clippy::too_many_arguments)]
+pub fn decay_gaussian_exponential_periodic_1d(
+    py: Python,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+    irf_center: f64,
+    irf_width: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    with_warnings(py, || {
+        simulation::decay::gaussian_exponential_periodic_1d(
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+            GaussianIrf {
+                center: irf_center,
+                width: irf_width,
+            },
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional Gaussian IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// This function generates a 3-dimensonal Gaussian instrument response function
+/// (IRF) circularly convolved periodic monoexponential or multiexponential decay
+/// curve.
+///
+/// The decay is treated as periodic: fluorescence that has not decayed by the
+/// end of the period is carried over into the next period and the IRF is
+/// circularly convolved, modeling the incomplete decay observed when tau is
+/// comparable to the period:
+///
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+///
+/// where T is the period.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (_i.e._ time interval).
+/// :param taus: An array of lifetimes. For a monoexponential decay curve use a
+///     single tau value and a fractional intensity of 1.0. For a
+///     multiexponential decay curve use two or more tau values, matched with
+///     their respective fractional intensity. The "taus" and "fractions" arrays
+///     must have the same length. Tau values set to 0.0 will be skipped.
+/// :param fractions: An array of fractional intensities for each tau in the "taus"
+///     array. The "fractions" array must be the same length as the "taus" array
+///     and sum to 1.0. Fraction values set to 0.0 will be skipped.
+/// :param total_counts: The total intensity count (_e.g._ photon count) of the
+///     decay curve.
+/// :param irf_center: The temporal position of the IRF peak within the time range.
+/// :param irf_width: The full width at half maximum (FWHM) of the IRF.
+/// :param shape: The row and col shape to broadcast the decay curve into.
+/// :return: The 3-dimensional Gaussian IRF circularly convolved periodic
+///     monoexponential or multiexponential decay curve.
+#[pyfunction]
+#[pyo3(name = "gaussian_exponential_periodic_3d")]
+#[allow(clippy::too_many_arguments)]
+pub fn decay_gaussian_exponential_periodic_3d(
+    py: Python,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+    irf_center: f64,
+    irf_width: f64,
+    shape: (usize, usize),
+) -> PyResult<Bound<PyArray3<f64>>> {
+    with_warnings(py, || {
+        simulation::decay::gaussian_exponential_periodic_3d(
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+            GaussianIrf {
+                center: irf_center,
+                width: irf_width,
+            },
+            shape,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 1-dimensional Gaussian IRF convolved FRET donor decay curve.
+///
+/// This function generates an ideal FRET donor decay curve and convolves it
+/// with a Gaussian instrument response function (IRF). See
+/// "fret_exponential_1d" for the efficiency distribution parameters.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param donor_tau: The unquenched donor lifetime.
+/// :param efficiency: The FRET efficiency (or mean efficiency) of the donors,
+///     must be in [0, 1).
+/// :param donor_only_fraction: The fraction of donors that do not undergo FRET,
+///     must be in [0, 1].
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     decay curve.
+/// :param irf_center: The temporal position of the IRF peak within the time range.
+/// :param irf_width: The full width at half maximum (FWHM) of the IRF.
+/// :param efficiency_sigma: The standard deviation of a Gaussian efficiency
+///     distribution, default = None.
+/// :param second_efficiency: The FRET efficiency of a second donor population,
+///     default = None.
+/// :param second_fraction: The fraction of FRET donors in the second population,
+///     default = None.
+/// :return: The 1-dimensional Gaussian IRF convolved FRET donor decay curve.
+#[pyfunction]
+#[pyo3(name = "gaussian_fret_exponential_1d")]
+#[pyo3(signature = (samples, period, donor_tau, efficiency, donor_only_fraction, total_counts, irf_center, irf_width, efficiency_sigma=None, second_efficiency=None, second_fraction=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn decay_gaussian_fret_exponential_1d(
+    py: Python,
+    samples: usize,
+    period: f64,
+    donor_tau: f64,
+    efficiency: f64,
+    donor_only_fraction: f64,
+    total_counts: f64,
+    irf_center: f64,
+    irf_width: f64,
+    efficiency_sigma: Option<f64>,
+    second_efficiency: Option<f64>,
+    second_fraction: Option<f64>,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    let efficiency = parse_fret_efficiency(
+        efficiency,
+        efficiency_sigma,
+        second_efficiency,
+        second_fraction,
+    )?;
+    simulation::decay::gaussian_fret_exponential_1d(
+        samples,
+        period,
+        Fret {
+            donor_tau,
+            efficiency,
+            donor_only_fraction,
+        },
+        total_counts,
+        GaussianIrf {
+            center: irf_center,
+            width: irf_width,
+        },
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional Gaussian IRF convolved FRET donor decay curve.
+///
+/// This function generates a Gaussian IRF convolved FRET donor decay curve and
+/// broadcasts it into a 3-dimensional image. See "fret_exponential_1d" for the
+/// efficiency distribution parameters.
+///
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (i.e. time interval).
+/// :param donor_tau: The unquenched donor lifetime.
+/// :param efficiency: The FRET efficiency (or mean efficiency) of the donors,
+///     must be in [0, 1).
+/// :param donor_only_fraction: The fraction of donors that do not undergo FRET,
+///     must be in [0, 1].
+/// :param total_counts: The total intensity count (e.g. photon count) of the
+///     decay curve.
+/// :param irf_center: The temporal position of the IRF peak within the time range.
+/// :param irf_width: The full width at half maximum (FWHM) of the IRF.
+/// :param shape: The row and col shape to broadcast the decay curve into.
+/// :param efficiency_sigma: The standard deviation of a Gaussian efficiency
+///     distribution, default = None.
+/// :param second_efficiency: The FRET efficiency of a second donor population,
+///     default = None.
+/// :param second_fraction: The fraction of FRET donors in the second population,
+///     default = None.
+/// :return: The 3-dimensional Gaussian IRF convolved FRET donor decay curve.
+#[pyfunction]
+#[pyo3(name = "gaussian_fret_exponential_3d")]
+#[pyo3(signature = (samples, period, donor_tau, efficiency, donor_only_fraction, total_counts, irf_center, irf_width, shape, efficiency_sigma=None, second_efficiency=None, second_fraction=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn decay_gaussian_fret_exponential_3d(
+    py: Python,
+    samples: usize,
+    period: f64,
+    donor_tau: f64,
+    efficiency: f64,
+    donor_only_fraction: f64,
+    total_counts: f64,
+    irf_center: f64,
+    irf_width: f64,
+    shape: (usize, usize),
+    efficiency_sigma: Option<f64>,
+    second_efficiency: Option<f64>,
+    second_fraction: Option<f64>,
+) -> PyResult<Bound<PyArray3<f64>>> {
+    let efficiency = parse_fret_efficiency(
+        efficiency,
+        efficiency_sigma,
+        second_efficiency,
+        second_fraction,
+    )?;
+    simulation::decay::gaussian_fret_exponential_3d(
         samples,
         period,
-        &taus,
-        &fractions,
+        Fret {
+            donor_tau,
+            efficiency,
+            donor_only_fraction,
+        },
         total_counts,
-        irf_center,
-        irf_width,
+        GaussianIrf {
+            center: irf_center,
+            width: irf_width,
+        },
         shape,
     )
     .map(|output| output.into_pyarray(py))
-    .map_err(map_array_error)
+    .map_err(map_imgal_error)
 }
 
 /// Simulate an ideal 1-dimensional monoexponential or multiexponential decay
@@ -148,9 +851,11 @@ pub fn decay_ideal_exponential_1d(
     fractions: Vec<f64>,
     total_counts: f64,
 ) -> PyResult<Bound<PyArray1<f64>>> {
-    simulation::decay::ideal_exponential_1d(samples, period, &taus, &fractions, total_counts)
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+    with_warnings(py, || {
+        simulation::decay::ideal_exponential_1d(samples, period, &taus, &fractions, total_counts)
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
 }
 
 /// Simulate an ideal 3-dimensional monoexponential or multiexponential decay
@@ -193,22 +898,31 @@ pub fn decay_ideal_exponential_3d(
     total_counts: f64,
     shape: (usize, usize),
 ) -> PyResult<Bound<PyArray3<f64>>> {
-    simulation::decay::ideal_exponential_3d(samples, period, &taus, &fractions, total_counts, shape)
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+    with_warnings(py, || {
+        simulation::decay::ideal_exponential_3d(
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+            shape,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
 }
 
-/// Simulate a 1-dimensional IRF convolved monoexponential or multiexponential
+/// Simulate an ideal 1-dimensional periodic monoexponential or multiexponential
 /// decay curve.
 ///
-/// This function generates a 1-dimensonal instrument response function (IRF)
-/// convolved monoexponential or multiexponential decay curve. The ideal
-/// decay curve is defined as the sum of one or more exponential components,
-/// each characterized by a lifetime (tau) and fractional intensity:
+/// This function generates a 1-dimensonal ideal exponential decay curve that
+/// includes the incomplete decay tails of all previous excitation periods:
 ///
-/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+///
+/// where αᵢ are the pre-exponential factors derived from the fractional
+/// intensities and lifetimes, and T is the period.
 ///
-/// :param irf: The IRF as a 1-dimensonal array.
 /// :param samples: The number of discrete points that make up the decay curve.
 /// :param period: The period (_i.e._ time interval).
 /// :param taus: An array of lifetimes. For a monoexponential decay curve use a
@@ -221,25 +935,82 @@ pub fn decay_ideal_exponential_3d(
 ///     and sum to 1.0. Fraction values set to 0.0 will be skipped.
 /// :param total_counts: The total intensity count (_e.g._ photon count) of the
 ///     decay curve.
-/// :return: The 1-dimensional IRF convolved monoexponential or
-///     multiexponential decay curve.
+/// :return: The 1-dimensonal periodic monoexponential or multiexponential
+///     decay curve.
 #[pyfunction]
-#[pyo3(name = "irf_exponential_1d")]
-pub fn decay_irf_exponential_1d(
+#[pyo3(name = "ideal_exponential_periodic_1d")]
+pub fn decay_ideal_exponential_periodic_1d(
     py: Python,
-    irf: Vec<f64>,
     samples: usize,
     period: f64,
     taus: Vec<f64>,
     fractions: Vec<f64>,
     total_counts: f64,
 ) -> PyResult<Bound<PyArray1<f64>>> {
-    simulation::decay::irf_exponential_1d(&irf, samples, period, &taus, &fractions, total_counts)
-        .map(|output| output.into_pyarray(py))
-        .map_err(map_array_error)
+    with_warnings(py, || {
+        simulation::decay::ideal_exponential_periodic_1d(
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
 }
 
-/// Simulate a 3-dimensional IRF convolved monoexponential or multiexponential
+/// Simulate a 1-dimensional IRF convolved monoexponential or multiexponential
+/// decay curve.
+///
+/// This function generates a 1-dimensonal instrument response function (IRF)
+/// convolved monoexponential or multiexponential decay curve. The ideal
+/// decay curve is defined as the sum of one or more exponential components,
+/// each characterized by a lifetime (tau) and fractional intensity:
+///
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ)
+///
+/// :param irf: The IRF as a 1-dimensonal array.
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (_i.e._ time interval).
+/// :param taus: An array of lifetimes. For a monoexponential decay curve use a
+///     single tau value and a fractional intensity of 1.0. For a
+///     multiexponential decay curve use two or more tau values, matched with
+///     their respective fractional intensity. The "taus" and "fractions" arrays
+///     must have the same length. Tau values set to 0.0 will be skipped.
+/// :param fractions: An array of fractional intensities for each tau in the "taus"
+///     array. The "fractions" array must be the same length as the "taus" array
+///     and sum to 1.0. Fraction values set to 0.0 will be skipped.
+/// :param total_counts: The total intensity count (_e.g._ photon count) of the
+///     decay curve.
+/// :return: The 1-dimensional IRF convolved monoexponential or
+///     multiexponential decay curve.
+#[pyfunction]
+#[pyo3(name = "irf_exponential_1d")]
+pub fn decay_irf_exponential_1d(
+    py: Python,
+    irf: Vec<f64>,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    with_warnings(py, || {
+        simulation::decay::irf_exponential_1d(
+            &irf,
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional IRF convolved monoexponential or multiexponential
 /// decay curve.
 ///
 /// This function generates a 3-dimensonal instrument response function (IRF)
@@ -267,6 +1038,7 @@ pub fn decay_irf_exponential_1d(
 ///     multiexponential decay curve.
 #[pyfunction]
 #[pyo3(name = "irf_exponential_3d")]
+#[allow(clippy::too_many_arguments)]
 pub fn decay_irf_exponential_3d(
     py: Python,
     irf: Vec<f64>,
@@ -277,17 +1049,378 @@ pub fn decay_irf_exponential_3d(
     total_counts: f64,
     shape: (usize, usize),
 ) -> PyResult<Bound<PyArray3<f64>>> {
-    simulation::decay::irf_exponential_3d(
-        &irf,
-        samples,
-        period,
-        &taus,
-        &fractions,
-        total_counts,
+    with_warnings(py, || {
+        simulation::decay::irf_exponential_3d(
+            &irf,
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+            shape,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 1-dimensional IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// This function generates a 1-dimensonal instrument response function (IRF)
+/// circularly convolved periodic monoexponential or multiexponential decay
+/// curve.
+///
+/// The decay is treated as periodic: fluorescence that has not decayed by the
+/// end of the period is carried over into the next period and the IRF is
+/// circularly convolved, modeling the incomplete decay observed when tau is
+/// comparable to the period:
+///
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+///
+/// where T is the period.
+///
+/// :param irf: The IRF as a 1-dimensonal array. IRFs longer than "samples" are
+///     wrapped into one period.
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (_i.e._ time interval).
+/// :param taus: An array of lifetimes. For a monoexponential decay curve use a
+///     single tau value and a fractional intensity of 1.0. For a
+///     multiexponential decay curve use two or more tau values, matched with
+///     their respective fractional intensity. The "taus" and "fractions" arrays
+///     must have the same length. Tau values set to 0.0 will be skipped.
+/// :param fractions: An array of fractional intensities for each tau in the "taus"
+///     array. The "fractions" array must be the same length as the "taus" array
+///     and sum to 1.0. Fraction values set to 0.0 will be skipped.
+/// :param total_counts: The total intensity count (_e.g._ photon count) of the
+///     decay curve.
+/// :return: The 1-dimensional IRF circularly convolved periodic
+///     monoexponential or multiexponential decay curve.
+#[pyfunction]
+#[pyo3(name = "irf_exponential_periodic_1d")]
+pub fn decay_irf_exponential_periodic_1d(
+    py: Python,
+    irf: Vec<f64>,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    with_warnings(py, || {
+        simulation::decay::irf_exponential_periodic_1d(
+            &irf,
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional IRF convolved periodic monoexponential or
+/// multiexponential decay curve.
+///
+/// This function generates a 3-dimensonal instrument response function (IRF)
+/// circularly convolved periodic monoexponential or multiexponential decay
+/// curve.
+///
+/// The decay is treated as periodic: fluorescence that has not decayed by the
+/// end of the period is carried over into the next period and the IRF is
+/// circularly convolved, modeling the incomplete decay observed when tau is
+/// comparable to the period:
+///
+/// I(t) = Σᵢ αᵢ × exp(-t/τᵢ) / (1 - exp(-T/τᵢ))
+///
+/// where T is the period.
+///
+/// :param irf: The IRF as a 1-dimensonal array. IRFs longer than "samples" are
+///     wrapped into one period.
+/// :param samples: The number of discrete points that make up the decay curve.
+/// :param period: The period (_i.e._ time interval).
+/// :param taus: An array of lifetimes. For a monoexponential decay curve use a
+///     single tau value and a fractional intensity of 1.0. For a
+///     multiexponential decay curve use two or more tau values, matched with
+///     their respective fractional intensity. The "taus" and "fractions" arrays
+///     must have the same length. Tau values set to 0.0 will be skipped.
+/// :param fractions: An array of fractional intensities for each tau in the "taus"
+///     array. The "fractions" array must be the same length as the "taus" array
+///     and sum to 1.0. Fraction values set to 0.0 will be skipped.
+/// :param total_counts: The total intensity count (_e.g._ photon count) of the
+///     decay curve.
+/// :param shape: The row and col shape to broadcast the decay curve into.
+/// :return: The 3-dimensional IRF circularly convolved periodic
+///     monoexponential or multiexponential decay curve.
+#[pyfunction]
+#[pyo3(name = "irf_exponential_periodic_3d")]
+#[allow(clippy::too_many_arguments)]
+pub fn decay_irf_exponential_periodic_3d(
+    py: Python,
+    irf: Vec<f64>,
+    samples: usize,
+    period: f64,
+    taus: Vec<f64>,
+    fractions: Vec<f64>,
+    total_counts: f64,
+    shape: (usize, usize),
+) -> PyResult<Bound<PyArray3<f64>>> {
+    with_warnings(py, || {
+        simulation::decay::irf_exponential_periodic_3d(
+            &irf,
+            samples,
+            period,
+            &taus,
+            &fractions,
+            total_counts,
+            shape,
+        )
+    })?
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Sample photon arrival times from a 1-dimensional decay curve.
+///
+/// This function treats the input decay curve (e.g. an IRF convolved decay)
+/// as a probability distribution over its time bins and draws exactly
+/// "photons" photon arrival times from it. Each photon is assigned to a bin with
+/// probability proportional to the bin value and its arrival time is drawn
+/// uniformly within the bin. Negative bin values are treated as 0.0.
+///
+/// :param data: The decay curve to sample from.
+/// :param period: The period (i.e. time interval) spanned by "data".
+/// :param photons: The exact number of photons to draw.
+/// :param seed: Pseudorandom number generator seed, default = 0.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :return: The sorted photon arrival times in the range [0.0, period).
+#[pyfunction]
+#[pyo3(name = "sample_photons")]
+#[pyo3(signature = (data, period, photons, seed=None))]
+pub fn decay_sample_photons<'py>(
+    py: Python<'py>,
+    data: Vec<f64>,
+    period: f64,
+    photons: usize,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    simulation::decay::sample_photons(&data, period, photons, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Sample a 1-dimensional photon histogram from a decay curve.
+///
+/// This function treats the input decay curve (e.g. an IRF convolved decay)
+/// as a probability distribution over its time bins and draws exactly
+/// "photons" photons from it, returning the resulting photon count histogram.
+/// Unlike applying Poisson noise to a scaled decay curve, the total photon
+/// count is exact and the histogram follows a multinomial distribution.
+/// Negative bin values are treated as 0.0.
+///
+/// :param data: The decay curve to sample from.
+/// :param photons: The exact number of photons to draw.
+/// :param seed: Pseudorandom number generator seed, default = 0.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :return: The photon count histogram with the same length as "data" and a
+///     sum equal to "photons".
+#[pyfunction]
+#[pyo3(name = "sample_photons_1d")]
+#[pyo3(signature = (data, photons, seed=None))]
+pub fn decay_sample_photons_1d<'py>(
+    py: Python<'py>,
+    data: Vec<f64>,
+    photons: usize,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    simulation::decay::sample_photons_1d(&data, photons, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Sample a 3-dimensional photon histogram image from a decay curve.
+///
+/// This function draws exactly "photons" photons per pixel from the input
+/// decay curve, treated as a probability distribution over its time bins.
+/// Each pixel is sampled independently.
+///
+/// :param data: The decay curve to sample from.
+/// :param photons: The exact number of photons to draw per pixel.
+/// :param shape: The row and col shape of the output image.
+/// :param seed: Pseudorandom number generator seed. If set, each pixel is
+///     sampled with a generator seeded from "seed" and the pixel index, giving
+///     reproducible output.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :return: The 3-dimensional (row, col, t) photon count histogram image,
+///     where each pixel sums to "photons".
+#[pyfunction]
+#[pyo3(name = "sample_photons_3d")]
+#[pyo3(signature = (data, photons, shape, seed=None))]
+pub fn decay_sample_photons_3d<'py>(
+    py: Python<'py>,
+    data: Vec<f64>,
+    photons: usize,
+    shape: (usize, usize),
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    simulation::decay::sample_photons_3d(&data, photons, shape, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 2-dimensional Born–Wolf (i.e. Airy) point spread function (PSF).
+///
+/// This function creates the in-focus scalar diffraction PSF of a circular
+/// aperture, the Airy pattern:
+///
+/// I(r) = (2 × J₁(v) / v)², v = 2π × NA × r / λ
+///
+/// The PSF is centered in the image and normalized to sum to 1.0.
+///
+/// :param shape: The row and col shape of the PSF image.
+/// :param pixel_size: The lateral pixel size.
+/// :param wavelength: The emission wavelength, in the same unit as "pixel_size".
+/// :param na: The numerical aperture of the objective.
+/// :return: The normalized 2-dimensional PSF.
+#[pyfunction]
+#[pyo3(name = "born_wolf_psf_2d")]
+pub fn instrument_born_wolf_psf_2d(
+    py: Python,
+    shape: (usize, usize),
+    pixel_size: f64,
+    wavelength: f64,
+    na: f64,
+) -> PyResult<Bound<PyArray2<f64>>> {
+    simulation::instrument::born_wolf_psf_2d(shape, pixel_size, wavelength, na)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional Born–Wolf point spread function (PSF).
+///
+/// This function creates the scalar diffraction PSF of a circular aperture
+/// with paraxial defocus, integrating over the normalized pupil radius ρ:
+///
+/// I(r, z) = |∫₀¹ J₀(k × NA × r × ρ) × exp(-½ × i × k × z × NA² × ρ² / n) × ρ dρ|²
+///
+/// The PSF is centered in the volume and normalized to sum to 1.0.
+///
+/// :param shape: The pln, row and col shape of the PSF volume.
+/// :param pixel_size: The lateral pixel size.
+/// :param z_step: The axial distance between planes.
+/// :param wavelength: The emission wavelength, in the same unit as "pixel_size".
+/// :param na: The numerical aperture of the objective.
+/// :param refractive_index: The refractive index of the immersion medium.
+/// :return: The normalized 3-dimensional PSF with shape (pln, row, col).
+#[pyfunction]
+#[pyo3(name = "born_wolf_psf_3d")]
+pub fn instrument_born_wolf_psf_3d(
+    py: Python,
+    shape: (usize, usize, usize),
+    pixel_size: f64,
+    z_step: f64,
+    wavelength: f64,
+    na: f64,
+    refractive_index: f64,
+) -> PyResult<Bound<PyArray3<f64>>> {
+    simulation::instrument::born_wolf_psf_3d(
         shape,
+        pixel_size,
+        z_step,
+        wavelength,
+        na,
+        refractive_index,
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 1-dimensional double peak instrument response function (IRF).
+///
+/// This function creates an IRF with a primary Gaussian peak and a secondary
+/// Gaussian peak (e.g. an optical reflection artifact) of the same width.
+/// The secondary peak is positioned "peak_offset" after the primary peak and
+/// scaled by "peak_ratio" relative to the primary peak before the combined
+/// curve is normalized to sum to 1.0:
+///
+/// IRF(t) = G(t, c) + r × G(t, c + Δ)
+///
+/// Where "G" is the Gaussian IRF, "c" is the IRF center, "r" is the peak ratio
+/// and "Δ" is the peak offset.
+///
+/// :param bins: The number of discrete points to sample the IRF.
+/// :param time_range: The total time range over which to simulate the IRF.
+/// :param irf_center: The temporal position of the primary IRF peak within the
+///     time range.
+/// :param irf_width: The full width at half maximum (FWHM) of both IRF peaks.
+/// :param peak_offset: The temporal offset of the secondary peak relative to
+///     the primary peak.
+/// :param peak_ratio: The amplitude of the secondary peak relative to the
+///     primary peak.
+/// :return: The simulated 1-dimensional double peak IRF curve.
+#[pyfunction]
+#[pyo3(name = "double_peak_irf_1d")]
+pub fn instrument_double_peak_irf_1d(
+    py: Python,
+    bins: usize,
+    time_range: f64,
+    irf_center: f64,
+    irf_width: f64,
+    peak_offset: f64,
+    peak_ratio: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    simulation::instrument::double_peak_irf_1d(
+        bins,
+        time_range,
+        irf_center,
+        irf_width,
+        peak_offset,
+        peak_ratio,
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 1-dimensional exponentially modified Gaussian instrument response
+/// function (IRF).
+///
+/// This function creates an asymmetric IRF by convolving a Gaussian IRF with
+/// an exponential decay tail, producing the exponentially modified Gaussian
+/// (EMG) shape typical of detectors with a slow diffusion tail (e.g. PMTs and
+/// SPADs):
+///
+/// IRF(t) = G(t) ⊛ exp(-t/τ)
+///
+/// Where "G" is the Gaussian IRF and "τ" is the tail time constant. The
+/// resulting IRF is normalized to sum to 1.0.
+///
+/// :param bins: The number of discrete points to sample the IRF.
+/// :param time_range: The total time range over which to simulate the IRF.
+/// :param irf_center: The temporal position of the Gaussian component peak
+///     within the time range.
+/// :param irf_width: The full width at half maximum (FWHM) of the Gaussian
+///     component.
+/// :param tail_tau: The time constant of the exponential tail. If "tail_tau"
+///     is 0.0, the Gaussian IRF is returned.
+/// :return: The simulated 1-dimensional exponentially modified Gaussian IRF
+///     curve.
+#[pyfunction]
+#[pyo3(name = "exponentially_modified_gaussian_irf_1d")]
+pub fn instrument_exponentially_modified_gaussian_irf_1d(
+    py: Python,
+    bins: usize,
+    time_range: f64,
+    irf_center: f64,
+    irf_width: f64,
+    tail_tau: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    simulation::instrument::exponentially_modified_gaussian_irf_1d(
+        bins, time_range, irf_center, irf_width, tail_tau,
     )
     .map(|output| output.into_pyarray(py))
-    .map_err(map_array_error)
+    .map_err(map_imgal_error)
 }
 
 /// Simulate a 1-dimensional Gaussian instruement response function (IRF).
@@ -314,8 +1447,220 @@ pub fn instrument_gaussian_irf_1d(
     irf_center: f64,
     irf_width: f64,
 ) -> PyResult<Bound<PyArray1<f64>>> {
-    let output = simulation::instrument::gaussian_irf_1d(bins, time_range, irf_center, irf_width);
-    Ok(output.into_pyarray(py))
+    simulation::instrument::gaussian_irf_1d(bins, time_range, irf_center, irf_width)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 2-dimensional Gaussian point spread function (PSF).
+///
+/// This function creates a Gaussian approximation of the in-focus widefield
+/// PSF with the lateral standard deviation σ_xy = 0.21 × λ / NA. The PSF is
+/// centered in the image and normalized to sum to 1.0.
+///
+/// :param shape: The row and col shape of the PSF image.
+/// :param pixel_size: The lateral pixel size.
+/// :param wavelength: The emission wavelength, in the same unit as "pixel_size".
+/// :param na: The numerical aperture of the objective.
+/// :return: The normalized 2-dimensional PSF.
+#[pyfunction]
+#[pyo3(name = "gaussian_psf_2d")]
+pub fn instrument_gaussian_psf_2d(
+    py: Python,
+    shape: (usize, usize),
+    pixel_size: f64,
+    wavelength: f64,
+    na: f64,
+) -> PyResult<Bound<PyArray2<f64>>> {
+    simulation::instrument::gaussian_psf_2d(shape, pixel_size, wavelength, na)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional Gaussian point spread function (PSF).
+///
+/// This function creates a Gaussian approximation of the widefield PSF with
+/// the lateral and axial standard deviations:
+///
+/// σ_xy = 0.21 × λ / NA
+/// σ_z  = 0.66 × λ × n / NA²
+///
+/// The PSF is centered in the volume and normalized to sum to 1.0.
+///
+/// :param shape: The pln, row and col shape of the PSF volume.
+/// :param pixel_size: The lateral pixel size.
+/// :param z_step: The axial distance between planes.
+/// :param wavelength: The emission wavelength, in the same unit as "pixel_size".
+/// :param na: The numerical aperture of the objective.
+/// :param refractive_index: The refractive index of the immersion medium.
+/// :return: The normalized 3-dimensional PSF with shape (pln, row, col).
+#[pyfunction]
+#[pyo3(name = "gaussian_psf_3d")]
+pub fn instrument_gaussian_psf_3d(
+    py: Python,
+    shape: (usize, usize, usize),
+    pixel_size: f64,
+    z_step: f64,
+    wavelength: f64,
+    na: f64,
+    refractive_index: f64,
+) -> PyResult<Bound<PyArray3<f64>>> {
+    simulation::instrument::gaussian_psf_3d(
+        shape,
+        pixel_size,
+        z_step,
+        wavelength,
+        na,
+        refractive_index,
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional Gibson–Lanni point spread function (PSF).
+///
+/// This function creates the scalar diffraction PSF of an objective imaging a
+/// point source below the coverslip, including the spherical aberration caused
+/// by refractive index mismatch between the sample and the immersion medium.
+/// The PSF is centered in the volume and normalized to sum to 1.0. Unset
+/// objective and sample parameters default to an oil immersion objective
+/// imaging an aqueous sample at the coverslip.
+///
+/// :param shape: The pln, row and col shape of the PSF volume.
+/// :param pixel_size: The lateral pixel size.
+/// :param z_step: The axial distance between planes.
+/// :param wavelength: The emission wavelength, in the same unit as "pixel_size".
+/// :param na: The numerical aperture of the objective.
+/// :param sample_ri: The refractive index of the sample, default = 1.33.
+/// :param immersion_ri: The refractive index of the immersion medium,
+///     default = 1.515.
+/// :param immersion_ri_design: The design refractive index of the immersion
+///     medium, default = 1.515.
+/// :param working_distance: The design working distance, default = 150.0.
+/// :param particle_depth: The depth of the point source below the coverslip,
+///     default = 0.0.
+/// :return: The normalized 3-dimensional PSF with shape (pln, row, col).
+#[pyfunction]
+#[pyo3(name = "gibson_lanni_psf_3d")]
+#[pyo3(signature = (shape, pixel_size, z_step, wavelength, na, sample_ri=None, immersion_ri=None, immersion_ri_design=None, working_distance=None, particle_depth=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn instrument_gibson_lanni_psf_3d(
+    py: Python,
+    shape: (usize, usize, usize),
+    pixel_size: f64,
+    z_step: f64,
+    wavelength: f64,
+    na: f64,
+    sample_ri: Option<f64>,
+    immersion_ri: Option<f64>,
+    immersion_ri_design: Option<f64>,
+    working_distance: Option<f64>,
+    particle_depth: Option<f64>,
+) -> PyResult<Bound<PyArray3<f64>>> {
+    // set optional parameters if needed
+    let d = GibsonLanni::default();
+    let params = GibsonLanni {
+        sample_ri: sample_ri.unwrap_or(d.sample_ri),
+        immersion_ri: immersion_ri.unwrap_or(d.immersion_ri),
+        immersion_ri_design: immersion_ri_design.unwrap_or(d.immersion_ri_design),
+        working_distance: working_distance.unwrap_or(d.working_distance),
+        particle_depth: particle_depth.unwrap_or(d.particle_depth),
+    };
+    simulation::instrument::gibson_lanni_psf_3d(shape, pixel_size, z_step, wavelength, na, params)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Create a 1-dimensional instrument response function (IRF) from measured data.
+///
+/// This function resamples a measured IRF (e.g. the decay histogram of a
+/// scattering sample) to the requested number of bins using linear
+/// interpolation. Negative values (e.g. from background subtraction) are
+/// clamped to 0.0 and the resampled IRF is normalized to sum to 1.0.
+///
+/// :param data: The measured IRF, spanning the same time range as the
+///     simulation. Must contain at least 2 points.
+/// :param bins: The number of discrete points of the resampled IRF.
+/// :return: The resampled and normalized 1-dimensional IRF curve.
+#[pyfunction]
+#[pyo3(name = "measured_irf_1d")]
+pub fn instrument_measured_irf_1d(
+    py: Python,
+    data: Vec<f64>,
+    bins: usize,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    simulation::instrument::measured_irf_1d(&data, bins)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate Poisson noise on an n-dimensional array.
+///
+/// This function applies Poisson noise (i.e. shot noise) on an n-dimensional
+/// array of data. An element-wise lambda value (scaled by the "scale" parameter)
+/// is used to simulate Poisson noise with variable signal strength.
+///
+/// The output array has the same dtype as the input array, e.g. a u16
+/// acquisition stays u16 (bool arrays are returned as u8). Noisy values are
+/// rounded and saturated at the bounds of integer dtypes.
+///
+/// :param data: The input n-dimensional array.
+/// :param scale: The scale factor.
+/// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
+///     homogenous noise to the input array. If "None", then heterogenous noise
+///     is applied to the input array.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :param axis: The signal data axis, default = the last axis.
+/// :return: An n-dimensional array of the input data with Poisson noise
+///     applied.
+#[pyfunction]
+#[pyo3(name = "poisson")]
+#[pyo3(signature = (data, scale, seed=None, axis=None))]
+pub fn noise_poisson<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    scale: f64,
+    seed: Option<PySeed<'py>>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyAny>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        simulation::noise::poisson(arr.as_array(), scale, rng_seed(seed), axis)
+            .map(|output| output.into_pyarray(py).into_any())
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Simulate Poisson noise on an n-dimensional array.
+///
+/// This function applies Poisson noise (i.e. shot noise) on an n-dimensional
+/// array of data. An element-wise lambda value (scaled by the "scale" parameter)
+/// is used to simulate Poisson noise with variable signal strength. Noisy
+/// values are rounded and saturated at the bounds of integer dtypes.
+///
+/// This function mutates the input array and does not create a new array.
+///
+/// :param data: The input n-dimensional array to mutate.
+/// :param scale: The scale factor.
+/// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
+///     homogenous noise to the input array. If "None", then heterogenous noise
+///     is applied to the input array.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :param axis: The signal data axis, default = the last axis.
+#[pyfunction]
+#[pyo3(name = "poisson_mut")]
+#[pyo3(signature = (data, scale, seed=None, axis=None))]
+pub fn noise_poisson_mut<'py>(
+    data: Bound<'py, PyAny>,
+    scale: f64,
+    seed: Option<PySeed<'py>>,
+    axis: Option<usize>,
+) -> PyResult<()> {
+    // pattern match and extract allowed array types
+    dispatch_dtype_mut!(data, PyReadwriteArrayDyn::<T>, |mut arr| {
+        simulation::noise::poisson_mut(arr.as_array_mut(), scale, rng_seed(seed), axis)
+            .map_err(map_imgal_error)
+    })
 }
 
 /// Simulate Poisson noise on a 1-dimensional array.
@@ -331,6 +1676,7 @@ pub fn instrument_gaussian_irf_1d(
 /// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
 ///     homogenous noise to the input array. If "None", then heterogenous noise
 ///     is applied to the input array.
+///     Pass an "Rng" context to draw its next stream instead.
 /// :return: A 1-dimensonal array of the input data with Poisson noise applied.
 #[pyfunction]
 #[pyo3(name = "poisson_1d")]
@@ -339,26 +1685,14 @@ pub fn noise_poisson_1d<'py>(
     py: Python<'py>,
     data: Bound<'py, PyAny>,
     scale: f64,
-    seed: Option<u64>,
+    seed: Option<PySeed<'py>>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray1<u8>>() {
-        let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray1<u16>>() {
-        let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray1<f32>>() {
-        let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray1<f64>>() {
-        let output = simulation::noise::poisson_1d(arr.as_slice().unwrap(), scale, seed);
-        return Ok(output.into_pyarray(py));
-    } else {
-        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+    dispatch_dtype!(data, PyReadonlyArray1::<T>, |arr| {
+        simulation::noise::poisson_1d(&contiguous(arr.as_array()), scale, rng_seed(seed))
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
 }
 
 /// Simulate Poisson noise on a 1-dimensional array.
@@ -374,13 +1708,20 @@ pub fn noise_poisson_1d<'py>(
 /// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
 ///     homogenous noise to the input array. If "None", then heterogenous noise
 ///     is applied to the input array.
+///     Pass an "Rng" context to draw its next stream instead.
 #[pyfunction]
 #[pyo3(name = "poisson_1d_mut")]
 #[pyo3(signature= (data, scale, seed=None))]
-pub fn noise_poisson_1d_mut(mut data: PyReadwriteArray1<f64>, scale: f64, seed: Option<u64>) {
-    // get mutable slice, all 1D arrays are contiguous
-    let d = data.as_slice_mut().unwrap();
-    simulation::noise::poisson_1d_mut(d, scale, seed);
+pub fn noise_poisson_1d_mut<'py>(
+    mut data: PyReadwriteArray1<f64>,
+    scale: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<()> {
+    // strided views are mutated through a contiguous copy
+    with_slice_mut(data.as_array_mut(), |d| {
+        simulation::noise::poisson_1d_mut(d, scale, rng_seed(seed))
+    })
+    .map_err(map_imgal_error)
 }
 
 /// Simulate Poisson noise on a 3-dimensional array.
@@ -397,6 +1738,7 @@ pub fn noise_poisson_1d_mut(mut data: PyReadwriteArray1<f64>, scale: f64, seed:
 /// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
 ///     homogenous noise to the input array. If "None", then heterogenous noise
 ///     is applied to the input array.
+///     Pass an "Rng" context to draw its next stream instead.
 /// :param axis: The signal data axis, default = 2.
 /// :return: A 3-dimensional array of the input data with Poisson noise
 ///     applied.
@@ -407,38 +1749,24 @@ pub fn noise_poisson_3d<'py>(
     py: Python<'py>,
     data: Bound<'py, PyAny>,
     scale: f64,
-    seed: Option<u64>,
+    seed: Option<PySeed<'py>>,
     axis: Option<usize>,
 ) -> PyResult<Bound<'py, PyArray3<f64>>> {
     // pattern match and extract allowed array types
-    if let Ok(arr) = data.extract::<PyReadonlyArray3<u8>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<u16>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f32>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
-            .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else if let Ok(arr) = data.extract::<PyReadonlyArray3<f64>>() {
-        simulation::noise::poisson_3d(arr.as_array(), scale, seed, axis)
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        simulation::noise::poisson_3d(arr.as_array(), scale, rng_seed(seed), axis)
             .map(|output| output.into_pyarray(py))
-            .map_err(map_array_error)
-    } else {
-        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+            .map_err(map_imgal_error)
+    })
 }
 
 /// Simulate Poisson noise on a 3-dimensional array.
 ///
 /// This function applies Poisson noise (i.e. shot noise) on a 3-dimensional
 /// array of data. An element-wise lambda value (scaled by the "scale" parameter)
-/// is used to simulate Poisson noise with variable signal strength.
+/// is used to simulate Poisson noise with variable signal strength. The array
+/// keeps its dtype, noisy values are rounded and saturated at the bounds of
+/// integer dtypes.
 ///
 /// This function mutates the input array and does not create a new array.
 ///
@@ -447,16 +1775,411 @@ pub fn noise_poisson_3d<'py>(
 /// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
 ///     homogenous noise to the input array. If "None", then heterogenous noise
 ///     is applied to the input array.
+///     Pass an "Rng" context to draw its next stream instead.
 /// :param axis: The signal data axis, default = 2.
 #[pyfunction]
 #[pyo3(name = "poisson_3d_mut")]
 #[pyo3(signature = (data, scale, seed=None, axis=None))]
-pub fn noise_poisson_3d_mut(
-    mut data: PyReadwriteArray3<f64>,
+pub fn noise_poisson_3d_mut<'py>(
+    data: Bound<'py, PyAny>,
     scale: f64,
-    seed: Option<u64>,
+    seed: Option<PySeed<'py>>,
+    axis: Option<usize>,
+) -> PyResult<()> {
+    // pattern match and extract allowed array types
+    dispatch_dtype_mut!(data, PyReadwriteArray3::<T>, |mut arr| {
+        simulation::noise::poisson_3d_mut(arr.as_array_mut(), scale, rng_seed(seed), axis)
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Simulate a 2-dimensional image of randomly placed Gaussian blobs.
+///
+/// This function places round(density × rows × cols) Gaussian blobs at
+/// uniformly random positions. Each blob has a peak value of 1.0 and
+/// overlapping blobs are summed.
+///
+/// :param shape: The row and col shape of the image.
+/// :param density: The number of blobs per pixel, e.g. 0.001 places 10 blobs
+///     in a 100 × 100 image.
+/// :param sigma: The standard deviation (i.e. size) of each blob, in pixels.
+/// :param seed: Pseudorandom number generator seed, default = 0.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :return: The 2-dimensional blob image.
+#[pyfunction]
+#[pyo3(name = "blobs_2d")]
+#[pyo3(signature = (shape, density, sigma, seed=None))]
+pub fn pattern_blobs_2d<'py>(
+    py: Python<'py>,
+    shape: (usize, usize),
+    density: f64,
+    sigma: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    simulation::pattern::blobs_2d(shape, density, sigma, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 2-dimensional checkerboard pattern.
+///
+/// This function creates a checkerboard of alternating square tiles with
+/// values of 0.0 and 1.0. The top left tile has a value of 0.0.
+///
+/// :param shape: The row and col shape of the image.
+/// :param square_size: The side length of each square tile, in pixels.
+/// :return: The 2-dimensional checkerboard image.
+#[pyfunction]
+#[pyo3(name = "checkerboard_2d")]
+pub fn pattern_checkerboard_2d(
+    py: Python,
+    shape: (usize, usize),
+    square_size: usize,
+) -> PyResult<Bound<PyArray2<f64>>> {
+    simulation::pattern::checkerboard_2d(shape, square_size)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 2-dimensional noise field with a power law power spectrum.
+///
+/// This function creates a noise field whose radially averaged power spectrum
+/// follows a power law, P(k) ∝ 1 / k^β, where "k" is the radial spatial
+/// frequency and "β" is the spectral exponent, e.g. 0.0 for white, 1.0 for
+/// pink and 2.0 for brown noise.
+///
+/// :param shape: The row and col shape of the image.
+/// :param exponent: The spectral exponent, β.
+/// :param seed: Pseudorandom number generator seed, default = 0.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :return: The 2-dimensional noise field with zero mean and unit standard
+///     deviation.
+#[pyfunction]
+#[pyo3(name = "power_law_noise_2d")]
+#[pyo3(signature = (shape, exponent, seed=None))]
+pub fn pattern_power_law_noise_2d<'py>(
+    py: Python<'py>,
+    shape: (usize, usize),
+    exponent: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    simulation::pattern::power_law_noise_2d(shape, exponent, rng_seed(seed))
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 2-dimensional sinusoidal grating.
+///
+/// This function creates a sinusoidal grating with values in [0, 1]:
+///
+/// I(r, c) = 0.5 + 0.5 × cos(2π × f × (c × cos(θ) + r × sin(θ)) + φ)
+///
+/// :param shape: The row and col shape of the image.
+/// :param frequency: The spatial frequency in cycles per pixel, in [0, 0.5].
+/// :param angle: The orientation angle of the wave vector, in radians,
+///     default = 0.0 (i.e. vertical stripes).
+/// :param phase: The phase offset, in radians, default = 0.0.
+/// :return: The 2-dimensional grating image.
+#[pyfunction]
+#[pyo3(name = "sinusoidal_grating_2d")]
+#[pyo3(signature = (shape, frequency, angle=None, phase=None))]
+pub fn pattern_sinusoidal_grating_2d(
+    py: Python,
+    shape: (usize, usize),
+    frequency: f64,
+    angle: Option<f64>,
+    phase: Option<f64>,
+) -> PyResult<Bound<PyArray2<f64>>> {
+    simulation::pattern::sinusoidal_grating_2d(shape, frequency, angle, phase)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 1-dimensional Gaussian emission spectrum.
+///
+/// This function creates a Gaussian emission spectrum sampled at "channels"
+/// evenly spaced wavelengths spanning "wavelength_range" (inclusive). The
+/// spectrum is normalized to sum to 1.0.
+///
+/// :param channels: The number of spectral channels.
+/// :param wavelength_range: The first and last channel wavelengths.
+/// :param peak: The emission peak wavelength.
+/// :param width: The full width at half maximum (FWHM) of the emission peak.
+/// :return: The normalized 1-dimensional emission spectrum.
+#[pyfunction]
+#[pyo3(name = "gaussian_spectrum_1d")]
+pub fn spectra_gaussian_spectrum_1d(
+    py: Python,
+    channels: usize,
+    wavelength_range: (f64, f64),
+    peak: f64,
+    width: f64,
+) -> PyResult<Bound<PyArray1<f64>>> {
+    simulation::spectra::gaussian_spectrum_1d(channels, wavelength_range, peak, width)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional set of linear gradient abundance maps.
+///
+/// This function creates abundance maps for "components" spectral components
+/// that vary smoothly along the column axis. Each component peaks at an evenly
+/// spaced column and falls off linearly to zero at its neighbors' peaks, so the
+/// abundances of every pixel sum to 1.0.
+///
+/// :param shape: The row and col shape of the abundance maps.
+/// :param components: The number of spectral components.
+/// :return: The abundance maps with shape (row, col, component).
+#[pyfunction]
+#[pyo3(name = "gradient_abundances_3d")]
+pub fn spectra_gradient_abundances_3d(
+    py: Python,
+    shape: (usize, usize),
+    components: usize,
+) -> Bound<PyArray3<f64>> {
+    simulation::spectra::gradient_abundances_3d(shape, components).into_pyarray(py)
+}
+
+/// Simulate a 3-dimensional hyperspectral image as a linear mixture of spectra.
+///
+/// This function generates a hyperspectral image cube where each pixel is the
+/// linear mixture of the input emission spectra weighted by the pixel's
+/// abundances:
+///
+/// I(x, y, λ) = N × Σₖ aₖ(x, y) × Sₖ(λ)
+///
+/// :param spectra: The emission spectra of each component, each is normalized
+///     to sum to 1.0. All spectra must have the same number of channels.
+/// :param abundances: The abundance maps with shape (row, col, component).
+/// :param total_counts: The total intensity count (e.g. photon count) of a
+///     pixel with abundances that sum to 1.0.
+/// :return: The hyperspectral image with shape (row, col, channel).
+#[pyfunction]
+#[pyo3(name = "linear_mixture_3d")]
+pub fn spectra_linear_mixture_3d<'py>(
+    py: Python<'py>,
+    spectra: Vec<Vec<f64>>,
+    abundances: DLPackArray3<f64>,
+    total_counts: f64,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    simulation::spectra::linear_mixture_3d(&spectra, abundances.as_array(), total_counts)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 3-dimensional hyperspectral image as a linear mixture of spectra
+/// with Poisson noise.
+///
+/// This function generates a hyperspectral image cube as a linear mixture of
+/// the input emission spectra and applies Poisson noise (i.e. shot noise) to
+/// each pixel spectrum.
+///
+/// :param spectra: The emission spectra of each component, each is normalized
+///     to sum to 1.0. All spectra must have the same number of channels.
+/// :param abundances: The abundance maps with shape (row, col, component).
+/// :param total_counts: The total intensity count (e.g. photon count) of a
+///     pixel with abundances that sum to 1.0.
+/// :param seed: Pseudorandom number generator seed. Set the "seed" value to apply
+///     homogenous noise to the image. If "None", then heterogenous noise is
+///     applied to the image.
+///     Pass an "Rng" context to draw its next stream instead.
+/// :return: The noisy hyperspectral image with shape (row, col, channel).
+#[pyfunction]
+#[pyo3(name = "linear_mixture_poisson_3d")]
+#[pyo3(signature = (spectra, abundances, total_counts, seed=None))]
+pub fn spectra_linear_mixture_poisson_3d<'py>(
+    py: Python<'py>,
+    spectra: Vec<Vec<f64>>,
+    abundances: DLPackArray3<f64>,
+    total_counts: f64,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyArray3<f64>>> {
+    simulation::spectra::linear_mixture_poisson_3d(
+        &spectra,
+        abundances.as_array(),
+        total_counts,
+        rng_seed(seed),
+    )
+    .map(|output| output.into_pyarray(py))
+    .map_err(map_imgal_error)
+}
+
+/// Create a 1-dimensional emission spectrum from registered (e.g. measured or
+/// library) spectral data.
+///
+/// This function resamples a registered emission spectrum onto "channels"
+/// evenly spaced wavelengths spanning "wavelength_range" (inclusive) using
+/// linear interpolation. Channels outside of the registered wavelengths are set
+/// to 0.0, negative values are clamped to 0.0 and the spectrum is normalized to
+/// sum to 1.0.
+///
+/// :param data: The registered spectrum intensities.
+/// :param wavelengths: The wavelength of each value in "data", in ascending
+///     order.
+/// :param channels: The number of spectral channels.
+/// :param wavelength_range: The first and last channel wavelengths.
+/// :return: The resampled and normalized 1-dimensional emission spectrum.
+#[pyfunction]
+#[pyo3(name = "registered_spectrum_1d")]
+pub fn spectra_registered_spectrum_1d<'py>(
+    py: Python<'py>,
+    data: Vec<f64>,
+    wavelengths: Vec<f64>,
+    channels: usize,
+    wavelength_range: (f64, f64),
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    simulation::spectra::registered_spectrum_1d(&data, &wavelengths, channels, wavelength_range)
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+}
+
+/// Simulate a 4-dimensional photobleaching time-lapse of a decay image.
+///
+/// This function repeats the input decay image for "frames" time points and
+/// scales the total counts of each frame by an exponential photobleaching
+/// curve:
+///
+/// I(t) = I₀ × exp(-k × t)
+///
+/// :param data: The 3-dimensional decay image of the first frame.
+/// :param frames: The number of time points.
+/// :param bleach_rate: The exponential photobleaching rate per frame. Must be
+///     >= 0.0, a value of 0.0 disables photobleaching.
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The time-lapse with shape (t, row, col, tau).
+#[pyfunction]
+#[pyo3(name = "photobleaching_4d")]
+#[pyo3(signature = (data, frames, bleach_rate, axis=None))]
+pub fn timelapse_photobleaching_4d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    frames: usize,
+    bleach_rate: f64,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyArray4<f64>>> {
+    timelapse_timelapse_4d(py, data, frames, bleach_rate, None, None, axis)
+}
+
+/// Simulate a 4-dimensional photobleaching and drifting time-lapse of a decay
+/// image.
+///
+/// This function repeats the input decay image for "frames" time points with
+/// exponential photobleaching of the total counts and an optional constant
+/// lateral drift per frame. Drifted frames are resampled with bilinear
+/// interpolation and motion blur is simulated by averaging "blur_samples"
+/// positions along the drift path of each frame's exposure:
+///
+/// Iₜ(x) = exp(-k × t) × (1/n) × Σⱼ I₀(x - d × (t + j/n))
+///
+/// :param data: The 3-dimensional decay image of the first frame.
+/// :param frames: The number of time points.
+/// :param bleach_rate: The exponential photobleaching rate per frame. Must be
+///     >= 0.0, a value of 0.0 disables photobleaching.
+/// :param drift: The (row, col) lateral drift in pixels per frame. If "None",
+///     then no drift is applied.
+/// :param blur_samples: The number of positions averaged along the drift path
+///     of each frame to simulate motion blur, default = 1 (i.e. no blur).
+/// :param axis: The decay or lifetime axis, default = 2.
+/// :return: The time-lapse with shape (t, row, col, tau).
+#[pyfunction]
+#[pyo3(name = "timelapse_4d")]
+#[pyo3(signature = (data, frames, bleach_rate, drift=None, blur_samples=None, axis=None))]
+pub fn timelapse_timelapse_4d<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    frames: usize,
+    bleach_rate: f64,
+    drift: Option<(f64, f64)>,
+    blur_samples: Option<usize>,
     axis: Option<usize>,
-) {
-    let arr = data.as_array_mut();
-    simulation::noise::poisson_3d_mut(arr, scale, seed, axis);
+) -> PyResult<Bound<'py, PyArray4<f64>>> {
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArray3::<T>, |arr| {
+        simulation::timelapse::timelapse_4d(
+            arr.as_array(),
+            frames,
+            bleach_rate,
+            drift,
+            blur_samples,
+            axis,
+        )
+        .map(|output| output.into_pyarray(py))
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Build a FRET efficiency distribution from the optional Python parameters.
+fn parse_fret_efficiency(
+    efficiency: f64,
+    efficiency_sigma: Option<f64>,
+    second_efficiency: Option<f64>,
+    second_fraction: Option<f64>,
+) -> PyResult<FretEfficiency> {
+    match (efficiency_sigma, second_efficiency, second_fraction) {
+        (None, None, None) => Ok(FretEfficiency::Single(efficiency)),
+        (Some(sigma), None, None) => Ok(FretEfficiency::Gaussian {
+            mean: efficiency,
+            sigma,
+        }),
+        (None, Some(second), Some(fraction)) => Ok(FretEfficiency::TwoPopulation {
+            first: efficiency,
+            second,
+            second_fraction: fraction,
+        }),
+        _ => Err(PyValueError::new_err(
+            "Invalid FRET efficiency distribution, set either \"efficiency_sigma\" or both \"second_efficiency\" and \"second_fraction\".",
+        )),
+    }
+}
+
+/// A seeded random number generator context for multi-step simulations.
+///
+/// Pass the context as the "seed" of simulation functions to draw the next
+/// independent random stream. Each stream derives one counter-based generator
+/// per lane (e.g. pixel) from the lane index, so a whole multi-step simulation
+/// is exactly reproducible from a single seed, even when run in parallel.
+///
+/// :param seed: The context seed.
+#[pyclass(name = "Rng", module = "imgal.simulation.rng")]
+pub struct SimulationRng {
+    inner: rng::Rng,
+}
+
+#[pymethods]
+impl SimulationRng {
+    #[new]
+    fn new(seed: u64) -> Self {
+        SimulationRng {
+            inner: rng::Rng::new(seed),
+        }
+    }
+
+    /// The context seed.
+    #[getter]
+    fn seed(&self) -> u64 {
+        self.inner.seed()
+    }
+
+    /// The number of streams drawn from the context.
+    #[getter]
+    fn streams(&self) -> u64 {
+        self.inner.streams()
+    }
+}
+
+/// A simulation seed, an integer seed or an Rng context.
+#[derive(FromPyObject)]
+pub enum PySeed<'py> {
+    Int(u64),
+    Rng(Bound<'py, SimulationRng>),
+}
+
+/// Convert an optional Python seed into a simulation seed, an Rng context
+/// draws its next stream.
+pub(crate) fn rng_seed(seed: Option<PySeed>) -> Seed {
+    match seed {
+        None => Seed::Fixed(None),
+        Some(PySeed::Int(s)) => Seed::Fixed(Some(s)),
+        Some(PySeed::Rng(r)) => (&mut r.borrow_mut().inner).into(),
+    }
 }
@@ -1,9 +1,263 @@
-use numpy::{PyReadonlyArrayDyn, PyReadwriteArray1};
-use pyo3::exceptions::PyTypeError;
+use std::sync::Mutex;
+
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2, PyReadonlyArrayDyn, PyReadwriteArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+
+use crate::error::map_imgal_error;
+use crate::functions::simulation_functions::{PySeed, rng_seed};
+use crate::utils::{
+    DLPackArray2, DLPackArrayDyn, dispatch_dtype, dispatch_dtype_mut, with_slice_mut,
+};
+use imgal::statistics::{self, Alternative, Binning, Bootstrap, FrcCriterion};
+
+/// A mergeable accumulator of running statistics.
+///
+/// The accumulator computes the count, mean, variance, skewness, excess
+/// kurtosis, minimum and maximum of a stream of values in a single pass
+/// (Welford's online algorithm). Chunks of a dataset (e.g. the planes of a
+/// large image) can be pushed one at a time, and accumulators over disjoint
+/// values can be merged exactly.
+#[pyclass(name = "RunningStats", module = "imgal.statistics")]
+#[derive(Clone, Default)]
+pub struct RunningStats {
+    inner: statistics::RunningStats,
+}
+
+#[pymethods]
+impl RunningStats {
+    #[new]
+    fn new() -> Self {
+        RunningStats::default()
+    }
+
+    /// Add the (masked) values of an n-dimensional array.
+    ///
+    /// :param data: An n-dimensional array.
+    /// :param mask: An optional boolean mask, values set to "False" are
+    ///     skipped. Must match the shape of "data".
+    #[pyo3(signature = (data, mask=None))]
+    fn push(&mut self, data: Bound<'_, PyAny>, mask: Option<DLPackArrayDyn<bool>>) -> PyResult<()> {
+        let msk = mask.as_ref().map(|m| m.as_array());
+        dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+            self.inner.push_array(arr.as_array(), msk)
+        })
+        .map_err(map_imgal_error)
+    }
+
+    /// Merge the statistics of another accumulator over disjoint values.
+    ///
+    /// :param other: The other accumulator.
+    fn merge(&mut self, other: PyRef<RunningStats>) {
+        self.inner.merge(&other.inner);
+    }
+
+    /// The number of values.
+    #[getter]
+    fn count(&self) -> u64 {
+        self.inner.count()
+    }
+
+    /// The arithmetic mean, NaN if there are no values.
+    #[getter]
+    fn mean(&self) -> f64 {
+        self.inner.mean()
+    }
 
-use crate::error::map_array_error;
-use imgal::statistics;
+    /// The sample variance (n - 1 degrees of freedom).
+    #[getter]
+    fn variance(&self) -> f64 {
+        self.inner.variance()
+    }
+
+    /// The sample standard deviation.
+    #[getter]
+    fn std_dev(&self) -> f64 {
+        self.inner.std_dev()
+    }
+
+    /// The (biased) sample skewness g₁.
+    #[getter]
+    fn skewness(&self) -> f64 {
+        self.inner.skewness()
+    }
+
+    /// The (biased) sample excess kurtosis g₂.
+    #[getter]
+    fn kurtosis(&self) -> f64 {
+        self.inner.kurtosis()
+    }
+
+    /// The minimum value, NaN if there are no values.
+    #[getter]
+    fn min(&self) -> f64 {
+        self.inner.min()
+    }
+
+    /// The maximum value, NaN if there are no values.
+    #[getter]
+    fn max(&self) -> f64 {
+        self.inner.max()
+    }
+}
+
+/// Compute bootstrap confidence intervals of a statistic.
+///
+/// This function estimates the sampling distribution of a statistic by
+/// resampling the data with replacement and computing the statistic of each
+/// resampled data set (i.e. replicate). The percentile interval is given by the
+/// (1 - c) / 2 and (1 + c) / 2 quantiles of the replicates, where "c" is the
+/// confidence level. The bias-corrected and accelerated (BCa) interval adjusts
+/// these quantiles for the median bias (z₀) and the skewness (acceleration "a",
+/// estimated with the jackknife) of the replicates. Non-finite replicates are
+/// excluded from the intervals. The first Python exception raised by the
+/// statistic function is returned.
+///
+/// :param data: The 1-dimensional data. Must have at least 2 values.
+/// :param statistic: The function computing the statistic (a float) of a list
+///     of values.
+/// :param iterations: The number of replicates, default = 1000. Must be >= 1.
+/// :param confidence: The confidence level, default = 0.95. Must be in (0, 1).
+/// :param seed: Pseudorandom number generator seed, the replicate "i" is seeded
+///     with "seed + i". If "None", a thread local generator is used. Pass an
+///     "Rng" context to draw its next stream.
+/// :return: A dictionary with the "estimate", the "percentile" and "bca"
+///     confidence intervals as (lower, upper) tuples, and the "replicates".
+#[pyfunction]
+#[pyo3(name = "bootstrap")]
+#[pyo3(signature = (data, statistic, iterations=None, confidence=None, seed=None))]
+pub fn statistics_bootstrap<'py>(
+    py: Python<'py>,
+    data: Vec<f64>,
+    statistic: Py<PyAny>,
+    iterations: Option<usize>,
+    confidence: Option<f64>,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let seed = rng_seed(seed);
+    let err: Mutex<Option<PyErr>> = Mutex::new(None);
+    let output = py
+        .allow_threads(|| {
+            statistics::bootstrap(
+                &data,
+                |v| call_statistic(&statistic, (v.to_vec(),), &err),
+                iterations,
+                confidence,
+                seed,
+            )
+        })
+        .map_err(map_imgal_error);
+    if let Some(e) = err.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    bootstrap_to_dict(py, output?)
+}
+
+/// Compute the circular mean of phase angles.
+///
+/// This function computes the mean direction of a set of phase angles (e.g. a
+/// phasor phase image), the direction of the resultant of their unit vectors:
+///
+/// θ̄ = atan2(Σsin(θᵢ), Σcos(θᵢ))
+///
+/// Unlike the arithmetic mean, the circular mean is independent of where the
+/// angles wrap. Non-finite angles are ignored.
+///
+/// :param data: An n-dimensional array of phase angles in radians.
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the statistics. Must match the shape of "data".
+/// :return: The circular mean in radians, within (-π, π]. NaN if the mean
+///     resultant length is (numerically) 0.0, e.g. evenly spread angles.
+#[pyfunction]
+#[pyo3(name = "circular_mean")]
+#[pyo3(signature = (data, mask=None))]
+pub fn statistics_circular_mean<'py>(
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::circular_mean(arr.as_array(), msk).map_err(map_imgal_error)
+    })
+}
+
+/// Compute the circular variance of phase angles.
+///
+/// This function computes the circular variance of a set of phase angles from
+/// the mean resultant length R̄:
+///
+/// R̄ = √((Σcos(θᵢ))² + (Σsin(θᵢ))²) / n
+/// V = 1 - R̄
+///
+/// The circular variance is 0.0 if all angles are equal and 1.0 if their
+/// resultant is 0.0. Non-finite angles are ignored.
+///
+/// :param data: An n-dimensional array of phase angles in radians.
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the statistics. Must match the shape of "data".
+/// :return: The circular variance, within [0, 1].
+#[pyfunction]
+#[pyo3(name = "circular_variance")]
+#[pyo3(signature = (data, mask=None))]
+pub fn statistics_circular_variance<'py>(
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::circular_variance(arr.as_array(), msk).map_err(map_imgal_error)
+    })
+}
+
+/// Compute the descriptive statistics of an n-dimensional array.
+///
+/// This function computes the count, mean, variance, skewness, excess kurtosis,
+/// minimum, maximum and median of the (masked) values of the input
+/// n-dimensional array. The central moments are accumulated in a single
+/// parallel pass. The skewness and kurtosis are:
+///
+/// g₁ = √n × M₃ / M₂^(3/2)
+/// g₂ = n × M₄ / M₂² - 3
+///
+/// where Mₖ is the sum of the k-th power deviations from the mean. The
+/// variance is 0.0 for a single value, the skewness and kurtosis are 0.0 if
+/// all values are equal.
+///
+/// :param data: An n-dimensional array.
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the statistics. Must match the shape of "data".
+/// :return: A dictionary with the "count", "mean", sample "variance" (n - 1
+///     degrees of freedom), "skewness", "kurtosis", "min", "max" and "median".
+#[pyfunction]
+#[pyo3(name = "describe")]
+#[pyo3(signature = (data, mask=None))]
+pub fn statistics_describe<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    let d = dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::describe(arr.as_array(), msk).map_err(map_imgal_error)?
+    });
+    let dict = PyDict::new(py);
+    dict.set_item("count", d.count)?;
+    dict.set_item("mean", d.mean)?;
+    dict.set_item("variance", d.variance)?;
+    dict.set_item("skewness", d.skewness)?;
+    dict.set_item("kurtosis", d.kurtosis)?;
+    dict.set_item("min", d.min)?;
+    dict.set_item("max", d.max)?;
+    dict.set_item("median", d.median)?;
+
+    Ok(dict)
+}
 
 /// Compute the effective sample size (ESS) of a weighted sample set.
 ///
@@ -22,6 +276,139 @@ pub fn statistics_effective_sample_size(weights: Vec<f64>) -> f64 {
     statistics::effective_sample_size(&weights)
 }
 
+/// The bin counts and bin edges of a histogram.
+type HistogramEdges = (Vec<f64>, Vec<f64>);
+
+/// The two halves of a split photon count image.
+type PhotonHalves<'py> = (Bound<'py, PyArray2<f64>>, Bound<'py, PyArray2<f64>>);
+
+/// Compute the Fourier ring correlation (FRC) of two 2-dimensional images.
+///
+/// This function computes the normalized cross-correlation of the Fourier
+/// transforms of two independent images of the same sample (e.g. two
+/// consecutive acquisitions or the two halves of a split photon count image,
+/// see "split_photons") over rings of equal spatial frequency:
+///
+/// FRC(r) = Re(Σ F₁(k) × F₂*(k)) / √(Σ |F₁(k)|² × Σ |F₂(k)|²)
+///
+/// Ring "r" holds the frequencies with a radius in [(r - 0.5) / n,
+/// (r + 0.5) / n) cycles per pixel, where "n" is the shorter image axis, up to
+/// the Nyquist frequency. The resolution is the inverse of the first frequency
+/// at which the FRC drops below the threshold of the criterion, linearly
+/// interpolated between rings.
+///
+/// :param data_a: The first 2-dimensional image. Must have the same shape and
+///     dtype as "data_b".
+/// :param data_b: The second 2-dimensional image. Must have the same shape and
+///     dtype as "data_a".
+/// :param criterion: The resolution criterion, "one_seventh" (the fixed 1/7
+///     threshold) or "sigma" (the k / √(n / 2) threshold, where "n" is the
+///     number of pixels in the ring), default = "one_seventh".
+/// :param k: The number of standard deviations of the "sigma" criterion,
+///     default = 3.0.
+/// :param pixel_size: The pixel size, default = 1.0 (i.e. the resolution is in
+///     pixels).
+/// :return: A dictionary with the "frequencies" (cycles per pixel),
+///     "correlation" and pixel "counts" of each ring, and the "resolution" in
+///     units of "pixel_size" ("None" if the FRC does not cross the threshold).
+#[pyfunction]
+#[pyo3(name = "frc")]
+#[pyo3(signature = (data_a, data_b, criterion=None, k=None, pixel_size=None))]
+pub fn statistics_frc<'py>(
+    py: Python<'py>,
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    criterion: Option<&str>,
+    k: Option<f64>,
+    pixel_size: Option<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let criterion = parse_frc_criterion(criterion, k)?;
+    let curve = dispatch_dtype!(data_a, PyReadonlyArray2::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArray2<T>>()?;
+        statistics::frc(arr_a.as_array(), arr_b.as_array()).map_err(map_imgal_error)?
+    });
+    let resolution = statistics::frc_resolution(&curve, Some(criterion), pixel_size);
+    let dict = PyDict::new(py);
+    dict.set_item("frequencies", curve.frequencies)?;
+    dict.set_item("correlation", curve.correlation)?;
+    dict.set_item("counts", curve.counts)?;
+    dict.set_item("resolution", resolution)?;
+
+    Ok(dict)
+}
+
+/// Compute the histogram of an n-dimensional array.
+///
+/// This function computes the frequency histogram of the (masked) values of
+/// the input n-dimensional array. The bins have equal widths and span the
+/// minimum and maximum values. If all (masked) values are equal, every value is
+/// counted in the first bin and all edges are equal.
+///
+/// :param data: An n-dimensional array.
+/// :param bins: The number of bins or an automatic binning rule, "fd"
+///     (Freedman–Diaconis, falls back to Sturges if the interquartile range is
+///     0.0) or "sturges", default = 256.
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the histogram. Must match the shape of "data".
+/// :return: The bin counts and the bin edges (one more than the number of
+///     bins).
+#[pyfunction]
+#[pyo3(name = "histogram")]
+#[pyo3(signature = (data, bins=None, mask=None))]
+pub fn statistics_histogram<'py>(
+    data: Bound<'py, PyAny>,
+    bins: Option<Bound<'py, PyAny>>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<HistogramEdges> {
+    let bins = parse_binning(bins)?;
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::histogram(arr.as_array(), bins, msk)
+            .map(|h| (h.counts, h.edges))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the joint histogram of two n-dimensional images.
+///
+/// This function counts the co-occurrences of the binned intensities of two
+/// images, the bin [i, j] holds the number of pixels with the intensity of
+/// image "A" in bin "i" and the intensity of image "B" in bin "j". The bins of
+/// each image have equal widths and span the minimum and maximum (masked)
+/// intensities of that image. The histogram can optionally be smoothed with a
+/// Gaussian kernel (i.e. a Parzen window estimate).
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape and dtype as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape and dtype as image "A".
+/// :param bins: The number of bins per image, default = 32. Must be >= 1.
+/// :param sigma: The standard deviation of the Gaussian smoothing kernel in
+///     bins, default = 0.0 (i.e. no smoothing). Must be >= 0.0.
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded
+///     from the histogram. Must match the shape of the images.
+/// :return: The (smoothed) joint histogram with shape (bins, bins), indexed by
+///     the bins of image "A" and "B".
+#[pyfunction]
+#[pyo3(name = "joint_histogram")]
+#[pyo3(signature = (data_a, data_b, bins=None, sigma=None, mask=None))]
+pub fn statistics_joint_histogram<'py>(
+    py: Python<'py>,
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    bins: Option<usize>,
+    sigma: Option<f64>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        statistics::joint_histogram(arr_a.as_array(), arr_b.as_array(), bins, sigma, msk)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
 /// Find the maximum value in an n-dimensional array.
 ///
 /// This function iterates through all elements of an n-dimensional array to
@@ -32,23 +419,10 @@ pub fn statistics_effective_sample_size(weights: Vec<f64>) -> f64 {
 #[pyfunction]
 #[pyo3(name = "max")]
 pub fn statistics_max<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
-        let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
         let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let m = statistics::max(arr.as_array());
-        return Ok(m as f64);
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+        Ok(m as f64)
+    })
 }
 
 /// Find the minimum value in an n-dimensional array.
@@ -61,23 +435,10 @@ pub fn statistics_max<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
 #[pyfunction]
 #[pyo3(name = "min")]
 pub fn statistics_min<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
         let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let m = statistics::min(arr.as_array());
-        return Ok(m as f64);
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
-    }
+        Ok(m as f64)
+    })
 }
 /// Find the minimum and maximum values in an n-dimensional array.
 ///
@@ -91,23 +452,359 @@ pub fn statistics_min<'py>(data: Bound<'py, PyAny>) -> PyResult<f64> {
 #[pyfunction]
 #[pyo3(name = "min_max")]
 pub fn statistics_min_max<'py>(data: Bound<'py, PyAny>) -> PyResult<(f64, f64)> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
         let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let mm = statistics::min_max(arr.as_array());
-        return Ok((mm.0 as f64, mm.1 as f64));
-    } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
+        Ok((mm.0 as f64, mm.1 as f64))
+    })
+}
+
+/// Compute the mutual information of two n-dimensional images.
+///
+/// This function computes the mutual information (MI) of the intensities of
+/// two images from their joint histogram (see "joint_histogram"):
+///
+/// MI = Σᵢⱼ p(i, j) × ln(p(i, j) / (p(i) × p(j)))
+///
+/// where p(i, j) is the normalized joint histogram and p(i) and p(j) are its
+/// marginals. The MI is 0.0 for independent intensities and grows with any
+/// statistical dependence between the images.
+///
+/// :param data_a: The n-dimensional input image, "A". Image "A" must have the
+///     same shape and dtype as image "B".
+/// :param data_b: The n-dimensional input image, "B". Image "B" must have the
+///     same shape and dtype as image "A".
+/// :param bins: The number of bins per image, default = 32. Must be >= 1.
+/// :param sigma: The standard deviation of the Gaussian smoothing kernel of the
+///     joint histogram in bins, default = 0.0 (i.e. no smoothing). Must be
+///     >= 0.0.
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded.
+///     Must match the shape of the images.
+/// :return: The mutual information in nats.
+#[pyfunction]
+#[pyo3(name = "mutual_information")]
+#[pyo3(signature = (data_a, data_b, bins=None, sigma=None, mask=None))]
+pub fn statistics_mutual_information<'py>(
+    data_a: Bound<'py, PyAny>,
+    data_b: Bound<'py, PyAny>,
+    bins: Option<usize>,
+    sigma: Option<f64>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data_a, PyReadonlyArrayDyn::<T>, |arr_a| {
+        let arr_b = data_b.extract::<DLPackArrayDyn<T>>()?;
+        statistics::mutual_information(arr_a.as_array(), arr_b.as_array(), bins, sigma, msk)
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute bootstrap confidence intervals of a statistic of paired data.
+///
+/// This function computes the bootstrap confidence intervals of a statistic of
+/// two paired data sets (e.g. a correlation coefficient of the pixel
+/// intensities of two channels), see "bootstrap". The pairs are resampled
+/// together, the i-th value of "data_a" stays paired with the i-th value of
+/// "data_b".
+///
+/// :param data_a: The first 1-dimensional data set. Must have at least 2 values
+///     and be the same length as "data_b".
+/// :param data_b: The second 1-dimensional data set. Must be the same length as
+///     "data_a".
+/// :param statistic: The function computing the statistic (a float) of two
+///     lists of paired values.
+/// :param iterations: The number of replicates, default = 1000. Must be >= 1.
+/// :param confidence: The confidence level, default = 0.95. Must be in (0, 1).
+/// :param seed: Pseudorandom number generator seed, the replicate "i" is seeded
+///     with "seed + i". If "None", a thread local generator is used. Pass an
+///     "Rng" context to draw its next stream.
+/// :return: A dictionary with the "estimate", the "percentile" and "bca"
+///     confidence intervals as (lower, upper) tuples, and the "replicates".
+#[pyfunction]
+#[pyo3(name = "paired_bootstrap")]
+#[pyo3(signature = (data_a, data_b, statistic, iterations=None, confidence=None, seed=None))]
+pub fn statistics_paired_bootstrap<'py>(
+    py: Python<'py>,
+    data_a: Vec<f64>,
+    data_b: Vec<f64>,
+    statistic: Py<PyAny>,
+    iterations: Option<usize>,
+    confidence: Option<f64>,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let seed = rng_seed(seed);
+    let err: Mutex<Option<PyErr>> = Mutex::new(None);
+    let output = py
+        .allow_threads(|| {
+            statistics::paired_bootstrap(
+                &data_a,
+                &data_b,
+                |a, b| call_statistic(&statistic, (a.to_vec(), b.to_vec()), &err),
+                iterations,
+                confidence,
+                seed,
+            )
+        })
+        .map_err(map_imgal_error);
+    if let Some(e) = err.into_inner().unwrap() {
+        return Err(e);
     }
+
+    bootstrap_to_dict(py, output?)
+}
+
+/// Test if a statistic of paired data is significant with a permutation test.
+///
+/// This function tests the null hypothesis that two paired data sets are
+/// independent (e.g. a correlation coefficient of the pixel intensities of two
+/// channels is 0.0). Each permutation shuffles "data_b", which breaks the
+/// pairing with "data_a", and computes the statistic. The p-value is computed
+/// as in "permutation_test". The first Python exception raised by the
+/// statistic function is returned.
+///
+/// :param data_a: The first data set. Must be the same length as "data_b".
+/// :param data_b: The second data set, that is shuffled. Must be the same
+///     length as "data_a".
+/// :param statistic: The function computing the test statistic (a float) of
+///     two lists of values.
+/// :param permutations: The number of permutations, default = 1000. Must be
+///     >= 1.
+/// :param alternative: The alternative hypothesis, "two-sided", "greater" or
+///     "less", default = "two-sided".
+/// :param seed: Pseudorandom number generator seed, the permutation "i" is
+///     seeded with "seed + i". If "None", a thread local generator is used.
+///     Pass an "Rng" context to draw its next stream.
+/// :return: The p-value and the statistic of each permutation (i.e. the null
+///     distribution).
+#[pyfunction]
+#[pyo3(name = "paired_permutation_test")]
+#[pyo3(signature = (data_a, data_b, statistic, permutations=None, alternative=None, seed=None))]
+pub fn statistics_paired_permutation_test<'py>(
+    py: Python<'py>,
+    data_a: Vec<f64>,
+    data_b: Vec<f64>,
+    statistic: Py<PyAny>,
+    permutations: Option<usize>,
+    alternative: Option<&str>,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<(f64, Vec<f64>)> {
+    let alternative = parse_alternative(alternative)?;
+    let seed = rng_seed(seed);
+    let err: Mutex<Option<PyErr>> = Mutex::new(None);
+    let output = py
+        .allow_threads(|| {
+            statistics::paired_permutation_test(
+                &data_a,
+                &data_b,
+                |a, b| call_statistic(&statistic, (a.to_vec(), b.to_vec()), &err),
+                permutations,
+                Some(alternative),
+                seed,
+            )
+        })
+        .map_err(map_imgal_error);
+    if let Some(e) = err.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    output
+}
+
+/// Test if two samples differ with a permutation test.
+///
+/// This function tests the null hypothesis that two independent samples come
+/// from the same distribution. The samples are pooled and the sample labels are
+/// shuffled, each permutation assigns the first len(data_a) pooled values to
+/// sample "A" and the rest to sample "B" and computes the statistic (e.g. the
+/// difference of the means). The p-value is the fraction of permuted statistics
+/// that are at least as extreme as the observed statistic, with the observed
+/// labeling counted as one of the permutations:
+///
+/// p_greater = (1 + #{t_perm >= t}) / (1 + permutations)
+/// p_less = (1 + #{t_perm <= t}) / (1 + permutations)
+/// p_two_sided = min(1, 2 × min(p_greater, p_less))
+///
+/// The first Python exception raised by the statistic function is returned.
+///
+/// :param data_a: The first sample. Must have at least 1 value.
+/// :param data_b: The second sample. Must have at least 1 value.
+/// :param statistic: The function computing the test statistic (a float) of
+///     two lists of values.
+/// :param permutations: The number of permutations, default = 1000. Must be
+///     >= 1.
+/// :param alternative: The alternative hypothesis, "two-sided", "greater" or
+///     "less", default = "two-sided".
+/// :param seed: Pseudorandom number generator seed, the permutation "i" is
+///     seeded with "seed + i". If "None", a thread local generator is used.
+///     Pass an "Rng" context to draw its next stream.
+/// :return: The p-value and the statistic of each permutation (i.e. the null
+///     distribution).
+#[pyfunction]
+#[pyo3(name = "permutation_test")]
+#[pyo3(signature = (data_a, data_b, statistic, permutations=None, alternative=None, seed=None))]
+pub fn statistics_permutation_test<'py>(
+    py: Python<'py>,
+    data_a: Vec<f64>,
+    data_b: Vec<f64>,
+    statistic: Py<PyAny>,
+    permutations: Option<usize>,
+    alternative: Option<&str>,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<(f64, Vec<f64>)> {
+    let alternative = parse_alternative(alternative)?;
+    let seed = rng_seed(seed);
+    let err: Mutex<Option<PyErr>> = Mutex::new(None);
+    let output = py
+        .allow_threads(|| {
+            statistics::permutation_test(
+                &data_a,
+                &data_b,
+                |a, b| call_statistic(&statistic, (a.to_vec(), b.to_vec()), &err),
+                permutations,
+                Some(alternative),
+                seed,
+            )
+        })
+        .map_err(map_imgal_error);
+    if let Some(e) = err.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    output
+}
+
+/// Compute a quantile of an n-dimensional array.
+///
+/// This function computes the "q" quantile of the (masked) values of the input
+/// n-dimensional array with linear interpolation between the closest ranks
+/// (i.e. at the position q × (n - 1) of the sorted values).
+///
+/// :param data: An n-dimensional array.
+/// :param q: The quantile. Must be in [0, 1].
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the quantile. Must match the shape of "data".
+/// :return: The quantile value.
+#[pyfunction]
+#[pyo3(name = "quantile")]
+#[pyo3(signature = (data, q, mask=None))]
+pub fn statistics_quantile<'py>(
+    data: Bound<'py, PyAny>,
+    q: f64,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::quantile(arr.as_array(), q, msk).map_err(map_imgal_error)
+    })
+}
+
+/// Test phase angles for uniformity with the Rayleigh test.
+///
+/// This function tests the null hypothesis that a set of phase angles is
+/// uniformly distributed around the circle against a single preferred
+/// direction:
+///
+/// Z = n × R̄²
+/// p = exp(√(1 + 4n + 4(n² - R²)) - (1 + 2n))
+///
+/// where "R = n × R̄" is the resultant length. The p-value uses Zar's
+/// approximation, which is accurate for n >= 10. Non-finite angles are
+/// ignored.
+///
+/// :param data: An n-dimensional array of phase angles in radians.
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the statistics. Must match the shape of "data".
+/// :return: The Rayleigh Z statistic and the p-value.
+#[pyfunction]
+#[pyo3(name = "rayleigh_test")]
+#[pyo3(signature = (data, mask=None))]
+pub fn statistics_rayleigh_test<'py>(
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<(f64, f64)> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::rayleigh_test(arr.as_array(), msk).map_err(map_imgal_error)
+    })
+}
+
+/// Rank data with tie handling.
+///
+/// This function computes the 1-based rank of each element of the input data
+/// in ascending order. Tied elements get the average of the ranks they span,
+/// e.g. [10, 20, 20, 30] is ranked [1.0, 2.5, 2.5, 4.0]. With weights, each
+/// element counts as wᵢ observations and its rank is:
+///
+/// rᵢ = W_less + (W_tied + 1) / 2
+///
+/// :param data: The data to rank.
+/// :param weights: The optional non-negative weight of each element. Must be the
+///     same length as "data". If None, every element has a weight of 1.0.
+/// :return: The rank of each element, in the order of "data".
+#[pyfunction]
+#[pyo3(name = "rank")]
+#[pyo3(signature = (data, weights=None))]
+pub fn statistics_rank(data: Vec<f64>, weights: Option<Vec<f64>>) -> PyResult<Vec<f64>> {
+    statistics::rank(&data, weights.as_deref()).map_err(map_imgal_error)
+}
+
+/// Compute the (weighted) Spearman rank correlation coefficient.
+///
+/// This function calculates the Spearman rank correlation coefficient between
+/// two datasets, the Pearson correlation coefficient of their tie-aware ranks.
+/// With weights, each observation pair counts as wᵢ observations in both the
+/// ranking and the correlation.
+///
+/// :param data_a: The first dataset for correlation analysis. Must be the same
+///     length as "data_b".
+/// :param data_b: The second dataset for correlation analysis. Must be the same
+///     length as "data_a".
+/// :param weights: The optional non-negative weight of each observation pair.
+///     Must be the same length as both input datasets. If None, every pair has
+///     a weight of 1.0.
+/// :return: The Spearman rank correlation coefficient, ranging between -1.0
+///     (negative correlation), 0.0 (no correlation) and 1.0 (positive
+///     correlation).
+#[pyfunction]
+#[pyo3(name = "spearman")]
+#[pyo3(signature = (data_a, data_b, weights=None))]
+pub fn statistics_spearman(
+    data_a: Vec<f64>,
+    data_b: Vec<f64>,
+    weights: Option<Vec<f64>>,
+) -> PyResult<f64> {
+    statistics::spearman(&data_a, &data_b, weights.as_deref()).map_err(map_imgal_error)
+}
+
+/// Split a 2-dimensional photon count image into two independent halves.
+///
+/// This function assigns each detected photon to one of two images with equal
+/// probability (i.e. binomial thinning), the count "n" of each pixel is split
+/// into k ~ Binomial(n, 0.5) and n - k photons. For shot noise limited
+/// (Poisson) data the two halves are statistically independent images of the
+/// same sample with half the photons, which allows a Fourier ring correlation
+/// (see "frc") from a single acquisition.
+///
+/// :param data: The 2-dimensional photon count image. Each value must be a
+///     non-negative integer.
+/// :param seed: Pseudorandom number generator seed, the row "i" is seeded with
+///     "seed + i". If "None", a thread local generator is used. Pass an "Rng"
+///     context to draw its next stream.
+/// :return: The two halves, which sum to "data".
+#[pyfunction]
+#[pyo3(name = "split_photons")]
+#[pyo3(signature = (data, seed=None))]
+pub fn statistics_split_photons<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    seed: Option<PySeed<'py>>,
+) -> PyResult<PhotonHalves<'py>> {
+    dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+        statistics::split_photons(arr.as_array(), rng_seed(seed))
+            .map(|(a, b)| (a.into_pyarray(py), b.into_pyarray(py)))
+            .map_err(map_imgal_error)
+    })
 }
 
 /// Compute the sum of a sequence of numbers.
@@ -120,6 +817,69 @@ pub fn statistics_sum(data: Vec<f64>) -> f64 {
     statistics::sum(&data)
 }
 
+/// Compute the weighted covariance matrix of several variables.
+///
+/// This function computes the unbiased weighted covariance of each pair of
+/// variables (rows) over the weighted observations (columns):
+///
+/// Cⱼₖ = Σwᵢ × Σwᵢ(xⱼᵢ - μⱼ)(xₖᵢ - μₖ) / n₀
+///
+/// Where μⱼ is the weighted mean of variable "j" and n₀ = total weighted pairs
+/// = (Σwᵢ)² - Σwᵢ², see "weighted_variance".
+///
+/// :param data: The 2-dimensional dataset with shape (variables, observations).
+/// :param weights: The associated weights for each observation. Must be the
+///     same length as the observation axis of "data".
+/// :return: The symmetric weighted covariance matrix with shape (variables,
+///     variables).
+#[pyfunction]
+#[pyo3(name = "weighted_covariance")]
+pub fn statistics_weighted_covariance<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    weights: Vec<f64>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    dispatch_dtype!(data, PyReadonlyArray2::<T>, |arr| {
+        statistics::weighted_covariance(arr.as_array(), &weights)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the weighted histogram of an n-dimensional array.
+///
+/// This function computes the histogram of the (masked) values of the input
+/// n-dimensional array where each value adds its weight to its bin instead of
+/// 1.0. The bins are constructed as in "histogram", automatic binning rules use
+/// the number of (masked) values and ignore the weights.
+///
+/// :param data: An n-dimensional array.
+/// :param weights: The non-negative weight of each value. Must match the shape
+///     of "data".
+/// :param bins: The number of bins or an automatic binning rule, "fd" or
+///     "sturges", default = 256.
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the histogram. Must match the shape of "data".
+/// :return: The weighted bin counts and the bin edges (one more than the
+///     number of bins).
+#[pyfunction]
+#[pyo3(name = "weighted_histogram")]
+#[pyo3(signature = (data, weights, bins=None, mask=None))]
+pub fn statistics_weighted_histogram<'py>(
+    data: Bound<'py, PyAny>,
+    weights: DLPackArrayDyn<f64>,
+    bins: Option<Bound<'py, PyAny>>,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<HistogramEdges> {
+    let bins = parse_binning(bins)?;
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::weighted_histogram(arr.as_array(), weights.as_array(), bins, msk)
+            .map(|h| (h.counts, h.edges))
+            .map_err(map_imgal_error)
+    })
+}
+
 /// Compute the weighted Kendall's Tau-b rank correlation coefficient.
 ///
 /// This function calculates a weighted Kendall's Tau-b rank correlation
@@ -157,7 +917,23 @@ pub fn statistics_weighted_kendall_tau_b(
 ) -> PyResult<f64> {
     statistics::weighted_kendall_tau_b(&data_a, &data_b, &weights)
         .map(|output| output)
-        .map_err(map_array_error)
+        .map_err(map_imgal_error)
+}
+
+/// Compute the weighted mean.
+///
+/// This function computes the weighted arithmetic mean of a dataset,
+/// μ = Σwᵢxᵢ / Σwᵢ.
+///
+/// :param data: The dataset. Must be the same length as "weights".
+/// :param weights: The associated weights for each observation. Must be the
+///     same length as "data".
+/// :return: The weighted mean. 0.0 if the dataset is empty or the weights sum
+///     to 0.0.
+#[pyfunction]
+#[pyo3(name = "weighted_mean")]
+pub fn statistics_weighted_mean(data: Vec<f64>, weights: Vec<f64>) -> PyResult<f64> {
+    statistics::weighted_mean(&data, &weights).map_err(map_imgal_error)
 }
 
 /// Sort 1-dimensional arrays of values and their associated weights.
@@ -179,44 +955,166 @@ pub fn statistics_weighted_merge_sort_mut<'py>(
     mut weights: PyReadwriteArray1<f64>,
 ) -> PyResult<f64> {
     // pattern match and extract the allowed array type
-    if let Ok(mut d) = data.extract::<PyReadwriteArray1<u8>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<u16>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<f32>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<f64>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else if let Ok(mut d) = data.extract::<PyReadwriteArray1<i32>>() {
-        return statistics::weighted_merge_sort_mut(
-            d.as_slice_mut().unwrap(),
-            weights.as_slice_mut().unwrap(),
-        )
-        .map(|output| output)
-        .map_err(map_array_error);
-    } else {
-        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
+    dispatch_dtype_mut!(data, PyReadwriteArray1::<T>, |mut d| {
+        with_slice_mut(d.as_array_mut(), |d| {
+            with_slice_mut(weights.as_array_mut(), |w| {
+                statistics::weighted_merge_sort_mut(d, w)
+            })
+        })
+        .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the weighted Pearson correlation coefficient.
+///
+/// This function computes the Pearson correlation coefficient of two datasets
+/// from their weighted covariance and variances, r = C_ab / √(σ²_a × σ²_b). See
+/// "weighted_variance" for the weighting conventions.
+///
+/// :param data_a: The first dataset for correlation analysis. Must be the same
+///     length as "data_b".
+/// :param data_b: The second dataset for correlation analysis. Must be the same
+///     length as "data_a".
+/// :param weights: The associated weights for each observation pair. Must be
+///     the same length as both input datasets.
+/// :return: The weighted Pearson correlation coefficient, ranging between -1.0
+///     (negative correlation), 0.0 (no correlation) and 1.0 (positive
+///     correlation). 0.0 if there are less than 2 observations or either
+///     dataset is constant.
+#[pyfunction]
+#[pyo3(name = "weighted_pearson")]
+pub fn statistics_weighted_pearson(
+    data_a: Vec<f64>,
+    data_b: Vec<f64>,
+    weights: Vec<f64>,
+) -> PyResult<f64> {
+    statistics::weighted_pearson(&data_a, &data_b, &weights).map_err(map_imgal_error)
+}
+
+/// Compute a weighted quantile of an n-dimensional array.
+///
+/// This function computes the "q" quantile of the (masked) values of the input
+/// n-dimensional array where each value counts as its weight. The sorted values
+/// with a positive weight are placed at the centers of their cumulative weight,
+/// rescaled so that the smallest value is at 0.0 and the largest value is at
+/// 1.0, and the quantile is linearly interpolated between the closest
+/// positions. With equal weights this is the same as "quantile".
+///
+/// :param data: An n-dimensional array.
+/// :param weights: The non-negative weight of each value. Must match the shape
+///     of "data".
+/// :param q: The quantile. Must be in [0, 1].
+/// :param mask: An optional boolean mask, values set to "False" are excluded
+///     from the quantile. Must match the shape of "data".
+/// :return: The weighted quantile value.
+#[pyfunction]
+#[pyo3(name = "weighted_quantile")]
+#[pyo3(signature = (data, weights, q, mask=None))]
+pub fn statistics_weighted_quantile<'py>(
+    data: Bound<'py, PyAny>,
+    weights: DLPackArrayDyn<f64>,
+    q: f64,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        statistics::weighted_quantile(arr.as_array(), weights.as_array(), q, msk)
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute the weighted variance.
+///
+/// This function computes the unbiased weighted variance of a dataset, where
+/// each observation contributes its weight (i.e. reliability weights):
+///
+/// σ² = Σwᵢ × Σwᵢ(xᵢ - μ)² / n₀
+///
+/// Where μ is the weighted mean and n₀ = total weighted pairs = (Σwᵢ)² - Σwᵢ²,
+/// the same normalization as "weighted_kendall_tau_b". With unit weights this
+/// is the sample variance with n - 1 degrees of freedom.
+///
+/// :param data: The dataset. Must be the same length as "weights".
+/// :param weights: The associated weights for each observation. Must be the
+///     same length as "data".
+/// :return: The weighted variance. 0.0 if there are less than 2 observations
+///     or no weighted pairs.
+#[pyfunction]
+#[pyo3(name = "weighted_variance")]
+pub fn statistics_weighted_variance(data: Vec<f64>, weights: Vec<f64>) -> PyResult<f64> {
+    statistics::weighted_variance(&data, &weights).map_err(map_imgal_error)
+}
+
+/// Parse a histogram binning rule from a number of bins or a rule name.
+fn parse_binning(bins: Option<Bound<PyAny>>) -> PyResult<Option<Binning>> {
+    let Some(b) = bins else {
+        return Ok(None);
+    };
+    if let Ok(n) = b.extract::<usize>() {
+        return Ok(Some(Binning::Fixed(n)));
+    }
+    match b.extract::<String>()?.as_str() {
+        "fd" => Ok(Some(Binning::FreedmanDiaconis)),
+        "sturges" => Ok(Some(Binning::Sturges)),
+        r => Err(PyValueError::new_err(format!(
+            "Unknown binning rule \"{}\", supported rules are \"fd\" and \"sturges\".",
+            r
+        ))),
+    }
+}
+
+/// Call a Python statistic function, the first Python exception is stored and
+/// the remaining calls return NaN.
+fn call_statistic<A>(statistic: &Py<PyAny>, args: A, err: &Mutex<Option<PyErr>>) -> f64
+where
+    A: for<'py> IntoPyObject<'py, Target = PyTuple>,
+{
+    Python::with_gil(|py| {
+        if err.lock().unwrap().is_some() {
+            return f64::NAN;
+        }
+        match statistic.call1(py, args).and_then(|r| r.extract::<f64>(py)) {
+            Ok(v) => v,
+            Err(e) => {
+                err.lock().unwrap().get_or_insert(e);
+                f64::NAN
+            }
+        }
+    })
+}
+
+/// Convert bootstrap confidence intervals into a Python dictionary.
+fn bootstrap_to_dict(py: Python, output: Bootstrap) -> PyResult<Bound<PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("estimate", output.estimate)?;
+    dict.set_item("percentile", output.percentile)?;
+    dict.set_item("bca", output.bca)?;
+    dict.set_item("replicates", output.replicates)?;
+
+    Ok(dict)
+}
+
+/// Parse the alternative hypothesis of a permutation test.
+fn parse_alternative(alternative: Option<&str>) -> PyResult<Alternative> {
+    match alternative {
+        None | Some("two-sided") => Ok(Alternative::TwoSided),
+        Some("greater") => Ok(Alternative::Greater),
+        Some("less") => Ok(Alternative::Less),
+        Some(a) => Err(PyValueError::new_err(format!(
+            "Unknown alternative hypothesis \"{}\", supported alternatives are \"two-sided\", \"greater\", and \"less\".",
+            a
+        ))),
+    }
+}
+
+/// Parse a Fourier ring correlation resolution criterion.
+fn parse_frc_criterion(criterion: Option<&str>, k: Option<f64>) -> PyResult<FrcCriterion> {
+    match criterion {
+        None | Some("one_seventh") => Ok(FrcCriterion::OneSeventh),
+        Some("sigma") => Ok(FrcCriterion::Sigma(k.unwrap_or(3.0))),
+        Some(c) => Err(PyValueError::new_err(format!(
+            "Unknown FRC criterion \"{}\", supported criteria are \"one_seventh\" and \"sigma\".",
+            c
+        ))),
     }
 }
@@ -1,19 +1,23 @@
+use numpy::ndarray::ArrayD;
 use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn};
-use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 
-use imgal::threshold;
+use crate::error::{map_imgal_error, with_warnings};
+use crate::utils::{DLPackArrayDyn, dispatch_dtype, from_dlpack};
+use imgal::threshold::{self, ThresholdMethod};
 
 /// Create a boolean mask from a threshold value.
 ///
 /// This function computes a threshold mask (as a boolean array) from the input
-/// image at the given threshold value.
+/// image at the given threshold value. The input image can have any number of
+/// dimensions and the threshold value is cast to the dtype of the image.
 ///
-/// :param data: An n-dimensional image or array
+/// :param data: An n-dimensional image or array.
 /// :param threshold: The image pixel threshold value.
-/// :return: A boolean array of the same shape as the input image
-///     with pixels that are greater than the threshold value set as "true"
-///     and pixels that are below the threshold value set as "false".
+/// :return: A boolean array of the same shape as the input image with pixels
+///     that are greater than the threshold value set as "True" and pixels that
+///     are below the threshold value set as "False".
 #[pyfunction]
 #[pyo3(name = "manual_mask")]
 pub fn threshold_manual_mask<'py>(
@@ -21,21 +25,202 @@ pub fn threshold_manual_mask<'py>(
     data: Bound<'py, PyAny>,
     threshold: f64,
 ) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
-    if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u8>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold as u8);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<u16>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold as u16);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f32>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold as f32);
-        return Ok(output.into_pyarray(py));
-    } else if let Ok(arr) = data.extract::<PyReadonlyArrayDyn<f64>>() {
-        let output = threshold::manual_mask(arr.as_array(), threshold);
-        return Ok(output.into_pyarray(py));
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        let output = with_warnings(py, || {
+            threshold::manual_mask(arr.as_array(), threshold as T)
+        })?;
+        Ok(output.into_pyarray(py))
+    })
+}
+
+/// Compute the Otsu threshold value of an n-dimensional image.
+///
+/// This function computes the threshold value that separates the image
+/// histogram into a background and a foreground class with the maximum
+/// between-class variance (Otsu's method). The histogram spans the minimum and
+/// maximum values of the (masked) image and the threshold is the upper edge of
+/// the last background bin.
+///
+/// :param data: An n-dimensional image or array.
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded
+///     from the histogram. Must match the shape of "data".
+/// :param bins: The number of histogram bins, default = 256. Must be >= 1.
+/// :return: The Otsu threshold value. If all (masked) pixels have the same
+///     value, that value is returned.
+#[pyfunction]
+#[pyo3(name = "otsu")]
+#[pyo3(signature = (data, mask=None, bins=None))]
+pub fn threshold_otsu<'py>(
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+    bins: Option<usize>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        threshold::otsu(arr.as_array(), msk, bins).map_err(map_imgal_error)
+    })
+}
+
+/// Create a boolean mask with the Otsu threshold value.
+///
+/// This function computes the Otsu threshold value of the input image (see
+/// "otsu") and creates a threshold mask (as a boolean array) at that value.
+///
+/// :param data: An n-dimensional image or array.
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded
+///     from the histogram and set to "False" in the output. Must match the
+///     shape of "data".
+/// :param bins: The number of histogram bins, default = 256. Must be >= 1.
+/// :return: A boolean array of the same shape as the input image with pixels
+///     that are greater than the threshold value set as "True" and pixels that
+///     are below the threshold value set as "False".
+#[pyfunction]
+#[pyo3(name = "otsu_mask")]
+#[pyo3(signature = (data, mask=None, bins=None))]
+pub fn threshold_otsu_mask<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    mask: Option<DLPackArrayDyn<bool>>,
+    bins: Option<usize>,
+) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        with_warnings(py, || threshold::otsu_mask(arr.as_array(), msk, bins))?
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// The threshold values and label image of a multi-class threshold.
+type ClassLabels<'py> = (Vec<f64>, Bound<'py, PyArrayDyn<u8>>);
+
+/// Compute the multi-class Otsu threshold values and label image of an
+/// n-dimensional image.
+///
+/// This function generalizes Otsu's method to more than two classes, it
+/// computes the "classes - 1" threshold values that split the image histogram
+/// into "classes" intensity classes with the maximum between-class variance.
+/// With 2 classes the threshold is equal to "otsu".
+///
+/// :param data: An n-dimensional image or array.
+/// :param classes: The number of intensity classes. Must be >= 2, <= 256 and
+///     <= bins.
+/// :param bins: The number of histogram bins, default = 256. Must be >= 1.
+/// :return: The "classes - 1" ascending threshold values and a label image of
+///     the same shape as the input image. Each pixel is labeled with the number
+///     of threshold values it is greater than (i.e. 0 for the darkest and
+///     "classes - 1" for the brightest class).
+#[pyfunction]
+#[pyo3(name = "multi_otsu")]
+#[pyo3(signature = (data, classes, bins=None))]
+pub fn threshold_multi_otsu<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    classes: usize,
+    bins: Option<usize>,
+) -> PyResult<ClassLabels<'py>> {
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        threshold::multi_otsu(arr.as_array(), classes, bins)
+            .map(|(t, labels)| (t, labels.into_pyarray(py)))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Compute a percentile threshold value of an n-dimensional image.
+///
+/// This function computes the "p" percentile of the (masked) pixel values with
+/// linear interpolation between the closest ranks, e.g. a "p" of 95.0 keeps
+/// the brightest 5% of the pixels above the threshold.
+///
+/// :param data: An n-dimensional image or array.
+/// :param p: The percentile. Must be in [0, 100].
+/// :param mask: An optional boolean mask, pixels set to "False" are excluded
+///     from the percentile. Must match the shape of "data".
+/// :return: The percentile threshold value.
+#[pyfunction]
+#[pyo3(name = "percentile")]
+#[pyo3(signature = (data, p, mask=None))]
+pub fn threshold_percentile<'py>(
+    data: Bound<'py, PyAny>,
+    p: f64,
+    mask: Option<DLPackArrayDyn<bool>>,
+) -> PyResult<f64> {
+    let msk = mask.as_ref().map(|m| m.as_array());
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        threshold::percentile(arr.as_array(), p, msk).map_err(map_imgal_error)
+    })
+}
+
+/// Create a boolean mask with an independent threshold inside each labeled
+/// region.
+///
+/// This function computes a threshold value from the pixels of each labeled
+/// region (e.g. each segmented cell) with the given method, and creates a
+/// threshold mask (as a boolean array) where each pixel is compared to the
+/// threshold value of its own region. Pixels with the label 0 are unlabeled
+/// and set to "False".
+///
+/// :param data: An n-dimensional image or array.
+/// :param labels: The integer label image, pixels with the same label belong
+///     to the same region. Must match the shape of "data".
+/// :param method: The threshold method applied to each region, "otsu" or
+///     "percentile", default = "otsu".
+/// :param p: The percentile of the "percentile" method, default = 50.0. Must be
+///     in [0, 100].
+/// :return: A boolean array of the same shape as the input image with pixels
+///     that are greater than the threshold value of their region set as "True"
+///     and all other pixels set as "False".
+#[pyfunction]
+#[pyo3(name = "per_label")]
+#[pyo3(signature = (data, labels, method=None, p=None))]
+pub fn threshold_per_label<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    labels: Bound<'py, PyAny>,
+    method: Option<&str>,
+    p: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<bool>>> {
+    let m = parse_threshold_method(method, p)?;
+    let lbl = extract_labels(&labels)?;
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        with_warnings(py, || threshold::per_label(arr.as_array(), lbl.view(), m))?
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Parse an optional threshold method name and percentile.
+fn parse_threshold_method(method: Option<&str>, p: Option<f64>) -> PyResult<ThresholdMethod> {
+    match method {
+        None | Some("otsu") => Ok(ThresholdMethod::Otsu),
+        Some("percentile") => Ok(ThresholdMethod::Percentile(p.unwrap_or(50.0))),
+        Some(m) => Err(PyValueError::new_err(format!(
+            "Unknown threshold method \"{}\", supported methods are \"otsu\" and \"percentile\".",
+            m
+        ))),
+    }
+}
+
+/// Extract an integer label image.
+pub(crate) fn extract_labels(labels: &Bound<'_, PyAny>) -> PyResult<ArrayD<u64>> {
+    let labels = from_dlpack(labels)?;
+    if let Ok(arr) = labels.extract::<PyReadonlyArrayDyn<u8>>() {
+        Ok(arr.as_array().mapv(|v| v as u64))
+    } else if let Ok(arr) = labels.extract::<PyReadonlyArrayDyn<u16>>() {
+        Ok(arr.as_array().mapv(|v| v as u64))
+    } else if let Ok(arr) = labels.extract::<PyReadonlyArrayDyn<u32>>() {
+        Ok(arr.as_array().mapv(|v| v as u64))
+    } else if let Ok(arr) = labels.extract::<PyReadonlyArrayDyn<u64>>() {
+        Ok(arr.as_array().to_owned())
+    } else if let Ok(arr) = labels.extract::<PyReadonlyArrayDyn<i16>>() {
+        Ok(arr.as_array().mapv(|v| v as u64))
+    } else if let Ok(arr) = labels.extract::<PyReadonlyArrayDyn<i32>>() {
+        Ok(arr.as_array().mapv(|v| v as u64))
+    } else if let Ok(arr) = labels.extract::<PyReadonlyArrayDyn<i64>>() {
+        Ok(arr.as_array().mapv(|v| v as u64))
     } else {
-        return Err(PyErr::new::<PyTypeError, _>(
-            "Unsupported array dtype, supported array dtypes are u8, u16, f32, and f64.",
-        ));
+        Err(PyErr::new::<PyTypeError, _>(
+            "Unsupported label dtype, supported label dtypes are u8, u16, u32, u64, i16, i32, and i64.",
+        ))
     }
 }
@@ -0,0 +1,137 @@
+use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::error::map_imgal_error;
+use crate::functions::filter_functions::parse_border_mode;
+use crate::utils::{DLPackArray2, dispatch_dtype};
+use imgal::transform::{self, Interpolation};
+
+/// Rescale a 2 or 3-dimensional image by a factor per axis.
+///
+/// This function resizes the image (see "resize") to the shape
+/// round(n × scale) of each axis, with at least 1 pixel per axis. Scales > 1.0
+/// upsample and scales < 1.0 downsample an axis.
+///
+/// :param data: The 2 or 3-dimensional image.
+/// :param scale: The scale factor of each axis. Must have one value per axis
+///     and each value must be > 0.0.
+/// :param interpolation: The interpolation method, "nearest", "linear" or
+///     "cubic", default = "linear".
+/// :param anti_aliasing: If True, downsampled axes are smoothed before
+///     resampling, default = True.
+/// :return: The rescaled image.
+#[pyfunction]
+#[pyo3(name = "rescale")]
+#[pyo3(signature = (data, scale, interpolation=None, anti_aliasing=None))]
+pub fn transform_rescale<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    scale: Vec<f64>,
+    interpolation: Option<&str>,
+    anti_aliasing: Option<bool>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let interpolation = Some(parse_interpolation(interpolation)?);
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        transform::rescale(arr.as_array(), &scale, interpolation, anti_aliasing)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Resize a 2 or 3-dimensional image to a new shape.
+///
+/// This function resamples the image on a grid with the new "shape", aligning
+/// the outer edges of the edge pixels of the input and output images. The
+/// output pixel at index j of an axis samples the input at
+/// x = (j + 0.5) × n_in / n_out - 0.5. Positions past the edges reflect the
+/// input. When an axis is downsampled and "anti_aliasing" is enabled, the image
+/// is smoothed first with a Gaussian of σ = (n_in / n_out - 1) / 2 along that
+/// axis to suppress aliasing.
+///
+/// :param data: The 2 or 3-dimensional image.
+/// :param shape: The output shape. Must have one value per axis and each value
+///     must be >= 1.
+/// :param interpolation: The interpolation method, "nearest", "linear" or
+///     "cubic", default = "linear".
+/// :param anti_aliasing: If True, downsampled axes are smoothed before
+///     resampling, default = True.
+/// :return: The resized image with the given shape.
+#[pyfunction]
+#[pyo3(name = "resize")]
+#[pyo3(signature = (data, shape, interpolation=None, anti_aliasing=None))]
+pub fn transform_resize<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    shape: Vec<usize>,
+    interpolation: Option<&str>,
+    anti_aliasing: Option<bool>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let interpolation = Some(parse_interpolation(interpolation)?);
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        transform::resize(arr.as_array(), &shape, interpolation, anti_aliasing)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Transform a 2 or 3-dimensional image with an affine matrix.
+///
+/// This function maps the image with the homogeneous affine "matrix", which
+/// maps input (row, col, 1) or (pln, row, col, 1) coordinates to output
+/// coordinates. The output value at each pixel is the input sampled at the
+/// inverse mapped position, output(p) = input(M⁻¹ × p). With a constant
+/// "border", output pixels that sample outside of the input image are set to
+/// "cval", otherwise the input is extended with the border mode. Apply the
+/// matrix estimated by "register.affine" to the moving image to register it
+/// with the reference image.
+///
+/// :param data: The 2 or 3-dimensional image.
+/// :param matrix: The (n + 1) x (n + 1) affine matrix of an n-dimensional
+///     image. The last row must be (0, ..., 0, 1) and the matrix must be
+///     invertible.
+/// :param interpolation: The interpolation method, "nearest", "linear" or
+///     "cubic", default = "linear".
+/// :param border: The border mode, "reflect", "constant" or "wrap", default =
+///     "constant".
+/// :param cval: The constant value of the "constant" border mode, default =
+///     0.0.
+/// :return: The transformed image with the same shape as "data".
+#[pyfunction]
+#[pyo3(name = "warp_affine")]
+#[pyo3(signature = (data, matrix, interpolation=None, border=None, cval=None))]
+pub fn transform_warp_affine<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    matrix: DLPackArray2<f64>,
+    interpolation: Option<&str>,
+    border: Option<&str>,
+    cval: Option<f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let interpolation = Some(parse_interpolation(interpolation)?);
+    let border = parse_border_mode(border, cval)?;
+
+    // pattern match and extract allowed array types
+    dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        transform::warp_affine(arr.as_array(), matrix.as_array(), interpolation, border)
+            .map(|output| output.into_pyarray(py))
+            .map_err(map_imgal_error)
+    })
+}
+
+/// Parse an interpolation method name.
+pub(crate) fn parse_interpolation(interpolation: Option<&str>) -> PyResult<Interpolation> {
+    match interpolation {
+        None | Some("linear") => Ok(Interpolation::Linear),
+        Some("nearest") => Ok(Interpolation::Nearest),
+        Some("cubic") => Ok(Interpolation::Cubic),
+        Some(i) => Err(PyValueError::new_err(format!(
+            "Unknown interpolation \"{}\", supported interpolations are \"nearest\", \"linear\" and \"cubic\".",
+            i
+        ))),
+    }
+}
@@ -0,0 +1,50 @@
+use numpy::{IntoPyArray, PyReadonlyArrayDyn};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::error::map_imgal_error;
+use crate::utils::{DLPackArray2, dispatch_dtype};
+use imgal::unmix;
+
+/// Unmix a multichannel image into non-negative abundances of reference
+/// spectra.
+///
+/// This function models the spectrum (b) of each pixel as a non-negative linear
+/// combination of the reference spectra, the columns of "spectra" (A), and
+/// solves the non-negative least squares (NNLS) problem:
+///
+/// minimize |A × x - b|²  subject to  x ≥ 0
+///
+/// with the Lawson-Hanson active set method. The pixels are solved in
+/// parallel.
+///
+/// :param data: The n-dimensional multichannel (e.g. hyperspectral) image.
+/// :param spectra: The (channel, component) matrix of reference spectra, one
+///     column per component. Must have one row per channel of "data", at most
+///     as many components as channels and linearly independent columns.
+/// :param axis: The channel axis, default = the last axis.
+/// :return: A dict with the non-negative "abundances" of each reference
+///     spectrum (the channel axis has one entry per component) and the
+///     "residual" norm of each pixel.
+#[pyfunction]
+#[pyo3(name = "linear")]
+#[pyo3(signature = (data, spectra, axis=None))]
+pub fn unmix_linear<'py>(
+    py: Python<'py>,
+    data: Bound<'py, PyAny>,
+    spectra: DLPackArray2<f64>,
+    axis: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let spec = spectra.as_array();
+
+    // pattern match and extract allowed array types
+    let result = dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+        unmix::linear(arr.as_array(), spec, axis)
+    })
+    .map_err(map_imgal_error)?;
+    let dict = PyDict::new(py);
+    dict.set_item("abundances", result.abundances.into_pyarray(py))?;
+    dict.set_item("residual", result.residual.into_pyarray(py))?;
+
+    Ok(dict)
+}
@@ -0,0 +1,55 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use imgal::version::{self, AlgorithmVersion};
+
+/// Get the algorithm version number in effect.
+///
+/// The algorithm version describes the exact numerical behavior of versioned
+/// algorithms (e.g. the phasor integration scheme and image histogram binning).
+///
+/// :return: The algorithm version number in effect.
+#[pyfunction]
+#[pyo3(name = "algorithm_version")]
+pub fn version_algorithm_version() -> u8 {
+    version::algorithm::current().number()
+}
+
+/// Get the newest algorithm version number.
+///
+/// :return: The newest algorithm version number, used unless another version
+///     is pinned.
+#[pyfunction]
+#[pyo3(name = "latest_algorithm_version")]
+pub fn version_latest_algorithm_version() -> u8 {
+    AlgorithmVersion::LATEST.number()
+}
+
+/// Pin the algorithm version.
+///
+/// Pin the algorithm version used by the versioned algorithms of all threads.
+/// Version 1 is currently the only version, so pinning does not change any
+/// result yet.
+///
+/// :param version: The algorithm version number to pin. If None, then the
+///     version is unpinned and the newest algorithm version is used.
+#[pyfunction]
+#[pyo3(name = "set_algorithm_version")]
+#[pyo3(signature = (version=None))]
+pub fn version_set_algorithm_version(version: Option<u8>) -> PyResult<()> {
+    match version {
+        Some(n) => match AlgorithmVersion::from_number(n) {
+            Some(v) => version::algorithm::set(Some(v)),
+            None => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown algorithm version {}, the newest algorithm version is {}.",
+                    n,
+                    AlgorithmVersion::LATEST.number()
+                )));
+            }
+        },
+        None => version::algorithm::set(None),
+    }
+
+    Ok(())
+}
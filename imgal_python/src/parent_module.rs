@@ -1,25 +1,40 @@
 use pyo3::prelude::*;
 
 use super::child_modules::{
-    colocalization_module, distribution_module, filter_module, image_module, integration_module,
-    kernel_module, parameter_module, phasor_module, simulation_module, statistics_module,
-    threshold_module,
+    anisotropy_module, colocalization_module, config_module, correction_module,
+    distribution_module, feature_module, filter_module, image_module, integration_module,
+    kernel_module, lifetime_module, measure_module, morphology_module, operator_module,
+    parameter_module, phasor_module, register_module, roi_module, simulation_module,
+    statistics_module, threshold_module, transform_module, unmix_module, version_module,
 };
 
 /// Python binding for the imgal parent module.
 #[pymodule(name = "imgal")]
 fn imgal_parent_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // register child modules
+    anisotropy_module::register_anisotropy_module(m)?;
     colocalization_module::register_colocalization_module(m)?;
+    config_module::register_config_module(m)?;
+    correction_module::register_correction_module(m)?;
     distribution_module::register_distribution_module(m)?;
+    feature_module::register_feature_module(m)?;
     filter_module::register_filter_module(m)?;
     image_module::register_image_module(m)?;
     integration_module::register_integration_module(m)?;
     kernel_module::register_kernel_module(m)?;
+    lifetime_module::register_lifetime_module(m)?;
+    measure_module::register_measure_module(m)?;
+    morphology_module::register_morphology_module(m)?;
+    operator_module::register_operator_module(m)?;
     parameter_module::register_parameter_module(m)?;
     phasor_module::register_phasor_module(m)?;
+    register_module::register_register_module(m)?;
+    roi_module::register_roi_module(m)?;
     simulation_module::register_simulation_module(m)?;
     statistics_module::register_statistics_module(m)?;
     threshold_module::register_threshold_module(m)?;
+    transform_module::register_transform_module(m)?;
+    unmix_module::register_unmix_module(m)?;
+    version_module::register_version_module(m)?;
     Ok(())
 }
@@ -1,6 +1,240 @@
-use std::ffi::CString;
+use std::borrow::Cow;
+use std::ffi::{CStr, CString, c_void};
+use std::ops::Deref;
 
+use numpy::ndarray::{ArrayView1, ArrayViewMut1, Dimension, Ix2, Ix3, Ix4, IxDyn};
+use numpy::{Element, PyArrayDyn, PyReadonlyArray, PyUntypedArray, get_array_module};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::intern;
 use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+
+/// Dispatch a function over the supported dtypes of a read-only array.
+///
+/// # Description
+///
+/// This macro extracts `data` as the numpy array type `array` with each
+/// supported element type in turn and evaluates `body` for the first match.
+/// `T` is an alias of the matched element type inside `body` (_e.g._ to extract
+/// a second array of the same dtype or to cast a threshold). By default the
+/// u8, u16, u32, i16, i32, f32 and f64 dtypes are supported and bool arrays are
+/// copied to u8 arrays of 0 and 1. An explicit list of element types can be
+/// given instead. DLPack producers (_e.g._ PyTorch tensors) are imported as
+/// numpy arrays without a copy first (see `from_dlpack`). If no dtype matches,
+/// the enclosing function returns a `TypeError`.
+///
+/// # Example
+///
+/// ```ignore
+/// dispatch_dtype!(data, PyReadonlyArrayDyn::<T>, |arr| {
+///     threshold::otsu(arr.as_array(), None, None).map_err(map_imgal_error)
+/// })
+/// ```
+macro_rules! dispatch_dtype {
+    ($data:expr, $array:ident::<$T:ident>, [$($t:ty),+ $(,)?], |$arr:pat_param| $body:expr) => {{
+        let data = $crate::utils::from_dlpack(&$data)?;
+        $crate::utils::dispatch_dtype!(
+            @dispatch data,
+            $array::<$T>,
+            [$($t),+],
+            &[$(stringify!($t)),+],
+            |$arr| $body
+        )
+    }};
+    ($data:expr, $array:ident::<$T:ident>, |$arr:pat_param| $body:expr) => {{
+        let data = $crate::utils::bool_as_u8(&$crate::utils::from_dlpack(&$data)?)?;
+        $crate::utils::dispatch_dtype!(
+            @dispatch data,
+            $array::<$T>,
+            [u8, u16, u32, i16, i32, f32, f64],
+            &["bool", "u8", "u16", "u32", "i16", "i32", "f32", "f64"],
+            |$arr| $body
+        )
+    }};
+    (@dispatch $data:expr, $array:ident::<$T:ident>, [$($t:ty),+], $names:expr, |$arr:pat_param| $body:expr) => {
+        'dispatch: {
+            $({
+                type $T = $t;
+                if let Ok($arr) = $data.extract::<$array<$T>>() {
+                    let output = $body;
+                    break 'dispatch output;
+                }
+            })+
+            return Err($crate::utils::unsupported_dtype($names));
+        }
+    };
+}
+pub(crate) use dispatch_dtype;
+
+/// Dispatch a function over the supported dtypes of a mutable array.
+///
+/// # Description
+///
+/// This macro is the mutable array version of `dispatch_dtype`, the u8, u16,
+/// u32, i16, i32, f32 and f64 dtypes are supported by default. bool arrays are
+/// not supported, a copy would not mutate the input array.
+macro_rules! dispatch_dtype_mut {
+    ($data:expr, $array:ident::<$T:ident>, [$($t:ty),+ $(,)?], |$arr:pat_param| $body:expr) => {
+        $crate::utils::dispatch_dtype!($data, $array::<$T>, [$($t),+], |$arr| $body)
+    };
+    ($data:expr, $array:ident::<$T:ident>, |$arr:pat_param| $body:expr) => {
+        $crate::utils::dispatch_dtype!(
+            $data,
+            $array::<$T>,
+            [u8, u16, u32, i16, i32, f32, f64],
+            |$arr| $body
+        )
+    };
+}
+pub(crate) use dispatch_dtype_mut;
+
+/// A read-only numpy array input that also accepts DLPack producers.
+///
+/// # Description
+///
+/// This receiver extracts numpy arrays like `PyReadonlyArray` and imports
+/// other DLPack producers (_e.g._ PyTorch, JAX or CuPy CPU tensors) and raw
+/// DLPack capsules as numpy arrays without a copy first (see `from_dlpack`).
+#[repr(transparent)]
+pub struct DLPackArray<'py, T: Element, D: Dimension>(PyReadonlyArray<'py, T, D>);
+
+/// A 2-dimensional `DLPackArray`.
+pub type DLPackArray2<'py, T> = DLPackArray<'py, T, Ix2>;
+
+/// A 3-dimensional `DLPackArray`.
+pub type DLPackArray3<'py, T> = DLPackArray<'py, T, Ix3>;
+
+/// A 4-dimensional `DLPackArray`.
+pub type DLPackArray4<'py, T> = DLPackArray<'py, T, Ix4>;
+
+/// An n-dimensional `DLPackArray`.
+pub type DLPackArrayDyn<'py, T> = DLPackArray<'py, T, IxDyn>;
+
+impl<'py, T: Element, D: Dimension> Deref for DLPackArray<'py, T, D> {
+    type Target = PyReadonlyArray<'py, T, D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'py, T: Element + 'py, D: Dimension + 'py> FromPyObject<'py> for DLPackArray<'py, T, D> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        from_dlpack(ob)?.extract().map(DLPackArray)
+    }
+}
+
+/// Import a DLPack producer as a numpy array without a copy.
+///
+/// # Description
+///
+/// This function passes objects that implement the DLPack protocol (_i.e._
+/// `__dlpack__` and `__dlpack_device__`) and raw DLPack capsules (_e.g._ from
+/// `torch.utils.dlpack.to_dlpack`) to `numpy.from_dlpack`, the returned numpy
+/// array shares the memory of the producer. numpy only imports tensors in CPU
+/// memory, tensors on other devices raise the numpy error.
+///
+/// # Arguments
+///
+/// * `data` - The input object.
+///
+/// # Returns
+///
+/// * `PyResult<Bound<PyAny>>` - The numpy array of a DLPack producer, numpy
+///   arrays and other objects are returned unchanged.
+pub fn from_dlpack<'py>(data: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let py = data.py();
+    if data.downcast::<PyUntypedArray>().is_ok() {
+        return Ok(data.clone());
+    }
+    let producer = if let Ok(capsule) = data.downcast::<PyCapsule>() {
+        Bound::new(py, DLPackCapsule::new(capsule)?)?.into_any()
+    } else if data.hasattr(intern!(py, "__dlpack__"))? {
+        data.clone()
+    } else {
+        return Ok(data.clone());
+    };
+
+    get_array_module(py)?.call_method1(intern!(py, "from_dlpack"), (producer,))
+}
+
+/// The device of a DLPack tensor, the `DLDevice` struct of `dlpack.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DLDevice {
+    device_type: i32,
+    device_id: i32,
+}
+
+/// The leading fields of the `DLTensor` struct of `dlpack.h`.
+#[repr(C)]
+struct DLTensorHead {
+    data: *mut c_void,
+    device: DLDevice,
+}
+
+/// The leading fields of the `DLManagedTensorVersioned` struct of `dlpack.h`.
+#[repr(C)]
+struct DLManagedTensorVersionedHead {
+    version: [u32; 2],
+    manager_ctx: *mut c_void,
+    deleter: *mut c_void,
+    flags: u64,
+    dl_tensor: DLTensorHead,
+}
+
+/// A DLPack producer of a raw DLPack capsule.
+///
+/// `numpy.from_dlpack` only accepts objects with the `__dlpack__` and
+/// `__dlpack_device__` methods, this class hands a raw "dltensor" or
+/// "dltensor_versioned" capsule over to numpy.
+#[pyclass(module = "imgal", frozen)]
+struct DLPackCapsule {
+    capsule: Py<PyCapsule>,
+    device: DLDevice,
+}
+
+impl DLPackCapsule {
+    fn new(capsule: &Bound<'_, PyCapsule>) -> PyResult<Self> {
+        // read the device of the tensor, the capsule is consumed by numpy
+        let ptr = capsule.pointer();
+        let device = match capsule.name()?.map(CStr::to_bytes) {
+            Some(b"dltensor") => unsafe { (*(ptr as *const DLTensorHead)).device },
+            Some(b"dltensor_versioned") => unsafe {
+                (*(ptr as *const DLManagedTensorVersionedHead))
+                    .dl_tensor
+                    .device
+            },
+            _ => {
+                return Err(PyValueError::new_err(
+                    "The capsule is not an unconsumed DLPack \"dltensor\" capsule.",
+                ));
+            }
+        };
+
+        Ok(DLPackCapsule {
+            capsule: capsule.clone().unbind(),
+            device,
+        })
+    }
+}
+
+#[pymethods]
+impl DLPackCapsule {
+    #[pyo3(signature = (*_args, **_kwargs))]
+    fn __dlpack__<'py>(
+        &self,
+        py: Python<'py>,
+        _args: &Bound<'py, PyAny>,
+        _kwargs: Option<&Bound<'py, PyAny>>,
+    ) -> Bound<'py, PyCapsule> {
+        self.capsule.bind(py).clone()
+    }
+
+    fn __dlpack_device__(&self) -> (i32, i32) {
+        (self.device.device_type, self.device.device_id)
+    }
+}
 
 /// Add a child module to Python's sys.modules dict.
 ///
@@ -23,3 +257,98 @@ pub fn py_import_module(module_name: &str) {
         py.run(c_str_cmd.as_c_str(), None, None).unwrap();
     });
 }
+
+/// Get a 1-dimensional array view as a slice.
+///
+/// # Description
+///
+/// This function borrows the elements of contiguous views and copies the
+/// elements of strided views (e.g. a sliced column or a reversed array), so
+/// slice based functions accept any numpy view.
+///
+/// # Arguments
+///
+/// * `view` - The 1-dimensional array view.
+///
+/// # Returns
+///
+/// * `Cow<[T]>` - The elements of the view in order.
+pub fn contiguous<'a, T: Clone>(view: ArrayView1<'a, T>) -> Cow<'a, [T]> {
+    match view.to_slice() {
+        Some(s) => Cow::Borrowed(s),
+        None => Cow::Owned(view.to_vec()),
+    }
+}
+
+/// Apply a slice mutating function to a 1-dimensional mutable array view.
+///
+/// # Description
+///
+/// This function passes the elements of contiguous views to `f` without a
+/// copy. The elements of strided views (e.g. a sliced column or a reversed
+/// array) are copied into a contiguous buffer and written back to the view
+/// after `f` returns, so the mutation is visible through the numpy view either
+/// way.
+///
+/// # Arguments
+///
+/// * `view` - The 1-dimensional mutable array view.
+/// * `f` - The function that mutates the elements of the view in order.
+///
+/// # Returns
+///
+/// * `R` - The output of `f`.
+pub fn with_slice_mut<T, R, F>(mut view: ArrayViewMut1<T>, f: F) -> R
+where
+    T: Clone,
+    F: FnOnce(&mut [T]) -> R,
+{
+    if let Some(s) = view.as_slice_mut() {
+        return f(s);
+    }
+    let mut buf = view.to_vec();
+    let output = f(&mut buf);
+    view.iter_mut().zip(buf).for_each(|(v, b)| *v = b);
+
+    output
+}
+
+/// Copy a numpy bool array to a u8 array of 0 and 1.
+///
+/// # Arguments
+///
+/// * `data` - The input object.
+///
+/// # Returns
+///
+/// * `PyResult<Bound<PyAny>>` - The u8 copy of a bool array, other objects are
+///   returned unchanged.
+pub fn bool_as_u8<'py>(data: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    if data.downcast::<PyArrayDyn<bool>>().is_ok() {
+        data.call_method1("astype", ("uint8",))
+    } else {
+        Ok(data.clone())
+    }
+}
+
+/// Create the error of an unsupported array dtype.
+///
+/// # Arguments
+///
+/// * `dtypes` - The names of the supported dtypes.
+///
+/// # Returns
+///
+/// * `PyErr` - A `TypeError` listing the supported dtypes.
+pub fn unsupported_dtype(dtypes: &[&str]) -> PyErr {
+    let supported = match dtypes {
+        [] => String::new(),
+        [one] => one.to_string(),
+        [a, b] => format!("{} and {}", a, b),
+        [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+    };
+    PyTypeError::new_err(format!(
+        "Unsupported array dtype, supported array dtypes are {}.",
+        supported
+    ))
+}
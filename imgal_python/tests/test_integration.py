@@ -0,0 +1,15 @@
+import pytest
+
+import imgal.integration as integration
+
+
+def test_simpson():
+    # an even number of subintervals is integrated with Simpson's 1/3 rule
+    assert integration.simpson([0.0, 1.0, 4.0, 9.0, 16.0]) == pytest.approx(64.0 / 3.0)
+    assert integration.simpson([1.0, 1.0, 1.0], 0.5) == pytest.approx(1.0)
+
+
+def test_simpson_odd_subintervals():
+    # an odd number of subintervals raises an exception instead of panicking
+    with pytest.raises(Exception, match="odd number of subintervals"):
+        integration.simpson([0.0, 1.0, 2.0, 3.0])
@@ -0,0 +1,257 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use ndarray::{ArrayD, ArrayViewD, Dimension, IxDyn};
+
+/// Environment variable that, when set, makes `assert_golden` overwrite golden
+/// files with the actual output instead of comparing against them.
+pub const UPDATE_GOLDEN_ENV: &str = "IMGAL_UPDATE_GOLDEN";
+
+/// The difference between an expected and an actual array.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayDiff {
+    /// The array shapes do not match.
+    Shape {
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+    /// One or more values differ by more than the tolerance.
+    Values {
+        mismatched: usize,
+        max_abs_diff: f64,
+        index: Vec<usize>,
+        expected: f64,
+        actual: f64,
+    },
+}
+
+impl fmt::Display for ArrayDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArrayDiff::Shape { expected, actual } => {
+                write!(
+                    f,
+                    "Mismatched array shapes, expected {:?} but got {:?}.",
+                    expected, actual
+                )
+            }
+            ArrayDiff::Values {
+                mismatched,
+                max_abs_diff,
+                index,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "{} value(s) outside of tolerance, max absolute difference {} at index {:?} (expected {} but got {}).",
+                    mismatched, max_abs_diff, index, expected, actual
+                )
+            }
+        }
+    }
+}
+
+/// Compare two arrays element-wise within an absolute tolerance.
+///
+/// # Description
+///
+/// This function compares the `expected` and `actual` arrays and reports the
+/// number of elements whose absolute difference exceeds `tolerance`, along with
+/// the index of the largest difference. NaN values are considered equal to
+/// other NaN values and infinite values are equal if they have the same sign.
+///
+/// # Arguments
+///
+/// * `expected`: The expected (_e.g._ golden) array.
+/// * `actual`: The actual array to compare.
+/// * `tolerance`: The maximum allowed absolute difference between elements.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the arrays have the same shape and all elements are within
+///   tolerance.
+/// * `Err(ArrayDiff)`: The shape mismatch or the summary of mismatched values.
+pub fn diff(
+    expected: ArrayViewD<f64>,
+    actual: ArrayViewD<f64>,
+    tolerance: f64,
+) -> Result<(), ArrayDiff> {
+    if expected.shape() != actual.shape() {
+        return Err(ArrayDiff::Shape {
+            expected: expected.shape().to_vec(),
+            actual: actual.shape().to_vec(),
+        });
+    }
+
+    // find the number of mismatches and the largest difference
+    let mut mismatched = 0;
+    let mut worst: Option<(f64, Vec<usize>, f64, f64)> = None;
+    expected
+        .indexed_iter()
+        .zip(actual.iter())
+        .for_each(|((idx, &e), &a)| {
+            let d = abs_diff(e, a);
+            if d > tolerance {
+                mismatched += 1;
+                if worst.as_ref().is_none_or(|w| d > w.0) {
+                    worst = Some((d, idx.slice().to_vec(), e, a));
+                }
+            }
+        });
+
+    match worst {
+        None => Ok(()),
+        Some((max_abs_diff, index, expected, actual)) => Err(ArrayDiff::Values {
+            mismatched,
+            max_abs_diff,
+            index,
+            expected,
+            actual,
+        }),
+    }
+}
+
+/// Assert that two arrays are element-wise equal within an absolute tolerance.
+///
+/// # Arguments
+///
+/// * `expected`: The expected array.
+/// * `actual`: The actual array to compare.
+/// * `tolerance`: The maximum allowed absolute difference between elements.
+///
+/// # Panics
+///
+/// Panics with the `ArrayDiff` description if the arrays do not match.
+pub fn assert_array_close(expected: ArrayViewD<f64>, actual: ArrayViewD<f64>, tolerance: f64) {
+    if let Err(d) = diff(expected, actual, tolerance) {
+        panic!("{}", d);
+    }
+}
+
+/// Assert that an array matches a golden file within an absolute tolerance.
+///
+/// # Description
+///
+/// This function compares `actual` against the golden file at `path`. If the
+/// golden file does not exist, or the `IMGAL_UPDATE_GOLDEN` environment
+/// variable is set, the golden file is (re)written with `actual` instead.
+///
+/// # Arguments
+///
+/// * `path`: The golden file path.
+/// * `actual`: The actual array to compare.
+/// * `tolerance`: The maximum allowed absolute difference between elements.
+///
+/// # Panics
+///
+/// Panics if the golden file can not be read or written, or if `actual` does
+/// not match the golden file.
+pub fn assert_golden<P: AsRef<Path>>(path: P, actual: ArrayViewD<f64>, tolerance: f64) {
+    let path = path.as_ref();
+    if env::var_os(UPDATE_GOLDEN_ENV).is_some() || !path.exists() {
+        write_golden(path, actual)
+            .unwrap_or_else(|e| panic!("Failed to write golden file {}: {}", path.display(), e));
+        return;
+    }
+    let expected = read_golden(path)
+        .unwrap_or_else(|e| panic!("Failed to read golden file {}: {}", path.display(), e));
+    if let Err(d) = diff(expected.view(), actual, tolerance) {
+        panic!("Golden file {} mismatch: {}", path.display(), d);
+    }
+}
+
+/// Read an array from a golden file.
+///
+/// # Description
+///
+/// Golden files are plain text. The first line holds the comma separated
+/// array shape prefixed with `# shape:` and every following line holds one
+/// value in row-major (_i.e._ C) order.
+///
+/// # Arguments
+///
+/// * `path`: The golden file path.
+///
+/// # Returns
+///
+/// * `Ok(ArrayD<f64>)`: The array stored in the golden file.
+/// * `Err(io::Error)`: If the file can not be read or is malformed.
+pub fn read_golden<P: AsRef<Path>>(path: P) -> io::Result<ArrayD<f64>> {
+    let text = fs::read_to_string(path)?;
+    let mut lines = text.lines();
+
+    // parse the shape header
+    let header = lines
+        .next()
+        .and_then(|l| l.strip_prefix("# shape:"))
+        .ok_or_else(|| invalid_data("missing '# shape:' header".to_string()))?;
+    let shape = header
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|e| invalid_data(format!("invalid shape, {}", e)))?;
+
+    // parse the values
+    let data = lines
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|e| invalid_data(format!("invalid value, {}", e)))?;
+
+    ArrayD::from_shape_vec(IxDyn(&shape), data).map_err(|e| invalid_data(e.to_string()))
+}
+
+/// Write an array to a golden file.
+///
+/// # Description
+///
+/// See `read_golden` for the golden file format. Values are written with the
+/// shortest representation that round trips exactly.
+///
+/// # Arguments
+///
+/// * `path`: The golden file path. Missing parent directories are created.
+/// * `data`: The array to write.
+///
+/// # Returns
+///
+/// * `Ok(())`: If the golden file was written.
+/// * `Err(io::Error)`: If the file could not be written.
+pub fn write_golden<P: AsRef<Path>>(path: P, data: ArrayViewD<f64>) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let shape: Vec<String> = data.shape().iter().map(|d| d.to_string()).collect();
+    let mut text = format!("# shape: {}\n", shape.join(","));
+    data.iter().for_each(|v| {
+        text.push_str(&v.to_string());
+        text.push('\n');
+    });
+
+    fs::write(path, text)
+}
+
+/// Compute the absolute difference between two values, treating NaNs and
+/// matching infinities as equal.
+fn abs_diff(a: f64, b: f64) -> f64 {
+    if (a.is_nan() && b.is_nan()) || a == b {
+        0.0
+    } else if a.is_nan() || b.is_nan() {
+        f64::INFINITY
+    } else {
+        (a - b).abs()
+    }
+}
+
+/// Create an invalid data IO error.
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
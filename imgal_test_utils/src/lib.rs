@@ -0,0 +1,6 @@
+//! The `imgal_test_utils` crate provides the property-based testing strategies
+//! and golden-file comparison helpers used to test `imgal`. Downstream plugin
+//! authors can use these utilities to test their code against `imgal` behavior
+//! the same way the `imgal` crate does.
+pub mod golden;
+pub mod strategies;
@@ -0,0 +1,154 @@
+use ndarray::{Array2, Array3, Axis, Zip};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use imgal::simulation::decay;
+use imgal::simulation::instrument::GaussianIrf;
+
+/// Create a strategy for lifetimes and their fractional intensities.
+///
+/// # Description
+///
+/// This strategy generates one to `max_components` lifetimes (taus) in the
+/// range [0.1, 10.0) with matching positive fractional intensities that sum to
+/// 1.0, suitable for the `imgal::simulation::decay` functions.
+///
+/// # Arguments
+///
+/// * `max_components`: The maximum number of exponential components, must be
+///   >= 1.
+///
+/// # Returns
+///
+/// * `impl Strategy<Value = (Vec<f64>, Vec<f64>)>`: The taus and fractions
+///   strategy.
+pub fn lifetimes(max_components: usize) -> impl Strategy<Value = (Vec<f64>, Vec<f64>)> {
+    (1..=max_components.max(1)).prop_flat_map(|n: usize| {
+        (vec(0.1..10.0f64, n), vec(0.05..1.0f64, n)).prop_map(move |(taus, weights)| {
+            let total: f64 = weights.iter().sum();
+            let mut fractions: Vec<f64> = weights.iter().map(|w| w / total).collect();
+
+            // absorb round off in the last fraction so the sum is 1.0
+            let head: f64 = fractions[..n - 1].iter().sum();
+            fractions[n - 1] = 1.0 - head;
+            (taus, fractions)
+        })
+    })
+}
+
+/// Create a strategy for 1-dimensional Gaussian IRF convolved decay curves.
+///
+/// # Description
+///
+/// This strategy generates decay curves with `imgal::simulation::decay::gaussian_exponential_1d`
+/// from random lifetimes (see `lifetimes`), total counts in the range
+/// [100.0, 10000.0), an IRF center within the first 40% of the period and an
+/// IRF width between 1% and 10% of the period.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up the decay curve.
+/// * `period`: The period (_i.e._ time interval), must be greater than 0.0.
+///
+/// # Returns
+///
+/// * `impl Strategy<Value = Vec<f64>>`: The decay curve strategy.
+pub fn decay_curve(samples: usize, period: f64) -> impl Strategy<Value = Vec<f64>> {
+    (lifetimes(3), 100.0..10000.0f64, 0.05..0.4f64, 0.01..0.1f64).prop_map(
+        move |((taus, fractions), counts, center, width)| {
+            decay::gaussian_exponential_1d(
+                samples,
+                period,
+                &taus,
+                &fractions,
+                counts,
+                GaussianIrf {
+                    center: center * period,
+                    width: width * period,
+                },
+            )
+            .expect("Failed to simulate a decay curve from valid parameters.")
+        },
+    )
+}
+
+/// Create a strategy for 3-dimensional (row, col, t) decay stacks.
+///
+/// # Description
+///
+/// This strategy generates a decay curve (see `decay_curve`) and broadcasts it
+/// into an image with a random shape up to `max_shape`, scaling each pixel by a
+/// random intensity in the range [0.0, 1.0]. Roughly 10% of pixels are empty
+/// (_i.e._ all zeros) to exercise background handling.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up each decay curve.
+/// * `period`: The period (_i.e._ time interval), must be greater than 0.0.
+/// * `max_shape`: The maximum row and col shape of the stack.
+///
+/// # Returns
+///
+/// * `impl Strategy<Value = Array3<f64>>`: The decay stack strategy.
+pub fn decay_stack(
+    samples: usize,
+    period: f64,
+    max_shape: (usize, usize),
+) -> impl Strategy<Value = Array3<f64>> {
+    (1..=max_shape.0.max(1), 1..=max_shape.1.max(1)).prop_flat_map(move |(rows, cols)| {
+        (
+            decay_curve(samples, period),
+            vec(prop_oneof![1 => Just(0.0), 9 => 0.0..=1.0f64], rows * cols),
+        )
+            .prop_map(move |(curve, scales)| {
+                let mut stack = Array3::<f64>::zeros((rows, cols, samples));
+                stack
+                    .lanes_mut(Axis(2))
+                    .into_iter()
+                    .zip(scales.iter())
+                    .for_each(|(mut ln, &s)| {
+                        Zip::from(&mut ln).and(&curve).for_each(|v, &c| *v = c * s);
+                    });
+                stack
+            })
+    })
+}
+
+/// Create a strategy for 2-dimensional boolean masks.
+///
+/// # Arguments
+///
+/// * `shape`: The row and col shape of the mask.
+///
+/// # Returns
+///
+/// * `impl Strategy<Value = Array2<bool>>`: The mask strategy, where each pixel
+///   is independently `true` or `false`.
+pub fn mask(shape: (usize, usize)) -> impl Strategy<Value = Array2<bool>> {
+    vec(any::<bool>(), shape.0 * shape.1).prop_map(move |v| {
+        Array2::from_shape_vec(shape, v).expect("Mask shape and data length must match.")
+    })
+}
+
+/// Create a strategy for a decay stack paired with a mask of the same shape.
+///
+/// # Arguments
+///
+/// * `samples`: The number of discrete points that make up each decay curve.
+/// * `period`: The period (_i.e._ time interval), must be greater than 0.0.
+/// * `max_shape`: The maximum row and col shape of the stack and mask.
+///
+/// # Returns
+///
+/// * `impl Strategy<Value = (Array3<f64>, Array2<bool>)>`: The decay stack and
+///   matching mask strategy.
+pub fn masked_decay_stack(
+    samples: usize,
+    period: f64,
+    max_shape: (usize, usize),
+) -> impl Strategy<Value = (Array3<f64>, Array2<bool>)> {
+    decay_stack(samples, period, max_shape).prop_flat_map(|stack| {
+        let (rows, cols, _) = stack.dim();
+        (Just(stack), mask((rows, cols)))
+    })
+}
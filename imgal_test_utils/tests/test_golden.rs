@@ -0,0 +1,82 @@
+use std::env;
+use std::path::PathBuf;
+
+use ndarray::{Array1, Array2};
+
+use imgal::simulation::decay;
+use imgal::simulation::instrument::GaussianIrf;
+use imgal_test_utils::golden::{self, ArrayDiff};
+
+// simulated bioexponential decay parameters, unit is nanoseconds
+const SAMPLES: usize = 256;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+const IRF: GaussianIrf = GaussianIrf {
+    center: 3.0,
+    width: 0.5,
+};
+
+// helper functions
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name)
+}
+
+#[test]
+fn golden_assert_golden() {
+    let i = decay::gaussian_exponential_1d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, IRF)
+        .unwrap();
+    let i = Array1::from_vec(i);
+
+    golden::assert_golden(
+        golden_path("decay_gaussian_exponential_1d.txt"),
+        i.view().into_dyn(),
+        1e-9,
+    );
+}
+
+#[test]
+fn golden_diff() {
+    let a = Array2::<f64>::zeros((2, 3));
+    let mut b = a.clone();
+    b[[1, 2]] = 0.5;
+    b[[0, 1]] = f64::NAN;
+
+    assert!(golden::diff(a.view().into_dyn(), a.view().into_dyn(), 0.0).is_ok());
+    match golden::diff(a.view().into_dyn(), b.view().into_dyn(), 1e-3) {
+        Err(ArrayDiff::Values {
+            mismatched, index, ..
+        }) => {
+            // a NaN against a number is the largest possible difference
+            assert_eq!(mismatched, 2);
+            assert_eq!(index, vec![0, 1]);
+        }
+        other => panic!("Expected a values diff but got {:?}.", other),
+    }
+    assert_eq!(
+        golden::diff(
+            a.view().into_dyn(),
+            Array2::<f64>::zeros((3, 2)).view().into_dyn(),
+            0.0
+        ),
+        Err(ArrayDiff::Shape {
+            expected: vec![2, 3],
+            actual: vec![3, 2],
+        })
+    );
+}
+
+#[test]
+fn golden_read_write() {
+    let path = env::temp_dir().join("imgal_test_utils_golden_read_write.txt");
+    let data = Array2::from_shape_vec((2, 2), vec![0.1, -2.5e-12, f64::NAN, 1e300]).unwrap();
+    golden::write_golden(&path, data.view().into_dyn()).unwrap();
+    let read = golden::read_golden(&path).unwrap();
+
+    assert_eq!(read.shape(), [2, 2]);
+    golden::assert_array_close(data.view().into_dyn(), read.view(), 0.0);
+}
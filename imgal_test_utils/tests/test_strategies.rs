@@ -0,0 +1,45 @@
+use ndarray::Axis;
+use proptest::prelude::*;
+
+use imgal::phasor::time_domain;
+use imgal::simulation::decay;
+use imgal::statistics::sum;
+use imgal_test_utils::strategies;
+
+const SAMPLES: usize = 128;
+const PERIOD: f64 = 12.5;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn strategies_lifetimes((taus, fractions) in strategies::lifetimes(3)) {
+        prop_assert_eq!(taus.len(), fractions.len());
+        prop_assert!((sum(&fractions) - 1.0).abs() < 1e-12);
+        prop_assert!(fractions.iter().all(|&f| f > 0.0));
+    }
+
+    #[test]
+    fn strategies_decay_curve_sample_photons(curve in strategies::decay_curve(SAMPLES, PERIOD)) {
+        // exact photon sampling always returns the requested counts
+        let hist = decay::sample_photons_1d(&curve, 500, None).unwrap();
+        prop_assert_eq!(sum(&hist), 500.0);
+    }
+
+    #[test]
+    fn strategies_masked_decay_stack_phasor(
+        (stack, mask) in strategies::masked_decay_stack(SAMPLES, PERIOD, (8, 8))
+    ) {
+        // phasor coordinates of non-empty pixels lie within the universal circle
+        let gs = time_domain::image(stack.view(), PERIOD, Some(mask.view()), None, None).unwrap();
+        let g = gs.index_axis(Axis(2), 0);
+        let s = gs.index_axis(Axis(2), 1);
+        for ((&gv, &sv), &m) in g.iter().zip(s.iter()).zip(mask.iter()) {
+            if !m {
+                prop_assert_eq!((gv, sv), (0.0, 0.0));
+            } else if gv.is_finite() {
+                prop_assert!(gv * gv + sv * sv <= 1.0 + 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use wasm_bindgen::prelude::*;
+
+use imgal::filter;
+
+use crate::{js_error, row_major, view_nd};
+
+/// Blur an n-dimensional image with a Gaussian filter.
+///
+/// # Arguments
+///
+/// * `data`: The row major n-dimensional image.
+/// * `shape`: The shape of the image.
+/// * `sigma`: The standard deviation of the Gaussian, either one value for all
+///   axes or one value per axis. Must be >= 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The row major filtered image with the same shape.
+/// * `Err(JsError)`: If the data length does not match the shape. If sigma does
+///   not have one value or one value per axis. If a sigma is < 0.0.
+#[wasm_bindgen(js_name = filterGaussian)]
+pub fn filter_gaussian(data: &[f64], shape: &[usize], sigma: &[f64]) -> Result<Vec<f64>, JsError> {
+    let arr = view_nd(data, shape)?;
+    let sigma = match sigma {
+        [s] => vec![*s; shape.len()],
+        _ => sigma.to_vec(),
+    };
+    filter::gaussian(arr, &sigma, None, None)
+        .map(row_major)
+        .map_err(js_error)
+}
@@ -0,0 +1,63 @@
+//! WebAssembly bindings for a browser-friendly subset of `imgal`.
+//!
+//! The functions take images as JavaScript typed arrays in C (_i.e._ row major)
+//! order with their dimensions, return typed arrays in the same order and
+//! throw a JavaScript `Error` with the `imgal` error message on invalid
+//! arguments.
+use ndarray::{Array, ArrayView3, ArrayViewD, Dimension, IxDyn};
+use wasm_bindgen::JsError;
+
+use imgal::error::ImgalError;
+
+pub mod filter;
+pub mod phasor;
+pub mod statistics;
+pub mod threshold;
+
+/// Convert an imgal error into a JavaScript error.
+pub(crate) fn js_error(e: ImgalError) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Convert a boolean mask into row major bytes of 0 and 1.
+pub(crate) fn mask_bytes<D: Dimension>(mask: Array<bool, D>) -> Vec<u8> {
+    mask.iter().map(|&m| u8::from(m)).collect()
+}
+
+/// Copy an array into a row major vector, whatever its memory layout.
+pub(crate) fn row_major<T: Clone, D: Dimension>(arr: Array<T, D>) -> Vec<T> {
+    if arr.is_standard_layout() {
+        arr.into_raw_vec_and_offset().0
+    } else {
+        arr.iter().cloned().collect()
+    }
+}
+
+/// View a row major typed array as a 3-dimensional (row, col, n) array.
+pub(crate) fn view_3d<T>(
+    data: &[T],
+    rows: usize,
+    cols: usize,
+) -> Result<ArrayView3<'_, T>, JsError> {
+    let pixels = rows * cols;
+    if pixels == 0 || !data.len().is_multiple_of(pixels) {
+        return Err(JsError::new(&format!(
+            "The array length {} is not a multiple of rows × cols = {}.",
+            data.len(),
+            pixels
+        )));
+    }
+
+    Ok(ArrayView3::from_shape((rows, cols, data.len() / pixels), data).unwrap())
+}
+
+/// View a row major typed array as an n-dimensional array.
+pub(crate) fn view_nd<'a, T>(data: &'a [T], shape: &[usize]) -> Result<ArrayViewD<'a, T>, JsError> {
+    ArrayViewD::from_shape(IxDyn(shape), data).map_err(|_| {
+        JsError::new(&format!(
+            "The array length {} does not match the shape {:?}.",
+            data.len(),
+            shape
+        ))
+    })
+}
@@ -0,0 +1,167 @@
+use wasm_bindgen::prelude::*;
+
+use imgal::phasor::analysis::PhasorCursor;
+use imgal::phasor::{calibration, spectral, time_domain};
+use imgal::traits::numeric::ToFloat64;
+
+use crate::{js_error, mask_bytes, row_major, view_3d};
+
+/// The modulation and phase calibration values.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Calibration {
+    /// The modulation, M, to scale the (G, S) coordinates.
+    pub modulation: f64,
+    /// The phase, φ angle, to rotate the (G, S) coordinates.
+    pub phase: f64,
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a decay image.
+///
+/// # Arguments
+///
+/// * `data`: I(t), the row major (row, col, t) decay data image.
+/// * `rows`: The number of image rows.
+/// * `cols`: The number of image columns.
+/// * `period`: The period (_i.e._ time interval).
+/// * `harmonic`: The harmonic value, default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The row major (row, col, 2) G and S image.
+/// * `Err(JsError)`: If the data length is not a multiple of rows × cols. If
+///   period is <= 0.0 or harmonic is < 1.0.
+#[wasm_bindgen(js_name = phasorTimeDomainImage)]
+pub fn phasor_time_domain_image(
+    data: &[f64],
+    rows: usize,
+    cols: usize,
+    period: f64,
+    harmonic: Option<f64>,
+) -> Result<Vec<f64>, JsError> {
+    time_domain_image(data, rows, cols, period, harmonic)
+}
+
+/// Compute the real and imaginary (G, S) coordinates of a u16 decay image
+/// (_e.g._ photon counts).
+///
+/// # Arguments
+///
+/// * `data`: I(t), the row major (row, col, t) decay data image.
+/// * `rows`: The number of image rows.
+/// * `cols`: The number of image columns.
+/// * `period`: The period (_i.e._ time interval).
+/// * `harmonic`: The harmonic value, default = 1.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The row major (row, col, 2) G and S image.
+/// * `Err(JsError)`: If the data length is not a multiple of rows × cols. If
+///   period is <= 0.0 or harmonic is < 1.0.
+#[wasm_bindgen(js_name = phasorTimeDomainImageU16)]
+pub fn phasor_time_domain_image_u16(
+    data: &[u16],
+    rows: usize,
+    cols: usize,
+    period: f64,
+    harmonic: Option<f64>,
+) -> Result<Vec<f64>, JsError> {
+    time_domain_image(data, rows, cols, period, harmonic)
+}
+
+/// Calibrate the real and imaginary (G, S) coordinates of a phasor image.
+///
+/// # Arguments
+///
+/// * `data`: The row major (row, col, 2) G and S image.
+/// * `rows`: The number of image rows.
+/// * `cols`: The number of image columns.
+/// * `modulation`: The modulation to scale the input (G, S) coordinates.
+/// * `phase`: The phase, φ angle, to rotate the input (G, S) coordinates.
+///
+/// # Returns
+///
+/// * `Ok(Vec<f64>)`: The row major (row, col, 2) calibrated G and S image.
+/// * `Err(JsError)`: If the data length is not a multiple of rows × cols.
+#[wasm_bindgen(js_name = phasorCalibrationImage)]
+pub fn phasor_calibration_image(
+    data: &[f64],
+    rows: usize,
+    cols: usize,
+    modulation: f64,
+    phase: f64,
+) -> Result<Vec<f64>, JsError> {
+    let arr = view_3d(data, rows, cols)?;
+    calibration::image(arr, modulation, phase, None, None)
+        .map(row_major)
+        .map_err(js_error)
+}
+
+/// Compute the modulation and phase calibration values from the measured
+/// coordinates of a reference with a known monoexponential lifetime.
+///
+/// # Arguments
+///
+/// * `g`: The measured real (G) value.
+/// * `s`: The measured imaginary (S) value.
+/// * `tau`: The lifetime, τ.
+/// * `omega`: The angular frequency, ω.
+///
+/// # Returns
+///
+/// * `Calibration`: The modulation and phase calibration values.
+#[wasm_bindgen(js_name = phasorModulationAndPhase)]
+pub fn phasor_modulation_and_phase(g: f64, s: f64, tau: f64, omega: f64) -> Calibration {
+    let (modulation, phase) = calibration::modulation_and_phase(g, s, tau, omega);
+
+    Calibration { modulation, phase }
+}
+
+/// Select the pixels of a phasor image within a circular phasor cursor.
+///
+/// # Arguments
+///
+/// * `data`: The row major (row, col, 2) G and S image.
+/// * `rows`: The number of image rows.
+/// * `cols`: The number of image columns.
+/// * `g`: The real component, G, of the cursor center.
+/// * `s`: The imaginary component, S, of the cursor center.
+/// * `radius`: The cursor radius. Must be > 0.0.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)`: The row major (row, col) selection mask, 1 for the
+///   pixels within the cursor and 0 otherwise.
+/// * `Err(JsError)`: If the data is not a (row, col, 2) image. If radius is
+///   <= 0.0.
+#[wasm_bindgen(js_name = phasorCursorMask)]
+pub fn phasor_cursor_mask(
+    data: &[f64],
+    rows: usize,
+    cols: usize,
+    g: f64,
+    s: f64,
+    radius: f64,
+) -> Result<Vec<u8>, JsError> {
+    let arr = view_3d(data, rows, cols)?;
+    spectral::cursor_mask(arr, PhasorCursor { g, s, radius }, None, None)
+        .map(mask_bytes)
+        .map_err(js_error)
+}
+
+/// Compute the time domain phasor image of any decay data type.
+fn time_domain_image<T>(
+    data: &[T],
+    rows: usize,
+    cols: usize,
+    period: f64,
+    harmonic: Option<f64>,
+) -> Result<Vec<f64>, JsError>
+where
+    T: ToFloat64,
+{
+    let arr = view_3d(data, rows, cols)?;
+    time_domain::image(arr, period, None, harmonic, None)
+        .map(row_major)
+        .map_err(js_error)
+}
@@ -0,0 +1,99 @@
+use wasm_bindgen::prelude::*;
+
+use imgal::statistics::{self, Binning};
+
+use crate::{js_error, view_nd};
+
+/// Descriptive statistics of a set of values.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Description {
+    /// The number of values.
+    pub count: usize,
+    /// The arithmetic mean.
+    pub mean: f64,
+    /// The sample variance (_i.e._ with `n - 1` degrees of freedom).
+    pub variance: f64,
+    /// The (biased) sample skewness.
+    pub skewness: f64,
+    /// The (biased) sample excess kurtosis.
+    pub kurtosis: f64,
+    /// The minimum value.
+    pub min: f64,
+    /// The maximum value.
+    pub max: f64,
+    /// The median value.
+    pub median: f64,
+}
+
+/// A histogram with bin counts and bin edges.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    counts: Vec<f64>,
+    edges: Vec<f64>,
+}
+
+#[wasm_bindgen]
+impl Histogram {
+    /// The count of each bin.
+    #[wasm_bindgen(getter)]
+    pub fn counts(&self) -> Vec<f64> {
+        self.counts.clone()
+    }
+
+    /// The bin edges, one more than the number of bins.
+    #[wasm_bindgen(getter)]
+    pub fn edges(&self) -> Vec<f64> {
+        self.edges.clone()
+    }
+}
+
+/// Compute the descriptive statistics of a set of values.
+///
+/// # Arguments
+///
+/// * `data`: The values.
+///
+/// # Returns
+///
+/// * `Ok(Description)`: The descriptive statistics.
+/// * `Err(JsError)`: If the data is empty.
+#[wasm_bindgen(js_name = statisticsDescribe)]
+pub fn statistics_describe(data: &[f64]) -> Result<Description, JsError> {
+    let arr = view_nd(data, &[data.len()])?;
+    let d = statistics::describe(arr, None).map_err(js_error)?;
+
+    Ok(Description {
+        count: d.count,
+        mean: d.mean,
+        variance: d.variance,
+        skewness: d.skewness,
+        kurtosis: d.kurtosis,
+        min: d.min,
+        max: d.max,
+        median: d.median,
+    })
+}
+
+/// Compute the histogram of a set of values.
+///
+/// # Arguments
+///
+/// * `data`: The values.
+/// * `bins`: The number of bins, default = 256. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(Histogram)`: The bin counts and edges.
+/// * `Err(JsError)`: If bins is 0. If the data is empty.
+#[wasm_bindgen(js_name = statisticsHistogram)]
+pub fn statistics_histogram(data: &[f64], bins: Option<usize>) -> Result<Histogram, JsError> {
+    let arr = view_nd(data, &[data.len()])?;
+    let h = statistics::histogram(arr, bins.map(Binning::Fixed), None).map_err(js_error)?;
+
+    Ok(Histogram {
+        counts: h.counts,
+        edges: h.edges,
+    })
+}
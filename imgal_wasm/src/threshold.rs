@@ -0,0 +1,72 @@
+use wasm_bindgen::prelude::*;
+
+use imgal::threshold;
+
+use crate::{js_error, mask_bytes, view_nd};
+
+/// Create a threshold mask from a threshold value.
+///
+/// # Arguments
+///
+/// * `data`: The row major n-dimensional image.
+/// * `shape`: The shape of the image.
+/// * `threshold`: The image pixel threshold value.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)`: The row major mask with pixels that are greater than the
+///   threshold value set to 1 and all other pixels set to 0.
+/// * `Err(JsError)`: If the data length does not match the shape.
+#[wasm_bindgen(js_name = thresholdManualMask)]
+pub fn threshold_manual_mask(
+    data: &[f64],
+    shape: &[usize],
+    threshold: f64,
+) -> Result<Vec<u8>, JsError> {
+    let arr = view_nd(data, shape)?;
+
+    Ok(mask_bytes(threshold::manual_mask(arr, threshold)))
+}
+
+/// Compute the Otsu threshold value of an n-dimensional image.
+///
+/// # Arguments
+///
+/// * `data`: The row major n-dimensional image.
+/// * `bins`: The number of histogram bins, default = 256. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(f64)`: The Otsu threshold value.
+/// * `Err(JsError)`: If bins is 0. If the image is empty.
+#[wasm_bindgen(js_name = thresholdOtsu)]
+pub fn threshold_otsu(data: &[f64], bins: Option<usize>) -> Result<f64, JsError> {
+    let arr = view_nd(data, &[data.len()])?;
+    threshold::otsu(arr, None, bins).map_err(js_error)
+}
+
+/// Create a threshold mask with the Otsu threshold value.
+///
+/// # Arguments
+///
+/// * `data`: The row major n-dimensional image.
+/// * `shape`: The shape of the image.
+/// * `bins`: The number of histogram bins, default = 256. Must be >= 1.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)`: The row major mask with pixels that are greater than the
+///   Otsu threshold value set to 1 and all other pixels set to 0.
+/// * `Err(JsError)`: If the data length does not match the shape. If bins is 0.
+///   If the image is empty.
+#[wasm_bindgen(js_name = thresholdOtsuMask)]
+pub fn threshold_otsu_mask(
+    data: &[f64],
+    shape: &[usize],
+    bins: Option<usize>,
+) -> Result<Vec<u8>, JsError> {
+    let arr = view_nd(data, shape)?;
+    threshold::otsu_mask(arr, None, bins)
+        .map(mask_bytes)
+        .map_err(js_error)
+}
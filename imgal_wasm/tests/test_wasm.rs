@@ -0,0 +1,141 @@
+use ndarray::{Array, Array2, Array3, ArrayD, Dimension};
+
+use imgal::filter;
+use imgal::phasor::analysis::PhasorCursor;
+use imgal::phasor::{calibration, spectral, time_domain};
+use imgal::simulation::decay;
+use imgal::statistics::{self, Binning};
+use imgal::threshold;
+use imgal_wasm::filter::filter_gaussian;
+use imgal_wasm::phasor::{
+    phasor_calibration_image, phasor_cursor_mask, phasor_modulation_and_phase,
+    phasor_time_domain_image, phasor_time_domain_image_u16,
+};
+use imgal_wasm::statistics::{statistics_describe, statistics_histogram};
+use imgal_wasm::threshold::{threshold_manual_mask, threshold_otsu, threshold_otsu_mask};
+
+// simulated biexponential decay parameters
+const SAMPLES: usize = 64;
+const PERIOD: f64 = 12.5;
+const TAUS: [f64; 2] = [1.0, 3.0];
+const FRACTIONS: [f64; 2] = [0.7, 0.3];
+const TOTAL_COUNTS: f64 = 5000.0;
+
+// helper functions
+fn decay_image() -> Array3<f64> {
+    decay::ideal_exponential_3d(SAMPLES, PERIOD, &TAUS, &FRACTIONS, TOTAL_COUNTS, (5, 4)).unwrap()
+}
+
+fn row_major<T: Copy, D: Dimension>(arr: &Array<T, D>) -> Vec<T> {
+    arr.iter().copied().collect()
+}
+
+fn ramp_image() -> ArrayD<f64> {
+    Array2::from_shape_fn((8, 6), |(r, c)| (r * c) as f64).into_dyn()
+}
+
+#[test]
+fn wasm_phasor_time_domain_image() {
+    let data = decay_image();
+    let output = phasor_time_domain_image(&row_major(&data), 5, 4, PERIOD, None).unwrap();
+    let expected = time_domain::image(data.view(), PERIOD, None, None, None).unwrap();
+    assert_eq!(output, row_major(&expected));
+
+    // photon counts give the same coordinates as the f64 counts
+    let counts = data.mapv(|v| v.round() as u16);
+    let output =
+        phasor_time_domain_image_u16(&row_major(&counts), 5, 4, PERIOD, Some(2.0)).unwrap();
+    let expected = time_domain::image(counts.view(), PERIOD, None, Some(2.0), None).unwrap();
+    assert_eq!(output, row_major(&expected));
+}
+
+#[test]
+fn wasm_phasor_calibration() {
+    let cal = phasor_modulation_and_phase(0.5, 0.4, 4.0, 0.5);
+    let (modulation, phase) = calibration::modulation_and_phase(0.5, 0.4, 4.0, 0.5);
+    assert_eq!(cal.modulation, modulation);
+    assert_eq!(cal.phase, phase);
+
+    let gs = time_domain::image(decay_image().view(), PERIOD, None, None, None).unwrap();
+    let output = phasor_calibration_image(&row_major(&gs), 5, 4, modulation, phase).unwrap();
+    let expected = calibration::image(gs.view(), modulation, phase, None, None).unwrap();
+    assert_eq!(output, row_major(&expected));
+}
+
+#[test]
+fn wasm_phasor_cursor_mask() {
+    let gs = time_domain::image(decay_image().view(), PERIOD, None, None, None).unwrap();
+    let g = gs[[0, 0, 0]];
+    let s = gs[[0, 0, 1]];
+    let output = phasor_cursor_mask(&row_major(&gs), 5, 4, g, s, 0.05).unwrap();
+    let cursor = PhasorCursor { g, s, radius: 0.05 };
+    let expected = spectral::cursor_mask(gs.view(), cursor, None, None).unwrap();
+    assert_eq!(output.len(), 20);
+    assert!(
+        output
+            .iter()
+            .zip(expected.iter())
+            .all(|(&o, &e)| (o == 1) == e)
+    );
+    assert!(output.iter().all(|&o| o == 1));
+}
+
+#[test]
+fn wasm_threshold() {
+    let data = ramp_image();
+    let slice = data.as_slice().unwrap();
+    let t = threshold_otsu(slice, Some(64)).unwrap();
+    assert_eq!(t, threshold::otsu(data.view(), None, Some(64)).unwrap());
+
+    let output = threshold_otsu_mask(slice, &[8, 6], None).unwrap();
+    let expected = threshold::otsu_mask(data.view(), None, None).unwrap();
+    assert!(
+        output
+            .iter()
+            .zip(expected.iter())
+            .all(|(&o, &e)| (o == 1) == e)
+    );
+
+    let output = threshold_manual_mask(slice, &[8, 6], 10.0).unwrap();
+    let expected = threshold::manual_mask(data.view(), 10.0);
+    assert!(
+        output
+            .iter()
+            .zip(expected.iter())
+            .all(|(&o, &e)| (o == 1) == e)
+    );
+    assert_eq!(output.iter().filter(|&&o| o == 1).count(), 16);
+}
+
+#[test]
+fn wasm_filter_gaussian() {
+    let data = ramp_image();
+    let slice = data.as_slice().unwrap();
+    let expected = filter::gaussian(data.view(), &[1.5, 1.5], None, None).unwrap();
+    assert_eq!(
+        filter_gaussian(slice, &[8, 6], &[1.5]).unwrap(),
+        row_major(&expected)
+    );
+    assert_eq!(
+        filter_gaussian(slice, &[8, 6], &[1.5, 1.5]).unwrap(),
+        row_major(&expected)
+    );
+}
+
+#[test]
+fn wasm_statistics() {
+    let data = ramp_image();
+    let slice = data.as_slice().unwrap();
+    let d = statistics_describe(slice).unwrap();
+    let expected = statistics::describe(data.view(), None).unwrap();
+    assert_eq!(d.count, 48);
+    assert_eq!(d.mean, expected.mean);
+    assert_eq!(d.variance, expected.variance);
+    assert_eq!(d.median, expected.median);
+    assert_eq!((d.min, d.max), (0.0, 35.0));
+
+    let h = statistics_histogram(slice, Some(5)).unwrap();
+    let expected = statistics::histogram(data.view(), Some(Binning::Fixed(5)), None).unwrap();
+    assert_eq!(h.counts(), expected.counts);
+    assert_eq!(h.edges(), expected.edges);
+}